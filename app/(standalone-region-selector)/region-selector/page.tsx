@@ -116,6 +116,29 @@ export default function RegionSelectorPage() {
     };
   }, []);
 
+  // Report cursor and selection to the backend for region-preview events
+  useEffect(() => {
+    if (!mounted) return;
+    const offsetX = screenInfo?.screenX ?? 0;
+    const offsetY = screenInfo?.screenY ?? 0;
+    const frame = requestAnimationFrame(async () => {
+      const { emit } = await import("@tauri-apps/api/event");
+      emit("region-selection-update", {
+        region: selection
+          ? {
+              x: Math.round(selection.x + offsetX),
+              y: Math.round(selection.y + offsetY),
+              width: Math.round(selection.width),
+              height: Math.round(selection.height),
+            }
+          : null,
+        cursorX: Math.round(cursorPos.x + offsetX),
+        cursorY: Math.round(cursorPos.y + offsetY),
+      });
+    });
+    return () => cancelAnimationFrame(frame);
+  }, [mounted, cursorPos, selection, screenInfo]);
+
   // Handle keyboard shortcuts including arrow key micro-adjustments
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
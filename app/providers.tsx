@@ -59,6 +59,7 @@ import { SchedulerInitializer } from '@/components/scheduler';
 import { useChatWidgetStore } from '@/stores/chat';
 import { useScreenshotStore } from '@/stores/media';
 import { getWindowLabel, isTauri as detectTauri, WINDOW_LABELS } from '@/lib/native/utils';
+import { getCommandErrorCode, getCommandErrorMessage } from '@/lib/native/command-error';
 import { AppLoadingScreen } from '@/components/ui/app-loading-screen';
 import { createLogger } from '@/lib/logger';
 
@@ -677,9 +678,8 @@ function ScreenshotNativeSync() {
             language,
           });
         } catch (error) {
-          const message =
-            error instanceof Error ? error.message : String(error ?? 'Unknown screenshot error');
-          if (isSelectionCancelledError(message)) {
+          const message = getCommandErrorMessage(error, 'Unknown screenshot error');
+          if (getCommandErrorCode(error) === 'CANCELLED' || isSelectionCancelledError(message)) {
             return;
           }
           await emitScreenshotError(action, message);
@@ -153,7 +153,12 @@ describe('useScreenshot', () => {
     });
 
     it('should call extractText on the store', async () => {
-      mockInvoke.mockResolvedValue('Extracted text content');
+      mockInvoke.mockResolvedValue({
+        text: 'Extracted text content',
+        engine: 'platform',
+        provider: 'Windows OCR',
+        confidence: 0.9,
+      });
       const { result } = renderHook(() => useScreenshot());
 
       await act(async () => {
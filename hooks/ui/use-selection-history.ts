@@ -8,6 +8,7 @@ import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { isTauri } from '@/lib/native/utils';
+import { getCommandErrorMessage } from '@/lib/native/command-error';
 import { loggers } from '@/lib/logger';
 
 const log = loggers.native;
@@ -67,7 +68,7 @@ export function useSelectionHistory() {
       const result = await invoke<SelectionHistoryEntry[]>('selection_get_history', { count });
       setHistory(result);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(getCommandErrorMessage(err));
     } finally {
       setIsLoading(false);
     }
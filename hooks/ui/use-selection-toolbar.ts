@@ -20,6 +20,7 @@ import type { ProviderName } from '@/lib/ai/core/client';
 import type { SelectionConfig as NativeSelectionConfig } from '@/lib/native/selection';
 import { isTauri } from '@/lib/native/utils';
 import { loggers } from '@/lib/logger';
+import selectionActionPrompts from '@/src-tauri/src/selection/action_prompts.json';
 
 const log = loggers.native;
 
@@ -41,38 +42,19 @@ const initialState: ToolbarState = {
   references: [],
 };
 
-// System prompt for selection toolbar actions
-const SELECTION_SYSTEM_PROMPT = `You are a helpful assistant that processes selected text. Be concise and accurate. Follow the user's instructions precisely.`;
-
-// Prompts for different actions
-const ACTION_PROMPTS: Record<SelectionAction, (text: string, targetLang?: string) => string> = {
-  explain: (text) => `Please explain the following text in a clear and concise way:\n\n"${text}"`,
-  translate: (text, targetLang = 'zh-CN') =>
-    `Translate the following text to ${getLanguageName(targetLang)}. Only provide the translation, no explanations:\n\n"${text}"`,
-  summarize: (text) => `Summarize the following text in 1-2 sentences:\n\n"${text}"`,
-  extract: (text) =>
-    `Extract the key points from the following text as a bullet list:\n\n"${text}"`,
-  define: (text) => `Provide a clear definition for the following term or phrase:\n\n"${text}"`,
-  rewrite: (text) =>
-    `Rewrite the following text to improve clarity and flow while maintaining the original meaning:\n\n"${text}"`,
-  grammar: (text) =>
-    `Check the following text for grammar and spelling errors. List any issues found and provide the corrected version:\n\n"${text}"`,
-  copy: () => '',
-  'send-to-chat': () => '',
-  search: () => '',
-  'code-explain': (text) =>
-    `Explain the following code in detail, including what it does and how it works:\n\n\`\`\`\n${text}\n\`\`\``,
-  'code-optimize': (text) =>
-    `Optimize the following code for better performance and readability. Provide the improved version with explanations:\n\n\`\`\`\n${text}\n\`\`\``,
-  'tone-formal': (text) =>
-    `Rewrite the following text in a more formal, professional tone:\n\n"${text}"`,
-  'tone-casual': (text) =>
-    `Rewrite the following text in a more casual, conversational tone:\n\n"${text}"`,
-  expand: (text) => `Expand on the following text with more details and context:\n\n"${text}"`,
-  shorten: (text) => `Shorten the following text while keeping the essential meaning:\n\n"${text}"`,
-  'knowledge-map': (text) =>
-    `Analyze the following text and generate a knowledge map with key concepts and their relationships:\n\n"${text}"`,
-};
+// Prompt templates shared with the native selection AI actions
+const SELECTION_SYSTEM_PROMPT = selectionActionPrompts.systemPrompt;
+const ACTION_PROMPT_TEMPLATES: Partial<Record<SelectionAction, string>> = selectionActionPrompts.actions;
+
+// Builds the prompt for an action; local actions (copy, search, send-to-chat) have none
+const buildActionPrompt = (
+  action: SelectionAction,
+  text: string,
+  targetLang = 'zh-CN'
+): string | undefined =>
+  ACTION_PROMPT_TEMPLATES[action]
+    ?.replace('{language}', () => getLanguageName(targetLang))
+    .replace('{text}', () => text);
 
 const toNativeConfig = (config: ToolbarConfig): NativeSelectionConfig => ({
   enabled: config.enabled,
@@ -460,13 +442,7 @@ export function useSelectionToolbar() {
         }
       }
 
-      const promptFn = ACTION_PROMPTS[action];
-      if (!promptFn) {
-        await completeActionError(`Unknown action: ${action}`, 'prompt_resolution');
-        return;
-      }
-
-      const prompt = promptFn(state.selectedText, config.targetLanguage);
+      const prompt = buildActionPrompt(action, state.selectedText, config.targetLanguage);
       if (!prompt) {
         await completeActionError(`No prompt for action: ${action}`, 'prompt_resolution');
         return;
@@ -5,6 +5,8 @@
 
 export {
   extractPDFContent,
+  cancelPDFExtraction,
+  onPDFExtractionProgress,
   convertPDFToMarkdown,
   generateKnowledgeMapFromPDF,
   generateMindMapFromPDF,
@@ -84,6 +86,7 @@ export type {
 export type {
   PDFConversionOptions,
   PDFConversionResult,
+  PDFExtractionProgress,
   PDFExtractedElement,
   KnowledgeMap,
   KnowledgeMapTrace,
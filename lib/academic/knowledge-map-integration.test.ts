@@ -6,6 +6,10 @@ import {
   generateKnowledgeMapFromSelection,
   generateKnowledgeMapFromPDFFile,
   generateKnowledgeMapWithAI,
+  generateKnowledgeMapStream,
+  cancelKnowledgeMapGeneration,
+  onKnowledgeMapTrace,
+  onKnowledgeMapComplete,
   generateMindMapFromKnowledgeMap,
   generateMindMapFromContent,
   hasKnowledgeMapForSource,
@@ -25,6 +29,10 @@ jest.mock('@tauri-apps/api/core', () => ({
   invoke: jest.fn(),
 }));
 
+jest.mock('@tauri-apps/api/event', () => ({
+  listen: jest.fn(),
+}));
+
 // Helper to create test knowledge map
 const createTestKnowledgeMap = (overrides: Partial<KnowledgeMap> = {}): KnowledgeMap => ({
   schemaVersion: 1,
@@ -150,6 +158,68 @@ describe('Knowledge Map Integration', () => {
     });
   });
 
+  describe('streaming generation', () => {
+    it('should start a streamed generation with an operation id', async () => {
+      const { invoke } = await import('@tauri-apps/api/core');
+      const knowledgeMap = createTestKnowledgeMap({ id: 'stream-km' });
+      (invoke as jest.Mock).mockResolvedValueOnce({
+        operationId: 'op-1',
+        knowledgeMapId: 'stream-km',
+        status: 'completed',
+        tracesEmitted: 0,
+        knowledgeMap,
+      });
+
+      const result = await generateKnowledgeMapStream('# Title\ncontent', 'op-1', 'Streamed', {
+        deterministicIds: true,
+      });
+
+      expect(invoke).toHaveBeenCalledWith('academic_generate_knowledge_map_stream', {
+        content: '# Title\ncontent',
+        title: 'Streamed',
+        operationId: 'op-1',
+        options: { deterministicIds: true },
+      });
+      expect(result.knowledgeMap).toEqual(knowledgeMap);
+    });
+
+    it('should cancel a generation by operation id', async () => {
+      const { invoke } = await import('@tauri-apps/api/core');
+      (invoke as jest.Mock).mockResolvedValueOnce(true);
+
+      const cancelled = await cancelKnowledgeMapGeneration('op-1');
+
+      expect(invoke).toHaveBeenCalledWith('academic_cancel_knowledge_map_generation', {
+        operationId: 'op-1',
+      });
+      expect(cancelled).toBe(true);
+    });
+
+    it('should forward trace and complete event payloads', async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = jest.fn();
+      (listen as jest.Mock).mockImplementation(async (event, callback) => {
+        callback({
+          payload:
+            event === 'knowledge-map://trace'
+              ? { operationId: 'op-1', knowledgeMapId: 'km', index: 0, trace: { title: 'Intro' } }
+              : { operationId: 'op-1', status: 'cancelled', knowledgeMap: null },
+        });
+        return unlisten;
+      });
+      const onTrace = jest.fn();
+      const onComplete = jest.fn();
+
+      expect(await onKnowledgeMapTrace(onTrace)).toBe(unlisten);
+      expect(await onKnowledgeMapComplete(onComplete)).toBe(unlisten);
+
+      expect(listen).toHaveBeenCalledWith('knowledge-map://trace', expect.any(Function));
+      expect(listen).toHaveBeenCalledWith('knowledge-map://complete', expect.any(Function));
+      expect(onTrace).toHaveBeenCalledWith(expect.objectContaining({ index: 0 }));
+      expect(onComplete).toHaveBeenCalledWith(expect.objectContaining({ status: 'cancelled' }));
+    });
+  });
+
   describe('generateMindMapFromKnowledgeMap', () => {
     it('should generate mind map from knowledge map ID', async () => {
       const mockMindMap = {
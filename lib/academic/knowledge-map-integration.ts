@@ -3,11 +3,15 @@
  * Provides unified interface for knowledge map generation from various sources
  */
 
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { extractPDFContent } from './pdf-to-markdown';
 import { useKnowledgeMapStore } from '@/stores/academic/knowledge-map-store';
 import type {
   KnowledgeMap,
   KnowledgeMapGenerationRequest,
+  KnowledgeMapGenerationResult,
+  KnowledgeMapTraceEvent,
   MindMapData,
   PDFConversionOptions,
 } from '@/types/learning/knowledge-map';
@@ -107,6 +111,61 @@ export async function generateKnowledgeMapWithAI(
   }
 }
 
+// ============================================================================
+// Streaming Generation
+// ============================================================================
+
+/**
+ * Generate a knowledge map incrementally. Traces are reported through
+ * `onKnowledgeMapTrace` as they are parsed and the full map through
+ * `onKnowledgeMapComplete`; the returned promise resolves with the same result.
+ * The generation can be stopped with `cancelKnowledgeMapGeneration`.
+ * With `deterministicIds` the same content always yields the same ids.
+ */
+export async function generateKnowledgeMapStream(
+  content: string,
+  operationId: string,
+  title?: string,
+  options: { deterministicIds?: boolean } = {}
+): Promise<KnowledgeMapGenerationResult> {
+  return invoke<KnowledgeMapGenerationResult>('academic_generate_knowledge_map_stream', {
+    content,
+    title,
+    operationId,
+    options,
+  });
+}
+
+/**
+ * Cancel a streamed generation.
+ * Resolves to false when no such generation is running.
+ */
+export async function cancelKnowledgeMapGeneration(operationId: string): Promise<boolean> {
+  return invoke<boolean>('academic_cancel_knowledge_map_generation', { operationId });
+}
+
+/**
+ * Listen for traces of streamed generations as they are parsed
+ */
+export async function onKnowledgeMapTrace(
+  handler: (event: KnowledgeMapTraceEvent) => void
+): Promise<UnlistenFn> {
+  return listen<KnowledgeMapTraceEvent>('knowledge-map://trace', (event) => {
+    handler(event.payload);
+  });
+}
+
+/**
+ * Listen for completed or cancelled streamed generations
+ */
+export async function onKnowledgeMapComplete(
+  handler: (result: KnowledgeMapGenerationResult) => void
+): Promise<UnlistenFn> {
+  return listen<KnowledgeMapGenerationResult>('knowledge-map://complete', (event) => {
+    handler(event.payload);
+  });
+}
+
 // ============================================================================
 // Mind Map Generation
 // ============================================================================
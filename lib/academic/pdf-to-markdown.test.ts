@@ -4,6 +4,8 @@
 
 import {
   extractPDFContent,
+  cancelPDFExtraction,
+  onPDFExtractionProgress,
   convertPDFToMarkdown,
   generateKnowledgeMapFromPDF,
   parseMarkdownToElements,
@@ -15,6 +17,10 @@ jest.mock('@tauri-apps/api/core', () => ({
   invoke: jest.fn(),
 }));
 
+jest.mock('@tauri-apps/api/event', () => ({
+  listen: jest.fn(),
+}));
+
 describe('PDF to Markdown Conversion', () => {
   describe('extractPDFContent', () => {
     it('should extract content from PDF path', async () => {
@@ -73,6 +79,55 @@ describe('PDF to Markdown Conversion', () => {
       
       expect(invoke).toHaveBeenCalled();
     });
+
+    it('should pass the operation id and surface a cancelled status', async () => {
+      const { invoke } = await import('@tauri-apps/api/core');
+
+      (invoke as jest.Mock).mockResolvedValueOnce({
+        success: true,
+        elements: [],
+        metadata: { pageCount: 3 },
+        status: 'cancelled',
+      });
+
+      const result = await extractPDFContent('/path/to/book.pdf', {}, 'op-1');
+
+      expect(invoke).toHaveBeenCalledWith(
+        'academic_extract_pdf_content',
+        expect.objectContaining({ pdfPath: '/path/to/book.pdf', operationId: 'op-1' })
+      );
+      expect(result.status).toBe('cancelled');
+    });
+  });
+
+  describe('PDF extraction progress and cancellation', () => {
+    it('should cancel an extraction by operation id', async () => {
+      const { invoke } = await import('@tauri-apps/api/core');
+      (invoke as jest.Mock).mockResolvedValueOnce(true);
+
+      const cancelled = await cancelPDFExtraction('op-1');
+
+      expect(invoke).toHaveBeenCalledWith('academic_cancel_pdf_extraction', {
+        operationId: 'op-1',
+      });
+      expect(cancelled).toBe(true);
+    });
+
+    it('should forward progress event payloads', async () => {
+      const { listen } = await import('@tauri-apps/api/event');
+      const unlisten = jest.fn();
+      (listen as jest.Mock).mockImplementationOnce(async (_event, callback) => {
+        callback({ payload: { operationId: 'op-1', pdfPath: '/a.pdf', page: 2, totalPages: 10 } });
+        return unlisten;
+      });
+      const handler = jest.fn();
+
+      const result = await onPDFExtractionProgress(handler);
+
+      expect(listen).toHaveBeenCalledWith('academic://pdf-progress', expect.any(Function));
+      expect(handler).toHaveBeenCalledWith(expect.objectContaining({ page: 2, totalPages: 10 }));
+      expect(result).toBe(unlisten);
+    });
   });
 
   describe('convertPDFToMarkdown', () => {
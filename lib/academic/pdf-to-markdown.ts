@@ -4,10 +4,13 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { nanoid } from 'nanoid';
 import type {
   PDFConversionOptions,
   PDFConversionResult,
+  PDFExtractionProgress,
+  PDFExtractionStatus,
   PDFExtractedElement,
   PDFDocumentMetadata,
   PDFFigureData,
@@ -23,9 +26,15 @@ import { DEFAULT_PDF_CONVERSION_OPTIONS } from '@/types/learning/knowledge-map';
 // PDF Extraction Functions
 // ============================================================================
 
+/**
+ * Extract PDF content. Pass an `operationId` to be able to cancel the
+ * extraction with `cancelPDFExtraction`; progress is reported through
+ * `onPDFExtractionProgress` either way.
+ */
 export async function extractPDFContent(
   pdfPath: string,
-  options: Partial<PDFConversionOptions> = {}
+  options: Partial<PDFConversionOptions> = {},
+  operationId?: string
 ): Promise<PDFConversionResult> {
   const mergedOptions: PDFConversionOptions = {
     ...DEFAULT_PDF_CONVERSION_OPTIONS,
@@ -38,8 +47,10 @@ export async function extractPDFContent(
       elements: PDFExtractedElement[];
       metadata: PDFDocumentMetadata;
       errors?: string[];
+      status?: PDFExtractionStatus;
     }>('academic_extract_pdf_content', {
       pdfPath,
+      operationId,
       options: {
         preserve_images: mergedOptions.preserveImages,
         image_output_dir: mergedOptions.imageOutputDir,
@@ -96,6 +107,7 @@ export async function extractPDFContent(
       knowledgeMap,
       mindMap,
       metadata: result.metadata,
+      status: result.status,
     };
   } catch (error) {
     return {
@@ -113,6 +125,25 @@ export async function extractPDFContent(
   }
 }
 
+/**
+ * Cancel an extraction started with an operation id.
+ * Resolves to false when no such extraction is running.
+ */
+export async function cancelPDFExtraction(operationId: string): Promise<boolean> {
+  return invoke<boolean>('academic_cancel_pdf_extraction', { operationId });
+}
+
+/**
+ * Listen for per-page extraction progress
+ */
+export async function onPDFExtractionProgress(
+  handler: (progress: PDFExtractionProgress) => void
+): Promise<UnlistenFn> {
+  return listen<PDFExtractionProgress>('academic://pdf-progress', (event) => {
+    handler(event.payload);
+  });
+}
+
 // ============================================================================
 // Markdown Conversion
 // ============================================================================
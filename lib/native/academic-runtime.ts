@@ -19,6 +19,8 @@ import type {
   Paper,
   PaperAnnotation,
   PaperCollection,
+  PaperReadingStatus,
+  ReadingProgressUpdate,
 } from '@/types/academic';
 import { DEFAULT_ACADEMIC_PROVIDERS } from '@/types/academic/provider';
 
@@ -274,6 +276,52 @@ function createLibraryPaper(paper: Paper, collectionId?: string): LibraryPaper {
   };
 }
 
+const READING_STATUSES: Record<string, PaperReadingStatus> = {
+  unread: 'unread',
+  reading: 'reading',
+  read: 'completed',
+  completed: 'completed',
+  archived: 'archived',
+};
+
+function applyReadingProgress(paper: LibraryPaper, progress: ReadingProgressUpdate): LibraryPaper {
+  const status = progress.status
+    ? READING_STATUSES[progress.status.trim().toLowerCase()]
+    : undefined;
+  if (progress.status && !status) {
+    throw createRuntimeError(`Invalid reading status: ${progress.status}`);
+  }
+  const percent = progress.progressPercent;
+  if (percent !== undefined && (percent < 0 || percent > 100)) {
+    throw createRuntimeError(`Invalid reading progress: ${percent}%`);
+  }
+  if (progress.lastPageRead !== undefined && progress.lastPageRead < 1) {
+    throw createRuntimeError(`Invalid last page read: ${progress.lastPageRead}`);
+  }
+
+  const addedSecs = progress.addReadingTimeSecs ?? 0;
+  let readingStatus = paper.readingStatus;
+  if (status) {
+    readingStatus = status;
+  } else if (percent === 100) {
+    readingStatus = 'completed';
+  } else if (
+    readingStatus === 'unread' &&
+    ((percent ?? 0) > 0 || progress.lastPageRead !== undefined || addedSecs > 0)
+  ) {
+    readingStatus = 'reading';
+  }
+
+  return {
+    ...paper,
+    readingStatus,
+    readingProgress: percent ?? paper.readingProgress,
+    lastPageRead: progress.lastPageRead ?? paper.lastPageRead,
+    readingTimeSecs: (paper.readingTimeSecs ?? 0) + addedSecs,
+    lastAccessedAt: new Date(),
+  };
+}
+
 function toSearchResult(
   provider: AcademicProviderType,
   aggregate: AggregatedSearchResult
@@ -354,6 +402,7 @@ function buildAcademicStatistics(state: WebAcademicState): AcademicStatistics {
     papersReadThisWeek: 0,
     papersReadThisMonth: 0,
     averageReadingTime: undefined,
+    totalReadingTimeSecs: papers.reduce((total, paper) => total + (paper.readingTimeSecs ?? 0), 0),
     topAuthors: Object.entries(authorCounter)
       .sort((a, b) => b[1] - a[1])
       .slice(0, 10)
@@ -557,6 +606,22 @@ async function executeWebCommand<T>(command: string, rawArgs: JsonRecord): Promi
       return updated as T;
     }
 
+    case 'academic_update_reading_progress': {
+      const paperId = getField<string>(args, ['paperId', 'paper_id']);
+      const progress = (getField<JsonRecord>(args, ['progress']) ?? {}) as ReadingProgressUpdate;
+      if (!paperId) {
+        throw createRuntimeError('Missing paperId');
+      }
+      const existing = state.papers[paperId];
+      if (!existing) {
+        throw createRuntimeError(`Paper '${paperId}' not found`);
+      }
+      const updated = applyReadingProgress(existing, progress);
+      state.papers[paperId] = updated;
+      saveWebState(state);
+      return updated as T;
+    }
+
     case 'academic_get_paper_by_id': {
       const paperId = getField<string>(args, ['paperId', 'paper_id']);
       return (paperId ? (state.papers[paperId] ?? null) : null) as T;
@@ -46,6 +46,7 @@ import {
   // Extended Focus Tracker Functions
   getAllFocusSessions,
   getFocusSessionCount,
+  exportFocusSessions,
   type AwarenessState,
   type SystemState,
   type Suggestion,
@@ -692,6 +693,25 @@ describe('Awareness - Extended Focus Tracker Functions', () => {
       expect(result).toBe(42);
     });
   });
+
+  describe('exportFocusSessions', () => {
+    it('should call invoke with range, format and options', async () => {
+      const ics = 'BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n';
+      mockInvoke.mockResolvedValue(ics);
+
+      const result = await exportFocusSessions(1000, 2000, 'ics', {
+        include_window_titles: true,
+        redact_keywords: ['bank'],
+      });
+      expect(mockInvoke).toHaveBeenCalledWith('awareness_export_focus_sessions', {
+        startMs: 1000,
+        endMs: 2000,
+        format: 'ics',
+        options: { include_window_titles: true, redact_keywords: ['bank'] },
+      });
+      expect(result).toBe(ics);
+    });
+  });
 });
 
 describe('Awareness Types', () => {
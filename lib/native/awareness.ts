@@ -104,6 +104,25 @@ export interface FocusSession {
   is_active: boolean;
 }
 
+export type FocusExportFormat = "ics" | "json";
+
+export interface FocusExportOptions {
+  /** Include window titles in event descriptions */
+  include_window_titles?: boolean;
+  /** Case-insensitive keywords; matching sessions are exported as "Private session" */
+  redact_keywords?: string[];
+}
+
+export interface FocusCalendarEvent {
+  uid: string;
+  title: string;
+  start_time: number;
+  end_time: number;
+  category: string;
+  description?: string | null;
+  redacted: boolean;
+}
+
 export interface AppUsageStats {
   app_name: string;
   total_time_ms: number;
@@ -482,3 +501,21 @@ export async function getAllFocusSessions(): Promise<FocusSession[]> {
 export async function getFocusSessionCount(): Promise<number> {
   return invoke("awareness_get_focus_session_count");
 }
+
+/**
+ * Export completed focus sessions in a time range (at most 31 days) as an
+ * iCalendar document or a JSON array of FocusCalendarEvent
+ */
+export async function exportFocusSessions(
+  startMs: number,
+  endMs: number,
+  format: FocusExportFormat,
+  options?: FocusExportOptions
+): Promise<string> {
+  return invoke("awareness_export_focus_sessions", {
+    startMs,
+    endMs,
+    format,
+    options,
+  });
+}
@@ -0,0 +1,32 @@
+/**
+ * Command Error Tests
+ */
+
+import { getCommandErrorCode, getCommandErrorMessage, isCommandError } from './command-error';
+
+describe('command-error', () => {
+  const structured = {
+    code: 'NOT_FOUND',
+    message: 'Collection not found',
+    details: { collection: 'docs' },
+  };
+
+  it('should detect structured command errors', () => {
+    expect(isCommandError(structured)).toBe(true);
+    expect(isCommandError('Collection not found')).toBe(false);
+    expect(isCommandError(new Error('boom'))).toBe(false);
+    expect(isCommandError(null)).toBe(false);
+  });
+
+  it('should extract messages from any error shape', () => {
+    expect(getCommandErrorMessage(structured)).toBe('Collection not found');
+    expect(getCommandErrorMessage('legacy error')).toBe('legacy error');
+    expect(getCommandErrorMessage(new Error('boom'))).toBe('boom');
+    expect(getCommandErrorMessage(undefined, 'fallback')).toBe('fallback');
+  });
+
+  it('should return the code only for structured errors', () => {
+    expect(getCommandErrorCode(structured)).toBe('NOT_FOUND');
+    expect(getCommandErrorCode('Collection not found')).toBeUndefined();
+  });
+});
@@ -0,0 +1,56 @@
+/**
+ * Command Error Handling
+ *
+ * Structured errors returned by Tauri commands (selection, screenshot, vector).
+ * Commands reject with `{ code, message, details? }` instead of a plain string.
+ */
+
+export type CommandErrorCode =
+  | 'NOT_FOUND'
+  | 'ALREADY_EXISTS'
+  | 'INVALID_INPUT'
+  | 'PERMISSION_DENIED'
+  | 'TIMEOUT'
+  | 'UNAVAILABLE'
+  | 'CANCELLED'
+  | 'INTERNAL';
+
+export interface CommandError {
+  code: CommandErrorCode;
+  message: string;
+  details?: Record<string, unknown>;
+}
+
+/**
+ * Check whether a rejected invoke value is a structured command error
+ */
+export function isCommandError(error: unknown): error is CommandError {
+  return (
+    !!error &&
+    typeof error === 'object' &&
+    typeof (error as CommandError).code === 'string' &&
+    typeof (error as CommandError).message === 'string'
+  );
+}
+
+/**
+ * Get a readable message from any rejected invoke value
+ *
+ * Handles structured command errors, legacy string errors and `Error` instances.
+ */
+export function getCommandErrorMessage(error: unknown, fallback = 'Unknown error'): string {
+  if (isCommandError(error) || error instanceof Error) {
+    return error.message || fallback;
+  }
+  if (typeof error === 'string') {
+    return error || fallback;
+  }
+  return error == null ? fallback : String(error);
+}
+
+/**
+ * Get the error code of a rejected invoke value, if it is a structured command error
+ */
+export function getCommandErrorCode(error: unknown): CommandErrorCode | undefined {
+  return isCommandError(error) ? error.code : undefined;
+}
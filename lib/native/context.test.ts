@@ -380,6 +380,22 @@ describe('New Context Functions', () => {
     });
   });
 
+  describe('getContextStatus', () => {
+    it('should call invoke and return degraded status', async () => {
+      const mockStatus = {
+        degraded: true,
+        degraded_reason: 'Accessibility permission has not been granted',
+        cache_duration_ms: 500,
+      };
+      mockInvoke.mockResolvedValue(mockStatus);
+
+      const { getContextStatus } = await import('./context');
+      const result = await getContextStatus();
+      expect(mockInvoke).toHaveBeenCalledWith('context_get_status');
+      expect(result).toEqual(mockStatus);
+    });
+  });
+
   describe('analyzeUiAutomation', () => {
     it('should call invoke and return UI elements', async () => {
       const mockElements = [
@@ -194,6 +194,20 @@ export interface ScreenAnalyzeOptions {
   language?: string;
 }
 
+export interface ContextStatus {
+  /** Whether window queries lack the OS accessibility permission */
+  degraded: boolean;
+  degraded_reason: string | null;
+  cache_duration_ms: number;
+}
+
+/** Payload of the `permission://missing` event */
+export interface PermissionMissingPayload {
+  permission: "accessibility";
+  component: "context" | "selection" | "input_completion";
+  reason: string;
+}
+
 // ============== Context Functions ==============
 
 /**
@@ -298,6 +312,13 @@ export async function getCacheDuration(): Promise<number> {
   return invoke("context_get_cache_duration");
 }
 
+/**
+ * Get context status, including whether it runs degraded
+ */
+export async function getContextStatus(): Promise<ContextStatus> {
+  return invoke("context_get_status");
+}
+
 /**
  * Analyze UI using Windows UI Automation
  */
@@ -16,12 +16,15 @@ export * as screenshot from './screenshot';
 export * as screenRecording from './screen-recording';
 export * as recordingToolbar from './recording-toolbar';
 export * as recordingErrors from './recording-errors';
+export * as commandError from './command-error';
 export * as context from './context';
 export * as awareness from './awareness';
 export * as sandbox from './sandbox';
 export * as sandboxDb from './sandbox-db';
 export * as environment from './environment';
 export * as process from './process';
+export * as settings from './settings';
+export * as setup from './setup';
 export * as proxy from './proxy';
 export * as opener from './opener';
 export * as deepLink from './deep-link';
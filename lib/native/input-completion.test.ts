@@ -110,6 +110,33 @@ describe('input-completion API', () => {
     });
   });
 
+  describe('acceptAndContinue', () => {
+    it('should call accept-and-continue command with suggestion ref', async () => {
+      const accepted = {
+        text: ' there was',
+        display_text: ' there was',
+        confidence: 0.9,
+        completion_type: 'Line',
+        id: 'chain-1',
+      };
+      const mockResult = {
+        accepted,
+        text: 'Once upon a time there was',
+        suggestions: [{ ...accepted, text: ' a dragon.', display_text: ' a dragon.', id: 'chain-2' }],
+        latency_ms: 80,
+        model: 'qwen2.5-coder:0.5b',
+        cached: false,
+      };
+      mockInvoke.mockResolvedValue(mockResult);
+
+      const result = await api.acceptAndContinue({ suggestion_id: 'chain-1' });
+      expect(mockInvoke).toHaveBeenCalledWith('input_completion_accept_and_continue', {
+        suggestion: { suggestion_id: 'chain-1' },
+      });
+      expect(result).toEqual(mockResult);
+    });
+  });
+
   describe('dismissSuggestion', () => {
     it('should call dismiss command', async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -306,6 +333,76 @@ describe('input-completion API', () => {
     });
   });
 
+  describe('rewriteCompletion', () => {
+    it('should request a rewrite for the given range', async () => {
+      const mockResult = {
+        request_id: 'req-4',
+        surface: 'generic',
+        mode: 'plain_text',
+        cursor_offset: 12,
+        suggestions: [
+          {
+            text: 'I have a cat',
+            display_text: 'I have a cat',
+            confidence: 0.7,
+            completion_type: 'Rewrite',
+            id: 'rw-1',
+            replace_range: { start: 0, end: 12 },
+            diff: [
+              { op: 'equal', text: 'I ' },
+              { op: 'delete', text: 'has' },
+              { op: 'insert', text: 'have' },
+              { op: 'equal', text: ' a cat' },
+            ],
+          },
+        ],
+        latency_ms: 30,
+        model: 'test-model',
+        cached: false,
+      };
+      mockInvoke.mockResolvedValue(mockResult);
+
+      const request = {
+        request_id: 'req-4',
+        original: 'I has a cat!',
+        replace_range: { start: 0, end: 12 },
+      };
+
+      const result = await api.rewriteCompletion(request);
+      expect(mockInvoke).toHaveBeenCalledWith('input_completion_rewrite', { request });
+      expect(result.suggestions[0].replace_range).toEqual({ start: 0, end: 12 });
+    });
+  });
+
+  describe('applyCompletionSuggestion', () => {
+    const base = {
+      display_text: '',
+      confidence: 0.7,
+      id: 'sugg-1',
+    };
+
+    it('should replace the range of rewrite suggestions', () => {
+      const result = api.applyCompletionSuggestion('say I has a cat. ok', 19, {
+        ...base,
+        text: 'I have a cat.',
+        completion_type: 'Rewrite',
+        replace_range: { start: 4, end: 16 },
+      });
+
+      expect(result).toEqual({ text: 'say I have a cat. ok', cursorOffset: 17 });
+    });
+
+    it('should insert other suggestions at the cursor', () => {
+      const result = api.applyCompletionSuggestion('hello', 5, {
+        ...base,
+        text: ' world',
+        completion_type: 'Line',
+      });
+
+      expect(result).toEqual({ text: 'hello world', cursorOffset: 11 });
+    });
+  });
+
   describe('isInputCompletionRunning', () => {
     beforeEach(() => {
       jest.resetModules();
@@ -4,8 +4,10 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type {
+  CompletionChainResult,
   CompletionConfig,
   CompletionFeedback,
+  CompletionRewriteRequest,
   CompletionSuggestionRef,
   InputCompletionResult,
   TriggerCompletionV2Request,
@@ -61,6 +63,16 @@ export async function acceptSuggestionV2(suggestion: CompletionSuggestionRef): P
   return invoke('input_completion_accept_v2', { suggestion });
 }
 
+/**
+ * Accept a suggestion and request the next continuation from the new end.
+ * Resolves to null when the suggestion is no longer active.
+ */
+export async function acceptAndContinue(
+  suggestion: CompletionSuggestionRef
+): Promise<CompletionChainResult | null> {
+  return invoke('input_completion_accept_and_continue', { suggestion });
+}
+
 /**
  * Dismiss the current suggestion
  */
@@ -117,6 +129,33 @@ export async function triggerCompletionV3(request: TriggerCompletionV3Request):
   return invoke('input_completion_trigger_v3', { request });
 }
 
+/**
+ * Request a rewrite suggestion for the current line or selection
+ */
+export async function rewriteCompletion(request: CompletionRewriteRequest): Promise<TriggerCompletionV3Result> {
+  return invoke('input_completion_rewrite', { request });
+}
+
+/**
+ * Apply an accepted suggestion to the text.
+ *
+ * Rewrite suggestions replace their `replace_range`; other suggestions are
+ * inserted at the cursor. Returns the new text and cursor offset.
+ */
+export function applyCompletionSuggestion(
+  text: string,
+  cursorOffset: number,
+  suggestion: CompletionSuggestion
+): { text: string; cursorOffset: number } {
+  const range = suggestion.replace_range ?? { start: cursorOffset, end: cursorOffset };
+  const start = Math.max(0, Math.min(range.start, text.length));
+  const end = Math.max(start, Math.min(range.end, text.length));
+  return {
+    text: text.slice(0, start) + suggestion.text + text.slice(end),
+    cursorOffset: start + suggestion.text.length,
+  };
+}
+
 /**
  * Check if input completion is running
  */
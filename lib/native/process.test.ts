@@ -210,6 +210,27 @@ describe('Process Management Service', () => {
       expect(result).toEqual(mockProcesses);
     });
 
+    it('listSnapshot passes forceRefresh and normalizes processes', async () => {
+      mockInvoke.mockResolvedValue({
+        processes: [{ pid: 7, name: 'node', status: 'running', memory_bytes: 2048 }],
+        snapshotAgeMs: 0,
+        refreshed: true,
+      });
+
+      const result = await processService.listSnapshot({ limit: 5 }, { forceRefresh: true });
+
+      expect(mockInvoke).toHaveBeenCalledWith(
+        'process_list_snapshot',
+        expect.objectContaining({
+          filter: expect.objectContaining({ limit: 5 }),
+          forceRefresh: true,
+        })
+      );
+      expect(result.refreshed).toBe(true);
+      expect(result.snapshotAgeMs).toBe(0);
+      expect(result.processes[0]).toEqual(expect.objectContaining({ pid: 7, memoryBytes: 2048 }));
+    });
+
     it('getProcess calls invoke with pid', async () => {
       const mockProcess = { pid: 123, name: 'test', status: 'running' };
       mockInvoke.mockResolvedValue(mockProcess);
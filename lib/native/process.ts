@@ -84,6 +84,12 @@ export interface StartProcessRequest {
   timeoutSecs?: number;
   /** Capture stdout/stderr (for non-detached) */
   captureOutput?: boolean;
+  /**
+   * Encoding of captured output: 'utf-8', 'auto', an encoding label ('gbk',
+   * 'shift_jis') or Windows codepage ('cp936'). Defaults to the console
+   * codepage on Windows and UTF-8 elsewhere.
+   */
+  outputEncoding?: string;
 }
 
 /** Result of starting a process */
@@ -215,6 +221,17 @@ export interface ProcessManagerConfig {
   maxTrackedProcesses: number;
   /** Default timeout for operations */
   defaultTimeoutSecs: number;
+  /** Minimum interval between full process list refreshes in ms (0 = always refresh) */
+  minRefreshIntervalMs?: number;
+}
+
+/** Process list with the age of the snapshot it was read from */
+export interface ProcessListSnapshot {
+  processes: ProcessInfo[];
+  /** Time since the process snapshot was taken (ms) */
+  snapshotAgeMs: number;
+  /** Whether this call refreshed the snapshot */
+  refreshed: boolean;
 }
 
 interface BackendProcessInfo {
@@ -331,7 +348,7 @@ interface BackendProcessOperation {
   result?: BackendProcessOperationResult;
 }
 
-interface BackendProcessConfig {
+export interface BackendProcessConfig {
   enabled?: boolean;
   allowedPrograms?: string[];
   allowed_programs?: string[];
@@ -345,6 +362,14 @@ interface BackendProcessConfig {
   max_tracked_processes?: number;
   defaultTimeoutSecs?: number;
   default_timeout_secs?: number;
+  minRefreshIntervalMs?: number;
+  min_refresh_interval_ms?: number;
+}
+
+interface BackendProcessListSnapshot {
+  processes: BackendProcessInfo[];
+  snapshotAgeMs: number;
+  refreshed: boolean;
 }
 
 function firstDefined<T>(...values: Array<T | undefined>): T | undefined {
@@ -367,7 +392,7 @@ function normalizeProcessInfo(process: BackendProcessInfo): ProcessInfo {
   };
 }
 
-function normalizeProcessConfig(config: BackendProcessConfig): ProcessManagerConfig {
+export function normalizeProcessConfig(config: BackendProcessConfig): ProcessManagerConfig {
   return {
     enabled: config.enabled ?? false,
     allowedPrograms: firstDefined(config.allowedPrograms, config.allowed_programs) ?? [],
@@ -377,6 +402,7 @@ function normalizeProcessConfig(config: BackendProcessConfig): ProcessManagerCon
     maxTrackedProcesses:
       firstDefined(config.maxTrackedProcesses, config.max_tracked_processes) ?? 100,
     defaultTimeoutSecs: firstDefined(config.defaultTimeoutSecs, config.default_timeout_secs) ?? 30,
+    minRefreshIntervalMs: firstDefined(config.minRefreshIntervalMs, config.min_refresh_interval_ms),
   };
 }
 
@@ -504,6 +530,7 @@ function toBackendStartRequest(request: StartProcessRequest): Record<string, unk
     ...request,
     timeout_secs: request.timeoutSecs,
     capture_output: request.captureOutput,
+    output_encoding: request.outputEncoding,
   };
 }
 
@@ -532,7 +559,7 @@ function toBackendTerminateBatchRequest(
   };
 }
 
-function toBackendProcessConfig(config: ProcessManagerConfig): Record<string, unknown> {
+export function toBackendProcessConfig(config: ProcessManagerConfig): Record<string, unknown> {
   return {
     ...config,
     allowed_programs: config.allowedPrograms,
@@ -541,6 +568,7 @@ function toBackendProcessConfig(config: ProcessManagerConfig): Record<string, un
     only_terminate_own: config.onlyTerminateOwn,
     max_tracked_processes: config.maxTrackedProcesses,
     default_timeout_secs: config.defaultTimeoutSecs,
+    min_refresh_interval_ms: config.minRefreshIntervalMs,
   };
 }
 
@@ -558,6 +586,24 @@ export async function listProcesses(filter?: ProcessFilter): Promise<ProcessInfo
   return result.map(normalizeProcessInfo);
 }
 
+/**
+ * List running processes with snapshot metadata. Calls within the configured
+ * minimum refresh interval reuse the cached snapshot unless forceRefresh is set.
+ */
+export async function listProcessesSnapshot(
+  filter?: ProcessFilter,
+  options?: { forceRefresh?: boolean }
+): Promise<ProcessListSnapshot> {
+  if (!isTauri()) {
+    throw new Error('Process management requires Tauri environment');
+  }
+  const result = await invoke<BackendProcessListSnapshot>('process_list_snapshot', {
+    filter: toBackendFilter(filter),
+    forceRefresh: options?.forceRefresh,
+  });
+  return { ...result, processes: result.processes.map(normalizeProcessInfo) };
+}
+
 /** Get process by PID */
 export async function getProcess(pid: number): Promise<ProcessInfo | null> {
   if (!isTauri()) {
@@ -731,6 +777,7 @@ export async function getTopMemoryProcesses(limit?: number): Promise<ProcessInfo
 export const processService = {
   isAvailable: isProcessManagementAvailable,
   list: listProcesses,
+  listSnapshot: listProcessesSnapshot,
   get: getProcess,
   start: startProcess,
   terminate: terminateProcess,
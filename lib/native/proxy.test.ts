@@ -28,6 +28,8 @@ import {
   buildProxyUrlFromDetected,
   setBackendProxy,
   syncBackendProxy,
+  setOfflineMode,
+  getOfflineMode,
   proxyService,
   type SystemProxySettings,
 } from './proxy';
@@ -345,6 +347,44 @@ describe('Proxy - setBackendProxy', () => {
   });
 });
 
+describe('Proxy - offline mode', () => {
+  beforeEach(() => {
+    jest.clearAllMocks();
+  });
+
+  it('should set offline mode in the backend', async () => {
+    mockIsTauri.mockReturnValue(true);
+    mockInvoke.mockResolvedValue(undefined);
+    await setOfflineMode(true);
+    expect(mockInvoke).toHaveBeenCalledWith('set_offline_mode', { enabled: true });
+  });
+
+  it('should skip setting offline mode when not in Tauri', async () => {
+    mockIsTauri.mockReturnValue(false);
+    await setOfflineMode(true);
+    expect(mockInvoke).not.toHaveBeenCalled();
+  });
+
+  it('should throw when setting offline mode fails', async () => {
+    mockIsTauri.mockReturnValue(true);
+    mockInvoke.mockRejectedValue(new Error('write failed'));
+    await expect(setOfflineMode(false)).rejects.toThrow('write failed');
+  });
+
+  it('should read offline mode from the backend', async () => {
+    mockIsTauri.mockReturnValue(true);
+    mockInvoke.mockResolvedValue(true);
+    expect(await getOfflineMode()).toBe(true);
+    expect(mockInvoke).toHaveBeenCalledWith('get_offline_mode');
+  });
+
+  it('should report online when the backend is unavailable', async () => {
+    mockIsTauri.mockReturnValue(true);
+    mockInvoke.mockRejectedValue(new Error('backend failure'));
+    expect(await getOfflineMode()).toBe(false);
+  });
+});
+
 describe('Proxy - syncBackendProxy', () => {
   beforeEach(() => {
     jest.clearAllMocks();
@@ -359,6 +359,38 @@ export async function syncBackendProxy(
   };
 }
 
+/** Enable or disable offline mode in the Rust backend.
+ *
+ * The choice is persisted by the backend and restored on the next launch.
+ * While enabled, network-dependent commands fail immediately and AI features
+ * only use local models.
+ */
+export async function setOfflineMode(enabled: boolean): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+
+  try {
+    await invoke('set_offline_mode', { enabled });
+    log.info(`Offline mode ${enabled ? 'enabled' : 'disabled'}`);
+  } catch (error) {
+    const message = mapInvokeError(error, 'Failed to set offline mode');
+    log.error('Failed to set offline mode:', error);
+    throw new Error(message);
+  }
+}
+
+/** Get whether offline mode is enabled in the Rust backend. */
+export async function getOfflineMode(): Promise<boolean> {
+  if (!isTauri()) return false;
+  try {
+    return await invoke<boolean>('get_offline_mode');
+  } catch (error) {
+    log.error('Failed to get offline mode:', error);
+    return false;
+  }
+}
+
 /** Proxy service object for convenient access */
 export const proxyService = {
   isAvailable: isProxyAvailable,
@@ -373,6 +405,8 @@ export const proxyService = {
   setBackendProxy,
   getBackendProxy,
   syncBackendProxy,
+  setOfflineMode,
+  getOfflineMode,
 };
 
 export default proxyService;
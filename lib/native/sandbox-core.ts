@@ -3,6 +3,7 @@ import type {
   BackendSandboxConfig,
   ExecutionRequest,
   ExecutionSession,
+  HighlightToken,
   Language,
   RuntimeType,
   SandboxPreflightRequest,
@@ -60,6 +61,28 @@ export async function cancelExecution(executionId: string): Promise<boolean> {
   });
 }
 
+/**
+ * Extend the time limit of an in-flight streaming execution.
+ * Resolves to the new limit in seconds, or null once the execution has finished or hit its limit.
+ */
+export async function extendTimeout(
+  executionId: string,
+  extraSecs: number
+): Promise<number | null> {
+  return invokeSandboxCommand<number | null>('sandbox_extend_timeout', {
+    execution_id: executionId,
+    extra_secs: extraSecs,
+  });
+}
+
+/**
+ * Tokenize code for syntax-highlighted display.
+ * Unsupported languages resolve to a single plain token.
+ */
+export async function highlightCode(language: string, code: string): Promise<HighlightToken[]> {
+  return invokeSandboxCommand<HighlightToken[]>('sandbox_highlight', { language, code });
+}
+
 export async function executeCodeStreaming(
   request: ExecutionRequest
 ): Promise<SandboxExecutionResult> {
@@ -256,14 +279,31 @@ export async function getSessionExecutions(
   );
 }
 
+/**
+ * How imported records are reconciled with existing data:
+ * `merge` skips existing ids, `overwrite` replaces them and `replace_all`
+ * clears existing data first.
+ */
+export type ImportMergeStrategy = 'merge' | 'overwrite' | 'replace_all';
+
 export interface ImportResult {
   imported_snippets: number;
   skipped_snippets: number;
-}
-
-export async function importData(jsonData: string): Promise<ImportResult> {
+  imported_executions?: number;
+  skipped_executions?: number;
+  imported_sessions?: number;
+  skipped_sessions?: number;
+  /** Existing records replaced under the overwrite strategy */
+  overwritten?: number;
+}
+
+export async function importData(
+  jsonData: string,
+  mergeStrategy?: ImportMergeStrategy
+): Promise<ImportResult> {
   return invokeSandboxCommand<ImportResult>('sandbox_import_data', {
     json_data: jsonData,
+    ...(mergeStrategy ? { merge_strategy: mergeStrategy } : {}),
   });
 }
 
@@ -516,6 +516,19 @@ describe('New Sandbox API Functions', () => {
       expect(result.imported_snippets).toBe(0);
       expect(result.skipped_snippets).toBe(0);
     });
+
+    it('should pass the merge strategy when given', async () => {
+      mockInvoke.mockResolvedValue({ imported_snippets: 1, skipped_snippets: 0, overwritten: 2 });
+
+      const { importData } = await import('@/lib/native/sandbox');
+      const result = await importData('{"version":"1.0"}', 'overwrite');
+
+      expect(mockInvoke).toHaveBeenCalledWith('sandbox_import_data', {
+        json_data: '{"version":"1.0"}',
+        merge_strategy: 'overwrite',
+      });
+      expect(result.overwritten).toBe(2);
+    });
   });
 
   describe('cancelExecution', () => {
@@ -539,6 +552,50 @@ describe('New Sandbox API Functions', () => {
       expect(result).toBe(false);
     });
   });
+
+  describe('extendTimeout', () => {
+    it('should call sandbox_extend_timeout command', async () => {
+      mockInvoke.mockResolvedValue(45);
+
+      const { extendTimeout } = await import('@/lib/native/sandbox');
+      const result = await extendTimeout('exec-slow', 15);
+
+      expect(mockInvoke).toHaveBeenCalledWith('sandbox_extend_timeout', {
+        execution_id: 'exec-slow',
+        extra_secs: 15,
+      });
+      expect(result).toBe(45);
+    });
+
+    it('should return null once the execution has finished', async () => {
+      mockInvoke.mockResolvedValue(null);
+
+      const { extendTimeout } = await import('@/lib/native/sandbox');
+      const result = await extendTimeout('finished', 15);
+      expect(result).toBeNull();
+    });
+  });
+
+  describe('highlightCode', () => {
+    it('should call sandbox_highlight command', async () => {
+      const tokens = [
+        { text: 'print', kind: 'function' },
+        { text: '(', kind: 'punctuation' },
+        { text: '"hi"', kind: 'string' },
+        { text: ')', kind: 'punctuation' },
+      ];
+      mockInvoke.mockResolvedValue(tokens);
+
+      const { highlightCode } = await import('@/lib/native/sandbox');
+      const result = await highlightCode('python', 'print("hi")');
+
+      expect(mockInvoke).toHaveBeenCalledWith('sandbox_highlight', {
+        language: 'python',
+        code: 'print("hi")',
+      });
+      expect(result).toEqual(tokens);
+    });
+  });
 });
 
 describe('OutputLine Type', () => {
@@ -14,6 +14,7 @@ import type {
   SandboxExecutionResult,
   SandboxStatus,
 } from '@/types/system/sandbox';
+import type { ImportMergeStrategy, ImportResult } from './sandbox-core';
 import {
   cancelExecution,
   checkRuntime,
@@ -23,6 +24,7 @@ import {
   executeCodeWithOptions,
   executeWithLimits,
   executeWithStdin,
+  extendTimeout,
   getAllLanguages,
   getAvailableLanguages,
   getAvailableRuntimes,
@@ -32,6 +34,7 @@ import {
   getSandboxStatus,
   getSessionExecutions,
   getSupportedLanguages,
+  highlightCode,
   invokeSandboxCommand,
   isSandboxAvailable,
   prepareLanguage,
@@ -53,6 +56,7 @@ export {
   executeCodeWithOptions as executeWithOptions,
   executeWithLimits,
   executeWithStdin,
+  extendTimeout,
   getAllLanguages,
   getAvailableLanguages,
   getAvailableRuntimes,
@@ -62,6 +66,7 @@ export {
   getSandboxStatus,
   getSessionExecutions,
   getSupportedLanguages,
+  highlightCode,
   isSandboxAvailable,
   prepareLanguage,
   quickExecute,
@@ -85,9 +90,12 @@ export async function exportData(): Promise<string> {
   return invokeSandboxCommand<string>('sandbox_export_data');
 }
 
-export async function importData(jsonData: string): Promise<{ imported_snippets: number; skipped_snippets: number }> {
+export async function importData(
+  jsonData: string,
+  mergeStrategy?: ImportMergeStrategy
+): Promise<ImportResult> {
   const { importData: coreImportData } = await import('./sandbox-core');
-  return coreImportData(jsonData);
+  return coreImportData(jsonData, mergeStrategy);
 }
 
 export async function getDatabaseSize(): Promise<number> {
@@ -124,6 +132,8 @@ export const sandboxService = {
   setMemoryLimit,
   setNetworkEnabled,
   cancelExecution,
+  extendTimeout,
+  highlight: highlightCode,
   executeStreaming: executeCodeStreaming,
   cleanup: cleanupRuntimes,
   isAvailable: isSandboxAvailable,
@@ -18,6 +18,7 @@ import {
   captureWindowWithHistory,
   captureRegionWithHistory,
   extractText,
+  extractTextWithEngine,
   extractTextWindows,
   extractTextWithLanguage,
   getOcrLanguages,
@@ -48,6 +49,7 @@ import {
   type ScreenshotConfig,
   type MonitorInfo,
   type WinOcrResult,
+  type ScreenshotOcrResult,
   type WindowInfo,
   type ElementInfo,
   type SelectionSnapResult,
@@ -162,13 +164,38 @@ describe('Screenshot - OCR', () => {
 
   describe('extractText', () => {
     it('should call invoke with image base64', async () => {
-      mockInvoke.mockResolvedValue('Extracted text');
+      mockInvoke.mockResolvedValue({
+        text: 'Extracted text',
+        engine: 'platform',
+        provider: 'Windows OCR',
+        confidence: 0.9,
+      });
       const result = await extractText('base64data');
       expect(mockInvoke).toHaveBeenCalledWith('screenshot_ocr', { imageBase64: 'base64data' });
       expect(result).toBe('Extracted text');
     });
   });
 
+  describe('extractTextWithEngine', () => {
+    it('should report the engine that ran', async () => {
+      const mockResult: ScreenshotOcrResult = {
+        text: 'Hello',
+        engine: 'tesseract',
+        provider: 'Tesseract',
+        language: 'eng',
+        confidence: 0.85,
+        fallback_reason: 'platform OCR is not available',
+      };
+      mockInvoke.mockResolvedValue(mockResult);
+
+      const result = await extractTextWithEngine('base64data');
+
+      expect(mockInvoke).toHaveBeenCalledWith('screenshot_ocr', { imageBase64: 'base64data' });
+      expect(result.engine).toBe('tesseract');
+      expect(result.fallback_reason).toBe('platform OCR is not available');
+    });
+  });
+
   describe('extractTextWindows', () => {
     it('should call invoke and return detailed result', async () => {
       const mockResult: WinOcrResult = {
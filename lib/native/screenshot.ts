@@ -52,6 +52,24 @@ export interface MonitorInfo {
   scale_factor: number;
 }
 
+/** OCR engine requested for screenshot OCR */
+export type OcrEngineSelection = "auto" | "platform" | "tesseract" | "cloud";
+
+/** OCR engine that actually produced a result */
+export type OcrEngineKind = "platform" | "tesseract" | "cloud";
+
+/** Screenshot OCR result including the engine that ran */
+export interface ScreenshotOcrResult {
+  text: string;
+  engine: OcrEngineKind;
+  /** Display name of the provider behind the engine */
+  provider: string;
+  language?: string | null;
+  confidence: number;
+  /** Why earlier engines in the chain were skipped, if any */
+  fallback_reason?: string | null;
+}
+
 export interface ScreenshotConfig {
   save_directory?: string;
   format: string;
@@ -61,8 +79,11 @@ export interface ScreenshotConfig {
   copy_to_clipboard: boolean;
   show_notification: boolean;
   ocr_language?: string;
+  /** OCR engine used by `screenshot_ocr` (default: "auto") */
+  ocr_engine?: OcrEngineSelection;
   auto_save: boolean;
   filename_template: string;
+  region_preview_pixel_color?: boolean;
 }
 
 export interface ScreenshotHistoryEntry {
@@ -287,9 +308,19 @@ export async function validateSelection(
 // ============== OCR Functions ==============
 
 /**
- * Extract text from screenshot using basic OCR
+ * Extract text from screenshot using the configured OCR engine
  */
 export async function extractText(imageBase64: string): Promise<string> {
+  const result = await extractTextWithEngine(imageBase64);
+  return result.text;
+}
+
+/**
+ * Extract text using the configured OCR engine and report which engine ran
+ */
+export async function extractTextWithEngine(
+  imageBase64: string
+): Promise<ScreenshotOcrResult> {
   return invoke("screenshot_ocr", { imageBase64 });
 }
 
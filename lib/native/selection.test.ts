@@ -35,6 +35,9 @@ import {
   pinClipboardEntry,
   unpinClipboardEntry,
   deleteClipboardEntry,
+  pinClipboardEntries,
+  unpinClipboardEntries,
+  deleteClipboardEntries,
   clearClipboardAll,
   smartExpand,
   autoExpand,
@@ -62,6 +65,7 @@ import {
   detectTextType,
   getToolbarConfig,
   setToolbarTheme,
+  setToolbarThemeConfig,
   getStatsSummary,
   analyzeClipboardContent,
   getCurrentClipboardWithAnalysis,
@@ -69,6 +73,8 @@ import {
   writeClipboardText,
   readClipboardText,
   writeClipboardHtml,
+  convertClipboardEntry,
+  pasteClipboardAsPlainText,
   clearClipboard,
   getClipboardSuggestedActions,
   extractClipboardEntities,
@@ -79,6 +85,11 @@ import {
   onSelectionAIChunk,
   onQuickAction,
   onQuickTranslate,
+  startMacroRecording,
+  stopMacroRecording,
+  recordMacroStep,
+  listMacros,
+  runMacro,
   type SelectionPayload,
   type SelectionConfig,
   type SelectionHistoryEntry,
@@ -366,6 +377,42 @@ describe('Selection - Clipboard History', () => {
     });
   });
 
+  describe('clipboard batch operations', () => {
+    const batchResult = {
+      total: 2,
+      success_count: 1,
+      failure_count: 1,
+      results: [
+        { id: 'clip-1', success: true, error: null },
+        { id: 'missing', success: false, error: 'Entry not found' },
+      ],
+    };
+
+    it('should pin entries in one call', async () => {
+      mockInvoke.mockResolvedValue(batchResult);
+      const result = await pinClipboardEntries(['clip-1', 'missing']);
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_pin_entries', {
+        ids: ['clip-1', 'missing'],
+      });
+      expect(result.failure_count).toBe(1);
+    });
+
+    it('should unpin entries in one call', async () => {
+      mockInvoke.mockResolvedValue(batchResult);
+      await unpinClipboardEntries(['clip-1']);
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_unpin_entries', { ids: ['clip-1'] });
+    });
+
+    it('should delete entries in one call', async () => {
+      mockInvoke.mockResolvedValue(batchResult);
+      const result = await deleteClipboardEntries(['clip-1', 'missing']);
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_delete_entries', {
+        ids: ['clip-1', 'missing'],
+      });
+      expect(result.results[1].error).toBe('Entry not found');
+    });
+  });
+
   describe('clearClipboardAll', () => {
     it('should call invoke', async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -736,6 +783,18 @@ describe('Selection - Toolbar Config (New APIs)', () => {
     });
   });
 
+  describe('setToolbarThemeConfig', () => {
+    it('should call invoke with theme config', async () => {
+      mockInvoke.mockResolvedValue(undefined);
+      const theme = {
+        theme: 'high-contrast' as const,
+        action_styles: { summarize: { color: '#22c55e', icon: 'sparkles' } },
+      };
+      await setToolbarThemeConfig(theme);
+      expect(mockInvoke).toHaveBeenCalledWith('selection_set_toolbar_theme', { theme });
+    });
+  });
+
   describe('setToolbarTheme', () => {
     it('should persist theme locally', async () => {
       await setToolbarTheme('glass');
@@ -806,6 +865,51 @@ describe('Selection - Clipboard Context Analysis (New APIs)', () => {
     });
   });
 
+  describe('pasteClipboardAsPlainText', () => {
+    it('should default to not pasting', async () => {
+      mockInvoke.mockResolvedValue({ text: 'bold', from_html: true, pasted: false, restored: false });
+      const result = await pasteClipboardAsPlainText();
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_paste_plain', { paste: false, restore: false });
+      expect(result.from_html).toBe(true);
+    });
+
+    it('should pass paste and restore flags', async () => {
+      mockInvoke.mockResolvedValue({ text: 'bold', from_html: true, pasted: true, restored: true });
+      await pasteClipboardAsPlainText({ paste: true, restore: true });
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_paste_plain', { paste: true, restore: true });
+    });
+  });
+
+  describe('convertClipboardEntry', () => {
+    it('should call invoke with target format', async () => {
+      const conversion = {
+        entry_id: 'clip-1',
+        source_format: 'html',
+        target_format: 'markdown',
+        content: '**bold**',
+        written_to_clipboard: false,
+      };
+      mockInvoke.mockResolvedValue(conversion);
+      const result = await convertClipboardEntry('clip-1', 'markdown');
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_convert_entry', {
+        id: 'clip-1',
+        targetFormat: 'markdown',
+        writeToClipboard: false,
+      });
+      expect(result).toEqual(conversion);
+    });
+
+    it('should pass write flag', async () => {
+      mockInvoke.mockResolvedValue({});
+      await convertClipboardEntry('clip-1', 'text', true);
+      expect(mockInvoke).toHaveBeenCalledWith('clipboard_convert_entry', {
+        id: 'clip-1',
+        targetFormat: 'text',
+        writeToClipboard: true,
+      });
+    });
+  });
+
   describe('clearClipboard', () => {
     it('should call invoke', async () => {
       mockInvoke.mockResolvedValue(undefined);
@@ -905,3 +1009,57 @@ describe('Selection - Event Listeners (New APIs)', () => {
     });
   });
 });
+
+describe('Selection - Macros', () => {
+  beforeEach(() => {
+    jest.clearAllMocks();
+  });
+
+  it('should start and stop macro recording', async () => {
+    mockInvoke.mockResolvedValueOnce(undefined);
+    await startMacroRecording();
+    expect(mockInvoke).toHaveBeenCalledWith('selection_record_macro_start');
+
+    const saved = {
+      id: 'macro-1',
+      name: 'translate → replace',
+      steps: [{ type: 'action', action_id: 'translate', target_language: 'en' }, { type: 'replace' }],
+      created_at: 1704067200000,
+      run_count: 0,
+    };
+    mockInvoke.mockResolvedValueOnce(saved);
+    const result = await stopMacroRecording('Translate in place');
+    expect(mockInvoke).toHaveBeenCalledWith('selection_record_macro_stop', {
+      name: 'Translate in place',
+    });
+    expect(result.steps).toHaveLength(2);
+  });
+
+  it('should record frontend steps', async () => {
+    mockInvoke.mockResolvedValue(true);
+    const recording = await recordMacroStep({ type: 'copy' });
+    expect(mockInvoke).toHaveBeenCalledWith('selection_record_macro_step', {
+      step: { type: 'copy' },
+    });
+    expect(recording).toBe(true);
+  });
+
+  it('should list and run macros', async () => {
+    mockInvoke.mockResolvedValueOnce([]);
+    expect(await listMacros()).toEqual([]);
+    expect(mockInvoke).toHaveBeenCalledWith('selection_list_macros');
+
+    mockInvoke.mockResolvedValueOnce({
+      macro_id: 'macro-1',
+      input: 'hola',
+      output: 'hello',
+      steps: [],
+    });
+    const run = await runMacro('macro-1', 'hola');
+    expect(mockInvoke).toHaveBeenCalledWith('selection_run_macro', {
+      id: 'macro-1',
+      input: 'hola',
+    });
+    expect(run.output).toBe('hello');
+  });
+});
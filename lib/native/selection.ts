@@ -38,6 +38,31 @@ export interface SelectionConfig {
   delay_ms: number;
   target_language: string;
   excluded_apps: string[];
+  toolbar_theme?: SelectionToolbarTheme;
+}
+
+export type ToolbarThemeName = "light" | "dark" | "high-contrast" | "custom";
+
+export interface ToolbarThemeColors {
+  background: string;
+  text: string;
+  accent: string;
+  border: string;
+}
+
+export interface ToolbarActionStyle {
+  /** Button color; defaults to the theme accent */
+  color?: string | null;
+  /** Icon name understood by the overlay */
+  icon?: string | null;
+}
+
+export interface SelectionToolbarTheme {
+  theme: ToolbarThemeName;
+  /** Required when `theme` is `custom` */
+  custom_colors?: ToolbarThemeColors | null;
+  /** Per-action colors and icons keyed by action ID */
+  action_styles: Record<string, ToolbarActionStyle>;
 }
 
 /**
@@ -216,6 +241,33 @@ export interface ClipboardEntry {
   preview: string;
 }
 
+export interface ClipboardBatchItemResult {
+  id: string;
+  success: boolean;
+  /** Why the operation failed for this ID */
+  error: string | null;
+}
+
+export type ClipboardFormat = "html" | "markdown" | "text";
+
+export interface ClipboardConversion {
+  entry_id: string;
+  /** Format the entry content was read as */
+  source_format: ClipboardFormat;
+  target_format: ClipboardFormat;
+  content: string;
+  /** Whether the converted content was written to the clipboard */
+  written_to_clipboard: boolean;
+}
+
+export interface ClipboardBatchResult {
+  total: number;
+  success_count: number;
+  failure_count: number;
+  /** Per-ID results in request order */
+  results: ClipboardBatchItemResult[];
+}
+
 export type SelectionMode =
   | "word"
   | "line"
@@ -347,6 +399,27 @@ export async function deleteClipboardEntry(id: string): Promise<boolean> {
   return invoke("clipboard_delete_entry", { id });
 }
 
+/**
+ * Pin several clipboard entries; unknown IDs are reported per item
+ */
+export async function pinClipboardEntries(ids: string[]): Promise<ClipboardBatchResult> {
+  return invoke("clipboard_pin_entries", { ids });
+}
+
+/**
+ * Unpin several clipboard entries; unknown IDs are reported per item
+ */
+export async function unpinClipboardEntries(ids: string[]): Promise<ClipboardBatchResult> {
+  return invoke("clipboard_unpin_entries", { ids });
+}
+
+/**
+ * Delete several clipboard entries; unknown IDs are reported per item
+ */
+export async function deleteClipboardEntries(ids: string[]): Promise<ClipboardBatchResult> {
+  return invoke("clipboard_delete_entries", { ids });
+}
+
 /**
  * Clear unpinned clipboard history
  */
@@ -494,6 +567,9 @@ export interface SelectionStatus {
   selected_text: string | null;
   last_selection_timestamp: number | null;
   config: SelectionConfig;
+  /** Whether selection detection lacks the OS accessibility permission */
+  degraded: boolean;
+  degraded_reason?: string | null;
 }
 
 /**
@@ -652,10 +728,20 @@ export async function replaceSelectedText(text: string): Promise<void> {
 /**
  * Get toolbar configuration as JSON
  */
-export async function getToolbarConfig(): Promise<Record<string, unknown>> {
+export async function getToolbarConfig(): Promise<
+  Record<string, unknown> & { toolbar_colors?: ToolbarThemeColors }
+> {
   return invoke("selection_get_toolbar_config");
 }
 
+/**
+ * Set the native toolbar theme and per-action colors and icons.
+ * Colors must be hex, rgb()/rgba() or hsl()/hsla() values.
+ */
+export async function setToolbarThemeConfig(theme: SelectionToolbarTheme): Promise<void> {
+  return invoke("selection_set_toolbar_theme", { theme });
+}
+
 /**
  * Update toolbar theme
  */
@@ -796,6 +882,42 @@ export async function writeClipboardHtml(
   return invoke("clipboard_write_html", { html, altText });
 }
 
+export interface ClipboardPastePlainResult {
+  /** Plain text written to the clipboard */
+  text: string;
+  /** Whether the text was extracted from HTML content */
+  from_html: boolean;
+  /** Whether the paste keystroke was sent to the foreground app */
+  pasted: boolean;
+  /** Whether the original clipboard content was restored after pasting */
+  restored: boolean;
+}
+
+/**
+ * Replace the clipboard with its plain-text content, optionally pasting it into
+ * the foreground app and restoring the original rich content afterwards
+ */
+export async function pasteClipboardAsPlainText(
+  options: { paste?: boolean; restore?: boolean } = {}
+): Promise<ClipboardPastePlainResult> {
+  return invoke("clipboard_paste_plain", {
+    paste: options.paste ?? false,
+    restore: options.restore ?? false,
+  });
+}
+
+/**
+ * Convert a clipboard entry between HTML, Markdown and plain text,
+ * optionally writing the result to the clipboard
+ */
+export async function convertClipboardEntry(
+  id: string,
+  targetFormat: ClipboardFormat,
+  writeToClipboard = false
+): Promise<ClipboardConversion> {
+  return invoke("clipboard_convert_entry", { id, targetFormat, writeToClipboard });
+}
+
 /**
  * Clear clipboard
  */
@@ -857,6 +979,88 @@ export async function detectClipboardLanguage(
   return invoke("clipboard_detect_language", { content });
 }
 
+// ============== Selection Macros ==============
+
+/** One recorded step of a selection macro */
+export type SelectionMacroStep =
+  | { type: "action"; action_id: string; target_language?: string | null }
+  | { type: "copy" }
+  | { type: "replace" };
+
+export interface SelectionMacro {
+  id: string;
+  name: string;
+  steps: SelectionMacroStep[];
+  created_at: number;
+  last_run_at?: number | null;
+  run_count: number;
+}
+
+export interface SelectionMacroRunResult {
+  macro_id: string;
+  input: string;
+  /** Text after the last step */
+  output: string;
+  steps: Array<{ step: SelectionMacroStep; output: string }>;
+}
+
+/**
+ * Start recording a selection macro
+ */
+export async function startMacroRecording(): Promise<void> {
+  return invoke("selection_record_macro_start");
+}
+
+/**
+ * Stop recording and save the captured steps as a macro
+ */
+export async function stopMacroRecording(name?: string): Promise<SelectionMacro> {
+  return invoke("selection_record_macro_stop", { name });
+}
+
+/**
+ * Discard the macro being recorded
+ */
+export async function cancelMacroRecording(): Promise<void> {
+  return invoke("selection_record_macro_cancel");
+}
+
+/**
+ * Report an action performed in the frontend while recording.
+ * Resolves to whether a recording is in progress.
+ */
+export async function recordMacroStep(step: SelectionMacroStep): Promise<boolean> {
+  return invoke("selection_record_macro_step", { step });
+}
+
+/**
+ * Check whether a macro is being recorded
+ */
+export async function isRecordingMacro(): Promise<boolean> {
+  return invoke("selection_is_recording_macro");
+}
+
+/**
+ * List saved selection macros
+ */
+export async function listMacros(): Promise<SelectionMacro[]> {
+  return invoke("selection_list_macros");
+}
+
+/**
+ * Delete a saved selection macro
+ */
+export async function deleteMacro(id: string): Promise<boolean> {
+  return invoke("selection_delete_macro", { id });
+}
+
+/**
+ * Replay a macro against `input`, or the current selection when omitted
+ */
+export async function runMacro(id: string, input?: string): Promise<SelectionMacroRunResult> {
+  return invoke("selection_run_macro", { id, input });
+}
+
 // ============== Selection Event Listeners ==============
 
 /**
@@ -0,0 +1,77 @@
+/**
+ * Settings Tests
+ */
+
+jest.mock('@tauri-apps/api/core', () => ({
+  invoke: jest.fn(),
+}));
+
+import { invoke } from '@tauri-apps/api/core';
+import { getAllSettings, updateSettings } from './settings';
+import type { ProcessManagerConfig } from './process';
+
+const mockInvoke = invoke as jest.MockedFunction<typeof invoke>;
+
+describe('settings', () => {
+  const backendProcess = {
+    enabled: true,
+    allowed_programs: ['node'],
+    denied_programs: ['rm'],
+    allow_terminate_any: false,
+    only_terminate_own: true,
+    max_tracked_processes: 100,
+    default_timeout_secs: 30,
+  };
+
+  beforeEach(() => {
+    jest.clearAllMocks();
+  });
+
+  it('should normalize the process slice of the snapshot', async () => {
+    mockInvoke.mockResolvedValue({ sandbox: null, process: backendProcess });
+
+    const settings = await getAllSettings();
+
+    expect(mockInvoke).toHaveBeenCalledWith('settings_get_all');
+    expect(settings.sandbox).toBeNull();
+    expect(settings.process?.allowedPrograms).toEqual(['node']);
+    expect(settings.process?.maxTrackedProcesses).toBe(100);
+  });
+
+  it('should keep a null process slice while initializing', async () => {
+    mockInvoke.mockResolvedValue({ sandbox: null, process: null });
+
+    const settings = await getAllSettings();
+
+    expect(settings.process).toBeNull();
+  });
+
+  it('should send only the provided slices', async () => {
+    mockInvoke.mockResolvedValue({ sandbox: null, process: null });
+    const screenshot = { format: 'png' } as unknown as Parameters<
+      typeof updateSettings
+    >[0]['screenshot'];
+
+    await updateSettings({ screenshot });
+
+    expect(mockInvoke).toHaveBeenCalledWith('settings_update', { patch: { screenshot } });
+  });
+
+  it('should convert the process slice to the backend shape', async () => {
+    mockInvoke.mockResolvedValue({ sandbox: null, process: backendProcess });
+    const process: ProcessManagerConfig = {
+      enabled: true,
+      allowedPrograms: ['node'],
+      deniedPrograms: ['rm'],
+      allowTerminateAny: false,
+      onlyTerminateOwn: true,
+      maxTrackedProcesses: 100,
+      defaultTimeoutSecs: 30,
+    };
+
+    await updateSettings({ process });
+
+    const args = mockInvoke.mock.calls[0][1] as { patch: { process: Record<string, unknown> } };
+    expect(args.patch.process.allowed_programs).toEqual(['node']);
+  });
+});
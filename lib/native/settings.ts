@@ -0,0 +1,71 @@
+/**
+ * Settings Native API
+ *
+ * Single settings surface over the backend subsystem configs. Updates that touch
+ * several subsystems are applied atomically: if one subsystem rejects its slice,
+ * the backend rolls back the others and rejects with a command error whose
+ * `details.subsystem` names the failing subsystem.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { BackendSandboxConfig } from '@/types/system/sandbox';
+import type { CompletionConfig } from '@/types/input-completion';
+import {
+  normalizeProcessConfig,
+  toBackendProcessConfig,
+  type BackendProcessConfig,
+  type ProcessManagerConfig,
+} from './process';
+import type { ScreenshotConfig } from './screenshot';
+import type { SelectionConfig } from './selection';
+
+export type SettingsSubsystem = 'sandbox' | 'process' | 'completion' | 'selection' | 'screenshot';
+
+export interface SettingsSnapshot {
+  /** `null` while the sandbox is still initializing */
+  sandbox: BackendSandboxConfig | null;
+  /** `null` while the process manager is still initializing */
+  process: ProcessManagerConfig | null;
+  completion: CompletionConfig;
+  selection: SelectionConfig;
+  screenshot: ScreenshotConfig;
+}
+
+/** Replacement slices; omitted subsystems are left unchanged */
+export interface SettingsPatch {
+  sandbox?: BackendSandboxConfig;
+  process?: ProcessManagerConfig;
+  completion?: CompletionConfig;
+  selection?: SelectionConfig;
+  screenshot?: ScreenshotConfig;
+}
+
+interface BackendSettingsSnapshot extends Omit<SettingsSnapshot, 'process'> {
+  process: BackendProcessConfig | null;
+}
+
+function normalizeSnapshot(snapshot: BackendSettingsSnapshot): SettingsSnapshot {
+  return {
+    ...snapshot,
+    process: snapshot.process ? normalizeProcessConfig(snapshot.process) : null,
+  };
+}
+
+/**
+ * Get the current configuration of every subsystem
+ */
+export async function getAllSettings(): Promise<SettingsSnapshot> {
+  const snapshot = await invoke<BackendSettingsSnapshot>('settings_get_all');
+  return normalizeSnapshot(snapshot);
+}
+
+/**
+ * Apply a settings patch atomically and return the resulting settings
+ */
+export async function updateSettings(patch: SettingsPatch): Promise<SettingsSnapshot> {
+  const { process, ...rest } = patch;
+  const snapshot = await invoke<BackendSettingsSnapshot>('settings_update', {
+    patch: process ? { ...rest, process: toBackendProcessConfig(process) } : rest,
+  });
+  return normalizeSnapshot(snapshot);
+}
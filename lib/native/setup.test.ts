@@ -0,0 +1,65 @@
+/**
+ * Setup Tests
+ */
+
+jest.mock('@tauri-apps/api/core', () => ({
+  invoke: jest.fn(),
+}));
+
+import { invoke } from '@tauri-apps/api/core';
+import { checkDependencies, getMissingDependencies, type SetupReport } from './setup';
+
+const mockInvoke = invoke as jest.MockedFunction<typeof invoke>;
+
+describe('setup', () => {
+  const report: SetupReport = {
+    platform: 'linux',
+    missing_count: 1,
+    dependencies: [
+      {
+        dependency: 'docker',
+        name: 'Docker',
+        present: true,
+        version: '27.0.1',
+        detail: null,
+        features: ['Code sandbox (isolated execution)'],
+        install_hint: null,
+      },
+      {
+        dependency: 'ffmpeg',
+        name: 'FFmpeg',
+        present: false,
+        version: null,
+        detail: null,
+        features: ['Screen recording'],
+        install_hint: {
+          platform: 'Linux',
+          download_url: 'https://ffmpeg.org/download.html',
+          instructions: ['Ubuntu/Debian: sudo apt install ffmpeg'],
+        },
+      },
+    ],
+  };
+
+  beforeEach(() => {
+    jest.clearAllMocks();
+  });
+
+  it('should invoke the dependency check with the Ollama endpoint', async () => {
+    mockInvoke.mockResolvedValue(report);
+
+    const result = await checkDependencies('http://localhost:11434');
+
+    expect(mockInvoke).toHaveBeenCalledWith('setup_check_dependencies', {
+      ollamaBaseUrl: 'http://localhost:11434',
+    });
+    expect(result.missing_count).toBe(1);
+  });
+
+  it('should list missing dependencies', () => {
+    const missing = getMissingDependencies(report);
+
+    expect(missing).toHaveLength(1);
+    expect(missing[0].dependency).toBe('ffmpeg');
+  });
+});
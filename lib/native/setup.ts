@@ -0,0 +1,47 @@
+/**
+ * Setup Native API
+ *
+ * First-run dependency diagnostics: reports which optional external tools are
+ * installed, the features they unlock, and how to install missing ones.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { FFmpegInstallGuide } from './screen-recording';
+
+export type Dependency = 'docker' | 'podman' | 'ollama' | 'ffmpeg' | 'ocr' | 'node' | 'uv';
+
+export interface DependencyStatus {
+  dependency: Dependency;
+  name: string;
+  /** Whether the dependency is installed (or, for Ollama, reachable) */
+  present: boolean;
+  version: string | null;
+  /** Extra context, e.g. installed OCR languages */
+  detail: string | null;
+  /** Features unlocked by this dependency */
+  features: string[];
+  /** Install hint for the current platform (null when present) */
+  install_hint: FFmpegInstallGuide | null;
+}
+
+export interface SetupReport {
+  platform: string;
+  dependencies: DependencyStatus[];
+  missing_count: number;
+}
+
+/**
+ * Check every optional dependency
+ *
+ * @param ollamaBaseUrl - Ollama endpoint to probe (defaults to the local server)
+ */
+export async function checkDependencies(ollamaBaseUrl?: string): Promise<SetupReport> {
+  return invoke<SetupReport>('setup_check_dependencies', { ollamaBaseUrl });
+}
+
+/**
+ * Get the dependencies that are missing from a report
+ */
+export function getMissingDependencies(report: SetupReport): DependencyStatus[] {
+  return report.dependencies.filter((dependency) => !dependency.present);
+}
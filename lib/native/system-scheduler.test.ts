@@ -16,6 +16,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { isTauri } from '@/lib/utils';
 import {
   getSchedulerCapabilities,
+  getSchedulerUsage,
   isSchedulerAvailable,
   isSchedulerElevated,
   createSystemTask,
@@ -26,6 +27,11 @@ import {
   enableSystemTask,
   disableSystemTask,
   runSystemTaskNow,
+  getTaskRunHistory,
+  setRunHistoryLimit,
+  previewCronSchedule,
+  exportSystemTasks,
+  importSystemTasks,
   confirmSystemTask,
   cancelTaskConfirmation,
   getPendingConfirmations,
@@ -76,6 +82,27 @@ describe('System Scheduler Native API', () => {
     });
   });
 
+  describe('getSchedulerUsage', () => {
+    it('should return usage from Tauri', async () => {
+      const mockUsage = { task_count: 3, max_tasks: 10, remaining: 7 };
+      (invoke as jest.Mock).mockResolvedValue(mockUsage);
+
+      const result = await getSchedulerUsage();
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_get_usage');
+      expect(result).toEqual(mockUsage);
+    });
+
+    it('should return unlimited usage when not in Tauri', async () => {
+      (isTauri as jest.Mock).mockReturnValue(false);
+
+      const result = await getSchedulerUsage();
+
+      expect(invoke).not.toHaveBeenCalled();
+      expect(result).toEqual({ task_count: 0, max_tasks: 0, remaining: null });
+    });
+  });
+
   describe('isSchedulerAvailable', () => {
     it('should return true when scheduler is available', async () => {
       (invoke as jest.Mock).mockResolvedValue(true);
@@ -222,7 +249,17 @@ describe('System Scheduler Native API', () => {
 
       const result = await listSystemTasks();
 
-      expect(invoke).toHaveBeenCalledWith('scheduler_list_tasks');
+      expect(invoke).toHaveBeenCalledWith('scheduler_list_tasks', { ownedOnly: true });
+      expect(result).toEqual(mockTasks);
+    });
+
+    it('should include external tasks when requested', async () => {
+      const mockTasks = [{ id: 'task-1' }, { id: 'com.apple.backup', external: true }];
+      (invoke as jest.Mock).mockResolvedValue(mockTasks);
+
+      const result = await listSystemTasks(false);
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_list_tasks', { ownedOnly: false });
       expect(result).toEqual(mockTasks);
     });
 
@@ -280,6 +317,102 @@ describe('System Scheduler Native API', () => {
     });
   });
 
+  describe('run history', () => {
+    it('loads run history with an optional limit', async () => {
+      const runs = [{ task_id: 'task-1', success: true, attempt: 2 }];
+      (invoke as jest.Mock).mockResolvedValue(runs);
+
+      const result = await getTaskRunHistory('task-1', 10);
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_get_run_history', {
+        taskId: 'task-1',
+        limit: 10,
+      });
+      expect(result).toEqual(runs);
+    });
+
+    it('sets the run history limit', async () => {
+      (invoke as jest.Mock).mockResolvedValue(undefined);
+
+      await setRunHistoryLimit(20);
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_set_run_history_limit', { limit: 20 });
+    });
+
+    it('returns no history outside Tauri', async () => {
+      (isTauri as jest.Mock).mockReturnValue(false);
+
+      expect(await getTaskRunHistory('task-1')).toEqual([]);
+      expect(invoke).not.toHaveBeenCalled();
+    });
+  });
+
+  describe('previewCronSchedule', () => {
+    it('previews fire times in a timezone', async () => {
+      const times = ['2026-01-01T09:00:00+01:00'];
+      (invoke as jest.Mock).mockResolvedValue(times);
+
+      const result = await previewCronSchedule('0 9 * * *', 1, 'Europe/Berlin');
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_preview_cron', {
+        expression: '0 9 * * *',
+        count: 1,
+        timezone: 'Europe/Berlin',
+      });
+      expect(result).toEqual(times);
+    });
+  });
+
+  describe('export/import tasks', () => {
+    const bundle = {
+      version: 1,
+      exported_at: '2026-01-01T00:00:00Z',
+      tasks: [
+        {
+          name: 'Backup',
+          trigger: { type: 'interval' as const, seconds: 3600 },
+          action: { type: 'run_command' as const, command: 'backup' },
+          retry_policy: { max_attempts: 3, backoff_secs: 30 },
+        },
+      ],
+    };
+
+    it('exports selected tasks', async () => {
+      (invoke as jest.Mock).mockResolvedValue(bundle);
+
+      const result = await exportSystemTasks(['task-1']);
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_export_tasks', { taskIds: ['task-1'] });
+      expect(result).toEqual(bundle);
+    });
+
+    it('imports a bundle with default flags', async () => {
+      const importResult = { imported: [], confirmations: [], skipped: [] };
+      (invoke as jest.Mock).mockResolvedValue(importResult);
+
+      const result = await importSystemTasks(bundle);
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_import_tasks', {
+        bundle,
+        overwrite: false,
+        confirmed: false,
+      });
+      expect(result).toEqual(importResult);
+    });
+
+    it('passes overwrite and confirmed through', async () => {
+      (invoke as jest.Mock).mockResolvedValue({ imported: [], confirmations: [], skipped: [] });
+
+      await importSystemTasks(bundle, { overwrite: true, confirmed: true });
+
+      expect(invoke).toHaveBeenCalledWith('scheduler_import_tasks', {
+        bundle,
+        overwrite: true,
+        confirmed: true,
+      });
+    });
+  });
+
   describe('confirm/cancel pending confirmations', () => {
     it('confirms by confirmation id', async () => {
       const confirmedTask = { id: 'task-confirmed', name: 'Confirmed Task' };
@@ -11,10 +11,14 @@ import { isTauri } from '@/lib/utils';
 import type {
   CreateSystemTaskInput,
   SchedulerCapabilities,
+  SchedulerUsage,
   SystemTask,
   SystemTaskId,
+  TaskBundle,
   TaskConfirmationRequest,
+  TaskImportResult,
   TaskOperationResponse,
+  TaskRunRecord,
   TaskRunResult,
   ValidationResult,
 } from '@/types/scheduler';
@@ -37,6 +41,17 @@ export async function getSchedulerCapabilities(): Promise<SchedulerCapabilities>
   return invoke<SchedulerCapabilities>('scheduler_get_capabilities');
 }
 
+/**
+ * Get the current task count against the platform task limit
+ */
+export async function getSchedulerUsage(): Promise<SchedulerUsage> {
+  if (!isTauri()) {
+    return { task_count: 0, max_tasks: 0, remaining: null };
+  }
+
+  return invoke<SchedulerUsage>('scheduler_get_usage');
+}
+
 /**
  * Check if the system scheduler is available
  */
@@ -132,14 +147,15 @@ export async function getSystemTask(taskId: SystemTaskId): Promise<SystemTask |
 }
 
 /**
- * List all Cognia-managed system tasks
+ * List system tasks created through Cognia.
+ * Pass `ownedOnly: false` to include external tasks, marked `external` and read-only.
  */
-export async function listSystemTasks(): Promise<SystemTask[]> {
+export async function listSystemTasks(ownedOnly = true): Promise<SystemTask[]> {
   if (!isTauri()) {
     return [];
   }
 
-  return invoke<SystemTask[]>('scheduler_list_tasks');
+  return invoke<SystemTask[]>('scheduler_list_tasks', { ownedOnly });
 }
 
 /**
@@ -175,6 +191,83 @@ export async function runSystemTaskNow(taskId: SystemTaskId): Promise<TaskRunRes
   return invoke<TaskRunResult>('scheduler_run_task_now', { taskId });
 }
 
+/**
+ * Get the most recent runs of a task started through Cognia, newest first
+ *
+ * @param limit Maximum runs to return (default: the run history limit)
+ */
+export async function getTaskRunHistory(
+  taskId: SystemTaskId,
+  limit?: number
+): Promise<TaskRunRecord[]> {
+  if (!isTauri()) {
+    return [];
+  }
+
+  return invoke<TaskRunRecord[]>('scheduler_get_run_history', { taskId, limit });
+}
+
+/**
+ * Set how many runs are kept per task; older runs are pruned on the task's next run
+ */
+export async function setRunHistoryLimit(limit: number): Promise<void> {
+  if (!isTauri()) {
+    throw new Error('System scheduler requires Tauri environment');
+  }
+
+  return invoke<void>('scheduler_set_run_history_limit', { limit });
+}
+
+/**
+ * Preview the next fire times of a cron expression as RFC 3339 strings
+ *
+ * @param count Number of fire times (default 5)
+ * @param timezone IANA timezone name (default: local time)
+ */
+export async function previewCronSchedule(
+  expression: string,
+  count?: number,
+  timezone?: string
+): Promise<string[]> {
+  if (!isTauri()) {
+    return [];
+  }
+
+  return invoke<string[]>('scheduler_preview_cron', { expression, count, timezone });
+}
+
+/**
+ * Export tasks as a JSON bundle; all Cognia tasks when `taskIds` is omitted
+ */
+export async function exportSystemTasks(taskIds?: SystemTaskId[]): Promise<TaskBundle> {
+  if (!isTauri()) {
+    throw new Error('System scheduler requires Tauri environment');
+  }
+
+  return invoke<TaskBundle>('scheduler_export_tasks', { taskIds });
+}
+
+/**
+ * Import tasks from a bundle
+ *
+ * Tasks needing confirmation come back as pending confirmations unless `confirmed`
+ * is set; tasks whose name already exists are skipped unless `overwrite` is set.
+ */
+export async function importSystemTasks(
+  bundle: TaskBundle,
+  options: { overwrite?: boolean; confirmed?: boolean } = {}
+): Promise<TaskImportResult> {
+  if (!isTauri()) {
+    throw new Error('System scheduler requires Tauri environment');
+  }
+
+  return invoke<TaskImportResult>('scheduler_import_tasks', {
+    bundle,
+    overwrite: options.overwrite ?? false,
+    confirmed: options.confirmed ?? false,
+  });
+}
+
 /**
  * Confirm a pending task operation
  */
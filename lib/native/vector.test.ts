@@ -16,15 +16,19 @@ import { invoke } from '@tauri-apps/api/core';
 import { isTauri } from './utils';
 import {
   nativeVectorCreateCollection,
+  nativeVectorCreateCollectionForModel,
   nativeVectorDeleteCollection,
   nativeVectorListCollections,
   nativeVectorGetCollection,
   nativeVectorUpsertPoints,
+  nativeVectorEmbedAndUpsert,
   nativeVectorDeletePoints,
   nativeVectorGetPoints,
   nativeVectorSearch,
+  nativeVectorScoreHistogram,
   nativeVectorDeleteAllPoints,
   nativeVectorStats,
+  nativeVectorMigrateStorageFormat,
   nativeVectorScrollPoints,
   nativeVectorRenameCollection,
   nativeVectorTruncateCollection,
@@ -65,6 +69,41 @@ describe('Vector - Collection Operations', () => {
     });
   });
 
+  describe('nativeVectorCreateCollectionForModel', () => {
+    it('should throw when not in Tauri', async () => {
+      mockIsTauri.mockReturnValue(false);
+      await expect(
+        nativeVectorCreateCollectionForModel('test', 'http://localhost:11434', 'nomic-embed-text')
+      ).rejects.toThrow('Tauri');
+    });
+
+    it('should call invoke with model parameters', async () => {
+      const info: NativeCollectionInfo = {
+        name: 'test',
+        dimension: 768,
+        embedding_model: 'nomic-embed-text',
+        embedding_provider: 'ollama',
+      };
+      mockInvoke.mockResolvedValue(info);
+
+      const result = await nativeVectorCreateCollectionForModel(
+        'test',
+        'http://localhost:11434',
+        'nomic-embed-text'
+      );
+      expect(mockInvoke).toHaveBeenCalledWith('vector_create_collection_for_model', {
+        payload: {
+          name: 'test',
+          base_url: 'http://localhost:11434',
+          model: 'nomic-embed-text',
+          metadata: undefined,
+          description: undefined,
+        },
+      });
+      expect(result.dimension).toBe(768);
+    });
+  });
+
   describe('nativeVectorDeleteCollection', () => {
     it('should throw when not in Tauri', async () => {
       mockIsTauri.mockReturnValue(false);
@@ -147,6 +186,36 @@ describe('Vector - Point Operations', () => {
     });
   });
 
+  describe('nativeVectorEmbedAndUpsert', () => {
+    it('should throw when not in Tauri', async () => {
+      mockIsTauri.mockReturnValue(false);
+      await expect(
+        nativeVectorEmbedAndUpsert('test', 'http://localhost:11434', 'nomic-embed-text', [])
+      ).rejects.toThrow('Tauri');
+    });
+
+    it('should call invoke with documents and return warnings', async () => {
+      mockInvoke.mockResolvedValue({ upserted: 1, warnings: ['model mismatch'] });
+      const documents = [{ id: 'doc-1', text: 'hello' }];
+
+      const result = await nativeVectorEmbedAndUpsert(
+        'test',
+        'http://localhost:11434',
+        'all-minilm',
+        documents
+      );
+      expect(mockInvoke).toHaveBeenCalledWith('vector_embed_and_upsert', {
+        payload: {
+          collection: 'test',
+          base_url: 'http://localhost:11434',
+          model: 'all-minilm',
+          documents,
+        },
+      });
+      expect(result.warnings).toEqual(['model mismatch']);
+    });
+  });
+
   describe('nativeVectorDeletePoints', () => {
     it('should throw when not in Tauri', async () => {
       mockIsTauri.mockReturnValue(false);
@@ -239,6 +308,37 @@ describe('Vector - Search Operations', () => {
     });
   });
 
+  describe('nativeVectorScoreHistogram', () => {
+    it('should throw when not in Tauri', async () => {
+      mockIsTauri.mockReturnValue(false);
+      await expect(nativeVectorScoreHistogram('test', [0.1, 0.2])).rejects.toThrow('Tauri');
+    });
+
+    it('should call invoke with histogram parameters', async () => {
+      const histogram = {
+        buckets: [
+          { min: 0.1, max: 0.5, count: 3 },
+          { min: 0.5, max: 0.9, count: 1 },
+        ],
+        total: 4,
+        score_stats: { min: 0.1, max: 0.9, mean: 0.4 },
+      };
+      mockInvoke.mockResolvedValue(histogram);
+
+      const result = await nativeVectorScoreHistogram('test', [0.1, 0.2], { buckets: 2 });
+      expect(mockInvoke).toHaveBeenCalledWith('vector_score_histogram', {
+        payload: {
+          collection: 'test',
+          vector: [0.1, 0.2],
+          buckets: 2,
+          filters: undefined,
+          filter_mode: undefined,
+        },
+      });
+      expect(result).toEqual(histogram);
+    });
+  });
+
   describe('nativeVectorScrollPoints', () => {
     it('should throw when not in Tauri', async () => {
       mockIsTauri.mockReturnValue(false);
@@ -295,6 +395,7 @@ describe('Vector - Management Operations', () => {
         total_points: 10000,
         storage_path: '/path/to/storage',
         storage_size_bytes: 1073741824,
+        storage_format: 'json',
       };
       mockInvoke.mockResolvedValue(mockStats);
 
@@ -304,6 +405,29 @@ describe('Vector - Management Operations', () => {
     });
   });
 
+  describe('nativeVectorMigrateStorageFormat', () => {
+    it('should throw when not in Tauri', async () => {
+      mockIsTauri.mockReturnValue(false);
+      await expect(nativeVectorMigrateStorageFormat('binary')).rejects.toThrow('Tauri');
+    });
+
+    it('should call invoke with target format', async () => {
+      mockInvoke.mockResolvedValue({
+        from: 'json',
+        to: 'binary',
+        storage_path: '/path/to/vector_store.bin',
+        backup_path: '/path/to/vector_store.json.bak',
+        size_before_bytes: 4096,
+        size_after_bytes: 1024,
+      });
+
+      const result = await nativeVectorMigrateStorageFormat('binary');
+      expect(mockInvoke).toHaveBeenCalledWith('vector_migrate_storage_format', { target: 'binary' });
+      expect(result.to).toBe('binary');
+      expect(result.backup_path).toBe('/path/to/vector_store.json.bak');
+    });
+  });
+
   describe('nativeVectorRenameCollection', () => {
     it('should throw when not in Tauri', async () => {
       mockIsTauri.mockReturnValue(false);
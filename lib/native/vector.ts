@@ -45,11 +45,31 @@ export interface NativeSearchResult {
   payload?: Record<string, unknown>;
 }
 
+export interface NativeScoreStats {
+  min: number;
+  max: number;
+  mean: number;
+}
+
 export interface NativeSearchResponse {
   results: NativeSearchResult[];
   total: number;
   offset: number;
   limit: number;
+  /** Score distribution over all matches; omitted when nothing matched */
+  score_stats?: NativeScoreStats;
+}
+
+export interface NativeScoreBucket {
+  min: number;
+  max: number;
+  count: number;
+}
+
+export interface NativeScoreHistogram {
+  buckets: NativeScoreBucket[];
+  total: number;
+  score_stats?: NativeScoreStats;
 }
 
 export interface NativeScrollResponse {
@@ -60,11 +80,36 @@ export interface NativeScrollResponse {
   has_more: boolean;
 }
 
+export type NativeVectorStorageFormat = 'json' | 'binary';
+
 export interface NativeVectorStats {
   collection_count: number;
   total_points: number;
   storage_path: string;
   storage_size_bytes: number;
+  storage_format: NativeVectorStorageFormat;
+}
+
+export interface NativeStorageMigrationResult {
+  from: NativeVectorStorageFormat;
+  to: NativeVectorStorageFormat;
+  storage_path: string;
+  /** Previous store file, kept until the user removes it */
+  backup_path?: string | null;
+  size_before_bytes: number;
+  size_after_bytes: number;
+}
+
+export interface NativeEmbedDocument {
+  id: string;
+  text: string;
+  payload?: Record<string, unknown>;
+}
+
+export interface NativeEmbedAndUpsertResponse {
+  upserted: number;
+  /** Set when the model differs from the one the collection was created for */
+  warnings: string[];
 }
 
 export interface NativeCollectionExport {
@@ -86,6 +131,28 @@ export async function nativeVectorCreateCollection(
   return invoke<boolean>('vector_create_collection', { payload: { name, dimension, metadata } });
 }
 
+/**
+ * Create a collection sized for an Ollama embedding model, probing the model
+ * to discover its dimension.
+ */
+export async function nativeVectorCreateCollectionForModel(
+  name: string,
+  baseUrl: string,
+  model: string,
+  options: { metadata?: Record<string, unknown>; description?: string } = {}
+): Promise<NativeCollectionInfo> {
+  if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
+  return invoke<NativeCollectionInfo>('vector_create_collection_for_model', {
+    payload: {
+      name,
+      base_url: baseUrl,
+      model,
+      metadata: options.metadata,
+      description: options.description,
+    },
+  });
+}
+
 export async function nativeVectorDeleteCollection(name: string): Promise<boolean> {
   if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
   return invoke<boolean>('vector_delete_collection', { name });
@@ -106,6 +173,18 @@ export async function nativeVectorUpsertPoints(collection: string, points: Nativ
   return invoke<boolean>('vector_upsert_points', { collection, points });
 }
 
+export async function nativeVectorEmbedAndUpsert(
+  collection: string,
+  baseUrl: string,
+  model: string,
+  documents: NativeEmbedDocument[]
+): Promise<NativeEmbedAndUpsertResponse> {
+  if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
+  return invoke<NativeEmbedAndUpsertResponse>('vector_embed_and_upsert', {
+    payload: { collection, base_url: baseUrl, model, documents },
+  });
+}
+
 export async function nativeVectorDeletePoints(collection: string, ids: string[]): Promise<boolean> {
   if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
   return invoke<boolean>('vector_delete_points', { collection, ids });
@@ -145,6 +224,33 @@ export async function nativeVectorSearch(
   });
 }
 
+export interface NativeScoreHistogramOptions {
+  buckets?: number;
+  filters?: NativePayloadFilter[];
+  filterMode?: 'and' | 'or';
+}
+
+/**
+ * Score a sample query against a whole collection and bucket the raw
+ * similarity scores, for calibrating score thresholds.
+ */
+export async function nativeVectorScoreHistogram(
+  collection: string,
+  sampleQuery: number[],
+  options: NativeScoreHistogramOptions = {}
+): Promise<NativeScoreHistogram> {
+  if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
+  return invoke<NativeScoreHistogram>('vector_score_histogram', {
+    payload: {
+      collection,
+      vector: sampleQuery,
+      buckets: options.buckets,
+      filters: options.filters,
+      filter_mode: options.filterMode,
+    },
+  });
+}
+
 export async function nativeVectorDeleteAllPoints(collection: string): Promise<number> {
   if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
   return invoke<number>('vector_delete_all_points', { collection });
@@ -155,6 +261,16 @@ export async function nativeVectorStats(): Promise<NativeVectorStats> {
   return invoke<NativeVectorStats>('vector_stats');
 }
 
+/**
+ * Convert the on-disk vector store to `target`, keeping the previous file as a backup.
+ */
+export async function nativeVectorMigrateStorageFormat(
+  target: NativeVectorStorageFormat
+): Promise<NativeStorageMigrationResult> {
+  if (!isTauri()) throw new Error('Native vector store requires Tauri runtime');
+  return invoke<NativeStorageMigrationResult>('vector_migrate_storage_format', { target });
+}
+
 export interface NativeScrollOptions {
   offset?: number;
   limit?: number;
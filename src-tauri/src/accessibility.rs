@@ -0,0 +1,145 @@
+//! Accessibility permission detection
+//!
+//! Selection, context and input completion rely on OS accessibility and global
+//! input APIs. Without them they start normally but never see any events, so each
+//! manager records a degraded status with the reason and emits
+//! `permission://missing` once so the UI can prompt the user.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted once per component and run when a required permission is missing
+pub const PERMISSION_MISSING_EVENT: &str = "permission://missing";
+
+/// Components that already emitted `permission://missing`
+static NOTIFIED_COMPONENTS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Whether a component runs without the OS APIs it needs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DegradedStatus {
+    pub degraded: bool,
+    /// Why the component is degraded
+    pub reason: Option<String>,
+}
+
+impl DegradedStatus {
+    /// Detect the accessibility permission state for the current platform
+    pub fn detect() -> Self {
+        Self::from_reason(unavailable_reason())
+    }
+
+    fn from_reason(reason: Option<String>) -> Self {
+        Self {
+            degraded: reason.is_some(),
+            reason,
+        }
+    }
+}
+
+/// Payload of `permission://missing`
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionMissingPayload {
+    pub permission: &'static str,
+    /// Component that detected the missing permission
+    pub component: String,
+    pub reason: String,
+}
+
+/// Detect the permission state for `component` and notify the UI if it is degraded
+pub fn check_and_notify(app_handle: &AppHandle, component: &str) -> DegradedStatus {
+    let status = DegradedStatus::detect();
+    notify_if_degraded(app_handle, component, &status);
+    status
+}
+
+/// Emit `permission://missing` for a degraded status, at most once per component
+pub fn notify_if_degraded(app_handle: &AppHandle, component: &str, status: &DegradedStatus) {
+    let Some(reason) = status.reason.clone() else {
+        return;
+    };
+    log::warn!("[Accessibility] {} is degraded: {}", component, reason);
+
+    if !NOTIFIED_COMPONENTS.lock().insert(component.to_string()) {
+        return;
+    }
+    let payload = PermissionMissingPayload {
+        permission: "accessibility",
+        component: component.to_string(),
+        reason,
+    };
+    if let Err(e) = app_handle.emit(PERMISSION_MISSING_EVENT, payload) {
+        log::warn!("[Accessibility] Failed to emit permission event: {}", e);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn unavailable_reason() -> Option<String> {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    // SAFETY: AXIsProcessTrusted takes no arguments and only reads process state.
+    if unsafe { AXIsProcessTrusted() } {
+        None
+    } else {
+        Some(
+            "Accessibility permission has not been granted \
+             (System Settings > Privacy & Security > Accessibility)"
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn unavailable_reason() -> Option<String> {
+    linux_unavailable_reason(
+        std::env::var_os("DISPLAY").is_some(),
+        std::env::var_os("WAYLAND_DISPLAY").is_some(),
+    )
+}
+
+/// Global input hooks need an X11 display; pure Wayland sessions do not expose one
+#[cfg(any(target_os = "linux", test))]
+fn linux_unavailable_reason(has_x11: bool, has_wayland: bool) -> Option<String> {
+    match (has_x11, has_wayland) {
+        (true, _) => None,
+        (false, true) => Some(
+            "Global input hooks are not available in a Wayland session without XWayland"
+                .to_string(),
+        ),
+        (false, false) => Some("No display server is available for input hooks".to_string()),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn unavailable_reason() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degraded_status_from_reason() {
+        assert_eq!(DegradedStatus::from_reason(None), DegradedStatus::default());
+
+        let status = DegradedStatus::from_reason(Some("missing".to_string()));
+        assert!(status.degraded);
+        assert_eq!(status.reason.as_deref(), Some("missing"));
+    }
+
+    #[test]
+    fn test_linux_unavailable_reason() {
+        assert!(linux_unavailable_reason(true, false).is_none());
+        assert!(linux_unavailable_reason(true, true).is_none());
+        assert!(linux_unavailable_reason(false, true)
+            .unwrap()
+            .contains("Wayland"));
+        assert!(linux_unavailable_reason(false, false).is_some());
+    }
+}
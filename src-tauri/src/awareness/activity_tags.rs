@@ -0,0 +1,358 @@
+//! Activity tagging
+//!
+//! Extracts structured tags (projects, file types, URLs) from window titles and
+//! activity targets with configurable regex patterns, so time spent on a project
+//! can be found regardless of the application it happened in.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::activity_tracker::UserActivity;
+
+/// What a tag describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagKind {
+    Project,
+    FileType,
+    Url,
+    Custom,
+}
+
+impl TagKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TagKind::Project => "project",
+            TagKind::FileType => "file_type",
+            TagKind::Url => "url",
+            TagKind::Custom => "custom",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind.trim().to_lowercase().as_str() {
+            "project" => Some(TagKind::Project),
+            "file_type" | "filetype" | "file" => Some(TagKind::FileType),
+            "url" | "domain" => Some(TagKind::Url),
+            "custom" => Some(TagKind::Custom),
+            _ => None,
+        }
+    }
+}
+
+/// Structured tag attached to an activity
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ActivityTag {
+    pub kind: TagKind,
+    pub value: String,
+}
+
+impl ActivityTag {
+    /// Whether this tag matches a query of the form `kind:value` or a bare `value`
+    ///
+    /// Values compare case-insensitively.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim();
+        match query
+            .split_once(':')
+            .and_then(|(kind, value)| TagKind::parse(kind).map(|kind| (kind, value.trim())))
+        {
+            Some((kind, value)) => kind == self.kind && self.value.eq_ignore_ascii_case(value),
+            None => self.value.eq_ignore_ascii_case(query),
+        }
+    }
+}
+
+impl std::fmt::Display for ActivityTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.kind.as_str(), self.value)
+    }
+}
+
+/// Pattern that turns matching text into a tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagPattern {
+    /// Human-readable name shown in settings
+    pub name: String,
+    pub kind: TagKind,
+    /// Regex; the first capture group (or the whole match) becomes the tag value
+    pub pattern: String,
+    /// Fixed tag value to use instead of the matched text
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Only apply to these applications (case-insensitive substring); empty applies to all
+    #[serde(default)]
+    pub applications: Vec<String>,
+}
+
+impl TagPattern {
+    fn new(name: &str, kind: TagKind, pattern: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            pattern: pattern.to_string(),
+            value: None,
+            applications: Vec::new(),
+        }
+    }
+
+    fn for_applications(mut self, applications: &[&str]) -> Self {
+        self.applications = applications.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    fn applies_to(&self, application: Option<&str>) -> bool {
+        if self.applications.is_empty() {
+            return true;
+        }
+        let Some(application) = application.map(str::to_lowercase) else {
+            return false;
+        };
+        self.applications
+            .iter()
+            .any(|app| application.contains(&app.to_lowercase()))
+    }
+}
+
+/// Built-in patterns for editors, terminals, code hosts, file names and URLs
+pub fn default_tag_patterns() -> Vec<TagPattern> {
+    vec![
+        // "main.rs - my-project - Visual Studio Code"; segments are split on " - "
+        TagPattern::new(
+            "VS Code workspace",
+            TagKind::Project,
+            r"(?:^|\s[-—–]\s)(?:● )?([^\s](?:[^ ]| [^ \-—–]| [-—–][^ ])*?)(?: \[[^\]]*\])? [-—–] (?:Visual Studio Code|VSCodium|Cursor|Windsurf)\s*$",
+        ),
+        // "my-project [~/code/my-project] – main.rs"
+        TagPattern::new(
+            "JetBrains project",
+            TagKind::Project,
+            r"^([\w.-]+)(?: \[[^\]]+\])? – ",
+        )
+        .for_applications(&[
+            "idea",
+            "intellij",
+            "pycharm",
+            "webstorm",
+            "goland",
+            "clion",
+            "rustrover",
+            "rider",
+            "phpstorm",
+            "rubymine",
+            "datagrip",
+            "android studio",
+        ]),
+        // Terminal titles and paths: "~/code/my-project", "C:\Users\me\repos\my-project"
+        TagPattern::new(
+            "Project directory",
+            TagKind::Project,
+            r"(?i)(?:~|/home/[^/\s]+|/Users/[^/\s]+|[a-z]:\\Users\\[^\\\s]+)[/\\](?:code|src|projects|repos|dev|workspace|git)[/\\]([\w.-]+)",
+        ),
+        // "GitHub - owner/repo: description", "github.com/owner/repo"
+        TagPattern::new(
+            "Code host repository",
+            TagKind::Project,
+            r"(?i)(?:github\.com/|gitlab\.com/|bitbucket\.org/|GitHub - |GitLab - )[\w.-]+/([\w.-]+)",
+        ),
+        TagPattern::new(
+            "File extension",
+            TagKind::FileType,
+            r"(?i)\b[\w-]+\.(rs|tsx?|jsx?|py|go|java|kt|swift|c|h|cpp|hpp|cc|cs|rb|php|lua|sh|ps1|sql|md|txt|json|ya?ml|toml|html?|css|scss|vue|svelte|ipynb|pdf|docx?|xlsx?|pptx?|csv)\b",
+        ),
+        TagPattern::new("URL host", TagKind::Url, r"(?i)https?://([^/\s:?#]+)"),
+        TagPattern::new(
+            "Domain name",
+            TagKind::Url,
+            r"(?i)\b((?:[a-z0-9-]+\.)+(?:com|org|net|io|dev|ai|app|edu|gov|co))\b",
+        ),
+    ]
+}
+
+struct CompiledPattern {
+    pattern: TagPattern,
+    regex: Regex,
+}
+
+/// Applies tag patterns to activity text
+pub struct ActivityTagger {
+    patterns: Vec<CompiledPattern>,
+}
+
+impl ActivityTagger {
+    /// Compile `patterns`, failing on the first invalid regex
+    pub fn new(patterns: Vec<TagPattern>) -> Result<Self, String> {
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| {
+                Regex::new(&pattern.pattern)
+                    .map(|regex| CompiledPattern {
+                        pattern: pattern.clone(),
+                        regex,
+                    })
+                    .map_err(|e| format!("Invalid tag pattern '{}': {}", pattern.name, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// The configured patterns
+    pub fn patterns(&self) -> Vec<TagPattern> {
+        self.patterns.iter().map(|p| p.pattern.clone()).collect()
+    }
+
+    /// Extract tags from one piece of text, e.g. a window title
+    pub fn extract(&self, text: &str, application: Option<&str>) -> Vec<ActivityTag> {
+        let mut tags = Vec::new();
+        if text.trim().is_empty() {
+            return tags;
+        }
+
+        for compiled in &self.patterns {
+            if !compiled.pattern.applies_to(application) {
+                continue;
+            }
+            for captures in compiled.regex.captures_iter(text) {
+                let matched = captures.get(1).or_else(|| captures.get(0));
+                let raw = match (&compiled.pattern.value, matched) {
+                    (Some(value), _) => value.as_str(),
+                    (None, Some(matched)) => matched.as_str(),
+                    (None, None) => continue,
+                };
+                if let Some(value) = normalize_value(compiled.pattern.kind, raw) {
+                    push_unique(
+                        &mut tags,
+                        ActivityTag {
+                            kind: compiled.pattern.kind,
+                            value,
+                        },
+                    );
+                }
+            }
+        }
+
+        tags
+    }
+
+    /// Tags for an activity from its window title (`metadata["window_title"]`),
+    /// description and target
+    pub fn tag_activity(&self, activity: &UserActivity) -> Vec<ActivityTag> {
+        let application = activity.application.as_deref();
+        let texts = [
+            activity.metadata.get("window_title").map(String::as_str),
+            Some(activity.description.as_str()),
+            activity.target.as_deref(),
+        ];
+
+        let mut tags = activity.tags.clone();
+        for text in texts.into_iter().flatten() {
+            for tag in self.extract(text, application) {
+                push_unique(&mut tags, tag);
+            }
+        }
+        tags
+    }
+}
+
+impl Default for ActivityTagger {
+    fn default() -> Self {
+        Self::new(default_tag_patterns()).expect("default tag patterns are valid")
+    }
+}
+
+fn normalize_value(kind: TagKind, raw: &str) -> Option<String> {
+    let value = raw.trim();
+    let value = match kind {
+        TagKind::Project => value.trim_end_matches(".git").to_string(),
+        TagKind::FileType => value.to_lowercase(),
+        TagKind::Url => {
+            let host = value.to_lowercase();
+            host.strip_prefix("www.")
+                .map(str::to_string)
+                .unwrap_or(host)
+        }
+        TagKind::Custom => value.to_string(),
+    };
+    (!value.is_empty()).then_some(value)
+}
+
+/// Add a tag unless one of the same kind and value (ignoring case) is present
+fn push_unique(tags: &mut Vec<ActivityTag>, tag: ActivityTag) {
+    if !tags
+        .iter()
+        .any(|t| t.kind == tag.kind && t.value.eq_ignore_ascii_case(&tag.value))
+    {
+        tags.push(tag);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(tags: &[ActivityTag], kind: TagKind) -> Vec<&str> {
+        tags.iter()
+            .filter(|t| t.kind == kind)
+            .map(|t| t.value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_extracts_projects_from_editor_titles() {
+        let tagger = ActivityTagger::default();
+
+        let tags = tagger.extract("● main.rs - my-project - Visual Studio Code", Some("Code"));
+        assert_eq!(values(&tags, TagKind::Project), vec!["my-project"]);
+        assert_eq!(values(&tags, TagKind::FileType), vec!["rs"]);
+
+        let tags = tagger.extract("cognia - Cursor", None);
+        assert_eq!(values(&tags, TagKind::Project), vec!["cognia"]);
+
+        // JetBrains patterns only apply to JetBrains IDEs
+        let title = "cognia [~/code/cognia] – lib.rs";
+        let tags = tagger.extract(title, Some("RustRover"));
+        assert_eq!(values(&tags, TagKind::Project), vec!["cognia"]);
+        assert!(values(
+            &tagger.extract("Inbox – Mail", Some("Mail")),
+            TagKind::Project
+        )
+        .is_empty());
+
+        let tags = tagger.extract("user@host: ~/code/cognia/src-tauri", Some("Terminal"));
+        assert_eq!(values(&tags, TagKind::Project), vec!["cognia"]);
+    }
+
+    #[test]
+    fn test_extracts_urls_and_repositories() {
+        let tagger = ActivityTagger::default();
+        let tags = tagger.extract(
+            "Pull request · https://www.github.com/ElementsAI-Dev/Cognia.git/pull/1",
+            Some("Firefox"),
+        );
+        assert_eq!(values(&tags, TagKind::Url), vec!["github.com"]);
+        assert_eq!(values(&tags, TagKind::Project), vec!["Cognia"]);
+
+        let tags = tagger.extract("This Week in Rust - blog.rust-lang.org", None);
+        assert_eq!(values(&tags, TagKind::Url), vec!["blog.rust-lang.org"]);
+    }
+
+    #[test]
+    fn test_custom_patterns_and_matching() {
+        let mut patterns = vec![TagPattern {
+            name: "Ticket".to_string(),
+            kind: TagKind::Custom,
+            pattern: r"\b(COG-\d+)\b".to_string(),
+            value: None,
+            applications: Vec::new(),
+        }];
+        let tagger = ActivityTagger::new(patterns.clone()).unwrap();
+        let tags = tagger.extract("COG-42 Fix login - Jira", None);
+        assert_eq!(tags[0].to_string(), "custom:COG-42");
+        assert!(tags[0].matches("custom:cog-42"));
+        assert!(tags[0].matches("COG-42"));
+        assert!(!tags[0].matches("project:COG-42"));
+
+        patterns[0].pattern = "(".to_string();
+        assert!(ActivityTagger::new(patterns).is_err());
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
+use super::activity_tags::ActivityTag;
+
 /// Maximum number of activities to keep in history
 const MAX_HISTORY_SIZE: usize = 1000;
 
@@ -25,6 +27,9 @@ pub struct UserActivity {
     pub duration_ms: Option<u64>,
     /// Additional metadata
     pub metadata: std::collections::HashMap<String, String>,
+    /// Structured tags extracted from the window title, description and target
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<ActivityTag>,
 }
 
 /// Activity type classification
@@ -147,6 +152,15 @@ impl ActivityTracker {
             .collect()
     }
 
+    /// Get activities carrying a tag (`kind:value` or a bare value)
+    pub fn get_by_tag(&self, query: &str) -> Vec<UserActivity> {
+        self.history
+            .iter()
+            .filter(|a| a.tags.iter().any(|tag| tag.matches(query)))
+            .cloned()
+            .collect()
+    }
+
     /// Get activity statistics
     pub fn get_stats(&self) -> ActivityStats {
         let total = self.history.len();
@@ -195,6 +209,34 @@ impl ActivityTracker {
         }
     }
 
+    /// Remove activities older than `cutoff_ms` (all activities when `None`)
+    ///
+    /// Returns the number removed.
+    pub fn purge(&mut self, cutoff_ms: Option<i64>) -> usize {
+        let Some(cutoff) = cutoff_ms else {
+            let count = self.history.len();
+            self.clear();
+            return count;
+        };
+
+        let before = self.history.len();
+        let counts = &mut self.activity_counts;
+        self.history.retain(|activity| {
+            if activity.timestamp >= cutoff {
+                return true;
+            }
+            let type_key = format!("{:?}", activity.activity_type);
+            if let Some(count) = counts.get_mut(&type_key) {
+                *count = count.saturating_sub(1);
+            }
+            false
+        });
+        self.activity_counts.retain(|_, count| *count > 0);
+        let removed = before - self.history.len();
+        log::info!("Purged {} activities from history", removed);
+        removed
+    }
+
     /// Clear all history
     pub fn clear(&mut self) {
         let count = self.history.len();
@@ -277,6 +319,7 @@ impl UserActivity {
                 }
                 m
             },
+            tags: Vec::new(),
         }
     }
 
@@ -297,6 +340,7 @@ impl UserActivity {
                 m.insert("height".to_string(), height.to_string());
                 m
             },
+            tags: Vec::new(),
         }
     }
 
@@ -311,6 +355,7 @@ impl UserActivity {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         }
     }
 
@@ -338,6 +383,7 @@ impl UserActivity {
                 m.insert("query_length".to_string(), query.len().to_string());
                 m
             },
+            tags: Vec::new(),
         }
     }
 }
@@ -421,6 +467,27 @@ mod tests {
         assert_eq!(tracker.get_recent(10).len(), 0);
     }
 
+    #[test]
+    fn test_purge_before_cutoff() {
+        let mut tracker = ActivityTracker::new();
+        let mut old = UserActivity::text_selection("old", None);
+        old.timestamp = 1_000;
+        tracker.record(old);
+        tracker.record(UserActivity::ai_query("new", "explain"));
+
+        assert_eq!(tracker.purge(Some(10_000)), 1);
+        let recent = tracker.get_recent(10);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].activity_type, ActivityType::AiQuery);
+        assert!(!tracker
+            .get_stats()
+            .activity_counts
+            .contains_key("TextSelection"));
+
+        assert_eq!(tracker.purge(None), 1);
+        assert!(tracker.get_recent(10).is_empty());
+    }
+
     #[test]
     fn test_get_stats() {
         let mut tracker = ActivityTracker::new();
@@ -589,6 +656,7 @@ mod tests {
                 m.insert("key".to_string(), "value".to_string());
                 m
             },
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&activity);
@@ -813,6 +881,7 @@ mod tests {
             timestamp: 1000,
             duration_ms: Some(100),
             metadata,
+            tags: Vec::new(),
         };
 
         let mut tracker = ActivityTracker::new();
@@ -0,0 +1,362 @@
+//! Focus session export
+//!
+//! Turns completed focus sessions into calendar events, as iCalendar (.ics)
+//! or JSON, so users can review their day in a calendar app.
+
+use super::FocusSession;
+use crate::context::{AppContext, AppType};
+use serde::{Deserialize, Serialize};
+
+/// Longest range that can be exported at once (31 days)
+const MAX_EXPORT_RANGE_MS: i64 = 31 * 86_400_000;
+
+/// Window title fragments that mark a session as private
+const SENSITIVE_TITLE_MARKERS: &[&str] = &[
+    "incognito",
+    "inprivate",
+    "private browsing",
+    "password",
+    "1password",
+    "bitwarden",
+    "keepass",
+];
+
+/// Title used for events from redacted sessions
+const REDACTED_TITLE: &str = "Private session";
+
+/// Export output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FocusExportFormat {
+    Ics,
+    Json,
+}
+
+/// Privacy options for a focus export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusExportOptions {
+    /// Include window titles in event descriptions
+    pub include_window_titles: bool,
+    /// Extra case-insensitive keywords; sessions whose app or window title
+    /// contains one are exported without identifying details
+    pub redact_keywords: Vec<String>,
+}
+
+/// A focus session as a calendar event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusCalendarEvent {
+    /// Stable identifier, so re-imports update instead of duplicating
+    pub uid: String,
+    /// Event title (application and category)
+    pub title: String,
+    /// Start timestamp in milliseconds
+    pub start_time: i64,
+    /// End timestamp in milliseconds
+    pub end_time: i64,
+    /// Application category
+    pub category: AppType,
+    /// Window title, when included and not redacted
+    pub description: Option<String>,
+    /// Whether identifying details were removed
+    pub redacted: bool,
+}
+
+/// Export completed sessions overlapping `[start_ms, end_ms)`
+pub fn export_focus_sessions(
+    sessions: &[FocusSession],
+    start_ms: i64,
+    end_ms: i64,
+    format: FocusExportFormat,
+    options: &FocusExportOptions,
+    generated_at: i64,
+) -> Result<String, String> {
+    if end_ms <= start_ms {
+        return Err("Invalid range: end must be after start".to_string());
+    }
+    if end_ms - start_ms > MAX_EXPORT_RANGE_MS {
+        return Err(format!(
+            "Invalid range: at most {} days can be exported at once",
+            MAX_EXPORT_RANGE_MS / 86_400_000
+        ));
+    }
+
+    let events = build_events(sessions, start_ms, end_ms, options);
+    match format {
+        FocusExportFormat::Json => serde_json::to_string_pretty(&events)
+            .map_err(|e| format!("Failed to serialize focus sessions: {}", e)),
+        FocusExportFormat::Ics => Ok(render_ics(&events, generated_at)),
+    }
+}
+
+fn build_events(
+    sessions: &[FocusSession],
+    start_ms: i64,
+    end_ms: i64,
+    options: &FocusExportOptions,
+) -> Vec<FocusCalendarEvent> {
+    let keywords: Vec<String> = options
+        .redact_keywords
+        .iter()
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect();
+
+    let mut events: Vec<FocusCalendarEvent> = sessions
+        .iter()
+        .filter_map(|session| {
+            let session_end = session.end_time?;
+            if session_end <= start_ms || session.start_time >= end_ms {
+                return None;
+            }
+
+            let category = AppContext::classify(&session.process_name, &session.window_title);
+            let redacted = is_sensitive(session, &keywords);
+            let (title, description) = if redacted {
+                (REDACTED_TITLE.to_string(), None)
+            } else {
+                let title = format!("{} ({:?})", session.app_name, category);
+                let description = (options.include_window_titles
+                    && !session.window_title.trim().is_empty())
+                .then(|| session.window_title.trim().to_string());
+                (title, description)
+            };
+
+            Some(FocusCalendarEvent {
+                uid: event_uid(session),
+                title,
+                start_time: session.start_time,
+                end_time: session_end,
+                category,
+                description,
+                redacted,
+            })
+        })
+        .collect();
+    events.sort_by_key(|e| e.start_time);
+    events
+}
+
+fn is_sensitive(session: &FocusSession, keywords: &[String]) -> bool {
+    let app = session.app_name.to_lowercase();
+    let title = session.window_title.to_lowercase();
+    SENSITIVE_TITLE_MARKERS
+        .iter()
+        .any(|marker| title.contains(marker))
+        || keywords
+            .iter()
+            .any(|k| app.contains(k.as_str()) || title.contains(k.as_str()))
+}
+
+fn event_uid(session: &FocusSession) -> String {
+    let process: String = session
+        .process_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("focus-{}-{}@cognia", session.start_time, process)
+}
+
+/// Render events as an iCalendar document (RFC 5545)
+fn render_ics(events: &[FocusCalendarEvent], generated_at: i64) -> String {
+    let stamp = ics_timestamp(generated_at);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Cognia//Focus Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", ics_timestamp(event.start_time)));
+        lines.push(format!("DTEND:{}", ics_timestamp(event.end_time)));
+        lines.push(format!("SUMMARY:{}", escape_ics_text(&event.title)));
+        if !event.redacted {
+            lines.push(format!("CATEGORIES:{:?}", event.category));
+        }
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_ics_text(description)));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        ics.push_str(&fold_ics_line(&line));
+        ics.push_str("\r\n");
+    }
+    ics
+}
+
+/// UTC timestamp in iCalendar basic format (e.g. 20260101T090000Z)
+fn ics_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+fn escape_ics_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c if c.is_control() => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold content lines longer than 75 octets, never splitting a character
+fn fold_ics_line(line: &str) -> String {
+    const LIMIT: usize = 75;
+    let mut folded = String::with_capacity(line.len() + line.len() / LIMIT * 3);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LIMIT {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_START: i64 = 1_767_225_600_000; // 2026-01-01T00:00:00Z
+
+    fn session(app: &str, process: &str, title: &str, start: i64, minutes: i64) -> FocusSession {
+        FocusSession {
+            app_name: app.to_string(),
+            process_name: process.to_string(),
+            window_title: title.to_string(),
+            start_time: start,
+            end_time: Some(start + minutes * 60_000),
+            duration_ms: (minutes * 60_000) as u64,
+            is_active: false,
+        }
+    }
+
+    #[test]
+    fn test_export_ics_events() {
+        let sessions = vec![
+            session(
+                "Code",
+                "code.exe",
+                "main.rs - cognia",
+                DAY_START + 3_600_000,
+                30,
+            ),
+            session("Slack", "slack.exe", "general", DAY_START - 86_400_000, 10),
+        ];
+        let options = FocusExportOptions {
+            include_window_titles: true,
+            ..Default::default()
+        };
+
+        let ics = export_focus_sessions(
+            &sessions,
+            DAY_START,
+            DAY_START + 86_400_000,
+            FocusExportFormat::Ics,
+            &options,
+            DAY_START,
+        )
+        .unwrap();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("DTSTART:20260101T010000Z\r\n"));
+        assert!(ics.contains("DTEND:20260101T013000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Code (CodeEditor)\r\n"));
+        assert!(ics.contains("DESCRIPTION:main.rs - cognia\r\n"));
+        assert!(ics.contains("UID:focus-1767229200000-code-exe@cognia\r\n"));
+    }
+
+    #[test]
+    fn test_export_redacts_sensitive_sessions() {
+        let sessions = vec![
+            session("Chrome", "chrome", "New Incognito Tab", DAY_START, 5),
+            session("Bank", "bank", "Statement", DAY_START + 600_000, 5),
+            session(
+                "Code",
+                "code",
+                "secret-project.rs",
+                DAY_START + 1_200_000,
+                5,
+            ),
+        ];
+        let options = FocusExportOptions {
+            include_window_titles: false,
+            redact_keywords: vec!["BANK".to_string()],
+        };
+
+        let json = export_focus_sessions(
+            &sessions,
+            DAY_START,
+            DAY_START + 86_400_000,
+            FocusExportFormat::Json,
+            &options,
+            DAY_START,
+        )
+        .unwrap();
+        let events: Vec<FocusCalendarEvent> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert!(events[0].redacted && events[0].title == REDACTED_TITLE);
+        assert!(events[1].redacted && events[1].title == REDACTED_TITLE);
+        assert!(!events[2].redacted);
+        // Window titles are left out unless requested
+        assert!(events.iter().all(|e| e.description.is_none()));
+        assert!(!json.contains("secret-project"));
+    }
+
+    #[test]
+    fn test_export_rejects_invalid_ranges() {
+        let options = FocusExportOptions::default();
+        assert!(export_focus_sessions(
+            &[],
+            DAY_START,
+            DAY_START,
+            FocusExportFormat::Json,
+            &options,
+            0
+        )
+        .is_err());
+        assert!(export_focus_sessions(
+            &[],
+            DAY_START,
+            DAY_START + MAX_EXPORT_RANGE_MS + 1,
+            FocusExportFormat::Ics,
+            &options,
+            0
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_ics_text_escaping_and_folding() {
+        assert_eq!(escape_ics_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+
+        let long = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold_ics_line(&long);
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+}
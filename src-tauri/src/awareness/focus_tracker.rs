@@ -2,6 +2,8 @@
 //!
 //! Tracks application focus changes and usage statistics.
 
+use crate::context::{AppContext, AppType};
+use chrono::Timelike;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -58,6 +60,44 @@ pub struct DailyUsageSummary {
     pub switch_count: usize,
 }
 
+/// Time spent in one application category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryUsage {
+    /// Application category
+    pub category: AppType,
+    /// Total time in milliseconds
+    pub total_ms: u64,
+    /// Share of the day's active time (0-100)
+    pub percentage: f64,
+}
+
+/// End-of-day report assembled from focus sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReport {
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+    /// Total active time in milliseconds
+    pub total_active_ms: u64,
+    /// Number of focus sessions
+    pub session_count: usize,
+    /// Average session duration in milliseconds
+    pub avg_session_ms: u64,
+    /// Time per application category (sorted by time)
+    pub categories: Vec<CategoryUsage>,
+    /// Most used applications (sorted by time)
+    pub top_apps: Vec<(String, u64)>,
+    /// Longest uninterrupted focus session
+    pub longest_session: Option<FocusSession>,
+    /// Number of app switches
+    pub context_switches: usize,
+    /// Hour of day (UTC) with the most active time, by session start
+    pub peak_hour: Option<u32>,
+    /// Notable patterns in plain language
+    pub patterns: Vec<String>,
+    /// Markdown rendering of the report (when requested)
+    pub markdown: Option<String>,
+}
+
 /// Focus tracker
 pub struct FocusTracker {
     /// Current focus session
@@ -320,6 +360,91 @@ impl FocusTracker {
         }
     }
 
+    /// Generate an end-of-day report for a date (YYYY-MM-DD)
+    pub fn generate_daily_report(
+        &self,
+        date: &str,
+        include_markdown: bool,
+    ) -> Result<DailyReport, String> {
+        let day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+        let date_start = day
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis();
+        let date_end = date_start + 86400000;
+
+        let summary = self.get_daily_summary(date);
+        let day_sessions: Vec<FocusSession> = self
+            .sessions
+            .read()
+            .iter()
+            .filter(|s| s.start_time >= date_start && s.start_time < date_end)
+            .cloned()
+            .collect();
+
+        // Category breakdown
+        let mut categories: Vec<CategoryUsage> = Vec::new();
+        for session in &day_sessions {
+            let category = AppContext::classify(&session.process_name, &session.window_title);
+            match categories.iter_mut().find(|c| c.category == category) {
+                Some(usage) => usage.total_ms += session.duration_ms,
+                None => categories.push(CategoryUsage {
+                    category,
+                    total_ms: session.duration_ms,
+                    percentage: 0.0,
+                }),
+            }
+        }
+        for usage in &mut categories {
+            usage.percentage = if summary.total_active_ms > 0 {
+                usage.total_ms as f64 / summary.total_active_ms as f64 * 100.0
+            } else {
+                0.0
+            };
+        }
+        categories.sort_by(|a, b| b.total_ms.cmp(&a.total_ms));
+
+        // Active time per hour of day
+        let mut hourly = [0u64; 24];
+        for session in &day_sessions {
+            if let Some(start) = chrono::DateTime::from_timestamp_millis(session.start_time) {
+                hourly[start.hour() as usize] += session.duration_ms;
+            }
+        }
+        let peak_hour = hourly
+            .iter()
+            .enumerate()
+            .filter(|(_, ms)| **ms > 0)
+            .max_by_key(|(_, ms)| **ms)
+            .map(|(hour, _)| hour as u32);
+
+        let mut report = DailyReport {
+            date: summary.date,
+            total_active_ms: summary.total_active_ms,
+            session_count: day_sessions.len(),
+            avg_session_ms: if day_sessions.is_empty() {
+                0
+            } else {
+                summary.total_active_ms / day_sessions.len() as u64
+            },
+            categories,
+            top_apps: summary.top_apps,
+            longest_session: day_sessions.into_iter().max_by_key(|s| s.duration_ms),
+            context_switches: summary.switch_count,
+            peak_hour,
+            patterns: Vec::new(),
+            markdown: None,
+        };
+        report.patterns = detect_patterns(&report);
+        if include_markdown {
+            report.markdown = Some(render_report_markdown(&report));
+        }
+
+        Ok(report)
+    }
+
     /// Get today's summary
     pub fn get_today_summary(&self) -> DailyUsageSummary {
         let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
@@ -334,12 +459,151 @@ impl FocusTracker {
         log::info!("Cleared {} focus sessions", count);
     }
 
+    /// Remove sessions started before `cutoff_ms` (all sessions when `None`),
+    /// including the current one
+    ///
+    /// Returns the number removed.
+    pub fn purge(&self, cutoff_ms: Option<i64>) -> usize {
+        let is_old = |session: &FocusSession| match cutoff_ms {
+            Some(cutoff) => session.start_time < cutoff,
+            None => true,
+        };
+
+        let mut removed = 0;
+        {
+            let mut current = self.current_session.write();
+            if current.as_ref().is_some_and(is_old) {
+                *current = None;
+                removed += 1;
+            }
+        }
+
+        let mut sessions = self.sessions.write();
+        let before = sessions.len();
+        sessions.retain(|session| !is_old(session));
+        removed += before - sessions.len();
+        log::info!("Purged {} focus sessions", removed);
+        removed
+    }
+
     /// Get session count
     pub fn session_count(&self) -> usize {
         self.sessions.read().len()
     }
 }
 
+/// Describe notable patterns in a daily report
+fn detect_patterns(report: &DailyReport) -> Vec<String> {
+    let mut patterns = Vec::new();
+    if report.total_active_ms == 0 {
+        return patterns;
+    }
+
+    if let Some(longest) = &report.longest_session {
+        if longest.duration_ms >= 45 * 60 * 1000 {
+            patterns.push(format!(
+                "Deep work: {} uninterrupted in {}",
+                format_duration(longest.duration_ms),
+                longest.app_name
+            ));
+        }
+    }
+
+    let active_hours = report.total_active_ms as f64 / 3_600_000.0;
+    if active_hours >= 0.5 {
+        let switches_per_hour = report.context_switches as f64 / active_hours;
+        if switches_per_hour > 30.0 {
+            patterns.push(format!(
+                "Frequent context switching: {:.0} switches per active hour",
+                switches_per_hour
+            ));
+        }
+    }
+
+    if report.session_count >= 10 && report.avg_session_ms < 60_000 {
+        patterns.push("Fragmented attention: average focus session under a minute".to_string());
+    }
+
+    if let Some(top) = report.categories.first() {
+        if top.percentage >= 50.0 && top.category != AppType::Unknown {
+            patterns.push(format!(
+                "{:?} apps took {:.0}% of active time",
+                top.category, top.percentage
+            ));
+        }
+    }
+
+    if let Some(hour) = report.peak_hour {
+        patterns.push(format!("Most active around {:02}:00 UTC", hour));
+    }
+
+    patterns
+}
+
+/// Render a daily report as Markdown
+fn render_report_markdown(report: &DailyReport) -> String {
+    let mut md = format!("# Daily Report: {}\n\n", report.date);
+    md.push_str(&format!(
+        "**Active time:** {} across {} sessions ({} context switches)\n",
+        format_duration(report.total_active_ms),
+        report.session_count,
+        report.context_switches
+    ));
+
+    if !report.categories.is_empty() {
+        md.push_str("\n## Categories\n\n");
+        for usage in &report.categories {
+            md.push_str(&format!(
+                "- {:?}: {} ({:.0}%)\n",
+                usage.category,
+                format_duration(usage.total_ms),
+                usage.percentage
+            ));
+        }
+    }
+
+    if !report.top_apps.is_empty() {
+        md.push_str("\n## Top Apps\n\n");
+        for (i, (app, ms)) in report.top_apps.iter().enumerate() {
+            md.push_str(&format!("{}. {}: {}\n", i + 1, app, format_duration(*ms)));
+        }
+    }
+
+    if let Some(longest) = &report.longest_session {
+        md.push_str("\n## Longest Focus Session\n\n");
+        md.push_str(&format!(
+            "{} for {}",
+            longest.app_name,
+            format_duration(longest.duration_ms)
+        ));
+        if !longest.window_title.is_empty() {
+            md.push_str(&format!(" ({})", longest.window_title));
+        }
+        md.push('\n');
+    }
+
+    if !report.patterns.is_empty() {
+        md.push_str("\n## Patterns\n\n");
+        for pattern in &report.patterns {
+            md.push_str(&format!("- {}\n", pattern));
+        }
+    }
+
+    md
+}
+
+/// Format milliseconds as "1h 05m", "12m", or "<1m"
+fn format_duration(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    if minutes == 0 {
+        "<1m".to_string()
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+}
+
 impl Default for FocusTracker {
     fn default() -> Self {
         Self::new()
@@ -505,6 +769,37 @@ mod tests {
         assert!(tracker.get_current_session().is_none());
     }
 
+    #[test]
+    fn test_purge_honors_cutoff() {
+        let tracker = FocusTracker::new();
+        let session = |start_time| FocusSession {
+            app_name: "App".to_string(),
+            process_name: "app.exe".to_string(),
+            window_title: "Secret".to_string(),
+            start_time,
+            end_time: Some(start_time + 10),
+            duration_ms: 10,
+            is_active: false,
+        };
+        tracker
+            .sessions
+            .write()
+            .extend([session(100), session(200), session(300)]);
+        *tracker.current_session.write() = Some(FocusSession {
+            end_time: None,
+            is_active: true,
+            ..session(400)
+        });
+
+        assert_eq!(tracker.purge(Some(250)), 2);
+        assert_eq!(tracker.session_count(), 1);
+        assert!(tracker.get_current_session().is_some());
+
+        assert_eq!(tracker.purge(None), 2);
+        assert_eq!(tracker.session_count(), 0);
+        assert!(tracker.get_current_session().is_none());
+    }
+
     #[test]
     fn test_get_all_sessions() {
         let tracker = FocusTracker::new();
@@ -890,4 +1185,64 @@ mod tests {
         assert!(tracker.get_current_session().is_none());
         assert_eq!(tracker.session_count(), 1);
     }
+
+    fn push_session(tracker: &FocusTracker, app: &str, process: &str, start: i64, minutes: u64) {
+        tracker.sessions.write().push_back(FocusSession {
+            app_name: app.to_string(),
+            process_name: process.to_string(),
+            window_title: String::new(),
+            start_time: start,
+            end_time: Some(start + (minutes * 60_000) as i64),
+            duration_ms: minutes * 60_000,
+            is_active: false,
+        });
+    }
+
+    #[test]
+    fn test_generate_daily_report() {
+        let tracker = FocusTracker::new();
+        // 2024-01-15 09:00 UTC
+        let nine = 1705309200000;
+        push_session(&tracker, "VS Code", "code.exe", nine, 90);
+        push_session(&tracker, "Chrome", "chrome.exe", nine + 90 * 60_000, 20);
+        push_session(&tracker, "VS Code", "code.exe", nine + 110 * 60_000, 10);
+        // Previous day, excluded
+        push_session(&tracker, "Slack", "slack.exe", nine - 86400000, 30);
+
+        let report = tracker.generate_daily_report("2024-01-15", true).unwrap();
+        assert_eq!(report.session_count, 3);
+        assert_eq!(report.total_active_ms, 120 * 60_000);
+        assert_eq!(report.context_switches, 2);
+        assert_eq!(report.top_apps[0], ("VS Code".to_string(), 100 * 60_000));
+        assert_eq!(
+            report.longest_session.as_ref().unwrap().duration_ms,
+            90 * 60_000
+        );
+        assert_eq!(report.peak_hour, Some(9));
+
+        assert_eq!(report.categories[0].category, AppType::CodeEditor);
+        assert_eq!(report.categories[1].category, AppType::Browser);
+        assert!((report.categories[0].percentage - 83.33).abs() < 0.1);
+
+        assert!(report
+            .patterns
+            .iter()
+            .any(|p| p.starts_with("Deep work: 1h 30m")));
+        let markdown = report.markdown.unwrap();
+        assert!(markdown.starts_with("# Daily Report: 2024-01-15"));
+        assert!(markdown.contains("**Active time:** 2h 00m across 3 sessions"));
+        assert!(markdown.contains("1. VS Code: 1h 40m"));
+    }
+
+    #[test]
+    fn test_generate_daily_report_empty_and_invalid_date() {
+        let tracker = FocusTracker::new();
+        let report = tracker.generate_daily_report("2024-01-15", false).unwrap();
+        assert_eq!(report.total_active_ms, 0);
+        assert!(report.longest_session.is_none());
+        assert!(report.patterns.is_empty());
+        assert!(report.markdown.is_none());
+
+        assert!(tracker.generate_daily_report("yesterday", false).is_err());
+    }
 }
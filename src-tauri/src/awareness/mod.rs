@@ -7,20 +7,30 @@
 //! - Smart suggestions
 //! - Focus tracking
 
+mod activity_tags;
 pub mod activity_tracker;
+mod focus_export;
 mod focus_tracker;
 mod smart_suggestions;
 mod system_monitor;
 
+pub use activity_tags::{default_tag_patterns, ActivityTag, ActivityTagger, TagKind, TagPattern};
 pub use activity_tracker::{ActivityTracker, ActivityType, UserActivity};
-pub use focus_tracker::{AppUsageStats, DailyUsageSummary, FocusSession, FocusTracker};
+pub use focus_export::{FocusCalendarEvent, FocusExportFormat, FocusExportOptions};
+pub use focus_tracker::{
+    AppUsageStats, DailyReport, DailyUsageSummary, FocusSession, FocusTracker,
+};
 pub use smart_suggestions::{SmartSuggestions, Suggestion};
 pub use system_monitor::{SystemMonitor, SystemState};
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// File in the app data directory holding the persisted awareness settings
+pub const AWARENESS_SETTINGS_FILE: &str = "awareness_settings.json";
+
 /// Complete awareness state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AwarenessState {
@@ -34,13 +44,74 @@ pub struct AwarenessState {
     pub timestamp: i64,
 }
 
+/// Everything recorded under one tag, across applications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagActivityReport {
+    /// The tag query (`kind:value` or a bare value)
+    pub tag: String,
+    /// Activities carrying the tag, most recent first
+    pub activities: Vec<UserActivity>,
+    /// Total duration of the matching activities that report one
+    pub activity_time_ms: u64,
+    /// Number of focus sessions whose window title carries the tag
+    pub focus_session_count: usize,
+    /// Time spent in those focus sessions
+    pub focus_time_ms: u64,
+    /// Applications the tag was seen in
+    pub applications: Vec<String>,
+}
+
+/// Awareness settings persisted across restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AwarenessSettings {
+    pub activity_tracking_enabled: bool,
+    /// Patterns used to tag activities from window titles
+    pub tag_patterns: Vec<TagPattern>,
+}
+
+impl Default for AwarenessSettings {
+    fn default() -> Self {
+        Self {
+            activity_tracking_enabled: true,
+            tag_patterns: default_tag_patterns(),
+        }
+    }
+}
+
+impl AwarenessSettings {
+    /// Load settings from `path`, falling back to defaults if missing or invalid
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Invalid awareness settings, using defaults: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings dir: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write awareness settings: {}", e))
+    }
+}
+
 /// Awareness manager
 pub struct AwarenessManager {
     system_monitor: SystemMonitor,
     activity_tracker: Arc<RwLock<ActivityTracker>>,
+    activity_tagger: RwLock<ActivityTagger>,
     smart_suggestions: SmartSuggestions,
     focus_tracker: Arc<FocusTracker>,
     is_running: Arc<std::sync::atomic::AtomicBool>,
+    /// Where settings changes are saved; `None` keeps them in memory only
+    settings_path: Option<PathBuf>,
 }
 
 impl AwarenessManager {
@@ -49,9 +120,42 @@ impl AwarenessManager {
         Self {
             system_monitor: SystemMonitor::new(),
             activity_tracker: Arc::new(RwLock::new(ActivityTracker::new())),
+            activity_tagger: RwLock::new(ActivityTagger::default()),
             smart_suggestions: SmartSuggestions::new(),
             focus_tracker: Arc::new(FocusTracker::new()),
             is_running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            settings_path: None,
+        }
+    }
+
+    /// Create a manager that restores its settings from `path` and saves changes there
+    pub fn with_settings_path(path: PathBuf) -> Self {
+        let settings = AwarenessSettings::load(&path);
+        let mut manager = Self::new();
+        manager
+            .activity_tracker
+            .write()
+            .set_enabled(settings.activity_tracking_enabled);
+        match ActivityTagger::new(settings.tag_patterns) {
+            Ok(tagger) => *manager.activity_tagger.write() = tagger,
+            Err(e) => log::warn!("Invalid saved tag patterns, using defaults: {}", e),
+        }
+        manager.settings_path = Some(path);
+        manager
+    }
+
+    /// Current settings, as they would be persisted
+    pub fn settings(&self) -> AwarenessSettings {
+        AwarenessSettings {
+            activity_tracking_enabled: self.is_activity_tracking_enabled(),
+            tag_patterns: self.get_tag_patterns(),
+        }
+    }
+
+    fn save_settings(&self, settings: &AwarenessSettings) -> Result<(), String> {
+        match &self.settings_path {
+            Some(path) => settings.save(path),
+            None => Ok(()),
         }
     }
 
@@ -77,9 +181,10 @@ impl AwarenessManager {
         self.system_monitor.get_state()
     }
 
-    /// Record an activity
-    pub fn record_activity(&self, activity: UserActivity) {
+    /// Record an activity, tagging it from its window title, description and target
+    pub fn record_activity(&self, mut activity: UserActivity) {
         log::debug!("Recording activity: {:?}", activity.activity_type);
+        activity.tags = self.activity_tagger.read().tag_activity(&activity);
         self.activity_tracker.write().record(activity);
     }
 
@@ -130,12 +235,25 @@ impl AwarenessManager {
         self.is_running.store(false, Ordering::SeqCst);
     }
 
+    /// Stop background monitoring and focus tracking before the app exits
+    ///
+    /// Activity and focus statistics are kept in memory only, so there is nothing to persist.
+    pub fn shutdown(&self) {
+        self.stop_monitoring();
+        self.stop_focus_tracking();
+    }
+
     /// Clear activity history
     pub fn clear_history(&self) {
         log::info!("Clearing activity history");
         self.activity_tracker.write().clear();
     }
 
+    /// Remove activities older than `cutoff_ms` (all activities when `None`)
+    pub fn purge_history(&self, cutoff_ms: Option<i64>) -> usize {
+        self.activity_tracker.write().purge(cutoff_ms)
+    }
+
     // ============== Focus Tracking Methods ==============
 
     /// Start focus tracking
@@ -191,12 +309,45 @@ impl AwarenessManager {
         self.focus_tracker.get_daily_summary(date)
     }
 
+    /// Generate an end-of-day report for a date (YYYY-MM-DD)
+    pub fn generate_daily_report(
+        &self,
+        date: &str,
+        include_markdown: bool,
+    ) -> Result<DailyReport, String> {
+        self.focus_tracker
+            .generate_daily_report(date, include_markdown)
+    }
+
+    /// Export completed focus sessions in a time range as calendar events
+    pub fn export_focus_sessions(
+        &self,
+        start_ms: i64,
+        end_ms: i64,
+        format: FocusExportFormat,
+        options: &FocusExportOptions,
+    ) -> Result<String, String> {
+        focus_export::export_focus_sessions(
+            &self.focus_tracker.get_all_sessions(),
+            start_ms,
+            end_ms,
+            format,
+            options,
+            chrono::Utc::now().timestamp_millis(),
+        )
+    }
+
     /// Clear focus history
     pub fn clear_focus_history(&self) {
         log::info!("Clearing focus history");
         self.focus_tracker.clear();
     }
 
+    /// Remove focus sessions started before `cutoff_ms` (all sessions when `None`)
+    pub fn purge_focus_history(&self, cutoff_ms: Option<i64>) -> usize {
+        self.focus_tracker.purge(cutoff_ms)
+    }
+
     /// Check if focus tracking is enabled
     pub fn is_focus_tracking(&self) -> bool {
         self.focus_tracker.is_tracking()
@@ -219,14 +370,68 @@ impl AwarenessManager {
         self.activity_tracker.read().get_by_application(app_name)
     }
 
+    /// Collect activities and focus time for a tag (`kind:value` or a bare value)
+    pub fn search_activities_by_tag(&self, query: &str) -> TagActivityReport {
+        let activities = self.activity_tracker.read().get_by_tag(query);
+        let mut applications: Vec<String> = activities
+            .iter()
+            .filter_map(|a| a.application.clone())
+            .collect();
+
+        let (mut focus_session_count, mut focus_time_ms) = (0, 0);
+        {
+            let tagger = self.activity_tagger.read();
+            for session in self.focus_tracker.get_all_sessions() {
+                let tags = tagger.extract(&session.window_title, Some(&session.app_name));
+                if tags.iter().any(|tag| tag.matches(query)) {
+                    focus_session_count += 1;
+                    focus_time_ms += session.duration_ms;
+                    applications.push(session.app_name);
+                }
+            }
+        }
+        applications.sort();
+        applications.dedup();
+
+        TagActivityReport {
+            tag: query.trim().to_string(),
+            activity_time_ms: activities.iter().filter_map(|a| a.duration_ms).sum(),
+            activities,
+            focus_session_count,
+            focus_time_ms,
+            applications,
+        }
+    }
+
+    /// Get the patterns used to tag activities
+    pub fn get_tag_patterns(&self) -> Vec<TagPattern> {
+        self.activity_tagger.read().patterns()
+    }
+
+    /// Replace the patterns used to tag new activities and persist them
+    pub fn set_tag_patterns(&self, patterns: Vec<TagPattern>) -> Result<(), String> {
+        let tagger = ActivityTagger::new(patterns.clone())?;
+        self.save_settings(&AwarenessSettings {
+            tag_patterns: patterns,
+            ..self.settings()
+        })?;
+        *self.activity_tagger.write() = tagger;
+        Ok(())
+    }
+
     /// Get activity statistics
     pub fn get_activity_stats(&self) -> activity_tracker::ActivityStats {
         self.activity_tracker.read().get_stats()
     }
 
-    /// Enable/disable activity tracking
-    pub fn set_activity_tracking_enabled(&self, enabled: bool) {
+    /// Enable/disable activity tracking and persist the choice
+    pub fn set_activity_tracking_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.save_settings(&AwarenessSettings {
+            activity_tracking_enabled: enabled,
+            ..self.settings()
+        })?;
         self.activity_tracker.write().set_enabled(enabled);
+        Ok(())
     }
 
     /// Check if activity tracking is enabled
@@ -317,6 +522,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         };
 
         manager.record_activity(activity);
@@ -325,6 +531,71 @@ mod tests {
         assert_eq!(recent.len(), 1);
     }
 
+    #[test]
+    fn test_search_activities_by_tag() {
+        let manager = AwarenessManager::new();
+        let activity = |application: &str, window_title: &str| UserActivity {
+            activity_type: ActivityType::TextSelection,
+            description: "Selected text".to_string(),
+            application: Some(application.to_string()),
+            target: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            duration_ms: Some(1000),
+            metadata: [("window_title".to_string(), window_title.to_string())]
+                .into_iter()
+                .collect(),
+            tags: Vec::new(),
+        };
+
+        manager.record_activity(activity("Code", "lib.rs - cognia - Visual Studio Code"));
+        manager.record_activity(activity("Terminal", "user@host: ~/code/cognia"));
+        manager.record_activity(activity("Terminal", "user@host: ~/code/other"));
+
+        manager.start_focus_tracking();
+        manager.record_focus_change("Firefox", "firefox", "github.com/ElementsAI-Dev/cognia");
+        manager.record_focus_change("Slack", "slack", "general");
+        manager.stop_focus_tracking();
+
+        let report = manager.search_activities_by_tag("project:Cognia");
+        assert_eq!(report.activities.len(), 2);
+        assert_eq!(report.activity_time_ms, 2000);
+        assert_eq!(report.focus_session_count, 1);
+        assert_eq!(report.applications, vec!["Code", "Firefox", "Terminal"]);
+
+        assert!(manager
+            .search_activities_by_tag("url:cognia")
+            .activities
+            .is_empty());
+        assert!(manager.set_tag_patterns(vec![]).is_ok());
+        assert!(manager.get_tag_patterns().is_empty());
+    }
+
+    #[test]
+    fn test_settings_persist_across_managers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(AWARENESS_SETTINGS_FILE);
+
+        let manager = AwarenessManager::with_settings_path(path.clone());
+        assert_eq!(manager.settings(), AwarenessSettings::default());
+
+        let patterns = vec![default_tag_patterns().remove(0)];
+        manager.set_tag_patterns(patterns.clone()).unwrap();
+        manager.set_activity_tracking_enabled(false).unwrap();
+        assert!(manager
+            .set_tag_patterns(vec![TagPattern {
+                pattern: "(".to_string(),
+                ..patterns[0].clone()
+            }])
+            .is_err());
+
+        let restored = AwarenessManager::with_settings_path(path.clone());
+        assert_eq!(restored.get_tag_patterns(), patterns);
+        assert!(!restored.is_activity_tracking_enabled());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(AwarenessSettings::load(&path), AwarenessSettings::default());
+    }
+
     #[test]
     fn test_get_suggestions() {
         let manager = AwarenessManager::new();
@@ -345,6 +616,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         };
 
         manager.record_activity(activity);
@@ -506,6 +778,7 @@ mod tests {
                 timestamp: chrono::Utc::now().timestamp_millis() + i as i64,
                 duration_ms: None,
                 metadata: std::collections::HashMap::new(),
+                tags: Vec::new(),
             };
             manager.record_activity(activity);
         }
@@ -529,6 +802,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         };
         manager.record_activity(activity);
 
@@ -655,6 +929,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         };
         manager.record_activity(activity);
 
@@ -691,6 +966,7 @@ mod tests {
                     m.insert("text_length".to_string(), "100".to_string());
                     m
                 },
+                tags: Vec::new(),
             };
             manager.record_activity(activity);
         }
@@ -729,6 +1005,7 @@ mod tests {
                 timestamp: chrono::Utc::now().timestamp_millis(),
                 duration_ms: None,
                 metadata: std::collections::HashMap::new(),
+                tags: Vec::new(),
             };
             manager.record_activity(activity);
         }
@@ -753,6 +1030,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         };
         manager.record_activity(activity);
 
@@ -809,6 +1087,7 @@ mod tests {
                 timestamp: chrono::Utc::now().timestamp_millis() + i as i64,
                 duration_ms: None,
                 metadata: std::collections::HashMap::new(),
+                tags: Vec::new(),
             };
             manager.record_activity(activity);
         }
@@ -842,6 +1121,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
         manager.record_activity(UserActivity {
             activity_type: ActivityType::Screenshot,
@@ -851,6 +1131,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
         manager.record_activity(UserActivity {
             activity_type: ActivityType::TextSelection,
@@ -860,6 +1141,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
 
         let selections = manager.get_activities_by_type(&ActivityType::TextSelection);
@@ -883,6 +1165,7 @@ mod tests {
                 timestamp: now + (i * 1000) as i64,
                 duration_ms: None,
                 metadata: std::collections::HashMap::new(),
+                tags: Vec::new(),
             });
         }
 
@@ -902,6 +1185,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
         manager.record_activity(UserActivity {
             activity_type: ActivityType::TextSelection,
@@ -911,6 +1195,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
 
         let vscode_activities = manager.get_activities_by_application("vscode");
@@ -930,6 +1215,7 @@ mod tests {
                 timestamp: chrono::Utc::now().timestamp_millis(),
                 duration_ms: None,
                 metadata: std::collections::HashMap::new(),
+                tags: Vec::new(),
             });
         }
 
@@ -943,10 +1229,10 @@ mod tests {
 
         assert!(manager.is_activity_tracking_enabled());
 
-        manager.set_activity_tracking_enabled(false);
+        manager.set_activity_tracking_enabled(false).unwrap();
         assert!(!manager.is_activity_tracking_enabled());
 
-        manager.set_activity_tracking_enabled(true);
+        manager.set_activity_tracking_enabled(true).unwrap();
         assert!(manager.is_activity_tracking_enabled());
     }
 
@@ -962,6 +1248,7 @@ mod tests {
             timestamp: chrono::Utc::now().timestamp_millis(),
             duration_ms: None,
             metadata: std::collections::HashMap::new(),
+            tags: Vec::new(),
         });
 
         let exported = manager.export_activity_history();
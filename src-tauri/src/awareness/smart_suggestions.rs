@@ -664,6 +664,7 @@ mod tests {
                 m.insert("text_length".to_string(), "24".to_string());
                 m
             },
+            tags: Vec::new(),
         };
 
         let suggestions = engine.get_suggestions(&system, &[activity]);
@@ -692,6 +693,7 @@ mod tests {
                 m.insert("text_length".to_string(), "1000".to_string());
                 m
             },
+            tags: Vec::new(),
         };
 
         let suggestions = engine.get_suggestions(&system, &[activity]);
@@ -972,6 +974,7 @@ mod tests {
                 m.insert("text_length".to_string(), "50".to_string());
                 m
             },
+            tags: Vec::new(),
         };
 
         let suggestions = engine.get_suggestions(&system, &[activity]);
@@ -1001,6 +1004,7 @@ mod tests {
                 );
                 m
             },
+            tags: Vec::new(),
         };
 
         let suggestions = engine.get_suggestions(&system, &[activity]);
@@ -3,13 +3,19 @@
 //! Provides paper search, download, and management functionality
 //! through various academic paper providers.
 
+pub mod normalize;
 pub mod providers;
+pub mod search_cache;
 pub mod storage;
 pub mod types;
 
 #[cfg(test)]
 mod tests;
 
+use crate::commands::storage::vector::{
+    chunk_pages, embed_and_upsert_impl, get_collection_impl, ChunkingConfig, EmbedAndUpsertPayload,
+    EmbedDocument, VectorStoreState,
+};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
 use lopdf::{Document, Object, ObjectId};
@@ -19,20 +25,33 @@ use providers::{
     openreview::OpenReviewProvider, semantic_scholar::SemanticScholarProvider,
     unpaywall::UnpaywallProvider, AcademicProvider,
 };
+use search_cache::SearchCache;
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::sync::Arc;
 use storage::PaperStorage;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use types::*;
 
 /// State for academic mode functionality
 pub struct AcademicState {
     pub providers: RwLock<Vec<Box<dyn AcademicProvider + Send + Sync>>>,
     pub storage: Arc<PaperStorage>,
+    pub search_cache: SearchCache,
+    /// In-flight PDF extractions that can be cancelled (operation_id -> token)
+    pub pdf_extractions: RwLock<HashMap<String, CancellationToken>>,
+    /// In-flight streamed knowledge map generations (operation_id -> token)
+    pub knowledge_map_generations: RwLock<HashMap<String, CancellationToken>>,
 }
 
 impl AcademicState {
     pub fn new(storage_path: std::path::PathBuf) -> Result<Self, String> {
+        let search_cache = SearchCache::with_settings_path(
+            storage_path.join(search_cache::SEARCH_CACHE_SETTINGS_FILE),
+        );
         let storage = PaperStorage::new(storage_path)
             .map_err(|e| format!("Failed to initialize paper storage: {}", e))?;
 
@@ -50,6 +69,9 @@ impl AcademicState {
         Ok(Self {
             providers: RwLock::new(providers),
             storage: Arc::new(storage),
+            search_cache,
+            pdf_extractions: RwLock::new(HashMap::new()),
+            knowledge_map_generations: RwLock::new(HashMap::new()),
         })
     }
 }
@@ -81,15 +103,44 @@ pub async fn academic_search(
         return Err("No enabled providers available".to_string());
     }
 
+    let provider_ids: Vec<String> = target_providers
+        .iter()
+        .map(|p| p.provider_id().to_string())
+        .collect();
+    let cache_key = SearchCache::cache_key(&query, &provider_ids, &options);
+    if let Some(cached) = state.search_cache.get(&cache_key).await {
+        log::debug!("Serving academic search from cache: '{}'", query);
+        return Ok(cached);
+    }
+
+    // Cached results stay available offline; fresh searches need the network
+    crate::http::ensure_online()?;
+
     let start_time = std::time::Instant::now();
     let mut all_papers = Vec::new();
     let mut provider_results = std::collections::HashMap::new();
     let mut degraded_providers = std::collections::HashMap::new();
 
-    for provider in target_providers {
-        let provider_id = provider.provider_id().to_string();
-        match provider.search(&query, &options).await {
-            Ok(result) => {
+    // Query providers in parallel, bounded by the global resource budget
+    let searches = target_providers.iter().map(|provider| {
+        let query = &query;
+        let options = &options;
+        async move {
+            let _permit = crate::resource_governor::global().acquire("academic").await;
+            (
+                provider.provider_id().to_string(),
+                provider.search(query, options).await,
+            )
+        }
+    });
+    let outcomes = futures::future::join_all(searches).await;
+
+    for (provider_id, outcome) in outcomes {
+        match outcome {
+            Ok(mut result) => {
+                for paper in &mut result.papers {
+                    normalize::normalize_paper(paper);
+                }
                 provider_results.insert(
                     provider_id,
                     ProviderSearchResult {
@@ -101,7 +152,7 @@ pub async fn academic_search(
                 all_papers.extend(result.papers);
             }
             Err(e) => {
-                log::warn!("Provider {} search failed: {}", provider.provider_id(), e);
+                log::warn!("Provider {} search failed: {}", provider_id, e);
                 let retriable = is_retriable_provider_error(&e);
                 degraded_providers.insert(
                     provider_id.clone(),
@@ -128,13 +179,36 @@ pub async fn academic_search(
     // Sort by relevance or specified field
     let papers = sort_papers(papers, &options.sort_by, &options.sort_order);
 
-    Ok(AggregatedSearchResult {
+    let result = AggregatedSearchResult {
         papers,
         total_results: provider_results.values().map(|r| r.count).sum(),
         provider_results,
         degraded_providers,
         search_time_ms: start_time.elapsed().as_millis() as u64,
-    })
+        cached: false,
+    };
+
+    // Don't pin partial results from failing providers for the whole TTL
+    if result.degraded_providers.is_empty() {
+        state.search_cache.insert(cache_key, result.clone()).await;
+    }
+
+    Ok(result)
+}
+
+/// Drop all cached search results, returning how many were removed
+#[tauri::command]
+pub async fn academic_clear_search_cache(state: State<'_, AcademicState>) -> Result<usize, String> {
+    Ok(state.search_cache.clear().await)
+}
+
+/// Set how long search results are cached (0 disables caching)
+#[tauri::command]
+pub async fn academic_set_search_cache_ttl(
+    state: State<'_, AcademicState>,
+    ttl_secs: u64,
+) -> Result<(), String> {
+    state.search_cache.set_ttl(ttl_secs)
 }
 
 fn is_retriable_provider_error(error: &str) -> bool {
@@ -154,6 +228,8 @@ pub async fn academic_search_provider(
     query: String,
     options: SearchOptions,
 ) -> Result<SearchResult, String> {
+    crate::http::ensure_online()?;
+
     let providers = state.providers.read().await;
     let provider = providers
         .iter()
@@ -164,7 +240,11 @@ pub async fn academic_search_provider(
         return Err(format!("Provider '{}' is not enabled", provider_id));
     }
 
-    provider.search(&query, &options).await
+    let mut result = provider.search(&query, &options).await?;
+    for paper in &mut result.papers {
+        normalize::normalize_paper(paper);
+    }
+    Ok(result)
 }
 
 #[tauri::command]
@@ -173,13 +253,17 @@ pub async fn academic_get_paper(
     provider_id: String,
     paper_id: String,
 ) -> Result<Paper, String> {
+    crate::http::ensure_online()?;
+
     let providers = state.providers.read().await;
     let provider = providers
         .iter()
         .find(|p| p.provider_id() == provider_id)
         .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
 
-    provider.get_paper(&paper_id).await
+    let mut paper = provider.get_paper(&paper_id).await?;
+    normalize::normalize_paper(&mut paper);
+    Ok(paper)
 }
 
 #[tauri::command]
@@ -190,6 +274,8 @@ pub async fn academic_get_citations(
     limit: Option<u32>,
     offset: Option<u32>,
 ) -> Result<Vec<PaperCitation>, String> {
+    crate::http::ensure_online()?;
+
     let providers = state.providers.read().await;
     let provider = providers
         .iter()
@@ -209,6 +295,8 @@ pub async fn academic_get_references(
     limit: Option<u32>,
     offset: Option<u32>,
 ) -> Result<Vec<PaperReference>, String> {
+    crate::http::ensure_online()?;
+
     let providers = state.providers.read().await;
     let provider = providers
         .iter()
@@ -230,6 +318,8 @@ pub async fn academic_download_pdf(
     paper_id: String,
     pdf_url: String,
 ) -> Result<String, String> {
+    crate::http::ensure_online()?;
+
     state.storage.download_pdf(&paper_id, &pdf_url).await
 }
 
@@ -287,6 +377,19 @@ pub async fn academic_update_paper(
     state.storage.update_paper(&paper_id, updates).await
 }
 
+/// Record reading progress for a library paper
+#[tauri::command]
+pub async fn academic_update_reading_progress(
+    state: State<'_, AcademicState>,
+    paper_id: String,
+    progress: ReadingProgressUpdate,
+) -> Result<LibraryPaper, String> {
+    state
+        .storage
+        .update_reading_progress(&paper_id, progress)
+        .await
+}
+
 #[tauri::command]
 pub async fn academic_get_paper_by_id(
     state: State<'_, AcademicState>,
@@ -457,6 +560,7 @@ pub async fn academic_set_provider_api_key(
         .find(|p| p.provider_id() == provider_id)
     {
         provider.set_api_key(api_key);
+        state.search_cache.clear().await;
         Ok(())
     } else {
         Err(format!("Provider '{}' not found", provider_id))
@@ -486,6 +590,8 @@ pub async fn academic_test_provider(
     state: State<'_, AcademicState>,
     provider_id: String,
 ) -> Result<bool, String> {
+    crate::http::ensure_online()?;
+
     let providers = state.providers.read().await;
     let provider = providers
         .iter()
@@ -511,39 +617,82 @@ pub async fn academic_get_statistics(
 // ============================================================================
 
 fn deduplicate_papers(papers: Vec<Paper>) -> Vec<Paper> {
-    use std::collections::HashSet;
-    let mut seen_dois: HashSet<String> = HashSet::new();
-    let mut seen_titles: HashSet<String> = HashSet::new();
-    let mut result = Vec::new();
+    let mut by_doi: HashMap<String, usize> = HashMap::new();
+    let mut by_title: HashMap<String, usize> = HashMap::new();
+    let mut result: Vec<Paper> = Vec::new();
 
     for paper in papers {
-        // Check DOI first
-        if let Some(ref doi) = paper.metadata.doi {
-            if seen_dois.contains(doi) {
-                continue;
+        let doi = paper
+            .metadata
+            .doi
+            .as_ref()
+            .map(|doi| doi.trim().to_lowercase());
+        let normalized_title = normalize_title(&paper.title);
+
+        // Match on DOI first, then fall back to normalized title when the
+        // authors agree, so generic titles don't merge unrelated papers
+        let existing = doi
+            .as_ref()
+            .and_then(|doi| by_doi.get(doi))
+            .or_else(|| {
+                (!normalized_title.is_empty())
+                    .then(|| by_title.get(&normalized_title))
+                    .flatten()
+                    .filter(|&&index| share_first_author(&result[index], &paper))
+            })
+            .copied();
+
+        let index = match existing {
+            Some(index) => {
+                result[index].merge_from(paper);
+                index
             }
-            seen_dois.insert(doi.clone());
-        }
-
-        // Fallback to normalized title
-        let normalized_title = paper
-            .title
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-            .collect::<String>();
+            None => {
+                result.push(paper);
+                result.len() - 1
+            }
+        };
 
-        if seen_titles.contains(&normalized_title) {
-            continue;
+        // A merge may have filled in the DOI, so index by the merged paper
+        if let Some(doi) = result[index].metadata.doi.as_ref() {
+            by_doi.entry(doi.trim().to_lowercase()).or_insert(index);
+        }
+        if !normalized_title.is_empty() {
+            by_title.entry(normalized_title).or_insert(index);
         }
-        seen_titles.insert(normalized_title);
-
-        result.push(paper);
     }
 
     result
 }
 
+/// Whether either paper's first author appears among the other's authors,
+/// compared by family name; true when either side has no parsed names
+fn share_first_author(a: &Paper, b: &Paper) -> bool {
+    let family_keys = |paper: &Paper| -> Vec<String> {
+        paper
+            .authors
+            .iter()
+            .filter_map(normalize::family_key)
+            .collect()
+    };
+    let (a_keys, b_keys) = (family_keys(a), family_keys(b));
+    match (a_keys.first(), b_keys.first()) {
+        (Some(a_first), Some(b_first)) => b_keys.contains(a_first) || a_keys.contains(b_first),
+        _ => true,
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn sort_papers(mut papers: Vec<Paper>, sort_by: &str, sort_order: &str) -> Vec<Paper> {
     let ascending = sort_order == "asc";
 
@@ -583,34 +732,18 @@ pub async fn academic_generate_knowledge_map(
     content: String,
     title: Option<String>,
     mode: Option<String>,
-    _options: Option<serde_json::Value>,
+    options: Option<serde_json::Value>,
 ) -> Result<KnowledgeMap, String> {
-    let now = chrono::Utc::now().to_rfc3339();
-    let id = uuid::Uuid::new_v4().to_string();
     let title = title.unwrap_or_else(|| "Knowledge Map".to_string());
     let _mode = mode.unwrap_or_else(|| "DETAILED".to_string());
 
-    // Parse content into traces
-    let traces = parse_content_to_traces(&content, &id);
+    let ids = IdMode::from_options(options.as_ref());
+    let id = ids.id(&["knowledge-map", &title, &content]);
 
-    // Generate mermaid diagram
-    let mermaid_diagram = generate_mermaid_from_traces(&traces);
-
-    // Generate mind map data
-    let mind_map_data = generate_mind_map_from_traces(&traces, &title);
+    // Parse content into traces
+    let traces = parse_content_to_traces(&content, &id, ids);
 
-    Ok(KnowledgeMap {
-        id,
-        title,
-        description: format!("Generated from content with {} traces", traces.len()),
-        source_type: "content".to_string(),
-        source_path: None,
-        traces,
-        mind_map_data: Some(mind_map_data),
-        mermaid_diagram: Some(mermaid_diagram),
-        created_at: now.clone(),
-        updated_at: now,
-    })
+    Ok(build_knowledge_map(id, title, traces, ids))
 }
 
 #[tauri::command]
@@ -618,30 +751,106 @@ pub async fn academic_generate_knowledge_map_from_content(
     content: String,
     title: String,
 ) -> Result<KnowledgeMap, String> {
-    let now = chrono::Utc::now().to_rfc3339();
     let id = uuid::Uuid::new_v4().to_string();
 
     // Parse content into traces
-    let traces = parse_content_to_traces(&content, &id);
+    let traces = parse_content_to_traces(&content, &id, IdMode::Random);
 
-    // Generate mermaid diagram
-    let mermaid_diagram = generate_mermaid_from_traces(&traces);
+    Ok(build_knowledge_map(id, title, traces, IdMode::Random))
+}
 
-    // Generate mind map data
-    let mind_map_data = generate_mind_map_from_traces(&traces, &title);
+/// Generate a knowledge map incrementally, emitting `knowledge-map://trace` as
+/// each trace is parsed and `knowledge-map://complete` with the full map at the
+/// end. The generation can be stopped with
+/// `academic_cancel_knowledge_map_generation`; with `options.deterministicIds`
+/// the same content always yields the same IDs.
+#[tauri::command]
+pub async fn academic_generate_knowledge_map_stream(
+    app: AppHandle,
+    state: State<'_, AcademicState>,
+    content: String,
+    title: Option<String>,
+    operation_id: String,
+    options: Option<serde_json::Value>,
+) -> Result<KnowledgeMapGenerationResult, String> {
+    let cancel = CancellationToken::new();
+    {
+        let mut generations = state.knowledge_map_generations.write().await;
+        if generations.contains_key(&operation_id) {
+            return Err(format!(
+                "Knowledge map generation already running: {}",
+                operation_id
+            ));
+        }
+        generations.insert(operation_id.clone(), cancel.clone());
+    }
 
-    Ok(KnowledgeMap {
-        id,
-        title,
-        description: format!("Generated from content with {} traces", traces.len()),
-        source_type: "content".to_string(),
-        source_path: None,
-        traces,
-        mind_map_data: Some(mind_map_data),
-        mermaid_diagram: Some(mermaid_diagram),
-        created_at: now.clone(),
-        updated_at: now,
+    let title = title.unwrap_or_else(|| "Knowledge Map".to_string());
+    let task_operation_id = operation_id.clone();
+    let ids = IdMode::from_options(options.as_ref());
+    let result = tokio::task::spawn_blocking(move || {
+        let id = ids.id(&["knowledge-map", &title, &content]);
+        let parsed = parse_content_to_traces_streaming(
+            &content,
+            &id,
+            ids,
+            Some(&cancel),
+            &mut |index, trace| {
+                let _ = app.emit(
+                    "knowledge-map://trace",
+                    KnowledgeMapTraceEvent {
+                        operation_id: task_operation_id.clone(),
+                        knowledge_map_id: id.clone(),
+                        index,
+                        trace: trace.clone(),
+                    },
+                );
+            },
+        );
+
+        let traces_emitted = parsed.traces.len();
+        let (status, knowledge_map) = if parsed.cancelled {
+            (KnowledgeMapGenerationStatus::Cancelled, None)
+        } else {
+            let knowledge_map = build_knowledge_map(id.clone(), title, parsed.traces, ids);
+            (KnowledgeMapGenerationStatus::Completed, Some(knowledge_map))
+        };
+
+        let result = KnowledgeMapGenerationResult {
+            operation_id: task_operation_id,
+            knowledge_map_id: id,
+            status,
+            traces_emitted,
+            knowledge_map,
+        };
+        let _ = app.emit("knowledge-map://complete", result.clone());
+        result
     })
+    .await
+    .map_err(|e| format!("Knowledge map generation task failed: {}", e));
+
+    state
+        .knowledge_map_generations
+        .write()
+        .await
+        .remove(&operation_id);
+    result
+}
+
+/// Cancel an in-flight streamed knowledge map generation
+#[tauri::command]
+pub async fn academic_cancel_knowledge_map_generation(
+    state: State<'_, AcademicState>,
+    operation_id: String,
+) -> Result<bool, String> {
+    let generations = state.knowledge_map_generations.read().await;
+    match generations.get(&operation_id) {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
 #[tauri::command]
@@ -651,7 +860,8 @@ pub async fn academic_generate_mind_map(
     _max_depth: Option<i32>,
     theme: Option<String>,
 ) -> Result<MindMapData, String> {
-    let mind_map = generate_mind_map_from_traces(&knowledge_map.traces, &knowledge_map.title);
+    let mind_map =
+        generate_mind_map_from_traces(&knowledge_map.traces, &knowledge_map.title, IdMode::Random);
 
     Ok(MindMapData {
         layout: layout.unwrap_or_else(|| "radial".to_string()),
@@ -672,10 +882,10 @@ pub async fn academic_generate_mind_map_from_content(
     let id = uuid::Uuid::new_v4().to_string();
 
     // Parse content into traces first
-    let traces = parse_content_to_traces(&content, &id);
+    let traces = parse_content_to_traces(&content, &id, IdMode::Random);
 
     // Generate mind map from traces
-    let mind_map = generate_mind_map_from_traces(&traces, &title);
+    let mind_map = generate_mind_map_from_traces(&traces, &title, IdMode::Random);
 
     Ok(MindMapData {
         layout: layout.unwrap_or_else(|| "radial".to_string()),
@@ -684,23 +894,220 @@ pub async fn academic_generate_mind_map_from_content(
     })
 }
 
+/// Extract PDF content, emitting `academic://pdf-progress` after each page.
+/// When `operation_id` is given the extraction can be stopped with
+/// `academic_cancel_pdf_extraction`; the result then holds the pages processed so far.
 #[tauri::command]
 pub async fn academic_extract_pdf_content(
+    app: AppHandle,
+    state: State<'_, AcademicState>,
     pdf_path: String,
     options: Option<PDFConversionOptions>,
+    operation_id: Option<String>,
 ) -> Result<PDFConversionResult, String> {
-    let options = options.unwrap_or_default();
+    let cancel = CancellationToken::new();
+    if let Some(id) = &operation_id {
+        let mut extractions = state.pdf_extractions.write().await;
+        if extractions.contains_key(id) {
+            return Err(format!("PDF extraction already running: {}", id));
+        }
+        extractions.insert(id.clone(), cancel.clone());
+    }
 
+    let progress_path = pdf_path.clone();
+    let progress_operation_id = operation_id.clone();
+    let result = convert_pdf(
+        pdf_path,
+        options.unwrap_or_default(),
+        Some(cancel),
+        move |page, total_pages| {
+            let _ = app.emit(
+                "academic://pdf-progress",
+                PdfExtractionProgress {
+                    operation_id: progress_operation_id.clone(),
+                    pdf_path: progress_path.clone(),
+                    page,
+                    total_pages,
+                },
+            );
+        },
+    )
+    .await;
+
+    if let Some(id) = &operation_id {
+        state.pdf_extractions.write().await.remove(id);
+    }
+    result
+}
+
+/// Cancel an in-flight PDF extraction started with an operation id
+#[tauri::command]
+pub async fn academic_cancel_pdf_extraction(
+    state: State<'_, AcademicState>,
+    operation_id: String,
+) -> Result<bool, String> {
+    let extractions = state.pdf_extractions.read().await;
+    match extractions.get(&operation_id) {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Extract a PDF page by page and index it into a vector collection
+///
+/// Each page gets the heading detection of `academic_extract_pdf_content`, is
+/// chunked according to `chunking`, embedded with `model` via Ollama at
+/// `base_url` (default `http://localhost:11434`) and upserted into `collection`.
+/// Chunk payloads carry the source path, page and heading. Chunk ids are derived
+/// from the path, page and chunk text, so indexing the same PDF again updates the
+/// existing points instead of duplicating them.
+#[tauri::command]
+pub async fn academic_index_pdf(
+    vector: State<'_, Arc<VectorStoreState>>,
+    pdf_path: String,
+    collection: String,
+    model: String,
+    base_url: Option<String>,
+    chunking: Option<ChunkingConfig>,
+) -> Result<PdfIndexResult, String> {
+    // Fail before extracting when the target collection is missing
+    get_collection_impl(&vector, collection.clone()).map_err(|e| e.to_string())?;
+
+    let path = pdf_path.clone();
+    let pages = tokio::task::spawn_blocking(move || extract_pdf_pages(&path))
+        .await
+        .map_err(|e| format!("PDF extraction task failed: {}", e))??;
+    let total_pages = pages.len() as u32;
+    let pages: Vec<String> = pages
+        .iter()
+        .map(|page| convert_text_to_markdown(page))
+        .collect();
+
+    let chunks = chunk_pages(&pages, &chunking.unwrap_or_default());
+    if chunks.is_empty() {
+        log::info!("PDF '{}' contained no text to index", pdf_path);
+        return Ok(PdfIndexResult {
+            total_pages,
+            chunks_indexed: 0,
+            warnings: vec!["PDF contained no text to index".to_string()],
+        });
+    }
+
+    let indexed_at = chrono::Utc::now().timestamp_millis();
+    let chunk_count = chunks.len();
+    let documents = chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| EmbedDocument {
+            id: pdf_chunk_id(&pdf_path, chunk.page, &chunk.text),
+            payload: Some(serde_json::json!({
+                "content": chunk.text,
+                "source": "pdf",
+                "pdf_path": pdf_path,
+                "page": chunk.page,
+                "heading": chunk.heading,
+                "chunk_index": index,
+                "chunk_count": chunk_count,
+                "indexed_at": indexed_at,
+            })),
+            text: chunk.text,
+        })
+        .collect();
+
+    let response = embed_and_upsert_impl(
+        &vector,
+        EmbedAndUpsertPayload {
+            collection: collection.clone(),
+            base_url: base_url.unwrap_or_else(|| "http://localhost:11434".to_string()),
+            model,
+            documents,
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    log::info!(
+        "Indexed {} chunk(s) from PDF '{}' into '{}'",
+        response.upserted,
+        pdf_path,
+        collection
+    );
+    Ok(PdfIndexResult {
+        total_pages,
+        chunks_indexed: response.upserted,
+        warnings: response.warnings,
+    })
+}
+
+/// Stable point id for a chunk of a PDF
+fn pdf_chunk_id(pdf_path: &str, page: Option<u32>, text: &str) -> String {
+    let page = page.map(|page| page.to_string()).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    for part in [pdf_path, page.as_str(), text] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hasher.finalize();
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("pdf-{}", hex)
+}
+
+/// Convert a PDF off the async runtime, reporting `(page, total_pages)` after
+/// each page and stopping early once `cancel` fires
+pub(crate) async fn convert_pdf<F>(
+    pdf_path: String,
+    options: PDFConversionOptions,
+    cancel: Option<CancellationToken>,
+    on_page: F,
+) -> Result<PDFConversionResult, String>
+where
+    F: FnMut(u32, u32) + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        convert_pdf_blocking(&pdf_path, &options, cancel.as_ref(), on_page)
+    })
+    .await
+    .map_err(|e| format!("PDF extraction task failed: {}", e))?
+}
+
+fn convert_pdf_blocking<F>(
+    pdf_path: &str,
+    options: &PDFConversionOptions,
+    cancel: Option<&CancellationToken>,
+    mut on_page: F,
+) -> Result<PDFConversionResult, String>
+where
+    F: FnMut(u32, u32),
+{
     // Read PDF file
     let pdf_bytes =
-        std::fs::read(&pdf_path).map_err(|e| format!("Failed to read PDF file: {}", e))?;
-
-    // Extract text content using pdf-extract or similar
-    let text_content = extract_pdf_text(&pdf_bytes)?;
+        std::fs::read(pdf_path).map_err(|e| format!("Failed to read PDF file: {}", e))?;
 
-    // Convert to markdown
+    let extraction = extract_pdf_text_by_pages(&pdf_bytes, cancel, &mut on_page)?;
+    let text_content = extraction.text;
     let markdown = convert_text_to_markdown(&text_content);
 
+    if extraction.cancelled {
+        // Only the text of the processed pages is returned; derived content
+        // would describe a partial document
+        return Ok(PDFConversionResult {
+            success: true,
+            markdown,
+            knowledge_map: None,
+            mind_map: None,
+            images: Vec::new(),
+            tables: Vec::new(),
+            equations: Vec::new(),
+            error: None,
+            status: PdfExtractionStatus::Cancelled,
+            pages_processed: extraction.pages_processed,
+            total_pages: extraction.total_pages,
+        });
+    }
+
     // Extract images, tables, equations if enabled
     let images = if options.extract_images {
         extract_pdf_images(&pdf_bytes)?
@@ -723,10 +1130,14 @@ pub async fn academic_extract_pdf_content(
     // Generate knowledge map if enabled
     let knowledge_map = if options.generate_knowledge_map {
         let id = uuid::Uuid::new_v4().to_string();
-        let traces = parse_content_to_traces(&text_content, &id);
+        let traces = parse_content_to_traces(&text_content, &id, IdMode::Random);
         let mermaid_diagram = generate_mermaid_from_traces(&traces);
         let mind_map_data = if options.generate_mind_map {
-            Some(generate_mind_map_from_traces(&traces, &pdf_path))
+            Some(generate_mind_map_from_traces(
+                &traces,
+                pdf_path,
+                IdMode::Random,
+            ))
         } else {
             None
         };
@@ -734,14 +1145,14 @@ pub async fn academic_extract_pdf_content(
 
         Some(KnowledgeMap {
             id,
-            title: std::path::Path::new(&pdf_path)
+            title: std::path::Path::new(pdf_path)
                 .file_stem()
                 .and_then(|s| s.to_str())
                 .unwrap_or("PDF Document")
                 .to_string(),
             description: format!("Generated from {}", pdf_path),
             source_type: "pdf".to_string(),
-            source_path: Some(pdf_path.clone()),
+            source_path: Some(pdf_path.to_string()),
             traces,
             mind_map_data,
             mermaid_diagram: Some(mermaid_diagram),
@@ -755,8 +1166,12 @@ pub async fn academic_extract_pdf_content(
     // Generate mind map if enabled (separate from knowledge map)
     let mind_map = if options.generate_mind_map && knowledge_map.is_none() {
         let id = uuid::Uuid::new_v4().to_string();
-        let traces = parse_content_to_traces(&text_content, &id);
-        Some(generate_mind_map_from_traces(&traces, &pdf_path))
+        let traces = parse_content_to_traces(&text_content, &id, IdMode::Random);
+        Some(generate_mind_map_from_traces(
+            &traces,
+            pdf_path,
+            IdMode::Random,
+        ))
     } else {
         None
     };
@@ -770,6 +1185,9 @@ pub async fn academic_extract_pdf_content(
         tables,
         equations,
         error: None,
+        status: PdfExtractionStatus::Completed,
+        pages_processed: extraction.pages_processed,
+        total_pages: extraction.total_pages,
     })
 }
 
@@ -777,7 +1195,95 @@ pub async fn academic_extract_pdf_content(
 // Knowledge Map Helper Functions
 // ============================================================================
 
-fn parse_content_to_traces(content: &str, knowledge_map_id: &str) -> Vec<KnowledgeMapTrace> {
+/// Source of knowledge map, trace and mind map IDs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdMode {
+    Random,
+    /// IDs are hashed from content and position, so identical input yields
+    /// identical IDs for caching and diffing
+    Deterministic,
+}
+
+impl IdMode {
+    /// `options.deterministicIds` derives all IDs from the content
+    fn from_options(options: Option<&serde_json::Value>) -> Self {
+        let deterministic = options
+            .and_then(|options| options.get("deterministicIds"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if deterministic {
+            Self::Deterministic
+        } else {
+            Self::Random
+        }
+    }
+
+    /// A new ID; deterministic IDs are a UUID-formatted SHA-256 of `parts`
+    fn id(self, parts: &[&str]) -> String {
+        match self {
+            Self::Random => uuid::Uuid::new_v4().to_string(),
+            Self::Deterministic => {
+                let mut hasher = Sha256::new();
+                for part in parts {
+                    hasher.update(part.len().to_le_bytes());
+                    hasher.update(part.as_bytes());
+                }
+                let digest = hasher.finalize();
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(&digest[..16]);
+                uuid::Uuid::from_bytes(bytes).to_string()
+            }
+        }
+    }
+}
+
+fn parse_content_to_traces(
+    content: &str,
+    knowledge_map_id: &str,
+    ids: IdMode,
+) -> Vec<KnowledgeMapTrace> {
+    parse_content_to_traces_streaming(content, knowledge_map_id, ids, None, &mut |_, _| {}).traces
+}
+
+/// Assemble a content-sourced knowledge map with its mermaid diagram and mind map
+fn build_knowledge_map(
+    id: String,
+    title: String,
+    traces: Vec<KnowledgeMapTrace>,
+    ids: IdMode,
+) -> KnowledgeMap {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mermaid_diagram = generate_mermaid_from_traces(&traces);
+    let mind_map_data = generate_mind_map_from_traces(&traces, &title, ids);
+
+    KnowledgeMap {
+        id,
+        title,
+        description: format!("Generated from content with {} traces", traces.len()),
+        source_type: "content".to_string(),
+        source_path: None,
+        traces,
+        mind_map_data: Some(mind_map_data),
+        mermaid_diagram: Some(mermaid_diagram),
+        created_at: now.clone(),
+        updated_at: now,
+    }
+}
+
+struct TraceParse {
+    traces: Vec<KnowledgeMapTrace>,
+    cancelled: bool,
+}
+
+/// Parse content into traces, reporting `(index, trace)` as soon as each trace
+/// is complete and stopping early once `cancel` fires
+fn parse_content_to_traces_streaming(
+    content: &str,
+    knowledge_map_id: &str,
+    ids: IdMode,
+    cancel: Option<&CancellationToken>,
+    on_trace: &mut dyn FnMut(usize, &KnowledgeMapTrace),
+) -> TraceParse {
     let mut traces = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
 
@@ -787,6 +1293,13 @@ fn parse_content_to_traces(content: &str, knowledge_map_id: &str) -> Vec<Knowled
     let mut line_num = 1;
 
     for line in &lines {
+        if cancel.is_some_and(CancellationToken::is_cancelled) {
+            return TraceParse {
+                traces,
+                cancelled: true,
+            };
+        }
+
         let trimmed = line.trim();
 
         // Check if this is a header (markdown style)
@@ -794,14 +1307,16 @@ fn parse_content_to_traces(content: &str, knowledge_map_id: &str) -> Vec<Knowled
             // Save previous trace
             if let Some(mut trace) = current_trace.take() {
                 trace.locations = current_locations.clone();
+                on_trace(traces.len(), &trace);
                 traces.push(trace);
                 current_locations.clear();
             }
 
             // Start new trace
             let title = trimmed.trim_start_matches('#').trim().to_string();
+            let position = traces.len().to_string();
             current_trace = Some(KnowledgeMapTrace {
-                id: uuid::Uuid::new_v4().to_string(),
+                id: ids.id(&["trace", &position, &title]),
                 title: title.clone(),
                 description: format!("Section: {}", title),
                 locations: Vec::new(),
@@ -825,13 +1340,14 @@ fn parse_content_to_traces(content: &str, knowledge_map_id: &str) -> Vec<Knowled
     // Save last trace
     if let Some(mut trace) = current_trace.take() {
         trace.locations = current_locations;
+        on_trace(traces.len(), &trace);
         traces.push(trace);
     }
 
     // If no traces found, create a default one
     if traces.is_empty() {
-        traces.push(KnowledgeMapTrace {
-            id: uuid::Uuid::new_v4().to_string(),
+        let trace = KnowledgeMapTrace {
+            id: ids.id(&["trace", "0", "Content Overview"]),
             title: "Content Overview".to_string(),
             description: "Main content".to_string(),
             locations: vec![KnowledgeMapLocation {
@@ -843,10 +1359,15 @@ fn parse_content_to_traces(content: &str, knowledge_map_id: &str) -> Vec<Knowled
             }],
             trace_text_diagram: None,
             trace_guide: None,
-        });
+        };
+        on_trace(0, &trace);
+        traces.push(trace);
     }
 
-    traces
+    TraceParse {
+        traces,
+        cancelled: false,
+    }
 }
 
 fn generate_mermaid_from_traces(traces: &[KnowledgeMapTrace]) -> String {
@@ -855,13 +1376,8 @@ fn generate_mermaid_from_traces(traces: &[KnowledgeMapTrace]) -> String {
     let mut prev_id: Option<String> = None;
 
     for trace in traces {
-        let safe_id = trace.id.replace('-', "_");
-        let safe_title = trace
-            .title
-            .replace('"', "'")
-            .chars()
-            .take(30)
-            .collect::<String>();
+        let safe_id = mermaid_node_id(&trace.id);
+        let safe_title = mermaid_label(&trace.title, 30);
 
         mermaid.push_str(&format!("    {}[\"{}\"]\n", safe_id, safe_title));
 
@@ -875,9 +1391,57 @@ fn generate_mermaid_from_traces(traces: &[KnowledgeMapTrace]) -> String {
     mermaid
 }
 
-fn generate_mind_map_from_traces(traces: &[KnowledgeMapTrace], title: &str) -> MindMapData {
+/// Mermaid node ID made only of ASCII letters, digits and underscores. The
+/// prefix keeps IDs from starting with a digit or matching keywords like `end`.
+fn mermaid_node_id(id: &str) -> String {
+    let safe: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("n_{}", safe)
+}
+
+/// Label text safe inside a quoted mermaid node label (`id["..."]`).
+///
+/// Whitespace runs and line breaks collapse to single spaces, the label is
+/// truncated to `max_chars` characters, and characters mermaid treats as
+/// syntax are written as entity codes, which mermaid renders literally.
+fn mermaid_label(text: &str, max_chars: usize) -> String {
+    let collapsed = text
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let truncated: String = collapsed.chars().take(max_chars).collect();
+    let truncated = truncated.trim_end();
+    if truncated.is_empty() {
+        return "Untitled".to_string();
+    }
+
+    let mut label = String::with_capacity(truncated.len());
+    for c in truncated.chars() {
+        match c {
+            '"' => label.push_str("#quot;"),
+            '#' => label.push_str("#35;"),
+            '&' => label.push_str("#amp;"),
+            '<' => label.push_str("#lt;"),
+            '>' => label.push_str("#gt;"),
+            '(' | ')' | '[' | ']' | '{' | '}' | ';' | '|' | '`' => {
+                label.push_str(&format!("#{};", c as u32))
+            }
+            _ => label.push(c),
+        }
+    }
+    label
+}
+
+fn generate_mind_map_from_traces(
+    traces: &[KnowledgeMapTrace],
+    title: &str,
+    ids: IdMode,
+) -> MindMapData {
     let now = chrono::Utc::now().to_rfc3339();
-    let root_id = uuid::Uuid::new_v4().to_string();
+    let root_id = ids.id(&["mind-map-root", title]);
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
 
@@ -915,7 +1479,7 @@ fn generate_mind_map_from_traces(traces: &[KnowledgeMapTrace], title: &str) -> M
 
         // Create edge from root to trace
         edges.push(MindMapEdge {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: ids.id(&["edge", &root_id, &trace.id]),
             source: root_id.clone(),
             target: trace.id.clone(),
             label: None,
@@ -938,7 +1502,7 @@ fn generate_mind_map_from_traces(traces: &[KnowledgeMapTrace], title: &str) -> M
             });
 
             edges.push(MindMapEdge {
-                id: uuid::Uuid::new_v4().to_string(),
+                id: ids.id(&["edge", &trace.id, &loc_id]),
                 source: trace.id.clone(),
                 target: loc_id,
                 label: None,
@@ -947,8 +1511,14 @@ fn generate_mind_map_from_traces(traces: &[KnowledgeMapTrace], title: &str) -> M
         }
     }
 
+    let trace_ids = traces
+        .iter()
+        .map(|trace| trace.id.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+
     MindMapData {
-        id: uuid::Uuid::new_v4().to_string(),
+        id: ids.id(&["mind-map", &root_id, &trace_ids]),
         title: title.to_string(),
         root_id,
         nodes,
@@ -960,9 +1530,155 @@ fn generate_mind_map_from_traces(traces: &[KnowledgeMapTrace], title: &str) -> M
     }
 }
 
-fn extract_pdf_text(pdf_bytes: &[u8]) -> Result<String, String> {
-    pdf_extract::extract_text_from_mem(pdf_bytes)
-        .map_err(|e| format!("Failed to extract PDF text: {}", e))
+struct PdfTextExtraction {
+    text: String,
+    /// Offsets in `text` at which each processed page ends
+    page_ends: Vec<usize>,
+    pages_processed: u32,
+    total_pages: u32,
+    cancelled: bool,
+}
+
+/// `PlainTextOutput` writer into a string that is also read between pages
+struct SharedText<'a>(&'a RefCell<String>);
+
+impl std::fmt::Write for SharedText<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.borrow_mut().push_str(s);
+        Ok(())
+    }
+}
+
+impl pdf_extract::ConvertToFmt for SharedText<'_> {
+    type Writer = Self;
+
+    fn convert(self) -> Self::Writer {
+        self
+    }
+}
+
+/// Plain text output that records where each page ends, reports progress and
+/// stops before the next page once cancelled
+struct PagedTextOutput<'a> {
+    output: pdf_extract::PlainTextOutput<SharedText<'a>>,
+    text: &'a RefCell<String>,
+    page_ends: Vec<usize>,
+    total_pages: u32,
+    cancel: Option<&'a CancellationToken>,
+    cancelled: bool,
+    on_page: &'a mut dyn FnMut(u32, u32),
+}
+
+impl pdf_extract::OutputDev for PagedTextOutput<'_> {
+    fn begin_page(
+        &mut self,
+        page_num: u32,
+        media_box: &pdf_extract::MediaBox,
+        art_box: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), pdf_extract::OutputError> {
+        if self.cancel.is_some_and(CancellationToken::is_cancelled) {
+            // Any error stops `output_doc`; the flag tells it apart from a real failure
+            self.cancelled = true;
+            return Err(pdf_extract::OutputError::FormatError(std::fmt::Error));
+        }
+        self.output.begin_page(page_num, media_box, art_box)
+    }
+
+    fn end_page(&mut self) -> Result<(), pdf_extract::OutputError> {
+        self.output.end_page()?;
+        self.page_ends.push(self.text.borrow().len());
+        (self.on_page)(self.page_ends.len() as u32, self.total_pages);
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &pdf_extract::Transform,
+        width: f64,
+        spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> Result<(), pdf_extract::OutputError> {
+        self.output
+            .output_character(trm, width, spacing, font_size, char)
+    }
+
+    fn begin_word(&mut self) -> Result<(), pdf_extract::OutputError> {
+        self.output.begin_word()
+    }
+
+    fn end_word(&mut self) -> Result<(), pdf_extract::OutputError> {
+        self.output.end_word()
+    }
+
+    fn end_line(&mut self) -> Result<(), pdf_extract::OutputError> {
+        self.output.end_line()
+    }
+}
+
+fn extract_pdf_text_by_pages(
+    pdf_bytes: &[u8],
+    cancel: Option<&CancellationToken>,
+    on_page: &mut dyn FnMut(u32, u32),
+) -> Result<PdfTextExtraction, String> {
+    let mut document = pdf_extract::Document::load_mem(pdf_bytes)
+        .map_err(|e| format!("Failed to extract PDF text: {}", e))?;
+    if document.is_encrypted() {
+        document
+            .decrypt("")
+            .map_err(|e| format!("Failed to extract PDF text: {}", e))?;
+    }
+
+    let total_pages = document.get_pages().len() as u32;
+    let text = RefCell::new(String::new());
+    let mut output = PagedTextOutput {
+        output: pdf_extract::PlainTextOutput::new(SharedText(&text)),
+        text: &text,
+        page_ends: Vec::with_capacity(total_pages as usize),
+        total_pages,
+        cancel,
+        cancelled: false,
+        on_page,
+    };
+
+    // One pass over the document, so the page tree is walked and the
+    // content processor set up once rather than for every page
+    let result = pdf_extract::output_doc(&document, &mut output);
+    if !output.cancelled {
+        result.map_err(|e| {
+            format!(
+                "Failed to extract PDF text on page {}: {}",
+                output.page_ends.len() + 1,
+                e
+            )
+        })?;
+    }
+
+    Ok(PdfTextExtraction {
+        text: text.take(),
+        pages_processed: output.page_ends.len() as u32,
+        page_ends: output.page_ends,
+        total_pages,
+        cancelled: output.cancelled,
+    })
+}
+
+/// Extract the text of every page of a PDF file
+fn extract_pdf_pages(pdf_path: &str) -> Result<Vec<String>, String> {
+    let pdf_bytes =
+        std::fs::read(pdf_path).map_err(|e| format!("Failed to read PDF file: {}", e))?;
+    let extraction = extract_pdf_text_by_pages(&pdf_bytes, None, &mut |_: u32, _: u32| {})?;
+
+    let mut start = 0;
+    Ok(extraction
+        .page_ends
+        .iter()
+        .map(|&end| {
+            let page = extraction.text[start..end].to_string();
+            start = end;
+            page
+        })
+        .collect())
 }
 
 fn convert_text_to_markdown(text: &str) -> String {
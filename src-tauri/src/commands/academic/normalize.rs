@@ -0,0 +1,297 @@
+//! Normalization of provider results
+//!
+//! Providers shape authors and dates differently: "Smith, J." next to
+//! "John Smith", DBLP homonym numbers ("Wei Wang 0001"), and dates ranging from
+//! "2023" to "2023-05-01T00:00:00Z" or "May 2023". Every `Paper` is normalized
+//! before it is merged or returned, so dedup and citation keys compare like with
+//! like.
+
+use chrono::NaiveDate;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use super::types::{DatePrecision, Paper, PaperAuthor};
+
+/// Lowercase particles that belong to the family name ("van Gogh", "de la Cruz")
+const NAME_PARTICLES: &[&str] = &[
+    "van", "von", "der", "den", "de", "del", "della", "di", "da", "das", "dos", "du", "la", "le",
+    "ten", "ter", "zu", "bin", "al",
+];
+
+/// Generational and honorific suffixes that are neither given nor family name
+const NAME_SUFFIXES: &[&str] = &["jr", "sr", "ii", "iii", "iv"];
+
+/// Title words skipped when picking the citation key word
+const TITLE_STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "in", "for", "to", "and", "with",
+];
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Normalize the authors and publication date of a provider result in place
+pub fn normalize_paper(paper: &mut Paper) {
+    paper.authors.retain_mut(|author| {
+        normalize_author(author);
+        !author.name.is_empty()
+    });
+
+    match paper.publication_date.as_deref().and_then(normalize_date) {
+        Some((date, precision)) => {
+            if paper.year.is_none() {
+                paper.year = date.get(..4).and_then(|year| year.parse().ok());
+            }
+            paper.publication_date = Some(date);
+            paper.publication_date_precision = Some(precision);
+        }
+        None if paper.publication_date.is_none() => {
+            if let Some(year) = paper.year {
+                paper.publication_date = Some(format!("{:04}", year));
+                paper.publication_date_precision = Some(DatePrecision::Year);
+            }
+        }
+        // Unparseable dates are kept as reported, without a precision
+        None => paper.publication_date_precision = None,
+    }
+}
+
+/// Fill `given_name`/`family_name` and tidy `name`
+///
+/// Names reported as "Family, Given" are rewritten to "Given Family" so every
+/// provider displays authors the same way. Structured names from the provider
+/// are kept.
+pub fn normalize_author(author: &mut PaperAuthor) {
+    let given = clean_part(author.given_name.take());
+    let family = clean_part(author.family_name.take());
+
+    if family.is_some() {
+        if author.name.trim().is_empty() {
+            author.name = join_name(given.as_deref(), family.as_deref().unwrap_or_default());
+        }
+        author.name = collapse_whitespace(&author.name);
+        author.given_name = given;
+        author.family_name = family;
+        return;
+    }
+
+    let name = strip_homonym_number(&collapse_whitespace(&author.name));
+    match split_name(&name) {
+        Some((given, family, suffix)) => {
+            if name.contains(',') {
+                author.name = join_name(given.as_deref(), &family);
+                if let Some(suffix) = suffix {
+                    author.name = format!("{} {}", author.name, suffix);
+                }
+            } else {
+                author.name = name;
+            }
+            author.given_name = given;
+            author.family_name = Some(family);
+        }
+        None => author.name = name,
+    }
+}
+
+/// Split a display name into given names and family name
+///
+/// Handles "Family, Given", "Given Family", initials ("J. R. R. Tolkien"),
+/// family particles ("Ludwig van Beethoven"), suffixes ("Martin Luther King Jr.")
+/// and DBLP homonym numbers. Returns `None` for empty names.
+pub fn parse_author_name(name: &str) -> Option<(Option<String>, String)> {
+    let name = strip_homonym_number(&collapse_whitespace(name));
+    split_name(&name).map(|(given, family, _)| (given, family))
+}
+
+/// Split a cleaned name into given names, family name and suffix
+fn split_name(name: &str) -> Option<(Option<String>, String, Option<String>)> {
+    if name.is_empty() {
+        return None;
+    }
+
+    if name.contains(',') {
+        let parts: Vec<&str> = name
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect();
+        let suffix = parts.iter().skip(1).find(|part| is_suffix(part));
+        let mut names = parts.iter().filter(|part| !is_suffix(part));
+        let family = names.next()?.to_string();
+        let given = names.copied().collect::<Vec<_>>().join(" ");
+        return Some((
+            (!given.is_empty()).then_some(given),
+            family,
+            suffix.map(|s| s.to_string()),
+        ));
+    }
+
+    let mut tokens: Vec<&str> = name.split(' ').collect();
+    let mut suffix = None;
+    while tokens.len() > 1 && tokens.last().is_some_and(|token| is_suffix(token)) {
+        suffix = tokens.pop().map(str::to_string);
+    }
+    if tokens.len() == 1 {
+        return Some((None, tokens[0].to_string(), suffix));
+    }
+
+    // The last token is the family name, extended backwards over particles
+    // while at least one given name remains
+    let mut family_start = tokens.len() - 1;
+    while family_start > 1 && NAME_PARTICLES.contains(&tokens[family_start - 1]) {
+        family_start -= 1;
+    }
+
+    Some((
+        Some(tokens[..family_start].join(" ")),
+        tokens[family_start..].join(" "),
+        suffix,
+    ))
+}
+
+/// Normalize a provider date to `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+///
+/// Accepts ISO dates and timestamps, slash or dot separators, and month names
+/// ("May 2023", "1 May 2023", "May 1, 2023"). Returns `None` when no valid date
+/// can be read.
+pub fn normalize_date(raw: &str) -> Option<(String, DatePrecision)> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+
+    // Drop the time of day from timestamps
+    let date = raw.split(['T', ' ']).next().unwrap_or(raw);
+    let numeric: Option<Vec<u32>> = date
+        .split(['-', '/', '.'])
+        .map(|part| part.parse().ok())
+        .collect();
+    if let Some(parts) = numeric {
+        if date.len() >= 4 && date.as_bytes()[..4].iter().all(u8::is_ascii_digit) {
+            return format_date(parts[0], parts.get(1).copied(), parts.get(2).copied());
+        }
+    }
+
+    let (mut year, mut month, mut day) = (None, None, None);
+    for token in raw
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '-' || c == '/')
+        .filter(|token| !token.is_empty())
+    {
+        let token = token.trim_end_matches('.');
+        if let Ok(number) = token.parse::<u32>() {
+            if token.len() == 4 {
+                year = Some(number);
+            } else if (1..=31).contains(&number) {
+                day = Some(number);
+            }
+        } else if let Some(index) = month_index(token) {
+            month = Some(index);
+        }
+    }
+
+    // A day without a month is meaningless
+    format_date(year?, month, month.and(day))
+}
+
+/// Lowercase family name with diacritics removed, for comparing authors
+pub fn family_key(author: &PaperAuthor) -> Option<String> {
+    let family = author.family_name.as_deref()?;
+    let key: String = family
+        .nfkd()
+        .filter(|c| c.is_alphanumeric() && !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase();
+    (!key.is_empty()).then_some(key)
+}
+
+/// Citation key in the common `familyYEARword` form, e.g. `vaswani2017attention`
+///
+/// Built from the first author's family name (parsed from `name` for papers
+/// saved before normalization), the year and the first significant title word.
+/// Returns `None` without a usable author name.
+pub fn citation_key(paper: &Paper) -> Option<String> {
+    let author = paper.authors.first()?;
+    let family = match author.family_name.clone() {
+        Some(family) => family,
+        None => parse_author_name(&author.name)?.1,
+    };
+    let family = ascii_key(&family);
+    if family.is_empty() {
+        return None;
+    }
+
+    let word = paper
+        .title
+        .split_whitespace()
+        .map(ascii_key)
+        .find(|word| !word.is_empty() && !TITLE_STOP_WORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+    let year = paper.year.map(|year| year.to_string()).unwrap_or_default();
+
+    Some(format!("{}{}{}", family, year, word))
+}
+
+/// Lowercase ASCII letters and digits of `text`, with accents stripped
+fn ascii_key(text: &str) -> String {
+    text.nfkd()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn format_date(year: u32, month: Option<u32>, day: Option<u32>) -> Option<(String, DatePrecision)> {
+    if !(1000..=2999).contains(&year) {
+        return None;
+    }
+    let Some(month) = month.filter(|m| (1..=12).contains(m)) else {
+        return Some((format!("{:04}", year), DatePrecision::Year));
+    };
+    match day {
+        Some(day) if NaiveDate::from_ymd_opt(year as i32, month, day).is_some() => Some((
+            format!("{:04}-{:02}-{:02}", year, month, day),
+            DatePrecision::Day,
+        )),
+        _ => Some((format!("{:04}-{:02}", year, month), DatePrecision::Month)),
+    }
+}
+
+fn month_index(token: &str) -> Option<u32> {
+    let token = token.to_lowercase();
+    if token.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|month| token.starts_with(month))
+        .map(|index| index as u32 + 1)
+}
+
+fn is_suffix(token: &str) -> bool {
+    NAME_SUFFIXES.contains(&token.trim_end_matches('.').to_lowercase().as_str())
+}
+
+/// DBLP disambiguates homonyms with a trailing four-digit number ("Wei Wang 0001")
+fn strip_homonym_number(name: &str) -> String {
+    match name.rsplit_once(' ') {
+        Some((rest, number)) if number.len() == 4 && number.bytes().all(|b| b.is_ascii_digit()) => {
+            rest.to_string()
+        }
+        _ => name.to_string(),
+    }
+}
+
+fn clean_part(part: Option<String>) -> Option<String> {
+    part.map(|p| collapse_whitespace(&p))
+        .filter(|p| !p.is_empty())
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn join_name(given: Option<&str>, family: &str) -> String {
+    match given {
+        Some(given) => format!("{} {}", given, family),
+        None => family.to_string(),
+    }
+}
@@ -129,6 +129,8 @@ impl ArxivProvider {
                                     affiliation: None,
                                     email: None,
                                     orcid: None,
+                                    given_name: None,
+                                    family_name: None,
                                 });
                                 current_author.clear();
                             }
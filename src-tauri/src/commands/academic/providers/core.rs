@@ -118,6 +118,8 @@ impl From<CoreWork> for Paper {
                 affiliation: None,
                 email: None,
                 orcid: None,
+                given_name: None,
+                family_name: None,
             })
             .collect();
 
@@ -171,6 +171,8 @@ impl From<DblpInfo> for Paper {
                         affiliation: None,
                         email: None,
                         orcid: None,
+                        given_name: None,
+                        family_name: None,
                     }],
                     DblpAuthorList::Multiple(list) => list
                         .into_iter()
@@ -180,6 +182,8 @@ impl From<DblpInfo> for Paper {
                             affiliation: None,
                             email: None,
                             orcid: None,
+                            given_name: None,
+                            family_name: None,
                         })
                         .collect(),
                 };
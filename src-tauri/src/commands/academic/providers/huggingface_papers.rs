@@ -52,6 +52,8 @@ impl HuggingFacePapersProvider {
                             affiliation: None,
                             email: None,
                             orcid: None,
+                            given_name: None,
+                            family_name: None,
                         })
                     }
                 }
@@ -67,6 +69,8 @@ impl HuggingFacePapersProvider {
                         affiliation: None,
                         email: None,
                         orcid: None,
+                        given_name: None,
+                        family_name: None,
                     }),
                 _ => None,
             })
@@ -212,6 +212,8 @@ impl From<OAWork> for Paper {
                     affiliation,
                     email: None,
                     orcid: author.orcid,
+                    given_name: None,
+                    family_name: None,
                 }
             })
             .collect();
@@ -526,6 +528,8 @@ impl AcademicProvider for OpenAlexProvider {
                             affiliation: None,
                             email: None,
                             orcid: None,
+                            given_name: None,
+                            family_name: None,
                         })
                         .collect()
                 }),
@@ -599,6 +603,8 @@ impl AcademicProvider for OpenAlexProvider {
                             affiliation: None,
                             email: None,
                             orcid: None,
+                            given_name: None,
+                            family_name: None,
                         })
                         .collect()
                 }),
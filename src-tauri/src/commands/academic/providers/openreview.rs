@@ -72,6 +72,8 @@ impl OpenReviewProvider {
                                 affiliation: None,
                                 email: None,
                                 orcid: None,
+                                given_name: None,
+                                family_name: None,
                             })
                         }
                     }
@@ -87,6 +89,8 @@ impl OpenReviewProvider {
                             affiliation: None,
                             email: None,
                             orcid: None,
+                            given_name: None,
+                            family_name: None,
                         }),
                     _ => None,
                 })
@@ -158,6 +158,8 @@ impl From<S2Paper> for Paper {
                 affiliation: None,
                 email: None,
                 orcid: None,
+                given_name: None,
+                family_name: None,
             })
             .collect();
 
@@ -415,6 +417,8 @@ impl AcademicProvider for SemanticScholarProvider {
                                 affiliation: None,
                                 email: None,
                                 orcid: None,
+                                given_name: None,
+                                family_name: None,
                             })
                             .collect()
                     }),
@@ -480,6 +484,8 @@ impl AcademicProvider for SemanticScholarProvider {
                                 affiliation: None,
                                 email: None,
                                 orcid: None,
+                                given_name: None,
+                                family_name: None,
                             })
                             .collect()
                     }),
@@ -100,6 +100,8 @@ impl From<UnpaywallResponse> for Paper {
                     affiliation: None,
                     email: None,
                     orcid: a.orcid,
+                    given_name: a.given,
+                    family_name: a.family,
                 }
             })
             .collect();
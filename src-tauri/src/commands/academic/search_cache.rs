@@ -0,0 +1,164 @@
+//! Search result cache
+//!
+//! Keeps aggregated `academic_search` results for a short time so repeated
+//! identical searches don't hit the providers again.
+
+use super::types::{AggregatedSearchResult, SearchOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Default time a search result stays valid
+pub const DEFAULT_SEARCH_CACHE_TTL_SECS: u64 = 300;
+
+/// Maximum number of cached searches (oldest entries are evicted first)
+const MAX_CACHED_SEARCHES: usize = 100;
+
+/// File in the academic data directory holding the cache settings
+pub const SEARCH_CACHE_SETTINGS_FILE: &str = "search_cache_settings.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchCacheSettings {
+    ttl_secs: u64,
+}
+
+struct CachedSearch {
+    result: AggregatedSearchResult,
+    stored_at: Instant,
+}
+
+/// TTL cache of aggregated search results
+pub struct SearchCache {
+    entries: RwLock<HashMap<String, CachedSearch>>,
+    ttl_secs: AtomicU64,
+    /// Where the TTL is persisted; `None` keeps it in memory only
+    settings_path: Option<PathBuf>,
+}
+
+impl SearchCache {
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            ttl_secs: AtomicU64::new(ttl_secs),
+            settings_path: None,
+        }
+    }
+
+    /// Cache whose TTL is loaded from and saved to `settings_path`; a missing
+    /// or unreadable file yields the default TTL
+    pub fn with_settings_path(settings_path: PathBuf) -> Self {
+        let ttl_secs = std::fs::read_to_string(&settings_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SearchCacheSettings>(&content).ok())
+            .map_or(DEFAULT_SEARCH_CACHE_TTL_SECS, |settings| settings.ttl_secs);
+        Self {
+            settings_path: Some(settings_path),
+            ..Self::new(ttl_secs)
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs.load(Ordering::Relaxed))
+    }
+
+    /// Change and persist the TTL; 0 disables caching
+    pub fn set_ttl(&self, ttl_secs: u64) -> Result<(), String> {
+        self.ttl_secs.store(ttl_secs, Ordering::Relaxed);
+
+        let Some(path) = &self.settings_path else {
+            return Ok(());
+        };
+        let content = serde_json::to_string_pretty(&SearchCacheSettings { ttl_secs })
+            .map_err(|e| format!("Failed to serialize search cache settings: {}", e))?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write search cache settings: {}", e))
+    }
+
+    /// Build the cache key from the normalized query, provider set, and options
+    pub fn cache_key(query: &str, provider_ids: &[String], options: &SearchOptions) -> String {
+        let mut providers = provider_ids.to_vec();
+        providers.sort();
+        providers.dedup();
+
+        let mut options = options.clone();
+        options.providers = providers;
+        options.query = options.query.as_deref().map(normalize_query);
+
+        format!(
+            "{}|{}",
+            normalize_query(query),
+            serde_json::to_string(&options).unwrap_or_default()
+        )
+    }
+
+    /// Get a still-valid cached result, marked as `cached`
+    pub async fn get(&self, key: &str) -> Option<AggregatedSearchResult> {
+        let ttl = self.ttl();
+        if ttl.is_zero() {
+            return None;
+        }
+
+        let entries = self.entries.read().await;
+        let entry = entries.get(key)?;
+        if entry.stored_at.elapsed() >= ttl {
+            return None;
+        }
+
+        let mut result = entry.result.clone();
+        result.cached = true;
+        Some(result)
+    }
+
+    pub async fn insert(&self, key: String, result: AggregatedSearchResult) {
+        let ttl = self.ttl();
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        if entries.len() >= MAX_CACHED_SEARCHES && !entries.contains_key(&key) {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            CachedSearch {
+                result,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Remove all cached results, returning how many were dropped
+    pub async fn clear(&self) -> usize {
+        let mut entries = self.entries.write().await;
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+}
+
+impl Default for SearchCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEARCH_CACHE_TTL_SECS)
+    }
+}
+
+/// Lowercase and collapse whitespace so trivially different queries share a key
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
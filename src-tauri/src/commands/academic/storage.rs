@@ -2,10 +2,11 @@
 //!
 //! Handles persistence of papers, collections, and annotations
 
+use crate::commands::academic::normalize::{citation_key, normalize_paper};
 use crate::commands::academic::types::*;
 use crate::http::create_proxy_client_long;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::RwLock;
 use tokio::fs;
@@ -341,6 +342,29 @@ impl PaperStorage {
         Ok(result)
     }
 
+    pub async fn update_reading_progress(
+        &self,
+        paper_id: &str,
+        progress: ReadingProgressUpdate,
+    ) -> Result<LibraryPaper, String> {
+        let mut data = self
+            .data
+            .write()
+            .map_err(|e| format!("Lock error: {}", e))?;
+
+        let paper = data
+            .papers
+            .get_mut(paper_id)
+            .ok_or_else(|| format!("Paper '{}' not found", paper_id))?;
+        paper.apply_reading_progress(progress)?;
+
+        let result = paper.clone();
+        drop(data);
+
+        self.save()?;
+        Ok(result)
+    }
+
     // ========================================================================
     // PDF Management
     // ========================================================================
@@ -814,6 +838,9 @@ impl PaperStorage {
                 .papers_by_status
                 .entry(paper.reading_status.clone())
                 .or_insert(0) += 1;
+            stats.total_reading_time_secs = stats
+                .total_reading_time_secs
+                .saturating_add(paper.reading_time_secs);
             *stats
                 .papers_by_provider
                 .entry(paper.paper.provider_id.clone())
@@ -958,15 +985,19 @@ fn create_paper_from_bibtex(
                 affiliation: None,
                 email: None,
                 orcid: None,
+                given_name: None,
+                family_name: None,
             })
             .collect();
     }
 
+    normalize_paper(&mut paper);
     Some(paper)
 }
 
 fn generate_bibtex(papers: &[&LibraryPaper]) -> String {
     let mut output = String::new();
+    let mut used_keys = HashSet::new();
 
     for paper in papers {
         let p = &paper.paper;
@@ -975,7 +1006,18 @@ fn generate_bibtex(papers: &[&LibraryPaper]) -> String {
         } else {
             "article"
         };
-        let key = p.external_id.replace(['/', ':'], "_");
+        let base_key = citation_key(p).unwrap_or_else(|| p.external_id.replace(['/', ':'], "_"));
+        // Disambiguate clashes the usual way: smith2020, smith2020a, smith2020b, ...
+        let mut key = base_key.clone();
+        let mut attempt = 0u32;
+        while !used_keys.insert(key.clone()) {
+            key = if attempt < 26 {
+                format!("{}{}", base_key, (b'a' + attempt as u8) as char)
+            } else {
+                format!("{}{}", base_key, attempt)
+            };
+            attempt += 1;
+        }
 
         output.push_str(&format!("@{}{{{},\n", entry_type, key));
         output.push_str(&format!("  title = {{{}}},\n", p.title));
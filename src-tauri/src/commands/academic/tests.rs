@@ -2,6 +2,7 @@
 //!
 //! Unit tests for paper types and basic functionality
 
+use crate::commands::academic::search_cache::SearchCache;
 use crate::commands::academic::types::*;
 use std::collections::HashMap;
 
@@ -29,6 +30,8 @@ fn test_paper_author_serialization() {
         affiliation: Some("MIT".to_string()),
         email: None,
         orcid: Some("0000-0001-2345-6789".to_string()),
+        given_name: None,
+        family_name: None,
     };
 
     let json = serde_json::to_string(&author).unwrap();
@@ -76,6 +79,87 @@ fn test_library_paper_creation() {
     assert!(library_paper.user_notes.is_none());
 }
 
+#[test]
+fn test_reading_progress_updates_status() {
+    let mut paper = LibraryPaper::from_paper(Paper::new("arxiv", "1", "Paper"));
+
+    paper
+        .apply_reading_progress(ReadingProgressUpdate {
+            progress_percent: Some(40),
+            last_page_read: Some(6),
+            add_reading_time_secs: Some(300),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(paper.reading_status, "reading");
+    assert_eq!(paper.reading_progress, Some(40));
+    assert_eq!(paper.last_page_read, Some(6));
+
+    paper
+        .apply_reading_progress(ReadingProgressUpdate {
+            progress_percent: Some(100),
+            add_reading_time_secs: Some(120),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(paper.reading_status, "completed");
+    assert_eq!(paper.reading_time_secs, 420);
+
+    paper
+        .apply_reading_progress(ReadingProgressUpdate {
+            status: Some("unread".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(paper.reading_status, "unread");
+
+    paper
+        .apply_reading_progress(ReadingProgressUpdate {
+            status: Some("Read".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(paper.reading_status, "completed");
+}
+
+#[test]
+fn test_reading_progress_rejects_invalid_values() {
+    let mut paper = LibraryPaper::from_paper(Paper::new("arxiv", "1", "Paper"));
+
+    for update in [
+        ReadingProgressUpdate {
+            progress_percent: Some(101),
+            ..Default::default()
+        },
+        ReadingProgressUpdate {
+            last_page_read: Some(0),
+            ..Default::default()
+        },
+        ReadingProgressUpdate {
+            status: Some("skimmed".to_string()),
+            progress_percent: Some(10),
+            ..Default::default()
+        },
+    ] {
+        assert!(paper.apply_reading_progress(update).is_err());
+    }
+    assert_eq!(paper.reading_status, "unread");
+    assert!(paper.reading_progress.is_none());
+}
+
+#[test]
+fn test_library_paper_without_reading_fields_deserializes() {
+    let mut json =
+        serde_json::to_value(LibraryPaper::from_paper(Paper::new("arxiv", "1", "P"))).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("lastPageRead");
+    object.remove("readingTimeSecs");
+
+    let paper: LibraryPaper = serde_json::from_value(json).unwrap();
+    assert_eq!(paper.reading_time_secs, 0);
+    assert!(paper.last_page_read.is_none());
+}
+
 // ============================================================================
 // Collection Tests
 // ============================================================================
@@ -124,6 +208,8 @@ fn test_paper_citation() {
             affiliation: None,
             email: None,
             orcid: None,
+            given_name: None,
+            family_name: None,
         }]),
         year: Some(2022),
         venue: Some("ICML".to_string()),
@@ -210,6 +296,7 @@ fn test_aggregated_search_result() {
         provider_results,
         degraded_providers: HashMap::new(),
         search_time_ms: 150,
+        cached: false,
     };
 
     assert_eq!(result.provider_results.len(), 2);
@@ -239,3 +326,697 @@ fn test_search_result_serializes_camel_case() {
     assert!(json.get("searchTimeMs").is_some());
     assert!(json.get("total_results").is_none());
 }
+
+// ============================================================================
+// Search Cache Tests
+// ============================================================================
+
+fn single_paper_search_result() -> AggregatedSearchResult {
+    AggregatedSearchResult {
+        papers: vec![Paper::new("arxiv", "2301.00001", "Cached Paper")],
+        total_results: 1,
+        provider_results: HashMap::new(),
+        degraded_providers: HashMap::new(),
+        search_time_ms: 42,
+        cached: false,
+    }
+}
+
+#[test]
+fn test_search_cache_key_normalizes_query_and_providers() {
+    let options = SearchOptions::default();
+    let a = SearchCache::cache_key(
+        "  Graph   Neural Networks ",
+        &["arxiv".to_string(), "openalex".to_string()],
+        &options,
+    );
+    let b = SearchCache::cache_key(
+        "graph neural networks",
+        &["openalex".to_string(), "arxiv".to_string()],
+        &options,
+    );
+    assert_eq!(a, b);
+
+    let filtered = SearchOptions {
+        year_from: Some(2020),
+        ..SearchOptions::default()
+    };
+    let c = SearchCache::cache_key(
+        "graph neural networks",
+        &["arxiv".to_string(), "openalex".to_string()],
+        &filtered,
+    );
+    assert_ne!(a, c);
+}
+
+#[tokio::test]
+async fn test_search_cache_returns_cached_flag_and_clears() {
+    let cache = SearchCache::new(60);
+    let key = SearchCache::cache_key("rust", &["arxiv".to_string()], &SearchOptions::default());
+
+    assert!(cache.get(&key).await.is_none());
+    cache
+        .insert(key.clone(), single_paper_search_result())
+        .await;
+
+    let hit = cache.get(&key).await.unwrap();
+    assert!(hit.cached);
+    assert_eq!(hit.papers.len(), 1);
+
+    assert_eq!(cache.clear().await, 1);
+    assert!(cache.get(&key).await.is_none());
+}
+
+#[tokio::test]
+async fn test_search_cache_zero_ttl_disables_caching() {
+    let cache = SearchCache::new(60);
+    cache.set_ttl(0).unwrap();
+    cache
+        .insert("key".to_string(), single_paper_search_result())
+        .await;
+    assert!(cache.get("key").await.is_none());
+}
+
+#[test]
+fn test_search_cache_ttl_is_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("search_cache_settings.json");
+
+    let cache = SearchCache::with_settings_path(path.clone());
+    assert_eq!(
+        cache.ttl().as_secs(),
+        crate::commands::academic::search_cache::DEFAULT_SEARCH_CACHE_TTL_SECS
+    );
+    cache.set_ttl(30).unwrap();
+
+    let reloaded = SearchCache::with_settings_path(path);
+    assert_eq!(reloaded.ttl().as_secs(), 30);
+}
+
+// ============================================================================
+// Deduplication Tests
+// ============================================================================
+
+#[test]
+fn test_deduplicate_merges_metadata_from_providers() {
+    let mut arxiv = Paper::new("arxiv", "2301.00001", "Attention Is All You Need");
+    arxiv.abstract_text = Some("Transformers.".to_string());
+    arxiv.metadata.arxiv_id = Some("2301.00001".to_string());
+    arxiv.categories = Some(vec!["cs.CL".to_string()]);
+
+    let mut s2 = Paper::new("semantic-scholar", "abc", "Attention is all you need!");
+    s2.citation_count = Some(1000);
+    s2.metadata.doi = Some("10.1000/xyz".to_string());
+    s2.categories = Some(vec!["CS.CL".to_string(), "cs.LG".to_string()]);
+
+    let mut unpaywall = Paper::new("unpaywall", "10.1000/xyz", "");
+    unpaywall.pdf_url = Some("https://example.org/paper.pdf".to_string());
+    unpaywall.metadata.doi = Some("10.1000/XYZ".to_string());
+
+    let other = Paper::new("arxiv", "2301.00002", "Something Else");
+
+    let papers = super::deduplicate_papers(vec![arxiv, s2, unpaywall, other]);
+    assert_eq!(papers.len(), 2);
+
+    let merged = &papers[0];
+    assert_eq!(merged.provider_id, "arxiv");
+    assert_eq!(merged.abstract_text.as_deref(), Some("Transformers."));
+    assert_eq!(merged.citation_count, Some(1000));
+    assert_eq!(merged.metadata.doi.as_deref(), Some("10.1000/xyz"));
+    assert_eq!(
+        merged.pdf_url.as_deref(),
+        Some("https://example.org/paper.pdf")
+    );
+    assert_eq!(
+        merged.categories,
+        Some(vec!["cs.CL".to_string(), "cs.LG".to_string()])
+    );
+
+    assert_eq!(merged.sources["abstractText"], "arxiv");
+    assert_eq!(merged.sources["citationCount"], "semantic-scholar");
+    assert_eq!(merged.sources["doi"], "semantic-scholar");
+    assert_eq!(merged.sources["pdfUrl"], "unpaywall");
+
+    assert!(papers[1].sources.is_empty());
+}
+
+#[test]
+fn test_merge_keeps_existing_values() {
+    let mut first = Paper::new("openalex", "W1", "Paper");
+    first.year = Some(2020);
+    first.urls.push(PaperUrl {
+        url: "https://example.org".to_string(),
+        url_type: "html".to_string(),
+        source: "openalex".to_string(),
+        is_open_access: None,
+    });
+
+    let mut second = Paper::new("core", "C1", "Paper");
+    second.year = Some(2021);
+    second.urls = first.urls.clone();
+
+    first.merge_from(second);
+    assert_eq!(first.year, Some(2020));
+    assert_eq!(first.urls.len(), 1);
+    assert_eq!(first.sources["year"], "openalex");
+    assert_eq!(first.sources["urls"], "openalex");
+
+    let json = serde_json::to_value(&first).unwrap();
+    assert!(json.get("sources").is_some());
+    let single = serde_json::to_value(Paper::new("arxiv", "1", "x")).unwrap();
+    assert!(single.get("sources").is_none());
+}
+
+// ============================================================================
+// Normalization Tests
+// ============================================================================
+
+fn named_author(name: &str) -> PaperAuthor {
+    PaperAuthor {
+        name: name.to_string(),
+        author_id: None,
+        affiliation: None,
+        email: None,
+        orcid: None,
+        given_name: None,
+        family_name: None,
+    }
+}
+
+fn given_family(author: &PaperAuthor) -> (Option<&str>, Option<&str>) {
+    (author.given_name.as_deref(), author.family_name.as_deref())
+}
+
+#[test]
+fn test_normalize_author_names_from_providers() {
+    use super::normalize::normalize_author;
+
+    let cases = [
+        // arXiv: extra whitespace from the Atom feed
+        (
+            "  Ashish   Vaswani ",
+            "Ashish Vaswani",
+            Some("Ashish"),
+            "Vaswani",
+        ),
+        // Semantic Scholar: initials
+        (
+            "J. R. R. Tolkien",
+            "J. R. R. Tolkien",
+            Some("J. R. R."),
+            "Tolkien",
+        ),
+        // OpenAlex: family name particle
+        (
+            "Ludwig van Beethoven",
+            "Ludwig van Beethoven",
+            Some("Ludwig"),
+            "van Beethoven",
+        ),
+        // DBLP: homonym number
+        ("Wei Wang 0001", "Wei Wang", Some("Wei"), "Wang"),
+        // CORE and BibTeX: "Family, Given"
+        ("Smith, J.", "J. Smith", Some("J."), "Smith"),
+        (
+            "King, Jr., Martin Luther",
+            "Martin Luther King Jr.",
+            Some("Martin Luther"),
+            "King",
+        ),
+        (
+            "Martin Luther King Jr.",
+            "Martin Luther King Jr.",
+            Some("Martin Luther"),
+            "King",
+        ),
+        // Hugging Face: single-token names
+        ("Mononymous", "Mononymous", None, "Mononymous"),
+    ];
+    for (raw, name, given, family) in cases {
+        let mut author = named_author(raw);
+        normalize_author(&mut author);
+        assert_eq!(author.name, name, "name of {:?}", raw);
+        assert_eq!(
+            given_family(&author),
+            (given, Some(family)),
+            "parts of {:?}",
+            raw
+        );
+    }
+
+    // Unpaywall: structured parts are kept, the display name is rebuilt if missing
+    let mut author = named_author("");
+    author.given_name = Some(" Maria ".to_string());
+    author.family_name = Some("de la Cruz".to_string());
+    normalize_author(&mut author);
+    assert_eq!(author.name, "Maria de la Cruz");
+    assert_eq!(given_family(&author), (Some("Maria"), Some("de la Cruz")));
+}
+
+#[test]
+fn test_normalize_dates_from_providers() {
+    use super::normalize::normalize_date;
+
+    let cases = [
+        // arXiv / Hugging Face timestamps
+        (
+            "2017-06-12T17:57:34Z",
+            Some(("2017-06-12", DatePrecision::Day)),
+        ),
+        // OpenReview RFC 3339
+        (
+            "2023-05-01T12:00:00+00:00",
+            Some(("2023-05-01", DatePrecision::Day)),
+        ),
+        // OpenAlex / Semantic Scholar / Unpaywall
+        ("2021-03-15", Some(("2021-03-15", DatePrecision::Day))),
+        ("2021-3", Some(("2021-03", DatePrecision::Month))),
+        ("2020", Some(("2020", DatePrecision::Year))),
+        ("2023/5/1", Some(("2023-05-01", DatePrecision::Day))),
+        // CORE: time separated by a space
+        (
+            "2019-01-01 00:00:00",
+            Some(("2019-01-01", DatePrecision::Day)),
+        ),
+        // Free-text dates from publisher metadata
+        ("May 2023", Some(("2023-05", DatePrecision::Month))),
+        ("1 May 2023", Some(("2023-05-01", DatePrecision::Day))),
+        ("Sept. 3, 2022", Some(("2022-09-03", DatePrecision::Day))),
+        // Invalid day falls back to the month
+        ("2023-02-30", Some(("2023-02", DatePrecision::Month))),
+        ("n.d.", None),
+        ("", None),
+    ];
+    for (raw, expected) in cases {
+        let normalized = normalize_date(raw);
+        assert_eq!(
+            normalized
+                .as_ref()
+                .map(|(date, precision)| (date.as_str(), *precision)),
+            expected,
+            "date {:?}",
+            raw
+        );
+    }
+}
+
+#[test]
+fn test_normalize_paper_fills_year_and_date() {
+    use super::normalize::normalize_paper;
+
+    let mut arxiv = Paper::new("arxiv", "1706.03762", "Attention Is All You Need");
+    arxiv.publication_date = Some("2017-06-12T17:57:34Z".to_string());
+    arxiv.authors = vec![named_author("Ashish Vaswani"), named_author("  ")];
+    normalize_paper(&mut arxiv);
+    assert_eq!(arxiv.year, Some(2017));
+    assert_eq!(arxiv.publication_date.as_deref(), Some("2017-06-12"));
+    assert_eq!(arxiv.publication_date_precision, Some(DatePrecision::Day));
+    assert_eq!(arxiv.authors.len(), 1);
+
+    // DBLP only reports a year
+    let mut dblp = Paper::new(
+        "dblp",
+        "conf/nips/VaswaniSPUJGKP17",
+        "Attention is All you Need",
+    );
+    dblp.year = Some(2017);
+    normalize_paper(&mut dblp);
+    assert_eq!(dblp.publication_date.as_deref(), Some("2017"));
+    assert_eq!(dblp.publication_date_precision, Some(DatePrecision::Year));
+
+    // Unreadable dates are kept as reported
+    let mut core = Paper::new("core", "1", "Paper");
+    core.publication_date = Some("unknown".to_string());
+    normalize_paper(&mut core);
+    assert_eq!(core.publication_date.as_deref(), Some("unknown"));
+    assert!(core.publication_date_precision.is_none());
+
+    let json = serde_json::to_value(&dblp).unwrap();
+    assert_eq!(json["publicationDatePrecision"], "year");
+}
+
+#[test]
+fn test_deduplicate_uses_normalized_authors_and_dates() {
+    use super::normalize::normalize_paper;
+
+    let mut dblp = Paper::new("dblp", "journals/x/Muller20", "Introduction");
+    dblp.year = Some(2020);
+    dblp.authors = vec![named_author("Jürgen Müller 0002")];
+
+    let mut core = Paper::new("core", "C1", "Introduction.");
+    core.publication_date = Some("2020-04-02T00:00:00".to_string());
+    core.authors = vec![named_author("Muller, J."), named_author("Doe, Jane")];
+
+    // Same generic title, different authors
+    let mut unrelated = Paper::new("openalex", "W9", "Introduction");
+    unrelated.authors = vec![named_author("Ada Lovelace")];
+
+    let mut papers = vec![dblp, core, unrelated];
+    papers.iter_mut().for_each(normalize_paper);
+    let papers = super::deduplicate_papers(papers);
+    assert_eq!(papers.len(), 2);
+
+    let merged = &papers[0];
+    assert_eq!(merged.authors[0].name, "Jürgen Müller");
+    assert_eq!(merged.publication_date.as_deref(), Some("2020-04-02"));
+    assert_eq!(merged.publication_date_precision, Some(DatePrecision::Day));
+    assert_eq!(merged.sources["publicationDate"], "core");
+    assert_eq!(papers[1].provider_id, "openalex");
+}
+
+#[test]
+fn test_citation_key_uses_family_name_and_year() {
+    use super::normalize::{citation_key, normalize_paper};
+
+    let mut paper = Paper::new("semantic-scholar", "abc", "The Theory of Éléments");
+    paper.year = Some(1999);
+    paper.authors = vec![named_author("Ludwig van Beethoven")];
+    normalize_paper(&mut paper);
+    assert_eq!(
+        citation_key(&paper).as_deref(),
+        Some("vanbeethoven1999theory")
+    );
+
+    // Library papers saved before normalization still get a key from `name`
+    let mut saved = Paper::new("core", "1", "Deep Learning");
+    saved.authors = vec![named_author("Müller, Jürgen")];
+    assert_eq!(citation_key(&saved).as_deref(), Some("mullerdeep"));
+
+    assert!(citation_key(&Paper::new("arxiv", "1", "No Authors")).is_none());
+}
+
+// ============================================================================
+// PDF Extraction Tests
+// ============================================================================
+
+fn build_test_pdf(pages: &[&str]) -> Vec<u8> {
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Document, Object, Stream};
+
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Courier",
+    });
+    let resources_id = doc.add_object(dictionary! {
+        "Font" => dictionary! { "F1" => font_id },
+    });
+
+    let mut kids = Vec::new();
+    for text in pages {
+        let content = Content {
+            operations: vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                Operation::new("Td", vec![72.into(), 720.into()]),
+                Operation::new("Tj", vec![Object::string_literal(*text)]),
+                Operation::new("ET", vec![]),
+            ],
+        };
+        let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+
+    let pages_dict = dictionary! {
+        "Type" => "Pages",
+        "Count" => pages.len() as i64,
+        "Kids" => kids,
+        "Resources" => resources_id,
+        "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+    };
+    doc.objects.insert(pages_id, Object::Dictionary(pages_dict));
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+
+    let mut bytes = Vec::new();
+    doc.save_to(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_extract_pdf_text_reports_each_page() {
+    let bytes = build_test_pdf(&["First page", "Second page"]);
+    let mut progress = Vec::new();
+
+    let extraction = super::extract_pdf_text_by_pages(&bytes, None, &mut |page, total| {
+        progress.push((page, total))
+    })
+    .unwrap();
+
+    assert!(!extraction.cancelled);
+    assert_eq!(extraction.total_pages, 2);
+    assert_eq!(extraction.pages_processed, 2);
+    assert_eq!(progress, vec![(1, 2), (2, 2)]);
+    assert_eq!(extraction.page_ends.len(), 2);
+    let (first, second) = extraction.text.split_at(extraction.page_ends[0]);
+    assert!(first.contains("First page") && !first.contains("Second page"));
+    assert!(second.contains("Second page"));
+}
+
+#[test]
+fn test_extract_pdf_text_stops_when_cancelled() {
+    let bytes = build_test_pdf(&["First page", "Second page", "Third page"]);
+    let cancel = tokio_util::sync::CancellationToken::new();
+
+    let extraction = super::extract_pdf_text_by_pages(&bytes, Some(&cancel), &mut |page, _| {
+        if page == 1 {
+            cancel.cancel();
+        }
+    })
+    .unwrap();
+
+    assert!(extraction.cancelled);
+    assert_eq!(extraction.pages_processed, 1);
+    assert_eq!(extraction.total_pages, 3);
+    assert!(extraction.text.contains("First page"));
+    assert!(!extraction.text.contains("Second page"));
+}
+
+#[test]
+fn test_pdf_conversion_result_defaults_to_completed() {
+    let json = serde_json::json!({
+        "success": true,
+        "markdown": "",
+        "knowledgeMap": null,
+        "mindMap": null,
+        "images": [],
+        "tables": [],
+        "equations": [],
+        "error": null
+    });
+
+    let result: PDFConversionResult = serde_json::from_value(json).unwrap();
+    assert_eq!(result.status, PdfExtractionStatus::Completed);
+    assert_eq!(
+        serde_json::to_value(PdfExtractionStatus::Cancelled).unwrap(),
+        "cancelled"
+    );
+}
+
+// ============================================================================
+// Knowledge Map Streaming Tests
+// ============================================================================
+
+#[test]
+fn test_streaming_trace_parse_reports_each_trace() {
+    let content = "# Intro\nFirst line\n# Methods\nSecond line\n# Results\nThird line";
+    let mut reported = Vec::new();
+
+    let parsed = super::parse_content_to_traces_streaming(
+        content,
+        "km",
+        super::IdMode::Random,
+        None,
+        &mut |index, trace| reported.push((index, trace.title.clone())),
+    );
+
+    assert!(!parsed.cancelled);
+    assert_eq!(parsed.traces.len(), 3);
+    assert_eq!(
+        reported,
+        vec![
+            (0, "Intro".to_string()),
+            (1, "Methods".to_string()),
+            (2, "Results".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_streaming_trace_parse_stops_when_cancelled() {
+    let content = "# Intro\nFirst line\n# Methods\nSecond line\n# Results\nThird line";
+    let cancel = tokio_util::sync::CancellationToken::new();
+
+    let parsed = super::parse_content_to_traces_streaming(
+        content,
+        "km",
+        super::IdMode::Random,
+        Some(&cancel),
+        &mut |_, _| cancel.cancel(),
+    );
+
+    assert!(parsed.cancelled);
+    assert_eq!(parsed.traces.len(), 1);
+    assert_eq!(parsed.traces[0].title, "Intro");
+}
+
+#[test]
+fn test_build_knowledge_map_from_streamed_traces() {
+    let content = "# Intro\nFirst line\n# Methods\nSecond line";
+    let ids = super::IdMode::Deterministic;
+    let parsed = super::parse_content_to_traces_streaming(content, "km", ids, None, &mut |_, _| {});
+
+    let map = super::build_knowledge_map("km".to_string(), "Doc".to_string(), parsed.traces, ids);
+
+    assert_eq!(map.description, "Generated from content with 2 traces");
+    assert_eq!(map.source_type, "content");
+    assert_eq!(
+        map.mermaid_diagram.as_deref(),
+        Some(super::generate_mermaid_from_traces(&map.traces).as_str())
+    );
+    let mind_map = map
+        .mind_map_data
+        .as_ref()
+        .expect("mind map should be built");
+    assert_eq!(
+        mind_map.id,
+        super::generate_mind_map_from_traces(&map.traces, "Doc", ids).id
+    );
+    assert_eq!(map.created_at, map.updated_at);
+}
+
+// ============================================================================
+// Mermaid Generation Tests
+// ============================================================================
+
+fn trace_with_title(id: &str, title: &str) -> KnowledgeMapTrace {
+    KnowledgeMapTrace {
+        id: id.to_string(),
+        title: title.to_string(),
+        description: String::new(),
+        locations: Vec::new(),
+        trace_text_diagram: None,
+        trace_guide: None,
+    }
+}
+
+/// Text of every node label, in order
+fn mermaid_labels(mermaid: &str) -> Vec<String> {
+    mermaid
+        .lines()
+        .filter_map(|line| {
+            let start = line.find("[\"")? + 2;
+            let end = line.rfind("\"]")?;
+            Some(line[start..end].to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn test_mermaid_labels_escape_special_characters() {
+    let traces = vec![
+        trace_with_title("a-1", "f(x) = [a]; {b} | \"c\""),
+        trace_with_title("b-2", "A <b> & #1 `code`"),
+    ];
+
+    let mermaid = super::generate_mermaid_from_traces(&traces);
+    let labels = mermaid_labels(&mermaid);
+
+    assert_eq!(
+        labels[0],
+        "f#40;x#41; = #91;a#93;#59; #123;b#125; #124; #quot;c#quot;"
+    );
+    assert_eq!(labels[1], "A #lt;b#gt; #amp; #35;1 #96;code#96;");
+    // Entity codes end in `;`, so only the other characters can be checked here
+    for label in &labels {
+        assert!(!label.contains(|c| "()[]{}|\"<>`".contains(c)));
+    }
+    assert!(mermaid.contains("    n_a_1 --> n_b_2\n"));
+}
+
+#[test]
+fn test_mermaid_labels_handle_newlines_and_non_ascii() {
+    let traces = vec![
+        trace_with_title("1", "Überblick:\n  Введение\r\n\t数据分析 🚀"),
+        trace_with_title("end", "   \n  "),
+        trace_with_title("long", &"é".repeat(40)),
+    ];
+
+    let mermaid = super::generate_mermaid_from_traces(&traces);
+    let labels = mermaid_labels(&mermaid);
+
+    assert_eq!(labels[0], "Überblick: Введение 数据分析 🚀");
+    assert_eq!(labels[1], "Untitled");
+    assert_eq!(labels[2], "é".repeat(30));
+    assert_eq!(mermaid.lines().count(), 1 + 3 + 2);
+    // IDs are prefixed so they never start with a digit or match keywords
+    assert!(mermaid.contains("    n_1[\""));
+    assert!(mermaid.contains("    n_end[\""));
+}
+
+// ============================================================================
+// Deterministic ID Tests
+// ============================================================================
+
+#[test]
+fn test_deterministic_ids_are_stable_across_runs() {
+    let content = "# Intro\nFirst line\n# Methods\nSecond line";
+    let ids = super::IdMode::Deterministic;
+
+    let first = super::parse_content_to_traces(content, "km", ids);
+    let second = super::parse_content_to_traces(content, "km", ids);
+    let first_ids: Vec<_> = first.iter().map(|t| t.id.clone()).collect();
+    let second_ids: Vec<_> = second.iter().map(|t| t.id.clone()).collect();
+    assert_eq!(first_ids, second_ids);
+    assert_ne!(first_ids[0], first_ids[1]);
+    assert!(uuid::Uuid::parse_str(&first_ids[0]).is_ok());
+
+    let first_map = super::generate_mind_map_from_traces(&first, "Doc", ids);
+    let second_map = super::generate_mind_map_from_traces(&second, "Doc", ids);
+    assert_eq!(first_map.id, second_map.id);
+    assert_eq!(first_map.root_id, second_map.root_id);
+    let edge_ids = |map: &MindMapData| map.edges.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+    assert_eq!(edge_ids(&first_map), edge_ids(&second_map));
+}
+
+#[test]
+fn test_id_mode_reads_deterministic_ids_option() {
+    let options = serde_json::json!({ "deterministicIds": true, "maxTraces": 5 });
+    assert_eq!(
+        super::IdMode::from_options(Some(&options)),
+        super::IdMode::Deterministic
+    );
+
+    let options = serde_json::json!({ "deterministicIds": false });
+    assert_eq!(
+        super::IdMode::from_options(Some(&options)),
+        super::IdMode::Random
+    );
+    assert_eq!(super::IdMode::from_options(None), super::IdMode::Random);
+}
+
+#[test]
+fn test_deterministic_trace_ids_follow_title_and_position() {
+    let ids = super::IdMode::Deterministic;
+    let original = super::parse_content_to_traces("# Intro\nold text\n# Methods\nx", "km", ids);
+    let edited = super::parse_content_to_traces("# Intro\nnew text\n# Results\ny", "km", ids);
+
+    // An unchanged section keeps its ID when the body text changes
+    assert_eq!(original[0].id, edited[0].id);
+    assert_ne!(original[1].id, edited[1].id);
+
+    let random = super::parse_content_to_traces("# Intro\nold text", "km", super::IdMode::Random);
+    let random_again =
+        super::parse_content_to_traces("# Intro\nold text", "km", super::IdMode::Random);
+    assert_ne!(random[0].id, random_again[0].id);
+}
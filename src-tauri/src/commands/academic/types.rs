@@ -15,6 +15,21 @@ pub struct PaperAuthor {
     pub affiliation: Option<String>,
     pub email: Option<String>,
     pub orcid: Option<String>,
+    /// Given names as parsed from `name` (or reported by the provider)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    /// Family name including particles such as "van" or "de"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+}
+
+/// How much of a normalized `publication_date` is known
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatePrecision {
+    Year,
+    Month,
+    Day,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +93,9 @@ pub struct Paper {
     pub authors: Vec<PaperAuthor>,
     pub year: Option<i32>,
     pub publication_date: Option<String>,
+    // Precision of `publication_date` once normalized to `YYYY[-MM[-DD]]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_date_precision: Option<DatePrecision>,
     pub venue: Option<String>,
     pub journal: Option<String>,
     pub conference: Option<String>,
@@ -104,6 +122,11 @@ pub struct Paper {
     // External IDs
     pub metadata: PaperMetadata,
 
+    // Provenance of merged fields (field name -> provider id); empty when the
+    // paper came from a single provider
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sources: HashMap<String, String>,
+
     // Timestamps
     pub created_at: String,
     pub updated_at: String,
@@ -124,6 +147,7 @@ impl Paper {
             authors: Vec::new(),
             year: None,
             publication_date: None,
+            publication_date_precision: None,
             venue: None,
             journal: None,
             conference: None,
@@ -141,11 +165,188 @@ impl Paper {
             open_access_url: None,
             is_open_access: None,
             metadata: PaperMetadata::default(),
+            sources: HashMap::new(),
             created_at: now.clone(),
             updated_at: now.clone(),
             fetched_at: now,
         }
     }
+
+    /// Merge a duplicate of this paper from another provider.
+    ///
+    /// Empty fields are filled from `other`, list fields are unioned, and each
+    /// field taken from `other` is recorded in `sources`.
+    pub fn merge_from(&mut self, other: Paper) {
+        if other.provider_id == self.provider_id && other.external_id == self.external_id {
+            return;
+        }
+        if self.sources.is_empty() {
+            self.sources = self.populated_fields(&self.provider_id);
+        }
+        let provider = other.provider_id.clone();
+        let mut contributed = Vec::new();
+
+        macro_rules! fill {
+            ($($field:ident).+, $name:expr) => {
+                if self.$($field).+.is_none() && other.$($field).+.is_some() {
+                    self.$($field).+ = other.$($field).+.clone();
+                    contributed.push($name);
+                }
+            };
+        }
+
+        if self
+            .abstract_text
+            .as_deref()
+            .map_or(true, |a| a.trim().is_empty())
+            && other
+                .abstract_text
+                .as_deref()
+                .is_some_and(|a| !a.trim().is_empty())
+        {
+            self.abstract_text = other.abstract_text.clone();
+            contributed.push("abstractText");
+        }
+        if self.authors.is_empty() && !other.authors.is_empty() {
+            self.authors = other.authors.clone();
+            contributed.push("authors");
+        }
+
+        fill!(year, "year");
+        // A more precise date for the same year replaces a coarser one
+        let refines_date = match (
+            &self.publication_date,
+            &other.publication_date,
+            self.publication_date_precision,
+            other.publication_date_precision,
+        ) {
+            (None, Some(_), _, _) => true,
+            (Some(own), Some(theirs), Some(own_precision), Some(their_precision)) => {
+                their_precision > own_precision && theirs.starts_with(own.as_str())
+            }
+            _ => false,
+        };
+        if refines_date {
+            self.publication_date = other.publication_date.clone();
+            self.publication_date_precision = other.publication_date_precision;
+            self.sources
+                .insert("publicationDate".to_string(), provider.clone());
+        }
+        fill!(venue, "venue");
+        fill!(journal, "journal");
+        fill!(conference, "conference");
+        fill!(volume, "volume");
+        fill!(issue, "issue");
+        fill!(pages, "pages");
+        fill!(citation_count, "citationCount");
+        fill!(reference_count, "referenceCount");
+        fill!(influential_citation_count, "influentialCitationCount");
+        fill!(pdf_url, "pdfUrl");
+        fill!(open_access_url, "openAccessUrl");
+        fill!(metadata.doi, "doi");
+        fill!(metadata.arxiv_id, "arxivId");
+        fill!(metadata.pmid, "pmid");
+        fill!(metadata.pmcid, "pmcid");
+        fill!(metadata.corpus_id, "corpusId");
+        fill!(metadata.mag_id, "magId");
+        fill!(metadata.open_alex_id, "openAlexId");
+        fill!(metadata.core_id, "coreId");
+        fill!(metadata.dblp_key, "dblpKey");
+
+        if other.is_open_access == Some(true) && self.is_open_access != Some(true) {
+            self.is_open_access = Some(true);
+            contributed.push("isOpenAccess");
+        }
+
+        if union_strings(&mut self.categories, &other.categories) {
+            contributed.push("categories");
+        }
+        if union_strings(&mut self.keywords, &other.keywords) {
+            contributed.push("keywords");
+        }
+        if union_strings(&mut self.fields_of_study, &other.fields_of_study) {
+            contributed.push("fieldsOfStudy");
+        }
+
+        let mut added_url = false;
+        for url in other.urls {
+            if !self.urls.iter().any(|u| u.url == url.url) {
+                self.urls.push(url);
+                added_url = true;
+            }
+        }
+        if added_url {
+            contributed.push("urls");
+        }
+
+        for field in contributed {
+            self.sources
+                .entry(field.to_string())
+                .or_insert_with(|| provider.clone());
+        }
+    }
+
+    /// Fields this paper already has values for, attributed to `provider`
+    fn populated_fields(&self, provider: &str) -> HashMap<String, String> {
+        let m = &self.metadata;
+        let present = [
+            ("abstractText", self.abstract_text.is_some()),
+            ("authors", !self.authors.is_empty()),
+            ("year", self.year.is_some()),
+            ("publicationDate", self.publication_date.is_some()),
+            ("venue", self.venue.is_some()),
+            ("journal", self.journal.is_some()),
+            ("conference", self.conference.is_some()),
+            ("volume", self.volume.is_some()),
+            ("issue", self.issue.is_some()),
+            ("pages", self.pages.is_some()),
+            ("categories", self.categories.is_some()),
+            ("keywords", self.keywords.is_some()),
+            ("fieldsOfStudy", self.fields_of_study.is_some()),
+            ("citationCount", self.citation_count.is_some()),
+            ("referenceCount", self.reference_count.is_some()),
+            (
+                "influentialCitationCount",
+                self.influential_citation_count.is_some(),
+            ),
+            ("urls", !self.urls.is_empty()),
+            ("pdfUrl", self.pdf_url.is_some()),
+            ("openAccessUrl", self.open_access_url.is_some()),
+            ("isOpenAccess", self.is_open_access == Some(true)),
+            ("doi", m.doi.is_some()),
+            ("arxivId", m.arxiv_id.is_some()),
+            ("pmid", m.pmid.is_some()),
+            ("pmcid", m.pmcid.is_some()),
+            ("corpusId", m.corpus_id.is_some()),
+            ("magId", m.mag_id.is_some()),
+            ("openAlexId", m.open_alex_id.is_some()),
+            ("coreId", m.core_id.is_some()),
+            ("dblpKey", m.dblp_key.is_some()),
+        ];
+
+        present
+            .into_iter()
+            .filter(|(_, has)| *has)
+            .map(|(field, _)| (field.to_string(), provider.to_string()))
+            .collect()
+    }
+}
+
+/// Add values from `extra` missing in `target` (case-insensitive); returns
+/// whether anything was added
+fn union_strings(target: &mut Option<Vec<String>>, extra: &Option<Vec<String>>) -> bool {
+    let Some(extra) = extra else {
+        return false;
+    };
+    let list = target.get_or_insert_with(Vec::new);
+    let mut added = false;
+    for value in extra {
+        if !list.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+            list.push(value.clone());
+            added = true;
+        }
+    }
+    added
 }
 
 // ============================================================================
@@ -171,6 +372,10 @@ pub struct LibraryPaper {
     pub reading_status: String, // unread, reading, completed, archived
     pub priority: String,       // low, medium, high, urgent
     pub reading_progress: Option<i32>, // 0-100
+    #[serde(default)]
+    pub last_page_read: Option<i32>,
+    #[serde(default)]
+    pub reading_time_secs: u64,
 
     // User data
     pub user_rating: Option<i32>, // 1-5
@@ -204,6 +409,8 @@ impl LibraryPaper {
             reading_status: "unread".to_string(),
             priority: "medium".to_string(),
             reading_progress: None,
+            last_page_read: None,
+            reading_time_secs: 0,
             user_rating: None,
             user_notes: None,
             local_pdf_path: None,
@@ -216,6 +423,82 @@ impl LibraryPaper {
             last_analyzed_at: None,
         }
     }
+
+    /// Apply a reading progress update.
+    ///
+    /// Without an explicit status, reaching 100% marks the paper completed and
+    /// any progress on an unread paper marks it as being read.
+    pub fn apply_reading_progress(&mut self, update: ReadingProgressUpdate) -> Result<(), String> {
+        let status = update
+            .status
+            .as_deref()
+            .map(normalize_reading_status)
+            .transpose()?;
+        if let Some(percent) = update.progress_percent {
+            if !(0..=100).contains(&percent) {
+                return Err(format!("Invalid reading progress: {}%", percent));
+            }
+        }
+        if let Some(page) = update.last_page_read {
+            if page < 1 {
+                return Err(format!("Invalid last page read: {}", page));
+            }
+        }
+
+        if let Some(percent) = update.progress_percent {
+            self.reading_progress = Some(percent);
+        }
+        if let Some(page) = update.last_page_read {
+            self.last_page_read = Some(page);
+        }
+        if let Some(secs) = update.add_reading_time_secs {
+            self.reading_time_secs = self.reading_time_secs.saturating_add(secs);
+        }
+
+        match status {
+            Some(status) => self.reading_status = status.to_string(),
+            None if update.progress_percent == Some(100) => {
+                self.reading_status = "completed".to_string()
+            }
+            None if self.reading_status == "unread"
+                && (update.progress_percent.is_some_and(|p| p > 0)
+                    || update.last_page_read.is_some()
+                    || update.add_reading_time_secs.is_some_and(|s| s > 0)) =>
+            {
+                self.reading_status = "reading".to_string()
+            }
+            None => {}
+        }
+
+        self.last_accessed_at = Some(chrono::Utc::now().to_rfc3339());
+        Ok(())
+    }
+}
+
+/// Map a reading status to its stored form; `read` is accepted for `completed`
+fn normalize_reading_status(status: &str) -> Result<&'static str, String> {
+    match status.trim().to_lowercase().as_str() {
+        "unread" => Ok("unread"),
+        "reading" => Ok("reading"),
+        "read" | "completed" => Ok("completed"),
+        "archived" => Ok("archived"),
+        other => Err(format!("Invalid reading status: {}", other)),
+    }
+}
+
+/// Reading progress reported by the reader
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingProgressUpdate {
+    /// unread, reading, read (stored as completed) or archived
+    pub status: Option<String>,
+    #[serde(alias = "progress_percent")]
+    pub progress_percent: Option<i32>,
+    #[serde(alias = "last_page_read")]
+    pub last_page_read: Option<i32>,
+    /// Reading time to add to the paper's total
+    #[serde(alias = "add_reading_time_secs")]
+    pub add_reading_time_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -464,6 +747,9 @@ pub struct AggregatedSearchResult {
     pub provider_results: HashMap<String, ProviderSearchResult>,
     pub degraded_providers: HashMap<String, ProviderDegradedInfo>,
     pub search_time_ms: u64,
+    /// Served from the search cache instead of querying providers
+    #[serde(default)]
+    pub cached: bool,
 }
 
 // ============================================================================
@@ -561,6 +847,9 @@ pub struct AcademicStatistics {
     pub papers_read_this_week: i32,
     pub papers_read_this_month: i32,
     pub average_reading_time: Option<i32>,
+    /// Reading time across all papers
+    #[serde(default)]
+    pub total_reading_time_secs: u64,
 
     pub top_authors: Vec<AuthorCount>,
     pub top_venues: Vec<VenueCount>,
@@ -605,6 +894,7 @@ impl Default for AcademicStatistics {
             papers_read_this_week: 0,
             papers_read_this_month: 0,
             average_reading_time: None,
+            total_reading_time_secs: 0,
             top_authors: Vec::new(),
             top_venues: Vec::new(),
             top_keywords: Vec::new(),
@@ -760,6 +1050,73 @@ pub struct PDFConversionResult {
     pub tables: Vec<ExtractedTable>,
     pub equations: Vec<ExtractedEquation>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub status: PdfExtractionStatus,
+    #[serde(default)]
+    pub pages_processed: u32,
+    #[serde(default)]
+    pub total_pages: u32,
+}
+
+/// Whether a PDF extraction ran to the end or was cancelled partway
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfExtractionStatus {
+    #[default]
+    Completed,
+    Cancelled,
+}
+
+/// Payload of the `academic://pdf-progress` event, emitted per processed page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfExtractionProgress {
+    pub operation_id: Option<String>,
+    pub pdf_path: String,
+    pub page: u32,
+    pub total_pages: u32,
+}
+
+/// Result of `academic_index_pdf`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfIndexResult {
+    pub total_pages: u32,
+    /// Number of chunks embedded and upserted into the collection
+    pub chunks_indexed: usize,
+    /// Non-fatal issues, such as an embedding model mismatch
+    pub warnings: Vec<String>,
+}
+
+/// Whether a streamed knowledge map generation ran to the end or was cancelled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KnowledgeMapGenerationStatus {
+    #[default]
+    Completed,
+    Cancelled,
+}
+
+/// Payload of the `knowledge-map://trace` event, emitted per parsed trace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnowledgeMapTraceEvent {
+    pub operation_id: String,
+    pub knowledge_map_id: String,
+    pub index: usize,
+    pub trace: KnowledgeMapTrace,
+}
+
+/// Payload of the `knowledge-map://complete` event and result of a streamed
+/// generation. `knowledge_map` is `None` when the generation was cancelled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnowledgeMapGenerationResult {
+    pub operation_id: String,
+    pub knowledge_map_id: String,
+    pub status: KnowledgeMapGenerationStatus,
+    pub traces_emitted: usize,
+    pub knowledge_map: Option<KnowledgeMap>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +27,9 @@ use crate::screen_recording::timeline_renderer::{
     TimelineRenderTrack, TimelineTransition,
 };
 use crate::screen_recording::TimelineRenderer;
+use crate::selection::{
+    SelectionMacroStep, SelectionManager, SelectionToolbarTheme, ToolbarThemeName,
+};
 
 static MEDIA_CLIPS: Lazy<Mutex<HashMap<String, StoredVideoClip>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
@@ -567,10 +570,19 @@ pub async fn shell_open(url: String) -> Result<(), String> {
     open::that(url).map_err(|e| e.to_string())
 }
 
+/// Switch the selection toolbar to a named theme, keeping custom colors and action styles
 #[tauri::command]
-pub async fn selection_set_theme(theme: String) -> Result<(), String> {
-    log::info!("[compat] selection_set_theme deprecated command called with theme={theme}");
-    Ok(())
+pub async fn selection_set_theme(
+    manager: State<'_, SelectionManager>,
+    theme: String,
+) -> Result<(), String> {
+    let name = ToolbarThemeName::parse(&theme)
+        .ok_or_else(|| format!("Invalid toolbar theme: unknown theme '{theme}'"))?;
+    let toolbar_theme = SelectionToolbarTheme {
+        theme: name,
+        ..manager.get_config().toolbar_theme
+    };
+    manager.set_toolbar_theme(toolbar_theme)
 }
 
 #[tauri::command]
@@ -588,11 +600,21 @@ pub async fn agent_cancel(agent_id: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn selection_replace_text(text: String) -> Result<(), String> {
+pub async fn selection_replace_text(
+    manager: State<'_, SelectionManager>,
+    text: String,
+) -> Result<(), String> {
+    replace_selected_text(text)?;
+    manager.macros.record_step(SelectionMacroStep::Replace);
+    Ok(())
+}
+
+/// Paste `text` over the selection in the focused application, restoring the clipboard afterwards
+pub fn replace_selected_text(text: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
+        use crate::commands::window::clipboard_commands::simulate_paste_shortcut;
         use arboard::Clipboard;
-        use rdev::{simulate, EventType, Key};
         use std::thread;
         use std::time::Duration;
 
@@ -600,13 +622,7 @@ pub async fn selection_replace_text(text: String) -> Result<(), String> {
         let original = clipboard.get_text().ok();
         clipboard.set_text(text).map_err(|e| e.to_string())?;
 
-        simulate(&EventType::KeyPress(Key::ControlLeft)).map_err(|e| format!("{e:?}"))?;
-        thread::sleep(Duration::from_millis(8));
-        simulate(&EventType::KeyPress(Key::KeyV)).map_err(|e| format!("{e:?}"))?;
-        thread::sleep(Duration::from_millis(8));
-        simulate(&EventType::KeyRelease(Key::KeyV)).map_err(|e| format!("{e:?}"))?;
-        thread::sleep(Duration::from_millis(8));
-        simulate(&EventType::KeyRelease(Key::ControlLeft)).map_err(|e| format!("{e:?}"))?;
+        simulate_paste_shortcut()?;
 
         thread::sleep(Duration::from_millis(20));
         if let Some(old) = original {
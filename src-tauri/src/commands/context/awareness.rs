@@ -4,7 +4,8 @@
 
 use crate::awareness::{
     activity_tracker::ActivityStats, ActivityType, AppUsageStats, AwarenessManager, AwarenessState,
-    DailyUsageSummary, FocusSession, Suggestion, SystemState, UserActivity,
+    DailyReport, DailyUsageSummary, FocusExportFormat, FocusExportOptions, FocusSession,
+    Suggestion, SystemState, TagActivityReport, TagPattern, UserActivity,
 };
 use tauri::State;
 
@@ -41,7 +42,13 @@ pub async fn awareness_record_activity(
     application: Option<String>,
     target: Option<String>,
     metadata: Option<std::collections::HashMap<String, String>>,
+    window_title: Option<String>,
 ) -> Result<(), String> {
+    let mut metadata = metadata.unwrap_or_default();
+    if let Some(window_title) = window_title {
+        metadata.insert("window_title".to_string(), window_title);
+    }
+
     let activity = UserActivity {
         activity_type: parse_activity_type(&activity_type),
         description,
@@ -49,7 +56,8 @@ pub async fn awareness_record_activity(
         target,
         timestamp: chrono::Utc::now().timestamp_millis(),
         duration_ms: None,
-        metadata: metadata.unwrap_or_default(),
+        metadata,
+        tags: Vec::new(),
     };
 
     manager.record_activity(activity);
@@ -198,6 +206,28 @@ pub async fn awareness_get_daily_usage_summary(
     Ok(manager.get_daily_usage_summary(&date))
 }
 
+/// Generate an end-of-day report (optionally with a Markdown rendering)
+#[tauri::command]
+pub async fn awareness_generate_daily_report(
+    manager: State<'_, AwarenessManager>,
+    date: String,
+    include_markdown: Option<bool>,
+) -> Result<DailyReport, String> {
+    manager.generate_daily_report(&date, include_markdown.unwrap_or(true))
+}
+
+/// Export focus sessions in a time range as iCalendar or JSON calendar events
+#[tauri::command]
+pub async fn awareness_export_focus_sessions(
+    manager: State<'_, AwarenessManager>,
+    start_ms: i64,
+    end_ms: i64,
+    format: FocusExportFormat,
+    options: Option<FocusExportOptions>,
+) -> Result<String, String> {
+    manager.export_focus_sessions(start_ms, end_ms, format, &options.unwrap_or_default())
+}
+
 /// Clear focus history
 #[tauri::command]
 pub async fn awareness_clear_focus_history(
@@ -238,6 +268,35 @@ pub async fn awareness_get_activities_by_application(
     Ok(manager.get_activities_by_application(&app_name))
 }
 
+/// Get activities and focus time for a tag, e.g. `project:cognia` or `github.com`
+#[tauri::command]
+pub async fn awareness_search_activities_by_tag(
+    manager: State<'_, AwarenessManager>,
+    tag: String,
+) -> Result<TagActivityReport, String> {
+    if tag.trim().is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    Ok(manager.search_activities_by_tag(&tag))
+}
+
+/// Get the patterns used to tag activities from window titles
+#[tauri::command]
+pub async fn awareness_get_tag_patterns(
+    manager: State<'_, AwarenessManager>,
+) -> Result<Vec<TagPattern>, String> {
+    Ok(manager.get_tag_patterns())
+}
+
+/// Replace the patterns used to tag activities from window titles
+#[tauri::command]
+pub async fn awareness_set_tag_patterns(
+    manager: State<'_, AwarenessManager>,
+    patterns: Vec<TagPattern>,
+) -> Result<(), String> {
+    manager.set_tag_patterns(patterns)
+}
+
 /// Get activity statistics
 #[tauri::command]
 pub async fn awareness_get_activity_stats(
@@ -252,8 +311,7 @@ pub async fn awareness_set_activity_tracking_enabled(
     manager: State<'_, AwarenessManager>,
     enabled: bool,
 ) -> Result<(), String> {
-    manager.set_activity_tracking_enabled(enabled);
-    Ok(())
+    manager.set_activity_tracking_enabled(enabled)
 }
 
 /// Check if activity tracking is enabled
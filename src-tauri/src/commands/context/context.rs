@@ -4,13 +4,16 @@
 
 use crate::commands::media::ocr::OcrState;
 use crate::context::{
-    AppContext, BrowserContext, ContextManager, EditorContext, FileContext, FullContext,
-    ScreenContent, UiElement, WindowInfo,
+    ActiveDocument, AppContext, BrowserContext, ContextManager, ContextStatus, DocumentSelection,
+    EditorContext, FileChangeSink, FileContext, FullContext, LayoutRestoreReport, ScreenContent,
+    UiElement, WindowInfo, WindowLayout, FILE_CHANGED_EVENT,
 };
 use crate::screenshot::ocr_provider::DocumentHint;
 use crate::screenshot::{OcrOptions, OcrProviderType, ScreenshotCapture, UnifiedOcrResult};
+use crate::selection::SelectionManager;
 use log::warn;
-use tauri::State;
+use std::sync::Arc;
+use tauri::{Emitter, Manager, State};
 
 async fn extract_ocr_result(
     ocr_state: &State<'_, OcrState>,
@@ -77,6 +80,41 @@ pub async fn context_get_file(manager: State<'_, ContextManager>) -> Result<File
     manager.get_file_context()
 }
 
+/// Watch a file for on-disk changes, emitting `context://file-changed` events.
+///
+/// Returns the canonical path being watched. The watch is dropped automatically
+/// once the file is no longer open in any window.
+#[tauri::command]
+pub async fn context_watch_file(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ContextManager>,
+    path: String,
+) -> Result<String, String> {
+    let sink: FileChangeSink = Arc::new(move |event| {
+        if let Err(e) = app_handle.emit(FILE_CHANGED_EVENT, &event) {
+            warn!("Failed to emit file change for {}: {}", event.path, e);
+        }
+    });
+    manager.get_file_watcher().watch(&path, sink)
+}
+
+/// Stop watching a file. Returns whether it was being watched.
+#[tauri::command]
+pub async fn context_unwatch_file(
+    manager: State<'_, ContextManager>,
+    path: String,
+) -> Result<bool, String> {
+    Ok(manager.get_file_watcher().unwatch(&path))
+}
+
+/// List files currently watched for changes
+#[tauri::command]
+pub async fn context_get_watched_files(
+    manager: State<'_, ContextManager>,
+) -> Result<Vec<String>, String> {
+    Ok(manager.get_file_watcher().watched_files())
+}
+
 /// Get browser context
 #[tauri::command]
 pub async fn context_get_browser(
@@ -102,6 +140,23 @@ pub async fn context_get_all_windows(
     manager.get_all_windows()
 }
 
+/// Capture the position and size of all visible windows
+#[tauri::command]
+pub async fn context_snapshot_layout(
+    manager: State<'_, ContextManager>,
+) -> Result<WindowLayout, String> {
+    manager.snapshot_layout()
+}
+
+/// Move windows back to a captured layout, reporting which were repositioned
+#[tauri::command]
+pub async fn context_restore_layout(
+    manager: State<'_, ContextManager>,
+    snapshot: WindowLayout,
+) -> Result<LayoutRestoreReport, String> {
+    manager.restore_layout(&snapshot)
+}
+
 /// Clear context cache
 #[tauri::command]
 pub async fn context_clear_cache(manager: State<'_, ContextManager>) -> Result<(), String> {
@@ -117,6 +172,24 @@ pub async fn context_get_editor(
     manager.get_editor_context()
 }
 
+/// Get the focused editor's file, language, contents, selection, and cursor in one call
+#[tauri::command]
+pub async fn context_get_active_document(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, ContextManager>,
+) -> Result<ActiveDocument, String> {
+    // Latest selection seen by the selection service; only used if it came from the same window
+    let selection = app_handle
+        .try_state::<SelectionManager>()
+        .and_then(|selection| selection.history.get_recent(1).into_iter().next())
+        .map(|entry| DocumentSelection {
+            text: entry.text,
+            window_title: entry.window_title,
+            timestamp: entry.timestamp,
+        });
+    manager.get_active_document(selection)
+}
+
 /// Check if the current context is a code editor
 #[tauri::command]
 pub async fn context_is_code_editor(manager: State<'_, ContextManager>) -> Result<bool, String> {
@@ -160,6 +233,14 @@ pub async fn context_get_cache_duration(manager: State<'_, ContextManager>) -> R
     Ok(manager.get_cache_duration())
 }
 
+/// Get context manager status, including whether it is degraded by missing permissions
+#[tauri::command]
+pub async fn context_get_status(
+    manager: State<'_, ContextManager>,
+) -> Result<ContextStatus, String> {
+    Ok(manager.get_status())
+}
+
 /// Analyze UI using Windows UI Automation
 #[tauri::command]
 pub async fn context_analyze_ui_automation(
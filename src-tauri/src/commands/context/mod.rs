@@ -5,3 +5,4 @@
 pub mod awareness;
 #[allow(clippy::module_inception)]
 pub mod context;
+pub mod recent;
@@ -0,0 +1,310 @@
+//! Recent items Tauri commands
+//!
+//! Merges the history subsystems (selections, clipboard, screenshots, sandbox
+//! executions, focus changes) into a single time-sorted activity feed.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::awareness::{AwarenessManager, FocusSession};
+use crate::sandbox::{ExecutionRecord, SandboxState};
+use crate::screenshot::{ScreenshotHistoryEntry, ScreenshotManager};
+use crate::selection::{ClipboardEntry, SelectionHistoryEntry, SelectionManager};
+
+/// Maximum preview length in characters
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// Subsystem a recent item comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecentItemSource {
+    Selection,
+    Clipboard,
+    Screenshot,
+    SandboxExecution,
+    FocusChange,
+}
+
+impl RecentItemSource {
+    pub const ALL: [RecentItemSource; 5] = [
+        RecentItemSource::Selection,
+        RecentItemSource::Clipboard,
+        RecentItemSource::Screenshot,
+        RecentItemSource::SandboxExecution,
+        RecentItemSource::FocusChange,
+    ];
+}
+
+/// A single entry in the recent items feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentItem {
+    /// Source subsystem
+    pub source: RecentItemSource,
+    /// ID of the item within its source
+    pub id: String,
+    /// Timestamp in milliseconds
+    pub timestamp: i64,
+    /// Short label (app, language, capture mode, ...)
+    pub title: String,
+    /// Single-line preview of the content
+    pub preview: String,
+}
+
+/// A page of the recent items feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentItemsPage {
+    pub items: Vec<RecentItem>,
+    pub offset: usize,
+    pub limit: usize,
+    /// Whether more items exist past this page
+    pub has_more: bool,
+}
+
+/// Collapse whitespace and truncate to a single-line preview
+fn make_preview(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() > PREVIEW_MAX_CHARS {
+        let truncated: String = collapsed.chars().take(PREVIEW_MAX_CHARS).collect();
+        format!("{}...", truncated)
+    } else {
+        collapsed
+    }
+}
+
+impl From<SelectionHistoryEntry> for RecentItem {
+    fn from(entry: SelectionHistoryEntry) -> Self {
+        Self {
+            source: RecentItemSource::Selection,
+            id: entry.id,
+            timestamp: entry.timestamp,
+            title: entry.app_name.unwrap_or_else(|| "Selection".to_string()),
+            preview: make_preview(&entry.text),
+        }
+    }
+}
+
+impl From<ClipboardEntry> for RecentItem {
+    fn from(entry: ClipboardEntry) -> Self {
+        Self {
+            source: RecentItemSource::Clipboard,
+            id: entry.id,
+            timestamp: entry.timestamp,
+            title: entry
+                .label
+                .or(entry.source_app)
+                .unwrap_or_else(|| format!("{:?}", entry.content_type)),
+            preview: make_preview(&entry.preview),
+        }
+    }
+}
+
+impl From<ScreenshotHistoryEntry> for RecentItem {
+    fn from(entry: ScreenshotHistoryEntry) -> Self {
+        let preview = match &entry.ocr_text {
+            Some(text) if !text.trim().is_empty() => make_preview(text),
+            _ => format!("{}x{} {}", entry.width, entry.height, entry.mode),
+        };
+        Self {
+            source: RecentItemSource::Screenshot,
+            id: entry.id,
+            timestamp: entry.timestamp,
+            title: entry
+                .label
+                .or(entry.window_title)
+                .unwrap_or_else(|| entry.mode.clone()),
+            preview,
+        }
+    }
+}
+
+impl From<ExecutionRecord> for RecentItem {
+    fn from(record: ExecutionRecord) -> Self {
+        Self {
+            source: RecentItemSource::SandboxExecution,
+            id: record.id,
+            timestamp: record.created_at.timestamp_millis(),
+            title: format!("{} ({:?})", record.language, record.status),
+            preview: make_preview(&record.code),
+        }
+    }
+}
+
+impl From<FocusSession> for RecentItem {
+    fn from(session: FocusSession) -> Self {
+        Self {
+            source: RecentItemSource::FocusChange,
+            id: format!("{}-{}", session.process_name, session.start_time),
+            timestamp: session.start_time,
+            title: session.app_name,
+            preview: make_preview(&session.window_title),
+        }
+    }
+}
+
+/// Merge per-source items (each newest first) into one page of the feed
+fn paginate(mut items: Vec<RecentItem>, offset: usize, limit: usize) -> RecentItemsPage {
+    items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    let has_more = items.len() > offset.saturating_add(limit);
+    let items = items.into_iter().skip(offset).take(limit).collect();
+    RecentItemsPage {
+        items,
+        offset,
+        limit,
+        has_more,
+    }
+}
+
+/// Get a merged, time-sorted feed of recent activity across history subsystems
+///
+/// `sources` restricts the feed to the given subsystems (all by default).
+/// Sandbox executions are skipped while the sandbox is still initializing.
+#[tauri::command]
+pub async fn recent_items(
+    app: AppHandle,
+    selection: State<'_, SelectionManager>,
+    screenshot: State<'_, ScreenshotManager>,
+    awareness: State<'_, AwarenessManager>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    sources: Option<Vec<RecentItemSource>>,
+) -> Result<RecentItemsPage, String> {
+    let limit = limit.unwrap_or(20);
+    let offset = offset.unwrap_or(0);
+    let sources = sources.unwrap_or_else(|| RecentItemSource::ALL.to_vec());
+    // Each source is sorted newest first, so this many per source covers the page
+    // plus one extra item to detect `has_more`.
+    let per_source = offset.saturating_add(limit).saturating_add(1);
+
+    let mut items = Vec::new();
+    for source in sources {
+        match source {
+            RecentItemSource::Selection => items.extend(
+                selection
+                    .history
+                    .get_recent(per_source)
+                    .into_iter()
+                    .map(RecentItem::from),
+            ),
+            RecentItemSource::Clipboard => items.extend(
+                selection
+                    .clipboard_history
+                    .get_recent(per_source)
+                    .into_iter()
+                    .map(RecentItem::from),
+            ),
+            RecentItemSource::Screenshot => items.extend(
+                screenshot
+                    .get_history(per_source)
+                    .into_iter()
+                    .map(RecentItem::from),
+            ),
+            RecentItemSource::SandboxExecution => {
+                let Some(sandbox) = app.try_state::<SandboxState>() else {
+                    log::debug!("Sandbox not initialized; skipping executions in recent items");
+                    continue;
+                };
+                match sandbox
+                    .get_recent_executions(u32::try_from(per_source).unwrap_or(u32::MAX))
+                    .await
+                {
+                    Ok(records) => items.extend(records.into_iter().map(RecentItem::from)),
+                    Err(e) => log::warn!("Failed to load sandbox executions: {}", e),
+                }
+            }
+            RecentItemSource::FocusChange => items.extend(
+                awareness
+                    .get_recent_focus_sessions(per_source)
+                    .into_iter()
+                    .map(RecentItem::from),
+            ),
+        }
+    }
+
+    Ok(paginate(items, offset, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(source: RecentItemSource, id: &str, timestamp: i64) -> RecentItem {
+        RecentItem {
+            source,
+            id: id.to_string(),
+            timestamp,
+            title: String::new(),
+            preview: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_make_preview() {
+        assert_eq!(make_preview("  hello\n\n  world\t"), "hello world");
+
+        let long = "a".repeat(PREVIEW_MAX_CHARS + 10);
+        let preview = make_preview(&long);
+        assert_eq!(preview.chars().count(), PREVIEW_MAX_CHARS + 3);
+        assert!(preview.ends_with("..."));
+
+        // Multi-byte text is truncated on character boundaries
+        let cjk = "文".repeat(PREVIEW_MAX_CHARS + 1);
+        assert!(make_preview(&cjk).starts_with("文文"));
+    }
+
+    #[test]
+    fn test_paginate_merges_by_time() {
+        let items = vec![
+            item(RecentItemSource::Selection, "s1", 300),
+            item(RecentItemSource::Selection, "s2", 100),
+            item(RecentItemSource::Clipboard, "c1", 400),
+            item(RecentItemSource::FocusChange, "f1", 200),
+        ];
+
+        let page = paginate(items.clone(), 0, 2);
+        let ids: Vec<_> = page.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["c1", "s1"]);
+        assert!(page.has_more);
+
+        let page = paginate(items, 2, 2);
+        let ids: Vec<_> = page.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["f1", "s2"]);
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_paginate_offset_past_end() {
+        let page = paginate(vec![item(RecentItemSource::Screenshot, "x", 1)], 5, 10);
+        assert!(page.items.is_empty());
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_focus_session_conversion() {
+        let session = FocusSession {
+            app_name: "Editor".to_string(),
+            process_name: "code".to_string(),
+            window_title: "main.rs  -  project".to_string(),
+            start_time: 42,
+            end_time: None,
+            duration_ms: 0,
+            is_active: true,
+        };
+
+        let item = RecentItem::from(session);
+        assert_eq!(item.source, RecentItemSource::FocusChange);
+        assert_eq!(item.id, "code-42");
+        assert_eq!(item.title, "Editor");
+        assert_eq!(item.preview, "main.rs - project");
+    }
+
+    #[test]
+    fn test_source_serialization() {
+        assert_eq!(
+            serde_json::to_value(RecentItemSource::SandboxExecution).unwrap(),
+            "sandbox_execution"
+        );
+        let source: RecentItemSource =
+            serde_json::from_value(serde_json::json!("focus_change")).unwrap();
+        assert_eq!(source, RecentItemSource::FocusChange);
+    }
+}
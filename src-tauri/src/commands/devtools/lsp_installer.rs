@@ -252,6 +252,7 @@ async fn download_vsix(
     task_id: &str,
     language_id: Option<&str>,
 ) -> Result<PathBuf, String> {
+    crate::http::ensure_online()?;
     ensure_lsp_dirs(app)?;
     let downloads_dir = lsp_downloads_dir(app)?;
     let file_name = format!(
@@ -264,6 +264,7 @@ async fn query_marketplace(payload: &Value) -> Result<Vec<LspRegistryEntry>, Str
 pub async fn registry_search(
     request: LspRegistrySearchRequest,
 ) -> Result<Vec<LspRegistryEntry>, String> {
+    crate::http::ensure_online()?;
     let providers = request.providers.unwrap_or_else(default_providers);
     let page_number = request.page_number.unwrap_or(1);
     let page_size = request.page_size.unwrap_or(20).clamp(1, 100);
@@ -358,6 +359,7 @@ pub async fn resolve_download_asset(
     version: Option<&str>,
     provider: &LspProvider,
 ) -> Result<RegistryDownloadAsset, String> {
+    crate::http::ensure_online()?;
     match provider {
         LspProvider::OpenVsx => {
             let metadata = fetch_openvsx_extension_details(extension_id, version).await?;
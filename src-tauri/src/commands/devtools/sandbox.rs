@@ -4,15 +4,25 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::sandbox::{
-    CodeSnippet, CompilerSettings, ExecutionFilter, ExecutionRecord, ExecutionRequest,
-    ExecutionResult, ExecutionSession, ImportResult, Language, LanguageStats, OutputLine,
-    RuntimeType, SandboxConfig, SandboxPreflightResult, SandboxState, SandboxStats, SnippetFilter,
+    get_language_config, BenchmarkRecord, CodeSnippet, CompilerSettings, ExecutionFilter,
+    ExecutionRecord, ExecutionRequest, ExecutionResult, ExecutionSession, HighlightToken,
+    ImportMergeStrategy, ImportResult, IntegrityReport, Language, LanguageStats, OutputLine,
+    RepairReport, RuntimeDescriptions, RuntimeType, SandboxConfig, SandboxPreflightResult,
+    SandboxState, SandboxStats, SnippetFilter, SyntaxCheckResult, TimeoutWarning,
 };
+use crate::selection::{SelectionManager, TextAnalyzer};
 use chrono::{DateTime, Utc};
 
+/// Emitted with the execution id when a selection starts running
+const SELECTION_STARTED_EVENT: &str = "sandbox://selection-started";
+/// Emitted with the `SelectionExecution` of a shortcut-triggered run
+const SELECTION_RESULT_EVENT: &str = "sandbox://selection-result";
+/// Emitted when a shortcut-triggered run could not start or failed
+const SELECTION_ERROR_EVENT: &str = "sandbox://selection-error";
+
 /// Execution request from frontend
 #[derive(Debug, Deserialize)]
 pub struct ExecuteCodeRequest {
@@ -43,6 +53,9 @@ pub struct ExecuteCodeRequest {
     pub policy_profile: Option<String>,
     /// Compiler/interpreter settings
     pub compiler_settings: Option<CompilerSettings>,
+    /// Also return stdout/stderr with ANSI colors rendered as HTML
+    #[serde(default, alias = "renderAnsi")]
+    pub render_ansi: bool,
 }
 
 /// Preflight request from frontend
@@ -78,12 +91,32 @@ pub struct SandboxStatus {
     pub config: SandboxConfig,
 }
 
+/// Result of running the text selection
+#[derive(Debug, Serialize)]
+pub struct SelectionExecution {
+    /// Sandbox language the selection ran as
+    pub language: String,
+    /// Whether the language was detected from the selection rather than given
+    pub detected: bool,
+    pub result: ExecutionResult,
+}
+
+/// Attach HTML-rendered output when the caller asked for it
+fn render_output(result: ExecutionResult, render_ansi: bool) -> ExecutionResult {
+    if render_ansi {
+        result.with_rendered_ansi()
+    } else {
+        result
+    }
+}
+
 /// Execute code in sandbox
 #[tauri::command]
 pub async fn sandbox_execute(
     request: ExecuteCodeRequest,
     state: State<'_, SandboxState>,
 ) -> Result<ExecutionResult, String> {
+    let render_ansi = request.render_ansi;
     let exec_request = ExecutionRequest {
         id: uuid::Uuid::new_v4().to_string(),
         language: request.language,
@@ -101,7 +134,11 @@ pub async fn sandbox_execute(
         compiler_settings: request.compiler_settings,
     };
 
-    state.execute(exec_request).await.map_err(|e| e.to_string())
+    state
+        .execute(exec_request)
+        .await
+        .map(|result| render_output(result, render_ansi))
+        .map_err(|e| e.to_string())
 }
 
 /// Cancel a running execution
@@ -116,8 +153,24 @@ pub async fn sandbox_cancel_execution(
         .map_err(|e| e.to_string())
 }
 
+/// Extend the time limit of an in-flight streaming execution.
+/// Returns the new limit in seconds, or null if the execution is no longer running.
+#[tauri::command]
+pub async fn sandbox_extend_timeout(
+    execution_id: String,
+    extra_secs: u64,
+    state: State<'_, SandboxState>,
+) -> Result<Option<u64>, String> {
+    state
+        .extend_timeout(&execution_id, extra_secs)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Execute code with streaming output via Tauri events
-/// Emits "sandbox-output-line" events for each line of stdout/stderr.
+/// Emits "sandbox-output-line" events for each line of stdout/stderr, and a
+/// "sandbox://timeout-warning" event once the configured fraction of the time
+/// limit has elapsed. The hard limit can be pushed out with `sandbox_extend_timeout`.
 /// Returns the final ExecutionResult when execution completes.
 #[tauri::command]
 pub async fn sandbox_execute_streaming(
@@ -125,6 +178,7 @@ pub async fn sandbox_execute_streaming(
     app: AppHandle,
     state: State<'_, SandboxState>,
 ) -> Result<ExecutionResult, String> {
+    let render_ansi = request.render_ansi;
     let exec_request = ExecutionRequest {
         id: uuid::Uuid::new_v4().to_string(),
         language: request.language,
@@ -142,6 +196,17 @@ pub async fn sandbox_execute_streaming(
         compiler_settings: request.compiler_settings,
     };
 
+    execute_with_events(&app, &state, exec_request)
+        .await
+        .map(|result| render_output(result, render_ansi))
+}
+
+/// Run a request, forwarding output lines and timeout warnings as events
+async fn execute_with_events(
+    app: &AppHandle,
+    state: &SandboxState,
+    request: ExecutionRequest,
+) -> Result<ExecutionResult, String> {
     let (tx, mut rx) = tokio::sync::mpsc::channel::<OutputLine>(256);
 
     // Spawn a task to forward output lines to the frontend via Tauri events
@@ -152,12 +217,206 @@ pub async fn sandbox_execute_streaming(
         }
     });
 
+    let (warning_tx, mut warning_rx) = tokio::sync::mpsc::channel::<TimeoutWarning>(4);
+    let app_handle = app.clone();
+    tokio::spawn(async move {
+        while let Some(warning) = warning_rx.recv().await {
+            let _ = app_handle.emit("sandbox://timeout-warning", &warning);
+        }
+    });
+
     state
-        .execute_streaming(exec_request, tx)
+        .execute_streaming(request, tx, Some(warning_tx))
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Run the current text selection in the sandbox
+///
+/// Uses the text captured by the selection toolbar, or reads the selection from
+/// the focused application. The language is detected from the code unless
+/// given; output streams as "sandbox-output-line" events. Limits and network
+/// access follow the sandbox configuration.
+#[tauri::command]
+pub async fn sandbox_execute_selection(
+    language: Option<String>,
+    app: AppHandle,
+    state: State<'_, SandboxState>,
+) -> Result<SelectionExecution, String> {
+    let code = read_selection(&app, true).await?;
+    run_selection(&app, &state, code, language).await
+}
+
+/// Selected text, optionally preferring what the toolbar captured over a fresh read
+async fn read_selection(app: &AppHandle, prefer_toolbar: bool) -> Result<String, String> {
+    let app = app.clone();
+    let text = tokio::task::spawn_blocking(move || {
+        let manager = app
+            .try_state::<SelectionManager>()
+            .ok_or_else(|| "Selection manager is not initialized".to_string())?;
+        let captured = prefer_toolbar
+            .then(|| manager.toolbar_window.get_selected_text())
+            .flatten()
+            .filter(|text| !text.trim().is_empty());
+        match captured {
+            Some(text) => Ok(Some(text)),
+            None => manager.detector.get_selected_text(),
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to read selection: {}", e))??;
+
+    text.filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| "No text is selected".to_string())
+}
+
+/// Pick the sandbox language for a selection: the given one, else the detected one
+///
+/// Returns the canonical language id and whether it was detected.
+fn resolve_selection_language(
+    code: &str,
+    language: Option<&str>,
+    enabled_languages: &[String],
+) -> Result<(String, bool), String> {
+    let (requested, detected) = match language.map(str::trim).filter(|l| !l.is_empty()) {
+        Some(language) => (language.to_string(), false),
+        None => {
+            let detected = TextAnalyzer::new().detect_language(code).ok_or_else(|| {
+                "Could not detect the language of the selection; specify one".to_string()
+            })?;
+            (detected, true)
+        }
+    };
+
+    let config = get_language_config(&requested)
+        .ok_or_else(|| format!("Language not supported: {}", requested))?;
+    if !enabled_languages.iter().any(|enabled| enabled == config.id) {
+        return Err(format!(
+            "Language '{}' is disabled in sandbox settings",
+            config.id
+        ));
+    }
+    Ok((config.id.to_string(), detected))
+}
+
+async fn run_selection(
+    app: &AppHandle,
+    state: &SandboxState,
+    code: String,
+    language: Option<String>,
+) -> Result<SelectionExecution, String> {
+    let enabled_languages = state.config.read().await.enabled_languages.clone();
+    let (language, detected) =
+        resolve_selection_language(&code, language.as_deref(), &enabled_languages)?;
+
+    // Network access, timeout and memory limits come from the sandbox config
+    let request = ExecutionRequest::new(language.clone(), code);
+    let _ = app.emit(
+        SELECTION_STARTED_EVENT,
+        serde_json::json!({
+            "execution_id": request.id,
+            "language": language,
+            "detected": detected,
+        }),
+    );
+    log::info!(
+        "[Sandbox] Running selection as {} ({})",
+        language,
+        if detected { "detected" } else { "given" }
+    );
+
+    let result = execute_with_events(app, state, request).await?;
+    Ok(SelectionExecution {
+        language,
+        detected,
+        result,
+    })
+}
+
+/// Run the selection of the focused application and report through events
+#[cfg_attr(mobile, allow(dead_code))]
+async fn run_selection_from_shortcut(app: AppHandle) {
+    let Some(state) = app.try_state::<SandboxState>() else {
+        log::warn!("[Sandbox] Run selection shortcut pressed before the sandbox initialized");
+        return;
+    };
+    let outcome = match read_selection(&app, false).await {
+        Ok(code) => run_selection(&app, &state, code, None).await,
+        Err(e) => Err(e),
+    };
+    match outcome {
+        Ok(execution) => {
+            let _ = app.emit(SELECTION_RESULT_EVENT, &execution);
+        }
+        Err(error) => {
+            log::warn!("[Sandbox] Run selection failed: {}", error);
+            let _ = app.emit(SELECTION_ERROR_EVENT, serde_json::json!({ "error": error }));
+        }
+    }
+}
+
+/// Bind the "run selection" global shortcut, replacing the `previous` binding
+#[cfg(not(mobile))]
+pub fn bind_execute_selection_shortcut(
+    app: &AppHandle,
+    previous: Option<&str>,
+    shortcut: Option<&str>,
+) -> Result<(), String> {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+    let global_shortcut = app.global_shortcut();
+    if let Some(previous) = previous.and_then(|p| p.parse::<Shortcut>().ok()) {
+        if let Err(e) = global_shortcut.unregister(previous) {
+            log::warn!(
+                "[Sandbox] Failed to unregister run selection shortcut: {}",
+                e
+            );
+        }
+    }
+
+    let Some(shortcut) = shortcut else {
+        return Ok(());
+    };
+    let parsed: Shortcut = shortcut
+        .parse()
+        .map_err(|e| format!("Invalid shortcut '{}': {}", shortcut, e))?;
+    global_shortcut
+        .on_shortcut(parsed, |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                tauri::async_runtime::spawn(run_selection_from_shortcut(app.clone()));
+            }
+        })
+        .map_err(|e| format!("Failed to register shortcut '{}': {}", shortcut, e))?;
+    log::info!(
+        "Global shortcut registered: {} for running the selection in the sandbox",
+        shortcut
+    );
+    Ok(())
+}
+
+#[cfg(mobile)]
+/// Global shortcuts are unsupported on mobile (no-op fallback)
+pub fn bind_execute_selection_shortcut(
+    _app: &AppHandle,
+    _previous: Option<&str>,
+    _shortcut: Option<&str>,
+) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(not(mobile))]
+fn validate_shortcut(shortcut: &str) -> Result<(), String> {
+    shortcut
+        .parse::<tauri_plugin_global_shortcut::Shortcut>()
+        .map(|_| ())
+        .map_err(|e| format!("Invalid shortcut '{}': {}", shortcut, e))
+}
+
+#[cfg(mobile)]
+fn validate_shortcut(_shortcut: &str) -> Result<(), String> {
+    Ok(())
+}
+
 /// Get sandbox status
 #[tauri::command]
 pub async fn sandbox_get_status(state: State<'_, SandboxState>) -> Result<SandboxStatus, String> {
@@ -238,9 +497,25 @@ pub async fn sandbox_get_config(state: State<'_, SandboxState>) -> Result<Sandbo
 #[tauri::command]
 pub async fn sandbox_update_config(
     config: SandboxConfig,
+    app: AppHandle,
     state: State<'_, SandboxState>,
 ) -> Result<(), String> {
-    state.update_config(config).await.map_err(|e| e.to_string())
+    if let Some(shortcut) = config.execute_selection_shortcut.as_deref() {
+        validate_shortcut(shortcut)?;
+    }
+    let previous_shortcut = state.config.read().await.execute_selection_shortcut.clone();
+    let shortcut = config.execute_selection_shortcut.clone();
+
+    state
+        .update_config(config)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if previous_shortcut != shortcut {
+        bind_execute_selection_shortcut(&app, previous_shortcut.as_deref(), shortcut.as_deref())
+            .map_err(|e| format!("Configuration saved, but the shortcut was not bound: {}", e))?;
+    }
+    Ok(())
 }
 
 /// Get available runtimes
@@ -268,6 +543,17 @@ pub async fn sandbox_check_runtime(
     Ok(state.is_runtime_available(runtime).await)
 }
 
+/// Describe each runtime's availability and isolation capabilities on this host
+///
+/// Probe results are cached; pass `refresh` to probe again.
+#[tauri::command]
+pub async fn sandbox_describe_runtimes(
+    refresh: Option<bool>,
+    state: State<'_, SandboxState>,
+) -> Result<RuntimeDescriptions, String> {
+    Ok(state.describe_runtimes(refresh.unwrap_or(false)).await)
+}
+
 /// Prepare/pull image for a language (container runtimes)
 #[tauri::command]
 pub async fn sandbox_prepare_language(
@@ -280,6 +566,32 @@ pub async fn sandbox_prepare_language(
         .map_err(|e| e.to_string())
 }
 
+/// Check code for errors without executing it (rustc --emit=metadata, tsc --noEmit, pyflakes)
+#[tauri::command]
+pub async fn sandbox_check_syntax(
+    language: String,
+    code: String,
+    state: State<'_, SandboxState>,
+) -> Result<SyntaxCheckResult, String> {
+    state
+        .check_syntax(&language, code)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tokenize code for syntax-highlighted display.
+///
+/// Unsupported languages return the whole code as a single plain token.
+#[tauri::command]
+pub async fn sandbox_highlight(
+    language: String,
+    code: String,
+) -> Result<Vec<HighlightToken>, String> {
+    tokio::task::spawn_blocking(move || crate::sandbox::highlight(&language, &code))
+        .await
+        .map_err(|e| format!("Highlight task failed: {}", e))
+}
+
 /// Quick execute - simplified execution for common use cases
 #[tauri::command]
 pub async fn sandbox_quick_execute(
@@ -747,14 +1059,18 @@ pub async fn sandbox_export_data(state: State<'_, SandboxState>) -> Result<Strin
     state.export_data().await.map_err(|e| e.to_string())
 }
 
-/// Import sandbox data from JSON (merges with existing, skips duplicates)
+/// Import sandbox data from an export blob.
+///
+/// `merge_strategy` defaults to merge (skip existing ids); `overwrite` replaces
+/// records with matching ids and `replace_all` clears existing data first.
 #[tauri::command]
 pub async fn sandbox_import_data(
     json_data: String,
+    merge_strategy: Option<ImportMergeStrategy>,
     state: State<'_, SandboxState>,
 ) -> Result<ImportResult, String> {
     state
-        .import_data(&json_data)
+        .import_data(&json_data, merge_strategy.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -811,6 +1127,28 @@ pub async fn sandbox_vacuum_db(state: State<'_, SandboxState>) -> Result<(), Str
     state.vacuum_db().await.map_err(|e| e.to_string())
 }
 
+/// Run an integrity check on the database and report any problems
+#[tauri::command]
+pub async fn sandbox_check_integrity(
+    state: State<'_, SandboxState>,
+) -> Result<IntegrityReport, String> {
+    state.check_db_integrity().await.map_err(|e| e.to_string())
+}
+
+/// Rebuild a corrupt database, keeping the original as a backup
+///
+/// A healthy database is left alone unless `force` is set.
+#[tauri::command]
+pub async fn sandbox_repair_db(
+    state: State<'_, SandboxState>,
+    force: Option<bool>,
+) -> Result<RepairReport, String> {
+    state
+        .repair_db(force.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Execute code with history tracking options
 #[tauri::command]
 pub async fn sandbox_execute_with_options(
@@ -819,6 +1157,7 @@ pub async fn sandbox_execute_with_options(
     save_to_history: bool,
     state: State<'_, SandboxState>,
 ) -> Result<ExecutionResult, String> {
+    let render_ansi = request.render_ansi;
     let exec_request = ExecutionRequest {
         id: uuid::Uuid::new_v4().to_string(),
         language: request.language,
@@ -839,6 +1178,57 @@ pub async fn sandbox_execute_with_options(
     state
         .execute_with_history(exec_request, &tags, save_to_history)
         .await
+        .map(|result| render_output(result, render_ansi))
+        .map_err(|e| e.to_string())
+}
+
+/// Run code repeatedly and report timing statistics
+#[tauri::command]
+pub async fn sandbox_benchmark(
+    request: ExecuteCodeRequest,
+    iterations: u32,
+    warmups: Option<u32>,
+    save_to_history: Option<bool>,
+    state: State<'_, SandboxState>,
+) -> Result<BenchmarkRecord, String> {
+    let exec_request = ExecutionRequest {
+        id: uuid::Uuid::new_v4().to_string(),
+        language: request.language,
+        code: request.code,
+        stdin: request.stdin,
+        args: request.args,
+        env: request.env,
+        timeout_secs: request.timeout_secs,
+        memory_limit_mb: request.memory_limit_mb,
+        cpu_limit_percent: None,
+        runtime: request.runtime,
+        files: request.files,
+        network_enabled: request.network_enabled,
+        policy_profile: request.policy_profile,
+        compiler_settings: request.compiler_settings,
+    };
+
+    state
+        .benchmark(
+            exec_request,
+            iterations,
+            warmups.unwrap_or(0),
+            save_to_history.unwrap_or(true),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List saved benchmark runs, newest first
+#[tauri::command]
+pub async fn sandbox_list_benchmarks(
+    language: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, SandboxState>,
+) -> Result<Vec<BenchmarkRecord>, String> {
+    state
+        .list_benchmarks(language.as_deref(), limit)
+        .await
         .map_err(|e| e.to_string())
 }
 
@@ -888,6 +1278,7 @@ mod tests {
             network_enabled: Some(false),
             policy_profile: None,
             compiler_settings: None,
+            render_ansi: false,
         };
 
         assert_eq!(request.language, "javascript");
@@ -995,6 +1386,36 @@ mod tests {
         assert!(request.is_template);
     }
 
+    #[test]
+    fn test_execute_code_request_render_ansi() {
+        let json = json!({
+            "language": "python",
+            "code": "print('\\x1b[32mok\\x1b[0m')",
+            "renderAnsi": true
+        });
+
+        let request: ExecuteCodeRequest = serde_json::from_value(json).unwrap();
+        assert!(request.render_ansi);
+
+        let result = render_output(
+            ExecutionResult::success(
+                "id".to_string(),
+                "\u{1b}[32mok\u{1b}[0m\n".to_string(),
+                String::new(),
+                0,
+                10,
+                RuntimeType::Native,
+                "python".to_string(),
+            ),
+            request.render_ansi,
+        );
+        assert_eq!(
+            result.stdout_html.as_deref(),
+            Some("<span style=\"color:#0dbc79\">ok</span>\n")
+        );
+        assert_eq!(result.stderr_html.as_deref(), Some(""));
+    }
+
     #[test]
     fn test_execute_code_request_defaults() {
         let json = json!({
@@ -1011,6 +1432,7 @@ mod tests {
         assert!(request.memory_limit_mb.is_none());
         assert!(request.runtime.is_none());
         assert!(request.network_enabled.is_none());
+        assert!(!request.render_ansi);
     }
 
     #[test]
@@ -1028,6 +1450,7 @@ mod tests {
             network_enabled: None,
             policy_profile: None,
             compiler_settings: None,
+            render_ansi: false,
         };
 
         assert!(matches!(request.runtime, Some(RuntimeType::Docker)));
@@ -1056,6 +1479,7 @@ mod tests {
                 enable_warnings: Some(true),
                 ..Default::default()
             }),
+            render_ansi: false,
         };
 
         let settings = request.compiler_settings.as_ref().unwrap();
@@ -1180,6 +1604,7 @@ mod tests {
             network_enabled: Some(false),
             policy_profile: None,
             compiler_settings: Some(settings),
+            render_ansi: false,
         };
 
         // Simulate the conversion that sandbox_execute does
@@ -1222,5 +1647,27 @@ mod tests {
             Some("c++17".to_string())
         );
     }
+
+    #[test]
+    fn test_resolve_selection_language() {
+        let enabled = vec!["python".to_string(), "bash".to_string(), "rust".to_string()];
+
+        // Given languages are resolved through aliases
+        assert_eq!(
+            resolve_selection_language("print(1)", Some(" py3 "), &enabled),
+            Ok(("python".to_string(), false))
+        );
+        assert_eq!(
+            resolve_selection_language("fn main() -> () { let mut x = 1; }", None, &enabled),
+            Ok(("rust".to_string(), true))
+        );
+
+        // Disabled and unknown languages are rejected
+        let err =
+            resolve_selection_language("console.log(1)", Some("javascript"), &enabled).unwrap_err();
+        assert!(err.contains("disabled"));
+        assert!(resolve_selection_language("x", Some("cobol"), &enabled).is_err());
+        assert!(resolve_selection_language("Just some prose.", None, &enabled).is_err());
+    }
 }
 
@@ -0,0 +1,195 @@
+//! Command error types
+//!
+//! Structured error returned across the Tauri command boundary, so the frontend
+//! can branch on a stable `code` instead of matching error messages.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Machine-readable error category
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CommandErrorCode {
+    /// The requested item does not exist
+    NotFound,
+    /// An item with the same identity already exists
+    AlreadyExists,
+    /// The arguments were rejected
+    InvalidInput,
+    /// The operating system or a policy refused the operation
+    PermissionDenied,
+    /// The operation did not finish in time
+    Timeout,
+    /// The feature is not available on this platform or in this state
+    Unavailable,
+    /// The operation was cancelled by the user
+    Cancelled,
+    /// Any other failure
+    Internal,
+}
+
+impl fmt::Display for CommandErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "NOT_FOUND"),
+            Self::AlreadyExists => write!(f, "ALREADY_EXISTS"),
+            Self::InvalidInput => write!(f, "INVALID_INPUT"),
+            Self::PermissionDenied => write!(f, "PERMISSION_DENIED"),
+            Self::Timeout => write!(f, "TIMEOUT"),
+            Self::Unavailable => write!(f, "UNAVAILABLE"),
+            Self::Cancelled => write!(f, "CANCELLED"),
+            Self::Internal => write!(f, "INTERNAL"),
+        }
+    }
+}
+
+/// Structured error for Tauri commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandError {
+    /// Error code for programmatic handling
+    pub code: CommandErrorCode,
+    /// Human-readable error message
+    pub message: String,
+    /// Additional structured context (ids, paths, limits)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl CommandError {
+    /// Create a new CommandError
+    pub fn new(code: CommandErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Add structured details to the error
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorCode::NotFound, message)
+    }
+
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorCode::AlreadyExists, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorCode::InvalidInput, message)
+    }
+
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorCode::Unavailable, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(CommandErrorCode::Internal, message)
+    }
+
+    /// Classify a legacy error message by its wording
+    ///
+    /// Lower layers still report errors as strings; this keeps their codes
+    /// meaningful until they return structured errors themselves.
+    fn classify(message: &str) -> CommandErrorCode {
+        let lower = message.to_lowercase();
+        if lower.contains("not found") || lower.contains("no such") {
+            CommandErrorCode::NotFound
+        } else if lower.contains("already exists") {
+            CommandErrorCode::AlreadyExists
+        } else if lower.contains("permission denied") || lower.contains("access denied") {
+            CommandErrorCode::PermissionDenied
+        } else if lower.contains("timed out") || lower.contains("timeout") {
+            CommandErrorCode::Timeout
+        } else if lower.contains("cancelled") || lower.contains("canceled") {
+            CommandErrorCode::Cancelled
+        } else if lower.contains("not available")
+            || lower.contains("not supported")
+            || lower.contains("not implemented")
+        {
+            CommandErrorCode::Unavailable
+        } else if lower.starts_with("invalid") || lower.contains("mismatch") {
+            CommandErrorCode::InvalidInput
+        } else {
+            CommandErrorCode::Internal
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(Self::classify(&message), message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_error_serialization() {
+        let error = CommandError::not_found("Collection not found")
+            .with_details(serde_json::json!({ "collection": "docs" }));
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["message"], "Collection not found");
+        assert_eq!(json["details"]["collection"], "docs");
+
+        let json = serde_json::to_value(CommandError::internal("boom")).unwrap();
+        assert!(json.get("details").is_none());
+    }
+
+    #[test]
+    fn test_command_error_from_string() {
+        let cases = [
+            ("Screenshot not found: abc", CommandErrorCode::NotFound),
+            ("Preset already exists", CommandErrorCode::AlreadyExists),
+            (
+                "Permission denied (os error 13)",
+                CommandErrorCode::PermissionDenied,
+            ),
+            ("OCR timed out after 30s", CommandErrorCode::Timeout),
+            ("Selection cancelled", CommandErrorCode::Cancelled),
+            (
+                "Window capture not implemented for this platform",
+                CommandErrorCode::Unavailable,
+            ),
+            ("Vector dimension mismatch", CommandErrorCode::InvalidInput),
+            ("Failed to encode image", CommandErrorCode::Internal),
+        ];
+
+        for (message, code) in cases {
+            let error = CommandError::from(message);
+            assert_eq!(error.code, code, "{}", message);
+            assert_eq!(error.message, message);
+        }
+    }
+
+    #[test]
+    fn test_command_error_code_display() {
+        assert_eq!(CommandErrorCode::NotFound.to_string(), "NOT_FOUND");
+        assert_eq!(
+            CommandErrorCode::PermissionDenied.to_string(),
+            "PERMISSION_DENIED"
+        );
+    }
+}
@@ -301,11 +301,16 @@ pub async fn plugin_python_list(
 /// Show notification (for plugins)
 #[tauri::command]
 pub async fn plugin_show_notification(
-    _title: String,
-    _body: String,
+    app_handle: tauri::AppHandle,
+    title: String,
+    body: String,
     _icon: Option<String>,
 ) -> Result<(), String> {
-    // TODO: Integrate with system notifications
+    crate::notifications::notify(
+        &app_handle,
+        crate::notifications::NotificationCategory::System,
+        crate::notifications::NotificationPayload::new(title, body),
+    );
     Ok(())
 }
 
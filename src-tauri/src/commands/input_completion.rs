@@ -2,9 +2,10 @@
 
 use crate::input_completion::types::CompletionStats;
 use crate::input_completion::{
-    CompletionConfig, CompletionFeedback, CompletionRequestV2, CompletionRequestV3,
-    CompletionResult, CompletionResultV2, CompletionResultV3, CompletionStatus,
-    CompletionSuggestion, CompletionSuggestionRef, ImeState, InputCompletionManager,
+    CompletionChainResult, CompletionConfig, CompletionFeedback, CompletionRequestV2,
+    CompletionRequestV3, CompletionResult, CompletionResultV2, CompletionResultV3,
+    CompletionRewriteRequest, CompletionStatus, CompletionSuggestion, CompletionSuggestionRef,
+    ImeState, InputCompletionManager,
 };
 use tauri::State;
 
@@ -69,6 +70,15 @@ pub fn input_completion_accept_v2(
     Ok(manager.accept_suggestion_v2(suggestion))
 }
 
+/// Accept a suggestion and request the next continuation from the new end.
+#[tauri::command]
+pub async fn input_completion_accept_and_continue(
+    manager: State<'_, InputCompletionManager>,
+    suggestion: CompletionSuggestionRef,
+) -> Result<Option<CompletionChainResult>, String> {
+    manager.accept_and_continue(suggestion).await
+}
+
 /// Dismiss the current suggestion
 #[tauri::command]
 pub fn input_completion_dismiss(manager: State<'_, InputCompletionManager>) -> Result<(), String> {
@@ -138,6 +148,15 @@ pub async fn input_completion_trigger_v3(
     manager.trigger_completion_v3(request).await
 }
 
+/// Request a rewrite suggestion for the current line or selection.
+#[tauri::command]
+pub async fn input_completion_rewrite(
+    manager: State<'_, InputCompletionManager>,
+    request: CompletionRewriteRequest,
+) -> Result<CompletionResultV3, String> {
+    manager.trigger_rewrite(request).await
+}
+
 /// Check if input completion is running
 #[tauri::command]
 pub fn input_completion_is_running(
@@ -190,6 +209,24 @@ pub fn input_completion_submit_feedback(
     Ok(())
 }
 
+/// Replay recorded key events through the completion key handler
+///
+/// Debug builds only (`completion-replay` feature). Uses `config`, or the current
+/// configuration, against fresh completion state.
+#[cfg(feature = "completion-replay")]
+#[tauri::command]
+pub async fn completion_replay(
+    manager: State<'_, InputCompletionManager>,
+    keystrokes: Vec<crate::input_completion::KeyEvent>,
+    config: Option<CompletionConfig>,
+) -> Result<crate::input_completion::CompletionReplay, String> {
+    if keystrokes.is_empty() {
+        return Err("No keystrokes to replay".to_string());
+    }
+    let config = config.unwrap_or_else(|| manager.get_config());
+    Ok(crate::input_completion::replay_keystrokes(keystrokes, config).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
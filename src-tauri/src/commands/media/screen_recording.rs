@@ -2,9 +2,10 @@
 
 use crate::screen_recording::{
     ffmpeg, AggregatedStorageStatus, AudioDevices, CleanupResult, EncodingSupport, FFmpegInfo,
-    FFmpegInstallGuide, HardwareAcceleration, MonitorInfo, RecordingConfig, RecordingHistoryEntry,
-    RecordingMetadata, RecordingRegion, RecordingStats, RecordingStatus, RecordingToolbar,
-    RecordingToolbarConfig, RecordingToolbarState, ScreenRecordingManager, SnapEdge, StorageConfig,
+    FFmpegInstallGuide, HardwareAcceleration, MarkerExportFormat, MonitorInfo, RecordingConfig,
+    RecordingHistoryEntry, RecordingMarker, RecordingMetadata, RecordingRegion, RecordingStats,
+    RecordingStatus, RecordingToolbar, RecordingToolbarConfig, RecordingToolbarState,
+    RecordingTrimResult, ReplayStatus, ScreenRecordingManager, SnapEdge, StorageConfig,
     StorageFile, StorageFileType, StorageStats, TimelineRenderOptions, TimelineRenderPlan,
     TimelineRenderer, ToolbarPosition, VideoConvertOptions, VideoInfo, VideoProcessingResult,
     VideoProcessor, VideoTrimOptions,
@@ -131,6 +132,132 @@ pub async fn recording_get_audio_devices(
     Ok(manager.get_audio_devices())
 }
 
+/// Add a marker to the active recording at its current position
+#[tauri::command]
+pub async fn recording_add_marker(
+    manager: State<'_, ScreenRecordingManager>,
+    label: String,
+) -> Result<RecordingMarker, String> {
+    manager.add_marker(label)
+}
+
+/// Get markers for a recording
+#[tauri::command]
+pub async fn recording_get_markers(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+) -> Result<Vec<RecordingMarker>, String> {
+    manager.get_markers(&id)
+}
+
+/// Export a recording's markers as chapters or a sidecar file
+#[tauri::command]
+pub async fn recording_export_markers(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+    format: MarkerExportFormat,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    manager.export_markers(&id, format, output_path)
+}
+
+/// Trim a recording into a new history entry, optionally keeping the original
+#[tauri::command]
+pub async fn recording_trim(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+    start_ms: u64,
+    end_ms: u64,
+    keep_original: bool,
+) -> Result<RecordingTrimResult, String> {
+    manager
+        .trim_recording(&id, start_ms, end_ms, keep_original)
+        .await
+}
+
+/// Start the instant replay buffer, which keeps the last few seconds without saving
+#[tauri::command]
+pub async fn recording_start_replay(
+    manager: State<'_, ScreenRecordingManager>,
+    monitor_index: Option<usize>,
+) -> Result<ReplayStatus, String> {
+    manager.start_replay(monitor_index)
+}
+
+/// Stop the instant replay buffer and discard its footage
+#[tauri::command]
+pub async fn recording_stop_replay(
+    manager: State<'_, ScreenRecordingManager>,
+) -> Result<(), String> {
+    manager.stop_replay().await
+}
+
+/// Get the instant replay buffer state
+#[tauri::command]
+pub async fn recording_get_replay_status(
+    manager: State<'_, ScreenRecordingManager>,
+) -> Result<ReplayStatus, String> {
+    Ok(manager.get_replay_status())
+}
+
+/// Save the instant replay buffer to a new recording
+#[tauri::command]
+pub async fn recording_save_replay(
+    manager: State<'_, ScreenRecordingManager>,
+) -> Result<RecordingMetadata, String> {
+    manager.save_replay().await
+}
+
+/// Get a recording's thumbnail as a base64 data URL
+#[tauri::command]
+pub async fn recording_get_thumbnail(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+) -> Result<String, String> {
+    let thumbnail = manager.get_thumbnail(&id).await?;
+    Ok(format!("data:image/jpeg;base64,{}", thumbnail))
+}
+
+/// Get a recording's filmstrip frames as base64 data URLs
+#[tauri::command]
+pub async fn recording_get_filmstrip(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+) -> Result<Vec<String>, String> {
+    let entry = manager
+        .get_recording_by_id(&id)
+        .ok_or_else(|| format!("Recording not found: {}", id))?;
+    entry
+        .filmstrip
+        .iter()
+        .map(|frame| read_image_data_url(&frame.path))
+        .collect()
+}
+
+/// Regenerate a recording's thumbnail, optionally at a specific timestamp and with a filmstrip
+#[tauri::command]
+pub async fn recording_regenerate_thumbnail(
+    manager: State<'_, ScreenRecordingManager>,
+    id: String,
+    timestamp_ms: Option<u64>,
+    filmstrip_frames: Option<u32>,
+) -> Result<RecordingHistoryEntry, String> {
+    let filmstrip_frames =
+        filmstrip_frames.unwrap_or_else(|| manager.get_config().filmstrip_frames);
+    manager
+        .generate_previews_async(&id, timestamp_ms, filmstrip_frames)
+        .await
+}
+
+fn read_image_data_url(path: &str) -> Result<String, String> {
+    use base64::Engine;
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    Ok(format!(
+        "data:image/jpeg;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
 /// Get recording history
 #[tauri::command]
 pub async fn recording_get_history(
@@ -2,13 +2,25 @@
 //!
 //! Commands for capturing screenshots and performing OCR.
 
+use crate::commands::error::CommandError;
+use crate::commands::media::ocr::OcrState;
+use crate::commands::storage::model_download::{
+    self, DownloadProgress, DownloadResult, DownloadStatus, ModelSource,
+};
+use crate::context::ContextManager;
+use crate::input_completion::{CompletionModelConfig, InputCompletionManager};
 use crate::screenshot::{
     Annotation, CaptureRegion, ElementInfo, MonitorInfo, ScreenshotAnnotator, ScreenshotConfig,
-    ScreenshotHistoryEntry, ScreenshotManager, ScreenshotMetadata, SelectionSnapResult,
-    SelectionState, SnapConfig, SnapResult, WinOcrResult, WindowInfo,
+    ScreenshotHistoryEntry, ScreenshotManager, ScreenshotMetadata, ScreenshotOcrResult,
+    SelectionSnapResult, SelectionState, SnapConfig, SnapResult, TesseractProvider, WinOcrResult,
+    WindowInfo,
+};
+use crate::selection::{
+    action_uses_target_language, build_action_prompt, selection_system_prompt, SelectionAiResult,
+    SelectionManager,
 };
 use base64::Engine;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 fn into_frontend_result(result: crate::screenshot::ScreenshotResult) -> ScreenshotResult {
     ScreenshotResult {
@@ -22,7 +34,7 @@ fn into_frontend_result(result: crate::screenshot::ScreenshotResult) -> Screensh
 pub async fn screenshot_capture_fullscreen(
     manager: State<'_, ScreenshotManager>,
     monitor_index: Option<usize>,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager.capture_fullscreen(monitor_index).await?;
     Ok(into_frontend_result(result))
 }
@@ -39,15 +51,19 @@ pub struct AnnotatedScreenshotResult {
 pub async fn screenshot_apply_annotations(
     image_base64: String,
     annotations: Vec<Annotation>,
-) -> Result<AnnotatedScreenshotResult, String> {
+) -> Result<AnnotatedScreenshotResult, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
 
     let decoder = png::Decoder::new(std::io::Cursor::new(&image_data));
-    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     let mut buf = vec![0; reader.output_buffer_size()];
-    let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    let info = reader
+        .next_frame(&mut buf)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     let pixels = &buf[..info.buffer_size()];
 
     let mut rgba_pixels = match info.color_type {
@@ -61,7 +77,12 @@ pub async fn screenshot_apply_annotations(
             .chunks(2)
             .flat_map(|chunk| [chunk[0], chunk[0], chunk[0], chunk[1]])
             .collect(),
-        _ => return Err(format!("Unsupported color type: {:?}", info.color_type)),
+        _ => {
+            return Err(CommandError::invalid_input(format!(
+                "Unsupported color type: {:?}",
+                info.color_type
+            )))
+        }
     };
 
     let mut annotator = ScreenshotAnnotator::new(info.width, info.height);
@@ -75,10 +96,12 @@ pub async fn screenshot_apply_annotations(
         let mut encoder = png::Encoder::new(&mut png_data, info.width, info.height);
         encoder.set_color(png::ColorType::Rgba);
         encoder.set_depth(png::BitDepth::Eight);
-        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| CommandError::internal(e.to_string()))?;
         writer
             .write_image_data(&rgba_pixels)
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| CommandError::internal(e.to_string()))?;
     }
 
     Ok(AnnotatedScreenshotResult {
@@ -90,7 +113,7 @@ pub async fn screenshot_apply_annotations(
 #[tauri::command]
 pub async fn screenshot_capture_window(
     manager: State<'_, ScreenshotManager>,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager.capture_window().await?;
     Ok(into_frontend_result(result))
 }
@@ -103,7 +126,7 @@ pub async fn screenshot_capture_region(
     y: i32,
     width: u32,
     height: u32,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let region = CaptureRegion {
         x,
         y,
@@ -118,28 +141,37 @@ pub async fn screenshot_capture_region(
 #[tauri::command]
 pub async fn screenshot_start_region_selection(
     manager: State<'_, ScreenshotManager>,
-) -> Result<CaptureRegion, String> {
-    manager.start_region_selection().await
+) -> Result<CaptureRegion, CommandError> {
+    manager
+        .start_region_selection()
+        .await
+        .map_err(CommandError::from)
 }
 
-/// Extract text from image using OCR
+/// Extract text from image using the configured OCR engine
 #[tauri::command]
 pub async fn screenshot_ocr(
     manager: State<'_, ScreenshotManager>,
+    ocr_state: State<'_, OcrState>,
     image_base64: String,
-) -> Result<String, String> {
+) -> Result<ScreenshotOcrResult, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
-
-    manager.extract_text(&image_data)
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
+
+    // Clone the OCR manager to avoid holding the lock across await
+    let cloud = ocr_state.manager.read().clone();
+    manager
+        .extract_text_with_engine(&image_data, Some(&cloud))
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Get list of available monitors
 #[tauri::command]
 pub async fn screenshot_get_monitors(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<MonitorInfo>, String> {
+) -> Result<Vec<MonitorInfo>, CommandError> {
     Ok(manager.get_monitors())
 }
 
@@ -148,7 +180,7 @@ pub async fn screenshot_get_monitors(
 pub async fn screenshot_update_config(
     manager: State<'_, ScreenshotManager>,
     config: ScreenshotConfig,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.update_config(config);
     Ok(())
 }
@@ -157,7 +189,7 @@ pub async fn screenshot_update_config(
 #[tauri::command]
 pub async fn screenshot_get_config(
     manager: State<'_, ScreenshotManager>,
-) -> Result<ScreenshotConfig, String> {
+) -> Result<ScreenshotConfig, CommandError> {
     Ok(manager.get_config())
 }
 
@@ -167,12 +199,14 @@ pub async fn screenshot_save(
     manager: State<'_, ScreenshotManager>,
     image_base64: String,
     path: String,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
 
-    manager.save_to_file(&image_data, &path)
+    manager
+        .save_to_file(&image_data, &path)
+        .map_err(CommandError::from)
 }
 
 // ============== Screenshot History Commands ==============
@@ -182,7 +216,7 @@ pub async fn screenshot_save(
 pub async fn screenshot_get_history(
     manager: State<'_, ScreenshotManager>,
     count: Option<usize>,
-) -> Result<Vec<ScreenshotHistoryEntry>, String> {
+) -> Result<Vec<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.get_history(count.unwrap_or(20)))
 }
 
@@ -191,7 +225,7 @@ pub async fn screenshot_get_history(
 pub async fn screenshot_search_history(
     manager: State<'_, ScreenshotManager>,
     query: String,
-) -> Result<Vec<ScreenshotHistoryEntry>, String> {
+) -> Result<Vec<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.search_history_by_text(&query))
 }
 
@@ -200,7 +234,7 @@ pub async fn screenshot_search_history(
 pub async fn screenshot_get_by_id(
     manager: State<'_, ScreenshotManager>,
     id: String,
-) -> Result<Option<ScreenshotHistoryEntry>, String> {
+) -> Result<Option<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.get_screenshot_by_id(&id))
 }
 
@@ -209,7 +243,7 @@ pub async fn screenshot_get_by_id(
 pub async fn screenshot_pin(
     manager: State<'_, ScreenshotManager>,
     id: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.pin_screenshot(&id))
 }
 
@@ -218,7 +252,7 @@ pub async fn screenshot_pin(
 pub async fn screenshot_unpin(
     manager: State<'_, ScreenshotManager>,
     id: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.unpin_screenshot(&id))
 }
 
@@ -227,13 +261,15 @@ pub async fn screenshot_unpin(
 pub async fn screenshot_delete(
     manager: State<'_, ScreenshotManager>,
     id: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.delete_screenshot(&id))
 }
 
 /// Clear screenshot history
 #[tauri::command]
-pub async fn screenshot_clear_history(manager: State<'_, ScreenshotManager>) -> Result<(), String> {
+pub async fn screenshot_clear_history(
+    manager: State<'_, ScreenshotManager>,
+) -> Result<(), CommandError> {
     manager.clear_history();
     Ok(())
 }
@@ -244,7 +280,7 @@ pub async fn screenshot_add_tag(
     manager: State<'_, ScreenshotManager>,
     id: String,
     tag: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.add_tag(&id, tag))
 }
 
@@ -254,7 +290,7 @@ pub async fn screenshot_remove_tag(
     manager: State<'_, ScreenshotManager>,
     id: String,
     tag: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.remove_tag(&id, &tag))
 }
 
@@ -264,7 +300,7 @@ pub async fn screenshot_set_label(
     manager: State<'_, ScreenshotManager>,
     id: String,
     label: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.set_label(&id, label))
 }
 
@@ -275,46 +311,148 @@ pub async fn screenshot_set_label(
 pub async fn screenshot_ocr_windows(
     manager: State<'_, ScreenshotManager>,
     image_base64: String,
-) -> Result<WinOcrResult, String> {
+) -> Result<WinOcrResult, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
 
-    manager.extract_text_windows(&image_data)
+    manager
+        .extract_text_windows(&image_data)
+        .map_err(CommandError::from)
 }
 
 /// Get available OCR languages installed on the system
 #[tauri::command]
 pub async fn screenshot_get_ocr_languages(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, CommandError> {
     Ok(manager.get_ocr_languages())
 }
 
 /// Check if OCR is available on this system
 #[tauri::command]
-pub async fn screenshot_ocr_is_available() -> Result<bool, String> {
+pub async fn screenshot_ocr_is_available() -> Result<bool, CommandError> {
     use crate::screenshot::WindowsOcr;
     Ok(WindowsOcr::is_available())
 }
 
 /// Check if a specific OCR language is available
 #[tauri::command]
-pub async fn screenshot_ocr_is_language_available(language: String) -> Result<bool, String> {
+pub async fn screenshot_ocr_is_language_available(language: String) -> Result<bool, CommandError> {
     use crate::screenshot::WindowsOcr;
     Ok(WindowsOcr::is_language_available(&language))
 }
 
+/// Resolve a user-supplied language (e.g. `ja` or `jpn`) to a Tesseract language code
+fn resolve_tesseract_language(lang_code: &str) -> Result<String, CommandError> {
+    use crate::screenshot::providers::tesseract;
+    let code = TesseractProvider::to_tesseract_lang(lang_code.trim());
+    if !tesseract::is_valid_language_code(&code) {
+        return Err(CommandError::invalid_input(format!(
+            "Invalid OCR language code: {}",
+            lang_code
+        )));
+    }
+    Ok(code)
+}
+
+/// Download and install Tesseract language data into the app data directory
+///
+/// The download size is probed first and reported as a `pending` `model-download-progress`
+/// event before the transfer starts; progress then follows on the same event.
+#[tauri::command]
+pub async fn screenshot_install_ocr_language(
+    app: AppHandle,
+    lang_code: String,
+    source: Option<ModelSource>,
+) -> Result<DownloadResult, CommandError> {
+    crate::http::ensure_online().map_err(CommandError::unavailable)?;
+    let code = resolve_tesseract_language(&lang_code)?;
+    let mut model = model_download::tesseract_language_model(&code);
+
+    let config = model_download::load_download_config(&app).await?;
+    let client = model_download::create_download_client(&config).await?;
+    let probe_source = source.unwrap_or(model.default_source);
+    let probe_url = model
+        .sources
+        .get(&probe_source)
+        .ok_or_else(|| CommandError::invalid_input(format!("No {:?} source", probe_source)))?;
+    match model_download::probe_download(&client, probe_url).await {
+        Ok(probe) if probe.status == reqwest::StatusCode::NOT_FOUND => {
+            return Err(CommandError::not_found(format!(
+                "No Tesseract language data for '{}'",
+                code
+            )));
+        }
+        Ok(probe) if probe.status.is_success() => {
+            if let Some(size) = probe.size {
+                model.size = size;
+            }
+        }
+        Ok(probe) => log::warn!(
+            "Failed to probe size of OCR language '{}': HTTP {}",
+            code,
+            probe.status
+        ),
+        Err(e) => log::warn!("Failed to probe size of OCR language '{}': {}", code, e),
+    }
+
+    log::info!("Installing OCR language '{}' ({} bytes)", code, model.size);
+    let _ = app.emit(
+        "model-download-progress",
+        DownloadProgress {
+            model_id: model.id.clone(),
+            status: DownloadStatus::Pending,
+            source: probe_source,
+            total_bytes: model.size,
+            downloaded_bytes: 0,
+            percent: 0.0,
+            speed_bps: 0,
+            eta_secs: None,
+            error: None,
+        },
+    );
+
+    let result = model_download::download_model(&app, &model, source, config).await?;
+    if let Some(error) = result.error.as_ref().filter(|_| !result.success) {
+        return Err(CommandError::internal(format!(
+            "Failed to install OCR language '{}': {}",
+            code, error
+        )));
+    }
+    Ok(result)
+}
+
+/// Remove Tesseract language data installed by `screenshot_install_ocr_language`
+///
+/// Returns `false` when the language was not installed in the app data directory.
+#[tauri::command]
+pub async fn screenshot_remove_ocr_language(
+    app: AppHandle,
+    lang_code: String,
+) -> Result<bool, CommandError> {
+    let code = resolve_tesseract_language(&lang_code)?;
+    let path = model_download::get_tessdata_dir(&app)?.join(format!("{}.traineddata", code));
+    if !path.exists() {
+        return Ok(false);
+    }
+    tokio::fs::remove_file(&path)
+        .await
+        .map_err(|e| CommandError::internal(format!("Failed to remove OCR language: {}", e)))?;
+    log::info!("Removed OCR language '{}'", code);
+    Ok(true)
+}
+
 /// Extract text using Windows OCR with language parameter
 #[tauri::command]
 pub async fn screenshot_ocr_with_language(
     _manager: State<'_, ScreenshotManager>,
     image_base64: String,
     language: Option<String>,
-) -> Result<WinOcrResult, String> {
+) -> Result<WinOcrResult, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
 
     // Create a temporary OCR engine with the specified language
     let mut ocr = crate::screenshot::WindowsOcr::new();
@@ -322,7 +460,7 @@ pub async fn screenshot_ocr_with_language(
         ocr.set_language(&lang);
     }
 
-    ocr.extract_text(&image_data)
+    ocr.extract_text(&image_data).map_err(CommandError::from)
 }
 
 /// Set Windows OCR language used by the screenshot manager
@@ -330,7 +468,7 @@ pub async fn screenshot_ocr_with_language(
 pub async fn screenshot_set_ocr_language(
     manager: State<'_, ScreenshotManager>,
     language: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.set_ocr_language(&language);
     Ok(())
 }
@@ -342,7 +480,7 @@ pub async fn screenshot_set_ocr_language(
 pub async fn screenshot_capture_fullscreen_with_history(
     manager: State<'_, ScreenshotManager>,
     monitor_index: Option<usize>,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager
         .capture_fullscreen_with_history(monitor_index)
         .await?;
@@ -353,7 +491,7 @@ pub async fn screenshot_capture_fullscreen_with_history(
 #[tauri::command]
 pub async fn screenshot_capture_window_with_history(
     manager: State<'_, ScreenshotManager>,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager.capture_window_with_history().await?;
     Ok(into_frontend_result(result))
 }
@@ -366,7 +504,7 @@ pub async fn screenshot_capture_region_with_history(
     y: i32,
     width: u32,
     height: u32,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let region = CaptureRegion {
         x,
         y,
@@ -390,7 +528,7 @@ pub struct ScreenshotResult {
 #[tauri::command]
 pub async fn screenshot_get_windows(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<WindowInfo>, String> {
+) -> Result<Vec<WindowInfo>, CommandError> {
     Ok(manager.get_windows())
 }
 
@@ -399,7 +537,7 @@ pub async fn screenshot_get_windows(
 pub async fn screenshot_get_windows_with_thumbnails(
     manager: State<'_, ScreenshotManager>,
     thumbnail_size: Option<u32>,
-) -> Result<Vec<WindowInfo>, String> {
+) -> Result<Vec<WindowInfo>, CommandError> {
     Ok(manager.get_windows_with_thumbnails(thumbnail_size.unwrap_or(160)))
 }
 
@@ -408,7 +546,7 @@ pub async fn screenshot_get_windows_with_thumbnails(
 pub async fn screenshot_capture_window_by_hwnd(
     manager: State<'_, ScreenshotManager>,
     hwnd: isize,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager.capture_window_by_hwnd(hwnd)?;
     Ok(into_frontend_result(result))
 }
@@ -418,11 +556,37 @@ pub async fn screenshot_capture_window_by_hwnd(
 pub async fn screenshot_capture_window_by_hwnd_with_history(
     manager: State<'_, ScreenshotManager>,
     hwnd: isize,
-) -> Result<ScreenshotResult, String> {
+) -> Result<ScreenshotResult, CommandError> {
     let result = manager.capture_window_by_hwnd_with_history(hwnd).await?;
     Ok(into_frontend_result(result))
 }
 
+/// Capture the currently focused window and add to history
+///
+/// The returned metadata carries the window title for labeling.
+#[tauri::command]
+pub async fn screenshot_capture_focused_window(
+    manager: State<'_, ScreenshotManager>,
+    context: State<'_, ContextManager>,
+) -> Result<ScreenshotResult, CommandError> {
+    let window = context.get_window_info()?;
+    let result = manager
+        .capture_titled_window_with_history(window.handle as isize, &window.title)
+        .await?;
+    Ok(into_frontend_result(result))
+}
+
+/// Capture the window under the mouse cursor and add to history
+///
+/// The returned metadata carries the window title for labeling.
+#[tauri::command]
+pub async fn screenshot_capture_window_under_cursor(
+    manager: State<'_, ScreenshotManager>,
+) -> Result<ScreenshotResult, CommandError> {
+    let result = manager.capture_window_under_cursor_with_history().await?;
+    Ok(into_frontend_result(result))
+}
+
 /// Calculate snap position for window movement
 #[tauri::command]
 pub async fn screenshot_calculate_snap(
@@ -432,7 +596,7 @@ pub async fn screenshot_calculate_snap(
     proposed_y: i32,
     window_width: u32,
     window_height: u32,
-) -> Result<SnapResult, String> {
+) -> Result<SnapResult, CommandError> {
     Ok(manager.calculate_snap_position(
         window_hwnd,
         proposed_x,
@@ -446,7 +610,7 @@ pub async fn screenshot_calculate_snap(
 #[tauri::command]
 pub async fn screenshot_get_snap_config(
     manager: State<'_, ScreenshotManager>,
-) -> Result<SnapConfig, String> {
+) -> Result<SnapConfig, CommandError> {
     Ok(manager.get_snap_config())
 }
 
@@ -455,7 +619,7 @@ pub async fn screenshot_get_snap_config(
 pub async fn screenshot_set_snap_config(
     manager: State<'_, ScreenshotManager>,
     config: SnapConfig,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.set_snap_config(config);
     Ok(())
 }
@@ -468,7 +632,7 @@ pub async fn screenshot_get_window_at_point(
     manager: State<'_, ScreenshotManager>,
     x: i32,
     y: i32,
-) -> Result<Option<WindowInfo>, String> {
+) -> Result<Option<WindowInfo>, CommandError> {
     Ok(manager.get_window_at_point(x, y))
 }
 
@@ -478,7 +642,7 @@ pub async fn screenshot_get_child_elements(
     manager: State<'_, ScreenshotManager>,
     hwnd: isize,
     max_depth: Option<u32>,
-) -> Result<Vec<ElementInfo>, String> {
+) -> Result<Vec<ElementInfo>, CommandError> {
     Ok(manager.get_child_elements(hwnd, max_depth.unwrap_or(1)))
 }
 
@@ -490,7 +654,7 @@ pub async fn screenshot_calculate_selection_snap(
     selection_y: i32,
     selection_width: u32,
     selection_height: u32,
-) -> Result<SelectionSnapResult, String> {
+) -> Result<SelectionSnapResult, CommandError> {
     Ok(manager.calculate_selection_snap(
         selection_x,
         selection_y,
@@ -505,7 +669,7 @@ pub async fn screenshot_get_pixel_color(
     manager: State<'_, ScreenshotManager>,
     x: i32,
     y: i32,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     Ok(manager.get_pixel_color(x, y))
 }
 
@@ -524,7 +688,7 @@ pub async fn screenshot_validate_selection(
     start_y: i32,
     current_x: i32,
     current_y: i32,
-) -> Result<SelectionValidationResult, String> {
+) -> Result<SelectionValidationResult, CommandError> {
     let state = SelectionState {
         is_selecting: true,
         start_x,
@@ -545,7 +709,7 @@ pub async fn screenshot_validate_selection(
 #[tauri::command]
 pub async fn screenshot_get_all_history(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<ScreenshotHistoryEntry>, String> {
+) -> Result<Vec<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.get_all_history())
 }
 
@@ -554,7 +718,7 @@ pub async fn screenshot_get_all_history(
 pub async fn screenshot_search_history_by_label(
     manager: State<'_, ScreenshotManager>,
     label: String,
-) -> Result<Vec<ScreenshotHistoryEntry>, String> {
+) -> Result<Vec<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.search_history_by_label(&label))
 }
 
@@ -562,7 +726,7 @@ pub async fn screenshot_search_history_by_label(
 #[tauri::command]
 pub async fn screenshot_get_pinned_history(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<ScreenshotHistoryEntry>, String> {
+) -> Result<Vec<ScreenshotHistoryEntry>, CommandError> {
     Ok(manager.get_pinned_history())
 }
 
@@ -570,7 +734,7 @@ pub async fn screenshot_get_pinned_history(
 #[tauri::command]
 pub async fn screenshot_clear_all_history(
     manager: State<'_, ScreenshotManager>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.clear_all_history();
     Ok(())
 }
@@ -579,7 +743,7 @@ pub async fn screenshot_clear_all_history(
 #[tauri::command]
 pub async fn screenshot_get_history_stats(
     manager: State<'_, ScreenshotManager>,
-) -> Result<(usize, bool), String> {
+) -> Result<(usize, bool), CommandError> {
     Ok(manager.get_history_stats())
 }
 
@@ -591,7 +755,7 @@ pub async fn screenshot_annotator_init(
     manager: State<'_, ScreenshotManager>,
     width: u32,
     height: u32,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.init_annotator(width, height);
     Ok(())
 }
@@ -601,7 +765,7 @@ pub async fn screenshot_annotator_init(
 pub async fn screenshot_annotator_add(
     manager: State<'_, ScreenshotManager>,
     annotation: Annotation,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.add_annotation(annotation);
     Ok(())
 }
@@ -610,7 +774,7 @@ pub async fn screenshot_annotator_add(
 #[tauri::command]
 pub async fn screenshot_annotator_undo(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Option<Annotation>, String> {
+) -> Result<Option<Annotation>, CommandError> {
     Ok(manager.annotator_undo())
 }
 
@@ -618,7 +782,7 @@ pub async fn screenshot_annotator_undo(
 #[tauri::command]
 pub async fn screenshot_annotator_clear(
     manager: State<'_, ScreenshotManager>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.annotator_clear();
     Ok(())
 }
@@ -627,7 +791,7 @@ pub async fn screenshot_annotator_clear(
 #[tauri::command]
 pub async fn screenshot_annotator_get_all(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<Annotation>, String> {
+) -> Result<Vec<Annotation>, CommandError> {
     Ok(manager.get_annotations())
 }
 
@@ -635,7 +799,7 @@ pub async fn screenshot_annotator_get_all(
 #[tauri::command]
 pub async fn screenshot_annotator_export(
     manager: State<'_, ScreenshotManager>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     Ok(manager.export_annotations())
 }
 
@@ -644,8 +808,10 @@ pub async fn screenshot_annotator_export(
 pub async fn screenshot_annotator_import(
     manager: State<'_, ScreenshotManager>,
     json: String,
-) -> Result<(), String> {
-    manager.import_annotations(&json)
+) -> Result<(), CommandError> {
+    manager
+        .import_annotations(&json)
+        .map_err(CommandError::from)
 }
 
 // ============== Detailed OCR Commands ==============
@@ -655,19 +821,22 @@ pub async fn screenshot_annotator_import(
 pub async fn screenshot_ocr_extract_detailed(
     manager: State<'_, ScreenshotManager>,
     image_base64: String,
-) -> Result<crate::screenshot::LegacyOcrResult, String> {
+) -> Result<crate::screenshot::LegacyOcrResult, CommandError> {
     let image_data = base64::engine::general_purpose::STANDARD
         .decode(&image_base64)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+        .map_err(|e| CommandError::invalid_input(format!("Failed to decode image: {}", e)))?;
 
-    manager.extract_text_detailed(&image_data).await
+    manager
+        .extract_text_detailed(&image_data)
+        .await
+        .map_err(CommandError::from)
 }
 
 /// Get current OCR language setting
 #[tauri::command]
 pub async fn screenshot_get_current_ocr_language(
     manager: State<'_, ScreenshotManager>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     Ok(manager.get_current_ocr_language())
 }
 
@@ -675,7 +844,7 @@ pub async fn screenshot_get_current_ocr_language(
 #[tauri::command]
 pub async fn screenshot_is_ocr_available(
     manager: State<'_, ScreenshotManager>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.is_ocr_available())
 }
 
@@ -683,10 +852,117 @@ pub async fn screenshot_is_ocr_available(
 #[tauri::command]
 pub async fn screenshot_get_ocr_engine_languages(
     manager: State<'_, ScreenshotManager>,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<String>, CommandError> {
     Ok(manager.get_ocr_engine_languages())
 }
 
+// ============== Screen AI Commands ==============
+
+/// Event carrying streamed AI output for `screenshot_capture_and_ai`
+const SCREENSHOT_AI_CHUNK_EVENT: &str = "screenshot-ai-chunk";
+
+/// Streamed piece of AI output
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScreenshotAiChunk {
+    pub request_id: String,
+    pub delta: String,
+}
+
+/// Result of running an AI action on text captured from the screen
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ScreenshotAiResult {
+    pub request_id: String,
+    /// Text recognized in the captured region
+    pub ocr: ScreenshotOcrResult,
+    /// Output of the AI action
+    pub action: SelectionAiResult,
+}
+
+/// Capture a region, OCR it, and run a selection AI action on the recognized text
+///
+/// Output is streamed as "screenshot-ai-chunk" events tagged with `request_id`
+/// (generated when omitted); the final result includes the OCR text.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn screenshot_capture_and_ai(
+    app: AppHandle,
+    manager: State<'_, ScreenshotManager>,
+    ocr_state: State<'_, OcrState>,
+    selection: State<'_, SelectionManager>,
+    completion: State<'_, InputCompletionManager>,
+    region: CaptureRegion,
+    action_id: String,
+    target_language: Option<String>,
+    model: Option<CompletionModelConfig>,
+    request_id: Option<String>,
+) -> Result<ScreenshotAiResult, CommandError> {
+    let target_language = if action_uses_target_language(&action_id) {
+        Some(target_language.unwrap_or_else(|| selection.get_config().target_language))
+    } else {
+        None
+    };
+    let language = target_language.as_deref().unwrap_or_default();
+    // Reject unsupported actions before capturing anything
+    if build_action_prompt(&action_id, "", language).is_none() {
+        return Err(CommandError::invalid_input(format!(
+            "Unsupported AI action: {}",
+            action_id
+        )));
+    }
+
+    let capture = manager.capture_region_silent(region)?;
+    // Clone the OCR manager to avoid holding the lock across await
+    let cloud = ocr_state.manager.read().clone();
+    let ocr = manager
+        .extract_text_with_engine(&capture.image_data, Some(&cloud))
+        .await?;
+    let text = ocr.text.trim();
+    if text.is_empty() {
+        return Err(CommandError::invalid_input(
+            "No text was recognized in the captured region",
+        ));
+    }
+
+    let prompt = build_action_prompt(&action_id, text, language).ok_or_else(|| {
+        CommandError::invalid_input(format!("Unsupported AI action: {}", action_id))
+    })?;
+    let request_id = request_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    log::info!(
+        "[Screenshot] Running '{}' on {} OCR chars (request {})",
+        action_id,
+        text.len(),
+        request_id
+    );
+    let output = completion
+        .generate_text_streaming(selection_system_prompt(), &prompt, model, |delta| {
+            let _ = app.emit(
+                SCREENSHOT_AI_CHUNK_EVENT,
+                ScreenshotAiChunk {
+                    request_id: request_id.clone(),
+                    delta: delta.to_string(),
+                },
+            );
+        })
+        .await?;
+    if output.is_empty() {
+        return Err(CommandError::internal("Model returned an empty response"));
+    }
+
+    Ok(ScreenshotAiResult {
+        request_id,
+        ocr,
+        action: SelectionAiResult {
+            action_id,
+            target_language,
+            result: output,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            cached: false,
+            context_collection: None,
+        },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -875,8 +1151,10 @@ mod tests {
             copy_to_clipboard: false,
             show_notification: false,
             ocr_language: "chi_sim".to_string(),
+            ocr_engine: crate::screenshot::OcrEngineSelection::Tesseract,
             auto_save: true,
             filename_template: "custom_{mode}_{timestamp}".to_string(),
+            region_preview_pixel_color: false,
         };
 
         assert_eq!(config.save_directory, Some("/custom/path".to_string()));
@@ -896,8 +1174,10 @@ mod tests {
             copy_to_clipboard: true,
             show_notification: true,
             ocr_language: "en".to_string(),
+            ocr_engine: crate::screenshot::OcrEngineSelection::Auto,
             auto_save: false,
             filename_template: "screenshot_{timestamp}".to_string(),
+            region_preview_pixel_color: false,
         };
 
         assert_eq!(config.format, "png");
@@ -917,8 +1197,10 @@ mod tests {
             copy_to_clipboard: false,
             show_notification: false,
             ocr_language: "zh-CN".to_string(),
+            ocr_engine: crate::screenshot::OcrEngineSelection::Platform,
             auto_save: true,
             filename_template: "shot_{mode}_{timestamp}".to_string(),
+            region_preview_pixel_color: false,
         };
 
         let serialized = serde_json::to_string(&config).unwrap();
@@ -927,6 +1209,7 @@ mod tests {
         assert_eq!(config.format, deserialized.format);
         assert_eq!(config.quality, deserialized.quality);
         assert_eq!(config.ocr_language, deserialized.ocr_language);
+        assert_eq!(config.ocr_engine, deserialized.ocr_engine);
         assert_eq!(config.auto_save, deserialized.auto_save);
         assert_eq!(config.filename_template, deserialized.filename_template);
     }
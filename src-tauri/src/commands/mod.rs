@@ -2,6 +2,9 @@
 //!
 //! All Tauri IPC commands organized by functional category.
 
+// Shared command error type
+pub mod error;
+
 // Functional submodules
 pub mod compatibility;
 pub mod context;
@@ -3,7 +3,7 @@
 //! This module provides commands for testing API connections to various
 //! AI providers. It uses the shared HTTP client from the http module.
 
-use crate::http::{create_proxy_client, get_client_for_url};
+use crate::http::{create_proxy_client, ensure_online, ensure_url_allowed, get_client_for_url};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -53,6 +53,7 @@ async fn test_bearer_auth_api(
     models_path: &str,
     _provider_name: &str,
 ) -> Result<ApiTestResult, String> {
+    ensure_url_allowed(url)?;
     let start = std::time::Instant::now();
 
     let client = create_proxy_client().map_err(|e| format!("HTTP client error: {}", e))?;
@@ -96,6 +97,7 @@ pub async fn test_openai_connection(
 /// Test Anthropic API connection
 #[tauri::command]
 pub async fn test_anthropic_connection(api_key: String) -> Result<ApiTestResult, String> {
+    ensure_online()?;
     let start = std::time::Instant::now();
 
     // Anthropic doesn't have a models endpoint, so we send a minimal message request
@@ -142,6 +144,7 @@ pub async fn test_anthropic_connection(api_key: String) -> Result<ApiTestResult,
 /// Test Google AI API connection
 #[tauri::command]
 pub async fn test_google_connection(api_key: String) -> Result<ApiTestResult, String> {
+    ensure_online()?;
     let start = std::time::Instant::now();
 
     let client = create_proxy_client().map_err(|e| format!("HTTP client error: {}", e))?;
@@ -177,6 +180,7 @@ pub async fn test_google_connection(api_key: String) -> Result<ApiTestResult, St
 /// Test DeepSeek API connection
 #[tauri::command]
 pub async fn test_deepseek_connection(api_key: String) -> Result<ApiTestResult, String> {
+    ensure_online()?;
     let start = std::time::Instant::now();
 
     let client = create_proxy_client().map_err(|e| format!("HTTP client error: {}", e))?;
@@ -235,6 +239,7 @@ pub async fn test_ollama_connection(base_url: String) -> Result<ApiTestResult, S
     let start = std::time::Instant::now();
 
     let url = base_url.trim_end_matches("/v1");
+    ensure_url_allowed(url)?;
 
     let client = get_client_for_url(url).map_err(|e| format!("HTTP client error: {}", e))?;
     let response = client
@@ -511,6 +511,9 @@ pub async fn local_provider_pull_model(
     base_url: Option<String>,
     model_name: String,
 ) -> Result<bool, String> {
+    // Pulling fetches from the model registry, not just the local server
+    crate::http::ensure_online()?;
+
     let url = normalize_base_url(&base_url.unwrap_or_else(|| provider_id.default_base_url()));
 
     match provider_id {
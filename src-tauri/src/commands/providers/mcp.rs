@@ -1,8 +1,17 @@
 //! Tauri IPC commands for MCP functionality
 
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use tauri::State;
 
-use crate::mcp::error::McpErrorInfo;
+use crate::commands::error::CommandError;
+use crate::commands::storage::vector::{
+    chunk_document, embed_and_upsert_impl, get_collection_impl, ChunkingConfig,
+    EmbedAndUpsertPayload, EmbedDocument, VectorStoreState,
+};
+use crate::input_completion::{CompletionModelConfig, InputCompletionManager};
+use crate::mcp::config::{McpImportReport, McpImportSkipped, StandardMcpConfig};
+use crate::mcp::error::{McpError, McpErrorInfo};
 use crate::mcp::manager::McpManager;
 use crate::mcp::types::*;
 
@@ -36,6 +45,87 @@ pub async fn mcp_add_server(
         .map_err(|e| (&e).into())
 }
 
+/// Import servers from a standard `mcpServers` config document.
+///
+/// Each server is added through the same path as `mcp_add_server`, keyed by its
+/// name. Servers whose name matches an existing server id or name are skipped.
+/// With `auto_connect`, imported servers are marked for auto-start, except stdio
+/// servers whose command cannot be found on this system.
+#[tauri::command]
+pub async fn mcp_import_config(
+    manager: State<'_, McpManager>,
+    json: String,
+    auto_connect: Option<bool>,
+) -> Result<McpImportReport, McpErrorInfo> {
+    let standard = StandardMcpConfig::parse(&json).map_err(|e| McpErrorInfo::from(&e))?;
+    let auto_connect = auto_connect.unwrap_or(false);
+
+    let existing = manager.get_all_servers().await;
+    let mut report = McpImportReport::default();
+
+    for (name, server) in standard.mcp_servers {
+        if existing.iter().any(|s| s.id == name || s.name == name) {
+            report.skipped.push(McpImportSkipped {
+                name,
+                reason: "A server with this name already exists".to_string(),
+            });
+            continue;
+        }
+
+        let mut config = match server.to_server_config(&name) {
+            Ok(config) => config,
+            Err(e) => {
+                report.skipped.push(McpImportSkipped {
+                    name,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if auto_connect && config.enabled {
+            let command_found = match config.connection_type {
+                McpConnectionType::Stdio => mcp_check_command_exists(config.command.clone())
+                    .await
+                    .unwrap_or(false),
+                McpConnectionType::Sse | McpConnectionType::StreamableHttp => true,
+            };
+            if command_found {
+                config.auto_start = true;
+            } else {
+                report.auto_connect_disabled.push(name.clone());
+            }
+        }
+
+        match manager.add_server(name.clone(), config).await {
+            Ok(()) => report.imported.push(name),
+            Err(e) => report.skipped.push(McpImportSkipped {
+                name,
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    log::info!(
+        "Imported {} MCP server(s), skipped {}",
+        report.imported.len(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+/// Export all servers as a standard `mcpServers` config document
+#[tauri::command]
+pub async fn mcp_export_config(manager: State<'_, McpManager>) -> Result<String, McpErrorInfo> {
+    let servers = manager.get_all_servers().await;
+    let standard = StandardMcpConfig::from_servers(
+        servers
+            .iter()
+            .map(|server| (server.id.as_str(), &server.config)),
+    );
+    serde_json::to_string_pretty(&standard).map_err(|e| (&McpError::from(e)).into())
+}
+
 /// Remove an MCP server
 #[tauri::command]
 pub async fn mcp_remove_server(
@@ -93,6 +183,129 @@ pub async fn mcp_call_tool(
         .map_err(|e| (&e).into())
 }
 
+/// Call a tool and index its text output into a vector collection
+///
+/// The text content of the result is chunked according to `chunking`, embedded
+/// with `model` via Ollama at `base_url` (default `http://localhost:11434`), and
+/// upserted into `collection`. The older `chunk_size` (in characters) is still
+/// accepted and only used when `chunking` is not given.
+/// Chunk ids are derived from the server, tool, and chunk text, so indexing the
+/// same output again updates the existing points instead of duplicating them.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn mcp_call_tool_and_index(
+    manager: State<'_, McpManager>,
+    vector: State<'_, Arc<VectorStoreState>>,
+    server_id: String,
+    tool_name: String,
+    arguments: serde_json::Value,
+    collection: String,
+    model: String,
+    base_url: Option<String>,
+    chunk_size: Option<usize>,
+    chunking: Option<ChunkingConfig>,
+) -> Result<ToolIndexResult, McpErrorInfo> {
+    // Fail before running the tool when the target collection is missing
+    get_collection_impl(&vector, collection.clone()).map_err(index_error)?;
+
+    let result = manager
+        .call_tool(&server_id, &tool_name, arguments)
+        .await
+        .map_err(|e| McpErrorInfo::from(&e))?;
+    if result.is_error {
+        return Err(McpErrorInfo {
+            error_type: "tool_error".to_string(),
+            message: format!("Tool '{}' returned an error; nothing indexed", tool_name),
+            code: None,
+            data: Some(serde_json::json!({ "result": result })),
+        });
+    }
+
+    let chunking = chunking
+        .or_else(|| chunk_size.map(ChunkingConfig::from_chunk_chars))
+        .unwrap_or_default();
+    let chunks = chunk_document(&result.text_content(), &chunking);
+    if chunks.is_empty() {
+        log::info!(
+            "Tool '{}' on server '{}' returned no text to index",
+            tool_name,
+            server_id
+        );
+        return Ok(ToolIndexResult {
+            result,
+            chunks_indexed: 0,
+            warnings: vec!["Tool result contained no text to index".to_string()],
+        });
+    }
+
+    let indexed_at = chrono::Utc::now().timestamp_millis();
+    let chunk_count = chunks.len();
+    let documents = chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| EmbedDocument {
+            id: tool_chunk_id(&server_id, &tool_name, &chunk.text),
+            payload: Some(serde_json::json!({
+                "content": chunk.text,
+                "source": "mcp",
+                "server_id": server_id,
+                "tool_name": tool_name,
+                "heading": chunk.heading,
+                "chunk_index": index,
+                "chunk_count": chunk_count,
+                "indexed_at": indexed_at,
+            })),
+            text: chunk.text,
+        })
+        .collect();
+
+    let response = embed_and_upsert_impl(
+        &vector,
+        EmbedAndUpsertPayload {
+            collection: collection.clone(),
+            base_url: base_url.unwrap_or_else(|| "http://localhost:11434".to_string()),
+            model,
+            documents,
+        },
+    )
+    .await
+    .map_err(index_error)?;
+
+    log::info!(
+        "Indexed {} chunk(s) from tool '{}' on server '{}' into '{}'",
+        response.upserted,
+        tool_name,
+        server_id,
+        collection
+    );
+    Ok(ToolIndexResult {
+        result,
+        chunks_indexed: response.upserted,
+        warnings: response.warnings,
+    })
+}
+
+/// Stable point id for a chunk of tool output
+fn tool_chunk_id(server_id: &str, tool_name: &str, text: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [server_id, tool_name, text] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hasher.finalize();
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("mcp-{}", hex)
+}
+
+fn index_error(err: CommandError) -> McpErrorInfo {
+    McpErrorInfo {
+        error_type: "index_failed".to_string(),
+        message: err.message,
+        code: None,
+        data: err.details,
+    }
+}
+
 /// Call a tool on an MCP server from MCP Apps UI bridge with session/origin context
 #[tauri::command]
 pub async fn mcp_call_tool_from_ui(
@@ -118,13 +331,53 @@ pub async fn mcp_call_tool_from_ui(
 }
 
 /// Get all tools from all connected servers
+///
+/// With `sort_by_usage`, pinned tools come first, followed by the most used.
 #[tauri::command]
 pub async fn mcp_get_all_tools(
     manager: State<'_, McpManager>,
+    sort_by_usage: Option<bool>,
 ) -> Result<Vec<(String, McpTool)>, McpErrorInfo> {
+    if sort_by_usage.unwrap_or(false) {
+        return Ok(manager.get_all_tools_by_usage().await);
+    }
     Ok(manager.get_all_tools().await)
 }
 
+/// Get invocation counts and last-used times for called or pinned tools
+#[tauri::command]
+pub async fn mcp_get_tool_usage(
+    manager: State<'_, McpManager>,
+) -> Result<Vec<ToolUsage>, McpErrorInfo> {
+    Ok(manager.get_tool_usage().await)
+}
+
+/// Pin a tool as a favorite, returning false if it was already pinned
+#[tauri::command]
+pub async fn mcp_pin_tool(
+    manager: State<'_, McpManager>,
+    server_id: String,
+    tool_name: String,
+) -> Result<bool, McpErrorInfo> {
+    manager
+        .pin_tool(&server_id, &tool_name)
+        .await
+        .map_err(|e| (&e).into())
+}
+
+/// Unpin a favorite tool, returning false if it was not pinned
+#[tauri::command]
+pub async fn mcp_unpin_tool(
+    manager: State<'_, McpManager>,
+    server_id: String,
+    tool_name: String,
+) -> Result<bool, McpErrorInfo> {
+    manager
+        .unpin_tool(&server_id, &tool_name)
+        .await
+        .map_err(|e| (&e).into())
+}
+
 /// Read a resource from an MCP server
 #[tauri::command]
 pub async fn mcp_read_resource(
@@ -166,7 +419,35 @@ pub async fn mcp_respond_sampling(
         .map_err(|e| (&e).into())
 }
 
-/// Set roots for an MCP server and notify it
+/// Approve a pending sampling request and answer it with the configured LLM
+#[tauri::command]
+pub async fn mcp_approve_sampling(
+    manager: State<'_, McpManager>,
+    completion: State<'_, InputCompletionManager>,
+    server_id: String,
+    request_id: String,
+    model: Option<CompletionModelConfig>,
+) -> Result<SamplingResult, McpErrorInfo> {
+    manager
+        .approve_sampling(&server_id, &request_id, &completion, model)
+        .await
+        .map_err(|e| (&e).into())
+}
+
+/// Decline a pending sampling request from an MCP server
+#[tauri::command]
+pub async fn mcp_deny_sampling(
+    manager: State<'_, McpManager>,
+    server_id: String,
+    request_id: String,
+) -> Result<(), McpErrorInfo> {
+    manager
+        .deny_sampling(&server_id, &request_id)
+        .await
+        .map_err(|e| (&e).into())
+}
+
+/// Set and persist the roots (URIs or directory paths) advertised to an MCP server
 #[tauri::command]
 pub async fn mcp_set_roots(
     manager: State<'_, McpManager>,
@@ -179,7 +460,7 @@ pub async fn mcp_set_roots(
         .map_err(|e| (&e).into())
 }
 
-/// Get the current roots for an MCP server
+/// Get the roots advertised to an MCP server
 #[tauri::command]
 pub async fn mcp_get_roots(
     manager: State<'_, McpManager>,
@@ -449,6 +730,19 @@ pub async fn mcp_set_server_auto_start(
         .map_err(|e| e.to_info())
 }
 
+/// Set whether a server may issue sampling requests
+#[tauri::command]
+pub async fn mcp_set_server_allow_sampling(
+    manager: State<'_, McpManager>,
+    server_id: String,
+    allow: bool,
+) -> Result<bool, McpErrorInfo> {
+    manager
+        .set_server_allow_sampling(&server_id, allow)
+        .await
+        .map_err(|e| e.to_info())
+}
+
 /// Get MCP configuration file path
 #[tauri::command]
 pub async fn mcp_get_config_path(manager: State<'_, McpManager>) -> Result<String, McpErrorInfo> {
@@ -574,4 +868,13 @@ mod tests {
 
         assert!(result.is_error);
     }
+    #[test]
+    fn test_tool_chunk_id_is_stable() {
+        let id = tool_chunk_id("search", "web_search", "chunk text");
+        assert_eq!(id, tool_chunk_id("search", "web_search", "chunk text"));
+        assert!(id.starts_with("mcp-"));
+        assert_eq!(id.len(), 4 + 32);
+        assert_ne!(id, tool_chunk_id("search", "web_search", "other text"));
+        assert_ne!(id, tool_chunk_id("docs", "web_search", "chunk text"));
+    }
 }
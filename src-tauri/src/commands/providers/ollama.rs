@@ -191,6 +191,9 @@ pub async fn ollama_pull_model(
     base_url: String,
     model_name: String,
 ) -> Result<bool, String> {
+    // Pulling fetches from the model registry, not just the local server
+    crate::http::ensure_online()?;
+
     let url = normalize_base_url(&base_url);
     let client =
         get_client_for_url(&url).map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -363,6 +366,10 @@ pub async fn ollama_generate_embedding(
     let client =
         get_client_for_url(&url).map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
+    // Embedding batches fan out one call per chunk; share the global budget
+    let _permit = crate::resource_governor::global()
+        .acquire("embedding")
+        .await;
     let response = client
         .post(format!("{}/api/embed", url))
         .json(&serde_json::json!({
@@ -6,8 +6,9 @@ use log::{debug, error, info};
 use tauri::State;
 
 use crate::scheduler::{
-    CreateSystemTaskInput, SchedulerCapabilities, SchedulerState, SystemTask, SystemTaskId,
-    TaskConfirmationRequest, TaskRunResult,
+    CreateSystemTaskInput, SchedulerCapabilities, SchedulerState, SchedulerUsage, SystemTask,
+    SystemTaskId, TaskBundle, TaskConfirmationRequest, TaskImportResult, TaskRunRecord,
+    TaskRunResult,
 };
 
 /// Response type for operations that may require confirmation
@@ -30,6 +31,14 @@ pub fn scheduler_get_capabilities(state: State<'_, SchedulerState>) -> Scheduler
     state.capabilities()
 }
 
+/// Get the current task count against the platform task limit
+#[tauri::command]
+pub async fn scheduler_get_usage(
+    state: State<'_, SchedulerState>,
+) -> Result<SchedulerUsage, String> {
+    state.usage().await.map_err(|e| e.to_string())
+}
+
 /// Check if the system scheduler is available
 #[tauri::command]
 pub fn scheduler_is_available(state: State<'_, SchedulerState>) -> bool {
@@ -147,12 +156,16 @@ pub async fn scheduler_get_task(
     }
 }
 
-/// List all Cognia-managed system tasks
+/// List system tasks created through Cognia
+///
+/// `owned_only` defaults to true; when false, tasks created outside Cognia are
+/// included and marked `external` (read-only).
 #[tauri::command]
 pub async fn scheduler_list_tasks(
     state: State<'_, SchedulerState>,
+    owned_only: Option<bool>,
 ) -> Result<Vec<SystemTask>, String> {
-    match state.list_tasks().await {
+    match state.list_tasks(!owned_only.unwrap_or(true)).await {
         Ok(tasks) => {
             debug!("Listed {} system tasks", tasks.len());
             Ok(tasks)
@@ -231,6 +244,80 @@ pub async fn scheduler_run_task_now(
     }
 }
 
+/// Get the most recent runs of a task started through Cognia, newest first
+#[tauri::command]
+pub async fn scheduler_get_run_history(
+    state: State<'_, SchedulerState>,
+    task_id: SystemTaskId,
+    limit: Option<usize>,
+) -> Result<Vec<TaskRunRecord>, String> {
+    state
+        .get_task_run_history(&task_id, limit)
+        .map_err(|e| e.to_string())
+}
+
+/// Set how many runs are kept per task in the run history
+#[tauri::command]
+pub fn scheduler_set_run_history_limit(
+    state: State<'_, SchedulerState>,
+    limit: usize,
+) -> Result<(), String> {
+    state
+        .set_run_history_limit(limit)
+        .map_err(|e| e.to_string())
+}
+
+/// Export tasks as a JSON bundle; all Cognia tasks when `task_ids` is omitted
+#[tauri::command]
+pub async fn scheduler_export_tasks(
+    state: State<'_, SchedulerState>,
+    task_ids: Option<Vec<SystemTaskId>>,
+) -> Result<TaskBundle, String> {
+    state
+        .export_tasks(task_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import tasks from a bundle
+///
+/// Tasks needing confirmation are returned as pending confirmations unless
+/// `confirmed` is true; existing names are skipped unless `overwrite` is true.
+#[tauri::command]
+pub async fn scheduler_import_tasks(
+    state: State<'_, SchedulerState>,
+    bundle: TaskBundle,
+    overwrite: Option<bool>,
+    confirmed: Option<bool>,
+) -> Result<TaskImportResult, String> {
+    debug!(
+        "Importing task bundle: version={}, tasks={}",
+        bundle.version,
+        bundle.tasks.len()
+    );
+    state
+        .import_tasks(
+            bundle,
+            overwrite.unwrap_or(false),
+            confirmed.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Preview the next fire times of a cron expression as RFC 3339 strings
+///
+/// `count` defaults to 5; `timezone` is an IANA name, defaulting to local time.
+#[tauri::command]
+pub fn scheduler_preview_cron(
+    expression: String,
+    count: Option<usize>,
+    timezone: Option<String>,
+) -> Result<Vec<String>, String> {
+    crate::scheduler::preview_cron_schedule(&expression, count.unwrap_or(5), timezone)
+        .map_err(|e| e.to_string())
+}
+
 /// Cancel a pending confirmation
 #[tauri::command]
 pub async fn scheduler_cancel_confirmation(
@@ -309,6 +396,10 @@ pub fn scheduler_validate_task(
             if expression.split_whitespace().count() != 5 {
                 errors.push("Cron expression must have exactly 5 fields".to_string());
             }
+            if let Err(e) = state.validate_trigger_timezone(&input.trigger) {
+                errors.push(e.to_string());
+            }
+            warnings.extend(state.trigger_warnings(&input.trigger));
         }
         crate::scheduler::SystemTaskTrigger::Interval { seconds } => {
             if *seconds < 60 {
@@ -325,6 +416,12 @@ pub fn scheduler_validate_task(
         _ => {}
     }
 
+    if let Some(policy) = &input.retry_policy {
+        if let Err(e) = policy.validate() {
+            errors.push(e.to_string());
+        }
+    }
+
     // Validate action
     match &input.action {
         crate::scheduler::SystemTaskAction::ExecuteScript { language, code, .. } => {
@@ -369,6 +466,9 @@ pub fn scheduler_validate_task(
         next_run_at: None,
         last_result: None,
         metadata_state: crate::scheduler::TaskMetadataState::Full,
+        external: false,
+        notify_on: crate::scheduler::TaskNotifyOn::Never,
+        retry_policy: input.retry_policy.clone(),
     };
 
     let risk_level = temp_task.calculate_risk_level();
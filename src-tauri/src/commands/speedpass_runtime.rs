@@ -2,6 +2,7 @@
 
 use tauri::State;
 
+use crate::input_completion::{CompletionModelConfig, CompletionProvider, InputCompletionManager};
 use crate::speedpass_runtime::{
     ExtractTextbookRequest, ExtractTextbookResult, SpeedPassImportLegacySnapshotRequest,
     SpeedPassRuntimeSnapshot, SpeedPassRuntimeState, SpeedPassSaveSnapshotRequest,
@@ -44,6 +45,46 @@ pub async fn speedpass_runtime_extract_textbook_content(
 pub async fn speedpass_runtime_match_teacher_keypoints(
     request: TeacherKeyPointMatchCommandRequest,
     runtime_state: State<'_, SpeedPassRuntimeState>,
+    completion: State<'_, InputCompletionManager>,
 ) -> Result<TeacherKeyPointMatchResult, String> {
-    runtime_state.match_teacher_keypoints(request.user_id, request.request)
+    let provider = request.request.provider.clone();
+    let model = request.request.model.clone();
+    let base_model = completion.get_config().model;
+    runtime_state
+        .match_teacher_keypoints(
+            request.user_id,
+            request.request,
+            |system_prompt, prompt| async move {
+                let model =
+                    keypoint_match_model(base_model, provider.as_deref(), model.as_deref())?;
+                completion
+                    .generate_text(system_prompt, &prompt, Some(model))
+                    .await
+            },
+        )
+        .await
+}
+
+/// Completion model for keypoint matching, overriding the input-completion
+/// model with the requested provider and model
+///
+/// Input completion is tuned for short, fast suggestions, so the token and
+/// timeout limits are raised to fit a full match list.
+fn keypoint_match_model(
+    mut config: CompletionModelConfig,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> Result<CompletionModelConfig, String> {
+    if let Some(provider) = provider.map(str::trim).filter(|value| !value.is_empty()) {
+        config.provider = serde_json::from_value::<CompletionProvider>(serde_json::Value::String(
+            provider.to_lowercase(),
+        ))
+        .map_err(|_| format!("Unsupported completion provider: {provider}"))?;
+    }
+    if let Some(model) = model.map(str::trim).filter(|value| !value.is_empty()) {
+        config.model_id = model.to_string();
+    }
+    config.max_tokens = config.max_tokens.max(1024);
+    config.timeout_secs = config.timeout_secs.max(30);
+    Ok(config)
 }
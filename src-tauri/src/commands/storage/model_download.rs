@@ -195,6 +195,9 @@ pub struct DownloadResult {
     pub error: Option<String>,
 }
 
+/// Subdirectory of the models folder holding Tesseract language data
+pub const TESSDATA_SUBDIR: &str = "tessdata";
+
 // ============== Built-in Models ==============
 
 /// Get built-in model definitions
@@ -248,6 +251,46 @@ pub fn get_builtin_models() -> Vec<ModelDefinition> {
     ]
 }
 
+/// Build a model definition for any Tesseract language data file
+///
+/// `code` must already be a Tesseract language code such as `jpn` or `chi_sim`.
+pub fn tesseract_language_model(code: &str) -> ModelDefinition {
+    let filename = format!("{}.traineddata", code);
+    let known_size = get_builtin_models()
+        .into_iter()
+        .find(|m| m.filename == filename)
+        .map(|m| m.size);
+    ModelDefinition {
+        id: format!("tesseract-{}", code.replace('_', "-")),
+        name: format!("Tesseract {}", code),
+        category: ModelCategory::Ocr,
+        description: format!("{} language data for Tesseract OCR", code),
+        size: known_size.unwrap_or(0),
+        sources: [
+            (
+                ModelSource::GitHub,
+                format!(
+                    "https://github.com/tesseract-ocr/tessdata/raw/main/{}",
+                    filename
+                ),
+            ),
+            (
+                ModelSource::HuggingFace,
+                format!(
+                    "https://huggingface.co/datasets/tesseract-ocr/tessdata/resolve/main/{}",
+                    filename
+                ),
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        default_source: ModelSource::GitHub,
+        filename,
+        subdir: Some(TESSDATA_SUBDIR.to_string()),
+        required: false,
+    }
+}
+
 // ============== Helper Functions ==============
 
 /// Detect system proxy settings
@@ -293,7 +336,9 @@ async fn test_proxy_connectivity(proxy_url: &str) -> bool {
 }
 
 /// Create HTTP client with optional proxy
-async fn create_download_client(config: &DownloadConfig) -> Result<reqwest::Client, String> {
+pub(crate) async fn create_download_client(
+    config: &DownloadConfig,
+) -> Result<reqwest::Client, String> {
     let proxy_url = if let Some(ref proxy) = config.proxy_url {
         Some(proxy.clone())
     } else if config.use_system_proxy {
@@ -326,6 +371,34 @@ fn get_models_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data.join("models"))
 }
 
+/// Get the directory holding downloaded Tesseract language data
+pub(crate) fn get_tessdata_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(get_models_dir(app)?.join(TESSDATA_SUBDIR))
+}
+
+/// Response to a `HEAD` request for a download
+pub(crate) struct DownloadProbe {
+    pub status: reqwest::StatusCode,
+    /// Reported length, `None` when the server does not send one
+    pub size: Option<u64>,
+}
+
+/// Query the status and size of a download without fetching it
+pub(crate) async fn probe_download(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<DownloadProbe, String> {
+    let response = client
+        .head(url)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    Ok(DownloadProbe {
+        status: response.status(),
+        size: response.content_length().filter(|len| *len > 0),
+    })
+}
+
 // ============== Tauri Commands ==============
 
 /// Get list of available models
@@ -372,6 +445,11 @@ fn get_config_path(app: &AppHandle) -> Result<PathBuf, String> {
 /// Get download configuration
 #[tauri::command]
 pub async fn model_get_download_config(app: AppHandle) -> Result<DownloadConfig, String> {
+    load_download_config(&app).await
+}
+
+/// Load the saved download configuration, falling back to defaults
+pub(crate) async fn load_download_config(app: &AppHandle) -> Result<DownloadConfig, String> {
     let config_path = get_config_path(&app)?;
 
     if config_path.exists() {
@@ -419,8 +497,7 @@ pub async fn model_download(
     source: Option<ModelSource>,
     config: Option<DownloadConfig>,
 ) -> Result<DownloadResult, String> {
-    let config = config.unwrap_or_default();
-    let start_time = std::time::Instant::now();
+    crate::http::ensure_online()?;
 
     // Find model definition
     let models = get_builtin_models();
@@ -429,6 +506,22 @@ pub async fn model_download(
         .find(|m| m.id == model_id)
         .ok_or_else(|| format!("Model '{}' not found", model_id))?;
 
+    download_model(&app, model, source, config.unwrap_or_default()).await
+}
+
+/// Download a model definition, trying sources in priority order
+///
+/// Progress is reported through `model-download-progress` events. The file is written to a
+/// temporary name and only moved into place once complete.
+pub(crate) async fn download_model(
+    app: &AppHandle,
+    model: &ModelDefinition,
+    source: Option<ModelSource>,
+    config: DownloadConfig,
+) -> Result<DownloadResult, String> {
+    let model_id = model.id.clone();
+    let start_time = std::time::Instant::now();
+
     // Determine source priority
     let sources_to_try: Vec<ModelSource> = if let Some(s) = source {
         vec![s]
@@ -441,7 +534,7 @@ pub async fn model_download(
     };
 
     // Prepare download path
-    let models_dir = get_models_dir(&app)?;
+    let models_dir = get_models_dir(app)?;
     let mut target_path = models_dir.clone();
     if let Some(ref subdir) = model.subdir {
         target_path = target_path.join(subdir);
@@ -450,6 +543,7 @@ pub async fn model_download(
         .await
         .map_err(|e| format!("Failed to create directory: {}", e))?;
     target_path = target_path.join(&model.filename);
+    let partial_path = target_path.with_file_name(format!("{}.part", model.filename));
 
     // Create HTTP client
     let client = create_download_client(&config).await?;
@@ -480,17 +574,24 @@ pub async fn model_download(
             },
         );
 
-        match download_file(
-            &app,
+        let result = match download_file(
+            app,
             &client,
             &url,
-            &target_path,
+            &partial_path,
             &model_id,
             source,
             model.size,
         )
         .await
         {
+            Ok(_) => fs::rename(&partial_path, &target_path)
+                .await
+                .map_err(|e| format!("Failed to move download into place: {}", e)),
+            Err(e) => Err(e),
+        };
+
+        match result {
             Ok(_) => {
                 let elapsed = start_time.elapsed().as_secs();
 
@@ -521,6 +622,7 @@ pub async fn model_download(
             }
             Err(e) => {
                 log::warn!("Download from {:?} failed: {}", source, e);
+                let _ = fs::remove_file(&partial_path).await;
                 last_error = Some(e);
             }
         }
@@ -715,6 +817,19 @@ mod tests {
         assert!(models.iter().any(|m| m.id == "tesseract-eng"));
     }
 
+    #[test]
+    fn test_tesseract_language_model() {
+        let model = tesseract_language_model("chi_sim");
+        assert_eq!(model.id, "tesseract-chi-sim");
+        assert_eq!(model.filename, "chi_sim.traineddata");
+        assert_eq!(model.subdir.as_deref(), Some(TESSDATA_SUBDIR));
+        assert_eq!(model.size, 40_000_000);
+        assert!(model.sources[&ModelSource::GitHub].ends_with("/chi_sim.traineddata"));
+
+        // Languages without a built-in entry have no known size
+        assert_eq!(tesseract_language_model("kor").size, 0);
+    }
+
     #[test]
     fn test_download_config_default() {
         let config = DownloadConfig::default();
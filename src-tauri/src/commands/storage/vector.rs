@@ -1,14 +1,25 @@
 use anyhow::Result;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::commands::error::CommandError;
+use crate::commands::providers::ollama::ollama_generate_embedding;
+use crate::operations::{Operation, OperationHandle, OperationRegistry};
+
+mod chunking;
+mod storage_format;
+pub use chunking::{chunk_document, chunk_pages, ChunkingConfig, DocumentChunk};
+pub use storage_format::VectorStorageFormat;
+
 #[cfg(test)]
 mod tests;
 
@@ -30,6 +41,9 @@ pub struct CollectionMeta {
     pub embedding_model: Option<String>,
     #[serde(default)]
     pub embedding_provider: Option<String>,
+    /// Payload fields with an equality index, rebuilt in memory on load
+    #[serde(default)]
+    pub indexed_fields: Vec<String>,
 }
 
 fn default_timestamp() -> u64 {
@@ -51,46 +65,306 @@ pub struct PointRecord {
 struct VectorData {
     collections: HashMap<String, CollectionMeta>,
     points: HashMap<String, Vec<PointRecord>>, // collection -> points
+    #[serde(skip)]
+    indexes: HashMap<String, HashMap<String, PayloadIndex>>, // collection -> field -> index
+}
+
+impl VectorData {
+    /// Rebuild the payload indexes declared on `collection` from its current points
+    fn reindex(&mut self, collection: &str) {
+        let fields = self
+            .collections
+            .get(collection)
+            .map(|meta| meta.indexed_fields.clone())
+            .unwrap_or_default();
+        if fields.is_empty() {
+            self.indexes.remove(collection);
+            return;
+        }
+        let points = self
+            .points
+            .get(collection)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let indexes = fields
+            .into_iter()
+            .map(|field| {
+                let index = PayloadIndex::build(points, &field);
+                (field, index)
+            })
+            .collect();
+        self.indexes.insert(collection.to_string(), indexes);
+    }
+
+    fn reindex_all(&mut self) {
+        let names: Vec<String> = self.collections.keys().cloned().collect();
+        for name in names {
+            self.reindex(&name);
+        }
+    }
+}
+
+/// Equality index over one top-level payload field
+///
+/// Positions point into the collection's point list. Upserts and deletes update
+/// them in place; other changes to the list rebuild the index.
+#[derive(Debug, Default)]
+struct PayloadIndex {
+    /// Indexed value -> ascending point positions
+    positions: HashMap<String, Vec<usize>>,
+    /// Number of points with an indexed value for the field
+    indexed_points: usize,
+}
+
+impl PayloadIndex {
+    fn build(points: &[PointRecord], field: &str) -> Self {
+        let mut index = Self::default();
+        for (position, point) in points.iter().enumerate() {
+            if let Some(key) = point_index_key(point, field) {
+                index.positions.entry(key).or_default().push(position);
+                index.indexed_points += 1;
+            }
+        }
+        index
+    }
+
+    /// Index `point` at `position`
+    fn insert(&mut self, field: &str, position: usize, point: &PointRecord) {
+        let Some(key) = point_index_key(point, field) else {
+            return;
+        };
+        let positions = self.positions.entry(key).or_default();
+        if let Err(at) = positions.binary_search(&position) {
+            positions.insert(at, position);
+            self.indexed_points += 1;
+        }
+    }
+
+    /// Remove `point`, as currently stored at `position`, from the index
+    fn remove(&mut self, field: &str, position: usize, point: &PointRecord) {
+        let Some(key) = point_index_key(point, field) else {
+            return;
+        };
+        let Some(positions) = self.positions.get_mut(&key) else {
+            return;
+        };
+        if let Ok(at) = positions.binary_search(&position) {
+            positions.remove(at);
+            self.indexed_points -= 1;
+        }
+        if positions.is_empty() {
+            self.positions.remove(&key);
+        }
+    }
+
+    /// Drop the positions of deleted points and shift the rest down
+    ///
+    /// `kept[i]` tells whether the point previously at position `i` was kept.
+    fn retain_positions(&mut self, kept: &[bool]) {
+        let mut shifted = Vec::with_capacity(kept.len());
+        let mut next = 0;
+        for &is_kept in kept {
+            shifted.push(next);
+            if is_kept {
+                next += 1;
+            }
+        }
+        self.positions.retain(|_, positions| {
+            positions.retain(|position| kept[*position]);
+            for position in positions.iter_mut() {
+                *position = shifted[*position];
+            }
+            !positions.is_empty()
+        });
+        self.indexed_points = self.positions.values().map(Vec::len).sum();
+    }
+}
+
+/// Apply `update` to every payload index of `collection`
+fn update_indexes(
+    indexes: &mut HashMap<String, HashMap<String, PayloadIndex>>,
+    collection: &str,
+    mut update: impl FnMut(&str, &mut PayloadIndex),
+) {
+    if let Some(indexes) = indexes.get_mut(collection) {
+        for (field, index) in indexes.iter_mut() {
+            update(field, index);
+        }
+    }
+}
+
+fn point_index_key(point: &PointRecord, field: &str) -> Option<String> {
+    point
+        .payload
+        .as_ref()
+        .and_then(|payload| payload.get(field))
+        .and_then(payload_index_key)
+}
+
+/// Key under which a scalar payload value is indexed; objects and arrays are not indexed
+fn payload_index_key(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(_) | Value::Array(_) => None,
+        scalar => Some(scalar.to_string()),
+    }
+}
+
+/// Tuning for brute-force similarity search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VectorSearchConfig {
+    /// Number of points scored per work unit
+    pub chunk_size: usize,
+    /// Maximum worker threads for a single search (0 = available parallelism)
+    pub max_threads: usize,
+}
+
+impl Default for VectorSearchConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 4096,
+            max_threads: 0,
+        }
+    }
+}
+
+impl VectorSearchConfig {
+    fn effective_threads(&self, chunk_count: usize) -> usize {
+        let available = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let max = if self.max_threads == 0 {
+            available
+        } else {
+            self.max_threads
+        };
+        max.min(chunk_count).max(1)
+    }
 }
 
 pub struct VectorStoreState {
     path: PathBuf,
+    format: RwLock<VectorStorageFormat>,
     data: Mutex<VectorData>,
+    search_config: RwLock<VectorSearchConfig>,
 }
 
 impl VectorStoreState {
+    /// Load the store next to `path`, preferring a binary store over JSON when both exist
+    ///
+    /// A store that cannot be read is moved aside with a `.corrupt` suffix and
+    /// the next candidate is tried: the other format, then the `.bak` backups
+    /// left by format migrations. When nothing is readable the store starts
+    /// empty. The store keeps the format of the first file found.
     pub fn new(path: PathBuf) -> Result<Self> {
-        let data = if path.exists() {
-            let content = fs::read_to_string(&path)?;
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            VectorData::default()
+        use VectorStorageFormat::{Binary, Json};
+        let candidates = [
+            (path.with_extension(Binary.extension()), Binary, Binary),
+            (path.with_extension(Json.extension()), Json, Json),
+            // A JSON backup is left by migrating to binary and vice versa
+            (path.with_extension("json.bak"), Json, Binary),
+            (path.with_extension("bin.bak"), Binary, Json),
+        ];
+
+        let mut format = None;
+        let mut loaded = None;
+        for (candidate, encoding, store_format) in candidates {
+            if !candidate.exists() {
+                continue;
+            }
+            format.get_or_insert(store_format);
+            match read_store(&candidate, encoding) {
+                Ok(data) => {
+                    loaded = Some(data);
+                    break;
+                }
+                Err(e) => {
+                    let mut corrupt = candidate.clone().into_os_string();
+                    corrupt.push(".corrupt");
+                    let corrupt = PathBuf::from(corrupt);
+                    log::warn!(
+                        "[Vector] Failed to load {:?}, moving it to {:?}: {}",
+                        candidate,
+                        corrupt,
+                        e
+                    );
+                    if let Err(e) = fs::rename(&candidate, &corrupt) {
+                        log::warn!("[Vector] Failed to move {:?} aside: {}", candidate, e);
+                    }
+                }
+            }
+        }
+        let mut data = match loaded {
+            Some(data) => data,
+            None => {
+                if format.is_some() {
+                    log::warn!("[Vector] No readable vector store found, starting empty");
+                }
+                VectorData::default()
+            }
         };
+        data.reindex_all();
+        let format = format.unwrap_or(Json);
         Ok(Self {
             path,
+            format: RwLock::new(format),
             data: Mutex::new(data),
+            search_config: RwLock::new(VectorSearchConfig::default()),
         })
     }
 
+    /// Wait for an in-flight write before the app exits
+    ///
+    /// Every mutation persists the store before releasing the data lock, so once
+    /// the lock is acquired nothing is left unwritten.
+    pub fn shutdown(&self) {
+        let data = self.data.lock();
+        log::debug!(
+            "[Vector] Store flushed on shutdown ({} collection(s))",
+            data.collections.len()
+        );
+    }
+
+    fn storage_path(&self, format: VectorStorageFormat) -> PathBuf {
+        self.path.with_extension(format.extension())
+    }
+
     fn persist(&self, data: &VectorData) -> Result<()> {
-        if let Some(parent) = self.path.parent() {
+        let format = *self.format.read();
+        self.write_store(data, format)?;
+        Ok(())
+    }
+
+    fn write_store(&self, data: &VectorData, format: VectorStorageFormat) -> Result<PathBuf> {
+        let path = self.storage_path(format);
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         // Atomic write: write to temp file, then rename
-        let temp_path = self.path.with_extension("tmp");
-        let serialized = serde_json::to_string_pretty(data)?;
+        let temp_path = path.with_extension("tmp");
+        let serialized = match format {
+            VectorStorageFormat::Json => serde_json::to_vec_pretty(data)?,
+            VectorStorageFormat::Binary => storage_format::encode(data)?,
+        };
 
         {
             let mut file = fs::File::create(&temp_path)?;
-            file.write_all(serialized.as_bytes())?;
+            file.write_all(&serialized)?;
             file.sync_all()?; // Ensure data is flushed to disk
         }
 
         // Atomic rename (on most filesystems)
-        fs::rename(&temp_path, &self.path)?;
+        fs::rename(&temp_path, &path)?;
 
-        Ok(())
+        Ok(path)
+    }
+}
+
+fn read_store(path: &std::path::Path, encoding: VectorStorageFormat) -> Result<VectorData> {
+    match encoding {
+        VectorStorageFormat::Binary => storage_format::decode(&fs::read(path)?),
+        VectorStorageFormat::Json => Ok(serde_json::from_str(&fs::read_to_string(path)?)?),
     }
 }
 
@@ -131,18 +405,20 @@ pub struct CreateCollectionPayload {
 pub fn vector_create_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     payload: CreateCollectionPayload,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     create_collection_impl(&state, payload)
 }
 
 pub fn create_collection_impl(
     state: &VectorStoreState,
     payload: CreateCollectionPayload,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
     if let Some(existing) = data.collections.get(&payload.name) {
         if existing.dimension != payload.dimension {
-            return Err("Collection exists with different dimension".to_string());
+            return Err(CommandError::invalid_input(
+                "Collection exists with different dimension",
+            ));
         }
         return Ok(true);
     }
@@ -159,18 +435,87 @@ pub fn create_collection_impl(
             description: payload.description,
             embedding_model: payload.embedding_model,
             embedding_provider: payload.embedding_provider,
+            indexed_fields: Vec::new(),
         },
     );
     data.points.insert(payload.name, Vec::new());
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
-pub fn delete_collection_impl(state: &VectorStoreState, name: String) -> Result<bool, String> {
+/// Text embedded to discover a model's output dimension
+const DIMENSION_PROBE_INPUT: &str = "dimension probe";
+
+/// Provider recorded on collections whose dimension was probed from a model
+const PROBED_EMBEDDING_PROVIDER: &str = "ollama";
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCollectionForModelPayload {
+    pub name: String,
+    pub base_url: String,
+    pub model: String,
+    #[serde(default)]
+    pub metadata: Option<Value>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Create a collection whose dimension is discovered by embedding a probe input
+/// with `model`, so callers never have to know the dimension up front.
+#[tauri::command]
+pub async fn vector_create_collection_for_model(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    payload: CreateCollectionForModelPayload,
+) -> Result<CollectionMeta, CommandError> {
+    let probe = ollama_generate_embedding(
+        payload.base_url.clone(),
+        payload.model.clone(),
+        DIMENSION_PROBE_INPUT.to_string(),
+    )
+    .await
+    .map_err(CommandError::from)?;
+    create_collection_for_model_impl(&state, payload, probe.len())
+}
+
+pub fn create_collection_for_model_impl(
+    state: &VectorStoreState,
+    payload: CreateCollectionForModelPayload,
+    dimension: usize,
+) -> Result<CollectionMeta, CommandError> {
+    if dimension == 0 {
+        return Err(CommandError::invalid_input(format!(
+            "Model '{}' returned an empty embedding",
+            payload.model
+        )));
+    }
+    let name = payload.name.clone();
+    create_collection_impl(
+        state,
+        CreateCollectionPayload {
+            name: payload.name,
+            dimension,
+            metadata: payload.metadata,
+            description: payload.description,
+            embedding_model: Some(payload.model),
+            embedding_provider: Some(PROBED_EMBEDDING_PROVIDER.to_string()),
+        },
+    )?;
+    get_collection_impl(state, name)
+}
+
+pub fn delete_collection_impl(
+    state: &VectorStoreState,
+    name: String,
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
     data.collections.remove(&name);
     data.points.remove(&name);
-    state.persist(&data).map_err(|e| e.to_string())?;
+    data.indexes.remove(&name);
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
@@ -178,7 +523,7 @@ pub fn delete_collection_impl(state: &VectorStoreState, name: String) -> Result<
 pub fn vector_delete_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     name: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     delete_collection_impl(&state, name)
 }
 
@@ -187,7 +532,7 @@ pub fn vector_rename_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     old_name: String,
     new_name: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     rename_collection_impl(&state, old_name, new_name)
 }
 
@@ -195,7 +540,7 @@ pub fn rename_collection_impl(
     state: &VectorStoreState,
     old_name: String,
     new_name: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
 
     // Check if old collection exists
@@ -203,11 +548,13 @@ pub fn rename_collection_impl(
         .collections
         .get(&old_name)
         .cloned()
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&old_name))?;
 
     // Check if new name already exists
     if data.collections.contains_key(&new_name) {
-        return Err("Collection with new name already exists".to_string());
+        return Err(CommandError::already_exists(
+            "Collection with new name already exists",
+        ));
     }
 
     // Update metadata
@@ -219,10 +566,14 @@ pub fn rename_collection_impl(
     data.collections.insert(new_name.clone(), meta);
 
     if let Some(points) = data.points.remove(&old_name) {
-        data.points.insert(new_name, points);
+        data.points.insert(new_name.clone(), points);
     }
+    data.indexes.remove(&old_name);
+    data.reindex(&new_name);
 
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
@@ -230,11 +581,14 @@ pub fn rename_collection_impl(
 pub fn vector_truncate_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     name: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     truncate_collection_impl(&state, name)
 }
 
-pub fn truncate_collection_impl(state: &VectorStoreState, name: String) -> Result<bool, String> {
+pub fn truncate_collection_impl(
+    state: &VectorStoreState,
+    name: String,
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
 
     // Check if collection exists
@@ -242,7 +596,7 @@ pub fn truncate_collection_impl(state: &VectorStoreState, name: String) -> Resul
         .collections
         .get(&name)
         .cloned()
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&name))?;
 
     // Update metadata
     meta.document_count = 0;
@@ -250,9 +604,12 @@ pub fn truncate_collection_impl(state: &VectorStoreState, name: String) -> Resul
     data.collections.insert(name.clone(), meta);
 
     // Clear all points
-    data.points.insert(name, Vec::new());
+    data.points.insert(name.clone(), Vec::new());
+    data.reindex(&name);
 
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
@@ -266,20 +623,20 @@ pub struct CollectionExport {
 pub fn vector_export_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     name: String,
-) -> Result<CollectionExport, String> {
+) -> Result<CollectionExport, CommandError> {
     export_collection_impl(&state, name)
 }
 
 pub fn export_collection_impl(
     state: &VectorStoreState,
     name: String,
-) -> Result<CollectionExport, String> {
+) -> Result<CollectionExport, CommandError> {
     let data = state.data.lock();
     let meta = data
         .collections
         .get(&name)
         .cloned()
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&name))?;
     let points = data.points.get(&name).cloned().unwrap_or_default();
 
     Ok(CollectionExport { meta, points })
@@ -296,7 +653,7 @@ pub fn vector_import_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     import_data: CollectionImport,
     overwrite: Option<bool>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     import_collection_impl(&state, import_data, overwrite)
 }
 
@@ -304,13 +661,15 @@ pub fn import_collection_impl(
     state: &VectorStoreState,
     import_data: CollectionImport,
     overwrite: Option<bool>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
     let overwrite = overwrite.unwrap_or(false);
 
     // Check if collection already exists
     if data.collections.contains_key(&import_data.meta.name) && !overwrite {
-        return Err("Collection already exists. Use overwrite=true to replace".to_string());
+        return Err(CommandError::already_exists(
+            "Collection already exists. Use overwrite=true to replace",
+        ));
     }
 
     // Update timestamps
@@ -325,20 +684,26 @@ pub fn import_collection_impl(
 
     // Import collection and points
     data.collections.insert(collection_name.clone(), meta);
-    data.points.insert(collection_name, import_data.points);
+    data.points
+        .insert(collection_name.clone(), import_data.points);
+    data.reindex(&collection_name);
 
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
 #[tauri::command]
 pub fn vector_list_collections(
     state: tauri::State<Arc<VectorStoreState>>,
-) -> Result<Vec<CollectionMeta>, String> {
+) -> Result<Vec<CollectionMeta>, CommandError> {
     list_collections_impl(&state)
 }
 
-pub fn list_collections_impl(state: &VectorStoreState) -> Result<Vec<CollectionMeta>, String> {
+pub fn list_collections_impl(
+    state: &VectorStoreState,
+) -> Result<Vec<CollectionMeta>, CommandError> {
     let data = state.data.lock();
     let mut list: Vec<CollectionMeta> = Vec::new();
     for (name, meta) in data.collections.iter() {
@@ -353,20 +718,20 @@ pub fn list_collections_impl(state: &VectorStoreState) -> Result<Vec<CollectionM
 pub fn vector_get_collection(
     state: tauri::State<Arc<VectorStoreState>>,
     name: String,
-) -> Result<CollectionMeta, String> {
+) -> Result<CollectionMeta, CommandError> {
     get_collection_impl(&state, name)
 }
 
 pub fn get_collection_impl(
     state: &VectorStoreState,
     name: String,
-) -> Result<CollectionMeta, String> {
+) -> Result<CollectionMeta, CommandError> {
     let data = state.data.lock();
     let mut meta = data
         .collections
         .get(&name)
         .cloned()
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&name))?;
     meta.document_count = collection_count(&name, &data);
     Ok(meta)
 }
@@ -383,23 +748,332 @@ pub fn vector_upsert_points(
     state: tauri::State<Arc<VectorStoreState>>,
     collection: String,
     points: Vec<UpsertPoint>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     upsert_points_impl(&state, collection, points)
 }
 
-fn validate_vector(vector: &[f64], id: &str) -> Result<(), String> {
+#[derive(Debug, Deserialize)]
+pub struct EmbedDocument {
+    pub id: String,
+    pub text: String,
+    pub payload: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EmbedAndUpsertPayload {
+    pub collection: String,
+    pub base_url: String,
+    pub model: String,
+    pub documents: Vec<EmbedDocument>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmbedAndUpsertResponse {
+    pub upserted: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Embed documents with `model` and upsert them into a collection.
+///
+/// Embedding with a different model than the collection was created for is
+/// allowed, but reported as a warning since the scores would not be comparable.
+#[tauri::command]
+pub async fn vector_embed_and_upsert(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    payload: EmbedAndUpsertPayload,
+) -> Result<EmbedAndUpsertResponse, CommandError> {
+    embed_and_upsert_impl(&state, payload).await
+}
+
+/// Embed and upsert documents as a background operation.
+///
+/// Returns the operation immediately; poll it with `operation_get` for progress
+/// and the `EmbedAndUpsertResponse` result. Cancelling stops before the next
+/// document and nothing is upserted.
+#[tauri::command]
+pub async fn vector_embed_and_upsert_async(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    operations: tauri::State<'_, OperationRegistry>,
+    payload: EmbedAndUpsertPayload,
+) -> Result<Operation, CommandError> {
+    // Fail fast on an unknown collection instead of in the background
+    get_collection_impl(&state, payload.collection.clone())?;
+    let state = state.inner().clone();
+    Ok(
+        operations.spawn("vector.embedAndUpsert", move |handle| async move {
+            embed_and_upsert_with_progress(&state, payload, Some(&handle))
+                .await
+                .map_err(|e| e.to_string())
+        }),
+    )
+}
+
+pub async fn embed_and_upsert_impl(
+    state: &VectorStoreState,
+    payload: EmbedAndUpsertPayload,
+) -> Result<EmbedAndUpsertResponse, CommandError> {
+    embed_and_upsert_with_progress(state, payload, None).await
+}
+
+async fn embed_and_upsert_with_progress(
+    state: &VectorStoreState,
+    payload: EmbedAndUpsertPayload,
+    operation: Option<&OperationHandle>,
+) -> Result<EmbedAndUpsertResponse, CommandError> {
+    let meta = get_collection_impl(state, payload.collection.clone())?;
+    let warnings: Vec<String> = embedding_model_warning(&meta, &payload.model)
+        .into_iter()
+        .collect();
+    for warning in &warnings {
+        log::warn!("[Vector] {}", warning);
+    }
+
+    let total = payload.documents.len() as u64;
+    let mut points = Vec::with_capacity(payload.documents.len());
+    for (index, document) in payload.documents.into_iter().enumerate() {
+        if let Some(operation) = operation {
+            if operation.is_cancelled() {
+                return Err(CommandError::from("Embedding was cancelled".to_string()));
+            }
+            operation.set_progress(index as u64, Some(total), Some("Embedding".to_string()));
+        }
+        let vector = ollama_generate_embedding(
+            payload.base_url.clone(),
+            payload.model.clone(),
+            document.text,
+        )
+        .await
+        .map_err(CommandError::from)?;
+        points.push(UpsertPoint {
+            id: document.id,
+            vector,
+            payload: document.payload,
+        });
+    }
+
+    let upserted = points.len();
+    if let Some(operation) = operation {
+        operation.set_progress(total, Some(total), Some("Upserting".to_string()));
+    }
+    upsert_points_impl(state, payload.collection, points)?;
+    Ok(EmbedAndUpsertResponse { upserted, warnings })
+}
+
+/// Maximum sample texts embedded per model by `vector_benchmark_embedding_models`
+pub const MAX_BENCHMARK_SAMPLES: usize = 50;
+
+/// Benchmark result of one embedding model
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingModelBenchmark {
+    pub model: String,
+    /// Whether every sample could be embedded
+    pub available: bool,
+    pub dimension: Option<usize>,
+    /// Mean time to embed one sample
+    pub avg_latency_ms: Option<f64>,
+    /// Mean pairwise cosine similarity of the samples
+    pub mean_similarity: Option<f64>,
+    /// Difference between the most and least similar sample pairs; a wider spread
+    /// means the model separates related and unrelated texts more clearly
+    pub similarity_spread: Option<f64>,
+    /// Why the model is unavailable
+    pub error: Option<String>,
+}
+
+impl EmbeddingModelBenchmark {
+    fn unavailable(model: String, error: String) -> Self {
+        Self {
+            model,
+            available: false,
+            dimension: None,
+            avg_latency_ms: None,
+            mean_similarity: None,
+            similarity_spread: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Results of benchmarking several embedding models on the same samples
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingBenchmarkReport {
+    pub samples: usize,
+    pub models: Vec<EmbeddingModelBenchmark>,
+    /// Available model with the widest similarity spread
+    pub best_model: Option<String>,
+}
+
+/// Embed the sample texts with each Ollama model and compare dimension, latency
+/// and similarity spread.
+///
+/// Models that are missing or fail to embed are reported as unavailable instead of
+/// failing the benchmark. At least two samples are needed for similarity figures.
+#[tauri::command]
+pub async fn vector_benchmark_embedding_models(
+    models: Vec<String>,
+    sample_texts: Vec<String>,
+    base_url: Option<String>,
+) -> Result<EmbeddingBenchmarkReport, CommandError> {
+    let base_url = base_url.unwrap_or_else(|| "http://localhost:11434".to_string());
+    let samples: Vec<String> = sample_texts
+        .into_iter()
+        .filter(|text| !text.trim().is_empty())
+        .collect();
+    if samples.is_empty() {
+        return Err(CommandError::invalid_input("No sample texts to embed"));
+    }
+    if samples.len() > MAX_BENCHMARK_SAMPLES {
+        return Err(CommandError::invalid_input(format!(
+            "At most {} sample texts can be benchmarked",
+            MAX_BENCHMARK_SAMPLES
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for model in models {
+        let model = model.trim().to_string();
+        if model.is_empty() || !seen.insert(model.clone()) {
+            continue;
+        }
+        results.push(benchmark_embedding_model(&base_url, model, &samples).await);
+    }
+    if results.is_empty() {
+        return Err(CommandError::invalid_input(
+            "No embedding models to benchmark",
+        ));
+    }
+
+    Ok(embedding_benchmark_report(samples.len(), results))
+}
+
+async fn benchmark_embedding_model(
+    base_url: &str,
+    model: String,
+    samples: &[String],
+) -> EmbeddingModelBenchmark {
+    let mut embeddings = Vec::with_capacity(samples.len());
+    let mut latencies_ms = Vec::with_capacity(samples.len());
+    for text in samples {
+        let started = std::time::Instant::now();
+        match ollama_generate_embedding(base_url.to_string(), model.clone(), text.clone()).await {
+            Ok(embedding) => {
+                latencies_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+                embeddings.push(embedding);
+            }
+            Err(e) => {
+                log::info!("[Vector] Embedding model '{}' unavailable: {}", model, e);
+                return EmbeddingModelBenchmark::unavailable(model, e);
+            }
+        }
+    }
+    summarize_embedding_benchmark(model, &embeddings, &latencies_ms)
+}
+
+/// Summarize the embeddings one model produced for the benchmark samples
+fn summarize_embedding_benchmark(
+    model: String,
+    embeddings: &[Vec<f64>],
+    latencies_ms: &[f64],
+) -> EmbeddingModelBenchmark {
+    let dimension = embeddings.first().map(Vec::len).unwrap_or(0);
+    if dimension == 0 {
+        return EmbeddingModelBenchmark::unavailable(
+            model,
+            "Model returned an empty embedding".to_string(),
+        );
+    }
+    if embeddings
+        .iter()
+        .any(|embedding| embedding.len() != dimension)
+    {
+        return EmbeddingModelBenchmark::unavailable(
+            model,
+            "Model returned embeddings of different dimensions".to_string(),
+        );
+    }
+
+    let mut similarities = Vec::new();
+    for (i, a) in embeddings.iter().enumerate() {
+        for b in &embeddings[i + 1..] {
+            similarities.push(cosine_similarity(a, b));
+        }
+    }
+    let (mean_similarity, similarity_spread) = if similarities.is_empty() {
+        (None, None)
+    } else {
+        let mean = similarities.iter().sum::<f64>() / similarities.len() as f64;
+        let min = similarities.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = similarities
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        (Some(mean), Some(max - min))
+    };
+
+    EmbeddingModelBenchmark {
+        model,
+        available: true,
+        dimension: Some(dimension),
+        avg_latency_ms: (!latencies_ms.is_empty())
+            .then(|| latencies_ms.iter().sum::<f64>() / latencies_ms.len() as f64),
+        mean_similarity,
+        similarity_spread,
+        error: None,
+    }
+}
+
+fn embedding_benchmark_report(
+    samples: usize,
+    models: Vec<EmbeddingModelBenchmark>,
+) -> EmbeddingBenchmarkReport {
+    let best_model = models
+        .iter()
+        .filter(|result| result.available)
+        .filter_map(|result| {
+            result
+                .similarity_spread
+                .map(|spread| (spread, &result.model))
+        })
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+        .map(|(_, model)| model.clone());
+    EmbeddingBenchmarkReport {
+        samples,
+        models,
+        best_model,
+    }
+}
+
+/// Warning when `model` differs from the model a collection was created for
+fn embedding_model_warning(meta: &CollectionMeta, model: &str) -> Option<String> {
+    let recorded = meta.embedding_model.as_deref()?;
+    (recorded != model).then(|| {
+        format!(
+            "Collection '{}' was created for embedding model '{}' but '{}' was used",
+            meta.name, recorded, model
+        )
+    })
+}
+
+/// Error for a missing collection, carrying its name for the frontend
+fn collection_not_found(name: &str) -> CommandError {
+    CommandError::not_found("Collection not found")
+        .with_details(serde_json::json!({ "collection": name }))
+}
+
+fn validate_vector(vector: &[f64], id: &str) -> Result<(), CommandError> {
     for (i, &val) in vector.iter().enumerate() {
         if val.is_nan() {
-            return Err(format!(
+            return Err(CommandError::invalid_input(format!(
                 "Vector for id '{}' contains NaN at index {}",
                 id, i
-            ));
+            )));
         }
         if val.is_infinite() {
-            return Err(format!(
+            return Err(CommandError::invalid_input(format!(
                 "Vector for id '{}' contains Infinity at index {}",
                 id, i
-            ));
+            )));
         }
     }
     Ok(())
@@ -409,42 +1083,58 @@ pub fn upsert_points_impl(
     state: &VectorStoreState,
     collection: String,
     points: Vec<UpsertPoint>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let mut data = state.data.lock();
 
     // Check collection exists and get dimension
     let collection_meta = data
         .collections
         .get(&collection)
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&collection))?;
     let expected_dimension = collection_meta.dimension;
 
     // Validate dimensions and vector values
     for p in &points {
         if p.vector.len() != expected_dimension {
-            return Err(format!(
+            return Err(CommandError::invalid_input(format!(
                 "Vector dimension mismatch for id '{}': expected {}, got {}",
                 p.id,
                 expected_dimension,
                 p.vector.len()
-            ));
+            )));
         }
         validate_vector(&p.vector, &p.id)?;
     }
 
-    let entry = data.points.entry(collection.clone()).or_default();
+    let VectorData {
+        collections,
+        points: all_points,
+        indexes,
+    } = &mut *data;
+    let entry = all_points.entry(collection.clone()).or_default();
     let mut added_count = 0;
 
     for p in points {
-        if let Some(existing) = entry.iter_mut().find(|x| x.id == p.id) {
+        if let Some(position) = entry.iter().position(|x| x.id == p.id) {
+            let existing = &mut entry[position];
+            update_indexes(indexes, &collection, |field, index| {
+                index.remove(field, position, existing)
+            });
             existing.vector = p.vector;
             existing.payload = p.payload;
+            update_indexes(indexes, &collection, |field, index| {
+                index.insert(field, position, existing)
+            });
         } else {
             entry.push(PointRecord {
                 id: p.id,
                 vector: p.vector,
                 payload: p.payload,
             });
+            let position = entry.len() - 1;
+            update_indexes(indexes, &collection, |field, index| {
+                index.insert(field, position, &entry[position])
+            });
             added_count += 1;
         }
     }
@@ -452,13 +1142,15 @@ pub fn upsert_points_impl(
     // Update document count and timestamp in collection metadata
     if added_count > 0 {
         let entry_len = entry.len();
-        if let Some(meta) = data.collections.get_mut(&collection) {
+        if let Some(meta) = collections.get_mut(&collection) {
             meta.document_count = entry_len;
             meta.updated_at = default_timestamp();
         }
     }
 
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(true)
 }
 
@@ -467,7 +1159,7 @@ pub fn vector_delete_points(
     state: tauri::State<Arc<VectorStoreState>>,
     collection: String,
     ids: Vec<String>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     delete_points_impl(&state, collection, ids)
 }
 
@@ -475,25 +1167,47 @@ pub fn delete_points_impl(
     state: &VectorStoreState,
     collection: String,
     ids: Vec<String>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
+    let ids: HashSet<String> = ids.into_iter().collect();
+    retain_points_impl(state, &collection, |p| !ids.contains(&p.id))?;
+    Ok(true)
+}
+
+/// Keep only the points of `collection` matching `keep`, returning how many were deleted
+///
+/// A missing collection has nothing to delete. The store is only rewritten when
+/// points were removed.
+pub fn retain_points_impl(
+    state: &VectorStoreState,
+    collection: &str,
+    mut keep: impl FnMut(&PointRecord) -> bool,
+) -> Result<usize, CommandError> {
     let mut data = state.data.lock();
-    if let Some(points) = data.points.get_mut(&collection) {
-        let original_len = points.len();
-        points.retain(|p| !ids.contains(&p.id));
-        let deleted_count = original_len - points.len();
-
-        // Update document count and timestamp in collection metadata
-        if deleted_count > 0 {
-            let points_len = points.len();
-            if let Some(meta) = data.collections.get_mut(&collection) {
-                meta.document_count = points_len;
-                meta.updated_at = default_timestamp();
-            }
-        }
+    let Some(points) = data.points.get_mut(collection) else {
+        return Ok(0);
+    };
+    let kept: Vec<bool> = points.iter().map(&mut keep).collect();
+    let deleted_count = kept.iter().filter(|is_kept| !**is_kept).count();
+    if deleted_count == 0 {
+        return Ok(0);
     }
+    let mut flags = kept.iter();
+    points.retain(|_| *flags.next().unwrap_or(&false));
 
-    state.persist(&data).map_err(|e| e.to_string())?;
-    Ok(true)
+    // Update document count and timestamp in collection metadata
+    let points_len = points.len();
+    if let Some(meta) = data.collections.get_mut(collection) {
+        meta.document_count = points_len;
+        meta.updated_at = default_timestamp();
+    }
+    update_indexes(&mut data.indexes, collection, |_, index| {
+        index.retain_positions(&kept)
+    });
+
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    Ok(deleted_count)
 }
 
 #[tauri::command]
@@ -501,7 +1215,7 @@ pub fn vector_get_points(
     state: tauri::State<Arc<VectorStoreState>>,
     collection: String,
     ids: Vec<String>,
-) -> Result<Vec<PointRecord>, String> {
+) -> Result<Vec<PointRecord>, CommandError> {
     get_points_impl(&state, collection, ids)
 }
 
@@ -509,12 +1223,12 @@ pub fn get_points_impl(
     state: &VectorStoreState,
     collection: String,
     ids: Vec<String>,
-) -> Result<Vec<PointRecord>, String> {
+) -> Result<Vec<PointRecord>, CommandError> {
     let data = state.data.lock();
     let points = data
         .points
         .get(&collection)
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&collection))?;
     let filtered = points
         .iter()
         .filter(|p| ids.contains(&p.id))
@@ -523,7 +1237,7 @@ pub fn get_points_impl(
     Ok(filtered)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct PayloadFilter {
     pub key: String,
     pub value: Value,
@@ -555,6 +1269,62 @@ pub struct SearchResponse {
     pub total: usize,
     pub offset: usize,
     pub limit: usize,
+    /// Distribution of scores across every match, not just the returned page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_stats: Option<ScoreStats>,
+}
+
+/// Summary of similarity scores across a set of matches
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct ScoreStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+/// Running min/max/sum of scores, mergeable across search workers
+#[derive(Debug, Clone, Copy, Default)]
+struct ScoreAccumulator {
+    count: usize,
+    min: f64,
+    max: f64,
+    sum: f64,
+}
+
+impl ScoreAccumulator {
+    fn add(&mut self, score: f64) {
+        if self.count == 0 {
+            self.min = score;
+            self.max = score;
+        } else {
+            self.min = self.min.min(score);
+            self.max = self.max.max(score);
+        }
+        self.count += 1;
+        self.sum += score;
+    }
+
+    fn merge(&mut self, other: ScoreAccumulator) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other;
+            return;
+        }
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+    }
+
+    fn stats(&self) -> Option<ScoreStats> {
+        (self.count > 0).then(|| ScoreStats {
+            min: self.min,
+            max: self.max,
+            mean: self.sum / self.count as f64,
+        })
+    }
 }
 
 fn matches_filter(payload_value: &Value, filter: &PayloadFilter) -> bool {
@@ -687,65 +1457,282 @@ fn apply_payload_filters(point: &PointRecord, filters: &[PayloadFilter], mode: &
     !use_or
 }
 
+/// Points of `collection` matching `filters`, narrowed through its payload indexes
+/// when possible so only candidates are checked and scored.
+fn filter_points<'a>(
+    data: &VectorData,
+    collection: &str,
+    points: &'a [PointRecord],
+    filters: Option<&[PayloadFilter]>,
+    mode: &str,
+) -> Vec<&'a PointRecord> {
+    let Some(filters) = filters else {
+        return points.iter().collect();
+    };
+    let candidates = data
+        .indexes
+        .get(collection)
+        .and_then(|indexes| indexed_candidates(indexes, filters, mode));
+    match candidates {
+        Some(positions) => positions
+            .into_iter()
+            .filter_map(|position| points.get(position))
+            .filter(|p| apply_payload_filters(p, filters, mode))
+            .collect(),
+        None => points
+            .iter()
+            .filter(|p| apply_payload_filters(p, filters, mode))
+            .collect(),
+    }
+}
+
+/// Point positions that can match `filters` according to the payload indexes,
+/// or `None` when every point has to be checked.
+fn indexed_candidates(
+    indexes: &HashMap<String, PayloadIndex>,
+    filters: &[PayloadFilter],
+    mode: &str,
+) -> Option<Vec<usize>> {
+    if filters.is_empty() {
+        return None;
+    }
+
+    if mode == "or" {
+        // A filter the index cannot answer may match any point
+        let mut positions = Vec::new();
+        for filter in filters {
+            positions.extend_from_slice(index_lookup(indexes, filter)?);
+        }
+        positions.sort_unstable();
+        positions.dedup();
+        return Some(positions);
+    }
+
+    // Every indexed equality filter narrows the set, the rest are checked per point
+    let mut lookups = filters
+        .iter()
+        .filter_map(|filter| index_lookup(indexes, filter));
+    let mut positions = lookups.next()?.to_vec();
+    for lookup in lookups {
+        let allowed: HashSet<usize> = lookup.iter().copied().collect();
+        positions.retain(|position| allowed.contains(position));
+    }
+    Some(positions)
+}
+
+fn index_lookup<'a>(
+    indexes: &'a HashMap<String, PayloadIndex>,
+    filter: &PayloadFilter,
+) -> Option<&'a [usize]> {
+    if filter.operation != "equals" {
+        return None;
+    }
+    let index = indexes.get(&filter.key)?;
+    let key = payload_index_key(&filter.value)?;
+    Some(
+        index
+            .positions
+            .get(&key)
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+    )
+}
+
+/// Search candidate ordered so that "greater" means a better match
+/// (higher score first, then earlier position for stable ties).
+#[derive(Debug, Clone, Copy)]
+struct ScoredCandidate {
+    score: f64,
+    index: usize,
+}
+
+impl PartialEq for ScoredCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredCandidate {}
+
+impl PartialOrd for ScoredCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Bounded min-heap that keeps the best `capacity` candidates
+struct TopK {
+    capacity: usize,
+    heap: BinaryHeap<Reverse<ScoredCandidate>>,
+    matched: usize,
+    scores: ScoreAccumulator,
+}
+
+impl TopK {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity.min(1024)),
+            matched: 0,
+            scores: ScoreAccumulator::default(),
+        }
+    }
+
+    fn push(&mut self, candidate: ScoredCandidate) {
+        self.matched += 1;
+        self.scores.add(candidate.score);
+        if self.capacity == 0 {
+            return;
+        }
+        if self.heap.len() < self.capacity {
+            self.heap.push(Reverse(candidate));
+        } else if let Some(Reverse(worst)) = self.heap.peek() {
+            if candidate > *worst {
+                self.heap.pop();
+                self.heap.push(Reverse(candidate));
+            }
+        }
+    }
+
+    fn merge(&mut self, other: TopK) {
+        let matched = self.matched + other.matched;
+        let mut scores = self.scores;
+        scores.merge(other.scores);
+        for Reverse(candidate) in other.heap {
+            self.push(candidate);
+        }
+        self.matched = matched;
+        self.scores = scores;
+    }
+
+    /// Best candidates first
+    fn into_sorted(self) -> Vec<ScoredCandidate> {
+        let mut items: Vec<ScoredCandidate> = self.heap.into_iter().map(|r| r.0).collect();
+        items.sort_by(|a, b| b.cmp(a));
+        items
+    }
+}
+
+/// Score points against a query in parallel chunks, keeping only the best `keep` matches.
+///
+/// Returns the best candidates (indices into `points`), the number of points that
+/// passed the score threshold and the score statistics over those points.
+fn score_top_k(
+    points: &[&PointRecord],
+    query: &[f64],
+    score_threshold: Option<f64>,
+    keep: usize,
+    config: &VectorSearchConfig,
+) -> (Vec<ScoredCandidate>, usize, Option<ScoreStats>) {
+    let chunk_size = config.chunk_size.max(1);
+    let score_chunk = |start: usize, end: usize, top: &mut TopK| {
+        for (index, point) in points[start..end].iter().enumerate() {
+            let score = cosine_similarity(query, &point.vector);
+            if score_threshold.map(|t| score >= t).unwrap_or(true) {
+                top.push(ScoredCandidate {
+                    score,
+                    index: start + index,
+                });
+            }
+        }
+    };
+
+    let chunk_count = points.len().div_ceil(chunk_size);
+    let threads = config.effective_threads(chunk_count);
+
+    let top = if threads <= 1 {
+        let mut top = TopK::new(keep);
+        score_chunk(0, points.len(), &mut top);
+        top
+    } else {
+        let next_chunk = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut top = TopK::new(keep);
+                        loop {
+                            let chunk = next_chunk.fetch_add(1, AtomicOrdering::Relaxed);
+                            if chunk >= chunk_count {
+                                break;
+                            }
+                            let start = chunk * chunk_size;
+                            let end = (start + chunk_size).min(points.len());
+                            score_chunk(start, end, &mut top);
+                        }
+                        top
+                    })
+                })
+                .collect();
+
+            let mut merged = TopK::new(keep);
+            for worker in workers {
+                match worker.join() {
+                    Ok(top) => merged.merge(top),
+                    Err(_) => log::error!("[Vector] Search worker panicked"),
+                }
+            }
+            merged
+        })
+    };
+
+    let matched = top.matched;
+    let stats = top.scores.stats();
+    (top.into_sorted(), matched, stats)
+}
+
 #[tauri::command]
-pub fn vector_search_points(
-    state: tauri::State<Arc<VectorStoreState>>,
+pub async fn vector_search_points(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
     payload: SearchPayload,
-) -> Result<SearchResponse, String> {
-    search_points_impl(&state, payload)
+) -> Result<SearchResponse, CommandError> {
+    // Large collections are scored on worker threads so the async runtime stays responsive.
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || search_points_impl(&state, payload))
+        .await
+        .map_err(|e| CommandError::internal(format!("Vector search task failed: {}", e)))?
 }
 
 pub fn search_points_impl(
     state: &VectorStoreState,
     payload: SearchPayload,
-) -> Result<SearchResponse, String> {
+) -> Result<SearchResponse, CommandError> {
+    let config = state.search_config.read().clone();
     let data = state.data.lock();
     let points = data
         .points
         .get(&payload.collection)
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&payload.collection))?;
 
     let top_k = payload.top_k.unwrap_or(5);
     let offset = payload.offset.unwrap_or(0);
     let limit = payload.limit.unwrap_or(top_k);
 
     // Apply filters first
-    let filtered_points: Vec<&PointRecord> = if let Some(filters) = &payload.filters {
-        points
-            .iter()
-            .filter(|p| {
-                apply_payload_filters(p, filters, payload.filter_mode.as_deref().unwrap_or("and"))
-            })
-            .collect()
-    } else {
-        points.iter().collect()
-    };
-
-    // Calculate similarity scores
-    let mut scored: Vec<SearchResult> = filtered_points
-        .iter()
-        .map(|p| SearchResult {
-            id: p.id.clone(),
-            score: cosine_similarity(&payload.vector, &p.vector),
-            payload: p.payload.clone(),
-        })
-        .filter(|r| {
-            payload
-                .score_threshold
-                .map(|t| r.score >= t)
-                .unwrap_or(true)
-        })
-        .collect();
-
-    // Sort by score
-    scored.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    let filtered_points = filter_points(
+        &data,
+        &payload.collection,
+        points,
+        payload.filters.as_deref(),
+        payload.filter_mode.as_deref().unwrap_or("and"),
+    );
 
-    // Total before pagination
-    let total = scored.len();
+    // Only the first `offset + limit` matches are ever returned, so keep a bounded top-K.
+    let (ranked, total, score_stats) = score_top_k(
+        &filtered_points,
+        &payload.vector,
+        payload.score_threshold,
+        offset.saturating_add(limit),
+        &config,
+    );
 
     // Apply pagination
     if offset >= total {
@@ -754,45 +1741,284 @@ pub fn search_points_impl(
             total,
             offset,
             limit,
+            score_stats,
         });
     }
 
-    let end_index = std::cmp::min(offset + limit, total);
-    let results = scored[offset..end_index].to_vec();
+    let results = ranked
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|c| {
+            let point = filtered_points[c.index];
+            SearchResult {
+                id: point.id.clone(),
+                score: c.score,
+                payload: point.payload.clone(),
+            }
+        })
+        .collect();
 
     Ok(SearchResponse {
         results,
         total,
         offset,
         limit,
+        score_stats,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FindSimilarPayload {
+    pub collection: String,
+    pub point_id: String,
+    pub limit: Option<usize>,
+    pub score_threshold: Option<f64>,
+    pub filters: Option<Vec<PayloadFilter>>,
+    pub filter_mode: Option<String>, // "and" or "or", defaults to "and"
+}
+
+#[tauri::command]
+pub async fn vector_find_similar(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    payload: FindSimilarPayload,
+) -> Result<SearchResponse, CommandError> {
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || find_similar_impl(&state, payload))
+        .await
+        .map_err(|e| CommandError::internal(format!("Vector search task failed: {}", e)))?
+}
+
+/// Find the nearest neighbors of a stored point, excluding the point itself.
+pub fn find_similar_impl(
+    state: &VectorStoreState,
+    payload: FindSimilarPayload,
+) -> Result<SearchResponse, CommandError> {
+    let config = state.search_config.read().clone();
+    let data = state.data.lock();
+    let points = data
+        .points
+        .get(&payload.collection)
+        .ok_or_else(|| collection_not_found(&payload.collection))?;
+
+    let source = points
+        .iter()
+        .find(|p| p.id == payload.point_id)
+        .ok_or_else(|| CommandError::not_found(format!("Point not found: {}", payload.point_id)))?;
+
+    let limit = payload.limit.unwrap_or(5);
+    let filter_mode = payload.filter_mode.as_deref().unwrap_or("and");
+    let candidates: Vec<&PointRecord> = filter_points(
+        &data,
+        &payload.collection,
+        points,
+        payload.filters.as_deref(),
+        filter_mode,
+    )
+    .into_iter()
+    .filter(|p| p.id != source.id)
+    .collect();
+
+    let (ranked, total, score_stats) = score_top_k(
+        &candidates,
+        &source.vector,
+        payload.score_threshold,
+        limit,
+        &config,
+    );
+
+    let results = ranked
+        .into_iter()
+        .map(|c| {
+            let point = candidates[c.index];
+            SearchResult {
+                id: point.id.clone(),
+                score: c.score,
+                payload: point.payload.clone(),
+            }
+        })
+        .collect();
+
+    Ok(SearchResponse {
+        results,
+        total,
+        offset: 0,
+        limit,
+        score_stats,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScoreHistogramPayload {
+    pub collection: String,
+    pub vector: Vec<f64>,
+    pub buckets: Option<usize>,
+    pub filters: Option<Vec<PayloadFilter>>,
+    pub filter_mode: Option<String>, // "and" or "or", defaults to "and"
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ScoreBucket {
+    pub min: f64,
+    pub max: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScoreHistogramResponse {
+    pub buckets: Vec<ScoreBucket>,
+    pub total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_stats: Option<ScoreStats>,
+}
+
+const DEFAULT_HISTOGRAM_BUCKETS: usize = 20;
+const MAX_HISTOGRAM_BUCKETS: usize = 1000;
+
+#[tauri::command]
+pub async fn vector_score_histogram(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    payload: ScoreHistogramPayload,
+) -> Result<ScoreHistogramResponse, CommandError> {
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || score_histogram_impl(&state, payload))
+        .await
+        .map_err(|e| CommandError::internal(format!("Vector search task failed: {}", e)))?
+}
+
+/// Score a sample query against every point in a collection and bucket the raw
+/// similarity scores, for calibrating score thresholds.
+///
+/// Buckets evenly split the observed `[min, max]` score range; the last bucket
+/// includes its upper bound.
+pub fn score_histogram_impl(
+    state: &VectorStoreState,
+    payload: ScoreHistogramPayload,
+) -> Result<ScoreHistogramResponse, CommandError> {
+    let bucket_count = payload.buckets.unwrap_or(DEFAULT_HISTOGRAM_BUCKETS);
+    if bucket_count == 0 || bucket_count > MAX_HISTOGRAM_BUCKETS {
+        return Err(CommandError::invalid_input(format!(
+            "buckets must be between 1 and {}",
+            MAX_HISTOGRAM_BUCKETS
+        )));
+    }
+
+    let data = state.data.lock();
+    let points = data
+        .points
+        .get(&payload.collection)
+        .ok_or_else(|| collection_not_found(&payload.collection))?;
+
+    let filter_mode = payload.filter_mode.as_deref().unwrap_or("and");
+    let mut accumulator = ScoreAccumulator::default();
+    let scores: Vec<f64> = filter_points(
+        &data,
+        &payload.collection,
+        points,
+        payload.filters.as_deref(),
+        filter_mode,
+    )
+    .into_iter()
+    .map(|p| {
+        let score = cosine_similarity(&payload.vector, &p.vector);
+        accumulator.add(score);
+        score
+    })
+    .collect();
+
+    let score_stats = accumulator.stats();
+    let Some(stats) = score_stats else {
+        return Ok(ScoreHistogramResponse {
+            buckets: vec![],
+            total: 0,
+            score_stats: None,
+        });
+    };
+
+    let width = (stats.max - stats.min) / bucket_count as f64;
+    let mut buckets: Vec<ScoreBucket> = (0..bucket_count)
+        .map(|i| ScoreBucket {
+            min: stats.min + width * i as f64,
+            max: if i + 1 == bucket_count {
+                stats.max
+            } else {
+                stats.min + width * (i + 1) as f64
+            },
+            count: 0,
+        })
+        .collect();
+
+    for score in &scores {
+        let index = if width > 0.0 {
+            (((score - stats.min) / width) as usize).min(bucket_count - 1)
+        } else {
+            bucket_count - 1
+        };
+        buckets[index].count += 1;
+    }
+
+    Ok(ScoreHistogramResponse {
+        buckets,
+        total: scores.len(),
+        score_stats,
     })
 }
 
+#[tauri::command]
+pub fn vector_get_search_config(
+    state: tauri::State<Arc<VectorStoreState>>,
+) -> Result<VectorSearchConfig, CommandError> {
+    Ok(state.search_config.read().clone())
+}
+
+#[tauri::command]
+pub fn vector_set_search_config(
+    state: tauri::State<Arc<VectorStoreState>>,
+    config: VectorSearchConfig,
+) -> Result<VectorSearchConfig, CommandError> {
+    set_search_config_impl(&state, config)
+}
+
+pub fn set_search_config_impl(
+    state: &VectorStoreState,
+    mut config: VectorSearchConfig,
+) -> Result<VectorSearchConfig, CommandError> {
+    if config.chunk_size == 0 {
+        return Err(CommandError::invalid_input(
+            "chunk_size must be greater than 0",
+        ));
+    }
+    config.max_threads = config.max_threads.min(256);
+    *state.search_config.write() = config.clone();
+    Ok(config)
+}
+
 // ============ Delete All Points ============
 
 #[tauri::command]
 pub fn vector_delete_all_points(
     state: tauri::State<Arc<VectorStoreState>>,
     collection: String,
-) -> Result<usize, String> {
+) -> Result<usize, CommandError> {
     delete_all_points_impl(&state, collection)
 }
 
 pub fn delete_all_points_impl(
     state: &VectorStoreState,
     collection: String,
-) -> Result<usize, String> {
+) -> Result<usize, CommandError> {
     let mut data = state.data.lock();
 
     // Check collection exists
     if !data.collections.contains_key(&collection) {
-        return Err("Collection not found".to_string());
+        return Err(collection_not_found(&collection));
     }
 
     let deleted_count = data.points.get(&collection).map(|v| v.len()).unwrap_or(0);
 
     // Clear all points
     data.points.insert(collection.clone(), Vec::new());
+    data.reindex(&collection);
 
     // Update metadata
     if let Some(meta) = data.collections.get_mut(&collection) {
@@ -800,10 +2026,105 @@ pub fn delete_all_points_impl(
         meta.updated_at = default_timestamp();
     }
 
-    state.persist(&data).map_err(|e| e.to_string())?;
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
     Ok(deleted_count)
 }
 
+// ============ Payload Indexes ============
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PayloadIndexInfo {
+    pub collection: String,
+    pub field: String,
+    /// Number of distinct indexed values
+    pub cardinality: usize,
+    /// Number of points with an indexed value for the field
+    pub indexed_points: usize,
+}
+
+fn payload_index_info(data: &VectorData, collection: &str, field: &str) -> PayloadIndexInfo {
+    let index = data
+        .indexes
+        .get(collection)
+        .and_then(|indexes| indexes.get(field));
+    PayloadIndexInfo {
+        collection: collection.to_string(),
+        field: field.to_string(),
+        cardinality: index.map(|i| i.positions.len()).unwrap_or(0),
+        indexed_points: index.map(|i| i.indexed_points).unwrap_or(0),
+    }
+}
+
+/// Index a top-level payload field so `equals` filters on it only check matching points
+#[tauri::command]
+pub fn vector_create_payload_index(
+    state: tauri::State<Arc<VectorStoreState>>,
+    collection: String,
+    field: String,
+) -> Result<PayloadIndexInfo, CommandError> {
+    create_payload_index_impl(&state, collection, field)
+}
+
+pub fn create_payload_index_impl(
+    state: &VectorStoreState,
+    collection: String,
+    field: String,
+) -> Result<PayloadIndexInfo, CommandError> {
+    if field.trim().is_empty() {
+        return Err(CommandError::invalid_input("Index field must not be empty"));
+    }
+
+    let mut data = state.data.lock();
+    let meta = data
+        .collections
+        .get_mut(&collection)
+        .ok_or_else(|| collection_not_found(&collection))?;
+    if !meta.indexed_fields.contains(&field) {
+        meta.indexed_fields.push(field.clone());
+        data.reindex(&collection);
+        state
+            .persist(&data)
+            .map_err(|e| CommandError::internal(e.to_string()))?;
+    }
+
+    Ok(payload_index_info(&data, &collection, &field))
+}
+
+#[tauri::command]
+pub fn vector_drop_payload_index(
+    state: tauri::State<Arc<VectorStoreState>>,
+    collection: String,
+    field: String,
+) -> Result<bool, CommandError> {
+    drop_payload_index_impl(&state, collection, field)
+}
+
+/// Remove a payload index; returns whether the field was indexed
+pub fn drop_payload_index_impl(
+    state: &VectorStoreState,
+    collection: String,
+    field: String,
+) -> Result<bool, CommandError> {
+    let mut data = state.data.lock();
+    let meta = data
+        .collections
+        .get_mut(&collection)
+        .ok_or_else(|| collection_not_found(&collection))?;
+    let before = meta.indexed_fields.len();
+    meta.indexed_fields.retain(|f| f != &field);
+    if meta.indexed_fields.len() == before {
+        return Ok(false);
+    }
+
+    data.reindex(&collection);
+    state
+        .persist(&data)
+        .map_err(|e| CommandError::internal(e.to_string()))?;
+    Ok(true)
+}
+
 // ============ Vector Stats ============
 
 #[derive(Debug, Serialize)]
@@ -812,26 +2133,129 @@ pub struct VectorStats {
     pub total_points: usize,
     pub storage_path: String,
     pub storage_size_bytes: u64,
+    pub storage_format: VectorStorageFormat,
+    pub payload_indexes: Vec<PayloadIndexInfo>,
 }
 
 #[tauri::command]
-pub fn vector_stats(state: tauri::State<Arc<VectorStoreState>>) -> Result<VectorStats, String> {
+pub fn vector_stats(
+    state: tauri::State<Arc<VectorStoreState>>,
+) -> Result<VectorStats, CommandError> {
     stats_impl(&state)
 }
 
-pub fn stats_impl(state: &VectorStoreState) -> Result<VectorStats, String> {
+pub fn stats_impl(state: &VectorStoreState) -> Result<VectorStats, CommandError> {
     let data = state.data.lock();
 
     let collection_count = data.collections.len();
     let total_points: usize = data.points.values().map(|v| v.len()).sum();
-    let storage_path = state.path.to_string_lossy().to_string();
-    let storage_size_bytes = fs::metadata(&state.path).map(|m| m.len()).unwrap_or(0);
+    let storage_format = *state.format.read();
+    let path = state.storage_path(storage_format);
+    let storage_path = path.to_string_lossy().to_string();
+    let storage_size_bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    let mut payload_indexes: Vec<PayloadIndexInfo> = data
+        .collections
+        .iter()
+        .flat_map(|(name, meta)| {
+            meta.indexed_fields
+                .iter()
+                .map(|field| payload_index_info(&data, name, field))
+        })
+        .collect();
+    payload_indexes.sort_by(|a, b| (&a.collection, &a.field).cmp(&(&b.collection, &b.field)));
 
     Ok(VectorStats {
         collection_count,
         total_points,
         storage_path,
         storage_size_bytes,
+        storage_format,
+        payload_indexes,
+    })
+}
+
+#[derive(Debug, Serialize)]
+pub struct StorageMigrationResult {
+    pub from: VectorStorageFormat,
+    pub to: VectorStorageFormat,
+    pub storage_path: String,
+    /// Previous store file, kept until the user removes it
+    pub backup_path: Option<String>,
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn vector_migrate_storage_format(
+    state: tauri::State<'_, Arc<VectorStoreState>>,
+    target: VectorStorageFormat,
+) -> Result<StorageMigrationResult, CommandError> {
+    // Rewriting a large store is slow, keep it off the async runtime
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || migrate_storage_format_impl(&state, target))
+        .await
+        .map_err(|e| CommandError::internal(format!("Vector migration task failed: {}", e)))?
+}
+
+/// Rewrite the store in `target` format and move the previous file to a `.bak` backup.
+pub fn migrate_storage_format_impl(
+    state: &VectorStoreState,
+    target: VectorStorageFormat,
+) -> Result<StorageMigrationResult, CommandError> {
+    let data = state.data.lock();
+    let from = *state.format.read();
+    let source_path = state.storage_path(from);
+    let size_before_bytes = fs::metadata(&source_path).map(|m| m.len()).unwrap_or(0);
+
+    if from == target {
+        return Ok(StorageMigrationResult {
+            from,
+            to: target,
+            storage_path: source_path.to_string_lossy().to_string(),
+            backup_path: None,
+            size_before_bytes,
+            size_after_bytes: size_before_bytes,
+        });
+    }
+
+    let target_path = state
+        .write_store(&data, target)
+        .map_err(|e| CommandError::internal(format!("Failed to write vector store: {}", e)))?;
+
+    // A leftover source file would shadow the new store on the next load, so undo
+    // the write if it cannot be moved aside.
+    let backup_path = if source_path.exists() {
+        let backup = source_path.with_extension(format!("{}.bak", from.extension()));
+        if let Err(e) = fs::rename(&source_path, &backup) {
+            let _ = fs::remove_file(&target_path);
+            return Err(CommandError::internal(format!(
+                "Failed to back up previous vector store: {}",
+                e
+            )));
+        }
+        Some(backup.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    *state.format.write() = target;
+
+    let size_after_bytes = fs::metadata(&target_path).map(|m| m.len()).unwrap_or(0);
+    log::info!(
+        "[Vector] Migrated store from {:?} to {:?} ({} -> {} bytes)",
+        from,
+        target,
+        size_before_bytes,
+        size_after_bytes
+    );
+
+    Ok(StorageMigrationResult {
+        from,
+        to: target,
+        storage_path: target_path.to_string_lossy().to_string(),
+        backup_path,
+        size_before_bytes,
+        size_after_bytes,
     })
 }
 
@@ -859,34 +2283,31 @@ pub struct ScrollResponse {
 pub fn vector_scroll_points(
     state: tauri::State<Arc<VectorStoreState>>,
     payload: ScrollPayload,
-) -> Result<ScrollResponse, String> {
+) -> Result<ScrollResponse, CommandError> {
     scroll_points_impl(&state, payload)
 }
 
 pub fn scroll_points_impl(
     state: &VectorStoreState,
     payload: ScrollPayload,
-) -> Result<ScrollResponse, String> {
+) -> Result<ScrollResponse, CommandError> {
     let data = state.data.lock();
     let points = data
         .points
         .get(&payload.collection)
-        .ok_or_else(|| "Collection not found".to_string())?;
+        .ok_or_else(|| collection_not_found(&payload.collection))?;
 
     let offset = payload.offset.unwrap_or(0);
     let limit = payload.limit.unwrap_or(100);
 
     // Apply filters
-    let filtered: Vec<&PointRecord> = if let Some(filters) = &payload.filters {
-        points
-            .iter()
-            .filter(|p| {
-                apply_payload_filters(p, filters, payload.filter_mode.as_deref().unwrap_or("and"))
-            })
-            .collect()
-    } else {
-        points.iter().collect()
-    };
+    let filtered = filter_points(
+        &data,
+        &payload.collection,
+        points,
+        payload.filters.as_deref(),
+        payload.filter_mode.as_deref().unwrap_or("and"),
+    );
 
     let total = filtered.len();
 
@@ -0,0 +1,222 @@
+//! Token-budgeted chunking of documents for embedding
+//!
+//! Whole documents exceed the context of embedding models, so ingestion paths
+//! split them into chunks sized in estimated tokens, each repeating the end of
+//! the previous one. With `split_on_headings` a Markdown heading always starts a
+//! new chunk, keeping every chunk within one section. Chunks carry the page they
+//! start on and the heading they fall under for use as point payload.
+//!
+//! Token budgets are converted to characters with the estimated
+//! characters-per-token ratio of each section (see `token_estimate`), so dense
+//! text such as CJK gets proportionally shorter chunks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::token_estimate::estimate_tokens;
+
+/// Default chunk size in estimated tokens
+pub const DEFAULT_CHUNK_TOKENS: usize = 256;
+/// Default tokens shared between consecutive chunks
+pub const DEFAULT_CHUNK_OVERLAP_TOKENS: usize = 32;
+/// Characters per token assumed when converting a character-based chunk size
+const CHARS_PER_TOKEN: usize = 4;
+
+/// How documents are split into chunks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChunkingConfig {
+    /// Maximum chunk size in estimated tokens
+    pub chunk_tokens: usize,
+    /// Estimated tokens repeated from the end of the previous chunk, at most
+    /// half of `chunk_tokens`
+    pub overlap_tokens: usize,
+    /// Start a new chunk at every Markdown heading
+    pub split_on_headings: bool,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            chunk_tokens: DEFAULT_CHUNK_TOKENS,
+            overlap_tokens: DEFAULT_CHUNK_OVERLAP_TOKENS,
+            split_on_headings: true,
+        }
+    }
+}
+
+impl ChunkingConfig {
+    /// Config for a chunk size given in characters, as accepted before chunk
+    /// sizes were measured in tokens
+    pub fn from_chunk_chars(chunk_chars: usize) -> Self {
+        let chunk_tokens = (chunk_chars / CHARS_PER_TOKEN).max(1);
+        Self {
+            chunk_tokens,
+            overlap_tokens: DEFAULT_CHUNK_OVERLAP_TOKENS.min(chunk_tokens / 2),
+            ..Self::default()
+        }
+    }
+}
+
+/// One chunk of a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentChunk {
+    pub text: String,
+    /// Page the chunk starts on (1-based), for paged documents
+    pub page: Option<u32>,
+    /// Nearest heading at or above the start of the chunk
+    pub heading: Option<String>,
+    /// Estimated token count
+    pub tokens: usize,
+}
+
+/// Split an unpaged document into chunks
+pub fn chunk_document(text: &str, config: &ChunkingConfig) -> Vec<DocumentChunk> {
+    chunk_segments(&[(None, text)], config)
+}
+
+/// Split a paged document into chunks; `pages[0]` is page 1
+pub fn chunk_pages<S: AsRef<str>>(pages: &[S], config: &ChunkingConfig) -> Vec<DocumentChunk> {
+    let segments: Vec<(Option<u32>, &str)> = pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| (Some(index as u32 + 1), page.as_ref()))
+        .collect();
+    chunk_segments(&segments, config)
+}
+
+/// Run of text that chunks never cross
+#[derive(Default)]
+struct Section {
+    chars: Vec<char>,
+    /// Character offsets at which each page starts
+    pages: Vec<(usize, Option<u32>)>,
+    /// Character offsets of the headings in effect
+    headings: Vec<(usize, String)>,
+}
+
+impl Section {
+    fn starting_on(page: Option<u32>) -> Self {
+        Self {
+            pages: vec![(0, page)],
+            ..Default::default()
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        self.chars.iter().all(|c| c.is_whitespace())
+    }
+
+    fn page_at(&self, offset: usize) -> Option<u32> {
+        self.pages
+            .iter()
+            .take_while(|(start, _)| *start <= offset)
+            .last()
+            .and_then(|(_, page)| *page)
+    }
+
+    fn heading_at(&self, offset: usize) -> Option<String> {
+        self.headings
+            .iter()
+            .take_while(|(start, _)| *start <= offset)
+            .last()
+            .map(|(_, heading)| heading.clone())
+    }
+}
+
+fn chunk_segments(segments: &[(Option<u32>, &str)], config: &ChunkingConfig) -> Vec<DocumentChunk> {
+    let mut sections = Vec::new();
+    let mut section = Section::default();
+
+    for &(page, text) in segments {
+        if !section.chars.is_empty() {
+            section.chars.push('\n');
+        }
+        section.pages.push((section.chars.len(), page));
+
+        for line in text.split_inclusive('\n') {
+            if let Some(title) = heading_text(line) {
+                if config.split_on_headings && !section.is_blank() {
+                    sections.push(std::mem::replace(&mut section, Section::starting_on(page)));
+                }
+                section.headings.push((section.chars.len(), title));
+            }
+            section.chars.extend(line.chars());
+        }
+    }
+    sections.push(section);
+
+    let mut chunks = Vec::new();
+    for section in &sections {
+        chunk_section(section, config, &mut chunks);
+    }
+    chunks
+}
+
+fn chunk_section(section: &Section, config: &ChunkingConfig, chunks: &mut Vec<DocumentChunk>) {
+    if section.is_blank() {
+        return;
+    }
+    let chars = &section.chars;
+    let text: String = chars.iter().collect();
+    let tokens = estimate_tokens(&text, "", None).tokens.max(1);
+    let chars_per_token = chars.len() as f64 / tokens as f64;
+    let max_chars = ((config.chunk_tokens as f64 * chars_per_token) as usize).max(1);
+    let overlap = ((config.overlap_tokens as f64 * chars_per_token) as usize).min(max_chars / 2);
+
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = (start + max_chars).min(chars.len());
+        if end < chars.len() {
+            // Break at the last line break or space in the second half, if any
+            let window = &chars[start + max_chars / 2..end];
+            let boundary = window
+                .iter()
+                .rposition(|&c| c == '\n')
+                .or_else(|| window.iter().rposition(|c| c.is_whitespace()));
+            if let Some(pos) = boundary {
+                end = start + max_chars / 2 + pos + 1;
+            }
+        }
+
+        if let Some(first) = chars[start..end].iter().position(|c| !c.is_whitespace()) {
+            let chunk: String = chars[start..end].iter().collect();
+            let chunk = chunk.trim().to_string();
+            chunks.push(DocumentChunk {
+                tokens: estimate_tokens(&chunk, "", None).tokens,
+                page: section.page_at(start + first),
+                heading: section.heading_at(start + first),
+                text: chunk,
+            });
+        }
+        if end == chars.len() {
+            break;
+        }
+        start = overlap_start(chars, start, end, overlap);
+    }
+}
+
+/// Start of the chunk after `start..end`, moved forward to a word boundary so
+/// the overlap never begins mid-word
+fn overlap_start(chars: &[char], start: usize, end: usize, overlap: usize) -> usize {
+    let next = end.saturating_sub(overlap).max(start + 1);
+    if next >= end || chars[next - 1].is_whitespace() {
+        return next;
+    }
+    chars[next..end]
+        .iter()
+        .position(|c| c.is_whitespace())
+        .map_or(next, |pos| next + pos + 1)
+}
+
+/// Title of a Markdown ATX heading line (`#` to `######`)
+fn heading_text(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let title = trimmed.trim_start_matches('#');
+    let level = trimmed.len() - title.len();
+    if !(1..=6).contains(&level) || !title.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let title = title.trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
@@ -0,0 +1,158 @@
+//! Compact binary on-disk format for the vector store
+//!
+//! JSON stores every vector component as text, which makes large stores slow to
+//! parse and several times larger than needed. The binary format keeps the
+//! collection metadata, point ids and payloads in a small JSON header and writes
+//! all vectors as one contiguous block of little-endian `f32` values.
+//!
+//! Layout: `MAGIC | version: u32 | header_len: u64 | header | vectors`
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use super::{CollectionMeta, PointRecord, VectorData};
+
+const MAGIC: &[u8; 4] = b"CGVS";
+const VERSION: u32 = 1;
+
+/// On-disk format of the vector store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorStorageFormat {
+    Json,
+    Binary,
+}
+
+impl VectorStorageFormat {
+    /// File extension used for stores in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Binary => "bin",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryHeader {
+    collections: Vec<BinaryCollection>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryCollection {
+    name: String,
+    #[serde(default)]
+    meta: Option<CollectionMeta>,
+    points: Vec<BinaryPoint>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinaryPoint {
+    id: String,
+    dimension: usize,
+    #[serde(default)]
+    payload: Option<Value>,
+}
+
+/// Encode the store; vectors are narrowed to `f32`, the precision embeddings are produced in
+pub(super) fn encode(data: &VectorData) -> Result<Vec<u8>> {
+    // Sorted so the vector block order is stable across writes
+    let names: BTreeSet<&String> = data.collections.keys().chain(data.points.keys()).collect();
+
+    let mut collections = Vec::with_capacity(names.len());
+    let mut component_count = 0;
+    for name in &names {
+        let points = data
+            .points
+            .get(*name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        component_count += points.iter().map(|p| p.vector.len()).sum::<usize>();
+        collections.push(BinaryCollection {
+            name: (*name).clone(),
+            meta: data.collections.get(*name).cloned(),
+            points: points
+                .iter()
+                .map(|p| BinaryPoint {
+                    id: p.id.clone(),
+                    dimension: p.vector.len(),
+                    payload: p.payload.clone(),
+                })
+                .collect(),
+        });
+    }
+
+    let header = serde_json::to_vec(&BinaryHeader { collections })?;
+    let mut out = Vec::with_capacity(MAGIC.len() + 12 + header.len() + component_count * 4);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    out.extend_from_slice(&header);
+    for name in &names {
+        for point in data.points.get(*name).into_iter().flatten() {
+            for value in &point.vector {
+                out.extend_from_slice(&(*value as f32).to_le_bytes());
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub(super) fn decode(bytes: &[u8]) -> Result<VectorData> {
+    let rest = bytes
+        .strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| anyhow!("Not a binary vector store"))?;
+    let (version, rest) = split_array::<4>(rest)?;
+    let version = u32::from_le_bytes(version);
+    if version != VERSION {
+        bail!("Unsupported binary vector store version {}", version);
+    }
+    let (header_len, rest) = split_array::<8>(rest)?;
+    let header_len = usize::try_from(u64::from_le_bytes(header_len))?;
+    if rest.len() < header_len {
+        bail!("Truncated binary vector store header");
+    }
+    let (header, mut vectors) = rest.split_at(header_len);
+    let header: BinaryHeader = serde_json::from_slice(header)?;
+
+    let mut data = VectorData::default();
+    for collection in header.collections {
+        let mut points = Vec::with_capacity(collection.points.len());
+        for point in collection.points {
+            let byte_len = point
+                .dimension
+                .checked_mul(4)
+                .filter(|len| *len <= vectors.len())
+                .ok_or_else(|| anyhow!("Truncated vector data for point '{}'", point.id))?;
+            let (block, remaining) = vectors.split_at(byte_len);
+            vectors = remaining;
+            points.push(PointRecord {
+                id: point.id,
+                vector: block
+                    .chunks_exact(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f64)
+                    .collect(),
+                payload: point.payload,
+            });
+        }
+        if let Some(meta) = collection.meta {
+            data.collections.insert(collection.name.clone(), meta);
+        }
+        data.points.insert(collection.name, points);
+    }
+
+    if !vectors.is_empty() {
+        bail!("Unexpected trailing data in binary vector store");
+    }
+    Ok(data)
+}
+
+fn split_array<const N: usize>(bytes: &[u8]) -> Result<([u8; N], &[u8])> {
+    if bytes.len() < N {
+        bail!("Truncated binary vector store");
+    }
+    let (head, rest) = bytes.split_at(N);
+    Ok((head.try_into()?, rest))
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 #[allow(clippy::module_inception)]
 mod tests {
+    use crate::commands::error::CommandErrorCode;
     use crate::commands::storage::vector::*;
     use serde_json::json;
     use std::sync::Arc;
@@ -24,6 +25,7 @@ mod tests {
             description: Some("Test collection".to_string()),
             embedding_model: Some("text-embedding-3-small".to_string()),
             embedding_provider: Some("openai".to_string()),
+            indexed_fields: Vec::new(),
         };
 
         let serialized = serde_json::to_string(&meta).unwrap();
@@ -97,7 +99,7 @@ mod tests {
         };
         let result3 = create_collection_impl(&state, payload_different);
         assert!(result3.is_err());
-        assert!(result3.unwrap_err().contains("different dimension"));
+        assert!(result3.unwrap_err().message.contains("different dimension"));
     }
 
     #[test]
@@ -175,8 +177,9 @@ mod tests {
         // Try to rename non-existent collection
         let result1 =
             rename_collection_impl(&state, "nonexistent".to_string(), "new_name".to_string());
-        assert!(result1.is_err());
-        assert!(result1.unwrap_err().contains("not found"));
+        let err = result1.unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::NotFound);
+        assert_eq!(err.details.unwrap()["collection"], "nonexistent");
 
         // Create two collections
         let payload1 = CreateCollectionPayload {
@@ -201,8 +204,9 @@ mod tests {
         // Try to rename to existing name
         let result2 =
             rename_collection_impl(&state, "collection1".to_string(), "collection2".to_string());
-        assert!(result2.is_err());
-        assert!(result2.unwrap_err().contains("already exists"));
+        let err = result2.unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::AlreadyExists);
+        assert!(err.message.contains("already exists"));
     }
 
     #[test]
@@ -259,7 +263,7 @@ mod tests {
 
         let result = truncate_collection_impl(&state, "nonexistent".to_string());
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        assert!(result.unwrap_err().message.contains("not found"));
     }
 
     #[test]
@@ -329,7 +333,7 @@ mod tests {
         // Test non-existent collection
         let result = get_collection_impl(&state, "nonexistent".to_string());
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        assert!(result.unwrap_err().message.contains("not found"));
 
         // Create a collection
         let payload = CreateCollectionPayload {
@@ -460,7 +464,7 @@ mod tests {
 
         let result = upsert_points_impl(&state, "test_dim".to_string(), points);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("dimension mismatch"));
+        assert!(result.unwrap_err().message.contains("dimension mismatch"));
     }
 
     #[test]
@@ -971,7 +975,7 @@ mod tests {
 
         let result = search_points_impl(&state, search_payload);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        assert!(result.unwrap_err().message.contains("not found"));
     }
 
     #[test]
@@ -1045,7 +1049,7 @@ mod tests {
 
         let result = export_collection_impl(&state, "nonexistent".to_string());
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("not found"));
+        assert!(result.unwrap_err().message.contains("not found"));
     }
 
     #[test]
@@ -1089,6 +1093,7 @@ mod tests {
                 description: Some("Imported test collection".to_string()),
                 embedding_model: Some("imported-model".to_string()),
                 embedding_provider: Some("imported-provider".to_string()),
+                indexed_fields: Vec::new(),
             },
             points: vec![
                 PointRecord {
@@ -1175,6 +1180,7 @@ mod tests {
                 description: Some("Imported collection".to_string()),
                 embedding_model: Some("imported-model".to_string()),
                 embedding_provider: Some("imported-provider".to_string()),
+                indexed_fields: Vec::new(),
             },
             points: vec![PointRecord {
                 id: "imported1".to_string(),
@@ -1185,7 +1191,7 @@ mod tests {
 
         let result = import_collection_impl(&state, import_data.clone(), Some(false));
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("already exists"));
+        assert!(result.unwrap_err().message.contains("already exists"));
 
         // Import with overwrite - should succeed
         let result = import_collection_impl(&state, import_data, Some(true));
@@ -1228,6 +1234,7 @@ mod tests {
                 description: None,
                 embedding_model: None,
                 embedding_provider: None,
+                indexed_fields: Vec::new(),
             },
             points: vec![],
         };
@@ -1262,6 +1269,7 @@ mod tests {
                 description: Some("Empty imported collection".to_string()),
                 embedding_model: Some("empty-model".to_string()),
                 embedding_provider: Some("empty-provider".to_string()),
+                indexed_fields: Vec::new(),
             },
             points: vec![],
         };
@@ -1462,7 +1470,7 @@ mod tests {
 
         let result = delete_all_points_impl(&state, "nonexistent".to_string());
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Collection not found"));
+        assert!(result.unwrap_err().message.contains("Collection not found"));
     }
 
     #[test]
@@ -1862,7 +1870,7 @@ mod tests {
         let result = upsert_points_impl(&state, "nan_test".to_string(), points);
 
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("NaN"));
+        assert!(result.unwrap_err().message.contains("NaN"));
     }
 
     #[test]
@@ -1889,6 +1897,821 @@ mod tests {
         let result = upsert_points_impl(&state, "inf_test".to_string(), points);
 
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Infinity"));
+        assert!(result.unwrap_err().message.contains("Infinity"));
+    }
+
+    fn seed_points(state: &VectorStoreState, collection: &str, dimension: usize, count: usize) {
+        create_collection_impl(
+            state,
+            CreateCollectionPayload {
+                name: collection.to_string(),
+                dimension,
+                metadata: None,
+                description: None,
+                embedding_model: None,
+                embedding_provider: None,
+            },
+        )
+        .unwrap();
+
+        // Insert directly: upsert is O(n^2) for bulk loads and not what these tests measure
+        let mut data = state.data.lock();
+        let points = data.points.get_mut(collection).unwrap();
+        for i in 0..count {
+            let vector = (0..dimension)
+                .map(|d| (((i * 31 + d * 17) % 101) as f64 - 50.0) / 50.0)
+                .collect();
+            points.push(PointRecord {
+                id: format!("p{}", i),
+                vector,
+                payload: Some(json!({"bucket": i % 4})),
+            });
+        }
+    }
+
+    fn search_payload(collection: &str, vector: Vec<f64>) -> SearchPayload {
+        SearchPayload {
+            collection: collection.to_string(),
+            vector,
+            top_k: Some(10),
+            score_threshold: None,
+            offset: None,
+            limit: None,
+            filters: None,
+            filter_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_search_parallel_chunks_match_single_thread() {
+        let state = create_test_state();
+        seed_points(&state, "chunked", 8, 5_000);
+        let query = vec![0.3, -0.2, 0.9, 0.1, 0.0, -0.5, 0.4, 0.2];
+
+        set_search_config_impl(
+            &state,
+            VectorSearchConfig {
+                chunk_size: 100_000,
+                max_threads: 1,
+            },
+        )
+        .unwrap();
+        let mut payload = search_payload("chunked", query.clone());
+        payload.offset = Some(3);
+        payload.score_threshold = Some(0.1);
+        let single = search_points_impl(&state, payload).unwrap();
+
+        set_search_config_impl(
+            &state,
+            VectorSearchConfig {
+                chunk_size: 128,
+                max_threads: 4,
+            },
+        )
+        .unwrap();
+        let mut payload = search_payload("chunked", query);
+        payload.offset = Some(3);
+        payload.score_threshold = Some(0.1);
+        let parallel = search_points_impl(&state, payload).unwrap();
+
+        assert_eq!(single.total, parallel.total);
+        let single_ids: Vec<_> = single.results.iter().map(|r| r.id.clone()).collect();
+        let parallel_ids: Vec<_> = parallel.results.iter().map(|r| r.id.clone()).collect();
+        assert_eq!(single_ids, parallel_ids);
+        assert!(parallel
+            .results
+            .windows(2)
+            .all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_search_ties_keep_insertion_order() {
+        let state = create_test_state();
+        seed_points(&state, "ties", 2, 0);
+        {
+            let mut data = state.data.lock();
+            let points = data.points.get_mut("ties").unwrap();
+            for i in 0..50 {
+                points.push(PointRecord {
+                    id: format!("t{}", i),
+                    vector: vec![1.0, 0.0],
+                    payload: None,
+                });
+            }
+        }
+        set_search_config_impl(
+            &state,
+            VectorSearchConfig {
+                chunk_size: 7,
+                max_threads: 3,
+            },
+        )
+        .unwrap();
+
+        let result = search_points_impl(&state, search_payload("ties", vec![1.0, 0.0])).unwrap();
+        let ids: Vec<_> = result.results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["t0", "t1", "t2", "t3", "t4", "t5", "t6", "t7", "t8", "t9"]
+        );
+        assert_eq!(result.total, 50);
+    }
+
+    #[test]
+    fn test_set_search_config_rejects_zero_chunk() {
+        let state = create_test_state();
+        let result = set_search_config_impl(
+            &state,
+            VectorSearchConfig {
+                chunk_size: 0,
+                max_threads: 2,
+            },
+        );
+        assert_eq!(result.unwrap_err().code, CommandErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn test_search_100k_points_benchmark() {
+        let state = create_test_state();
+        seed_points(&state, "bench", 64, 100_000);
+        let query: Vec<f64> = (0..64).map(|d| (d as f64 / 64.0) - 0.5).collect();
+
+        let start = std::time::Instant::now();
+        let result = search_points_impl(&state, search_payload("bench", query)).unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(result.results.len(), 10);
+        assert_eq!(result.total, 100_000);
+        // Generous bound so unoptimized test builds on slow CI machines still pass
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "search over 100k points took {:?}",
+            elapsed
+        );
+    }
+
+    fn similar_payload(collection: &str, point_id: &str) -> FindSimilarPayload {
+        FindSimilarPayload {
+            collection: collection.to_string(),
+            point_id: point_id.to_string(),
+            limit: Some(10),
+            score_threshold: None,
+            filters: None,
+            filter_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_find_similar_excludes_source_point() {
+        let state = create_test_state();
+        seed_points(&state, "similar", 8, 200);
+
+        let result = find_similar_impl(&state, similar_payload("similar", "p7")).unwrap();
+        assert_eq!(result.results.len(), 10);
+        assert_eq!(result.total, 199);
+        assert!(result.results.iter().all(|r| r.id != "p7"));
+        assert!(result.results.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_find_similar_applies_filters() {
+        let state = create_test_state();
+        seed_points(&state, "similar", 8, 200);
+
+        let mut payload = similar_payload("similar", "p0");
+        payload.filters = Some(vec![PayloadFilter {
+            key: "bucket".to_string(),
+            value: json!(1),
+            operation: "equals".to_string(),
+        }]);
+        let result = find_similar_impl(&state, payload).unwrap();
+        assert_eq!(result.total, 50);
+        assert!(result
+            .results
+            .iter()
+            .all(|r| r.payload.as_ref().unwrap()["bucket"] == json!(1)));
+    }
+
+    #[test]
+    fn test_find_similar_missing_point() {
+        let state = create_test_state();
+        seed_points(&state, "similar", 8, 10);
+
+        let result = find_similar_impl(&state, similar_payload("similar", "missing"));
+        let err = result.unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::NotFound);
+        assert!(err.message.contains("Point not found"));
+
+        let result = find_similar_impl(&state, similar_payload("nope", "p0"));
+        assert_eq!(result.unwrap_err().code, CommandErrorCode::NotFound);
+    }
+
+    #[test]
+    fn test_search_reports_score_stats_over_all_matches() {
+        let state = create_test_state();
+        seed_points(&state, "stats", 8, 500);
+        let query = vec![0.3, -0.2, 0.9, 0.1, 0.0, -0.5, 0.4, 0.2];
+
+        let mut payload = search_payload("stats", query.clone());
+        payload.top_k = Some(3);
+        let result = search_points_impl(&state, payload).unwrap();
+        let stats = result.score_stats.unwrap();
+
+        let scores: Vec<f64> = state.data.lock().points["stats"]
+            .iter()
+            .map(|p| cosine_similarity(&query, &p.vector))
+            .collect();
+        let min = scores.iter().cloned().fold(f64::INFINITY, f64::min);
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        assert_eq!(stats.max, result.results[0].score);
+        assert_eq!(stats.min, min);
+        assert!((stats.mean - mean).abs() < 1e-9);
+
+        let mut payload = search_payload("stats", query);
+        payload.score_threshold = Some(2.0);
+        let result = search_points_impl(&state, payload).unwrap();
+        assert!(result.score_stats.is_none());
+    }
+
+    fn histogram_payload(
+        collection: &str,
+        vector: Vec<f64>,
+        buckets: usize,
+    ) -> ScoreHistogramPayload {
+        ScoreHistogramPayload {
+            collection: collection.to_string(),
+            vector,
+            buckets: Some(buckets),
+            filters: None,
+            filter_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_score_histogram_counts_every_point() {
+        let state = create_test_state();
+        seed_points(&state, "histogram", 8, 300);
+        let query = vec![0.3, -0.2, 0.9, 0.1, 0.0, -0.5, 0.4, 0.2];
+
+        let result =
+            score_histogram_impl(&state, histogram_payload("histogram", query, 8)).unwrap();
+        let stats = result.score_stats.unwrap();
+        assert_eq!(result.total, 300);
+        assert_eq!(result.buckets.len(), 8);
+        assert_eq!(result.buckets.iter().map(|b| b.count).sum::<usize>(), 300);
+        assert_eq!(result.buckets[0].min, stats.min);
+        assert_eq!(result.buckets[7].max, stats.max);
+        assert!(result.buckets[0].count > 0 && result.buckets[7].count > 0);
+    }
+
+    #[test]
+    fn test_score_histogram_edge_cases() {
+        let state = create_test_state();
+        seed_points(&state, "histogram", 2, 0);
+
+        let result =
+            score_histogram_impl(&state, histogram_payload("histogram", vec![1.0, 0.0], 4))
+                .unwrap();
+        assert_eq!(result.total, 0);
+        assert!(result.buckets.is_empty());
+        assert!(result.score_stats.is_none());
+
+        let err = score_histogram_impl(&state, histogram_payload("histogram", vec![1.0, 0.0], 0))
+            .unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::InvalidInput);
+
+        let err =
+            score_histogram_impl(&state, histogram_payload("nope", vec![1.0, 0.0], 4)).unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::NotFound);
+    }
+
+    fn model_payload(name: &str, model: &str) -> CreateCollectionForModelPayload {
+        CreateCollectionForModelPayload {
+            name: name.to_string(),
+            base_url: "http://localhost:11434".to_string(),
+            model: model.to_string(),
+            metadata: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_create_collection_for_model_records_dimension_and_model() {
+        let state = create_test_state();
+
+        let meta = create_collection_for_model_impl(
+            &state,
+            model_payload("docs", "nomic-embed-text"),
+            768,
+        )
+        .unwrap();
+        assert_eq!(meta.dimension, 768);
+        assert_eq!(meta.embedding_model.as_deref(), Some("nomic-embed-text"));
+        assert_eq!(meta.embedding_provider.as_deref(), Some("ollama"));
+
+        let err =
+            create_collection_for_model_impl(&state, model_payload("docs", "all-minilm"), 384)
+                .unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::InvalidInput);
+
+        let err = create_collection_for_model_impl(&state, model_payload("empty", "broken"), 0)
+            .unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn test_embedding_model_warning() {
+        let state = create_test_state();
+        create_collection_for_model_impl(&state, model_payload("docs", "nomic-embed-text"), 4)
+            .unwrap();
+        let meta = get_collection_impl(&state, "docs".to_string()).unwrap();
+
+        assert!(embedding_model_warning(&meta, "nomic-embed-text").is_none());
+        let warning = embedding_model_warning(&meta, "all-minilm").unwrap();
+        assert!(warning.contains("nomic-embed-text") && warning.contains("all-minilm"));
+
+        let mut untracked = meta.clone();
+        untracked.embedding_model = None;
+        assert!(embedding_model_warning(&untracked, "all-minilm").is_none());
+    }
+
+    #[test]
+    fn test_migrate_storage_format_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("vector_store.json");
+        let state = VectorStoreState::new(json_path.clone()).unwrap();
+        seed_points(&state, "docs", 16, 50);
+        {
+            let data = state.data.lock();
+            state.persist(&data).unwrap();
+        }
+
+        let result = migrate_storage_format_impl(&state, VectorStorageFormat::Binary).unwrap();
+        assert_eq!(result.from, VectorStorageFormat::Json);
+        assert_eq!(result.to, VectorStorageFormat::Binary);
+        assert!(result.size_after_bytes < result.size_before_bytes);
+        assert!(!json_path.exists());
+        assert!(temp_dir.path().join("vector_store.json.bak").exists());
+        assert!(temp_dir.path().join("vector_store.bin").exists());
+
+        // Reloading picks up the binary store
+        let reloaded = VectorStoreState::new(json_path.clone()).unwrap();
+        let stats = stats_impl(&reloaded).unwrap();
+        assert_eq!(stats.storage_format, VectorStorageFormat::Binary);
+        assert_eq!(stats.total_points, 50);
+        assert_eq!(
+            get_collection_impl(&reloaded, "docs".to_string())
+                .unwrap()
+                .dimension,
+            16
+        );
+        {
+            let original = state.data.lock();
+            let loaded = reloaded.data.lock();
+            for (a, b) in original.points["docs"].iter().zip(&loaded.points["docs"]) {
+                assert_eq!(a.id, b.id);
+                assert_eq!(a.payload, b.payload);
+                assert!(a
+                    .vector
+                    .iter()
+                    .zip(&b.vector)
+                    .all(|(x, y)| (x - y).abs() < 1e-6));
+            }
+        }
+
+        // Writes go to the binary store and survive a reload
+        upsert_points_impl(
+            &reloaded,
+            "docs".to_string(),
+            vec![UpsertPoint {
+                id: "extra".to_string(),
+                vector: vec![0.5; 16],
+                payload: None,
+            }],
+        )
+        .unwrap();
+        let noop = migrate_storage_format_impl(&reloaded, VectorStorageFormat::Binary).unwrap();
+        assert!(noop.backup_path.is_none());
+
+        let back = migrate_storage_format_impl(&reloaded, VectorStorageFormat::Json).unwrap();
+        assert_eq!(back.to, VectorStorageFormat::Json);
+        assert!(!temp_dir.path().join("vector_store.bin").exists());
+        let reloaded = VectorStoreState::new(json_path).unwrap();
+        assert_eq!(stats_impl(&reloaded).unwrap().total_points, 51);
+    }
+
+    #[test]
+    fn test_corrupt_binary_store_falls_back_to_backup() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("vector_store.json");
+        let state = VectorStoreState::new(json_path.clone()).unwrap();
+        seed_points(&state, "docs", 4, 3);
+        {
+            let data = state.data.lock();
+            state.persist(&data).unwrap();
+        }
+        migrate_storage_format_impl(&state, VectorStorageFormat::Binary).unwrap();
+
+        let binary_path = temp_dir.path().join("vector_store.bin");
+        let bytes = std::fs::read(&binary_path).unwrap();
+        std::fs::write(&binary_path, &bytes[..bytes.len() - 3]).unwrap();
+
+        let recovered = VectorStoreState::new(json_path.clone()).unwrap();
+        let stats = stats_impl(&recovered).unwrap();
+        assert_eq!(stats.storage_format, VectorStorageFormat::Binary);
+        assert_eq!(stats.total_points, 3);
+        assert!(!binary_path.exists());
+        assert!(temp_dir.path().join("vector_store.bin.corrupt").exists());
+
+        // Without a readable backup the store starts empty
+        std::fs::write(temp_dir.path().join("vector_store.json.bak"), "{ not json").unwrap();
+        let empty = VectorStoreState::new(json_path).unwrap();
+        assert_eq!(stats_impl(&empty).unwrap().total_points, 0);
+        assert!(temp_dir
+            .path()
+            .join("vector_store.json.bak.corrupt")
+            .exists());
+    }
+
+    fn chunk_words(chunk: &DocumentChunk) -> Vec<&str> {
+        chunk.text.split_whitespace().collect()
+    }
+
+    /// Number of words the end of `prev` shares with the start of `next`
+    fn shared_words(prev: &[&str], next: &[&str]) -> usize {
+        (0..=prev.len().min(next.len()))
+            .rev()
+            .find(|&k| prev[prev.len() - k..] == next[..k])
+            .unwrap_or(0)
+    }
+
+    fn numbered_words(count: usize) -> String {
+        (0..count)
+            .map(|i| format!("word{}", i))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_chunking_overlap_repeats_whole_words_without_gaps() {
+        let text = numbered_words(400);
+        let config = ChunkingConfig {
+            chunk_tokens: 40,
+            overlap_tokens: 8,
+            split_on_headings: false,
+        };
+        let chunks = chunk_document(&text, &config);
+        assert!(chunks.len() > 5);
+
+        let mut rebuilt = chunk_words(&chunks[0]);
+        for pair in chunks.windows(2) {
+            let (prev, next) = (chunk_words(&pair[0]), chunk_words(&pair[1]));
+            let shared = shared_words(&prev, &next);
+            assert!(shared > 0, "consecutive chunks should overlap");
+            assert!(shared <= prev.len() / 2, "overlap is at most half a chunk");
+            rebuilt.extend(&next[shared..]);
+        }
+        assert_eq!(rebuilt, text.split_whitespace().collect::<Vec<_>>());
+
+        // Chunks start and end on whole words and stay within the budget
+        for chunk in &chunks {
+            assert!(chunk_words(chunk).iter().all(|w| w.starts_with("word")));
+            assert!(
+                chunk.tokens <= config.chunk_tokens,
+                "{} tokens",
+                chunk.tokens
+            );
+            assert_eq!(chunk.page, None);
+        }
+    }
+
+    #[test]
+    fn test_chunking_without_overlap_partitions_text() {
+        let text = numbered_words(200);
+        let config = ChunkingConfig {
+            chunk_tokens: 30,
+            overlap_tokens: 0,
+            split_on_headings: false,
+        };
+        let chunks = chunk_document(&text, &config);
+        assert!(chunks.len() > 1);
+
+        for pair in chunks.windows(2) {
+            assert_eq!(
+                shared_words(&chunk_words(&pair[0]), &chunk_words(&pair[1])),
+                0
+            );
+        }
+        let joined: Vec<&str> = chunks.iter().flat_map(chunk_words).collect();
+        assert_eq!(joined, text.split_whitespace().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_chunking_splits_on_headings_and_tracks_pages() {
+        let pages = vec![
+            "# Introduction\nWhy this matters.\n## Methods\nWe measured things.",
+            "More methods on page two.\n## Results\nIt worked.",
+        ];
+        let chunks = chunk_pages(&pages, &ChunkingConfig::default());
+
+        let summary: Vec<(Option<&str>, Option<u32>)> = chunks
+            .iter()
+            .map(|c| (c.heading.as_deref(), c.page))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("Introduction"), Some(1)),
+                (Some("Methods"), Some(1)),
+                (Some("Results"), Some(2)),
+            ]
+        );
+        assert!(chunks[1].text.ends_with("More methods on page two."));
+        assert!(chunks[2].text.starts_with("## Results"));
+
+        let unsplit = chunk_pages(
+            &pages,
+            &ChunkingConfig {
+                split_on_headings: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(unsplit.len(), 1);
+        assert_eq!(unsplit[0].heading.as_deref(), Some("Introduction"));
+    }
+
+    #[test]
+    fn test_chunking_edge_cases() {
+        let config = ChunkingConfig::default();
+        assert!(chunk_document("", &config).is_empty());
+        assert!(chunk_document("   \n  ", &config).is_empty());
+        assert!(chunk_pages(&["", " "], &config).is_empty());
+
+        let chunks = chunk_document("#hashtag is not a heading", &config);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].heading, None);
+        assert_eq!(chunks[0].text, "#hashtag is not a heading");
+
+        // Text without whitespace is split by character, never inside a character
+        let cjk = "日本語のテキストを分割します。".repeat(40);
+        let chunks = chunk_document(
+            &cjk,
+            &ChunkingConfig {
+                chunk_tokens: 50,
+                overlap_tokens: 0,
+                split_on_headings: false,
+            },
+        );
+        assert!(chunks.len() > 1);
+        assert_eq!(
+            chunks.iter().map(|c| c.text.as_str()).collect::<String>(),
+            cjk
+        );
+    }
+
+    #[test]
+    fn test_chunking_config_from_chunk_chars() {
+        let config = ChunkingConfig::from_chunk_chars(1000);
+        assert_eq!(config.chunk_tokens, 250);
+        assert_eq!(config.overlap_tokens, 32);
+        assert!(config.split_on_headings);
+
+        let tiny = ChunkingConfig::from_chunk_chars(10);
+        assert_eq!(tiny.chunk_tokens, 2);
+        assert_eq!(tiny.overlap_tokens, 1);
+        assert_eq!(ChunkingConfig::from_chunk_chars(0).chunk_tokens, 1);
+    }
+
+    fn bucket_search(
+        state: &VectorStoreState,
+        filters: Vec<PayloadFilter>,
+        mode: &str,
+    ) -> SearchResponse {
+        search_points_impl(
+            state,
+            SearchPayload {
+                collection: "docs".to_string(),
+                vector: vec![0.5; 8],
+                top_k: Some(100),
+                score_threshold: None,
+                offset: None,
+                limit: None,
+                filters: Some(filters),
+                filter_mode: Some(mode.to_string()),
+            },
+        )
+        .unwrap()
+    }
+
+    fn bucket_equals(bucket: u64) -> PayloadFilter {
+        PayloadFilter {
+            key: "bucket".to_string(),
+            value: json!(bucket),
+            operation: "equals".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_payload_index_matches_unindexed_search() {
+        let state = create_test_state();
+        seed_points(&state, "docs", 8, 40);
+        let range_filter = PayloadFilter {
+            key: "bucket".to_string(),
+            value: json!(2),
+            operation: "less_than".to_string(),
+        };
+        let cases = vec![
+            (vec![bucket_equals(1)], "and"),
+            (vec![bucket_equals(1), range_filter.clone()], "and"),
+            (vec![bucket_equals(1), bucket_equals(3)], "or"),
+            (vec![bucket_equals(3), range_filter], "or"),
+        ];
+        let unindexed: Vec<Vec<String>> = cases
+            .iter()
+            .map(|(filters, mode)| {
+                bucket_search(&state, filters.clone(), mode)
+                    .results
+                    .into_iter()
+                    .map(|r| r.id)
+                    .collect()
+            })
+            .collect();
+
+        let info =
+            create_payload_index_impl(&state, "docs".to_string(), "bucket".to_string()).unwrap();
+        assert_eq!(info.cardinality, 4);
+        assert_eq!(info.indexed_points, 40);
+
+        for ((filters, mode), expected) in cases.into_iter().zip(unindexed) {
+            let ids: Vec<String> = bucket_search(&state, filters, mode)
+                .results
+                .into_iter()
+                .map(|r| r.id)
+                .collect();
+            assert!(!ids.is_empty());
+            assert_eq!(ids, expected);
+        }
+        assert_eq!(
+            bucket_search(&state, vec![bucket_equals(7)], "and").total,
+            0
+        );
+
+        let stats = stats_impl(&state).unwrap();
+        assert_eq!(stats.payload_indexes.len(), 1);
+        assert_eq!(stats.payload_indexes[0].field, "bucket");
+        assert_eq!(stats.payload_indexes[0].cardinality, 4);
+
+        let err = create_payload_index_impl(&state, "missing".to_string(), "bucket".to_string())
+            .unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::NotFound);
+    }
+
+    #[test]
+    fn test_payload_index_follows_writes_and_reload() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("vector_store.json");
+        let state = VectorStoreState::new(json_path.clone()).unwrap();
+        seed_points(&state, "docs", 8, 8);
+        create_payload_index_impl(&state, "docs".to_string(), "bucket".to_string()).unwrap();
+
+        delete_points_impl(&state, "docs".to_string(), vec!["p1".to_string()]).unwrap();
+        upsert_points_impl(
+            &state,
+            "docs".to_string(),
+            vec![
+                UpsertPoint {
+                    id: "p5".to_string(),
+                    vector: vec![0.1; 8],
+                    payload: Some(json!({"bucket": 9})),
+                },
+                UpsertPoint {
+                    id: "new".to_string(),
+                    vector: vec![0.2; 8],
+                    payload: Some(json!({"bucket": 1})),
+                },
+            ],
+        )
+        .unwrap();
+
+        let ids = |response: SearchResponse| {
+            let mut ids: Vec<String> = response.results.into_iter().map(|r| r.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(
+            ids(bucket_search(&state, vec![bucket_equals(1)], "and")),
+            vec!["new".to_string()]
+        );
+        assert_eq!(
+            ids(bucket_search(&state, vec![bucket_equals(9)], "and")),
+            vec!["p5".to_string()]
+        );
+
+        // The declaration is persisted and the index rebuilt on load
+        let reloaded = VectorStoreState::new(json_path).unwrap();
+        let stats = stats_impl(&reloaded).unwrap();
+        assert_eq!(stats.payload_indexes.len(), 1);
+        assert_eq!(stats.payload_indexes[0].cardinality, 5);
+        assert_eq!(stats.payload_indexes[0].indexed_points, 8);
+
+        assert!(
+            drop_payload_index_impl(&reloaded, "docs".to_string(), "bucket".to_string()).unwrap()
+        );
+        assert!(
+            !drop_payload_index_impl(&reloaded, "docs".to_string(), "bucket".to_string()).unwrap()
+        );
+        assert!(stats_impl(&reloaded).unwrap().payload_indexes.is_empty());
+        assert_eq!(
+            bucket_search(&reloaded, vec![bucket_equals(0)], "and").total,
+            2
+        );
+    }
+
+    #[test]
+    fn test_payload_index_incremental_updates_match_rebuild() {
+        let state = create_test_state();
+        seed_points(&state, "docs", 8, 12);
+        create_payload_index_impl(&state, "docs".to_string(), "bucket".to_string()).unwrap();
+
+        delete_points_impl(
+            &state,
+            "docs".to_string(),
+            vec!["p0".to_string(), "p4".to_string(), "p7".to_string()],
+        )
+        .unwrap();
+        upsert_points_impl(
+            &state,
+            "docs".to_string(),
+            vec![
+                UpsertPoint {
+                    id: "p2".to_string(),
+                    vector: vec![0.1; 8],
+                    payload: Some(json!({"bucket": 5})),
+                },
+                UpsertPoint {
+                    id: "p3".to_string(),
+                    vector: vec![0.1; 8],
+                    payload: None,
+                },
+                UpsertPoint {
+                    id: "new".to_string(),
+                    vector: vec![0.2; 8],
+                    payload: Some(json!({"bucket": 2})),
+                },
+            ],
+        )
+        .unwrap();
+        delete_points_impl(&state, "docs".to_string(), vec!["p9".to_string()]).unwrap();
+
+        let data = state.data.lock();
+        let index = &data.indexes["docs"]["bucket"];
+        let rebuilt = PayloadIndex::build(&data.points["docs"], "bucket");
+        assert_eq!(index.positions, rebuilt.positions);
+        assert_eq!(index.indexed_points, rebuilt.indexed_points);
+        assert_eq!(index.indexed_points, 8);
+    }
+
+    #[test]
+    fn test_summarize_embedding_benchmark() {
+        let embeddings = vec![vec![1.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0]];
+        let result =
+            summarize_embedding_benchmark("m".to_string(), &embeddings, &[10.0, 20.0, 30.0]);
+        assert!(result.available);
+        assert_eq!(result.dimension, Some(2));
+        assert_eq!(result.avg_latency_ms, Some(20.0));
+        assert!((result.mean_similarity.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert!((result.similarity_spread.unwrap() - 1.0).abs() < 1e-9);
+
+        let single = summarize_embedding_benchmark("m".to_string(), &[vec![1.0]], &[5.0]);
+        assert!(single.available);
+        assert!(single.similarity_spread.is_none());
+
+        let mismatched =
+            summarize_embedding_benchmark("m".to_string(), &[vec![1.0], vec![1.0, 0.0]], &[]);
+        assert!(!mismatched.available);
+        assert!(mismatched.error.is_some());
+    }
+
+    #[test]
+    fn test_embedding_benchmark_report_picks_widest_spread() {
+        let narrow = summarize_embedding_benchmark(
+            "narrow".to_string(),
+            &[vec![1.0, 0.0], vec![0.9, 0.1]],
+            &[1.0, 1.0],
+        );
+        let wide = summarize_embedding_benchmark(
+            "wide".to_string(),
+            &[vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.1]],
+            &[1.0, 1.0, 1.0],
+        );
+        let missing = EmbeddingModelBenchmark::unavailable(
+            "missing".to_string(),
+            "model not found".to_string(),
+        );
+
+        let report = embedding_benchmark_report(2, vec![narrow, missing, wide]);
+        assert_eq!(report.models.len(), 3);
+        assert!(!report.models[1].available);
+        assert_eq!(report.best_model.as_deref(), Some("wide"));
     }
 }
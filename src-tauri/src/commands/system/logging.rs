@@ -0,0 +1,31 @@
+//! Log control commands
+//!
+//! Adjust log levels at runtime and read back recent log output.
+
+use crate::logging::{self, LogLevels};
+
+/// Default number of lines returned by `log_get_logs`
+const DEFAULT_TAIL_LINES: usize = 200;
+
+/// Get the current default and per-module log levels
+#[tauri::command]
+pub fn log_get_levels() -> LogLevels {
+    logging::levels()
+}
+
+/// Set the log level for a module (e.g. `app_lib::sandbox`), or the default level when
+/// `module` is omitted. Pass `"default"` as the level to remove a module override.
+#[tauri::command]
+pub fn log_set_level(module: Option<String>, level: String) -> Result<LogLevels, String> {
+    logging::set_level(module.as_deref(), &level)
+}
+
+/// Get the most recent log lines from the in-memory buffer, oldest first
+#[tauri::command]
+pub fn log_get_logs(lines: Option<usize>) -> Vec<String> {
+    logging::tail(
+        lines
+            .unwrap_or(DEFAULT_TAIL_LINES)
+            .min(logging::LOG_BUFFER_CAPACITY),
+    )
+}
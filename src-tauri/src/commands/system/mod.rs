@@ -1,10 +1,21 @@
 //! System Commands
 //!
-//! Commands for system-level operations (clipboard, environment, ports, processes, proxy, tray).
+//! Commands for system-level operations (background operations, clipboard, environment,
+//! logging, notification preferences, ports, privacy, processes, proxy, resource governor,
+//! settings, setup diagnostics, text diffs, token estimation, tray).
 
 pub mod clipboard;
 pub mod environment;
+pub mod logging;
+pub mod notifications;
+pub mod operations;
 pub mod port;
+pub mod privacy;
 pub mod process;
 pub mod proxy;
+pub mod resource_governor;
+pub mod settings;
+pub mod setup;
+pub mod text_diff;
+pub mod tokens;
 pub mod tray;
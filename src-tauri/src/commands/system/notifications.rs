@@ -0,0 +1,38 @@
+//! Notification preferences commands
+//!
+//! Read and update the preferences consulted before desktop notifications are shown.
+
+use crate::notifications::{
+    self, NotificationCategory, NotificationCenter, NotificationPayload, NotificationPreferences,
+};
+use tauri::State;
+
+/// Get the current notification preferences
+#[tauri::command]
+pub fn notification_get_preferences(
+    center: State<'_, NotificationCenter>,
+) -> NotificationPreferences {
+    center.preferences()
+}
+
+/// Replace and persist the notification preferences
+#[tauri::command]
+pub fn notification_set_preferences(
+    center: State<'_, NotificationCenter>,
+    preferences: NotificationPreferences,
+) -> Result<NotificationPreferences, String> {
+    center.set_preferences(preferences)?;
+    Ok(center.preferences())
+}
+
+/// Show a desktop notification from the frontend, subject to the preferences
+///
+/// Returns whether it was shown rather than suppressed or queued for the digest.
+#[tauri::command]
+pub fn notification_send(
+    app: tauri::AppHandle,
+    category: NotificationCategory,
+    payload: NotificationPayload,
+) -> bool {
+    notifications::notify(&app, category, payload)
+}
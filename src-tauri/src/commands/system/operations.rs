@@ -0,0 +1,35 @@
+//! Operation commands
+//!
+//! Poll and cancel long-running background operations.
+
+use crate::commands::error::CommandError;
+use crate::operations::{Operation, OperationRegistry};
+use tauri::State;
+
+/// Get an operation by ID
+#[tauri::command]
+pub fn operation_get(
+    registry: State<'_, OperationRegistry>,
+    operation_id: String,
+) -> Result<Operation, CommandError> {
+    registry
+        .get(&operation_id)
+        .ok_or_else(|| CommandError::not_found(format!("Operation not found: {}", operation_id)))
+}
+
+/// List recent operations, most recent first
+#[tauri::command]
+pub fn operation_list(
+    registry: State<'_, OperationRegistry>,
+    limit: Option<usize>,
+) -> Vec<Operation> {
+    registry.list(limit)
+}
+
+/// Request cancellation of an unfinished operation
+///
+/// Returns false when the operation is unknown or already finished.
+#[tauri::command]
+pub fn operation_cancel(registry: State<'_, OperationRegistry>, operation_id: String) -> bool {
+    registry.cancel(&operation_id)
+}
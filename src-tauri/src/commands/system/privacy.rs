@@ -0,0 +1,257 @@
+//! Privacy commands
+//!
+//! Purge stored user data across subsystems in a single call.
+
+use crate::awareness::AwarenessManager;
+use crate::commands::error::CommandError;
+use crate::sandbox::SandboxState;
+use crate::screenshot::ScreenshotManager;
+use crate::selection::SelectionManager;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+/// A data store that can be purged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacySubsystem {
+    SelectionHistory,
+    ClipboardHistory,
+    ScreenshotHistory,
+    AwarenessActivity,
+    SandboxExecutions,
+    /// Embeddings of selection and clipboard entries
+    SemanticHistory,
+    /// Cached translations of selected text
+    TranslationCache,
+}
+
+impl PrivacySubsystem {
+    pub const ALL: [PrivacySubsystem; 7] = [
+        PrivacySubsystem::SelectionHistory,
+        PrivacySubsystem::ClipboardHistory,
+        PrivacySubsystem::SemanticHistory,
+        PrivacySubsystem::TranslationCache,
+        PrivacySubsystem::ScreenshotHistory,
+        PrivacySubsystem::AwarenessActivity,
+        PrivacySubsystem::SandboxExecutions,
+    ];
+}
+
+/// Outcome of purging one subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemPurgeResult {
+    pub subsystem: PrivacySubsystem,
+    /// Number of records removed
+    pub removed: u64,
+    /// Why the subsystem could not be purged
+    pub error: Option<String>,
+}
+
+/// Result of a privacy purge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyPurgeReport {
+    /// Records older than this timestamp (ms) were removed; `None` means everything
+    pub cutoff: Option<i64>,
+    /// Whether pinned and favorited records were removed too
+    pub force: bool,
+    pub results: Vec<SubsystemPurgeResult>,
+    pub total_removed: u64,
+}
+
+/// Cutoff timestamp in milliseconds for records older than `older_than_secs`
+fn cutoff_from_age(now_ms: i64, older_than_secs: Option<u64>) -> Option<i64> {
+    older_than_secs.map(|secs| {
+        let age_ms = i64::try_from(secs.saturating_mul(1000)).unwrap_or(i64::MAX);
+        now_ms.saturating_sub(age_ms)
+    })
+}
+
+/// Deduplicated subsystems to purge, defaulting to all of them
+fn resolve_subsystems(subsystems: Option<Vec<PrivacySubsystem>>) -> Vec<PrivacySubsystem> {
+    match subsystems {
+        Some(requested) if !requested.is_empty() => PrivacySubsystem::ALL
+            .into_iter()
+            .filter(|s| requested.contains(s))
+            .collect(),
+        _ => PrivacySubsystem::ALL.to_vec(),
+    }
+}
+
+async fn purge_subsystem(
+    app: &AppHandle,
+    subsystem: PrivacySubsystem,
+    cutoff: Option<i64>,
+    force: bool,
+) -> Result<u64, String> {
+    let not_running = || format!("{:?} is not available", subsystem);
+    match subsystem {
+        PrivacySubsystem::SelectionHistory => {
+            let manager = app
+                .try_state::<SelectionManager>()
+                .ok_or_else(not_running)?;
+            let removed = manager.history.purge(cutoff, force);
+            manager.semantic_history.forget_removed();
+            Ok(removed as u64)
+        }
+        PrivacySubsystem::ClipboardHistory => {
+            let manager = app
+                .try_state::<SelectionManager>()
+                .ok_or_else(not_running)?;
+            let removed = manager.clipboard_history.purge(cutoff, force);
+            manager.semantic_history.forget_removed();
+            Ok(removed as u64)
+        }
+        PrivacySubsystem::SemanticHistory => {
+            let manager = app
+                .try_state::<SelectionManager>()
+                .ok_or_else(not_running)?;
+            manager
+                .semantic_history
+                .purge(cutoff, force)
+                .map(|removed| removed as u64)
+                .map_err(|e| e.to_string())
+        }
+        PrivacySubsystem::TranslationCache => {
+            let manager = app
+                .try_state::<SelectionManager>()
+                .ok_or_else(not_running)?;
+            manager
+                .translation_cache
+                .purge(cutoff)
+                .map(|removed| removed as u64)
+        }
+        PrivacySubsystem::ScreenshotHistory => {
+            let manager = app
+                .try_state::<ScreenshotManager>()
+                .ok_or_else(not_running)?;
+            Ok(manager.purge_history(cutoff, force) as u64)
+        }
+        PrivacySubsystem::AwarenessActivity => {
+            let manager = app
+                .try_state::<AwarenessManager>()
+                .ok_or_else(not_running)?;
+            // Focus sessions hold window titles too
+            let removed = manager.purge_history(cutoff) + manager.purge_focus_history(cutoff);
+            Ok(removed as u64)
+        }
+        PrivacySubsystem::SandboxExecutions => {
+            let state = app.try_state::<SandboxState>().ok_or_else(not_running)?;
+            let before = match cutoff {
+                Some(ms) => Some(
+                    chrono::DateTime::from_timestamp_millis(ms)
+                        .ok_or_else(|| format!("Invalid cutoff timestamp: {}", ms))?,
+                ),
+                None => None,
+            };
+            state
+                .purge_execution_history(before, force)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Delete stored user data older than `older_than_secs` seconds, or everything when omitted
+///
+/// Purges every subsystem unless `subsystems` narrows the selection. Pinned and favorited
+/// records are kept unless `force` is set. A failing subsystem does not stop the others;
+/// its error is reported in the result.
+#[tauri::command]
+pub async fn privacy_purge(
+    app: AppHandle,
+    older_than_secs: Option<u64>,
+    subsystems: Option<Vec<PrivacySubsystem>>,
+    force: Option<bool>,
+) -> Result<PrivacyPurgeReport, CommandError> {
+    let force = force.unwrap_or(false);
+    let cutoff = cutoff_from_age(chrono::Utc::now().timestamp_millis(), older_than_secs);
+    let subsystems = resolve_subsystems(subsystems);
+    log::info!(
+        "Privacy purge requested: cutoff={:?}, force={}, subsystems={:?}",
+        cutoff,
+        force,
+        subsystems
+    );
+
+    let mut results = Vec::with_capacity(subsystems.len());
+    for subsystem in subsystems {
+        let result = match purge_subsystem(&app, subsystem, cutoff, force).await {
+            Ok(removed) => SubsystemPurgeResult {
+                subsystem,
+                removed,
+                error: None,
+            },
+            Err(error) => {
+                log::warn!("Privacy purge of {:?} failed: {}", subsystem, error);
+                SubsystemPurgeResult {
+                    subsystem,
+                    removed: 0,
+                    error: Some(error),
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    let total_removed = results.iter().map(|r| r.removed).sum();
+    log::info!("Privacy purge removed {} records", total_removed);
+    Ok(PrivacyPurgeReport {
+        cutoff,
+        force,
+        results,
+        total_removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_from_age() {
+        assert_eq!(cutoff_from_age(100_000, None), None);
+        assert_eq!(cutoff_from_age(100_000, Some(60)), Some(40_000));
+        assert_eq!(
+            cutoff_from_age(100_000, Some(u64::MAX)),
+            Some(i64::MIN + 100_001)
+        );
+    }
+
+    #[test]
+    fn test_resolve_subsystems() {
+        assert_eq!(resolve_subsystems(None), PrivacySubsystem::ALL.to_vec());
+        assert_eq!(
+            resolve_subsystems(Some(vec![])),
+            PrivacySubsystem::ALL.to_vec()
+        );
+        assert_eq!(
+            resolve_subsystems(Some(vec![
+                PrivacySubsystem::SandboxExecutions,
+                PrivacySubsystem::SelectionHistory,
+                PrivacySubsystem::SandboxExecutions,
+            ])),
+            vec![
+                PrivacySubsystem::SelectionHistory,
+                PrivacySubsystem::SandboxExecutions
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subsystem_serde_names() {
+        let parsed: Vec<PrivacySubsystem> = serde_json::from_str(
+            r#"["clipboard_history", "awareness_activity", "translation_cache"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                PrivacySubsystem::ClipboardHistory,
+                PrivacySubsystem::AwarenessActivity,
+                PrivacySubsystem::TranslationCache
+            ]
+        );
+    }
+}
@@ -2,13 +2,19 @@
 //!
 //! Exposes process management functionality to the frontend.
 
-use tauri::State;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{Emitter, State};
 
+use crate::notifications::{self, NotificationCategory, NotificationPayload};
 use crate::process::{
-    ProcessFilter, ProcessInfo, ProcessManager, ProcessManagerConfig, ProcessOperation,
-    ProcessSortField, StartProcessBatchRequest, StartProcessBatchResult, StartProcessRequest,
-    StartProcessResult, TerminateProcessBatchRequest, TerminateProcessBatchResult,
-    TerminateProcessRequest, TerminateProcessResult,
+    MemoryAlertSink, MemoryWatch, MemoryWatchRequest, ProcessError, ProcessFilter, ProcessHistory,
+    ProcessInfo, ProcessListSnapshot, ProcessManager, ProcessManagerConfig, ProcessOperation,
+    ProcessSortField, ProcessStreamEvent, ProcessStreamSink, ProcessWaitResult,
+    StartProcessBatchRequest, StartProcessBatchResult, StartProcessRequest, StartProcessResult,
+    StartProcessStreamResult, TerminateProcessBatchRequest, TerminateProcessBatchResult,
+    TerminateProcessRequest, TerminateProcessResult, TerminateProcessTreeResult,
+    MEMORY_ALERT_EVENT, PROCESS_OUTPUT_EVENT, PROCESS_STREAM_EXIT_EVENT,
 };
 
 /// List running processes
@@ -23,6 +29,20 @@ pub async fn process_list(
         .map_err(|e| e.to_string())
 }
 
+/// List running processes with snapshot metadata, bypassing the refresh
+/// throttle when `force_refresh` is set
+#[tauri::command]
+pub async fn process_list_snapshot(
+    filter: Option<ProcessFilter>,
+    force_refresh: Option<bool>,
+    state: State<'_, ProcessManager>,
+) -> Result<ProcessListSnapshot, String> {
+    state
+        .list_processes_snapshot(filter, force_refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Get process by PID
 #[tauri::command]
 pub async fn process_get(
@@ -32,6 +52,15 @@ pub async fn process_get(
     state.get_process(pid).await.map_err(|e| e.to_string())
 }
 
+/// Get the environment variables of a process
+#[tauri::command]
+pub async fn process_get_env(
+    pid: u32,
+    state: State<'_, ProcessManager>,
+) -> Result<HashMap<String, String>, String> {
+    state.get_process_env(pid).await.map_err(|e| e.to_string())
+}
+
 /// Start a new process
 #[tauri::command]
 pub async fn process_start(
@@ -45,6 +74,38 @@ pub async fn process_start(
         .map_err(|e| e.to_string())
 }
 
+/// Start a process and stream its output as `process://output` events
+#[tauri::command]
+pub async fn process_start_streaming(
+    request: StartProcessRequest,
+    app: tauri::AppHandle,
+    state: State<'_, ProcessManager>,
+) -> Result<StartProcessStreamResult, String> {
+    log::info!("Starting streaming process: {}", request.program);
+    let sink: ProcessStreamSink = Arc::new(move |event| {
+        let emitted = match &event {
+            ProcessStreamEvent::Output(chunk) => app.emit(PROCESS_OUTPUT_EVENT, chunk),
+            ProcessStreamEvent::Exit(exit) => app.emit(PROCESS_STREAM_EXIT_EVENT, exit),
+        };
+        if let Err(e) = emitted {
+            log::warn!("Failed to emit process output event: {}", e);
+        }
+    });
+    state
+        .start_process_streaming(request, sink)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop a streaming process, killing it
+#[tauri::command]
+pub async fn process_stop_stream(
+    handle_id: String,
+    state: State<'_, ProcessManager>,
+) -> Result<bool, String> {
+    Ok(state.stop_stream(&handle_id).await)
+}
+
 /// Terminate a process
 #[tauri::command]
 pub async fn process_terminate(
@@ -52,9 +113,10 @@ pub async fn process_terminate(
     state: State<'_, ProcessManager>,
 ) -> Result<TerminateProcessResult, String> {
     log::info!(
-        "Terminating process: {} (force: {})",
+        "Terminating process: {} (force: {}, signal: {:?})",
         request.pid,
-        request.force
+        request.force,
+        request.signal
     );
     state
         .terminate_process(request)
@@ -62,6 +124,55 @@ pub async fn process_terminate(
         .map_err(|e| e.to_string())
 }
 
+/// Terminate a process and all of its descendants, children first
+#[tauri::command]
+pub async fn process_terminate_tree(
+    pid: u32,
+    force: Option<bool>,
+    state: State<'_, ProcessManager>,
+) -> Result<TerminateProcessTreeResult, String> {
+    let force = force.unwrap_or(false);
+    log::info!("Terminating process tree: {} (force: {})", pid, force);
+    state
+        .terminate_process_tree(pid, force)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Suspend a process; fails if it is already suspended
+#[tauri::command]
+pub async fn process_suspend(pid: u32, state: State<'_, ProcessManager>) -> Result<(), String> {
+    log::info!("Suspending process: {}", pid);
+    state.suspend_process(pid).await.map_err(|e| e.to_string())
+}
+
+/// Resume a suspended process; fails if it is not suspended
+#[tauri::command]
+pub async fn process_resume(pid: u32, state: State<'_, ProcessManager>) -> Result<(), String> {
+    log::info!("Resuming process: {}", pid);
+    state.resume_process(pid).await.map_err(|e| e.to_string())
+}
+
+/// Wait for a process to exit, up to `timeout_secs` (default: configured timeout)
+#[tauri::command]
+pub async fn process_wait(
+    pid: u32,
+    timeout_secs: Option<u64>,
+    state: State<'_, ProcessManager>,
+) -> Result<ProcessWaitResult, String> {
+    match state.wait_for_exit(pid, timeout_secs).await {
+        Ok(exit_code) => Ok(ProcessWaitResult {
+            exited: true,
+            exit_code,
+        }),
+        Err(ProcessError::Timeout) => Ok(ProcessWaitResult {
+            exited: false,
+            exit_code: None,
+        }),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 /// Start multiple processes in parallel
 #[tauri::command]
 pub async fn process_start_batch(
@@ -138,6 +249,89 @@ pub async fn process_list_operations(
     Ok(state.list_operations(limit).await)
 }
 
+/// Watch a process's memory, emitting `process://memory-alert` when it exceeds the threshold
+#[tauri::command]
+pub async fn process_watch_memory(
+    request: MemoryWatchRequest,
+    app: tauri::AppHandle,
+    state: State<'_, ProcessManager>,
+) -> Result<MemoryWatch, String> {
+    let sink: MemoryAlertSink = Arc::new(move |alert| {
+        if let Err(e) = app.emit(MEMORY_ALERT_EVENT, &alert) {
+            log::warn!("Failed to emit memory alert for {}: {}", alert.pid, e);
+        }
+        let action = if alert.terminated {
+            "was terminated"
+        } else {
+            "exceeded its limit"
+        };
+        notifications::notify(
+            &app,
+            NotificationCategory::MemoryAlerts,
+            NotificationPayload::new(
+                format!("{} {}", alert.name, action),
+                format!(
+                    "Using {} MB of memory (limit {} MB)",
+                    alert.memory_bytes / (1024 * 1024),
+                    alert.max_bytes / (1024 * 1024)
+                ),
+            ),
+        );
+    });
+    state
+        .watch_process_memory(request, sink)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop watching a process's memory
+#[tauri::command]
+pub async fn process_unwatch_memory(
+    pid: u32,
+    state: State<'_, ProcessManager>,
+) -> Result<bool, String> {
+    Ok(state.unwatch_process_memory(pid).await)
+}
+
+/// Start recording CPU/memory history of a process
+#[tauri::command]
+pub async fn process_start_sampling(
+    pid: u32,
+    interval_ms: Option<u64>,
+    state: State<'_, ProcessManager>,
+) -> Result<(), String> {
+    state
+        .start_sampling(pid, interval_ms)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stop recording CPU/memory history of a process
+#[tauri::command]
+pub async fn process_stop_sampling(
+    pid: u32,
+    state: State<'_, ProcessManager>,
+) -> Result<bool, String> {
+    Ok(state.stop_sampling(pid).await)
+}
+
+/// Get the recorded CPU/memory history of a sampled process
+#[tauri::command]
+pub async fn process_get_history(
+    pid: u32,
+    state: State<'_, ProcessManager>,
+) -> Result<Option<ProcessHistory>, String> {
+    Ok(state.get_process_history(pid).await)
+}
+
+/// List active process memory watches
+#[tauri::command]
+pub async fn process_list_memory_watches(
+    state: State<'_, ProcessManager>,
+) -> Result<Vec<MemoryWatch>, String> {
+    Ok(state.list_memory_watches().await)
+}
+
 /// Get process manager configuration
 #[tauri::command]
 pub async fn process_get_config(
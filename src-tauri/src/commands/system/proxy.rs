@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::net::TcpStream;
 use std::process::Command;
 use std::time::{Duration, Instant};
+use tauri::Manager;
 
 /// Known proxy software types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -816,6 +817,31 @@ pub fn get_backend_proxy() -> Option<String> {
     crate::http::get_global_proxy()
 }
 
+/// Enable or disable offline mode and persist the choice.
+///
+/// While enabled, network-dependent commands (academic providers, cloud AI,
+/// model downloads) fail immediately and AI features only use local Ollama.
+#[tauri::command]
+pub fn set_offline_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(crate::http::NETWORK_SETTINGS_FILE);
+    crate::http::NetworkSettings {
+        offline_mode: enabled,
+    }
+    .save(&path)?;
+    crate::http::set_offline_mode(enabled);
+    Ok(())
+}
+
+/// Get whether offline mode is enabled.
+#[tauri::command]
+pub fn get_offline_mode() -> bool {
+    crate::http::is_offline_mode()
+}
+
 /// HTTP request input for proxied requests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProxiedRequestInput {
@@ -844,6 +870,8 @@ pub struct ProxiedRequestOutput {
 pub async fn proxy_http_request(
     input: ProxiedRequestInput,
 ) -> Result<ProxiedRequestOutput, String> {
+    crate::http::ensure_url_allowed(&input.url)?;
+
     let client = if let Some(ref proxy_url) = input.proxy_url {
         crate::http::create_client_with_proxy(proxy_url, input.timeout_secs)
             .map_err(|e| format!("Failed to create proxy client: {}", e))?
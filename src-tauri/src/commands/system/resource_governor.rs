@@ -0,0 +1,36 @@
+//! Resource governor commands
+//!
+//! Inspect and configure the global concurrency budget shared by batch operations.
+
+use crate::resource_governor::{
+    GovernorSettings, GovernorStatus, ResourceGovernor, GOVERNOR_SETTINGS_FILE,
+    MAX_GLOBAL_CONCURRENCY,
+};
+use tauri::{Manager, State};
+
+/// Get current global concurrency utilization
+#[tauri::command]
+pub fn resource_governor_get_status(governor: State<'_, ResourceGovernor>) -> GovernorStatus {
+    governor.status()
+}
+
+/// Set and persist the global concurrency budget, returning the applied
+/// (clamped) value
+#[tauri::command]
+pub fn resource_governor_set_budget(
+    app: tauri::AppHandle,
+    governor: State<'_, ResourceGovernor>,
+    budget: usize,
+) -> Result<usize, String> {
+    if budget == 0 {
+        return Err("Concurrency budget must be at least 1".to_string());
+    }
+    let path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(GOVERNOR_SETTINGS_FILE);
+    let budget = budget.clamp(1, MAX_GLOBAL_CONCURRENCY);
+    GovernorSettings { budget }.save(&path)?;
+    Ok(governor.set_budget(budget))
+}
@@ -0,0 +1,410 @@
+//! Settings Tauri commands
+//!
+//! Single settings surface over the per-subsystem configs (sandbox, process
+//! manager, input completion, selection, screenshot). Updates touching several
+//! subsystems are applied atomically: every slice is validated up front, and if
+//! a subsystem rejects its slice the slices already applied are rolled back.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::error::CommandError;
+use crate::input_completion::{CompletionConfig, InputCompletionManager};
+use crate::process::{ProcessManager, ProcessManagerConfig};
+use crate::sandbox::{SandboxConfig, SandboxState};
+use crate::screenshot::{ScreenshotConfig, ScreenshotManager};
+use crate::selection::{SelectionConfig, SelectionManager};
+
+/// Subsystem owning a slice of the settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsSubsystem {
+    Sandbox,
+    Process,
+    Completion,
+    Selection,
+    Screenshot,
+}
+
+impl fmt::Display for SettingsSubsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sandbox => write!(f, "sandbox"),
+            Self::Process => write!(f, "process"),
+            Self::Completion => write!(f, "completion"),
+            Self::Selection => write!(f, "selection"),
+            Self::Screenshot => write!(f, "screenshot"),
+        }
+    }
+}
+
+/// Current configuration of every subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsSnapshot {
+    /// `None` while the sandbox is still initializing
+    pub sandbox: Option<SandboxConfig>,
+    /// `None` while the process manager is still initializing
+    pub process: Option<ProcessManagerConfig>,
+    pub completion: CompletionConfig,
+    pub selection: SelectionConfig,
+    pub screenshot: ScreenshotConfig,
+}
+
+/// Replacement slices for `settings_update`; omitted subsystems are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsPatch {
+    #[serde(default)]
+    pub sandbox: Option<SandboxConfig>,
+    #[serde(default)]
+    pub process: Option<ProcessManagerConfig>,
+    #[serde(default)]
+    pub completion: Option<CompletionConfig>,
+    #[serde(default)]
+    pub selection: Option<SelectionConfig>,
+    #[serde(default)]
+    pub screenshot: Option<ScreenshotConfig>,
+}
+
+/// Configuration in effect before a slice was applied, kept for rollback
+enum PreviousConfig {
+    Sandbox(SandboxConfig),
+    Process(ProcessManagerConfig),
+    Completion(CompletionConfig),
+    Selection(SelectionConfig),
+    Screenshot(ScreenshotConfig),
+}
+
+fn subsystem_error(error: CommandError, subsystem: SettingsSubsystem) -> CommandError {
+    error.with_details(serde_json::json!({ "subsystem": subsystem }))
+}
+
+fn validate_sandbox(config: &SandboxConfig) -> Result<(), String> {
+    if !(config.enable_docker || config.enable_podman || config.enable_native) {
+        return Err("At least one sandbox runtime must be enabled".to_string());
+    }
+    if config.default_timeout_secs == 0 {
+        return Err("default_timeout_secs must be greater than 0".to_string());
+    }
+    if config.default_memory_limit_mb == 0 {
+        return Err("default_memory_limit_mb must be greater than 0".to_string());
+    }
+    if !(1..=100).contains(&config.default_cpu_limit_percent) {
+        return Err("default_cpu_limit_percent must be between 1 and 100".to_string());
+    }
+    if config.max_output_size == 0 {
+        return Err("max_output_size must be greater than 0".to_string());
+    }
+    if !crate::sandbox::is_valid_timeout_warning_fraction(config.timeout_warning_fraction) {
+        return Err("timeout_warning_fraction must be greater than 0 and at most 1".to_string());
+    }
+    Ok(())
+}
+
+fn validate_process(config: &ProcessManagerConfig) -> Result<(), String> {
+    if config.max_tracked_processes == 0 {
+        return Err("max_tracked_processes must be greater than 0".to_string());
+    }
+    if config.default_timeout_secs == 0 {
+        return Err("default_timeout_secs must be greater than 0".to_string());
+    }
+    if let Some(program) = config
+        .allowed_programs
+        .iter()
+        .find(|p| config.denied_programs.contains(p))
+    {
+        return Err(format!("Program '{}' is both allowed and denied", program));
+    }
+    Ok(())
+}
+
+fn validate_completion(config: &CompletionConfig) -> Result<(), String> {
+    if !(0.0..=2.0).contains(&config.model.temperature) {
+        return Err("model.temperature must be between 0 and 2".to_string());
+    }
+    if config.model.timeout_secs == 0 {
+        return Err("model.timeout_secs must be greater than 0".to_string());
+    }
+    if config.trigger.min_context_length > config.trigger.max_context_length {
+        return Err("trigger.min_context_length exceeds max_context_length".to_string());
+    }
+    let debounce = &config.trigger.adaptive_debounce;
+    if debounce.min_debounce_ms > debounce.max_debounce_ms {
+        return Err("adaptive_debounce.min_debounce_ms exceeds max_debounce_ms".to_string());
+    }
+    Ok(())
+}
+
+fn validate_selection(config: &SelectionConfig) -> Result<(), String> {
+    config.toolbar_theme.validate()
+}
+
+/// Validate every slice of the patch before anything is applied
+fn validate_patch(patch: &SettingsPatch) -> Result<(), CommandError> {
+    let checks = [
+        (
+            SettingsSubsystem::Sandbox,
+            patch.sandbox.as_ref().map(validate_sandbox),
+        ),
+        (
+            SettingsSubsystem::Process,
+            patch.process.as_ref().map(validate_process),
+        ),
+        (
+            SettingsSubsystem::Completion,
+            patch.completion.as_ref().map(validate_completion),
+        ),
+        (
+            SettingsSubsystem::Selection,
+            patch.selection.as_ref().map(validate_selection),
+        ),
+    ];
+    for (subsystem, result) in checks {
+        if let Some(Err(message)) = result {
+            let message = format!("Invalid {} settings: {}", subsystem, message);
+            return Err(subsystem_error(
+                CommandError::invalid_input(message),
+                subsystem,
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn snapshot(app: &AppHandle) -> SettingsSnapshot {
+    let sandbox = match app.try_state::<SandboxState>() {
+        Some(sandbox) => Some(sandbox.config.read().await.clone()),
+        None => None,
+    };
+    let process = match app.try_state::<ProcessManager>() {
+        Some(process) => Some(process.get_config().await),
+        None => None,
+    };
+    SettingsSnapshot {
+        sandbox,
+        process,
+        completion: app.state::<InputCompletionManager>().get_config(),
+        selection: app.state::<SelectionManager>().get_config(),
+        screenshot: app.state::<ScreenshotManager>().get_config(),
+    }
+}
+
+/// Apply each slice in turn, recording the previous config of every applied slice
+///
+/// Slices that can fail (sandbox rebuild, config writes) are applied first so the
+/// infallible in-memory slices rarely need rolling back.
+async fn apply_patch(
+    app: &AppHandle,
+    patch: SettingsPatch,
+    applied: &mut Vec<PreviousConfig>,
+) -> Result<(), CommandError> {
+    if let Some(config) = patch.sandbox {
+        let sandbox = app.state::<SandboxState>();
+        let previous = sandbox.config.read().await.clone();
+        sandbox.update_config(config).await.map_err(|e| {
+            subsystem_error(
+                CommandError::internal(e.to_string()),
+                SettingsSubsystem::Sandbox,
+            )
+        })?;
+        applied.push(PreviousConfig::Sandbox(previous));
+    }
+
+    if let Some(config) = patch.process {
+        let process = app.state::<ProcessManager>();
+        let previous = process.get_config().await;
+        if let Err(e) = process.update_config(config).await {
+            // The in-memory config is swapped before the write, so restore it here
+            if let Err(restore) = process.update_config(previous).await {
+                log::error!("Failed to restore process manager config: {}", restore);
+            }
+            return Err(subsystem_error(
+                CommandError::internal(e.to_string()),
+                SettingsSubsystem::Process,
+            ));
+        }
+        applied.push(PreviousConfig::Process(previous));
+    }
+
+    if let Some(config) = patch.selection {
+        let selection = app.state::<SelectionManager>();
+        applied.push(PreviousConfig::Selection(selection.get_config()));
+        selection.update_config(config);
+        selection
+            .save_config()
+            .map_err(|e| subsystem_error(e.into(), SettingsSubsystem::Selection))?;
+    }
+
+    if let Some(config) = patch.completion {
+        let completion = app.state::<InputCompletionManager>();
+        applied.push(PreviousConfig::Completion(completion.get_config()));
+        completion.update_config(config);
+    }
+
+    if let Some(config) = patch.screenshot {
+        let screenshot = app.state::<ScreenshotManager>();
+        applied.push(PreviousConfig::Screenshot(screenshot.get_config()));
+        screenshot.update_config(config);
+    }
+
+    Ok(())
+}
+
+/// Restore previously applied slices, newest first
+async fn rollback(app: &AppHandle, applied: Vec<PreviousConfig>) {
+    for previous in applied.into_iter().rev() {
+        match previous {
+            PreviousConfig::Sandbox(config) => {
+                if let Err(e) = app.state::<SandboxState>().update_config(config).await {
+                    log::error!("Failed to roll back sandbox config: {}", e);
+                }
+            }
+            PreviousConfig::Process(config) => {
+                if let Err(e) = app.state::<ProcessManager>().update_config(config).await {
+                    log::error!("Failed to roll back process manager config: {}", e);
+                }
+            }
+            PreviousConfig::Completion(config) => {
+                app.state::<InputCompletionManager>().update_config(config);
+            }
+            PreviousConfig::Selection(config) => {
+                let selection = app.state::<SelectionManager>();
+                selection.update_config(config);
+                if let Err(e) = selection.save_config() {
+                    log::error!("Failed to roll back selection config: {}", e);
+                }
+            }
+            PreviousConfig::Screenshot(config) => {
+                app.state::<ScreenshotManager>().update_config(config);
+            }
+        }
+    }
+}
+
+/// Get the current configuration of every subsystem
+///
+/// Sandbox and process manager settings are `null` while those subsystems are
+/// still initializing.
+#[tauri::command]
+pub async fn settings_get_all(app: AppHandle) -> Result<SettingsSnapshot, CommandError> {
+    Ok(snapshot(&app).await)
+}
+
+/// Apply a settings patch atomically and return the resulting settings
+///
+/// Every slice is validated first. If a subsystem then rejects its slice, the
+/// slices already applied are restored and the error names the subsystem in
+/// `details.subsystem`.
+#[tauri::command]
+pub async fn settings_update(
+    app: AppHandle,
+    patch: SettingsPatch,
+) -> Result<SettingsSnapshot, CommandError> {
+    validate_patch(&patch)?;
+
+    let unavailable = [
+        (
+            SettingsSubsystem::Sandbox,
+            patch.sandbox.is_some() && app.try_state::<SandboxState>().is_none(),
+        ),
+        (
+            SettingsSubsystem::Process,
+            patch.process.is_some() && app.try_state::<ProcessManager>().is_none(),
+        ),
+    ];
+    if let Some((subsystem, _)) = unavailable.into_iter().find(|(_, missing)| *missing) {
+        return Err(subsystem_error(
+            CommandError::unavailable(format!("The {} subsystem is still initializing", subsystem)),
+            subsystem,
+        ));
+    }
+
+    let mut applied = Vec::new();
+    if let Err(e) = apply_patch(&app, patch, &mut applied).await {
+        log::warn!("Settings update failed, rolling back: {}", e);
+        rollback(&app, applied).await;
+        return Err(e);
+    }
+
+    log::info!("Settings updated");
+    Ok(snapshot(&app).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::error::CommandErrorCode;
+
+    #[test]
+    fn test_default_configs_are_valid() {
+        let patch = SettingsPatch {
+            sandbox: Some(SandboxConfig::default()),
+            process: Some(ProcessManagerConfig::default()),
+            completion: Some(CompletionConfig::default()),
+            selection: Some(SelectionConfig::default()),
+            screenshot: Some(ScreenshotConfig::default()),
+        };
+        assert!(validate_patch(&patch).is_ok());
+    }
+
+    #[test]
+    fn test_validate_patch_reports_subsystem() {
+        let mut process = ProcessManagerConfig::default();
+        process.allowed_programs = vec!["rm".to_string()];
+        let patch = SettingsPatch {
+            process: Some(process),
+            ..Default::default()
+        };
+
+        let err = validate_patch(&patch).unwrap_err();
+        assert_eq!(err.code, CommandErrorCode::InvalidInput);
+        assert!(err.message.contains("both allowed and denied"));
+        assert_eq!(err.details.unwrap()["subsystem"], "process");
+    }
+
+    #[test]
+    fn test_validate_sandbox() {
+        let mut config = SandboxConfig::default();
+        config.enable_docker = false;
+        config.enable_podman = false;
+        config.enable_native = false;
+        assert!(validate_sandbox(&config).is_err());
+
+        let mut config = SandboxConfig::default();
+        config.default_cpu_limit_percent = 150;
+        assert!(validate_sandbox(&config).is_err());
+
+        let mut config = SandboxConfig::default();
+        config.timeout_warning_fraction = 0.0;
+        assert!(validate_sandbox(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_completion() {
+        let mut config = CompletionConfig::default();
+        config.trigger.min_context_length = config.trigger.max_context_length + 1;
+        assert!(validate_completion(&config).is_err());
+
+        let mut config = CompletionConfig::default();
+        config.model.temperature = 3.0;
+        assert!(validate_completion(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_selection() {
+        let mut config = SelectionConfig::default();
+        config.toolbar_theme.theme = crate::selection::ToolbarThemeName::Custom;
+        assert!(validate_selection(&config).is_err());
+    }
+
+    #[test]
+    fn test_patch_deserializes_partial() {
+        let patch: SettingsPatch = serde_json::from_value(serde_json::json!({
+            "screenshot": serde_json::to_value(ScreenshotConfig::default()).unwrap(),
+        }))
+        .unwrap();
+        assert!(patch.screenshot.is_some());
+        assert!(patch.sandbox.is_none());
+        assert!(patch.selection.is_none());
+    }
+}
@@ -0,0 +1,414 @@
+//! Setup Tauri commands
+//!
+//! First-run diagnostics for the optional external dependencies that individual
+//! features rely on (container runtimes, Ollama, FFmpeg, OCR engines, MCP
+//! launchers), with per-platform install hints for anything missing.
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tokio::process::Command;
+
+use crate::commands::error::CommandError;
+use crate::commands::media::ocr::OcrState;
+use crate::commands::providers::mcp::mcp_check_command_exists;
+use crate::commands::providers::ollama::ollama_get_status;
+use crate::sandbox::{DockerRuntime, PodmanRuntime, SandboxRuntime};
+use crate::screen_recording::ffmpeg;
+use crate::screen_recording::FFmpegInstallGuide;
+use crate::screenshot::OcrProviderType;
+
+/// Default local Ollama endpoint
+const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+/// npx is a batch script on Windows and cannot be spawned without its extension
+pub(crate) const NPX_COMMAND: &str = if cfg!(windows) { "npx.cmd" } else { "npx" };
+
+/// Optional external dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dependency {
+    Docker,
+    Podman,
+    Ollama,
+    Ffmpeg,
+    Ocr,
+    Node,
+    Uv,
+}
+
+impl Dependency {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Docker => "Docker",
+            Self::Podman => "Podman",
+            Self::Ollama => "Ollama",
+            Self::Ffmpeg => "FFmpeg",
+            Self::Ocr => "OCR engine",
+            Self::Node => "Node.js (npx)",
+            Self::Uv => "uv (uvx)",
+        }
+    }
+
+    /// Features that stop working without this dependency
+    pub fn features(&self) -> Vec<String> {
+        let features: &[&str] = match self {
+            Self::Docker | Self::Podman => &["Code sandbox (isolated execution)"],
+            Self::Ollama => &[
+                "Local chat models",
+                "Local embeddings for vector search",
+                "Ollama vision OCR",
+            ],
+            Self::Ffmpeg => &["Screen recording", "Video trimming and conversion"],
+            Self::Ocr => &["Screenshot text extraction", "Selection OCR fallback"],
+            Self::Node => &["MCP servers launched with npx"],
+            Self::Uv => &["MCP servers launched with uvx"],
+        };
+        features.iter().map(|f| f.to_string()).collect()
+    }
+}
+
+/// Status of a single optional dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub dependency: Dependency,
+    pub name: String,
+    /// Whether the dependency is installed (or, for Ollama, reachable)
+    pub present: bool,
+    pub version: Option<String>,
+    /// Extra context, e.g. installed OCR languages or why a present tool is unusable
+    pub detail: Option<String>,
+    /// Features unlocked by this dependency
+    pub features: Vec<String>,
+    /// Install hint for the current platform (omitted when present)
+    pub install_hint: Option<FFmpegInstallGuide>,
+}
+
+/// Result of `setup_check_dependencies`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupReport {
+    /// Current platform (`windows`, `macos`, `linux`)
+    pub platform: String,
+    pub dependencies: Vec<DependencyStatus>,
+    /// Number of dependencies that are missing
+    pub missing_count: usize,
+}
+
+impl DependencyStatus {
+    fn new(dependency: Dependency, present: bool, version: Option<String>) -> Self {
+        Self {
+            dependency,
+            name: dependency.display_name().to_string(),
+            present,
+            version,
+            detail: None,
+            features: dependency.features(),
+            install_hint: (!present).then(|| install_hint(dependency, std::env::consts::OS)),
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+fn guide(
+    platform: &str,
+    download_url: &str,
+    instructions: &[&str],
+    quick_install: Option<&str>,
+) -> FFmpegInstallGuide {
+    FFmpegInstallGuide {
+        platform: platform.to_string(),
+        download_url: download_url.to_string(),
+        instructions: instructions.iter().map(|s| s.to_string()).collect(),
+        quick_install: quick_install.map(str::to_string),
+    }
+}
+
+/// Install hint for a dependency on the given platform (`std::env::consts::OS`)
+///
+/// FFmpeg reuses the screen recording guide, which always targets the build platform.
+fn install_hint(dependency: Dependency, os: &str) -> FFmpegInstallGuide {
+    let platform = match os {
+        "windows" => "Windows",
+        "macos" => "macOS",
+        _ => "Linux",
+    };
+    match (dependency, platform) {
+        (Dependency::Ffmpeg, _) => ffmpeg::get_install_guide(),
+        (Dependency::Docker, "Linux") => guide(
+            platform,
+            "https://docs.docker.com/engine/install/",
+            &[
+                "Install Docker Engine for your distribution",
+                "Add your user to the docker group: sudo usermod -aG docker $USER",
+                "Log out and back in, then restart the application",
+            ],
+            Some("curl -fsSL https://get.docker.com | sh"),
+        ),
+        (Dependency::Docker, _) => guide(
+            platform,
+            "https://www.docker.com/products/docker-desktop/",
+            &[
+                "Install Docker Desktop",
+                "Start Docker Desktop and wait until the engine is running",
+                "Restart the application",
+            ],
+            match platform {
+                "Windows" => Some("winget install Docker.DockerDesktop"),
+                _ => Some("brew install --cask docker"),
+            },
+        ),
+        (Dependency::Podman, _) => guide(
+            platform,
+            "https://podman.io/docs/installation",
+            &[
+                "Install Podman",
+                "On Windows and macOS, create a machine: podman machine init",
+                "Start the machine: podman machine start",
+                "Restart the application",
+            ],
+            match platform {
+                "Windows" => Some("winget install RedHat.Podman"),
+                "macOS" => Some("brew install podman"),
+                _ => Some("sudo apt install podman"),
+            },
+        ),
+        (Dependency::Ollama, _) => guide(
+            platform,
+            "https://ollama.com/download",
+            &[
+                "Install Ollama and start it",
+                "Pull a model, e.g.: ollama pull llama3.2",
+                "Pull an embedding model for vector search: ollama pull nomic-embed-text",
+            ],
+            match platform {
+                "Windows" => Some("winget install Ollama.Ollama"),
+                "macOS" => Some("brew install ollama"),
+                _ => Some("curl -fsSL https://ollama.com/install.sh | sh"),
+            },
+        ),
+        (Dependency::Ocr, "Windows") => guide(
+            platform,
+            "https://support.microsoft.com/windows/language-packs-for-windows",
+            &[
+                "Open Settings > Time & Language > Language & region",
+                "Add a language and include its optical character recognition feature",
+                "Restart the application",
+            ],
+            None,
+        ),
+        (Dependency::Ocr, _) => guide(
+            platform,
+            "https://tesseract-ocr.github.io/tessdoc/Installation.html",
+            &[
+                "Install Tesseract OCR",
+                "Install language data for the languages you need (e.g. tesseract-ocr-chi-sim)",
+                "Restart the application",
+            ],
+            match platform {
+                "macOS" => Some("brew install tesseract tesseract-lang"),
+                _ => Some("sudo apt install tesseract-ocr"),
+            },
+        ),
+        (Dependency::Node, _) => guide(
+            platform,
+            "https://nodejs.org/en/download",
+            &[
+                "Install the Node.js LTS release (includes npx)",
+                "Restart the application",
+            ],
+            match platform {
+                "Windows" => Some("winget install OpenJS.NodeJS.LTS"),
+                "macOS" => Some("brew install node"),
+                _ => Some("sudo apt install nodejs npm"),
+            },
+        ),
+        (Dependency::Uv, _) => guide(
+            platform,
+            "https://docs.astral.sh/uv/getting-started/installation/",
+            &["Install uv (includes uvx)", "Restart the application"],
+            match platform {
+                "Windows" => Some("winget install astral-sh.uv"),
+                _ => Some("curl -LsSf https://astral.sh/uv/install.sh | sh"),
+            },
+        ),
+    }
+}
+
+/// Read the first line of `<command> --version`
+async fn command_version(command: &str) -> Option<String> {
+    let output = Command::new(command).arg("--version").output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+async fn check_container_runtime(
+    dependency: Dependency,
+    runtime: &dyn SandboxRuntime,
+) -> DependencyStatus {
+    if runtime.is_available().await {
+        DependencyStatus::new(dependency, true, runtime.get_version().await.ok())
+    } else {
+        DependencyStatus::new(dependency, false, None)
+    }
+}
+
+async fn check_ollama(base_url: String) -> DependencyStatus {
+    match ollama_get_status(base_url.clone()).await {
+        Ok(status) if status.connected => {
+            let models = format!("{} model(s) installed", status.models_count);
+            DependencyStatus::new(Dependency::Ollama, true, status.version).with_detail(models)
+        }
+        Ok(_) => DependencyStatus::new(Dependency::Ollama, false, None)
+            .with_detail(format!("Not reachable at {}", base_url)),
+        Err(e) => DependencyStatus::new(Dependency::Ollama, false, None).with_detail(e),
+    }
+}
+
+async fn check_ffmpeg() -> DependencyStatus {
+    let info = tokio::task::spawn_blocking(ffmpeg::get_ffmpeg_info)
+        .await
+        .unwrap_or_default();
+    let status = DependencyStatus::new(Dependency::Ffmpeg, info.available, info.version);
+    if info.available && !info.version_ok {
+        status.with_detail(format!(
+            "Version is older than the required {}",
+            ffmpeg::MIN_FFMPEG_VERSION
+        ))
+    } else {
+        status
+    }
+}
+
+async fn check_ocr(ocr: &OcrState) -> DependencyStatus {
+    // Clone manager to avoid holding lock across await
+    let manager = ocr.manager.read().clone();
+    let engines: Vec<_> = manager
+        .get_provider_info()
+        .await
+        .into_iter()
+        .filter(|info| {
+            info.available
+                && matches!(
+                    info.provider_type,
+                    OcrProviderType::WindowsOcr | OcrProviderType::Tesseract
+                )
+        })
+        .collect();
+
+    if engines.is_empty() {
+        return DependencyStatus::new(Dependency::Ocr, false, None);
+    }
+    let detail = engines
+        .iter()
+        .map(|info| {
+            if info.languages.is_empty() {
+                info.display_name.clone()
+            } else {
+                format!("{} ({})", info.display_name, info.languages.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    DependencyStatus::new(Dependency::Ocr, true, None).with_detail(detail)
+}
+
+async fn check_command(dependency: Dependency, command: &str) -> DependencyStatus {
+    let present = mcp_check_command_exists(command.to_string())
+        .await
+        .unwrap_or(false);
+    let version = if present {
+        command_version(command).await
+    } else {
+        None
+    };
+    DependencyStatus::new(dependency, present, version)
+}
+
+/// Check every optional dependency and report what is missing
+///
+/// Each entry lists the features the dependency unlocks and, when it is missing,
+/// an install hint for the current platform. `ollama_base_url` defaults to the
+/// local Ollama endpoint.
+#[tauri::command]
+pub async fn setup_check_dependencies(
+    ocr_state: State<'_, OcrState>,
+    ollama_base_url: Option<String>,
+) -> Result<SetupReport, CommandError> {
+    let ollama_base_url = ollama_base_url.unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string());
+    let docker = DockerRuntime::new();
+    let podman = PodmanRuntime::new();
+
+    let (docker, podman, ollama, ffmpeg, ocr, node, uv) = tokio::join!(
+        check_container_runtime(Dependency::Docker, &docker),
+        check_container_runtime(Dependency::Podman, &podman),
+        check_ollama(ollama_base_url),
+        check_ffmpeg(),
+        check_ocr(&ocr_state),
+        check_command(Dependency::Node, NPX_COMMAND),
+        check_command(Dependency::Uv, "uvx"),
+    );
+
+    let dependencies = vec![docker, podman, ollama, ffmpeg, ocr, node, uv];
+    let missing_count = dependencies.iter().filter(|d| !d.present).count();
+    log::info!(
+        "[Setup] Dependency check complete: {} of {} missing",
+        missing_count,
+        dependencies.len()
+    );
+    Ok(SetupReport {
+        platform: std::env::consts::OS.to_string(),
+        dependencies,
+        missing_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_hint_per_platform() {
+        let hint = install_hint(Dependency::Ollama, "windows");
+        assert_eq!(hint.platform, "Windows");
+        assert_eq!(
+            hint.quick_install.as_deref(),
+            Some("winget install Ollama.Ollama")
+        );
+
+        let hint = install_hint(Dependency::Docker, "linux");
+        assert_eq!(hint.platform, "Linux");
+        assert!(hint.download_url.contains("engine/install"));
+
+        let hint = install_hint(Dependency::Ocr, "macos");
+        assert!(hint.quick_install.unwrap().contains("tesseract"));
+    }
+
+    #[test]
+    fn test_status_hint_only_when_missing() {
+        let present = DependencyStatus::new(Dependency::Node, true, Some("10.2.0".to_string()));
+        assert!(present.install_hint.is_none());
+        assert!(!present.features.is_empty());
+
+        let missing = DependencyStatus::new(Dependency::Uv, false, None);
+        assert!(missing.install_hint.is_some());
+        assert_eq!(missing.name, "uv (uvx)");
+    }
+
+    #[test]
+    fn test_dependency_serialization() {
+        let status = DependencyStatus::new(Dependency::Ffmpeg, false, None);
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["dependency"], "ffmpeg");
+        assert_eq!(json["present"], false);
+        assert!(json["install_hint"]["instructions"].is_array());
+    }
+}
@@ -0,0 +1,23 @@
+//! Text diff commands
+//!
+//! Expose the shared diff implementation to the frontend.
+
+use crate::commands::error::CommandError;
+use crate::text_diff::{self, DiffMode, TextDiff, DEFAULT_CONTEXT_LINES};
+
+/// Diff `a` against `b` by lines, words or characters, or as unified hunks
+///
+/// `context` sets the unchanged lines kept around unified hunks (default 3).
+#[tauri::command]
+pub async fn text_diff(
+    a: String,
+    b: String,
+    mode: DiffMode,
+    context: Option<usize>,
+) -> Result<TextDiff, CommandError> {
+    let context = context.unwrap_or(DEFAULT_CONTEXT_LINES);
+    // Large inputs take a while to diff, keep them off the async runtime
+    tauri::async_runtime::spawn_blocking(move || text_diff::diff(&a, &b, mode, context))
+        .await
+        .map_err(|e| CommandError::internal(format!("Diff task failed: {}", e)))
+}
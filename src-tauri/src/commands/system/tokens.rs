@@ -0,0 +1,13 @@
+//! Token estimation commands
+//!
+//! Expose the local token estimator to the frontend.
+
+use crate::token_estimate::{self, TokenEstimate};
+
+/// Estimate how many tokens `text` will use with `provider` (and `model`, when known)
+///
+/// The count is a local approximation; `maxTokens` is the safe bound for budgeting.
+#[tauri::command]
+pub fn tokens_estimate(text: String, provider: String, model: Option<String>) -> TokenEstimate {
+    token_estimate::estimate_tokens(&text, &provider, model.as_deref())
+}
@@ -3,10 +3,23 @@
 //! Commands for clipboard history management and context-aware clipboard analysis.
 
 use crate::selection::{
-    ClipboardAnalysis, ClipboardEntry, ContentCategory, ContentStats, DetectedLanguage,
-    ExtractedEntity, SelectionManager, SuggestedAction,
+    convert_entry, html_to_text, ClipboardAnalysis, ClipboardBatchResult, ClipboardConversion,
+    ClipboardEntry, ClipboardEvictionReport, ClipboardFormat, ClipboardRetentionPolicy,
+    ClipboardRetentionStats, ContentCategory, ContentStats, DetectedLanguage, ExtractedEntity,
+    SelectionManager, SuggestedAction,
 };
-use tauri::State;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State};
+
+/// Notify the frontend about entries evicted by the retention policy
+fn emit_clipboard_evictions(app_handle: &tauri::AppHandle, manager: &SelectionManager) {
+    if let Some(report) = manager.clipboard_history.take_pending_eviction() {
+        manager.semantic_history.forget_removed();
+        if let Err(e) = app_handle.emit("clipboard-history-evicted", &report) {
+            log::warn!("[Clipboard] Failed to emit eviction event: {}", e);
+        }
+    }
+}
 
 // ============== Clipboard History Commands ==============
 
@@ -60,13 +73,77 @@ pub async fn clipboard_delete_entry(
     manager: State<'_, SelectionManager>,
     id: String,
 ) -> Result<bool, String> {
-    Ok(manager.clipboard_history.delete_entry(&id))
+    let deleted = manager.clipboard_history.delete_entry(&id);
+    manager.semantic_history.forget_removed();
+    Ok(deleted)
+}
+
+/// Pin several clipboard entries, reporting the result for each ID
+#[tauri::command]
+pub async fn clipboard_pin_entries(
+    manager: State<'_, SelectionManager>,
+    ids: Vec<String>,
+) -> Result<ClipboardBatchResult, String> {
+    Ok(manager.clipboard_history.pin_entries(&ids))
+}
+
+/// Unpin several clipboard entries, reporting the result for each ID
+#[tauri::command]
+pub async fn clipboard_unpin_entries(
+    manager: State<'_, SelectionManager>,
+    ids: Vec<String>,
+) -> Result<ClipboardBatchResult, String> {
+    Ok(manager.clipboard_history.unpin_entries(&ids))
+}
+
+/// Delete several clipboard entries, reporting the result for each ID
+#[tauri::command]
+pub async fn clipboard_delete_entries(
+    manager: State<'_, SelectionManager>,
+    ids: Vec<String>,
+) -> Result<ClipboardBatchResult, String> {
+    let result = manager.clipboard_history.delete_entries(&ids);
+    manager.semantic_history.forget_removed();
+    Ok(result)
+}
+
+/// Convert a clipboard entry between HTML, Markdown and plain text
+///
+/// When `write_to_clipboard` is set, the converted content replaces the current
+/// clipboard; HTML output is written with its plain-text rendering as fallback.
+#[tauri::command]
+pub async fn clipboard_convert_entry(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, SelectionManager>,
+    id: String,
+    target_format: ClipboardFormat,
+    write_to_clipboard: Option<bool>,
+) -> Result<ClipboardConversion, String> {
+    let entry = manager
+        .clipboard_history
+        .get_by_id(&id)
+        .ok_or_else(|| format!("Clipboard entry not found: {}", id))?;
+    let mut conversion = convert_entry(&entry, target_format)?;
+
+    if write_to_clipboard.unwrap_or(false) {
+        match conversion.target_format {
+            ClipboardFormat::Html => {
+                let alt_text = entry.text.clone();
+                clipboard_write_html(app_handle, conversion.content.clone(), alt_text).await?
+            }
+            _ => clipboard_write_text(app_handle, conversion.content.clone()).await?,
+        }
+        conversion.written_to_clipboard = true;
+    }
+
+    Ok(conversion)
 }
 
 /// Clear unpinned clipboard history
 #[tauri::command]
 pub async fn clipboard_clear_unpinned(manager: State<'_, SelectionManager>) -> Result<(), String> {
     manager.clipboard_history.clear_unpinned();
+    manager.semantic_history.forget_removed();
     Ok(())
 }
 
@@ -74,6 +151,7 @@ pub async fn clipboard_clear_unpinned(manager: State<'_, SelectionManager>) -> R
 #[tauri::command]
 pub async fn clipboard_clear_all(manager: State<'_, SelectionManager>) -> Result<(), String> {
     manager.clipboard_history.clear_all();
+    manager.semantic_history.forget_removed();
     Ok(())
 }
 
@@ -87,9 +165,61 @@ pub async fn clipboard_copy_entry(
 }
 
 /// Check and update clipboard history
+///
+/// Emits `clipboard-history-evicted` when the retention policy removed old entries.
+/// A new entry that matches a recent selection is linked to it and
+/// `selection-copy-correlated` is emitted.
 #[tauri::command]
-pub async fn clipboard_check_update(manager: State<'_, SelectionManager>) -> Result<bool, String> {
-    manager.clipboard_history.check_and_update()
+pub async fn clipboard_check_update(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, SelectionManager>,
+) -> Result<bool, String> {
+    let changed = manager.clipboard_history.check_and_update()?;
+    if changed {
+        manager.correlate_latest_clipboard_entry();
+        manager.index_latest_clipboard_entry();
+    }
+    emit_clipboard_evictions(&app_handle, &manager);
+    Ok(changed)
+}
+
+/// Get the clipboard history retention policy
+#[tauri::command]
+pub async fn clipboard_get_retention_policy(
+    manager: State<'_, SelectionManager>,
+) -> Result<ClipboardRetentionPolicy, String> {
+    Ok(manager.clipboard_history.get_retention_policy())
+}
+
+/// Set the clipboard history retention policy and enforce it immediately
+#[tauri::command]
+pub async fn clipboard_set_retention_policy(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, SelectionManager>,
+    policy: ClipboardRetentionPolicy,
+) -> Result<ClipboardEvictionReport, String> {
+    let report = manager.set_clipboard_retention(policy);
+    emit_clipboard_evictions(&app_handle, &manager);
+    Ok(report)
+}
+
+/// Enforce the clipboard history retention policy now
+#[tauri::command]
+pub async fn clipboard_enforce_retention(
+    app_handle: tauri::AppHandle,
+    manager: State<'_, SelectionManager>,
+) -> Result<ClipboardEvictionReport, String> {
+    let report = manager.clipboard_history.enforce_retention();
+    emit_clipboard_evictions(&app_handle, &manager);
+    Ok(report)
+}
+
+/// Get clipboard history retention statistics
+#[tauri::command]
+pub async fn clipboard_get_retention_stats(
+    manager: State<'_, SelectionManager>,
+) -> Result<ClipboardRetentionStats, String> {
+    Ok(manager.clipboard_history.get_retention_stats())
 }
 
 // ============== Clipboard Context Awareness Commands ==============
@@ -174,6 +304,121 @@ pub async fn clipboard_write_html(
         .map_err(|e| format!("Failed to write HTML to clipboard: {}", e))
 }
 
+/// Result of pasting the clipboard as plain text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardPastePlainResult {
+    /// Plain text written to the clipboard
+    pub text: String,
+    /// Whether the text was extracted from HTML content
+    pub from_html: bool,
+    /// Whether the paste keystroke was sent to the foreground app
+    pub pasted: bool,
+    /// Whether the original clipboard content was restored after pasting
+    pub restored: bool,
+}
+
+/// Replace the clipboard with its plain-text content, optionally pasting it
+///
+/// HTML content is converted to text; otherwise the text flavor is used as is.
+/// With `paste`, the paste keystroke is sent to the foreground app, and with
+/// `restore` the original rich content is put back once the paste is done.
+#[tauri::command]
+pub async fn clipboard_paste_plain(
+    paste: Option<bool>,
+    restore: Option<bool>,
+) -> Result<ClipboardPastePlainResult, String> {
+    let paste = paste.unwrap_or(false);
+    let restore = restore.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || paste_plain(paste, restore))
+        .await
+        .map_err(|e| format!("Paste task failed: {}", e))?
+}
+
+fn paste_plain(paste: bool, restore: bool) -> Result<ClipboardPastePlainResult, String> {
+    use arboard::Clipboard;
+
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    let original_html = clipboard.get().html().ok().filter(|html| !html.is_empty());
+    let original_text = clipboard.get_text().ok();
+
+    let html_text = original_html
+        .as_deref()
+        .map(html_to_text)
+        .filter(|text| !text.is_empty());
+    let from_html = html_text.is_some();
+    let text = html_text
+        .or_else(|| original_text.clone())
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| "Clipboard does not contain text".to_string())?;
+
+    // Writing text clears the other flavors, leaving only plain text
+    clipboard
+        .set_text(text.clone())
+        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+
+    if !paste {
+        return Ok(ClipboardPastePlainResult {
+            text,
+            from_html,
+            pasted: false,
+            restored: false,
+        });
+    }
+
+    simulate_paste_shortcut()?;
+
+    let mut restored = false;
+    if restore {
+        // Give the target app time to read the clipboard before restoring it
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let result = match (original_html, original_text) {
+            (Some(html), alt_text) => clipboard.set_html(html, alt_text),
+            (None, Some(text)) => clipboard.set_text(text),
+            (None, None) => Ok(()),
+        };
+        match result {
+            Ok(()) => restored = true,
+            Err(e) => log::warn!("[Clipboard] Failed to restore clipboard: {}", e),
+        }
+    }
+
+    Ok(ClipboardPastePlainResult {
+        text,
+        from_html,
+        pasted: true,
+        restored,
+    })
+}
+
+/// Send the platform paste shortcut to the foreground application
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+pub fn simulate_paste_shortcut() -> Result<(), String> {
+    use rdev::{simulate, EventType, Key};
+    use std::thread;
+    use std::time::Duration;
+
+    #[cfg(target_os = "macos")]
+    let modifier = Key::MetaLeft;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Key::ControlLeft;
+
+    for event in [
+        EventType::KeyPress(modifier),
+        EventType::KeyPress(Key::KeyV),
+        EventType::KeyRelease(Key::KeyV),
+        EventType::KeyRelease(modifier),
+    ] {
+        simulate(&event).map_err(|e| format!("Failed to simulate paste: {:?}", e))?;
+        thread::sleep(Duration::from_millis(8));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+pub fn simulate_paste_shortcut() -> Result<(), String> {
+    Err("Simulating paste is not supported on this platform".to_string())
+}
+
 /// Clear clipboard using Tauri plugin
 #[tauri::command]
 pub async fn clipboard_clear(app_handle: tauri::AppHandle) -> Result<(), String> {
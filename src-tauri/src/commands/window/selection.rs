@@ -2,18 +2,33 @@
 //!
 //! Commands for controlling the selection toolbar from the frontend.
 
+use crate::commands::compatibility::replace_selected_text;
+use crate::commands::error::CommandError;
+use crate::commands::media::ocr::OcrState;
+use crate::commands::providers::ollama::ollama_generate_embedding;
+use crate::commands::storage::vector::{
+    get_collection_impl, search_points_impl, SearchPayload, VectorStoreState,
+};
+use crate::input_completion::{CompletionModelConfig, InputCompletionManager};
+use crate::screenshot::{CaptureRegion, OcrOptions, ScreenshotManager};
 use crate::selection::{
-    Selection, SelectionConfig, SelectionHistoryEntry, SelectionHistoryStats, SelectionManager,
-    SelectionPayload, SelectionStatus, SourceAppInfo,
+    action_uses_target_language, build_action_prompt, context_snippet_text, model_key,
+    selection_system_prompt, source_filter, with_context_snippets, MouseHook, SelectedText,
+    Selection, SelectionAiResult, SelectionConfig, SelectionContextConfig, SelectionDetector,
+    SelectionHistoryEntry, SelectionHistoryRetentionPolicy, SelectionHistoryStats, SelectionMacro,
+    SelectionMacroRunResult, SelectionMacroStep, SelectionMacroStepResult, SelectionManager,
+    SelectionPayload, SelectionRegion, SelectionStatus, SelectionTextSource, SelectionToolbarTheme,
+    SelectionTrimStats, SemanticHistoryHit, SemanticHistorySource, SourceAppInfo,
 };
-use tauri::State;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
 
 /// Release all stuck modifier keys (Ctrl, Alt, Shift, Win)
 ///
 /// This command can be called to reset the keyboard state if modifier keys
 /// get stuck due to interrupted key simulations or other issues.
 #[tauri::command]
-pub async fn selection_release_stuck_keys() -> Result<(), String> {
+pub async fn selection_release_stuck_keys() -> Result<(), CommandError> {
     #[cfg(target_os = "windows")]
     {
         use rdev::{simulate, EventType, Key};
@@ -58,22 +73,115 @@ pub async fn selection_release_stuck_keys() -> Result<(), String> {
 
 /// Start the selection detection service
 #[tauri::command]
-pub async fn selection_start(manager: State<'_, SelectionManager>) -> Result<(), String> {
-    manager.start().await
+pub async fn selection_start(manager: State<'_, SelectionManager>) -> Result<(), CommandError> {
+    manager.start().await.map_err(CommandError::from)
 }
 
 /// Stop the selection detection service
 #[tauri::command]
-pub async fn selection_stop(manager: State<'_, SelectionManager>) -> Result<(), String> {
-    manager.stop()
+pub async fn selection_stop(manager: State<'_, SelectionManager>) -> Result<(), CommandError> {
+    manager.stop().map_err(CommandError::from)
+}
+
+/// Read the selected text, falling back to OCR of the last drag region when the
+/// application exposes no text and `ocr_fallback` is set
+///
+/// Shared by the selection commands and the auto-detect loop.
+pub(crate) async fn get_selected_text_with_fallback(
+    detector: &SelectionDetector,
+    mouse_hook: &MouseHook,
+    ocr_fallback: bool,
+    app: &AppHandle,
+) -> Result<Option<SelectedText>, CommandError> {
+    let text = detector.get_selected_text()?;
+    if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+        return Ok(Some(SelectedText {
+            text,
+            source: SelectionTextSource::Accessibility,
+        }));
+    }
+
+    if !ocr_fallback {
+        return Ok(None);
+    }
+    let Some((start, end)) = mouse_hook.take_last_drag() else {
+        log::debug!("[Selection] No drag region available for OCR fallback");
+        return Ok(None);
+    };
+    let (Some(screenshot), Some(ocr)) = (
+        app.try_state::<ScreenshotManager>(),
+        app.try_state::<OcrState>(),
+    ) else {
+        log::debug!("[Selection] OCR fallback unavailable before screenshot and OCR init");
+        return Ok(None);
+    };
+    // Multi-line drags span the full width of the monitor they started on
+    let full_width = screenshot
+        .get_monitors()
+        .into_iter()
+        .find(|m| {
+            start.0 >= m.x as f64
+                && start.1 >= m.y as f64
+                && start.0 < m.x as f64 + m.width as f64
+                && start.1 < m.y as f64 + m.height as f64
+        })
+        .map(|m| (m.x, m.width));
+    let region = SelectionRegion::from_drag(start, end, full_width);
+
+    log::debug!(
+        "[Selection] No selectable text, falling back to OCR of {:?}",
+        region
+    );
+    let capture = screenshot.capture_region_silent(CaptureRegion {
+        x: region.x,
+        y: region.y,
+        width: region.width,
+        height: region.height,
+    })?;
+
+    // Clone manager to avoid holding lock across await
+    let ocr_manager = ocr.manager.read().clone();
+    let result = ocr_manager
+        .extract_text(None, &capture.image_data, &OcrOptions::default())
+        .await
+        .map_err(|e| e.message)?;
+
+    let text = result.text.trim();
+    Ok((!text.is_empty()).then(|| SelectedText {
+        text: text.to_string(),
+        source: SelectionTextSource::Ocr,
+    }))
 }
 
 /// Get selected text from the focused application
 #[tauri::command]
 pub async fn selection_get_text(
+    app: AppHandle,
     manager: State<'_, SelectionManager>,
-) -> Result<Option<String>, String> {
-    manager.detector.get_selected_text()
+) -> Result<Option<String>, CommandError> {
+    let selected = get_selected_text_with_fallback(
+        &manager.detector,
+        &manager.mouse_hook,
+        manager.get_config().ocr_fallback,
+        &app,
+    )
+    .await?;
+    Ok(selected.map(|s| s.text))
+}
+
+/// Get selected text along with whether it came from the application or OCR
+#[tauri::command]
+pub async fn selection_get_text_with_source(
+    app: AppHandle,
+    manager: State<'_, SelectionManager>,
+) -> Result<Option<SelectedText>, CommandError> {
+    get_selected_text_with_fallback(
+        &manager.detector,
+        &manager.mouse_hook,
+        manager.get_config().ocr_fallback,
+        &app,
+    )
+    .await
 }
 
 /// Show the selection toolbar at the specified position
@@ -83,21 +191,26 @@ pub async fn selection_show_toolbar(
     x: i32,
     y: i32,
     text: String,
-) -> Result<(), String> {
-    manager.toolbar_window.show(x, y, text)
+) -> Result<(), CommandError> {
+    manager
+        .toolbar_window
+        .show(x, y, text)
+        .map_err(CommandError::from)
 }
 
 /// Hide the selection toolbar
 #[tauri::command]
-pub async fn selection_hide_toolbar(manager: State<'_, SelectionManager>) -> Result<(), String> {
-    manager.toolbar_window.hide()
+pub async fn selection_hide_toolbar(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
+    manager.toolbar_window.hide().map_err(CommandError::from)
 }
 
 /// Check if the toolbar is currently visible
 #[tauri::command]
 pub async fn selection_is_toolbar_visible(
     manager: State<'_, SelectionManager>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     Ok(manager.toolbar_window.is_visible())
 }
 
@@ -105,7 +218,7 @@ pub async fn selection_is_toolbar_visible(
 #[tauri::command]
 pub async fn selection_get_toolbar_text(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     Ok(manager.toolbar_window.get_selected_text())
 }
 
@@ -114,22 +227,28 @@ pub async fn selection_get_toolbar_text(
 pub async fn selection_update_config(
     manager: State<'_, SelectionManager>,
     config: SelectionConfig,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    config
+        .toolbar_theme
+        .validate()
+        .map_err(CommandError::invalid_input)?;
     manager.update_config(config);
     Ok(())
 }
 
 /// Save selection configuration to file
 #[tauri::command]
-pub async fn selection_save_config(manager: State<'_, SelectionManager>) -> Result<(), String> {
-    manager.save_config()
+pub async fn selection_save_config(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
+    manager.save_config().map_err(CommandError::from)
 }
 
 /// Get current selection configuration
 #[tauri::command]
 pub async fn selection_get_config(
     manager: State<'_, SelectionManager>,
-) -> Result<SelectionConfig, String> {
+) -> Result<SelectionConfig, CommandError> {
     Ok(manager.get_config())
 }
 
@@ -137,7 +256,7 @@ pub async fn selection_get_config(
 #[tauri::command]
 pub async fn selection_get_status(
     manager: State<'_, SelectionManager>,
-) -> Result<SelectionStatus, String> {
+) -> Result<SelectionStatus, CommandError> {
     Ok(manager.get_status())
 }
 
@@ -146,21 +265,23 @@ pub async fn selection_get_status(
 pub async fn selection_set_enabled(
     manager: State<'_, SelectionManager>,
     enabled: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.set_enabled(enabled);
     Ok(())
 }
 
 /// Check if selection toolbar is enabled
 #[tauri::command]
-pub async fn selection_is_enabled(manager: State<'_, SelectionManager>) -> Result<bool, String> {
+pub async fn selection_is_enabled(
+    manager: State<'_, SelectionManager>,
+) -> Result<bool, CommandError> {
     Ok(manager.is_enabled())
 }
 
 /// Restart the selection detection service
 #[tauri::command]
-pub async fn selection_restart(manager: State<'_, SelectionManager>) -> Result<(), String> {
-    manager.restart().await
+pub async fn selection_restart(manager: State<'_, SelectionManager>) -> Result<(), CommandError> {
+    manager.restart().await.map_err(CommandError::from)
 }
 
 /// Set toolbar hover state (called from frontend when mouse enters/leaves toolbar)
@@ -168,7 +289,7 @@ pub async fn selection_restart(manager: State<'_, SelectionManager>) -> Result<(
 pub async fn selection_set_toolbar_hovered(
     manager: State<'_, SelectionManager>,
     hovered: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.toolbar_window.set_hovered(hovered);
     Ok(())
 }
@@ -178,7 +299,7 @@ pub async fn selection_set_toolbar_hovered(
 #[tauri::command]
 pub async fn selection_get_toolbar_state(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<serde_json::Value>, String> {
+) -> Result<Option<serde_json::Value>, CommandError> {
     if manager.toolbar_window.is_visible() {
         let text = manager.toolbar_window.get_selected_text();
         let (x, y) = manager.toolbar_window.get_position();
@@ -200,7 +321,7 @@ pub async fn selection_get_toolbar_state(
 pub async fn selection_set_auto_hide_timeout(
     manager: State<'_, SelectionManager>,
     timeout_ms: u64,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     manager.toolbar_window.set_auto_hide_timeout(timeout_ms);
     Ok(())
 }
@@ -209,12 +330,19 @@ pub async fn selection_set_auto_hide_timeout(
 #[tauri::command]
 pub async fn selection_get_detection_stats(
     manager: State<'_, SelectionManager>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     let (attempts, successes) = manager.detector.get_stats();
+    let (debounced, suppressed_short, suppressed_long) = manager.filter_counters.snapshot();
+    let config = manager.get_config();
     Ok(serde_json::json!({
         "attempts": attempts,
         "successes": successes,
-        "successRate": if attempts > 0 { (successes as f64 / attempts as f64) * 100.0 } else { 0.0 }
+        "successRate": if attempts > 0 { (successes as f64 / attempts as f64) * 100.0 } else { 0.0 },
+        "debounceMs": config.debounce_ms,
+        "minTextLength": config.min_text_length,
+        "debounced": debounced,
+        "suppressedShort": suppressed_short,
+        "suppressedLong": suppressed_long
     }))
 }
 
@@ -222,8 +350,8 @@ pub async fn selection_get_detection_stats(
 #[tauri::command]
 pub async fn selection_trigger(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<SelectionPayload>, String> {
-    manager.trigger()
+) -> Result<Option<SelectionPayload>, CommandError> {
+    manager.trigger().map_err(CommandError::from)
 }
 
 /// Get current mouse position
@@ -250,7 +378,7 @@ pub async fn selection_get_enhanced(
     app_name: Option<String>,
     process_name: Option<String>,
     window_title: Option<String>,
-) -> Result<Selection, String> {
+) -> Result<Selection, CommandError> {
     let source_app = if app_name.is_some() || process_name.is_some() || window_title.is_some() {
         Some(SourceAppInfo {
             name: app_name.clone().unwrap_or_default(),
@@ -269,7 +397,7 @@ pub async fn selection_get_enhanced(
 #[tauri::command]
 pub async fn selection_analyze_current(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<Selection>, String> {
+) -> Result<Option<Selection>, CommandError> {
     let text = match manager.detector.get_selected_text()? {
         Some(t) if !t.is_empty() => t,
         _ => return Ok(None),
@@ -284,7 +412,7 @@ pub async fn selection_expand_to_word(
     manager: State<'_, SelectionManager>,
     text: String,
     cursor_pos: usize,
-) -> Result<(usize, usize, String), String> {
+) -> Result<(usize, usize, String), CommandError> {
     let (start, end) = manager.detector.expand_to_word(&text, cursor_pos);
     let expanded_text = if start < end && end <= text.len() {
         text.chars().skip(start).take(end - start).collect()
@@ -300,7 +428,7 @@ pub async fn selection_expand_to_sentence(
     manager: State<'_, SelectionManager>,
     text: String,
     cursor_pos: usize,
-) -> Result<(usize, usize, String), String> {
+) -> Result<(usize, usize, String), CommandError> {
     let (start, end) = manager.detector.expand_to_sentence(&text, cursor_pos);
     let expanded_text = if start < end && end <= text.len() {
         text.chars().skip(start).take(end - start).collect()
@@ -316,7 +444,7 @@ pub async fn selection_expand_to_line(
     manager: State<'_, SelectionManager>,
     text: String,
     cursor_pos: usize,
-) -> Result<(usize, usize, String), String> {
+) -> Result<(usize, usize, String), CommandError> {
     let (start, end) = manager.detector.expand_to_line(&text, cursor_pos);
     let expanded_text = if start < end && end <= text.len() {
         text.chars().skip(start).take(end - start).collect()
@@ -332,7 +460,7 @@ pub async fn selection_expand_to_paragraph(
     manager: State<'_, SelectionManager>,
     text: String,
     cursor_pos: usize,
-) -> Result<(usize, usize, String), String> {
+) -> Result<(usize, usize, String), CommandError> {
     let (start, end) = manager.detector.expand_to_paragraph(&text, cursor_pos);
     let expanded_text = if start < end && end <= text.len() {
         text.chars().skip(start).take(end - start).collect()
@@ -349,7 +477,7 @@ pub async fn selection_expand_to_paragraph(
 pub async fn selection_get_history(
     manager: State<'_, SelectionManager>,
     count: Option<usize>,
-) -> Result<Vec<SelectionHistoryEntry>, String> {
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
     Ok(manager.history.get_recent(count.unwrap_or(20)))
 }
 
@@ -358,7 +486,7 @@ pub async fn selection_get_history(
 pub async fn selection_search_history(
     manager: State<'_, SelectionManager>,
     query: String,
-) -> Result<Vec<SelectionHistoryEntry>, String> {
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
     Ok(manager.history.search(&query))
 }
 
@@ -367,7 +495,7 @@ pub async fn selection_search_history(
 pub async fn selection_search_history_by_app(
     manager: State<'_, SelectionManager>,
     app_name: String,
-) -> Result<Vec<SelectionHistoryEntry>, String> {
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
     Ok(manager.history.search_by_app(&app_name))
 }
 
@@ -376,7 +504,7 @@ pub async fn selection_search_history_by_app(
 pub async fn selection_search_history_by_type(
     manager: State<'_, SelectionManager>,
     text_type: String,
-) -> Result<Vec<SelectionHistoryEntry>, String> {
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
     Ok(manager.history.search_by_type(&text_type))
 }
 
@@ -384,7 +512,7 @@ pub async fn selection_search_history_by_type(
 #[tauri::command]
 pub async fn selection_get_history_stats(
     manager: State<'_, SelectionManager>,
-) -> Result<SelectionHistoryStats, String> {
+) -> Result<SelectionHistoryStats, CommandError> {
     Ok(manager.history.get_stats())
 }
 
@@ -394,23 +522,617 @@ pub async fn selection_search_history_by_time(
     manager: State<'_, SelectionManager>,
     start: i64,
     end: i64,
-) -> Result<Vec<SelectionHistoryEntry>, String> {
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
     Ok(manager.history.search_by_time(start, end))
 }
 
 /// Clear selection history
 #[tauri::command]
-pub async fn selection_clear_history(manager: State<'_, SelectionManager>) -> Result<(), String> {
+pub async fn selection_clear_history(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
     manager.history.clear();
+    manager.semantic_history.forget_removed();
     Ok(())
 }
 
+/// Get a selection history entry by ID
+#[tauri::command]
+pub async fn selection_get_history_entry(
+    manager: State<'_, SelectionManager>,
+    id: String,
+) -> Result<Option<SelectionHistoryEntry>, CommandError> {
+    Ok(manager.history.get_by_id(&id))
+}
+
+/// Pin a selection history entry so it is exempt from automatic trimming
+#[tauri::command]
+pub async fn selection_pin_history_entry(
+    manager: State<'_, SelectionManager>,
+    id: String,
+) -> Result<bool, CommandError> {
+    Ok(manager.history.set_pinned(&id, true))
+}
+
+/// Unpin a selection history entry
+#[tauri::command]
+pub async fn selection_unpin_history_entry(
+    manager: State<'_, SelectionManager>,
+    id: String,
+) -> Result<bool, CommandError> {
+    Ok(manager.history.set_pinned(&id, false))
+}
+
+/// Get pinned selection history entries
+#[tauri::command]
+pub async fn selection_get_pinned_history(
+    manager: State<'_, SelectionManager>,
+) -> Result<Vec<SelectionHistoryEntry>, CommandError> {
+    Ok(manager.history.get_pinned())
+}
+
+/// Get the selection history retention policy
+#[tauri::command]
+pub async fn selection_get_history_retention(
+    manager: State<'_, SelectionManager>,
+) -> Result<SelectionHistoryRetentionPolicy, CommandError> {
+    Ok(manager.history.get_retention_policy())
+}
+
+/// Set the selection history retention policy and trim immediately
+#[tauri::command]
+pub async fn selection_set_history_retention(
+    manager: State<'_, SelectionManager>,
+    policy: SelectionHistoryRetentionPolicy,
+) -> Result<SelectionTrimStats, CommandError> {
+    Ok(manager.set_history_retention(policy))
+}
+
+/// Apply the selection history retention policy now
+#[tauri::command]
+pub async fn selection_trim_history(
+    manager: State<'_, SelectionManager>,
+) -> Result<SelectionTrimStats, CommandError> {
+    let stats = manager.history.trim();
+    manager.semantic_history.forget_removed();
+    Ok(stats)
+}
+
+/// Number of hits returned by `history_search_semantic` when no limit is given
+const DEFAULT_SEMANTIC_HISTORY_LIMIT: usize = 10;
+
+/// Search selection and clipboard history by meaning
+///
+/// Embeds `query` with the semantic history model and searches its collection,
+/// optionally restricted to one `source`. Returns no hits until an entry has been
+/// indexed, which requires `semantic_history.enabled` in the selection config.
+#[tauri::command]
+pub async fn history_search_semantic(
+    manager: State<'_, SelectionManager>,
+    vector: State<'_, Arc<VectorStoreState>>,
+    query: String,
+    limit: Option<usize>,
+    source: Option<SemanticHistorySource>,
+) -> Result<Vec<SemanticHistoryHit>, CommandError> {
+    if query.trim().is_empty() {
+        return Err(CommandError::invalid_input(
+            "Search query must not be empty",
+        ));
+    }
+    let cfg = manager.get_config().semantic_history;
+    if !cfg.has_local_endpoint() {
+        return Err(CommandError::invalid_input(format!(
+            "Semantic history embeddings must use a local endpoint, not {}",
+            cfg.embedding_base_url
+        )));
+    }
+    if get_collection_impl(&vector, cfg.collection.clone()).is_err() {
+        return Ok(Vec::new());
+    }
+
+    let embedding = ollama_generate_embedding(cfg.embedding_base_url, cfg.embedding_model, query)
+        .await
+        .map_err(CommandError::from)?;
+    let payload = SearchPayload {
+        collection: cfg.collection,
+        vector: embedding,
+        top_k: Some(limit.unwrap_or(DEFAULT_SEMANTIC_HISTORY_LIMIT)),
+        score_threshold: None,
+        offset: None,
+        limit: None,
+        filters: source.map(|source| vec![source_filter(source)]),
+        filter_mode: None,
+    };
+    let vector = vector.inner().clone();
+    let response =
+        tauri::async_runtime::spawn_blocking(move || search_points_impl(&vector, payload))
+            .await
+            .map_err(|e| CommandError::internal(format!("History search task failed: {}", e)))??;
+
+    Ok(response
+        .results
+        .iter()
+        .filter_map(SemanticHistoryHit::from_search_result)
+        .collect())
+}
+
+/// Retrieve snippets related to `text` from the configured context collection
+///
+/// Returns no snippets when retrieval is not configured, the collection is empty,
+/// or the selection cannot be embedded, so the action still runs without context.
+async fn retrieve_selection_context(
+    vector: &Arc<VectorStoreState>,
+    config: &SelectionContextConfig,
+    text: &str,
+) -> Vec<String> {
+    let Some(collection) = config.collection.clone() else {
+        return Vec::new();
+    };
+    let meta = match get_collection_impl(vector, collection.clone()) {
+        Ok(meta) if meta.document_count > 0 => meta,
+        Ok(_) => {
+            log::debug!("[Selection] Context collection '{}' is empty", collection);
+            return Vec::new();
+        }
+        Err(e) => {
+            log::warn!(
+                "[Selection] Context collection '{}' unavailable: {}",
+                collection,
+                e
+            );
+            return Vec::new();
+        }
+    };
+    let Some(model) = config.embedding_model.clone().or(meta.embedding_model) else {
+        log::warn!(
+            "[Selection] No embedding model configured for context collection '{}'",
+            collection
+        );
+        return Vec::new();
+    };
+
+    let query = match crate::commands::providers::ollama::ollama_generate_embedding(
+        config.embedding_base_url.clone(),
+        model,
+        text.to_string(),
+    )
+    .await
+    {
+        Ok(query) => query,
+        Err(e) => {
+            log::warn!("[Selection] Failed to embed selection for context: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let payload = SearchPayload {
+        collection,
+        vector: query,
+        top_k: Some(config.top_k),
+        score_threshold: None,
+        offset: None,
+        limit: None,
+        filters: None,
+        filter_mode: None,
+    };
+    let vector = vector.clone();
+    match tauri::async_runtime::spawn_blocking(move || search_points_impl(&vector, payload)).await {
+        Ok(Ok(response)) => response
+            .results
+            .iter()
+            .filter_map(|result| result.payload.as_ref().and_then(context_snippet_text))
+            .collect(),
+        Ok(Err(e)) => {
+            log::warn!("[Selection] Context search failed: {}", e);
+            Vec::new()
+        }
+        Err(e) => {
+            log::warn!("[Selection] Context search task failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Translate `text`, serving repeated translations from the translation cache
+///
+/// Returns the translation and whether it came from the cache. Fresh translations
+/// are always written to the cache, even when `use_cache` is `false`.
+async fn translate_cached(
+    manager: &SelectionManager,
+    completion: &InputCompletionManager,
+    text: &str,
+    target_language: &str,
+    model: Option<CompletionModelConfig>,
+    use_cache: bool,
+) -> Result<(String, bool), CommandError> {
+    let model_id = match &model {
+        Some(model) => model_key(model),
+        None => model_key(&completion.get_config().model),
+    };
+    if use_cache {
+        if let Some(translation) = manager
+            .translation_cache
+            .get(text, target_language, &model_id)
+        {
+            log::debug!(
+                "[Selection] Using cached translation to {} ({} chars)",
+                target_language,
+                text.len()
+            );
+            return Ok((translation, true));
+        }
+    }
+
+    let prompt = build_action_prompt("translate", text, target_language)
+        .ok_or_else(|| CommandError::internal("Translate action has no prompt"))?;
+    let output = completion
+        .generate_text(selection_system_prompt(), &prompt, model)
+        .await?;
+    if output.is_empty() {
+        return Err(CommandError::internal("Model returned an empty response"));
+    }
+    manager
+        .translation_cache
+        .insert(text, target_language, &model_id, &output);
+    Ok((output, false))
+}
+
+/// Translate text with the selection translate action
+///
+/// `target_language` defaults to the configured selection target language.
+/// Results are cached by normalized text, target language and model, and repeated
+/// translations are returned from the cache with `cached: true`; pass
+/// `use_cache: false` to force a new translation.
+#[tauri::command]
+pub async fn selection_translate(
+    manager: State<'_, SelectionManager>,
+    completion: State<'_, InputCompletionManager>,
+    text: String,
+    target_language: Option<String>,
+    model: Option<CompletionModelConfig>,
+    use_cache: Option<bool>,
+) -> Result<SelectionAiResult, CommandError> {
+    if text.trim().is_empty() {
+        return Err(CommandError::invalid_input("No text to translate"));
+    }
+    let target_language = target_language.unwrap_or_else(|| manager.get_config().target_language);
+
+    let (output, cached) = translate_cached(
+        &manager,
+        &completion,
+        &text,
+        &target_language,
+        model,
+        use_cache.unwrap_or(true),
+    )
+    .await?;
+    manager.macros.record_step(SelectionMacroStep::Action {
+        action_id: "translate".to_string(),
+        target_language: Some(target_language.clone()),
+    });
+    Ok(SelectionAiResult {
+        action_id: "translate".to_string(),
+        target_language: Some(target_language),
+        result: output,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        cached,
+        context_collection: None,
+    })
+}
+
+/// Remove all cached translations, returning how many were removed
+#[tauri::command]
+pub async fn selection_clear_translation_cache(
+    manager: State<'_, SelectionManager>,
+) -> Result<usize, CommandError> {
+    manager
+        .translation_cache
+        .clear()
+        .map_err(CommandError::from)
+}
+
+/// Re-run an AI action on a selection history entry
+///
+/// Loads the stored selection text, so the text no longer needs to be selected in
+/// any application. Results are cached on the history entry unless `store_result`
+/// is `false`; pass `use_cache: false` to force regeneration. Translations also go
+/// through the shared translation cache. With `use_context`, snippets from the
+/// configured vector collection are prepended to the prompt and both caches are
+/// bypassed; such results are stored apart from ungrounded ones.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn selection_ai_process_history(
+    manager: State<'_, SelectionManager>,
+    completion: State<'_, InputCompletionManager>,
+    vector: State<'_, Arc<VectorStoreState>>,
+    id: String,
+    action_id: String,
+    target_language: Option<String>,
+    model: Option<CompletionModelConfig>,
+    use_cache: Option<bool>,
+    store_result: Option<bool>,
+    use_context: Option<bool>,
+) -> Result<SelectionAiResult, CommandError> {
+    let entry = manager.history.get_by_id(&id).ok_or_else(|| {
+        CommandError::not_found(format!("Selection history entry not found: {}", id))
+    })?;
+
+    let target_language = if action_uses_target_language(&action_id) {
+        Some(target_language.unwrap_or_else(|| manager.get_config().target_language))
+    } else {
+        None
+    };
+
+    let use_context = use_context.unwrap_or(false);
+    let use_cache = use_cache.unwrap_or(true);
+    if use_cache && !use_context {
+        if let Some(mut cached) =
+            manager
+                .history
+                .get_cached_ai_result(&id, &action_id, target_language.as_deref(), None)
+        {
+            log::debug!(
+                "[Selection] Using cached '{}' result for history entry {}",
+                action_id,
+                id
+            );
+            cached.cached = true;
+            manager.macros.record_step(SelectionMacroStep::Action {
+                action_id: action_id.clone(),
+                target_language: target_language.clone(),
+            });
+            return Ok(cached);
+        }
+    }
+
+    let (output, cached, context_collection) = if action_id == "translate" && !use_context {
+        log::info!(
+            "[Selection] Translating history entry {} ({} chars)",
+            id,
+            entry.text.len()
+        );
+        let (output, cached) = translate_cached(
+            &manager,
+            &completion,
+            &entry.text,
+            target_language.as_deref().unwrap_or_default(),
+            model,
+            use_cache,
+        )
+        .await?;
+        (output, cached, None)
+    } else {
+        let prompt = build_action_prompt(
+            &action_id,
+            &entry.text,
+            target_language.as_deref().unwrap_or_default(),
+        )
+        .ok_or_else(|| {
+            CommandError::invalid_input(format!("Unsupported AI action: {}", action_id))
+        })?;
+        let (prompt, context_collection) = if use_context {
+            let context_config = manager.get_config().ai_context;
+            let snippets = retrieve_selection_context(&vector, &context_config, &entry.text).await;
+            log::debug!(
+                "[Selection] Retrieved {} context snippet(s) for '{}'",
+                snippets.len(),
+                action_id
+            );
+            let grounded_in = context_config.collection.filter(|_| !snippets.is_empty());
+            (with_context_snippets(prompt, &snippets), grounded_in)
+        } else {
+            (prompt, None)
+        };
+
+        log::info!(
+            "[Selection] Running '{}' on history entry {} ({} chars)",
+            action_id,
+            id,
+            entry.text.len()
+        );
+        let output = completion
+            .generate_text(selection_system_prompt(), &prompt, model)
+            .await?;
+        if output.is_empty() {
+            return Err(CommandError::internal("Model returned an empty response"));
+        }
+        (output, false, context_collection)
+    };
+
+    manager.macros.record_step(SelectionMacroStep::Action {
+        action_id: action_id.clone(),
+        target_language: target_language.clone(),
+    });
+    let result = SelectionAiResult {
+        action_id,
+        target_language,
+        result: output,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        cached,
+        context_collection,
+    };
+    if store_result.unwrap_or(true) {
+        manager.history.store_ai_result(&id, result.clone());
+    }
+    Ok(result)
+}
+
+/// Start recording a selection macro
+///
+/// AI actions run through `selection_ai_process_history` and replacements made with
+/// `selection_replace_text` are captured automatically; actions performed elsewhere
+/// are reported with `selection_record_macro_step`.
+#[tauri::command]
+pub async fn selection_record_macro_start(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
+    manager.macros.start_recording().map_err(CommandError::from)
+}
+
+/// Stop recording and save the captured steps as a macro
+#[tauri::command]
+pub async fn selection_record_macro_stop(
+    manager: State<'_, SelectionManager>,
+    name: Option<String>,
+) -> Result<SelectionMacro, CommandError> {
+    manager
+        .macros
+        .stop_recording(name)
+        .map_err(CommandError::from)
+}
+
+/// Discard the macro being recorded
+#[tauri::command]
+pub async fn selection_record_macro_cancel(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
+    manager.macros.cancel_recording();
+    Ok(())
+}
+
+/// Report an action performed outside the backend while recording
+///
+/// Returns whether a recording is in progress.
+#[tauri::command]
+pub async fn selection_record_macro_step(
+    manager: State<'_, SelectionManager>,
+    step: SelectionMacroStep,
+) -> Result<bool, CommandError> {
+    manager.macros.record_step(step);
+    Ok(manager.macros.is_recording())
+}
+
+/// Check whether a macro is being recorded
+#[tauri::command]
+pub async fn selection_is_recording_macro(
+    manager: State<'_, SelectionManager>,
+) -> Result<bool, CommandError> {
+    Ok(manager.macros.is_recording())
+}
+
+/// List saved selection macros
+#[tauri::command]
+pub async fn selection_list_macros(
+    manager: State<'_, SelectionManager>,
+) -> Result<Vec<SelectionMacro>, CommandError> {
+    Ok(manager.macros.list())
+}
+
+/// Delete a saved selection macro
+#[tauri::command]
+pub async fn selection_delete_macro(
+    manager: State<'_, SelectionManager>,
+    id: String,
+) -> Result<bool, CommandError> {
+    manager.macros.delete(&id).map_err(CommandError::from)
+}
+
+/// Replay a saved macro
+///
+/// Runs against `input` when given, otherwise the current toolbar selection or the
+/// last detected selection. Each AI action receives the previous step's output.
+#[tauri::command]
+pub async fn selection_run_macro(
+    manager: State<'_, SelectionManager>,
+    completion: State<'_, InputCompletionManager>,
+    id: String,
+    input: Option<String>,
+    model: Option<CompletionModelConfig>,
+) -> Result<SelectionMacroRunResult, CommandError> {
+    let selection_macro = manager
+        .macros
+        .get(&id)
+        .ok_or_else(|| CommandError::not_found(format!("Selection macro not found: {}", id)))?;
+    let input = input
+        .or_else(|| manager.toolbar_window.get_selected_text())
+        .or_else(|| manager.detector.get_last_text())
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| CommandError::invalid_input("No selected text to run the macro on"))?;
+
+    log::info!(
+        "[Selection] Running macro '{}' ({} step(s), {} chars)",
+        selection_macro.name,
+        selection_macro.steps.len(),
+        input.len()
+    );
+    let mut current = input.clone();
+    let mut steps = Vec::with_capacity(selection_macro.steps.len());
+    for step in &selection_macro.steps {
+        match step {
+            SelectionMacroStep::Action {
+                action_id,
+                target_language,
+            } => {
+                let target_language = if action_uses_target_language(action_id) {
+                    target_language
+                        .clone()
+                        .unwrap_or_else(|| manager.get_config().target_language)
+                } else {
+                    String::new()
+                };
+                current = if action_id == "translate" {
+                    translate_cached(
+                        &manager,
+                        &completion,
+                        &current,
+                        &target_language,
+                        model.clone(),
+                        true,
+                    )
+                    .await?
+                    .0
+                } else {
+                    let prompt = build_action_prompt(action_id, &current, &target_language)
+                        .ok_or_else(|| {
+                            CommandError::invalid_input(format!(
+                                "Unsupported AI action: {}",
+                                action_id
+                            ))
+                        })?;
+                    let output = completion
+                        .generate_text(selection_system_prompt(), &prompt, model.clone())
+                        .await?;
+                    if output.is_empty() {
+                        return Err(CommandError::internal(format!(
+                            "Model returned an empty response for '{}'",
+                            action_id
+                        )));
+                    }
+                    output
+                };
+            }
+            SelectionMacroStep::Copy => {
+                let mut clipboard = arboard::Clipboard::new()
+                    .map_err(|e| CommandError::unavailable(e.to_string()))?;
+                clipboard
+                    .set_text(current.clone())
+                    .map_err(|e| CommandError::internal(e.to_string()))?;
+            }
+            SelectionMacroStep::Replace => {
+                replace_selected_text(current.clone())?;
+            }
+        }
+        steps.push(SelectionMacroStepResult {
+            step: step.clone(),
+            output: current.clone(),
+        });
+    }
+
+    manager.macros.mark_run(&id);
+    Ok(SelectionMacroRunResult {
+        macro_id: id,
+        input,
+        output: current,
+        steps,
+    })
+}
+
 /// Export selection history to JSON
 #[tauri::command]
 pub async fn selection_export_history(
     manager: State<'_, SelectionManager>,
-) -> Result<String, String> {
-    manager.history.export_json()
+) -> Result<String, CommandError> {
+    manager.history.export_json().map_err(CommandError::from)
 }
 
 /// Import selection history from JSON
@@ -418,24 +1140,47 @@ pub async fn selection_export_history(
 pub async fn selection_import_history(
     manager: State<'_, SelectionManager>,
     json: String,
-) -> Result<usize, String> {
-    manager.history.import_json(&json)
+) -> Result<usize, CommandError> {
+    manager
+        .history
+        .import_json(&json)
+        .map_err(CommandError::from)
 }
 
 /// Get toolbar configuration
+///
+/// Includes `toolbar_colors`, the colors resolved from the active toolbar theme.
 #[tauri::command]
 pub async fn selection_get_toolbar_config(
     manager: State<'_, SelectionManager>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     let config = manager.get_config();
-    serde_json::to_value(&config).map_err(|e| format!("Failed to serialize config: {}", e))
+    let colors = config.toolbar_theme.resolved_colors();
+    let mut value = serde_json::to_value(&config)
+        .map_err(|e| CommandError::internal(format!("Failed to serialize config: {}", e)))?;
+    value["toolbar_colors"] = serde_json::to_value(colors)
+        .map_err(|e| CommandError::internal(format!("Failed to serialize colors: {}", e)))?;
+    Ok(value)
+}
+
+/// Set the toolbar theme and per-action colors and icons
+///
+/// Colors must be hex, `rgb()`/`rgba()` or `hsl()`/`hsla()` values.
+#[tauri::command]
+pub async fn selection_set_toolbar_theme(
+    manager: State<'_, SelectionManager>,
+    theme: SelectionToolbarTheme,
+) -> Result<(), CommandError> {
+    manager
+        .set_toolbar_theme(theme)
+        .map_err(CommandError::invalid_input)
 }
 
 /// Get selection statistics
 #[tauri::command]
 pub async fn selection_get_stats_summary(
     manager: State<'_, SelectionManager>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     let (attempts, successes) = manager.detector.get_stats();
     let history_stats = manager.history.get_stats();
 
@@ -450,6 +1195,8 @@ pub async fn selection_get_stats_summary(
             "byApp": history_stats.by_app,
             "byType": history_stats.by_type,
             "averageLength": history_stats.avg_text_length,
+            "pinned": history_stats.pinned_count,
+            "trimmed": history_stats.trimmed.total(),
         }
     }))
 }
@@ -460,7 +1207,7 @@ pub async fn selection_get_stats_summary(
 #[tauri::command]
 pub async fn selection_time_since_last_detection(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<u64>, String> {
+) -> Result<Option<u64>, CommandError> {
     Ok(manager
         .detector
         .time_since_last_detection()
@@ -471,13 +1218,15 @@ pub async fn selection_time_since_last_detection(
 #[tauri::command]
 pub async fn selection_get_last_text(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     Ok(manager.detector.get_last_text())
 }
 
 /// Clear the last detected text
 #[tauri::command]
-pub async fn selection_clear_last_text(manager: State<'_, SelectionManager>) -> Result<(), String> {
+pub async fn selection_clear_last_text(
+    manager: State<'_, SelectionManager>,
+) -> Result<(), CommandError> {
     manager.detector.clear_last_text();
     Ok(())
 }
@@ -486,7 +1235,7 @@ pub async fn selection_clear_last_text(manager: State<'_, SelectionManager>) ->
 #[tauri::command]
 pub async fn selection_get_last_selection(
     manager: State<'_, SelectionManager>,
-) -> Result<Option<Selection>, String> {
+) -> Result<Option<Selection>, CommandError> {
     Ok(manager.detector.get_last_selection())
 }
 
@@ -39,6 +39,8 @@ pub async fn selection_smart_expand(
         "url" => SelectionMode::Url,
         "email" => SelectionMode::Email,
         "file_path" => SelectionMode::FilePath,
+        "identifier" => SelectionMode::Identifier,
+        "number_unit" => SelectionMode::NumberWithUnit,
         _ => SelectionMode::Word,
     };
 
@@ -46,6 +48,9 @@ pub async fn selection_smart_expand(
 }
 
 /// Auto-detect best expansion mode and expand
+///
+/// URLs, emails, numbers with units and dotted identifiers under the cursor are
+/// preferred over quote, bracket and word expansion.
 #[tauri::command]
 pub async fn selection_auto_expand(
     manager: State<'_, SelectionManager>,
@@ -67,7 +72,8 @@ pub async fn selection_auto_expand(
     Ok(manager.smart_selection.auto_expand(&context))
 }
 
-/// Get available selection modes
+/// Get available selection modes, including the type-specific ones
+/// (`url`, `email`, `file_path`, `identifier`, `number_unit`)
 #[tauri::command]
 pub async fn selection_get_modes() -> Result<Vec<String>, String> {
     Ok(vec![
@@ -82,6 +88,8 @@ pub async fn selection_get_modes() -> Result<Vec<String>, String> {
         "url".to_string(),
         "email".to_string(),
         "file_path".to_string(),
+        "identifier".to_string(),
+        "number_unit".to_string(),
     ])
 }
 
@@ -169,6 +177,8 @@ mod tests {
         assert!(modes.contains(&"code_block".to_string()));
         assert!(modes.contains(&"url".to_string()));
         assert!(modes.contains(&"email".to_string()));
+        assert!(modes.contains(&"identifier".to_string()));
+        assert!(modes.contains(&"number_unit".to_string()));
     }
 
     #[test]
@@ -0,0 +1,317 @@
+//! Active document context
+//!
+//! Bundles the focused editor's file, language, contents, selection, and cursor
+//! into a single snapshot taken from one active-window query.
+
+use super::{EditorContext, FileContext, WindowInfo};
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Largest file whose contents are included in the snapshot
+const MAX_DOCUMENT_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Cursor position in the active document (1-based)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocumentCursor {
+    pub line: u32,
+    pub column: Option<u32>,
+}
+
+/// Text selected in the active window, as seen by the selection service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSelection {
+    pub text: String,
+    /// Title of the window the selection was made in
+    pub window_title: Option<String>,
+    pub timestamp: i64,
+}
+
+/// Per-field flags set when the platform could not provide a value
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ActiveDocumentPartial {
+    pub file_path: bool,
+    pub language: bool,
+    /// Contents unavailable, too large, or possibly stale (unsaved edits)
+    pub text: bool,
+    pub selection: bool,
+    pub cursor: bool,
+}
+
+/// One-call snapshot of the document being edited
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveDocument {
+    /// Editor name (if the focused window is a known editor)
+    pub editor_name: Option<String>,
+    /// Title of the focused window
+    pub window_title: Option<String>,
+    /// Absolute file path when resolvable, otherwise the path as shown in the title
+    pub file_path: Option<String>,
+    pub file_name: Option<String>,
+    /// Language inferred from the file extension
+    pub language: Option<String>,
+    /// File contents as saved on disk
+    pub text: Option<String>,
+    pub selection: Option<String>,
+    pub cursor: Option<DocumentCursor>,
+    /// Whether the editor reports unsaved changes
+    pub is_modified: bool,
+    pub project_root: Option<String>,
+    pub partial: ActiveDocumentPartial,
+    pub timestamp: i64,
+}
+
+impl ActiveDocument {
+    /// Build a snapshot from a single window query.
+    ///
+    /// `selection` is only used when it was made in the same window.
+    pub fn from_window_info(
+        window: Option<&WindowInfo>,
+        selection: Option<DocumentSelection>,
+    ) -> Self {
+        let editor = window.and_then(|w| EditorContext::from_window_info(w).ok());
+        let file = window.and_then(|w| FileContext::from_window_info(w).ok());
+        Self::assemble(window, editor.as_ref(), file.as_ref(), selection)
+    }
+
+    fn assemble(
+        window: Option<&WindowInfo>,
+        editor: Option<&EditorContext>,
+        file: Option<&FileContext>,
+        selection: Option<DocumentSelection>,
+    ) -> Self {
+        let window_title = window.map(|w| w.title.clone());
+        let editor_name = editor
+            .map(|e| e.editor_name.clone())
+            .filter(|name| name != "Unknown Editor");
+
+        let raw_path = editor
+            .and_then(|e| e.file_path.clone())
+            .or_else(|| file.and_then(|f| f.path.clone()));
+        let project_root = file.and_then(|f| f.project_root.clone());
+        let file_path = raw_path
+            .as_deref()
+            .map(|p| Self::resolve_path(p, project_root.as_deref()));
+        let file_name = editor
+            .and_then(|e| e.file_name.clone())
+            .or_else(|| file.and_then(|f| f.name.clone()))
+            .or_else(|| {
+                file_path.as_deref().and_then(|p| {
+                    Path::new(p)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                })
+            });
+        let language = editor
+            .and_then(|e| e.language.clone())
+            .or_else(|| file.and_then(|f| f.language.clone()));
+        let is_modified = editor.map(|e| e.is_modified).unwrap_or(false)
+            || file.map(|f| f.is_modified).unwrap_or(false);
+
+        let text = file_path.as_deref().and_then(Self::read_text);
+        let cursor = editor.and_then(|e| {
+            e.line_number.map(|line| DocumentCursor {
+                line,
+                column: e.column_number,
+            })
+        });
+
+        let selection = selection.and_then(|s| {
+            let same_window = match (&s.window_title, &window_title) {
+                (Some(selected_in), Some(active)) => selected_in == active,
+                _ => false,
+            };
+            if same_window && !s.text.is_empty() {
+                Some(s.text)
+            } else {
+                trace!("Ignoring selection from another window");
+                None
+            }
+        });
+
+        let file_path_resolved = file_path
+            .as_deref()
+            .map(|p| Path::new(p).is_absolute())
+            .unwrap_or(false);
+        let partial = ActiveDocumentPartial {
+            file_path: !file_path_resolved,
+            language: language.is_none(),
+            text: text.is_none() || is_modified,
+            selection: selection.is_none(),
+            cursor: cursor.is_none(),
+        };
+
+        debug!(
+            "Active document: {:?} (lang: {:?}, text: {}, selection: {}, cursor: {:?})",
+            file_name,
+            language,
+            text.is_some(),
+            selection.is_some(),
+            cursor
+        );
+
+        Self {
+            editor_name,
+            window_title,
+            file_path,
+            file_name,
+            language,
+            text,
+            selection,
+            cursor,
+            is_modified,
+            project_root,
+            partial,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Resolve a title-derived path against the project root when it is relative
+    fn resolve_path(path: &str, project_root: Option<&str>) -> String {
+        let candidate = Path::new(path);
+        if candidate.is_absolute() {
+            return path.to_string();
+        }
+        if let Some(root) = project_root {
+            let joined = Path::new(root).join(candidate);
+            if joined.exists() {
+                return joined.to_string_lossy().to_string();
+            }
+        }
+        path.to_string()
+    }
+
+    fn read_text(path: &str) -> Option<String> {
+        let path = Path::new(path);
+        if !path.is_absolute() {
+            return None;
+        }
+        let metadata = std::fs::metadata(path).ok()?;
+        if !metadata.is_file() || metadata.len() > MAX_DOCUMENT_BYTES {
+            trace!(
+                "Skipping document contents: {:?} ({} bytes)",
+                path,
+                metadata.len()
+            );
+            return None;
+        }
+        std::fs::read_to_string(path).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn window(title: &str) -> WindowInfo {
+        WindowInfo {
+            handle: 1,
+            title: title.to_string(),
+            class_name: "Chrome_WidgetWin_1".to_string(),
+            process_id: 42,
+            process_name: "Code.exe".to_string(),
+            exe_path: None,
+            x: 0,
+            y: 0,
+            width: 1280,
+            height: 800,
+            is_minimized: false,
+            is_maximized: false,
+            is_focused: true,
+            is_visible: true,
+        }
+    }
+
+    fn editor(file_path: Option<String>, line: Option<u32>) -> EditorContext {
+        EditorContext {
+            editor_name: "Visual Studio Code".to_string(),
+            file_path,
+            file_name: Some("main.rs".to_string()),
+            file_extension: Some("rs".to_string()),
+            language: Some("Rust".to_string()),
+            project_name: None,
+            is_modified: false,
+            git_branch: None,
+            line_number: line,
+            column_number: line.map(|_| 5),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_assemble_reads_text_and_keeps_same_window_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        std::fs::write(&path, "fn main() {}\n").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let window = window("main.rs - app - Visual Studio Code");
+        let editor = editor(Some(path.clone()), Some(12));
+        let selection = DocumentSelection {
+            text: "main".to_string(),
+            window_title: Some(window.title.clone()),
+            timestamp: 0,
+        };
+
+        let doc = ActiveDocument::assemble(Some(&window), Some(&editor), None, Some(selection));
+        assert_eq!(doc.file_path.as_deref(), Some(path.as_str()));
+        assert_eq!(doc.language.as_deref(), Some("Rust"));
+        assert_eq!(doc.text.as_deref(), Some("fn main() {}\n"));
+        assert_eq!(doc.selection.as_deref(), Some("main"));
+        assert_eq!(
+            doc.cursor,
+            Some(DocumentCursor {
+                line: 12,
+                column: Some(5)
+            })
+        );
+        assert_eq!(doc.partial, ActiveDocumentPartial::default());
+    }
+
+    #[test]
+    fn test_assemble_flags_missing_fields() {
+        let window = window("main.rs - app - Visual Studio Code");
+        let editor = editor(Some("main.rs".to_string()), None);
+        let selection = DocumentSelection {
+            text: "elsewhere".to_string(),
+            window_title: Some("Other window".to_string()),
+            timestamp: 0,
+        };
+
+        let doc = ActiveDocument::assemble(Some(&window), Some(&editor), None, Some(selection));
+        assert_eq!(doc.file_name.as_deref(), Some("main.rs"));
+        assert!(doc.text.is_none());
+        assert!(doc.selection.is_none());
+        assert!(doc.partial.file_path);
+        assert!(doc.partial.text);
+        assert!(doc.partial.selection);
+        assert!(doc.partial.cursor);
+        assert!(!doc.partial.language);
+    }
+
+    #[test]
+    fn test_assemble_without_window() {
+        let doc = ActiveDocument::assemble(None, None, None, None);
+        assert!(doc.file_path.is_none());
+        assert!(doc.editor_name.is_none());
+        assert!(doc.partial.file_path);
+        assert!(doc.partial.language);
+    }
+
+    #[test]
+    fn test_unsaved_changes_mark_text_partial() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.rs");
+        std::fs::write(&path, "fn main() {}\n").unwrap();
+
+        let window = window("● main.rs - app - Visual Studio Code");
+        let mut editor = editor(Some(path.to_string_lossy().to_string()), Some(1));
+        editor.is_modified = true;
+
+        let doc = ActiveDocument::assemble(Some(&window), Some(&editor), None, None);
+        assert!(doc.text.is_some());
+        assert!(doc.is_modified);
+        assert!(doc.partial.text);
+    }
+}
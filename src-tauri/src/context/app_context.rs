@@ -130,6 +130,11 @@ impl AppContext {
         })
     }
 
+    /// Classify an application by process name and window title
+    pub fn classify(process_name: &str, title: &str) -> AppType {
+        Self::detect_app_type(&process_name.to_lowercase(), &title.to_lowercase(), "").0
+    }
+
     /// Detect application type from process and window information
     fn detect_app_type(process_name: &str, title: &str, class_name: &str) -> (AppType, String) {
         trace!(
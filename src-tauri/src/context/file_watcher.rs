@@ -0,0 +1,490 @@
+//! File watching for context files
+//!
+//! Watches files surfaced by the context APIs and reports on-disk changes so
+//! agents can react to external edits. Parent directories are watched rather
+//! than the files themselves so atomic saves (write to temp + rename) are seen.
+
+use super::{FileContext, WindowManager};
+use log::{debug, trace, warn};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Event emitted when a watched file changes on disk
+pub const FILE_CHANGED_EVENT: &str = "context://file-changed";
+
+/// Maximum number of files watched at once
+pub const MAX_FILE_WATCHERS: usize = 32;
+
+/// How often watched files are checked against open windows
+const PRUNE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Changes to the same file within this window are reported once
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Kind of on-disk change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Payload of [`FILE_CHANGED_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChangedEvent {
+    pub path: String,
+    pub kind: FileChangeKind,
+    pub timestamp: i64,
+}
+
+/// Callback invoked for each reported change
+pub type FileChangeSink = Arc<dyn Fn(FileChangedEvent) + Send + Sync>;
+
+struct WatcherState {
+    watcher: Option<RecommendedWatcher>,
+    /// Watched file -> time it was first watched
+    files: HashMap<PathBuf, Instant>,
+    /// Watched parent directory -> number of watched files in it
+    dirs: HashMap<PathBuf, usize>,
+    pruner_running: bool,
+}
+
+/// Bounded set of file watchers shared by the context commands
+pub struct FileWatcher {
+    state: Arc<Mutex<WatcherState>>,
+    /// Read by the notify callback; never written while `watch`/`unwatch` runs
+    watched: Arc<RwLock<HashSet<PathBuf>>>,
+    max_watchers: usize,
+}
+
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self::with_limit(MAX_FILE_WATCHERS)
+    }
+
+    pub fn with_limit(max_watchers: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(WatcherState {
+                watcher: None,
+                files: HashMap::new(),
+                dirs: HashMap::new(),
+                pruner_running: false,
+            })),
+            watched: Arc::new(RwLock::new(HashSet::new())),
+            max_watchers,
+        }
+    }
+
+    /// Start watching a file. Returns the canonical path being watched.
+    ///
+    /// `sink` is only used when the underlying OS watcher is first created.
+    pub fn watch(&self, path: &str, sink: FileChangeSink) -> Result<String, String> {
+        let path =
+            std::fs::canonicalize(path).map_err(|e| format!("Cannot watch {}: {}", path, e))?;
+        if !path.is_file() {
+            return Err(format!("Not a file: {}", path.display()));
+        }
+        let dir = path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| format!("No parent directory: {}", path.display()))?;
+
+        let mut state = self.state.lock();
+        if state.files.contains_key(&path) {
+            trace!("[FileWatcher] Already watching {:?}", path);
+            return Ok(path.to_string_lossy().to_string());
+        }
+        if state.files.len() >= self.max_watchers {
+            return Err(format!(
+                "Too many watched files (max {})",
+                self.max_watchers
+            ));
+        }
+
+        if state.watcher.is_none() {
+            state.watcher = Some(self.create_watcher(sink)?);
+        }
+        if !state.dirs.contains_key(&dir) {
+            if let Some(watcher) = state.watcher.as_mut() {
+                watcher
+                    .watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+            }
+        }
+        *state.dirs.entry(dir).or_insert(0) += 1;
+        state.files.insert(path.clone(), Instant::now());
+        self.watched.write().insert(path.clone());
+        debug!(
+            "[FileWatcher] Watching {:?} ({} files)",
+            path,
+            state.files.len()
+        );
+
+        if !state.pruner_running {
+            state.pruner_running = true;
+            self.spawn_pruner();
+        }
+
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    /// Stop watching a file. Returns whether it was being watched.
+    pub fn unwatch(&self, path: &str) -> bool {
+        let path = std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+        Self::unwatch_path(&self.state, &self.watched, &path)
+    }
+
+    /// Currently watched files
+    pub fn watched_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .watched
+            .read()
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn unwatch_path(
+        state: &Mutex<WatcherState>,
+        watched: &RwLock<HashSet<PathBuf>>,
+        path: &Path,
+    ) -> bool {
+        let mut state = state.lock();
+        if state.files.remove(path).is_none() {
+            return false;
+        }
+        watched.write().remove(path);
+
+        if let Some(dir) = path.parent() {
+            let remaining = state.dirs.get_mut(dir).map(|count| {
+                *count = count.saturating_sub(1);
+                *count
+            });
+            if remaining == Some(0) {
+                state.dirs.remove(dir);
+                if let Some(watcher) = state.watcher.as_mut() {
+                    if let Err(e) = watcher.unwatch(dir) {
+                        trace!("[FileWatcher] Failed to unwatch {:?}: {}", dir, e);
+                    }
+                }
+            }
+        }
+        if state.files.is_empty() {
+            // Drop the OS watcher so no threads linger while idle
+            state.watcher = None;
+        }
+        debug!("[FileWatcher] Unwatched {:?}", path);
+        true
+    }
+
+    fn create_watcher(&self, sink: FileChangeSink) -> Result<RecommendedWatcher, String> {
+        let reporter = self.change_reporter(sink);
+        notify::recommended_watcher(move |result| reporter.report(result))
+            .map_err(|e| format!("Failed to create file watcher: {}", e))
+    }
+
+    fn change_reporter(&self, sink: FileChangeSink) -> ChangeReporter {
+        ChangeReporter {
+            watched: self.watched.clone(),
+            last_reported: Mutex::new(HashMap::new()),
+            sink,
+        }
+    }
+
+    /// Periodically drop watches for files no longer shown in any window
+    fn spawn_pruner(&self) {
+        let state = self.state.clone();
+        let watched = self.watched.clone();
+
+        std::thread::spawn(move || {
+            let window_manager = WindowManager::new();
+            loop {
+                std::thread::sleep(PRUNE_INTERVAL);
+
+                let candidates: Vec<PathBuf> = {
+                    let mut state = state.lock();
+                    if state.files.is_empty() {
+                        state.pruner_running = false;
+                        trace!("[FileWatcher] No watched files, stopping pruner");
+                        return;
+                    }
+                    state
+                        .files
+                        .iter()
+                        .filter(|(_, since)| since.elapsed() >= PRUNE_INTERVAL)
+                        .map(|(path, _)| path.clone())
+                        .collect()
+                };
+                if candidates.is_empty() {
+                    continue;
+                }
+
+                // Keep watching if windows can't be enumerated on this platform
+                let windows = match window_manager.get_all_windows() {
+                    Ok(windows) => windows,
+                    Err(e) => {
+                        trace!("[FileWatcher] Skipping prune: {}", e);
+                        continue;
+                    }
+                };
+                let contexts: Vec<FileContext> = windows
+                    .iter()
+                    .filter_map(|w| FileContext::from_window_info(w).ok())
+                    .collect();
+
+                for path in stale_files(&candidates, &contexts) {
+                    debug!(
+                        "[FileWatcher] {:?} is no longer open in any window, unwatching",
+                        path
+                    );
+                    Self::unwatch_path(&state, &watched, &path);
+                }
+            }
+        });
+    }
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turns notify events into debounced change reports for watched files
+struct ChangeReporter {
+    watched: Arc<RwLock<HashSet<PathBuf>>>,
+    last_reported: Mutex<HashMap<(PathBuf, FileChangeKind), Instant>>,
+    sink: FileChangeSink,
+}
+
+impl ChangeReporter {
+    fn report(&self, result: notify::Result<Event>) {
+        let event = match result {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("[FileWatcher] Watch error: {}", e);
+                return;
+            }
+        };
+        let watched = self.watched.read();
+        for path in event.paths.iter().filter(|p| watched.contains(*p)) {
+            let Some(kind) = classify_event(&event.kind, path.exists()) else {
+                continue;
+            };
+
+            let now = Instant::now();
+            let key = (path.clone(), kind);
+            let mut last_reported = self.last_reported.lock();
+            if last_reported
+                .get(&key)
+                .is_some_and(|at| now.duration_since(*at) < DEBOUNCE_INTERVAL)
+            {
+                continue;
+            }
+            last_reported.insert(key, now);
+            drop(last_reported);
+
+            trace!("[FileWatcher] {:?} {:?}", kind, path);
+            (self.sink)(FileChangedEvent {
+                path: path.to_string_lossy().to_string(),
+                kind,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+            });
+        }
+    }
+}
+
+/// Map a notify event to the change reported for a watched file
+fn classify_event(kind: &EventKind, exists: bool) -> Option<FileChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(FileChangeKind::Created),
+        EventKind::Remove(_) => Some(FileChangeKind::Removed),
+        // Atomic saves rename a temp file over the original
+        EventKind::Modify(ModifyKind::Name(_)) => Some(if exists {
+            FileChangeKind::Modified
+        } else {
+            FileChangeKind::Removed
+        }),
+        EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) => Some(FileChangeKind::Modified),
+        _ => None,
+    }
+}
+
+/// Watched files that no window context refers to.
+///
+/// Window titles often carry only the file name, so a name match is enough to
+/// keep a file watched.
+fn stale_files(watched: &[PathBuf], contexts: &[FileContext]) -> Vec<PathBuf> {
+    let open_paths: HashSet<PathBuf> = contexts
+        .iter()
+        .filter_map(|c| c.path.as_deref())
+        .map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| PathBuf::from(p)))
+        .collect();
+    let open_names: HashSet<&str> = contexts.iter().filter_map(|c| c.name.as_deref()).collect();
+
+    watched
+        .iter()
+        .filter(|path| {
+            let name_open = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| open_names.contains(n));
+            !open_paths.contains(*path) && !name_open
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::file_context::FileType;
+    use notify::event::{CreateKind, DataChange, MetadataKind, RemoveKind, RenameMode};
+
+    fn noop_sink() -> FileChangeSink {
+        Arc::new(|_| {})
+    }
+
+    fn context(path: Option<&str>, name: Option<&str>) -> FileContext {
+        FileContext {
+            path: path.map(String::from),
+            name: name.map(String::from),
+            extension: None,
+            language: None,
+            is_modified: false,
+            project_root: None,
+            git_branch: None,
+            file_type: FileType::Unknown,
+        }
+    }
+
+    #[test]
+    fn test_classify_event() {
+        assert_eq!(
+            classify_event(&EventKind::Create(CreateKind::File), true),
+            Some(FileChangeKind::Created)
+        );
+        assert_eq!(
+            classify_event(
+                &EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+                true
+            ),
+            Some(FileChangeKind::Modified)
+        );
+        assert_eq!(
+            classify_event(&EventKind::Remove(RemoveKind::File), false),
+            Some(FileChangeKind::Removed)
+        );
+        assert_eq!(
+            classify_event(&EventKind::Modify(ModifyKind::Name(RenameMode::To)), true),
+            Some(FileChangeKind::Modified)
+        );
+        assert_eq!(
+            classify_event(
+                &EventKind::Modify(ModifyKind::Name(RenameMode::From)),
+                false
+            ),
+            Some(FileChangeKind::Removed)
+        );
+        assert_eq!(
+            classify_event(
+                &EventKind::Modify(ModifyKind::Metadata(MetadataKind::AccessTime)),
+                true
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stale_files_matches_path_or_name() {
+        let watched = vec![
+            PathBuf::from("/project/src/main.rs"),
+            PathBuf::from("/project/src/lib.rs"),
+            PathBuf::from("/project/README.md"),
+        ];
+        let contexts = vec![
+            context(Some("/project/src/main.rs"), Some("main.rs")),
+            context(None, Some("lib.rs")),
+        ];
+
+        let stale = stale_files(&watched, &contexts);
+        assert_eq!(stale, vec![PathBuf::from("/project/README.md")]);
+    }
+
+    #[test]
+    fn test_watch_limit_and_unwatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "b").unwrap();
+
+        let watcher = FileWatcher::with_limit(1);
+        let watched = watcher.watch(a.to_str().unwrap(), noop_sink()).unwrap();
+        // Watching the same file again is not counted twice
+        watcher.watch(a.to_str().unwrap(), noop_sink()).unwrap();
+        assert_eq!(watcher.watched_files(), vec![watched.clone()]);
+
+        let err = watcher.watch(b.to_str().unwrap(), noop_sink()).unwrap_err();
+        assert!(err.contains("Too many watched files"));
+
+        assert!(watcher.unwatch(&watched));
+        assert!(!watcher.unwatch(&watched));
+        assert!(watcher.watched_files().is_empty());
+        watcher.watch(b.to_str().unwrap(), noop_sink()).unwrap();
+    }
+
+    #[test]
+    fn test_watch_rejects_missing_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = FileWatcher::new();
+
+        assert!(watcher
+            .watch(
+                dir.path().join("missing.txt").to_str().unwrap(),
+                noop_sink()
+            )
+            .is_err());
+        assert!(watcher
+            .watch(dir.path().to_str().unwrap(), noop_sink())
+            .unwrap_err()
+            .contains("Not a file"));
+    }
+
+    #[test]
+    fn test_watch_reports_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("watched.txt");
+        std::fs::write(&file, "before").unwrap();
+
+        let watcher = FileWatcher::new();
+        let watched = PathBuf::from(watcher.watch(file.to_str().unwrap(), noop_sink()).unwrap());
+
+        // Feed events straight to the reporter instead of waiting on the OS watcher
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink_reported = reported.clone();
+        let reporter = watcher.change_reporter(Arc::new(move |event| {
+            sink_reported.lock().push(event);
+        }));
+        let modified = |path: PathBuf| {
+            Ok(Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content))).add_path(path))
+        };
+        reporter.report(modified(dir.path().join("unwatched.txt")));
+        reporter.report(modified(watched.clone()));
+
+        let reported = reported.lock();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(PathBuf::from(&reported[0].path), watched);
+        assert_eq!(reported[0].kind, FileChangeKind::Modified);
+    }
+}
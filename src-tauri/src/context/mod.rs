@@ -4,24 +4,32 @@
 //! active window, application type, file context, and browser context,
 //! editor context, and screen content analysis.
 
+mod active_document;
 mod app_context;
 mod browser_context;
 mod editor_context;
 mod file_context;
+mod file_watcher;
 mod screen_content;
 mod window_info;
+mod window_layout;
 
+pub use active_document::{ActiveDocument, DocumentSelection};
 pub use app_context::{AppContext, AppType};
 pub use browser_context::BrowserContext;
 pub use editor_context::EditorContext;
 pub use file_context::FileContext;
+pub use file_watcher::{FileChangeSink, FileWatcher, FILE_CHANGED_EVENT};
 pub use screen_content::{ScreenContent, ScreenContentAnalyzer, UiElement};
 pub use window_info::{WindowInfo, WindowManager};
+pub use window_layout::{LayoutRestoreReport, WindowBounds, WindowLayout};
 
+use crate::accessibility::DegradedStatus;
 use log::{debug, trace};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use window_layout::{RepositionedWindow, SkippedWindow};
 
 /// Complete context information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,12 +48,26 @@ pub struct FullContext {
     pub timestamp: i64,
 }
 
+/// Context manager status for frontend queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextStatus {
+    /// Whether window and UI queries run without the accessibility permission they need
+    pub degraded: bool,
+    /// Why context queries are degraded
+    pub degraded_reason: Option<String>,
+    /// Current cache duration in milliseconds
+    pub cache_duration_ms: u64,
+}
+
 /// Context manager for gathering and caching context information
 pub struct ContextManager {
     window_manager: WindowManager,
     screen_analyzer: ScreenContentAnalyzer,
+    file_watcher: FileWatcher,
     last_context: Arc<RwLock<Option<FullContext>>>,
     cache_duration_ms: Arc<RwLock<u64>>,
+    /// Permission state detected at startup
+    degraded: DegradedStatus,
 }
 
 impl ContextManager {
@@ -54,8 +76,24 @@ impl ContextManager {
         Self {
             window_manager: WindowManager::new(),
             screen_analyzer: ScreenContentAnalyzer::new(),
+            file_watcher: FileWatcher::new(),
             last_context: Arc::new(RwLock::new(None)),
             cache_duration_ms: Arc::new(RwLock::new(500)), // Cache for 500ms
+            degraded: DegradedStatus::detect(),
+        }
+    }
+
+    /// Permission state detected at startup
+    pub fn degraded_status(&self) -> &DegradedStatus {
+        &self.degraded
+    }
+
+    /// Get manager status, including whether queries are degraded
+    pub fn get_status(&self) -> ContextStatus {
+        ContextStatus {
+            degraded: self.degraded.degraded,
+            degraded_reason: self.degraded.reason.clone(),
+            cache_duration_ms: self.get_cache_duration(),
         }
     }
 
@@ -64,6 +102,11 @@ impl ContextManager {
         &self.screen_analyzer
     }
 
+    /// Get the watcher for files surfaced by the context APIs
+    pub fn get_file_watcher(&self) -> &FileWatcher {
+        &self.file_watcher
+    }
+
     /// Get current cache duration in milliseconds
     pub fn get_cache_duration(&self) -> u64 {
         *self.cache_duration_ms.read()
@@ -254,6 +297,17 @@ impl ContextManager {
         result
     }
 
+    /// Get the focused document's path, language, contents, selection, and cursor
+    /// from a single active-window query
+    pub fn get_active_document(
+        &self,
+        selection: Option<DocumentSelection>,
+    ) -> Result<ActiveDocument, String> {
+        trace!("get_active_document called");
+        let window = self.window_manager.get_active_window().ok();
+        Ok(ActiveDocument::from_window_info(window.as_ref(), selection))
+    }
+
     /// Set cache duration
     pub fn set_cache_duration(&self, ms: u64) {
         let old_duration = *self.cache_duration_ms.read();
@@ -304,6 +358,58 @@ impl ContextManager {
         }
         result
     }
+
+    /// Capture the bounds of all visible windows
+    pub fn snapshot_layout(&self) -> Result<WindowLayout, String> {
+        let windows = self.window_manager.get_all_windows()?;
+        Ok(WindowLayout::capture(&windows))
+    }
+
+    /// Move windows back to the bounds recorded in a snapshot.
+    ///
+    /// Windows that are no longer open, or can't be moved, are reported as skipped.
+    pub fn restore_layout(&self, layout: &WindowLayout) -> Result<LayoutRestoreReport, String> {
+        let current = self.window_manager.get_all_windows()?;
+        let mut report = LayoutRestoreReport::default();
+
+        for (entry, window) in layout.match_windows(&current) {
+            let Some(window) = window else {
+                report.skipped.push(SkippedWindow {
+                    title: entry.title.clone(),
+                    process_name: entry.process_name.clone(),
+                    reason: "Window no longer exists".to_string(),
+                });
+                continue;
+            };
+
+            match self
+                .window_manager
+                .set_window_bounds(window.handle, &entry.bounds)
+            {
+                Ok(()) => report.repositioned.push(RepositionedWindow {
+                    handle: window.handle,
+                    title: window.title.clone(),
+                    process_name: window.process_name.clone(),
+                    bounds: entry.bounds.clone(),
+                }),
+                Err(e) => {
+                    trace!("Failed to restore '{}': {}", window.title, e);
+                    report.skipped.push(SkippedWindow {
+                        title: entry.title.clone(),
+                        process_name: entry.process_name.clone(),
+                        reason: e,
+                    });
+                }
+            }
+        }
+
+        debug!(
+            "Restored layout: {} repositioned, {} skipped",
+            report.repositioned.len(),
+            report.skipped.len()
+        );
+        Ok(report)
+    }
 }
 
 impl Default for ContextManager {
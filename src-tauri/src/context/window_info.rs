@@ -2,6 +2,7 @@
 //!
 //! Provides functionality to get information about windows on the system.
 
+use super::WindowBounds;
 use log::{debug, trace, warn};
 use serde::{Deserialize, Serialize};
 
@@ -279,6 +280,50 @@ impl WindowManager {
         Ok(matched)
     }
 
+    /// Move and resize a window, then apply its minimized/maximized state
+    #[cfg(target_os = "windows")]
+    pub fn set_window_bounds(&self, handle: u64, bounds: &WindowBounds) -> Result<(), String> {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            IsIconic, IsWindow, IsZoomed, SetWindowPos, ShowWindow, SWP_NOACTIVATE, SWP_NOZORDER,
+            SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+        };
+
+        trace!("Setting bounds for window {}: {:?}", handle, bounds);
+        unsafe {
+            let hwnd = HWND(handle as *mut _);
+            if !IsWindow(hwnd).as_bool() {
+                return Err(format!("Window {} no longer exists", handle));
+            }
+
+            // Maximized/minimized windows ignore SetWindowPos until restored
+            if IsIconic(hwnd).as_bool() || IsZoomed(hwnd).as_bool() {
+                let _ = ShowWindow(hwnd, SW_RESTORE);
+            }
+
+            // The recorded rect of a minimized window is the off-screen icon position
+            if !bounds.is_minimized {
+                SetWindowPos(
+                    hwnd,
+                    HWND::default(),
+                    bounds.x,
+                    bounds.y,
+                    bounds.width as i32,
+                    bounds.height as i32,
+                    SWP_NOZORDER | SWP_NOACTIVATE,
+                )
+                .map_err(|e| format!("Failed to move window {}: {}", handle, e))?;
+            }
+
+            if bounds.is_maximized {
+                let _ = ShowWindow(hwnd, SW_MAXIMIZE);
+            } else if bounds.is_minimized {
+                let _ = ShowWindow(hwnd, SW_MINIMIZE);
+            }
+        }
+        Ok(())
+    }
+
     // Non-Windows implementations
     #[cfg(not(target_os = "windows"))]
     pub fn get_active_window(&self) -> Result<WindowInfo, String> {
@@ -303,6 +348,12 @@ impl WindowManager {
         debug!("find_windows_by_process called on non-Windows platform");
         Ok(Vec::new())
     }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_window_bounds(&self, _handle: u64, _bounds: &WindowBounds) -> Result<(), String> {
+        debug!("set_window_bounds called on non-Windows platform");
+        Err("Window management not available on this platform".to_string())
+    }
 }
 
 impl Default for WindowManager {
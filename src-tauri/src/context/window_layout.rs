@@ -0,0 +1,214 @@
+//! Window layout snapshots
+//!
+//! Captures the bounds of all visible windows so a workspace arrangement can be
+//! restored later.
+
+use super::WindowInfo;
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+
+/// Position, size, and show state of a window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub is_minimized: bool,
+    pub is_maximized: bool,
+}
+
+/// A window as recorded in a layout snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutWindow {
+    /// Window handle at capture time (may be stale or reused later)
+    pub handle: u64,
+    pub title: String,
+    pub process_name: String,
+    pub bounds: WindowBounds,
+}
+
+/// Arrangement of all visible windows at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowLayout {
+    pub windows: Vec<LayoutWindow>,
+    pub captured_at: i64,
+}
+
+/// A window that was moved back to its recorded bounds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositionedWindow {
+    /// Current handle of the window
+    pub handle: u64,
+    pub title: String,
+    pub process_name: String,
+    pub bounds: WindowBounds,
+}
+
+/// A snapshot entry that could not be restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedWindow {
+    pub title: String,
+    pub process_name: String,
+    pub reason: String,
+}
+
+/// Outcome of restoring a layout
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayoutRestoreReport {
+    pub repositioned: Vec<RepositionedWindow>,
+    pub skipped: Vec<SkippedWindow>,
+}
+
+impl WindowLayout {
+    /// Record the bounds of the given windows
+    pub fn capture(windows: &[WindowInfo]) -> Self {
+        let windows: Vec<LayoutWindow> = windows
+            .iter()
+            .map(|w| LayoutWindow {
+                handle: w.handle,
+                title: w.title.clone(),
+                process_name: w.process_name.clone(),
+                bounds: WindowBounds {
+                    x: w.x,
+                    y: w.y,
+                    width: w.width,
+                    height: w.height,
+                    is_minimized: w.is_minimized,
+                    is_maximized: w.is_maximized,
+                },
+            })
+            .collect();
+        debug!("Captured layout of {} windows", windows.len());
+        Self {
+            windows,
+            captured_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Pair each recorded window with a currently open window.
+    ///
+    /// A window is matched by handle when the handle still belongs to the same
+    /// process, otherwise by process name and exact title. Each open window is
+    /// used at most once; unmatched entries get `None`.
+    pub fn match_windows<'a>(
+        &'a self,
+        current: &'a [WindowInfo],
+    ) -> Vec<(&'a LayoutWindow, Option<&'a WindowInfo>)> {
+        let mut used = vec![false; current.len()];
+        let mut matched: Vec<Option<usize>> = vec![None; self.windows.len()];
+
+        // Handle matches first so a title match can't steal a window from its own entry
+        for (entry_idx, entry) in self.windows.iter().enumerate() {
+            if let Some(idx) = current.iter().enumerate().position(|(idx, w)| {
+                !used[idx] && w.handle == entry.handle && w.process_name == entry.process_name
+            }) {
+                used[idx] = true;
+                matched[entry_idx] = Some(idx);
+            }
+        }
+        for (entry_idx, entry) in self.windows.iter().enumerate() {
+            if matched[entry_idx].is_some() {
+                continue;
+            }
+            if let Some(idx) = current.iter().enumerate().position(|(idx, w)| {
+                !used[idx] && w.process_name == entry.process_name && w.title == entry.title
+            }) {
+                used[idx] = true;
+                matched[entry_idx] = Some(idx);
+            }
+        }
+
+        self.windows
+            .iter()
+            .zip(matched)
+            .map(|(entry, idx)| {
+                if idx.is_none() {
+                    trace!(
+                        "No open window for '{}' ({})",
+                        entry.title,
+                        entry.process_name
+                    );
+                }
+                (entry, idx.map(|i| &current[i]))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(handle: u64, title: &str, process: &str, x: i32) -> WindowInfo {
+        WindowInfo {
+            handle,
+            title: title.to_string(),
+            class_name: String::new(),
+            process_id: 1,
+            process_name: process.to_string(),
+            exe_path: None,
+            x,
+            y: 0,
+            width: 800,
+            height: 600,
+            is_minimized: false,
+            is_maximized: false,
+            is_focused: false,
+            is_visible: true,
+        }
+    }
+
+    #[test]
+    fn test_capture_records_bounds() {
+        let mut w = window(1, "Editor", "code.exe", 100);
+        w.is_maximized = true;
+        let layout = WindowLayout::capture(&[w]);
+
+        assert_eq!(layout.windows.len(), 1);
+        assert_eq!(layout.windows[0].bounds.x, 100);
+        assert!(layout.windows[0].bounds.is_maximized);
+    }
+
+    #[test]
+    fn test_match_by_handle_then_title() {
+        let layout = WindowLayout::capture(&[
+            window(1, "Editor", "code.exe", 0),
+            window(2, "Docs", "chrome.exe", 800),
+            window(3, "Closed", "notepad.exe", 0),
+        ]);
+        let current = vec![
+            // Same handle, title changed
+            window(1, "Editor - main.rs", "code.exe", 50),
+            // Reopened with a new handle
+            window(20, "Docs", "chrome.exe", 10),
+        ];
+
+        let matched = layout.match_windows(&current);
+        assert_eq!(matched[0].1.map(|w| w.handle), Some(1));
+        assert_eq!(matched[1].1.map(|w| w.handle), Some(20));
+        assert!(matched[2].1.is_none());
+    }
+
+    #[test]
+    fn test_match_ignores_reused_handle_from_other_process() {
+        let layout = WindowLayout::capture(&[window(1, "Editor", "code.exe", 0)]);
+        let current = vec![window(1, "Terminal", "wt.exe", 0)];
+
+        let matched = layout.match_windows(&current);
+        assert!(matched[0].1.is_none());
+    }
+
+    #[test]
+    fn test_match_uses_each_window_once() {
+        let layout = WindowLayout::capture(&[
+            window(1, "Untitled", "notepad.exe", 0),
+            window(2, "Untitled", "notepad.exe", 400),
+        ]);
+        let current = vec![window(9, "Untitled", "notepad.exe", 0)];
+
+        let matched = layout.match_windows(&current);
+        assert_eq!(matched[0].1.map(|w| w.handle), Some(9));
+        assert!(matched[1].1.is_none());
+    }
+}
@@ -35,7 +35,13 @@ impl ConvexHttpClient {
         format!("Convex {}", self.deploy_key)
     }
 
+    /// Fail in offline mode unless the deployment is local
+    fn ensure_allowed(&self) -> Result<(), ConvexError> {
+        crate::http::ensure_url_allowed(&self.deployment_url).map_err(ConvexError::Connection)
+    }
+
     pub async fn health_check(&self) -> Result<bool, ConvexError> {
+        self.ensure_allowed()?;
         let response = self
             .client
             .get(self.url("/health"))
@@ -52,6 +58,7 @@ impl ConvexHttpClient {
         function_path: &str,
         args: JsonValue,
     ) -> Result<JsonValue, ConvexError> {
+        self.ensure_allowed()?;
         let body = serde_json::json!({
             "path": function_path,
             "args": args,
@@ -85,6 +92,7 @@ impl ConvexHttpClient {
         function_path: &str,
         args: JsonValue,
     ) -> Result<JsonValue, ConvexError> {
+        self.ensure_allowed()?;
         let body = serde_json::json!({
             "path": function_path,
             "args": args,
@@ -118,6 +126,7 @@ impl ConvexHttpClient {
         function_path: &str,
         args: JsonValue,
     ) -> Result<JsonValue, ConvexError> {
+        self.ensure_allowed()?;
         let body = serde_json::json!({
             "path": function_path,
             "args": args,
@@ -148,6 +157,7 @@ impl ConvexHttpClient {
     /// Call a Convex HTTP route (custom HTTP endpoints defined in convex/http.ts)
     #[allow(dead_code)]
     pub async fn http_get(&self, path: &str) -> Result<JsonValue, ConvexError> {
+        self.ensure_allowed()?;
         let response = self
             .client
             .get(self.url(path))
@@ -170,6 +180,7 @@ impl ConvexHttpClient {
 
     #[allow(dead_code)]
     pub async fn http_post(&self, path: &str, body: JsonValue) -> Result<JsonValue, ConvexError> {
+        self.ensure_allowed()?;
         let response = self
             .client
             .post(self.url(path))
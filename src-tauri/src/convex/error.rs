@@ -6,7 +6,6 @@ pub enum ConvexError {
     Http(String),
 
     #[error("Connection error: {0}")]
-    #[allow(dead_code)]
     Connection(String),
 
     #[error("Configuration error: {0}")]
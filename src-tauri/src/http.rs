@@ -11,9 +11,19 @@
 //!
 //! For localhost/127.0.0.1 targets, use the static `HTTP_CLIENT*` instances
 //! directly since local requests should bypass the proxy.
+//!
+//! ## Offline Mode
+//!
+//! `set_offline_mode` disables all non-local network access. Network-dependent
+//! commands call `ensure_online` (or `ensure_url_allowed` for user-supplied
+//! endpoints) and fail fast with `OFFLINE_MODE_ERROR` instead of timing out.
+//! The flag is persisted in `NETWORK_SETTINGS_FILE` and restored at startup.
 
 use once_cell::sync::Lazy;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::RwLock;
 use std::time::Duration;
 
@@ -162,6 +172,83 @@ pub fn should_bypass_proxy(url: &str) -> bool {
     false
 }
 
+// =============================================================================
+// Offline Mode
+// =============================================================================
+
+/// Error returned by network-dependent operations while offline mode is on
+pub const OFFLINE_MODE_ERROR: &str = "Offline mode enabled: network access is disabled";
+
+/// File in the app data directory holding the persisted network settings
+pub const NETWORK_SETTINGS_FILE: &str = "network_settings.json";
+
+/// Global offline mode flag, set from the frontend via Tauri command.
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Network settings persisted across restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkSettings {
+    pub offline_mode: bool,
+}
+
+impl NetworkSettings {
+    /// Load settings from `path`, falling back to defaults if missing or invalid
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Invalid network settings, using defaults: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings dir: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write network settings: {}", e))
+    }
+}
+
+/// Enable or disable offline mode.
+pub fn set_offline_mode(enabled: bool) {
+    let previous = OFFLINE_MODE.swap(enabled, Ordering::SeqCst);
+    if previous != enabled {
+        log::info!(
+            "Offline mode {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+}
+
+/// Check whether offline mode is enabled.
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::SeqCst)
+}
+
+/// Fail with `OFFLINE_MODE_ERROR` if offline mode is enabled.
+pub fn ensure_online() -> Result<(), String> {
+    check_network_access(is_offline_mode(), None)
+}
+
+/// Like `ensure_online`, but local targets (localhost) are always allowed.
+pub fn ensure_url_allowed(url: &str) -> Result<(), String> {
+    check_network_access(is_offline_mode(), Some(url))
+}
+
+fn check_network_access(offline: bool, url: Option<&str>) -> Result<(), String> {
+    if offline && !url.is_some_and(should_bypass_proxy) {
+        Err(OFFLINE_MODE_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Proxy-aware Client Factory Functions
 // =============================================================================
@@ -360,4 +447,33 @@ mod tests {
         assert!(client.is_ok());
         set_global_proxy(None);
     }
+
+    // Exercises the check directly so the process-wide flag is never toggled
+    // while other tests make requests
+    #[test]
+    fn test_offline_mode_blocks_remote_but_not_local() {
+        assert_eq!(
+            check_network_access(true, None).unwrap_err(),
+            OFFLINE_MODE_ERROR
+        );
+        assert!(check_network_access(true, Some("http://localhost:11434/api/generate")).is_ok());
+        assert!(check_network_access(true, Some("https://api.openai.com/v1/models")).is_err());
+
+        assert!(check_network_access(false, None).is_ok());
+        assert!(check_network_access(false, Some("https://api.openai.com/v1/models")).is_ok());
+    }
+
+    #[test]
+    fn test_network_settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(NETWORK_SETTINGS_FILE);
+        assert_eq!(NetworkSettings::load(&path), NetworkSettings::default());
+
+        let settings = NetworkSettings { offline_mode: true };
+        settings.save(&path).unwrap();
+        assert_eq!(NetworkSettings::load(&path), settings);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(!NetworkSettings::load(&path).offline_mode);
+    }
 }
@@ -2,13 +2,15 @@
 //!
 //! Handles requesting completions from various AI providers.
 
-use super::config::{CompletionModelConfig, CompletionProvider};
+use super::config::{CompletionConfig, CompletionModelConfig, CompletionProvider};
 use super::types::{
-    CompletionContext, CompletionFeedback, CompletionMode, CompletionResult, CompletionSuggestion,
-    CompletionType, FeedbackStats,
+    CompletionContext, CompletionFeedback, CompletionMode, CompletionResult,
+    CompletionRewriteRequest, CompletionSuggestion, CompletionType, FeedbackRating, FeedbackStats,
+    FocusedAppContext, FocusedAppKind, ModelPerformanceStats,
 };
+use futures::StreamExt;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Instant;
 use unicode_normalization::UnicodeNormalization;
@@ -25,6 +27,58 @@ pub struct CompletionService {
     cache_ttl_secs: u64,
     /// Statistics
     stats: Arc<RwLock<ServiceStats>>,
+    /// Per-model feedback tracking for automatic model selection
+    selection: Arc<RwLock<ModelSelectionState>>,
+}
+
+/// System prompt for rewrite suggestions
+const REWRITE_SYSTEM_PROMPT: &str = "You rewrite a fragment of text or code in place. \
+Reply with the rewritten fragment only: no explanation, no quotes, no code fences. \
+Preserve the language, indentation and line structure unless asked otherwise.";
+
+/// Maximum number of context characters sent on each side of a rewrite
+const REWRITE_CONTEXT_CHARS: usize = 1000;
+
+/// Maximum number of suggestion ids remembered for feedback attribution
+const MAX_TRACKED_SUGGESTIONS: usize = 500;
+
+/// Ollama endpoint used when none is configured
+const OLLAMA_DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+/// Feedback counters for one model within a surface/language bucket
+#[derive(Debug, Clone, Default)]
+struct ModelPerformance {
+    accepted: u64,
+    rejected: u64,
+}
+
+impl ModelPerformance {
+    fn samples(&self) -> u64 {
+        self.accepted + self.rejected
+    }
+
+    fn acceptance_rate(&self) -> f64 {
+        if self.samples() == 0 {
+            0.0
+        } else {
+            self.accepted as f64 / self.samples() as f64
+        }
+    }
+}
+
+/// State for feedback-driven model selection
+#[derive(Debug, Default)]
+struct ModelSelectionState {
+    /// Suggestion id -> (bucket, model key) that produced it
+    suggestion_origins: HashMap<String, (String, String)>,
+    /// Insertion order of `suggestion_origins` for eviction
+    origin_order: VecDeque<String>,
+    /// (bucket, model key) -> feedback counters
+    performance: HashMap<(String, String), ModelPerformance>,
+    /// Bucket -> model key currently selected
+    active: HashMap<String, String>,
+    /// Model key used for the most recent request
+    last_model: Option<String>,
 }
 
 /// Internal statistics for the completion service
@@ -66,6 +120,27 @@ enum CacheHitKind {
     Normalized,
 }
 
+/// A single prompt sent to a provider, shared by completions and text generation
+struct PromptRequest<'a> {
+    system_prompt: Option<&'a str>,
+    prompt: &'a str,
+    /// Stop sequences; empty for none
+    stop: &'a [&'a str],
+    stream: bool,
+}
+
+impl<'a> PromptRequest<'a> {
+    /// Free-form generation request without stop sequences
+    fn generation(system_prompt: &'a str, prompt: &'a str, stream: bool) -> Self {
+        Self {
+            system_prompt: Some(system_prompt),
+            prompt,
+            stop: &[],
+            stream,
+        }
+    }
+}
+
 impl CompletionService {
     /// Create a new completion service
     pub fn new() -> Self {
@@ -77,6 +152,7 @@ impl CompletionService {
             max_cache_size: 100,
             cache_ttl_secs: 60,
             stats: Arc::new(RwLock::new(ServiceStats::default())),
+            selection: Arc::new(RwLock::new(ModelSelectionState::default())),
         }
     }
 
@@ -123,6 +199,11 @@ impl CompletionService {
             return Ok(prefix_cached);
         }
 
+        if let Err(e) = Self::ensure_provider_allowed(config) {
+            self.stats.write().failed_completions += 1;
+            return Err(e);
+        }
+
         let mut last_error = String::new();
         let mut retry_count = 0;
 
@@ -135,18 +216,17 @@ impl CompletionService {
             }
 
             // Request completion based on provider
-            let result = match config.provider {
-                CompletionProvider::Ollama => self.get_ollama_completion(context, config).await,
-                CompletionProvider::OpenAI => self.get_openai_completion(context, config).await,
-                CompletionProvider::Groq => self.get_groq_completion(context, config).await,
-                CompletionProvider::Auto => self.get_auto_completion(context, config).await,
-                CompletionProvider::Custom => self.get_custom_completion(context, config).await,
+            let result = if config.provider == CompletionProvider::Auto {
+                self.get_auto_completion(context, config).await
+            } else {
+                self.get_provider_completion(context, config).await
             };
 
             match result {
                 Ok(mut result) => {
                     let latency = start.elapsed().as_millis() as u64;
                     result.latency_ms = latency;
+                    self.record_suggestion_origins(context, config, &result);
 
                     // Update stats
                     {
@@ -188,6 +268,30 @@ impl CompletionService {
         ))
     }
 
+    /// In offline mode only local endpoints (Ollama or a localhost custom server) may be used
+    fn ensure_provider_allowed(config: &CompletionModelConfig) -> Result<(), String> {
+        if !crate::http::is_offline_mode() {
+            return Ok(());
+        }
+
+        match config.provider {
+            CompletionProvider::Ollama => crate::http::ensure_url_allowed(
+                config
+                    .endpoint
+                    .as_deref()
+                    .unwrap_or(OLLAMA_DEFAULT_ENDPOINT),
+            ),
+            CompletionProvider::Custom => {
+                crate::http::ensure_url_allowed(config.endpoint.as_deref().unwrap_or_default())
+            }
+            // Auto only falls back to cloud providers when online
+            CompletionProvider::Auto => Ok(()),
+            CompletionProvider::OpenAI | CompletionProvider::Groq => {
+                Err(crate::http::OFFLINE_MODE_ERROR.to_string())
+            }
+        }
+    }
+
     /// Check if an error is retryable
     fn is_retryable_error(error: &str) -> bool {
         let retryable_patterns = [
@@ -207,53 +311,42 @@ impl CompletionService {
         retryable_patterns.iter().any(|p| error_lower.contains(p))
     }
 
-    /// Get completion from Ollama
-    async fn get_ollama_completion(
+    /// Get completion from a provider other than [`CompletionProvider::Auto`]
+    async fn get_provider_completion(
         &self,
         context: &CompletionContext,
         config: &CompletionModelConfig,
     ) -> Result<CompletionResult, String> {
-        let endpoint = config
-            .endpoint
-            .clone()
-            .unwrap_or_else(|| "http://localhost:11434".to_string());
-
-        let prompt = self.build_completion_prompt(context);
-
-        let request_body = serde_json::json!({
-            "model": config.model_id,
-            "prompt": prompt,
-            "stream": false,
-            "options": {
-                "temperature": config.temperature,
-                "num_predict": config.max_tokens,
-                "stop": ["\n\n", "```", "// ", "# "]
+        let (system_prompt, stop, confidence): (_, &[&str], _) = match config.provider {
+            CompletionProvider::Ollama => (None, &["\n\n", "```", "// ", "# "], 0.8),
+            CompletionProvider::OpenAI => (
+                Some("You are a code completion assistant. Complete the following text naturally. Only output the completion, nothing else."),
+                &["\n\n"],
+                0.85,
+            ),
+            CompletionProvider::Groq => (
+                Some("You are a fast code completion assistant. Complete the following text naturally. Only output the completion, nothing else."),
+                &["\n\n"],
+                0.8,
+            ),
+            CompletionProvider::Custom => (None, &[], 0.7),
+            CompletionProvider::Auto => {
+                return Err("Auto must be resolved to a concrete provider".to_string())
             }
-        });
-
-        let response = self
-            .client
-            .post(format!("{}/api/generate", endpoint))
-            .json(&request_body)
-            .timeout(std::time::Duration::from_secs(config.timeout_secs.into()))
-            .send()
-            .await
-            .map_err(|e| format!("Ollama request failed: {}", e))?;
-
-        if !response.status().is_success() {
-            return Err(format!("Ollama returned status: {}", response.status()));
-        }
-
-        let response_json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+        };
 
-        let completion_text = response_json["response"]
-            .as_str()
-            .unwrap_or("")
-            .trim()
-            .to_string();
+        let prompt = self.build_completion_prompt(context);
+        let completion_text = self
+            .send_prompt(
+                config,
+                &PromptRequest {
+                    system_prompt,
+                    prompt: &prompt,
+                    stop,
+                    stream: false,
+                },
+            )
+            .await?;
 
         if completion_text.is_empty() {
             return Ok(CompletionResult::default());
@@ -261,7 +354,7 @@ impl CompletionService {
 
         let suggestion = CompletionSuggestion::new(
             completion_text.clone(),
-            0.8,
+            confidence,
             if completion_text.contains('\n') {
                 CompletionType::Block
             } else {
@@ -277,286 +370,385 @@ impl CompletionService {
         })
     }
 
-    /// Get completion from OpenAI
-    async fn get_openai_completion(
+    /// Get completion using auto-router (try Ollama first, then fallback)
+    async fn get_auto_completion(
         &self,
         context: &CompletionContext,
         config: &CompletionModelConfig,
     ) -> Result<CompletionResult, String> {
-        let endpoint = config
-            .endpoint
-            .clone()
-            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        // Try Ollama first (local, fast)
+        let local = Self::auto_local_model(config, 3);
+        match self.get_provider_completion(context, &local).await {
+            Ok(result) if !result.suggestions.is_empty() => return Ok(result),
+            _ => {
+                log::trace!("Ollama not available, trying Groq");
+            }
+        }
 
-        let api_key = config.api_key.clone().ok_or("OpenAI API key is required")?;
+        match Self::auto_fallback_model(config) {
+            Ok(fallback) => self.get_provider_completion(context, &fallback).await,
+            Err(_) => Ok(CompletionResult::default()),
+        }
+    }
 
-        let prompt = self.build_completion_prompt(context);
+    /// Local model tried first by [`CompletionProvider::Auto`]
+    fn auto_local_model(
+        config: &CompletionModelConfig,
+        timeout_secs: u32,
+    ) -> CompletionModelConfig {
+        CompletionModelConfig {
+            provider: CompletionProvider::Ollama,
+            model_id: "qwen2.5-coder:0.5b".to_string(),
+            endpoint: Some(OLLAMA_DEFAULT_ENDPOINT.to_string()),
+            api_key: None,
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+            timeout_secs,
+        }
+    }
 
-        let request_body = serde_json::json!({
-            "model": config.model_id,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": "You are a code completion assistant. Complete the following text naturally. Only output the completion, nothing else."
-                },
-                {
-                    "role": "user",
-                    "content": prompt
+    /// Cloud model [`CompletionProvider::Auto`] falls back to when the local one fails
+    ///
+    /// Requires an API key and is never used in offline mode.
+    fn auto_fallback_model(
+        config: &CompletionModelConfig,
+    ) -> Result<CompletionModelConfig, String> {
+        if crate::http::is_offline_mode() {
+            return Err(format!(
+                "No local model available. {}",
+                crate::http::OFFLINE_MODE_ERROR
+            ));
+        }
+        if config.api_key.is_none() {
+            return Err("No local model available and no API key configured".to_string());
+        }
+        Ok(CompletionModelConfig {
+            provider: CompletionProvider::Groq,
+            model_id: "llama-3.1-8b-instant".to_string(),
+            endpoint: None,
+            api_key: config.api_key.clone(),
+            max_tokens: config.max_tokens,
+            temperature: config.temperature,
+            timeout_secs: config.timeout_secs,
+        })
+    }
+
+    /// Build the HTTP request for one prompt to a provider other than
+    /// [`CompletionProvider::Auto`]
+    fn build_prompt_request(
+        &self,
+        config: &CompletionModelConfig,
+        request: &PromptRequest<'_>,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        let builder = match config.provider {
+            CompletionProvider::Ollama => {
+                let endpoint = config
+                    .endpoint
+                    .as_deref()
+                    .unwrap_or(OLLAMA_DEFAULT_ENDPOINT);
+                let mut options = serde_json::json!({
+                    "temperature": config.temperature,
+                    "num_predict": config.max_tokens,
+                });
+                if !request.stop.is_empty() {
+                    options["stop"] = serde_json::json!(request.stop);
                 }
-            ],
-            "max_tokens": config.max_tokens,
-            "temperature": config.temperature,
-            "stop": ["\n\n"]
-        });
+                let mut body = serde_json::json!({
+                    "model": config.model_id,
+                    "prompt": request.prompt,
+                    "stream": request.stream,
+                    "options": options
+                });
+                if let Some(system_prompt) = request.system_prompt {
+                    body["system"] = serde_json::json!(system_prompt);
+                }
+                self.client
+                    .post(format!("{}/api/generate", endpoint))
+                    .json(&body)
+            }
+            CompletionProvider::OpenAI | CompletionProvider::Groq => {
+                let default_endpoint = if config.provider == CompletionProvider::OpenAI {
+                    "https://api.openai.com/v1"
+                } else {
+                    "https://api.groq.com/openai/v1"
+                };
+                let endpoint = config.endpoint.as_deref().unwrap_or(default_endpoint);
+                let api_key = config.api_key.as_deref().ok_or_else(|| {
+                    format!("{} API key is required", provider_name(&config.provider))
+                })?;
+                let mut messages = Vec::new();
+                if let Some(system_prompt) = request.system_prompt {
+                    messages
+                        .push(serde_json::json!({ "role": "system", "content": system_prompt }));
+                }
+                messages.push(serde_json::json!({ "role": "user", "content": request.prompt }));
+                let mut body = serde_json::json!({
+                    "model": config.model_id,
+                    "messages": messages,
+                    "max_tokens": config.max_tokens,
+                    "temperature": config.temperature
+                });
+                if !request.stop.is_empty() {
+                    body["stop"] = serde_json::json!(request.stop);
+                }
+                if request.stream {
+                    body["stream"] = serde_json::json!(true);
+                }
+                self.client
+                    .post(format!("{}/chat/completions", endpoint))
+                    .header("Authorization", format!("Bearer {}", api_key))
+                    .header("Content-Type", "application/json")
+                    .json(&body)
+            }
+            CompletionProvider::Custom => {
+                let endpoint = config
+                    .endpoint
+                    .as_deref()
+                    .ok_or("Custom endpoint is required")?;
+                // Custom endpoints take a bare prompt, so the system prompt is prepended
+                let prompt = match request.system_prompt {
+                    Some(system_prompt) => format!("{}\n\n{}", system_prompt, request.prompt),
+                    None => request.prompt.to_string(),
+                };
+                let mut body = serde_json::json!({
+                    "prompt": prompt,
+                    "max_tokens": config.max_tokens,
+                    "temperature": config.temperature
+                });
+                if !config.model_id.is_empty() {
+                    body["model"] = serde_json::json!(config.model_id);
+                }
+                if request.stream {
+                    body["stream"] = serde_json::json!(true);
+                }
+                let mut builder = self
+                    .client
+                    .post(endpoint)
+                    .header("Content-Type", "application/json")
+                    .json(&body);
+                if let Some(api_key) = &config.api_key {
+                    builder = builder.header("Authorization", format!("Bearer {}", api_key));
+                }
+                builder
+            }
+            CompletionProvider::Auto => {
+                return Err("Auto must be resolved to a concrete provider".to_string())
+            }
+        };
 
+        Ok(builder.timeout(std::time::Duration::from_secs(config.timeout_secs.into())))
+    }
+
+    /// Send one prompt and return the trimmed response text
+    async fn send_prompt(
+        &self,
+        config: &CompletionModelConfig,
+        request: &PromptRequest<'_>,
+    ) -> Result<String, String> {
+        let name = provider_name(&config.provider);
         let response = self
-            .client
-            .post(format!("{}/chat/completions", endpoint))
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .timeout(std::time::Duration::from_secs(config.timeout_secs.into()))
+            .build_prompt_request(config, request)?
             .send()
             .await
-            .map_err(|e| format!("OpenAI request failed: {}", e))?;
+            .map_err(|e| format!("{} request failed: {}", name, e))?;
 
         if !response.status().is_success() {
-            return Err(format!("OpenAI returned status: {}", response.status()));
+            return Err(format!("{} returned status: {}", name, response.status()));
         }
 
         let response_json: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+            .map_err(|e| format!("Failed to parse {} response: {}", name, e))?;
 
-        let completion_text = response_json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
+        Ok(response_text(&config.provider, &response_json)
             .trim()
-            .to_string();
-
-        if completion_text.is_empty() {
-            return Ok(CompletionResult::default());
-        }
-
-        let suggestion = CompletionSuggestion::new(
-            completion_text.clone(),
-            0.85,
-            if completion_text.contains('\n') {
-                CompletionType::Block
-            } else {
-                CompletionType::Line
-            },
-        );
-
-        Ok(CompletionResult {
-            suggestions: vec![suggestion],
-            latency_ms: 0,
-            model: config.model_id.clone(),
-            cached: false,
-        })
+            .to_string())
     }
 
-    /// Get completion from Groq
-    async fn get_groq_completion(
+    /// Send one prompt with streaming enabled, passing each text delta to `on_delta`
+    async fn stream_prompt(
         &self,
-        context: &CompletionContext,
         config: &CompletionModelConfig,
-    ) -> Result<CompletionResult, String> {
-        let endpoint = config
-            .endpoint
-            .clone()
-            .unwrap_or_else(|| "https://api.groq.com/openai/v1".to_string());
-
-        let api_key = config.api_key.clone().ok_or("Groq API key is required")?;
-
-        let prompt = self.build_completion_prompt(context);
-
-        let request_body = serde_json::json!({
-            "model": config.model_id,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": "You are a fast code completion assistant. Complete the following text naturally. Only output the completion, nothing else."
-                },
-                {
-                    "role": "user",
-                    "content": prompt
-                }
-            ],
-            "max_tokens": config.max_tokens,
-            "temperature": config.temperature,
-            "stop": ["\n\n"]
-        });
+        request: &PromptRequest<'_>,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<String, String> {
+        let name = provider_name(&config.provider);
+        let parse_line: fn(&str) -> Option<String> = match config.provider {
+            CompletionProvider::Ollama => parse_ollama_stream_line,
+            CompletionProvider::Custom => parse_custom_stream_line,
+            _ => parse_chat_stream_line,
+        };
 
         let response = self
-            .client
-            .post(format!("{}/chat/completions", endpoint))
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .timeout(std::time::Duration::from_secs(config.timeout_secs.into()))
+            .build_prompt_request(config, request)?
             .send()
             .await
-            .map_err(|e| format!("Groq request failed: {}", e))?;
+            .map_err(|e| format!("{} request failed: {}", name, e))?;
 
         if !response.status().is_success() {
-            return Err(format!("Groq returned status: {}", response.status()));
+            return Err(format!("{} returned status: {}", name, response.status()));
         }
 
-        let response_json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse Groq response: {}", e))?;
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        // Kept until the first delta in case the endpoint ignores `stream` and
+        // answers with a single JSON document
+        let mut body = Vec::new();
+        let mut output = String::new();
+        let mut emit_line = |line: &[u8], output: &mut String| {
+            if let Some(delta) = std::str::from_utf8(line).ok().and_then(parse_line) {
+                if !delta.is_empty() {
+                    on_delta(&delta);
+                    output.push_str(&delta);
+                }
+            }
+        };
 
-        let completion_text = response_json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
-            .trim()
-            .to_string();
+        while let Some(chunk) = stream.next().await {
+            let bytes = chunk.map_err(|e| format!("{} stream error: {}", name, e))?;
+            buffer.extend_from_slice(&bytes);
+            if output.is_empty() {
+                body.extend_from_slice(&bytes);
+            }
 
-        if completion_text.is_empty() {
-            return Ok(CompletionResult::default());
+            while let Some(newline_pos) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=newline_pos).collect();
+                emit_line(&line, &mut output);
+            }
+        }
+        emit_line(&buffer, &mut output);
+
+        if output.is_empty() {
+            if let Ok(response_json) = serde_json::from_slice::<serde_json::Value>(&body) {
+                let text = response_text(&config.provider, &response_json).trim();
+                if !text.is_empty() {
+                    on_delta(text);
+                    output.push_str(text);
+                }
+            }
         }
 
-        let suggestion = CompletionSuggestion::new(
-            completion_text.clone(),
-            0.8,
-            if completion_text.contains('\n') {
-                CompletionType::Block
-            } else {
-                CompletionType::Line
-            },
-        );
-
-        Ok(CompletionResult {
-            suggestions: vec![suggestion],
-            latency_ms: 0,
-            model: config.model_id.clone(),
-            cached: false,
-        })
+        Ok(output.trim().to_string())
     }
 
-    /// Get completion using auto-router (try Ollama first, then fallback)
-    async fn get_auto_completion(
+    /// Generate free-form text for a single prompt.
+    ///
+    /// Unlike [`Self::get_completion`], this bypasses the completion cache and does not
+    /// apply completion stop sequences, so it is suitable for longer AI actions.
+    pub async fn generate_text(
         &self,
-        context: &CompletionContext,
+        system_prompt: &str,
+        prompt: &str,
         config: &CompletionModelConfig,
-    ) -> Result<CompletionResult, String> {
-        // Try Ollama first (local, fast)
-        let ollama_config = CompletionModelConfig {
-            provider: CompletionProvider::Ollama,
-            model_id: "qwen2.5-coder:0.5b".to_string(),
-            endpoint: Some("http://localhost:11434".to_string()),
-            api_key: None,
-            max_tokens: config.max_tokens,
-            temperature: config.temperature,
-            timeout_secs: 3, // Short timeout for local
-        };
+    ) -> Result<String, String> {
+        Self::ensure_provider_allowed(config)?;
 
-        match self.get_ollama_completion(context, &ollama_config).await {
-            Ok(result) if !result.suggestions.is_empty() => return Ok(result),
-            _ => {
-                log::trace!("Ollama not available, trying Groq");
-            }
+        let request = PromptRequest::generation(system_prompt, prompt, false);
+        if config.provider != CompletionProvider::Auto {
+            return self.send_prompt(config, &request).await;
         }
 
-        // Fallback to Groq if API key is available
-        if config.api_key.is_some() {
-            let groq_config = CompletionModelConfig {
-                provider: CompletionProvider::Groq,
-                model_id: "llama-3.1-8b-instant".to_string(),
-                endpoint: None,
-                api_key: config.api_key.clone(),
-                max_tokens: config.max_tokens,
-                temperature: config.temperature,
-                timeout_secs: config.timeout_secs,
-            };
-
-            return self.get_groq_completion(context, &groq_config).await;
+        let local = Self::auto_local_model(config, config.timeout_secs);
+        match self.send_prompt(&local, &request).await {
+            Ok(text) if !text.is_empty() => return Ok(text),
+            _ => log::trace!("Ollama not available for text generation, trying Groq"),
         }
-
-        Ok(CompletionResult::default())
+        let fallback = Self::auto_fallback_model(config)?;
+        self.send_prompt(&fallback, &request).await
     }
 
-    /// Get completion from custom endpoint
-    async fn get_custom_completion(
+    /// Generate free-form text, passing output to `on_delta` as it arrives.
+    ///
+    /// Every provider is asked to stream. Custom endpoints may answer with Ollama-style
+    /// JSON lines, OpenAI-style server-sent events, or a single JSON document, which is
+    /// passed on as one chunk. [`CompletionProvider::Auto`] falls back to its cloud model
+    /// only when the local one fails before producing output. Returns the complete
+    /// output, trimmed like [`Self::generate_text`].
+    pub async fn generate_text_streaming<F>(
         &self,
-        context: &CompletionContext,
+        system_prompt: &str,
+        prompt: &str,
         config: &CompletionModelConfig,
-    ) -> Result<CompletionResult, String> {
-        let endpoint = config
-            .endpoint
-            .clone()
-            .ok_or("Custom endpoint is required")?;
-
-        let prompt = self.build_completion_prompt(context);
-
-        let mut request_body = serde_json::json!({
-            "prompt": prompt,
-            "max_tokens": config.max_tokens,
-            "temperature": config.temperature
-        });
-
-        if !config.model_id.is_empty() {
-            request_body["model"] = serde_json::json!(config.model_id);
+        mut on_delta: F,
+    ) -> Result<String, String>
+    where
+        F: FnMut(&str) + Send,
+    {
+        Self::ensure_provider_allowed(config)?;
+
+        let request = PromptRequest::generation(system_prompt, prompt, true);
+        if config.provider != CompletionProvider::Auto {
+            return self.stream_prompt(config, &request, &mut on_delta).await;
         }
 
-        let mut request = self
-            .client
-            .post(&endpoint)
-            .header("Content-Type", "application/json")
-            .json(&request_body);
-
-        if let Some(api_key) = &config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
+        let local = Self::auto_local_model(config, config.timeout_secs);
+        let mut emitted = false;
+        let result = self
+            .stream_prompt(&local, &request, &mut |delta: &str| {
+                emitted = true;
+                on_delta(delta);
+            })
+            .await;
+        match result {
+            // Output already reached the caller, so it cannot be replaced
+            result if emitted => return result,
+            Ok(text) if !text.is_empty() => return Ok(text),
+            _ => log::trace!("Ollama not available for text generation, trying Groq"),
         }
+        let fallback = Self::auto_fallback_model(config)?;
+        self.stream_prompt(&fallback, &request, &mut on_delta).await
+    }
 
-        let response = request
-            .timeout(std::time::Duration::from_secs(config.timeout_secs.into()))
-            .send()
-            .await
-            .map_err(|e| format!("Custom endpoint request failed: {}", e))?;
-
-        if !response.status().is_success() {
-            return Err(format!(
-                "Custom endpoint returned status: {}",
-                response.status()
-            ));
+    /// Suggest a replacement for the line or selection in `request`.
+    ///
+    /// Returns no suggestions when the model leaves the text unchanged.
+    pub async fn get_rewrite(
+        &self,
+        request: &CompletionRewriteRequest,
+        config: &CompletionModelConfig,
+    ) -> Result<CompletionResult, String> {
+        if request.original.trim().is_empty() {
+            return Ok(CompletionResult::default());
         }
 
-        let response_json: serde_json::Value = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse custom response: {}", e))?;
+        let start = Instant::now();
+        self.stats.write().total_requests += 1;
 
-        // Try common response formats
-        let completion_text = response_json["response"]
-            .as_str()
-            .or_else(|| response_json["text"].as_str())
-            .or_else(|| response_json["choices"][0]["text"].as_str())
-            .or_else(|| response_json["choices"][0]["message"]["content"].as_str())
-            .unwrap_or("")
-            .trim()
-            .to_string();
+        let prompt = Self::build_rewrite_prompt(request);
+        let output = match self
+            .generate_text(REWRITE_SYSTEM_PROMPT, &prompt, config)
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => {
+                self.stats.write().failed_completions += 1;
+                return Err(e);
+            }
+        };
 
-        if completion_text.is_empty() {
-            return Ok(CompletionResult::default());
+        let latency = start.elapsed().as_millis() as u64;
+        {
+            let mut stats = self.stats.write();
+            stats.successful_completions += 1;
+            stats.total_latency_ms += latency;
         }
 
-        let suggestion = CompletionSuggestion::new(
-            completion_text.clone(),
-            0.7,
-            if completion_text.contains('\n') {
-                CompletionType::Block
-            } else {
-                CompletionType::Line
-            },
-        );
+        let replacement = Self::clean_rewrite_output(&output, &request.original);
+        let suggestions = if replacement.is_empty() || replacement == request.original {
+            Vec::new()
+        } else {
+            vec![CompletionSuggestion::rewrite(
+                &request.original,
+                replacement,
+                request.replace_range,
+                0.7,
+            )]
+        };
 
         Ok(CompletionResult {
-            suggestions: vec![suggestion],
-            latency_ms: 0,
+            suggestions,
+            latency_ms: latency,
             model: config.model_id.clone(),
             cached: false,
         })
@@ -576,6 +768,18 @@ impl CompletionService {
         if let Some(surface) = &context.surface {
             prompt.push_str(&format!("Surface: {:?}\n", surface));
         }
+        if let Some(app) = &context.focused_app {
+            if let Some(app_name) = &app.app_name {
+                prompt.push_str(&format!("Application: {}\n", app_name));
+            }
+            if let Some(document_type) = &app.document_type {
+                prompt.push_str(&format!("Document type: {}\n", document_type));
+            }
+            if let Some(hint) = app.app_kind.and_then(Self::focused_app_hint) {
+                prompt.push_str(hint);
+                prompt.push('\n');
+            }
+        }
 
         match mode {
             CompletionMode::Code => {
@@ -629,6 +833,78 @@ impl CompletionService {
         prompt
     }
 
+    /// Build the prompt for rewriting a line or selection
+    fn build_rewrite_prompt(request: &CompletionRewriteRequest) -> String {
+        let before = request.text_before.as_deref().unwrap_or("");
+        let after = request.text_after.as_deref().unwrap_or("");
+        let context = CompletionContext {
+            text: format!("{}{}", before, request.original),
+            language: request.language.clone(),
+            mode: request.mode.clone(),
+            surface: request.surface.clone(),
+            ..Default::default()
+        };
+        let mode = Self::resolve_mode(&context);
+
+        let mut prompt = String::new();
+        if mode == CompletionMode::Code {
+            let language = request
+                .language
+                .clone()
+                .unwrap_or_else(|| Self::detect_language(&context.text));
+            if !language.is_empty() {
+                prompt.push_str(&format!("Language: {}\n", language));
+            }
+        }
+
+        let before: String = {
+            let skip = before.chars().count().saturating_sub(REWRITE_CONTEXT_CHARS);
+            before.chars().skip(skip).collect()
+        };
+        let after: String = after.chars().take(REWRITE_CONTEXT_CHARS).collect();
+        if !before.is_empty() {
+            prompt.push_str("Text before:\n");
+            prompt.push_str(&before);
+            prompt.push('\n');
+        }
+        if !after.is_empty() {
+            prompt.push_str("Text after:\n");
+            prompt.push_str(&after);
+            prompt.push('\n');
+        }
+
+        match request.instruction.as_deref().map(str::trim) {
+            Some(instruction) if !instruction.is_empty() => {
+                prompt.push_str(&format!("Instruction: {}\n", instruction));
+            }
+            _ => prompt.push_str(
+                "Instruction: Improve the fragment (fix mistakes, clarity and style) \
+                 without changing its meaning.\n",
+            ),
+        }
+
+        prompt.push_str("Fragment:\n");
+        prompt.push_str(&request.original);
+        prompt.push_str("\n\nRewritten fragment:");
+        prompt
+    }
+
+    /// Strip code fences from model output and restore the original's surrounding whitespace
+    fn clean_rewrite_output(output: &str, original: &str) -> String {
+        let mut text = output.trim();
+        if let Some(rest) = text.strip_prefix("```") {
+            let body = rest.split_once('\n').map(|(_, body)| body).unwrap_or("");
+            text = body.strip_suffix("```").unwrap_or(body).trim();
+        }
+        if text.is_empty() {
+            return String::new();
+        }
+
+        let leading = &original[..original.len() - original.trim_start().len()];
+        let trailing = &original[original.trim_end().len()..];
+        format!("{}{}{}", leading, text, trailing)
+    }
+
     /// Resolve effective completion mode from request context.
     pub fn resolve_mode(context: &CompletionContext) -> CompletionMode {
         let detected_lang = context
@@ -642,6 +918,13 @@ impl CompletionService {
         if let Some(mode) = &context.mode {
             return mode.clone();
         }
+        if let Some(mode) = context
+            .focused_app
+            .as_ref()
+            .and_then(Self::focused_app_mode)
+        {
+            return mode;
+        }
         if !detected_lang.is_empty() || Self::looks_like_code(&context.text) {
             return CompletionMode::Code;
         }
@@ -656,6 +939,41 @@ impl CompletionService {
         CompletionMode::PlainText
     }
 
+    /// Completion mode implied by the focused app, if it implies one
+    fn focused_app_mode(app: &FocusedAppContext) -> Option<CompletionMode> {
+        let is_markdown = app
+            .document_type
+            .as_deref()
+            .is_some_and(|doc| matches!(doc, "markdown" | "md"));
+        match app.app_kind? {
+            FocusedAppKind::Terminal => Some(CompletionMode::Code),
+            FocusedAppKind::CodeEditor if is_markdown => Some(CompletionMode::Markdown),
+            FocusedAppKind::CodeEditor => Some(CompletionMode::Code),
+            FocusedAppKind::NoteTaking => Some(CompletionMode::Markdown),
+            FocusedAppKind::DocumentEditor | FocusedAppKind::Email => {
+                Some(CompletionMode::PlainText)
+            }
+            FocusedAppKind::Chat => Some(CompletionMode::Chat),
+            FocusedAppKind::Browser | FocusedAppKind::Other => None,
+        }
+    }
+
+    /// Prompt guidance for apps whose input differs from free text
+    fn focused_app_hint(kind: FocusedAppKind) -> Option<&'static str> {
+        match kind {
+            FocusedAppKind::Terminal => {
+                Some("The user is typing in a terminal. Complete the current shell command only.")
+            }
+            FocusedAppKind::DocumentEditor | FocusedAppKind::NoteTaking => {
+                Some("The user is writing a document. Continue the prose, not code.")
+            }
+            FocusedAppKind::Email => {
+                Some("The user is writing an email. Keep the tone of the message.")
+            }
+            _ => None,
+        }
+    }
+
     fn looks_like_code(text: &str) -> bool {
         let lowered = text.to_lowercase();
         lowered.contains("function ")
@@ -1037,6 +1355,9 @@ impl CompletionService {
             super::types::CompletionSurface::ChatInput => "chat_input",
             super::types::CompletionSurface::ChatWidget => "chat_widget",
             super::types::CompletionSurface::LatexEditor => "latex_editor",
+            super::types::CompletionSurface::Terminal => "terminal",
+            super::types::CompletionSurface::CodeEditor => "code_editor",
+            super::types::CompletionSurface::Document => "document",
             super::types::CompletionSurface::Generic => "generic",
         }
         .to_string()
@@ -1073,6 +1394,8 @@ impl CompletionService {
 
     /// Process completion feedback for quality tracking
     pub fn submit_feedback(&self, feedback: CompletionFeedback) {
+        self.record_model_feedback(&feedback);
+
         let mut stats = self.stats.write();
         match &feedback {
             CompletionFeedback::FullAccept {
@@ -1146,9 +1469,168 @@ impl CompletionService {
         );
     }
 
+    /// Identify a model configuration for feedback tracking
+    fn model_key(config: &CompletionModelConfig) -> String {
+        format!(
+            "{}:{}",
+            Self::normalize_provider_for_key(&config.provider),
+            config.model_id
+        )
+    }
+
+    /// Surface/language bucket that model performance is tracked per
+    fn selection_bucket(context: &CompletionContext) -> String {
+        let surface = context
+            .surface
+            .as_ref()
+            .map(Self::normalize_surface_for_key)
+            .unwrap_or_else(|| "generic".to_string());
+        let language = context
+            .language
+            .as_deref()
+            .map(Self::normalize_language_for_key)
+            .filter(|language| !language.is_empty())
+            .unwrap_or_else(|| "*".to_string());
+        format!("{}/{}", surface, language)
+    }
+
+    /// Remember which model produced each suggestion so feedback can be attributed
+    fn record_suggestion_origins(
+        &self,
+        context: &CompletionContext,
+        config: &CompletionModelConfig,
+        result: &CompletionResult,
+    ) {
+        if result.suggestions.is_empty() {
+            return;
+        }
+
+        let bucket = Self::selection_bucket(context);
+        let model = Self::model_key(config);
+        let mut selection = self.selection.write();
+        for suggestion in &result.suggestions {
+            let previous = selection
+                .suggestion_origins
+                .insert(suggestion.id.clone(), (bucket.clone(), model.clone()));
+            if previous.is_none() {
+                selection.origin_order.push_back(suggestion.id.clone());
+            }
+        }
+        while selection.origin_order.len() > MAX_TRACKED_SUGGESTIONS {
+            if let Some(oldest) = selection.origin_order.pop_front() {
+                selection.suggestion_origins.remove(&oldest);
+            }
+        }
+    }
+
+    /// Count feedback against the model that produced the suggestion
+    fn record_model_feedback(&self, feedback: &CompletionFeedback) {
+        let (suggestion_id, accepted) = match feedback {
+            CompletionFeedback::FullAccept { suggestion_id, .. }
+            | CompletionFeedback::PartialAccept { suggestion_id, .. } => (suggestion_id, true),
+            CompletionFeedback::QuickDismiss { suggestion_id, .. } => (suggestion_id, false),
+            CompletionFeedback::ExplicitRating {
+                suggestion_id,
+                rating,
+            } => (suggestion_id, matches!(rating, FeedbackRating::Positive)),
+        };
+
+        let mut selection = self.selection.write();
+        let Some(origin) = selection.suggestion_origins.remove(suggestion_id) else {
+            return;
+        };
+        selection.origin_order.retain(|id| id != suggestion_id);
+
+        let performance = selection.performance.entry(origin).or_default();
+        if accepted {
+            performance.accepted += 1;
+        } else {
+            performance.rejected += 1;
+        }
+    }
+
+    /// Choose the model for a request.
+    ///
+    /// Without `auto_model_selection` (or without candidates) this is the configured model.
+    /// Otherwise candidates are sampled until each has `auto_selection_min_samples` feedback
+    /// events for the context's surface/language, after which the one with the best
+    /// acceptance rate is used.
+    pub fn select_model(
+        &self,
+        context: &CompletionContext,
+        config: &CompletionConfig,
+    ) -> CompletionModelConfig {
+        let mut candidates: Vec<&CompletionModelConfig> = vec![&config.model];
+        for candidate in &config.candidate_models {
+            if !candidates
+                .iter()
+                .any(|c| Self::model_key(c) == Self::model_key(candidate))
+            {
+                candidates.push(candidate);
+            }
+        }
+
+        // Offline mode restricts selection to local candidates
+        candidates.retain(|c| Self::ensure_provider_allowed(c).is_ok());
+
+        if !config.auto_model_selection || candidates.len() < 2 {
+            self.selection.write().last_model = Some(Self::model_key(&config.model));
+            return config.model.clone();
+        }
+
+        let bucket = Self::selection_bucket(context);
+        let mut selection = self.selection.write();
+        let performance_of = |candidate: &CompletionModelConfig| {
+            selection
+                .performance
+                .get(&(bucket.clone(), Self::model_key(candidate)))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let exploring = candidates
+            .iter()
+            .any(|c| performance_of(c).samples() < config.auto_selection_min_samples);
+        let mut chosen = candidates[0];
+        for candidate in candidates.iter().skip(1) {
+            let (current, best) = (performance_of(candidate), performance_of(chosen));
+            let better = if exploring {
+                // Gather samples from the least-tried candidate first
+                current.samples() < best.samples()
+            } else {
+                current.acceptance_rate() > best.acceptance_rate()
+            };
+            if better {
+                chosen = candidate;
+            }
+        }
+
+        let key = Self::model_key(chosen);
+        let previous = selection.active.insert(bucket.clone(), key.clone());
+        if !exploring && previous.as_ref() != Some(&key) {
+            log::info!("Auto-selected completion model {} for {}", key, bucket);
+        }
+        selection.last_model = Some(key);
+        chosen.clone()
+    }
+
     /// Get statistics
     pub fn get_stats(&self) -> super::types::CompletionStats {
         let stats = self.stats.read();
+        let selection = self.selection.read();
+        let mut model_performance: Vec<ModelPerformanceStats> = selection
+            .performance
+            .iter()
+            .map(|((bucket, model), performance)| ModelPerformanceStats {
+                bucket: bucket.clone(),
+                model: model.clone(),
+                accepted: performance.accepted,
+                rejected: performance.rejected,
+                acceptance_rate: performance.acceptance_rate(),
+                active: selection.active.get(bucket) == Some(model),
+            })
+            .collect();
+        model_performance.sort_by(|a, b| (&a.bucket, &a.model).cmp(&(&b.bucket, &b.model)));
         let avg_latency = if stats.successful_completions > 0 {
             stats.total_latency_ms as f64 / stats.successful_completions as f64
         } else {
@@ -1173,12 +1655,15 @@ impl CompletionService {
             cache_hits_normalized: stats.cache_hits_normalized,
             cache_stale_rejects: stats.cache_stale_rejects,
             feedback_stats: stats.feedback.clone(),
+            active_model: selection.last_model.clone(),
+            model_performance,
         }
     }
 
     /// Reset statistics
     pub fn reset_stats(&self) {
         *self.stats.write() = ServiceStats::default();
+        *self.selection.write() = ModelSelectionState::default();
     }
 }
 
@@ -1188,6 +1673,69 @@ impl Default for CompletionService {
     }
 }
 
+/// Display name of a provider in error messages
+fn provider_name(provider: &CompletionProvider) -> &'static str {
+    match provider {
+        CompletionProvider::Ollama => "Ollama",
+        CompletionProvider::OpenAI => "OpenAI",
+        CompletionProvider::Groq => "Groq",
+        CompletionProvider::Auto => "Auto",
+        CompletionProvider::Custom => "Custom endpoint",
+    }
+}
+
+/// Generated text in a provider's non-streaming response
+fn response_text<'a>(provider: &CompletionProvider, response: &'a serde_json::Value) -> &'a str {
+    match provider {
+        CompletionProvider::Ollama => response["response"].as_str(),
+        CompletionProvider::OpenAI | CompletionProvider::Groq => {
+            response["choices"][0]["message"]["content"].as_str()
+        }
+        // Try common response formats
+        CompletionProvider::Custom | CompletionProvider::Auto => response["response"]
+            .as_str()
+            .or_else(|| response["text"].as_str())
+            .or_else(|| response["choices"][0]["text"].as_str())
+            .or_else(|| response["choices"][0]["message"]["content"].as_str()),
+    }
+    .unwrap_or("")
+}
+
+/// Text delta from one line of Ollama's streaming generate response
+fn parse_ollama_stream_line(line: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    value["response"].as_str().map(str::to_string)
+}
+
+/// Text delta from one server-sent event line of a streaming chat completions response
+fn parse_chat_stream_line(line: &str) -> Option<String> {
+    let data = line.trim().strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(data).ok()?;
+    value["choices"][0]["delta"]["content"]
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Text delta from one line of a custom endpoint's streaming response, which may be
+/// an Ollama-style JSON line or an OpenAI-style server-sent event
+fn parse_custom_stream_line(line: &str) -> Option<String> {
+    let line = line.trim();
+    let data = line.strip_prefix("data:").map_or(line, str::trim);
+    if data == "[DONE]" {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_str(data).ok()?;
+    value["response"]
+        .as_str()
+        .or_else(|| value["choices"][0]["delta"]["content"].as_str())
+        .or_else(|| value["choices"][0]["text"].as_str())
+        .or_else(|| value["text"].as_str())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1220,6 +1768,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let prompt = service.build_completion_prompt(&context);
@@ -1244,6 +1793,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let prompt = service.build_completion_prompt(&context);
@@ -1270,6 +1820,7 @@ mod tests {
                 ime_state: None,
                 mode: None,
                 surface: None,
+                focused_app: None,
             };
 
             let prompt = service.build_completion_prompt(&context);
@@ -1292,6 +1843,7 @@ mod tests {
             ime_state: None,
             mode: Some(CompletionMode::Chat),
             surface: None,
+            focused_app: None,
         };
 
         let prompt = service.build_completion_prompt(&context);
@@ -1314,6 +1866,7 @@ mod tests {
             ime_state: None,
             mode: Some(CompletionMode::Code),
             surface: None,
+            focused_app: None,
         };
 
         let prompt = service.build_completion_prompt(&context);
@@ -1321,6 +1874,80 @@ mod tests {
         assert!(prompt.contains("Complete the following code naturally"));
     }
 
+    #[test]
+    fn test_build_rewrite_prompt() {
+        let request = CompletionRewriteRequest {
+            original: "let x = 1".to_string(),
+            text_before: Some("fn main() {\n    ".to_string()),
+            language: Some("rust".to_string()),
+            mode: Some(CompletionMode::Code),
+            instruction: Some("rename x to count".to_string()),
+            ..Default::default()
+        };
+
+        let prompt = CompletionService::build_rewrite_prompt(&request);
+
+        assert!(prompt.contains("Language: rust"));
+        assert!(prompt.contains("Text before:\nfn main() {"));
+        assert!(!prompt.contains("Text after:"));
+        assert!(prompt.contains("Instruction: rename x to count"));
+        assert!(prompt.ends_with("Fragment:\nlet x = 1\n\nRewritten fragment:"));
+    }
+
+    #[test]
+    fn test_clean_rewrite_output() {
+        assert_eq!(
+            CompletionService::clean_rewrite_output(
+                "```rust\nlet count = 1\n```",
+                "    let x = 1\n"
+            ),
+            "    let count = 1\n"
+        );
+        assert_eq!(
+            CompletionService::clean_rewrite_output("  I have a cat.  ", "I has a cat."),
+            "I have a cat."
+        );
+        assert!(CompletionService::clean_rewrite_output("   ", "text").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_mode_uses_focused_app() {
+        let mut context = CompletionContext {
+            text: "git status".to_string(),
+            surface: Some(FocusedAppKind::Terminal.surface()),
+            focused_app: Some(FocusedAppContext {
+                app_name: Some("Windows Terminal".to_string()),
+                app_kind: Some(FocusedAppKind::Terminal),
+                document_type: None,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            CompletionService::resolve_mode(&context),
+            CompletionMode::Code
+        );
+
+        let prompt = CompletionService::new().build_completion_prompt(&context);
+        assert!(prompt.contains("Application: Windows Terminal"));
+        assert!(prompt.contains("shell command"));
+
+        context.focused_app = Some(FocusedAppContext {
+            app_name: Some("Visual Studio Code".to_string()),
+            app_kind: Some(FocusedAppKind::CodeEditor),
+            document_type: Some("markdown".to_string()),
+        });
+        assert_eq!(
+            CompletionService::resolve_mode(&context),
+            CompletionMode::Markdown
+        );
+
+        context.mode = Some(CompletionMode::Chat);
+        assert_eq!(
+            CompletionService::resolve_mode(&context),
+            CompletionMode::Chat
+        );
+    }
+
     #[test]
     fn test_resolve_mode_infers_code_and_chat() {
         let code_context = CompletionContext {
@@ -1334,6 +1961,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
         let chat_context = CompletionContext {
             text: "Can you summarize this?".to_string(),
@@ -1346,6 +1974,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         assert_eq!(
@@ -1373,6 +2002,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let context2 = CompletionContext {
@@ -1386,6 +2016,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let key1 = service.compute_cache_key(&context1, &CompletionModelConfig::default());
@@ -1409,6 +2040,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let context2 = CompletionContext {
@@ -1422,6 +2054,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let key1 = service.compute_cache_key(&context1, &CompletionModelConfig::default());
@@ -1445,6 +2078,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let context2 = CompletionContext {
@@ -1458,6 +2092,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let key1 = service.compute_cache_key(&context1, &CompletionModelConfig::default());
@@ -1593,6 +2228,7 @@ mod tests {
             max_cache_size: 3,
             cache_ttl_secs: 60,
             stats: Arc::new(RwLock::new(ServiceStats::default())),
+            selection: Arc::new(RwLock::new(ModelSelectionState::default())),
         };
 
         // Add entries beyond capacity
@@ -1675,6 +2311,7 @@ mod tests {
             ime_state: None,
             mode: None,
             surface: None,
+            focused_app: None,
         };
 
         let key1 = service.compute_cache_key(&context, &CompletionModelConfig::default());
@@ -1779,6 +2416,7 @@ mod tests {
             max_cache_size: 3,
             cache_ttl_secs: 60,
             stats: Arc::new(RwLock::new(ServiceStats::default())),
+            selection: Arc::new(RwLock::new(ModelSelectionState::default())),
         };
 
         // Add entries
@@ -1816,4 +2454,223 @@ mod tests {
             .get_cached("lfu_key_new", CacheHitKind::Exact)
             .is_some());
     }
+
+    fn selection_context(language: &str) -> CompletionContext {
+        CompletionContext {
+            text: "fn main".to_string(),
+            text_after_cursor: None,
+            cursor_offset: None,
+            cursor_position: None,
+            file_path: None,
+            language: Some(language.to_string()),
+            conversation_digest: None,
+            ime_state: None,
+            mode: None,
+            surface: Some(super::super::types::CompletionSurface::ChatInput),
+            focused_app: None,
+        }
+    }
+
+    fn give_feedback(
+        service: &CompletionService,
+        context: &CompletionContext,
+        model: &CompletionModelConfig,
+        accepted: bool,
+    ) {
+        let suggestion = CompletionSuggestion::new("() {}".to_string(), 0.9, CompletionType::Line);
+        let result = CompletionResult {
+            suggestions: vec![suggestion.clone()],
+            latency_ms: 10,
+            model: model.model_id.clone(),
+            cached: false,
+        };
+        service.record_suggestion_origins(context, model, &result);
+        service.submit_feedback(if accepted {
+            CompletionFeedback::FullAccept {
+                suggestion_id: suggestion.id,
+                time_to_accept_ms: 300,
+            }
+        } else {
+            CompletionFeedback::QuickDismiss {
+                suggestion_id: suggestion.id,
+                time_to_dismiss_ms: 200,
+            }
+        });
+    }
+
+    #[test]
+    fn test_select_model_without_auto_selection_uses_configured_model() {
+        let service = CompletionService::new();
+        let mut config = CompletionConfig::default();
+        config.candidate_models.push(CompletionModelConfig {
+            model_id: "qwen2.5-coder:1.5b".to_string(),
+            ..CompletionModelConfig::default()
+        });
+
+        let selected = service.select_model(&selection_context("rust"), &config);
+        assert_eq!(selected.model_id, config.model.model_id);
+        assert_eq!(
+            service.get_stats().active_model.as_deref(),
+            Some("ollama:qwen2.5-coder:0.5b")
+        );
+    }
+
+    #[test]
+    fn test_select_model_switches_to_better_candidate() {
+        let service = CompletionService::new();
+        let small = CompletionModelConfig::default();
+        let large = CompletionModelConfig {
+            model_id: "qwen2.5-coder:1.5b".to_string(),
+            ..CompletionModelConfig::default()
+        };
+        let config = CompletionConfig {
+            candidate_models: vec![large.clone()],
+            auto_model_selection: true,
+            auto_selection_min_samples: 4,
+            ..CompletionConfig::default()
+        };
+        let rust = selection_context("rust");
+
+        // Exploration: the least-sampled candidate is tried first
+        give_feedback(&service, &rust, &small, false);
+        assert_eq!(
+            service.select_model(&rust, &config).model_id,
+            large.model_id
+        );
+
+        for _ in 0..3 {
+            give_feedback(&service, &rust, &small, false);
+        }
+        for _ in 0..4 {
+            give_feedback(&service, &rust, &large, true);
+        }
+        assert_eq!(
+            service.select_model(&rust, &config).model_id,
+            large.model_id
+        );
+
+        // Other languages are tracked separately and still explore
+        let python = selection_context("python");
+        assert_eq!(
+            service.select_model(&python, &config).model_id,
+            small.model_id
+        );
+
+        let stats = service.get_stats();
+        let active: Vec<_> = stats
+            .model_performance
+            .iter()
+            .filter(|p| p.active)
+            .collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].bucket, "chat_input/rust");
+        assert_eq!(active[0].model, "ollama:qwen2.5-coder:1.5b");
+        assert_eq!(active[0].acceptance_rate, 1.0);
+    }
+
+    #[test]
+    fn test_parse_stream_lines() {
+        assert_eq!(
+            parse_ollama_stream_line("{\"response\":\"Hel\",\"done\":false}\n"),
+            Some("Hel".to_string())
+        );
+        assert_eq!(parse_ollama_stream_line("not json"), None);
+
+        assert_eq!(
+            parse_chat_stream_line("data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n"),
+            Some("lo".to_string())
+        );
+        assert_eq!(parse_chat_stream_line("data: [DONE]"), None);
+        assert_eq!(parse_chat_stream_line(": keep-alive"), None);
+        assert_eq!(
+            parse_chat_stream_line("data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}"),
+            None
+        );
+
+        assert_eq!(
+            parse_custom_stream_line("{\"response\":\"Hi\"}"),
+            Some("Hi".to_string())
+        );
+        assert_eq!(
+            parse_custom_stream_line("data: {\"choices\":[{\"text\":\"there\"}]}"),
+            Some("there".to_string())
+        );
+        assert_eq!(parse_custom_stream_line("data: [DONE]"), None);
+    }
+
+    fn request_body(request: reqwest::RequestBuilder) -> serde_json::Value {
+        let request = request.build().unwrap();
+        serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_build_prompt_request_per_provider() {
+        let service = CompletionService::new();
+        let config = |provider: CompletionProvider| CompletionModelConfig {
+            provider,
+            model_id: "model".to_string(),
+            endpoint: Some("http://localhost:9999".to_string()),
+            api_key: Some("key".to_string()),
+            max_tokens: 64,
+            temperature: 0.2,
+            timeout_secs: 5,
+        };
+        let generation = PromptRequest::generation("system", "prompt", true);
+
+        let ollama = service
+            .build_prompt_request(&config(CompletionProvider::Ollama), &generation)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(ollama.url().as_str(), "http://localhost:9999/api/generate");
+        let body = request_body(
+            service
+                .build_prompt_request(&config(CompletionProvider::Ollama), &generation)
+                .unwrap(),
+        );
+        assert_eq!(body["system"], "system");
+        assert_eq!(body["stream"], true);
+        assert!(body["options"].get("stop").is_none());
+
+        let completion = PromptRequest {
+            system_prompt: None,
+            prompt: "prompt",
+            stop: &["\n\n"],
+            stream: false,
+        };
+        let body = request_body(
+            service
+                .build_prompt_request(&config(CompletionProvider::Groq), &completion)
+                .unwrap(),
+        );
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(body["stop"][0], "\n\n");
+        assert!(body.get("stream").is_none());
+
+        let body = request_body(
+            service
+                .build_prompt_request(&config(CompletionProvider::Custom), &generation)
+                .unwrap(),
+        );
+        assert_eq!(body["prompt"], "system\n\nprompt");
+        assert_eq!(body["model"], "model");
+
+        let mut keyless = config(CompletionProvider::OpenAI);
+        keyless.api_key = None;
+        assert!(service.build_prompt_request(&keyless, &generation).is_err());
+        assert!(service
+            .build_prompt_request(&config(CompletionProvider::Auto), &generation)
+            .is_err());
+    }
+
+    #[test]
+    fn test_response_text_per_provider() {
+        let chat = serde_json::json!({ "choices": [{ "message": { "content": "chat" } }] });
+        assert_eq!(response_text(&CompletionProvider::OpenAI, &chat), "chat");
+        assert_eq!(response_text(&CompletionProvider::Ollama, &chat), "");
+        assert_eq!(response_text(&CompletionProvider::Custom, &chat), "chat");
+        let ollama = serde_json::json!({ "response": "local" });
+        assert_eq!(response_text(&CompletionProvider::Ollama, &ollama), "local");
+        assert_eq!(response_text(&CompletionProvider::Custom, &ollama), "local");
+    }
 }
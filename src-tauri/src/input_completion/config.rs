@@ -13,6 +13,19 @@ pub struct CompletionConfig {
     pub trigger: CompletionTriggerConfig,
     /// UI configuration
     pub ui: CompletionUiConfig,
+    /// Additional models that automatic model selection may choose from
+    #[serde(default)]
+    pub candidate_models: Vec<CompletionModelConfig>,
+    /// Switch between `model` and `candidate_models` based on acceptance feedback
+    #[serde(default)]
+    pub auto_model_selection: bool,
+    /// Feedback events each candidate needs before acceptance rates are compared
+    #[serde(default = "default_auto_selection_min_samples")]
+    pub auto_selection_min_samples: u64,
+}
+
+fn default_auto_selection_min_samples() -> u64 {
+    20
 }
 
 /// Model configuration for completions
@@ -139,6 +152,9 @@ impl Default for CompletionConfig {
             model: CompletionModelConfig::default(),
             trigger: CompletionTriggerConfig::default(),
             ui: CompletionUiConfig::default(),
+            candidate_models: Vec::new(),
+            auto_model_selection: false,
+            auto_selection_min_samples: default_auto_selection_min_samples(),
         }
     }
 }
@@ -361,6 +377,9 @@ mod tests {
                 auto_dismiss_ms: 10000,
                 show_accept_hint: false,
             },
+            candidate_models: vec![CompletionModelConfig::default()],
+            auto_model_selection: true,
+            auto_selection_min_samples: 10,
         };
 
         let json = serde_json::to_string_pretty(&config).unwrap();
@@ -372,6 +391,9 @@ mod tests {
         assert_eq!(parsed.model.max_tokens, config.model.max_tokens);
         assert_eq!(parsed.trigger.debounce_ms, config.trigger.debounce_ms);
         assert_eq!(parsed.ui.max_suggestions, config.ui.max_suggestions);
+        assert!(parsed.auto_model_selection);
+        assert_eq!(parsed.candidate_models.len(), 1);
+        assert_eq!(parsed.auto_selection_min_samples, 10);
     }
 
     #[test]
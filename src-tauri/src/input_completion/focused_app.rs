@@ -0,0 +1,120 @@
+//! Focused application detection for input completion
+//!
+//! Reads the active window from the context module, falling back to the
+//! awareness module's current focus session, and reduces it to the app name,
+//! app kind and document type used to adjust completion prompts.
+
+use super::types::{FocusedAppContext, FocusedAppKind};
+use crate::awareness::AwarenessManager;
+use crate::context::{AppContext, AppType, ContextManager, FileContext};
+use tauri::{AppHandle, Manager};
+
+/// Detect the focused application and document.
+///
+/// Returns `None` when neither the context nor the awareness manager knows the
+/// focused window.
+pub fn detect_focused_app(app_handle: &AppHandle) -> Option<FocusedAppContext> {
+    if let Some(context_manager) = app_handle.try_state::<ContextManager>() {
+        match context_manager.get_window_info() {
+            Ok(window) => {
+                let app = AppContext::from_window_info(&window).ok();
+                let file = FileContext::from_window_info(&window).ok();
+                if let Some(app) = app {
+                    return Some(from_app_context(&app, file.as_ref()));
+                }
+            }
+            Err(e) => log::trace!("Focused window unavailable for completion: {}", e),
+        }
+    }
+
+    let awareness = app_handle.try_state::<AwarenessManager>()?;
+    let session = awareness.get_current_focus()?;
+    let app_type = AppContext::classify(&session.process_name, &session.window_title);
+    Some(FocusedAppContext {
+        app_name: Some(session.app_name),
+        app_kind: Some(kind_for_app_type(&app_type)),
+        document_type: None,
+    })
+}
+
+/// Build the completion view of an application and its open file
+fn from_app_context(app: &AppContext, file: Option<&FileContext>) -> FocusedAppContext {
+    let document_type = file
+        .and_then(|f| f.language.as_ref().or(f.extension.as_ref()))
+        .map(|value| value.to_lowercase());
+
+    FocusedAppContext {
+        app_name: Some(app.app_name.clone()).filter(|name| !name.is_empty()),
+        app_kind: Some(kind_for_app_type(&app.app_type)),
+        document_type,
+    }
+}
+
+fn kind_for_app_type(app_type: &AppType) -> FocusedAppKind {
+    match app_type {
+        AppType::Terminal => FocusedAppKind::Terminal,
+        AppType::CodeEditor | AppType::Database | AppType::ApiClient => FocusedAppKind::CodeEditor,
+        AppType::DocumentEditor | AppType::Presentation | AppType::Spreadsheet => {
+            FocusedAppKind::DocumentEditor
+        }
+        AppType::NoteTaking => FocusedAppKind::NoteTaking,
+        AppType::Email => FocusedAppKind::Email,
+        AppType::Chat => FocusedAppKind::Chat,
+        AppType::Browser => FocusedAppKind::Browser,
+        _ => FocusedAppKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WindowInfo;
+
+    fn window(process_name: &str, title: &str) -> WindowInfo {
+        WindowInfo {
+            handle: 1,
+            title: title.to_string(),
+            class_name: "Chrome_WidgetWin_1".to_string(),
+            process_id: 42,
+            process_name: process_name.to_string(),
+            exe_path: None,
+            x: 0,
+            y: 0,
+            width: 1280,
+            height: 800,
+            is_minimized: false,
+            is_maximized: false,
+            is_focused: true,
+            is_visible: true,
+        }
+    }
+
+    #[test]
+    fn test_kind_for_app_type() {
+        assert_eq!(
+            kind_for_app_type(&AppType::Terminal),
+            FocusedAppKind::Terminal
+        );
+        assert_eq!(
+            kind_for_app_type(&AppType::CodeEditor),
+            FocusedAppKind::CodeEditor
+        );
+        assert_eq!(
+            kind_for_app_type(&AppType::DocumentEditor),
+            FocusedAppKind::DocumentEditor
+        );
+        assert_eq!(kind_for_app_type(&AppType::Game), FocusedAppKind::Other);
+    }
+
+    #[test]
+    fn test_from_app_context_uses_file_language() {
+        let window = window("Code.exe", "main.rs - cognia - Visual Studio Code");
+        let app = AppContext::from_window_info(&window).unwrap();
+        let file = FileContext::from_window_info(&window).ok();
+
+        let focused = from_app_context(&app, file.as_ref());
+        assert_eq!(focused.app_kind, Some(FocusedAppKind::CodeEditor));
+        assert!(focused.app_name.is_some());
+        assert_eq!(focused.document_type.as_deref(), Some("rust"));
+    }
+}
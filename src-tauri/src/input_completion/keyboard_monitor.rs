@@ -5,13 +5,14 @@
 
 use parking_lot::RwLock;
 use rdev::{Event, EventType, Key};
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 use tokio::sync::mpsc;
 
 /// Type of keyboard event
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum KeyEventType {
     /// Key was pressed
     KeyPress,
@@ -20,19 +21,23 @@ pub enum KeyEventType {
 }
 
 /// A keyboard event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyEvent {
     /// Type of event
     pub event_type: KeyEventType,
     /// Key name (e.g., "A", "Tab", "Shift")
     pub key: String,
     /// Character produced (if printable)
+    #[serde(default)]
     pub char: Option<char>,
     /// Whether Ctrl/Cmd is held
+    #[serde(default)]
     pub ctrl: bool,
     /// Whether Shift is held
+    #[serde(default)]
     pub shift: bool,
     /// Whether Alt is held
+    #[serde(default)]
     pub alt: bool,
     /// Timestamp in milliseconds
     pub timestamp: i64,
@@ -5,28 +5,36 @@
 //! - IME (Input Method Editor) state detection
 //! - Real-time keyboard input capture
 //! - AI-powered text completion suggestions
+//! - Focused app/document awareness for prompt and surface selection
 //! - Overlay window for displaying suggestions
 
 mod completion_service;
 mod config;
+mod focused_app;
 mod ime_state;
 mod keyboard_monitor;
+#[cfg(feature = "completion-replay")]
+mod replay;
 pub mod types;
 
 pub use completion_service::CompletionService;
-pub use config::{CompletionConfig, InputCaptureMode};
+pub use config::{CompletionConfig, CompletionModelConfig, CompletionProvider, InputCaptureMode};
 pub use ime_state::{ImeMonitor, ImeState};
 // Note: InputMode is used in tests but not re-exported to avoid unused import warning
 #[cfg(test)]
 use ime_state::InputMode;
 pub use keyboard_monitor::{KeyEvent, KeyEventType, KeyboardMonitor};
+#[cfg(feature = "completion-replay")]
+pub use replay::{replay_keystrokes, CompletionReplay, ReplayRequest, ReplayStep};
 pub use types::{
-    CompletionContext, CompletionFeedback, CompletionMode, CompletionRequestV2,
-    CompletionRequestV3, CompletionResult, CompletionResultV2, CompletionResultV3,
-    CompletionStatus, CompletionSuggestion, CompletionSuggestionRef, CompletionSurface,
-    InputCompletionEvent,
+    CompletionChainResult, CompletionContext, CompletionFeedback, CompletionMode,
+    CompletionRequestV2, CompletionRequestV3, CompletionResult, CompletionResultV2,
+    CompletionResultV3, CompletionRewriteRequest, CompletionStatus, CompletionSuggestion,
+    CompletionSuggestionRef, CompletionSurface, InputCompletionEvent, KeyDecision,
 };
+use types::{FocusedAppContext, FocusedAppKind};
 
+use crate::accessibility::{self, DegradedStatus};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -34,6 +42,32 @@ use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::mpsc;
 
+/// Context a batch of suggestions was generated from, kept so an accepted
+/// suggestion can be continued
+struct SuggestionOrigin {
+    suggestion_ids: Vec<String>,
+    context: CompletionContext,
+}
+
+/// Context for continuing after `suggestion` is accepted at the end of `context`.
+///
+/// Cursor position and offset are dropped since they no longer match the grown text.
+fn continuation_context(
+    context: &CompletionContext,
+    suggestion: &CompletionSuggestion,
+) -> Result<CompletionContext, String> {
+    if suggestion.replace_range.is_some() {
+        return Err("Rewrite suggestions cannot be continued".to_string());
+    }
+
+    Ok(CompletionContext {
+        text: format!("{}{}", context.text, suggestion.text),
+        cursor_offset: None,
+        cursor_position: None,
+        ..context.clone()
+    })
+}
+
 /// Input Completion Manager
 ///
 /// Coordinates IME state detection, keyboard monitoring, and AI completion.
@@ -56,10 +90,14 @@ pub struct InputCompletionManager {
     current_suggestion: Arc<RwLock<Option<CompletionSuggestion>>>,
     /// Indexed active suggestions for v2 accept/dismiss by suggestion_id
     active_suggestions: Arc<RwLock<HashMap<String, CompletionSuggestion>>>,
+    /// Context the active manually triggered suggestions were generated from
+    suggestion_origin: Arc<RwLock<Option<SuggestionOrigin>>>,
     /// Debounce task handle
     debounce_handle: Arc<RwLock<Option<tauri::async_runtime::JoinHandle<()>>>>,
     /// Last key event timestamp (ms) for adaptive debounce typing speed calculation
     last_key_timestamp: Arc<AtomicU64>,
+    /// Permission state detected when global capture last started
+    degraded: Arc<RwLock<DegradedStatus>>,
 }
 
 impl InputCompletionManager {
@@ -82,8 +120,10 @@ impl InputCompletionManager {
             input_buffer: Arc::new(RwLock::new(String::new())),
             current_suggestion: Arc::new(RwLock::new(None)),
             active_suggestions: Arc::new(RwLock::new(HashMap::new())),
+            suggestion_origin: Arc::new(RwLock::new(None)),
             debounce_handle: Arc::new(RwLock::new(None)),
             last_key_timestamp: Arc::new(AtomicU64::new(0)),
+            degraded: Arc::new(RwLock::new(DegradedStatus::default())),
         }
     }
 
@@ -133,6 +173,9 @@ impl InputCompletionManager {
 
         // Global keyboard capture is now legacy compatibility path.
         if config.trigger.input_capture_mode == InputCaptureMode::GlobalLegacy {
+            *self.degraded.write() =
+                accessibility::check_and_notify(&self.app_handle, "input_completion");
+
             let (key_tx, mut key_rx) = mpsc::unbounded_channel::<KeyEvent>();
             if let Err(err) = self.keyboard_monitor.start(key_tx) {
                 self.ime_monitor.stop();
@@ -182,6 +225,7 @@ impl InputCompletionManager {
             });
         } else {
             log::info!("Input completion running in local-only trigger mode");
+            *self.degraded.write() = DegradedStatus::default();
         }
 
         Ok(())
@@ -269,33 +313,122 @@ impl InputCompletionManager {
         last_key_timestamp: &Arc<AtomicU64>,
     ) {
         let cfg = config.read().clone();
+        let ime_state = ime_monitor.get_state();
+
+        let decision = Self::process_key_event(
+            key_event,
+            &cfg,
+            &ime_state,
+            input_buffer,
+            current_suggestion,
+            active_suggestions,
+            completion_service,
+            last_key_timestamp,
+            &|event| {
+                let _ = app_handle.emit("input-completion://event", event);
+            },
+        );
+
+        let KeyDecision::Debounced {
+            debounce_ms,
+            text: buffer_text,
+        } = decision
+        else {
+            return;
+        };
+
+        // Cancel previous debounce
+        if let Some(handle) = debounce_handle.write().take() {
+            handle.abort();
+        }
+
+        let completion_service = completion_service.clone();
+        let current_suggestion = current_suggestion.clone();
+        let active_suggestions = active_suggestions.clone();
+        let app_handle = app_handle.clone();
+        let completion_config = cfg;
+
+        let handle = tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(debounce_ms)).await;
+
+            // Adapt surface and prompt to the focused app
+            let focused_app = focused_app::detect_focused_app(&app_handle);
+            let surface = focused_app
+                .as_ref()
+                .and_then(|app| app.app_kind)
+                .map(FocusedAppKind::surface)
+                .unwrap_or_default();
+
+            // Request completion
+            let context = CompletionContext {
+                text: buffer_text,
+                text_after_cursor: None,
+                cursor_offset: None,
+                cursor_position: None,
+                file_path: None,
+                language: None,
+                conversation_digest: None,
+                ime_state: Some(ime_state),
+                mode: None,
+                surface: Some(surface),
+                focused_app,
+            };
+            let _ = Self::request_debounced_completion(
+                &context,
+                &completion_config,
+                &completion_service,
+                &current_suggestion,
+                &active_suggestions,
+                &|event| {
+                    let _ = app_handle.emit("input-completion://event", event);
+                },
+            )
+            .await;
+        });
+
+        *debounce_handle.write() = Some(handle);
+    }
 
+    /// Decide what a key event does and apply it to the input state
+    ///
+    /// Events are passed to `emit`. A completion request is not started here:
+    /// `KeyDecision::Debounced` tells the caller to request one for `text` once
+    /// `debounce_ms` passes without another debounced key.
+    #[allow(clippy::too_many_arguments)]
+    fn process_key_event(
+        key_event: &KeyEvent,
+        cfg: &CompletionConfig,
+        ime_state: &ImeState,
+        input_buffer: &RwLock<String>,
+        current_suggestion: &RwLock<Option<CompletionSuggestion>>,
+        active_suggestions: &RwLock<HashMap<String, CompletionSuggestion>>,
+        completion_service: &CompletionService,
+        last_key_timestamp: &Arc<AtomicU64>,
+        emit: &dyn Fn(InputCompletionEvent),
+    ) -> KeyDecision {
         // Skip if IME is composing (user is typing CJK characters)
-        let ime_state = ime_monitor.get_state();
         if ime_state.is_composing {
             log::trace!("Skipping completion: IME is composing");
-            return;
+            return KeyDecision::ImeComposing;
         }
 
         match key_event.event_type {
             KeyEventType::KeyPress => {
                 // Handle Tab key - accept suggestion
                 if key_event.key == "Tab" {
-                    if let Some(suggestion) = current_suggestion.read().clone() {
+                    let suggestion = current_suggestion.read().clone();
+                    if let Some(suggestion) = suggestion {
                         log::debug!("Accepting completion suggestion");
 
                         // Emit accept event using structured event type
-                        let _ = app_handle.emit(
-                            "input-completion://event",
-                            InputCompletionEvent::Accept(suggestion.clone()),
-                        );
+                        emit(InputCompletionEvent::Accept(suggestion.clone()));
 
                         // Clear current suggestion
                         *current_suggestion.write() = None;
                         active_suggestions.write().clear();
                         input_buffer.write().clear();
 
-                        return;
+                        return KeyDecision::Accepted { suggestion };
                     }
                 }
 
@@ -305,11 +438,12 @@ impl InputCompletionManager {
                     *current_suggestion.write() = None;
                     active_suggestions.write().clear();
 
-                    let _ =
-                        app_handle.emit("input-completion://event", InputCompletionEvent::Dismiss);
-                    return;
+                    emit(InputCompletionEvent::Dismiss);
+                    return KeyDecision::Dismissed;
                 }
 
+                let mut decision = KeyDecision::Ignored;
+
                 // Handle character input
                 if let Some(ch) = key_event.char {
                     // Skip if modifier keys are held and skip_with_modifiers is enabled
@@ -317,7 +451,7 @@ impl InputCompletionManager {
                         && (key_event.ctrl || key_event.alt || key_event.shift)
                     {
                         log::trace!("Skipping completion: modifier key held");
-                        return;
+                        return KeyDecision::ModifierHeld;
                     }
 
                     // Check if character should be skipped
@@ -333,7 +467,7 @@ impl InputCompletionManager {
                         // Clear suggestion on skip chars
                         *current_suggestion.write() = None;
                         active_suggestions.write().clear();
-                        return;
+                        return KeyDecision::SkipChar { ch };
                     }
 
                     let mut buffer = input_buffer.write();
@@ -346,6 +480,9 @@ impl InputCompletionManager {
                     }
 
                     log::trace!("Input buffer updated: {} chars", buffer.len());
+                    decision = KeyDecision::Buffered {
+                        buffer_len: buffer.len(),
+                    };
                 }
 
                 // Handle backspace
@@ -356,20 +493,23 @@ impl InputCompletionManager {
                     // Clear suggestion on backspace
                     *current_suggestion.write() = None;
                     active_suggestions.write().clear();
-                    let _ =
-                        app_handle.emit("input-completion://event", InputCompletionEvent::Dismiss);
+                    emit(InputCompletionEvent::Dismiss);
+                    decision = KeyDecision::Backspace {
+                        buffer_len: buffer.len(),
+                    };
                 }
+
+                decision
             }
             KeyEventType::KeyRelease => {
                 // Trigger completion after debounce
-                let buffer = input_buffer.read();
-                let buffer_len = buffer.len();
-                let buffer_text = buffer.clone();
+                let buffer_text = input_buffer.read().clone();
+                let buffer_len = buffer_text.len();
 
                 // Check word boundary trigger if enabled
                 if cfg.trigger.trigger_on_word_boundary {
                     // Only trigger if the last character is a word boundary
-                    if let Some(last_char) = buffer.chars().last() {
+                    if let Some(last_char) = buffer_text.chars().last() {
                         let is_word_boundary = last_char.is_whitespace()
                             || last_char == '.'
                             || last_char == ','
@@ -384,118 +524,100 @@ impl InputCompletionManager {
 
                         if !is_word_boundary {
                             log::trace!("Skipping completion: not at word boundary");
-                            drop(buffer);
-                            return;
+                            return KeyDecision::NotAtWordBoundary;
                         }
                     }
                 }
-                drop(buffer);
-
-                if buffer_len >= cfg.trigger.min_context_length {
-                    // Try prefix cache matching first (instant, no API call needed)
-                    // If user types characters that match an existing cached suggestion,
-                    // return the remaining portion immediately
-                    if let Some(prefix_result) =
-                        completion_service.get_cached_by_prefix(&buffer_text, None)
-                    {
-                        if let Some(suggestion) = prefix_result.suggestions.first() {
-                            log::debug!(
-                                "Prefix cache hit: {}",
-                                suggestion.text.chars().take(50).collect::<String>()
-                            );
-                            *current_suggestion.write() = Some(suggestion.clone());
-                            {
-                                let mut suggestions = active_suggestions.write();
-                                suggestions.clear();
-                                suggestions.insert(suggestion.id.clone(), suggestion.clone());
-                            }
-                            let _ = app_handle.emit(
-                                "input-completion://event",
-                                InputCompletionEvent::Suggestion(suggestion.clone()),
-                            );
-                            // Update timestamp for adaptive debounce
-                            last_key_timestamp
-                                .store(key_event.timestamp.max(0) as u64, Ordering::Relaxed);
-                            return;
+
+                if buffer_len < cfg.trigger.min_context_length {
+                    return KeyDecision::ContextTooShort { buffer_len };
+                }
+
+                // Try prefix cache matching first (instant, no API call needed)
+                // If user types characters that match an existing cached suggestion,
+                // return the remaining portion immediately
+                if let Some(prefix_result) =
+                    completion_service.get_cached_by_prefix(&buffer_text, None)
+                {
+                    if let Some(suggestion) = prefix_result.suggestions.first() {
+                        log::debug!(
+                            "Prefix cache hit: {}",
+                            suggestion.text.chars().take(50).collect::<String>()
+                        );
+                        *current_suggestion.write() = Some(suggestion.clone());
+                        {
+                            let mut suggestions = active_suggestions.write();
+                            suggestions.clear();
+                            suggestions.insert(suggestion.id.clone(), suggestion.clone());
                         }
+                        emit(InputCompletionEvent::Suggestion(suggestion.clone()));
+                        // Update timestamp for adaptive debounce
+                        last_key_timestamp
+                            .store(key_event.timestamp.max(0) as u64, Ordering::Relaxed);
+                        return KeyDecision::PrefixCacheHit {
+                            suggestion: suggestion.clone(),
+                        };
                     }
+                }
 
-                    // Cancel previous debounce
-                    if let Some(handle) = debounce_handle.write().take() {
-                        handle.abort();
-                    }
+                // Compute adaptive debounce delay based on typing speed
+                let current_ts = key_event.timestamp.max(0) as u64;
+                let debounce_ms =
+                    Self::compute_adaptive_debounce(cfg, last_key_timestamp, current_ts);
 
-                    // Compute adaptive debounce delay based on typing speed
-                    let current_ts = key_event.timestamp.max(0) as u64;
-                    let debounce_ms =
-                        Self::compute_adaptive_debounce(&cfg, last_key_timestamp, current_ts);
-
-                    // Update last key timestamp for next adaptive debounce calculation
-                    last_key_timestamp.store(current_ts, Ordering::Relaxed);
-
-                    let completion_service = completion_service.clone();
-                    let current_suggestion = current_suggestion.clone();
-                    let active_suggestions = active_suggestions.clone();
-                    let app_handle = app_handle.clone();
-                    let model_config = cfg.model.clone();
-                    let ime_state = ime_state.clone();
-
-                    let handle = tauri::async_runtime::spawn(async move {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(debounce_ms)).await;
-
-                        // Request completion
-                        let context = CompletionContext {
-                            text: buffer_text,
-                            text_after_cursor: None,
-                            cursor_offset: None,
-                            cursor_position: None,
-                            file_path: None,
-                            language: None,
-                            conversation_digest: None,
-                            ime_state: Some(ime_state),
-                            mode: None,
-                            surface: Some(CompletionSurface::Generic),
-                        };
+                // Update last key timestamp for next adaptive debounce calculation
+                last_key_timestamp.store(current_ts, Ordering::Relaxed);
 
-                        match completion_service
-                            .get_completion(&context, &model_config)
-                            .await
-                        {
-                            Ok(result) => {
-                                if let Some(suggestion) = result.suggestions.first() {
-                                    log::debug!(
-                                        "Got completion suggestion: {}",
-                                        suggestion.text.chars().take(50).collect::<String>()
-                                    );
-
-                                    *current_suggestion.write() = Some(suggestion.clone());
-                                    {
-                                        let mut suggestions = active_suggestions.write();
-                                        suggestions.clear();
-                                        for item in &result.suggestions {
-                                            suggestions.insert(item.id.clone(), item.clone());
-                                        }
-                                    }
-
-                                    // Emit suggestion event using structured event type
-                                    let _ = app_handle.emit(
-                                        "input-completion://event",
-                                        InputCompletionEvent::Suggestion(suggestion.clone()),
-                                    );
-                                }
-                            }
-                            Err(e) => {
-                                log::warn!("Completion request failed: {}", e);
-                                let _ = app_handle.emit(
-                                    "input-completion://event",
-                                    InputCompletionEvent::Error(e),
-                                );
-                            }
+                KeyDecision::Debounced {
+                    debounce_ms,
+                    text: buffer_text,
+                }
+            }
+        }
+    }
+
+    /// Request the completion a debounced key event asked for
+    ///
+    /// The first suggestion becomes current and is passed to `emit`, as are errors.
+    async fn request_debounced_completion(
+        context: &CompletionContext,
+        cfg: &CompletionConfig,
+        completion_service: &CompletionService,
+        current_suggestion: &RwLock<Option<CompletionSuggestion>>,
+        active_suggestions: &RwLock<HashMap<String, CompletionSuggestion>>,
+        emit: &(dyn Fn(InputCompletionEvent) + Sync),
+    ) -> Result<Vec<CompletionSuggestion>, String> {
+        let model_config = completion_service.select_model(context, cfg);
+
+        match completion_service
+            .get_completion(context, &model_config)
+            .await
+        {
+            Ok(result) => {
+                if let Some(suggestion) = result.suggestions.first() {
+                    log::debug!(
+                        "Got completion suggestion: {}",
+                        suggestion.text.chars().take(50).collect::<String>()
+                    );
+
+                    *current_suggestion.write() = Some(suggestion.clone());
+                    {
+                        let mut suggestions = active_suggestions.write();
+                        suggestions.clear();
+                        for item in &result.suggestions {
+                            suggestions.insert(item.id.clone(), item.clone());
                         }
-                    });
+                    }
 
-                    *debounce_handle.write() = Some(handle);
+                    // Emit suggestion event using structured event type
+                    emit(InputCompletionEvent::Suggestion(suggestion.clone()));
                 }
+                Ok(result.suggestions)
+            }
+            Err(e) => {
+                log::warn!("Completion request failed: {}", e);
+                emit(InputCompletionEvent::Error(e.clone()));
+                Err(e)
             }
         }
     }
@@ -518,9 +640,37 @@ impl InputCompletionManager {
         }
     }
 
+    fn set_suggestion_origin(
+        &self,
+        suggestions: &[CompletionSuggestion],
+        context: &CompletionContext,
+    ) {
+        *self.suggestion_origin.write() = Some(SuggestionOrigin {
+            suggestion_ids: suggestions.iter().map(|item| item.id.clone()).collect(),
+            context: context.clone(),
+        });
+    }
+
     fn clear_suggestions(&self) {
         *self.current_suggestion.write() = None;
         self.active_suggestions.write().clear();
+        *self.suggestion_origin.write() = None;
+    }
+
+    /// Find an active suggestion without accepting it
+    fn find_suggestion(
+        &self,
+        suggestion_ref: &CompletionSuggestionRef,
+    ) -> Option<CompletionSuggestion> {
+        let current = self.current_suggestion.read().clone();
+        if suggestion_ref.suggestion_id.is_empty()
+            || current
+                .as_ref()
+                .is_some_and(|item| item.id == suggestion_ref.suggestion_id)
+        {
+            return current;
+        }
+        self.get_suggestion_by_id(&suggestion_ref.suggestion_id)
     }
 
     /// Accept current suggestion
@@ -567,6 +717,73 @@ impl InputCompletionManager {
         accepted
     }
 
+    /// Accept a suggestion and immediately request the next continuation.
+    ///
+    /// The accepted text is appended to the context the suggestion was generated
+    /// from (or the captured input buffer) and completion runs again from the new
+    /// end, emitting `Accept` followed by `Suggestion` so clients can follow the
+    /// growing passage. Returns `None` when the suggestion is no longer active.
+    pub async fn accept_and_continue(
+        &self,
+        suggestion_ref: CompletionSuggestionRef,
+    ) -> Result<Option<CompletionChainResult>, String> {
+        let Some(suggestion) = self.find_suggestion(&suggestion_ref) else {
+            return Ok(None);
+        };
+
+        let origin = self
+            .suggestion_origin
+            .read()
+            .as_ref()
+            .filter(|origin| origin.suggestion_ids.contains(&suggestion.id))
+            .map(|origin| origin.context.clone());
+        let context = match origin {
+            Some(context) => context,
+            None => {
+                let buffer = self.input_buffer.read().clone();
+                if buffer.is_empty() {
+                    return Err("No completion context to continue from".to_string());
+                }
+                CompletionContext {
+                    text: buffer,
+                    ime_state: Some(self.ime_monitor.get_state()),
+                    ..Default::default()
+                }
+            }
+        };
+        let next_context = continuation_context(&context, &suggestion)?;
+
+        let Some(accepted) = self.accept_suggestion_v2(suggestion_ref) else {
+            return Ok(None);
+        };
+
+        let config = self.config.read().clone();
+        let model = self.completion_service.select_model(&next_context, &config);
+        let result = self
+            .completion_service
+            .get_completion(&next_context, &model)
+            .await?;
+
+        if let Some(first) = result.suggestions.first() {
+            *self.current_suggestion.write() = Some(first.clone());
+            self.set_active_suggestions(&result.suggestions);
+            self.set_suggestion_origin(&result.suggestions, &next_context);
+            let _ = self.app_handle.emit(
+                "input-completion://event",
+                InputCompletionEvent::Suggestion(first.clone()),
+            );
+        }
+
+        Ok(Some(CompletionChainResult {
+            accepted,
+            text: next_context.text,
+            suggestions: result.suggestions,
+            latency_ms: result.latency_ms,
+            model: result.model,
+            cached: result.cached,
+        }))
+    }
+
     /// Dismiss current suggestion
     pub fn dismiss_suggestion(&self) {
         let current_id = self
@@ -629,8 +846,34 @@ impl InputCompletionManager {
         self.active_suggestions.read().values().cloned().collect()
     }
 
+    /// Focused app for a v2 request.
+    ///
+    /// An explicit `focused_app` wins. Otherwise it is detected, unless the request
+    /// names one of the app's own input surfaces.
+    fn resolve_focused_app(&self, request: &CompletionRequestV2) -> Option<FocusedAppContext> {
+        if request.focused_app.is_some() {
+            return request.focused_app.clone();
+        }
+        match request.surface {
+            None | Some(CompletionSurface::Generic) => {
+                focused_app::detect_focused_app(&self.app_handle)
+            }
+            Some(_) => None,
+        }
+    }
+
     /// Convert v2 request to internal completion context.
     fn request_to_context(&self, request: &CompletionRequestV2) -> CompletionContext {
+        let focused_app = self.resolve_focused_app(request);
+        let surface = match request.surface {
+            None | Some(CompletionSurface::Generic) => focused_app
+                .as_ref()
+                .and_then(|app| app.app_kind)
+                .map(FocusedAppKind::surface)
+                .or_else(|| request.surface.clone()),
+            Some(_) => request.surface.clone(),
+        };
+
         CompletionContext {
             text: request.text.clone(),
             text_after_cursor: None,
@@ -644,7 +887,8 @@ impl InputCompletionManager {
                 .clone()
                 .or_else(|| Some(self.ime_monitor.get_state())),
             mode: request.mode.clone(),
-            surface: request.surface.clone(),
+            surface,
+            focused_app,
         }
     }
 
@@ -664,6 +908,7 @@ impl InputCompletionManager {
                 .or_else(|| Some(self.ime_monitor.get_state())),
             mode: request.mode.clone(),
             surface: request.surface.clone(),
+            focused_app: None,
         }
     }
 
@@ -677,18 +922,20 @@ impl InputCompletionManager {
             .request_id
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-        let surface = request.surface.clone().unwrap_or_default();
         let context = self.request_to_context(&request);
+        let surface = context.surface.clone().unwrap_or_default();
         let mode = CompletionService::resolve_mode(&context);
 
+        let model = self.completion_service.select_model(&context, &config);
         let result = self
             .completion_service
-            .get_completion(&context, &config.model)
+            .get_completion(&context, &model)
             .await?;
 
         if let Some(first) = result.suggestions.first() {
             *self.current_suggestion.write() = Some(first.clone());
             self.set_active_suggestions(&result.suggestions);
+            self.set_suggestion_origin(&result.suggestions, &context);
             let _ = self.app_handle.emit(
                 "input-completion://event",
                 InputCompletionEvent::Suggestion(first.clone()),
@@ -723,14 +970,16 @@ impl InputCompletionManager {
         let context = self.request_v3_to_context(&request);
         let mode = CompletionService::resolve_mode(&context);
 
+        let model = self.completion_service.select_model(&context, &config);
         let result = self
             .completion_service
-            .get_completion(&context, &config.model)
+            .get_completion(&context, &model)
             .await?;
 
         if let Some(first) = result.suggestions.first() {
             *self.current_suggestion.write() = Some(first.clone());
             self.set_active_suggestions(&result.suggestions);
+            self.set_suggestion_origin(&result.suggestions, &context);
             let _ = self.app_handle.emit(
                 "input-completion://event",
                 InputCompletionEvent::Suggestion(first.clone()),
@@ -751,6 +1000,63 @@ impl InputCompletionManager {
         })
     }
 
+    /// Request a rewrite of the current line or selection.
+    ///
+    /// The resulting suggestion carries `replace_range` and `diff`, so accepting it
+    /// replaces the range instead of inserting at the cursor.
+    pub async fn trigger_rewrite(
+        &self,
+        request: CompletionRewriteRequest,
+    ) -> Result<CompletionResultV3, String> {
+        let config = self.config.read().clone();
+        let request_id = request
+            .request_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let surface = request.surface.clone().unwrap_or_default();
+        let context = CompletionContext {
+            text: format!(
+                "{}{}",
+                request.text_before.as_deref().unwrap_or(""),
+                request.original
+            ),
+            file_path: request.file_path.clone(),
+            language: request.language.clone(),
+            mode: request.mode.clone(),
+            surface: request.surface.clone(),
+            ..Default::default()
+        };
+        let mode = CompletionService::resolve_mode(&context);
+
+        let model = self.completion_service.select_model(&context, &config);
+        let result = self
+            .completion_service
+            .get_rewrite(&request, &model)
+            .await?;
+
+        if let Some(first) = result.suggestions.first() {
+            *self.current_suggestion.write() = Some(first.clone());
+            self.set_active_suggestions(&result.suggestions);
+            let _ = self.app_handle.emit(
+                "input-completion://event",
+                InputCompletionEvent::Suggestion(first.clone()),
+            );
+        } else {
+            self.clear_suggestions();
+        }
+
+        Ok(CompletionResultV3 {
+            request_id,
+            surface,
+            mode,
+            cursor_offset: Some(request.replace_range.end),
+            suggestions: result.suggestions,
+            latency_ms: result.latency_ms,
+            model: result.model,
+            cached: result.cached,
+        })
+    }
+
     /// Manually trigger completion for given text
     pub async fn trigger_completion(&self, text: &str) -> Result<CompletionResult, String> {
         let result = self
@@ -781,10 +1087,45 @@ impl InputCompletionManager {
             ime_state: Some(self.ime_monitor.get_state()),
             mode,
             surface: Some(surface),
+            focused_app: None,
         })
         .await
     }
 
+    /// Generate free-form text with the configured completion model
+    ///
+    /// `model` overrides the model configured for input completion.
+    pub async fn generate_text(
+        &self,
+        system_prompt: &str,
+        prompt: &str,
+        model: Option<CompletionModelConfig>,
+    ) -> Result<String, String> {
+        let model = model.unwrap_or_else(|| self.config.read().model.clone());
+        self.completion_service
+            .generate_text(system_prompt, prompt, &model)
+            .await
+    }
+
+    /// Generate free-form text, passing output to `on_delta` as it arrives
+    ///
+    /// `model` overrides the model configured for input completion.
+    pub async fn generate_text_streaming<F>(
+        &self,
+        system_prompt: &str,
+        prompt: &str,
+        model: Option<CompletionModelConfig>,
+        on_delta: F,
+    ) -> Result<String, String>
+    where
+        F: FnMut(&str) + Send,
+    {
+        let model = model.unwrap_or_else(|| self.config.read().model.clone());
+        self.completion_service
+            .generate_text_streaming(system_prompt, prompt, &model, on_delta)
+            .await
+    }
+
     /// Update configuration
     pub fn update_config(&self, config: CompletionConfig) {
         *self.config.write() = config;
@@ -803,11 +1144,14 @@ impl InputCompletionManager {
 
     /// Get completion status
     pub fn get_status(&self) -> CompletionStatus {
+        let degraded = self.degraded.read().clone();
         CompletionStatus {
             is_running: self.is_running(),
             ime_state: self.get_ime_state(),
             has_suggestion: self.current_suggestion.read().is_some(),
             buffer_length: self.input_buffer.read().len(),
+            degraded: degraded.degraded,
+            degraded_reason: degraded.reason,
         }
     }
 
@@ -901,6 +1245,8 @@ mod tests {
             ime_state: ImeState::default(),
             has_suggestion: false,
             buffer_length: 10,
+            degraded: false,
+            degraded_reason: None,
         };
 
         assert!(status.is_running);
@@ -1019,6 +1365,7 @@ mod tests {
             ime_state: None,
             mode: Some(CompletionMode::Code),
             surface: Some(CompletionSurface::Generic),
+            focused_app: None,
         };
 
         assert_eq!(context.language, Some("rust".to_string()));
@@ -1079,6 +1426,8 @@ mod tests {
             ime_state: ImeState::default(),
             has_suggestion: true,
             buffer_length: 25,
+            degraded: false,
+            degraded_reason: None,
         };
 
         let json = serde_json::to_string(&status).unwrap();
@@ -1173,4 +1522,86 @@ mod tests {
         assert!(manager.get_current_suggestion().is_none());
         assert!(manager.get_active_suggestions().is_empty());
     }
+    #[test]
+    fn test_continuation_context_appends_accepted_text() {
+        let context = CompletionContext {
+            text: "Once upon a time".to_string(),
+            cursor_offset: Some(16),
+            language: Some("markdown".to_string()),
+            mode: Some(CompletionMode::Chat),
+            ..Default::default()
+        };
+        let first = CompletionSuggestion::new(", there was".to_string(), 0.9, CompletionType::Line);
+
+        let next = continuation_context(&context, &first).expect("continuation");
+        assert_eq!(next.text, "Once upon a time, there was");
+        assert_eq!(next.cursor_offset, None);
+        assert_eq!(next.language.as_deref(), Some("markdown"));
+
+        // Chaining keeps growing the passage from the new end
+        let second = CompletionSuggestion::new(" a dragon.".to_string(), 0.8, CompletionType::Line);
+        let next = continuation_context(&next, &second).expect("continuation");
+        assert_eq!(next.text, "Once upon a time, there was a dragon.");
+
+        let mut rewrite =
+            CompletionSuggestion::new("Once".to_string(), 0.8, CompletionType::Rewrite);
+        rewrite.replace_range = Some(types::ReplaceRange { start: 0, end: 4 });
+        assert!(continuation_context(&context, &rewrite).is_err());
+    }
+
+    #[test]
+    fn test_process_key_event_accepts_and_debounces() {
+        let config = CompletionConfig::default();
+        let service = CompletionService::new();
+        let input_buffer = RwLock::new("hello".to_string());
+        let current_suggestion = RwLock::new(None);
+        let active_suggestions = RwLock::new(HashMap::new());
+        let last_key_timestamp = Arc::new(AtomicU64::new(0));
+        let events = parking_lot::Mutex::new(Vec::new());
+        let process = |key: &str, event_type: KeyEventType| {
+            let key_event = KeyEvent {
+                event_type,
+                key: key.to_string(),
+                char: None,
+                ctrl: false,
+                shift: false,
+                alt: false,
+                timestamp: 1_000,
+            };
+            InputCompletionManager::process_key_event(
+                &key_event,
+                &config,
+                &ImeState::default(),
+                &input_buffer,
+                &current_suggestion,
+                &active_suggestions,
+                &service,
+                &last_key_timestamp,
+                &|event| events.lock().push(event),
+            )
+        };
+
+        match process("O", KeyEventType::KeyRelease) {
+            KeyDecision::Debounced { debounce_ms, text } => {
+                assert_eq!(debounce_ms, config.trigger.debounce_ms);
+                assert_eq!(text, "hello");
+            }
+            other => panic!("expected debounce, got {:?}", other),
+        }
+
+        let suggestion = CompletionSuggestion::new(" world".to_string(), 0.9, CompletionType::Line);
+        *current_suggestion.write() = Some(suggestion.clone());
+        match process("Tab", KeyEventType::KeyPress) {
+            KeyDecision::Accepted {
+                suggestion: accepted,
+            } => assert_eq!(accepted.id, suggestion.id),
+            other => panic!("expected accept, got {:?}", other),
+        }
+        assert!(current_suggestion.read().is_none());
+        assert!(input_buffer.read().is_empty());
+        assert!(matches!(
+            events.lock().as_slice(),
+            [InputCompletionEvent::Accept(_)]
+        ));
+    }
 }
@@ -0,0 +1,249 @@
+//! Keystroke replay for debugging input completion
+//!
+//! Feeds a recorded sequence of key events through the same key handling as
+//! global capture and reports the decision, emitted events and completion
+//! request behind every step. Debounce timing follows the recorded timestamps
+//! rather than the wall clock, so a recording replays the same way every time;
+//! only the completion requests themselves reach the configured model.
+
+use super::types::{CompletionContext, CompletionSurface, KeyDecision};
+use super::{
+    CompletionConfig, CompletionService, CompletionSuggestion, ImeState, InputCompletionEvent,
+    InputCompletionManager, KeyEvent, KeyEventType,
+};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+/// Completion request fired once a step's debounce elapsed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayRequest {
+    /// Recorded time at which the debounce elapsed
+    pub fired_at: i64,
+    /// Text the completion was requested for
+    pub text: String,
+    /// Suggestions returned by the model
+    pub suggestions: Vec<CompletionSuggestion>,
+    /// Error returned instead of suggestions
+    pub error: Option<String>,
+}
+
+/// One replayed key event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStep {
+    /// Position in the recording
+    pub index: usize,
+    pub key: String,
+    pub event_type: KeyEventType,
+    pub timestamp: i64,
+    /// What the key handler decided
+    pub decision: KeyDecision,
+    /// Events emitted for this key, including those of its completion request
+    pub events: Vec<InputCompletionEvent>,
+    /// Whether a later debounced key cancelled this step's request
+    pub cancelled: bool,
+    /// Request fired after this step's debounce elapsed
+    pub request: Option<ReplayRequest>,
+}
+
+/// Result of replaying a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionReplay {
+    pub steps: Vec<ReplayStep>,
+    /// Input buffer after the last key
+    pub final_buffer: String,
+    /// Suggestion showing after the last key
+    pub final_suggestion: Option<CompletionSuggestion>,
+}
+
+/// Replay `keystrokes` against fresh completion state and a fresh cache
+///
+/// The IME is treated as idle and no focused app is detected, so requests use
+/// the generic surface. A request fires when a later key arrives after its
+/// debounce elapsed, or after the last key; its latency is not simulated.
+pub async fn replay_keystrokes(
+    keystrokes: Vec<KeyEvent>,
+    config: CompletionConfig,
+) -> CompletionReplay {
+    let completion_service = CompletionService::new();
+    let input_buffer = RwLock::new(String::new());
+    let current_suggestion = RwLock::new(None);
+    let active_suggestions = RwLock::new(HashMap::new());
+    let last_key_timestamp = Arc::new(AtomicU64::new(0));
+    let ime_state = ImeState::default();
+
+    let mut steps: Vec<ReplayStep> = Vec::with_capacity(keystrokes.len());
+    // Step waiting for its debounce, and the time it elapses
+    let mut pending: Option<(usize, i64)> = None;
+
+    for (index, key_event) in keystrokes.into_iter().enumerate() {
+        if let Some((step, fired_at)) =
+            pending.filter(|(_, fired_at)| *fired_at <= key_event.timestamp)
+        {
+            pending = None;
+            fire_request(
+                &mut steps[step],
+                fired_at,
+                &config,
+                &completion_service,
+                &current_suggestion,
+                &active_suggestions,
+            )
+            .await;
+        }
+
+        let events = Mutex::new(Vec::new());
+        let decision = InputCompletionManager::process_key_event(
+            &key_event,
+            &config,
+            &ime_state,
+            &input_buffer,
+            &current_suggestion,
+            &active_suggestions,
+            &completion_service,
+            &last_key_timestamp,
+            &|event| events.lock().push(event),
+        );
+
+        if let KeyDecision::Debounced { debounce_ms, .. } = &decision {
+            let fires_at = key_event.timestamp.saturating_add(*debounce_ms as i64);
+            if let Some((step, _)) = pending.replace((index, fires_at)) {
+                steps[step].cancelled = true;
+            }
+        }
+
+        steps.push(ReplayStep {
+            index,
+            key: key_event.key,
+            event_type: key_event.event_type,
+            timestamp: key_event.timestamp,
+            decision,
+            events: events.into_inner(),
+            cancelled: false,
+            request: None,
+        });
+    }
+
+    if let Some((step, fired_at)) = pending {
+        fire_request(
+            &mut steps[step],
+            fired_at,
+            &config,
+            &completion_service,
+            &current_suggestion,
+            &active_suggestions,
+        )
+        .await;
+    }
+
+    CompletionReplay {
+        steps,
+        final_buffer: input_buffer.into_inner(),
+        final_suggestion: current_suggestion.into_inner(),
+    }
+}
+
+/// Run the completion request of a debounced step and record it on the step
+async fn fire_request(
+    step: &mut ReplayStep,
+    fired_at: i64,
+    config: &CompletionConfig,
+    completion_service: &CompletionService,
+    current_suggestion: &RwLock<Option<CompletionSuggestion>>,
+    active_suggestions: &RwLock<HashMap<String, CompletionSuggestion>>,
+) {
+    let KeyDecision::Debounced { text, .. } = &step.decision else {
+        return;
+    };
+    let context = CompletionContext {
+        text: text.clone(),
+        ime_state: Some(ImeState::default()),
+        surface: Some(CompletionSurface::default()),
+        ..Default::default()
+    };
+
+    let events = Mutex::new(Vec::new());
+    let result = InputCompletionManager::request_debounced_completion(
+        &context,
+        config,
+        completion_service,
+        current_suggestion,
+        active_suggestions,
+        &|event| events.lock().push(event),
+    )
+    .await;
+
+    step.events.extend(events.into_inner());
+    step.request = Some(match result {
+        Ok(suggestions) => ReplayRequest {
+            fired_at,
+            text: context.text,
+            suggestions,
+            error: None,
+        },
+        Err(error) => ReplayRequest {
+            fired_at,
+            text: context.text,
+            suggestions: Vec::new(),
+            error: Some(error),
+        },
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(event_type: KeyEventType, key: &str, char: Option<char>, timestamp: i64) -> KeyEvent {
+        KeyEvent {
+            event_type,
+            key: key.to_string(),
+            char,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_decisions_without_requests() {
+        let mut config = CompletionConfig::default();
+        config.trigger.min_context_length = 100;
+
+        let keystrokes = vec![
+            key(KeyEventType::KeyPress, "H", Some('h'), 0),
+            key(KeyEventType::KeyRelease, "H", None, 40),
+            key(KeyEventType::KeyPress, "Space", Some(' '), 80),
+            key(KeyEventType::KeyPress, "Backspace", None, 120),
+            key(KeyEventType::KeyPress, "Tab", None, 160),
+        ];
+        let replay = replay_keystrokes(keystrokes, config).await;
+
+        let decisions: Vec<_> = replay.steps.iter().map(|step| &step.decision).collect();
+        assert!(matches!(
+            decisions[0],
+            KeyDecision::Buffered { buffer_len: 1 }
+        ));
+        assert!(matches!(
+            decisions[1],
+            KeyDecision::ContextTooShort { buffer_len: 1 }
+        ));
+        assert!(matches!(decisions[2], KeyDecision::SkipChar { ch: ' ' }));
+        assert!(matches!(
+            decisions[3],
+            KeyDecision::Backspace { buffer_len: 1 }
+        ));
+        assert!(matches!(decisions[4], KeyDecision::Ignored));
+
+        assert!(matches!(
+            replay.steps[3].events.as_slice(),
+            [InputCompletionEvent::Dismiss]
+        ));
+        assert!(replay.steps.iter().all(|step| step.request.is_none()));
+        assert_eq!(replay.final_buffer, "h");
+        assert!(replay.final_suggestion.is_none());
+    }
+}
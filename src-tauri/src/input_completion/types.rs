@@ -1,6 +1,8 @@
 //! Types for input completion module
 
 use super::ime_state::ImeState;
+use crate::text_diff::diff_words;
+pub use crate::text_diff::{DiffOp, DiffSegment};
 use serde::{Deserialize, Serialize};
 
 /// Input surface where completion is requested.
@@ -10,6 +12,12 @@ pub enum CompletionSurface {
     ChatInput,
     ChatWidget,
     LatexEditor,
+    /// Terminal or shell in another application
+    Terminal,
+    /// Code editor or IDE in another application
+    CodeEditor,
+    /// Document, note or email editor in another application
+    Document,
     Generic,
 }
 
@@ -35,6 +43,43 @@ impl Default for CompletionMode {
     }
 }
 
+/// Kind of the focused application, as reported by context awareness.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusedAppKind {
+    Terminal,
+    CodeEditor,
+    DocumentEditor,
+    NoteTaking,
+    Email,
+    Chat,
+    Browser,
+    Other,
+}
+
+impl FocusedAppKind {
+    /// Completion surface used when the request does not name one.
+    pub fn surface(self) -> CompletionSurface {
+        match self {
+            Self::Terminal => CompletionSurface::Terminal,
+            Self::CodeEditor => CompletionSurface::CodeEditor,
+            Self::DocumentEditor | Self::NoteTaking | Self::Email => CompletionSurface::Document,
+            Self::Chat | Self::Browser | Self::Other => CompletionSurface::Generic,
+        }
+    }
+}
+
+/// Focused application and document the completion is requested for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FocusedAppContext {
+    /// Application name (e.g. "Visual Studio Code")
+    pub app_name: Option<String>,
+    /// Application kind
+    pub app_kind: Option<FocusedAppKind>,
+    /// Document type: the file's language when known, otherwise its extension
+    pub document_type: Option<String>,
+}
+
 /// Context for requesting a completion
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CompletionContext {
@@ -58,6 +103,8 @@ pub struct CompletionContext {
     pub mode: Option<CompletionMode>,
     /// UI surface that triggered completion
     pub surface: Option<CompletionSurface>,
+    /// Focused application and document (if known)
+    pub focused_app: Option<FocusedAppContext>,
 }
 
 /// v2 completion request payload with explicit mode and surface.
@@ -79,6 +126,8 @@ pub struct CompletionRequestV2 {
     pub mode: Option<CompletionMode>,
     /// UI surface that triggered completion
     pub surface: Option<CompletionSurface>,
+    /// Focused application override; detected from context awareness when omitted
+    pub focused_app: Option<FocusedAppContext>,
 }
 
 /// v3 completion request payload with explicit before/after cursor semantics.
@@ -108,6 +157,43 @@ pub struct CompletionRequestV3 {
     pub conversation_digest: Option<String>,
 }
 
+/// Rewrite request for the current line or selection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionRewriteRequest {
+    /// Client-side request id for stale-response protection
+    pub request_id: Option<String>,
+    /// Current line or selection to rewrite
+    pub original: String,
+    /// Range of `original` in the full text, echoed back on the suggestion
+    pub replace_range: ReplaceRange,
+    /// Text before the range, used as context only
+    pub text_before: Option<String>,
+    /// Text after the range, used as context only
+    pub text_after: Option<String>,
+    /// Optional rewrite instruction (e.g. "fix grammar")
+    pub instruction: Option<String>,
+    /// File path (if editing a file)
+    pub file_path: Option<String>,
+    /// Programming language (if known)
+    pub language: Option<String>,
+    /// Completion mode hint
+    pub mode: Option<CompletionMode>,
+    /// UI surface that triggered the rewrite
+    pub surface: Option<CompletionSurface>,
+}
+
+/// Range of the original text replaced by an edit suggestion.
+///
+/// Offsets are supplied by the client and echoed back unchanged, so they use
+/// whatever unit the editor uses for its cursor offsets.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplaceRange {
+    /// Start offset (inclusive)
+    pub start: usize,
+    /// End offset (exclusive)
+    pub end: usize,
+}
+
 /// Minimal suggestion reference for v2 accept/dismiss actions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionSuggestionRef {
@@ -154,6 +240,23 @@ pub struct CompletionResultV3 {
     pub cached: bool,
 }
 
+/// Result of accepting a suggestion and requesting the next continuation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionChainResult {
+    /// Suggestion that was accepted
+    pub accepted: CompletionSuggestion,
+    /// Context text with the accepted suggestion appended
+    pub text: String,
+    /// Suggestions continuing from the end of `text`
+    pub suggestions: Vec<CompletionSuggestion>,
+    /// Request latency in milliseconds
+    pub latency_ms: u64,
+    /// Model used for completion
+    pub model: String,
+    /// Whether the result was cached
+    pub cached: bool,
+}
+
 /// Cursor position information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CursorPosition {
@@ -193,6 +296,12 @@ pub struct CompletionSuggestion {
     pub completion_type: CompletionType,
     /// Unique identifier for this suggestion
     pub id: String,
+    /// Range of the original text this suggestion replaces (rewrite suggestions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace_range: Option<ReplaceRange>,
+    /// Word-level diff from the replaced text to `text` (rewrite suggestions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<Vec<DiffSegment>>,
 }
 
 /// Type of completion suggestion
@@ -206,6 +315,8 @@ pub enum CompletionType {
     Word,
     /// Snippet with placeholders
     Snippet,
+    /// Replacement for an existing line or selection
+    Rewrite,
 }
 
 /// Status of the input completion system
@@ -219,6 +330,12 @@ pub struct CompletionStatus {
     pub has_suggestion: bool,
     /// Current input buffer length
     pub buffer_length: usize,
+    /// Whether global capture runs without the accessibility/input permission it needs
+    #[serde(default)]
+    pub degraded: bool,
+    /// Why completion is degraded
+    #[serde(default)]
+    pub degraded_reason: Option<String>,
 }
 
 /// Events emitted by the input completion system
@@ -241,6 +358,36 @@ pub enum InputCompletionEvent {
     Stopped,
 }
 
+/// What the key handler decided for one key event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KeyDecision {
+    /// Ignored while the IME is composing
+    ImeComposing,
+    /// Tab accepted the current suggestion
+    Accepted { suggestion: CompletionSuggestion },
+    /// Escape dismissed the current suggestion
+    Dismissed,
+    /// Character added to the input buffer
+    Buffered { buffer_len: usize },
+    /// Backspace removed a character and cleared suggestions
+    Backspace { buffer_len: usize },
+    /// Character ignored because a modifier was held
+    ModifierHeld,
+    /// Skip character buffered; suggestions cleared without triggering
+    SkipChar { ch: char },
+    /// Key press with nothing to do
+    Ignored,
+    /// Release ignored because the buffer does not end at a word boundary
+    NotAtWordBoundary,
+    /// Release ignored because the buffer is shorter than `min_context_length`
+    ContextTooShort { buffer_len: usize },
+    /// Suggestion served from the prefix cache without a request
+    PrefixCacheHit { suggestion: CompletionSuggestion },
+    /// Completion for `text` requested after `debounce_ms` without another debounced key
+    Debounced { debounce_ms: u64, text: String },
+}
+
 /// Statistics for the completion system
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CompletionStats {
@@ -268,6 +415,29 @@ pub struct CompletionStats {
     pub cache_stale_rejects: u64,
     /// Quality feedback stats
     pub feedback_stats: FeedbackStats,
+    /// Model used for the most recent completion request
+    #[serde(default)]
+    pub active_model: Option<String>,
+    /// Per-model acceptance tracking for automatic model selection
+    #[serde(default)]
+    pub model_performance: Vec<ModelPerformanceStats>,
+}
+
+/// Feedback-based performance of one model for a surface/language bucket
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModelPerformanceStats {
+    /// Surface/language bucket (e.g. "chat_input/*")
+    pub bucket: String,
+    /// Model key ("provider:model_id")
+    pub model: String,
+    /// Accepted suggestions (full, partial, or positive rating)
+    pub accepted: u64,
+    /// Rejected suggestions (quick dismiss or negative rating)
+    pub rejected: u64,
+    /// Accepted / (accepted + rejected)
+    pub acceptance_rate: f64,
+    /// Whether auto selection currently uses this model for the bucket
+    pub active: bool,
 }
 
 /// Feedback statistics for completion quality tracking
@@ -334,6 +504,23 @@ impl CompletionSuggestion {
             confidence,
             completion_type,
             id: uuid::Uuid::new_v4().to_string(),
+            replace_range: None,
+            diff: None,
+        }
+    }
+
+    /// Create a rewrite suggestion replacing `range` (holding `original`) with `replacement`
+    pub fn rewrite(
+        original: &str,
+        replacement: String,
+        range: ReplaceRange,
+        confidence: f64,
+    ) -> Self {
+        let diff = diff_words(original, &replacement);
+        Self {
+            replace_range: Some(range),
+            diff: Some(diff),
+            ..Self::new(replacement, confidence, CompletionType::Rewrite)
         }
     }
 }
@@ -374,6 +561,33 @@ mod tests {
         assert_eq!(suggestion.completion_type, CompletionType::Snippet);
     }
 
+    #[test]
+    fn test_completion_suggestion_rewrite() {
+        let range = ReplaceRange { start: 4, end: 13 };
+        let suggestion =
+            CompletionSuggestion::rewrite("teh quick", "the quick".to_string(), range, 0.7);
+
+        assert_eq!(suggestion.text, "the quick");
+        assert_eq!(suggestion.completion_type, CompletionType::Rewrite);
+        assert_eq!(suggestion.replace_range, Some(range));
+        let diff = suggestion.diff.unwrap();
+        assert_eq!(diff[0].op, DiffOp::Delete);
+        assert_eq!(diff[0].text, "teh");
+        assert_eq!(diff[1].op, DiffOp::Insert);
+        assert_eq!(diff[1].text, "the");
+    }
+
+    #[test]
+    fn test_append_suggestion_omits_rewrite_fields() {
+        let suggestion = CompletionSuggestion::new("world".to_string(), 0.9, CompletionType::Line);
+        let json = serde_json::to_value(&suggestion).unwrap();
+        assert!(json.get("replace_range").is_none());
+        assert!(json.get("diff").is_none());
+
+        let parsed: CompletionSuggestion = serde_json::from_value(json).unwrap();
+        assert!(parsed.replace_range.is_none());
+    }
+
     #[test]
     fn test_completion_context_default() {
         let context = CompletionContext::default();
@@ -407,6 +621,7 @@ mod tests {
             ime_state: None,
             mode: Some(CompletionMode::Code),
             surface: Some(CompletionSurface::Generic),
+            focused_app: None,
         };
 
         assert_eq!(context.text, "fn main()");
@@ -418,6 +633,25 @@ mod tests {
         assert_eq!(pos.column, Some(5));
     }
 
+    #[test]
+    fn test_focused_app_kind_surface() {
+        assert_eq!(
+            FocusedAppKind::Terminal.surface(),
+            CompletionSurface::Terminal
+        );
+        assert_eq!(
+            FocusedAppKind::NoteTaking.surface(),
+            CompletionSurface::Document
+        );
+        assert_eq!(
+            FocusedAppKind::Browser.surface(),
+            CompletionSurface::Generic
+        );
+
+        let json = serde_json::to_string(&FocusedAppKind::CodeEditor).unwrap();
+        assert_eq!(json, "\"code_editor\"");
+    }
+
     #[test]
     fn test_cursor_position() {
         let pos = CursorPosition {
@@ -476,6 +710,8 @@ mod tests {
             ime_state: ImeState::default(),
             has_suggestion: true,
             buffer_length: 10,
+            degraded: false,
+            degraded_reason: None,
         };
 
         assert!(status.is_running);
@@ -514,6 +750,8 @@ mod tests {
             cache_hits_normalized: 12,
             cache_stale_rejects: 3,
             feedback_stats: FeedbackStats::default(),
+            active_model: Some("ollama:qwen2.5-coder:0.5b".to_string()),
+            model_performance: Vec::new(),
         };
 
         assert_eq!(stats.total_requests, 100);
@@ -589,6 +827,7 @@ mod tests {
             ime_state: None,
             mode: Some(CompletionMode::Code),
             surface: Some(CompletionSurface::ChatInput),
+            focused_app: None,
         };
 
         let json = serde_json::to_string(&context).unwrap();
@@ -629,6 +868,8 @@ mod tests {
             ime_state: ImeState::default(),
             has_suggestion: false,
             buffer_length: 5,
+            degraded: false,
+            degraded_reason: None,
         };
 
         let json = serde_json::to_string(&status).unwrap();
@@ -646,6 +887,7 @@ mod tests {
             CompletionType::Block,
             CompletionType::Word,
             CompletionType::Snippet,
+            CompletionType::Rewrite,
         ];
 
         for t in types {
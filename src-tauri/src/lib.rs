@@ -2,6 +2,7 @@
 //!
 //! This is the main entry point for the Tauri desktop application.
 
+mod accessibility;
 mod assistant_bubble;
 mod awareness;
 mod chat_runtime;
@@ -13,18 +14,25 @@ mod external_agent;
 mod http;
 mod input_completion;
 mod jupyter;
+mod logging;
 mod mcp;
+mod notifications;
+mod operations;
 mod plugin;
 mod port_utils;
 mod process;
+mod resource_governor;
 mod sandbox;
 mod scheduler;
 mod screen_recording;
 mod screenshot;
 mod selection;
+mod shutdown;
 mod skill;
 mod skill_seekers;
 mod speedpass_runtime;
+mod text_diff;
+mod token_estimate;
 mod tray;
 mod workflow_runtime;
 
@@ -390,6 +398,8 @@ pub fn run() {
 
     builder
         .setup(|app| {
+            // The log plugin attaches with a permissive max level; narrow it to the runtime filter
+            logging::apply_max_level();
             log::info!("Cognia application starting...");
 
             // Ensure Ctrl+C (or terminal close) performs graceful teardown instead of abrupt kill
@@ -470,7 +480,13 @@ pub fn run() {
                 .app_data_dir()
                 .expect("Failed to get app data directory");
 
-            // Initialize vector store state (local JSON persistence)
+            // Restore offline mode before anything touches the network
+            http::set_offline_mode(
+                http::NetworkSettings::load(&app_data_dir.join(http::NETWORK_SETTINGS_FILE))
+                    .offline_mode,
+            );
+
+            // Initialize vector store state (local JSON or binary persistence)
             let vector_path = app_data_dir.join("vector_store.json");
             let vector_state = Arc::new(
                 VectorStoreState::new(vector_path)
@@ -478,6 +494,33 @@ pub fn run() {
             );
             app.manage(vector_state);
 
+            // Shared concurrency budget for batch operations across subsystems
+            resource_governor::global().set_budget(
+                resource_governor::GovernorSettings::load(
+                    &app_data_dir.join(resource_governor::GOVERNOR_SETTINGS_FILE),
+                )
+                .budget,
+            );
+            app.manage(resource_governor::global().clone());
+
+            // Registry for long-running background operations
+            app.manage(operations::global().clone());
+
+            // Notification preferences consulted before showing desktop notifications
+            app.manage(notifications::NotificationCenter::load(
+                app_data_dir.join("notification_preferences.json"),
+            ));
+
+            // Show notifications held back by quiet hours once they are over (every 30 seconds)
+            let handle_for_digest = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+                loop {
+                    interval.tick().await;
+                    notifications::flush_digest(&handle_for_digest);
+                }
+            });
+
             // Initialize MCP Manager
             let sandbox_data_dir = app_data_dir.clone();
             let mcp_manager = McpManager::new(app.handle().clone(), app_data_dir.clone());
@@ -512,6 +555,11 @@ pub fn run() {
             app.manage(screenshot_manager);
 
             // Initialize OCR Manager (multi-provider support)
+            if let Ok(tessdata_dir) =
+                commands::storage::model_download::get_tessdata_dir(app.handle())
+            {
+                screenshot::providers::tesseract::set_app_tessdata_dir(tessdata_dir);
+            }
             let ocr_state = commands::media::ocr::OcrState::new();
             app.manage(ocr_state);
             log::info!("OCR manager initialized with Windows OCR provider");
@@ -545,10 +593,17 @@ pub fn run() {
 
             // Initialize Context Manager
             let context_manager = ContextManager::new();
+            accessibility::notify_if_degraded(
+                app.handle(),
+                "context",
+                context_manager.degraded_status(),
+            );
             app.manage(context_manager);
 
             // Initialize Awareness Manager
-            let awareness_manager = AwarenessManager::new();
+            let awareness_manager = AwarenessManager::with_settings_path(
+                app_data_dir.join(awareness::AWARENESS_SETTINGS_FILE),
+            );
             app.manage(awareness_manager);
 
             // Initialize Input Completion Manager
@@ -847,8 +902,22 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 match SandboxState::new(sandbox_config_path).await {
                     Ok(sandbox_state) => {
+                        let selection_shortcut = sandbox_state
+                            .config
+                            .read()
+                            .await
+                            .execute_selection_shortcut
+                            .clone();
                         handle_for_sandbox.manage(sandbox_state);
                         log::info!("Sandbox state initialized");
+
+                        if let Err(e) = commands::devtools::sandbox::bind_execute_selection_shortcut(
+                            &handle_for_sandbox,
+                            None,
+                            selection_shortcut.as_deref(),
+                        ) {
+                            log::error!("Failed to register run selection shortcut: {}", e);
+                        }
                     }
                     Err(e) => {
                         log::error!("Failed to initialize sandbox state: {}", e);
@@ -877,6 +946,33 @@ pub fn run() {
             app.manage(scheduler_state);
             log::info!("System scheduler state initialized");
 
+            // Periodic scheduler run-history check for task-run notifications (every 30 seconds)
+            let handle_for_scheduler = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+                loop {
+                    interval.tick().await;
+                    let scheduler_state = handle_for_scheduler.state::<SchedulerState>();
+                    let notifications = match scheduler_state.collect_run_notifications().await {
+                        Ok(notifications) => notifications,
+                        Err(e) => {
+                            log::debug!("Scheduler run-history check failed: {}", e);
+                            continue;
+                        }
+                    };
+                    for notification in notifications {
+                        notifications::notify(
+                            &handle_for_scheduler,
+                            notifications::NotificationCategory::Scheduler,
+                            notifications::NotificationPayload::new(
+                                notification.title,
+                                notification.body,
+                            ),
+                        );
+                    }
+                }
+            });
+
             // Initialize Workflow Runtime State
             let workflow_runtime_db_path = app_data_dir.join("workflow_runtime.db");
             let workflow_runtime_state = WorkflowRuntimeState::from_db_path(
@@ -1013,13 +1109,19 @@ pub fn run() {
             commands::providers::mcp::mcp_get_servers,
             commands::providers::mcp::mcp_get_server,
             commands::providers::mcp::mcp_add_server,
+            commands::providers::mcp::mcp_import_config,
+            commands::providers::mcp::mcp_export_config,
             commands::providers::mcp::mcp_remove_server,
             commands::providers::mcp::mcp_update_server,
             commands::providers::mcp::mcp_connect_server,
             commands::providers::mcp::mcp_disconnect_server,
             commands::providers::mcp::mcp_call_tool,
             commands::providers::mcp::mcp_call_tool_from_ui,
+            commands::providers::mcp::mcp_call_tool_and_index,
             commands::providers::mcp::mcp_get_all_tools,
+            commands::providers::mcp::mcp_get_tool_usage,
+            commands::providers::mcp::mcp_pin_tool,
+            commands::providers::mcp::mcp_unpin_tool,
             commands::providers::mcp::mcp_read_resource,
             commands::providers::mcp::mcp_get_prompt,
             commands::providers::mcp::mcp_reload_config,
@@ -1030,6 +1132,8 @@ pub fn run() {
             commands::providers::mcp::mcp_test_connection,
             commands::providers::mcp::mcp_set_log_level,
             commands::providers::mcp::mcp_respond_sampling,
+            commands::providers::mcp::mcp_approve_sampling,
+            commands::providers::mcp::mcp_deny_sampling,
             commands::providers::mcp::mcp_set_roots,
             commands::providers::mcp::mcp_get_roots,
             commands::providers::mcp::mcp_list_resource_templates,
@@ -1042,6 +1146,7 @@ pub fn run() {
             commands::providers::mcp::mcp_is_server_connected,
             commands::providers::mcp::mcp_set_server_enabled,
             commands::providers::mcp::mcp_set_server_auto_start,
+            commands::providers::mcp::mcp_set_server_allow_sampling,
             commands::providers::mcp::mcp_get_config_path,
             commands::providers::mcp::mcp_get_full_config,
             commands::providers::mcp::mcp_shutdown,
@@ -1085,6 +1190,8 @@ pub fn run() {
             commands::providers::local_provider::local_provider_test_connection,
             // Vector (local) commands
             commands::storage::vector::vector_create_collection,
+            commands::storage::vector::vector_create_collection_for_model,
+            commands::storage::vector::vector_benchmark_embedding_models,
             commands::storage::vector::vector_delete_collection,
             commands::storage::vector::vector_rename_collection,
             commands::storage::vector::vector_truncate_collection,
@@ -1093,12 +1200,21 @@ pub fn run() {
             commands::storage::vector::vector_list_collections,
             commands::storage::vector::vector_get_collection,
             commands::storage::vector::vector_upsert_points,
+            commands::storage::vector::vector_embed_and_upsert,
+            commands::storage::vector::vector_embed_and_upsert_async,
             commands::storage::vector::vector_delete_points,
             commands::storage::vector::vector_delete_all_points,
             commands::storage::vector::vector_get_points,
             commands::storage::vector::vector_search_points,
+            commands::storage::vector::vector_find_similar,
+            commands::storage::vector::vector_score_histogram,
+            commands::storage::vector::vector_get_search_config,
+            commands::storage::vector::vector_set_search_config,
             commands::storage::vector::vector_scroll_points,
+            commands::storage::vector::vector_create_payload_index,
+            commands::storage::vector::vector_drop_payload_index,
             commands::storage::vector::vector_stats,
+            commands::storage::vector::vector_migrate_storage_format,
             // Convex cloud sync commands
             commands::cloud::convex::convex_get_config,
             commands::cloud::convex::convex_set_config,
@@ -1132,6 +1248,7 @@ pub fn run() {
             commands::window::selection::selection_stop,
             commands::window::selection::selection_release_stuck_keys,
             commands::window::selection::selection_get_text,
+            commands::window::selection::selection_get_text_with_source,
             commands::window::selection::selection_show_toolbar,
             commands::window::selection::selection_hide_toolbar,
             commands::window::selection::selection_is_toolbar_visible,
@@ -1162,6 +1279,25 @@ pub fn run() {
             commands::window::selection::selection_get_history_stats,
             commands::window::selection::selection_search_history_by_time,
             commands::window::selection::selection_clear_history,
+            commands::window::selection::selection_get_history_entry,
+            commands::window::selection::selection_pin_history_entry,
+            commands::window::selection::selection_unpin_history_entry,
+            commands::window::selection::selection_get_pinned_history,
+            commands::window::selection::selection_get_history_retention,
+            commands::window::selection::selection_set_history_retention,
+            commands::window::selection::selection_trim_history,
+            commands::window::selection::history_search_semantic,
+            commands::window::selection::selection_ai_process_history,
+            commands::window::selection::selection_translate,
+            commands::window::selection::selection_clear_translation_cache,
+            commands::window::selection::selection_record_macro_start,
+            commands::window::selection::selection_record_macro_stop,
+            commands::window::selection::selection_record_macro_cancel,
+            commands::window::selection::selection_record_macro_step,
+            commands::window::selection::selection_is_recording_macro,
+            commands::window::selection::selection_list_macros,
+            commands::window::selection::selection_delete_macro,
+            commands::window::selection::selection_run_macro,
             commands::window::selection::selection_export_history,
             commands::window::selection::selection_import_history,
             // Clipboard history commands
@@ -1171,10 +1307,18 @@ pub fn run() {
             commands::window::clipboard_commands::clipboard_pin_entry,
             commands::window::clipboard_commands::clipboard_unpin_entry,
             commands::window::clipboard_commands::clipboard_delete_entry,
+            commands::window::clipboard_commands::clipboard_pin_entries,
+            commands::window::clipboard_commands::clipboard_unpin_entries,
+            commands::window::clipboard_commands::clipboard_delete_entries,
+            commands::window::clipboard_commands::clipboard_convert_entry,
             commands::window::clipboard_commands::clipboard_clear_unpinned,
             commands::window::clipboard_commands::clipboard_clear_all,
             commands::window::clipboard_commands::clipboard_copy_entry,
             commands::window::clipboard_commands::clipboard_check_update,
+            commands::window::clipboard_commands::clipboard_get_retention_policy,
+            commands::window::clipboard_commands::clipboard_set_retention_policy,
+            commands::window::clipboard_commands::clipboard_enforce_retention,
+            commands::window::clipboard_commands::clipboard_get_retention_stats,
             // Clipboard context awareness commands
             commands::window::clipboard_commands::clipboard_analyze_content,
             commands::window::clipboard_commands::clipboard_get_current_with_analysis,
@@ -1182,6 +1326,7 @@ pub fn run() {
             commands::window::clipboard_commands::clipboard_write_text,
             commands::window::clipboard_commands::clipboard_read_text,
             commands::window::clipboard_commands::clipboard_write_html,
+            commands::window::clipboard_commands::clipboard_paste_plain,
             commands::window::clipboard_commands::clipboard_clear,
             commands::window::clipboard_commands::clipboard_get_suggested_actions,
             commands::window::clipboard_commands::clipboard_extract_entities,
@@ -1196,6 +1341,7 @@ pub fn run() {
             // Text type detection & toolbar config
             commands::window::smart_selection_commands::selection_detect_text_type,
             commands::window::selection::selection_get_toolbar_config,
+            commands::window::selection::selection_set_toolbar_theme,
             commands::window::selection::selection_get_stats_summary,
             commands::window::selection::selection_time_since_last_detection,
             commands::window::selection::selection_get_last_text,
@@ -1233,6 +1379,8 @@ pub fn run() {
             // Windows OCR commands (legacy)
             commands::media::screenshot::screenshot_ocr_windows,
             commands::media::screenshot::screenshot_get_ocr_languages,
+            commands::media::screenshot::screenshot_install_ocr_language,
+            commands::media::screenshot::screenshot_remove_ocr_language,
             commands::media::screenshot::screenshot_ocr_is_available,
             commands::media::screenshot::screenshot_ocr_is_language_available,
             commands::media::screenshot::screenshot_ocr_with_language,
@@ -1256,6 +1404,8 @@ pub fn run() {
             commands::media::screenshot::screenshot_get_windows_with_thumbnails,
             commands::media::screenshot::screenshot_capture_window_by_hwnd,
             commands::media::screenshot::screenshot_capture_window_by_hwnd_with_history,
+            commands::media::screenshot::screenshot_capture_focused_window,
+            commands::media::screenshot::screenshot_capture_window_under_cursor,
             commands::media::screenshot::screenshot_calculate_snap,
             commands::media::screenshot::screenshot_get_snap_config,
             commands::media::screenshot::screenshot_set_snap_config,
@@ -1273,6 +1423,7 @@ pub fn run() {
             commands::media::screenshot::screenshot_annotator_import,
             // Detailed OCR commands
             commands::media::screenshot::screenshot_ocr_extract_detailed,
+            commands::media::screenshot::screenshot_capture_and_ai,
             commands::media::screenshot::screenshot_get_current_ocr_language,
             commands::media::screenshot::screenshot_is_ocr_available,
             commands::media::screenshot::screenshot_get_ocr_engine_languages,
@@ -1281,16 +1432,23 @@ pub fn run() {
             commands::context::context::context_get_window,
             commands::context::context::context_get_app,
             commands::context::context::context_get_file,
+            commands::context::context::context_watch_file,
+            commands::context::context::context_unwatch_file,
+            commands::context::context::context_get_watched_files,
             commands::context::context::context_get_browser,
             commands::context::context::context_get_browser_suggested_actions,
             commands::context::context::context_get_editor,
+            commands::context::context::context_get_active_document,
             commands::context::context::context_is_code_editor,
             commands::context::context::context_get_all_windows,
+            commands::context::context::context_snapshot_layout,
+            commands::context::context::context_restore_layout,
             commands::context::context::context_clear_cache,
             commands::context::context::context_find_windows_by_title,
             commands::context::context::context_find_windows_by_process,
             commands::context::context::context_set_cache_duration,
             commands::context::context::context_get_cache_duration,
+            commands::context::context::context_get_status,
             commands::context::context::context_analyze_ui_automation,
             commands::context::context::context_get_text_at,
             commands::context::context::context_get_element_at,
@@ -1317,11 +1475,16 @@ pub fn run() {
             commands::context::awareness::awareness_get_all_app_usage_stats,
             commands::context::awareness::awareness_get_today_usage_summary,
             commands::context::awareness::awareness_get_daily_usage_summary,
+            commands::context::awareness::awareness_generate_daily_report,
+            commands::context::awareness::awareness_export_focus_sessions,
             commands::context::awareness::awareness_clear_focus_history,
             // Activity tracker extended commands
             commands::context::awareness::awareness_get_activities_by_type,
             commands::context::awareness::awareness_get_activities_in_range,
             commands::context::awareness::awareness_get_activities_by_application,
+            commands::context::awareness::awareness_search_activities_by_tag,
+            commands::context::awareness::awareness_get_tag_patterns,
+            commands::context::awareness::awareness_set_tag_patterns,
             commands::context::awareness::awareness_get_activity_stats,
             commands::context::awareness::awareness_set_activity_tracking_enabled,
             commands::context::awareness::awareness_is_activity_tracking_enabled,
@@ -1335,10 +1498,13 @@ pub fn run() {
             // Focus tracker extended commands
             commands::context::awareness::awareness_get_all_focus_sessions,
             commands::context::awareness::awareness_get_focus_session_count,
+            commands::context::recent::recent_items,
             // Sandbox commands
             commands::devtools::sandbox::sandbox_execute,
             commands::devtools::sandbox::sandbox_cancel_execution,
+            commands::devtools::sandbox::sandbox_extend_timeout,
             commands::devtools::sandbox::sandbox_execute_streaming,
+            commands::devtools::sandbox::sandbox_execute_selection,
             commands::devtools::sandbox::sandbox_get_status,
             commands::devtools::sandbox::sandbox_preflight,
             commands::devtools::sandbox::sandbox_get_config,
@@ -1346,7 +1512,10 @@ pub fn run() {
             commands::devtools::sandbox::sandbox_get_runtimes,
             commands::devtools::sandbox::sandbox_get_languages,
             commands::devtools::sandbox::sandbox_check_runtime,
+            commands::devtools::sandbox::sandbox_describe_runtimes,
             commands::devtools::sandbox::sandbox_prepare_language,
+            commands::devtools::sandbox::sandbox_check_syntax,
+            commands::devtools::sandbox::sandbox_highlight,
             commands::devtools::sandbox::sandbox_quick_execute,
             commands::devtools::sandbox::sandbox_execute_with_stdin,
             commands::devtools::sandbox::sandbox_toggle_language,
@@ -1394,7 +1563,11 @@ pub fn run() {
             commands::devtools::sandbox::sandbox_get_all_categories,
             commands::devtools::sandbox::sandbox_get_db_size,
             commands::devtools::sandbox::sandbox_vacuum_db,
+            commands::devtools::sandbox::sandbox_check_integrity,
+            commands::devtools::sandbox::sandbox_repair_db,
             commands::devtools::sandbox::sandbox_execute_with_options,
+            commands::devtools::sandbox::sandbox_benchmark,
+            commands::devtools::sandbox::sandbox_list_benchmarks,
             commands::devtools::sandbox::sandbox_get_all_languages,
             commands::devtools::sandbox::sandbox_get_available_languages,
             commands::devtools::sandbox::sandbox_update_session,
@@ -1505,6 +1678,33 @@ pub fn run() {
             commands::system::proxy::proxy_http_request,
             commands::system::proxy::set_backend_proxy,
             commands::system::proxy::get_backend_proxy,
+            commands::system::proxy::set_offline_mode,
+            commands::system::proxy::get_offline_mode,
+            commands::system::resource_governor::resource_governor_get_status,
+            commands::system::resource_governor::resource_governor_set_budget,
+            // Notification preferences commands
+            commands::system::notifications::notification_get_preferences,
+            commands::system::notifications::notification_set_preferences,
+            commands::system::notifications::notification_send,
+            // Operation commands
+            commands::system::operations::operation_get,
+            commands::system::operations::operation_list,
+            commands::system::operations::operation_cancel,
+            // Text diff commands
+            commands::system::text_diff::text_diff,
+            // Token estimation commands
+            commands::system::tokens::tokens_estimate,
+            // Runtime log control commands
+            commands::system::logging::log_get_levels,
+            commands::system::logging::log_set_level,
+            commands::system::logging::log_get_logs,
+            // Privacy commands
+            commands::system::privacy::privacy_purge,
+            // Settings aggregation commands
+            commands::system::settings::settings_get_all,
+            commands::system::settings::settings_update,
+            // Setup diagnostics commands
+            commands::system::setup::setup_check_dependencies,
             // Screen recording commands
             commands::media::screen_recording::recording_get_status,
             commands::media::screen_recording::recording_get_duration,
@@ -1520,6 +1720,17 @@ pub fn run() {
             commands::media::screen_recording::recording_get_monitors,
             commands::media::screen_recording::recording_check_ffmpeg,
             commands::media::screen_recording::recording_get_audio_devices,
+            commands::media::screen_recording::recording_add_marker,
+            commands::media::screen_recording::recording_get_markers,
+            commands::media::screen_recording::recording_export_markers,
+            commands::media::screen_recording::recording_trim,
+            commands::media::screen_recording::recording_start_replay,
+            commands::media::screen_recording::recording_stop_replay,
+            commands::media::screen_recording::recording_get_replay_status,
+            commands::media::screen_recording::recording_save_replay,
+            commands::media::screen_recording::recording_get_thumbnail,
+            commands::media::screen_recording::recording_get_filmstrip,
+            commands::media::screen_recording::recording_regenerate_thumbnail,
             commands::media::screen_recording::recording_get_history,
             commands::media::screen_recording::recording_delete,
             commands::media::screen_recording::recording_clear_history,
@@ -1727,15 +1938,29 @@ pub fn run() {
             commands::devtools::vcs::vcs_blame,
             // Process management commands
             commands::system::process::process_list,
+            commands::system::process::process_list_snapshot,
             commands::system::process::process_get,
+            commands::system::process::process_get_env,
             commands::system::process::process_start,
+            commands::system::process::process_start_streaming,
+            commands::system::process::process_stop_stream,
             commands::system::process::process_terminate,
+            commands::system::process::process_terminate_tree,
+            commands::system::process::process_suspend,
+            commands::system::process::process_resume,
+            commands::system::process::process_wait,
             commands::system::process::process_start_batch,
             commands::system::process::process_terminate_batch,
             commands::system::process::process_start_batch_async,
             commands::system::process::process_terminate_batch_async,
             commands::system::process::process_get_operation,
             commands::system::process::process_list_operations,
+            commands::system::process::process_watch_memory,
+            commands::system::process::process_unwatch_memory,
+            commands::system::process::process_list_memory_watches,
+            commands::system::process::process_start_sampling,
+            commands::system::process::process_stop_sampling,
+            commands::system::process::process_get_history,
             commands::system::process::process_get_config,
             commands::system::process::process_update_config,
             commands::system::process::process_is_allowed,
@@ -1759,6 +1984,8 @@ pub fn run() {
             commands::system::tray::tray_get_all_item_ids,
             // Academic mode commands
             commands::academic::academic_search,
+            commands::academic::academic_clear_search_cache,
+            commands::academic::academic_set_search_cache_ttl,
             commands::academic::academic_search_provider,
             commands::academic::academic_get_paper,
             commands::academic::academic_get_citations,
@@ -1770,6 +1997,7 @@ pub fn run() {
             commands::academic::academic_remove_from_library,
             commands::academic::academic_get_library_papers,
             commands::academic::academic_update_paper,
+            commands::academic::academic_update_reading_progress,
             commands::academic::academic_get_paper_by_id,
             commands::academic::academic_create_collection,
             commands::academic::academic_update_collection,
@@ -1790,9 +2018,13 @@ pub fn run() {
             commands::academic::academic_get_statistics,
             commands::academic::academic_generate_knowledge_map,
             commands::academic::academic_generate_knowledge_map_from_content,
+            commands::academic::academic_generate_knowledge_map_stream,
+            commands::academic::academic_cancel_knowledge_map_generation,
             commands::academic::academic_generate_mind_map,
             commands::academic::academic_generate_mind_map_from_content,
             commands::academic::academic_extract_pdf_content,
+            commands::academic::academic_cancel_pdf_extraction,
+            commands::academic::academic_index_pdf,
             // Plugin system commands
             commands::extensions::plugin::plugin_python_initialize,
             commands::extensions::plugin::plugin_get_directory,
@@ -2015,6 +2247,7 @@ pub fn run() {
             commands::input_completion::input_completion_get_suggestion,
             commands::input_completion::input_completion_accept,
             commands::input_completion::input_completion_accept_v2,
+            commands::input_completion::input_completion_accept_and_continue,
             commands::input_completion::input_completion_dismiss,
             commands::input_completion::input_completion_dismiss_v2,
             commands::input_completion::input_completion_get_status,
@@ -2023,14 +2256,18 @@ pub fn run() {
             commands::input_completion::input_completion_trigger,
             commands::input_completion::input_completion_trigger_v2,
             commands::input_completion::input_completion_trigger_v3,
+            commands::input_completion::input_completion_rewrite,
             commands::input_completion::input_completion_is_running,
             commands::input_completion::input_completion_get_stats,
             commands::input_completion::input_completion_reset_stats,
             commands::input_completion::input_completion_clear_cache,
             commands::input_completion::input_completion_test_connection,
             commands::input_completion::input_completion_submit_feedback,
+            #[cfg(feature = "completion-replay")]
+            commands::input_completion::completion_replay,
             // System scheduler commands
             commands::scheduler::scheduler_get_capabilities,
+            commands::scheduler::scheduler_get_usage,
             commands::scheduler::scheduler_is_available,
             commands::scheduler::scheduler_is_elevated,
             commands::scheduler::scheduler_create_task,
@@ -2041,6 +2278,11 @@ pub fn run() {
             commands::scheduler::scheduler_enable_task,
             commands::scheduler::scheduler_disable_task,
             commands::scheduler::scheduler_run_task_now,
+            commands::scheduler::scheduler_get_run_history,
+            commands::scheduler::scheduler_set_run_history_limit,
+            commands::scheduler::scheduler_preview_cron,
+            commands::scheduler::scheduler_export_tasks,
+            commands::scheduler::scheduler_import_tasks,
             commands::scheduler::scheduler_cancel_confirmation,
             commands::scheduler::scheduler_get_pending_confirmations,
             commands::scheduler::scheduler_request_elevation,
@@ -2126,29 +2368,10 @@ pub fn run() {
 /// - LogDir: Persistent log files with rotation for production debugging
 fn build_log_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
     tauri_plugin_log::Builder::new()
-        // Set default log level based on build mode
-        .level(if cfg!(debug_assertions) {
-            log::LevelFilter::Debug
-        } else {
-            log::LevelFilter::Info
-        })
-        // Configure per-module log levels
-        .level_for("app_lib", log::LevelFilter::Debug)
-        .level_for("app_lib::mcp", log::LevelFilter::Debug)
-        .level_for("app_lib::commands", log::LevelFilter::Debug)
-        .level_for("app_lib::selection", log::LevelFilter::Debug)
-        .level_for("app_lib::screenshot", log::LevelFilter::Debug)
-        .level_for("app_lib::sandbox", log::LevelFilter::Debug)
-        .level_for("app_lib::awareness", log::LevelFilter::Debug)
-        .level_for("app_lib::context", log::LevelFilter::Debug)
-        .level_for("app_lib::screen_recording", log::LevelFilter::Debug)
-        // Filter out noisy third-party crate logs
-        .level_for("hyper", log::LevelFilter::Warn)
-        .level_for("reqwest", log::LevelFilter::Warn)
-        .level_for("tao", log::LevelFilter::Warn)
-        .level_for("wry", log::LevelFilter::Warn)
-        .level_for("tokio", log::LevelFilter::Warn)
-        .level_for("tracing", log::LevelFilter::Warn)
+        // Levels are resolved at runtime by the logging module's filter
+        // (defaults: Info in debug builds, Warn in release, with per-module overrides)
+        .level(log::LevelFilter::Trace)
+        .filter(logging::is_enabled)
         // Configure log targets
         .targets([
             // Console output (stdout)
@@ -2159,6 +2382,10 @@ fn build_log_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
             Target::new(TargetKind::LogDir {
                 file_name: Some("cognia".to_string()),
             }),
+            // In-memory tail for the log viewer
+            Target::new(TargetKind::Dispatch(
+                tauri_plugin_log::fern::Dispatch::new().chain(logging::buffer_logger()),
+            )),
         ])
         // Log rotation: keep all rotated files instead of discarding
         .rotation_strategy(RotationStrategy::KeepAll)
@@ -2530,7 +2757,18 @@ fn perform_full_cleanup(app: &tauri::AppHandle) {
         }
     }
 
-    // 3. Stop screen recording and shutdown MCP manager synchronously with timeout
+    // 3. Flush pending manager state to disk, each step bounded by a timeout.
+    // Runs on its own thread since cleanup may be triggered from inside the async runtime.
+    let app_clone = app.clone();
+    if std::thread::spawn(move || shutdown::flush_app_state(&app_clone))
+        .join()
+        .is_err()
+    {
+        log::warn!("State flush thread panicked");
+    }
+
+    // 4. Stop screen recording and streaming processes, and shutdown MCP manager
+    // synchronously with timeout
     if let Ok(rt_handle) = tokio::runtime::Handle::try_current() {
         let app_clone = app.clone();
         let cleanup_result = std::thread::spawn(move || {
@@ -2540,6 +2778,10 @@ fn perform_full_cleanup(app: &tauri::AppHandle) {
                         let _ = mgr.stop().await;
                         log::debug!("Screen recording stopped");
                     }
+                    if let Some(mgr) = app_clone.try_state::<ProcessManager>() {
+                        mgr.stop_all_streams().await;
+                        log::debug!("Process output streams stopped");
+                    }
                     if let Some(mgr) = app_clone.try_state::<McpManager>() {
                         mgr.shutdown().await;
                         log::debug!("MCP manager shut down");
@@ -2558,7 +2800,7 @@ fn perform_full_cleanup(app: &tauri::AppHandle) {
 
     #[cfg(not(mobile))]
     {
-        // 4. Remove tray icon FIRST (fast operation, prevents "Error removing system tray icon")
+        // 5. Remove tray icon FIRST (fast operation, prevents "Error removing system tray icon")
         if let Some(tray) = app.tray_by_id("main-tray") {
             // Clear menu to release resources, then hide to trigger OS cleanup
             let _ = tray.set_menu(None::<tauri::menu::Menu<tauri::Wry>>);
@@ -2566,7 +2808,7 @@ fn perform_full_cleanup(app: &tauri::AppHandle) {
             log::debug!("Tray icon removed");
         }
 
-        // 5. Destroy ALL windows (auxiliary + main) to allow Win32 class unregistration
+        // 6. Destroy ALL windows (auxiliary + main) to allow Win32 class unregistration
         destroy_all_windows(app);
 
         // Allow WebView2 to fully tear down its Win32 window classes
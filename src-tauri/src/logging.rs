@@ -0,0 +1,300 @@
+//! Runtime log control
+//!
+//! The log plugin is registered with a permissive level and defers every
+//! decision to the filter held here, so levels can be changed per module
+//! while the app is running. Records that pass the filter are also kept in a
+//! bounded in-memory buffer so recent output can be read back without access
+//! to the log directory.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// Number of formatted lines kept in the in-memory log buffer
+pub const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// Environment variable that enables runtime log control in release builds
+pub const DEBUG_LOGGING_ENV: &str = "COGNIA_DEBUG_LOGGING";
+
+static LOG_FILTER: Lazy<RwLock<LogFilter>> = Lazy::new(|| RwLock::new(LogFilter::initial()));
+
+static LOG_BUFFER: Lazy<Mutex<LogBuffer>> =
+    Lazy::new(|| Mutex::new(LogBuffer::new(LOG_BUFFER_CAPACITY)));
+
+/// A module-specific level override
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleLevel {
+    pub module: String,
+    pub level: String,
+}
+
+/// Current log filter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevels {
+    /// Level applied to modules without an override
+    pub default_level: String,
+    /// Per-module overrides; the longest matching module path wins
+    pub modules: Vec<ModuleLevel>,
+    /// Whether levels can be changed at runtime in this build
+    pub runtime_control: bool,
+}
+
+/// Default level plus per-module overrides
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    default_level: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    /// Filter used at startup: Info in debug builds, Warn in release
+    pub fn initial() -> Self {
+        let mut filter = Self {
+            default_level: if cfg!(debug_assertions) {
+                LevelFilter::Info
+            } else {
+                LevelFilter::Warn
+            },
+            modules: Vec::new(),
+        };
+        // Noisy third-party crates
+        for module in ["hyper", "reqwest", "tao", "wry", "tokio", "tracing"] {
+            filter.set(Some(module), LevelFilter::Warn);
+        }
+        filter
+    }
+
+    /// Set the level for a module, or the default level when `module` is `None`
+    pub fn set(&mut self, module: Option<&str>, level: LevelFilter) {
+        let Some(module) = module else {
+            self.default_level = level;
+            return;
+        };
+        match self.modules.iter_mut().find(|(name, _)| name == module) {
+            Some(entry) => entry.1 = level,
+            None => self.modules.push((module.to_string(), level)),
+        }
+    }
+
+    /// Remove a module override; returns whether one existed
+    pub fn clear(&mut self, module: &str) -> bool {
+        let before = self.modules.len();
+        self.modules.retain(|(name, _)| name != module);
+        self.modules.len() != before
+    }
+
+    /// Effective level for a log target
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(name, _)| {
+                target == name
+                    || (target.starts_with(name.as_str()) && target[name.len()..].starts_with("::"))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level)
+    }
+
+    /// Most verbose level any target can currently log at
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default_level, |acc, level| acc.max(level))
+    }
+
+    fn snapshot(&self) -> LogLevels {
+        let mut modules: Vec<ModuleLevel> = self
+            .modules
+            .iter()
+            .map(|(module, level)| ModuleLevel {
+                module: module.clone(),
+                level: level.to_string().to_lowercase(),
+            })
+            .collect();
+        modules.sort_by(|a, b| a.module.cmp(&b.module));
+        LogLevels {
+            default_level: self.default_level.to_string().to_lowercase(),
+            modules,
+            runtime_control: runtime_control_enabled(),
+        }
+    }
+}
+
+/// Bounded buffer of formatted log lines
+#[derive(Debug)]
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append a line, dropping the oldest when full
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// The last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        let skip = self.lines.len().saturating_sub(count);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// Logger that appends records to the in-memory buffer
+struct BufferLogger;
+
+impl Log for BufferLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        LOG_BUFFER.lock().push(format!(
+            "{} {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+            record.args()
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+/// Boxed logger for chaining the in-memory buffer into the log plugin
+pub fn buffer_logger() -> Box<dyn Log> {
+    Box::new(BufferLogger)
+}
+
+/// Whether a record passes the runtime filter
+pub fn is_enabled(metadata: &Metadata) -> bool {
+    metadata.level() <= LOG_FILTER.read().level_for(metadata.target())
+}
+
+/// Sync the global `log` max level with the runtime filter
+pub fn apply_max_level() {
+    log::set_max_level(LOG_FILTER.read().max_level());
+}
+
+/// Whether levels may be changed at runtime
+///
+/// Always allowed in debug builds; release builds require `COGNIA_DEBUG_LOGGING`.
+pub fn runtime_control_enabled() -> bool {
+    cfg!(debug_assertions)
+        || std::env::var(DEBUG_LOGGING_ENV)
+            .map(|v| {
+                matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false)
+}
+
+/// Get the current filter configuration
+pub fn levels() -> LogLevels {
+    LOG_FILTER.read().snapshot()
+}
+
+/// Change the level for a module (or the default level when `module` is `None`)
+///
+/// A level of `"default"` removes the module's override.
+pub fn set_level(module: Option<&str>, level: &str) -> Result<LogLevels, String> {
+    if !runtime_control_enabled() {
+        return Err(format!(
+            "Runtime log control is disabled; set {}=1 to enable it",
+            DEBUG_LOGGING_ENV
+        ));
+    }
+    let module = module.map(str::trim).filter(|m| !m.is_empty() && *m != "*");
+
+    let mut filter = LOG_FILTER.write();
+    if level.trim().eq_ignore_ascii_case("default") {
+        let module = module.ok_or("Cannot reset the default level")?;
+        if !filter.clear(module) {
+            return Err(format!("No level override for module: {}", module));
+        }
+    } else {
+        let level = LevelFilter::from_str(level.trim())
+            .map_err(|_| format!("Invalid log level: {}", level))?;
+        filter.set(module, level);
+    }
+    log::set_max_level(filter.max_level());
+    let snapshot = filter.snapshot();
+    drop(filter);
+
+    log::info!(
+        "Log level for {} set to {}",
+        module.unwrap_or("default"),
+        level.trim().to_lowercase()
+    );
+    Ok(snapshot)
+}
+
+/// The most recent `count` buffered log lines, oldest first
+pub fn tail(count: usize) -> Vec<String> {
+    LOG_BUFFER.lock().tail(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_module_prefix_wins() {
+        let mut filter = LogFilter {
+            default_level: LevelFilter::Warn,
+            modules: Vec::new(),
+        };
+        filter.set(Some("app_lib"), LevelFilter::Info);
+        filter.set(Some("app_lib::sandbox"), LevelFilter::Trace);
+
+        assert_eq!(filter.level_for("app_lib"), LevelFilter::Info);
+        assert_eq!(filter.level_for("app_lib::mcp::client"), LevelFilter::Info);
+        assert_eq!(filter.level_for("app_lib::sandbox::db"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("app_lib_extra"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("hyper"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+
+        assert!(filter.clear("app_lib::sandbox"));
+        assert!(!filter.clear("app_lib::sandbox"));
+        assert_eq!(filter.level_for("app_lib::sandbox::db"), LevelFilter::Info);
+        assert_eq!(filter.max_level(), LevelFilter::Info);
+
+        filter.set(None, LevelFilter::Error);
+        assert_eq!(filter.level_for("tao"), LevelFilter::Error);
+    }
+
+    #[test]
+    fn test_initial_filter_quiets_third_party_crates() {
+        let filter = LogFilter::initial();
+        assert_eq!(filter.level_for("reqwest::connect"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("app_lib::sandbox"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_buffer_keeps_most_recent_lines() {
+        let mut buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(format!("line {}", i));
+        }
+        assert_eq!(buffer.tail(10), vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(buffer.tail(2), vec!["line 3", "line 4"]);
+        assert!(buffer.tail(0).is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, oneshot, Mutex as TokioMutex};
 
 use crate::mcp::error::{McpError, McpResult};
@@ -30,6 +31,98 @@ struct PendingRequest {
     response_tx: oneshot::Sender<Result<serde_json::Value, McpError>>,
 }
 
+/// How long a sampling request may wait for the user before it is rejected
+const SAMPLING_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Server-initiated sampling request awaiting a response
+struct PendingSampling {
+    id: crate::mcp::protocol::jsonrpc::RequestId,
+    request: SamplingCreateMessageRequest,
+    received_at: Instant,
+}
+
+type PendingSamplingMap = Arc<TokioMutex<HashMap<String, PendingSampling>>>;
+
+/// Answers one sampling request taken out of the pending set
+///
+/// Holds its own handle to the transport so the answer can be sent without
+/// borrowing the client, e.g. after a slow model call.
+pub struct SamplingResponder {
+    transport: Arc<dyn Transport>,
+    request_id: crate::mcp::protocol::jsonrpc::RequestId,
+    request_id_str: String,
+}
+
+impl SamplingResponder {
+    /// Send the result of the sampling request
+    pub async fn respond(self, result: SamplingCreateMessageResponse) -> McpResult<()> {
+        log::info!(
+            "Sending sampling response for request: {}",
+            self.request_id_str
+        );
+        let result_value = serde_json::to_value(&result)?;
+        let response = JsonRpcResponse::success(self.request_id, result_value);
+        let message = serde_json::to_string(&response)?;
+        self.transport.send(&message).await?;
+        log::debug!("Sampling response sent successfully");
+        Ok(())
+    }
+
+    /// Answer the sampling request with an error
+    pub async fn reject(self, code: i32, reason: &str) -> McpResult<()> {
+        log::info!(
+            "Rejecting sampling request {}: {}",
+            self.request_id_str,
+            reason
+        );
+        let response = JsonRpcResponse::error(self.request_id, RpcError::new(code, reason));
+        let message = serde_json::to_string(&response)?;
+        self.transport.send(&message).await?;
+        Ok(())
+    }
+}
+
+/// Reject a sampling request that is still pending after the timeout
+async fn expire_sampling_request(
+    pending: &PendingSamplingMap,
+    transport: &Arc<dyn Transport>,
+    request_id_str: &str,
+) {
+    let expired = {
+        let mut pending = pending.lock().await;
+        let is_expired = pending
+            .get(request_id_str)
+            .is_some_and(|p| p.received_at.elapsed() >= SAMPLING_REQUEST_TIMEOUT);
+        if is_expired {
+            pending.remove(request_id_str)
+        } else {
+            None
+        }
+    };
+    let Some(expired) = expired else {
+        return;
+    };
+
+    log::warn!(
+        "Sampling request {} was not answered in time",
+        request_id_str
+    );
+    let responder = SamplingResponder {
+        transport: transport.clone(),
+        request_id: expired.id,
+        request_id_str: request_id_str.to_string(),
+    };
+    if let Err(e) = responder
+        .reject(
+            error_codes::USER_REJECTED,
+            "Sampling request timed out waiting for user approval",
+        )
+        .await
+    {
+        log::warn!("Failed to reject expired sampling request: {}", e);
+    }
+}
+
 /// MCP client for communicating with a single server
 pub struct McpClient {
     /// Transport layer
@@ -48,9 +141,8 @@ pub struct McpClient {
     receive_task: TokioMutex<Option<tokio::task::JoinHandle<()>>>,
     /// Client roots exposed to the server
     roots: Arc<TokioMutex<Vec<Root>>>,
-    /// Pending sampling requests awaiting frontend response (request_id_string → RequestId)
-    pending_sampling_requests:
-        Arc<TokioMutex<HashMap<String, crate::mcp::protocol::jsonrpc::RequestId>>>,
+    /// Pending sampling requests awaiting a response (request_id_string → request)
+    pending_sampling_requests: PendingSamplingMap,
 }
 
 impl McpClient {
@@ -285,47 +377,76 @@ impl McpClient {
                                             }
                                             Some(params) => {
                                                 // Validate params structure
-                                                if let Err(parse_err) = serde_json::from_value::<
+                                                match serde_json::from_value::<
                                                     SamplingCreateMessageRequest,
                                                 >(
                                                     params.clone()
                                                 ) {
-                                                    log::warn!(
-                                                        "Invalid sampling request parameters: {}",
-                                                        parse_err
-                                                    );
-                                                    let response = JsonRpcResponse::error(
-                                                        request.id,
-                                                        RpcError::with_data(
-                                                            error_codes::INVALID_PARAMS,
-                                                            "Invalid sampling request parameters",
-                                                            serde_json::json!({ "parse_error": parse_err.to_string() }),
-                                                        ),
-                                                    );
-                                                    if let Ok(resp_msg) =
-                                                        serde_json::to_string(&response)
-                                                    {
-                                                        let _ = transport.send(&resp_msg).await;
+                                                    Err(parse_err) => {
+                                                        log::warn!(
+                                                            "Invalid sampling request parameters: {}",
+                                                            parse_err
+                                                        );
+                                                        let response = JsonRpcResponse::error(
+                                                            request.id,
+                                                            RpcError::with_data(
+                                                                error_codes::INVALID_PARAMS,
+                                                                "Invalid sampling request parameters",
+                                                                serde_json::json!({ "parse_error": parse_err.to_string() }),
+                                                            ),
+                                                        );
+                                                        if let Ok(resp_msg) =
+                                                            serde_json::to_string(&response)
+                                                        {
+                                                            let _ = transport.send(&resp_msg).await;
+                                                        }
+                                                    }
+                                                    Ok(sampling_request) => {
+                                                        let req_id_str = match &request.id {
+                                                            crate::mcp::protocol::jsonrpc::RequestId::Number(n) => n.to_string(),
+                                                            crate::mcp::protocol::jsonrpc::RequestId::String(s) => s.clone(),
+                                                        };
+                                                        log::info!("Received sampling/createMessage request: id={}", req_id_str);
+                                                        pending_sampling.lock().await.insert(
+                                                            req_id_str.clone(),
+                                                            PendingSampling {
+                                                                id: request.id.clone(),
+                                                                request: sampling_request,
+                                                                received_at: Instant::now(),
+                                                            },
+                                                        );
+
+                                                        // Reject the request if the user never answers it
+                                                        let expire_pending =
+                                                            pending_sampling.clone();
+                                                        let expire_transport = transport.clone();
+                                                        let expire_id = req_id_str.clone();
+                                                        tokio::spawn(async move {
+                                                            tokio::time::sleep(
+                                                                SAMPLING_REQUEST_TIMEOUT,
+                                                            )
+                                                            .await;
+                                                            expire_sampling_request(
+                                                                &expire_pending,
+                                                                &expire_transport,
+                                                                &expire_id,
+                                                            )
+                                                            .await;
+                                                        });
+
+                                                        // Forward to the manager with the id so it can be answered later
+                                                        let sampling_notification = JsonRpcNotification {
+                                                            jsonrpc: crate::mcp::protocol::jsonrpc::JSONRPC_VERSION.to_string(),
+                                                            method: methods::SAMPLING_CREATE_MESSAGE.to_string(),
+                                                            params: Some(serde_json::json!({
+                                                                "requestId": req_id_str,
+                                                                "request": params,
+                                                            })),
+                                                        };
+                                                        let _ = notification_tx
+                                                            .send(sampling_notification)
+                                                            .await;
                                                     }
-                                                } else {
-                                                    let req_id_str = match &request.id {
-                                                        crate::mcp::protocol::jsonrpc::RequestId::Number(n) => n.to_string(),
-                                                        crate::mcp::protocol::jsonrpc::RequestId::String(s) => s.clone(),
-                                                    };
-                                                    log::info!("Received sampling/createMessage request: id={}", req_id_str);
-                                                    pending_sampling.lock().await.insert(
-                                                        req_id_str.clone(),
-                                                        request.id.clone(),
-                                                    );
-
-                                                    let sampling_notification = JsonRpcNotification {
-                                                        jsonrpc: crate::mcp::protocol::jsonrpc::JSONRPC_VERSION.to_string(),
-                                                        method: methods::SAMPLING_CREATE_MESSAGE.to_string(),
-                                                        params: request.params,
-                                                    };
-                                                    let _ = notification_tx
-                                                        .send(sampling_notification)
-                                                        .await;
                                                 }
                                             }
                                         }
@@ -746,24 +867,46 @@ impl McpClient {
         request_id_str: &str,
         result: SamplingCreateMessageResponse,
     ) -> McpResult<()> {
-        let request_id = {
-            let mut pending = self.pending_sampling_requests.lock().await;
-            pending.remove(request_id_str).ok_or_else(|| {
+        let (_, responder) = self.take_sampling(request_id_str).await?;
+        responder.respond(result).await
+    }
+
+    /// Reject a pending sampling/createMessage request with an error response
+    pub async fn reject_sampling(
+        &self,
+        request_id_str: &str,
+        code: i32,
+        reason: &str,
+    ) -> McpResult<()> {
+        let (_, responder) = self.take_sampling(request_id_str).await?;
+        responder.reject(code, reason).await
+    }
+
+    /// Take a pending sampling/createMessage request out of the pending set
+    ///
+    /// Each request can be taken only once, so it cannot be answered twice.
+    pub async fn take_sampling(
+        &self,
+        request_id_str: &str,
+    ) -> McpResult<(SamplingCreateMessageRequest, SamplingResponder)> {
+        let pending = self
+            .pending_sampling_requests
+            .lock()
+            .await
+            .remove(request_id_str)
+            .ok_or_else(|| {
                 log::error!(
                     "No pending sampling request found for id: {}",
                     request_id_str
                 );
                 McpError::ProtocolError(format!("No pending sampling request: {}", request_id_str))
-            })?
+            })?;
+        let responder = SamplingResponder {
+            transport: self.transport.clone(),
+            request_id: pending.id,
+            request_id_str: request_id_str.to_string(),
         };
-
-        log::info!("Sending sampling response for request: {}", request_id_str);
-        let result_value = serde_json::to_value(&result)?;
-        let response = JsonRpcResponse::success(request_id, result_value);
-        let message = serde_json::to_string(&response)?;
-        self.transport.send(&message).await?;
-        log::debug!("Sampling response sent successfully");
-        Ok(())
+        Ok((pending.request, responder))
     }
 
     /// Notify the server that the roots list has changed
@@ -1343,4 +1486,91 @@ mod tests {
         assert!(content.contents[0].blob.is_some());
         assert!(content.contents[0].text.is_none());
     }
+
+    // ============================================================================
+    // Sampling Request Tests
+    // ============================================================================
+
+    /// Transport that records sent messages and never receives any
+    struct RecordingTransport {
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for RecordingTransport {
+        async fn send(&self, message: &str) -> McpResult<()> {
+            self.sent.lock().unwrap().push(message.to_string());
+            Ok(())
+        }
+
+        async fn receive(&self) -> McpResult<String> {
+            std::future::pending().await
+        }
+
+        async fn close(&self) -> McpResult<()> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+    }
+
+    fn client_with_sampling(ids: &[(&str, Instant)]) -> (McpClient, Arc<RecordingTransport>) {
+        let transport = Arc::new(RecordingTransport {
+            sent: std::sync::Mutex::new(Vec::new()),
+        });
+        let (notification_tx, _) = mpsc::channel(1);
+        let client = McpClient::new(transport.clone(), notification_tx).unwrap();
+        let mut pending = client.pending_sampling_requests.try_lock().unwrap();
+        for (i, (id, received_at)) in ids.iter().enumerate() {
+            pending.insert(
+                id.to_string(),
+                PendingSampling {
+                    id: RequestId::Number(i as i64),
+                    request: serde_json::from_value(serde_json::json!({ "messages": [] })).unwrap(),
+                    received_at: *received_at,
+                },
+            );
+        }
+        drop(pending);
+        (client, transport)
+    }
+
+    #[tokio::test]
+    async fn test_sampling_request_can_only_be_taken_once() {
+        let (client, transport) = client_with_sampling(&[("7", Instant::now())]);
+
+        let (_, responder) = client.take_sampling("7").await.unwrap();
+        assert!(client.take_sampling("7").await.is_err());
+        assert!(client
+            .reject_sampling("7", error_codes::USER_REJECTED, "denied")
+            .await
+            .is_err());
+
+        responder
+            .reject(error_codes::INTERNAL_ERROR, "failed")
+            .await
+            .unwrap();
+        let sent = transport.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("failed"));
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_sampling_request_expires() {
+        let stale = Instant::now() - SAMPLING_REQUEST_TIMEOUT;
+        let (client, transport) = client_with_sampling(&[("old", stale), ("new", Instant::now())]);
+        let dyn_transport: Arc<dyn Transport> = transport.clone();
+
+        expire_sampling_request(&client.pending_sampling_requests, &dyn_transport, "old").await;
+        expire_sampling_request(&client.pending_sampling_requests, &dyn_transport, "new").await;
+
+        let pending = client.pending_sampling_requests.lock().await;
+        assert!(!pending.contains_key("old"));
+        assert!(pending.contains_key("new"));
+        let sent = transport.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("timed out"));
+    }
 }
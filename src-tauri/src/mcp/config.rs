@@ -2,11 +2,11 @@
 //!
 //! Handles loading and saving MCP server configurations
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use crate::mcp::error::{McpError, McpResult};
-use crate::mcp::types::McpServerConfig;
+use crate::mcp::types::{McpConnectionType, McpServerConfig, PinnedTool, Root};
 
 /// Complete MCP configuration file structure
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
@@ -15,6 +15,9 @@ pub struct McpConfig {
     /// Map of server ID to server configuration
     #[serde(default)]
     pub mcp_servers: HashMap<String, McpServerConfig>,
+    /// Tools pinned as favorites
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_tools: Vec<PinnedTool>,
 }
 
 impl McpConfig {
@@ -37,6 +40,7 @@ impl McpConfig {
     pub fn remove_server(&mut self, id: &str) -> Option<McpServerConfig> {
         log::debug!("Removing server configuration: id='{}'", id);
         let removed = self.mcp_servers.remove(id);
+        self.pinned_tools.retain(|pinned| pinned.server_id != id);
         if removed.is_some() {
             log::trace!("Server '{}' removed from configuration", id);
         } else {
@@ -68,6 +72,157 @@ impl McpConfig {
     }
 }
 
+/// Server entry in the standard `mcpServers` format used by other MCP clients
+/// (Claude Desktop, Cursor, VS Code, ...)
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardMcpServer {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Transport hint for remote servers: "sse" or "http" (streamable HTTP)
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+impl StandardMcpServer {
+    /// Convert to a server config named `name`; auto-start is left off
+    pub fn to_server_config(&self, name: &str) -> McpResult<McpServerConfig> {
+        let mut config = McpServerConfig {
+            name: name.to_string(),
+            enabled: !self.disabled,
+            ..Default::default()
+        };
+
+        if let Some(url) = self.url.as_deref().filter(|url| !url.trim().is_empty()) {
+            config.url = Some(url.to_string());
+            match self.transport.as_deref().unwrap_or("http") {
+                "sse" => config.connection_type = McpConnectionType::Sse,
+                "http" | "streamable-http" | "streamableHttp" => {
+                    config.connection_type = McpConnectionType::StreamableHttp;
+                    // Without an explicit type the server may well be SSE-only
+                    config.fallback_to_sse = self.transport.is_none();
+                }
+                other => {
+                    return Err(McpError::ProtocolError(format!(
+                        "Invalid MCP config: unsupported transport '{}' for server '{}'",
+                        other, name
+                    )))
+                }
+            }
+        } else if let Some(command) = self.command.as_deref().filter(|c| !c.trim().is_empty()) {
+            config.connection_type = McpConnectionType::Stdio;
+            config.command = command.to_string();
+            config.args = self.args.clone();
+            config.env = self.env.clone();
+        } else {
+            return Err(McpError::ProtocolError(format!(
+                "Invalid MCP config: server '{}' needs either a command or a url",
+                name
+            )));
+        }
+
+        Ok(config)
+    }
+}
+
+impl From<&McpServerConfig> for StandardMcpServer {
+    fn from(config: &McpServerConfig) -> Self {
+        let disabled = !config.enabled;
+        match config.connection_type {
+            McpConnectionType::Stdio => Self {
+                command: Some(config.command.clone()),
+                args: config.args.clone(),
+                env: config.env.clone(),
+                disabled,
+                ..Default::default()
+            },
+            McpConnectionType::Sse => Self {
+                url: config.url.clone(),
+                transport: Some("sse".to_string()),
+                disabled,
+                ..Default::default()
+            },
+            McpConnectionType::StreamableHttp => Self {
+                url: config.url.clone(),
+                transport: Some("http".to_string()),
+                disabled,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The standard `{ "mcpServers": { name: server } }` configuration document
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardMcpConfig {
+    pub mcp_servers: BTreeMap<String, StandardMcpServer>,
+}
+
+impl StandardMcpConfig {
+    /// Parse a standard config document, requiring the `mcpServers` object
+    pub fn parse(json: &str) -> McpResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if !value
+            .get("mcpServers")
+            .is_some_and(serde_json::Value::is_object)
+        {
+            return Err(McpError::ProtocolError(
+                "Invalid MCP config: expected an 'mcpServers' object".to_string(),
+            ));
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Build a document from `(id, config)` pairs, keyed by server name.
+    /// Falls back to the id when the name is empty or already taken.
+    pub fn from_servers<'a>(
+        servers: impl IntoIterator<Item = (&'a str, &'a McpServerConfig)>,
+    ) -> Self {
+        let mut servers: Vec<_> = servers.into_iter().collect();
+        servers.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut mcp_servers = BTreeMap::new();
+        for (id, config) in servers {
+            let name = config.name.trim();
+            let key = if name.is_empty() || mcp_servers.contains_key(name) {
+                id.to_string()
+            } else {
+                name.to_string()
+            };
+            mcp_servers.insert(key, StandardMcpServer::from(config));
+        }
+        Self { mcp_servers }
+    }
+}
+
+/// Server skipped during an import, with the reason
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpImportSkipped {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Outcome of importing a standard config document
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpImportReport {
+    /// Names of the servers that were added
+    pub imported: Vec<String>,
+    pub skipped: Vec<McpImportSkipped>,
+    /// Imported servers left without auto-connect because their command was not found
+    pub auto_connect_disabled: Vec<String>,
+}
+
 /// Configuration manager for MCP settings
 pub struct McpConfigManager {
     /// Path to the configuration file
@@ -263,6 +418,36 @@ impl McpConfigManager {
         }
     }
 
+    /// Update whether a server may issue sampling requests
+    pub fn set_server_allow_sampling(&self, id: &str, allow: bool) -> bool {
+        let mut config = self.config.write();
+        if let Some(server) = config.mcp_servers.get_mut(id) {
+            server.allow_sampling = allow;
+            log::info!(
+                "Server '{}' sampling {}",
+                id,
+                if allow { "allowed" } else { "disallowed" }
+            );
+            true
+        } else {
+            log::warn!("Cannot set sampling permission: server '{}' not found", id);
+            false
+        }
+    }
+
+    /// Replace the root directories advertised to a server
+    pub fn set_server_roots(&self, id: &str, roots: Vec<Root>) -> bool {
+        let mut config = self.config.write();
+        if let Some(server) = config.mcp_servers.get_mut(id) {
+            log::info!("Server '{}' roots updated: {} root(s)", id, roots.len());
+            server.roots = roots;
+            true
+        } else {
+            log::warn!("Cannot set roots: server '{}' not found", id);
+            false
+        }
+    }
+
     /// Update a server's auto-start status
     pub fn set_server_auto_start(&self, id: &str, auto_start: bool) -> bool {
         log::debug!(
@@ -284,12 +469,45 @@ impl McpConfigManager {
             false
         }
     }
+
+    /// Pin a tool as a favorite, returning false if it was already pinned
+    pub fn pin_tool(&self, server_id: &str, tool_name: &str) -> bool {
+        let pinned = PinnedTool {
+            server_id: server_id.to_string(),
+            tool_name: tool_name.to_string(),
+        };
+        let mut config = self.config.write();
+        if config.pinned_tools.contains(&pinned) {
+            return false;
+        }
+        log::info!("Pinned tool '{}' on server '{}'", tool_name, server_id);
+        config.pinned_tools.push(pinned);
+        true
+    }
+
+    /// Unpin a tool, returning false if it was not pinned
+    pub fn unpin_tool(&self, server_id: &str, tool_name: &str) -> bool {
+        let mut config = self.config.write();
+        let before = config.pinned_tools.len();
+        config
+            .pinned_tools
+            .retain(|pinned| pinned.server_id != server_id || pinned.tool_name != tool_name);
+        let removed = config.pinned_tools.len() != before;
+        if removed {
+            log::info!("Unpinned tool '{}' on server '{}'", tool_name, server_id);
+        }
+        removed
+    }
+
+    /// Get the pinned tools
+    pub fn get_pinned_tools(&self) -> Vec<PinnedTool> {
+        self.config.read().pinned_tools.clone()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mcp::types::McpConnectionType;
     use tempfile::TempDir;
 
     // ============================================================================
@@ -436,6 +654,8 @@ mod tests {
                 fallback_to_sse: false,
                 enabled: true,
                 auto_start: false,
+                allow_sampling: false,
+                roots: Vec::new(),
             },
         );
 
@@ -660,6 +880,45 @@ mod tests {
         assert!(!manager.get_server("test").unwrap().auto_start);
     }
 
+    #[test]
+    fn test_config_manager_pin_tool() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = McpConfigManager::new(temp_dir.path().to_path_buf());
+        manager.set_server("s1".to_string(), McpServerConfig::default());
+        manager.set_server("s2".to_string(), McpServerConfig::default());
+
+        assert!(manager.pin_tool("s1", "search"));
+        assert!(!manager.pin_tool("s1", "search"));
+        assert!(manager.pin_tool("s2", "fetch"));
+        assert_eq!(manager.get_pinned_tools().len(), 2);
+
+        let json = serde_json::to_string(&manager.get_config()).unwrap();
+        assert!(json.contains("pinnedTools"));
+
+        assert!(manager.unpin_tool("s1", "search"));
+        assert!(!manager.unpin_tool("s1", "search"));
+
+        // Removing a server drops its pins
+        manager.remove_server("s2");
+        assert!(manager.get_pinned_tools().is_empty());
+    }
+
+    #[test]
+    fn test_config_manager_set_server_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = McpConfigManager::new(temp_dir.path().to_path_buf());
+
+        manager.set_server("test".to_string(), McpServerConfig::default());
+        assert!(manager.get_server("test").unwrap().roots.is_empty());
+
+        assert!(manager.set_server_roots("test", vec![Root::from_path("/workspace")]));
+        let roots = manager.get_server("test").unwrap().roots;
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].uri, "file:///workspace");
+
+        assert!(!manager.set_server_roots("nonexistent", Vec::new()));
+    }
+
     #[test]
     fn test_config_manager_set_server_auto_start_nonexistent() {
         let temp_dir = TempDir::new().unwrap();
@@ -760,6 +1019,95 @@ mod tests {
         assert!(nested_path.join("mcp_servers.json").exists());
     }
 
+    // ============================================================================
+    // Standard Format Tests
+    // ============================================================================
+
+    #[test]
+    fn test_standard_config_parses_stdio_and_remote_servers() {
+        let json = r#"{
+            "mcpServers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                    "env": { "DEBUG": "1" }
+                },
+                "remote": { "url": "https://example.com/mcp", "type": "sse" },
+                "untyped": { "url": "https://example.com/mcp" }
+            }
+        }"#;
+
+        let config = StandardMcpConfig::parse(json).unwrap();
+        assert_eq!(config.mcp_servers.len(), 3);
+
+        let fs = config.mcp_servers["filesystem"]
+            .to_server_config("filesystem")
+            .unwrap();
+        assert_eq!(fs.name, "filesystem");
+        assert_eq!(fs.connection_type, McpConnectionType::Stdio);
+        assert_eq!(fs.command, "npx");
+        assert_eq!(fs.args.len(), 3);
+        assert_eq!(fs.env.get("DEBUG"), Some(&"1".to_string()));
+        assert!(fs.enabled);
+        assert!(!fs.auto_start);
+
+        let remote = config.mcp_servers["remote"]
+            .to_server_config("remote")
+            .unwrap();
+        assert_eq!(remote.connection_type, McpConnectionType::Sse);
+
+        let untyped = config.mcp_servers["untyped"]
+            .to_server_config("untyped")
+            .unwrap();
+        assert_eq!(untyped.connection_type, McpConnectionType::StreamableHttp);
+        assert!(untyped.fallback_to_sse);
+    }
+
+    #[test]
+    fn test_standard_config_rejects_invalid_documents() {
+        assert!(StandardMcpConfig::parse("not json").is_err());
+        assert!(StandardMcpConfig::parse(r#"{"servers": {}}"#).is_err());
+
+        let empty = StandardMcpServer::default();
+        assert!(empty.to_server_config("empty").is_err());
+
+        let unknown = StandardMcpServer {
+            url: Some("https://example.com".to_string()),
+            transport: Some("websocket".to_string()),
+            ..Default::default()
+        };
+        assert!(unknown.to_server_config("ws").is_err());
+    }
+
+    #[test]
+    fn test_standard_config_round_trip() {
+        let stdio = McpServerConfig {
+            name: "files".to_string(),
+            command: "uvx".to_string(),
+            args: vec!["mcp-server-fetch".to_string()],
+            enabled: false,
+            ..Default::default()
+        };
+        let remote = McpServerConfig {
+            name: "files".to_string(),
+            connection_type: McpConnectionType::StreamableHttp,
+            url: Some("https://example.com/mcp".to_string()),
+            ..Default::default()
+        };
+
+        let exported = StandardMcpConfig::from_servers([("a", &stdio), ("b", &remote)]);
+        let json = serde_json::to_string(&exported).unwrap();
+        assert!(json.contains("\"mcpServers\""));
+        assert!(json.contains("\"disabled\":true"));
+
+        let parsed = StandardMcpConfig::parse(&json).unwrap();
+        // The second server's name collides, so it is keyed by id
+        assert_eq!(parsed.mcp_servers["files"], StandardMcpServer::from(&stdio));
+        let imported = parsed.mcp_servers["b"].to_server_config("b").unwrap();
+        assert_eq!(imported.connection_type, McpConnectionType::StreamableHttp);
+        assert!(!imported.fallback_to_sse);
+    }
+
     // ============================================================================
     // Edge Cases
     // ============================================================================
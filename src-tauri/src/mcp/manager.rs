@@ -4,18 +4,23 @@
 //! state management, notification handling, and auto-reconnection.
 
 use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
 use tokio::task::JoinHandle;
 
 use tauri::{AppHandle, Emitter};
 
+use crate::input_completion::{CompletionModelConfig, InputCompletionManager};
 use crate::mcp::client::McpClient;
 use crate::mcp::config::{McpConfig, McpConfigManager};
 use crate::mcp::error::{McpError, McpResult};
-use crate::mcp::protocol::jsonrpc::{methods, JsonRpcNotification};
-use crate::mcp::protocol::sampling::SamplingProgressParams;
+use crate::mcp::protocol::jsonrpc::{error_codes, methods, JsonRpcNotification};
+use crate::mcp::protocol::sampling::{
+    build_sampling_prompt, SamplingProgressParams, SamplingRequestNotice,
+};
 use crate::mcp::transport::TransportType;
 use crate::mcp::types::*;
 
@@ -30,6 +35,7 @@ pub mod events {
     pub const TRANSPORT_EVENT: &str = "mcp:transport-event";
     pub const APP_BRIDGE: &str = "mcp:app-bridge";
     pub const APP_SECURITY_EVENT: &str = "mcp:app-security-event";
+    pub const SAMPLING_REQUEST: &str = "mcp://sampling-request";
 }
 
 /// Internal state for a connected server
@@ -71,6 +77,131 @@ fn now_ms() -> i64 {
     chrono::Utc::now().timestamp_millis()
 }
 
+/// File in the app data directory holding tool call statistics
+const TOOL_USAGE_FILE: &str = "mcp_tool_usage.json";
+
+/// Delay before changed tool call statistics are written to disk
+const TOOL_USAGE_FLUSH_DELAY: Duration = Duration::from_secs(5);
+
+/// Load saved tool call statistics, starting empty if missing or invalid
+fn load_tool_usage(path: &Path) -> HashMap<(String, String), ToolUsage> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return HashMap::new(),
+    };
+    let usage: Vec<ToolUsage> = serde_json::from_str(&content).unwrap_or_else(|e| {
+        log::warn!(
+            "Invalid MCP tool usage file {:?}, starting empty: {}",
+            path,
+            e
+        );
+        Vec::new()
+    });
+    usage
+        .into_iter()
+        .map(|usage| {
+            // Pins live in the server config
+            let usage = ToolUsage {
+                pinned: false,
+                ..usage
+            };
+            ((usage.server_id.clone(), usage.tool_name.clone()), usage)
+        })
+        .collect()
+}
+
+/// Order tool usage pinned first, then by call count and most recent use
+fn sort_tool_usage(usage: &mut [ToolUsage]) {
+    usage.sort_by(|a, b| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then(b.call_count.cmp(&a.call_count))
+            .then(b.last_used_at.cmp(&a.last_used_at))
+            .then_with(|| (&a.server_id, &a.tool_name).cmp(&(&b.server_id, &b.tool_name)))
+    });
+}
+
+/// Tool call statistics, written to disk a short while after they change
+struct ToolUsageStore {
+    /// Invocation statistics per (server ID, tool name)
+    usage: RwLock<HashMap<(String, String), ToolUsage>>,
+    /// Where the statistics are persisted
+    path: PathBuf,
+    /// Whether the statistics changed since the last write
+    dirty: AtomicBool,
+    /// Whether a delayed write is already pending
+    flush_scheduled: AtomicBool,
+    /// Serializes writes so an older snapshot cannot be saved last
+    write_lock: Mutex<()>,
+}
+
+impl ToolUsageStore {
+    fn load(path: PathBuf) -> Self {
+        Self {
+            usage: RwLock::new(load_tool_usage(&path)),
+            path,
+            dirty: AtomicBool::new(false),
+            flush_scheduled: AtomicBool::new(false),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Mark the statistics changed and write them after the flush delay
+    fn mark_dirty(self: &Arc<Self>) {
+        self.dirty.store(true, Ordering::SeqCst);
+        if self.flush_scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let store = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(TOOL_USAGE_FLUSH_DELAY).await;
+            store.flush_scheduled.store(false, Ordering::SeqCst);
+            store.flush().await;
+        });
+    }
+
+    /// Drop the statistics of a removed server
+    async fn remove_server(self: &Arc<Self>, server_id: &str) {
+        let removed = {
+            let mut usage = self.usage.write().await;
+            let before = usage.len();
+            usage.retain(|(id, _), _| id != server_id);
+            usage.len() != before
+        };
+        if removed {
+            self.mark_dirty();
+        }
+    }
+
+    /// Write the statistics if they changed, via a temp file and rename
+    async fn flush(&self) {
+        let _guard = self.write_lock.lock().await;
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        let content = {
+            let usage = self.usage.read().await;
+            serde_json::to_string(&usage.values().collect::<Vec<_>>())
+        };
+        let temp_path = self.path.with_extension("tmp");
+        let result = match content {
+            Ok(content) => match tokio::fs::write(&temp_path, content).await {
+                Ok(()) => tokio::fs::rename(&temp_path, &self.path)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            // Keep the changes so the next flush retries
+            self.dirty.store(true, Ordering::SeqCst);
+            log::warn!("Failed to save MCP tool usage to {:?}: {}", self.path, e);
+        }
+    }
+}
+
 /// MCP Manager - manages all MCP server connections
 pub struct McpManager {
     /// Configuration manager
@@ -87,12 +218,15 @@ pub struct McpManager {
     ui_call_inflight: Arc<RwLock<HashMap<String, usize>>>,
     /// Rolling UI-initiated tool call history per server/session
     ui_call_history: Arc<RwLock<HashMap<String, VecDeque<i64>>>>,
+    /// Invocation statistics per (server ID, tool name)
+    tool_usage: Arc<ToolUsageStore>,
 }
 
 impl McpManager {
     /// Create a new MCP manager
     pub fn new(app_handle: AppHandle, app_data_dir: std::path::PathBuf) -> Self {
         log::info!("Creating MCP manager with data dir: {:?}", app_data_dir);
+        let tool_usage = ToolUsageStore::load(app_data_dir.join(TOOL_USAGE_FILE));
         let config_manager = Arc::new(McpConfigManager::new(app_data_dir));
 
         let reconnect_config = ReconnectConfig::default();
@@ -120,6 +254,7 @@ impl McpManager {
             ui_call_policy,
             ui_call_inflight: Arc::new(RwLock::new(HashMap::new())),
             ui_call_history: Arc::new(RwLock::new(HashMap::new())),
+            tool_usage: Arc::new(tool_usage),
         }
     }
 
@@ -823,6 +958,7 @@ impl McpManager {
         servers.remove(id);
 
         drop(servers);
+        self.tool_usage.remove_server(id).await;
 
         log::info!("Server '{}' removed successfully", id);
         self.emit_servers_changed().await;
@@ -948,6 +1084,9 @@ impl McpManager {
             selected_transport
         );
 
+        // Advertise configured roots before the server can ask for them
+        client.set_roots(config.roots.clone()).await;
+
         // Start receive loop
         log::debug!("Starting receive loop for server '{}'", id);
         client.start_receive_loop().await;
@@ -1122,6 +1261,8 @@ impl McpManager {
         let result = client.call_tool(tool_name, arguments).await;
         let ended_at = chrono::Utc::now().timestamp_millis();
         let duration_ms = ended_at - started_at;
+        self.record_tool_usage(server_id, tool_name, result.is_err(), ended_at)
+            .await;
 
         // Emit progress end
         let progress = ToolCallProgress {
@@ -1578,41 +1719,126 @@ impl McpManager {
             .await
     }
 
-    /// Set roots for a connected server and notify it
+    /// Approve a pending sampling request and answer it with the configured LLM
+    ///
+    /// `model` overrides the model configured for input completion.
+    pub async fn approve_sampling(
+        &self,
+        server_id: &str,
+        request_id: &str,
+        completion: &InputCompletionManager,
+        model: Option<CompletionModelConfig>,
+    ) -> McpResult<SamplingResult> {
+        // Take the request while holding the lock only briefly; the model call
+        // below can take a long time and must not block server changes
+        let (request, responder) = {
+            let servers = self.servers.read().await;
+            let client = servers
+                .get(server_id)
+                .ok_or_else(|| McpError::ServerNotFound(server_id.to_string()))?
+                .client
+                .as_ref()
+                .ok_or(McpError::NotConnected)?;
+            client.take_sampling(request_id).await?
+        };
+
+        let mut model = model.unwrap_or_else(|| completion.get_config().model);
+        if let Some(max_tokens) = request.max_tokens {
+            model.max_tokens = max_tokens;
+        }
+        if let Some(temperature) = request.temperature {
+            model.temperature = temperature as f32;
+        }
+
+        log::info!(
+            "Answering sampling request '{}' from server '{}' with model '{}'",
+            request_id,
+            server_id,
+            model.model_id
+        );
+        let (system_prompt, prompt) = build_sampling_prompt(&request);
+        let text = match completion
+            .generate_text(&system_prompt, &prompt, Some(model.clone()))
+            .await
+        {
+            Ok(text) => text,
+            Err(e) => {
+                let reason = format!("Sampling failed: {}", e);
+                responder
+                    .reject(error_codes::INTERNAL_ERROR, &reason)
+                    .await?;
+                return Err(McpError::ProtocolError(reason));
+            }
+        };
+
+        let result = SamplingResult {
+            role: SamplingRole::Assistant,
+            content: SamplingContent::Item(ContentItem::Text { text }),
+            model: model.model_id,
+            stop_reason: Some("endTurn".to_string()),
+        };
+        responder.respond(result.clone()).await?;
+        Ok(result)
+    }
+
+    /// Decline a pending sampling request on the user's behalf
+    pub async fn deny_sampling(&self, server_id: &str, request_id: &str) -> McpResult<()> {
+        let servers = self.servers.read().await;
+        let client = servers
+            .get(server_id)
+            .ok_or_else(|| McpError::ServerNotFound(server_id.to_string()))?
+            .client
+            .as_ref()
+            .ok_or(McpError::NotConnected)?;
+        client
+            .reject_sampling(
+                request_id,
+                error_codes::USER_REJECTED,
+                "User rejected sampling request",
+            )
+            .await
+    }
+
+    /// Set the roots advertised to a server and persist them
+    ///
+    /// Bare filesystem paths are converted to `file://` URIs. Connected servers
+    /// are sent `notifications/roots/list_changed`.
     pub async fn set_roots(
         &self,
         server_id: &str,
         roots: Vec<crate::mcp::types::Root>,
     ) -> McpResult<()> {
+        let roots: Vec<_> = roots.into_iter().map(Root::normalize).collect();
         log::info!("Setting {} roots for server '{}'", roots.len(), server_id);
 
-        let servers = self.servers.read().await;
-        let instance = servers
-            .get(server_id)
-            .ok_or_else(|| McpError::ServerNotFound(server_id.to_string()))?;
+        if !self
+            .config_manager
+            .set_server_roots(server_id, roots.clone())
+        {
+            return Err(McpError::ServerNotFound(server_id.to_string()));
+        }
+        self.config_manager.save().await?;
 
-        let client = instance
-            .client
-            .as_ref()
-            .ok_or_else(|| McpError::NotConnected)?;
-        client.set_roots(roots).await;
-        client.notify_roots_changed().await?;
-        log::debug!("Roots set and notification sent for server '{}'", server_id);
+        let servers = self.servers.read().await;
+        if let Some(client) = servers.get(server_id).and_then(|i| i.client.as_ref()) {
+            client.set_roots(roots).await;
+            client.notify_roots_changed().await?;
+            log::debug!("Roots set and notification sent for server '{}'", server_id);
+        }
         Ok(())
     }
 
-    /// Get the current roots for a connected server
+    /// Get the roots advertised to a server
     pub async fn get_roots(&self, server_id: &str) -> McpResult<Vec<crate::mcp::types::Root>> {
         let servers = self.servers.read().await;
-        let instance = servers
-            .get(server_id)
-            .ok_or_else(|| McpError::ServerNotFound(server_id.to_string()))?;
+        if let Some(client) = servers.get(server_id).and_then(|i| i.client.as_ref()) {
+            return Ok(client.get_roots().await);
+        }
 
-        let client = instance
-            .client
-            .as_ref()
-            .ok_or_else(|| McpError::NotConnected)?;
-        Ok(client.get_roots().await)
+        self.config_manager
+            .get_server(server_id)
+            .map(|config| config.roots)
+            .ok_or_else(|| McpError::ServerNotFound(server_id.to_string()))
     }
 
     /// List resource templates from a connected server
@@ -1698,6 +1924,90 @@ impl McpManager {
         all_tools
     }
 
+    /// Get all tools from all connected servers, pinned tools first and then
+    /// the most used
+    pub async fn get_all_tools_by_usage(&self) -> Vec<(String, McpTool)> {
+        let mut all_tools = self.get_all_tools().await;
+        let usage = self.get_tool_usage().await;
+        let rank: HashMap<(&str, &str), usize> = usage
+            .iter()
+            .enumerate()
+            .map(|(rank, u)| ((u.server_id.as_str(), u.tool_name.as_str()), rank))
+            .collect();
+        all_tools.sort_by(|(a_server, a_tool), (b_server, b_tool)| {
+            let a_rank = rank.get(&(a_server.as_str(), a_tool.name.as_str()));
+            let b_rank = rank.get(&(b_server.as_str(), b_tool.name.as_str()));
+            match (a_rank, b_rank) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => (a_server, &a_tool.name).cmp(&(b_server, &b_tool.name)),
+            }
+        });
+        all_tools
+    }
+
+    /// Get usage statistics for every called or pinned tool, pinned tools first
+    /// and then by call count and recency
+    pub async fn get_tool_usage(&self) -> Vec<ToolUsage> {
+        let mut usage = self.tool_usage.usage.read().await.clone();
+        for pinned in self.config_manager.get_pinned_tools() {
+            usage
+                .entry((pinned.server_id.clone(), pinned.tool_name.clone()))
+                .or_insert_with(|| ToolUsage {
+                    server_id: pinned.server_id,
+                    tool_name: pinned.tool_name,
+                    ..Default::default()
+                })
+                .pinned = true;
+        }
+
+        let mut usage: Vec<ToolUsage> = usage.into_values().collect();
+        sort_tool_usage(&mut usage);
+        usage
+    }
+
+    /// Pin a tool as a favorite and persist, returning false if already pinned
+    pub async fn pin_tool(&self, server_id: &str, tool_name: &str) -> McpResult<bool> {
+        if !self.config_manager.has_server(server_id) {
+            return Err(McpError::ServerNotFound(server_id.to_string()));
+        }
+        let result = self.config_manager.pin_tool(server_id, tool_name);
+        if result {
+            self.config_manager.save().await?;
+        }
+        Ok(result)
+    }
+
+    /// Unpin a tool and persist, returning false if it was not pinned
+    pub async fn unpin_tool(&self, server_id: &str, tool_name: &str) -> McpResult<bool> {
+        let result = self.config_manager.unpin_tool(server_id, tool_name);
+        if result {
+            self.config_manager.save().await?;
+        }
+        Ok(result)
+    }
+
+    /// Count a tool call that reached the server and schedule saving the statistics
+    async fn record_tool_usage(&self, server_id: &str, tool_name: &str, failed: bool, at: i64) {
+        let mut usage = self.tool_usage.usage.write().await;
+        let entry = usage
+            .entry((server_id.to_string(), tool_name.to_string()))
+            .or_insert_with(|| ToolUsage {
+                server_id: server_id.to_string(),
+                tool_name: tool_name.to_string(),
+                ..Default::default()
+            });
+        entry.call_count += 1;
+        if failed {
+            entry.error_count += 1;
+        }
+        entry.last_used_at = Some(at);
+        drop(usage);
+
+        self.tool_usage.mark_dirty();
+    }
+
     /// Subscribe to resource updates for a server
     pub async fn subscribe_resource(&self, server_id: &str, uri: &str) -> McpResult<()> {
         log::info!(
@@ -1762,6 +2072,7 @@ impl McpManager {
         log::debug!("Spawning notification handler for server '{}'", server_id);
         let app_handle = self.app_handle.clone();
         let servers = self.servers.clone();
+        let config_manager = self.config_manager.clone();
         let server_id_clone = server_id.clone();
 
         let task = tokio::spawn(async move {
@@ -1778,7 +2089,7 @@ impl McpManager {
                     notification = notification_rx.recv() => {
                         match notification {
                             Some(notif) => {
-                                Self::handle_notification(&app_handle, &servers, &server_id, notif).await;
+                                Self::handle_notification(&app_handle, &servers, &config_manager, &server_id, notif).await;
                             }
                             None => {
                                 log::debug!("Notification channel closed for server: {}", server_id);
@@ -1876,10 +2187,64 @@ impl McpManager {
         }
     }
 
+    /// Gate an incoming sampling request behind the per-server opt-in
+    ///
+    /// Allowed requests are surfaced to the UI for user consent; the model is
+    /// only called once the user approves via [`Self::approve_sampling`].
+    async fn handle_sampling_request(
+        app_handle: &AppHandle,
+        servers: &Arc<RwLock<HashMap<String, ServerInstance>>>,
+        config_manager: &McpConfigManager,
+        server_id: &str,
+        notice: SamplingRequestNotice,
+    ) {
+        let allowed = config_manager
+            .get_server(server_id)
+            .map(|config| config.allow_sampling)
+            .unwrap_or(false);
+
+        if !allowed {
+            log::warn!(
+                "Rejecting sampling request '{}' from server '{}': sampling not allowed",
+                notice.request_id,
+                server_id
+            );
+            let servers = servers.read().await;
+            if let Some(client) = servers.get(server_id).and_then(|i| i.client.as_ref()) {
+                if let Err(e) = client
+                    .reject_sampling(
+                        &notice.request_id,
+                        error_codes::USER_REJECTED,
+                        "Sampling is not enabled for this server",
+                    )
+                    .await
+                {
+                    log::warn!("Failed to reject sampling request: {}", e);
+                }
+            }
+            return;
+        }
+
+        log::info!(
+            "Sampling request '{}' from server '{}' awaiting user consent",
+            notice.request_id,
+            server_id
+        );
+        let _ = app_handle.emit(
+            events::SAMPLING_REQUEST,
+            &serde_json::json!({
+                "serverId": server_id,
+                "requestId": notice.request_id,
+                "request": notice.request,
+            }),
+        );
+    }
+
     /// Handle a notification from an MCP server
     async fn handle_notification(
         app_handle: &AppHandle,
         servers: &Arc<RwLock<HashMap<String, ServerInstance>>>,
+        config_manager: &McpConfigManager,
         server_id: &str,
         notification: JsonRpcNotification,
     ) {
@@ -1992,6 +2357,23 @@ impl McpManager {
                 }
             }
             methods::SAMPLING_CREATE_MESSAGE => {
+                // Server-initiated sampling request forwarded by the client
+                if let Some(notice) = notification
+                    .params
+                    .clone()
+                    .and_then(|p| serde_json::from_value::<SamplingRequestNotice>(p).ok())
+                {
+                    Self::handle_sampling_request(
+                        app_handle,
+                        servers,
+                        config_manager,
+                        server_id,
+                        notice,
+                    )
+                    .await;
+                    return;
+                }
+
                 // Handle sampling progress notification
                 if let Some(params) = notification.params {
                     match serde_json::from_value::<SamplingProgressParams>(params) {
@@ -2169,6 +2551,7 @@ impl McpManager {
                     }
                 };
 
+            client.set_roots(config.roots.clone()).await;
             client.start_receive_loop().await;
 
             let init_result = match client.initialize(ClientInfo::default()).await {
@@ -2229,6 +2612,7 @@ impl McpManager {
                                     Self::handle_notification(
                                         &app_handle_clone,
                                         &servers_clone,
+                                        &config_manager,
                                         &server_id_for_handler,
                                         n
                                     ).await;
@@ -2325,6 +2709,16 @@ impl McpManager {
         Ok(result)
     }
 
+    /// Set whether a server may issue sampling requests and persist
+    pub async fn set_server_allow_sampling(&self, id: &str, allow: bool) -> McpResult<bool> {
+        let result = self.config_manager.set_server_allow_sampling(id, allow);
+        if result {
+            self.config_manager.save().await?;
+            self.emit_server_update(id).await;
+        }
+        Ok(result)
+    }
+
     /// Get the configuration file path
     pub fn get_config_path(&self) -> String {
         self.config_manager
@@ -2355,6 +2749,8 @@ impl McpManager {
             }
         }
 
+        self.tool_usage.flush().await;
+
         log::info!("MCP manager shutdown completed");
     }
 }
@@ -2380,6 +2776,85 @@ mod tests {
         assert_eq!(events::APP_SECURITY_EVENT, "mcp:app-security-event");
     }
 
+    #[test]
+    fn test_sort_tool_usage_pinned_then_most_used() {
+        let usage = |tool: &str, call_count, last_used_at, pinned| ToolUsage {
+            server_id: "s1".to_string(),
+            tool_name: tool.to_string(),
+            call_count,
+            error_count: 0,
+            last_used_at,
+            pinned,
+        };
+        let mut tools = vec![
+            usage("rare", 1, Some(500), false),
+            usage("favorite", 0, None, true),
+            usage("older", 5, Some(100), false),
+            usage("newer", 5, Some(200), false),
+        ];
+        sort_tool_usage(&mut tools);
+
+        let order: Vec<&str> = tools.iter().map(|t| t.tool_name.as_str()).collect();
+        assert_eq!(order, vec!["favorite", "newer", "older", "rare"]);
+    }
+
+    #[test]
+    fn test_load_tool_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TOOL_USAGE_FILE);
+        assert!(load_tool_usage(&path).is_empty());
+
+        let saved = vec![ToolUsage {
+            server_id: "s1".to_string(),
+            tool_name: "search".to_string(),
+            call_count: 3,
+            error_count: 1,
+            last_used_at: Some(100),
+            pinned: true,
+        }];
+        std::fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+        let loaded = load_tool_usage(&path);
+        let usage = &loaded[&("s1".to_string(), "search".to_string())];
+        assert_eq!(usage.call_count, 3);
+        assert_eq!(usage.error_count, 1);
+        assert!(!usage.pinned);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_tool_usage(&path).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tool_usage_store_flush_and_remove_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TOOL_USAGE_FILE);
+        let store = Arc::new(ToolUsageStore::load(path.clone()));
+        {
+            let mut usage = store.usage.write().await;
+            for server_id in ["s1", "s2"] {
+                usage.insert(
+                    (server_id.to_string(), "search".to_string()),
+                    ToolUsage {
+                        server_id: server_id.to_string(),
+                        tool_name: "search".to_string(),
+                        call_count: 1,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        // Nothing is written until the statistics are marked changed
+        store.flush().await;
+        assert!(!path.exists());
+
+        store.remove_server("s1").await;
+        store.flush().await;
+        let loaded = load_tool_usage(&path);
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded.contains_key(&("s2".to_string(), "search".to_string())));
+        assert!(!path.with_extension("tmp").exists());
+    }
+
     #[test]
     fn test_parse_truthy_flag_enabled_values() {
         assert!(McpManager::parse_truthy_flag("1"));
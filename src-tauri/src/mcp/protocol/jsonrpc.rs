@@ -197,6 +197,9 @@ pub mod error_codes {
 
     /// Internal JSON-RPC error
     pub const INTERNAL_ERROR: i32 = -32603;
+
+    /// The user declined a server-initiated request (e.g. sampling)
+    pub const USER_REJECTED: i32 = -1;
 }
 
 /// MCP-specific methods
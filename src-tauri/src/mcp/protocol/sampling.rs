@@ -2,7 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::mcp::types::{SamplingRequest, SamplingResult};
+use crate::mcp::types::{
+    ContentItem, SamplingContent, SamplingRequest, SamplingResult, SamplingRole,
+};
 
 /// Request for sampling/createMessage
 pub type SamplingCreateMessageRequest = SamplingRequest;
@@ -20,6 +22,64 @@ pub struct SamplingProgressParams {
     pub total: Option<f64>,
 }
 
+/// Sampling request forwarded from the client receive loop to the manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplingRequestNotice {
+    pub request_id: String,
+    pub request: SamplingRequest,
+}
+
+/// Flatten a sampling request into a (system prompt, prompt) pair
+///
+/// Non-text content is replaced by a short placeholder so the model still
+/// sees where it appeared in the conversation.
+pub fn build_sampling_prompt(request: &SamplingRequest) -> (String, String) {
+    let system_prompt = request.system_prompt.clone().unwrap_or_default();
+
+    let prompt = if let [message] = request.messages.as_slice() {
+        content_to_text(&message.content)
+    } else {
+        request
+            .messages
+            .iter()
+            .map(|message| {
+                let role = match message.role {
+                    SamplingRole::User => "User",
+                    SamplingRole::Assistant => "Assistant",
+                };
+                format!("{}: {}", role, content_to_text(&message.content))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    (system_prompt, prompt)
+}
+
+fn content_to_text(content: &SamplingContent) -> String {
+    fn item_to_text(item: &ContentItem) -> String {
+        match item {
+            ContentItem::Text { text } => text.clone(),
+            ContentItem::Image { mime_type, .. } => format!("[image: {}]", mime_type),
+            ContentItem::Resource { resource } => resource
+                .text
+                .clone()
+                .unwrap_or_else(|| format!("[resource: {}]", resource.uri)),
+        }
+    }
+
+    match content {
+        SamplingContent::Text(text) => text.clone(),
+        SamplingContent::Item(item) => item_to_text(item),
+        SamplingContent::Items(items) => items
+            .iter()
+            .map(item_to_text)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +422,79 @@ mod tests {
         assert_eq!(user_json["role"], "user");
         assert_eq!(assistant_json["role"], "assistant");
     }
+
+    // ============================================================================
+    // Sampling Request Routing Tests
+    // ============================================================================
+
+    #[test]
+    fn test_sampling_request_notice_deserialization() {
+        let json = serde_json::json!({
+            "requestId": "7",
+            "request": {
+                "messages": [
+                    { "role": "user", "content": { "type": "text", "text": "Summarize" } }
+                ],
+                "maxTokens": 64
+            }
+        });
+        let notice: SamplingRequestNotice = serde_json::from_value(json).unwrap();
+
+        assert_eq!(notice.request_id, "7");
+        assert_eq!(notice.request.max_tokens, Some(64));
+        assert!(matches!(
+            &notice.request.messages[0].content,
+            SamplingContent::Item(ContentItem::Text { text }) if text == "Summarize"
+        ));
+    }
+
+    #[test]
+    fn test_build_sampling_prompt() {
+        let single = SamplingRequest {
+            messages: vec![SamplingMessage {
+                role: SamplingRole::User,
+                content: SamplingContent::Text("Hello".to_string()),
+            }],
+            model_preferences: None,
+            system_prompt: Some("Be brief".to_string()),
+            include_context: None,
+            temperature: None,
+            max_tokens: None,
+            stop_sequences: None,
+            metadata: None,
+        };
+        assert_eq!(
+            build_sampling_prompt(&single),
+            ("Be brief".to_string(), "Hello".to_string())
+        );
+
+        let conversation = SamplingRequest {
+            messages: vec![
+                SamplingMessage {
+                    role: SamplingRole::User,
+                    content: SamplingContent::Items(vec![
+                        ContentItem::Text {
+                            text: "What is this?".to_string(),
+                        },
+                        ContentItem::Image {
+                            data: String::new(),
+                            mime_type: "image/png".to_string(),
+                        },
+                    ]),
+                },
+                SamplingMessage {
+                    role: SamplingRole::Assistant,
+                    content: SamplingContent::Text("A chart.".to_string()),
+                },
+            ],
+            system_prompt: None,
+            ..single
+        };
+        let (system_prompt, prompt) = build_sampling_prompt(&conversation);
+        assert!(system_prompt.is_empty());
+        assert_eq!(
+            prompt,
+            "User: What is this?\n[image: image/png]\n\nAssistant: A chart."
+        );
+    }
 }
@@ -59,6 +59,7 @@ impl SseTransport {
 
     /// Connect to an SSE endpoint with optional proxy support
     pub async fn connect_with_proxy(url: &str, proxy_url: Option<&str>) -> McpResult<Self> {
+        crate::http::ensure_url_allowed(url).map_err(McpError::TransportError)?;
         log::info!("Connecting to SSE endpoint: {}", url);
         if let Some(proxy) = proxy_url {
             log::info!("Using proxy: {}", proxy);
@@ -204,6 +205,7 @@ impl Transport for SseTransport {
             log::error!("Cannot send SSE message: message URL not configured");
             McpError::TransportError("Message URL not configured".to_string())
         })?;
+        crate::http::ensure_url_allowed(url).map_err(McpError::TransportError)?;
 
         log::trace!(
             "Sending HTTP POST to {} ({} bytes): {}",
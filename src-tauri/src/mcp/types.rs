@@ -59,6 +59,14 @@ pub struct McpServerConfig {
     /// Whether to auto-start on application launch
     #[serde(default)]
     pub auto_start: bool,
+
+    /// Whether the server may request LLM completions (sampling/createMessage)
+    #[serde(default)]
+    pub allow_sampling: bool,
+
+    /// Root directories advertised to the server via roots/list
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<Root>,
 }
 
 fn default_true() -> bool {
@@ -78,6 +86,8 @@ impl Default for McpServerConfig {
             fallback_to_sse: false,
             enabled: true,
             auto_start: false,
+            allow_sampling: false,
+            roots: Vec::new(),
         }
     }
 }
@@ -364,6 +374,45 @@ pub struct ToolCallResult {
     pub is_error: bool,
 }
 
+impl ToolCallResult {
+    /// Text carried by the result, for indexing or display
+    ///
+    /// Joins text items and embedded text resources; falls back to the structured
+    /// content as JSON when there is no text. Images and binary resources are skipped.
+    pub fn text_content(&self) -> String {
+        let parts: Vec<&str> = self
+            .content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::Text { text } => Some(text.as_str()),
+                ContentItem::Resource { resource } => resource.text.as_deref(),
+                ContentItem::Image { .. } => None,
+            })
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .collect();
+        if !parts.is_empty() {
+            return parts.join("\n\n");
+        }
+        self.structured_content
+            .as_ref()
+            .and_then(|value| serde_json::to_string_pretty(value).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Result of `mcp_call_tool_and_index`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolIndexResult {
+    /// The tool's result, unchanged
+    pub result: ToolCallResult,
+    /// Number of chunks embedded and upserted into the collection
+    pub chunks_indexed: usize,
+    /// Non-fatal issues, such as an embedding model mismatch
+    pub warnings: Vec<String>,
+}
+
 /// Content item in tool results or messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -500,6 +549,7 @@ pub enum SamplingRole {
 #[serde(untagged)]
 pub enum SamplingContent {
     Text(String),
+    Item(ContentItem),
     Items(Vec<ContentItem>),
 }
 
@@ -718,6 +768,45 @@ pub enum ToolCallState {
     Cancelled,
 }
 
+// ============================================================================
+// Tool Usage Tracking
+// ============================================================================
+
+/// A tool pinned as a favorite
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedTool {
+    /// Server ID
+    pub server_id: String,
+
+    /// Tool name
+    pub tool_name: String,
+}
+
+/// Invocation statistics for a tool
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolUsage {
+    /// Server ID
+    pub server_id: String,
+
+    /// Tool name
+    pub tool_name: String,
+
+    /// Number of calls sent to the server
+    pub call_count: u64,
+
+    /// Number of those calls that failed
+    pub error_count: u64,
+
+    /// Timestamp (ms) of the most recent call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<i64>,
+
+    /// Whether the tool is pinned as a favorite
+    pub pinned: bool,
+}
+
 // ============================================================================
 // Reconnection Configuration
 // ============================================================================
@@ -786,6 +875,45 @@ pub struct Root {
     pub name: Option<String>,
 }
 
+impl Root {
+    /// Create a root from a local directory path
+    ///
+    /// The name defaults to the last path component.
+    pub fn from_path(path: &str) -> Self {
+        let normalized = path.replace('\\', "/");
+        let trimmed = normalized.trim_end_matches('/');
+        let name = trimmed
+            .rsplit('/')
+            .next()
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_string());
+        let encoded = trimmed
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        // Windows drive paths (C:/...) need an extra slash after the scheme
+        let uri = if encoded.starts_with('/') {
+            format!("file://{}", encoded)
+        } else {
+            format!("file:///{}", encoded.replacen("%3A", ":", 1))
+        };
+        Self { uri, name }
+    }
+
+    /// Convert a bare filesystem path into a `file://` root, keeping URIs as-is
+    pub fn normalize(self) -> Self {
+        if self.uri.contains("://") {
+            return self;
+        }
+        let root = Self::from_path(&self.uri);
+        Self {
+            name: self.name.or(root.name),
+            ..root
+        }
+    }
+}
+
 /// Server health status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1484,6 +1612,8 @@ mod tests {
             fallback_to_sse: false,
             enabled: true,
             auto_start: true,
+            allow_sampling: false,
+            roots: Vec::new(),
         };
 
         let json = serde_json::to_value(&config).unwrap();
@@ -2028,4 +2158,73 @@ mod tests {
         assert_eq!(json["uri"], "file:///resource.json");
         assert_eq!(json["mimeType"], "application/json");
     }
+
+    // ============================================================================
+    // Root Tests
+    // ============================================================================
+
+    #[test]
+    fn test_root_from_path() {
+        let unix = Root::from_path("/home/user/My Project/");
+        assert_eq!(unix.uri, "file:///home/user/My%20Project");
+        assert_eq!(unix.name.as_deref(), Some("My Project"));
+
+        let windows = Root::from_path("C:\\Users\\dev\\repo");
+        assert_eq!(windows.uri, "file:///C:/Users/dev/repo");
+        assert_eq!(windows.name.as_deref(), Some("repo"));
+    }
+
+    #[test]
+    fn test_root_normalize() {
+        let uri = Root {
+            uri: "file:///workspace".to_string(),
+            name: Some("Workspace".to_string()),
+        }
+        .normalize();
+        assert_eq!(uri.uri, "file:///workspace");
+        assert_eq!(uri.name.as_deref(), Some("Workspace"));
+
+        let path = Root {
+            uri: "/srv/data".to_string(),
+            name: None,
+        }
+        .normalize();
+        assert_eq!(path.uri, "file:///srv/data");
+        assert_eq!(path.name.as_deref(), Some("data"));
+    }
+
+    #[test]
+    fn test_tool_call_result_text_content() {
+        let result = ToolCallResult {
+            content: vec![
+                ContentItem::Text {
+                    text: "First result".to_string(),
+                },
+                ContentItem::Image {
+                    data: "aGVsbG8=".to_string(),
+                    mime_type: "image/png".to_string(),
+                },
+                ContentItem::Resource {
+                    resource: EmbeddedResource {
+                        uri: "file:///notes.md".to_string(),
+                        mime_type: Some("text/markdown".to_string()),
+                        text: Some("  Second result\n".to_string()),
+                        blob: None,
+                    },
+                },
+            ],
+            structured_content: None,
+            meta: None,
+            is_error: false,
+        };
+        assert_eq!(result.text_content(), "First result\n\nSecond result");
+
+        let structured = ToolCallResult {
+            content: vec![],
+            structured_content: Some(serde_json::json!({ "count": 2 })),
+            meta: None,
+            is_error: false,
+        };
+        assert!(structured.text_content().contains("\"count\": 2"));
+    }
 }
@@ -0,0 +1,434 @@
+//! Desktop notification preferences
+//!
+//! Subsystems show desktop notifications through `notify`, which checks the
+//! user's preferences first: a master switch, per-category toggles and a
+//! quiet-hours window. Notifications held back by quiet hours can be kept for a
+//! digest that is shown once quiet hours end; disabled categories are dropped.
+
+use chrono::{NaiveTime, Timelike};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Most notifications kept for one digest; older ones are dropped first
+pub const MAX_DIGEST_ENTRIES: usize = 50;
+
+/// Notification titles listed in the digest body
+const DIGEST_TITLES_SHOWN: usize = 5;
+
+/// Source of a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationCategory {
+    /// Scheduled task runs
+    Scheduler,
+    /// Storage and disk space warnings
+    Storage,
+    /// Focus and awareness suggestions
+    FocusSuggestions,
+    /// Process memory alerts
+    MemoryAlerts,
+    /// Other system notices
+    System,
+}
+
+/// Daily window during which notifications are held back
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub enabled: bool,
+    /// Local start time, "HH:MM"
+    pub start: String,
+    /// Local end time, "HH:MM"; earlier than `start` for windows spanning midnight
+    pub end: String,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+        }
+    }
+}
+
+impl QuietHours {
+    /// Whether `time` falls inside the window; the end time is exclusive
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Ok(start), Ok(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        let time = NaiveTime::from_hms_opt(time.hour(), time.minute(), 0).unwrap_or(time);
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+/// User preferences consulted before showing a notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    /// Master switch for all desktop notifications
+    pub enabled: bool,
+    /// Per-category toggles; categories not listed are enabled
+    #[serde(default)]
+    pub categories: HashMap<NotificationCategory, bool>,
+    #[serde(default)]
+    pub quiet_hours: QuietHours,
+    /// Keep notifications held back by quiet hours for a digest
+    #[serde(default)]
+    pub digest: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            categories: HashMap::new(),
+            quiet_hours: QuietHours::default(),
+            digest: true,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Whether notifications of `category` are enabled
+    pub fn is_enabled(&self, category: NotificationCategory) -> bool {
+        self.enabled && self.categories.get(&category).copied().unwrap_or(true)
+    }
+
+    /// Check that the quiet-hours times parse
+    pub fn validate(&self) -> Result<(), String> {
+        for time in [&self.quiet_hours.start, &self.quiet_hours.end] {
+            parse_time(time)
+                .map_err(|_| format!("Invalid quiet hours time '{}', expected HH:MM", time))?;
+        }
+        Ok(())
+    }
+}
+
+/// Content of a desktop notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPayload {
+    pub title: String,
+    pub body: String,
+}
+
+impl NotificationPayload {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// What happened to a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyDecision {
+    Show,
+    /// Category or all notifications disabled
+    Disabled,
+    /// Held back by quiet hours
+    QuietHours,
+    /// Held back by quiet hours and kept for the digest
+    Queued,
+}
+
+#[derive(Debug, Clone)]
+struct DigestEntry {
+    category: NotificationCategory,
+    payload: NotificationPayload,
+}
+
+/// Notification preferences and the pending quiet-hours digest
+pub struct NotificationCenter {
+    preferences: RwLock<NotificationPreferences>,
+    digest: Mutex<Vec<DigestEntry>>,
+    /// Where preferences are persisted; `None` keeps them in memory
+    path: Option<PathBuf>,
+}
+
+impl NotificationCenter {
+    /// Load preferences from `path`, falling back to defaults
+    pub fn load(path: PathBuf) -> Self {
+        let preferences = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse notification preferences: {}", e);
+                NotificationPreferences::default()
+            }),
+            Err(_) => NotificationPreferences::default(),
+        };
+        Self {
+            preferences: RwLock::new(preferences),
+            digest: Mutex::new(Vec::new()),
+            path: Some(path),
+        }
+    }
+
+    /// Preferences kept in memory only
+    pub fn in_memory(preferences: NotificationPreferences) -> Self {
+        Self {
+            preferences: RwLock::new(preferences),
+            digest: Mutex::new(Vec::new()),
+            path: None,
+        }
+    }
+
+    pub fn preferences(&self) -> NotificationPreferences {
+        self.preferences.read().clone()
+    }
+
+    /// Validate, apply and persist new preferences
+    pub fn set_preferences(&self, preferences: NotificationPreferences) -> Result<(), String> {
+        preferences.validate()?;
+        if let Some(path) = &self.path {
+            let content = serde_json::to_string_pretty(&preferences)
+                .map_err(|e| format!("Failed to serialize notification preferences: {}", e))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create preferences directory: {}", e))?;
+            }
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to save notification preferences: {}", e))?;
+        }
+
+        if !preferences.digest {
+            self.digest.lock().clear();
+        }
+        *self.preferences.write() = preferences;
+        Ok(())
+    }
+
+    /// Decide whether to show a notification at local time `now`, queueing it
+    /// for the digest when quiet hours hold it back
+    pub fn decide(
+        &self,
+        category: NotificationCategory,
+        payload: &NotificationPayload,
+        now: NaiveTime,
+    ) -> NotifyDecision {
+        let preferences = self.preferences.read();
+        if !preferences.is_enabled(category) {
+            return NotifyDecision::Disabled;
+        }
+        if !preferences.quiet_hours.contains(now) {
+            return NotifyDecision::Show;
+        }
+        if !preferences.digest {
+            return NotifyDecision::QuietHours;
+        }
+
+        let mut digest = self.digest.lock();
+        if digest.len() >= MAX_DIGEST_ENTRIES {
+            digest.remove(0);
+        }
+        digest.push(DigestEntry {
+            category,
+            payload: payload.clone(),
+        });
+        NotifyDecision::Queued
+    }
+
+    /// Take the digest once quiet hours are over at local time `now`
+    ///
+    /// Entries of categories disabled since they were queued are left out.
+    pub fn take_digest(&self, now: NaiveTime) -> Option<NotificationPayload> {
+        let preferences = self.preferences.read();
+        if preferences.quiet_hours.contains(now) {
+            return None;
+        }
+        let entries: Vec<DigestEntry> = std::mem::take(&mut *self.digest.lock())
+            .into_iter()
+            .filter(|entry| preferences.is_enabled(entry.category))
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+
+        let mut lines: Vec<String> = entries
+            .iter()
+            .take(DIGEST_TITLES_SHOWN)
+            .map(|entry| format!("• {}", entry.payload.title))
+            .collect();
+        if entries.len() > DIGEST_TITLES_SHOWN {
+            lines.push(format!("and {} more", entries.len() - DIGEST_TITLES_SHOWN));
+        }
+        Some(NotificationPayload::new(
+            format!("{} notifications during quiet hours", entries.len()),
+            lines.join("\n"),
+        ))
+    }
+}
+
+/// Show a desktop notification unless the user's preferences hold it back
+///
+/// Returns whether the notification was shown. Without a managed
+/// `NotificationCenter` notifications are always shown.
+pub fn notify(
+    app: &AppHandle,
+    category: NotificationCategory,
+    payload: NotificationPayload,
+) -> bool {
+    if let Some(center) = app.try_state::<NotificationCenter>() {
+        let decision = center.decide(category, &payload, local_time());
+        if decision != NotifyDecision::Show {
+            log::debug!(
+                "Notification '{}' ({:?}) not shown: {:?}",
+                payload.title,
+                category,
+                decision
+            );
+            return false;
+        }
+    }
+    show(app, &payload)
+}
+
+/// Show the quiet-hours digest if quiet hours have ended
+pub fn flush_digest(app: &AppHandle) {
+    let Some(center) = app.try_state::<NotificationCenter>() else {
+        return;
+    };
+    if let Some(payload) = center.take_digest(local_time()) {
+        show(app, &payload);
+    }
+}
+
+fn show(app: &AppHandle, payload: &NotificationPayload) -> bool {
+    match app
+        .notification()
+        .builder()
+        .title(&payload.title)
+        .body(&payload.body)
+        .show()
+    {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Failed to show notification '{}': {}", payload.title, e);
+            false
+        }
+    }
+}
+
+fn local_time() -> NaiveTime {
+    chrono::Local::now().time()
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, chrono::ParseError> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> NaiveTime {
+        parse_time(time).unwrap()
+    }
+
+    fn quiet_center(digest: bool) -> NotificationCenter {
+        NotificationCenter::in_memory(NotificationPreferences {
+            quiet_hours: QuietHours {
+                enabled: true,
+                start: "22:00".to_string(),
+                end: "07:00".to_string(),
+            },
+            digest,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_quiet_hours_span_midnight() {
+        let quiet = quiet_center(false).preferences().quiet_hours;
+        assert!(quiet.contains(at("23:30")));
+        assert!(quiet.contains(at("06:59")));
+        assert!(!quiet.contains(at("07:00")));
+        assert!(!quiet.contains(at("12:00")));
+
+        let daytime = QuietHours {
+            enabled: true,
+            start: "12:00".to_string(),
+            end: "13:00".to_string(),
+        };
+        assert!(daytime.contains(at("12:30")));
+        assert!(!daytime.contains(at("13:30")));
+    }
+
+    #[test]
+    fn test_disabled_categories_are_dropped() {
+        let mut preferences = NotificationPreferences::default();
+        preferences
+            .categories
+            .insert(NotificationCategory::FocusSuggestions, false);
+        let center = NotificationCenter::in_memory(preferences);
+        let payload = NotificationPayload::new("Take a break", "");
+
+        assert_eq!(
+            center.decide(
+                NotificationCategory::FocusSuggestions,
+                &payload,
+                at("12:00")
+            ),
+            NotifyDecision::Disabled
+        );
+        assert_eq!(
+            center.decide(NotificationCategory::Scheduler, &payload, at("12:00")),
+            NotifyDecision::Show
+        );
+    }
+
+    #[test]
+    fn test_quiet_hours_queue_a_digest() {
+        let center = quiet_center(true);
+        for title in ["Backup finished", "Disk almost full"] {
+            let payload = NotificationPayload::new(title, "");
+            assert_eq!(
+                center.decide(NotificationCategory::Storage, &payload, at("23:00")),
+                NotifyDecision::Queued
+            );
+        }
+
+        assert!(center.take_digest(at("05:00")).is_none());
+        let digest = center
+            .take_digest(at("08:00"))
+            .expect("digest after quiet hours");
+        assert_eq!(digest.title, "2 notifications during quiet hours");
+        assert!(digest.body.contains("Disk almost full"));
+        assert!(center.take_digest(at("08:01")).is_none());
+
+        let center = quiet_center(false);
+        let payload = NotificationPayload::new("Backup finished", "");
+        assert_eq!(
+            center.decide(NotificationCategory::Storage, &payload, at("23:00")),
+            NotifyDecision::QuietHours
+        );
+        assert!(center.take_digest(at("08:00")).is_none());
+    }
+
+    #[test]
+    fn test_set_preferences_validates_times() {
+        let center = NotificationCenter::in_memory(NotificationPreferences::default());
+        let mut preferences = NotificationPreferences::default();
+        preferences.quiet_hours.start = "25:00".to_string();
+        assert!(center.set_preferences(preferences).is_err());
+
+        let json = r#"{"enabled":true,"categories":{"memoryAlerts":false}}"#;
+        let parsed: NotificationPreferences = serde_json::from_str(json).unwrap();
+        assert!(!parsed.is_enabled(NotificationCategory::MemoryAlerts));
+        assert!(parsed.is_enabled(NotificationCategory::Scheduler));
+        assert!(center.set_preferences(parsed).is_ok());
+    }
+}
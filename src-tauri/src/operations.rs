@@ -0,0 +1,414 @@
+//! Long-running operation registry
+//!
+//! Commands that take a while (embedding batches, extractions, downloads) can
+//! register an operation, hand its id back to the caller immediately and keep
+//! working in the background. Progress, completion, failure and cancellation
+//! are recorded here so the frontend can poll every kind of task the same way,
+//! following the model `ProcessManager` uses for its batch operations.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Maximum number of operations kept for listing
+pub const MAX_OPERATION_HISTORY: usize = 200;
+/// Default number of operations returned by `list`
+pub const DEFAULT_OPERATION_LIST_LIMIT: usize = 50;
+
+static GLOBAL_REGISTRY: Lazy<OperationRegistry> = Lazy::new(OperationRegistry::new);
+
+/// Get the process-wide registry shared by all long-running commands
+pub fn global() -> &'static OperationRegistry {
+    &GLOBAL_REGISTRY
+}
+
+/// Operation status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl OperationStatus {
+    /// Whether the operation has finished
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            OperationStatus::Completed | OperationStatus::Failed | OperationStatus::Cancelled
+        )
+    }
+}
+
+/// Progress reported by a running operation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationProgress {
+    /// Units of work done
+    pub completed: u64,
+    /// Total units of work, when known
+    pub total: Option<u64>,
+    /// Short description of the current step
+    pub message: Option<String>,
+}
+
+/// Operation record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    /// Unique operation ID
+    pub operation_id: String,
+    /// What kind of task this is (e.g. "vector.embedBatch")
+    pub kind: String,
+    /// Current status
+    pub status: OperationStatus,
+    /// Operation creation timestamp (unix ms)
+    pub created_at: i64,
+    /// Operation start timestamp (unix ms)
+    pub started_at: Option<i64>,
+    /// Operation completion timestamp (unix ms)
+    pub completed_at: Option<i64>,
+    /// Latest progress report
+    pub progress: Option<OperationProgress>,
+    /// Error message when failed
+    pub error: Option<String>,
+    /// Result payload when completed
+    pub result: Option<serde_json::Value>,
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    operations: RwLock<HashMap<String, Operation>>,
+    /// Operation ids, oldest first
+    order: RwLock<VecDeque<String>>,
+    cancellations: RwLock<HashMap<String, CancellationToken>>,
+}
+
+/// Registry of background operations
+#[derive(Clone, Default)]
+pub struct OperationRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+/// Handle given to the task running an operation
+#[derive(Clone)]
+pub struct OperationHandle {
+    registry: OperationRegistry,
+    operation_id: String,
+    cancel: CancellationToken,
+}
+
+impl OperationHandle {
+    pub fn id(&self) -> &str {
+        &self.operation_id
+    }
+
+    /// Whether cancellation was requested; tasks should check this between steps
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Token that resolves when cancellation is requested
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Record progress
+    pub fn set_progress(&self, completed: u64, total: Option<u64>, message: Option<String>) {
+        self.registry.update(&self.operation_id, |operation| {
+            operation.progress = Some(OperationProgress {
+                completed,
+                total,
+                message,
+            });
+        });
+    }
+}
+
+fn now_timestamp_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+impl OperationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a pending operation and return a handle for the task that runs it
+    pub fn register(&self, kind: &str) -> (Operation, OperationHandle) {
+        let operation = Operation {
+            operation_id: Uuid::new_v4().to_string(),
+            kind: kind.to_string(),
+            status: OperationStatus::Pending,
+            created_at: now_timestamp_ms(),
+            started_at: None,
+            completed_at: None,
+            progress: None,
+            error: None,
+            result: None,
+        };
+        let operation_id = operation.operation_id.clone();
+        let cancel = CancellationToken::new();
+
+        self.inner
+            .cancellations
+            .write()
+            .insert(operation_id.clone(), cancel.clone());
+        {
+            let mut operations = self.inner.operations.write();
+            let mut order = self.inner.order.write();
+            operations.insert(operation_id.clone(), operation.clone());
+            order.push_back(operation_id.clone());
+
+            // Only finished operations are evicted, so a running task never loses
+            // its record; the history may grow past the limit until they finish
+            while order.len() > MAX_OPERATION_HISTORY {
+                let Some(position) = order.iter().position(|id| {
+                    operations
+                        .get(id)
+                        .map_or(true, |operation| operation.status.is_terminal())
+                }) else {
+                    break;
+                };
+                if let Some(evicted_id) = order.remove(position) {
+                    operations.remove(&evicted_id);
+                    self.inner.cancellations.write().remove(&evicted_id);
+                }
+            }
+        }
+
+        log::debug!("Registered operation {} ({})", operation_id, kind);
+        let handle = OperationHandle {
+            registry: self.clone(),
+            operation_id,
+            cancel,
+        };
+        (operation, handle)
+    }
+
+    /// Run `task` in the background as a new operation and return its record immediately
+    ///
+    /// The task's `Ok` value becomes the operation result. An operation cancelled
+    /// while running is recorded as cancelled whatever the task returns.
+    pub fn spawn<T, F, Fut>(&self, kind: &str, task: F) -> Operation
+    where
+        T: Serialize + Send + 'static,
+        F: FnOnce(OperationHandle) -> Fut,
+        Fut: Future<Output = Result<T, String>> + Send + 'static,
+    {
+        let (operation, handle) = self.register(kind);
+        let future = task(handle.clone());
+        tauri::async_runtime::spawn(async move {
+            handle.registry.mark_running(&handle.operation_id);
+            let outcome = future.await;
+            handle.registry.finish(&handle.operation_id, outcome);
+        });
+        operation
+    }
+
+    fn update(&self, operation_id: &str, apply: impl FnOnce(&mut Operation)) {
+        if let Some(operation) = self.inner.operations.write().get_mut(operation_id) {
+            if !operation.status.is_terminal() {
+                apply(operation);
+            }
+        }
+    }
+
+    /// Mark an operation as running
+    pub fn mark_running(&self, operation_id: &str) {
+        self.update(operation_id, |operation| {
+            operation.status = OperationStatus::Running;
+            operation.started_at = Some(now_timestamp_ms());
+        });
+    }
+
+    /// Record the outcome of an operation
+    pub fn finish<T: Serialize>(&self, operation_id: &str, outcome: Result<T, String>) {
+        let cancelled = self
+            .inner
+            .cancellations
+            .write()
+            .remove(operation_id)
+            .is_some_and(|token| token.is_cancelled());
+        self.update(operation_id, |operation| {
+            operation.completed_at = Some(now_timestamp_ms());
+            if cancelled {
+                operation.status = OperationStatus::Cancelled;
+                return;
+            }
+            match outcome.and_then(|value| {
+                serde_json::to_value(value)
+                    .map_err(|e| format!("Failed to serialize operation result: {}", e))
+            }) {
+                Ok(result) => {
+                    operation.status = OperationStatus::Completed;
+                    operation.result = Some(result);
+                }
+                Err(error) => {
+                    log::warn!("Operation {} failed: {}", operation.operation_id, error);
+                    operation.status = OperationStatus::Failed;
+                    operation.error = Some(error);
+                }
+            }
+        });
+    }
+
+    /// Get an operation by ID
+    pub fn get(&self, operation_id: &str) -> Option<Operation> {
+        self.inner.operations.read().get(operation_id).cloned()
+    }
+
+    /// List recent operations, most recent first
+    pub fn list(&self, limit: Option<usize>) -> Vec<Operation> {
+        let max = limit
+            .unwrap_or(DEFAULT_OPERATION_LIST_LIMIT)
+            .clamp(1, MAX_OPERATION_HISTORY);
+        let ids: Vec<String> = self
+            .inner
+            .order
+            .read()
+            .iter()
+            .rev()
+            .take(max)
+            .cloned()
+            .collect();
+        let operations = self.inner.operations.read();
+        ids.into_iter()
+            .filter_map(|id| operations.get(&id).cloned())
+            .collect()
+    }
+
+    /// Request cancellation of an unfinished operation
+    ///
+    /// Returns whether a cancellation was requested. The task decides when to stop;
+    /// the operation is recorded as cancelled once it returns.
+    pub fn cancel(&self, operation_id: &str) -> bool {
+        let Some(token) = self.inner.cancellations.read().get(operation_id).cloned() else {
+            return false;
+        };
+        token.cancel();
+        log::info!("Cancellation requested for operation {}", operation_id);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_finish() {
+        let registry = OperationRegistry::new();
+        let (operation, handle) = registry.register("test.kind");
+        assert_eq!(operation.status, OperationStatus::Pending);
+
+        registry.mark_running(handle.id());
+        handle.set_progress(1, Some(2), Some("halfway".to_string()));
+        let running = registry.get(handle.id()).unwrap();
+        assert_eq!(running.status, OperationStatus::Running);
+        assert_eq!(running.progress.unwrap().completed, 1);
+
+        registry.finish(handle.id(), Ok(42));
+        let done = registry.get(handle.id()).unwrap();
+        assert_eq!(done.status, OperationStatus::Completed);
+        assert_eq!(done.result, Some(serde_json::json!(42)));
+        assert!(done.completed_at.is_some());
+
+        // Finished operations can no longer be cancelled or updated
+        assert!(!registry.cancel(handle.id()));
+        handle.set_progress(2, Some(2), None);
+        assert_eq!(
+            registry
+                .get(handle.id())
+                .unwrap()
+                .progress
+                .unwrap()
+                .completed,
+            1
+        );
+    }
+
+    #[test]
+    fn test_cancel_overrides_outcome() {
+        let registry = OperationRegistry::new();
+        let (_, handle) = registry.register("test.kind");
+        registry.mark_running(handle.id());
+
+        assert!(registry.cancel(handle.id()));
+        assert!(handle.is_cancelled());
+        registry.finish::<()>(handle.id(), Err("interrupted".to_string()));
+
+        let operation = registry.get(handle.id()).unwrap();
+        assert_eq!(operation.status, OperationStatus::Cancelled);
+        assert!(operation.error.is_none());
+        assert!(!registry.cancel("missing"));
+    }
+
+    #[test]
+    fn test_list_recent_first_and_evicts_oldest_finished() {
+        let registry = OperationRegistry::new();
+        let (pending, _handle) = registry.register("test.kind");
+        let ids: Vec<String> = (0..MAX_OPERATION_HISTORY + 4)
+            .map(|_| {
+                let (operation, handle) = registry.register("test.kind");
+                registry.finish(handle.id(), Ok(()));
+                operation.operation_id
+            })
+            .collect();
+
+        let listed = registry.list(Some(3));
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0].operation_id, ids[ids.len() - 1]);
+        assert_eq!(listed[2].operation_id, ids[ids.len() - 3]);
+
+        assert_eq!(registry.list(Some(usize::MAX)).len(), MAX_OPERATION_HISTORY);
+        // The oldest operation is still pending, so finished ones go first
+        assert!(registry.get(&pending.operation_id).is_some());
+        assert!(registry.get(&ids[4]).is_none());
+        assert!(registry.get(&ids[5]).is_some());
+    }
+
+    #[test]
+    fn test_unfinished_operations_are_never_evicted() {
+        let registry = OperationRegistry::new();
+        let handles: Vec<OperationHandle> = (0..MAX_OPERATION_HISTORY + 2)
+            .map(|_| registry.register("test.kind").1)
+            .collect();
+        assert!(handles
+            .iter()
+            .all(|handle| registry.get(handle.id()).is_some()));
+
+        registry.finish(handles[3].id(), Ok(()));
+        registry.register("test.kind");
+        assert!(registry.get(handles[3].id()).is_none());
+        assert!(registry.get(handles[0].id()).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_spawn_records_result() {
+        let registry = OperationRegistry::new();
+        let operation = registry.spawn("test.kind", |handle| async move {
+            handle.set_progress(1, Some(1), None);
+            Ok::<_, String>("done")
+        });
+
+        for _ in 0..100 {
+            let current = registry.get(&operation.operation_id).unwrap();
+            if current.status.is_terminal() {
+                assert_eq!(current.status, OperationStatus::Completed);
+                assert_eq!(current.result, Some(serde_json::json!("done")));
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("operation did not complete");
+    }
+}
@@ -369,6 +369,7 @@ async fn dispatch_api(
             )
             .map_err(io_error)?;
 
+            crate::http::ensure_url_allowed(&payload.url).map_err(io_error)?;
             let client = crate::http::create_proxy_client_long().map_err(io_error)?;
             let mut request = client.request(method, payload.url);
             if let Some(headers) = options.headers {
@@ -460,6 +461,7 @@ async fn dispatch_api(
             if let Some(parent) = target.parent() {
                 std::fs::create_dir_all(parent).map_err(io_error)?;
             }
+            crate::http::ensure_url_allowed(url).map_err(io_error)?;
             let client = crate::http::create_proxy_client_long().map_err(io_error)?;
             let response = client.get(url).send().await.map_err(io_error)?;
             let content_type = response
@@ -495,6 +497,7 @@ async fn dispatch_api(
                 })?;
             let source = resolve_plugin_path(Path::new(&runtime_dirs.data), file_path)?;
             let file = tokio::fs::read(source).await.map_err(io_error)?;
+            crate::http::ensure_url_allowed(url).map_err(io_error)?;
             let client = crate::http::create_proxy_client_long().map_err(io_error)?;
             let response = client.post(url).body(file).send().await.map_err(io_error)?;
             let status = response.status();
@@ -0,0 +1,179 @@
+//! Decoding of captured process output
+//!
+//! Console programs write in whatever encoding the platform uses for them,
+//! which on Windows is usually the console codepage (e.g. GBK/CP936) rather
+//! than UTF-8.
+
+use encoding_rs::Encoding;
+
+/// Encoding used to decode captured stdout/stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputEncoding {
+    /// UTF-8, with invalid sequences replaced
+    Utf8,
+    /// UTF-8 when the output is valid UTF-8, otherwise the system codepage
+    Auto,
+    /// A specific encoding
+    Named(&'static Encoding),
+}
+
+impl OutputEncoding {
+    /// Parse an `output_encoding` request value.
+    ///
+    /// Accepts `utf-8`, `auto`, WHATWG encoding labels (`gbk`, `shift_jis`,
+    /// `windows-1252`, ...) and Windows codepages (`cp936`, `936`). When unset,
+    /// Windows detects the console codepage and other platforms use UTF-8.
+    pub fn parse(label: Option<&str>) -> Result<Self, String> {
+        let Some(label) = label.map(str::trim).filter(|l| !l.is_empty()) else {
+            return Ok(Self::platform_default());
+        };
+
+        match label.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(Self::Utf8),
+            "auto" => Ok(Self::Auto),
+            other => encoding_for_name(other)
+                .map(Self::Named)
+                .ok_or_else(|| format!("Unsupported output encoding '{}'", label)),
+        }
+    }
+
+    fn platform_default() -> Self {
+        if cfg!(windows) {
+            Self::Auto
+        } else {
+            Self::Utf8
+        }
+    }
+
+    /// Decode captured output bytes
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Self::Auto => match std::str::from_utf8(bytes) {
+                Ok(text) => text.to_string(),
+                Err(_) => match system_encoding() {
+                    Some(encoding) => decode_with(encoding, bytes),
+                    None => String::from_utf8_lossy(bytes).into_owned(),
+                },
+            },
+            Self::Named(encoding) => decode_with(encoding, bytes),
+        }
+    }
+}
+
+fn decode_with(encoding: &'static Encoding, bytes: &[u8]) -> String {
+    encoding.decode(bytes).0.into_owned()
+}
+
+fn encoding_for_name(name: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(name.as_bytes()).or_else(|| {
+        let number = name.strip_prefix("cp").unwrap_or(name);
+        number.parse().ok().and_then(encoding_for_codepage)
+    })
+}
+
+/// Encoding for a Windows codepage number, if supported
+pub fn encoding_for_codepage(codepage: u32) -> Option<&'static Encoding> {
+    let encoding = match codepage {
+        65001 => encoding_rs::UTF_8,
+        936 => encoding_rs::GBK,
+        54936 => encoding_rs::GB18030,
+        950 => encoding_rs::BIG5,
+        932 => encoding_rs::SHIFT_JIS,
+        949 => encoding_rs::EUC_KR,
+        866 => encoding_rs::IBM866,
+        874 => encoding_rs::WINDOWS_874,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1252 => encoding_rs::WINDOWS_1252,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        20866 => encoding_rs::KOI8_R,
+        21866 => encoding_rs::KOI8_U,
+        _ => return None,
+    };
+    Some(encoding)
+}
+
+/// Encoding console programs write in, used by `Auto` for non-UTF-8 output
+#[cfg(windows)]
+fn system_encoding() -> Option<&'static Encoding> {
+    encoding_for_codepage(super::windows::console_codepage())
+}
+
+#[cfg(not(windows))]
+fn system_encoding() -> Option<&'static Encoding> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "你好" in GBK
+    const GBK_NIHAO: &[u8] = &[0xc4, 0xe3, 0xba, 0xc3];
+
+    #[test]
+    fn test_parse_labels_and_codepages() {
+        assert_eq!(
+            OutputEncoding::parse(Some("UTF-8")).unwrap(),
+            OutputEncoding::Utf8
+        );
+        assert_eq!(
+            OutputEncoding::parse(Some("auto")).unwrap(),
+            OutputEncoding::Auto
+        );
+        assert_eq!(
+            OutputEncoding::parse(Some("gbk")).unwrap(),
+            OutputEncoding::Named(encoding_rs::GBK)
+        );
+        assert_eq!(
+            OutputEncoding::parse(Some("cp936")).unwrap(),
+            OutputEncoding::Named(encoding_rs::GBK)
+        );
+        assert_eq!(
+            OutputEncoding::parse(Some("932")).unwrap(),
+            OutputEncoding::Named(encoding_rs::SHIFT_JIS)
+        );
+        assert!(OutputEncoding::parse(Some("cp99999")).is_err());
+        assert!(OutputEncoding::parse(Some("klingon")).is_err());
+    }
+
+    #[test]
+    fn test_default_follows_platform() {
+        let expected = if cfg!(windows) {
+            OutputEncoding::Auto
+        } else {
+            OutputEncoding::Utf8
+        };
+        assert_eq!(OutputEncoding::parse(None).unwrap(), expected);
+        assert_eq!(OutputEncoding::parse(Some("  ")).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_decode_non_utf8_output() {
+        let gbk = OutputEncoding::parse(Some("gbk")).unwrap();
+        assert_eq!(gbk.decode(GBK_NIHAO), "你好");
+
+        // Shift_JIS "テスト"
+        let sjis = OutputEncoding::parse(Some("shift_jis")).unwrap();
+        assert_eq!(sjis.decode(&[0x83, 0x65, 0x83, 0x58, 0x83, 0x67]), "テスト");
+
+        // Windows-1252 "café"
+        let latin = OutputEncoding::parse(Some("cp1252")).unwrap();
+        assert_eq!(latin.decode(b"caf\xe9"), "café");
+
+        // Decoding as UTF-8 is what produced mojibake before
+        assert!(OutputEncoding::Utf8.decode(GBK_NIHAO).contains('\u{fffd}'));
+    }
+
+    #[test]
+    fn test_auto_keeps_valid_utf8() {
+        assert_eq!(OutputEncoding::Auto.decode("héllo".as_bytes()), "héllo");
+        assert!(!OutputEncoding::Auto.decode(GBK_NIHAO).is_empty());
+    }
+}
@@ -11,19 +11,46 @@
 
 use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sysinfo::{Pid, ProcessesToUpdate, System};
-use tokio::sync::RwLock;
+use tokio::sync::{watch, Notify, RwLock};
+use tokio_util::sync::{CancellationToken, DropGuard};
 use uuid::Uuid;
 
+pub use encoding::OutputEncoding;
+pub use output::{
+    OutputStream, ProcessOutputChunk, ProcessStreamEvent, ProcessStreamExit, ProcessStreamSink,
+    StartProcessStreamResult, MAX_OUTPUT_STREAMS, PROCESS_OUTPUT_EVENT, PROCESS_STREAM_EXIT_EVENT,
+};
+
+mod encoding;
+mod output;
 #[cfg(windows)]
 mod windows;
 
 #[cfg(unix)]
 mod unix;
 
+/// State of a child process started by this app
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChildState {
+    Running,
+    /// Exited, with its exit code unless killed by a signal
+    Exited(Option<i32>),
+}
+
+#[cfg(unix)]
+use unix::signal_ends_process;
+
+/// Only termination signals are accepted outside Unix
+#[cfg(not(unix))]
+fn signal_ends_process(_signal: &str) -> bool {
+    true
+}
+
 /// Maximum number of processes to list at once
 pub const MAX_PROCESS_LIST: usize = 500;
 
@@ -35,6 +62,26 @@ pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
 pub const MAX_BATCH_CONCURRENCY: usize = 16;
 /// Maximum number of async operation records kept in memory
 pub const MAX_OPERATION_HISTORY: usize = 200;
+/// Event emitted when a watched process exceeds its memory threshold
+pub const MEMORY_ALERT_EVENT: &str = "process://memory-alert";
+/// Maximum number of concurrent memory watches
+pub const MAX_MEMORY_WATCHES: usize = 32;
+/// Default interval between memory samples (milliseconds)
+pub const DEFAULT_MEMORY_SAMPLE_INTERVAL_MS: u64 = 2000;
+/// Minimum interval between memory samples (milliseconds)
+pub const MIN_MEMORY_SAMPLE_INTERVAL_MS: u64 = 250;
+/// Default interval between CPU/memory history samples (milliseconds)
+pub const DEFAULT_HISTORY_SAMPLE_INTERVAL_MS: u64 = 1000;
+/// Lower bound for the history sampling interval (milliseconds)
+pub const MIN_HISTORY_SAMPLE_INTERVAL_MS: u64 = 100;
+/// Default number of history samples kept per process
+pub const DEFAULT_HISTORY_CAPACITY: usize = 120;
+/// Maximum number of processes sampled at once
+pub const MAX_SAMPLED_PROCESSES: usize = 32;
+/// Interval between checks while waiting for a process to exit
+pub const EXIT_POLL_INTERVAL_MS: u64 = 100;
+/// Default minimum interval between full process list refreshes (milliseconds)
+pub const DEFAULT_MIN_REFRESH_INTERVAL_MS: u64 = 2000;
 
 /// Process information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +109,9 @@ pub struct ProcessInfo {
     pub user: Option<String>,
     /// Working directory
     pub cwd: Option<String>,
+    /// Environment variables, only read when requested
+    #[serde(default)]
+    pub env: Option<HashMap<String, String>>,
 }
 
 /// Process status
@@ -71,6 +121,8 @@ pub enum ProcessStatus {
     Running,
     Sleeping,
     Stopped,
+    /// Paused with `ProcessManager::suspend_process`
+    Suspended,
     Zombie,
     Unknown,
 }
@@ -103,6 +155,8 @@ pub struct ProcessFilter {
     pub sort_by: Option<ProcessSortField>,
     /// Sort descending
     pub sort_desc: Option<bool>,
+    /// Read each listed process's environment variables
+    pub include_env: Option<bool>,
 }
 
 /// Sort field for process list
@@ -139,6 +193,11 @@ pub struct StartProcessRequest {
     /// Capture stdout/stderr (for non-detached)
     #[serde(default)]
     pub capture_output: bool,
+    /// Encoding of captured output: "utf-8", "auto", an encoding label such as
+    /// "gbk", or a Windows codepage such as "cp936". Defaults to the console
+    /// codepage on Windows and UTF-8 elsewhere.
+    #[serde(default)]
+    pub output_encoding: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -174,6 +233,10 @@ pub struct TerminateProcessRequest {
     /// Force kill (SIGKILL on Unix, TerminateProcess on Windows)
     #[serde(default)]
     pub force: bool,
+    /// Signal to send instead of SIGTERM, by name ("SIGINT", "HUP") or number;
+    /// `force` takes precedence. Windows only accepts SIGTERM and SIGKILL.
+    #[serde(default)]
+    pub signal: Option<String>,
     /// Timeout for graceful termination (seconds)
     pub timeout_secs: Option<u64>,
 }
@@ -264,6 +327,46 @@ pub struct TerminateProcessBatchResult {
     pub results: Vec<TerminateProcessBatchItemResult>,
 }
 
+/// Result for one process of a terminated process tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateProcessTreeItemResult {
+    /// Process ID
+    pub pid: u32,
+    /// Parent process ID within the tree (None for the root)
+    pub parent_pid: Option<u32>,
+    /// Distance from the root process (0 for the root)
+    pub depth: usize,
+    /// Operation result
+    pub result: TerminateProcessResult,
+}
+
+/// Result of terminating a process and all of its descendants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateProcessTreeResult {
+    /// Root process ID
+    pub pid: u32,
+    /// Number of processes in the tree
+    pub total: usize,
+    /// Number of processes terminated
+    pub success_count: usize,
+    /// Number of processes that could not be terminated
+    pub failure_count: usize,
+    /// Per-process results in termination order (children before parents)
+    pub results: Vec<TerminateProcessTreeItemResult>,
+}
+
+/// Result of waiting for a process to exit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessWaitResult {
+    /// Whether the process exited before the timeout
+    pub exited: bool,
+    /// Exit code, if it could be collected
+    pub exit_code: Option<i32>,
+}
+
 /// Async operation type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -312,6 +415,118 @@ pub struct ProcessOperation {
     pub result: Option<ProcessOperationResult>,
 }
 
+/// Request to watch a process's memory usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryWatchRequest {
+    /// Process ID to watch
+    pub pid: u32,
+    /// Resident memory threshold in bytes
+    pub max_bytes: u64,
+    /// Sampling interval (milliseconds)
+    #[serde(default, alias = "interval_ms")]
+    pub interval_ms: Option<u64>,
+    /// Terminate the process when the threshold is exceeded
+    #[serde(default, alias = "auto_terminate")]
+    pub auto_terminate: bool,
+}
+
+/// Active memory watch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryWatch {
+    /// Watched process ID
+    pub pid: u32,
+    /// Resident memory threshold in bytes
+    pub max_bytes: u64,
+    /// Sampling interval (milliseconds)
+    pub interval_ms: u64,
+    /// Whether the process is terminated when the threshold is exceeded
+    pub auto_terminate: bool,
+    /// Watch creation timestamp (unix ms)
+    pub created_at: i64,
+    /// Most recent memory sample in bytes
+    pub last_memory_bytes: Option<u64>,
+    /// Number of alerts raised so far
+    pub alert_count: u32,
+}
+
+/// Payload of [`MEMORY_ALERT_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryAlert {
+    /// Process ID
+    pub pid: u32,
+    /// Process name
+    pub name: String,
+    /// Sampled memory in bytes
+    pub memory_bytes: u64,
+    /// Threshold that was exceeded
+    pub max_bytes: u64,
+    /// Whether the process was terminated
+    pub terminated: bool,
+    /// Error from auto-termination, if it failed
+    pub terminate_error: Option<String>,
+    /// Alert timestamp (unix ms)
+    pub timestamp: i64,
+}
+
+/// One CPU/memory sample of a process
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessSample {
+    /// Sample timestamp (unix ms)
+    pub timestamp: i64,
+    /// CPU usage percentage (0-100, across all cores)
+    pub cpu_percent: f32,
+    /// Resident memory in bytes
+    pub memory_bytes: u64,
+}
+
+/// Recent samples of a process, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessHistory {
+    /// Process ID
+    pub pid: u32,
+    /// Sampling interval (milliseconds)
+    pub interval_ms: u64,
+    /// Samples, capped at `historyCapacity`
+    pub samples: Vec<ProcessSample>,
+}
+
+/// Callback invoked for each memory alert
+pub type MemoryAlertSink = Arc<dyn Fn(MemoryAlert) + Send + Sync>;
+
+struct OutputStreamEntry {
+    pid: Option<u32>,
+    /// Kills the process when the entry is removed
+    _stop_on_drop: DropGuard,
+}
+
+struct MemoryWatchEntry {
+    /// Distinguishes this watch from a later replacement on the same PID
+    id: Uuid,
+    watch: MemoryWatch,
+    cancel: CancellationToken,
+}
+
+struct HistoryEntry {
+    interval: std::time::Duration,
+    next_due: Instant,
+    samples: VecDeque<ProcessSample>,
+}
+
+/// Sampled processes, shared with the single sampler task
+#[derive(Default)]
+struct HistoryState {
+    entries: HashMap<u32, HistoryEntry>,
+    /// Whether the sampler task is running; it stops once `entries` is empty
+    sampler_running: bool,
+    /// When the sampler last read the process table
+    last_sampled_at: Option<Instant>,
+}
+
 fn now_timestamp_ms() -> i64 {
     chrono::Utc::now().timestamp_millis()
 }
@@ -346,6 +561,13 @@ pub struct ProcessManagerConfig {
     /// Default timeout for operations
     #[serde(alias = "default_timeout_secs")]
     pub default_timeout_secs: u64,
+    /// Minimum interval between full process list refreshes; listings within
+    /// the interval reuse the cached snapshot (0 = always refresh)
+    #[serde(alias = "min_refresh_interval_ms")]
+    pub min_refresh_interval_ms: u64,
+    /// Number of CPU/memory samples kept per sampled process
+    #[serde(alias = "history_capacity")]
+    pub history_capacity: usize,
 }
 
 impl Default for ProcessManagerConfig {
@@ -367,10 +589,24 @@ impl Default for ProcessManagerConfig {
             only_terminate_own: true,
             max_tracked_processes: 100,
             default_timeout_secs: DEFAULT_OPERATION_TIMEOUT,
+            min_refresh_interval_ms: DEFAULT_MIN_REFRESH_INTERVAL_MS,
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
         }
     }
 }
 
+/// Process list together with the age of the snapshot it was read from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessListSnapshot {
+    /// Matching processes
+    pub processes: Vec<ProcessInfo>,
+    /// Time since the process snapshot was taken (milliseconds)
+    pub snapshot_age_ms: u64,
+    /// Whether this call refreshed the snapshot
+    pub refreshed: bool,
+}
+
 /// Apply filter to a process (shared across all platforms)
 pub fn apply_filter(info: &ProcessInfo, filter: &ProcessFilter) -> bool {
     if let Some(pid) = filter.pid {
@@ -450,6 +686,61 @@ pub fn sort_processes(processes: &mut [ProcessInfo], sort_by: ProcessSortField,
     });
 }
 
+/// Memory available for new allocations system-wide, in bytes
+pub fn available_memory_bytes() -> u64 {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    sys.available_memory()
+}
+
+/// Processes in the tree rooted at `root` as `(pid, parent_pid, depth)`, every
+/// process listed after all of its descendants
+///
+/// `links` pairs each process with its parent. Each process is visited once, so
+/// cycles in the parent links (possible after PID reuse) cannot loop forever.
+fn process_tree_postorder(
+    root: u32,
+    links: &[(u32, Option<u32>)],
+) -> Vec<(u32, Option<u32>, usize)> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for &(pid, parent) in links {
+        if let Some(parent) = parent.filter(|&parent| parent != pid) {
+            children.entry(parent).or_default().push(pid);
+        }
+    }
+
+    let mut visited = HashSet::from([root]);
+    let mut order = Vec::new();
+    // (pid, parent, depth, children already pushed)
+    let mut stack = vec![(root, None, 0, false)];
+    while let Some((pid, parent, depth, expanded)) = stack.pop() {
+        if expanded {
+            order.push((pid, parent, depth));
+            continue;
+        }
+        stack.push((pid, parent, depth, true));
+        for &child in children.get(&pid).into_iter().flatten() {
+            if visited.insert(child) {
+                stack.push((child, Some(pid), depth + 1, false));
+            }
+        }
+    }
+    order
+}
+
+/// Key of a process in the suspended set; the start time tells a reused PID apart
+fn suspended_key(info: &ProcessInfo) -> (u32, u64) {
+    (info.pid, info.start_time.unwrap_or_default())
+}
+
+/// Report processes suspended by this app as `Suspended`
+fn mark_suspended(mut info: ProcessInfo, suspended: &HashSet<(u32, u64)>) -> ProcessInfo {
+    if suspended.contains(&suspended_key(&info)) {
+        info.status = ProcessStatus::Suspended;
+    }
+    info
+}
+
 /// Convert a sysinfo process to our ProcessInfo struct
 fn sysinfo_to_process_info(pid: &Pid, proc: &sysinfo::Process) -> ProcessInfo {
     let num_cpus = sysinfo::System::new().cpus().len().max(1) as f32;
@@ -482,9 +773,42 @@ fn sysinfo_to_process_info(pid: &Pid, proc: &sysinfo::Process) -> ProcessInfo {
         start_time: Some(proc.start_time()),
         user: proc.user_id().map(|u| u.to_string()),
         cwd: proc.cwd().map(|p| p.to_string_lossy().to_string()),
+        env: None,
     }
 }
 
+/// Build an environment map from `KEY=VALUE` entries
+///
+/// The separator is searched from the second character on, so Windows
+/// per-drive entries like `=C:=C:\dir` keep their leading `=` in the key.
+fn env_from_entries<I, S>(entries: I) -> HashMap<String, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let entry = entry.as_ref();
+            let split = entry.char_indices().skip(1).find(|(_, c)| *c == '=')?.0;
+            Some((entry[..split].to_string(), entry[split + 1..].to_string()))
+        })
+        .collect()
+}
+
+/// Read the environment of another process
+fn read_process_env(pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+    #[cfg(windows)]
+    return windows::read_process_env(pid);
+    #[cfg(unix)]
+    return unix::read_process_env(pid);
+    #[cfg(not(any(windows, unix)))]
+    Err(ProcessError::Unsupported(format!(
+        "Cannot read environment of process {} on this platform",
+        pid
+    )))
+}
+
 /// Process manager state
 #[derive(Clone)]
 pub struct ProcessManager {
@@ -492,14 +816,28 @@ pub struct ProcessManager {
     pub config: Arc<RwLock<ProcessManagerConfig>>,
     /// Tracked processes (PIDs started by this app)
     tracked_processes: Arc<RwLock<Vec<u32>>>,
+    /// State of tracked children whose handles this app holds, by PID
+    children: Arc<RwLock<HashMap<u32, watch::Receiver<ChildState>>>>,
     /// Config file path
     config_path: PathBuf,
     /// sysinfo System instance for process queries
     sys: Arc<RwLock<System>>,
+    /// When all processes were last refreshed by a listing
+    last_full_refresh: Arc<RwLock<Option<Instant>>>,
     /// Async operation records
     operations: Arc<RwLock<HashMap<String, ProcessOperation>>>,
     /// Operation order for recency listing
     operation_order: Arc<RwLock<Vec<String>>>,
+    /// Memory watches by PID
+    memory_watches: Arc<RwLock<HashMap<u32, MemoryWatchEntry>>>,
+    /// Processes suspended by this app, by PID and start time
+    suspended_processes: Arc<RwLock<HashSet<(u32, u64)>>>,
+    /// CPU/memory history of sampled processes
+    history: Arc<RwLock<HistoryState>>,
+    /// Wakes the sampler task when sampling starts or changes
+    history_wakeup: Arc<Notify>,
+    /// Processes streaming their output, by handle ID
+    output_streams: Arc<RwLock<HashMap<String, OutputStreamEntry>>>,
 }
 
 impl ProcessManager {
@@ -533,10 +871,17 @@ impl ProcessManager {
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
             tracked_processes: Arc::new(RwLock::new(Vec::new())),
+            children: Arc::new(RwLock::new(HashMap::new())),
             config_path,
             sys: Arc::new(RwLock::new(sys)),
+            last_full_refresh: Arc::new(RwLock::new(None)),
             operations: Arc::new(RwLock::new(HashMap::new())),
             operation_order: Arc::new(RwLock::new(Vec::new())),
+            memory_watches: Arc::new(RwLock::new(HashMap::new())),
+            suspended_processes: Arc::new(RwLock::new(HashSet::new())),
+            history: Arc::new(RwLock::new(HistoryState::default())),
+            history_wakeup: Arc::new(Notify::new()),
+            output_streams: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -637,7 +982,8 @@ impl ProcessManager {
 
         // Remove if at capacity
         if tracked.len() >= config.max_tracked_processes {
-            tracked.remove(0);
+            let evicted = tracked.remove(0);
+            self.children.write().await.remove(&evicted);
         }
 
         tracked.push(pid);
@@ -647,6 +993,17 @@ impl ProcessManager {
     pub async fn untrack_process(&self, pid: u32) {
         let mut tracked = self.tracked_processes.write().await;
         tracked.retain(|&p| p != pid);
+        self.children.write().await.remove(&pid);
+    }
+
+    /// Register a tracked child whose handle this app holds
+    ///
+    /// Its exit must be published through the returned sender by whoever
+    /// waits on the handle.
+    async fn register_child(&self, pid: u32) -> watch::Sender<ChildState> {
+        let (state, receiver) = watch::channel(ChildState::Running);
+        self.children.write().await.insert(pid, receiver);
+        state
     }
 
     /// Get tracked processes
@@ -659,27 +1016,60 @@ impl ProcessManager {
         &self,
         filter: Option<ProcessFilter>,
     ) -> Result<Vec<ProcessInfo>, ProcessError> {
+        Ok(self.list_processes_snapshot(filter, false).await?.processes)
+    }
+
+    /// List running processes, reusing the last snapshot if it is newer than
+    /// the configured minimum refresh interval unless `force_refresh` is set
+    pub async fn list_processes_snapshot(
+        &self,
+        filter: Option<ProcessFilter>,
+        force_refresh: bool,
+    ) -> Result<ProcessListSnapshot, ProcessError> {
         let config = self.config.read().await;
         if !config.enabled {
             return Err(ProcessError::Disabled);
         }
+        let min_interval = std::time::Duration::from_millis(config.min_refresh_interval_ms);
         drop(config);
 
         let filter = filter.unwrap_or_default();
 
-        // Refresh processes (this updates CPU deltas)
-        {
-            let mut sys = self.sys.write().await;
-            sys.refresh_processes(ProcessesToUpdate::All, true);
-        }
+        // Refresh processes (this updates CPU deltas). The timestamp lock is held
+        // across the refresh so concurrent callers share a single refresh.
+        let (snapshot_at, refreshed) = {
+            let mut last_refresh = self.last_full_refresh.write().await;
+            match *last_refresh {
+                Some(at) if !force_refresh && at.elapsed() < min_interval => (at, false),
+                _ => {
+                    let mut sys = self.sys.write().await;
+                    sys.refresh_processes(ProcessesToUpdate::All, true);
+                    let now = Instant::now();
+                    *last_refresh = Some(now);
+                    (now, true)
+                }
+            }
+        };
 
         let sys = self.sys.read().await;
+        if refreshed {
+            // Forget suspended processes that have exited, even if their PID was reused
+            self.suspended_processes
+                .write()
+                .await
+                .retain(|&(pid, start_time)| {
+                    sys.process(Pid::from_u32(pid))
+                        .is_some_and(|proc| proc.start_time() == start_time)
+                });
+        }
+        let suspended = self.suspended_processes.read().await;
         let mut processes: Vec<ProcessInfo> = sys
             .processes()
             .iter()
-            .map(|(pid, proc)| sysinfo_to_process_info(pid, proc))
+            .map(|(pid, proc)| mark_suspended(sysinfo_to_process_info(pid, proc), &suspended))
             .filter(|info| apply_filter(info, &filter))
             .collect();
+        drop(suspended);
 
         // Sort if requested
         if let Some(sort_by) = filter.sort_by {
@@ -691,7 +1081,23 @@ impl ProcessManager {
         let limit = filter.limit.unwrap_or(MAX_PROCESS_LIST);
         processes.truncate(limit);
 
-        Ok(processes)
+        // Environments the OS refuses to reveal are left empty
+        if filter.include_env.unwrap_or(false) {
+            processes = tokio::task::spawn_blocking(move || {
+                for info in &mut processes {
+                    info.env = read_process_env(info.pid).ok();
+                }
+                processes
+            })
+            .await
+            .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?;
+        }
+
+        Ok(ProcessListSnapshot {
+            processes,
+            snapshot_age_ms: snapshot_at.elapsed().as_millis() as u64,
+            refreshed,
+        })
     }
 
     /// Get process by PID using sysinfo
@@ -711,9 +1117,23 @@ impl ProcessManager {
         }
 
         let sys = self.sys.read().await;
+        let suspended = self.suspended_processes.read().await;
         Ok(sys
             .process(sysinfo_pid)
-            .map(|proc| sysinfo_to_process_info(&sysinfo_pid, proc)))
+            .map(|proc| mark_suspended(sysinfo_to_process_info(&sysinfo_pid, proc), &suspended)))
+    }
+
+    /// Read the environment variables of a process
+    ///
+    /// Fails with `PermissionDenied` when the OS refuses access, typically for
+    /// processes of other users.
+    pub async fn get_process_env(&self, pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+        if self.get_process(pid).await?.is_none() {
+            return Err(ProcessError::NotFound(pid));
+        }
+        tokio::task::spawn_blocking(move || read_process_env(pid))
+            .await
+            .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?
     }
 
     /// Start a new process
@@ -734,25 +1154,265 @@ impl ProcessManager {
             });
         }
 
+        let output_encoding = match OutputEncoding::parse(request.output_encoding.as_deref()) {
+            Ok(encoding) => encoding,
+            Err(error) => {
+                return Ok(StartProcessResult {
+                    success: false,
+                    pid: None,
+                    stdout: None,
+                    stderr: None,
+                    exit_code: None,
+                    error: Some(error),
+                    duration_ms: None,
+                });
+            }
+        };
+
         #[cfg(windows)]
-        let result = windows::start_process(request).await;
+        let started = windows::start_process(request, output_encoding).await;
         #[cfg(unix)]
-        let result = unix::start_process(request).await;
+        let started = unix::start_process(request, output_encoding).await;
         #[cfg(not(any(windows, unix)))]
-        let result = Err(ProcessError::Unsupported(
-            "Platform not supported".to_string(),
-        ));
+        let started: Result<(StartProcessResult, Option<tokio::process::Child>), _> = Err(
+            ProcessError::Unsupported("Platform not supported".to_string()),
+        );
+        let (result, child) = started?;
 
         // Track if successful
-        if let Ok(ref res) = result {
-            if res.success {
-                if let Some(pid) = res.pid {
-                    self.track_process(pid).await;
+        if result.success {
+            if let Some(pid) = result.pid {
+                self.track_process(pid).await;
+                if let Some(mut child) = child {
+                    // Own the wait so the exit code is kept for `wait_for_exit`
+                    let state = self.register_child(pid).await;
+                    tokio::spawn(async move {
+                        let exit_code = child.wait().await.ok().and_then(|status| status.code());
+                        let _ = state.send(ChildState::Exited(exit_code));
+                    });
                 }
             }
         }
 
-        result
+        Ok(result)
+    }
+
+    /// Start a process and stream its output to `sink` as it is written
+    ///
+    /// Returns as soon as the process is spawned. The process always runs
+    /// attached with piped output, so `detached` and `capture_output` are
+    /// ignored, and it is only killed after `timeout_secs` if one is given.
+    /// Stopping the stream, or otherwise dropping its handle, kills and reaps
+    /// the process.
+    pub async fn start_process_streaming(
+        &self,
+        request: StartProcessRequest,
+        sink: ProcessStreamSink,
+    ) -> Result<StartProcessStreamResult, ProcessError> {
+        if !self.config.read().await.enabled {
+            return Err(ProcessError::Disabled);
+        }
+        if !self.is_program_allowed(&request.program).await {
+            return Err(ProcessError::PermissionDenied(format!(
+                "Program '{}' is not allowed",
+                request.program
+            )));
+        }
+        let encoding = OutputEncoding::parse(request.output_encoding.as_deref())
+            .map_err(ProcessError::Other)?;
+
+        let mut cmd = tokio::process::Command::new(&request.program);
+        cmd.args(&request.args)
+            .envs(&request.env)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(cwd) = &request.cwd {
+            cmd.current_dir(cwd);
+        }
+
+        let handle_id = Uuid::new_v4().to_string();
+        let cancel = CancellationToken::new();
+        let mut child = {
+            // Checked and inserted under one lock so concurrent starts cannot exceed the limit
+            let mut streams = self.output_streams.write().await;
+            if streams.len() >= MAX_OUTPUT_STREAMS {
+                return Err(ProcessError::Other(format!(
+                    "Too many output streams (max {})",
+                    MAX_OUTPUT_STREAMS
+                )));
+            }
+            let child = cmd.spawn().map_err(|e| {
+                ProcessError::System(format!("Failed to start '{}': {}", request.program, e))
+            })?;
+            streams.insert(
+                handle_id.clone(),
+                OutputStreamEntry {
+                    pid: child.id(),
+                    _stop_on_drop: cancel.clone().drop_guard(),
+                },
+            );
+            child
+        };
+        let pid = child.id();
+        let child_state = match pid {
+            Some(pid) => {
+                self.track_process(pid).await;
+                Some(self.register_child(pid).await)
+            }
+            None => None,
+        };
+        log::info!(
+            "Streaming output of '{}' (pid {:?}, handle {})",
+            request.program,
+            pid,
+            handle_id
+        );
+
+        let manager = self.clone();
+        let task_handle_id = handle_id.clone();
+        let timeout = request.timeout_secs.map(Duration::from_secs);
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        tokio::spawn(async move {
+            let handle_id = task_handle_id;
+            let forward = async {
+                tokio::join!(
+                    async {
+                        if let Some(stdout) = stdout {
+                            output::forward_output(
+                                stdout,
+                                OutputStream::Stdout,
+                                &handle_id,
+                                encoding,
+                                &sink,
+                            )
+                            .await;
+                        }
+                    },
+                    async {
+                        if let Some(stderr) = stderr {
+                            output::forward_output(
+                                stderr,
+                                OutputStream::Stderr,
+                                &handle_id,
+                                encoding,
+                                &sink,
+                            )
+                            .await;
+                        }
+                    },
+                );
+            };
+            let run = async {
+                tokio::pin!(forward);
+                let exited_first = tokio::select! {
+                    _ = &mut forward => None,
+                    status = child.wait() => Some(status),
+                };
+                match exited_first {
+                    Some(status) => {
+                        // Processes left running may hold the pipes open; forward
+                        // what they write for a moment, then end the stream
+                        let _ = tokio::time::timeout(output::OUTPUT_DRAIN_TIMEOUT, forward).await;
+                        status
+                    }
+                    None => child.wait().await,
+                }
+            };
+            let deadline = async {
+                match timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => std::future::pending().await,
+                }
+            };
+            let exited = tokio::select! {
+                status = run => Some(status),
+                _ = cancel.cancelled() => None,
+                _ = deadline => None,
+            };
+
+            let stopped = exited.is_none();
+            if stopped {
+                // Waits for the process too, so it is not left as a zombie
+                if let Err(e) = child.kill().await {
+                    log::warn!("Failed to kill streaming process {:?}: {}", pid, e);
+                }
+            }
+            let exit_code = exited
+                .and_then(|status| status.ok())
+                .and_then(|status| status.code());
+            if let Some(state) = child_state {
+                let _ = state.send(ChildState::Exited(exit_code));
+            }
+            manager.output_streams.write().await.remove(&handle_id);
+            if let Some(pid) = pid {
+                manager.untrack_process(pid).await;
+            }
+            sink(ProcessStreamEvent::Exit(ProcessStreamExit {
+                handle_id,
+                pid,
+                exit_code,
+                stopped,
+            }));
+        });
+
+        Ok(StartProcessStreamResult { handle_id, pid })
+    }
+
+    /// Stop a streaming process, killing it. Returns whether the stream existed.
+    pub async fn stop_stream(&self, handle_id: &str) -> bool {
+        // Dropping the entry cancels the streaming task
+        match self.output_streams.write().await.remove(handle_id) {
+            Some(entry) => {
+                log::info!("Stopping output stream {} (pid {:?})", handle_id, entry.pid);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop every streaming process, killing it, and return how many there were
+    ///
+    /// For app shutdown: the app exits without dropping the async runtime, so
+    /// the streams' kill-on-drop handles never run and the processes are
+    /// killed directly instead.
+    pub async fn stop_all_streams(&self) -> usize {
+        let streams: Vec<_> = self.output_streams.write().await.drain().collect();
+        // Killed before the entries are dropped, while the unreaped children
+        // still hold their PIDs
+        for (handle_id, entry) in &streams {
+            let Some(pid) = entry.pid else {
+                continue;
+            };
+            let request = TerminateProcessRequest {
+                pid,
+                force: true,
+                signal: None,
+                timeout_secs: None,
+            };
+            #[cfg(windows)]
+            let result = windows::terminate_process(request).await;
+            #[cfg(unix)]
+            let result = unix::terminate_process(request).await;
+            #[cfg(not(any(windows, unix)))]
+            let result: Result<TerminateProcessResult, _> = Err(ProcessError::Unsupported(
+                "Platform not supported".to_string(),
+            ));
+            if let Err(e) = result {
+                log::warn!(
+                    "Failed to kill streaming process {} (handle {}): {}",
+                    pid,
+                    handle_id,
+                    e
+                );
+            }
+        }
+        if !streams.is_empty() {
+            log::info!("Stopped {} output stream(s)", streams.len());
+        }
+        streams.len()
     }
 
     /// Terminate a process
@@ -771,6 +1431,13 @@ impl ProcessManager {
 
         // Preserve pid for post-call usage to avoid moved-value error
         let pid = request.pid;
+        // Other signals may leave the process running, so it stays tracked
+        let ends_process = request.force
+            || request
+                .signal
+                .as_deref()
+                .map(signal_ends_process)
+                .unwrap_or(true);
 
         #[cfg(windows)]
         let result = windows::terminate_process(request).await;
@@ -781,16 +1448,249 @@ impl ProcessManager {
             "Platform not supported".to_string(),
         ));
 
-        // Untrack if successful
+        // Untrack if the process was told to exit
         if let Ok(ref res) = result {
-            if res.success {
+            if res.success && ends_process {
                 self.untrack_process(pid).await;
+                self.forget_suspended(pid).await;
             }
         }
 
         result
     }
 
+    /// Terminate a process and all of its descendants, children before parents
+    ///
+    /// Descendants are found through parent links in a fresh process snapshot.
+    /// Every process is subject to the usual termination restrictions; one that
+    /// cannot be terminated is reported in the result without stopping the rest.
+    pub async fn terminate_process_tree(
+        &self,
+        pid: u32,
+        force: bool,
+    ) -> Result<TerminateProcessTreeResult, ProcessError> {
+        if !self.config.read().await.enabled {
+            return Err(ProcessError::Disabled);
+        }
+
+        let links: Vec<(u32, Option<u32>)> = {
+            let mut sys = self.sys.write().await;
+            sys.refresh_processes(ProcessesToUpdate::All, true);
+            sys.processes()
+                .iter()
+                .filter(|(_, proc)| proc.thread_kind().is_none())
+                .map(|(pid, proc)| (pid.as_u32(), proc.parent().map(|p| p.as_u32())))
+                .collect()
+        };
+        if !links.iter().any(|(candidate, _)| *candidate == pid) {
+            return Err(ProcessError::NotFound(pid));
+        }
+
+        let mut results = Vec::new();
+        for (member, parent_pid, depth) in process_tree_postorder(pid, &links) {
+            let request = TerminateProcessRequest {
+                pid: member,
+                force,
+                signal: None,
+                timeout_secs: None,
+            };
+            let result = match self.terminate_process(request).await {
+                Ok(result) => result,
+                Err(error) => TerminateProcessResult {
+                    success: false,
+                    exit_code: None,
+                    error: Some(error.to_string()),
+                },
+            };
+            if !result.success {
+                log::warn!(
+                    "Failed to terminate process {} in tree of {}: {:?}",
+                    member,
+                    pid,
+                    result.error
+                );
+            }
+            results.push(TerminateProcessTreeItemResult {
+                pid: member,
+                parent_pid,
+                depth,
+                result,
+            });
+        }
+
+        let success_count = results.iter().filter(|item| item.result.success).count();
+        Ok(TerminateProcessTreeResult {
+            pid,
+            total: results.len(),
+            success_count,
+            failure_count: results.len() - success_count,
+            results,
+        })
+    }
+
+    /// Pause a process (SIGSTOP on Unix, suspending all of its threads on Windows)
+    ///
+    /// Subject to the same restrictions as termination. Fails if the process is
+    /// already suspended.
+    pub async fn suspend_process(&self, pid: u32) -> Result<(), ProcessError> {
+        let (key, suspended) = self.suspend_state(pid).await?;
+        if suspended {
+            return Err(ProcessError::InvalidState(format!(
+                "Process {} is already suspended",
+                pid
+            )));
+        }
+
+        #[cfg(windows)]
+        let result = windows::suspend_process(pid).await;
+        #[cfg(unix)]
+        let result = unix::suspend_process(pid).await;
+        #[cfg(not(any(windows, unix)))]
+        let result = Err(ProcessError::Unsupported(
+            "Platform not supported".to_string(),
+        ));
+
+        result?;
+        self.suspended_processes.write().await.insert(key);
+        log::info!("Suspended process {}", pid);
+        Ok(())
+    }
+
+    /// Continue a process paused with `suspend_process` or stopped by a signal
+    ///
+    /// Subject to the same restrictions as termination. Fails if the process is
+    /// not suspended.
+    pub async fn resume_process(&self, pid: u32) -> Result<(), ProcessError> {
+        let (key, suspended) = self.suspend_state(pid).await?;
+        if !suspended {
+            return Err(ProcessError::InvalidState(format!(
+                "Process {} is not suspended",
+                pid
+            )));
+        }
+
+        #[cfg(windows)]
+        let result = windows::resume_process(pid).await;
+        #[cfg(unix)]
+        let result = unix::resume_process(pid).await;
+        #[cfg(not(any(windows, unix)))]
+        let result = Err(ProcessError::Unsupported(
+            "Platform not supported".to_string(),
+        ));
+
+        if matches!(result, Ok(()) | Err(ProcessError::NotFound(_))) {
+            self.suspended_processes.write().await.remove(&key);
+        }
+        result?;
+        log::info!("Resumed process {}", pid);
+        Ok(())
+    }
+
+    /// Wait until a process exits and return its exit code
+    ///
+    /// Exit codes are known for children started by this app, whose handles it
+    /// waits on, and for any process on Windows through its process handle;
+    /// other processes are polled until they leave the process table and yield
+    /// `None`. Waits at most `timeout_secs` (default: `default_timeout_secs`)
+    /// before failing with `Timeout`.
+    pub async fn wait_for_exit(
+        &self,
+        pid: u32,
+        timeout_secs: Option<u64>,
+    ) -> Result<Option<i32>, ProcessError> {
+        let config = self.config.read().await;
+        if !config.enabled {
+            return Err(ProcessError::Disabled);
+        }
+        let timeout = Duration::from_secs(timeout_secs.unwrap_or(config.default_timeout_secs));
+        drop(config);
+
+        let child = self.children.read().await.get(&pid).cloned();
+        if let Some(mut state) = child {
+            let exited = tokio::time::timeout(
+                timeout,
+                state.wait_for(|state| *state != ChildState::Running),
+            )
+            .await
+            .map_err(|_| ProcessError::Timeout)?;
+            let exit_code = match exited.as_deref() {
+                Ok(ChildState::Exited(exit_code)) => *exit_code,
+                // The waiting task ended without seeing the exit
+                _ => None,
+            };
+            self.forget_exited(pid).await;
+            return Ok(exit_code);
+        }
+
+        #[cfg(windows)]
+        {
+            let exit_code = if self.get_process(pid).await?.is_some() {
+                windows::wait_for_exit(pid, timeout).await?
+            } else {
+                None
+            };
+            self.forget_exited(pid).await;
+            return Ok(exit_code);
+        }
+
+        #[cfg(not(windows))]
+        {
+            let deadline = Instant::now() + timeout;
+            loop {
+                // Children are reaped by whoever holds their handle, never here
+                let running = self
+                    .get_process(pid)
+                    .await?
+                    .is_some_and(|info| info.status != ProcessStatus::Zombie);
+                if !running {
+                    self.forget_exited(pid).await;
+                    return Ok(None);
+                }
+
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(ProcessError::Timeout);
+                }
+                let interval = Duration::from_millis(EXIT_POLL_INTERVAL_MS);
+                tokio::time::sleep(interval.min(deadline - now)).await;
+            }
+        }
+    }
+
+    /// Drop bookkeeping for a process that has exited
+    async fn forget_exited(&self, pid: u32) {
+        self.untrack_process(pid).await;
+        self.forget_suspended(pid).await;
+    }
+
+    /// Drop any suspended entry for `pid`
+    async fn forget_suspended(&self, pid: u32) {
+        self.suspended_processes
+            .write()
+            .await
+            .retain(|&(suspended, _)| suspended != pid);
+    }
+
+    /// Check that `pid` may be suspended or resumed, returning its key in the
+    /// suspended set and whether it is suspended
+    async fn suspend_state(&self, pid: u32) -> Result<((u32, u64), bool), ProcessError> {
+        if !self.can_terminate(pid).await {
+            return Err(ProcessError::PermissionDenied(format!(
+                "Not allowed to suspend or resume process {}",
+                pid
+            )));
+        }
+        let Some(info) = self.get_process(pid).await? else {
+            self.forget_suspended(pid).await;
+            return Err(ProcessError::NotFound(pid));
+        };
+        let suspended = matches!(
+            info.status,
+            ProcessStatus::Suspended | ProcessStatus::Stopped
+        );
+        Ok((suspended_key(&info), suspended))
+    }
+
     async fn insert_operation(&self, operation: ProcessOperation) {
         let operation_id = operation.operation_id.clone();
         {
@@ -853,6 +1753,7 @@ impl ProcessManager {
             .map(move |(index, start_request)| {
                 let manager = manager.clone();
                 async move {
+                    let _permit = crate::resource_governor::global().acquire("process").await;
                     let program = start_request.program.clone();
                     let result = match manager.start_process(start_request).await {
                         Ok(result) => result,
@@ -902,6 +1803,7 @@ impl ProcessManager {
             .map(move |(index, terminate_request)| {
                 let manager = manager.clone();
                 async move {
+                    let _permit = crate::resource_governor::global().acquire("process").await;
                     let pid = terminate_request.pid;
                     let result = match manager.terminate_process(terminate_request).await {
                         Ok(result) => result,
@@ -1032,13 +1934,348 @@ impl ProcessManager {
             .filter_map(|id| operations.get(&id).cloned())
             .collect()
     }
-}
 
-/// Process management error
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum ProcessError {
-    /// Process management is disabled
-    Disabled,
+    /// Watch a process's memory and call `on_alert` each time it rises above `max_bytes`.
+    ///
+    /// Replaces any existing watch on the same PID. The watch ends when the process
+    /// exits, is auto-terminated, or is unwatched.
+    pub async fn watch_process_memory(
+        &self,
+        request: MemoryWatchRequest,
+        on_alert: MemoryAlertSink,
+    ) -> Result<MemoryWatch, ProcessError> {
+        if !self.config.read().await.enabled {
+            return Err(ProcessError::Disabled);
+        }
+        if request.max_bytes == 0 {
+            return Err(ProcessError::Other(
+                "maxBytes must be greater than zero".to_string(),
+            ));
+        }
+        if request.auto_terminate && !self.can_terminate(request.pid).await {
+            return Err(ProcessError::PermissionDenied(format!(
+                "Not allowed to terminate process {}",
+                request.pid
+            )));
+        }
+        if self.get_process(request.pid).await?.is_none() {
+            return Err(ProcessError::NotFound(request.pid));
+        }
+
+        let watch = MemoryWatch {
+            pid: request.pid,
+            max_bytes: request.max_bytes,
+            interval_ms: request
+                .interval_ms
+                .unwrap_or(DEFAULT_MEMORY_SAMPLE_INTERVAL_MS)
+                .max(MIN_MEMORY_SAMPLE_INTERVAL_MS),
+            auto_terminate: request.auto_terminate,
+            created_at: now_timestamp_ms(),
+            last_memory_bytes: None,
+            alert_count: 0,
+        };
+        let watch_id = Uuid::new_v4();
+        let cancel = CancellationToken::new();
+
+        {
+            let mut watches = self.memory_watches.write().await;
+            if !watches.contains_key(&watch.pid) && watches.len() >= MAX_MEMORY_WATCHES {
+                return Err(ProcessError::Other(format!(
+                    "Too many memory watches (max {})",
+                    MAX_MEMORY_WATCHES
+                )));
+            }
+            if let Some(previous) = watches.insert(
+                watch.pid,
+                MemoryWatchEntry {
+                    id: watch_id,
+                    watch: watch.clone(),
+                    cancel: cancel.clone(),
+                },
+            ) {
+                previous.cancel.cancel();
+            }
+        }
+
+        log::info!(
+            "Watching memory of process {} (max {} bytes, every {}ms, autoTerminate={})",
+            watch.pid,
+            watch.max_bytes,
+            watch.interval_ms,
+            watch.auto_terminate
+        );
+
+        let manager = self.clone();
+        let pid = watch.pid;
+        let max_bytes = watch.max_bytes;
+        let auto_terminate = watch.auto_terminate;
+        let interval = std::time::Duration::from_millis(watch.interval_ms);
+        tokio::spawn(async move {
+            // Alert once per threshold crossing; re-armed when usage drops back below
+            let mut above_threshold = false;
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                let sample = {
+                    let sysinfo_pid = Pid::from_u32(pid);
+                    let mut sys = manager.sys.write().await;
+                    sys.refresh_processes(ProcessesToUpdate::Some(&[sysinfo_pid]), true);
+                    sys.process(sysinfo_pid)
+                        .filter(|proc| proc.status() != sysinfo::ProcessStatus::Zombie)
+                        .map(|proc| (proc.name().to_string_lossy().to_string(), proc.memory()))
+                };
+                let Some((name, memory_bytes)) = sample else {
+                    log::debug!("Process {} exited, stopping memory watch", pid);
+                    manager.remove_memory_watch(pid, watch_id).await;
+                    return;
+                };
+
+                let exceeded = memory_bytes > max_bytes;
+                let alert_due = exceeded && !above_threshold;
+                above_threshold = exceeded;
+                {
+                    let mut watches = manager.memory_watches.write().await;
+                    if let Some(entry) = watches.get_mut(&pid) {
+                        entry.watch.last_memory_bytes = Some(memory_bytes);
+                        if alert_due {
+                            entry.watch.alert_count += 1;
+                        }
+                    }
+                }
+                if !alert_due {
+                    continue;
+                }
+
+                log::warn!(
+                    "Process {} ({}) is using {} bytes, above limit of {}",
+                    pid,
+                    name,
+                    memory_bytes,
+                    max_bytes
+                );
+                let (terminated, terminate_error) = if auto_terminate {
+                    match manager
+                        .terminate_process(TerminateProcessRequest {
+                            pid,
+                            force: true,
+                            signal: None,
+                            timeout_secs: None,
+                        })
+                        .await
+                    {
+                        Ok(result) if result.success => (true, None),
+                        Ok(result) => (false, result.error),
+                        Err(error) => (false, Some(error.to_string())),
+                    }
+                } else {
+                    (false, None)
+                };
+
+                on_alert(MemoryAlert {
+                    pid,
+                    name,
+                    memory_bytes,
+                    max_bytes,
+                    terminated,
+                    terminate_error,
+                    timestamp: now_timestamp_ms(),
+                });
+
+                if terminated {
+                    manager.remove_memory_watch(pid, watch_id).await;
+                    return;
+                }
+            }
+        });
+
+        Ok(watch)
+    }
+
+    /// Stop watching a process's memory. Returns whether a watch existed.
+    pub async fn unwatch_process_memory(&self, pid: u32) -> bool {
+        let removed = self.memory_watches.write().await.remove(&pid);
+        match removed {
+            Some(entry) => {
+                entry.cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// List active memory watches
+    pub async fn list_memory_watches(&self) -> Vec<MemoryWatch> {
+        let watches = self.memory_watches.read().await;
+        let mut list: Vec<MemoryWatch> = watches.values().map(|e| e.watch.clone()).collect();
+        list.sort_by_key(|w| w.created_at);
+        list
+    }
+
+    /// Remove a watch unless it has since been replaced
+    async fn remove_memory_watch(&self, pid: u32, watch_id: Uuid) {
+        let mut watches = self.memory_watches.write().await;
+        if watches.get(&pid).is_some_and(|entry| entry.id == watch_id) {
+            watches.remove(&pid);
+        }
+    }
+
+    /// Start recording CPU/memory samples of a process every `interval_ms`
+    ///
+    /// All sampled processes share one background task, which reuses full
+    /// refreshes made by process listings in between. Samples are kept in a
+    /// ring buffer of `history_capacity` entries that is dropped once the
+    /// process exits. Calling this again for the same PID changes its interval
+    /// and keeps the samples.
+    pub async fn start_sampling(
+        &self,
+        pid: u32,
+        interval_ms: Option<u64>,
+    ) -> Result<(), ProcessError> {
+        if self.get_process(pid).await?.is_none() {
+            return Err(ProcessError::NotFound(pid));
+        }
+        let interval_ms = interval_ms
+            .unwrap_or(DEFAULT_HISTORY_SAMPLE_INTERVAL_MS)
+            .max(MIN_HISTORY_SAMPLE_INTERVAL_MS);
+        let interval = std::time::Duration::from_millis(interval_ms);
+
+        let mut history = self.history.write().await;
+        if !history.entries.contains_key(&pid) && history.entries.len() >= MAX_SAMPLED_PROCESSES {
+            return Err(ProcessError::Other(format!(
+                "Too many sampled processes (max {})",
+                MAX_SAMPLED_PROCESSES
+            )));
+        }
+        let entry = history.entries.entry(pid).or_insert_with(|| HistoryEntry {
+            interval,
+            next_due: Instant::now(),
+            samples: VecDeque::new(),
+        });
+        entry.interval = interval;
+        entry.next_due = entry.next_due.min(Instant::now() + interval);
+
+        if history.sampler_running {
+            self.history_wakeup.notify_one();
+        } else {
+            history.sampler_running = true;
+            self.spawn_history_sampler();
+        }
+        log::info!("Sampling process {} every {}ms", pid, interval_ms);
+        Ok(())
+    }
+
+    /// Stop sampling a process and drop its history. Returns whether it was sampled.
+    pub async fn stop_sampling(&self, pid: u32) -> bool {
+        // The sampler notices on its next wakeup and exits if nothing is left
+        self.history.write().await.entries.remove(&pid).is_some()
+    }
+
+    /// Recorded samples of a process, or `None` if it is not being sampled
+    pub async fn get_process_history(&self, pid: u32) -> Option<ProcessHistory> {
+        let history = self.history.read().await;
+        history.entries.get(&pid).map(|entry| ProcessHistory {
+            pid,
+            interval_ms: entry.interval.as_millis() as u64,
+            samples: entry.samples.iter().copied().collect(),
+        })
+    }
+
+    fn spawn_history_sampler(&self) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let next_due = {
+                    let mut history = manager.history.write().await;
+                    match history.entries.values().map(|entry| entry.next_due).min() {
+                        Some(next_due) => next_due,
+                        None => {
+                            history.sampler_running = false;
+                            return;
+                        }
+                    }
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(next_due.saturating_duration_since(Instant::now())) => {}
+                    _ = manager.history_wakeup.notified() => continue,
+                }
+                manager.sample_history().await;
+            }
+        });
+    }
+
+    /// Record a sample for every process that is due and drop exited ones
+    async fn sample_history(&self) {
+        let now = Instant::now();
+        let (due, last_sampled_at) = {
+            let history = self.history.read().await;
+            let due: Vec<Pid> = history
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.next_due <= now)
+                .map(|(pid, _)| Pid::from_u32(*pid))
+                .collect();
+            (due, history.last_sampled_at)
+        };
+        if due.is_empty() {
+            return;
+        }
+        let capacity = self.config.read().await.history_capacity.max(1);
+        // A listing refreshed every process since the last sample; reuse it
+        let listed_since = match (*self.last_full_refresh.read().await, last_sampled_at) {
+            (Some(listed_at), Some(sampled_at)) => listed_at > sampled_at,
+            _ => false,
+        };
+
+        let samples: Vec<(u32, Option<ProcessSample>)> = {
+            let mut sys = self.sys.write().await;
+            if !listed_since {
+                sys.refresh_processes(ProcessesToUpdate::Some(&due), true);
+            }
+            let num_cpus = sys.cpus().len().max(1) as f32;
+            let timestamp = now_timestamp_ms();
+            due.iter()
+                .map(|pid| {
+                    let sample = sys
+                        .process(*pid)
+                        .filter(|proc| proc.status() != sysinfo::ProcessStatus::Zombie)
+                        .map(|proc| ProcessSample {
+                            timestamp,
+                            cpu_percent: proc.cpu_usage() / num_cpus,
+                            memory_bytes: proc.memory(),
+                        });
+                    (pid.as_u32(), sample)
+                })
+                .collect()
+        };
+
+        let mut history = self.history.write().await;
+        history.last_sampled_at = Some(now);
+        for (pid, sample) in samples {
+            let Some(sample) = sample else {
+                if history.entries.remove(&pid).is_some() {
+                    log::debug!("Process {} exited, dropping its history", pid);
+                }
+                continue;
+            };
+            if let Some(entry) = history.entries.get_mut(&pid) {
+                entry.samples.push_back(sample);
+                while entry.samples.len() > capacity {
+                    entry.samples.pop_front();
+                }
+                entry.next_due = now + entry.interval;
+            }
+        }
+    }
+}
+
+/// Process management error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProcessError {
+    /// Process management is disabled
+    Disabled,
     /// Platform not supported
     Unsupported(String),
     /// Configuration error
@@ -1049,6 +2286,8 @@ pub enum ProcessError {
     PermissionDenied(String),
     /// Operation timeout
     Timeout,
+    /// Process is not in a state the operation applies to
+    InvalidState(String),
     /// System error
     System(String),
     /// Other error
@@ -1064,6 +2303,7 @@ impl std::fmt::Display for ProcessError {
             ProcessError::NotFound(pid) => write!(f, "Process {} not found", pid),
             ProcessError::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
             ProcessError::Timeout => write!(f, "Operation timed out"),
+            ProcessError::InvalidState(msg) => write!(f, "{}", msg),
             ProcessError::System(msg) => write!(f, "System error: {}", msg),
             ProcessError::Other(msg) => write!(f, "{}", msg),
         }
@@ -1223,6 +2463,7 @@ mod tests {
             start_time: Some(100),
             user: Some("tester".to_string()),
             cwd: None,
+            env: None,
         };
 
         let filter = ProcessFilter {
@@ -1241,6 +2482,39 @@ mod tests {
         assert!(!apply_filter(&info, &mismatched));
     }
 
+    #[test]
+    fn mark_suspended_ignores_reused_pid() {
+        let info = |start_time| ProcessInfo {
+            pid: 10,
+            name: "sleep".to_string(),
+            exe_path: None,
+            cmd_line: None,
+            parent_pid: None,
+            cpu_percent: None,
+            memory_bytes: None,
+            status: ProcessStatus::Sleeping,
+            start_time: Some(start_time),
+            user: None,
+            cwd: None,
+            env: None,
+        };
+        let suspended = HashSet::from([(10, 100)]);
+
+        let original = mark_suspended(info(100), &suspended);
+        assert_eq!(original.status, ProcessStatus::Suspended);
+        let reused = mark_suspended(info(200), &suspended);
+        assert_eq!(reused.status, ProcessStatus::Sleeping);
+    }
+
+    #[test]
+    fn env_from_entries_splits_on_first_separator() {
+        let env = env_from_entries(["PATH=/bin:/usr/bin", "EQ=a=b", "=C:=C:\\dir", "", "BROKEN"]);
+        assert_eq!(env.len(), 3);
+        assert_eq!(env["PATH"], "/bin:/usr/bin");
+        assert_eq!(env["EQ"], "a=b");
+        assert_eq!(env["=C:"], "C:\\dir");
+    }
+
     #[test]
     fn sort_processes_orders_by_memory_desc() {
         let mut processes = vec![
@@ -1256,6 +2530,7 @@ mod tests {
                 start_time: None,
                 user: None,
                 cwd: None,
+                env: None,
             },
             ProcessInfo {
                 pid: 2,
@@ -1269,6 +2544,7 @@ mod tests {
                 start_time: None,
                 user: None,
                 cwd: None,
+                env: None,
             },
         ];
 
@@ -1355,6 +2631,7 @@ mod tests {
                         detached: true,
                         timeout_secs: Some(1),
                         capture_output: false,
+                        output_encoding: None,
                     },
                     StartProcessRequest {
                         program: "bar".to_string(),
@@ -1364,6 +2641,7 @@ mod tests {
                         detached: true,
                         timeout_secs: Some(1),
                         capture_output: false,
+                        output_encoding: None,
                     },
                 ],
                 max_concurrency: Some(8),
@@ -1402,11 +2680,13 @@ mod tests {
                     TerminateProcessRequest {
                         pid: 999_001,
                         force: false,
+                        signal: None,
                         timeout_secs: Some(1),
                     },
                     TerminateProcessRequest {
                         pid: 999_002,
                         force: false,
+                        signal: None,
                         timeout_secs: Some(1),
                     },
                 ],
@@ -1447,6 +2727,7 @@ mod tests {
                     detached: true,
                     timeout_secs: Some(1),
                     capture_output: false,
+                    output_encoding: None,
                 }],
                 max_concurrency: Some(2),
             })
@@ -1512,6 +2793,7 @@ mod tests {
                     detached: true,
                     timeout_secs: Some(1),
                     capture_output: false,
+                    output_encoding: None,
                 }],
                 max_concurrency: Some(1),
             })
@@ -1528,6 +2810,7 @@ mod tests {
                     detached: true,
                     timeout_secs: Some(1),
                     capture_output: false,
+                    output_encoding: None,
                 }],
                 max_concurrency: Some(1),
             })
@@ -1544,4 +2827,846 @@ mod tests {
         assert!(all.iter().any(|op| op.operation_id == first.operation_id));
         assert!(all.iter().any(|op| op.operation_id == second.operation_id));
     }
+
+    async fn enabled_manager(dir: &std::path::Path) -> ProcessManager {
+        let manager = ProcessManager::new(dir.join("process.json"))
+            .await
+            .expect("manager");
+        manager
+            .update_config(ProcessManagerConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .await
+            .expect("update");
+        manager
+    }
+
+    fn channel_sink() -> (
+        MemoryAlertSink,
+        tokio::sync::mpsc::UnboundedReceiver<MemoryAlert>,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let sink: MemoryAlertSink = Arc::new(move |alert| {
+            let _ = tx.send(alert);
+        });
+        (sink, rx)
+    }
+
+    fn stream_sink() -> (
+        ProcessStreamSink,
+        tokio::sync::mpsc::UnboundedReceiver<ProcessStreamEvent>,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let sink: ProcessStreamSink = Arc::new(move |event| {
+            let _ = tx.send(event);
+        });
+        (sink, rx)
+    }
+
+    /// Collect output chunks until the exit event
+    async fn collect_stream(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<ProcessStreamEvent>,
+    ) -> (Vec<ProcessOutputChunk>, ProcessStreamExit) {
+        let mut chunks = Vec::new();
+        loop {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+                .await
+                .expect("stream event")
+                .expect("open channel");
+            match event {
+                ProcessStreamEvent::Output(chunk) => chunks.push(chunk),
+                ProcessStreamEvent::Exit(exit) => return (chunks, exit),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn start_process_streaming_forwards_lines() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, mut rx) = stream_sink();
+
+        let started = manager
+            .start_process_streaming(
+                capture_request("sh", &["-c", "echo one; echo two >&2; printf three"], None),
+                sink,
+            )
+            .await
+            .expect("start");
+        let (chunks, exit) = collect_stream(&mut rx).await;
+
+        assert_eq!(exit.handle_id, started.handle_id);
+        assert_eq!(exit.exit_code, Some(0));
+        assert!(!exit.stopped);
+        let lines = |stream: OutputStream| {
+            chunks
+                .iter()
+                .filter(|c| c.stream == stream && c.handle_id == started.handle_id)
+                .map(|c| c.chunk.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lines(OutputStream::Stdout), vec!["one", "three"]);
+        assert_eq!(lines(OutputStream::Stderr), vec!["two"]);
+        assert!(!manager.stop_stream(&started.handle_id).await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn start_process_streaming_ends_when_process_exits() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, mut rx) = stream_sink();
+
+        // The background sleep inherits stdout and keeps it open after sh exits
+        manager
+            .start_process_streaming(
+                capture_request("sh", &["-c", "sleep 30 & echo done"], None),
+                sink,
+            )
+            .await
+            .expect("start");
+        let started_at = Instant::now();
+        let (chunks, exit) = collect_stream(&mut rx).await;
+
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+        assert_eq!(exit.exit_code, Some(0));
+        assert!(!exit.stopped);
+        assert_eq!(chunks[0].chunk, "done");
+    }
+
+    #[tokio::test]
+    async fn forward_output_splits_long_lines_on_char_boundaries() {
+        let text = format!("a{}", "é".repeat(output::MAX_OUTPUT_CHUNK_BYTES));
+        let (sink, mut rx) = stream_sink();
+        output::forward_output(
+            text.as_bytes(),
+            OutputStream::Stdout,
+            "handle",
+            OutputEncoding::Utf8,
+            &sink,
+        )
+        .await;
+        drop(sink);
+
+        let mut chunks = Vec::new();
+        while let Some(ProcessStreamEvent::Output(chunk)) = rx.recv().await {
+            chunks.push(chunk.chunk);
+        }
+        assert!(chunks.len() > 1);
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.len() <= output::MAX_OUTPUT_CHUNK_BYTES));
+        assert!(!chunks.iter().any(|chunk| chunk.contains('\u{FFFD}')));
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stop_stream_kills_and_reaps_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, mut rx) = stream_sink();
+
+        let started = manager
+            .start_process_streaming(capture_request("sleep", &["30"], None), sink)
+            .await
+            .expect("start");
+        let pid = started.pid.expect("pid");
+        assert!(manager.get_tracked_processes().await.contains(&pid));
+
+        assert!(manager.stop_stream(&started.handle_id).await);
+        let (_, exit) = collect_stream(&mut rx).await;
+        assert!(exit.stopped);
+        assert_eq!(exit.exit_code, None);
+        assert!(!manager.get_tracked_processes().await.contains(&pid));
+        assert!(manager.get_process(pid).await.expect("get").is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stop_all_streams_kills_every_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, mut rx) = stream_sink();
+
+        let mut pids = Vec::new();
+        for _ in 0..2 {
+            let started = manager
+                .start_process_streaming(capture_request("sleep", &["30"], None), sink.clone())
+                .await
+                .expect("start");
+            pids.push(started.pid.expect("pid"));
+        }
+
+        assert_eq!(manager.stop_all_streams().await, 2);
+        assert_eq!(manager.stop_all_streams().await, 0);
+        for _ in 0..2 {
+            collect_stream(&mut rx).await;
+        }
+        for pid in pids {
+            assert!(manager.get_process(pid).await.expect("get").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_process_memory_requires_enabled_and_existing_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = ProcessManager::new(dir.path().join("process.json"))
+            .await
+            .expect("manager");
+        let request = MemoryWatchRequest {
+            pid: std::process::id(),
+            max_bytes: 1024,
+            interval_ms: None,
+            auto_terminate: false,
+        };
+
+        let (sink, _rx) = channel_sink();
+        let result = manager.watch_process_memory(request, sink).await;
+        assert!(matches!(result, Err(ProcessError::Disabled)));
+
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, _rx) = channel_sink();
+        let result = manager
+            .watch_process_memory(
+                MemoryWatchRequest {
+                    pid: 999_003,
+                    max_bytes: 1024,
+                    interval_ms: None,
+                    auto_terminate: false,
+                },
+                sink,
+            )
+            .await;
+        assert!(matches!(result, Err(ProcessError::NotFound(999_003))));
+    }
+
+    #[tokio::test]
+    async fn watch_process_memory_auto_terminate_respects_ownership() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+
+        let (sink, _rx) = channel_sink();
+        let result = manager
+            .watch_process_memory(
+                MemoryWatchRequest {
+                    pid: std::process::id(),
+                    max_bytes: 1024,
+                    interval_ms: None,
+                    auto_terminate: true,
+                },
+                sink,
+            )
+            .await;
+        assert!(matches!(result, Err(ProcessError::PermissionDenied(_))));
+        assert!(manager.list_memory_watches().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn watch_process_memory_alerts_once_per_crossing() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let pid = std::process::id();
+
+        let (sink, mut rx) = channel_sink();
+        let watch = manager
+            .watch_process_memory(
+                MemoryWatchRequest {
+                    pid,
+                    max_bytes: 1,
+                    interval_ms: Some(10),
+                    auto_terminate: false,
+                },
+                sink,
+            )
+            .await
+            .expect("watch");
+        assert_eq!(watch.interval_ms, MIN_MEMORY_SAMPLE_INTERVAL_MS);
+
+        let alert = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .expect("alert in time")
+            .expect("alert");
+        assert_eq!(alert.pid, pid);
+        assert!(alert.memory_bytes > alert.max_bytes);
+        assert!(!alert.terminated);
+
+        // Still above the threshold, so no repeat alert
+        tokio::time::sleep(std::time::Duration::from_millis(700)).await;
+        assert!(rx.try_recv().is_err());
+
+        let watches = manager.list_memory_watches().await;
+        assert_eq!(watches.len(), 1);
+        assert_eq!(watches[0].alert_count, 1);
+        assert!(watches[0].last_memory_bytes.is_some());
+
+        assert!(manager.unwatch_process_memory(pid).await);
+        assert!(!manager.unwatch_process_memory(pid).await);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn watch_process_memory_auto_terminates_tracked_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+        manager.track_process(pid).await;
+
+        let (sink, mut rx) = channel_sink();
+        manager
+            .watch_process_memory(
+                MemoryWatchRequest {
+                    pid,
+                    max_bytes: 1,
+                    interval_ms: Some(MIN_MEMORY_SAMPLE_INTERVAL_MS),
+                    auto_terminate: true,
+                },
+                sink,
+            )
+            .await
+            .expect("watch");
+
+        let alert = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .expect("alert in time")
+            .expect("alert");
+        let _ = child.kill();
+        let _ = child.wait();
+
+        assert_eq!(alert.pid, pid);
+        assert!(alert.terminated, "{:?}", alert.terminate_error);
+        assert!(manager.list_memory_watches().await.is_empty());
+        assert!(!manager.get_tracked_processes().await.contains(&pid));
+    }
+
+    #[test]
+    fn process_tree_postorder_lists_children_first_and_survives_cycles() {
+        let links = vec![
+            (1, None),
+            (10, Some(1)),
+            (11, Some(10)),
+            (12, Some(10)),
+            (20, Some(1)),
+            (99, Some(7)),
+            // Cycle 20 -> 30 -> 31 -> 20 through a reused PID
+            (30, Some(20)),
+            (31, Some(30)),
+            (20, Some(31)),
+        ];
+        let order = process_tree_postorder(1, &links);
+
+        let pids: Vec<u32> = order.iter().map(|(pid, _, _)| *pid).collect();
+        assert_eq!(pids.len(), 7);
+        assert_eq!(pids.last(), Some(&1));
+        assert!(!pids.contains(&99));
+        let position = |pid: u32| pids.iter().position(|&p| p == pid).unwrap();
+        for (pid, parent, _) in &order {
+            if let Some(parent) = parent {
+                assert!(
+                    position(*pid) < position(*parent),
+                    "{} before {}",
+                    pid,
+                    parent
+                );
+            }
+        }
+        assert!(order.contains(&(11, Some(10), 2)));
+        assert!(order.contains(&(31, Some(30), 3)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn terminate_process_tree_kills_descendants_first() {
+        let dir = tempdir().expect("tempdir");
+        let manager = ProcessManager::new(dir.path().join("process.json"))
+            .await
+            .expect("manager");
+        manager
+            .update_config(ProcessManagerConfig {
+                enabled: true,
+                allow_terminate_any: true,
+                ..Default::default()
+            })
+            .await
+            .expect("update");
+
+        let mut shell = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & sleep 30 & wait"])
+            .spawn()
+            .expect("spawn sh");
+        let pid = shell.id();
+
+        // Wait for the shell to start both children
+        let mut children = 0;
+        for _ in 0..50 {
+            let listed = manager
+                .list_processes_snapshot(
+                    Some(ProcessFilter {
+                        parent_pid: Some(pid),
+                        ..Default::default()
+                    }),
+                    true,
+                )
+                .await
+                .expect("list");
+            children = listed.processes.len();
+            if children == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert_eq!(children, 2);
+
+        let result = manager
+            .terminate_process_tree(pid, true)
+            .await
+            .expect("tree");
+        let _ = shell.wait();
+
+        assert_eq!(result.total, 3);
+        assert_eq!(result.success_count, 3, "{:?}", result.results);
+        assert_eq!(result.results.last().map(|item| item.pid), Some(pid));
+        assert!(result.results[..2]
+            .iter()
+            .all(|item| item.parent_pid == Some(pid) && item.depth == 1));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn suspend_and_resume_tracked_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+
+        // Untracked processes are off limits like for termination
+        let denied = manager.suspend_process(pid).await;
+        assert!(matches!(denied, Err(ProcessError::PermissionDenied(_))));
+
+        manager.track_process(pid).await;
+        manager.suspend_process(pid).await.expect("suspend");
+        let status = manager
+            .get_process(pid)
+            .await
+            .expect("get")
+            .map(|p| p.status);
+        assert_eq!(status, Some(ProcessStatus::Suspended));
+        let listed = manager
+            .list_processes_snapshot(
+                Some(ProcessFilter {
+                    pid: Some(pid),
+                    ..Default::default()
+                }),
+                true,
+            )
+            .await
+            .expect("list");
+        assert_eq!(listed.processes[0].status, ProcessStatus::Suspended);
+        assert!(matches!(
+            manager.suspend_process(pid).await,
+            Err(ProcessError::InvalidState(_))
+        ));
+
+        manager.resume_process(pid).await.expect("resume");
+        let status = manager
+            .get_process(pid)
+            .await
+            .expect("get")
+            .map(|p| p.status);
+        assert_ne!(status, Some(ProcessStatus::Suspended));
+        let resumed_again = manager.resume_process(pid).await;
+        let _ = child.kill();
+        let _ = child.wait();
+        assert!(matches!(resumed_again, Err(ProcessError::InvalidState(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn terminate_forgets_suspended_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+        manager.track_process(pid).await;
+        manager.suspend_process(pid).await.expect("suspend");
+        assert_eq!(manager.suspended_processes.read().await.len(), 1);
+
+        let result = manager
+            .terminate_process(TerminateProcessRequest {
+                pid,
+                force: true,
+                signal: None,
+                timeout_secs: None,
+            })
+            .await
+            .expect("terminate");
+        assert!(result.success);
+        let _ = child.wait();
+        assert!(manager.suspended_processes.read().await.is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn get_process_env_reads_child_environment() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .env("COGNIA_PROCESS_ENV_TEST", "a=b c")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+
+        let env = manager.get_process_env(pid).await;
+        let listed = manager
+            .list_processes_snapshot(
+                Some(ProcessFilter {
+                    pid: Some(pid),
+                    include_env: Some(true),
+                    ..Default::default()
+                }),
+                true,
+            )
+            .await
+            .expect("list");
+        let _ = child.kill();
+        let _ = child.wait();
+
+        let env = env.expect("env");
+        assert_eq!(env["COGNIA_PROCESS_ENV_TEST"], "a=b c");
+        let listed_env = listed.processes[0].env.as_ref().expect("listed env");
+        assert_eq!(listed_env["COGNIA_PROCESS_ENV_TEST"], "a=b c");
+        assert!(matches!(
+            manager.get_process_env(u32::MAX).await,
+            Err(ProcessError::NotFound(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_signal_accepts_names_and_numbers() {
+        use nix::sys::signal::Signal;
+
+        assert_eq!(unix::parse_signal("SIGINT").unwrap(), Signal::SIGINT);
+        assert_eq!(unix::parse_signal("hup").unwrap(), Signal::SIGHUP);
+        assert_eq!(unix::parse_signal(" SIGUSR1 ").unwrap(), Signal::SIGUSR1);
+        assert_eq!(unix::parse_signal("15").unwrap(), Signal::SIGTERM);
+        assert!(unix::parse_signal("SIGNOPE").is_err());
+        assert!(unix::parse_signal("0").is_err());
+        assert!(unix::parse_signal("").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn terminate_process_sends_requested_signal() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+        manager.track_process(pid).await;
+
+        let request = |signal: &str| TerminateProcessRequest {
+            pid,
+            force: false,
+            signal: Some(signal.to_string()),
+            timeout_secs: None,
+        };
+        let invalid = manager.terminate_process(request("SIGBOGUS")).await;
+        assert!(matches!(invalid, Err(ProcessError::Other(_))));
+
+        for signal in ["SIGSTOP", "CONT"] {
+            let paused = manager.terminate_process(request(signal)).await;
+            assert!(matches!(paused, Err(ProcessError::Other(_))), "{}", signal);
+        }
+
+        let result = manager
+            .terminate_process(request("SIGHUP"))
+            .await
+            .expect("terminate");
+        assert!(result.success);
+        let status = child.wait().expect("wait");
+        assert_eq!(status.signal(), Some(nix::libc::SIGHUP));
+        // Only SIGTERM and SIGKILL untrack the process
+        assert!(manager.get_tracked_processes().await.contains(&pid));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn wait_for_exit_reports_exit_code_of_started_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let request = StartProcessRequest {
+            detached: true,
+            capture_output: false,
+            ..capture_request("sh", &["-c", "sleep 0.5; exit 3"], None)
+        };
+        let pid = manager
+            .start_process(request)
+            .await
+            .expect("start")
+            .pid
+            .expect("pid");
+
+        let timed_out = manager.wait_for_exit(pid, Some(0)).await;
+        assert!(matches!(timed_out, Err(ProcessError::Timeout)));
+
+        let exit_code = manager.wait_for_exit(pid, Some(10)).await.expect("wait");
+        assert_eq!(exit_code, Some(3));
+        assert!(!manager.get_tracked_processes().await.contains(&pid));
+
+        // Once forgotten, the exit code is no longer known
+        let again = manager.wait_for_exit(pid, Some(1)).await.expect("wait");
+        assert_eq!(again, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn wait_for_exit_leaves_children_to_their_owner() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "exit 3"])
+            .spawn()
+            .expect("spawn sh");
+        let pid = child.id();
+        manager.track_process(pid).await;
+
+        let exit_code = manager.wait_for_exit(pid, Some(10)).await.expect("wait");
+        assert_eq!(exit_code, None);
+        // Still reaped through its own handle, with its exit code
+        let status = child.wait().expect("wait");
+        assert_eq!((status.code(), status.signal()), (Some(3), None));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn wait_for_exit_reports_exit_code_of_streaming_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let (sink, mut rx) = stream_sink();
+
+        let started = manager
+            .start_process_streaming(
+                capture_request("sh", &["-c", "sleep 0.5; exit 4"], None),
+                sink,
+            )
+            .await
+            .expect("start");
+        let pid = started.pid.expect("pid");
+
+        let exit_code = manager.wait_for_exit(pid, Some(10)).await.expect("wait");
+        assert_eq!(exit_code, Some(4));
+        let (_, exit) = collect_stream(&mut rx).await;
+        assert_eq!(exit.exit_code, Some(4));
+    }
+
+    #[tokio::test]
+    async fn sampling_records_bounded_history() {
+        let dir = tempdir().expect("tempdir");
+        let manager = ProcessManager::new(dir.path().join("process.json"))
+            .await
+            .expect("manager");
+        manager
+            .update_config(ProcessManagerConfig {
+                enabled: true,
+                history_capacity: 3,
+                ..Default::default()
+            })
+            .await
+            .expect("update");
+        let pid = std::process::id();
+
+        assert!(manager.get_process_history(pid).await.is_none());
+        manager
+            .start_sampling(pid, Some(MIN_HISTORY_SAMPLE_INTERVAL_MS))
+            .await
+            .expect("start");
+        tokio::time::sleep(std::time::Duration::from_millis(700)).await;
+
+        let history = manager.get_process_history(pid).await.expect("history");
+        assert_eq!(history.interval_ms, MIN_HISTORY_SAMPLE_INTERVAL_MS);
+        assert_eq!(history.samples.len(), 3);
+        assert!(history.samples.iter().all(|s| s.memory_bytes > 0));
+        assert!(history
+            .samples
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp));
+
+        assert!(manager.stop_sampling(pid).await);
+        assert!(!manager.stop_sampling(pid).await);
+        assert!(manager.get_process_history(pid).await.is_none());
+        assert!(matches!(
+            manager.start_sampling(u32::MAX, None).await,
+            Err(ProcessError::NotFound(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sampling_drops_history_of_exited_process() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+
+        manager
+            .start_sampling(pid, Some(MIN_HISTORY_SAMPLE_INTERVAL_MS))
+            .await
+            .expect("start");
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        assert!(manager.get_process_history(pid).await.is_some());
+
+        let _ = child.kill();
+        let _ = child.wait();
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        assert!(manager.get_process_history(pid).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_processes_reuses_fresh_snapshot() {
+        let dir = tempdir().expect("tempdir");
+        let manager = ProcessManager::new(dir.path().join("process.json"))
+            .await
+            .expect("manager");
+        manager
+            .update_config(ProcessManagerConfig {
+                enabled: true,
+                min_refresh_interval_ms: 60_000,
+                ..Default::default()
+            })
+            .await
+            .expect("update");
+
+        let first = manager
+            .list_processes_snapshot(None, false)
+            .await
+            .expect("list");
+        assert!(first.refreshed);
+        assert!(!first.processes.is_empty());
+
+        let cached = manager
+            .list_processes_snapshot(None, false)
+            .await
+            .expect("list");
+        assert!(!cached.refreshed);
+        assert!(cached.snapshot_age_ms >= first.snapshot_age_ms);
+
+        let forced = manager
+            .list_processes_snapshot(None, true)
+            .await
+            .expect("list");
+        assert!(forced.refreshed);
+
+        // An interval of 0 refreshes on every call
+        manager
+            .update_config(ProcessManagerConfig {
+                enabled: true,
+                min_refresh_interval_ms: 0,
+                ..Default::default()
+            })
+            .await
+            .expect("update");
+        let uncached = manager
+            .list_processes_snapshot(None, false)
+            .await
+            .expect("list");
+        assert!(uncached.refreshed);
+    }
+
+    fn capture_request(
+        program: &str,
+        args: &[&str],
+        encoding: Option<&str>,
+    ) -> StartProcessRequest {
+        StartProcessRequest {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: None,
+            env: HashMap::new(),
+            detached: false,
+            timeout_secs: Some(10),
+            capture_output: true,
+            output_encoding: encoding.map(str::to_string),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn start_process_decodes_non_utf8_output() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+
+        // "你好" in GBK
+        let result = manager
+            .start_process(capture_request(
+                "sh",
+                &["-c", r"printf '\304\343\272\303'"],
+                Some("gbk"),
+            ))
+            .await
+            .expect("start");
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.stdout.as_deref(), Some("你好"));
+
+        // "テスト" in Shift_JIS, on stderr
+        let result = manager
+            .start_process(capture_request(
+                "sh",
+                &["-c", r"printf '\203\145\203\130\203\147' >&2"],
+                Some("cp932"),
+            ))
+            .await
+            .expect("start");
+        assert_eq!(result.stderr.as_deref(), Some("テスト"));
+
+        // Without an encoding, non-Windows platforms decode as UTF-8
+        let result = manager
+            .start_process(capture_request(
+                "sh",
+                &["-c", r"printf '\304\343\272\303'"],
+                None,
+            ))
+            .await
+            .expect("start");
+        assert!(result.stdout.unwrap().contains('\u{fffd}'));
+    }
+
+    #[tokio::test]
+    async fn start_process_rejects_unknown_output_encoding() {
+        let dir = tempdir().expect("tempdir");
+        let manager = enabled_manager(dir.path()).await;
+
+        let result = manager
+            .start_process(capture_request("echo", &["hi"], Some("klingon")))
+            .await
+            .expect("start");
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("Unsupported output encoding"));
+    }
 }
@@ -0,0 +1,150 @@
+//! Live output of processes started with `ProcessManager::start_process_streaming`
+//!
+//! Output is forwarded line by line as it is written instead of being buffered
+//! until the process exits. Lines longer than [`MAX_OUTPUT_CHUNK_BYTES`] are
+//! split on a character boundary so a process that never writes a newline still
+//! produces output.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use super::OutputEncoding;
+
+/// Event carrying a [`ProcessOutputChunk`]
+pub const PROCESS_OUTPUT_EVENT: &str = "process://output";
+/// Event carrying a [`ProcessStreamExit`]
+pub const PROCESS_STREAM_EXIT_EVENT: &str = "process://stream-exit";
+/// Maximum number of processes streaming output at once
+pub const MAX_OUTPUT_STREAMS: usize = 16;
+/// Longest chunk emitted for a single line
+pub const MAX_OUTPUT_CHUNK_BYTES: usize = 8 * 1024;
+/// How long output is still forwarded after the process exits, in case a
+/// process it left running keeps the pipes open
+pub(super) const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Output pipe a chunk was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Payload of [`PROCESS_OUTPUT_EVENT`]: one line, without its line terminator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessOutputChunk {
+    pub handle_id: String,
+    pub stream: OutputStream,
+    pub chunk: String,
+}
+
+/// Payload of [`PROCESS_STREAM_EXIT_EVENT`], sent once after all output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStreamExit {
+    pub handle_id: String,
+    /// Process ID
+    pub pid: Option<u32>,
+    /// Exit code, if the process exited on its own
+    pub exit_code: Option<i32>,
+    /// Whether the process was killed because the stream was stopped or timed out
+    pub stopped: bool,
+}
+
+/// Result of starting a streaming process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartProcessStreamResult {
+    /// Handle for `stop_stream`, repeated in every event
+    pub handle_id: String,
+    /// Process ID
+    pub pid: Option<u32>,
+}
+
+/// Event from a streaming process
+#[derive(Debug, Clone)]
+pub enum ProcessStreamEvent {
+    Output(ProcessOutputChunk),
+    Exit(ProcessStreamExit),
+}
+
+/// Callback invoked for each streaming event
+pub type ProcessStreamSink = Arc<dyn Fn(ProcessStreamEvent) + Send + Sync>;
+
+/// Length of the longest prefix of `bytes` that does not end inside a character
+///
+/// Characters take at most 4 bytes, so only the last 3 bytes are dropped. Falls
+/// back to the whole input when no such prefix decodes cleanly.
+fn chunk_boundary(bytes: &[u8], encoding: OutputEncoding) -> usize {
+    let encoding = match encoding {
+        OutputEncoding::Named(encoding) => encoding,
+        OutputEncoding::Utf8 | OutputEncoding::Auto => encoding_rs::UTF_8,
+    };
+    (bytes.len().saturating_sub(3)..=bytes.len())
+        .rev()
+        .filter(|end| *end > 0)
+        .find(|end| {
+            encoding
+                .decode_without_bom_handling_and_without_replacement(&bytes[..*end])
+                .is_some()
+        })
+        .unwrap_or(bytes.len())
+}
+
+/// Forward a pipe to `sink` line by line until it closes
+pub(super) async fn forward_output<R: AsyncRead + Unpin>(
+    pipe: R,
+    stream: OutputStream,
+    handle_id: &str,
+    encoding: OutputEncoding,
+    sink: &ProcessStreamSink,
+) {
+    let emit = |line: &[u8]| {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        sink(ProcessStreamEvent::Output(ProcessOutputChunk {
+            handle_id: handle_id.to_string(),
+            stream,
+            chunk: encoding.decode(line),
+        }));
+    };
+
+    let mut reader = BufReader::new(pipe);
+    let mut line = Vec::new();
+    loop {
+        let available = match reader.fill_buf().await {
+            Ok(available) => available,
+            Err(e) => {
+                log::debug!("Stopped reading {:?} of {}: {}", stream, handle_id, e);
+                break;
+            }
+        };
+        if available.is_empty() {
+            break;
+        }
+        // Only a split character's bytes are left over, so there is always room
+        let room = MAX_OUTPUT_CHUNK_BYTES - line.len();
+        let available = &available[..available.len().min(room)];
+        let (taken, complete) = match available.iter().position(|b| *b == b'\n') {
+            Some(pos) => (pos + 1, true),
+            None => (available.len(), false),
+        };
+        line.extend_from_slice(&available[..taken]);
+        reader.consume(taken);
+        if complete {
+            emit(&line);
+            line.clear();
+        } else if line.len() >= MAX_OUTPUT_CHUNK_BYTES {
+            // The rest of a split character starts the next chunk
+            let end = chunk_boundary(&line, encoding);
+            emit(&line[..end]);
+            line.drain(..end);
+        }
+    }
+    if !line.is_empty() {
+        emit(&line);
+    }
+}
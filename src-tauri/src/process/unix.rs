@@ -1,19 +1,22 @@
 //! Unix-specific process operations
 //!
 //! Process listing/querying is now handled by sysinfo in mod.rs.
-//! This module only provides platform-specific start and terminate operations.
+//! This module only provides platform-specific start, terminate, suspend,
+//! resume and environment reading operations.
 
 use super::{
-    ProcessError, StartProcessRequest, StartProcessResult, TerminateProcessRequest,
-    TerminateProcessResult,
+    env_from_entries, OutputEncoding, ProcessError, StartProcessRequest,
+    StartProcessResult, TerminateProcessRequest, TerminateProcessResult,
 };
+use std::collections::HashMap;
 use std::time::Instant;
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 
 /// Start a new process
 pub async fn start_process(
     request: StartProcessRequest,
-) -> Result<StartProcessResult, ProcessError> {
+    output_encoding: OutputEncoding,
+) -> Result<(StartProcessResult, Option<Child>), ProcessError> {
     let start = Instant::now();
 
     let mut cmd = Command::new(&request.program);
@@ -50,7 +53,7 @@ pub async fn start_process(
         match cmd.spawn() {
             Ok(child) => {
                 let pid = child.id();
-                Ok(StartProcessResult {
+                let result = StartProcessResult {
                     success: true,
                     pid,
                     stdout: None,
@@ -58,29 +61,34 @@ pub async fn start_process(
                     exit_code: None,
                     error: None,
                     duration_ms: Some(start.elapsed().as_millis() as u64),
-                })
+                };
+                Ok((result, Some(child)))
             }
-            Err(e) => Ok(StartProcessResult {
-                success: false,
-                pid: None,
-                stdout: None,
-                stderr: None,
-                exit_code: None,
-                error: Some(e.to_string()),
-                duration_ms: Some(start.elapsed().as_millis() as u64),
-            }),
+            Err(e) => Ok((
+                StartProcessResult {
+                    success: false,
+                    pid: None,
+                    stdout: None,
+                    stderr: None,
+                    exit_code: None,
+                    error: Some(e.to_string()),
+                    duration_ms: Some(start.elapsed().as_millis() as u64),
+                },
+                None,
+            )),
         }
     } else {
         // Run and capture output
         let timeout_secs = request.timeout_secs.unwrap_or(30);
 
-        match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), cmd.output()).await
-        {
+        let output =
+            tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), cmd.output());
+        let result = match output.await {
             Ok(Ok(output)) => Ok(StartProcessResult {
                 success: output.status.success(),
                 pid: None,
-                stdout: Some(String::from_utf8_lossy(&output.stdout).to_string()),
-                stderr: Some(String::from_utf8_lossy(&output.stderr).to_string()),
+                stdout: Some(output_encoding.decode(&output.stdout)),
+                stderr: Some(output_encoding.decode(&output.stderr)),
                 exit_code: output.status.code(),
                 error: None,
                 duration_ms: Some(start.elapsed().as_millis() as u64),
@@ -103,7 +111,9 @@ pub async fn start_process(
                 error: Some("Process timed out".to_string()),
                 duration_ms: Some(start.elapsed().as_millis() as u64),
             }),
-        }
+        };
+        // The process has already been waited for
+        result.map(|result| (result, None))
     }
 }
 
@@ -115,10 +125,24 @@ pub async fn terminate_process(
     use nix::unistd::Pid;
 
     let pid = Pid::from_raw(request.pid as i32);
+    let requested = request.signal.as_deref().map(parse_signal).transpose()?;
+    if let Some(
+        sig @ (Signal::SIGSTOP
+        | Signal::SIGTSTP
+        | Signal::SIGTTIN
+        | Signal::SIGTTOU
+        | Signal::SIGCONT),
+    ) = requested.filter(|_| !request.force)
+    {
+        return Err(ProcessError::Other(format!(
+            "{} pauses or continues a process; use suspend or resume instead",
+            sig
+        )));
+    }
     let signal = if request.force {
         Signal::SIGKILL
     } else {
-        Signal::SIGTERM
+        requested.unwrap_or(Signal::SIGTERM)
     };
 
     match kill(pid, signal) {
@@ -145,3 +169,156 @@ pub async fn terminate_process(
         }
     }
 }
+
+/// Parse a signal given by name, with or without the `SIG` prefix, or number
+pub(super) fn parse_signal(value: &str) -> Result<nix::sys::signal::Signal, ProcessError> {
+    use nix::sys::signal::Signal;
+
+    let value = value.trim();
+    let signal = match value.parse::<i32>() {
+        Ok(number) => Signal::try_from(number).ok(),
+        Err(_) => {
+            let name = value.to_ascii_uppercase();
+            if name.starts_with("SIG") {
+                name.parse().ok()
+            } else {
+                format!("SIG{}", name).parse().ok()
+            }
+        }
+    };
+    signal.ok_or_else(|| ProcessError::Other(format!("Unknown signal: {}", value)))
+}
+
+/// Whether `signal` always ends the process it is sent to
+pub(super) fn signal_ends_process(signal: &str) -> bool {
+    use nix::sys::signal::Signal;
+
+    matches!(parse_signal(signal), Ok(Signal::SIGTERM | Signal::SIGKILL))
+}
+
+/// Suspend a process with SIGSTOP
+pub async fn suspend_process(pid: u32) -> Result<(), ProcessError> {
+    send_signal(pid, nix::sys::signal::Signal::SIGSTOP, "suspend")
+}
+
+/// Resume a process with SIGCONT
+pub async fn resume_process(pid: u32) -> Result<(), ProcessError> {
+    send_signal(pid, nix::sys::signal::Signal::SIGCONT, "resume")
+}
+
+fn send_signal(
+    pid: u32,
+    signal: nix::sys::signal::Signal,
+    action: &str,
+) -> Result<(), ProcessError> {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    kill(Pid::from_raw(pid as i32), signal).map_err(|e| match e {
+        nix::errno::Errno::ESRCH => ProcessError::NotFound(pid),
+        nix::errno::Errno::EPERM => {
+            ProcessError::PermissionDenied(format!("Cannot {} process {}: {}", action, pid, e))
+        }
+        _ => ProcessError::System(format!("Failed to {} process {}: {}", action, pid, e)),
+    })
+}
+
+/// Read a process's environment from `/proc/<pid>/environ`
+#[cfg(target_os = "linux")]
+pub(super) fn read_process_env(pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+    let block = std::fs::read(format!("/proc/{}/environ", pid)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => ProcessError::NotFound(pid),
+        std::io::ErrorKind::PermissionDenied => ProcessError::PermissionDenied(format!(
+            "Cannot read environment of process {}: {}",
+            pid, e
+        )),
+        _ => ProcessError::System(format!(
+            "Failed to read environment of process {}: {}",
+            pid, e
+        )),
+    })?;
+    Ok(env_from_entries(
+        block.split(|b| *b == 0).map(String::from_utf8_lossy),
+    ))
+}
+
+/// Read a process's environment from its `KERN_PROCARGS2` argument area
+#[cfg(target_os = "macos")]
+pub(super) fn read_process_env(pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+    use std::io::Error;
+
+    unsafe {
+        let mut arg_max: libc::c_int = 0;
+        let mut size = std::mem::size_of::<libc::c_int>();
+        let mut mib = [libc::CTL_KERN, libc::KERN_ARGMAX];
+        if libc::sysctl(
+            mib.as_mut_ptr(),
+            2,
+            &mut arg_max as *mut _ as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        ) != 0
+        {
+            return Err(ProcessError::System(format!(
+                "Failed to query KERN_ARGMAX: {}",
+                Error::last_os_error()
+            )));
+        }
+
+        let mut buffer = vec![0u8; arg_max as usize];
+        let mut size = buffer.len();
+        let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid as libc::c_int];
+        if libc::sysctl(
+            mib.as_mut_ptr(),
+            3,
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        ) != 0
+        {
+            let error = Error::last_os_error();
+            // The kernel answers EINVAL for processes of other users
+            return Err(match error.raw_os_error() {
+                Some(libc::ESRCH) => ProcessError::NotFound(pid),
+                Some(libc::EPERM) | Some(libc::EINVAL) => ProcessError::PermissionDenied(format!(
+                    "Cannot read environment of process {}: {}",
+                    pid, error
+                )),
+                _ => ProcessError::System(format!(
+                    "Failed to read environment of process {}: {}",
+                    pid, error
+                )),
+            });
+        }
+        buffer.truncate(size);
+
+        // Layout: argc, executable path, NUL padding, argv[argc], then the
+        // environment, terminated by an empty string
+        let argc_size = std::mem::size_of::<libc::c_int>();
+        if buffer.len() < argc_size {
+            return Ok(HashMap::new());
+        }
+        let argc = libc::c_int::from_ne_bytes(buffer[..argc_size].try_into().unwrap_or_default());
+        let strings = buffer[argc_size..]
+            .split(|b| *b == 0)
+            .skip(1)
+            .skip_while(|s| s.is_empty())
+            .skip(argc.max(0) as usize);
+        Ok(env_from_entries(
+            strings
+                .take_while(|s| !s.is_empty())
+                .map(String::from_utf8_lossy),
+        ))
+    }
+}
+
+/// Environments of other processes are not readable on this platform
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub(super) fn read_process_env(pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+    Err(ProcessError::Unsupported(format!(
+        "Cannot read environment of process {} on this platform",
+        pid
+    )))
+}
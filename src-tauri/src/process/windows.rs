@@ -1,23 +1,47 @@
 //! Windows-specific process operations
 //!
 //! Process listing/querying is now handled by sysinfo in mod.rs.
-//! This module only provides platform-specific start and terminate operations.
+//! This module only provides platform-specific start, terminate, suspend,
+//! resume, wait and environment reading operations.
 
 use super::{
-    ProcessError, StartProcessRequest, StartProcessResult, TerminateProcessRequest,
-    TerminateProcessResult,
+    env_from_entries, OutputEncoding, ProcessError, StartProcessRequest, StartProcessResult,
+    TerminateProcessRequest, TerminateProcessResult,
+};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+use windows::Wdk::System::Threading::{NtQueryInformationProcess, ProcessBasicInformation};
+use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0, WAIT_TIMEOUT};
+use windows::Win32::Globalization::GetOEMCP;
+use windows::Win32::System::Console::GetConsoleOutputCP;
+use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
 };
-use std::time::Instant;
-use tokio::process::Command;
-use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::System::Threading::{
-    OpenProcess, TerminateProcess, PROCESS_QUERY_INFORMATION, PROCESS_TERMINATE,
+    GetExitCodeProcess, OpenProcess, OpenThread, ResumeThread, SuspendThread, TerminateProcess,
+    WaitForSingleObject, INFINITE, PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+    PROCESS_SYNCHRONIZE, PROCESS_TERMINATE, PROCESS_VM_READ, THREAD_SUSPEND_RESUME,
 };
 
+/// Codepage console programs write their output in
+pub(super) fn console_codepage() -> u32 {
+    // A GUI process has no console; child console programs then use the OEM codepage
+    let codepage = unsafe { GetConsoleOutputCP() };
+    if codepage != 0 {
+        codepage
+    } else {
+        unsafe { GetOEMCP() }
+    }
+}
+
 /// Start a new process
 pub async fn start_process(
     request: StartProcessRequest,
-) -> Result<StartProcessResult, ProcessError> {
+    output_encoding: OutputEncoding,
+) -> Result<(StartProcessResult, Option<Child>), ProcessError> {
     let start = Instant::now();
 
     let mut cmd = Command::new(&request.program);
@@ -51,7 +75,7 @@ pub async fn start_process(
         match cmd.spawn() {
             Ok(child) => {
                 let pid = child.id();
-                Ok(StartProcessResult {
+                let result = StartProcessResult {
                     success: true,
                     pid,
                     stdout: None,
@@ -59,29 +83,34 @@ pub async fn start_process(
                     exit_code: None,
                     error: None,
                     duration_ms: Some(start.elapsed().as_millis() as u64),
-                })
+                };
+                Ok((result, Some(child)))
             }
-            Err(e) => Ok(StartProcessResult {
-                success: false,
-                pid: None,
-                stdout: None,
-                stderr: None,
-                exit_code: None,
-                error: Some(e.to_string()),
-                duration_ms: Some(start.elapsed().as_millis() as u64),
-            }),
+            Err(e) => Ok((
+                StartProcessResult {
+                    success: false,
+                    pid: None,
+                    stdout: None,
+                    stderr: None,
+                    exit_code: None,
+                    error: Some(e.to_string()),
+                    duration_ms: Some(start.elapsed().as_millis() as u64),
+                },
+                None,
+            )),
         }
     } else {
         // Run and capture output
         let timeout_secs = request.timeout_secs.unwrap_or(30);
 
-        match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), cmd.output()).await
-        {
+        let output =
+            tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), cmd.output());
+        let result = match output.await {
             Ok(Ok(output)) => Ok(StartProcessResult {
                 success: output.status.success(),
                 pid: None, // Process already completed
-                stdout: Some(String::from_utf8_lossy(&output.stdout).to_string()),
-                stderr: Some(String::from_utf8_lossy(&output.stderr).to_string()),
+                stdout: Some(output_encoding.decode(&output.stdout)),
+                stderr: Some(output_encoding.decode(&output.stderr)),
                 exit_code: output.status.code(),
                 error: None,
                 duration_ms: Some(start.elapsed().as_millis() as u64),
@@ -104,7 +133,9 @@ pub async fn start_process(
                 error: Some("Process timed out".to_string()),
                 duration_ms: Some(start.elapsed().as_millis() as u64),
             }),
-        }
+        };
+        // The process has already been waited for
+        result.map(|result| (result, None))
     }
 }
 
@@ -112,6 +143,10 @@ pub async fn start_process(
 pub async fn terminate_process(
     request: TerminateProcessRequest,
 ) -> Result<TerminateProcessResult, ProcessError> {
+    if let Some(signal) = request.signal.as_deref() {
+        check_signal(signal)?;
+    }
+
     tokio::task::spawn_blocking(move || {
         unsafe {
             let access_rights = PROCESS_TERMINATE | PROCESS_QUERY_INFORMATION;
@@ -144,3 +179,250 @@ pub async fn terminate_process(
     .await
     .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?
 }
+
+/// Wait for a process to exit and return its exit code
+pub async fn wait_for_exit(pid: u32, timeout: Duration) -> Result<Option<i32>, ProcessError> {
+    tokio::task::spawn_blocking(move || unsafe {
+        let access_rights = PROCESS_SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION;
+        let handle = OpenProcess(access_rights, false, pid)
+            .map_err(|e| ProcessError::PermissionDenied(format!("Cannot open process: {}", e)))?;
+
+        // INFINITE is reserved for waits without a deadline
+        let millis = u32::try_from(timeout.as_millis()).unwrap_or(INFINITE - 1);
+        let event = WaitForSingleObject(handle, millis);
+        let result = if event == WAIT_OBJECT_0 {
+            let mut exit_code = 0u32;
+            Ok(GetExitCodeProcess(handle, &mut exit_code)
+                .ok()
+                .map(|()| exit_code as i32))
+        } else if event == WAIT_TIMEOUT {
+            Err(ProcessError::Timeout)
+        } else {
+            Err(ProcessError::System(format!(
+                "Failed to wait for process {}",
+                pid
+            )))
+        };
+        let _ = CloseHandle(handle);
+        result
+    })
+    .await
+    .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?
+}
+
+/// Windows has no signals; only those that mean "terminate" are accepted
+fn check_signal(signal: &str) -> Result<(), ProcessError> {
+    let name = signal.trim().to_ascii_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+    if matches!(name, "TERM" | "KILL" | "15" | "9") {
+        return Ok(());
+    }
+    Err(ProcessError::Unsupported(format!(
+        "Signal {} is not supported on Windows, where processes can only be terminated",
+        signal
+    )))
+}
+
+/// Suspend a process by suspending each of its threads
+///
+/// When a thread cannot be suspended, the threads already suspended are
+/// resumed again so the process is not left partially frozen.
+pub async fn suspend_process(pid: u32) -> Result<(), ProcessError> {
+    tokio::task::spawn_blocking(move || {
+        for_each_thread(
+            pid,
+            "suspend",
+            |thread| unsafe { SuspendThread(thread) },
+            Some(|thread| unsafe { ResumeThread(thread) }),
+        )
+    })
+    .await
+    .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?
+}
+
+/// Resume a process by resuming each of its threads
+pub async fn resume_process(pid: u32) -> Result<(), ProcessError> {
+    tokio::task::spawn_blocking(move || {
+        for_each_thread(
+            pid,
+            "resume",
+            |thread| unsafe { ResumeThread(thread) },
+            None,
+        )
+    })
+    .await
+    .map_err(|e| ProcessError::System(format!("Task join error: {}", e)))?
+}
+
+/// Apply `SuspendThread` or `ResumeThread` to every thread of a process
+///
+/// With a `rollback`, the first failure stops the walk and `rollback` is
+/// applied to the threads already handled; without one, the remaining
+/// threads are still attempted.
+fn for_each_thread(
+    pid: u32,
+    action: &str,
+    apply: impl Fn(HANDLE) -> u32,
+    rollback: Option<fn(HANDLE) -> u32>,
+) -> Result<(), ProcessError> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)
+            .map_err(|e| ProcessError::System(format!("Cannot list threads: {}", e)))?;
+
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        // Handles stay open until the walk ends so a rollback can reuse them
+        let mut applied = Vec::new();
+        let mut result = Ok(());
+        let mut next = Thread32First(snapshot, &mut entry);
+        while next.is_ok() {
+            if entry.th32OwnerProcessID == pid {
+                match OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    // Both calls return the previous suspend count, or u32::MAX on failure
+                    Ok(thread) if apply(thread) != u32::MAX => applied.push(thread),
+                    Ok(thread) => {
+                        let _ = CloseHandle(thread);
+                        result = Err(ProcessError::System(format!(
+                            "Failed to {} thread {} of process {}",
+                            action, entry.th32ThreadID, pid
+                        )));
+                    }
+                    Err(e) => {
+                        result = Err(ProcessError::PermissionDenied(format!(
+                            "Cannot {} process {}: {}",
+                            action, pid, e
+                        )));
+                    }
+                }
+                if result.is_err() && rollback.is_some() {
+                    break;
+                }
+            }
+            next = Thread32Next(snapshot, &mut entry);
+        }
+        let _ = CloseHandle(snapshot);
+
+        if let (Err(_), Some(rollback)) = (&result, rollback) {
+            for &thread in &applied {
+                rollback(thread);
+            }
+        }
+        let threads = applied.len();
+        for thread in applied {
+            let _ = CloseHandle(thread);
+        }
+
+        if threads == 0 && result.is_ok() {
+            return Err(ProcessError::NotFound(pid));
+        }
+        result
+    }
+}
+
+// Offsets of `PEB.ProcessParameters` and of `Environment` and `EnvironmentSize`
+// in `RTL_USER_PROCESS_PARAMETERS`, which the SDK only declares as reserved
+#[cfg(target_pointer_width = "64")]
+const PEB_PROCESS_PARAMETERS_OFFSET: usize = 0x20;
+#[cfg(target_pointer_width = "64")]
+const PARAMETERS_ENVIRONMENT_OFFSET: usize = 0x80;
+#[cfg(target_pointer_width = "64")]
+const PARAMETERS_ENVIRONMENT_SIZE_OFFSET: usize = 0x3F0;
+#[cfg(target_pointer_width = "32")]
+const PEB_PROCESS_PARAMETERS_OFFSET: usize = 0x10;
+#[cfg(target_pointer_width = "32")]
+const PARAMETERS_ENVIRONMENT_OFFSET: usize = 0x48;
+#[cfg(target_pointer_width = "32")]
+const PARAMETERS_ENVIRONMENT_SIZE_OFFSET: usize = 0x290;
+
+/// Upper bound for an environment block, in case the size read is garbage
+const MAX_ENVIRONMENT_BYTES: usize = 1 << 20;
+
+/// `PROCESS_BASIC_INFORMATION`
+#[repr(C)]
+#[derive(Default)]
+struct BasicInformation {
+    exit_status: i32,
+    peb_base_address: usize,
+    affinity_mask: usize,
+    base_priority: i32,
+    unique_process_id: usize,
+    inherited_from_unique_process_id: usize,
+}
+
+/// Read a process's environment block through its PEB
+pub(super) fn read_process_env(pid: u32) -> Result<HashMap<String, String>, ProcessError> {
+    unsafe {
+        let handle =
+            OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid).map_err(|e| {
+                ProcessError::PermissionDenied(format!(
+                    "Cannot read environment of process {}: {}",
+                    pid, e
+                ))
+            })?;
+        let block = read_environment_block(handle, pid);
+        let _ = CloseHandle(handle);
+        Ok(env_from_entries(
+            String::from_utf16_lossy(&block?).split('\0'),
+        ))
+    }
+}
+
+unsafe fn read_environment_block(handle: HANDLE, pid: u32) -> Result<Vec<u16>, ProcessError> {
+    let mut info = BasicInformation::default();
+    let status = NtQueryInformationProcess(
+        handle,
+        ProcessBasicInformation,
+        &mut info as *mut BasicInformation as *mut c_void,
+        std::mem::size_of::<BasicInformation>() as u32,
+        std::ptr::null_mut(),
+    );
+    if status.is_err() {
+        return Err(ProcessError::PermissionDenied(format!(
+            "Cannot query process {}: NTSTATUS {:#x}",
+            pid, status.0
+        )));
+    }
+
+    let parameters: usize = read_remote(
+        handle,
+        info.peb_base_address + PEB_PROCESS_PARAMETERS_OFFSET,
+        pid,
+    )?;
+    let environment: usize = read_remote(handle, parameters + PARAMETERS_ENVIRONMENT_OFFSET, pid)?;
+    let size: usize = read_remote(handle, parameters + PARAMETERS_ENVIRONMENT_SIZE_OFFSET, pid)?;
+
+    let mut block = vec![0u16; size.min(MAX_ENVIRONMENT_BYTES) / 2];
+    ReadProcessMemory(
+        handle,
+        environment as *const c_void,
+        block.as_mut_ptr() as *mut c_void,
+        block.len() * 2,
+        None,
+    )
+    .map_err(|e| read_denied(pid, e))?;
+    Ok(block)
+}
+
+/// Read a value of type `T` from another process's memory
+unsafe fn read_remote<T: Default>(
+    handle: HANDLE,
+    address: usize,
+    pid: u32,
+) -> Result<T, ProcessError> {
+    let mut value = T::default();
+    ReadProcessMemory(
+        handle,
+        address as *const c_void,
+        &mut value as *mut T as *mut c_void,
+        std::mem::size_of::<T>(),
+        None,
+    )
+    .map_err(|e| read_denied(pid, e))?;
+    Ok(value)
+}
+
+fn read_denied(pid: u32, error: windows::core::Error) -> ProcessError {
+    ProcessError::PermissionDenied(format!("Cannot read memory of process {}: {}", pid, error))
+}
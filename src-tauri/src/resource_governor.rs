@@ -0,0 +1,356 @@
+//! Global resource governor
+//!
+//! Batch paths in different subsystems (process batches, academic
+//! multi-provider search, embedding requests) each bound their own
+//! concurrency. The governor adds a shared, app-wide budget on top: every
+//! unit of heavy parallel work acquires a permit here first, so running
+//! several batches at once cannot collectively saturate the machine.
+//!
+//! The budget is persisted in `GOVERNOR_SETTINGS_FILE` and restored at startup.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Default global concurrency budget
+pub const DEFAULT_GLOBAL_CONCURRENCY: usize = 8;
+/// Hard cap for the global concurrency budget
+pub const MAX_GLOBAL_CONCURRENCY: usize = 64;
+/// File in the app data directory holding the governor settings
+pub const GOVERNOR_SETTINGS_FILE: &str = "resource_governor.json";
+
+static GLOBAL_GOVERNOR: Lazy<ResourceGovernor> =
+    Lazy::new(|| ResourceGovernor::new(DEFAULT_GLOBAL_CONCURRENCY));
+
+/// Get the process-wide governor shared by all batch paths
+pub fn global() -> &'static ResourceGovernor {
+    &GLOBAL_GOVERNOR
+}
+
+/// Governor settings persisted across restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GovernorSettings {
+    pub budget: usize,
+}
+
+impl Default for GovernorSettings {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_GLOBAL_CONCURRENCY,
+        }
+    }
+}
+
+impl GovernorSettings {
+    /// Load settings from `path`, falling back to defaults if missing or invalid
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Invalid resource governor settings, using defaults: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings dir: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write resource governor settings: {}", e))
+    }
+}
+
+/// Current governor utilization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernorStatus {
+    /// Configured concurrency budget
+    pub budget: usize,
+    /// Permits currently held
+    pub in_use: usize,
+    /// Permits available right now
+    pub available: usize,
+    /// Tasks waiting for a permit
+    pub waiting: usize,
+    /// Permits held per subsystem
+    pub by_subsystem: HashMap<String, usize>,
+}
+
+struct GovernorInner {
+    semaphore: Arc<Semaphore>,
+    /// Serializes budget changes
+    budget: Mutex<usize>,
+    /// Permits to retire on release after the budget shrank below the permits in use
+    shrink_debt: AtomicUsize,
+    waiting: AtomicUsize,
+    active: Mutex<HashMap<String, usize>>,
+}
+
+/// Semaphore-based global concurrency limiter
+#[derive(Clone)]
+pub struct ResourceGovernor {
+    inner: Arc<GovernorInner>,
+}
+
+/// Permit held for the duration of one unit of work
+pub struct GovernorPermit {
+    inner: Arc<GovernorInner>,
+    subsystem: String,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for GovernorPermit {
+    fn drop(&mut self) {
+        // Retire the permit instead of returning it while the budget is over-committed
+        let retire = self
+            .inner
+            .shrink_debt
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |debt| {
+                debt.checked_sub(1)
+            })
+            .is_ok();
+        if let Some(permit) = self.permit.take() {
+            if retire {
+                permit.forget();
+            }
+        }
+
+        let mut active = self.inner.active.lock();
+        if let Some(count) = active.get_mut(&self.subsystem) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                active.remove(&self.subsystem);
+            }
+        }
+    }
+}
+
+/// Counts a task as waiting until it is dropped, so cancelled waits are uncounted too
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        waiting.fetch_add(1, Ordering::SeqCst);
+        Self(waiting)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ResourceGovernor {
+    /// Create a governor with the given budget (clamped to 1..=MAX_GLOBAL_CONCURRENCY)
+    pub fn new(budget: usize) -> Self {
+        let budget = budget.clamp(1, MAX_GLOBAL_CONCURRENCY);
+        Self {
+            inner: Arc::new(GovernorInner {
+                semaphore: Arc::new(Semaphore::new(budget)),
+                budget: Mutex::new(budget),
+                shrink_debt: AtomicUsize::new(0),
+                waiting: AtomicUsize::new(0),
+                active: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Wait for a permit on behalf of a subsystem
+    pub async fn acquire(&self, subsystem: &str) -> GovernorPermit {
+        let permit = {
+            let _waiting = WaitingGuard::new(&self.inner.waiting);
+            self.inner
+                .semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("governor semaphore is never closed")
+        };
+
+        *self
+            .inner
+            .active
+            .lock()
+            .entry(subsystem.to_string())
+            .or_insert(0) += 1;
+
+        GovernorPermit {
+            inner: self.inner.clone(),
+            subsystem: subsystem.to_string(),
+            permit: Some(permit),
+        }
+    }
+
+    /// Change the concurrency budget
+    ///
+    /// The semaphore is resized in place. Permits already held stay valid until
+    /// released; when the budget shrinks below the permits in use, released
+    /// permits are retired until usage fits the new budget.
+    pub fn set_budget(&self, budget: usize) -> usize {
+        let budget = budget.clamp(1, MAX_GLOBAL_CONCURRENCY);
+        let mut current = self.inner.budget.lock();
+        if budget > *current {
+            let grow = budget - *current;
+            // Growing first cancels permits still due to be retired
+            let debt = self
+                .inner
+                .shrink_debt
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |debt| {
+                    Some(debt.saturating_sub(grow))
+                })
+                .unwrap_or_else(|debt| debt);
+            self.inner.semaphore.add_permits(grow - debt.min(grow));
+        } else if budget < *current {
+            let shrink = *current - budget;
+            let forgotten = self.inner.semaphore.forget_permits(shrink);
+            self.inner
+                .shrink_debt
+                .fetch_add(shrink - forgotten, Ordering::SeqCst);
+        }
+        *current = budget;
+        log::info!("Resource governor budget set to {}", budget);
+        budget
+    }
+
+    /// Get the configured budget
+    pub fn budget(&self) -> usize {
+        *self.inner.budget.lock()
+    }
+
+    /// Get current utilization
+    pub fn status(&self) -> GovernorStatus {
+        let by_subsystem = self.inner.active.lock().clone();
+        let in_use: usize = by_subsystem.values().sum();
+        let budget = self.budget();
+        GovernorStatus {
+            budget,
+            in_use,
+            available: budget.saturating_sub(in_use),
+            waiting: self.inner.waiting.load(Ordering::SeqCst),
+            by_subsystem,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_permits_are_tracked_per_subsystem() {
+        let governor = ResourceGovernor::new(3);
+        let a = governor.acquire("process").await;
+        let _b = governor.acquire("process").await;
+        let _c = governor.acquire("academic").await;
+
+        let status = governor.status();
+        assert_eq!(status.budget, 3);
+        assert_eq!(status.in_use, 3);
+        assert_eq!(status.available, 0);
+        assert_eq!(status.by_subsystem.get("process"), Some(&2));
+        assert_eq!(status.by_subsystem.get("academic"), Some(&1));
+
+        drop(a);
+        let status = governor.status();
+        assert_eq!(status.in_use, 2);
+        assert_eq!(status.by_subsystem.get("process"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_budget_limits_concurrency() {
+        let governor = ResourceGovernor::new(1);
+        let held = governor.acquire("process").await;
+
+        let waiter = {
+            let governor = governor.clone();
+            tokio::spawn(async move {
+                let _permit = governor.acquire("embedding").await;
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+        assert_eq!(governor.status().waiting, 1);
+
+        drop(held);
+        waiter.await.unwrap();
+        assert_eq!(governor.status().in_use, 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_budget_is_clamped_and_wakes_waiters() {
+        let governor = ResourceGovernor::new(1);
+        assert_eq!(governor.set_budget(0), 1);
+        assert_eq!(governor.set_budget(1000), MAX_GLOBAL_CONCURRENCY);
+
+        governor.set_budget(1);
+        let _held = governor.acquire("process").await;
+        let waiter = {
+            let governor = governor.clone();
+            tokio::spawn(async move {
+                let _permit = governor.acquire("academic").await;
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        governor.set_budget(2);
+        waiter.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shrinking_budget_retires_released_permits() {
+        let governor = ResourceGovernor::new(3);
+        let a = governor.acquire("process").await;
+        let b = governor.acquire("process").await;
+        let _c = governor.acquire("process").await;
+
+        governor.set_budget(1);
+        drop(a);
+        drop(b);
+        // Two releases were retired, the remaining held permit fills the budget
+        assert_eq!(governor.inner.semaphore.available_permits(), 0);
+        assert_eq!(governor.inner.shrink_debt.load(Ordering::SeqCst), 0);
+
+        governor.set_budget(2);
+        assert_eq!(governor.inner.semaphore.available_permits(), 1);
+        let _d = governor.acquire("academic").await;
+        assert_eq!(governor.status().in_use, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_wait_is_not_counted() {
+        let governor = ResourceGovernor::new(1);
+        let _held = governor.acquire("process").await;
+
+        let timed_out = tokio::time::timeout(
+            std::time::Duration::from_millis(20),
+            governor.acquire("embedding"),
+        )
+        .await;
+        assert!(timed_out.is_err());
+        assert_eq!(governor.status().waiting, 0);
+    }
+
+    #[test]
+    fn test_governor_settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(GOVERNOR_SETTINGS_FILE);
+        assert_eq!(GovernorSettings::load(&path), GovernorSettings::default());
+
+        let settings = GovernorSettings { budget: 3 };
+        settings.save(&path).unwrap();
+        assert_eq!(GovernorSettings::load(&path), settings);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(GovernorSettings::load(&path), GovernorSettings::default());
+    }
+}
@@ -0,0 +1,311 @@
+//! ANSI escape sequence rendering
+//!
+//! Converts SGR (color/style) escape sequences in program output to HTML spans
+//! with inline styles. Other control sequences (cursor movement, OSC titles)
+//! are dropped, and all text is HTML-escaped.
+
+/// Standard 16-color palette (xterm defaults)
+const PALETTE: [&str; 16] = [
+    "#000000", "#cd3131", "#0dbc79", "#e5e510", "#2472c8", "#bc3fbc", "#11a8cd", "#e5e5e5",
+    "#666666", "#f14c4c", "#23d18b", "#f5f543", "#3b8eea", "#d670d6", "#29b8db", "#ffffff",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Style {
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    inverse: bool,
+    fg: Option<String>,
+    bg: Option<String>,
+}
+
+impl Style {
+    fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    fn css(&self) -> String {
+        let (fg, bg) = if self.inverse {
+            (
+                Some(
+                    self.bg
+                        .clone()
+                        .unwrap_or_else(|| "var(--background, #000000)".into()),
+                ),
+                Some(
+                    self.fg
+                        .clone()
+                        .unwrap_or_else(|| "var(--foreground, #e5e5e5)".into()),
+                ),
+            )
+        } else {
+            (self.fg.clone(), self.bg.clone())
+        };
+
+        let mut parts = Vec::new();
+        if let Some(fg) = fg {
+            parts.push(format!("color:{}", fg));
+        }
+        if let Some(bg) = bg {
+            parts.push(format!("background-color:{}", bg));
+        }
+        if self.bold {
+            parts.push("font-weight:bold".to_string());
+        }
+        if self.dim {
+            parts.push("opacity:0.7".to_string());
+        }
+        if self.italic {
+            parts.push("font-style:italic".to_string());
+        }
+        match (self.underline, self.strikethrough) {
+            (true, true) => parts.push("text-decoration:underline line-through".to_string()),
+            (true, false) => parts.push("text-decoration:underline".to_string()),
+            (false, true) => parts.push("text-decoration:line-through".to_string()),
+            (false, false) => {}
+        }
+        parts.join(";")
+    }
+
+    /// Apply the parameters of one SGR (`ESC [ ... m`) sequence
+    fn apply_sgr(&mut self, params: &[u32]) {
+        if params.is_empty() {
+            *self = Style::default();
+            return;
+        }
+
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                9 => self.strikethrough = true,
+                21 | 22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                29 => self.strikethrough = false,
+                code @ 30..=37 => self.fg = Some(PALETTE[(code - 30) as usize].to_string()),
+                39 => self.fg = None,
+                code @ 40..=47 => self.bg = Some(PALETTE[(code - 40) as usize].to_string()),
+                49 => self.bg = None,
+                code @ 90..=97 => self.fg = Some(PALETTE[(code - 90 + 8) as usize].to_string()),
+                code @ 100..=107 => self.bg = Some(PALETTE[(code - 100 + 8) as usize].to_string()),
+                code @ (38 | 48) => {
+                    let (color, consumed) = extended_color(&params[i + 1..]);
+                    if code == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                    i += consumed;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Parse a `5;n` (256-color) or `2;r;g;b` (truecolor) tail.
+///
+/// Returns the color and how many parameters were consumed.
+fn extended_color(params: &[u32]) -> (Option<String>, usize) {
+    match params {
+        [5, n, ..] => (Some(xterm_256_color(*n)), 2),
+        [2, r, g, b, ..] => (
+            Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                (*r).min(255),
+                (*g).min(255),
+                (*b).min(255)
+            )),
+            4,
+        ),
+        _ => (None, params.len()),
+    }
+}
+
+fn xterm_256_color(n: u32) -> String {
+    match n {
+        0..=15 => PALETTE[n as usize].to_string(),
+        16..=231 => {
+            let n = n - 16;
+            let level = |v: u32| if v == 0 { 0 } else { 55 + v * 40 };
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(n / 36),
+                level((n / 6) % 6),
+                level(n % 6)
+            )
+        }
+        232..=255 => {
+            let gray = 8 + (n - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", gray, gray, gray)
+        }
+        _ => PALETTE[7].to_string(),
+    }
+}
+
+fn escape_html_char(c: char, out: &mut String) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        _ => out.push(c),
+    }
+}
+
+/// Convert text containing ANSI escape sequences to HTML.
+///
+/// Styled runs become `<span style="...">`; unstyled text is emitted as-is
+/// (HTML-escaped). Newlines are preserved.
+pub fn ansi_to_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut style = Style::default();
+    let mut span_open = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            if c == '\r' && chars.peek() != Some(&'\n') {
+                // Bare carriage returns (progress bars) can't be rendered; drop them
+                continue;
+            }
+            escape_html_char(c, &mut out);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('[') => {
+                chars.next();
+                let mut raw = String::new();
+                let mut final_byte = None;
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        final_byte = Some(c);
+                        break;
+                    }
+                    raw.push(c);
+                }
+                if final_byte != Some('m') {
+                    continue;
+                }
+
+                let params: Vec<u32> = if raw.is_empty() {
+                    Vec::new()
+                } else {
+                    raw.split([';', ':'])
+                        .map(|p| p.parse().unwrap_or(0))
+                        .collect()
+                };
+                let previous = style.clone();
+                style.apply_sgr(&params);
+                if style == previous {
+                    continue;
+                }
+
+                if span_open {
+                    out.push_str("</span>");
+                    span_open = false;
+                }
+                if !style.is_plain() {
+                    out.push_str(&format!("<span style=\"{}\">", style.css()));
+                    span_open = true;
+                }
+            }
+            Some(']') => {
+                // OSC: terminated by BEL or ESC \
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '\u{07}' {
+                        break;
+                    }
+                    if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(_) => {
+                // Two-character escape (e.g. ESC ( B); skip the next char
+                chars.next();
+            }
+            None => {}
+        }
+    }
+
+    if span_open {
+        out.push_str("</span>");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_is_escaped() {
+        assert_eq!(ansi_to_html("a < b && c"), "a &lt; b &amp;&amp; c");
+        assert_eq!(ansi_to_html("line1\nline2"), "line1\nline2");
+    }
+
+    #[test]
+    fn test_basic_colors_and_reset() {
+        let html = ansi_to_html("\u{1b}[31merror\u{1b}[0m: done");
+        assert_eq!(html, "<span style=\"color:#cd3131\">error</span>: done");
+    }
+
+    #[test]
+    fn test_bold_bright_and_combined() {
+        let html = ansi_to_html("\u{1b}[1;92mok\u{1b}[m");
+        assert_eq!(
+            html,
+            "<span style=\"color:#23d18b;font-weight:bold\">ok</span>"
+        );
+    }
+
+    #[test]
+    fn test_extended_colors() {
+        let html = ansi_to_html("\u{1b}[38;5;196mred\u{1b}[48;2;0;128;255mboth\u{1b}[0m");
+        assert_eq!(
+            html,
+            "<span style=\"color:#ff0000\">red</span>\
+             <span style=\"color:#ff0000;background-color:#0080ff\">both</span>"
+        );
+    }
+
+    #[test]
+    fn test_non_sgr_sequences_are_dropped() {
+        let html = ansi_to_html("\u{1b}]0;title\u{07}\u{1b}[2K\u{1b}[1Gprogress\r\ndone");
+        assert_eq!(html, "progress\r\ndone");
+    }
+
+    #[test]
+    fn test_unclosed_style_is_closed() {
+        let html = ansi_to_html("\u{1b}[4munderlined");
+        assert_eq!(
+            html,
+            "<span style=\"text-decoration:underline\">underlined</span>"
+        );
+    }
+
+    #[test]
+    fn test_escaped_text_inside_span() {
+        let html = ansi_to_html("\u{1b}[33m<warn>\u{1b}[39m");
+        assert_eq!(html, "<span style=\"color:#e5e510\">&lt;warn&gt;</span>");
+    }
+}
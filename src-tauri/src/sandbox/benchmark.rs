@@ -0,0 +1,226 @@
+//! Benchmark runs - execute the same code repeatedly and summarize timings
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::runtime::{ExecutionResult, ExecutionStatus, RuntimeType};
+
+/// Maximum measured iterations per benchmark
+pub const MAX_BENCHMARK_ITERATIONS: u32 = 100;
+
+/// Maximum warmup iterations per benchmark
+pub const MAX_BENCHMARK_WARMUPS: u32 = 10;
+
+/// Timing statistics over the measured iterations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkStats {
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    /// Population standard deviation
+    pub stddev_ms: f64,
+}
+
+impl BenchmarkStats {
+    /// Compute statistics from per-iteration execution times; `None` when empty
+    pub fn from_samples(samples: &[u64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().sum::<u64>() as f64 / count;
+        let variance = sorted
+            .iter()
+            .map(|&sample| (sample as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            1 => sorted[mid] as f64,
+            _ => (sorted[mid - 1] + sorted[mid]) as f64 / 2.0,
+        };
+
+        Some(Self {
+            min_ms: sorted[0],
+            max_ms: sorted[sorted.len() - 1],
+            mean_ms: mean,
+            median_ms: median,
+            stddev_ms: variance.sqrt(),
+        })
+    }
+}
+
+/// Result of a benchmark run, stored in history as a single record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRecord {
+    pub id: String,
+    pub language: String,
+    pub code: String,
+    pub runtime: RuntimeType,
+    /// Measured iterations requested
+    pub iterations: u32,
+    /// Warmup iterations run before measuring
+    pub warmups: u32,
+    /// Measured iterations that completed successfully
+    pub completed_iterations: u32,
+    /// Execution time of each completed measured iteration
+    pub samples_ms: Vec<u64>,
+    pub stats: Option<BenchmarkStats>,
+    /// Highest memory usage reported by any measured iteration
+    pub peak_memory_bytes: Option<u64>,
+    /// Why the benchmark stopped early, if it did
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BenchmarkRecord {
+    /// Whether every requested iteration completed
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none() && self.completed_iterations == self.iterations
+    }
+}
+
+/// Accumulates iteration results for a benchmark run
+pub(super) struct BenchmarkRun {
+    record: BenchmarkRecord,
+}
+
+impl BenchmarkRun {
+    pub(super) fn new(language: &str, code: &str, iterations: u32, warmups: u32) -> Self {
+        Self {
+            record: BenchmarkRecord {
+                id: uuid::Uuid::new_v4().to_string(),
+                language: language.to_string(),
+                code: code.to_string(),
+                runtime: RuntimeType::Native,
+                iterations,
+                warmups,
+                completed_iterations: 0,
+                samples_ms: Vec::with_capacity(iterations as usize),
+                stats: None,
+                peak_memory_bytes: None,
+                error: None,
+                created_at: Utc::now(),
+            },
+        }
+    }
+
+    /// Record an iteration; returns false when the benchmark must stop
+    pub(super) fn record(&mut self, result: &ExecutionResult, warmup: bool) -> bool {
+        self.record.runtime = result.runtime;
+        if !matches!(result.status, ExecutionStatus::Completed) {
+            let reason = result
+                .error
+                .clone()
+                .or_else(|| result.exit_code.map(|code| format!("exit code {}", code)))
+                .unwrap_or_else(|| format!("status {:?}", result.status));
+            self.fail(&reason, warmup);
+            return false;
+        }
+        if !warmup {
+            self.record.completed_iterations += 1;
+            self.record.samples_ms.push(result.execution_time_ms);
+            if let Some(memory) = result.memory_used_bytes {
+                let peak = self.record.peak_memory_bytes.get_or_insert(0);
+                *peak = (*peak).max(memory);
+            }
+        }
+        true
+    }
+
+    /// Stop the benchmark because the current iteration failed
+    pub(super) fn fail(&mut self, reason: &str, warmup: bool) {
+        self.record.error = Some(if warmup {
+            format!("Warmup failed: {}", reason)
+        } else {
+            format!(
+                "Iteration {} failed: {}",
+                self.record.completed_iterations + 1,
+                reason
+            )
+        });
+    }
+
+    pub(super) fn finish(mut self) -> BenchmarkRecord {
+        self.record.stats = BenchmarkStats::from_samples(&self.record.samples_ms);
+        self.record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed(time_ms: u64, memory: Option<u64>) -> ExecutionResult {
+        let mut result = ExecutionResult::success(
+            "run".to_string(),
+            String::new(),
+            String::new(),
+            0,
+            time_ms,
+            RuntimeType::Docker,
+            "python".to_string(),
+        );
+        result.memory_used_bytes = memory;
+        result
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = BenchmarkStats::from_samples(&[40, 10, 30, 20]).unwrap();
+        assert_eq!(stats.min_ms, 10);
+        assert_eq!(stats.max_ms, 40);
+        assert_eq!(stats.mean_ms, 25.0);
+        assert_eq!(stats.median_ms, 25.0);
+        assert!((stats.stddev_ms - 125f64.sqrt()).abs() < 1e-9);
+
+        let odd = BenchmarkStats::from_samples(&[7, 3, 5]).unwrap();
+        assert_eq!(odd.median_ms, 5.0);
+        assert!(BenchmarkStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_run_discards_warmups_and_tracks_peak_memory() {
+        let mut run = BenchmarkRun::new("python", "print(1)", 2, 1);
+        assert!(run.record(&completed(500, Some(4096)), true));
+        assert!(run.record(&completed(10, Some(1024)), false));
+        assert!(run.record(&completed(20, Some(2048)), false));
+
+        let record = run.finish();
+        assert!(record.is_complete());
+        assert_eq!(record.samples_ms, vec![10, 20]);
+        assert_eq!(record.peak_memory_bytes, Some(2048));
+        assert_eq!(record.runtime, RuntimeType::Docker);
+        assert_eq!(record.stats.unwrap().mean_ms, 15.0);
+    }
+
+    #[test]
+    fn test_run_stops_on_failed_iteration() {
+        let mut run = BenchmarkRun::new("python", "raise", 3, 0);
+        assert!(run.record(&completed(10, None), false));
+
+        let failed = ExecutionResult::success(
+            "run".to_string(),
+            String::new(),
+            "Traceback".to_string(),
+            1,
+            5,
+            RuntimeType::Docker,
+            "python".to_string(),
+        );
+        assert!(!run.record(&failed, false));
+
+        let record = run.finish();
+        assert!(!record.is_complete());
+        assert_eq!(record.completed_iterations, 1);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("Iteration 2 failed: exit code 1")
+        );
+        assert_eq!(record.stats.unwrap().min_ms, 10);
+    }
+}
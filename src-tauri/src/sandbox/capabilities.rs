@@ -0,0 +1,371 @@
+//! Runtime capability probing
+//!
+//! Describes what each sandbox runtime can actually enforce on this host:
+//! availability, version, rootless mode, network isolation, seccomp and memory
+//! cgroups. Container runtimes are queried through `docker info` / `podman info`;
+//! the native runtime reports its fixed (weaker) guarantees.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use super::runtime::RuntimeType;
+use super::SandboxConfig;
+
+/// Maximum time to wait for a runtime info command
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Capabilities of one sandbox runtime on this host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeDescription {
+    pub runtime: RuntimeType,
+    /// Whether the runtime is enabled in the sandbox configuration
+    pub enabled: bool,
+    /// Whether the runtime responded to probing
+    pub available: bool,
+    pub version: Option<String>,
+    /// Whether code runs without root privileges (unknown when not probed)
+    pub rootless: Option<bool>,
+    /// Whether executions can be cut off from the network
+    pub network_isolation: bool,
+    /// Whether a seccomp profile is applied to executions
+    pub seccomp: bool,
+    /// Whether memory limits are enforced through cgroups
+    pub memory_cgroup: bool,
+    /// Human-readable explanations of missing guarantees
+    pub notes: Vec<String>,
+    /// Probe failure, when the runtime is unavailable
+    pub error: Option<String>,
+}
+
+impl RuntimeDescription {
+    fn unavailable(runtime: RuntimeType, enabled: bool, error: String) -> Self {
+        Self {
+            runtime,
+            enabled,
+            available: false,
+            version: None,
+            rootless: None,
+            network_isolation: false,
+            seccomp: false,
+            memory_cgroup: false,
+            notes: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    /// Number of isolation guarantees this runtime provides
+    fn isolation_score(&self) -> u8 {
+        [
+            self.network_isolation,
+            self.seccomp,
+            self.memory_cgroup,
+            self.rootless == Some(true),
+        ]
+        .iter()
+        .filter(|supported| **supported)
+        .count() as u8
+    }
+}
+
+/// Capabilities of all sandbox runtimes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeDescriptions {
+    pub runtimes: Vec<RuntimeDescription>,
+    /// Probe timestamp (unix ms)
+    pub probed_at: i64,
+    /// Enabled and available runtime with the strongest isolation
+    pub recommended: Option<RuntimeType>,
+}
+
+/// Probe every runtime and describe its capabilities
+pub async fn describe_runtimes(config: &SandboxConfig) -> RuntimeDescriptions {
+    let (docker, podman) = tokio::join!(
+        probe_docker(config.enable_docker),
+        probe_podman(config.enable_podman)
+    );
+    let runtimes = vec![docker, podman, describe_native(config.enable_native)];
+    let recommended = recommend(&runtimes);
+
+    RuntimeDescriptions {
+        runtimes,
+        probed_at: chrono::Utc::now().timestamp_millis(),
+        recommended,
+    }
+}
+
+/// Pick the enabled, available runtime with the most guarantees; earlier entries win ties
+fn recommend(runtimes: &[RuntimeDescription]) -> Option<RuntimeType> {
+    runtimes
+        .iter()
+        .filter(|description| description.enabled && description.available)
+        .rev()
+        .max_by_key(|description| description.isolation_score())
+        .map(|description| description.runtime)
+}
+
+/// Run a runtime info command and return its JSON output
+async fn run_info(program: &str, args: &[&str]) -> Result<serde_json::Value, String> {
+    let output = timeout(PROBE_TIMEOUT, Command::new(program).args(args).output())
+        .await
+        .map_err(|_| format!("`{} info` timed out", program))?
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse {} info: {}", program, e))
+}
+
+async fn probe_docker(enabled: bool) -> RuntimeDescription {
+    match run_info("docker", &["info", "--format", "{{json .}}"]).await {
+        Ok(info) => parse_docker_info(enabled, &info),
+        Err(e) => {
+            log::debug!("Docker capability probe failed: {}", e);
+            RuntimeDescription::unavailable(RuntimeType::Docker, enabled, e)
+        }
+    }
+}
+
+async fn probe_podman(enabled: bool) -> RuntimeDescription {
+    match run_info("podman", &["info", "--format", "json"]).await {
+        Ok(info) => parse_podman_info(enabled, &info),
+        Err(e) => {
+            log::debug!("Podman capability probe failed: {}", e);
+            RuntimeDescription::unavailable(RuntimeType::Podman, enabled, e)
+        }
+    }
+}
+
+/// Describe Docker from `docker info --format '{{json .}}'`
+fn parse_docker_info(enabled: bool, info: &serde_json::Value) -> RuntimeDescription {
+    // The daemon answers `docker info` even when it reports errors (e.g. no permission)
+    if let Some(errors) = info
+        .get("ServerErrors")
+        .and_then(|v| v.as_array())
+        .filter(|errors| !errors.is_empty())
+    {
+        let message = errors
+            .iter()
+            .filter_map(|e| e.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        return RuntimeDescription::unavailable(RuntimeType::Docker, enabled, message);
+    }
+
+    let security_options: Vec<&str> = info
+        .get("SecurityOptions")
+        .and_then(|v| v.as_array())
+        .map(|options| options.iter().filter_map(|o| o.as_str()).collect())
+        .unwrap_or_default();
+    let has_option = |name: &str| {
+        security_options.iter().any(|option| {
+            option
+                .split(',')
+                .any(|part| part == format!("name={}", name))
+        })
+    };
+
+    let seccomp = has_option("seccomp");
+    let rootless = has_option("rootless");
+    let memory_cgroup = info
+        .get("MemoryLimit")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    // `--network none` relies on the built-in null driver
+    let network_isolation = info
+        .pointer("/Plugins/Network")
+        .and_then(|v| v.as_array())
+        .map(|drivers| drivers.iter().any(|d| d.as_str() == Some("null")))
+        .unwrap_or(true);
+
+    let mut notes = Vec::new();
+    if !rootless {
+        notes.push(
+            "The Docker daemon runs as root; a container escape would grant root on the host"
+                .to_string(),
+        );
+    }
+    if !seccomp {
+        notes.push("Seccomp is not enabled; system calls are not filtered".to_string());
+    }
+    if !memory_cgroup {
+        notes.push(
+            "The memory cgroup controller is unavailable; memory limits are not enforced"
+                .to_string(),
+        );
+    }
+
+    RuntimeDescription {
+        runtime: RuntimeType::Docker,
+        enabled,
+        available: true,
+        version: info
+            .get("ServerVersion")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        rootless: Some(rootless),
+        network_isolation,
+        seccomp,
+        memory_cgroup,
+        notes,
+        error: None,
+    }
+}
+
+/// Describe Podman from `podman info --format json`
+fn parse_podman_info(enabled: bool, info: &serde_json::Value) -> RuntimeDescription {
+    let rootless = info
+        .pointer("/host/security/rootless")
+        .and_then(|v| v.as_bool());
+    let seccomp = info
+        .pointer("/host/security/seccompEnabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let memory_cgroup = info
+        .pointer("/host/cgroupControllers")
+        .and_then(|v| v.as_array())
+        .map(|controllers| controllers.iter().any(|c| c.as_str() == Some("memory")))
+        .unwrap_or(false);
+
+    let mut notes = Vec::new();
+    if rootless == Some(false) {
+        notes.push(
+            "Podman runs as root; a container escape would grant root on the host".to_string(),
+        );
+    }
+    if !seccomp {
+        notes.push("Seccomp is not enabled; system calls are not filtered".to_string());
+    }
+    if !memory_cgroup {
+        notes.push(
+            "The memory cgroup controller is not delegated; memory limits are not enforced"
+                .to_string(),
+        );
+    }
+
+    RuntimeDescription {
+        runtime: RuntimeType::Podman,
+        enabled,
+        available: true,
+        version: info
+            .pointer("/version/Version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        rootless,
+        // Podman always ships `--network none`
+        network_isolation: true,
+        seccomp,
+        memory_cgroup,
+        notes,
+        error: None,
+    }
+}
+
+/// Describe the native runtime, which only provides process-level limits
+fn describe_native(enabled: bool) -> RuntimeDescription {
+    #[cfg(unix)]
+    let rootless = Some(unsafe { libc::geteuid() } != 0);
+    #[cfg(not(unix))]
+    let rootless = None;
+
+    let mut notes = vec![
+        "Code runs directly on the host with the app's permissions".to_string(),
+        "Network access cannot be blocked".to_string(),
+        "No seccomp filtering or memory cgroup; only the timeout is enforced".to_string(),
+    ];
+    if rootless == Some(false) {
+        notes.push("The app is running as root".to_string());
+    }
+
+    RuntimeDescription {
+        runtime: RuntimeType::Native,
+        enabled,
+        available: true,
+        version: Some("native-1.0".to_string()),
+        rootless,
+        network_isolation: false,
+        seccomp: false,
+        memory_cgroup: false,
+        notes,
+        error: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_info() {
+        let info = serde_json::json!({
+            "ServerVersion": "24.0.7",
+            "MemoryLimit": true,
+            "SecurityOptions": ["name=apparmor", "name=seccomp,profile=builtin", "name=cgroupns"],
+            "Plugins": { "Network": ["bridge", "host", "null"] }
+        });
+        let description = parse_docker_info(true, &info);
+        assert!(description.available);
+        assert_eq!(description.version.as_deref(), Some("24.0.7"));
+        assert_eq!(description.rootless, Some(false));
+        assert!(description.seccomp);
+        assert!(description.memory_cgroup);
+        assert!(description.network_isolation);
+        assert_eq!(description.notes.len(), 1);
+
+        let errored = serde_json::json!({ "ServerErrors": ["permission denied"] });
+        let description = parse_docker_info(true, &errored);
+        assert!(!description.available);
+        assert_eq!(description.error.as_deref(), Some("permission denied"));
+    }
+
+    #[test]
+    fn test_parse_podman_info() {
+        let info = serde_json::json!({
+            "host": {
+                "cgroupControllers": ["cpu", "pids"],
+                "security": { "rootless": true, "seccompEnabled": true }
+            },
+            "version": { "Version": "4.9.3" }
+        });
+        let description = parse_podman_info(false, &info);
+        assert!(!description.enabled);
+        assert_eq!(description.version.as_deref(), Some("4.9.3"));
+        assert_eq!(description.rootless, Some(true));
+        assert!(description.seccomp);
+        assert!(!description.memory_cgroup);
+    }
+
+    #[test]
+    fn test_recommend_prefers_strongest_enabled_runtime() {
+        let docker = parse_docker_info(
+            true,
+            &serde_json::json!({
+                "MemoryLimit": true,
+                "SecurityOptions": ["name=seccomp,profile=builtin"]
+            }),
+        );
+        let podman = parse_podman_info(
+            true,
+            &serde_json::json!({
+                "host": {
+                    "cgroupControllers": ["memory"],
+                    "security": { "rootless": true, "seccompEnabled": true }
+                }
+            }),
+        );
+        let native = describe_native(true);
+
+        let runtimes = vec![docker.clone(), podman.clone(), native.clone()];
+        assert_eq!(recommend(&runtimes), Some(RuntimeType::Podman));
+
+        let mut disabled_podman = podman;
+        disabled_podman.enabled = false;
+        let runtimes = vec![docker, disabled_podman, native.clone()];
+        assert_eq!(recommend(&runtimes), Some(RuntimeType::Docker));
+
+        assert_eq!(recommend(&[native]), Some(RuntimeType::Native));
+    }
+}
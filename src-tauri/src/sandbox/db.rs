@@ -6,9 +6,10 @@
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+use super::benchmark::{BenchmarkRecord, BenchmarkStats};
 use super::runtime::{
     ExecutionDiagnostics, ExecutionPolicySnapshot, ExecutionResult, ExecutionStatus, RuntimeType,
 };
@@ -27,8 +28,17 @@ pub enum DbError {
 
     #[error("Serialization error: {0}")]
     Serialization(String),
+
+    #[error("Incompatible import: {0}")]
+    IncompatibleImport(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
+/// Schema version written by `export_to_json`; imports must share its major version
+pub const EXPORT_SCHEMA_VERSION: &str = "1.0";
+
 /// Execution history record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionRecord {
@@ -94,13 +104,86 @@ pub struct LanguageStats {
     pub last_used: Option<DateTime<Utc>>,
 }
 
+/// How imported records are reconciled with existing data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMergeStrategy {
+    /// Keep existing records and skip imported ones with the same id
+    #[default]
+    Merge,
+    /// Replace existing records that share an id with the imported version
+    Overwrite,
+    /// Delete all existing data before importing
+    #[serde(alias = "replaceAll")]
+    ReplaceAll,
+}
+
 /// Result of a data import operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ImportResult {
     pub imported_snippets: u64,
     pub skipped_snippets: u64,
+    #[serde(default)]
+    pub imported_executions: u64,
+    #[serde(default)]
+    pub skipped_executions: u64,
+    #[serde(default)]
+    pub imported_sessions: u64,
+    #[serde(default)]
+    pub skipped_sessions: u64,
+    /// Existing records replaced under the overwrite strategy
+    #[serde(default)]
+    pub overwritten: u64,
 }
 
+/// Result of `PRAGMA integrity_check`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    /// Problems reported by SQLite, empty when the database is healthy
+    pub problems: Vec<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Rows salvaged from one table during a repair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRecovery {
+    pub table: String,
+    pub recovered: u64,
+    /// Rows that were found but could not be read or reinserted
+    pub lost: u64,
+    /// Why the table could not be read in full
+    pub error: Option<String>,
+}
+
+/// Result of a database repair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairReport {
+    /// Integrity check before the repair
+    pub before: IntegrityReport,
+    /// Whether the database was rebuilt
+    pub repaired: bool,
+    /// Where the original database was moved
+    pub backup_path: Option<String>,
+    pub tables: Vec<TableRecovery>,
+    /// Integrity check after the repair
+    pub after: IntegrityReport,
+}
+
+/// Tables copied during a repair, parents before children
+const REPAIR_TABLES: [&str; 7] = [
+    "sessions",
+    "executions",
+    "execution_tags",
+    "snippets",
+    "snippet_tags",
+    "language_stats",
+    "benchmarks",
+];
+
+/// Maximum number of problems collected by an integrity check
+const MAX_INTEGRITY_PROBLEMS: u32 = 100;
+
 /// Overall sandbox statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SandboxStats {
@@ -144,9 +227,23 @@ pub struct SnippetFilter {
     pub offset: Option<u32>,
 }
 
+/// Move a database file together with its WAL and shared-memory files
+fn move_db_files(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::rename(from, to)?;
+    for suffix in ["-wal", "-shm"] {
+        let side = PathBuf::from(format!("{}{}", from.display(), suffix));
+        if side.exists() {
+            std::fs::rename(&side, format!("{}{}", to.display(), suffix))?;
+        }
+    }
+    Ok(())
+}
+
 /// Sandbox database manager
 pub struct SandboxDb {
     conn: Mutex<Connection>,
+    /// Database file, `None` for in-memory databases
+    path: Option<PathBuf>,
 }
 
 impl SandboxDb {
@@ -168,6 +265,7 @@ impl SandboxDb {
 
         let db = Self {
             conn: Mutex::new(conn),
+            path: Some(db_path.clone()),
         };
 
         log::trace!("Initializing database schema...");
@@ -183,6 +281,7 @@ impl SandboxDb {
         let conn = Connection::open_in_memory()?;
         let db = Self {
             conn: Mutex::new(conn),
+            path: None,
         };
         db.initialize_schema()?;
         log::trace!("In-memory database created successfully");
@@ -192,7 +291,10 @@ impl SandboxDb {
     /// Initialize database schema
     fn initialize_schema(&self) -> Result<(), DbError> {
         let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        Self::apply_schema(&conn)
+    }
 
+    fn apply_schema(conn: &Connection) -> Result<(), DbError> {
         conn.execute_batch(
             r#"
             PRAGMA journal_mode=WAL;
@@ -273,6 +375,26 @@ impl SandboxDb {
                 last_used TEXT
             );
 
+            -- Benchmark runs (one record per run)
+            CREATE TABLE IF NOT EXISTS benchmarks (
+                id TEXT PRIMARY KEY,
+                language TEXT NOT NULL,
+                code TEXT NOT NULL,
+                runtime TEXT NOT NULL,
+                iterations INTEGER NOT NULL,
+                warmups INTEGER NOT NULL,
+                completed_iterations INTEGER NOT NULL,
+                samples_json TEXT NOT NULL,
+                min_ms INTEGER,
+                max_ms INTEGER,
+                mean_ms REAL,
+                median_ms REAL,
+                stddev_ms REAL,
+                peak_memory_bytes INTEGER,
+                error TEXT,
+                created_at TEXT NOT NULL
+            );
+
             -- Create indexes for better query performance
             CREATE INDEX IF NOT EXISTS idx_executions_language ON executions(language);
             CREATE INDEX IF NOT EXISTS idx_executions_status ON executions(status);
@@ -283,11 +405,13 @@ impl SandboxDb {
             CREATE INDEX IF NOT EXISTS idx_snippets_language ON snippets(language);
             CREATE INDEX IF NOT EXISTS idx_snippets_category ON snippets(category);
             CREATE INDEX IF NOT EXISTS idx_snippets_template ON snippets(is_template);
+            CREATE INDEX IF NOT EXISTS idx_benchmarks_language ON benchmarks(language);
+            CREATE INDEX IF NOT EXISTS idx_benchmarks_created ON benchmarks(created_at);
             "#,
         )?;
 
-        Self::ensure_column(&conn, "executions", "diagnostics_json", "TEXT")?;
-        Self::ensure_column(&conn, "executions", "policy_snapshot_json", "TEXT")?;
+        Self::ensure_column(conn, "executions", "diagnostics_json", "TEXT")?;
+        Self::ensure_column(conn, "executions", "policy_snapshot_json", "TEXT")?;
 
         Ok(())
     }
@@ -757,16 +881,33 @@ impl SandboxDb {
 
     /// Clear execution history (with optional filter)
     pub fn clear_executions(&self, before_date: Option<DateTime<Utc>>) -> Result<u64, DbError> {
-        log::info!("Clearing executions: before_date={:?}", before_date);
+        self.purge_executions(before_date, false)
+    }
+
+    /// Delete executions created before `before_date` (all when `None`),
+    /// including favorites when `include_favorites` is set
+    pub fn purge_executions(
+        &self,
+        before_date: Option<DateTime<Utc>>,
+        include_favorites: bool,
+    ) -> Result<u64, DbError> {
+        log::info!(
+            "Clearing executions: before_date={:?}, include_favorites={}",
+            before_date,
+            include_favorites
+        );
         let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
 
         let deleted = if let Some(date) = before_date {
             conn.execute(
-                "DELETE FROM executions WHERE created_at < ?1 AND is_favorite = 0",
-                params![date.to_rfc3339()],
+                "DELETE FROM executions WHERE created_at < ?1 AND (is_favorite = 0 OR ?2)",
+                params![date.to_rfc3339(), include_favorites],
             )?
         } else {
-            conn.execute("DELETE FROM executions WHERE is_favorite = 0", [])?
+            conn.execute(
+                "DELETE FROM executions WHERE is_favorite = 0 OR ?1",
+                params![include_favorites],
+            )?
         };
 
         log::info!("Cleared {} execution record(s)", deleted);
@@ -999,17 +1140,7 @@ impl SandboxDb {
             ],
         )?;
 
-        // Update tags
-        conn.execute(
-            "DELETE FROM snippet_tags WHERE snippet_id = ?1",
-            params![snippet.id],
-        )?;
-        for tag in &snippet.tags {
-            conn.execute(
-                "INSERT OR IGNORE INTO snippet_tags (snippet_id, tag) VALUES (?1, ?2)",
-                params![snippet.id, tag],
-            )?;
-        }
+        Self::replace_snippet_tags(&conn, &snippet.id, &snippet.tags)?;
 
         log::debug!("Snippet updated: id={}", snippet.id);
         Ok(())
@@ -1245,6 +1376,114 @@ impl SandboxDb {
         })
     }
 
+    // ==================== Benchmarks ====================
+
+    /// Save a benchmark run
+    pub fn save_benchmark(&self, record: &BenchmarkRecord) -> Result<(), DbError> {
+        log::debug!(
+            "Saving benchmark: id={}, language={}, iterations={}/{}",
+            record.id,
+            record.language,
+            record.completed_iterations,
+            record.iterations
+        );
+        let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        let samples_json = serde_json::to_string(&record.samples_ms)
+            .map_err(|e| DbError::Serialization(e.to_string()))?;
+        let stats = record.stats.as_ref();
+
+        conn.execute(
+            r#"INSERT INTO benchmarks
+               (id, language, code, runtime, iterations, warmups, completed_iterations, samples_json,
+                min_ms, max_ms, mean_ms, median_ms, stddev_ms, peak_memory_bytes, error, created_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"#,
+            params![
+                record.id,
+                record.language,
+                record.code,
+                record.runtime.to_string(),
+                record.iterations,
+                record.warmups,
+                record.completed_iterations,
+                samples_json,
+                stats.map(|s| s.min_ms as i64),
+                stats.map(|s| s.max_ms as i64),
+                stats.map(|s| s.mean_ms),
+                stats.map(|s| s.median_ms),
+                stats.map(|s| s.stddev_ms),
+                record.peak_memory_bytes.map(|v| v as i64),
+                record.error,
+                record.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List benchmark runs, newest first, optionally for a single language
+    pub fn list_benchmarks(
+        &self,
+        language: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<BenchmarkRecord>, DbError> {
+        let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            r#"SELECT id, language, code, runtime, iterations, warmups, completed_iterations,
+                      samples_json, min_ms, max_ms, mean_ms, median_ms, stddev_ms,
+                      peak_memory_bytes, error, created_at
+               FROM benchmarks
+               WHERE ?1 IS NULL OR language = ?1
+               ORDER BY created_at DESC
+               LIMIT ?2"#,
+        )?;
+        let rows = stmt.query_map(
+            params![language, limit.map(i64::from).unwrap_or(-1)],
+            |row| {
+                let runtime_str: String = row.get(3)?;
+                let samples_json: String = row.get(7)?;
+                let created_at_str: String = row.get(15)?;
+                let stats = match row.get::<_, Option<i64>>(8)? {
+                    Some(min_ms) => Some(BenchmarkStats {
+                        min_ms: min_ms as u64,
+                        max_ms: row.get::<_, i64>(9)? as u64,
+                        mean_ms: row.get(10)?,
+                        median_ms: row.get(11)?,
+                        stddev_ms: row.get(12)?,
+                    }),
+                    None => None,
+                };
+
+                Ok(BenchmarkRecord {
+                    id: row.get(0)?,
+                    language: row.get(1)?,
+                    code: row.get(2)?,
+                    runtime: match runtime_str.as_str() {
+                        "docker" => RuntimeType::Docker,
+                        "podman" => RuntimeType::Podman,
+                        _ => RuntimeType::Native,
+                    },
+                    iterations: row.get(4)?,
+                    warmups: row.get(5)?,
+                    completed_iterations: row.get(6)?,
+                    samples_ms: serde_json::from_str(&samples_json).unwrap_or_default(),
+                    stats,
+                    peak_memory_bytes: row.get::<_, Option<i64>>(13)?.map(|v| v as u64),
+                    error: row.get(14)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
     // ==================== Statistics ====================
 
     /// Get language statistics
@@ -1447,7 +1686,7 @@ impl SandboxDb {
         );
 
         let export = serde_json::json!({
-            "version": "1.0",
+            "version": EXPORT_SCHEMA_VERSION,
             "exported_at": Utc::now().to_rfc3339(),
             "executions": executions,
             "snippets": snippets,
@@ -1461,55 +1700,364 @@ impl SandboxDb {
         Ok(result)
     }
 
-    /// Import data from JSON (merges with existing data, skips duplicates)
-    pub fn import_from_json(&self, json_data: &str) -> Result<ImportResult, DbError> {
-        log::info!("Importing sandbox data from JSON ({} bytes)", json_data.len());
+    /// Import executions, snippets, sessions, and statistics from an `export_to_json` blob.
+    ///
+    /// Malformed records are skipped and counted; everything else is applied in a
+    /// single transaction.
+    pub fn import_from_json(
+        &self,
+        json_data: &str,
+        strategy: ImportMergeStrategy,
+    ) -> Result<ImportResult, DbError> {
+        log::info!(
+            "Importing sandbox data from JSON ({} bytes, strategy={:?})",
+            json_data.len(),
+            strategy
+        );
 
         let data: serde_json::Value =
             serde_json::from_str(json_data).map_err(|e| DbError::Serialization(e.to_string()))?;
+        Self::check_import_version(&data)?;
+
+        let mut result = ImportResult::default();
+        let sessions: Vec<ExecutionSession> =
+            Self::parse_import_items(&data, "sessions", &mut result.skipped_sessions);
+        let executions: Vec<ExecutionRecord> =
+            Self::parse_import_items(&data, "executions", &mut result.skipped_executions);
+        let snippets: Vec<CodeSnippet> =
+            Self::parse_import_items(&data, "snippets", &mut result.skipped_snippets);
+        let language_stats: Option<Vec<LanguageStats>> = data
+            .get("stats")
+            .and_then(|stats| stats.get("languages"))
+            .and_then(|languages| serde_json::from_value(languages.clone()).ok());
+
+        let mut conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        let tx = conn.transaction()?;
+
+        if strategy == ImportMergeStrategy::ReplaceAll {
+            log::info!("Clearing existing sandbox data before import");
+            tx.execute_batch(
+                r#"DELETE FROM execution_tags;
+                   DELETE FROM executions;
+                   DELETE FROM snippet_tags;
+                   DELETE FROM snippets;
+                   DELETE FROM sessions;
+                   DELETE FROM language_stats;"#,
+            )?;
+        }
 
-        let mut imported_snippets = 0u64;
-        let mut skipped_snippets = 0u64;
-
-        // Import snippets
-        if let Some(snippets) = data.get("snippets").and_then(|v| v.as_array()) {
-            for snippet_val in snippets {
-                let snippet: CodeSnippet = match serde_json::from_value(snippet_val.clone()) {
-                    Ok(s) => s,
-                    Err(e) => {
-                        log::warn!("Skipping invalid snippet: {}", e);
-                        skipped_snippets += 1;
-                        continue;
-                    }
-                };
+        let overwrite = strategy == ImportMergeStrategy::Overwrite;
 
-                // Check if snippet already exists
-                match self.get_snippet(&snippet.id) {
-                    Ok(Some(_)) => {
-                        log::trace!("Skipping existing snippet: {}", snippet.id);
-                        skipped_snippets += 1;
-                    }
-                    _ => {
-                        if let Err(e) = self.create_snippet(&snippet) {
-                            log::warn!("Failed to import snippet {}: {}", snippet.id, e);
-                            skipped_snippets += 1;
-                        } else {
-                            imported_snippets += 1;
-                        }
-                    }
+        // Sessions first so executions can reference them
+        for session in &sessions {
+            let exists = Self::row_exists(&tx, "sessions", &session.id)?;
+            if exists && !overwrite {
+                log::trace!("Skipping existing session: {}", session.id);
+                result.skipped_sessions += 1;
+                continue;
+            }
+            Self::upsert_session(&tx, session)?;
+            if exists {
+                result.overwritten += 1;
+            } else {
+                result.imported_sessions += 1;
+            }
+        }
+
+        for record in &executions {
+            let exists = Self::row_exists(&tx, "executions", &record.id)?;
+            if exists && !overwrite {
+                log::trace!("Skipping existing execution: {}", record.id);
+                result.skipped_executions += 1;
+                continue;
+            }
+            Self::upsert_execution_record(&tx, record)?;
+            if exists {
+                result.overwritten += 1;
+            } else {
+                // Only new executions contribute to stats; overwritten ones were already counted
+                if strategy != ImportMergeStrategy::ReplaceAll || language_stats.is_none() {
+                    Self::add_record_to_language_stats(&tx, record)?;
+                }
+                result.imported_executions += 1;
+            }
+        }
+
+        for snippet in &snippets {
+            let exists = Self::row_exists(&tx, "snippets", &snippet.id)?;
+            if exists && !overwrite {
+                log::trace!("Skipping existing snippet: {}", snippet.id);
+                result.skipped_snippets += 1;
+                continue;
+            }
+            Self::upsert_snippet(&tx, snippet)?;
+            if exists {
+                result.overwritten += 1;
+            } else {
+                result.imported_snippets += 1;
+            }
+        }
+
+        // Replace-all restores the exported statistics verbatim, since they may
+        // cover history that was cleared before the export
+        if strategy == ImportMergeStrategy::ReplaceAll {
+            if let Some(languages) = &language_stats {
+                for stats in languages {
+                    tx.execute(
+                        r#"INSERT INTO language_stats
+                           (language, total_executions, successful_executions, failed_executions,
+                            timeout_executions, total_execution_time_ms, total_memory_used_bytes, last_used)
+                           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+                        params![
+                            stats.language,
+                            stats.total_executions as i64,
+                            stats.successful_executions as i64,
+                            stats.failed_executions as i64,
+                            stats.timeout_executions as i64,
+                            stats.total_execution_time_ms as i64,
+                            stats.total_memory_used_bytes as i64,
+                            stats.last_used.map(|dt| dt.to_rfc3339()),
+                        ],
+                    )?;
                 }
             }
         }
 
+        tx.commit()?;
+
         log::info!(
-            "Import completed: {} snippets imported, {} skipped",
-            imported_snippets, skipped_snippets
+            "Import completed: executions {}/{} skipped, snippets {}/{} skipped, sessions {}/{} skipped, {} overwritten",
+            result.imported_executions,
+            result.skipped_executions,
+            result.imported_snippets,
+            result.skipped_snippets,
+            result.imported_sessions,
+            result.skipped_sessions,
+            result.overwritten
         );
 
-        Ok(ImportResult {
-            imported_snippets,
-            skipped_snippets,
-        })
+        Ok(result)
+    }
+
+    /// Refuse blobs with a different major version
+    ///
+    /// Exports written before the version field existed use the version 1 layout.
+    fn check_import_version(data: &serde_json::Value) -> Result<(), DbError> {
+        let version = match data.get("version") {
+            None | Some(serde_json::Value::Null) => "1".to_string(),
+            Some(serde_json::Value::String(version)) => version.clone(),
+            Some(serde_json::Value::Number(version)) => version.to_string(),
+            Some(other) => {
+                return Err(DbError::IncompatibleImport(format!(
+                    "invalid schema version {}",
+                    other
+                )));
+            }
+        };
+
+        let major = |v: &str| v.split('.').next().unwrap_or_default().to_string();
+        if major(&version) != major(EXPORT_SCHEMA_VERSION) {
+            return Err(DbError::IncompatibleImport(format!(
+                "schema version {} is not compatible with this app (expects {})",
+                version, EXPORT_SCHEMA_VERSION
+            )));
+        }
+        Ok(())
+    }
+
+    /// Deserialize the array under `key`, counting entries that fail to parse
+    fn parse_import_items<T: serde::de::DeserializeOwned>(
+        data: &serde_json::Value,
+        key: &str,
+        skipped: &mut u64,
+    ) -> Vec<T> {
+        let Some(items) = data.get(key).and_then(|v| v.as_array()) else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| match serde_json::from_value(item.clone()) {
+                Ok(parsed) => Some(parsed),
+                Err(e) => {
+                    log::warn!("Skipping invalid {} entry: {}", key, e);
+                    *skipped += 1;
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn row_exists(conn: &Connection, table: &str, id: &str) -> Result<bool, DbError> {
+        let sql = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1)", table);
+        let exists: bool = conn.query_row(&sql, params![id], |row| row.get(0))?;
+        Ok(exists)
+    }
+
+    /// Insert or replace a session, keeping its original timestamps and count
+    fn upsert_session(conn: &Connection, session: &ExecutionSession) -> Result<(), DbError> {
+        conn.execute(
+            r#"INSERT INTO sessions (id, name, description, created_at, updated_at, execution_count, is_active)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+               ON CONFLICT(id) DO UPDATE SET
+                   name = ?2, description = ?3, created_at = ?4, updated_at = ?5,
+                   execution_count = ?6, is_active = ?7"#,
+            params![
+                session.id,
+                session.name,
+                session.description,
+                session.created_at.to_rfc3339(),
+                session.updated_at.to_rfc3339(),
+                session.execution_count,
+                if session.is_active { 1 } else { 0 },
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Insert or replace a snippet, keeping its original timestamps and tags
+    fn upsert_snippet(conn: &Connection, snippet: &CodeSnippet) -> Result<(), DbError> {
+        conn.execute(
+            r#"INSERT INTO snippets
+               (id, title, description, language, code, category, is_template, usage_count, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+               ON CONFLICT(id) DO UPDATE SET
+                   title = ?2, description = ?3, language = ?4, code = ?5, category = ?6,
+                   is_template = ?7, usage_count = ?8, created_at = ?9, updated_at = ?10"#,
+            params![
+                snippet.id,
+                snippet.title,
+                snippet.description,
+                snippet.language,
+                snippet.code,
+                snippet.category,
+                if snippet.is_template { 1 } else { 0 },
+                snippet.usage_count,
+                snippet.created_at.to_rfc3339(),
+                snippet.updated_at.to_rfc3339(),
+            ],
+        )?;
+        Self::replace_snippet_tags(conn, &snippet.id, &snippet.tags)
+    }
+
+    fn replace_snippet_tags(conn: &Connection, id: &str, tags: &[String]) -> Result<(), DbError> {
+        conn.execute(
+            "DELETE FROM snippet_tags WHERE snippet_id = ?1",
+            params![id],
+        )?;
+        for tag in tags {
+            conn.execute(
+                "INSERT OR IGNORE INTO snippet_tags (snippet_id, tag) VALUES (?1, ?2)",
+                params![id, tag],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Insert or replace an execution record, keeping its original timestamp and tags
+    fn upsert_execution_record(conn: &Connection, record: &ExecutionRecord) -> Result<(), DbError> {
+        let status_str = serde_json::to_string(&record.status)
+            .map_err(|e| DbError::Serialization(e.to_string()))?
+            .trim_matches('"')
+            .to_string();
+        let diagnostics_json = record
+            .diagnostics
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Serialization(e.to_string()))?;
+        let policy_snapshot_json = record
+            .policy_snapshot
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DbError::Serialization(e.to_string()))?;
+
+        // Drop dangling session references rather than failing the foreign key
+        let session_id = match &record.session_id {
+            Some(sid) if Self::row_exists(conn, "sessions", sid)? => Some(sid.as_str()),
+            _ => None,
+        };
+
+        conn.execute(
+            r#"INSERT INTO executions
+               (id, session_id, language, code, stdin, stdout, stderr, exit_code,
+                status, runtime, execution_time_ms, memory_used_bytes, error, diagnostics_json, policy_snapshot_json, created_at, is_favorite)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+               ON CONFLICT(id) DO UPDATE SET
+                   session_id = ?2, language = ?3, code = ?4, stdin = ?5, stdout = ?6, stderr = ?7,
+                   exit_code = ?8, status = ?9, runtime = ?10, execution_time_ms = ?11,
+                   memory_used_bytes = ?12, error = ?13, diagnostics_json = ?14,
+                   policy_snapshot_json = ?15, created_at = ?16, is_favorite = ?17"#,
+            params![
+                record.id,
+                session_id,
+                record.language,
+                record.code,
+                record.stdin,
+                record.stdout,
+                record.stderr,
+                record.exit_code,
+                status_str,
+                record.runtime.to_string(),
+                record.execution_time_ms as i64,
+                record.memory_used_bytes.map(|v| v as i64),
+                record.error,
+                diagnostics_json,
+                policy_snapshot_json,
+                record.created_at.to_rfc3339(),
+                if record.is_favorite { 1 } else { 0 },
+            ],
+        )?;
+
+        conn.execute(
+            "DELETE FROM execution_tags WHERE execution_id = ?1",
+            params![record.id],
+        )?;
+        for tag in &record.tags {
+            conn.execute(
+                "INSERT OR IGNORE INTO execution_tags (execution_id, tag) VALUES (?1, ?2)",
+                params![record.id, tag],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Count an imported execution in the language statistics
+    fn add_record_to_language_stats(
+        conn: &Connection,
+        record: &ExecutionRecord,
+    ) -> Result<(), DbError> {
+        let (success, failed, timeout) = match record.status {
+            ExecutionStatus::Completed => (1, 0, 0),
+            ExecutionStatus::Failed => (0, 1, 0),
+            ExecutionStatus::Timeout => (0, 0, 1),
+            _ => (0, 0, 0),
+        };
+
+        conn.execute(
+            r#"INSERT INTO language_stats
+               (language, total_executions, successful_executions, failed_executions,
+                timeout_executions, total_execution_time_ms, total_memory_used_bytes, last_used)
+               VALUES (?1, 1, ?2, ?3, ?4, ?5, ?6, ?7)
+               ON CONFLICT(language) DO UPDATE SET
+                   total_executions = total_executions + 1,
+                   successful_executions = successful_executions + ?2,
+                   failed_executions = failed_executions + ?3,
+                   timeout_executions = timeout_executions + ?4,
+                   total_execution_time_ms = total_execution_time_ms + ?5,
+                   total_memory_used_bytes = total_memory_used_bytes + ?6,
+                   last_used = MAX(COALESCE(last_used, ''), ?7)"#,
+            params![
+                record.language,
+                success,
+                failed,
+                timeout,
+                record.execution_time_ms as i64,
+                record.memory_used_bytes.unwrap_or(0) as i64,
+                record.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
     }
 
     /// Get database file size
@@ -1524,6 +2072,13 @@ impl SandboxDb {
         Ok(size as u64)
     }
 
+    /// Copy the write-ahead log into the main database file and truncate it
+    pub fn checkpoint(&self) -> Result<(), DbError> {
+        let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(())
+    }
+
     /// Vacuum database to reclaim space
     pub fn vacuum(&self) -> Result<(), DbError> {
         log::info!("Vacuuming sandbox database");
@@ -1533,6 +2088,245 @@ impl SandboxDb {
         Ok(())
     }
 
+    /// Run `PRAGMA integrity_check` and collect any reported problems
+    pub fn check_integrity(&self) -> Result<IntegrityReport, DbError> {
+        let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        Ok(Self::integrity_check(&conn))
+    }
+
+    /// Integrity check that reports a failure to run the check as a problem
+    fn integrity_check(conn: &Connection) -> IntegrityReport {
+        let messages = conn
+            .prepare(&format!(
+                "PRAGMA integrity_check({})",
+                MAX_INTEGRITY_PROBLEMS
+            ))
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>, _>>()
+            });
+        let problems: Vec<String> = match messages {
+            Ok(messages) => messages.into_iter().filter(|m| m != "ok").collect(),
+            Err(e) => vec![e.to_string()],
+        };
+        if !problems.is_empty() {
+            log::warn!(
+                "Sandbox database integrity check found {} problem(s)",
+                problems.len()
+            );
+        }
+        IntegrityReport {
+            ok: problems.is_empty(),
+            problems,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Rebuild the database from whatever rows can still be read
+    ///
+    /// The original file is moved aside as a backup and a fresh database is created
+    /// in its place, then every table is copied over, row by row when a bulk copy
+    /// fails. A healthy database is left untouched unless `force` is set. If the
+    /// rebuild cannot be started the original file is restored.
+    pub fn repair(&self, force: bool) -> Result<RepairReport, DbError> {
+        let mut conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
+        let before = Self::integrity_check(&conn);
+        if before.ok && !force {
+            log::info!("Sandbox database is healthy, skipping repair");
+            return Ok(RepairReport {
+                after: before.clone(),
+                before,
+                repaired: false,
+                backup_path: None,
+                tables: Vec::new(),
+            });
+        }
+        let Some(db_path) = self.path.clone() else {
+            return Err(DbError::NotFound(
+                "In-memory databases cannot be repaired".to_string(),
+            ));
+        };
+
+        log::warn!("Repairing sandbox database at {:?}", db_path);
+        // Fold the WAL into the main file; failure is expected on a damaged database
+        if let Err(e) = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);") {
+            log::debug!("WAL checkpoint before repair failed: {}", e);
+        }
+        // Release the file so it can be moved
+        *conn = Connection::open_in_memory()?;
+
+        let backup_path = PathBuf::from(format!(
+            "{}.corrupt-{}.bak",
+            db_path.display(),
+            Utc::now().format("%Y%m%d%H%M%S")
+        ));
+        if let Err(e) = move_db_files(&db_path, &backup_path) {
+            log::error!("Failed to back up database before repair: {}", e);
+            *conn = Connection::open(&db_path)?;
+            return Err(e.into());
+        }
+
+        let rebuilt = Connection::open(&db_path)
+            .map_err(DbError::from)
+            .and_then(|fresh| {
+                Self::apply_schema(&fresh)?;
+                Ok(fresh)
+            });
+        let fresh = match rebuilt {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                log::error!(
+                    "Failed to create repaired database, restoring original: {}",
+                    e
+                );
+                let _ = std::fs::remove_file(&db_path);
+                if let Err(restore_err) = move_db_files(&backup_path, &db_path) {
+                    log::error!("Failed to restore original database: {}", restore_err);
+                }
+                *conn = Connection::open(&db_path)?;
+                return Err(e);
+            }
+        };
+
+        let tables = match Self::copy_from_backup(&fresh, &backup_path) {
+            Ok(tables) => tables,
+            Err(e) => {
+                // Keep the fresh database live; the original stays in the backup
+                log::error!("Failed to copy data from {:?}: {}", backup_path, e);
+                *conn = fresh;
+                return Err(e);
+            }
+        };
+        let after = Self::integrity_check(&fresh);
+        *conn = fresh;
+
+        let recovered: u64 = tables.iter().map(|t| t.recovered).sum();
+        let lost: u64 = tables.iter().map(|t| t.lost).sum();
+        log::info!(
+            "Sandbox database repaired: {} rows recovered, {} lost, backup at {:?}",
+            recovered,
+            lost,
+            backup_path
+        );
+        Ok(RepairReport {
+            before,
+            repaired: true,
+            backup_path: Some(backup_path.to_string_lossy().to_string()),
+            tables,
+            after,
+        })
+    }
+
+    /// Copy every known table from the backup file into `fresh`
+    fn copy_from_backup(
+        fresh: &Connection,
+        backup_path: &Path,
+    ) -> Result<Vec<TableRecovery>, DbError> {
+        fresh.execute_batch("PRAGMA foreign_keys=OFF;")?;
+        fresh.execute(
+            "ATTACH DATABASE ?1 AS old",
+            params![backup_path.to_string_lossy()],
+        )?;
+
+        let mut tables = Vec::with_capacity(REPAIR_TABLES.len());
+        for table in REPAIR_TABLES {
+            let recovery = Self::copy_table(fresh, table);
+            if recovery.lost > 0 || recovery.error.is_some() {
+                log::warn!(
+                    "Recovered {} rows of {} ({} lost): {:?}",
+                    recovery.recovered,
+                    table,
+                    recovery.lost,
+                    recovery.error
+                );
+            }
+            tables.push(recovery);
+        }
+
+        fresh.execute_batch("DETACH DATABASE old; PRAGMA foreign_keys=ON;")?;
+        Ok(tables)
+    }
+
+    /// Copy one table from the attached `old` database, falling back to row by row
+    fn copy_table(conn: &Connection, table: &str) -> TableRecovery {
+        let mut recovery = TableRecovery {
+            table: table.to_string(),
+            recovered: 0,
+            lost: 0,
+            error: None,
+        };
+
+        let columns = |schema: &str| -> Result<Vec<String>, rusqlite::Error> {
+            let mut stmt = conn.prepare(&format!("PRAGMA {}.table_info({})", schema, table))?;
+            let names = stmt
+                .query_map([], |row| row.get::<_, String>(1))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(names)
+        };
+        let shared = match (columns("main"), columns("old")) {
+            (Ok(new_cols), Ok(old_cols)) => new_cols
+                .into_iter()
+                .filter(|c| old_cols.contains(c))
+                .collect::<Vec<_>>(),
+            (_, Err(e)) | (Err(e), _) => {
+                recovery.error = Some(e.to_string());
+                return recovery;
+            }
+        };
+        if shared.is_empty() {
+            recovery.error = Some("Table missing from the original database".to_string());
+            return recovery;
+        }
+
+        let column_list = shared.join(", ");
+        let bulk = format!(
+            "INSERT OR IGNORE INTO main.{table} ({column_list}) SELECT {column_list} FROM old.{table}"
+        );
+        match conn.execute(&bulk, []) {
+            Ok(rows) => {
+                recovery.recovered = rows as u64;
+                return recovery;
+            }
+            Err(e) => log::debug!("Bulk copy of {} failed, copying row by row: {}", table, e),
+        }
+
+        // Collect readable rowids; a damaged page ends the scan early
+        let mut rowids = Vec::new();
+        match conn.prepare(&format!("SELECT rowid FROM old.{} ORDER BY rowid", table)) {
+            Ok(mut stmt) => match stmt.query([]) {
+                Ok(mut rows) => loop {
+                    match rows.next() {
+                        Ok(Some(row)) => match row.get::<_, i64>(0) {
+                            Ok(rowid) => rowids.push(rowid),
+                            Err(e) => {
+                                recovery.error = Some(e.to_string());
+                                break;
+                            }
+                        },
+                        Ok(None) => break,
+                        Err(e) => {
+                            recovery.error = Some(e.to_string());
+                            break;
+                        }
+                    }
+                },
+                Err(e) => recovery.error = Some(e.to_string()),
+            },
+            Err(e) => recovery.error = Some(e.to_string()),
+        }
+
+        let single = format!(
+            "INSERT OR IGNORE INTO main.{table} ({column_list}) SELECT {column_list} FROM old.{table} WHERE rowid = ?1"
+        );
+        for rowid in rowids {
+            match conn.execute(&single, params![rowid]) {
+                Ok(inserted) => recovery.recovered += inserted as u64,
+                Err(_) => recovery.lost += 1,
+            }
+        }
+        recovery
+    }
+
     /// Get all unique tags
     pub fn get_all_tags(&self) -> Result<Vec<String>, DbError> {
         let conn = self.conn.lock().map_err(|e| DbError::Lock(e.to_string()))?;
@@ -1598,6 +2392,8 @@ mod tests {
             language: language.to_string(),
             diagnostics: None,
             policy_snapshot: None,
+            stdout_html: None,
+            stderr_html: None,
         }
     }
 
@@ -1902,6 +2698,24 @@ mod tests {
         assert!(db.get_execution("exec-clear-0").unwrap().is_some());
     }
 
+    #[test]
+    fn test_purge_executions_including_favorites() {
+        let db = SandboxDb::in_memory().unwrap();
+
+        for i in 0..3 {
+            let result = create_test_execution_result(&format!("exec-purge-{}", i), "python", 0);
+            db.save_execution(&result, "code", None, None, &[]).unwrap();
+        }
+        db.toggle_execution_favorite("exec-purge-0").unwrap();
+
+        // Nothing is older than a cutoff in the past
+        let past = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(db.purge_executions(Some(past), true).unwrap(), 0);
+
+        assert_eq!(db.purge_executions(None, true).unwrap(), 3);
+        assert!(db.get_execution("exec-purge-0").unwrap().is_none());
+    }
+
     // ==================== Session Tests ====================
 
     #[test]
@@ -2274,6 +3088,52 @@ mod tests {
         assert!(!counts.is_empty() || counts.is_empty()); // May vary based on DB implementation
     }
 
+    // ==================== Benchmark Tests ====================
+
+    #[test]
+    fn test_save_and_list_benchmarks() {
+        let db = SandboxDb::in_memory().unwrap();
+        let mut record = BenchmarkRecord {
+            id: "bench-1".to_string(),
+            language: "python".to_string(),
+            code: "sum(range(1000))".to_string(),
+            runtime: RuntimeType::Docker,
+            iterations: 3,
+            warmups: 1,
+            completed_iterations: 3,
+            samples_ms: vec![12, 10, 14],
+            stats: BenchmarkStats::from_samples(&[12, 10, 14]),
+            peak_memory_bytes: Some(2048),
+            error: None,
+            created_at: Utc::now() - chrono::Duration::minutes(1),
+        };
+        db.save_benchmark(&record).unwrap();
+
+        record.id = "bench-2".to_string();
+        record.language = "rust".to_string();
+        record.completed_iterations = 0;
+        record.samples_ms = Vec::new();
+        record.stats = None;
+        record.peak_memory_bytes = None;
+        record.error = Some("Warmup failed: exit code 1".to_string());
+        record.created_at = Utc::now();
+        db.save_benchmark(&record).unwrap();
+
+        let all = db.list_benchmarks(None, None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, "bench-2");
+        assert!(all[0].stats.is_none());
+
+        let python = db.list_benchmarks(Some("python"), Some(10)).unwrap();
+        assert_eq!(python.len(), 1);
+        assert_eq!(python[0].samples_ms, vec![12, 10, 14]);
+        assert_eq!(python[0].stats, BenchmarkStats::from_samples(&[12, 10, 14]));
+        assert_eq!(python[0].runtime, RuntimeType::Docker);
+        assert_eq!(python[0].peak_memory_bytes, Some(2048));
+
+        assert_eq!(db.list_benchmarks(None, Some(1)).unwrap().len(), 1);
+    }
+
     // ==================== Utility Tests ====================
 
     #[test]
@@ -2345,6 +3205,100 @@ mod tests {
         db.vacuum().unwrap();
     }
 
+    fn corrupt_file(path: &Path, offset: u64) {
+        use std::io::{Seek, SeekFrom, Write};
+        let mut file = std::fs::OpenOptions::new().write(true).open(path).unwrap();
+        file.seek(SeekFrom::Start(offset)).unwrap();
+        file.write_all(&[0xFF; 64]).unwrap();
+    }
+
+    #[test]
+    fn test_check_integrity_healthy() {
+        let db = SandboxDb::in_memory().unwrap();
+        let report = db.check_integrity().unwrap();
+        assert!(report.ok);
+        assert!(report.problems.is_empty());
+
+        let repair = db.repair(false).unwrap();
+        assert!(!repair.repaired);
+        assert!(repair.backup_path.is_none());
+    }
+
+    #[test]
+    fn test_repair_rebuilds_and_keeps_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("sandbox.db");
+        let db = SandboxDb::new(db_path.clone()).unwrap();
+        for i in 0..3 {
+            let result = create_test_execution_result(&format!("exec-{}", i), "python", 0);
+            db.save_execution(&result, "print(1)", None, None, &[])
+                .unwrap();
+        }
+
+        let report = db.repair(true).unwrap();
+        assert!(report.repaired);
+        assert!(report.after.ok);
+        let backup = PathBuf::from(report.backup_path.unwrap());
+        assert!(backup.exists());
+        let executions = report
+            .tables
+            .iter()
+            .find(|t| t.table == "executions")
+            .unwrap();
+        assert_eq!(executions.recovered, 3);
+        assert_eq!(executions.lost, 0);
+
+        // The rebuilt database is live and writable
+        let stats = db.get_sandbox_stats().unwrap();
+        assert_eq!(stats.total_executions, 3);
+        let result = create_test_execution_result("exec-new", "python", 0);
+        db.save_execution(&result, "print(1)", None, None, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_integrity_check_reports_corruption() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("sandbox.db");
+        let db = SandboxDb::new(db_path.clone()).unwrap();
+        for i in 0..50 {
+            let result = create_test_execution_result(&format!("exec-{}", i), "python", 0);
+            db.save_execution(&result, "print(1)", None, None, &[])
+                .unwrap();
+        }
+        let (root_page, page_size) = {
+            let conn = db.conn.lock().unwrap();
+            conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+                .unwrap();
+            let root: u64 = conn
+                .query_row(
+                    "SELECT rootpage FROM sqlite_master WHERE name = 'executions'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            let size: u64 = conn
+                .query_row("PRAGMA page_size", [], |row| row.get(0))
+                .unwrap();
+            (root, size)
+        };
+        drop(db);
+
+        // Damage the executions table; the schema page stays intact so the file still opens
+        corrupt_file(&db_path, (root_page - 1) * page_size);
+        let db = SandboxDb::new(db_path).unwrap();
+        let report = db.check_integrity().unwrap();
+        assert!(!report.ok);
+        assert!(!report.problems.is_empty());
+
+        let repair = db.repair(false).unwrap();
+        assert!(repair.repaired);
+        assert!(!repair.before.ok);
+        assert!(repair.after.ok);
+        assert!(db.check_integrity().unwrap().ok);
+        assert!(db.list_sessions(false).unwrap().is_empty());
+    }
+
     // ==================== Error Handling Tests ====================
 
     #[test]
@@ -2476,6 +3430,7 @@ mod tests {
         let result = ImportResult {
             imported_snippets: 5,
             skipped_snippets: 2,
+            ..Default::default()
         };
         let json = serde_json::to_string(&result).unwrap();
         let parsed: ImportResult = serde_json::from_str(&json).unwrap();
@@ -2488,6 +3443,7 @@ mod tests {
         let result = ImportResult {
             imported_snippets: 3,
             skipped_snippets: 1,
+            ..Default::default()
         };
         let cloned = result.clone();
         assert_eq!(cloned.imported_snippets, 3);
@@ -2500,7 +3456,9 @@ mod tests {
     fn test_import_from_json_empty() {
         let db = SandboxDb::in_memory().unwrap();
         let json = r#"{"version": "1.0", "snippets": [], "executions": [], "sessions": []}"#;
-        let result = db.import_from_json(json).unwrap();
+        let result = db
+            .import_from_json(json, ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 0);
         assert_eq!(result.skipped_snippets, 0);
     }
@@ -2517,7 +3475,9 @@ mod tests {
             "snippets": snippets_json,
         });
 
-        let result = db.import_from_json(&import_data.to_string()).unwrap();
+        let result = db
+            .import_from_json(&import_data.to_string(), ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 1);
         assert_eq!(result.skipped_snippets, 0);
 
@@ -2542,7 +3502,9 @@ mod tests {
             "snippets": snippets_json,
         });
 
-        let result = db.import_from_json(&import_data.to_string()).unwrap();
+        let result = db
+            .import_from_json(&import_data.to_string(), ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 0);
         assert_eq!(result.skipped_snippets, 1);
     }
@@ -2563,7 +3525,9 @@ mod tests {
             "snippets": snippets_json,
         });
 
-        let result = db.import_from_json(&import_data.to_string()).unwrap();
+        let result = db
+            .import_from_json(&import_data.to_string(), ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 1);
         assert_eq!(result.skipped_snippets, 1);
 
@@ -2575,7 +3539,7 @@ mod tests {
     #[test]
     fn test_import_from_json_invalid_json() {
         let db = SandboxDb::in_memory().unwrap();
-        let result = db.import_from_json("not valid json");
+        let result = db.import_from_json("not valid json", ImportMergeStrategy::Merge);
         assert!(result.is_err());
     }
 
@@ -2583,7 +3547,9 @@ mod tests {
     fn test_import_from_json_no_snippets_key() {
         let db = SandboxDb::in_memory().unwrap();
         let json = r#"{"version": "1.0"}"#;
-        let result = db.import_from_json(json).unwrap();
+        let result = db
+            .import_from_json(json, ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 0);
         assert_eq!(result.skipped_snippets, 0);
     }
@@ -2598,7 +3564,9 @@ mod tests {
                 {"not_a_snippet": "at all"}
             ]
         }"#;
-        let result = db.import_from_json(json).unwrap();
+        let result = db
+            .import_from_json(json, ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 0);
         assert_eq!(result.skipped_snippets, 2);
     }
@@ -2616,7 +3584,9 @@ mod tests {
 
         // Import into a fresh DB
         let db2 = SandboxDb::in_memory().unwrap();
-        let result = db2.import_from_json(&exported).unwrap();
+        let result = db2
+            .import_from_json(&exported, ImportMergeStrategy::Merge)
+            .unwrap();
         assert_eq!(result.imported_snippets, 1);
 
         // Verify
@@ -2625,6 +3595,139 @@ mod tests {
         assert_eq!(retrieved.unwrap().title, snippet.title);
     }
 
+    #[test]
+    fn test_import_restores_executions_sessions_and_stats() {
+        let db = SandboxDb::in_memory().unwrap();
+        let session = db.create_session("Work", None).unwrap();
+        let result = create_test_execution_result("exec-1", "python", 0);
+        db.save_execution(
+            &result,
+            "print(1)",
+            None,
+            Some(&session.id),
+            &["demo".to_string()],
+        )
+        .unwrap();
+        db.toggle_execution_favorite("exec-1").unwrap();
+        let exported = db.export_to_json().unwrap();
+
+        let db2 = SandboxDb::in_memory().unwrap();
+        let result = db2
+            .import_from_json(&exported, ImportMergeStrategy::Merge)
+            .unwrap();
+        assert_eq!(result.imported_sessions, 1);
+        assert_eq!(result.imported_executions, 1);
+
+        let record = db2.get_execution("exec-1").unwrap().unwrap();
+        assert_eq!(record.session_id.as_deref(), Some(session.id.as_str()));
+        assert_eq!(record.tags, vec!["demo".to_string()]);
+        assert!(record.is_favorite);
+        let stats = db2.get_language_stats("python").unwrap().unwrap();
+        assert_eq!(stats.total_executions, 1);
+
+        // Importing again under merge skips everything
+        let again = db2
+            .import_from_json(&exported, ImportMergeStrategy::Merge)
+            .unwrap();
+        assert_eq!(again.imported_executions, 0);
+        assert_eq!(again.skipped_executions, 1);
+        assert_eq!(again.skipped_sessions, 1);
+        let stats = db2.get_language_stats("python").unwrap().unwrap();
+        assert_eq!(stats.total_executions, 1);
+    }
+
+    #[test]
+    fn test_import_overwrite_replaces_matching_ids() {
+        let db = SandboxDb::in_memory().unwrap();
+        let mut snippet = create_test_snippet("ow-s1", "python");
+        db.create_snippet(&snippet).unwrap();
+
+        snippet.title = "Renamed".to_string();
+        let import_data = serde_json::json!({
+            "version": "1.0",
+            "snippets": [snippet],
+        });
+
+        let result = db
+            .import_from_json(&import_data.to_string(), ImportMergeStrategy::Overwrite)
+            .unwrap();
+        assert_eq!(result.overwritten, 1);
+        assert_eq!(result.imported_snippets, 0);
+        assert_eq!(db.get_snippet("ow-s1").unwrap().unwrap().title, "Renamed");
+    }
+
+    #[test]
+    fn test_import_replace_all_clears_existing_data() {
+        let source = SandboxDb::in_memory().unwrap();
+        source
+            .save_execution(
+                &create_test_execution_result("keep", "rust", 0),
+                "fn main() {}",
+                None,
+                None,
+                &[],
+            )
+            .unwrap();
+        source
+            .save_execution(
+                &create_test_execution_result("cleared", "rust", 1),
+                "fn main() {}",
+                None,
+                None,
+                &[],
+            )
+            .unwrap();
+        // History cleared after the stats were recorded
+        source.delete_execution("cleared").unwrap();
+        let exported = source.export_to_json().unwrap();
+
+        let db = SandboxDb::in_memory().unwrap();
+        db.create_snippet(&create_test_snippet("old-s1", "python"))
+            .unwrap();
+
+        let result = db
+            .import_from_json(&exported, ImportMergeStrategy::ReplaceAll)
+            .unwrap();
+        assert_eq!(result.imported_executions, 1);
+        assert!(db.get_snippet("old-s1").unwrap().is_none());
+        assert!(db.get_execution("keep").unwrap().is_some());
+
+        // Exported statistics are restored as-is
+        let stats = db.get_language_stats("rust").unwrap().unwrap();
+        assert_eq!(stats.total_executions, 2);
+    }
+
+    #[test]
+    fn test_import_rejects_incompatible_version() {
+        let db = SandboxDb::in_memory().unwrap();
+
+        // Exports without a version predate the field and use the version 1 layout
+        assert!(db
+            .import_from_json(r#"{"snippets": []}"#, ImportMergeStrategy::Merge)
+            .is_ok());
+
+        let newer = db.import_from_json(
+            r#"{"version": "2.0", "snippets": []}"#,
+            ImportMergeStrategy::Merge,
+        );
+        let err = newer.unwrap_err();
+        assert!(matches!(err, DbError::IncompatibleImport(_)));
+        assert!(err.to_string().contains("2.0"));
+
+        assert!(db
+            .import_from_json(r#"{"version": "1.3"}"#, ImportMergeStrategy::Merge)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_import_merge_strategy_deserialization() {
+        let strategy: ImportMergeStrategy = serde_json::from_str("\"replaceAll\"").unwrap();
+        assert_eq!(strategy, ImportMergeStrategy::ReplaceAll);
+        let strategy: ImportMergeStrategy = serde_json::from_str("\"overwrite\"").unwrap();
+        assert_eq!(strategy, ImportMergeStrategy::Overwrite);
+        assert_eq!(ImportMergeStrategy::default(), ImportMergeStrategy::Merge);
+    }
+
     #[test]
     fn test_wal_mode_enabled() {
         let db = SandboxDb::in_memory().unwrap();
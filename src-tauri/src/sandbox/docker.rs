@@ -450,11 +450,11 @@ impl SandboxRuntime for DockerRuntime {
             collected
         });
 
-        let result = timeout(exec_config.timeout, child.wait()).await;
+        let result = exec_config.run_within_limit(child.wait()).await;
         let execution_time_ms = start.elapsed().as_millis() as u64;
 
         match result {
-            Ok(Ok(status)) => {
+            Some(Ok(status)) => {
                 let exit_code = status.code().unwrap_or(-1);
                 let stdout = stdout_task.await.unwrap_or_default();
                 let stderr = stderr_task.await.unwrap_or_default();
@@ -463,12 +463,12 @@ impl SandboxRuntime for DockerRuntime {
                     execution_time_ms, RuntimeType::Docker, request.language.clone(),
                 ))
             }
-            Ok(Err(e)) => {
+            Some(Err(e)) => {
                 let _ = stdout_task.await;
                 let _ = stderr_task.await;
                 Err(SandboxError::ExecutionFailed(e.to_string()))
             }
-            Err(_) => {
+            None => {
                 let container_name = Self::container_name(request);
                 let _ = Command::new(&self.docker_path).arg("kill").arg(&container_name).output().await;
                 let _ = Command::new(&self.docker_path).args(["rm", "-f"]).arg(&container_name).output().await;
@@ -478,7 +478,7 @@ impl SandboxRuntime for DockerRuntime {
                 let _ = stderr_task.await;
                 Ok(ExecutionResult::timeout(
                     request.id.clone(), String::new(), String::new(),
-                    exec_config.timeout.as_secs(), RuntimeType::Docker, request.language.clone(),
+                    exec_config.effective_timeout().as_secs(), RuntimeType::Docker, request.language.clone(),
                 ))
             }
         }
@@ -617,6 +617,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -643,6 +644,7 @@ mod tests {
             network_enabled: true,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -669,6 +671,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -693,6 +696,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -717,6 +721,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -826,6 +831,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -857,6 +863,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -886,6 +893,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
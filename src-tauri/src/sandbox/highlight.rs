@@ -0,0 +1,243 @@
+//! Syntax highlighting token stream
+//!
+//! Parses code with syntect's bundled grammars and maps the resulting scopes
+//! onto a small set of token kinds, so the UI can render snippets and history
+//! entries consistently without shipping a highlighter per language.
+//! Unsupported languages yield a single plain token.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use syntect::easy::ScopeRegionIterator;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+use super::languages::get_language_config;
+
+/// Inputs larger than this are returned as a single plain token
+const MAX_HIGHLIGHT_BYTES: usize = 512 * 1024;
+
+/// Languages without a bundled grammar, highlighted with a close relative
+/// (language id, syntect file extension)
+const GRAMMAR_FALLBACKS: &[(&str, &str)] = &[("typescript", "js"), ("kotlin", "java")];
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+
+/// Scope prefixes in the order they are checked against the innermost scope
+static SCOPE_KINDS: Lazy<Vec<(Scope, TokenKind)>> = Lazy::new(|| {
+    [
+        ("constant.numeric", TokenKind::Number),
+        ("constant.language", TokenKind::Literal),
+        ("keyword.operator", TokenKind::Operator),
+        ("keyword", TokenKind::Keyword),
+        ("storage", TokenKind::Keyword),
+        ("entity.name.function", TokenKind::Function),
+        ("support.function", TokenKind::Function),
+        ("variable.function", TokenKind::Function),
+        ("entity.name", TokenKind::Type),
+        ("support.type", TokenKind::Type),
+        ("support.class", TokenKind::Type),
+        ("punctuation", TokenKind::Punctuation),
+    ]
+    .into_iter()
+    .map(|(prefix, kind)| (Scope::new(prefix).expect("valid scope"), kind))
+    .collect()
+});
+
+static COMMENT_SCOPE: Lazy<Scope> = Lazy::new(|| Scope::new("comment").expect("valid scope"));
+static STRING_SCOPE: Lazy<Scope> = Lazy::new(|| Scope::new("string").expect("valid scope"));
+
+/// Token classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    /// Built-in constants such as `true`, `None` or `nil`
+    Literal,
+    String,
+    Number,
+    Comment,
+    /// Function names at definitions and call sites
+    Function,
+    /// Type and class names
+    Type,
+    Operator,
+    Punctuation,
+}
+
+/// A run of source text with a single classification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightToken {
+    pub text: String,
+    pub kind: TokenKind,
+}
+
+fn get_highlight_syntax(language: &str) -> Option<&'static SyntaxReference> {
+    let config = get_language_config(language)?;
+    let extension = GRAMMAR_FALLBACKS
+        .iter()
+        .find(|(id, _)| *id == config.id)
+        .map_or(config.extension, |(_, extension)| *extension);
+    SYNTAX_SET.find_syntax_by_extension(extension)
+}
+
+/// Split `code` into highlighted tokens.
+///
+/// Unsupported languages and oversized inputs produce a single plain token.
+pub fn highlight(language: &str, code: &str) -> Vec<HighlightToken> {
+    if code.is_empty() {
+        return Vec::new();
+    }
+    get_highlight_syntax(language)
+        .filter(|_| code.len() <= MAX_HIGHLIGHT_BYTES)
+        .and_then(|syntax| tokenize(syntax, code))
+        .unwrap_or_else(|| {
+            vec![HighlightToken {
+                text: code.to_string(),
+                kind: TokenKind::Plain,
+            }]
+        })
+}
+
+/// Parse `code` line by line, merging adjacent regions of the same kind.
+///
+/// Returns `None` if the grammar fails to parse the input.
+fn tokenize(syntax: &SyntaxReference, code: &str) -> Option<Vec<HighlightToken>> {
+    let mut state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+    let mut tokens: Vec<HighlightToken> = Vec::new();
+
+    for line in LinesWithEndings::from(code) {
+        let ops = state.parse_line(line, &SYNTAX_SET).ok()?;
+        for (text, op) in ScopeRegionIterator::new(&ops, line) {
+            stack.apply(op).ok()?;
+            if text.is_empty() {
+                continue;
+            }
+            let kind = classify(&stack);
+            match tokens.last_mut() {
+                Some(last) if last.kind == kind => last.text.push_str(text),
+                _ => tokens.push(HighlightToken {
+                    text: text.to_string(),
+                    kind,
+                }),
+            }
+        }
+    }
+    Some(tokens)
+}
+
+/// Map a scope stack to a token kind.
+///
+/// Comments and strings win wherever they appear in the stack so their
+/// delimiters and escapes stay part of the same token; everything else is
+/// decided by the innermost scope that matches a known prefix.
+fn classify(stack: &ScopeStack) -> TokenKind {
+    let scopes = stack.as_slice();
+    if scopes
+        .iter()
+        .any(|scope| COMMENT_SCOPE.is_prefix_of(*scope))
+    {
+        return TokenKind::Comment;
+    }
+    if scopes.iter().any(|scope| STRING_SCOPE.is_prefix_of(*scope)) {
+        return TokenKind::String;
+    }
+    scopes
+        .iter()
+        .rev()
+        .find_map(|scope| {
+            SCOPE_KINDS
+                .iter()
+                .find(|(prefix, _)| prefix.is_prefix_of(*scope))
+                .map(|(_, kind)| *kind)
+        })
+        .unwrap_or(TokenKind::Plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(tokens: &[HighlightToken]) -> Vec<(&str, TokenKind)> {
+        tokens
+            .iter()
+            .filter(|token| !token.text.trim().is_empty())
+            .map(|token| (token.text.as_str(), token.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens_round_trip_source() {
+        let code = "def greet(name):\n    # say hi\n    return f\"hi {name}\" * 2\n";
+        let tokens = highlight("python", code);
+        let rebuilt: String = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(rebuilt, code);
+    }
+
+    #[test]
+    fn test_highlight_python() {
+        let tokens = highlight("py", "def greet(name):\n    return None  # done");
+        let kinds = kinds(&tokens);
+        assert!(kinds.contains(&("def", TokenKind::Keyword)));
+        assert!(kinds.contains(&("greet", TokenKind::Function)));
+        assert!(kinds.contains(&("None", TokenKind::Literal)));
+        assert!(kinds.contains(&("# done", TokenKind::Comment)));
+    }
+
+    #[test]
+    fn test_highlight_strings_and_numbers() {
+        let tokens = highlight("javascript", "const s = 'a\\'b'; let n = 3.14;");
+        let kinds = kinds(&tokens);
+        assert!(kinds.contains(&("'a\\'b'", TokenKind::String)));
+        assert!(kinds.contains(&("3.14", TokenKind::Number)));
+        assert!(kinds.contains(&("=", TokenKind::Operator)));
+    }
+
+    #[test]
+    fn test_highlight_block_comment_and_python_docstring() {
+        let js = highlight("ts", "/* a\nb */ x");
+        assert_eq!(js[0].text, "/* a\nb */");
+        assert_eq!(js[0].kind, TokenKind::Comment);
+
+        let py = highlight("python", "s = \"\"\"doc\nstring\"\"\"");
+        assert!(kinds(&py).contains(&("\"\"\"doc\nstring\"\"\"", TokenKind::String)));
+    }
+
+    #[test]
+    fn test_rust_lifetimes_are_not_strings() {
+        let tokens = highlight("rust", "fn f<'a>(x: &'a str) -> char { 'x' }");
+        let kinds = kinds(&tokens);
+        let strings: Vec<&str> = kinds
+            .iter()
+            .filter(|(_, kind)| *kind == TokenKind::String)
+            .map(|(text, _)| *text)
+            .collect();
+        assert_eq!(strings, vec!["'x'"]);
+        assert!(kinds.contains(&("fn", TokenKind::Keyword)));
+    }
+
+    #[test]
+    fn test_rust_enum_variants_are_not_literals() {
+        let tokens = highlight("rust", "let x = Some(1).ok_or(Err(true));");
+        let literals: Vec<&str> = kinds(&tokens)
+            .iter()
+            .filter(|(_, kind)| *kind == TokenKind::Literal)
+            .map(|(text, _)| *text)
+            .collect();
+        assert_eq!(literals, vec!["true"]);
+    }
+
+    #[test]
+    fn test_unsupported_language_returns_plain_token() {
+        let tokens = highlight("brainfuck", "++[>+<-]");
+        assert_eq!(
+            tokens,
+            vec![HighlightToken {
+                text: "++[>+<-]".to_string(),
+                kind: TokenKind::Plain,
+            }]
+        );
+        assert!(highlight("python", "").is_empty());
+    }
+}
@@ -6,32 +6,45 @@
 //! - Podman containers (rootless alternative)
 //! - Native process execution (fallback with limited isolation)
 
+mod ansi;
+mod benchmark;
+mod capabilities;
 mod db;
 mod docker;
+mod highlight;
 mod languages;
 mod native;
 mod podman;
 mod runtime;
+mod syntax;
 mod workspace;
 
+pub use benchmark::{
+    BenchmarkRecord, BenchmarkStats, MAX_BENCHMARK_ITERATIONS, MAX_BENCHMARK_WARMUPS,
+};
+pub use capabilities::{RuntimeDescription, RuntimeDescriptions};
 pub use db::{
-    CodeSnippet, ExecutionFilter, ExecutionRecord, ExecutionSession, ImportResult, LanguageStats,
-    SandboxDb, SandboxStats, SnippetFilter,
+    CodeSnippet, ExecutionFilter, ExecutionRecord, ExecutionSession, ImportMergeStrategy,
+    ImportResult, IntegrityReport, LanguageStats, RepairReport, SandboxDb, SandboxStats,
+    SnippetFilter, TableRecovery,
 };
 pub use docker::DockerRuntime;
-pub use languages::{Language, LANGUAGE_CONFIGS};
+pub use highlight::{highlight, HighlightToken};
+pub use languages::{get_language_config, Language, LANGUAGE_CONFIGS};
 pub use native::NativeRuntime;
 pub use podman::PodmanRuntime;
 pub use runtime::{
-    CompilerSettings, DiagnosticsCategory, ExecutionDiagnostics, ExecutionRequest, ExecutionResult,
-    ExecutionStatus, OutputLine, RuntimeType, SandboxError, SandboxManager,
-    SandboxPreflightResult, SandboxRuntime,
+    CompilerSettings, DiagnosticsCategory, ExecutionDeadline, ExecutionDiagnostics,
+    ExecutionRequest, ExecutionResult, ExecutionStatus, OutputLine, RuntimeType, SandboxError,
+    SandboxManager, SandboxPreflightResult, SandboxRuntime, TimeoutWarning,
 };
+pub use syntax::SyntaxCheckResult;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
@@ -47,6 +60,77 @@ pub const DEFAULT_CPU_LIMIT_PERCENT: u64 = 50;
 /// Maximum output size in bytes
 pub const DEFAULT_MAX_OUTPUT_SIZE: usize = 1024 * 1024; // 1MB
 
+/// Fraction of the time limit after which streaming executions get a timeout warning
+pub const DEFAULT_TIMEOUT_WARNING_FRACTION: f64 = 0.8;
+
+fn default_timeout_warning_fraction() -> f64 {
+    DEFAULT_TIMEOUT_WARNING_FRACTION
+}
+
+/// Whether `fraction` is a usable timeout warning fraction, in (0, 1]
+pub fn is_valid_timeout_warning_fraction(fraction: f64) -> bool {
+    fraction > 0.0 && fraction <= 1.0
+}
+
+fn deserialize_timeout_warning_fraction<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let fraction = f64::deserialize(deserializer)?;
+    if !is_valid_timeout_warning_fraction(fraction) {
+        return Err(serde::de::Error::custom(format!(
+            "timeout_warning_fraction must be greater than 0 and at most 1, got {}",
+            fraction
+        )));
+    }
+    Ok(fraction)
+}
+
+/// Free memory below which the resource guard holds back executions (default)
+pub const DEFAULT_MIN_FREE_MEMORY_MB: u64 = 512;
+
+/// How long a queued execution waits for memory before giving up (default)
+pub const DEFAULT_RESOURCE_QUEUE_WAIT_SECS: u64 = 60;
+
+/// What the resource guard does with an execution while memory is low
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceGuardAction {
+    /// Fail immediately with `SandboxError::ResourceExhausted`
+    #[default]
+    Refuse,
+    /// Wait for memory to free up, failing after `max_queue_wait_secs`
+    Queue,
+}
+
+/// Guard that keeps executions from starting while the system is low on memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceGuardConfig {
+    /// Check system memory before each execution
+    pub enabled: bool,
+    /// Minimum free system memory in MB required to start an execution
+    pub min_free_memory_mb: u64,
+    /// Refuse or queue executions while memory is low
+    pub action: ResourceGuardAction,
+    /// Longest time a queued execution waits for memory
+    pub max_queue_wait_secs: u64,
+}
+
+impl Default for ResourceGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_free_memory_mb: DEFAULT_MIN_FREE_MEMORY_MB,
+            action: ResourceGuardAction::Refuse,
+            max_queue_wait_secs: DEFAULT_RESOURCE_QUEUE_WAIT_SECS,
+        }
+    }
+}
+
+/// Upper bound on the hard limit of an execution after extensions
+pub const MAX_EXTENDED_TIMEOUT_SECS: u64 = 3600;
+
 /// Sandbox configuration stored in app data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SandboxConfig {
@@ -85,6 +169,22 @@ pub struct SandboxConfig {
 
     /// Enabled languages
     pub enabled_languages: Vec<String>,
+
+    /// Fraction of the time limit (0-1] at which streaming executions emit a timeout warning
+    #[serde(
+        default = "default_timeout_warning_fraction",
+        deserialize_with = "deserialize_timeout_warning_fraction"
+    )]
+    pub timeout_warning_fraction: f64,
+
+    /// Global shortcut that runs the current text selection (e.g. "CommandOrControl+Alt+R");
+    /// unbound by default so selected text never executes by accident
+    #[serde(default)]
+    pub execute_selection_shortcut: Option<String>,
+
+    /// Refuse or queue executions while free system memory is low
+    #[serde(default)]
+    pub resource_guard: ResourceGuardConfig,
 }
 
 impl Default for SandboxConfig {
@@ -128,7 +228,33 @@ impl Default for SandboxConfig {
                 "csharp".to_string(),
                 "zig".to_string(),
             ],
+            timeout_warning_fraction: DEFAULT_TIMEOUT_WARNING_FRACTION,
+            execute_selection_shortcut: None,
+            resource_guard: ResourceGuardConfig::default(),
+        }
+    }
+}
+
+/// Send a warning each time an execution crosses `fraction` of its current
+/// time limit; an extension re-arms the warning against the new limit.
+async fn send_timeout_warnings(
+    execution_id: String,
+    deadline: ExecutionDeadline,
+    fraction: f64,
+    tx: tokio::sync::mpsc::Sender<TimeoutWarning>,
+) {
+    loop {
+        deadline.reached_fraction(fraction).await;
+        let limit = deadline.limit();
+        let warning = TimeoutWarning {
+            execution_id: execution_id.clone(),
+            elapsed_secs: deadline.elapsed().as_secs(),
+            timeout_secs: limit.as_secs(),
+        };
+        if tx.send(warning).await.is_err() {
+            return;
         }
+        deadline.extended_beyond(limit).await;
     }
 }
 
@@ -149,6 +275,12 @@ pub struct SandboxState {
     /// Active executions tracked for cancellation (execution_id -> CancellationToken)
     pub active_executions: Arc<RwLock<HashMap<String, CancellationToken>>>,
 
+    /// Hard-limit deadlines of active streaming executions (execution_id -> deadline)
+    pub active_deadlines: Arc<RwLock<HashMap<String, ExecutionDeadline>>>,
+
+    /// Cached runtime capability probe, cleared when the configuration changes
+    runtime_descriptions: Arc<RwLock<Option<RuntimeDescriptions>>>,
+
     /// Config file path
     config_path: PathBuf,
 }
@@ -210,6 +342,8 @@ impl SandboxState {
             db: Arc::new(db),
             current_session: Arc::new(RwLock::new(None)),
             active_executions: Arc::new(RwLock::new(HashMap::new())),
+            active_deadlines: Arc::new(RwLock::new(HashMap::new())),
+            runtime_descriptions: Arc::new(RwLock::new(None)),
             config_path,
         })
     }
@@ -302,6 +436,13 @@ impl SandboxState {
             new_config.network_enabled
         );
 
+        if !is_valid_timeout_warning_fraction(new_config.timeout_warning_fraction) {
+            return Err(SandboxError::Config(format!(
+                "timeout_warning_fraction must be greater than 0 and at most 1, got {}",
+                new_config.timeout_warning_fraction
+            )));
+        }
+
         // Validate and warm runtime availability with the new configuration before swapping state.
         let new_manager = SandboxManager::new(new_config.clone()).await?;
 
@@ -316,6 +457,7 @@ impl SandboxState {
             let mut config = self.config.write().await;
             *config = new_config;
         }
+        *self.runtime_descriptions.write().await = None;
 
         log::info!("Sandbox configuration applied successfully");
         Ok(())
@@ -405,6 +547,8 @@ impl SandboxState {
                         remediation_hint: None,
                     }),
                     policy_snapshot: None,
+                    stdout_html: None,
+                    stderr_html: None,
                 })
             }
         };
@@ -456,6 +600,88 @@ impl SandboxState {
         Ok(result)
     }
 
+    /// Run the same code `warmups + iterations` times and summarize the measured runs.
+    ///
+    /// Warmup runs are discarded. The benchmark stops at the first failed run and
+    /// returns the statistics gathered so far. Individual runs are not added to the
+    /// execution history; when `save_to_history` is set the run is stored as one
+    /// benchmark record instead.
+    pub async fn benchmark(
+        &self,
+        request: ExecutionRequest,
+        iterations: u32,
+        warmups: u32,
+        save_to_history: bool,
+    ) -> Result<BenchmarkRecord, SandboxError> {
+        if iterations == 0 || iterations > MAX_BENCHMARK_ITERATIONS {
+            return Err(SandboxError::ResourceLimit(format!(
+                "Benchmark iterations must be between 1 and {}",
+                MAX_BENCHMARK_ITERATIONS
+            )));
+        }
+        if warmups > MAX_BENCHMARK_WARMUPS {
+            return Err(SandboxError::ResourceLimit(format!(
+                "Benchmark warmups must be at most {}",
+                MAX_BENCHMARK_WARMUPS
+            )));
+        }
+
+        log::info!(
+            "Benchmarking code: language={}, iterations={}, warmups={}",
+            request.language,
+            iterations,
+            warmups
+        );
+
+        let mut run =
+            benchmark::BenchmarkRun::new(&request.language, &request.code, iterations, warmups);
+        for index in 0..warmups + iterations {
+            let warmup = index < warmups;
+            let iteration = ExecutionRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                ..request.clone()
+            };
+            let keep_going = match self.execute_with_history(iteration, &[], false).await {
+                Ok(result) => run.record(&result, warmup),
+                Err(e) => {
+                    run.fail(&e.to_string(), warmup);
+                    false
+                }
+            };
+            if !keep_going {
+                break;
+            }
+        }
+
+        let record = run.finish();
+        log::info!(
+            "Benchmark finished: id={}, completed={}/{}, error={:?}",
+            record.id,
+            record.completed_iterations,
+            record.iterations,
+            record.error
+        );
+
+        if save_to_history {
+            if let Err(e) = self.db.save_benchmark(&record) {
+                log::warn!("Failed to save benchmark to history: {}", e);
+            }
+        }
+
+        Ok(record)
+    }
+
+    /// List saved benchmark runs, newest first
+    pub async fn list_benchmarks(
+        &self,
+        language: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<BenchmarkRecord>, SandboxError> {
+        self.db
+            .list_benchmarks(language, limit)
+            .map_err(|e| SandboxError::Config(format!("Failed to list benchmarks: {}", e)))
+    }
+
     /// Cancel a running execution by ID
     pub async fn cancel_execution(&self, execution_id: &str) -> Result<bool, SandboxError> {
         log::info!("Cancelling execution: id={}", execution_id);
@@ -496,12 +722,58 @@ impl SandboxState {
         }
     }
 
+    /// Extend the hard time limit of an in-flight streaming execution.
+    /// Returns the new limit in seconds, or `None` if the execution is not
+    /// running or has already hit its limit.
+    pub async fn extend_timeout(
+        &self,
+        execution_id: &str,
+        extra_secs: u64,
+    ) -> Result<Option<u64>, SandboxError> {
+        if extra_secs == 0 {
+            return Err(SandboxError::Config(
+                "extra_secs must be greater than 0".to_string(),
+            ));
+        }
+
+        let active = self.active_deadlines.read().await;
+        let Some(deadline) = active.get(execution_id) else {
+            log::warn!(
+                "No active streaming execution found with id={}",
+                execution_id
+            );
+            return Ok(None);
+        };
+        let limit = deadline.limit();
+        if deadline.elapsed() >= limit {
+            log::info!("Execution already hit its time limit: id={}", execution_id);
+            return Ok(None);
+        }
+        if limit.as_secs().saturating_add(extra_secs) > MAX_EXTENDED_TIMEOUT_SECS {
+            return Err(SandboxError::ResourceLimit(format!(
+                "Timeout can be extended to at most {} seconds",
+                MAX_EXTENDED_TIMEOUT_SECS
+            )));
+        }
+
+        let new_limit = deadline.extend(Duration::from_secs(extra_secs)).as_secs();
+        log::info!(
+            "Extended execution timeout: id={}, timeout={}s",
+            execution_id,
+            new_limit
+        );
+        Ok(Some(new_limit))
+    }
+
     /// Execute code with streaming output, returning the final result
-    /// The caller provides an mpsc sender to receive OutputLine events.
+    /// The caller provides an mpsc sender to receive OutputLine events, and
+    /// optionally one to receive TimeoutWarning events once the execution
+    /// crosses the configured fraction of its time limit.
     pub async fn execute_streaming(
         &self,
         request: ExecutionRequest,
         output_tx: tokio::sync::mpsc::Sender<OutputLine>,
+        warning_tx: Option<tokio::sync::mpsc::Sender<TimeoutWarning>>,
     ) -> Result<ExecutionResult, SandboxError> {
         log::info!(
             "Streaming execute: language={}, id={}",
@@ -514,15 +786,37 @@ impl SandboxState {
         let execution_id = request.id.clone();
         let language = request.language.clone();
 
-        // Register cancellation token
+        let (timeout_secs, warning_fraction) = {
+            let config = self.config.read().await;
+            (
+                request.timeout_secs.unwrap_or(config.default_timeout_secs),
+                config.timeout_warning_fraction,
+            )
+        };
+        let deadline = ExecutionDeadline::new(Duration::from_secs(timeout_secs));
+
+        // Register cancellation token and deadline
         let cancel_token = CancellationToken::new();
         {
             let mut active = self.active_executions.write().await;
             active.insert(execution_id.clone(), cancel_token.clone());
         }
+        {
+            let mut deadlines = self.active_deadlines.write().await;
+            deadlines.insert(execution_id.clone(), deadline.clone());
+        }
+
+        let warning_task = warning_tx.map(|tx| {
+            tokio::spawn(send_timeout_warnings(
+                execution_id.clone(),
+                deadline.clone(),
+                warning_fraction,
+                tx,
+            ))
+        });
 
         let result = tokio::select! {
-            exec_result = manager.execute_streaming(request, output_tx) => exec_result,
+            exec_result = manager.execute_streaming(request, output_tx, Some(deadline)) => exec_result,
             _ = cancel_token.cancelled() => {
                 log::info!("Streaming execution cancelled: id={}", execution_id);
                 Ok(ExecutionResult {
@@ -543,14 +837,23 @@ impl SandboxState {
                         remediation_hint: None,
                     }),
                     policy_snapshot: None,
+                    stdout_html: None,
+                    stderr_html: None,
                 })
             }
         };
 
+        if let Some(task) = warning_task {
+            task.abort();
+        }
         {
             let mut active = self.active_executions.write().await;
             active.remove(&execution_id);
         }
+        {
+            let mut deadlines = self.active_deadlines.write().await;
+            deadlines.remove(&execution_id);
+        }
 
         let result = result?;
 
@@ -618,6 +921,30 @@ impl SandboxState {
         manager.get_runtime_info(runtime).await
     }
 
+    /// Describe runtime capabilities, probing only when nothing is cached or `refresh` is set
+    pub async fn describe_runtimes(&self, refresh: bool) -> RuntimeDescriptions {
+        if !refresh {
+            if let Some(cached) = self.runtime_descriptions.read().await.clone() {
+                return cached;
+            }
+        }
+
+        let config = self.config.read().await.clone();
+        let descriptions = capabilities::describe_runtimes(&config).await;
+        *self.runtime_descriptions.write().await = Some(descriptions.clone());
+        descriptions
+    }
+
+    /// Persist pending database writes before the app exits
+    pub fn shutdown(&self) -> Result<(), SandboxError> {
+        self.db.checkpoint().map_err(|e| {
+            log::error!("Failed to checkpoint sandbox database: {}", e);
+            SandboxError::Config(format!("Failed to checkpoint database: {}", e))
+        })?;
+        log::debug!("Sandbox database checkpointed");
+        Ok(())
+    }
+
     /// Cleanup all runtimes
     pub async fn cleanup_all(&self) -> Result<(), SandboxError> {
         log::info!("Cleaning up all sandbox runtimes");
@@ -642,6 +969,16 @@ impl SandboxState {
         result
     }
 
+    /// Check code for syntax/type errors without executing it
+    pub async fn check_syntax(
+        &self,
+        language: &str,
+        code: String,
+    ) -> Result<SyntaxCheckResult, SandboxError> {
+        let manager = self.manager.read().await;
+        manager.check_syntax(language, code).await
+    }
+
     /// Execute code with specific timeout and memory limits
     pub async fn execute_with_limits(
         &self,
@@ -868,6 +1205,20 @@ impl SandboxState {
         Ok(deleted)
     }
 
+    /// Delete execution history, including favorites when `force` is set
+    pub async fn purge_execution_history(
+        &self,
+        before_date: Option<chrono::DateTime<chrono::Utc>>,
+        force: bool,
+    ) -> Result<u64, SandboxError> {
+        let deleted = self
+            .db
+            .purge_executions(before_date, force)
+            .map_err(|e| SandboxError::Config(format!("Failed to purge history: {}", e)))?;
+        log::info!("Purged {} execution records from history", deleted);
+        Ok(deleted)
+    }
+
     // ==================== Code Snippets ====================
 
     /// Create a new code snippet
@@ -1005,10 +1356,14 @@ impl SandboxState {
             .map_err(|e| SandboxError::Config(format!("Failed to export: {}", e)))
     }
 
-    /// Import data from JSON (merges with existing, skips duplicates)
-    pub async fn import_data(&self, json_data: &str) -> Result<ImportResult, SandboxError> {
+    /// Import data from an export blob using the given merge strategy
+    pub async fn import_data(
+        &self,
+        json_data: &str,
+        strategy: ImportMergeStrategy,
+    ) -> Result<ImportResult, SandboxError> {
         self.db
-            .import_from_json(json_data)
+            .import_from_json(json_data, strategy)
             .map_err(|e| SandboxError::Config(format!("Failed to import: {}", e)))
     }
 
@@ -1065,6 +1420,21 @@ impl SandboxState {
         log::info!("Sandbox database vacuum completed");
         Ok(())
     }
+
+    /// Check the database for corruption
+    pub async fn check_db_integrity(&self) -> Result<IntegrityReport, SandboxError> {
+        self.db
+            .check_integrity()
+            .map_err(|e| SandboxError::Config(format!("Failed to check integrity: {}", e)))
+    }
+
+    /// Rebuild the database from its readable rows, backing up the original
+    pub async fn repair_db(&self, force: bool) -> Result<RepairReport, SandboxError> {
+        self.db.repair(force).map_err(|e| {
+            log::error!("Failed to repair sandbox database: {}", e);
+            SandboxError::Config(format!("Failed to repair: {}", e))
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1381,6 +1751,7 @@ mod tests {
             SandboxError::Config("test".to_string()),
             SandboxError::ResourceLimit("test".to_string()),
             SandboxError::SecurityViolation("test".to_string()),
+            SandboxError::ResourceExhausted("test".to_string()),
         ];
 
         for err in errors {
@@ -1543,6 +1914,7 @@ hello()
         let result = ImportResult {
             imported_snippets: 3,
             skipped_snippets: 1,
+            ..Default::default()
         };
         assert_eq!(result.imported_snippets, 3);
         assert_eq!(result.skipped_snippets, 1);
@@ -1632,4 +2004,111 @@ hello()
         let cancelled = sandbox.cancel_execution("missing-id").await.unwrap();
         assert!(!cancelled);
     }
+
+    #[tokio::test]
+    async fn test_extend_timeout_extends_registered_deadline() {
+        let base = std::env::temp_dir().join(format!(
+            "cognia-sandbox-extend-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let config_path = base.join("sandbox-config-v2.json");
+        let sandbox = SandboxState::new(config_path).await.unwrap();
+        let deadline = ExecutionDeadline::new(Duration::from_secs(30));
+        deadline.start();
+        {
+            let mut deadlines = sandbox.active_deadlines.write().await;
+            deadlines.insert("exec-extend".to_string(), deadline.clone());
+        }
+
+        let extended = sandbox.extend_timeout("exec-extend", 15).await.unwrap();
+        assert_eq!(extended, Some(45));
+        assert_eq!(deadline.limit(), Duration::from_secs(45));
+
+        assert!(sandbox.extend_timeout("exec-extend", 0).await.is_err());
+        assert!(sandbox
+            .extend_timeout("exec-extend", MAX_EXTENDED_TIMEOUT_SECS)
+            .await
+            .is_err());
+        assert_eq!(sandbox.extend_timeout("missing-id", 15).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_warning_rearms_after_extension() {
+        let deadline = ExecutionDeadline::new(Duration::from_millis(40));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        deadline.start();
+        let task = tokio::spawn(send_timeout_warnings(
+            "exec-warn".to_string(),
+            deadline.clone(),
+            0.5,
+            tx,
+        ));
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.execution_id, "exec-warn");
+        assert_eq!(first.timeout_secs, 0);
+
+        deadline.extend(Duration::from_secs(2));
+        let second = rx.recv().await.unwrap();
+        assert_eq!(second.timeout_secs, 2);
+        task.abort();
+    }
+
+    #[test]
+    fn test_config_defaults_timeout_warning_fraction() {
+        let mut value = serde_json::to_value(SandboxConfig::default()).unwrap();
+        value
+            .as_object_mut()
+            .unwrap()
+            .remove("timeout_warning_fraction");
+
+        let parsed: SandboxConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.timeout_warning_fraction, DEFAULT_TIMEOUT_WARNING_FRACTION);
+    }
+
+    #[test]
+    fn test_partial_resource_guard_config_uses_defaults() {
+        let mut value = serde_json::to_value(SandboxConfig::default()).unwrap();
+        value["resource_guard"] = serde_json::json!({ "enabled": true });
+        let config: SandboxConfig = serde_json::from_value(value).unwrap();
+        assert!(config.resource_guard.enabled);
+        assert_eq!(
+            config.resource_guard.min_free_memory_mb,
+            DEFAULT_MIN_FREE_MEMORY_MB
+        );
+        assert_eq!(
+            config.resource_guard.max_queue_wait_secs,
+            DEFAULT_RESOURCE_QUEUE_WAIT_SECS
+        );
+    }
+
+    #[test]
+    fn test_config_rejects_timeout_warning_fraction_out_of_range() {
+        for fraction in [0.0, -0.5, 1.5] {
+            let mut value = serde_json::to_value(SandboxConfig::default()).unwrap();
+            value["timeout_warning_fraction"] = serde_json::json!(fraction);
+            assert!(serde_json::from_value::<SandboxConfig>(value).is_err());
+        }
+        assert!(!is_valid_timeout_warning_fraction(f64::NAN));
+        assert!(is_valid_timeout_warning_fraction(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_rejects_invalid_timeout_warning_fraction() {
+        let base = std::env::temp_dir().join(format!(
+            "cognia-sandbox-fraction-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let config_path = base.join("sandbox-config-v2.json");
+        let sandbox = SandboxState::new(config_path).await.unwrap();
+
+        let result = sandbox
+            .patch_config(|config| config.timeout_warning_fraction = f64::NAN)
+            .await;
+        assert!(matches!(result, Err(SandboxError::Config(_))));
+        assert_eq!(
+            sandbox.config.read().await.timeout_warning_fraction,
+            DEFAULT_TIMEOUT_WARNING_FRACTION
+        );
+    }
 }
@@ -630,11 +630,11 @@ impl SandboxRuntime for NativeRuntime {
             collected
         });
 
-        let result = timeout(exec_config.timeout, child.wait()).await;
+        let result = exec_config.run_within_limit(child.wait()).await;
         let execution_time_ms = start.elapsed().as_millis() as u64;
 
         match result {
-            Ok(Ok(status)) => {
+            Some(Ok(status)) => {
                 let exit_code = status.code().unwrap_or(-1);
                 let stdout = stdout_task.await.unwrap_or_default();
                 let stderr = stderr_task.await.unwrap_or_default();
@@ -643,19 +643,19 @@ impl SandboxRuntime for NativeRuntime {
                     execution_time_ms, RuntimeType::Native, request.language.clone(),
                 ))
             }
-            Ok(Err(e)) => {
+            Some(Err(e)) => {
                 let _ = stdout_task.await;
                 let _ = stderr_task.await;
                 Err(SandboxError::ExecutionFailed(e.to_string()))
             }
-            Err(_) => {
+            None => {
                 let _ = child.start_kill();
                 let _ = child.wait().await;
                 let _ = stdout_task.await;
                 let _ = stderr_task.await;
                 Ok(ExecutionResult::timeout(
                     request.id.clone(), String::new(), String::new(),
-                    exec_config.timeout.as_secs(), RuntimeType::Native, request.language.clone(),
+                    exec_config.effective_timeout().as_secs(), RuntimeType::Native, request.language.clone(),
                 ))
             }
         }
@@ -774,6 +774,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -879,6 +880,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         assert_eq!(config.timeout.as_secs(), 30);
@@ -903,6 +905,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -928,6 +931,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -952,6 +956,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -976,6 +981,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -1007,6 +1013,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -1033,6 +1040,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
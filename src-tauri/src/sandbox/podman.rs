@@ -435,11 +435,11 @@ impl SandboxRuntime for PodmanRuntime {
             collected
         });
 
-        let result = timeout(exec_config.timeout, child.wait()).await;
+        let result = exec_config.run_within_limit(child.wait()).await;
         let execution_time_ms = start.elapsed().as_millis() as u64;
 
         match result {
-            Ok(Ok(status)) => {
+            Some(Ok(status)) => {
                 let exit_code = status.code().unwrap_or(-1);
                 let stdout = stdout_task.await.unwrap_or_default();
                 let stderr = stderr_task.await.unwrap_or_default();
@@ -448,12 +448,12 @@ impl SandboxRuntime for PodmanRuntime {
                     execution_time_ms, RuntimeType::Podman, request.language.clone(),
                 ))
             }
-            Ok(Err(e)) => {
+            Some(Err(e)) => {
                 let _ = stdout_task.await;
                 let _ = stderr_task.await;
                 Err(SandboxError::ExecutionFailed(e.to_string()))
             }
-            Err(_) => {
+            None => {
                 let container_name = Self::container_name(request);
                 let _ = Command::new(&self.podman_path).arg("kill").arg(&container_name).output().await;
                 let _ = Command::new(&self.podman_path).args(["rm", "-f"]).arg(&container_name).output().await;
@@ -463,7 +463,7 @@ impl SandboxRuntime for PodmanRuntime {
                 let _ = stderr_task.await;
                 Ok(ExecutionResult::timeout(
                     request.id.clone(), String::new(), String::new(),
-                    exec_config.timeout.as_secs(), RuntimeType::Podman, request.language.clone(),
+                    exec_config.effective_timeout().as_secs(), RuntimeType::Podman, request.language.clone(),
                 ))
             }
         }
@@ -601,6 +601,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -627,6 +628,7 @@ mod tests {
             network_enabled: true,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -653,6 +655,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -677,6 +680,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -701,6 +705,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024,
             workspace_dir: None,
+            deadline: None,
         };
         let work_dir = PathBuf::from("/tmp/test");
 
@@ -793,6 +798,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -824,6 +830,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -853,6 +860,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
@@ -889,6 +897,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let result = runtime
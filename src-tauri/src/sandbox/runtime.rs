@@ -5,12 +5,21 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::Notify;
+use tokio::time::Instant;
 
-use super::languages::{LanguageConfig, LANGUAGE_CONFIGS};
-use super::{DockerRuntime, NativeRuntime, PodmanRuntime, SandboxConfig};
+use super::languages::{get_language_config, LanguageConfig, LANGUAGE_CONFIGS};
+use super::syntax::{get_syntax_checker, CheckOutput, SyntaxCheckResult};
+use super::{
+    DockerRuntime, NativeRuntime, PodmanRuntime, ResourceGuardAction, ResourceGuardConfig,
+    SandboxConfig,
+};
 
 /// Sandbox execution errors
 #[derive(Error, Debug)]
@@ -38,11 +47,13 @@ pub enum SandboxError {
     Io(#[from] std::io::Error),
 
     #[error("Resource limit exceeded: {0}")]
-    #[cfg_attr(not(test), allow(dead_code))]
     ResourceLimit(String),
 
     #[error("Security violation: {0}")]
     SecurityViolation(String),
+
+    #[error("System resources exhausted: {0}")]
+    ResourceExhausted(String),
 }
 
 /// Runtime type enumeration
@@ -181,6 +192,17 @@ pub struct OutputLine {
     pub timestamp_ms: u64,
 }
 
+/// Emitted when a streaming execution crosses the soft timeout threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeoutWarning {
+    /// Execution ID that is approaching its limit
+    pub execution_id: String,
+    /// Seconds elapsed since execution started
+    pub elapsed_secs: u64,
+    /// Hard limit in seconds, including extensions
+    pub timeout_secs: u64,
+}
+
 /// Execution request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionRequest {
@@ -429,6 +451,14 @@ pub struct ExecutionResult {
     /// Effective policy snapshot applied to this execution
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub policy_snapshot: Option<ExecutionPolicySnapshot>,
+
+    /// Standard output with ANSI styling converted to HTML spans (when requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout_html: Option<String>,
+
+    /// Standard error with ANSI styling converted to HTML spans (when requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr_html: Option<String>,
 }
 
 impl ExecutionResult {
@@ -458,6 +488,8 @@ impl ExecutionResult {
             language,
             diagnostics: None,
             policy_snapshot: None,
+            stdout_html: None,
+            stderr_html: None,
         }
     }
 
@@ -475,6 +507,8 @@ impl ExecutionResult {
             language,
             diagnostics: None,
             policy_snapshot: None,
+            stdout_html: None,
+            stderr_html: None,
         }
     }
 
@@ -506,8 +540,17 @@ impl ExecutionResult {
                 ),
             }),
             policy_snapshot: None,
+            stdout_html: None,
+            stderr_html: None,
         }
     }
+
+    /// Fill `stdout_html`/`stderr_html` by converting ANSI escape sequences to styled spans
+    pub fn with_rendered_ansi(mut self) -> Self {
+        self.stdout_html = Some(super::ansi::ansi_to_html(&self.stdout));
+        self.stderr_html = Some(super::ansi::ansi_to_html(&self.stderr));
+        self
+    }
 }
 
 /// Execution configuration derived from request and defaults
@@ -519,6 +562,124 @@ pub struct ExecutionConfig {
     pub network_enabled: bool,
     pub max_output_size: usize,
     pub workspace_dir: Option<PathBuf>,
+    /// Extendable hard limit for streaming executions; overrides `timeout` when set
+    pub deadline: Option<ExecutionDeadline>,
+}
+
+impl ExecutionConfig {
+    /// Hard time limit currently in force, including any extensions
+    pub fn effective_timeout(&self) -> Duration {
+        self.deadline
+            .as_ref()
+            .map(ExecutionDeadline::limit)
+            .unwrap_or(self.timeout)
+    }
+
+    /// Await `future` under the hard time limit, returning `None` once it elapses
+    pub async fn run_within_limit<F: Future>(&self, future: F) -> Option<F::Output> {
+        match &self.deadline {
+            Some(deadline) => {
+                deadline.start();
+                tokio::select! {
+                    output = future => Some(output),
+                    _ = deadline.expired() => None,
+                }
+            }
+            None => tokio::time::timeout(self.timeout, future).await.ok(),
+        }
+    }
+}
+
+/// Hard time limit of an in-flight execution that can be extended while it runs.
+///
+/// The clock starts when the runtime begins waiting on the process, so image
+/// pulls and container startup do not count against the limit.
+#[derive(Debug, Clone)]
+pub struct ExecutionDeadline {
+    inner: Arc<DeadlineInner>,
+}
+
+#[derive(Debug)]
+struct DeadlineInner {
+    started_at: OnceLock<Instant>,
+    limit_ms: AtomicU64,
+    changed: Notify,
+}
+
+impl ExecutionDeadline {
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            inner: Arc::new(DeadlineInner {
+                started_at: OnceLock::new(),
+                limit_ms: AtomicU64::new(limit.as_millis() as u64),
+                changed: Notify::new(),
+            }),
+        }
+    }
+
+    /// Start the clock; later calls are no-ops
+    pub fn start(&self) {
+        if self.inner.started_at.set(Instant::now()).is_ok() {
+            self.inner.changed.notify_waiters();
+        }
+    }
+
+    pub fn limit(&self) -> Duration {
+        Duration::from_millis(self.inner.limit_ms.load(Ordering::SeqCst))
+    }
+
+    /// Time since the clock started (zero before it starts)
+    pub fn elapsed(&self) -> Duration {
+        self.inner
+            .started_at
+            .get()
+            .map(Instant::elapsed)
+            .unwrap_or_default()
+    }
+
+    /// Push the hard limit out by `extra`, returning the new limit
+    pub fn extend(&self, extra: Duration) -> Duration {
+        let extra_ms = extra.as_millis() as u64;
+        let previous = self.inner.limit_ms.fetch_add(extra_ms, Ordering::SeqCst);
+        self.inner.changed.notify_waiters();
+        Duration::from_millis(previous.saturating_add(extra_ms))
+    }
+
+    /// Resolve once the current limit has elapsed
+    pub async fn expired(&self) {
+        self.reached_fraction(1.0).await
+    }
+
+    /// Resolve once `fraction` of the current limit has elapsed, following extensions
+    pub async fn reached_fraction(&self, fraction: f64) {
+        loop {
+            // Register before reading state so a concurrent change is not missed
+            let changed = self.inner.changed.notified();
+            let Some(started_at) = self.inner.started_at.get().copied() else {
+                changed.await;
+                continue;
+            };
+            let target = started_at + self.limit().mul_f64(fraction);
+            if Instant::now() >= target {
+                return;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep_until(target) => {}
+                _ = changed => {}
+            }
+        }
+    }
+
+    /// Resolve once the limit has been extended beyond `limit`
+    pub async fn extended_beyond(&self, limit: Duration) {
+        loop {
+            let changed = self.inner.changed.notified();
+            if self.limit() > limit {
+                return;
+            }
+            changed.await;
+        }
+    }
 }
 
 /// Sandbox runtime trait - implemented by Docker, Podman, Native
@@ -564,6 +725,57 @@ pub trait SandboxRuntime: Send + Sync {
     }
 }
 
+/// How often a queued execution re-checks free memory
+const RESOURCE_GUARD_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Hold back an execution while free memory is below the guard threshold
+///
+/// `available_memory_mb` reports free system memory. Depending on the guard
+/// action this fails immediately or waits for memory to free up, failing with
+/// `ResourceExhausted` once `max_queue_wait_secs` has passed.
+async fn wait_for_resources(
+    guard: &ResourceGuardConfig,
+    available_memory_mb: impl Fn() -> u64,
+) -> Result<(), SandboxError> {
+    if !guard.enabled {
+        return Ok(());
+    }
+
+    let started = Instant::now();
+    let max_wait = Duration::from_secs(guard.max_queue_wait_secs);
+    let mut queued = false;
+    loop {
+        let available_mb = available_memory_mb();
+        if available_mb >= guard.min_free_memory_mb {
+            return Ok(());
+        }
+
+        let waited = started.elapsed();
+        if guard.action == ResourceGuardAction::Refuse || waited >= max_wait {
+            let reason = match guard.action {
+                ResourceGuardAction::Refuse => String::new(),
+                ResourceGuardAction::Queue => {
+                    format!(" after waiting {}s", waited.as_secs())
+                }
+            };
+            return Err(SandboxError::ResourceExhausted(format!(
+                "only {} MB of memory free, {} MB required to start an execution{}",
+                available_mb, guard.min_free_memory_mb, reason
+            )));
+        }
+
+        if !queued {
+            log::info!(
+                "Queueing execution: {} MB free, waiting for {} MB",
+                available_mb,
+                guard.min_free_memory_mb
+            );
+            queued = true;
+        }
+        tokio::time::sleep(RESOURCE_GUARD_POLL_INTERVAL.min(max_wait - waited)).await;
+    }
+}
+
 /// Sandbox manager - coordinates multiple runtimes
 pub struct SandboxManager {
     docker: Option<DockerRuntime>,
@@ -791,6 +1003,15 @@ impl SandboxManager {
                     "Check container runtime health and image availability.".to_string(),
                 ),
             },
+            SandboxError::ResourceExhausted(message) => ExecutionDiagnostics {
+                category: DiagnosticsCategory::ResourceLimit,
+                code: "resource_exhausted".to_string(),
+                message: Some(message.clone()),
+                remediation_hint: Some(
+                    "Close memory-heavy applications, or lower the sandbox resource guard threshold."
+                        .to_string(),
+                ),
+            },
             SandboxError::ExecutionFailed(_)
             | SandboxError::Config(_)
             | SandboxError::Io(_) => ExecutionDiagnostics {
@@ -1066,9 +1287,16 @@ impl SandboxManager {
                     requested_runtime: request.runtime,
                     selected_runtime: preflight.selected_runtime,
                 }),
+                stdout_html: None,
+                stderr_html: None,
             });
         }
 
+        wait_for_resources(&self.config.resource_guard, || {
+            crate::process::available_memory_bytes() / (1024 * 1024)
+        })
+        .await?;
+
         // Get language configuration (safe because preflight already validated support)
         let language_config = match LANGUAGE_CONFIGS
             .iter()
@@ -1099,6 +1327,8 @@ impl SandboxManager {
                         ),
                     }),
                     policy_snapshot: None,
+                    stdout_html: None,
+                    stderr_html: None,
                 })
             }
         };
@@ -1122,6 +1352,7 @@ impl SandboxManager {
             network_enabled,
             max_output_size: self.config.max_output_size,
             workspace_dir: self.config.workspace_dir.clone(),
+            deadline: None,
         };
 
         let policy_snapshot = ExecutionPolicySnapshot {
@@ -1175,6 +1406,7 @@ impl SandboxManager {
         &self,
         request: ExecutionRequest,
         output_tx: tokio::sync::mpsc::Sender<OutputLine>,
+        deadline: Option<ExecutionDeadline>,
     ) -> Result<ExecutionResult, SandboxError> {
         log::debug!(
             "SandboxManager.execute_streaming: language={}, id={}",
@@ -1215,9 +1447,16 @@ impl SandboxManager {
                     requested_runtime: request.runtime,
                     selected_runtime: preflight.selected_runtime,
                 }),
+                stdout_html: None,
+                stderr_html: None,
             });
         }
 
+        wait_for_resources(&self.config.resource_guard, || {
+            crate::process::available_memory_bytes() / (1024 * 1024)
+        })
+        .await?;
+
         let language_config = match LANGUAGE_CONFIGS
             .iter()
             .find(|l| l.id == request.language || l.aliases.contains(&request.language.as_str()))
@@ -1247,6 +1486,8 @@ impl SandboxManager {
                         ),
                     }),
                     policy_snapshot: None,
+                    stdout_html: None,
+                    stderr_html: None,
                 })
             }
         };
@@ -1269,6 +1510,7 @@ impl SandboxManager {
             network_enabled,
             max_output_size: self.config.max_output_size,
             workspace_dir: self.config.workspace_dir.clone(),
+            deadline,
         };
 
         let policy_snapshot = ExecutionPolicySnapshot {
@@ -1407,6 +1649,84 @@ impl SandboxManager {
         Ok(())
     }
 
+    /// Run the language's compiler/linter in check-only mode and collect diagnostics
+    pub async fn check_syntax(
+        &self,
+        language: &str,
+        code: String,
+    ) -> Result<SyntaxCheckResult, SandboxError> {
+        let checker = get_syntax_checker(language).ok_or_else(|| {
+            SandboxError::LanguageNotSupported(format!(
+                "Syntax checking is not available for '{}'",
+                language
+            ))
+        })?;
+        let language_config = get_language_config(checker.language).ok_or_else(|| {
+            SandboxError::LanguageNotSupported(format!("Unknown language '{}'", language))
+        })?;
+
+        let profile = self.resolve_policy_profile(None);
+        let selected_runtime = self
+            .resolve_runtime_for_profile(None, &profile)
+            .ok_or_else(|| {
+                SandboxError::RuntimeNotAvailable(
+                    "No sandbox runtime is available for syntax checking".to_string(),
+                )
+            })?;
+        let runtime = self.get_runtime_by_type(selected_runtime).ok_or_else(|| {
+            SandboxError::RuntimeNotAvailable(format!(
+                "Runtime '{}' is no longer available",
+                selected_runtime
+            ))
+        })?;
+
+        let exec_config = ExecutionConfig {
+            timeout: Duration::from_secs(self.config.default_timeout_secs),
+            memory_limit_mb: self.config.default_memory_limit_mb,
+            cpu_limit_percent: self.config.default_cpu_limit_percent,
+            network_enabled: self.config.network_enabled,
+            max_output_size: self.config.max_output_size,
+            workspace_dir: self.config.workspace_dir.clone(),
+            deadline: None,
+        };
+        let request = ExecutionRequest::new(checker.language, code);
+
+        log::info!(
+            "Checking syntax: id={}, language={}, tool={}, runtime={}",
+            request.id,
+            checker.language,
+            checker.tool,
+            selected_runtime
+        );
+        let start = std::time::Instant::now();
+
+        let output = if selected_runtime == RuntimeType::Native {
+            checker
+                .run_native(&request, language_config, &exec_config)
+                .await?
+        } else {
+            let check_config = checker.container_config(language_config);
+            let result = runtime
+                .execute(&request, &check_config, &exec_config)
+                .await?;
+            if matches!(result.status, ExecutionStatus::Timeout) {
+                return Err(SandboxError::Timeout(exec_config.timeout.as_secs()));
+            }
+            CheckOutput {
+                stdout: result.stdout,
+                stderr: result.stderr,
+                exit_code: result.exit_code,
+            }
+        };
+
+        Ok(checker.build_result(
+            selected_runtime,
+            &output,
+            language_config.file_name,
+            start.elapsed().as_millis() as u64,
+        ))
+    }
+
     /// Execute code with timeout and memory limit
     pub async fn execute_with_limits(
         &self,
@@ -1803,6 +2123,7 @@ mod tests {
             network_enabled: false,
             max_output_size: 1024 * 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         assert_eq!(config.timeout.as_secs(), 30);
@@ -1821,6 +2142,7 @@ mod tests {
             network_enabled: true,
             max_output_size: 1024,
             workspace_dir: None,
+            deadline: None,
         };
 
         let cloned = config.clone();
@@ -1828,6 +2150,60 @@ mod tests {
         assert_eq!(config.memory_limit_mb, cloned.memory_limit_mb);
     }
 
+    #[tokio::test]
+    async fn test_run_within_limit_honours_deadline_extension() {
+        let deadline = ExecutionDeadline::new(Duration::from_millis(50));
+        let config = ExecutionConfig {
+            timeout: Duration::from_millis(50),
+            memory_limit_mb: 256,
+            cpu_limit_percent: 50,
+            network_enabled: false,
+            max_output_size: 1024,
+            workspace_dir: None,
+            deadline: Some(deadline.clone()),
+        };
+
+        assert_eq!(deadline.extend(Duration::from_millis(200)).as_millis(), 250);
+        let result = config
+            .run_within_limit(tokio::time::sleep(Duration::from_millis(100)))
+            .await;
+
+        assert!(result.is_some());
+        assert_eq!(config.effective_timeout().as_millis(), 250);
+    }
+
+    #[tokio::test]
+    async fn test_run_within_limit_enforces_hard_limit() {
+        let config = ExecutionConfig {
+            timeout: Duration::from_secs(30),
+            memory_limit_mb: 256,
+            cpu_limit_percent: 50,
+            network_enabled: false,
+            max_output_size: 1024,
+            workspace_dir: None,
+            deadline: Some(ExecutionDeadline::new(Duration::from_millis(20))),
+        };
+
+        let result = config
+            .run_within_limit(tokio::time::sleep(Duration::from_secs(5)))
+            .await;
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deadline_waits_for_start() {
+        let deadline = ExecutionDeadline::new(Duration::from_millis(10));
+        assert_eq!(deadline.elapsed(), Duration::ZERO);
+
+        let expired = tokio::time::timeout(Duration::from_millis(50), deadline.expired()).await;
+        assert!(expired.is_err());
+
+        deadline.start();
+        let expired = tokio::time::timeout(Duration::from_millis(500), deadline.expired()).await;
+        assert!(expired.is_ok());
+    }
+
     // ==================== SandboxError Tests ====================
 
     #[test]
@@ -1922,6 +2298,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await;
@@ -1945,6 +2322,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await;
@@ -1968,6 +2346,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -1990,6 +2369,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2014,6 +2394,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2036,6 +2417,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2061,6 +2443,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec![], // No languages enabled
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2086,6 +2469,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2109,6 +2493,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2136,6 +2521,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2165,6 +2551,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2196,6 +2583,7 @@ mod tests {
             network_enabled: false, // Network disabled in config
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2228,6 +2616,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
         let manager =
             build_preflight_test_manager(config, vec![RuntimeType::Native], vec!["python"]);
@@ -2256,6 +2645,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
         let manager =
             build_preflight_test_manager(config, vec![RuntimeType::Native], vec!["python"]);
@@ -2289,6 +2679,7 @@ mod tests {
             network_enabled: true,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
         let manager =
             build_preflight_test_manager(config, vec![RuntimeType::Native], vec!["python"]);
@@ -2327,6 +2718,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
         let manager =
             build_preflight_test_manager(config, vec![RuntimeType::Native], vec!["python"]);
@@ -2345,6 +2737,70 @@ mod tests {
         assert_eq!(diagnostics.code, "timeout_out_of_bounds");
     }
 
+    #[tokio::test]
+    async fn test_execute_refuses_when_memory_is_low() {
+        let config = SandboxConfig {
+            preferred_runtime: RuntimeType::Native,
+            enable_docker: false,
+            enable_podman: false,
+            enable_native: true,
+            enabled_languages: vec!["python".to_string()],
+            resource_guard: ResourceGuardConfig {
+                enabled: true,
+                min_free_memory_mb: u64::MAX,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let manager =
+            build_preflight_test_manager(config, vec![RuntimeType::Native], vec!["python"]);
+
+        let result = manager
+            .execute(ExecutionRequest::new("python", "print('ok')"))
+            .await;
+        match result {
+            Err(error @ SandboxError::ResourceExhausted(_)) => {
+                let diagnostics = SandboxManager::map_error_to_diagnostics(&error);
+                assert_eq!(diagnostics.code, "resource_exhausted");
+            }
+            other => panic!("Expected ResourceExhausted error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_resources_refuses_or_queues() {
+        let mut guard = ResourceGuardConfig {
+            enabled: true,
+            min_free_memory_mb: 1024,
+            ..Default::default()
+        };
+        assert!(wait_for_resources(&guard, || 2048).await.is_ok());
+        assert!(matches!(
+            wait_for_resources(&guard, || 512).await,
+            Err(SandboxError::ResourceExhausted(_))
+        ));
+
+        // Queued executions start once memory frees up
+        guard.action = ResourceGuardAction::Queue;
+        let checks = AtomicU64::new(0);
+        let freed = || {
+            if checks.fetch_add(1, Ordering::SeqCst) == 0 {
+                512
+            } else {
+                2048
+            }
+        };
+        assert!(wait_for_resources(&guard, freed).await.is_ok());
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+
+        // ...and give up once the queue wait is over
+        guard.max_queue_wait_secs = 0;
+        assert!(wait_for_resources(&guard, || 512).await.is_err());
+
+        guard.enabled = false;
+        assert!(wait_for_resources(&guard, || 0).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_sandbox_manager_execute_with_limits() {
         let config = SandboxConfig {
@@ -2360,6 +2816,7 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
@@ -2676,12 +3133,13 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec!["python".to_string()],
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
         let request = ExecutionRequest::new("python", "print('hello')").with_timeout(0);
         let (tx, _rx) = tokio::sync::mpsc::channel(16);
-        let result = manager.execute_streaming(request, tx).await;
+        let result = manager.execute_streaming(request, tx, None).await;
 
         assert!(result.is_err());
         match result {
@@ -2705,13 +3163,29 @@ mod tests {
             network_enabled: false,
             workspace_dir: None,
             enabled_languages: vec![], // No languages
+            ..Default::default()
         };
 
         let manager = SandboxManager::new(config).await.unwrap();
         let request = ExecutionRequest::new("python", "print('hello')");
         let (tx, _rx) = tokio::sync::mpsc::channel(16);
-        let result = manager.execute_streaming(request, tx).await;
+        let result = manager.execute_streaming(request, tx, None).await;
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_check_syntax_rejects_unsupported_language_and_missing_runtime() {
+        let manager =
+            build_preflight_test_manager(SandboxConfig::default(), Vec::new(), Vec::new());
+
+        let unsupported = manager.check_syntax("ruby", "puts 1".to_string()).await;
+        assert!(matches!(
+            unsupported,
+            Err(SandboxError::LanguageNotSupported(_))
+        ));
+
+        let no_runtime = manager.check_syntax("rust", "fn main() {}".to_string()).await;
+        assert!(matches!(no_runtime, Err(SandboxError::RuntimeNotAvailable(_))));
+    }
 }
@@ -0,0 +1,532 @@
+//! Check-only syntax diagnostics
+//!
+//! Runs a language's compiler or linter without executing the code and parses
+//! its output into structured diagnostics for inline editor markers.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::commands::system::setup::NPX_COMMAND;
+
+use super::languages::{get_language_config, LanguageConfig};
+use super::runtime::{ExecutionConfig, ExecutionRequest, RuntimeType, SandboxError};
+use super::workspace::{create_workspace, write_execution_files};
+
+/// Longest checker output kept in `tool_error`
+const MAX_TOOL_ERROR_CHARS: usize = 2000;
+
+#[cfg(target_os = "windows")]
+const NATIVE_PYTHON: &str = "python";
+#[cfg(not(target_os = "windows"))]
+const NATIVE_PYTHON: &str = "python3";
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A single compiler/linter diagnostic (1-based positions)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyntaxDiagnostic {
+    pub line: u32,
+    pub column: Option<u32>,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Tool-specific diagnostic code (e.g. `E0425`, `TS2322`)
+    pub code: Option<String>,
+}
+
+/// Result of a check-only run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntaxCheckResult {
+    pub language: String,
+    /// Checker that produced the diagnostics
+    pub tool: String,
+    pub runtime: RuntimeType,
+    pub diagnostics: Vec<SyntaxDiagnostic>,
+    pub has_errors: bool,
+    /// Checker output when it failed without reporting diagnostics (e.g. tool missing)
+    pub tool_error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Raw output of a checker run
+#[derive(Debug, Clone, Default)]
+pub struct CheckOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+}
+
+/// Check-only command and output parser for one language
+pub struct SyntaxChecker {
+    pub language: &'static str,
+    pub tool: &'static str,
+    /// Shell command run inside the language's container image
+    container_cmd: &'static str,
+    /// Command run directly in native mode (`{file}`, `{outdir}`, `{python}` and
+    /// `{npx}` are substituted)
+    native_cmd: &'static str,
+    /// Native command used when the primary tool is not installed
+    native_fallback_cmd: Option<&'static str>,
+    parse: fn(&CheckOutput) -> Vec<(String, SyntaxDiagnostic)>,
+}
+
+/// Languages with a check-only mode
+static SYNTAX_CHECKERS: &[SyntaxChecker] = &[
+    SyntaxChecker {
+        language: "rust",
+        tool: "rustc",
+        container_cmd: "rustc --emit=metadata --error-format=short --out-dir /tmp {file}",
+        native_cmd: "rustc --emit=metadata --error-format=short --out-dir {outdir} {file}",
+        native_fallback_cmd: None,
+        parse: parse_rustc,
+    },
+    SyntaxChecker {
+        language: "typescript",
+        tool: "tsc",
+        container_cmd: "npx tsc --noEmit --pretty false --target ES2022 --module NodeNext --moduleResolution NodeNext {file}",
+        native_cmd: "{npx} tsc --noEmit --pretty false --target ES2022 --module NodeNext --moduleResolution NodeNext {file}",
+        native_fallback_cmd: None,
+        parse: parse_tsc,
+    },
+    SyntaxChecker {
+        language: "python",
+        tool: "pyflakes",
+        container_cmd: "if python3 -c 'import pyflakes' 2>/dev/null; then python3 -m pyflakes {file}; else PYTHONPYCACHEPREFIX=/tmp python3 -m py_compile {file}; fi",
+        native_cmd: "{python} -m pyflakes {file}",
+        native_fallback_cmd: Some("{python} -m py_compile {file}"),
+        parse: parse_python,
+    },
+];
+
+/// Get the syntax checker for a language ID or alias
+pub fn get_syntax_checker(language: &str) -> Option<&'static SyntaxChecker> {
+    let config = get_language_config(language)?;
+    SYNTAX_CHECKERS.iter().find(|c| c.language == config.id)
+}
+
+impl SyntaxChecker {
+    /// Language config that runs the checker instead of the program
+    pub fn container_config(&self, base: &LanguageConfig) -> LanguageConfig {
+        LanguageConfig {
+            compile_cmd: None,
+            run_cmd: self.container_cmd,
+            ..base.clone()
+        }
+    }
+
+    /// Run the checker as a local process in a fresh workspace
+    pub async fn run_native(
+        &self,
+        request: &ExecutionRequest,
+        language_config: &LanguageConfig,
+        exec_config: &ExecutionConfig,
+    ) -> Result<CheckOutput, SandboxError> {
+        let workspace = create_workspace(exec_config.workspace_dir.as_deref(), &request.id).await?;
+        let work_dir = workspace.path().to_path_buf();
+        let code_path = write_execution_files(&work_dir, request, language_config).await?;
+
+        let output = self
+            .run_native_cmd(self.native_cmd, &code_path, &work_dir, exec_config)
+            .await?;
+        match self.native_fallback_cmd {
+            Some(fallback) if output.stderr.contains("No module named") => {
+                log::debug!(
+                    "{} is not installed, falling back to: {}",
+                    self.tool,
+                    fallback
+                );
+                self.run_native_cmd(fallback, &code_path, &work_dir, exec_config)
+                    .await
+            }
+            _ => Ok(output),
+        }
+    }
+
+    async fn run_native_cmd(
+        &self,
+        template: &str,
+        code_path: &Path,
+        work_dir: &Path,
+        exec_config: &ExecutionConfig,
+    ) -> Result<CheckOutput, SandboxError> {
+        let parts: Vec<String> = template
+            .split_whitespace()
+            .map(|part| match part {
+                "{file}" => code_path.to_string_lossy().to_string(),
+                "{outdir}" => work_dir.to_string_lossy().to_string(),
+                "{python}" => NATIVE_PYTHON.to_string(),
+                "{npx}" => NPX_COMMAND.to_string(),
+                other => other.to_string(),
+            })
+            .collect();
+        log::debug!("Running native syntax check: {}", parts.join(" "));
+
+        let mut cmd = Command::new(&parts[0]);
+        cmd.args(&parts[1..])
+            .current_dir(work_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        match timeout(exec_config.timeout, cmd.output()).await {
+            Ok(Ok(output)) => Ok(CheckOutput {
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                exit_code: output.status.code(),
+            }),
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(SandboxError::RuntimeNotAvailable(format!(
+                    "'{}' is not installed for native syntax checking",
+                    parts[0]
+                )))
+            }
+            Ok(Err(e)) => Err(SandboxError::Io(e)),
+            Err(_) => Err(SandboxError::Timeout(exec_config.timeout.as_secs())),
+        }
+    }
+
+    /// Parse checker output into diagnostics for the checked file
+    pub fn build_result(
+        &self,
+        runtime: RuntimeType,
+        output: &CheckOutput,
+        file_name: &str,
+        duration_ms: u64,
+    ) -> SyntaxCheckResult {
+        let diagnostics: Vec<SyntaxDiagnostic> = (self.parse)(output)
+            .into_iter()
+            .filter(|(path, _)| {
+                Path::new(path)
+                    .file_name()
+                    .map(|name| name == file_name)
+                    .unwrap_or(false)
+            })
+            .map(|(_, diagnostic)| diagnostic)
+            .collect();
+
+        let has_errors = diagnostics
+            .iter()
+            .any(|d| d.severity == DiagnosticSeverity::Error);
+        let failed = output.exit_code.map(|code| code != 0).unwrap_or(true);
+        let tool_error = if diagnostics.is_empty() && failed {
+            let raw = if output.stderr.trim().is_empty() {
+                output.stdout.trim()
+            } else {
+                output.stderr.trim()
+            };
+            Some(raw.chars().take(MAX_TOOL_ERROR_CHARS).collect())
+        } else {
+            None
+        };
+
+        log::debug!(
+            "{} reported {} diagnostic(s) (errors: {})",
+            self.tool,
+            diagnostics.len(),
+            has_errors
+        );
+
+        SyntaxCheckResult {
+            language: self.language.to_string(),
+            tool: self.tool.to_string(),
+            runtime,
+            diagnostics,
+            has_errors,
+            tool_error,
+            duration_ms,
+        }
+    }
+}
+
+/// Split `path:line[:col]: rest`, tolerating drive-letter colons in `path`
+fn split_colon_location(line: &str) -> Option<(&str, u32, Option<u32>, &str)> {
+    for (idx, _) in line.match_indices(':') {
+        let after = &line[idx + 1..];
+        let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 || after.as_bytes().get(digits) != Some(&b':') {
+            continue;
+        }
+        let line_no = after[..digits].parse().ok()?;
+        let rest = &after[digits + 1..];
+
+        let col_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if col_digits > 0 && rest.as_bytes().get(col_digits) == Some(&b':') {
+            let column = rest[..col_digits].parse().ok();
+            return Some((
+                &line[..idx],
+                line_no,
+                column,
+                rest[col_digits + 1..].trim_start(),
+            ));
+        }
+        return Some((&line[..idx], line_no, None, rest.trim_start()));
+    }
+    None
+}
+
+fn severity_from_word(word: &str) -> Option<DiagnosticSeverity> {
+    match word {
+        "error" => Some(DiagnosticSeverity::Error),
+        "warning" => Some(DiagnosticSeverity::Warning),
+        "note" | "help" | "message" => Some(DiagnosticSeverity::Info),
+        _ => None,
+    }
+}
+
+/// Parse `rustc --error-format=short` output:
+/// `main.rs:2:5: error[E0425]: cannot find value `x` in this scope`
+fn parse_rustc(output: &CheckOutput) -> Vec<(String, SyntaxDiagnostic)> {
+    output
+        .stderr
+        .lines()
+        .filter_map(|line| {
+            let (path, line_no, column, rest) = split_colon_location(line)?;
+            let (head, message) = rest.split_once(": ")?;
+            let (word, code) = match head.split_once('[') {
+                Some((word, code)) => (word, Some(code.trim_end_matches(']').to_string())),
+                None => (head, None),
+            };
+            Some((
+                path.to_string(),
+                SyntaxDiagnostic {
+                    line: line_no,
+                    column,
+                    severity: severity_from_word(word)?,
+                    message: message.to_string(),
+                    code,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Parse `tsc --pretty false` output:
+/// `main.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.`
+fn parse_tsc(output: &CheckOutput) -> Vec<(String, SyntaxDiagnostic)> {
+    // tsc reports diagnostics on stdout
+    output
+        .stdout
+        .lines()
+        .chain(output.stderr.lines())
+        .filter_map(|line| {
+            let (location, rest) = line.split_once("): ")?;
+            let (path, position) = location.rsplit_once('(')?;
+            let (line_no, column) = position.split_once(',')?;
+            let (head, message) = rest.split_once(": ")?;
+            let mut head = head.split_whitespace();
+            let severity = severity_from_word(head.next()?)?;
+            Some((
+                path.to_string(),
+                SyntaxDiagnostic {
+                    line: line_no.trim().parse().ok()?,
+                    column: column.trim().parse().ok(),
+                    severity,
+                    message: message.to_string(),
+                    code: head.next().map(str::to_string),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Parse pyflakes output (warnings on stdout, syntax errors on stderr) or,
+/// when pyflakes is unavailable, `py_compile` syntax errors:
+///
+/// ```text
+///   File "main.py", line 2
+///     print(
+///          ^
+/// SyntaxError: '(' was never closed
+/// ```
+fn parse_python(output: &CheckOutput) -> Vec<(String, SyntaxDiagnostic)> {
+    let mut diagnostics = Vec::new();
+
+    for (text, default_severity) in [
+        (&output.stdout, DiagnosticSeverity::Warning),
+        (&output.stderr, DiagnosticSeverity::Error),
+    ] {
+        for line in text.lines() {
+            let Some((path, line_no, column, message)) = split_colon_location(line) else {
+                continue;
+            };
+            let severity = if message.starts_with("undefined name") {
+                DiagnosticSeverity::Error
+            } else {
+                default_severity
+            };
+            diagnostics.push((
+                path.to_string(),
+                SyntaxDiagnostic {
+                    line: line_no,
+                    column,
+                    severity,
+                    message: message.to_string(),
+                    code: None,
+                },
+            ));
+        }
+    }
+
+    // py_compile traceback format
+    let mut location: Option<(String, u32)> = None;
+    for line in output.stderr.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("File \"") {
+            location = rest.split_once("\", line ").and_then(|(path, line_no)| {
+                let digits: String = line_no.chars().take_while(char::is_ascii_digit).collect();
+                Some((path.to_string(), digits.parse().ok()?))
+            });
+            continue;
+        }
+        let Some((path, line_no)) = location.as_ref() else {
+            continue;
+        };
+        let trimmed = trimmed.strip_prefix("Sorry: ").unwrap_or(trimmed);
+        if let Some((kind, message)) = trimmed.split_once(": ") {
+            if kind.ends_with("Error") && !kind.contains(' ') {
+                diagnostics.push((
+                    path.clone(),
+                    SyntaxDiagnostic {
+                        line: *line_no,
+                        column: None,
+                        severity: DiagnosticSeverity::Error,
+                        message: message.to_string(),
+                        code: Some(kind.to_string()),
+                    },
+                ));
+                location = None;
+            }
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(stdout: &str, stderr: &str, exit_code: i32) -> CheckOutput {
+        CheckOutput {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code: Some(exit_code),
+        }
+    }
+
+    #[test]
+    fn test_split_colon_location() {
+        assert_eq!(
+            split_colon_location("C:\\tmp\\main.rs:2:5: error: boom"),
+            Some(("C:\\tmp\\main.rs", 2, Some(5), "error: boom"))
+        );
+        assert_eq!(
+            split_colon_location("main.py:7: 'os' imported but unused"),
+            Some(("main.py", 7, None, "'os' imported but unused"))
+        );
+        assert_eq!(split_colon_location("error: aborting"), None);
+    }
+
+    #[test]
+    fn test_parse_rustc_short_format() {
+        let stderr = "/code/main.rs:2:13: error[E0425]: cannot find value `y` in this scope\n\
+                      /code/main.rs:1:9: warning: unused variable: `x`\n\
+                      error: aborting due to 1 previous error; 1 warning emitted\n";
+        let checker = get_syntax_checker("rs").unwrap();
+        let result =
+            checker.build_result(RuntimeType::Docker, &output("", stderr, 1), "main.rs", 10);
+
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(
+            result.diagnostics[0],
+            SyntaxDiagnostic {
+                line: 2,
+                column: Some(13),
+                severity: DiagnosticSeverity::Error,
+                message: "cannot find value `y` in this scope".to_string(),
+                code: Some("E0425".to_string()),
+            }
+        );
+        assert_eq!(result.diagnostics[1].severity, DiagnosticSeverity::Warning);
+        assert!(result.diagnostics[1].code.is_none());
+        assert!(result.has_errors);
+        assert!(result.tool_error.is_none());
+    }
+
+    #[test]
+    fn test_parse_tsc_output() {
+        let stdout =
+            "main.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n";
+        let diagnostics = parse_tsc(&output(stdout, "", 2));
+
+        assert_eq!(diagnostics.len(), 1);
+        let (path, diagnostic) = &diagnostics[0];
+        assert_eq!(path, "main.ts");
+        assert_eq!(diagnostic.line, 3);
+        assert_eq!(diagnostic.column, Some(7));
+        assert_eq!(diagnostic.code.as_deref(), Some("TS2322"));
+        assert!(diagnostic.message.starts_with("Type 'string'"));
+    }
+
+    #[test]
+    fn test_parse_pyflakes_output() {
+        let stdout = "main.py:1:1: 'os' imported but unused\nmain.py:3:7: undefined name 'y'\n";
+        let stderr = "main.py:5:6: '(' was never closed\nprint(\n     ^\n";
+        let diagnostics = parse_python(&output(stdout, stderr, 1));
+
+        let severities: Vec<_> = diagnostics.iter().map(|(_, d)| d.severity).collect();
+        assert_eq!(
+            severities,
+            vec![
+                DiagnosticSeverity::Warning,
+                DiagnosticSeverity::Error,
+                DiagnosticSeverity::Error
+            ]
+        );
+        assert_eq!(diagnostics[2].1.line, 5);
+        assert_eq!(diagnostics[2].1.column, Some(6));
+    }
+
+    #[test]
+    fn test_parse_py_compile_output() {
+        let stderr = "  File \"/code/main.py\", line 2\n    print(\n         ^\nSyntaxError: '(' was never closed\n";
+        let diagnostics = parse_python(&output("", stderr, 1));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, "/code/main.py");
+        assert_eq!(diagnostics[0].1.line, 2);
+        assert_eq!(diagnostics[0].1.code.as_deref(), Some("SyntaxError"));
+    }
+
+    #[test]
+    fn test_build_result_reports_tool_failure() {
+        let checker = get_syntax_checker("typescript").unwrap();
+        let result = checker.build_result(
+            RuntimeType::Native,
+            &output("", "sh: 1: npx: not found\n", 127),
+            "main.ts",
+            5,
+        );
+
+        assert!(result.diagnostics.is_empty());
+        assert!(!result.has_errors);
+        assert_eq!(result.tool_error.as_deref(), Some("sh: 1: npx: not found"));
+    }
+
+    #[test]
+    fn test_unsupported_language_has_no_checker() {
+        assert!(get_syntax_checker("ruby").is_none());
+        assert!(get_syntax_checker("unknown").is_none());
+        assert!(get_syntax_checker("py3").is_some());
+    }
+}
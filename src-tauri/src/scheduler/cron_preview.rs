@@ -0,0 +1,496 @@
+//! Preview of upcoming cron fire times and cron trigger timezones
+//!
+//! Accepts standard 5-field expressions (minute, hour, day of month, month,
+//! day of week) and 6-field ones with a leading seconds field. Days of week use
+//! the Unix numbering the platform backends expect (0 or 7 = Sunday), while the
+//! `cron` crate numbers them 1-7, so the field is rewritten as day names before
+//! parsing.
+//!
+//! Cron triggers may name an IANA timezone; without one they fire in local
+//! time, which is also the only time the Windows and macOS schedulers accept.
+//! Those backends convert a fixed hour and minute to local time when the task
+//! is created or updated, using that day's offsets. The conversion cannot
+//! follow later DST transitions, so such triggers carry a warning when the
+//! offset between the zones changes within a year, and triggers restricted to
+//! certain days are rejected when the conversion moves them to another day.
+//! Hourly triggers only have their minute converted, and other triggers
+//! without a fixed hour are rejected unless the zone cannot affect them.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, Offset, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+
+use super::error::{Result, SchedulerError};
+use super::types::SystemTaskTrigger;
+
+/// Maximum number of fire times returned by one preview
+pub const MAX_CRON_PREVIEW_COUNT: usize = 100;
+
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Next `count` fire times of a cron expression as RFC 3339 strings
+///
+/// Times are computed in `timezone` (an IANA name such as `Asia/Shanghai`),
+/// or in local time when unset, and carry that zone's offset.
+pub fn preview_cron_schedule(
+    expression: &str,
+    count: usize,
+    timezone: Option<String>,
+) -> Result<Vec<String>> {
+    let invalid = |reason: String| SchedulerError::InvalidTrigger {
+        expression: expression.to_string(),
+        reason,
+    };
+    let schedule = parse_schedule(expression).map_err(invalid)?;
+    let count = count.min(MAX_CRON_PREVIEW_COUNT);
+
+    match parse_timezone(timezone.as_deref()).map_err(invalid)? {
+        Some(tz) => Ok(upcoming(&schedule, tz, count)),
+        None => Ok(upcoming(&schedule, Local, count)),
+    }
+}
+
+/// Trimmed timezone name, or `None` when unset or blank (local time)
+pub fn timezone_name(timezone: Option<&str>) -> Option<&str> {
+    timezone.map(str::trim).filter(|tz| !tz.is_empty())
+}
+
+/// Reject cron triggers whose timezone is not a known IANA name
+pub fn validate_trigger_timezone(trigger: &SystemTaskTrigger) -> Result<()> {
+    if let SystemTaskTrigger::Cron {
+        expression,
+        timezone,
+    } = trigger
+    {
+        parse_timezone(timezone.as_deref()).map_err(|reason| SchedulerError::InvalidTrigger {
+            expression: expression.clone(),
+            reason,
+        })?;
+    }
+    Ok(())
+}
+
+/// Convert a wall-clock time in `timezone` to local wall-clock time
+///
+/// Uses today's offsets, so the result drifts by the DST difference when only
+/// one of the two zones observes a transition. Returns the local hour, minute
+/// and how many days the local date is ahead of the zoned one.
+pub fn to_local_time(hour: u32, minute: u32, timezone: &str) -> Option<(u32, u32, i64)> {
+    let tz: Tz = timezone.trim().parse().ok()?;
+    let today = Utc::now().with_timezone(&tz).date_naive();
+    let (local, day_shift) = local_time_on(tz, today, hour, minute)?;
+    Some((local.hour(), local.minute(), day_shift))
+}
+
+/// How converting a zoned cron trigger to local time behaves over the next year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTimeConversion {
+    /// Whether the local fire time falls on another day than the zoned one on
+    /// any sampled date
+    pub shifts_day: bool,
+    /// Whether the expression only fires on certain days of month or week
+    pub restricts_days: bool,
+    /// How far, in minutes, the offset between the zone and local time moves
+    pub dst_drift_minutes: i64,
+}
+
+/// Sample the local-time conversion of a cron trigger once a month for a year
+///
+/// Returns `None` unless the trigger is a cron expression with a valid
+/// timezone and a fixed hour and minute, the only ones backends convert.
+pub fn local_time_conversion(trigger: &SystemTaskTrigger) -> Option<LocalTimeConversion> {
+    let SystemTaskTrigger::Cron {
+        expression,
+        timezone,
+    } = trigger
+    else {
+        return None;
+    };
+    let tz: Tz = timezone_name(timezone.as_deref())?.parse().ok()?;
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let [minute, hour, day, _month, weekday] = fields[..] else {
+        return None;
+    };
+    let (hour, minute) = (hour.parse().ok()?, minute.parse().ok()?);
+
+    let today = Utc::now().with_timezone(&tz).date_naive();
+    let mut shifts_day = false;
+    let mut gaps = Vec::new();
+    for month in 0..=12 {
+        let date = today + Duration::days(30 * month);
+        let (local, day_shift) = local_time_on(tz, date, hour, minute)?;
+        shifts_day |= day_shift != 0;
+        let zone_offset = tz.offset_from_utc_datetime(&local.naive_utc()).fix();
+        gaps.push(i64::from(
+            zone_offset.local_minus_utc() - local.offset().local_minus_utc(),
+        ));
+    }
+    let drift = gaps.iter().max()? - gaps.iter().min()?;
+
+    Some(LocalTimeConversion {
+        shifts_day,
+        restricts_days: day != "*" || weekday != "*",
+        dst_drift_minutes: drift / 60,
+    })
+}
+
+/// Whether backends can honor the timezone of a cron expression without a
+/// fixed hour
+///
+/// Hourly expressions at a fixed minute have that minute converted. Every
+/// minute, or every few minutes when the step divides 15, fires at the same
+/// times in any zone, since zone offsets are multiples of 15 minutes.
+fn converts_without_fixed_hour(minute: &str, hour: &str) -> bool {
+    if hour != "*" {
+        return false;
+    }
+    match minute.strip_prefix("*/") {
+        Some(step) => step
+            .parse::<u32>()
+            .map(|s| s > 0 && 15 % s == 0)
+            .unwrap_or(false),
+        None => minute == "*" || minute.parse::<u32>().is_ok(),
+    }
+}
+
+/// Reject cron triggers with a timezone that backends converting to local
+/// time cannot honor: those without a fixed hour the conversion would ignore,
+/// and day-restricted ones the conversion would move to another day, since
+/// backends only convert the hour and minute
+pub fn validate_local_time_conversion(trigger: &SystemTaskTrigger) -> Result<()> {
+    if let SystemTaskTrigger::Cron {
+        expression,
+        timezone,
+    } = trigger
+    {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if let (Some(tz), [minute, hour, _, _, _]) =
+            (timezone_name(timezone.as_deref()), &fields[..])
+        {
+            if hour.parse::<u32>().is_err() && !converts_without_fixed_hour(minute, hour) {
+                return Err(SchedulerError::InvalidTrigger {
+                    expression: expression.clone(),
+                    reason: format!(
+                        "needs a fixed hour to run in {}, which this platform converts to local time; use local time instead",
+                        tz
+                    ),
+                });
+            }
+        }
+    }
+
+    let Some(conversion) = local_time_conversion(trigger) else {
+        return Ok(());
+    };
+    if let SystemTaskTrigger::Cron {
+        expression,
+        timezone,
+    } = trigger
+    {
+        if conversion.shifts_day && conversion.restricts_days {
+            return Err(SchedulerError::InvalidTrigger {
+                expression: expression.clone(),
+                reason: format!(
+                    "fires on a different local day than in {}, which this platform cannot schedule; use local time instead",
+                    timezone_name(timezone.as_deref()).unwrap_or_default()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Warnings for cron triggers whose converted local time drifts with DST
+pub fn local_time_conversion_warnings(trigger: &SystemTaskTrigger) -> Vec<String> {
+    match local_time_conversion(trigger) {
+        Some(conversion) if conversion.dst_drift_minutes > 0 => vec![format!(
+            "夏令时切换后运行时间将偏移 {minutes} 分钟，更新任务可重新换算 / Run time will be off by {minutes} minutes after daylight saving changes; update the task to convert it again",
+            minutes = conversion.dst_drift_minutes
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// Local time of a wall-clock time on `date` in `tz`, with the number of days
+/// the local date is ahead of `date`
+fn local_time_on(
+    tz: Tz,
+    date: NaiveDate,
+    hour: u32,
+    minute: u32,
+) -> Option<(DateTime<Local>, i64)> {
+    let zoned = tz
+        .from_local_datetime(&date.and_hms_opt(hour, minute, 0)?)
+        .earliest()?;
+    let local = zoned.with_timezone(&Local);
+    let day_shift = (local.date_naive() - date).num_days();
+    Some((local, day_shift))
+}
+
+fn parse_timezone(timezone: Option<&str>) -> std::result::Result<Option<Tz>, String> {
+    timezone_name(timezone)
+        .map(|name| {
+            name.parse::<Tz>()
+                .map_err(|_| format!("unknown timezone '{}'", name))
+        })
+        .transpose()
+}
+
+fn upcoming<Z: TimeZone>(schedule: &Schedule, tz: Z, count: usize) -> Vec<String>
+where
+    Z::Offset: std::fmt::Display,
+{
+    schedule
+        .upcoming(tz)
+        .take(count)
+        .map(|time: DateTime<Z>| time.to_rfc3339())
+        .collect()
+}
+
+fn parse_schedule(expression: &str) -> std::result::Result<Schedule, String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let (seconds, fields) = match fields.len() {
+        5 => ("0", &fields[..]),
+        6 => (fields[0], &fields[1..]),
+        n => return Err(format!("expected 5 or 6 fields, found {}", n)),
+    };
+    let normalized = format!(
+        "{} {} {} {} {} {}",
+        seconds,
+        fields[0],
+        fields[1],
+        fields[2],
+        fields[3],
+        normalize_days_of_week(fields[4])?
+    );
+    Schedule::from_str(&normalized).map_err(|e| e.to_string())
+}
+
+/// Rewrite a Unix day-of-week field (`1-5`, `0,6`, `*/2`, `mon-fri`) as a
+/// list of day names
+fn normalize_days_of_week(field: &str) -> std::result::Result<String, String> {
+    if field == "*" || field == "?" {
+        return Ok("*".to_string());
+    }
+
+    let mut days = [false; 7];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<usize>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid day-of-week step '{}'", step))?,
+            ),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (0, 6)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_day(start)?, parse_day(end)?)
+        } else {
+            let day = parse_day(range)?;
+            (day, if step > 1 { 6 } else { day })
+        };
+        if start > end {
+            return Err(format!("invalid day-of-week range '{}'", range));
+        }
+        for day in (start..=end).step_by(step) {
+            days[day % 7] = true;
+        }
+    }
+
+    Ok(DAY_NAMES
+        .iter()
+        .zip(days)
+        .filter(|(_, selected)| *selected)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Day of week by number (0-7, 0 and 7 = Sunday) or three-letter name
+fn parse_day(value: &str) -> std::result::Result<usize, String> {
+    if let Ok(day) = value.parse::<usize>() {
+        return if day <= 7 {
+            Ok(day)
+        } else {
+            Err(format!("day of week {} out of range", day))
+        };
+    }
+    DAY_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("invalid day of week '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike, Weekday};
+
+    #[test]
+    fn normalizes_unix_days_of_week() {
+        assert_eq!(normalize_days_of_week("*").unwrap(), "*");
+        assert_eq!(normalize_days_of_week("0").unwrap(), "SUN");
+        assert_eq!(normalize_days_of_week("7").unwrap(), "SUN");
+        assert_eq!(
+            normalize_days_of_week("1-5").unwrap(),
+            "MON,TUE,WED,THU,FRI"
+        );
+        assert_eq!(normalize_days_of_week("5-7").unwrap(), "SUN,FRI,SAT");
+        assert_eq!(normalize_days_of_week("*/2").unwrap(), "SUN,TUE,THU,SAT");
+        assert_eq!(normalize_days_of_week("mon,Wed").unwrap(), "MON,WED");
+        assert!(normalize_days_of_week("8").is_err());
+        assert!(normalize_days_of_week("5-1").is_err());
+        assert!(normalize_days_of_week("*/0").is_err());
+    }
+
+    #[test]
+    fn previews_five_field_expression_in_timezone() {
+        let times = preview_cron_schedule("30 9 * * 1-5", 5, Some("Asia/Shanghai".to_string()))
+            .expect("preview");
+        assert_eq!(times.len(), 5);
+
+        let parsed: Vec<_> = times
+            .iter()
+            .map(|time| DateTime::parse_from_rfc3339(time).expect("rfc3339"))
+            .collect();
+        for time in &parsed {
+            assert_eq!(time.offset().local_minus_utc(), 8 * 3600);
+            assert_eq!((time.hour(), time.minute(), time.second()), (9, 30, 0));
+            assert!(!matches!(time.weekday(), Weekday::Sat | Weekday::Sun));
+        }
+        assert!(parsed.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn previews_six_field_expression_with_seconds() {
+        let times =
+            preview_cron_schedule("*/15 * * * * *", 4, Some("UTC".to_string())).expect("preview");
+        assert_eq!(times.len(), 4);
+        for time in times {
+            let time = DateTime::parse_from_rfc3339(&time).expect("rfc3339");
+            assert_eq!(time.second() % 15, 0);
+        }
+        assert!(preview_cron_schedule("* * * * *", 0, None)
+            .expect("preview")
+            .is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions_and_timezones() {
+        for expression in ["* * *", "61 * * * *", "* * * * 9", "a b c d e"] {
+            let err = preview_cron_schedule(expression, 3, None).expect_err(expression);
+            assert!(
+                matches!(&err, SchedulerError::InvalidTrigger { expression: e, .. } if e == expression),
+                "{err}"
+            );
+        }
+        let err = preview_cron_schedule("0 * * * *", 3, Some("Mars/Olympus".to_string()))
+            .expect_err("unknown timezone");
+        assert!(err.to_string().contains("unknown timezone"));
+    }
+
+    #[test]
+    fn validates_cron_trigger_timezones() {
+        let cron = |timezone: Option<&str>| SystemTaskTrigger::Cron {
+            expression: "0 9 * * *".to_string(),
+            timezone: timezone.map(str::to_string),
+        };
+        assert!(validate_trigger_timezone(&cron(None)).is_ok());
+        assert!(validate_trigger_timezone(&cron(Some(" "))).is_ok());
+        assert!(validate_trigger_timezone(&cron(Some("Europe/Berlin"))).is_ok());
+        assert!(matches!(
+            validate_trigger_timezone(&cron(Some("Europe/Atlantis"))),
+            Err(SchedulerError::InvalidTrigger { .. })
+        ));
+        assert!(validate_trigger_timezone(&SystemTaskTrigger::Interval { seconds: 60 }).is_ok());
+    }
+
+    #[test]
+    fn converts_utc_wall_clock_to_local_time() {
+        let (hour, minute, day_shift) = to_local_time(12, 30, "UTC").expect("local time");
+        let today = Utc::now().date_naive();
+        let expected = today
+            .and_hms_opt(12, 30, 0)
+            .unwrap()
+            .and_utc()
+            .with_timezone(&Local);
+        assert_eq!((hour, minute), (expected.hour(), expected.minute()));
+        assert_eq!(day_shift, (expected.date_naive() - today).num_days());
+        assert!(to_local_time(25, 0, "UTC").is_none());
+        assert!(to_local_time(9, 0, "Nowhere/Special").is_none());
+    }
+
+    #[test]
+    fn checks_local_time_conversion_of_cron_triggers() {
+        let cron = |expression: &str, timezone: Option<&str>| SystemTaskTrigger::Cron {
+            expression: expression.to_string(),
+            timezone: timezone.map(str::to_string),
+        };
+        assert!(local_time_conversion(&cron("0 9 * * *", None)).is_none());
+        assert!(local_time_conversion(&cron("*/5 * * * *", Some("UTC"))).is_none());
+        assert!(local_time_conversion(&SystemTaskTrigger::Interval { seconds: 60 }).is_none());
+
+        let today = Utc::now().date_naive();
+        let local_offsets: Vec<i64> = (0..=12)
+            .map(|month| {
+                let date = today + Duration::days(30 * month);
+                let utc = date.and_hms_opt(23, 30, 0).unwrap().and_utc();
+                i64::from(utc.with_timezone(&Local).offset().local_minus_utc())
+            })
+            .collect();
+        let drift =
+            (local_offsets.iter().max().unwrap() - local_offsets.iter().min().unwrap()) / 60;
+
+        let daily = cron("30 23 * * *", Some("UTC"));
+        let conversion = local_time_conversion(&daily).expect("conversion");
+        assert!(!conversion.restricts_days);
+        assert_eq!(conversion.dst_drift_minutes, drift);
+        assert!(validate_local_time_conversion(&daily).is_ok());
+        assert_eq!(
+            local_time_conversion_warnings(&daily).len(),
+            usize::from(drift > 0)
+        );
+
+        let weekdays = cron("30 23 * * 1-5", Some("UTC"));
+        let conversion = local_time_conversion(&weekdays).expect("conversion");
+        assert!(conversion.restricts_days);
+        let shifts_day = local_offsets
+            .iter()
+            .any(|offset| !(-23 * 3600 - 1800..1800).contains(offset));
+        assert_eq!(conversion.shifts_day, shifts_day);
+        assert_eq!(
+            validate_local_time_conversion(&weekdays).is_err(),
+            shifts_day
+        );
+    }
+
+    #[test]
+    fn rejects_zoned_cron_triggers_without_fixed_hour() {
+        let cron = |expression: &str, timezone: Option<&str>| SystemTaskTrigger::Cron {
+            expression: expression.to_string(),
+            timezone: timezone.map(str::to_string),
+        };
+        for expression in ["0 */2 * * *", "0 9-17 * * *", "*/7 * * * *", "0 8,20 * * *"] {
+            assert!(
+                matches!(
+                    validate_local_time_conversion(&cron(expression, Some("Asia/Kolkata"))),
+                    Err(SchedulerError::InvalidTrigger { .. })
+                ),
+                "{expression}"
+            );
+            assert!(validate_local_time_conversion(&cron(expression, None)).is_ok());
+        }
+        for expression in ["30 * * * *", "* * * * *", "*/5 * * * *", "*/15 * * * *"] {
+            assert!(
+                validate_local_time_conversion(&cron(expression, Some("Asia/Kolkata"))).is_ok(),
+                "{expression}"
+            );
+        }
+    }
+}
@@ -26,6 +26,9 @@ pub enum SchedulerError {
     #[error("Operation requires confirmation")]
     ConfirmationRequired,
 
+    #[error("Task limit reached: {current} of {max} tasks in use")]
+    LimitReached { current: usize, max: u32 },
+
     #[error("Execution failed: {0}")]
     ExecutionFailed(String),
 
@@ -35,6 +38,9 @@ pub enum SchedulerError {
     #[error("Invalid cron expression: {0}")]
     InvalidCron(String),
 
+    #[error("Invalid trigger '{expression}': {reason}")]
+    InvalidTrigger { expression: String, reason: String },
+
     #[error("Script validation failed: {0}")]
     ScriptValidation(String),
 
@@ -12,11 +12,14 @@ use std::path::PathBuf;
 use std::process::Command;
 use tracing::{debug, error, info, warn};
 
+use super::cron_preview::timezone_name;
 use super::error::{Result, SchedulerError};
+use super::run_reports::{report_dir, report_path};
 use super::service::{generate_task_name, now_iso, SystemScheduler, TASK_PREFIX};
 use super::types::{
-    CreateSystemTaskInput, RunLevel, SchedulerCapabilities, SystemTask, SystemTaskAction,
-    SystemTaskId, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState, TaskRunResult,
+    CreateSystemTaskInput, RetryPolicy, RunLevel, SchedulerCapabilities, SystemTask,
+    SystemTaskAction, SystemTaskId, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState,
+    TaskNotifyOn, TaskRunResult,
 };
 
 /// Linux systemd scheduler implementation
@@ -96,14 +99,46 @@ impl LinuxScheduler {
             r#"[Unit]
 Description=Cognia Task: {}
 After=network.target
-
-[Service]
-Type=oneshot
-ExecStart={}
 "#,
-            task.name, exec_start
+            task.name
         );
 
+        let retry_delays = task
+            .retry_policy
+            .as_ref()
+            .map(|policy| policy.retry_delays())
+            .filter(|delays| !delays.is_empty());
+
+        if let Some(delays) = &retry_delays {
+            // Cap restarts at the policy's attempts within its backoff window
+            let window: u64 = delays.iter().sum::<u64>() + 60 * (delays.len() as u64 + 1);
+            unit.push_str(&format!("StartLimitIntervalSec={}\n", window));
+            unit.push_str(&format!("StartLimitBurst={}\n", delays.len() + 1));
+        }
+
+        unit.push_str(&format!(
+            "\n[Service]\nType=oneshot\nExecStart={}\n",
+            exec_start
+        ));
+
+        if let Some(delays) = &retry_delays {
+            unit.push_str("Restart=on-failure\n");
+            unit.push_str(&format!("RestartSec={}\n", delays[0]));
+            let last = delays[delays.len() - 1];
+            if last > delays[0] {
+                // Exponential backoff needs systemd 254; older versions ignore it
+                unit.push_str(&format!("RestartSteps={}\n", delays.len() - 1));
+                unit.push_str(&format!("RestartMaxDelaySec={}\n", last));
+            }
+        }
+
+        // Append every attempt's exit status to the task's run report;
+        // `$$` and `%%` escape the shell's `$` and `%` from systemd
+        unit.push_str(&format!(
+            "ExecStopPost=/bin/sh -c 'echo \"$$(date +%%s) $$EXIT_STATUS\" >> \"$$1\"' cognia-report \"{}\"\n",
+            report_path(&task.id).display().to_string().replace('%', "%%")
+        ));
+
         if let Some(dir) = working_dir {
             unit.push_str(&format!("WorkingDirectory={}\n", dir));
         }
@@ -161,8 +196,16 @@ Description=Timer for Cognia Task: {}
     /// Convert trigger to systemd OnCalendar format
     fn trigger_to_on_calendar(trigger: &SystemTaskTrigger) -> Result<Option<String>> {
         match trigger {
-            SystemTaskTrigger::Cron { expression, .. } => {
-                Self::cron_to_calendar(expression).map(Some)
+            SystemTaskTrigger::Cron {
+                expression,
+                timezone,
+            } => {
+                let calendar = Self::cron_to_calendar(expression)?;
+                // systemd evaluates the spec in the zone named as its last token
+                Ok(Some(match timezone_name(timezone.as_deref()) {
+                    Some(tz) => format!("{} {}", calendar, tz),
+                    None => calendar,
+                }))
             }
             SystemTaskTrigger::Once { run_at } => {
                 let dt = chrono::DateTime::parse_from_rfc3339(run_at).map_err(|e| {
@@ -423,9 +466,15 @@ Description=Timer for Cognia Task: {}
             "*".to_string()
         };
 
+        let timezone = trimmed
+            .split_whitespace()
+            .last()
+            .filter(|token| token.parse::<chrono_tz::Tz>().is_ok())
+            .map(str::to_string);
+
         Some(SystemTaskTrigger::Cron {
             expression: format!("{minute} {hour} * * {dow}"),
-            timezone: Some("UTC".to_string()),
+            timezone,
         })
     }
 
@@ -578,6 +627,10 @@ impl SystemScheduler for LinuxScheduler {
         self.available
     }
 
+    fn reports_runs(&self, _retry_policy: Option<&RetryPolicy>) -> bool {
+        true
+    }
+
     async fn create_task(&self, input: CreateSystemTaskInput) -> Result<SystemTask> {
         if !self.available {
             return Err(SchedulerError::NotAvailable(
@@ -614,12 +667,16 @@ impl SystemScheduler for LinuxScheduler {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: input.retry_policy,
         };
 
         task.requires_admin = task.check_requires_admin();
 
         // Generate and write service unit
         let service_content = Self::generate_service(&task)?;
+        fs::create_dir_all(report_dir())?;
         fs::write(self.user_dir.join(&service_name), &service_content)?;
 
         // Generate and write timer unit
@@ -736,6 +793,9 @@ impl SystemScheduler for LinuxScheduler {
             next_run_at: None,
             last_result: None,
             metadata_state,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         }))
     }
 
@@ -781,6 +841,8 @@ impl SystemScheduler for LinuxScheduler {
         let service_name = Self::service_name(id);
         let start = std::time::Instant::now();
 
+        // A failed run is retried by systemd per the unit's `Restart=`, and
+        // `ExecStopPost=` reports every attempt
         let output = self.systemctl(&["start", &service_name])?;
         let duration_ms = start.elapsed().as_millis() as u64;
 
@@ -815,6 +877,8 @@ impl SystemScheduler for LinuxScheduler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scheduler::run_reports::parse_reports;
+    use crate::scheduler::types::RetryPolicy;
 
     #[test]
     fn parses_systemd_units_for_trigger_and_action() {
@@ -837,4 +901,129 @@ WorkingDirectory=/tmp
         ));
         assert!(matches!(action, SystemTaskAction::RunCommand { .. }));
     }
+
+    #[test]
+    fn retry_policy_maps_to_restart_settings() {
+        let mut task = SystemTask {
+            id: "Cognia_Retry".to_string(),
+            name: "Retry".to_string(),
+            description: None,
+            trigger: SystemTaskTrigger::Interval { seconds: 3600 },
+            action: SystemTaskAction::RunCommand {
+                command: "/bin/false".to_string(),
+                args: vec![],
+                working_dir: None,
+                env: HashMap::new(),
+            },
+            run_level: RunLevel::User,
+            status: SystemTaskStatus::Enabled,
+            requires_admin: false,
+            tags: vec![],
+            created_at: None,
+            updated_at: None,
+            last_run_at: None,
+            next_run_at: None,
+            last_result: None,
+            metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
+        };
+        let plain = LinuxScheduler::generate_service(&task).expect("service");
+        assert!(!plain.contains("Restart="));
+        assert!(plain.contains("ExecStopPost=/bin/sh -c "));
+        assert!(plain.contains("[Service]\nType=oneshot\nExecStart=/bin/false\n"));
+
+        task.retry_policy = Some(RetryPolicy {
+            max_attempts: 4,
+            backoff_secs: 10,
+            backoff_multiplier: 2.0,
+        });
+        let service = LinuxScheduler::generate_service(&task).expect("service");
+        let unit_section = service.split("[Service]").next().expect("unit section");
+        assert!(unit_section.contains("StartLimitBurst=4\n"));
+        assert!(unit_section.contains("StartLimitIntervalSec=310\n"));
+        assert!(service.contains("Restart=on-failure\n"));
+        assert!(service.contains("RestartSec=10\n"));
+        assert!(service.contains("RestartSteps=2\n"));
+        assert!(service.contains("RestartMaxDelaySec=40\n"));
+    }
+
+    #[test]
+    fn stop_post_command_appends_exit_status_to_run_report() {
+        let task = SystemTask {
+            id: "Cognia_Report".to_string(),
+            name: "Report".to_string(),
+            description: None,
+            trigger: SystemTaskTrigger::Interval { seconds: 3600 },
+            action: SystemTaskAction::RunCommand {
+                command: "/bin/true".to_string(),
+                args: vec![],
+                working_dir: None,
+                env: HashMap::new(),
+            },
+            run_level: RunLevel::User,
+            status: SystemTaskStatus::Enabled,
+            requires_admin: false,
+            tags: vec![],
+            created_at: None,
+            updated_at: None,
+            last_run_at: None,
+            next_run_at: None,
+            last_result: None,
+            metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
+        };
+        let service = LinuxScheduler::generate_service(&task).expect("service");
+        let stop_post =
+            LinuxScheduler::parse_unit_value(&service, "ExecStopPost").expect("ExecStopPost");
+        let report = report_path(&task.id).display().to_string();
+        assert!(service.contains(&format!(" cognia-report \"{}\"\n", report)));
+
+        // Run the command the way systemd would after unescaping `$$` and `%%`
+        let script = stop_post
+            .split('\'')
+            .nth(1)
+            .expect("quoted script")
+            .replace("$$", "$")
+            .replace("%%", "%");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let report = dir.path().join("report.log");
+        let status = Command::new("/bin/sh")
+            .args(["-c", &script, "cognia-report"])
+            .arg(&report)
+            .env("EXIT_STATUS", "3")
+            .status()
+            .expect("run stop post");
+        assert!(status.success());
+        let reports = parse_reports(&fs::read_to_string(&report).expect("report"));
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].exit_code, Some(3));
+    }
+
+    #[test]
+    fn cron_timezone_round_trips_through_on_calendar() {
+        let trigger = SystemTaskTrigger::Cron {
+            expression: "30 9 * * *".to_string(),
+            timezone: Some("Europe/Berlin".to_string()),
+        };
+        let calendar = LinuxScheduler::trigger_to_on_calendar(&trigger)
+            .expect("calendar")
+            .expect("cron has calendar");
+        assert!(calendar.ends_with(" Europe/Berlin"), "{calendar}");
+
+        let parsed = LinuxScheduler::parse_oncalendar_to_trigger(&calendar).expect("trigger");
+        assert!(matches!(
+            parsed,
+            SystemTaskTrigger::Cron { timezone: Some(ref tz), .. } if tz == "Europe/Berlin"
+        ));
+
+        let local = LinuxScheduler::parse_oncalendar_to_trigger("*-*-* 09:30:00").expect("trigger");
+        assert!(matches!(
+            local,
+            SystemTaskTrigger::Cron { timezone: None, .. }
+        ));
+    }
 }
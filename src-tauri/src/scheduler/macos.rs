@@ -8,17 +8,23 @@ use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, error, info, warn};
 
+use super::cron_preview::{timezone_name, to_local_time};
 use super::error::{Result, SchedulerError};
+use super::run_reports::{report_dir, report_path};
 use super::service::{generate_task_name, is_cognia_task, now_iso, SystemScheduler, TASK_PREFIX};
 use super::types::{
-    CreateSystemTaskInput, RunLevel, SchedulerCapabilities, SystemTask, SystemTaskAction,
-    SystemTaskId, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState, TaskRunResult,
+    CreateSystemTaskInput, RetryPolicy, RunLevel, SchedulerCapabilities, SystemTask,
+    SystemTaskAction, SystemTaskId, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState,
+    TaskNotifyOn, TaskRunResult,
 };
 
+/// Script name the run shim is invoked under, marking wrapped plists
+const RUN_SHIM_NAME: &str = "cognia-run";
+
 /// macOS launchd scheduler implementation
 pub struct MacOSScheduler {
     /// Whether launchctl is available
@@ -82,6 +88,12 @@ impl MacOSScheduler {
     fn generate_plist(task: &SystemTask) -> Result<String> {
         let label = Self::task_to_label(&task.name);
         let (program, args) = Self::build_program_args(&task.action)?;
+        let (program, args) = Self::wrap_with_run_shim(
+            program,
+            args,
+            &report_path(&task.id),
+            task.retry_policy.as_ref(),
+        );
 
         let mut plist = format!(
             r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -108,8 +120,18 @@ impl MacOSScheduler {
 
         // Add trigger configuration
         match &task.trigger {
-            SystemTaskTrigger::Cron { expression, .. } => {
-                if let Some(calendar) = Self::cron_to_calendar_interval(expression) {
+            SystemTaskTrigger::Cron {
+                expression,
+                timezone,
+            } => {
+                let timezone = timezone_name(timezone.as_deref());
+                if let Some(calendar) = Self::cron_to_calendar_interval(expression, timezone) {
+                    if let Some(tz) = timezone {
+                        plist.push_str(&format!(
+                            "    <!-- Cron times in {}, converted to local time -->\n",
+                            Self::escape_xml(tz)
+                        ));
+                    }
                     plist.push_str("    <key>StartCalendarInterval</key>\n");
                     plist.push_str(&calendar);
                 }
@@ -219,6 +241,50 @@ impl MacOSScheduler {
         Ok(plist)
     }
 
+    /// Run the program through a shell shim that appends each attempt's exit
+    /// status to the task's run report, and retries failed runs since launchd
+    /// has no retry setting for calendar jobs
+    fn wrap_with_run_shim(
+        program: String,
+        args: Vec<String>,
+        report: &Path,
+        policy: Option<&RetryPolicy>,
+    ) -> (String, Vec<String>) {
+        let delays = policy.map(RetryPolicy::retry_delays).unwrap_or_default();
+        let report_attempt = if delays.is_empty() {
+            "echo \"$(date +%s) $status\" >> \"$report\""
+        } else {
+            "echo \"$(date +%s) $status $attempt\" >> \"$report\""
+        };
+
+        let mut script = String::from("report=\"$1\"; shift; attempt=1; ");
+        if !delays.is_empty() {
+            let delay_list = delays
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(" ");
+            script.push_str(&format!(
+                "for delay in {}; do \"$@\"; status=$?; {}; [ \"$status\" -eq 0 ] && exit 0; echo \"Attempt failed, retrying in ${{delay}}s\" >&2; sleep \"$delay\"; attempt=$((attempt + 1)); done; ",
+                delay_list, report_attempt
+            ));
+        }
+        script.push_str(&format!(
+            "\"$@\"; status=$?; {}; exit $status",
+            report_attempt
+        ));
+
+        let mut shim_args = vec![
+            "-c".to_string(),
+            script,
+            RUN_SHIM_NAME.to_string(),
+            report.display().to_string(),
+            program,
+        ];
+        shim_args.extend(args);
+        ("/bin/sh".to_string(), shim_args)
+    }
+
     /// Build program and arguments from action
     fn build_program_args(action: &SystemTaskAction) -> Result<(String, Vec<String>)> {
         match action {
@@ -280,7 +346,11 @@ impl MacOSScheduler {
     }
 
     /// Convert cron expression to launchd CalendarInterval
-    fn cron_to_calendar_interval(expression: &str) -> Option<String> {
+    ///
+    /// launchd always evaluates `StartCalendarInterval` in local time, so a
+    /// fixed hour and minute in `timezone` is shifted to local time using
+    /// today's offsets. Hourly expressions only have their minute shifted.
+    fn cron_to_calendar_interval(expression: &str, timezone: Option<&str>) -> Option<String> {
         let parts: Vec<&str> = expression.trim().split_whitespace().collect();
         if parts.len() != 5 {
             return None;
@@ -289,6 +359,36 @@ impl MacOSScheduler {
         let (minute, hour, day, _month, weekday) =
             (parts[0], parts[1], parts[2], parts[3], parts[4]);
 
+        let local_time = timezone.and_then(|tz| {
+            // Only the minute matters hourly, which moves for half-hour offsets
+            let zoned_hour = if hour == "*" { 0 } else { hour.parse().ok()? };
+            let local = to_local_time(zoned_hour, minute.parse().ok()?, tz);
+            if local.is_none() {
+                warn!(
+                    "Cannot convert cron '{}' from {}, using local time",
+                    expression, tz
+                );
+            }
+            local
+        });
+        let (minute, hour) = match local_time {
+            Some((_, local_minute, _)) if hour == "*" => {
+                (local_minute.to_string(), hour.to_string())
+            }
+            Some((local_hour, local_minute, day_shift)) => {
+                if day_shift != 0 && (day != "*" || weekday != "*") {
+                    warn!(
+                        "Cron '{}' in {} falls on a different local day; days are not shifted",
+                        expression,
+                        timezone.unwrap_or_default()
+                    );
+                }
+                (local_minute.to_string(), local_hour.to_string())
+            }
+            None => (minute.to_string(), hour.to_string()),
+        };
+        let (minute, hour) = (minute.as_str(), hour.as_str());
+
         let mut dict = String::from("    <dict>\n");
 
         // Minute
@@ -419,7 +519,11 @@ impl MacOSScheduler {
     }
 
     fn parse_action_from_plist(content: &str) -> Option<SystemTaskAction> {
-        let args = Self::extract_program_arguments(content);
+        let mut args = Self::extract_program_arguments(content);
+        // Unwrap the run shim: /bin/sh -c <script> <name> <report> <program> <args>...
+        if args.get(3).map(String::as_str) == Some(RUN_SHIM_NAME) {
+            args.drain(..5);
+        }
         if args.is_empty() {
             return None;
         }
@@ -500,9 +604,10 @@ impl MacOSScheduler {
         }
 
         if let (Some(minute), Some(hour)) = (minute, hour) {
+            // launchd calendar intervals are in local time
             return Some(SystemTaskTrigger::Cron {
                 expression: format!("{minute} {hour} * * *"),
-                timezone: Some("UTC".to_string()),
+                timezone: None,
             });
         }
 
@@ -562,6 +667,9 @@ impl MacOSScheduler {
             next_run_at: None,
             last_result: None,
             metadata_state,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         })
     }
 }
@@ -595,6 +703,14 @@ impl SystemScheduler for MacOSScheduler {
         self.available
     }
 
+    fn reports_runs(&self, _retry_policy: Option<&RetryPolicy>) -> bool {
+        true
+    }
+
+    fn converts_cron_timezones(&self) -> bool {
+        true
+    }
+
     async fn create_task(&self, input: CreateSystemTaskInput) -> Result<SystemTask> {
         if !self.available {
             return Err(SchedulerError::NotAvailable(
@@ -631,12 +747,16 @@ impl SystemScheduler for MacOSScheduler {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: input.retry_policy,
         };
 
         task.requires_admin = task.check_requires_admin();
 
         // Generate and write plist
         let plist_content = Self::generate_plist(&task)?;
+        fs::create_dir_all(report_dir())?;
         fs::write(&plist_path, &plist_content)?;
 
         debug!("Created plist at {:?}", plist_path);
@@ -777,6 +897,84 @@ use chrono::{Datelike, Timelike};
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scheduler::run_reports::parse_reports;
+
+    #[test]
+    fn retry_shim_runs_original_command_until_attempts_are_used() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            backoff_secs: 0,
+            backoff_multiplier: 1.0,
+        };
+        let dir = tempfile::tempdir().expect("tempdir");
+        let counter = dir.path().join("attempts");
+        let report = dir.path().join("report.log");
+        let (program, args) = MacOSScheduler::wrap_with_run_shim(
+            "/bin/sh".to_string(),
+            vec![
+                "-c".to_string(),
+                format!("echo x >> '{}'; exit 1", counter.display()),
+            ],
+            &report,
+            Some(&policy),
+        );
+        assert_eq!(program, "/bin/sh");
+
+        let status = Command::new(&program)
+            .args(&args)
+            .status()
+            .expect("run shim");
+        assert_eq!(status.code(), Some(1));
+        let attempts = fs::read_to_string(&counter).expect("counter");
+        assert_eq!(attempts.lines().count(), 3);
+
+        let reports = parse_reports(&fs::read_to_string(&report).expect("report"));
+        let attempts = reports
+            .iter()
+            .map(|report| (report.exit_code, report.attempt))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attempts,
+            vec![(Some(1), Some(1)), (Some(1), Some(2)), (Some(1), Some(3))]
+        );
+    }
+
+    #[test]
+    fn run_shim_reports_runs_and_round_trips_the_action() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let report = dir.path().join("report.log");
+        let (program, args) = MacOSScheduler::wrap_with_run_shim(
+            "/bin/sh".to_string(),
+            vec!["-c".to_string(), "exit 0".to_string()],
+            &report,
+            None,
+        );
+        let status = Command::new(&program)
+            .args(&args)
+            .status()
+            .expect("run shim");
+        assert_eq!(status.code(), Some(0));
+        let reports = parse_reports(&fs::read_to_string(&report).expect("report"));
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].success());
+        assert_eq!(reports[0].attempt, None);
+
+        let mut plist = String::from("<key>ProgramArguments</key>\n<array>\n");
+        for arg in std::iter::once(&program).chain(&args) {
+            plist.push_str(&format!(
+                "<string>{}</string>\n",
+                MacOSScheduler::escape_xml(arg)
+            ));
+        }
+        plist.push_str("</array>\n");
+        match MacOSScheduler::parse_action_from_plist(&plist).expect("action") {
+            SystemTaskAction::RunCommand { command, args, .. } => {
+                assert_eq!(command, "/bin/sh");
+                assert_eq!(args, vec!["-c".to_string(), "exit 0".to_string()]);
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
 
     #[test]
     fn parses_plist_trigger_and_action() {
@@ -803,4 +1001,36 @@ mod tests {
         ));
         assert!(matches!(action, SystemTaskAction::RunCommand { .. }));
     }
+
+    #[test]
+    fn cron_calendar_interval_converts_timezone_to_local_time() {
+        let (hour, minute, _) = to_local_time(9, 30, "UTC").expect("local time");
+        let calendar =
+            MacOSScheduler::cron_to_calendar_interval("30 9 * * *", Some("UTC")).expect("calendar");
+        assert_eq!(
+            MacOSScheduler::extract_key_int(&calendar, "Hour"),
+            Some(hour as u64)
+        );
+        assert_eq!(
+            MacOSScheduler::extract_key_int(&calendar, "Minute"),
+            Some(minute as u64)
+        );
+
+        let local =
+            MacOSScheduler::cron_to_calendar_interval("30 9 * * *", None).expect("calendar");
+        assert_eq!(MacOSScheduler::extract_key_int(&local, "Hour"), Some(9));
+    }
+
+    #[test]
+    fn cron_calendar_interval_shifts_hourly_minute_to_local_time() {
+        let (_, minute, _) = to_local_time(0, 15, "Asia/Kolkata").expect("local time");
+        let calendar =
+            MacOSScheduler::cron_to_calendar_interval("15 * * * *", Some("Asia/Kolkata"))
+                .expect("calendar");
+        assert_eq!(
+            MacOSScheduler::extract_key_int(&calendar, "Minute"),
+            Some(minute as u64)
+        );
+        assert_eq!(MacOSScheduler::extract_key_int(&calendar, "Hour"), None);
+    }
 }
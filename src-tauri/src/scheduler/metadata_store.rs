@@ -1,7 +1,8 @@
 //! Persistent metadata store for system scheduler tasks.
 //!
 //! Platform schedulers can lose rich task configuration when listing/querying tasks.
-//! This store keeps canonical trigger/action configuration for Cognia-managed tasks.
+//! This store keeps canonical trigger/action configuration for Cognia-managed tasks,
+//! along with the history of runs started through Cognia and scheduler settings.
 
 use std::fs;
 use std::path::PathBuf;
@@ -12,13 +13,17 @@ use rusqlite::{params, Connection, OptionalExtension, Row};
 use super::error::{Result, SchedulerError};
 use super::service::now_iso;
 use super::types::{
-    RunLevel, SystemTask, SystemTaskAction, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState,
+    RetryPolicy, RunLevel, SystemTask, SystemTaskAction, SystemTaskStatus, SystemTaskTrigger,
+    TaskMetadataState, TaskNotifyOn, TaskRunRecord,
 };
 
 fn normalize_id(id: &str) -> String {
     id.trim().trim_start_matches('\\').to_lowercase()
 }
 
+/// Settings key holding the number of runs kept per task
+const RUN_HISTORY_LIMIT_KEY: &str = "run_history_limit";
+
 fn map_sql_err(err: rusqlite::Error) -> SchedulerError {
     SchedulerError::Internal(format!("scheduler metadata sqlite error: {err}"))
 }
@@ -48,6 +53,8 @@ impl SchedulerMetadataStore {
                 action_json TEXT NOT NULL,
                 run_level_json TEXT NOT NULL,
                 tags_json TEXT NOT NULL,
+                notify_on_json TEXT,
+                retry_policy_json TEXT,
                 created_at TEXT,
                 updated_at TEXT,
                 last_seen_at TEXT NOT NULL
@@ -57,15 +64,69 @@ impl SchedulerMetadataStore {
                 ON scheduler_task_metadata(normalized_id);
             CREATE INDEX IF NOT EXISTS idx_scheduler_task_metadata_name
                 ON scheduler_task_metadata(name);
+
+            CREATE TABLE IF NOT EXISTS scheduler_task_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id TEXT NOT NULL,
+                normalized_id TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                exit_code INTEGER,
+                stdout TEXT,
+                stderr TEXT,
+                error TEXT,
+                duration_ms INTEGER,
+                attempt INTEGER
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_scheduler_task_runs_normalized_id
+                ON scheduler_task_runs(normalized_id, id);
+
+            CREATE TABLE IF NOT EXISTS scheduler_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
             "#,
         )
         .map_err(map_sql_err)?;
+        Self::migrate(&conn)?;
 
         Ok(Self {
             conn: Mutex::new(conn),
         })
     }
 
+    /// Add columns introduced after the tables were first created
+    fn migrate(conn: &Connection) -> Result<()> {
+        const ADDED_COLUMNS: [(&str, &str, &str); 3] = [
+            ("scheduler_task_metadata", "notify_on_json", "TEXT"),
+            ("scheduler_task_metadata", "retry_policy_json", "TEXT"),
+            ("scheduler_task_runs", "attempt", "INTEGER"),
+        ];
+
+        for (table, column, column_type) in ADDED_COLUMNS {
+            let exists = conn
+                .prepare(&format!(
+                    "SELECT 1 FROM pragma_table_info('{}') WHERE name = '{}'",
+                    table, column
+                ))
+                .and_then(|mut stmt| stmt.exists([]))
+                .map_err(map_sql_err)?;
+            if !exists {
+                conn.execute(
+                    &format!(
+                        "ALTER TABLE {} ADD COLUMN {} {}",
+                        table, column, column_type
+                    ),
+                    [],
+                )
+                .map_err(map_sql_err)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn upsert_task(&self, task: &SystemTask) -> Result<()> {
         let trigger_json = serde_json::to_string(&task.trigger)
             .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
@@ -75,6 +136,14 @@ impl SchedulerMetadataStore {
             .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
         let tags_json = serde_json::to_string(&task.tags)
             .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
+        let notify_on_json = serde_json::to_string(&task.notify_on)
+            .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
+        let retry_policy_json = task
+            .retry_policy
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
 
         let conn = self.conn.lock();
         conn.execute(
@@ -82,8 +151,8 @@ impl SchedulerMetadataStore {
             INSERT INTO scheduler_task_metadata (
                 task_id, normalized_id, name, description,
                 trigger_json, action_json, run_level_json, tags_json,
-                created_at, updated_at, last_seen_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                created_at, updated_at, last_seen_at, notify_on_json, retry_policy_json
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             ON CONFLICT(task_id) DO UPDATE SET
                 normalized_id = excluded.normalized_id,
                 name = excluded.name,
@@ -94,7 +163,9 @@ impl SchedulerMetadataStore {
                 tags_json = excluded.tags_json,
                 created_at = COALESCE(scheduler_task_metadata.created_at, excluded.created_at),
                 updated_at = excluded.updated_at,
-                last_seen_at = excluded.last_seen_at
+                last_seen_at = excluded.last_seen_at,
+                notify_on_json = excluded.notify_on_json,
+                retry_policy_json = excluded.retry_policy_json
             "#,
             params![
                 task.id,
@@ -108,6 +179,8 @@ impl SchedulerMetadataStore {
                 task.created_at,
                 task.updated_at,
                 now_iso(),
+                notify_on_json,
+                retry_policy_json,
             ],
         )
         .map_err(map_sql_err)?;
@@ -123,6 +196,135 @@ impl SchedulerMetadataStore {
             params![task_id, normalized],
         )
         .map_err(map_sql_err)?;
+        conn.execute(
+            "DELETE FROM scheduler_task_runs WHERE normalized_id = ?1",
+            params![normalized],
+        )
+        .map_err(map_sql_err)?;
+        Ok(())
+    }
+
+    /// Whether any stored task asks for run notifications
+    pub fn has_notifying_tasks(&self) -> Result<bool> {
+        let never = serde_json::to_string(&TaskNotifyOn::Never)
+            .map_err(|e| SchedulerError::Serialization(e.to_string()))?;
+        let conn = self.conn.lock();
+        conn.query_row(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM scheduler_task_metadata
+                WHERE notify_on_json IS NOT NULL AND notify_on_json != ?1
+            )
+            "#,
+            params![never],
+            |row| row.get(0),
+        )
+        .map_err(map_sql_err)
+    }
+
+    /// Record a run, keeping only the `keep` most recent runs of the task
+    pub fn insert_task_run(&self, run: &TaskRunRecord, keep: usize) -> Result<()> {
+        let normalized = normalize_id(&run.task_id);
+        let conn = self.conn.lock();
+        conn.execute(
+            r#"
+            INSERT INTO scheduler_task_runs (
+                task_id, normalized_id, started_at, finished_at, success,
+                exit_code, stdout, stderr, error, duration_ms, attempt
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            "#,
+            params![
+                run.task_id,
+                normalized,
+                run.started_at,
+                run.finished_at,
+                run.success,
+                run.exit_code,
+                run.stdout,
+                run.stderr,
+                run.error,
+                run.duration_ms.map(|ms| ms as i64),
+                run.attempt,
+            ],
+        )
+        .map_err(map_sql_err)?;
+        conn.execute(
+            r#"
+            DELETE FROM scheduler_task_runs
+            WHERE normalized_id = ?1 AND id NOT IN (
+                SELECT id FROM scheduler_task_runs
+                WHERE normalized_id = ?1
+                ORDER BY id DESC
+                LIMIT ?2
+            )
+            "#,
+            params![normalized, keep as i64],
+        )
+        .map_err(map_sql_err)?;
+        Ok(())
+    }
+
+    /// Most recent runs of a task, newest first
+    pub fn get_task_runs(&self, task_id: &str, limit: usize) -> Result<Vec<TaskRunRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT
+                    task_id, started_at, finished_at, success, exit_code,
+                    stdout, stderr, error, duration_ms, attempt
+                FROM scheduler_task_runs
+                WHERE normalized_id = ?1
+                ORDER BY id DESC
+                LIMIT ?2
+                "#,
+            )
+            .map_err(map_sql_err)?;
+        let runs = stmt
+            .query_map(params![normalize_id(task_id), limit as i64], |row| {
+                Ok(TaskRunRecord {
+                    task_id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    finished_at: row.get(2)?,
+                    success: row.get(3)?,
+                    exit_code: row.get(4)?,
+                    stdout: row.get(5)?,
+                    stderr: row.get(6)?,
+                    error: row.get(7)?,
+                    duration_ms: row.get::<_, Option<i64>>(8)?.map(|ms| ms as u64),
+                    attempt: row.get(9)?,
+                })
+            })
+            .map_err(map_sql_err)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(map_sql_err)?;
+        Ok(runs)
+    }
+
+    /// Saved number of runs kept per task, if one was set
+    pub fn get_run_history_limit(&self) -> Result<Option<usize>> {
+        let conn = self.conn.lock();
+        let value: Option<String> = conn
+            .query_row(
+                "SELECT value FROM scheduler_settings WHERE key = ?1",
+                params![RUN_HISTORY_LIMIT_KEY],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(map_sql_err)?;
+        Ok(value.and_then(|value| value.parse().ok()))
+    }
+
+    pub fn set_run_history_limit(&self, limit: usize) -> Result<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            r#"
+            INSERT INTO scheduler_settings (key, value) VALUES (?1, ?2)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value
+            "#,
+            params![RUN_HISTORY_LIMIT_KEY, limit.to_string()],
+        )
+        .map_err(map_sql_err)?;
         Ok(())
     }
 
@@ -139,7 +341,8 @@ impl SchedulerMetadataStore {
                 r#"
                 SELECT
                     task_id, name, description, trigger_json, action_json,
-                    run_level_json, tags_json, created_at, updated_at
+                    run_level_json, tags_json, created_at, updated_at, notify_on_json,
+                    retry_policy_json
                 FROM scheduler_task_metadata
                 WHERE task_id = ?1 OR normalized_id = ?2
                 LIMIT 1
@@ -160,7 +363,8 @@ impl SchedulerMetadataStore {
                     r#"
                     SELECT
                         task_id, name, description, trigger_json, action_json,
-                        run_level_json, tags_json, created_at, updated_at
+                        run_level_json, tags_json, created_at, updated_at, notify_on_json,
+                    retry_policy_json
                     FROM scheduler_task_metadata
                     WHERE name = ?1
                     ORDER BY updated_at DESC
@@ -201,6 +405,13 @@ impl SchedulerMetadataStore {
         let description: Option<String> = row.get(2)?;
         let created_at: Option<String> = row.get(7)?;
         let updated_at: Option<String> = row.get(8)?;
+        let notify_on = row
+            .get::<_, Option<String>>(9)?
+            .and_then(|json| serde_json::from_str::<TaskNotifyOn>(&json).ok())
+            .unwrap_or_default();
+        let retry_policy = row
+            .get::<_, Option<String>>(10)?
+            .and_then(|json| serde_json::from_str::<RetryPolicy>(&json).ok());
 
         let mut task = SystemTask {
             id,
@@ -218,6 +429,9 @@ impl SchedulerMetadataStore {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on,
+            retry_policy,
         };
         task.requires_admin = task.check_requires_admin();
         Ok(task)
@@ -251,6 +465,9 @@ mod tests {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         }
     }
 
@@ -299,4 +516,150 @@ mod tests {
             .expect("exists by name");
         assert_eq!(by_name.name, "Cognia Task");
     }
+
+    fn make_run(task_id: &str, exit_code: i32) -> TaskRunRecord {
+        TaskRunRecord {
+            task_id: task_id.to_string(),
+            started_at: now_iso(),
+            finished_at: now_iso(),
+            success: exit_code == 0,
+            exit_code: Some(exit_code),
+            stdout: Some(format!("run {exit_code}")),
+            stderr: None,
+            error: None,
+            duration_ms: Some(5),
+            attempt: None,
+        }
+    }
+
+    #[test]
+    fn records_and_prunes_task_runs() {
+        let dir = tempdir().expect("tempdir");
+        let store =
+            SchedulerMetadataStore::new(dir.path().join("scheduler_runs.db")).expect("store");
+
+        for exit_code in 0..5 {
+            store
+                .insert_task_run(&make_run("TaskA", exit_code), 3)
+                .expect("insert");
+        }
+        store
+            .insert_task_run(&make_run("TaskB", 0), 3)
+            .expect("insert");
+
+        let runs = store.get_task_runs("taska", 10).expect("runs");
+        let exit_codes: Vec<_> = runs.iter().map(|run| run.exit_code).collect();
+        assert_eq!(exit_codes, vec![Some(4), Some(3), Some(2)]);
+        assert_eq!(runs[0].stdout.as_deref(), Some("run 4"));
+        assert!(!runs[0].success);
+        assert_eq!(runs[0].duration_ms, Some(5));
+        assert_eq!(store.get_task_runs("TaskA", 1).expect("runs").len(), 1);
+
+        store.delete_task("TaskA").expect("delete");
+        assert!(store.get_task_runs("TaskA", 10).expect("runs").is_empty());
+        assert_eq!(store.get_task_runs("TaskB", 10).expect("runs").len(), 1);
+    }
+
+    #[test]
+    fn persists_notify_on_and_migrates_old_tables() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("scheduler_meta_notify.db");
+        Connection::open(&db_path)
+            .expect("open")
+            .execute_batch(
+                r#"
+                CREATE TABLE scheduler_task_metadata (
+                    task_id TEXT PRIMARY KEY,
+                    normalized_id TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    description TEXT,
+                    trigger_json TEXT NOT NULL,
+                    action_json TEXT NOT NULL,
+                    run_level_json TEXT NOT NULL,
+                    tags_json TEXT NOT NULL,
+                    created_at TEXT,
+                    updated_at TEXT,
+                    last_seen_at TEXT NOT NULL
+                );
+                "#,
+            )
+            .expect("create old table");
+
+        let store = SchedulerMetadataStore::new(db_path).expect("store");
+        let mut task = make_task("cognia-task-notify", "Notify");
+        store.upsert_task(&task).expect("upsert silent");
+        assert!(!store.has_notifying_tasks().expect("has notifying"));
+
+        task.notify_on = TaskNotifyOn::Failure;
+        store.upsert_task(&task).expect("upsert");
+        assert!(store.has_notifying_tasks().expect("has notifying"));
+
+        let loaded = store
+            .get_task_metadata("cognia-task-notify", None)
+            .expect("get")
+            .expect("task exists");
+        assert_eq!(loaded.notify_on, TaskNotifyOn::Failure);
+    }
+
+    #[test]
+    fn persists_retry_policy_and_run_attempts_after_migration() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("scheduler_meta_retry.db");
+        Connection::open(&db_path)
+            .expect("open")
+            .execute_batch(
+                r#"
+                CREATE TABLE scheduler_task_runs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    task_id TEXT NOT NULL,
+                    normalized_id TEXT NOT NULL,
+                    started_at TEXT NOT NULL,
+                    finished_at TEXT NOT NULL,
+                    success INTEGER NOT NULL,
+                    exit_code INTEGER,
+                    stdout TEXT,
+                    stderr TEXT,
+                    error TEXT,
+                    duration_ms INTEGER
+                );
+                "#,
+            )
+            .expect("create old runs table");
+
+        let store = SchedulerMetadataStore::new(db_path).expect("store");
+        let mut task = make_task("cognia-task-retry", "Retry");
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            backoff_secs: 30,
+            backoff_multiplier: 1.5,
+        };
+        task.retry_policy = Some(policy.clone());
+        store.upsert_task(&task).expect("upsert");
+        let loaded = store
+            .get_task_metadata("cognia-task-retry", None)
+            .expect("get")
+            .expect("task exists");
+        assert_eq!(loaded.retry_policy, Some(policy));
+
+        let mut run = make_run("cognia-task-retry", 1);
+        run.attempt = Some(2);
+        store.insert_task_run(&run, 10).expect("insert");
+        let runs = store.get_task_runs("cognia-task-retry", 10).expect("runs");
+        assert_eq!(runs[0].attempt, Some(2));
+    }
+
+    #[test]
+    fn persists_run_history_limit() {
+        let dir = tempdir().expect("tempdir");
+        let db_path = dir.path().join("scheduler_meta_settings.db");
+        let store = SchedulerMetadataStore::new(db_path.clone()).expect("store");
+        assert_eq!(store.get_run_history_limit().expect("get"), None);
+
+        store.set_run_history_limit(5).expect("set");
+        store.set_run_history_limit(7).expect("set again");
+        drop(store);
+
+        let reopened = SchedulerMetadataStore::new(db_path).expect("reopen");
+        assert_eq!(reopened.get_run_history_limit().expect("get"), Some(7));
+    }
 }
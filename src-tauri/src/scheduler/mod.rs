@@ -12,8 +12,11 @@
 //! - Risk assessment and confirmation
 //! - Admin elevation handling
 
+pub mod cron_preview;
 pub mod error;
 pub mod metadata_store;
+pub mod run_reports;
+pub mod run_watcher;
 pub mod service;
 pub mod types;
 
@@ -30,14 +33,21 @@ use chrono::{Duration as ChronoDuration, Utc};
 use log::{debug, info, warn};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+pub use cron_preview::preview_cron_schedule;
 pub use error::{Result, SchedulerError};
 use metadata_store::SchedulerMetadataStore;
+use run_watcher::{RunHistoryWatcher, TaskRunNotification};
+use service::parse_datetime;
 pub use service::SystemScheduler;
 pub use types::*;
 
+/// Default number of runs kept per task in the run history
+pub const DEFAULT_RUN_HISTORY_LIMIT: usize = 50;
+
 #[derive(Clone)]
 #[allow(dead_code)]
 enum PendingOperation {
@@ -71,6 +81,12 @@ pub struct SchedulerState {
     metadata_store: Option<SchedulerMetadataStore>,
     /// Pending confirmations keyed by confirmation_id
     pending_confirmations: RwLock<HashMap<String, PendingConfirmationRecord>>,
+    /// Latest run of each task seen by Cognia, keyed by task ID, for platforms
+    /// that do not report run history
+    recorded_runs: RwLock<HashMap<SystemTaskId, (String, TaskRunResult)>>,
+    run_watcher: RwLock<RunHistoryWatcher>,
+    /// Runs kept per task in the metadata store
+    run_history_limit: AtomicUsize,
 }
 
 impl SchedulerState {
@@ -92,10 +108,30 @@ impl SchedulerState {
             scheduler.capabilities().backend
         );
 
+        Self::with_scheduler(scheduler, metadata_store)
+    }
+
+    fn with_scheduler(
+        scheduler: Arc<dyn SystemScheduler>,
+        metadata_store: Option<SchedulerMetadataStore>,
+    ) -> Self {
+        let run_history_limit = metadata_store
+            .as_ref()
+            .and_then(|store| {
+                store.get_run_history_limit().unwrap_or_else(|error| {
+                    warn!("Failed to load scheduler run history limit: {}", error);
+                    None
+                })
+            })
+            .unwrap_or(DEFAULT_RUN_HISTORY_LIMIT);
+
         Self {
             scheduler,
             metadata_store,
             pending_confirmations: RwLock::new(HashMap::new()),
+            recorded_runs: RwLock::new(HashMap::new()),
+            run_watcher: RwLock::new(RunHistoryWatcher::new()),
+            run_history_limit: AtomicUsize::new(run_history_limit.max(1)),
         }
     }
 
@@ -125,6 +161,29 @@ impl SchedulerState {
         self.scheduler.capabilities()
     }
 
+    /// Get the current task count against the platform task limit
+    pub async fn usage(&self) -> Result<SchedulerUsage> {
+        let max_tasks = self.scheduler.capabilities().max_tasks;
+        let task_count = self.scheduler.list_tasks().await?.len();
+        Ok(SchedulerUsage::new(task_count, max_tasks))
+    }
+
+    /// Fail with `LimitReached` when the platform cannot take another task
+    async fn ensure_task_capacity(&self) -> Result<()> {
+        if self.scheduler.capabilities().max_tasks == 0 {
+            return Ok(());
+        }
+        let usage = self.usage().await?;
+        if usage.has_capacity() {
+            Ok(())
+        } else {
+            Err(SchedulerError::LimitReached {
+                current: usage.task_count,
+                max: usage.max_tasks,
+            })
+        }
+    }
+
     /// Check if scheduler is available
     pub fn is_available(&self) -> bool {
         self.scheduler.is_available()
@@ -170,6 +229,9 @@ impl SchedulerState {
             next_run_at: None,
             last_result: None,
             metadata_state,
+            external: false,
+            notify_on: input.notify_on,
+            retry_policy: input.retry_policy.clone(),
         }
     }
 
@@ -189,6 +251,8 @@ impl SchedulerState {
         confirmation_id: String,
     ) -> TaskConfirmationRequest {
         let requires_admin = task.check_requires_admin() || self.scheduler.requires_admin(task);
+        let mut warnings = task.generate_warnings();
+        warnings.extend(self.trigger_warnings(&task.trigger));
         TaskConfirmationRequest {
             confirmation_id,
             task_id: Some(task.id.clone()),
@@ -196,7 +260,7 @@ impl SchedulerState {
             operation,
             risk_level: task.calculate_risk_level(),
             requires_admin,
-            warnings: task.generate_warnings(),
+            warnings,
             details: TaskConfirmationDetails {
                 task_name: task.name.clone(),
                 action_summary: Some(Self::summarize_action(&task.action)),
@@ -296,6 +360,45 @@ impl SchedulerState {
         task
     }
 
+    /// Whether a task was created through Cognia.
+    ///
+    /// The task ID must follow the naming used by the platform schedulers for
+    /// Cognia tasks and, when the metadata store is available, be recorded
+    /// there. Earlier versions persisted every listed task, so the naming
+    /// check keeps those external tasks from counting as owned.
+    fn is_owned_task(&self, task_id: &str) -> bool {
+        let id = task_id.trim_start_matches('\\').to_lowercase();
+        let cognia_named = ["cognia-task-", "cognia_", "com.cognia.task."]
+            .iter()
+            .any(|prefix| id.starts_with(prefix));
+        if !cognia_named {
+            return false;
+        }
+
+        match &self.metadata_store {
+            Some(store) => match store.get_task_metadata(task_id, None) {
+                Ok(metadata) => metadata.is_some(),
+                Err(error) => {
+                    warn!("Failed checking ownership of task {}: {}", task_id, error);
+                    false
+                }
+            },
+            None => true,
+        }
+    }
+
+    /// Reject operations that would modify a task Cognia did not create
+    fn ensure_owned_task(&self, task_id: &str) -> Result<()> {
+        if self.is_owned_task(task_id) {
+            Ok(())
+        } else {
+            Err(SchedulerError::PermissionDenied(format!(
+                "Task {} was not created by Cognia and is read-only",
+                task_id
+            )))
+        }
+    }
+
     fn persist_task_metadata(&self, task: &SystemTask) {
         if let Some(store) = &self.metadata_store {
             if let Err(error) = store.upsert_task(task) {
@@ -318,12 +421,45 @@ impl SchedulerState {
         }
     }
 
+    /// Reject cron timezones that are unknown or that the platform scheduler
+    /// cannot convert to local time
+    pub fn validate_trigger_timezone(&self, trigger: &SystemTaskTrigger) -> Result<()> {
+        cron_preview::validate_trigger_timezone(trigger)?;
+        if self.scheduler.converts_cron_timezones() {
+            cron_preview::validate_local_time_conversion(trigger)?;
+        }
+        Ok(())
+    }
+
+    /// Warnings about a trigger that are specific to the platform scheduler
+    pub fn trigger_warnings(&self, trigger: &SystemTaskTrigger) -> Vec<String> {
+        if self.scheduler.converts_cron_timezones() {
+            cron_preview::local_time_conversion_warnings(trigger)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Reject task settings the platform schedulers cannot honor
+    fn validate_input(&self, input: &CreateSystemTaskInput) -> Result<()> {
+        self.validate_trigger_timezone(&input.trigger)?;
+        if let Some(policy) = &input.retry_policy {
+            policy.validate()?;
+        }
+        Ok(())
+    }
+
     /// Create a task with confirmation flow
     pub async fn create_task_with_confirmation(
         &self,
         input: CreateSystemTaskInput,
         confirmed: bool,
     ) -> Result<std::result::Result<SystemTask, TaskConfirmationRequest>> {
+        // Checked before asking for confirmation so the user is not asked to
+        // approve a task the platform would reject
+        self.validate_input(&input)?;
+        self.ensure_task_capacity().await?;
+
         let temp_task = Self::make_temp_task(
             SystemTask::generate_id(),
             &input,
@@ -362,8 +498,10 @@ impl SchedulerState {
         }
 
         // Create the task
+        let notify_on = input.notify_on;
         let mut task = self.scheduler.create_task(input).await?;
         task.metadata_state = TaskMetadataState::Full;
+        task.notify_on = notify_on;
         self.persist_task_metadata(&task);
         Ok(Ok(task))
     }
@@ -401,14 +539,19 @@ impl SchedulerState {
 
         match record.operation {
             PendingOperation::Create { input } => {
+                self.ensure_task_capacity().await?;
+                let notify_on = input.notify_on;
                 let mut task = self.scheduler.create_task(input).await?;
                 task.metadata_state = TaskMetadataState::Full;
+                task.notify_on = notify_on;
                 self.persist_task_metadata(&task);
                 Ok(Some(task))
             }
             PendingOperation::Update { task_id, input } => {
+                let notify_on = input.notify_on;
                 let mut task = self.scheduler.update_task(&task_id, input).await?;
                 task.metadata_state = TaskMetadataState::Full;
+                task.notify_on = notify_on;
                 self.persist_task_metadata(&task);
                 Ok(Some(task))
             }
@@ -428,9 +571,10 @@ impl SchedulerState {
                 }
             }
             PendingOperation::RunNow { task_id } => {
-                let _ = self.scheduler.run_task_now(&task_id).await?;
+                self.run_and_record(&task_id).await?;
                 if let Some(task) = self.scheduler.get_task(&task_id).await? {
-                    let enriched = self.enrich_task_with_metadata(task).await;
+                    let mut enriched = self.enrich_task_with_metadata(task).await;
+                    self.apply_recorded_run(&mut enriched).await;
                     if enriched.metadata_state == TaskMetadataState::Full {
                         self.persist_task_metadata(&enriched);
                     }
@@ -468,6 +612,8 @@ impl SchedulerState {
         input: CreateSystemTaskInput,
         confirmed: bool,
     ) -> Result<std::result::Result<SystemTask, TaskConfirmationRequest>> {
+        self.ensure_owned_task(id)?;
+        self.validate_input(&input)?;
         let temp_task = Self::make_temp_task(
             id.to_string(),
             &input,
@@ -508,14 +654,17 @@ impl SchedulerState {
             ));
         }
 
+        let notify_on = input.notify_on;
         let mut task = self.scheduler.update_task(id, input).await?;
         task.metadata_state = TaskMetadataState::Full;
+        task.notify_on = notify_on;
         self.persist_task_metadata(&task);
         Ok(Ok(task))
     }
 
     /// Delete a task
     pub async fn delete_task(&self, id: &str) -> Result<bool> {
+        self.ensure_owned_task(id)?;
         let deleted = self.scheduler.delete_task(id).await?;
         if deleted {
             self.delete_task_metadata(id);
@@ -526,13 +675,7 @@ impl SchedulerState {
     /// Get a task by ID
     pub async fn get_task(&self, id: &str) -> Result<Option<SystemTask>> {
         match self.scheduler.get_task(id).await? {
-            Some(task) => {
-                let enriched = self.enrich_task_with_metadata(task).await;
-                if enriched.metadata_state == TaskMetadataState::Full {
-                    self.persist_task_metadata(&enriched);
-                }
-                Ok(Some(enriched))
-            }
+            Some(task) => Ok(Some(self.enrich_listed_task(task).await)),
             None => {
                 if let Some(store) = &self.metadata_store {
                     if let Some(mut metadata_task) = store.get_task_metadata(id, None)? {
@@ -547,33 +690,338 @@ impl SchedulerState {
         }
     }
 
-    /// List all tasks
-    pub async fn list_tasks(&self) -> Result<Vec<SystemTask>> {
+    /// List tasks created through Cognia
+    ///
+    /// With `include_external`, tasks created outside Cognia are included and
+    /// marked `external`.
+    pub async fn list_tasks(&self, include_external: bool) -> Result<Vec<SystemTask>> {
         let tasks = self.scheduler.list_tasks().await?;
         let mut enriched = Vec::with_capacity(tasks.len());
         for task in tasks {
-            let merged = self.enrich_task_with_metadata(task).await;
-            if merged.metadata_state == TaskMetadataState::Full {
-                self.persist_task_metadata(&merged);
+            if !include_external && !self.is_owned_task(&task.id) {
+                continue;
             }
-            enriched.push(merged);
+            enriched.push(self.enrich_listed_task(task).await);
         }
         Ok(enriched)
     }
 
+    /// Merge stored metadata into an owned task, or mark the task external.
+    ///
+    /// External tasks are never persisted, so listing them does not adopt them.
+    async fn enrich_listed_task(&self, task: SystemTask) -> SystemTask {
+        if !self.is_owned_task(&task.id) {
+            return SystemTask {
+                external: true,
+                ..task
+            };
+        }
+
+        let mut enriched = self.enrich_task_with_metadata(task).await;
+        if enriched.metadata_state == TaskMetadataState::Full {
+            self.persist_task_metadata(&enriched);
+        }
+        self.ingest_run_reports(&enriched.id).await;
+        self.apply_recorded_run(&mut enriched).await;
+        enriched
+    }
+
+    async fn record_run(&self, task_id: &str, result: TaskRunResult) {
+        let mut runs = self.recorded_runs.write().await;
+        runs.insert(task_id.to_string(), (Self::now_iso(), result));
+    }
+
+    /// Add the attempts reported by a task's wrapper to its run history and
+    /// return how many there were
+    ///
+    /// Reports carry no start time, so runs are recorded as starting when
+    /// they finished.
+    async fn ingest_run_reports(&self, task_id: &str) -> usize {
+        let reports = run_reports::take_reports(task_id);
+        let Some(latest) = reports.last() else {
+            return 0;
+        };
+
+        if let Some(store) = &self.metadata_store {
+            for report in &reports {
+                let mut run = TaskRunRecord::new(
+                    task_id,
+                    report.finished_at.clone(),
+                    report.finished_at.clone(),
+                    &report.to_result(),
+                );
+                run.attempt = report.attempt;
+                if let Err(error) = store.insert_task_run(&run, self.run_history_limit()) {
+                    warn!("Failed to record run of task {}: {}", task_id, error);
+                }
+            }
+        }
+        self.recorded_runs.write().await.insert(
+            task_id.to_string(),
+            (latest.finished_at.clone(), latest.to_result()),
+        );
+        reports.len()
+    }
+
+    /// Latest run seen by Cognia, falling back to the stored run history
+    /// after a restart
+    async fn latest_recorded_run(&self, task_id: &str) -> Option<(String, TaskRunResult)> {
+        if let Some(run) = self.recorded_runs.read().await.get(task_id) {
+            return Some(run.clone());
+        }
+        let store = self.metadata_store.as_ref()?;
+        let run = store.get_task_runs(task_id, 1).ok()?.into_iter().next()?;
+        Some((run.finished_at.clone(), run.to_result()))
+    }
+
+    /// Fill in the last run from runs seen by Cognia unless the platform
+    /// scheduler reports a newer one
+    async fn apply_recorded_run(&self, task: &mut SystemTask) {
+        let Some((ran_at, result)) = self.latest_recorded_run(&task.id).await else {
+            return;
+        };
+        let platform_run = task.last_run_at.as_deref().and_then(parse_datetime);
+        let platform_is_newer = match (platform_run, parse_datetime(&ran_at)) {
+            (Some(platform_run), Some(recorded_run)) => platform_run > recorded_run,
+            // Without comparable times, a result reported by the platform wins
+            _ => task.last_result.is_some(),
+        };
+        if !platform_is_newer {
+            task.last_run_at = Some(ran_at);
+            task.last_result = Some(result);
+        }
+    }
+
+    /// Check owned tasks for runs completed since the last check and return
+    /// the notifications their `notify_on` settings ask for
+    ///
+    /// Skips listing tasks entirely while no task has notifications enabled.
+    pub async fn collect_run_notifications(&self) -> Result<Vec<TaskRunNotification>> {
+        let notifying = match &self.metadata_store {
+            Some(store) => store.has_notifying_tasks()?,
+            None => false,
+        };
+        if !notifying {
+            return Ok(Vec::new());
+        }
+        let tasks = self.list_tasks(false).await?;
+        let completed = self.run_watcher.write().await.observe(&tasks);
+        Ok(completed
+            .iter()
+            .filter_map(TaskRunNotification::for_task)
+            .collect())
+    }
+
     /// Enable a task
     pub async fn enable_task(&self, id: &str) -> Result<bool> {
+        self.ensure_owned_task(id)?;
         self.scheduler.enable_task(id).await
     }
 
     /// Disable a task
     pub async fn disable_task(&self, id: &str) -> Result<bool> {
+        self.ensure_owned_task(id)?;
         self.scheduler.disable_task(id).await
     }
 
+    /// Bundle task definitions for import on another machine
+    ///
+    /// Exports the given tasks, or every Cognia task with complete metadata
+    /// when `task_ids` is `None`.
+    pub async fn export_tasks(&self, task_ids: Option<Vec<String>>) -> Result<TaskBundle> {
+        let tasks = match task_ids {
+            Some(ids) => {
+                let mut tasks = Vec::with_capacity(ids.len());
+                for id in ids {
+                    self.ensure_owned_task(&id)?;
+                    let task = self
+                        .get_task(&id)
+                        .await?
+                        .ok_or_else(|| SchedulerError::TaskNotFound(id.clone()))?;
+                    if task.metadata_state != TaskMetadataState::Full {
+                        return Err(SchedulerError::InvalidConfig(format!(
+                            "Task {} has incomplete metadata and cannot be exported",
+                            id
+                        )));
+                    }
+                    tasks.push(task);
+                }
+                tasks
+            }
+            None => self
+                .list_tasks(false)
+                .await?
+                .into_iter()
+                .filter(|task| task.metadata_state == TaskMetadataState::Full)
+                .collect(),
+        };
+
+        Ok(TaskBundle {
+            version: TASK_BUNDLE_VERSION,
+            exported_at: Self::now_iso(),
+            tasks: tasks.into_iter().map(Self::task_to_input).collect(),
+        })
+    }
+
+    /// Recreate the tasks of a bundle
+    ///
+    /// A task whose name is already taken is skipped, or with `overwrite`
+    /// replaces the existing Cognia task of that name. Tasks that need
+    /// confirmation go through the usual confirmation flow and are returned
+    /// as pending unless `confirmed` is set.
+    pub async fn import_tasks(
+        &self,
+        bundle: TaskBundle,
+        overwrite: bool,
+        confirmed: bool,
+    ) -> Result<TaskImportResult> {
+        if bundle.version == 0 || bundle.version > TASK_BUNDLE_VERSION {
+            return Err(SchedulerError::InvalidConfig(format!(
+                "Unsupported task bundle version {}",
+                bundle.version
+            )));
+        }
+
+        let mut existing: HashMap<String, SystemTask> = self
+            .list_tasks(true)
+            .await?
+            .into_iter()
+            .map(|task| (task.name.clone(), task))
+            .collect();
+        let mut result = TaskImportResult::default();
+
+        for input in bundle.tasks {
+            let name = input.name.clone();
+            let skip_reason = match existing.get(&name) {
+                Some(task) if task.external => Some("Name is used by a task not created by Cognia"),
+                Some(_) if !overwrite => Some("A task with this name already exists"),
+                _ => None,
+            };
+            if let Some(reason) = skip_reason {
+                result.skipped.push(SkippedImport {
+                    name,
+                    reason: reason.to_string(),
+                });
+                continue;
+            }
+
+            let outcome = match existing.get(&name) {
+                Some(task) => {
+                    let id = task.id.clone();
+                    self.update_task(&id, input, confirmed).await
+                }
+                None => self.create_task_with_confirmation(input, confirmed).await,
+            };
+            match outcome {
+                Ok(Ok(task)) => {
+                    existing.insert(name, task.clone());
+                    result.imported.push(task);
+                }
+                Ok(Err(confirmation)) => result.confirmations.push(confirmation),
+                Err(error) => result.skipped.push(SkippedImport {
+                    name,
+                    reason: error.to_string(),
+                }),
+            }
+        }
+
+        info!(
+            "Imported task bundle: imported={}, pending={}, skipped={}",
+            result.imported.len(),
+            result.confirmations.len(),
+            result.skipped.len()
+        );
+        Ok(result)
+    }
+
+    /// Definition of a task as it would be entered to create it
+    fn task_to_input(task: SystemTask) -> CreateSystemTaskInput {
+        CreateSystemTaskInput {
+            name: task.name,
+            description: task.description,
+            trigger: task.trigger,
+            action: task.action,
+            run_level: task.run_level,
+            tags: task.tags,
+            notify_on: task.notify_on,
+            retry_policy: task.retry_policy,
+        }
+    }
+
     /// Run a task immediately
     pub async fn run_task_now(&self, id: &str) -> Result<TaskRunResult> {
-        self.scheduler.run_task_now(id).await
+        self.ensure_owned_task(id)?;
+        self.run_and_record(id).await
+    }
+
+    /// Run a task and add the result to its run history
+    ///
+    /// Tasks whose wrappers report runs retry and report every attempt
+    /// themselves; a run that failed to start reports nothing, so that is
+    /// recorded here.
+    async fn run_and_record(&self, id: &str) -> Result<TaskRunResult> {
+        let reports_runs = self
+            .scheduler
+            .reports_runs(self.task_retry_policy(id).as_ref());
+        let started_at = Self::now_iso();
+        let result = self.scheduler.run_task_now(id).await?;
+        if reports_runs && (self.ingest_run_reports(id).await > 0 || result.success) {
+            return Ok(result);
+        }
+
+        let finished_at = Self::now_iso();
+        self.record_run_history(id, TaskRunRecord::new(id, started_at, finished_at, &result));
+        self.record_run(id, result.clone()).await;
+        Ok(result)
+    }
+
+    fn record_run_history(&self, task_id: &str, run: TaskRunRecord) {
+        if let Some(store) = &self.metadata_store {
+            if let Err(error) = store.insert_task_run(&run, self.run_history_limit()) {
+                warn!("Failed to record run of task {}: {}", task_id, error);
+            }
+        }
+    }
+
+    /// Retry policy saved with a task's metadata
+    fn task_retry_policy(&self, id: &str) -> Option<RetryPolicy> {
+        let store = self.metadata_store.as_ref()?;
+        match store.get_task_metadata(id, None) {
+            Ok(task) => task.and_then(|task| task.retry_policy),
+            Err(error) => {
+                warn!("Failed loading retry policy of task {}: {}", id, error);
+                None
+            }
+        }
+    }
+
+    /// Runs of a task started through Cognia, newest first, at most `limit`
+    /// (default: the run history limit)
+    pub fn get_task_run_history(
+        &self,
+        task_id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<TaskRunRecord>> {
+        match &self.metadata_store {
+            Some(store) => store.get_task_runs(task_id, limit.unwrap_or(self.run_history_limit())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Number of runs kept per task
+    pub fn run_history_limit(&self) -> usize {
+        self.run_history_limit.load(Ordering::Relaxed)
+    }
+
+    /// Change and persist the number of runs kept per task; older runs are
+    /// pruned on the task's next run
+    pub fn set_run_history_limit(&self, limit: usize) -> Result<()> {
+        let limit = limit.max(1);
+        if let Some(store) = &self.metadata_store {
+            store.set_run_history_limit(limit)?;
+        }
+        self.run_history_limit.store(limit, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Request admin elevation
@@ -603,9 +1051,13 @@ impl SchedulerState {
     /// Summarize a trigger for display
     fn summarize_trigger(trigger: &SystemTaskTrigger) -> String {
         match trigger {
-            SystemTaskTrigger::Cron { expression, .. } => {
-                format!("Cron schedule: {}", expression)
-            }
+            SystemTaskTrigger::Cron {
+                expression,
+                timezone,
+            } => match cron_preview::timezone_name(timezone.as_deref()) {
+                Some(tz) => format!("Cron schedule: {} ({})", expression, tz),
+                None => format!("Cron schedule: {}", expression),
+            },
             SystemTaskTrigger::Interval { seconds } => {
                 if *seconds < 60 {
                     format!("Every {} seconds", seconds)
@@ -750,6 +1202,11 @@ mod tests {
     #[derive(Default)]
     struct MockScheduler {
         tasks: Mutex<HashMap<String, SystemTask>>,
+        max_tasks: u32,
+        /// Runs that fail before runs start succeeding
+        failing_runs: Mutex<u32>,
+        /// Tasks with a retry policy report their runs, like on Windows
+        reports_retried_runs: bool,
     }
 
     #[async_trait]
@@ -761,7 +1218,7 @@ mod tests {
                 available: true,
                 can_elevate: true,
                 supported_triggers: vec!["interval".to_string()],
-                max_tasks: 0,
+                max_tasks: self.max_tasks,
             }
         }
 
@@ -769,6 +1226,10 @@ mod tests {
             true
         }
 
+        fn reports_runs(&self, retry_policy: Option<&RetryPolicy>) -> bool {
+            self.reports_retried_runs && retry_policy.is_some()
+        }
+
         async fn create_task(&self, input: CreateSystemTaskInput) -> Result<SystemTask> {
             let id = SystemTask::generate_id();
             let task = SystemTask {
@@ -787,6 +1248,9 @@ mod tests {
                 next_run_at: None,
                 last_result: None,
                 metadata_state: TaskMetadataState::Full,
+                external: false,
+                notify_on: TaskNotifyOn::Never,
+                retry_policy: input.retry_policy,
             };
             self.tasks
                 .lock()
@@ -816,6 +1280,9 @@ mod tests {
                 next_run_at: None,
                 last_result: None,
                 metadata_state: TaskMetadataState::Full,
+                external: false,
+                notify_on: TaskNotifyOn::Never,
+                retry_policy: input.retry_policy,
             };
             tasks.insert(id.to_string(), task.clone());
             Ok(task)
@@ -848,6 +1315,18 @@ mod tests {
         }
 
         async fn run_task_now(&self, _id: &str) -> Result<TaskRunResult> {
+            let mut failing_runs = self.failing_runs.lock().expect("lock");
+            if *failing_runs > 0 {
+                *failing_runs -= 1;
+                return Ok(TaskRunResult {
+                    success: false,
+                    exit_code: Some(1),
+                    stdout: None,
+                    stderr: Some("transient failure".to_string()),
+                    error: Some("transient failure".to_string()),
+                    duration_ms: Some(1),
+                });
+            }
             Ok(TaskRunResult {
                 success: true,
                 exit_code: Some(0),
@@ -872,11 +1351,16 @@ mod tests {
     }
 
     fn build_state_with_mock() -> SchedulerState {
-        SchedulerState {
-            scheduler: Arc::new(MockScheduler::default()),
-            metadata_store: None,
-            pending_confirmations: RwLock::new(HashMap::new()),
-        }
+        SchedulerState::with_scheduler(Arc::new(MockScheduler::default()), None)
+    }
+
+    fn external_task(id: &str) -> SystemTask {
+        SchedulerState::make_temp_task(
+            id.to_string(),
+            &interval_command_input("external"),
+            SystemTaskStatus::Enabled,
+            TaskMetadataState::Full,
+        )
     }
 
     fn interval_command_input(name: &str) -> CreateSystemTaskInput {
@@ -892,6 +1376,8 @@ mod tests {
             },
             run_level: RunLevel::User,
             tags: vec!["test".to_string()],
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         }
     }
 
@@ -912,6 +1398,8 @@ mod tests {
             },
             run_level: RunLevel::User,
             tags: vec!["test".to_string()],
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         }
     }
 
@@ -947,11 +1435,98 @@ mod tests {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         };
 
         assert_eq!(task.calculate_risk_level(), RiskLevel::Low);
     }
 
+    #[test]
+    fn test_retry_policy_warns_for_destructive_commands_only() {
+        let task_with = |command: &str, args: &[&str], max_attempts: u32| {
+            let mut input = interval_command_input("retry");
+            input.action = SystemTaskAction::RunCommand {
+                command: command.to_string(),
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                working_dir: None,
+                env: HashMap::new(),
+            };
+            input.retry_policy = Some(RetryPolicy {
+                max_attempts,
+                backoff_secs: 10,
+                backoff_multiplier: 2.0,
+            });
+            SchedulerState::make_temp_task(
+                "retry".to_string(),
+                &input,
+                SystemTaskStatus::Enabled,
+                TaskMetadataState::Full,
+            )
+        };
+        let retry_warnings = |task: &SystemTask| {
+            task.generate_warnings()
+                .iter()
+                .filter(|warning| warning.contains("retried"))
+                .count()
+        };
+
+        let destructive = task_with("/bin/rm", &["-rf", "/tmp/cache"], 5);
+        assert_eq!(retry_warnings(&destructive), 1);
+        assert_eq!(destructive.calculate_risk_level(), RiskLevel::Low);
+        assert_eq!(
+            retry_warnings(&task_with("/bin/rm", &["-rf", "/tmp/cache"], 2)),
+            0
+        );
+        assert_eq!(
+            retry_warnings(&task_with("/bin/echo", &["perform", "form"], 5)),
+            0
+        );
+        assert_eq!(
+            retry_warnings(&task_with("cmd.exe", &["/C", "del", "C:\\temp\\x"], 5)),
+            1
+        );
+        assert_eq!(
+            RetryPolicy {
+                max_attempts: 4,
+                backoff_secs: 10,
+                backoff_multiplier: 1.5,
+            }
+            .retry_delays(),
+            vec![10, 15, 23]
+        );
+    }
+
+    #[tokio::test]
+    async fn out_of_range_retry_policies_are_rejected() {
+        let state = SchedulerState::with_scheduler(Arc::new(MockScheduler::default()), None);
+        let policy = |max_attempts: u32, backoff_secs: u64, backoff_multiplier: f64| RetryPolicy {
+            max_attempts,
+            backoff_secs,
+            backoff_multiplier,
+        };
+
+        assert!(policy(1, 0, 1.0).validate().is_ok());
+        assert!(policy(MAX_RETRY_ATTEMPTS, 60, 1.5).validate().is_ok());
+        for invalid in [
+            policy(0, 10, 1.0),
+            policy(MAX_RETRY_ATTEMPTS + 1, 10, 1.0),
+            policy(3, MAX_RETRY_DELAY_SECS + 1, 1.0),
+            // 600s * 10 exceeds the cap on the second retry
+            policy(3, 600, 10.0),
+            policy(3, 10, f64::NAN),
+            policy(3, 10, 0.5),
+        ] {
+            let mut input = interval_command_input("retry");
+            input.retry_policy = Some(invalid);
+            assert!(matches!(
+                state.create_task_with_confirmation(input, true).await,
+                Err(SchedulerError::InvalidConfig(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_admin_script_is_critical() {
         let task = SystemTask {
@@ -979,6 +1554,9 @@ mod tests {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         };
 
         assert_eq!(task.calculate_risk_level(), RiskLevel::Critical);
@@ -1035,4 +1613,453 @@ mod tests {
             .expect("confirm after cancel");
         assert!(confirmed.is_none());
     }
+
+    #[tokio::test]
+    async fn list_tasks_hides_external_tasks_unless_requested() {
+        let scheduler = Arc::new(MockScheduler::default());
+        let state = SchedulerState::with_scheduler(scheduler.clone(), None);
+        let owned = state
+            .create_task_with_confirmation(interval_command_input("owned"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        scheduler.tasks.lock().expect("lock").insert(
+            "com.apple.backup".to_string(),
+            external_task("com.apple.backup"),
+        );
+
+        let owned_only = state.list_tasks(false).await.expect("list");
+        assert_eq!(owned_only.len(), 1);
+        assert_eq!(owned_only[0].id, owned.id);
+        assert!(!owned_only[0].external);
+
+        let all = state.list_tasks(true).await.expect("list all");
+        assert_eq!(all.len(), 2);
+        let external = all
+            .iter()
+            .find(|task| task.id == "com.apple.backup")
+            .expect("external task");
+        assert!(external.external);
+
+        let err = state
+            .delete_task("com.apple.backup")
+            .await
+            .expect_err("external task is read-only");
+        assert!(matches!(err, SchedulerError::PermissionDenied(_)));
+        assert!(state.enable_task(&owned.id).await.expect("enable"));
+    }
+
+    #[tokio::test]
+    async fn exported_bundle_imports_with_new_ids_and_confirmations() {
+        let source = build_state_with_mock();
+        let backup = source
+            .create_task_with_confirmation(interval_command_input("backup"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        source
+            .create_task_with_confirmation(risky_script_input("cleanup"), true)
+            .await
+            .expect("create")
+            .expect("confirmed");
+
+        let bundle = source.export_tasks(None).await.expect("export");
+        assert_eq!(bundle.version, TASK_BUNDLE_VERSION);
+        assert_eq!(bundle.tasks.len(), 2);
+        let only_backup = source
+            .export_tasks(Some(vec![backup.id.clone()]))
+            .await
+            .expect("export one");
+        assert_eq!(only_backup.tasks.len(), 1);
+        assert_eq!(only_backup.tasks[0].name, "backup");
+
+        // Round-trip through JSON as a bundle file would
+        let bundle: TaskBundle =
+            serde_json::from_str(&serde_json::to_string(&bundle).expect("serialize"))
+                .expect("deserialize");
+
+        let target = build_state_with_mock();
+        let imported = target
+            .import_tasks(bundle.clone(), false, false)
+            .await
+            .expect("import");
+        assert_eq!(imported.imported.len(), 1);
+        assert_eq!(imported.imported[0].name, "backup");
+        assert_ne!(imported.imported[0].id, backup.id);
+        assert_eq!(imported.confirmations.len(), 1);
+        assert_eq!(imported.confirmations[0].details.task_name, "cleanup");
+        assert!(imported.skipped.is_empty());
+
+        let again = target
+            .import_tasks(bundle.clone(), false, true)
+            .await
+            .expect("import again");
+        assert!(again.imported.iter().all(|task| task.name == "cleanup"));
+        assert_eq!(again.skipped.len(), 1);
+        assert_eq!(again.skipped[0].name, "backup");
+
+        let overwritten = target
+            .import_tasks(bundle, true, true)
+            .await
+            .expect("overwrite");
+        assert_eq!(overwritten.imported.len(), 2);
+        assert_eq!(target.list_tasks(false).await.expect("list").len(), 2);
+
+        let unsupported = TaskBundle {
+            version: TASK_BUNDLE_VERSION + 1,
+            exported_at: SchedulerState::now_iso(),
+            tasks: vec![],
+        };
+        assert!(matches!(
+            target.import_tasks(unsupported, false, false).await,
+            Err(SchedulerError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn ownership_requires_metadata_record_when_store_available() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scheduler = Arc::new(MockScheduler::default());
+        let state = SchedulerState::with_scheduler(
+            scheduler.clone(),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        let owned = state
+            .create_task_with_confirmation(interval_command_input("owned"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        // Follows Cognia naming but was never created through this store
+        scheduler.tasks.lock().expect("lock").insert(
+            "cognia-task-unknown".to_string(),
+            external_task("cognia-task-unknown"),
+        );
+
+        let owned_only = state.list_tasks(false).await.expect("list");
+        assert_eq!(owned_only.len(), 1);
+        assert_eq!(owned_only[0].id, owned.id);
+
+        // Listing external tasks must not adopt them into the store
+        state.list_tasks(true).await.expect("list all");
+        assert!(state.disable_task("cognia-task-unknown").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn run_now_is_reported_by_run_watcher() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = SchedulerState::with_scheduler(
+            Arc::new(MockScheduler::default()),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        let mut input = interval_command_input("notified");
+        input.notify_on = TaskNotifyOn::Always;
+        let task = state
+            .create_task_with_confirmation(input, true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        assert_eq!(task.notify_on, TaskNotifyOn::Always);
+
+        // The first check only seeds the run history
+        assert!(state
+            .collect_run_notifications()
+            .await
+            .expect("seed")
+            .is_empty());
+
+        state.run_task_now(&task.id).await.expect("run");
+        let notifications = state.collect_run_notifications().await.expect("collect");
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].task_id, task.id);
+        assert!(notifications[0].title.contains("notified"));
+        assert_eq!(notifications[0].body, "Exit status: 0");
+
+        assert!(state
+            .collect_run_notifications()
+            .await
+            .expect("collect")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn reported_scheduled_failure_is_notified_and_recorded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = SchedulerState::with_scheduler(
+            Arc::new(MockScheduler::default()),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        let mut input = interval_command_input("scheduled");
+        input.notify_on = TaskNotifyOn::Failure;
+        let task = state
+            .create_task_with_confirmation(input, true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        assert!(state
+            .collect_run_notifications()
+            .await
+            .expect("seed")
+            .is_empty());
+
+        // A run started by the platform scheduler, retried once by its wrapper
+        let report = run_reports::report_path(&task.id);
+        std::fs::create_dir_all(report.parent().expect("report dir")).expect("report dir");
+        std::fs::write(&report, "1767225600 1 1\n1767225660 2 2\n").expect("report");
+
+        let notifications = state.collect_run_notifications().await.expect("collect");
+        assert!(!report.exists());
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].body, "Exit status: 2");
+
+        let history = state.get_task_run_history(&task.id, None).expect("history");
+        let attempts: Vec<_> = history
+            .iter()
+            .map(|run| (run.attempt, run.exit_code))
+            .collect();
+        assert_eq!(attempts, vec![(Some(2), Some(2)), (Some(1), Some(1))]);
+        assert_eq!(history[0].finished_at, "2026-01-01T00:01:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn recorded_run_times_are_compared_as_instants() {
+        let state = build_state_with_mock();
+        let run_result = |success: bool| TaskRunResult {
+            success,
+            exit_code: Some(if success { 0 } else { 1 }),
+            stdout: None,
+            stderr: None,
+            error: None,
+            duration_ms: None,
+        };
+        state.recorded_runs.write().await.insert(
+            "task".to_string(),
+            ("2026-01-01T09:30:00+00:00".to_string(), run_result(false)),
+        );
+
+        // Later as text, but 09:00 UTC
+        let mut task = external_task("task");
+        task.last_run_at = Some("2026-01-01T10:00:00+01:00".to_string());
+        task.last_result = Some(run_result(true));
+        state.apply_recorded_run(&mut task).await;
+        assert_eq!(
+            task.last_run_at.as_deref(),
+            Some("2026-01-01T09:30:00+00:00")
+        );
+        assert_eq!(task.last_result.map(|result| result.success), Some(false));
+
+        let mut task = external_task("task");
+        task.last_run_at = Some("2026-01-01T11:00:00+01:00".to_string());
+        task.last_result = Some(run_result(true));
+        state.apply_recorded_run(&mut task).await;
+        assert_eq!(
+            task.last_run_at.as_deref(),
+            Some("2026-01-01T11:00:00+01:00")
+        );
+        assert_eq!(task.last_result.map(|result| result.success), Some(true));
+    }
+
+    #[tokio::test]
+    async fn run_now_is_recorded_in_run_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = SchedulerState::with_scheduler(
+            Arc::new(MockScheduler::default()),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        let task = state
+            .create_task_with_confirmation(interval_command_input("history"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        assert!(state
+            .get_task_run_history(&task.id, None)
+            .expect("history")
+            .is_empty());
+
+        state.set_run_history_limit(2).expect("set limit");
+        for _ in 0..3 {
+            state.run_task_now(&task.id).await.expect("run");
+        }
+
+        let history = state.get_task_run_history(&task.id, None).expect("history");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].task_id, task.id);
+        assert!(history[0].success);
+        assert_eq!(history[0].exit_code, Some(0));
+        assert!(history[0].started_at <= history[0].finished_at);
+        assert!(history[1].started_at <= history[0].started_at);
+        assert_eq!(
+            state
+                .get_task_run_history(&task.id, Some(1))
+                .expect("history")
+                .len(),
+            1
+        );
+
+        // The limit is restored from the store
+        let reopened = SchedulerState::with_scheduler(
+            Arc::new(MockScheduler::default()),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        assert_eq!(reopened.run_history_limit(), 2);
+
+        // Without a store there is no history to read
+        assert!(build_state_with_mock()
+            .get_task_run_history(&task.id, None)
+            .expect("history")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_unreported_runs_are_recorded_on_run_now() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scheduler = Arc::new(MockScheduler {
+            reports_retried_runs: true,
+            ..Default::default()
+        });
+        let state = SchedulerState::with_scheduler(
+            scheduler.clone(),
+            Some(SchedulerMetadataStore::new(dir.path().join("scheduler.db")).expect("store")),
+        );
+        let mut input = interval_command_input("retry");
+        input.retry_policy = Some(RetryPolicy {
+            max_attempts: 3,
+            backoff_secs: 0,
+            backoff_multiplier: 2.0,
+        });
+        let retried = state
+            .create_task_with_confirmation(input, true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+
+        // The wrapper reports runs that start, and retries them itself
+        state.run_task_now(&retried.id).await.expect("run");
+        assert!(state
+            .get_task_run_history(&retried.id, None)
+            .expect("history")
+            .is_empty());
+
+        // A run that failed to start is recorded once and not retried here
+        *scheduler.failing_runs.lock().expect("lock") = 2;
+        let result = state.run_task_now(&retried.id).await.expect("run");
+        assert!(!result.success);
+        let history = state
+            .get_task_run_history(&retried.id, None)
+            .expect("history");
+        assert_eq!(history.len(), 1);
+        assert!(!history[0].success);
+        assert_eq!(history[0].attempt, None);
+        assert_eq!(*scheduler.failing_runs.lock().expect("lock"), 1);
+
+        // Tasks without a wrapper have every run recorded
+        let plain = state
+            .create_task_with_confirmation(interval_command_input("plain"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        *scheduler.failing_runs.lock().expect("lock") = 0;
+        state.run_task_now(&plain.id).await.expect("run");
+        let history = state
+            .get_task_run_history(&plain.id, None)
+            .expect("history");
+        assert_eq!(history.len(), 1);
+        assert!(history[0].success);
+    }
+
+    #[tokio::test]
+    async fn create_is_rejected_when_task_limit_is_reached() {
+        let state = SchedulerState::with_scheduler(
+            Arc::new(MockScheduler {
+                max_tasks: 2,
+                ..Default::default()
+            }),
+            None,
+        );
+
+        // A confirmation requested below the limit is re-checked when confirmed
+        let confirmation = state
+            .create_task_with_confirmation(risky_script_input("pending"), false)
+            .await
+            .expect("create")
+            .expect_err("should require confirmation");
+
+        for name in ["first", "second"] {
+            state
+                .create_task_with_confirmation(interval_command_input(name), true)
+                .await
+                .expect("create")
+                .expect("no confirmation for low risk");
+        }
+
+        let usage = state.usage().await.expect("usage");
+        assert_eq!(usage.task_count, 2);
+        assert_eq!(usage.max_tasks, 2);
+        assert_eq!(usage.remaining, Some(0));
+
+        let err = state
+            .create_task_with_confirmation(interval_command_input("third"), true)
+            .await
+            .expect_err("limit reached");
+        assert!(matches!(
+            err,
+            SchedulerError::LimitReached { current: 2, max: 2 }
+        ));
+
+        let err = state
+            .confirm_task(&confirmation.confirmation_id)
+            .await
+            .expect_err("limit reached on confirm");
+        assert!(matches!(err, SchedulerError::LimitReached { .. }));
+    }
+
+    #[tokio::test]
+    async fn usage_without_limit_reports_unlimited() {
+        let state = build_state_with_mock();
+        state
+            .create_task_with_confirmation(interval_command_input("one"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+
+        let usage = state.usage().await.expect("usage");
+        assert_eq!(usage.task_count, 1);
+        assert_eq!(usage.max_tasks, 0);
+        assert_eq!(usage.remaining, None);
+        assert!(usage.has_capacity());
+    }
+
+    #[tokio::test]
+    async fn cron_trigger_timezone_is_validated_and_summarized() {
+        let state = build_state_with_mock();
+        let cron_input = |timezone: &str| CreateSystemTaskInput {
+            trigger: SystemTaskTrigger::Cron {
+                expression: "0 9 * * *".to_string(),
+                timezone: Some(timezone.to_string()),
+            },
+            ..interval_command_input("cron")
+        };
+
+        let err = state
+            .create_task_with_confirmation(cron_input("Europe/Atlantis"), true)
+            .await
+            .expect_err("unknown timezone");
+        assert!(matches!(err, SchedulerError::InvalidTrigger { .. }));
+
+        let created = state
+            .create_task_with_confirmation(cron_input("Europe/Berlin"), true)
+            .await
+            .expect("create")
+            .expect("no confirmation for low risk");
+        assert_eq!(
+            SchedulerState::summarize_trigger(&created.trigger),
+            "Cron schedule: 0 9 * * * (Europe/Berlin)"
+        );
+        assert_eq!(
+            SchedulerState::summarize_trigger(&SystemTaskTrigger::Cron {
+                expression: "0 9 * * *".to_string(),
+                timezone: None,
+            }),
+            "Cron schedule: 0 9 * * *"
+        );
+    }
 }
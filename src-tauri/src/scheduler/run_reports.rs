@@ -0,0 +1,147 @@
+//! Run reports written by scheduled tasks
+//!
+//! Runs started by the platform scheduler happen outside Cognia, so the task
+//! wrappers append one line per finished attempt to a per-task report file:
+//! the finish time in Unix seconds, the exit status (an exit code, or a signal
+//! name on Linux) and, for tasks with a retry policy, the attempt number.
+//! The scheduler state ingests and removes these files when it lists tasks.
+
+use std::fs;
+use std::path::PathBuf;
+
+use super::service::format_datetime;
+use super::types::TaskRunResult;
+
+/// One finished attempt of a task run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    /// Finish time as RFC 3339
+    pub finished_at: String,
+    pub exit_code: Option<i32>,
+    /// Signal that ended the attempt when it did not exit normally
+    pub signal: Option<String>,
+    pub attempt: Option<u32>,
+}
+
+impl RunReport {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    pub fn to_result(&self) -> TaskRunResult {
+        TaskRunResult {
+            success: self.success(),
+            exit_code: self.exit_code,
+            stdout: None,
+            stderr: None,
+            error: self
+                .signal
+                .as_ref()
+                .map(|signal| format!("Terminated by signal {}", signal)),
+            duration_ms: None,
+        }
+    }
+}
+
+/// Directory holding the report files of all tasks
+pub fn report_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("Cognia")
+        .join("scheduler")
+        .join("runs")
+}
+
+/// Report file of a task
+///
+/// IDs are normalized like systemd unit names, since the Linux backend lists
+/// tasks under a lowercased ID.
+pub fn report_path(task_id: &str) -> PathBuf {
+    let key: String = task_id
+        .trim_start_matches('\\')
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    report_dir().join(format!("{}.log", key))
+}
+
+/// Parse report lines, skipping malformed ones
+pub fn parse_reports(content: &str) -> Vec<RunReport> {
+    content.lines().filter_map(parse_report_line).collect()
+}
+
+fn parse_report_line(line: &str) -> Option<RunReport> {
+    let mut fields = line.split_whitespace();
+    let finished_secs = fields.next()?.parse::<i64>().ok()?;
+    let status = fields.next()?;
+    let attempt = fields.next().and_then(|value| value.parse::<u32>().ok());
+
+    let finished_at = chrono::DateTime::from_timestamp(finished_secs, 0)?;
+    let (exit_code, signal) = match status.parse::<i32>() {
+        Ok(code) => (Some(code), None),
+        Err(_) => (None, Some(status.to_string())),
+    };
+    Some(RunReport {
+        finished_at: format_datetime(finished_at),
+        exit_code,
+        signal,
+        attempt,
+    })
+}
+
+/// Read and remove the pending reports of a task, oldest first
+///
+/// The file is renamed before reading, so attempts finishing meanwhile start
+/// a new file instead of being lost.
+pub fn take_reports(task_id: &str) -> Vec<RunReport> {
+    let path = report_path(task_id);
+    let taken = path.with_extension("log.taken");
+    if fs::rename(&path, &taken).is_err() {
+        return Vec::new();
+    }
+    let content = fs::read_to_string(&taken).unwrap_or_default();
+    let _ = fs::remove_file(&taken);
+    parse_reports(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_exit_codes_signals_and_attempts() {
+        let reports =
+            parse_reports("1767225600 0\n1767225660 3 2\nnot a report\n1767225720 TERM\n");
+        assert_eq!(reports.len(), 3);
+
+        assert_eq!(reports[0].finished_at, "2026-01-01T00:00:00+00:00");
+        assert!(reports[0].success());
+        assert_eq!(reports[0].attempt, None);
+
+        assert_eq!(reports[1].exit_code, Some(3));
+        assert_eq!(reports[1].attempt, Some(2));
+        assert!(!reports[1].to_result().success);
+
+        let signalled = reports[2].to_result();
+        assert!(!signalled.success);
+        assert_eq!(signalled.exit_code, None);
+        assert_eq!(
+            signalled.error.as_deref(),
+            Some("Terminated by signal TERM")
+        );
+    }
+
+    #[test]
+    fn normalizes_task_ids_in_report_paths() {
+        assert_eq!(report_path("Cognia_Backup"), report_path("cognia_backup"));
+        assert_eq!(report_path("\\Cognia_Backup"), report_path("cognia_backup"));
+        assert!(report_path("com.cognia.task.Cognia_Backup")
+            .ends_with("com-cognia-task-cognia_backup.log"));
+    }
+}
@@ -0,0 +1,224 @@
+//! Run-history watcher for system scheduler tasks
+//!
+//! Compares successive task listings and reports runs that completed since the
+//! previous observation, so the app can notify the user according to each
+//! task's `notify_on` setting.
+
+use std::collections::HashMap;
+
+use super::types::{SystemTask, SystemTaskId, TaskNotifyOn, TaskRunResult};
+
+/// Desktop notification for a completed task run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskRunNotification {
+    pub task_id: SystemTaskId,
+    pub title: String,
+    pub body: String,
+}
+
+impl TaskRunNotification {
+    /// Build the notification for a run of `task`, `None` if its setting
+    /// does not ask for one
+    pub fn for_task(task: &SystemTask) -> Option<Self> {
+        let success = task.last_result.as_ref().map(|result| result.success);
+        if !should_notify(task.notify_on, success) {
+            return None;
+        }
+
+        let title = match success {
+            Some(true) => format!("Scheduled task succeeded: {}", task.name),
+            Some(false) => format!("Scheduled task failed: {}", task.name),
+            None => format!("Scheduled task ran: {}", task.name),
+        };
+
+        Some(Self {
+            task_id: task.id.clone(),
+            title,
+            body: exit_status_summary(task.last_result.as_ref()),
+        })
+    }
+}
+
+/// Whether a run with the given outcome should be notified
+pub fn should_notify(notify_on: TaskNotifyOn, success: Option<bool>) -> bool {
+    match notify_on {
+        TaskNotifyOn::Never => false,
+        TaskNotifyOn::Failure => success == Some(false),
+        TaskNotifyOn::Always => true,
+    }
+}
+
+fn exit_status_summary(result: Option<&TaskRunResult>) -> String {
+    let Some(result) = result else {
+        return "Exit status: unknown".to_string();
+    };
+
+    let mut summary = match result.exit_code {
+        Some(code) => format!("Exit status: {}", code),
+        None if result.success => "Exit status: success".to_string(),
+        None => "Exit status: failed".to_string(),
+    };
+    if let Some(error) = result.error.as_deref().filter(|e| !e.trim().is_empty()) {
+        summary.push_str(&format!("\n{}", error.trim()));
+    }
+    summary
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RunSnapshot {
+    last_run_at: Option<String>,
+    success: Option<bool>,
+    exit_code: Option<i32>,
+}
+
+impl RunSnapshot {
+    fn of(task: &SystemTask) -> Self {
+        Self {
+            last_run_at: task.last_run_at.clone(),
+            success: task.last_result.as_ref().map(|result| result.success),
+            exit_code: task
+                .last_result
+                .as_ref()
+                .and_then(|result| result.exit_code),
+        }
+    }
+
+    fn has_run(&self) -> bool {
+        self.last_run_at.is_some() || self.success.is_some()
+    }
+}
+
+/// Tracks the last observed run of each task
+#[derive(Debug, Default)]
+pub struct RunHistoryWatcher {
+    seen: HashMap<SystemTaskId, RunSnapshot>,
+}
+
+impl RunHistoryWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current task list and return tasks whose last run changed
+    /// since the previous call.
+    ///
+    /// The first observation of a task only seeds its history, so runs that
+    /// finished before the app started are not reported.
+    pub fn observe(&mut self, tasks: &[SystemTask]) -> Vec<SystemTask> {
+        let mut completed = Vec::new();
+        let mut seen = HashMap::with_capacity(tasks.len());
+
+        for task in tasks {
+            let snapshot = RunSnapshot::of(task);
+            if let Some(previous) = self.seen.get(&task.id) {
+                if snapshot.has_run() && *previous != snapshot {
+                    completed.push(task.clone());
+                }
+            }
+            seen.insert(task.id.clone(), snapshot);
+        }
+
+        self.seen = seen;
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::types::{
+        RunLevel, SystemTaskAction, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState,
+    };
+
+    fn make_task(id: &str, notify_on: TaskNotifyOn) -> SystemTask {
+        SystemTask {
+            id: id.to_string(),
+            name: format!("Task {}", id),
+            description: None,
+            trigger: SystemTaskTrigger::Interval { seconds: 60 },
+            action: SystemTaskAction::RunCommand {
+                command: "/bin/true".to_string(),
+                args: vec![],
+                working_dir: None,
+                env: HashMap::new(),
+            },
+            run_level: RunLevel::User,
+            status: SystemTaskStatus::Enabled,
+            requires_admin: false,
+            tags: vec![],
+            created_at: None,
+            updated_at: None,
+            last_run_at: None,
+            next_run_at: None,
+            last_result: None,
+            metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on,
+            retry_policy: None,
+        }
+    }
+
+    fn run_result(success: bool, exit_code: i32) -> TaskRunResult {
+        TaskRunResult {
+            success,
+            exit_code: Some(exit_code),
+            stdout: None,
+            stderr: None,
+            error: (!success).then(|| "command failed".to_string()),
+            duration_ms: Some(10),
+        }
+    }
+
+    #[test]
+    fn test_should_notify() {
+        assert!(!should_notify(TaskNotifyOn::Never, Some(false)));
+        assert!(should_notify(TaskNotifyOn::Failure, Some(false)));
+        assert!(!should_notify(TaskNotifyOn::Failure, Some(true)));
+        assert!(!should_notify(TaskNotifyOn::Failure, None));
+        assert!(should_notify(TaskNotifyOn::Always, Some(true)));
+        assert!(should_notify(TaskNotifyOn::Always, None));
+    }
+
+    #[test]
+    fn test_notification_includes_name_and_exit_status() {
+        let mut task = make_task("a", TaskNotifyOn::Failure);
+        task.last_result = Some(run_result(false, 2));
+
+        let notification = TaskRunNotification::for_task(&task).unwrap();
+        assert_eq!(notification.title, "Scheduled task failed: Task a");
+        assert_eq!(notification.body, "Exit status: 2\ncommand failed");
+
+        task.last_result = Some(run_result(true, 0));
+        assert!(TaskRunNotification::for_task(&task).is_none());
+    }
+
+    #[test]
+    fn test_watcher_reports_only_new_runs() {
+        let mut watcher = RunHistoryWatcher::new();
+        let mut task = make_task("a", TaskNotifyOn::Always);
+        task.last_run_at = Some("2026-01-01T00:00:00Z".to_string());
+        task.last_result = Some(run_result(true, 0));
+
+        // Runs from before the first observation are not reported
+        assert!(watcher.observe(&[task.clone()]).is_empty());
+        assert!(watcher.observe(&[task.clone()]).is_empty());
+
+        task.last_run_at = Some("2026-01-01T01:00:00Z".to_string());
+        task.last_result = Some(run_result(false, 1));
+        let completed = watcher.observe(&[task.clone()]);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].id, "a");
+
+        assert!(watcher.observe(&[task]).is_empty());
+    }
+
+    #[test]
+    fn test_watcher_reports_first_run_of_known_task() {
+        let mut watcher = RunHistoryWatcher::new();
+        let mut task = make_task("b", TaskNotifyOn::Always);
+        assert!(watcher.observe(&[task.clone()]).is_empty());
+
+        task.last_run_at = Some("2026-01-01T00:00:00Z".to_string());
+        assert_eq!(watcher.observe(&[task]).len(), 1);
+    }
+}
@@ -3,7 +3,9 @@
 use async_trait::async_trait;
 
 use super::error::Result;
-use super::types::{CreateSystemTaskInput, SchedulerCapabilities, SystemTask, TaskRunResult};
+use super::types::{
+    CreateSystemTaskInput, RetryPolicy, SchedulerCapabilities, SystemTask, TaskRunResult,
+};
 
 /// Trait for platform-specific scheduler implementations
 #[async_trait]
@@ -38,6 +40,18 @@ pub trait SystemScheduler: Send + Sync {
     /// Run a task immediately
     async fn run_task_now(&self, id: &str) -> Result<TaskRunResult>;
 
+    /// Whether the wrapper of a task with `retry_policy` appends every run,
+    /// scheduled or manual, to the task's run report (see [`super::run_reports`])
+    fn reports_runs(&self, _retry_policy: Option<&RetryPolicy>) -> bool {
+        false
+    }
+
+    /// Whether cron times in a timezone are converted to local time when the
+    /// task is created (see [`super::cron_preview::local_time_conversion`])
+    fn converts_cron_timezones(&self) -> bool {
+        false
+    }
+
     /// Check if admin elevation is required for an operation
     fn requires_admin(&self, task: &SystemTask) -> bool;
 
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::error::{Result, SchedulerError};
+
 /// Unique identifier for system tasks
 pub type SystemTaskId = String;
 
@@ -49,6 +51,92 @@ pub enum TaskMetadataState {
     Degraded,
 }
 
+/// When to show a desktop notification after a task run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskNotifyOn {
+    #[default]
+    Never,
+    /// Only when the run failed
+    Failure,
+    Always,
+}
+
+/// How a failed run is retried before waiting for the next trigger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts per run, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry in seconds
+    pub backoff_secs: u64,
+    /// Factor applied to the delay after each retry
+    #[serde(default = "default_backoff_multiplier")]
+    pub backoff_multiplier: f64,
+}
+
+fn default_backoff_multiplier() -> f64 {
+    1.0
+}
+
+/// Attempts at which [`SystemTask::generate_warnings`] flags destructive commands
+pub const HIGH_RETRY_ATTEMPTS: u32 = 5;
+/// Most attempts a retry policy may request
+pub const MAX_RETRY_ATTEMPTS: u32 = 10;
+/// Longest delay allowed before a retry, in seconds
+pub const MAX_RETRY_DELAY_SECS: u64 = 3600;
+/// Largest factor allowed between consecutive retry delays
+pub const MAX_BACKOFF_MULTIPLIER: f64 = 10.0;
+
+impl RetryPolicy {
+    /// Reject policies with attempts outside `1..=MAX_RETRY_ATTEMPTS` or
+    /// delays growing past `MAX_RETRY_DELAY_SECS`
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=MAX_RETRY_ATTEMPTS).contains(&self.max_attempts) {
+            return Err(SchedulerError::InvalidConfig(format!(
+                "Retry attempts must be between 1 and {}",
+                MAX_RETRY_ATTEMPTS
+            )));
+        }
+        if !self.backoff_multiplier.is_finite()
+            || !(1.0..=MAX_BACKOFF_MULTIPLIER).contains(&self.backoff_multiplier)
+        {
+            return Err(SchedulerError::InvalidConfig(format!(
+                "Retry backoff multiplier must be between 1 and {}",
+                MAX_BACKOFF_MULTIPLIER
+            )));
+        }
+        if self.backoff_secs > MAX_RETRY_DELAY_SECS
+            || self
+                .retry_delays()
+                .iter()
+                .any(|delay| *delay > MAX_RETRY_DELAY_SECS)
+        {
+            return Err(SchedulerError::InvalidConfig(format!(
+                "Retry delays must not exceed {} seconds",
+                MAX_RETRY_DELAY_SECS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Delays in seconds before each retry, `max_attempts - 1` entries
+    pub fn retry_delays(&self) -> Vec<u64> {
+        let multiplier = if self.backoff_multiplier.is_finite() {
+            self.backoff_multiplier.max(1.0)
+        } else {
+            1.0
+        };
+        let mut delay = self.backoff_secs as f64;
+        (1..self.max_attempts.max(1))
+            .map(|_| {
+                let secs = delay.round() as u64;
+                delay *= multiplier;
+                secs
+            })
+            .collect()
+    }
+}
+
 /// Risk level for task operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -194,6 +282,15 @@ pub struct SystemTask {
     /// Whether metadata is complete for editing
     #[serde(default)]
     pub metadata_state: TaskMetadataState,
+    /// Whether the task was created outside Cognia; external tasks are read-only
+    #[serde(default)]
+    pub external: bool,
+    /// When to notify the user after a run
+    #[serde(default)]
+    pub notify_on: TaskNotifyOn,
+    /// Retries after a failed run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 /// Result of a task execution
@@ -213,6 +310,77 @@ pub struct TaskRunResult {
     pub duration_ms: Option<u64>,
 }
 
+/// Persisted record of one run of a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRunRecord {
+    pub task_id: SystemTaskId,
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Output, truncated to its last [`MAX_RUN_OUTPUT_CHARS`] characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Duration in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Attempt number (1-based) when the task has a retry policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<u32>,
+}
+
+/// Characters of stdout/stderr kept in a [`TaskRunRecord`]
+pub const MAX_RUN_OUTPUT_CHARS: usize = 4000;
+
+impl TaskRunRecord {
+    pub fn new(
+        task_id: &str,
+        started_at: String,
+        finished_at: String,
+        result: &TaskRunResult,
+    ) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            started_at,
+            finished_at,
+            success: result.success,
+            exit_code: result.exit_code,
+            stdout: result.stdout.as_deref().map(truncate_output),
+            stderr: result.stderr.as_deref().map(truncate_output),
+            error: result.error.clone(),
+            duration_ms: result.duration_ms,
+            attempt: None,
+        }
+    }
+
+    /// Result of the run, with output as stored
+    pub fn to_result(&self) -> TaskRunResult {
+        TaskRunResult {
+            success: self.success,
+            exit_code: self.exit_code,
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+            error: self.error.clone(),
+            duration_ms: self.duration_ms,
+        }
+    }
+}
+
+/// Keep the end of long output, where errors usually are
+fn truncate_output(output: &str) -> String {
+    let total = output.chars().count();
+    if total <= MAX_RUN_OUTPUT_CHARS {
+        return output.to_string();
+    }
+    let tail: String = output.chars().skip(total - MAX_RUN_OUTPUT_CHARS).collect();
+    format!("[truncated]\n{}", tail)
+}
+
 /// Input for creating a system task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSystemTaskInput {
@@ -225,6 +393,44 @@ pub struct CreateSystemTaskInput {
     pub run_level: RunLevel,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// When to notify the user after a run
+    #[serde(default)]
+    pub notify_on: TaskNotifyOn,
+    /// Retries after a failed run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+/// Version written into exported [`TaskBundle`]s
+pub const TASK_BUNDLE_VERSION: u32 = 1;
+
+/// Portable set of task definitions for moving tasks between machines
+///
+/// Tasks carry no IDs; each platform assigns new ones on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskBundle {
+    pub version: u32,
+    /// Export timestamp (RFC 3339)
+    pub exported_at: String,
+    pub tasks: Vec<CreateSystemTaskInput>,
+}
+
+/// Task from a bundle that was not imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedImport {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Outcome of importing a [`TaskBundle`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskImportResult {
+    /// Tasks created or overwritten
+    pub imported: Vec<SystemTask>,
+    /// Tasks waiting for the user to confirm them
+    pub confirmations: Vec<TaskConfirmationRequest>,
+    /// Tasks left out because of a name clash or an error
+    pub skipped: Vec<SkippedImport>,
 }
 
 /// Confirmation request for sensitive operations
@@ -284,6 +490,33 @@ pub struct SchedulerCapabilities {
     pub max_tasks: u32,
 }
 
+/// Current task count against the platform limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerUsage {
+    /// Tasks currently registered with the platform scheduler
+    pub task_count: usize,
+    /// Maximum tasks allowed (0 = unlimited)
+    pub max_tasks: u32,
+    /// Tasks that can still be created (None = unlimited)
+    pub remaining: Option<usize>,
+}
+
+impl SchedulerUsage {
+    pub fn new(task_count: usize, max_tasks: u32) -> Self {
+        let remaining = (max_tasks > 0).then(|| (max_tasks as usize).saturating_sub(task_count));
+        Self {
+            task_count,
+            max_tasks,
+            remaining,
+        }
+    }
+
+    /// Whether another task can be created
+    pub fn has_capacity(&self) -> bool {
+        self.remaining != Some(0)
+    }
+}
+
 impl SystemTask {
     /// Generate a unique task ID
     pub fn generate_id() -> SystemTaskId {
@@ -406,6 +639,50 @@ impl SystemTask {
             }
         }
 
+        if let Some(policy) = &self.retry_policy {
+            if policy.max_attempts >= HIGH_RETRY_ATTEMPTS && self.looks_destructive() {
+                warnings.push(format!(
+                    "破坏性操作失败后将重试最多 {0} 次 / Destructive-looking action will be retried up to {0} times on failure",
+                    policy.max_attempts
+                ));
+            }
+        }
+
         warnings
     }
+
+    /// Whether the action appears to delete or overwrite data
+    fn looks_destructive(&self) -> bool {
+        const PROGRAMS: [&str; 10] = [
+            "rm",
+            "rmdir",
+            "rd",
+            "del",
+            "erase",
+            "remove-item",
+            "format",
+            "dd",
+            "shred",
+            "truncate",
+        ];
+        const PHRASES: [&str; 3] = ["drop table", "drop database", "delete from"];
+
+        let text = match &self.action {
+            SystemTaskAction::RunCommand { command, args, .. } => {
+                format!("{} {}", command, args.join(" "))
+            }
+            SystemTaskAction::ExecuteScript { code, .. } => code.clone(),
+            SystemTaskAction::LaunchApp { .. } => return false,
+        }
+        .to_lowercase();
+
+        let destructive_program = text
+            .split(|c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')'))
+            .map(|token| {
+                let name = token.rsplit(['/', '\\']).next().unwrap_or(token);
+                name.strip_suffix(".exe").unwrap_or(name)
+            })
+            .any(|name| PROGRAMS.contains(&name) || name.starts_with("mkfs"));
+        destructive_program || PHRASES.iter().any(|phrase| text.contains(phrase))
+    }
 }
@@ -6,16 +6,21 @@
 // cfg(target_os = "windows") is already applied at module level in mod.rs
 
 use async_trait::async_trait;
-use chrono::Timelike;
+use chrono::{Local, NaiveDateTime, TimeZone, Timelike};
 use log::{debug, error, info, warn};
 use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use super::cron_preview::{timezone_name, to_local_time};
 use super::error::{Result, SchedulerError};
+use super::run_reports::{report_dir, report_path};
 use super::service::{generate_task_name, is_cognia_task, now_iso, SystemScheduler, TASK_PREFIX};
 use super::types::{
-    CreateSystemTaskInput, RunLevel, SchedulerCapabilities, SystemTask, SystemTaskAction,
-    SystemTaskStatus, SystemTaskTrigger, TaskMetadataState, TaskRunResult,
+    CreateSystemTaskInput, RetryPolicy, RunLevel, SchedulerCapabilities, SystemTask,
+    SystemTaskAction, SystemTaskStatus, SystemTaskTrigger, TaskMetadataState, TaskNotifyOn,
+    TaskRunResult,
 };
 
 /// Windows Task Scheduler implementation
@@ -67,10 +72,13 @@ impl WindowsScheduler {
     /// Convert trigger to schtasks schedule parameters
     fn trigger_to_schtasks_args(trigger: &SystemTaskTrigger) -> Result<Vec<String>> {
         match trigger {
-            SystemTaskTrigger::Cron { expression, .. } => {
+            SystemTaskTrigger::Cron {
+                expression,
+                timezone,
+            } => {
                 // Parse cron and convert to schtasks format
                 // schtasks supports: MINUTE, HOURLY, DAILY, WEEKLY, MONTHLY, ONCE, ONSTART, ONLOGON
-                Self::cron_to_schtasks(expression)
+                Self::cron_to_schtasks(expression, timezone_name(timezone.as_deref()))
             }
             SystemTaskTrigger::Interval { seconds } => {
                 let minutes = (*seconds / 60).max(1);
@@ -124,7 +132,10 @@ impl WindowsScheduler {
     }
 
     /// Convert simplified cron expression to schtasks format
-    fn cron_to_schtasks(expression: &str) -> Result<Vec<String>> {
+    ///
+    /// `/ST` is always local time, so fixed times in `timezone` are shifted to
+    /// local time using today's offsets.
+    fn cron_to_schtasks(expression: &str, timezone: Option<&str>) -> Result<Vec<String>> {
         let parts: Vec<&str> = expression.split_whitespace().collect();
         if parts.len() != 5 {
             return Err(SchedulerError::InvalidCron(format!(
@@ -152,22 +163,23 @@ impl WindowsScheduler {
                 ])
             }
             // Every hour at specific minute
-            (m, "*", "*", "*") if m.parse::<u32>().is_ok() => Ok(vec![
-                "/SC".to_string(),
-                "HOURLY".to_string(),
-                "/ST".to_string(),
-                format!("00:{:02}", m.parse::<u32>().unwrap()),
-            ]),
+            (m, "*", "*", "*") if m.parse::<u32>().is_ok() => {
+                // Only the minute matters, which moves for half-hour offsets
+                let (_, minute) =
+                    Self::local_start_time(expression, 0, m.parse().unwrap(), timezone, false);
+                Ok(vec![
+                    "/SC".to_string(),
+                    "HOURLY".to_string(),
+                    "/ST".to_string(),
+                    format!("00:{:02}", minute),
+                ])
+            }
             // Daily at specific time
             (m, h, "*", "*") if m.parse::<u32>().is_ok() && h.parse::<u32>().is_ok() => Ok(vec![
                 "/SC".to_string(),
                 "DAILY".to_string(),
                 "/ST".to_string(),
-                format!(
-                    "{:02}:{:02}",
-                    h.parse::<u32>().unwrap(),
-                    m.parse::<u32>().unwrap()
-                ),
+                Self::format_start_time(expression, h, m, timezone, false),
             ]),
             // Weekly on specific days
             (m, h, "*", days) if m.parse::<u32>().is_ok() && h.parse::<u32>().is_ok() => {
@@ -178,11 +190,7 @@ impl WindowsScheduler {
                     "/D".to_string(),
                     day_str,
                     "/ST".to_string(),
-                    format!(
-                        "{:02}:{:02}",
-                        h.parse::<u32>().unwrap(),
-                        m.parse::<u32>().unwrap()
-                    ),
+                    Self::format_start_time(expression, h, m, timezone, true),
                 ])
             }
             // Monthly on specific day
@@ -197,11 +205,7 @@ impl WindowsScheduler {
                     "/D".to_string(),
                     day.to_string(),
                     "/ST".to_string(),
-                    format!(
-                        "{:02}:{:02}",
-                        h.parse::<u32>().unwrap(),
-                        m.parse::<u32>().unwrap()
-                    ),
+                    Self::format_start_time(expression, h, m, timezone, true),
                 ])
             }
             _ => Err(SchedulerError::InvalidCron(format!(
@@ -211,6 +215,55 @@ impl WindowsScheduler {
         }
     }
 
+    /// `/ST` value for a cron hour and minute that already parsed as numbers
+    fn format_start_time(
+        expression: &str,
+        hour: &str,
+        minute: &str,
+        timezone: Option<&str>,
+        has_days: bool,
+    ) -> String {
+        let (hour, minute) = Self::local_start_time(
+            expression,
+            hour.parse().unwrap(),
+            minute.parse().unwrap(),
+            timezone,
+            has_days,
+        );
+        format!("{:02}:{:02}", hour, minute)
+    }
+
+    /// Shift a wall-clock time in `timezone` to local time
+    fn local_start_time(
+        expression: &str,
+        hour: u32,
+        minute: u32,
+        timezone: Option<&str>,
+        has_days: bool,
+    ) -> (u32, u32) {
+        let Some(tz) = timezone else {
+            return (hour, minute);
+        };
+        match to_local_time(hour, minute, tz) {
+            Some((local_hour, local_minute, day_shift)) => {
+                if day_shift != 0 && has_days {
+                    warn!(
+                        "Cron '{}' in {} falls on a different local day; days are not shifted",
+                        expression, tz
+                    );
+                }
+                (local_hour, local_minute)
+            }
+            None => {
+                warn!(
+                    "Cannot convert cron '{}' from {}, using local time",
+                    expression, tz
+                );
+                (hour, minute)
+            }
+        }
+    }
+
     /// Convert cron day-of-week to schtasks format
     fn convert_dow(dow: &str) -> Result<String> {
         if dow == "*" {
@@ -369,6 +422,130 @@ impl WindowsScheduler {
         }
     }
 
+    /// Path of the generated run shim for a task
+    ///
+    /// Task Scheduler has no per-run retry count for schtasks-created tasks and
+    /// `/TR` is limited to 261 characters, so tasks with a retry policy run
+    /// through a script that retries and reports each attempt.
+    fn run_shim_path(task_name: &str) -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("Cognia")
+            .join("scheduler")
+            .join("shims")
+            .join(format!("{}.ps1", task_name.trim_start_matches('\\')))
+    }
+
+    /// PowerShell script that runs the action until it succeeds or the
+    /// policy's attempts are used up, appending each attempt's exit code to
+    /// `report`
+    ///
+    /// Apps are started with `Start-Process -Wait`, since a GUI app invoked
+    /// directly returns at once without an exit code.
+    fn run_shim_script(
+        program: &str,
+        args: &[String],
+        launches_app: bool,
+        report: &Path,
+        delays: &[u64],
+    ) -> String {
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let run = if launches_app {
+            let argument_list = if args.is_empty() {
+                String::new()
+            } else {
+                let quoted = args.iter().map(|arg| quote(arg)).collect::<Vec<_>>();
+                format!(" -ArgumentList @({})", quoted.join(", "))
+            };
+            format!(
+                "$process = Start-Process -FilePath {}{} -Wait -PassThru\n    \
+                 # Unset when the app could not be started\n    \
+                 $code = if ($process) {{ $process.ExitCode }} else {{ 1 }}",
+                quote(program),
+                argument_list
+            )
+        } else {
+            let invocation = std::iter::once(program)
+                .chain(args.iter().map(String::as_str))
+                .map(quote)
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "& {}\n    \
+                 # Unset when the program could not be started\n    \
+                 $code = if ($null -ne $LASTEXITCODE) {{ $LASTEXITCODE }} else {{ 1 }}",
+                invocation
+            )
+        };
+        let delay_list = delays
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            r#"# Generated by Cognia; runs the scheduled action, retrying on failure
+$report = {}
+$delays = @({})
+for ($attempt = 1; ; $attempt++) {{
+    {}
+    $finished = [DateTimeOffset]::UtcNow.ToUnixTimeSeconds()
+    Add-Content -LiteralPath $report -Value "$finished $code $attempt"
+    if (-not $code) {{ exit 0 }}
+    if ($attempt -gt $delays.Count) {{ exit $code }}
+    Start-Sleep -Seconds $delays[$attempt - 1]
+}}
+"#,
+            quote(&report.to_string_lossy()),
+            delay_list,
+            run
+        )
+    }
+
+    /// Write the run shim for a task that retries and return the command that
+    /// runs it; `None` when the policy allows no retries
+    fn install_run_shim(
+        task_name: &str,
+        program: &str,
+        args: &[String],
+        launches_app: bool,
+        policy: &RetryPolicy,
+    ) -> Result<Option<(String, Vec<String>)>> {
+        let delays = policy.retry_delays();
+        if delays.is_empty() {
+            return Ok(None);
+        }
+
+        let shim_path = Self::run_shim_path(task_name);
+        if let Some(dir) = shim_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::create_dir_all(report_dir())?;
+        fs::write(
+            &shim_path,
+            Self::run_shim_script(
+                program,
+                args,
+                launches_app,
+                &report_path(task_name),
+                &delays,
+            ),
+        )?;
+
+        Ok(Some((
+            "powershell.exe".to_string(),
+            vec![
+                "-NoProfile".to_string(),
+                "-WindowStyle".to_string(),
+                "Hidden".to_string(),
+                "-ExecutionPolicy".to_string(),
+                "Bypass".to_string(),
+                "-File".to_string(),
+                format!("\"{}\"", shim_path.display()),
+            ],
+        )))
+    }
+
     /// Get path to Cognia executable
     fn get_cognia_exe_path() -> String {
         std::env::current_exe()
@@ -596,12 +773,52 @@ impl WindowsScheduler {
         Some(enriched)
     }
 
+    /// Convert a schtasks local time to RFC 3339
+    ///
+    /// schtasks prints times in the user's locale, so the common date orders
+    /// are tried; times that match none of them are dropped rather than
+    /// compared as text.
+    fn parse_schtasks_time(value: &str) -> Option<String> {
+        const FORMATS: [&str; 6] = [
+            "%m/%d/%Y %I:%M:%S %p",
+            "%d/%m/%Y %H:%M:%S",
+            "%Y/%m/%d %H:%M:%S",
+            "%d.%m.%Y %H:%M:%S",
+            "%Y-%m-%d %H:%M:%S",
+            "%d-%m-%Y %H:%M:%S",
+        ];
+        let naive = FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(value.trim(), format).ok())?;
+        let local = Local.from_local_datetime(&naive).earliest()?;
+        Some(local.with_timezone(&chrono::Utc).to_rfc3339())
+    }
+
+    /// Result of the last run from schtasks' "Last Result", `None` when the
+    /// task has not run yet or is still running
+    fn run_result_from_code(code: i32) -> Option<TaskRunResult> {
+        const SCHED_S_TASK_RUNNING: i32 = 0x41301;
+        const SCHED_S_TASK_HAS_NOT_RUN: i32 = 0x41303;
+        if code == SCHED_S_TASK_RUNNING || code == SCHED_S_TASK_HAS_NOT_RUN {
+            return None;
+        }
+        Some(TaskRunResult {
+            success: code == 0,
+            exit_code: Some(code),
+            stdout: None,
+            stderr: None,
+            error: None,
+            duration_ms: None,
+        })
+    }
+
     /// Parse schtasks /Query output to extract task info
     fn parse_task_query(output: &str, task_name: &str) -> Option<SystemTask> {
         // Basic parsing of schtasks output
         let mut status = SystemTaskStatus::Unknown;
         let mut next_run = None;
         let mut last_run = None;
+        let mut last_result = None;
 
         for line in output.lines() {
             let line = line.trim();
@@ -620,9 +837,13 @@ impl WindowsScheduler {
                 }
             } else if line.starts_with("Last Run Time:") {
                 let t = line.trim_start_matches("Last Run Time:").trim();
-                if t != "N/A" {
-                    last_run = Some(t.to_string());
-                }
+                last_run = Self::parse_schtasks_time(t);
+            } else if line.starts_with("Last Result:") {
+                let code = line.trim_start_matches("Last Result:").trim();
+                last_result = code
+                    .parse::<i32>()
+                    .ok()
+                    .and_then(Self::run_result_from_code);
             }
         }
 
@@ -649,10 +870,13 @@ impl WindowsScheduler {
             tags: vec![],
             created_at: None,
             updated_at: None,
-            last_run_at: last_run,
+            last_run_at: last_result.as_ref().and(last_run),
             next_run_at: next_run,
-            last_result: None,
+            last_result,
             metadata_state: TaskMetadataState::Degraded,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         })
     }
 }
@@ -686,6 +910,15 @@ impl SystemScheduler for WindowsScheduler {
         self.available
     }
 
+    fn reports_runs(&self, retry_policy: Option<&RetryPolicy>) -> bool {
+        // Only tasks that retry run through the reporting shim
+        retry_policy.is_some_and(|policy| !policy.retry_delays().is_empty())
+    }
+
+    fn converts_cron_timezones(&self) -> bool {
+        true
+    }
+
     async fn create_task(&self, input: CreateSystemTaskInput) -> Result<SystemTask> {
         if !self.available {
             return Err(SchedulerError::NotAvailable(
@@ -713,7 +946,15 @@ impl SystemScheduler for WindowsScheduler {
         args.extend(trigger_args);
 
         // Add action
-        let (program, prog_args) = Self::build_action_command(&input.action)?;
+        let (mut program, mut prog_args) = Self::build_action_command(&input.action)?;
+        if let Some(policy) = &input.retry_policy {
+            let launches_app = matches!(input.action, SystemTaskAction::LaunchApp { .. });
+            if let Some(shim) =
+                Self::install_run_shim(&task_name, &program, &prog_args, launches_app, policy)?
+            {
+                (program, prog_args) = shim;
+            }
+        }
         args.push("/TR".to_string());
         if prog_args.is_empty() {
             args.push(format!("\"{}\"", program));
@@ -735,6 +976,7 @@ impl SystemScheduler for WindowsScheduler {
         let output = Command::new("schtasks").args(&args).output()?;
 
         if !output.status.success() {
+            let _ = fs::remove_file(Self::run_shim_path(&task_name));
             let stderr = String::from_utf8_lossy(&output.stderr);
             error!("Failed to create task: {}", stderr);
 
@@ -766,6 +1008,9 @@ impl SystemScheduler for WindowsScheduler {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Full,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: input.retry_policy,
         };
 
         task.requires_admin = task.check_requires_admin();
@@ -803,6 +1048,11 @@ impl SystemScheduler for WindowsScheduler {
             return Err(SchedulerError::Platform(stderr.to_string()));
         }
 
+        let shim_path = Self::run_shim_path(id);
+        if shim_path.exists() {
+            let _ = fs::remove_file(&shim_path);
+        }
+
         info!("Deleted system task: {}", id);
         Ok(true)
     }
@@ -903,6 +1153,7 @@ impl SystemScheduler for WindowsScheduler {
         Ok(true)
     }
 
+    /// Starts the task without waiting for it; its run shim reports the outcome
     async fn run_task_now(&self, id: &str) -> Result<TaskRunResult> {
         let start = std::time::Instant::now();
 
@@ -959,7 +1210,7 @@ mod tests {
 
     #[test]
     fn test_cron_to_schtasks_every_minute() {
-        let result = WindowsScheduler::cron_to_schtasks("* * * * *");
+        let result = WindowsScheduler::cron_to_schtasks("* * * * *", None);
         assert!(result.is_ok());
         let args = result.unwrap();
         assert!(args.contains(&"MINUTE".to_string()));
@@ -967,7 +1218,7 @@ mod tests {
 
     #[test]
     fn test_cron_to_schtasks_every_5_minutes() {
-        let result = WindowsScheduler::cron_to_schtasks("*/5 * * * *");
+        let result = WindowsScheduler::cron_to_schtasks("*/5 * * * *", None);
         assert!(result.is_ok());
         let args = result.unwrap();
         assert!(args.contains(&"MINUTE".to_string()));
@@ -976,13 +1227,85 @@ mod tests {
 
     #[test]
     fn test_cron_to_schtasks_daily() {
-        let result = WindowsScheduler::cron_to_schtasks("0 9 * * *");
+        let result = WindowsScheduler::cron_to_schtasks("0 9 * * *", None);
         assert!(result.is_ok());
         let args = result.unwrap();
         assert!(args.contains(&"DAILY".to_string()));
         assert!(args.contains(&"09:00".to_string()));
     }
 
+    #[test]
+    fn test_cron_to_schtasks_converts_timezone() {
+        let (hour, minute, _) = to_local_time(9, 0, "UTC").unwrap();
+        let args = WindowsScheduler::cron_to_schtasks("0 9 * * *", Some("UTC")).unwrap();
+        assert!(args.contains(&format!("{:02}:{:02}", hour, minute)));
+    }
+
+    #[test]
+    fn test_run_shim_script_quotes_invocation_and_reports_attempts() {
+        let report = Path::new("C:\\Users\\O'Neil\\runs\\cognia_backup.log");
+        let script = WindowsScheduler::run_shim_script(
+            "cmd.exe",
+            &["/C".to_string(), "echo it's done".to_string()],
+            false,
+            report,
+            &[5, 10],
+        );
+        assert!(script.contains("$delays = @(5, 10)"));
+        assert!(script.contains("& 'cmd.exe' '/C' 'echo it''s done'"));
+        assert!(script.contains("$code = if ($null -ne $LASTEXITCODE)"));
+        assert!(script.contains("$report = 'C:\\Users\\O''Neil\\runs\\cognia_backup.log'"));
+        assert!(script.contains("-Value \"$finished $code $attempt\""));
+
+        // Apps are waited for, since they do not set $LASTEXITCODE
+        let app = WindowsScheduler::run_shim_script(
+            "C:\\Apps\\editor.exe",
+            &["notes.txt".to_string()],
+            true,
+            report,
+            &[5],
+        );
+        assert!(app.contains(
+            "$process = Start-Process -FilePath 'C:\\Apps\\editor.exe' -ArgumentList @('notes.txt') -Wait -PassThru"
+        ));
+        assert!(app.contains("$code = if ($process) { $process.ExitCode } else { 1 }"));
+        assert!(!app.contains("& '"));
+        let no_args = WindowsScheduler::run_shim_script("editor.exe", &[], true, report, &[5]);
+        assert!(no_args.contains("-FilePath 'editor.exe' -Wait -PassThru"));
+        assert!(WindowsScheduler::run_shim_path("\\Cognia_Backup").ends_with("Cognia_Backup.ps1"));
+    }
+
+    #[test]
+    fn test_parse_task_query_reads_last_run_and_result() {
+        let output = "TaskName: \\Cognia_Backup\nStatus: Ready\nLast Run Time: 1/2/2026 3:04:05 PM\nLast Result: 2\n";
+        let task = WindowsScheduler::parse_task_query(output, "\\Cognia_Backup").unwrap();
+        let expected = Local
+            .with_ymd_and_hms(2026, 1, 2, 15, 4, 5)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+            .to_rfc3339();
+        assert_eq!(task.last_run_at.as_deref(), Some(expected.as_str()));
+        let result = task.last_result.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.exit_code, Some(2));
+
+        let never_run =
+            "Status: Ready\nLast Run Time: 11/30/1999 12:00:00 AM\nLast Result: 267011\n";
+        let task = WindowsScheduler::parse_task_query(never_run, "\\Cognia_Backup").unwrap();
+        assert!(task.last_run_at.is_none());
+        assert!(task.last_result.is_none());
+
+        assert_eq!(
+            WindowsScheduler::parse_schtasks_time("2026/01/02 15:04:05"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            WindowsScheduler::parse_schtasks_time("02.01.2026 15:04:05"),
+            Some(expected)
+        );
+        assert!(WindowsScheduler::parse_schtasks_time("N/A").is_none());
+    }
+
     #[test]
     fn test_generate_task_name() {
         assert_eq!(generate_task_name("My Task"), "Cognia_My_Task");
@@ -1018,6 +1341,9 @@ mod tests {
             next_run_at: None,
             last_result: None,
             metadata_state: TaskMetadataState::Degraded,
+            external: false,
+            notify_on: TaskNotifyOn::Never,
+            retry_policy: None,
         };
 
         let xml = r#"
@@ -1,6 +1,6 @@
 //! Recording history management
 
-use super::RecordingMetadata;
+use super::{PreviewFrame, RecordingMetadata};
 use log::{debug, error, info, warn};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,34 @@ pub struct RecordingHistoryEntry {
     pub thumbnail: Option<String>,
     pub is_pinned: bool,
     pub tags: Vec<String>,
+    /// Bookmarks added during capture, ordered by offset
+    #[serde(default)]
+    pub markers: Vec<RecordingMarker>,
+    /// Evenly spaced preview frames extracted alongside the thumbnail
+    #[serde(default)]
+    pub filmstrip: Vec<PreviewFrame>,
+    /// Recording this entry was derived from (e.g. by trimming)
+    #[serde(default)]
+    pub source: Option<RecordingSource>,
+}
+
+/// Reference to the recording an entry was cut from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordingSource {
+    pub id: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Bookmark placed during a recording
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordingMarker {
+    pub id: String,
+    /// Offset from recording start in milliseconds, excluding paused time
+    pub offset_ms: u64,
+    pub label: String,
+    /// Wall-clock timestamp when the marker was added
+    pub created_at: i64,
 }
 
 impl RecordingHistoryEntry {
@@ -40,6 +68,9 @@ impl RecordingHistoryEntry {
             thumbnail: metadata.thumbnail.clone(),
             is_pinned: false,
             tags: Vec::new(),
+            markers: metadata.markers.clone(),
+            filmstrip: Vec::new(),
+            source: None,
         }
     }
 }
@@ -115,6 +146,13 @@ impl RecordingHistory {
         self.flush();
     }
 
+    /// Save history to disk if it has unsaved changes
+    pub fn flush_if_dirty(&self) {
+        if self.dirty.load(Ordering::SeqCst) {
+            self.flush();
+        }
+    }
+
     /// Force save history to disk regardless of dirty flag
     pub fn flush(&self) {
         if let Some(ref path) = self.persist_path {
@@ -261,6 +299,8 @@ impl RecordingHistory {
                 entry.id, entry.mode
             );
 
+            remove_preview_files(&entry);
+
             // Delete the file if it exists
             if let Some(path) = entry.file_path {
                 if std::path::Path::new(&path).exists() {
@@ -308,6 +348,7 @@ impl RecordingHistory {
         let mut deleted_files = 0;
         let mut failed_deletions = 0;
         for entry in entries.iter().filter(|e| !e.is_pinned) {
+            remove_preview_files(entry);
             if let Some(ref path) = entry.file_path {
                 debug!(
                     "[RecordingHistory] Deleting file for entry {}: {}",
@@ -415,6 +456,45 @@ impl RecordingHistory {
         }
     }
 
+    /// Get markers for an entry
+    pub fn get_markers(&self, id: &str) -> Option<Vec<RecordingMarker>> {
+        self.entries
+            .read()
+            .iter()
+            .find(|e| e.id == id)
+            .map(|e| e.markers.clone())
+    }
+
+    /// Replace the thumbnail and filmstrip of an entry, removing the previous filmstrip files
+    pub fn set_previews(&self, id: &str, thumbnail: String, filmstrip: Vec<PreviewFrame>) -> bool {
+        let mut entries = self.entries.write();
+        let Some(entry) = entries.iter_mut().find(|e| e.id == id) else {
+            warn!(
+                "[RecordingHistory] Cannot set previews - entry not found: id={}",
+                id
+            );
+            return false;
+        };
+
+        for old in &entry.filmstrip {
+            if !filmstrip.iter().any(|f| f.path == old.path) {
+                let _ = std::fs::remove_file(&old.path);
+            }
+        }
+
+        entry.thumbnail = Some(thumbnail);
+        entry.filmstrip = filmstrip;
+        debug!(
+            "[RecordingHistory] Updated previews for entry: id={}, filmstrip={}",
+            id,
+            entry.filmstrip.len()
+        );
+        drop(entries);
+        self.mark_dirty();
+        self.save_to_disk();
+        true
+    }
+
     /// Get total size of all recordings
     pub fn get_total_size(&self) -> u64 {
         let total = self.entries.read().iter().map(|e| e.file_size).sum();
@@ -436,6 +516,20 @@ impl RecordingHistory {
     }
 }
 
+/// Remove the filmstrip files of an entry
+fn remove_preview_files(entry: &RecordingHistoryEntry) {
+    for frame in &entry.filmstrip {
+        if let Err(e) = std::fs::remove_file(&frame.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(
+                    "[RecordingHistory] Failed to delete preview {}: {}",
+                    frame.path, e
+                );
+            }
+        }
+    }
+}
+
 impl Default for RecordingHistory {
     fn default() -> Self {
         Self::new()
@@ -470,6 +564,9 @@ mod tests {
             thumbnail: None,
             is_pinned: false,
             tags: Vec::new(),
+            markers: Vec::new(),
+            filmstrip: Vec::new(),
+            source: None,
         }
     }
 
@@ -556,6 +653,9 @@ mod tests {
             thumbnail: None,
             is_pinned: false,
             tags: Vec::new(),
+            markers: Vec::new(),
+            filmstrip: Vec::new(),
+            source: None,
         };
 
         history.add(entry);
@@ -565,6 +665,42 @@ mod tests {
         assert!(history.get_by_id("delete-test").is_none());
     }
 
+    #[test]
+    fn test_history_set_previews_replaces_files() {
+        let history = RecordingHistory::new();
+        let dir = tempdir().unwrap();
+        let frame = |name: &str, ts: u64| {
+            let path = dir.path().join(name);
+            fs::write(&path, [0u8]).unwrap();
+            PreviewFrame {
+                path: path.to_string_lossy().to_string(),
+                timestamp_ms: ts,
+            }
+        };
+
+        history.add(create_test_entry("preview-test"));
+        let old_strip = frame("rec_strip_0.jpg", 500);
+        let kept_strip = frame("rec_strip_1.jpg", 1500);
+        assert!(history.set_previews(
+            "preview-test",
+            "b2xk".to_string(),
+            vec![old_strip.clone(), kept_strip.clone()]
+        ));
+
+        // Regenerating drops filmstrip files that are no longer referenced
+        assert!(history.set_previews("preview-test", "bmV3".to_string(), vec![kept_strip.clone()]));
+        let entry = history.get_by_id("preview-test").unwrap();
+        assert_eq!(entry.thumbnail.as_deref(), Some("bmV3"));
+        assert_eq!(entry.filmstrip, vec![kept_strip.clone()]);
+        assert!(!std::path::Path::new(&old_strip.path).exists());
+        assert!(std::path::Path::new(&kept_strip.path).exists());
+
+        assert!(!history.set_previews("missing", String::new(), Vec::new()));
+
+        history.delete("preview-test").unwrap();
+        assert!(!std::path::Path::new(&kept_strip.path).exists());
+    }
+
     #[test]
     fn test_history_dirty_flag_on_add() {
         let dir = tempdir().unwrap();
@@ -670,6 +806,9 @@ mod tests {
             thumbnail: None,
             is_pinned: false,
             tags: Vec::new(),
+            markers: Vec::new(),
+            filmstrip: Vec::new(),
+            source: None,
         };
         let unpinned_entry = RecordingHistoryEntry {
             id: "unpinned".to_string(),
@@ -683,6 +822,9 @@ mod tests {
             thumbnail: None,
             is_pinned: false,
             tags: Vec::new(),
+            markers: Vec::new(),
+            filmstrip: Vec::new(),
+            source: None,
         };
 
         history.add(pinned_entry);
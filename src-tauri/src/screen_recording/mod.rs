@@ -5,6 +5,7 @@
 //! - Window recording
 //! - Region recording
 //! - Audio capture (system and microphone)
+//! - Instant replay of the last few seconds
 //! - Video trimming and export
 
 pub mod click_highlight;
@@ -13,6 +14,7 @@ pub mod ffmpeg;
 mod history;
 pub mod progress;
 mod recorder;
+mod replay;
 pub mod storage;
 pub mod timeline_renderer;
 pub mod toolbar;
@@ -22,8 +24,11 @@ pub mod window_snap;
 pub use click_highlight::RecordingClickOverlay;
 pub use error::RecordingError;
 pub use ffmpeg::{FFmpegInfo, FFmpegInstallGuide, HardwareAcceleration};
-pub use history::{RecordingHistory, RecordingHistoryEntry};
+pub use history::{RecordingHistory, RecordingHistoryEntry, RecordingMarker, RecordingSource};
 pub use recorder::ScreenRecorder;
+pub use replay::{
+    ReplayBuffer, ReplayStatus, MAX_REPLAY_BUFFER_SECS, MIN_REPLAY_BUFFER_SECS, REPLAY_SEGMENT_SECS,
+};
 pub use storage::{
     AggregatedStorageStatus, CleanupResult, StorageConfig, StorageFile, StorageFileType,
     StorageManager, StorageStats,
@@ -35,8 +40,8 @@ pub use toolbar::{
 
 use toolbar::ToolbarPosition as ToolbarPos;
 pub use video_processor::{
-    EncodingSupport, VideoConvertOptions, VideoInfo, VideoProcessingResult, VideoProcessor,
-    VideoTrimOptions,
+    EncodingSupport, MarkerExportFormat, PreviewFrame, TrimMode, VideoConvertOptions, VideoInfo,
+    VideoProcessingResult, VideoProcessor, VideoTrimOptions, MAX_FILMSTRIP_FRAMES,
 };
 pub use window_snap::SnapEdge;
 
@@ -59,6 +64,17 @@ pub struct RecordingStats {
     pub pinned_count: usize,
 }
 
+/// Result of trimming a recording from history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingTrimResult {
+    /// The new history entry for the trimmed clip
+    pub entry: RecordingHistoryEntry,
+    /// Whether the trim was a stream copy or a re-encode
+    pub mode: TrimMode,
+    /// Whether the source recording was kept
+    pub original_kept: bool,
+}
+
 /// Screen recording configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingConfig {
@@ -103,12 +119,49 @@ pub struct RecordingConfig {
     /// Microphone device name (None = default/auto)
     #[serde(default)]
     pub microphone_device: Option<String>,
+    /// System audio volume multiplier (0.0-4.0, 1.0 = unchanged)
+    #[serde(default = "default_audio_gain")]
+    pub system_audio_gain: f32,
+    /// Microphone volume multiplier (0.0-4.0, 1.0 = unchanged)
+    #[serde(default = "default_audio_gain")]
+    pub mic_gain: f32,
+    /// Whether to extract a preview thumbnail when a recording finishes
+    #[serde(default = "default_true")]
+    pub generate_thumbnail: bool,
+    /// Number of filmstrip frames to extract alongside the thumbnail (0 = none)
+    #[serde(default)]
+    pub filmstrip_frames: u32,
+    /// Seconds of footage the instant replay buffer keeps (5-600)
+    #[serde(default = "default_replay_buffer_secs")]
+    pub replay_buffer_secs: u32,
 }
 
+/// Minimum per-source audio gain
+pub const MIN_AUDIO_GAIN: f32 = 0.0;
+/// Maximum per-source audio gain
+pub const MAX_AUDIO_GAIN: f32 = 4.0;
+
 fn default_true() -> bool {
     true
 }
 
+fn default_audio_gain() -> f32 {
+    1.0
+}
+
+fn default_replay_buffer_secs() -> u32 {
+    30
+}
+
+/// Clamp an audio gain to the supported range, treating non-finite values as unity
+pub fn clamp_audio_gain(gain: f32) -> f32 {
+    if gain.is_finite() {
+        gain.clamp(MIN_AUDIO_GAIN, MAX_AUDIO_GAIN)
+    } else {
+        default_audio_gain()
+    }
+}
+
 impl Default for RecordingConfig {
     fn default() -> Self {
         Self {
@@ -130,10 +183,23 @@ impl Default for RecordingConfig {
             preferred_encoder: None,
             system_audio_device: None,
             microphone_device: None,
+            system_audio_gain: default_audio_gain(),
+            mic_gain: default_audio_gain(),
+            generate_thumbnail: true,
+            filmstrip_frames: 0,
+            replay_buffer_secs: default_replay_buffer_secs(),
         }
     }
 }
 
+impl RecordingConfig {
+    /// Clamp audio gains to the supported range
+    pub fn normalize_audio_gains(&mut self) {
+        self.system_audio_gain = clamp_audio_gain(self.system_audio_gain);
+        self.mic_gain = clamp_audio_gain(self.mic_gain);
+    }
+}
+
 /// Recording metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingMetadata {
@@ -165,6 +231,9 @@ pub struct RecordingMetadata {
     pub has_audio: bool,
     /// Thumbnail base64
     pub thumbnail: Option<String>,
+    /// Bookmarks added during capture
+    #[serde(default)]
+    pub markers: Vec<RecordingMarker>,
 }
 
 /// Recording region coordinates
@@ -197,6 +266,7 @@ impl Default for RecordingStatus {
 pub struct ScreenRecordingManager {
     config: Arc<RwLock<RecordingConfig>>,
     recorder: ScreenRecorder,
+    replay: Arc<ReplayBuffer>,
     history: RecordingHistory,
     storage: RwLock<StorageManager>,
     app_handle: AppHandle,
@@ -282,9 +352,17 @@ impl ScreenRecordingManager {
             RecordingHistory::new()
         };
 
+        // Replay segments are scratch data, kept out of the recordings directory
+        let replay_dir = app_handle
+            .path()
+            .app_cache_dir()
+            .map(|p| p.join("replay"))
+            .unwrap_or_else(|_| std::env::temp_dir().join("cognia_replay"));
+
         Self {
             config: Arc::new(RwLock::new(config.clone())),
             recorder: ScreenRecorder::new(app_handle.clone()),
+            replay: Arc::new(ReplayBuffer::new(app_handle.clone(), replay_dir)),
             history,
             storage: RwLock::new(StorageManager::new(storage_config)),
             app_handle,
@@ -292,7 +370,8 @@ impl ScreenRecordingManager {
     }
 
     /// Update configuration
-    pub fn update_config(&self, config: RecordingConfig) {
+    pub fn update_config(&self, mut config: RecordingConfig) {
+        config.normalize_audio_gains();
         info!("[ScreenRecording] Updating configuration: format={}, codec={}, fps={}, quality={}, audio={}, mic={}",
             config.format, config.codec, config.frame_rate, config.quality,
             config.capture_system_audio, config.capture_microphone);
@@ -409,6 +488,13 @@ impl ScreenRecordingManager {
         }
     }
 
+    /// Persist unsaved recording history and stop the replay buffer before the
+    /// app exits
+    pub fn shutdown(&self) {
+        self.history.flush_if_dirty();
+        self.replay.shutdown();
+    }
+
     /// Stop recording
     pub async fn stop(&self) -> Result<RecordingMetadata, String> {
         info!("[ScreenRecording] Stopping recording");
@@ -422,6 +508,19 @@ impl ScreenRecordingManager {
                 self.history.add(entry);
                 debug!("[ScreenRecording] Recording added to history");
 
+                let config = self.config.read().clone();
+                if config.generate_thumbnail {
+                    if let Err(e) = self
+                        .generate_previews_async(&metadata.id, None, config.filmstrip_frames)
+                        .await
+                    {
+                        warn!(
+                            "[ScreenRecording] Thumbnail generation failed for {}: {}",
+                            metadata.id, e
+                        );
+                    }
+                }
+
                 Ok(metadata)
             }
             Err(e) => {
@@ -451,6 +550,331 @@ impl ScreenRecordingManager {
         self.recorder.get_duration()
     }
 
+    /// Add a bookmark to the active recording
+    pub fn add_marker(&self, label: String) -> Result<RecordingMarker, String> {
+        match self.recorder.add_marker(label) {
+            Ok(marker) => {
+                info!(
+                    "[ScreenRecording] Marker added: '{}' at {}ms",
+                    marker.label, marker.offset_ms
+                );
+                Ok(marker)
+            }
+            Err(e) => {
+                warn!("[ScreenRecording] Failed to add marker: {}", e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Get markers for a recording (the active one or one in history)
+    pub fn get_markers(&self, id: &str) -> Result<Vec<RecordingMarker>, String> {
+        if let Some(markers) = self.recorder.get_active_markers(id) {
+            return Ok(markers);
+        }
+        self.history
+            .get_markers(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))
+    }
+
+    /// Write a recording's markers as mp4 chapters or a WebVTT sidecar file
+    pub fn export_markers(
+        &self,
+        id: &str,
+        format: MarkerExportFormat,
+        output_path: Option<String>,
+    ) -> Result<String, String> {
+        let entry = self
+            .history
+            .get_by_id(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))?;
+        let input_path = entry
+            .file_path
+            .as_deref()
+            .ok_or_else(|| format!("Recording has no file: {}", id))?;
+        info!(
+            "[ScreenRecording] Exporting {} markers for recording {} as {:?}",
+            entry.markers.len(),
+            id,
+            format
+        );
+        VideoProcessor::export_markers(
+            input_path,
+            &entry.markers,
+            entry.duration_ms,
+            format,
+            output_path.as_deref(),
+        )
+    }
+
+    /// Directory where preview frames are stored
+    fn thumbnails_dir(&self) -> Result<PathBuf, String> {
+        self.get_recordings_dir()
+            .map(|p| p.join("thumbnails"))
+            .ok_or_else(|| "Could not determine recordings directory".to_string())
+    }
+
+    /// Extract a thumbnail (and optional filmstrip) for a recording in history.
+    ///
+    /// Frame extraction runs on a blocking thread; the entry is updated when it finishes.
+    pub async fn generate_previews_async(
+        &self,
+        id: &str,
+        timestamp_ms: Option<u64>,
+        filmstrip_frames: u32,
+    ) -> Result<RecordingHistoryEntry, String> {
+        let entry = self
+            .history
+            .get_by_id(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))?;
+        let video_path = entry
+            .file_path
+            .clone()
+            .ok_or_else(|| format!("Recording has no file: {}", id))?;
+        let output_dir = self.thumbnails_dir()?;
+        let name = id.to_string();
+        let filmstrip_frames = filmstrip_frames.min(MAX_FILMSTRIP_FRAMES);
+
+        debug!(
+            "[ScreenRecording] Generating previews for {}: timestamp={:?}, filmstrip={}",
+            id, timestamp_ms, filmstrip_frames
+        );
+        let (thumbnail, filmstrip) = tauri::async_runtime::spawn_blocking(move || {
+            VideoProcessor::generate_previews(
+                &video_path,
+                &output_dir,
+                &name,
+                timestamp_ms,
+                filmstrip_frames,
+            )
+        })
+        .await
+        .map_err(|e| format!("Thumbnail task failed: {}", e))??;
+
+        if !self.history.set_previews(id, thumbnail, filmstrip) {
+            return Err(format!("Recording not found: {}", id));
+        }
+        self.history
+            .get_by_id(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))
+    }
+
+    /// Trim a recording into a new history entry that references the source.
+    ///
+    /// `start_ms`/`end_ms` are offsets into the source. When `keep_original` is false the
+    /// source entry and file are removed after the trimmed copy is written, and its pin
+    /// state and tags carry over to the new entry.
+    pub async fn trim_recording(
+        &self,
+        id: &str,
+        start_ms: u64,
+        end_ms: u64,
+        keep_original: bool,
+    ) -> Result<RecordingTrimResult, String> {
+        let source = self
+            .history
+            .get_by_id(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))?;
+        let input_path = source
+            .file_path
+            .clone()
+            .ok_or_else(|| format!("Recording has no file: {}", id))?;
+        let end_ms = if source.duration_ms > 0 {
+            end_ms.min(source.duration_ms)
+        } else {
+            end_ms
+        };
+        if start_ms >= end_ms {
+            return Err("End time must be greater than start time".to_string());
+        }
+
+        let input = std::path::Path::new(&input_path);
+        let stem = input
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| id.to_string());
+        let ext = input
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_else(|| "mp4".to_string());
+        let output_path = input
+            .with_file_name(format!("{}_trim_{}-{}.{}", stem, start_ms, end_ms, ext))
+            .to_string_lossy()
+            .to_string();
+
+        info!(
+            "[ScreenRecording] Trimming recording {}: {}ms-{}ms, keep_original={}",
+            id, start_ms, end_ms, keep_original
+        );
+        let app_handle = self.app_handle.clone();
+        let trim_output = output_path.clone();
+        let (result, mode) = tauri::async_runtime::spawn_blocking(move || {
+            VideoProcessor::trim_preserving_quality(
+                &input_path,
+                &trim_output,
+                start_ms as f64 / 1000.0,
+                end_ms as f64 / 1000.0,
+                &app_handle,
+            )
+        })
+        .await
+        .map_err(|e| format!("Trim task failed: {}", e))??;
+
+        let entry = RecordingHistoryEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            duration_ms: end_ms - start_ms,
+            width: source.width,
+            height: source.height,
+            mode: source.mode.clone(),
+            file_path: Some(result.output_path.clone()),
+            file_size: result.file_size,
+            thumbnail: None,
+            is_pinned: !keep_original && source.is_pinned,
+            tags: if keep_original {
+                Vec::new()
+            } else {
+                source.tags.clone()
+            },
+            markers: source
+                .markers
+                .iter()
+                .filter(|m| m.offset_ms >= start_ms && m.offset_ms <= end_ms)
+                .cloned()
+                .map(|mut m| {
+                    m.offset_ms -= start_ms;
+                    m
+                })
+                .collect(),
+            filmstrip: Vec::new(),
+            source: Some(RecordingSource {
+                id: source.id.clone(),
+                start_ms,
+                end_ms,
+            }),
+        };
+        let new_id = entry.id.clone();
+        self.history.add(entry);
+
+        if !keep_original {
+            self.history.delete(id)?;
+        }
+
+        let config = self.config.read().clone();
+        if config.generate_thumbnail {
+            if let Err(e) = self
+                .generate_previews_async(&new_id, None, config.filmstrip_frames)
+                .await
+            {
+                warn!(
+                    "[ScreenRecording] Thumbnail generation failed for {}: {}",
+                    new_id, e
+                );
+            }
+        }
+
+        let entry = self
+            .history
+            .get_by_id(&new_id)
+            .ok_or_else(|| format!("Recording not found: {}", new_id))?;
+        info!(
+            "[ScreenRecording] Trim complete: {} -> {} ({:?}, original kept: {})",
+            id, new_id, mode, keep_original
+        );
+        Ok(RecordingTrimResult {
+            entry,
+            mode,
+            original_kept: keep_original,
+        })
+    }
+
+    /// Get the base64 thumbnail of a recording, generating it if it does not exist yet
+    pub async fn get_thumbnail(&self, id: &str) -> Result<String, String> {
+        let entry = self
+            .history
+            .get_by_id(id)
+            .ok_or_else(|| format!("Recording not found: {}", id))?;
+        if let Some(thumbnail) = entry.thumbnail {
+            return Ok(thumbnail);
+        }
+        self.generate_previews_async(id, None, entry.filmstrip.len() as u32)
+            .await?
+            .thumbnail
+            .ok_or_else(|| "Failed to generate thumbnail".to_string())
+    }
+
+    /// Start the instant replay buffer on a monitor (primary when not given)
+    pub fn start_replay(&self, monitor_index: Option<usize>) -> Result<ReplayStatus, String> {
+        let monitors = self.recorder.get_monitors();
+        let monitor = monitor_index
+            .and_then(|i| monitors.get(i))
+            .or_else(|| monitors.iter().find(|m| m.is_primary))
+            .ok_or_else(|| {
+                String::from(RecordingError::monitor_not_found(
+                    monitor_index.unwrap_or(0),
+                    monitors.len(),
+                ))
+            })?;
+        let config = self.config.read().clone();
+        info!(
+            "[ScreenRecording] Starting replay buffer on monitor {}",
+            monitor.index
+        );
+        self.replay.start(&config, monitor)
+    }
+
+    /// Stop the instant replay buffer, discarding buffered footage
+    pub async fn stop_replay(&self) -> Result<(), String> {
+        info!("[ScreenRecording] Stopping replay buffer");
+        // Waiting for FFmpeg to exit blocks
+        let replay = self.replay.clone();
+        tauri::async_runtime::spawn_blocking(move || replay.stop())
+            .await
+            .map_err(|e| format!("Replay stop task failed: {}", e))?
+    }
+
+    /// Get the instant replay buffer state
+    pub fn get_replay_status(&self) -> ReplayStatus {
+        self.replay.status()
+    }
+
+    /// Save the buffered footage as a new recording; the buffer keeps running
+    pub async fn save_replay(&self) -> Result<RecordingMetadata, String> {
+        let config = self.config.read().clone();
+        let dir = config
+            .save_directory
+            .clone()
+            .ok_or_else(|| "No save directory configured".to_string())?;
+        let filename = format!(
+            "replay_{}_{}.{}",
+            chrono::Local::now().format("%Y%m%d_%H%M%S"),
+            &uuid::Uuid::new_v4().to_string()[..8],
+            config.format
+        );
+        let output_path = std::path::Path::new(&dir).join(filename);
+
+        info!("[ScreenRecording] Saving replay to {:?}", output_path);
+        let replay = self.replay.clone();
+        let metadata = tauri::async_runtime::spawn_blocking(move || replay.save(&output_path))
+            .await
+            .map_err(|e| format!("Replay save task failed: {}", e))??;
+
+        self.history
+            .add(RecordingHistoryEntry::from_metadata(&metadata));
+        if config.generate_thumbnail {
+            if let Err(e) = self
+                .generate_previews_async(&metadata.id, None, config.filmstrip_frames)
+                .await
+            {
+                warn!(
+                    "[ScreenRecording] Thumbnail generation failed for {}: {}",
+                    metadata.id, e
+                );
+            }
+        }
+        Ok(metadata)
+    }
+
     /// Get recording history
     pub fn get_history(&self, count: usize) -> Vec<RecordingHistoryEntry> {
         self.history.get_recent(count)
@@ -793,6 +1217,9 @@ pub struct MonitorInfo {
 pub struct AudioDevices {
     pub system_audio_available: bool,
     pub microphones: Vec<AudioDevice>,
+    /// Output/loopback devices usable as the system audio source
+    #[serde(default)]
+    pub system_audio_devices: Vec<AudioDevice>,
 }
 
 /// Audio device information
@@ -827,6 +1254,8 @@ mod tests {
         assert!(config.preferred_encoder.is_none());
         assert!(config.system_audio_device.is_none());
         assert!(config.microphone_device.is_none());
+        assert!(config.generate_thumbnail);
+        assert_eq!(config.filmstrip_frames, 0);
     }
 
     #[test]
@@ -862,6 +1291,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recording_config_audio_gains() {
+        let config = RecordingConfig::default();
+        assert_eq!(config.system_audio_gain, 1.0);
+        assert_eq!(config.mic_gain, 1.0);
+
+        // Configs saved before gains existed default to unity
+        let mut value = serde_json::to_value(&config).unwrap();
+        let obj = value.as_object_mut().unwrap();
+        obj.remove("system_audio_gain");
+        obj.remove("mic_gain");
+        let deserialized: RecordingConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.system_audio_gain, 1.0);
+        assert_eq!(deserialized.mic_gain, 1.0);
+
+        let mut config = RecordingConfig {
+            system_audio_gain: -2.0,
+            mic_gain: 12.0,
+            ..RecordingConfig::default()
+        };
+        config.normalize_audio_gains();
+        assert_eq!(config.system_audio_gain, MIN_AUDIO_GAIN);
+        assert_eq!(config.mic_gain, MAX_AUDIO_GAIN);
+        assert_eq!(clamp_audio_gain(f32::NAN), 1.0);
+    }
+
     #[test]
     fn test_recording_config_serialization() {
         let config = RecordingConfig::default();
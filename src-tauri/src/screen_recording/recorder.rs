@@ -3,9 +3,9 @@
 //! Uses FFmpeg for cross-platform screen recording
 
 use super::{
-    click_highlight::RecordingClickOverlay, ffmpeg, AudioDevice, AudioDevices, MonitorInfo,
-    RecordingConfig, RecordingError, RecordingMetadata, RecordingRegion, RecordingStatus,
-    RecordingToolbar,
+    clamp_audio_gain, click_highlight::RecordingClickOverlay, ffmpeg, AudioDevice, AudioDevices,
+    MonitorInfo, RecordingConfig, RecordingError, RecordingMarker, RecordingMetadata,
+    RecordingRegion, RecordingStatus, RecordingToolbar,
 };
 use crate::selection::{MouseEvent, MouseHook};
 use log::{debug, error, info, trace, warn};
@@ -41,6 +41,7 @@ pub struct RecordingState {
     pub max_duration: u64,
     pub pause_on_minimize: bool,
     pub highlight_clicks: bool,
+    pub markers: Vec<RecordingMarker>,
 }
 
 impl Default for RecordingState {
@@ -63,6 +64,7 @@ impl Default for RecordingState {
             max_duration: 0,
             pause_on_minimize: false,
             highlight_clicks: false,
+            markers: Vec::new(),
         }
     }
 }
@@ -230,25 +232,75 @@ impl ScreenRecorder {
         let state = self.state.read();
         if let Some(start) = state.start_time {
             let now = chrono::Utc::now().timestamp_millis();
-            let elapsed = (now - start) as u64;
-
-            // Subtract paused time
-            let paused = if state.status == RecordingStatus::Paused {
-                if let Some(pause_time) = state.pause_time {
-                    state.total_paused_ms + (now - pause_time) as u64
-                } else {
-                    state.total_paused_ms
-                }
+            let pause_time = if state.status == RecordingStatus::Paused {
+                state.pause_time
             } else {
-                state.total_paused_ms
+                None
             };
-
-            elapsed.saturating_sub(paused)
+            active_duration_ms(start, now, state.total_paused_ms, pause_time)
         } else {
             0
         }
     }
 
+    /// Add a marker at the current position of the active recording.
+    ///
+    /// While paused, the marker is placed at the point where recording paused.
+    pub fn add_marker(&self, label: String) -> Result<RecordingMarker, String> {
+        let mut state = self.state.write();
+        if state.status != RecordingStatus::Recording && state.status != RecordingStatus::Paused {
+            return Err(String::from(RecordingError::not_recording()));
+        }
+        let start = state
+            .start_time
+            .ok_or_else(|| "No start time".to_string())?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let pause_time = if state.status == RecordingStatus::Paused {
+            state.pause_time
+        } else {
+            None
+        };
+        let offset_ms = active_duration_ms(start, now, state.total_paused_ms, pause_time);
+        let label = label.trim();
+        let marker = RecordingMarker {
+            id: Uuid::new_v4().to_string(),
+            offset_ms,
+            label: if label.is_empty() {
+                format!("Marker {}", state.markers.len() + 1)
+            } else {
+                label.to_string()
+            },
+            created_at: now,
+        };
+
+        // Keep markers ordered by offset for timeline rendering
+        let position = state
+            .markers
+            .partition_point(|m| m.offset_ms <= marker.offset_ms);
+        state.markers.insert(position, marker.clone());
+        debug!(
+            "[ScreenRecorder] Marker '{}' added at {}ms ({} total)",
+            marker.label,
+            marker.offset_ms,
+            state.markers.len()
+        );
+        drop(state);
+
+        let _ = self.app_handle.emit("recording-marker-added", &marker);
+        Ok(marker)
+    }
+
+    /// Get markers of the active recording if `recording_id` refers to it
+    pub fn get_active_markers(&self, recording_id: &str) -> Option<Vec<RecordingMarker>> {
+        let state = self.state.read();
+        if state.recording_id.as_deref() == Some(recording_id) {
+            Some(state.markers.clone())
+        } else {
+            None
+        }
+    }
+
     /// Common countdown, state transition, and recording start logic
     async fn run_countdown_and_start<F>(
         &self,
@@ -857,11 +909,12 @@ impl ScreenRecorder {
         monitors
     }
 
-    /// Get available audio devices by querying FFmpeg
+    /// Get available audio devices by querying FFmpeg (Windows) or PulseAudio (Linux)
     pub fn get_audio_devices(&self) -> AudioDevices {
         debug!("[ScreenRecorder] Getting audio devices");
 
         let mut microphones = Vec::new();
+        let mut system_audio_devices = Vec::new();
         let system_audio_available;
 
         #[cfg(target_os = "windows")]
@@ -897,20 +950,32 @@ impl ScreenRecorder {
                                     let name = line[start + 1..start + 1 + end].to_string();
                                     // Skip alternative name lines
                                     if !line.contains("Alternative name") {
-                                        microphones.push(AudioDevice {
+                                        let device = AudioDevice {
                                             id: format!("dshow_{}", device_index),
                                             name,
-                                            is_default: device_index == 0,
-                                        });
+                                            is_default: false,
+                                        };
+                                        if is_loopback_device_name(&device.name) {
+                                            system_audio_devices.push(device);
+                                        } else {
+                                            microphones.push(device);
+                                        }
                                         device_index += 1;
                                     }
                                 }
                             }
                         }
                     }
+                    if let Some(first) = microphones.first_mut() {
+                        first.is_default = true;
+                    }
+                    if let Some(first) = system_audio_devices.first_mut() {
+                        first.is_default = true;
+                    }
                     debug!(
-                        "[ScreenRecorder] FFmpeg enumerated {} audio devices",
-                        microphones.len()
+                        "[ScreenRecorder] FFmpeg enumerated {} microphones, {} loopback devices",
+                        microphones.len(),
+                        system_audio_devices.len()
                     );
                 }
                 Err(e) => {
@@ -931,7 +996,57 @@ impl ScreenRecorder {
             }
         }
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            let default_source = Command::new("pactl")
+                .arg("get-default-source")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+            match Command::new("pactl")
+                .args(["list", "short", "sources"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .output()
+            {
+                Ok(output) if output.status.success() => {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let (mics, monitors) = parse_pulse_sources(&stdout, default_source.as_deref());
+                    microphones = mics;
+                    system_audio_devices = monitors;
+                    debug!(
+                        "[ScreenRecorder] PulseAudio enumerated {} microphones, {} monitors",
+                        microphones.len(),
+                        system_audio_devices.len()
+                    );
+                }
+                Ok(output) => {
+                    warn!(
+                        "[ScreenRecorder] pactl exited with status {}; using default audio devices",
+                        output.status
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "[ScreenRecorder] Failed to enumerate audio devices via pactl: {}",
+                        e
+                    );
+                }
+            }
+
+            system_audio_available = !system_audio_devices.is_empty();
+            if microphones.is_empty() {
+                microphones.push(AudioDevice {
+                    id: "default".to_string(),
+                    name: "Default Microphone".to_string(),
+                    is_default: true,
+                });
+            }
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             system_audio_available = false;
             microphones.push(AudioDevice {
@@ -944,11 +1059,13 @@ impl ScreenRecorder {
         let devices = AudioDevices {
             system_audio_available,
             microphones,
+            system_audio_devices,
         };
         debug!(
-            "[ScreenRecorder] Audio devices: system_audio={}, microphones={}",
+            "[ScreenRecorder] Audio devices: system_audio={}, microphones={}, outputs={}",
             devices.system_audio_available,
-            devices.microphones.len()
+            devices.microphones.len(),
+            devices.system_audio_devices.len()
         );
         devices
     }
@@ -1312,50 +1429,58 @@ impl ScreenRecorder {
     }
 
     fn build_audio_plan(config: &RecordingConfig) -> RecordingAudioPlan {
-        let mut sources: Vec<String> = Vec::new();
+        // (device, gain) per audio input, in ffmpeg input order after the video input
+        let mut sources: Vec<(String, f32)> = Vec::new();
+        let system_gain = clamp_audio_gain(config.system_audio_gain);
+        let mic_gain = clamp_audio_gain(config.mic_gain);
 
         #[cfg(target_os = "windows")]
         {
             if config.capture_system_audio {
-                sources.push(
+                sources.push((
                     config
                         .system_audio_device
                         .clone()
                         .unwrap_or_else(|| "virtual-audio-capturer".to_string()),
-                );
+                    system_gain,
+                ));
             }
             if config.capture_microphone {
-                sources.push(
+                sources.push((
                     config
                         .microphone_device
                         .clone()
                         .unwrap_or_else(|| "default".to_string()),
-                );
+                    mic_gain,
+                ));
             }
         }
 
         #[cfg(target_os = "linux")]
         {
             if config.capture_system_audio {
-                sources.push(
+                sources.push((
                     config
                         .system_audio_device
                         .clone()
                         .unwrap_or_else(|| "default".to_string()),
-                );
+                    system_gain,
+                ));
             }
             if config.capture_microphone {
-                sources.push(
+                sources.push((
                     config
                         .microphone_device
                         .clone()
                         .unwrap_or_else(|| "default".to_string()),
-                );
+                    mic_gain,
+                ));
             }
         }
 
         #[cfg(target_os = "macos")]
         {
+            let _ = (system_gain, mic_gain);
             if config.capture_system_audio || config.capture_microphone {
                 warn!(
                     "[ScreenRecorder] Audio capture on macOS is not configured in this build; recording will be video-only"
@@ -1365,7 +1490,7 @@ impl ScreenRecorder {
 
         let mut input_args: Vec<String> = Vec::new();
         #[cfg(target_os = "windows")]
-        for source in &sources {
+        for (source, _) in &sources {
             input_args.extend([
                 "-f".to_string(),
                 "dshow".to_string(),
@@ -1374,7 +1499,7 @@ impl ScreenRecorder {
             ]);
         }
         #[cfg(target_os = "linux")]
-        for source in &sources {
+        for (source, _) in &sources {
             input_args.extend([
                 "-f".to_string(),
                 "pulse".to_string(),
@@ -1383,24 +1508,17 @@ impl ScreenRecorder {
             ]);
         }
 
+        let gains: Vec<f32> = sources.iter().map(|(_, gain)| *gain).collect();
         let mut filter_args: Vec<String> = Vec::new();
         let mut map_args: Vec<String> = vec!["-map".to_string(), "0:v:0".to_string()];
         let has_audio = !sources.is_empty();
         if sources.is_empty() {
             map_args.push("-an".to_string());
-        } else if sources.len() == 1 {
-            map_args.extend(["-map".to_string(), "1:a:0".to_string()]);
-        } else {
-            let mut filter = String::new();
-            for index in 1..=sources.len() {
-                filter.push_str(&format!("[{index}:a]"));
-            }
-            filter.push_str(&format!(
-                "amix=inputs={}:duration=longest:dropout_transition=2[aout]",
-                sources.len()
-            ));
+        } else if let Some(filter) = build_audio_filter(&gains) {
             filter_args.extend(["-filter_complex".to_string(), filter]);
             map_args.extend(["-map".to_string(), "[aout]".to_string()]);
+        } else {
+            map_args.extend(["-map".to_string(), "1:a:0".to_string()]);
         }
 
         RecordingAudioPlan {
@@ -1523,9 +1641,9 @@ impl ScreenRecorder {
         args
     }
 
-    fn build_recording_args(
+    /// Capture inputs, audio mapping and encoder settings shared by every output
+    fn build_encode_args(
         config: &RecordingConfig,
-        output_path: &str,
         video_input: &RecordingVideoInput,
     ) -> (Vec<String>, bool) {
         let mut args = Self::build_video_input_args(config, video_input);
@@ -1562,14 +1680,67 @@ impl ScreenRecorder {
         }
 
         args.extend(["-pix_fmt".to_string(), "yuv420p".to_string()]);
+        (args, audio_plan.has_audio)
+    }
+
+    fn build_recording_args(
+        config: &RecordingConfig,
+        output_path: &str,
+        video_input: &RecordingVideoInput,
+    ) -> (Vec<String>, bool) {
+        let (mut args, has_audio) = Self::build_encode_args(config, video_input);
         if config.format == "mp4" {
             args.extend(["-movflags".to_string(), "+faststart".to_string()]);
         }
         args.extend(["-y".to_string(), output_path.to_string()]);
-        (args, audio_plan.has_audio)
+        (args, has_audio)
+    }
+
+    /// Build FFmpeg arguments for a fullscreen capture written as a ring of segment files
+    ///
+    /// Keyframes are forced on segment boundaries so each segment can be concatenated
+    /// with a stream copy. `segment_pattern` must contain a `%03d` placeholder.
+    pub(super) fn build_replay_args(
+        config: &RecordingConfig,
+        width: u32,
+        height: u32,
+        segment_pattern: &str,
+        segment_secs: u32,
+        segment_wrap: u32,
+    ) -> (Vec<String>, bool) {
+        let input = RecordingVideoInput::Fullscreen {
+            window_title: None,
+            width,
+            height,
+        };
+        let (mut args, has_audio) = Self::build_encode_args(config, &input);
+        args.extend([
+            "-force_key_frames".to_string(),
+            format!("expr:gte(t,n_forced*{})", segment_secs),
+            "-f".to_string(),
+            "segment".to_string(),
+            "-segment_time".to_string(),
+            segment_secs.to_string(),
+            "-segment_wrap".to_string(),
+            segment_wrap.to_string(),
+            "-segment_format".to_string(),
+            "matroska".to_string(),
+            "-reset_timestamps".to_string(),
+            "1".to_string(),
+            "-y".to_string(),
+            segment_pattern.to_string(),
+        ]);
+        (args, has_audio)
     }
 
     fn spawn_ffmpeg(&self, args: Vec<String>) -> Result<(), String> {
+        let child = Self::spawn_ffmpeg_process(&args)?;
+        *self.ffmpeg_process.write() = Some(child);
+        Ok(())
+    }
+
+    /// Spawn FFmpeg with a piped stdin and its stderr forwarded to the log
+    pub(super) fn spawn_ffmpeg_process(args: &[String]) -> Result<Child, String> {
         info!(
             "[ScreenRecorder] Spawning FFmpeg process with {} arguments",
             args.len()
@@ -1578,7 +1749,7 @@ impl ScreenRecorder {
 
         let mut command = Command::new("ffmpeg");
         command
-            .args(&args)
+            .args(args)
             .stdin(Stdio::piped())
             .stdout(Stdio::null())
             .stderr(Stdio::piped());
@@ -1615,8 +1786,7 @@ impl ScreenRecorder {
             "[ScreenRecorder] FFmpeg process spawned successfully, PID: {:?}",
             child.id()
         );
-        *self.ffmpeg_process.write() = Some(child);
-        Ok(())
+        Ok(child)
     }
 
     fn start_ffmpeg_recording(
@@ -1660,70 +1830,75 @@ impl ScreenRecorder {
         );
         let mut process = self.ffmpeg_process.write();
         if let Some(ref mut child) = *process {
-            let pid = child.id();
-            debug!(
-                "[ScreenRecorder] Sending 'q' to FFmpeg process (PID: {:?})",
-                pid
-            );
+            Self::stop_ffmpeg_process(child, timeout);
+        } else {
+            debug!("[ScreenRecorder] No FFmpeg process to stop");
+        }
+        *process = None;
+        info!("[ScreenRecorder] FFmpeg process stopped");
+        Ok(())
+    }
 
-            // Send 'q' to gracefully stop FFmpeg
-            if let Some(ref mut stdin) = child.stdin {
-                use std::io::Write;
-                match stdin.write_all(b"q") {
-                    Ok(_) => debug!("[ScreenRecorder] Successfully sent quit signal to FFmpeg"),
-                    Err(e) => warn!(
-                        "[ScreenRecorder] Failed to send quit signal to FFmpeg: {}",
-                        e
-                    ),
-                }
-                // Flush and drop stdin to signal EOF
-                let _ = stdin.flush();
-            } else {
-                warn!("[ScreenRecorder] FFmpeg stdin not available, cannot send quit signal");
+    /// Ask FFmpeg to finish by sending 'q', killing it if it outlives `timeout`
+    pub(super) fn stop_ffmpeg_process(child: &mut Child, timeout: Duration) {
+        let pid = child.id();
+        debug!(
+            "[ScreenRecorder] Sending 'q' to FFmpeg process (PID: {:?})",
+            pid
+        );
+
+        // Send 'q' to gracefully stop FFmpeg
+        if let Some(ref mut stdin) = child.stdin {
+            use std::io::Write;
+            match stdin.write_all(b"q") {
+                Ok(_) => debug!("[ScreenRecorder] Successfully sent quit signal to FFmpeg"),
+                Err(e) => warn!(
+                    "[ScreenRecorder] Failed to send quit signal to FFmpeg: {}",
+                    e
+                ),
             }
+            // Flush and drop stdin to signal EOF
+            let _ = stdin.flush();
+        } else {
+            warn!("[ScreenRecorder] FFmpeg stdin not available, cannot send quit signal");
+        }
 
-            // Wait for process to finish with timeout
-            debug!(
-                "[ScreenRecorder] Waiting for FFmpeg process to exit (timeout: {:?})",
-                timeout
-            );
-            let start = std::time::Instant::now();
-            let poll_interval = Duration::from_millis(100);
+        // Wait for process to finish with timeout
+        debug!(
+            "[ScreenRecorder] Waiting for FFmpeg process to exit (timeout: {:?})",
+            timeout
+        );
+        let start = std::time::Instant::now();
+        let poll_interval = Duration::from_millis(100);
 
-            loop {
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        info!(
-                            "[ScreenRecorder] FFmpeg process exited with status: {:?}",
-                            status
-                        );
-                        break;
-                    }
-                    Ok(None) => {
-                        // Process still running
-                        if start.elapsed() > timeout {
-                            warn!("[ScreenRecorder] FFmpeg process did not exit within timeout, force killing");
-                            if let Err(e) = child.kill() {
-                                error!("[ScreenRecorder] Failed to kill FFmpeg process: {}", e);
-                            }
-                            // Wait a bit more for kill to take effect
-                            std::thread::sleep(Duration::from_millis(500));
-                            break;
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    info!(
+                        "[ScreenRecorder] FFmpeg process exited with status: {:?}",
+                        status
+                    );
+                    break;
+                }
+                Ok(None) => {
+                    // Process still running
+                    if start.elapsed() > timeout {
+                        warn!("[ScreenRecorder] FFmpeg process did not exit within timeout, force killing");
+                        if let Err(e) = child.kill() {
+                            error!("[ScreenRecorder] Failed to kill FFmpeg process: {}", e);
                         }
-                        std::thread::sleep(poll_interval);
-                    }
-                    Err(e) => {
-                        warn!("[ScreenRecorder] Error waiting for FFmpeg process: {}", e);
+                        // Wait a bit more for kill to take effect
+                        std::thread::sleep(Duration::from_millis(500));
                         break;
                     }
+                    std::thread::sleep(poll_interval);
+                }
+                Err(e) => {
+                    warn!("[ScreenRecorder] Error waiting for FFmpeg process: {}", e);
+                    break;
                 }
             }
-        } else {
-            debug!("[ScreenRecorder] No FFmpeg process to stop");
         }
-        *process = None;
-        info!("[ScreenRecorder] FFmpeg process stopped");
-        Ok(())
     }
 
     fn create_metadata(&self) -> Result<RecordingMetadata, String> {
@@ -1785,6 +1960,15 @@ impl ScreenRecorder {
             file_size,
             has_audio: state.has_audio,
             thumbnail: None, // Thumbnail generated separately via video_generate_thumbnail
+            markers: state
+                .markers
+                .iter()
+                .cloned()
+                .map(|mut m| {
+                    m.offset_ms = m.offset_ms.min(duration_ms);
+                    m
+                })
+                .collect(),
         };
 
         info!("[ScreenRecorder] Metadata created: id={}, mode={}, {}x{}, duration={}ms, size={} bytes, path={:?}",
@@ -1887,8 +2071,110 @@ impl Drop for ScreenRecorder {
     }
 }
 
+// ============== Audio Filters and Devices ==============
+
+/// Build the `-filter_complex` graph for the audio inputs (ffmpeg inputs `1..=n`).
+///
+/// Each input gets a `volume` filter when its gain is not unity; multiple inputs are
+/// mixed with `amix`. Returns `None` when a single unity-gain input can be mapped directly.
+fn build_audio_filter(gains: &[f32]) -> Option<String> {
+    let is_unity = |gain: f32| (gain - 1.0).abs() < f32::EPSILON;
+    match gains {
+        [] => None,
+        [gain] if is_unity(*gain) => None,
+        [gain] => Some(format!("[1:a]volume={:.2}[aout]", gain)),
+        _ => {
+            let mut filter = String::new();
+            let mut mix_inputs = String::new();
+            for (offset, gain) in gains.iter().enumerate() {
+                let index = offset + 1;
+                if is_unity(*gain) {
+                    mix_inputs.push_str(&format!("[{index}:a]"));
+                } else {
+                    filter.push_str(&format!("[{index}:a]volume={:.2}[a{index}];", gain));
+                    mix_inputs.push_str(&format!("[a{index}]"));
+                }
+            }
+            filter.push_str(&mix_inputs);
+            filter.push_str(&format!(
+                "amix=inputs={}:duration=longest:dropout_transition=2[aout]",
+                gains.len()
+            ));
+            Some(filter)
+        }
+    }
+}
+
+/// Whether a DirectShow device name looks like a system audio loopback source
+#[cfg(any(target_os = "windows", test))]
+fn is_loopback_device_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    [
+        "stereo mix",
+        "virtual-audio-capturer",
+        "what u hear",
+        "loopback",
+        "wave out mix",
+    ]
+    .iter()
+    .any(|pattern| lower.contains(pattern))
+}
+
+/// Split `pactl list short sources` output into microphones and output monitors
+#[cfg(any(target_os = "linux", test))]
+fn parse_pulse_sources(
+    output: &str,
+    default_source: Option<&str>,
+) -> (Vec<AudioDevice>, Vec<AudioDevice>) {
+    let mut microphones = Vec::new();
+    let mut monitors = Vec::new();
+
+    for line in output.lines() {
+        // Format: <index>\t<name>\t<driver>\t<sample spec>\t<state>
+        let Some(name) = line.split('\t').nth(1).map(str::trim) else {
+            continue;
+        };
+        if name.is_empty() {
+            continue;
+        }
+        let device = AudioDevice {
+            id: name.to_string(),
+            name: name.to_string(),
+            is_default: default_source == Some(name),
+        };
+        if name.ends_with(".monitor") {
+            monitors.push(device);
+        } else {
+            microphones.push(device);
+        }
+    }
+
+    if !microphones.iter().any(|d| d.is_default) {
+        if let Some(first) = microphones.first_mut() {
+            first.is_default = true;
+        }
+    }
+    if let Some(first) = monitors.first_mut() {
+        first.is_default = true;
+    }
+
+    (microphones, monitors)
+}
+
 // ============== Cross-Platform Process Suspend/Resume ==============
 
+/// Recording time elapsed between `start` and `now`, excluding paused time.
+///
+/// `pause_time` is the start of the current pause, if the recording is paused.
+fn active_duration_ms(start: i64, now: i64, total_paused_ms: u64, pause_time: Option<i64>) -> u64 {
+    let elapsed = (now - start).max(0) as u64;
+    let paused = match pause_time {
+        Some(pause_time) => total_paused_ms + (now - pause_time).max(0) as u64,
+        None => total_paused_ms,
+    };
+    elapsed.saturating_sub(paused)
+}
+
 /// Suspend a process by PID (cross-platform)
 fn suspend_process(pid: u32) -> Result<(), String> {
     #[cfg(target_os = "windows")]
@@ -2105,4 +2391,97 @@ mod tests {
         );
         assert_eq!(plan.map_args, vec!["-map", "0:v:0", "-map", "[aout]"]);
     }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn build_audio_plan_clamps_gains() {
+        let mut config = base_config();
+        config.capture_microphone = true;
+        config.mic_gain = 10.0;
+
+        let plan = ScreenRecorder::build_audio_plan(&config);
+        assert_eq!(
+            plan.filter_args,
+            vec!["-filter_complex", "[1:a]volume=4.00[aout]"]
+        );
+        assert_eq!(plan.map_args, vec!["-map", "0:v:0", "-map", "[aout]"]);
+    }
+
+    #[test]
+    fn active_duration_excludes_paused_time() {
+        // 10s elapsed, 2s paused earlier
+        assert_eq!(active_duration_ms(0, 10_000, 2_000, None), 8_000);
+        // Currently paused since 7s: position stays at the pause point
+        assert_eq!(active_duration_ms(0, 10_000, 2_000, Some(7_000)), 5_000);
+        assert_eq!(active_duration_ms(0, 12_000, 2_000, Some(7_000)), 5_000);
+        // Clock skew never underflows
+        assert_eq!(active_duration_ms(5_000, 1_000, 0, None), 0);
+        assert_eq!(active_duration_ms(0, 1_000, 5_000, None), 0);
+    }
+
+    #[test]
+    fn build_replay_args_writes_wrapped_segments() {
+        let config = base_config();
+        let (args, has_audio) =
+            ScreenRecorder::build_replay_args(&config, 1920, 1080, "/tmp/replay_%03d.mkv", 5, 8);
+        assert!(!has_audio);
+        let pos = |flag: &str| args.iter().position(|a| a == flag).unwrap();
+        assert!(args.windows(2).any(|w| w[0] == "-f" && w[1] == "segment"));
+        assert_eq!(args[pos("-segment_time") + 1], "5");
+        assert_eq!(args[pos("-segment_wrap") + 1], "8");
+        assert_eq!(args[pos("-force_key_frames") + 1], "expr:gte(t,n_forced*5)");
+        assert!(!args.iter().any(|a| a == "+faststart"));
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some("/tmp/replay_%03d.mkv")
+        );
+    }
+
+    #[test]
+    fn build_audio_filter_applies_per_source_gain() {
+        assert_eq!(build_audio_filter(&[]), None);
+        assert_eq!(build_audio_filter(&[1.0]), None);
+        assert_eq!(
+            build_audio_filter(&[0.5]).as_deref(),
+            Some("[1:a]volume=0.50[aout]")
+        );
+        assert_eq!(
+            build_audio_filter(&[1.0, 1.0]).as_deref(),
+            Some("[1:a][2:a]amix=inputs=2:duration=longest:dropout_transition=2[aout]")
+        );
+        assert_eq!(
+            build_audio_filter(&[1.5, 0.25]).as_deref(),
+            Some(
+                "[1:a]volume=1.50[a1];[2:a]volume=0.25[a2];[a1][a2]amix=inputs=2:duration=longest:dropout_transition=2[aout]"
+            )
+        );
+    }
+
+    #[test]
+    fn parse_pulse_sources_splits_monitors() {
+        let output = "0\talsa_output.pci-0000_00_1f.3.analog-stereo.monitor\tmodule-alsa-card.c\ts16le 2ch 44100Hz\tSUSPENDED\n\
+                      1\talsa_input.pci-0000_00_1f.3.analog-stereo\tmodule-alsa-card.c\ts16le 2ch 44100Hz\tRUNNING\n\
+                      2\tbluez_input.headset\tmodule-bluez5-device.c\ts16le 1ch 16000Hz\tIDLE\n";
+
+        let (mics, monitors) = parse_pulse_sources(output, Some("bluez_input.headset"));
+        assert_eq!(mics.len(), 2);
+        assert_eq!(monitors.len(), 1);
+        assert!(monitors[0].is_default);
+        assert!(!mics[0].is_default);
+        assert!(mics[1].is_default);
+        assert_eq!(
+            monitors[0].id,
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+        );
+
+        let (mics, _) = parse_pulse_sources(output, None);
+        assert!(mics[0].is_default);
+    }
+
+    #[test]
+    fn loopback_device_names_are_detected() {
+        assert!(is_loopback_device_name("Stereo Mix (Realtek(R) Audio)"));
+        assert!(is_loopback_device_name("virtual-audio-capturer"));
+        assert!(!is_loopback_device_name("Microphone (USB Audio)"));
+    }
 }
@@ -0,0 +1,387 @@
+//! Instant replay buffer
+//!
+//! Captures the screen continuously into a small ring of segment files so the
+//! last few seconds can be saved after the fact. The buffer runs its own FFmpeg
+//! process, independent of normal recording, and never keeps more than a fixed
+//! number of segments on disk.
+
+use super::{MonitorInfo, RecordingConfig, RecordingMetadata, ScreenRecorder, VideoProcessor};
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+/// Length of each buffered segment in seconds
+pub const REPLAY_SEGMENT_SECS: u32 = 5;
+/// Shortest allowed replay buffer in seconds
+pub const MIN_REPLAY_BUFFER_SECS: u32 = 5;
+/// Longest allowed replay buffer in seconds
+pub const MAX_REPLAY_BUFFER_SECS: u32 = 600;
+
+const SEGMENT_PREFIX: &str = "replay_";
+const SEGMENT_EXTENSION: &str = "mkv";
+const REPLAY_STOP_TIMEOUT_SECS: u64 = 5;
+
+/// Clamp a replay buffer length to the supported range
+pub fn clamp_replay_buffer_secs(secs: u32) -> u32 {
+    secs.clamp(MIN_REPLAY_BUFFER_SECS, MAX_REPLAY_BUFFER_SECS)
+}
+
+/// Number of segment files FFmpeg cycles through for a buffer length
+///
+/// One extra segment holds the capture in progress and another is the next one
+/// to be overwritten, which a save skips so it is never read while rewritten.
+fn segment_wrap(buffer_secs: u32) -> u32 {
+    buffer_secs.div_ceil(REPLAY_SEGMENT_SECS) + 2
+}
+
+/// Segments to include in a saved replay, oldest first
+///
+/// Keeps the newest `segment_wrap - 1` segments, dropping the one FFmpeg will
+/// overwrite next.
+fn select_segments(mut segments: Vec<(PathBuf, SystemTime)>, buffer_secs: u32) -> Vec<PathBuf> {
+    segments.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    let keep = segment_wrap(buffer_secs) as usize - 1;
+    let skip = segments.len().saturating_sub(keep);
+    segments
+        .into_iter()
+        .skip(skip)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Input list for FFmpeg's concat demuxer
+fn concat_list(segments: &[PathBuf]) -> String {
+    segments
+        .iter()
+        .map(|path| format!("file '{}'\n", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect()
+}
+
+/// Current state of the replay buffer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayStatus {
+    /// Whether the buffer is capturing
+    pub active: bool,
+    /// When capture started (ms since epoch)
+    pub started_at: Option<i64>,
+    /// Configured buffer length in seconds
+    pub buffer_secs: u32,
+    /// Milliseconds of footage currently available to save
+    pub buffered_ms: u64,
+    /// Disk space used by buffered segments
+    pub buffered_bytes: u64,
+    pub monitor_index: Option<usize>,
+    pub width: u32,
+    pub height: u32,
+    pub has_audio: bool,
+}
+
+struct ReplaySession {
+    process: Child,
+    started_at: i64,
+    buffer_secs: u32,
+    monitor_index: usize,
+    width: u32,
+    height: u32,
+    has_audio: bool,
+}
+
+/// Rolling capture of the most recent seconds of screen activity
+pub struct ReplayBuffer {
+    app_handle: AppHandle,
+    segment_dir: PathBuf,
+    session: Mutex<Option<ReplaySession>>,
+}
+
+impl ReplayBuffer {
+    pub fn new(app_handle: AppHandle, segment_dir: PathBuf) -> Self {
+        Self {
+            app_handle,
+            segment_dir,
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Start capturing `monitor` into the buffer
+    pub fn start(
+        &self,
+        config: &RecordingConfig,
+        monitor: &MonitorInfo,
+    ) -> Result<ReplayStatus, String> {
+        let mut session = self.session.lock();
+        if let Some(active) = session.as_mut() {
+            if matches!(active.process.try_wait(), Ok(None)) {
+                return Err("Replay buffer is already running".to_string());
+            }
+            warn!("[ReplayBuffer] Previous replay capture exited unexpectedly, restarting");
+        }
+
+        self.clear_segments();
+        std::fs::create_dir_all(&self.segment_dir)
+            .map_err(|e| format!("Failed to create replay directory: {}", e))?;
+
+        let buffer_secs = clamp_replay_buffer_secs(config.replay_buffer_secs);
+        let pattern = self
+            .segment_dir
+            .join(format!("{}%03d.{}", SEGMENT_PREFIX, SEGMENT_EXTENSION))
+            .to_string_lossy()
+            .to_string();
+        let (args, has_audio) = ScreenRecorder::build_replay_args(
+            config,
+            monitor.width,
+            monitor.height,
+            &pattern,
+            REPLAY_SEGMENT_SECS,
+            segment_wrap(buffer_secs),
+        );
+        let process = ScreenRecorder::spawn_ffmpeg_process(&args)?;
+
+        info!(
+            "[ReplayBuffer] Started: monitor={}, {}x{}, buffer={}s",
+            monitor.index, monitor.width, monitor.height, buffer_secs
+        );
+        *session = Some(ReplaySession {
+            process,
+            started_at: chrono::Utc::now().timestamp_millis(),
+            buffer_secs,
+            monitor_index: monitor.index,
+            width: monitor.width,
+            height: monitor.height,
+            has_audio,
+        });
+        drop(session);
+
+        let status = self.status();
+        self.emit_status(&status);
+        Ok(status)
+    }
+
+    /// Stop capturing and discard the buffered footage
+    pub fn stop(&self) -> Result<(), String> {
+        let session = self.session.lock().take();
+        let Some(mut session) = session else {
+            return Err("Replay buffer is not running".to_string());
+        };
+        ScreenRecorder::stop_ffmpeg_process(
+            &mut session.process,
+            Duration::from_secs(REPLAY_STOP_TIMEOUT_SECS),
+        );
+        self.clear_segments();
+        info!("[ReplayBuffer] Stopped");
+        self.emit_status(&ReplayStatus::default());
+        Ok(())
+    }
+
+    /// Kill the capture when the app exits; the buffered footage is discarded
+    /// anyway, so FFmpeg is not given time to finish its segment
+    pub fn shutdown(&self) {
+        if let Some(mut session) = self.session.lock().take() {
+            let _ = session.process.kill();
+            let _ = session.process.wait();
+            self.clear_segments();
+            info!("[ReplayBuffer] Stopped for shutdown");
+        }
+    }
+
+    /// Current buffer state; notices when FFmpeg has exited on its own
+    pub fn status(&self) -> ReplayStatus {
+        let mut session = self.session.lock();
+        let Some(active) = session.as_mut() else {
+            return ReplayStatus::default();
+        };
+        if !matches!(active.process.try_wait(), Ok(None)) {
+            warn!("[ReplayBuffer] Replay capture is no longer running");
+            *session = None;
+            return ReplayStatus::default();
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let elapsed_ms = now.saturating_sub(active.started_at).max(0) as u64;
+        let buffered_bytes = self
+            .list_segments()
+            .iter()
+            .filter_map(|(path, _)| std::fs::metadata(path).ok())
+            .map(|m| m.len())
+            .sum();
+        ReplayStatus {
+            active: true,
+            started_at: Some(active.started_at),
+            buffer_secs: active.buffer_secs,
+            buffered_ms: elapsed_ms.min(active.buffer_secs as u64 * 1000),
+            buffered_bytes,
+            monitor_index: Some(active.monitor_index),
+            width: active.width,
+            height: active.height,
+            has_audio: active.has_audio,
+        }
+    }
+
+    /// Write the buffered footage to `output_path` while capture keeps running
+    pub fn save(&self, output_path: &Path) -> Result<RecordingMetadata, String> {
+        let status = self.status();
+        if !status.active {
+            return Err("Replay buffer is not running".to_string());
+        }
+        let segments = select_segments(self.list_segments(), status.buffer_secs);
+        if segments.is_empty() {
+            return Err("Replay buffer is still empty".to_string());
+        }
+        debug!(
+            "[ReplayBuffer] Saving {} segments to {:?}",
+            segments.len(),
+            output_path
+        );
+
+        // Each save gets its own list so concurrent saves don't overwrite it
+        let list_path = self
+            .segment_dir
+            .join(format!("concat_{}.txt", Uuid::new_v4()));
+        std::fs::write(&list_path, concat_list(&segments))
+            .map_err(|e| format!("Failed to write replay segment list: {}", e))?;
+
+        let mut args = vec![
+            "-f".to_string(),
+            "concat".to_string(),
+            "-safe".to_string(),
+            "0".to_string(),
+            "-i".to_string(),
+            list_path.to_string_lossy().to_string(),
+            "-c".to_string(),
+            "copy".to_string(),
+        ];
+        if output_path.extension().is_some_and(|ext| ext == "mp4") {
+            args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+        }
+        args.extend(["-y".to_string(), output_path.to_string_lossy().to_string()]);
+
+        let output = Command::new("ffmpeg").args(&args).output();
+        let _ = std::fs::remove_file(&list_path);
+        let output = output.map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let _ = std::fs::remove_file(output_path);
+            return Err(format!(
+                "Failed to save replay: {}",
+                stderr.lines().last().unwrap_or("unknown FFmpeg error")
+            ));
+        }
+
+        let file_path = output_path.to_string_lossy().to_string();
+        let file_size = std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0);
+        let duration_ms = VideoProcessor::get_video_info(&file_path)
+            .map(|info| info.duration_ms)
+            .unwrap_or(status.buffered_ms);
+        let end_time = chrono::Utc::now().timestamp_millis();
+        info!(
+            "[ReplayBuffer] Saved replay: {} ({}ms, {} bytes)",
+            file_path, duration_ms, file_size
+        );
+
+        Ok(RecordingMetadata {
+            id: Uuid::new_v4().to_string(),
+            start_time: end_time - duration_ms as i64,
+            end_time: Some(end_time),
+            duration_ms,
+            width: status.width,
+            height: status.height,
+            mode: "replay".to_string(),
+            monitor_index: status.monitor_index,
+            window_title: None,
+            region: None,
+            file_path: Some(file_path),
+            file_size,
+            has_audio: status.has_audio,
+            thumbnail: None,
+            markers: Vec::new(),
+        })
+    }
+
+    fn list_segments(&self) -> Vec<(PathBuf, SystemTime)> {
+        let Ok(entries) = std::fs::read_dir(&self.segment_dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with(SEGMENT_PREFIX)
+                    && Path::new(&name)
+                        .extension()
+                        .is_some_and(|ext| ext == SEGMENT_EXTENSION)
+            })
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((entry.path(), modified))
+            })
+            .collect()
+    }
+
+    fn clear_segments(&self) {
+        for (path, _) in self.list_segments() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("[ReplayBuffer] Failed to remove segment {:?}: {}", path, e);
+            }
+        }
+    }
+
+    fn emit_status(&self, status: &ReplayStatus) {
+        let _ = self
+            .app_handle
+            .emit("recording-replay-status-changed", status);
+    }
+}
+
+impl Drop for ReplayBuffer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_wrap_covers_buffer() {
+        assert_eq!(segment_wrap(5), 3);
+        assert_eq!(segment_wrap(30), 8);
+        assert_eq!(segment_wrap(31), 9);
+        assert_eq!(clamp_replay_buffer_secs(0), MIN_REPLAY_BUFFER_SECS);
+        assert_eq!(clamp_replay_buffer_secs(10_000), MAX_REPLAY_BUFFER_SECS);
+    }
+
+    #[test]
+    fn test_select_segments_skips_next_overwrite() {
+        let base = SystemTime::UNIX_EPOCH;
+        let segments: Vec<(PathBuf, SystemTime)> = [3u64, 0, 2, 1]
+            .iter()
+            .map(|&i| {
+                (
+                    PathBuf::from(format!("replay_{:03}.mkv", i)),
+                    base + Duration::from_secs(i * 5),
+                )
+            })
+            .collect();
+
+        // 5s buffer keeps 3 segments on disk and saves the newest 2
+        assert_eq!(
+            select_segments(segments.clone(), 5),
+            vec![
+                PathBuf::from("replay_002.mkv"),
+                PathBuf::from("replay_003.mkv")
+            ]
+        );
+        assert_eq!(select_segments(segments, 60).len(), 4);
+    }
+
+    #[test]
+    fn test_concat_list_escapes_quotes() {
+        let list = concat_list(&[PathBuf::from("/tmp/a.mkv"), PathBuf::from("/tmp/it's.mkv")]);
+        assert_eq!(list, "file '/tmp/a.mkv'\nfile '/tmp/it'\\''s.mkv'\n");
+    }
+}
@@ -6,6 +6,7 @@ use super::progress::{
     emit_processing_completed, emit_processing_error, emit_processing_started,
     monitor_ffmpeg_progress,
 };
+use super::RecordingMarker;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -68,6 +69,39 @@ pub struct VideoInfo {
     pub has_audio: bool,
 }
 
+/// A still frame extracted from a video
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PreviewFrame {
+    pub path: String,
+    pub timestamp_ms: u64,
+}
+
+/// How a trim was performed
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrimMode {
+    /// Packets copied without re-encoding (cut points on keyframes)
+    StreamCopy,
+    /// Video re-encoded to cut at exact frames
+    Reencode,
+}
+
+/// Cut points within this many seconds of a keyframe count as aligned
+const KEYFRAME_TOLERANCE_SECS: f64 = 0.05;
+
+/// Maximum number of frames in a filmstrip
+pub const MAX_FILMSTRIP_FRAMES: u32 = 24;
+
+/// How recording markers are written on export
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MarkerExportFormat {
+    /// Embed as container chapters (stream copy, no re-encode)
+    Chapters,
+    /// Write a WebVTT chapters file next to the video
+    Sidecar,
+}
+
 /// Video processing manager
 pub struct VideoProcessor;
 
@@ -689,33 +723,54 @@ impl VideoProcessor {
         video_path: &str,
         output_path: &str,
         timestamp_ms: u64,
+    ) -> Result<String, String> {
+        Self::generate_scaled_thumbnail(video_path, output_path, timestamp_ms, None)
+    }
+
+    /// Generate a thumbnail from video, downscaled to `width` pixels when given.
+    ///
+    /// Any previous file at `output_path` is removed first so a failed run is
+    /// never mistaken for a fresh frame.
+    pub fn generate_scaled_thumbnail(
+        video_path: &str,
+        output_path: &str,
+        timestamp_ms: u64,
+        width: Option<u32>,
     ) -> Result<String, String> {
         debug!(
-            "[VideoProcessor] Generating thumbnail: {} at {}ms",
-            video_path, timestamp_ms
+            "[VideoProcessor] Generating thumbnail: {} at {}ms (width {:?})",
+            video_path, timestamp_ms, width
         );
 
-        let timestamp_secs = timestamp_ms as f64 / 1000.0;
+        if let Err(e) = std::fs::remove_file(output_path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                return Err(format!("Failed to replace thumbnail: {}", e));
+            }
+        }
+
+        let timestamp = format!("{:.3}", timestamp_ms as f64 / 1000.0);
+        let scale = width.map(|width| format!("scale={}:-2", width));
+        let mut args = vec!["-y", "-ss", &timestamp, "-i", video_path, "-vframes", "1"];
+        if let Some(scale) = &scale {
+            args.extend(["-vf", scale.as_str(), "-q:v", "4"]);
+        } else {
+            args.extend(["-q:v", "2"]);
+        }
+        args.push(output_path);
 
         let output = Command::new("ffmpeg")
-            .args([
-                "-y",
-                "-ss",
-                &format!("{:.3}", timestamp_secs),
-                "-i",
-                video_path,
-                "-vframes",
-                "1",
-                "-q:v",
-                "2",
-                output_path,
-            ])
+            .args(&args)
             .output()
             .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("[VideoProcessor] Thumbnail generation warning: {}", stderr);
+            warn!("[VideoProcessor] Thumbnail generation failed: {}", stderr);
+            return Err(format!(
+                "Failed to generate thumbnail at {}ms: {}",
+                timestamp_ms,
+                stderr.lines().last().unwrap_or("unknown error")
+            ));
         }
 
         if Path::new(output_path).exists() {
@@ -746,6 +801,455 @@ impl VideoProcessor {
         }
     }
 
+    /// Trim a video, stream-copying when both cut points fall on keyframes and
+    /// re-encoding otherwise. Progress is reported as the `trim` operation.
+    pub fn trim_preserving_quality(
+        input_path: &str,
+        output_path: &str,
+        start_time: f64,
+        end_time: f64,
+        app_handle: &AppHandle,
+    ) -> Result<(VideoProcessingResult, TrimMode), String> {
+        let duration_secs = Self::get_video_duration(input_path).map(|ms| ms as f64 / 1000.0);
+        let keyframes = match Self::list_keyframes(input_path) {
+            Ok(keyframes) => keyframes,
+            Err(e) => {
+                warn!(
+                    "[VideoProcessor] Keyframe probe failed, falling back to re-encode: {}",
+                    e
+                );
+                Vec::new()
+            }
+        };
+
+        let mode = if Self::cut_points_aligned(&keyframes, start_time, end_time, duration_secs) {
+            TrimMode::StreamCopy
+        } else {
+            TrimMode::Reencode
+        };
+        info!(
+            "[VideoProcessor] Trimming {} ({:.3}s-{:.3}s) using {:?}",
+            input_path, start_time, end_time, mode
+        );
+
+        let result = match mode {
+            TrimMode::StreamCopy => Self::trim_stream_copy_with_progress(
+                input_path,
+                output_path,
+                start_time,
+                end_time,
+                app_handle,
+            )?,
+            TrimMode::Reencode => {
+                let format = Path::new(output_path)
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase());
+                Self::trim_video_with_progress(
+                    &VideoTrimOptions {
+                        input_path: input_path.to_string(),
+                        output_path: output_path.to_string(),
+                        start_time,
+                        end_time,
+                        format,
+                        quality: None,
+                        gif_fps: None,
+                    },
+                    app_handle,
+                )?
+            }
+        };
+        Ok((result, mode))
+    }
+
+    /// Trim without re-encoding, emitting progress events
+    fn trim_stream_copy_with_progress(
+        input_path: &str,
+        output_path: &str,
+        start_time: f64,
+        end_time: f64,
+        app_handle: &AppHandle,
+    ) -> Result<VideoProcessingResult, String> {
+        let duration = end_time - start_time;
+        if duration <= 0.0 {
+            let err = "End time must be greater than start time".to_string();
+            emit_processing_error(app_handle, "trim", &err);
+            return Err(err);
+        }
+
+        emit_processing_started(app_handle, "trim");
+
+        let mut cmd = Command::new("ffmpeg");
+        cmd.arg("-y")
+            .arg("-progress")
+            .arg("pipe:2")
+            .arg("-ss")
+            .arg(format!("{:.3}", start_time))
+            .arg("-i")
+            .arg(input_path)
+            .arg("-t")
+            .arg(format!("{:.3}", duration))
+            .arg("-map")
+            .arg("0")
+            .arg("-c")
+            .arg("copy")
+            .arg("-avoid_negative_ts")
+            .arg("make_zero")
+            .arg(output_path)
+            .stderr(Stdio::piped());
+
+        debug!("[VideoProcessor] Running stream-copy trim: {:?}", cmd);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to spawn FFmpeg: {}", e))?;
+        Self::register_pid(child.id());
+
+        if let Err(e) = monitor_ffmpeg_progress(app_handle, &mut child, "trim", Some(duration)) {
+            warn!("[VideoProcessor] Progress monitoring failed: {}", e);
+        }
+
+        let status = child
+            .wait()
+            .map_err(|e| format!("Failed to wait for FFmpeg: {}", e))?;
+        Self::unregister_pid();
+
+        if Self::is_cancelled() {
+            let _ = std::fs::remove_file(output_path);
+            let err = "Video processing was cancelled".to_string();
+            emit_processing_error(app_handle, "trim", &err);
+            return Err(err);
+        }
+
+        if !status.success() {
+            let err = "FFmpeg trim failed".to_string();
+            error!("[VideoProcessor] {}", err);
+            emit_processing_error(app_handle, "trim", &err);
+            return Err(err);
+        }
+
+        let file_size = std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0);
+        emit_processing_completed(app_handle, "trim", output_path);
+
+        Ok(VideoProcessingResult {
+            success: true,
+            output_path: output_path.to_string(),
+            file_size,
+            duration_ms: (duration * 1000.0) as u64,
+            error: None,
+        })
+    }
+
+    /// List keyframe timestamps (seconds) of the first video stream
+    fn list_keyframes(file_path: &str) -> Result<Vec<f64>, String> {
+        let output = Command::new("ffprobe")
+            .args([
+                "-v",
+                "error",
+                "-select_streams",
+                "v:0",
+                "-skip_frame",
+                "nokey",
+                "-show_entries",
+                "frame=best_effort_timestamp_time",
+                "-of",
+                "csv=p=0",
+                file_path,
+            ])
+            .output()
+            .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().trim_end_matches(',').parse::<f64>().ok())
+            .collect())
+    }
+
+    /// Whether a trim can be done by stream copy: the start must sit on a keyframe, and
+    /// the end on a keyframe or at the end of the video.
+    fn cut_points_aligned(
+        keyframes: &[f64],
+        start_time: f64,
+        end_time: f64,
+        duration_secs: Option<f64>,
+    ) -> bool {
+        let on_keyframe = |t: f64| {
+            keyframes
+                .iter()
+                .any(|k| (k - t).abs() <= KEYFRAME_TOLERANCE_SECS)
+        };
+        let at_end = duration_secs
+            .map(|d| end_time >= d - KEYFRAME_TOLERANCE_SECS)
+            .unwrap_or(false);
+        on_keyframe(start_time) && (at_end || on_keyframe(end_time))
+    }
+
+    /// Extract a representative thumbnail and an optional filmstrip for a video.
+    ///
+    /// The thumbnail is returned base64-encoded, as history entries store it, and
+    /// filmstrip frames are written to `output_dir` as `<name>_strip_<n>.jpg`.
+    /// When `timestamp_ms` is `None` a frame a little into the video is used, which
+    /// avoids the blank first frame most recordings start with.
+    pub fn generate_previews(
+        video_path: &str,
+        output_dir: &Path,
+        name: &str,
+        timestamp_ms: Option<u64>,
+        filmstrip_frames: u32,
+    ) -> Result<(String, Vec<PreviewFrame>), String> {
+        if !Self::check_ffmpeg() {
+            return Err(
+                "FFmpeg is not available. Please install FFmpeg to process videos.".to_string(),
+            );
+        }
+        if !Path::new(video_path).exists() {
+            return Err(format!("Input file not found: {}", video_path));
+        }
+        std::fs::create_dir_all(output_dir)
+            .map_err(|e| format!("Failed to create thumbnail directory: {}", e))?;
+
+        let duration_ms = Self::get_video_duration(video_path).unwrap_or(0);
+        let timestamp_ms = match timestamp_ms {
+            Some(ts) if duration_ms > 0 => ts.min(duration_ms.saturating_sub(1)),
+            Some(ts) => ts,
+            None => Self::representative_timestamp_ms(duration_ms),
+        };
+
+        let thumbnail_path = output_dir.join(format!("{}.jpg", name));
+        Self::generate_scaled_thumbnail(
+            video_path,
+            &thumbnail_path.to_string_lossy(),
+            timestamp_ms,
+            Some(320),
+        )?;
+        let bytes = std::fs::read(&thumbnail_path);
+        let _ = std::fs::remove_file(&thumbnail_path);
+        let bytes = bytes.map_err(|e| format!("Failed to read thumbnail: {}", e))?;
+        let thumbnail = {
+            use base64::Engine;
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        };
+
+        let mut filmstrip = Vec::new();
+        for (index, ts) in Self::filmstrip_timestamps(duration_ms, filmstrip_frames)
+            .into_iter()
+            .enumerate()
+        {
+            let frame_path = output_dir.join(format!("{}_strip_{}.jpg", name, index));
+            match Self::generate_scaled_thumbnail(
+                video_path,
+                &frame_path.to_string_lossy(),
+                ts,
+                Some(240),
+            ) {
+                Ok(path) => filmstrip.push(PreviewFrame {
+                    path,
+                    timestamp_ms: ts,
+                }),
+                Err(e) => warn!("[VideoProcessor] Skipping filmstrip frame: {}", e),
+            }
+        }
+
+        info!(
+            "[VideoProcessor] Generated thumbnail at {}ms and {} filmstrip frames for {}",
+            timestamp_ms,
+            filmstrip.len(),
+            video_path
+        );
+        Ok((thumbnail, filmstrip))
+    }
+
+    /// Default thumbnail position: 10% into the video, at most 5 seconds in
+    fn representative_timestamp_ms(duration_ms: u64) -> u64 {
+        (duration_ms / 10).min(5_000)
+    }
+
+    /// Evenly spaced frame positions, taken from the middle of each segment
+    fn filmstrip_timestamps(duration_ms: u64, count: u32) -> Vec<u64> {
+        let count = count.min(MAX_FILMSTRIP_FRAMES) as u64;
+        if count == 0 || duration_ms == 0 {
+            return Vec::new();
+        }
+        (0..count)
+            .map(|i| (2 * i + 1) * duration_ms / (2 * count))
+            .collect()
+    }
+
+    /// Export recording markers as embedded chapters or a sidecar file.
+    ///
+    /// Returns the path of the written file. Without an explicit `output_path`, chapters
+    /// are written to `<name>_chapters.<ext>` and sidecars to `<name>.chapters.vtt`.
+    pub fn export_markers(
+        input_path: &str,
+        markers: &[RecordingMarker],
+        duration_ms: u64,
+        format: MarkerExportFormat,
+        output_path: Option<&str>,
+    ) -> Result<String, String> {
+        if markers.is_empty() {
+            return Err("Recording has no markers to export".to_string());
+        }
+        let input = Path::new(input_path);
+        if !input.exists() {
+            return Err(format!("Input file not found: {}", input_path));
+        }
+        let stem = input
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "recording".to_string());
+
+        match format {
+            MarkerExportFormat::Sidecar => {
+                let output_path = output_path.map(str::to_string).unwrap_or_else(|| {
+                    input
+                        .with_file_name(format!("{}.chapters.vtt", stem))
+                        .to_string_lossy()
+                        .to_string()
+                });
+                std::fs::write(
+                    &output_path,
+                    Self::build_webvtt_chapters(markers, duration_ms),
+                )
+                .map_err(|e| format!("Failed to write chapters file: {}", e))?;
+                info!("[VideoProcessor] Wrote marker sidecar: {}", output_path);
+                Ok(output_path)
+            }
+            MarkerExportFormat::Chapters => {
+                if !Self::check_ffmpeg() {
+                    return Err(
+                        "FFmpeg is not available. Please install FFmpeg to process videos."
+                            .to_string(),
+                    );
+                }
+                let output_path = output_path.map(str::to_string).unwrap_or_else(|| {
+                    let ext = input
+                        .extension()
+                        .map(|e| e.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "mp4".to_string());
+                    input
+                        .with_file_name(format!("{}_chapters.{}", stem, ext))
+                        .to_string_lossy()
+                        .to_string()
+                });
+                let metadata_path = format!("{}.ffmeta", output_path);
+                std::fs::write(
+                    &metadata_path,
+                    Self::build_chapter_metadata(markers, duration_ms),
+                )
+                .map_err(|e| format!("Failed to write chapter metadata: {}", e))?;
+
+                let output = Command::new("ffmpeg")
+                    .args([
+                        "-y",
+                        "-i",
+                        input_path,
+                        "-i",
+                        &metadata_path,
+                        "-map",
+                        "0",
+                        "-map_metadata",
+                        "0",
+                        "-map_chapters",
+                        "1",
+                        "-c",
+                        "copy",
+                        &output_path,
+                    ])
+                    .output()
+                    .map_err(|e| format!("Failed to run FFmpeg: {}", e));
+                let _ = std::fs::remove_file(&metadata_path);
+                let output = output?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    error!("[VideoProcessor] FFmpeg chapter export error: {}", stderr);
+                    return Err(format!("FFmpeg failed: {}", stderr));
+                }
+                info!(
+                    "[VideoProcessor] Wrote {} chapters to {}",
+                    markers.len(),
+                    output_path
+                );
+                Ok(output_path)
+            }
+        }
+    }
+
+    /// Chapter spans as (start_ms, end_ms, title); each marker runs until the next one
+    fn marker_chapters(markers: &[RecordingMarker], duration_ms: u64) -> Vec<(u64, u64, &str)> {
+        let mut sorted: Vec<&RecordingMarker> = markers.iter().collect();
+        sorted.sort_by_key(|m| m.offset_ms);
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(i, marker)| {
+                let start = marker.offset_ms.min(duration_ms);
+                let end = sorted
+                    .get(i + 1)
+                    .map(|next| next.offset_ms.min(duration_ms))
+                    .unwrap_or(duration_ms)
+                    .max(start);
+                (start, end, marker.label.as_str())
+            })
+            .collect()
+    }
+
+    /// Build an FFMETADATA1 document with one chapter per marker
+    fn build_chapter_metadata(markers: &[RecordingMarker], duration_ms: u64) -> String {
+        let escape = |value: &str| {
+            let mut escaped = String::with_capacity(value.len());
+            for c in value.chars() {
+                if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        };
+
+        let mut doc = String::from(";FFMETADATA1\n");
+        for (start, end, title) in Self::marker_chapters(markers, duration_ms) {
+            doc.push_str(&format!(
+                "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+                start,
+                end,
+                escape(title)
+            ));
+        }
+        doc
+    }
+
+    /// Build a WebVTT chapters track with one cue per marker
+    fn build_webvtt_chapters(markers: &[RecordingMarker], duration_ms: u64) -> String {
+        let timestamp = |ms: u64| {
+            format!(
+                "{:02}:{:02}:{:02}.{:03}",
+                ms / 3_600_000,
+                (ms / 60_000) % 60,
+                (ms / 1000) % 60,
+                ms % 1000
+            )
+        };
+
+        let mut doc = String::from("WEBVTT\n");
+        for (index, (start, end, title)) in Self::marker_chapters(markers, duration_ms)
+            .into_iter()
+            .enumerate()
+        {
+            doc.push_str(&format!(
+                "\n{}\n{} --> {}\n{}\n",
+                index + 1,
+                timestamp(start),
+                timestamp(end),
+                title.replace("-->", "->")
+            ));
+        }
+        doc
+    }
+
     /// Check encoding support
     pub fn check_encoding_support() -> EncodingSupport {
         let check_encoder = |encoder: &str| -> bool {
@@ -833,6 +1337,96 @@ mod tests {
         assert!((VideoProcessor::parse_frame_rate("29.97") - 29.97).abs() < 0.01);
     }
 
+    fn marker(offset_ms: u64, label: &str) -> RecordingMarker {
+        RecordingMarker {
+            id: format!("m{}", offset_ms),
+            offset_ms,
+            label: label.to_string(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_chapter_metadata_from_markers() {
+        let markers = vec![marker(5_000, "Demo; part=2"), marker(1_000, "Intro")];
+        let doc = VideoProcessor::build_chapter_metadata(&markers, 8_000);
+
+        assert!(doc.starts_with(";FFMETADATA1\n"));
+        assert!(doc.contains("START=1000\nEND=5000\ntitle=Intro\n"));
+        assert!(doc.contains("START=5000\nEND=8000\ntitle=Demo\\; part\\=2\n"));
+    }
+
+    #[test]
+    fn test_webvtt_chapters_clamp_to_duration() {
+        let markers = vec![marker(61_500, "Late"), marker(0, "Start")];
+        let doc = VideoProcessor::build_webvtt_chapters(&markers, 60_000);
+
+        assert!(doc.starts_with("WEBVTT\n"));
+        assert!(doc.contains("1\n00:00:00.000 --> 00:01:00.000\nStart\n"));
+        assert!(doc.contains("2\n00:01:00.000 --> 00:01:00.000\nLate\n"));
+    }
+
+    #[test]
+    fn test_cut_points_aligned() {
+        let keyframes = [0.0, 2.0, 4.0, 6.0];
+        assert!(VideoProcessor::cut_points_aligned(
+            &keyframes,
+            2.0,
+            4.0,
+            Some(7.0)
+        ));
+        assert!(VideoProcessor::cut_points_aligned(
+            &keyframes, 2.02, 3.98, None
+        ));
+        // End at the end of the video needs no keyframe
+        assert!(VideoProcessor::cut_points_aligned(
+            &keyframes,
+            4.0,
+            7.0,
+            Some(7.0)
+        ));
+        assert!(!VideoProcessor::cut_points_aligned(
+            &keyframes,
+            1.0,
+            4.0,
+            Some(7.0)
+        ));
+        assert!(!VideoProcessor::cut_points_aligned(
+            &keyframes,
+            2.0,
+            5.0,
+            Some(7.0)
+        ));
+        assert!(!VideoProcessor::cut_points_aligned(&[], 0.0, 7.0, None));
+    }
+
+    #[test]
+    fn test_filmstrip_timestamps() {
+        assert_eq!(
+            VideoProcessor::filmstrip_timestamps(10_000, 0),
+            Vec::<u64>::new()
+        );
+        assert_eq!(
+            VideoProcessor::filmstrip_timestamps(0, 4),
+            Vec::<u64>::new()
+        );
+        assert_eq!(
+            VideoProcessor::filmstrip_timestamps(10_000, 4),
+            vec![1_250, 3_750, 6_250, 8_750]
+        );
+        assert_eq!(
+            VideoProcessor::filmstrip_timestamps(10_000, 100).len(),
+            MAX_FILMSTRIP_FRAMES as usize
+        );
+    }
+
+    #[test]
+    fn test_representative_timestamp() {
+        assert_eq!(VideoProcessor::representative_timestamp_ms(0), 0);
+        assert_eq!(VideoProcessor::representative_timestamp_ms(20_000), 2_000);
+        assert_eq!(VideoProcessor::representative_timestamp_ms(600_000), 5_000);
+    }
+
     #[test]
     fn test_check_ffmpeg_returns_bool() {
         let result = VideoProcessor::check_ffmpeg();
@@ -13,6 +13,7 @@ mod capture;
 mod ocr;
 pub mod ocr_manager;
 pub mod ocr_provider;
+mod ocr_selection;
 pub mod providers;
 mod region_selector;
 mod screenshot_history;
@@ -29,6 +30,7 @@ pub use ocr_provider::{
     OcrBounds as UnifiedOcrBounds, OcrError, OcrOptions, OcrProvider, OcrProviderConfig,
     OcrProviderInfo, OcrProviderType, OcrRegion, OcrResult as UnifiedOcrResult,
 };
+pub use ocr_selection::{OcrEngineSelection, ScreenshotOcrResult};
 pub use providers::{
     AnthropicVisionProvider, AzureVisionProvider, GoogleVisionProvider, OllamaVisionProvider,
     OpenAiVisionProvider, TesseractProvider, WindowsOcrProvider,
@@ -67,12 +69,18 @@ pub struct ScreenshotConfig {
     pub show_notification: bool,
     /// OCR language (e.g., "eng", "chi_sim", "jpn")
     pub ocr_language: String,
+    /// OCR engine used by `screenshot_ocr`
+    #[serde(default)]
+    pub ocr_engine: OcrEngineSelection,
     /// Whether to auto-save screenshots after capture
     #[serde(default)]
     pub auto_save: bool,
     /// Filename template for auto-saved screenshots, supports {timestamp} and {mode}
     #[serde(default = "default_filename_template")]
     pub filename_template: String,
+    /// Whether region-selection previews include the pixel color under the cursor
+    #[serde(default)]
+    pub region_preview_pixel_color: bool,
 }
 
 fn default_filename_template() -> String {
@@ -90,8 +98,10 @@ impl Default for ScreenshotConfig {
             copy_to_clipboard: true,
             show_notification: true,
             ocr_language: "eng".to_string(),
+            ocr_engine: OcrEngineSelection::default(),
             auto_save: false,
             filename_template: default_filename_template(),
+            region_preview_pixel_color: false,
         }
     }
 }
@@ -217,9 +227,21 @@ impl ScreenshotManager {
         .await
     }
 
+    /// Capture a region without capture delay or post-capture actions
+    /// (clipboard, auto-save), for internal use such as OCR
+    pub fn capture_region_silent(&self, region: CaptureRegion) -> Result<ScreenshotResult, String> {
+        self.capture
+            .capture_region(region.x, region.y, region.width, region.height)
+    }
+
     /// Start interactive region selection
     pub async fn start_region_selection(&self) -> Result<CaptureRegion, String> {
-        RegionSelector::select_region(&self.app_handle).await
+        let pixel_sampler = self
+            .config
+            .read()
+            .region_preview_pixel_color
+            .then(|| self.window_manager.clone());
+        RegionSelector::select_region(&self.app_handle, self.get_monitors(), pixel_sampler).await
     }
 
     /// Perform OCR on screenshot
@@ -377,6 +399,23 @@ impl ScreenshotManager {
         self.capture.get_monitors()
     }
 
+    /// Extract text with the configured OCR engine, reporting which engine ran
+    ///
+    /// `cloud` is the multi-provider OCR manager used for the `cloud` engine.
+    pub async fn extract_text_with_engine(
+        &self,
+        image_data: &[u8],
+        cloud: Option<&OcrManager>,
+    ) -> Result<ScreenshotOcrResult, String> {
+        let config = self.get_config();
+        let platform_language = self.windows_ocr.read().get_language().to_string();
+        let languages = ocr_selection::OcrLanguages {
+            platform: &platform_language,
+            tesseract: &config.ocr_language,
+        };
+        ocr_selection::run_ocr(config.ocr_engine, image_data, &languages, cloud).await
+    }
+
     /// Extract text using Windows OCR
     pub fn extract_text_windows(&self, image_data: &[u8]) -> Result<WinOcrResult, String> {
         self.windows_ocr.read().extract_text(image_data)
@@ -437,6 +476,11 @@ impl ScreenshotManager {
         self.history.clear_all()
     }
 
+    /// Remove screenshots older than `cutoff_ms`, keeping pinned ones unless `force`
+    pub fn purge_history(&self, cutoff_ms: Option<i64>, force: bool) -> usize {
+        self.history.purge(cutoff_ms, force)
+    }
+
     /// Get history stats
     pub fn get_history_stats(&self) -> (usize, bool) {
         (self.history.len(), self.history.is_empty())
@@ -589,6 +633,43 @@ impl ScreenshotManager {
         Ok(result)
     }
 
+    /// Capture a resolved window and add to history, labeled with its title
+    ///
+    /// The platform title lookup can come back empty for some windows, so the
+    /// caller-provided title is used as a fallback.
+    pub async fn capture_titled_window_with_history(
+        &self,
+        hwnd: isize,
+        title: &str,
+    ) -> Result<ScreenshotResult, String> {
+        let config = self.config.read().clone();
+        self.apply_capture_delay(&config).await;
+        let mut result = self.window_manager.read().capture_window_by_hwnd(hwnd)?;
+        if result.metadata.window_title.is_none() && !title.is_empty() {
+            result.metadata.window_title = Some(title.to_string());
+        }
+        self.post_capture_actions(&mut result, &config).await?;
+        self.add_to_history(&result);
+        Ok(result)
+    }
+
+    /// Capture the top-level window under the mouse cursor and add to history
+    pub async fn capture_window_under_cursor_with_history(
+        &self,
+    ) -> Result<ScreenshotResult, String> {
+        let (x, y) = get_cursor_position().ok_or("Failed to get cursor position")?;
+        let window = self
+            .get_window_at_point(x, y)
+            .ok_or_else(|| format!("No window found under cursor at ({}, {})", x, y))?;
+        log::debug!(
+            "Capturing window under cursor: '{}' (hwnd={})",
+            window.title,
+            window.hwnd
+        );
+        self.capture_titled_window_with_history(window.hwnd, &window.title)
+            .await
+    }
+
     /// Calculate snap position for window movement
     pub fn calculate_snap_position(
         &self,
@@ -718,6 +799,20 @@ impl ScreenshotManager {
     }
 }
 
+/// Get the current mouse cursor position in screen coordinates
+#[cfg(not(mobile))]
+fn get_cursor_position() -> Option<(i32, i32)> {
+    match mouse_position::mouse_position::Mouse::get_mouse_position() {
+        mouse_position::mouse_position::Mouse::Position { x, y } => Some((x, y)),
+        mouse_position::mouse_position::Mouse::Error => None,
+    }
+}
+
+#[cfg(mobile)]
+fn get_cursor_position() -> Option<(i32, i32)> {
+    None
+}
+
 /// Monitor information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitorInfo {
@@ -751,6 +846,7 @@ mod tests {
         assert!(config.copy_to_clipboard);
         assert!(config.show_notification);
         assert_eq!(config.ocr_language, "eng");
+        assert_eq!(config.ocr_engine, OcrEngineSelection::Auto);
         assert!(!config.auto_save);
         assert_eq!(config.filename_template, "screenshot_{timestamp}");
     }
@@ -766,8 +862,10 @@ mod tests {
             copy_to_clipboard: false,
             show_notification: false,
             ocr_language: "chi_sim".to_string(),
+            ocr_engine: OcrEngineSelection::Tesseract,
             auto_save: true,
             filename_template: "custom_{mode}_{timestamp}".to_string(),
+            region_preview_pixel_color: false,
         };
 
         assert_eq!(config.save_directory, Some("/custom/path".to_string()));
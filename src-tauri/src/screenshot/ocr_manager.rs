@@ -175,6 +175,14 @@ impl OcrManager {
             ))
         })?;
 
+        // Cloud vision providers are unavailable in offline mode
+        if provider_type.requires_api_key() && crate::http::is_offline_mode() {
+            return Err(
+                OcrError::provider_unavailable(crate::http::OFFLINE_MODE_ERROR)
+                    .with_provider(provider_type.display_name()),
+            );
+        }
+
         // Check if provider is available
         if !provider.is_available().await {
             return Err(OcrError::provider_unavailable(format!(
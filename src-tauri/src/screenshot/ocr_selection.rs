@@ -0,0 +1,244 @@
+//! OCR engine selection for screenshot OCR
+//!
+//! Resolves the configured `ocr_engine` into an ordered chain of engines and
+//! runs them until one succeeds. Platform OCR falls back to Tesseract when it
+//! is unavailable or the requested language is not installed.
+
+use super::ocr_manager::OcrManager;
+use super::ocr_provider::{OcrOptions, OcrProvider, OcrResult};
+use super::providers::{TesseractProvider, WindowsOcrProvider};
+use super::windows_ocr::WindowsOcr;
+use serde::{Deserialize, Serialize};
+
+/// OCR engine requested in the screenshot configuration
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrEngineSelection {
+    /// Platform OCR when usable, otherwise Tesseract
+    Auto,
+    /// Platform OCR (Windows OCR), falling back to Tesseract
+    Platform,
+    /// Tesseract only
+    Tesseract,
+    /// Default cloud provider of the OCR manager, falling back to local engines
+    Cloud,
+}
+
+impl Default for OcrEngineSelection {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+/// OCR engine that actually produced a result
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrEngineKind {
+    Platform,
+    Tesseract,
+    Cloud,
+}
+
+impl OcrEngineSelection {
+    /// Engines to try, in order
+    pub fn candidates(self) -> Vec<OcrEngineKind> {
+        match self {
+            Self::Auto | Self::Platform => vec![OcrEngineKind::Platform, OcrEngineKind::Tesseract],
+            Self::Tesseract => vec![OcrEngineKind::Tesseract],
+            Self::Cloud => vec![
+                OcrEngineKind::Cloud,
+                OcrEngineKind::Platform,
+                OcrEngineKind::Tesseract,
+            ],
+        }
+    }
+}
+
+/// Result of `screenshot_ocr`, including which engine ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotOcrResult {
+    /// Extracted text
+    pub text: String,
+    /// Engine that produced the text
+    pub engine: OcrEngineKind,
+    /// Display name of the provider behind the engine
+    pub provider: String,
+    /// Language used for recognition
+    pub language: Option<String>,
+    /// Overall confidence score (0.0 - 1.0)
+    pub confidence: f64,
+    /// Why earlier engines in the chain were skipped, if any
+    pub fallback_reason: Option<String>,
+}
+
+/// Languages passed to each engine; platform and Tesseract use different codes
+pub struct OcrLanguages<'a> {
+    /// BCP-47 tag for platform OCR (e.g. "en-US")
+    pub platform: &'a str,
+    /// Tesseract language code (e.g. "eng", "chi_sim")
+    pub tesseract: &'a str,
+}
+
+/// Run the engine chain for `selection` and return the first successful result
+pub async fn run_ocr(
+    selection: OcrEngineSelection,
+    image_data: &[u8],
+    languages: &OcrLanguages<'_>,
+    cloud: Option<&OcrManager>,
+) -> Result<ScreenshotOcrResult, String> {
+    let mut reasons = Vec::new();
+
+    for engine in selection.candidates() {
+        let attempt = match engine {
+            OcrEngineKind::Platform => run_platform(image_data, languages.platform).await,
+            OcrEngineKind::Tesseract => run_tesseract(image_data, languages.tesseract).await,
+            OcrEngineKind::Cloud => run_cloud(image_data, cloud).await,
+        };
+
+        match attempt {
+            Ok(result) => {
+                let fallback_reason = (!reasons.is_empty()).then(|| reasons.join("; "));
+                if let Some(reason) = &fallback_reason {
+                    log::info!("Screenshot OCR fell back to {:?}: {}", engine, reason);
+                }
+                return Ok(ScreenshotOcrResult {
+                    text: result.text,
+                    engine,
+                    provider: result.provider,
+                    language: result.language,
+                    confidence: result.confidence,
+                    fallback_reason,
+                });
+            }
+            Err(reason) => {
+                log::debug!("Screenshot OCR engine {:?} skipped: {}", engine, reason);
+                reasons.push(reason);
+            }
+        }
+    }
+
+    Err(format!("No OCR engine succeeded: {}", reasons.join("; ")))
+}
+
+/// Reason platform OCR cannot be used, if any
+fn platform_unavailable_reason(
+    available: bool,
+    language_installed: bool,
+    language: &str,
+) -> Option<String> {
+    if !available {
+        Some("platform OCR is not available".to_string())
+    } else if !language_installed {
+        Some(format!(
+            "platform OCR language '{}' is not installed",
+            language
+        ))
+    } else {
+        None
+    }
+}
+
+async fn run_platform(image_data: &[u8], language: &str) -> Result<OcrResult, String> {
+    let available = WindowsOcr::is_available();
+    let language_installed = available && WindowsOcr::is_language_available(language);
+    if let Some(reason) = platform_unavailable_reason(available, language_installed, language) {
+        return Err(reason);
+    }
+
+    let options = OcrOptions {
+        language: Some(language.to_string()),
+        ..Default::default()
+    };
+    WindowsOcrProvider::new()
+        .extract_text(image_data, &options)
+        .await
+        .map_err(|e| format!("platform OCR failed: {}", e.message))
+}
+
+async fn run_tesseract(image_data: &[u8], language: &str) -> Result<OcrResult, String> {
+    let provider = TesseractProvider::new();
+    if !provider.is_available().await {
+        return Err("Tesseract is not installed".to_string());
+    }
+
+    let options = OcrOptions {
+        language: Some(language.to_string()),
+        ..Default::default()
+    };
+    provider
+        .extract_text(image_data, &options)
+        .await
+        .map_err(|e| format!("Tesseract failed: {}", e.message))
+}
+
+async fn run_cloud(image_data: &[u8], cloud: Option<&OcrManager>) -> Result<OcrResult, String> {
+    let manager = cloud.ok_or_else(|| "cloud OCR is not initialized".to_string())?;
+    let provider = manager.get_default_provider();
+    if provider.is_local() {
+        return Err(format!(
+            "default OCR provider {} is not a cloud provider",
+            provider.display_name()
+        ));
+    }
+
+    manager
+        .extract_text(Some(provider), image_data, &OcrOptions::default())
+        .await
+        .map_err(|e| format!("cloud OCR failed: {}", e.message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_candidates() {
+        assert_eq!(
+            OcrEngineSelection::Auto.candidates(),
+            vec![OcrEngineKind::Platform, OcrEngineKind::Tesseract]
+        );
+        assert_eq!(
+            OcrEngineSelection::Platform.candidates(),
+            vec![OcrEngineKind::Platform, OcrEngineKind::Tesseract]
+        );
+        assert_eq!(
+            OcrEngineSelection::Tesseract.candidates(),
+            vec![OcrEngineKind::Tesseract]
+        );
+        assert_eq!(
+            OcrEngineSelection::Cloud.candidates()[0],
+            OcrEngineKind::Cloud
+        );
+    }
+
+    #[test]
+    fn test_selection_serialization() {
+        assert_eq!(OcrEngineSelection::default(), OcrEngineSelection::Auto);
+        let json = serde_json::to_string(&OcrEngineSelection::Tesseract).unwrap();
+        assert_eq!(json, "\"tesseract\"");
+        let parsed: OcrEngineSelection = serde_json::from_str("\"platform\"").unwrap();
+        assert_eq!(parsed, OcrEngineSelection::Platform);
+    }
+
+    #[test]
+    fn test_platform_unavailable_reason() {
+        assert!(platform_unavailable_reason(true, true, "en-US").is_none());
+        assert_eq!(
+            platform_unavailable_reason(false, false, "en-US").as_deref(),
+            Some("platform OCR is not available")
+        );
+        assert!(platform_unavailable_reason(true, false, "ja-JP")
+            .unwrap()
+            .contains("'ja-JP' is not installed"));
+    }
+
+    #[tokio::test]
+    async fn test_cloud_requires_manager() {
+        let error = run_cloud(b"png", None).await.unwrap_err();
+        assert!(error.contains("not initialized"));
+
+        let manager = OcrManager::new();
+        let error = run_cloud(b"png", Some(&manager)).await.unwrap_err();
+        assert!(error.contains("not a cloud provider"));
+    }
+}
@@ -8,9 +8,87 @@ use crate::screenshot::ocr_provider::{
     OcrRegion, OcrRegionType, OcrResult,
 };
 use async_trait::async_trait;
-use std::path::PathBuf;
+use once_cell::sync::OnceCell;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// File extension of Tesseract language data
+const TRAINEDDATA_EXT: &str = "traineddata";
+
+/// Directory holding language data installed from within the app
+static APP_TESSDATA_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Register the directory holding language data downloaded by the app
+pub fn set_app_tessdata_dir(path: PathBuf) {
+    let _ = APP_TESSDATA_DIR.set(path);
+}
+
+/// Language codes installed in the app's tessdata directory
+pub fn app_installed_languages() -> Vec<String> {
+    APP_TESSDATA_DIR
+        .get()
+        .map(|dir| list_traineddata(dir))
+        .unwrap_or_default()
+}
+
+fn list_traineddata(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut languages: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == TRAINEDDATA_EXT))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect();
+    languages.sort();
+    languages
+}
+
+/// Tessdata directory for a `+`-joined language spec
+///
+/// Each language is looked up in the app directory on its own. Returns the app
+/// directory when it holds every language, `None` when it holds none of them
+/// (Tesseract's own directory is used), and an error when the spec mixes both,
+/// since Tesseract reads all languages from a single directory.
+fn app_tessdata_for(lang: &str) -> Result<Option<PathBuf>, OcrError> {
+    let Some(dir) = APP_TESSDATA_DIR.get() else {
+        return Ok(None);
+    };
+    resolve_tessdata_dir(dir, lang)
+}
+
+fn resolve_tessdata_dir(dir: &Path, lang: &str) -> Result<Option<PathBuf>, OcrError> {
+    let (in_app, elsewhere): (Vec<&str>, Vec<&str>) = lang
+        .split('+')
+        .partition(|code| dir.join(format!("{}.{}", code, TRAINEDDATA_EXT)).is_file());
+    if in_app.is_empty() {
+        return Ok(None);
+    }
+    if elsewhere.is_empty() {
+        return Ok(Some(dir.to_path_buf()));
+    }
+    Err(OcrError::new(
+        OcrErrorCode::UnsupportedLanguage,
+        format!(
+            "OCR languages {} are installed in the app but {} are not; install them in the app to combine them",
+            in_app.join("+"),
+            elsewhere.join("+")
+        ),
+    ))
+}
+
+/// Whether `code` looks like a Tesseract language code (e.g. `jpn`, `chi_sim`)
+pub fn is_valid_language_code(code: &str) -> bool {
+    let mut parts = code.split('_');
+    let base_ok = parts
+        .next()
+        .is_some_and(|base| base.len() == 3 && base.bytes().all(|b| b.is_ascii_lowercase()));
+    base_ok
+        && code.len() <= 24
+        && parts.all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase()))
+}
+
 /// Tesseract OCR Provider
 pub struct TesseractProvider {
     /// Path to tessdata directory
@@ -48,11 +126,11 @@ impl TesseractProvider {
             .unwrap_or(false)
     }
 
-    /// Get list of installed languages from tesseract
+    /// Get list of installed languages from tesseract and the app's tessdata directory
     fn get_installed_languages() -> Vec<String> {
         let output = Command::new("tesseract").arg("--list-langs").output();
 
-        match output {
+        let mut languages: Vec<String> = match output {
             Ok(o) if o.status.success() => {
                 let stdout = String::from_utf8_lossy(&o.stdout);
                 stdout
@@ -63,11 +141,25 @@ impl TesseractProvider {
                     .collect()
             }
             _ => vec![],
+        };
+        for language in app_installed_languages() {
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
         }
+        languages
+    }
+
+    /// Convert each language of a `+`-joined spec to Tesseract format
+    pub fn to_tesseract_spec(spec: &str) -> String {
+        spec.split('+')
+            .map(|lang| Self::to_tesseract_lang(lang.trim()))
+            .collect::<Vec<_>>()
+            .join("+")
     }
 
     /// Convert language code to Tesseract format
-    fn to_tesseract_lang(lang: &str) -> String {
+    pub fn to_tesseract_lang(lang: &str) -> String {
         match lang {
             "en" | "en-US" | "en-GB" => "eng".to_string(),
             "zh" | "zh-Hans" | "zh-CN" => "chi_sim".to_string(),
@@ -154,6 +246,17 @@ impl OcrProvider for TesseractProvider {
             ));
         }
 
+        // Determine language and where its data lives
+        let lang = options
+            .language
+            .as_ref()
+            .map(|l| Self::to_tesseract_spec(l))
+            .unwrap_or_else(|| self.default_language.clone());
+        let tessdata = match &self.tessdata_path {
+            Some(path) => Some(path.clone()),
+            None => app_tessdata_for(&lang)?,
+        };
+
         // Create temporary file for image
         let temp_dir = std::env::temp_dir();
         let input_path = temp_dir.join(format!("ocr_input_{}.png", std::process::id()));
@@ -167,19 +270,12 @@ impl OcrProvider for TesseractProvider {
             )
         })?;
 
-        // Determine language
-        let lang = options
-            .language
-            .as_ref()
-            .map(|l| Self::to_tesseract_lang(l))
-            .unwrap_or_else(|| self.default_language.clone());
-
         // Build tesseract command
         let mut cmd = Command::new("tesseract");
         cmd.arg(&input_path).arg(&output_base).arg("-l").arg(&lang);
 
-        // Add tessdata path if specified
-        if let Some(ref tessdata) = self.tessdata_path {
+        // Add tessdata path if specified, or use the app directory for app-installed languages
+        if let Some(tessdata) = tessdata {
             cmd.arg("--tessdata-dir").arg(tessdata);
         }
 
@@ -330,6 +426,51 @@ mod tests {
         assert_eq!(TesseractProvider::to_tesseract_lang("unknown"), "unknown");
     }
 
+    #[test]
+    fn test_language_code_validation() {
+        assert!(is_valid_language_code("eng"));
+        assert!(is_valid_language_code("chi_sim"));
+        assert!(is_valid_language_code("chi_tra_vert"));
+        assert!(!is_valid_language_code("en"));
+        assert!(!is_valid_language_code("ENG"));
+        assert!(!is_valid_language_code("chi_"));
+        assert!(!is_valid_language_code("../eng"));
+        assert!(!is_valid_language_code("eng.traineddata"));
+    }
+
+    #[test]
+    fn test_to_tesseract_spec() {
+        assert_eq!(TesseractProvider::to_tesseract_spec("en+ja"), "eng+jpn");
+        assert_eq!(TesseractProvider::to_tesseract_spec("chi_sim"), "chi_sim");
+    }
+
+    #[test]
+    fn test_resolve_tessdata_dir_per_language() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("jpn.traineddata"), b"").unwrap();
+        std::fs::write(dir.path().join("kor.traineddata"), b"").unwrap();
+
+        assert_eq!(
+            resolve_tessdata_dir(dir.path(), "jpn+kor").unwrap(),
+            Some(dir.path().to_path_buf())
+        );
+        assert_eq!(resolve_tessdata_dir(dir.path(), "eng").unwrap(), None);
+        let err = resolve_tessdata_dir(dir.path(), "eng+jpn").unwrap_err();
+        assert_eq!(err.code, OcrErrorCode::UnsupportedLanguage);
+        assert!(err.message.contains("eng"));
+    }
+
+    #[test]
+    fn test_list_traineddata() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("jpn.traineddata"), b"").unwrap();
+        std::fs::write(dir.path().join("chi_sim.traineddata"), b"").unwrap();
+        std::fs::write(dir.path().join("jpn.traineddata.part"), b"").unwrap();
+
+        assert_eq!(list_traineddata(dir.path()), vec!["chi_sim", "jpn"]);
+        assert!(list_traineddata(&dir.path().join("missing")).is_empty());
+    }
+
     #[test]
     fn test_psm_modes() {
         assert_eq!(
@@ -2,8 +2,9 @@
 //!
 //! Provides an interactive overlay for selecting a screen region to capture.
 
-use super::CaptureRegion;
-use parking_lot::Mutex;
+use super::{CaptureRegion, MonitorInfo, WindowManager};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Listener, Manager, WebviewUrl, WebviewWindowBuilder};
 use tokio::sync::oneshot;
@@ -11,13 +12,77 @@ use tokio::sync::oneshot;
 /// Region selector window label
 const REGION_SELECTOR_LABEL: &str = "region-selector";
 
+/// Event emitted with live selection feedback while the user drags
+pub const REGION_PREVIEW_EVENT: &str = "screenshot://region-preview";
+
+/// Cursor/selection update reported by the selector overlay (screen coordinates)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegionSelectionUpdate {
+    region: Option<CaptureRegion>,
+    cursor_x: i32,
+    cursor_y: i32,
+}
+
+/// Live preview of the region being selected, for magnifier/coordinate HUDs
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionPreview {
+    /// Current selection rectangle, if the user has started selecting
+    pub region: Option<CaptureRegion>,
+    /// Cursor position
+    pub cursor_x: i32,
+    pub cursor_y: i32,
+    /// Index of the monitor under the cursor
+    pub monitor_index: Option<usize>,
+    /// Hex color of the pixel under the cursor, when sampling is enabled
+    pub pixel_color: Option<String>,
+}
+
+impl RegionPreview {
+    fn from_update(
+        update: RegionSelectionUpdate,
+        monitors: &[MonitorInfo],
+        pixel_sampler: Option<&RwLock<WindowManager>>,
+    ) -> Self {
+        let (x, y) = (update.cursor_x, update.cursor_y);
+        Self {
+            region: update.region,
+            cursor_x: x,
+            cursor_y: y,
+            monitor_index: monitor_at_point(monitors, x, y),
+            pixel_color: pixel_sampler.and_then(|wm| wm.read().get_pixel_color(x, y)),
+        }
+    }
+}
+
+/// Find the monitor containing a screen point
+fn monitor_at_point(monitors: &[MonitorInfo], x: i32, y: i32) -> Option<usize> {
+    monitors
+        .iter()
+        .find(|m| {
+            x >= m.x
+                && y >= m.y
+                && (x as i64) < m.x as i64 + m.width as i64
+                && (y as i64) < m.y as i64 + m.height as i64
+        })
+        .map(|m| m.index)
+}
+
 /// Region selector for interactive screen region selection
 pub struct RegionSelector;
 
 impl RegionSelector {
     /// Start interactive region selection
     /// Returns the selected region or an error if cancelled
-    pub async fn select_region(app_handle: &AppHandle) -> Result<CaptureRegion, String> {
+    ///
+    /// Drag updates from the overlay are re-emitted as [`REGION_PREVIEW_EVENT`];
+    /// pass `pixel_sampler` to include the color under the cursor.
+    pub async fn select_region(
+        app_handle: &AppHandle,
+        monitors: Vec<MonitorInfo>,
+        pixel_sampler: Option<Arc<RwLock<WindowManager>>>,
+    ) -> Result<CaptureRegion, String> {
         // Create a channel to receive the selection result
         let (tx, rx) = oneshot::channel::<Result<CaptureRegion, String>>();
         let tx = Arc::new(Mutex::new(Some(tx)));
@@ -79,6 +144,16 @@ impl RegionSelector {
             }
         });
 
+        // Listen for cursor/selection updates and turn them into preview events
+        let app_handle_clone = app_handle.clone();
+        let preview_listener = app_handle.listen("region-selection-update", move |event| {
+            if let Ok(update) = serde_json::from_str::<RegionSelectionUpdate>(event.payload()) {
+                let preview =
+                    RegionPreview::from_update(update, &monitors, pixel_sampler.as_deref());
+                let _ = app_handle_clone.emit(REGION_PREVIEW_EVENT, &preview);
+            }
+        });
+
         // Emit event to notify frontend that selection is ready
         let _ = app_handle.emit(
             "region-selection-started",
@@ -91,10 +166,12 @@ impl RegionSelector {
         );
 
         // Wait for selection result
-        match rx.await {
+        let result = match rx.await {
             Ok(result) => result,
             Err(_) => Err("Selection channel closed unexpectedly".to_string()),
-        }
+        };
+        app_handle.unlisten(preview_listener);
+        result
     }
 }
 
@@ -458,6 +535,80 @@ mod tests {
         assert!(height > 0);
     }
 
+    // ==================== RegionPreview Tests ====================
+
+    fn test_monitors() -> Vec<MonitorInfo> {
+        vec![
+            MonitorInfo {
+                index: 0,
+                name: "Primary".to_string(),
+                x: 0,
+                y: 0,
+                width: 1920,
+                height: 1080,
+                is_primary: true,
+                scale_factor: 1.0,
+            },
+            MonitorInfo {
+                index: 1,
+                name: "Left".to_string(),
+                x: -1280,
+                y: 0,
+                width: 1280,
+                height: 1024,
+                is_primary: false,
+                scale_factor: 1.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_monitor_at_point() {
+        let monitors = test_monitors();
+
+        assert_eq!(monitor_at_point(&monitors, 100, 100), Some(0));
+        assert_eq!(monitor_at_point(&monitors, -1, 500), Some(1));
+        assert_eq!(monitor_at_point(&monitors, 1920, 0), None);
+        assert_eq!(monitor_at_point(&monitors, -100, 1050), None);
+        assert_eq!(monitor_at_point(&[], 0, 0), None);
+    }
+
+    #[test]
+    fn test_region_preview_from_update() {
+        let update: RegionSelectionUpdate = serde_json::from_value(serde_json::json!({
+            "region": { "x": -200, "y": 100, "width": 350, "height": 200 },
+            "cursorX": -50,
+            "cursorY": 300
+        }))
+        .unwrap();
+
+        let preview = RegionPreview::from_update(update, &test_monitors(), None);
+
+        let region = preview.region.as_ref().unwrap();
+        assert_eq!(region.x, -200);
+        assert_eq!(region.width, 350);
+        assert_eq!(preview.monitor_index, Some(1));
+        assert!(preview.pixel_color.is_none());
+
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["cursorX"], -50);
+        assert_eq!(json["monitorIndex"], 1);
+    }
+
+    #[test]
+    fn test_region_preview_before_selection() {
+        let update: RegionSelectionUpdate = serde_json::from_value(serde_json::json!({
+            "cursorX": 640,
+            "cursorY": 480
+        }))
+        .unwrap();
+
+        let preview = RegionPreview::from_update(update, &test_monitors(), None);
+
+        assert!(preview.region.is_none());
+        assert_eq!(preview.monitor_index, Some(0));
+    }
+
     // ==================== REGION_SELECTOR_LABEL Tests ====================
 
     #[test]
@@ -348,6 +348,24 @@ impl ScreenshotHistory {
         self.save_to_disk();
     }
 
+    /// Remove entries older than `cutoff_ms` (all entries when `None`)
+    ///
+    /// Pinned entries are kept unless `force` is set. Returns the number removed.
+    pub fn purge(&self, cutoff_ms: Option<i64>, force: bool) -> usize {
+        let removed = {
+            let mut entries = self.entries.write();
+            let before = entries.len();
+            entries.retain(|e| {
+                (e.is_pinned && !force) || cutoff_ms.is_some_and(|cutoff| e.timestamp >= cutoff)
+            });
+            before - entries.len()
+        };
+        if removed > 0 {
+            self.save_to_disk();
+        }
+        removed
+    }
+
     /// Clear all entries
     pub fn clear_all(&self) {
         self.entries.write().clear();
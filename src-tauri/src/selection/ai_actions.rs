@@ -0,0 +1,209 @@
+//! Selection AI actions
+//!
+//! Prompt templates for the selection toolbar AI actions, used when an action is
+//! re-run on the backend (e.g. against a selection history entry). The templates
+//! live in `action_prompts.json` next to this module and are also imported by the toolbar.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prompt templates shared with the frontend selection toolbar
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SelectionActionPrompts {
+    system_prompt: String,
+    /// User prompt per action, with `{text}` and `{language}` placeholders
+    actions: HashMap<String, String>,
+}
+
+static ACTION_PROMPTS: Lazy<SelectionActionPrompts> = Lazy::new(|| {
+    serde_json::from_str(include_str!("action_prompts.json"))
+        .expect("selection action prompts are valid JSON")
+});
+
+/// System prompt shared by all selection actions
+pub fn selection_system_prompt() -> &'static str {
+    &ACTION_PROMPTS.system_prompt
+}
+
+/// Result of running an AI action on a selection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionAiResult {
+    /// Action identifier (e.g. `explain`, `translate`)
+    pub action_id: String,
+    /// Target language, for actions that use one
+    pub target_language: Option<String>,
+    /// Generated output
+    pub result: String,
+    /// Timestamp when the result was generated
+    pub timestamp: i64,
+    /// Whether this result was served from the history cache
+    #[serde(default)]
+    pub cached: bool,
+    /// Vector collection whose snippets grounded this result, if any
+    #[serde(default)]
+    pub context_collection: Option<String>,
+}
+
+/// Retrieval settings for grounding selection AI actions in a vector collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectionContextConfig {
+    /// Vector collection to search; `None` disables retrieval
+    pub collection: Option<String>,
+    /// Number of snippets prepended to the prompt
+    pub top_k: usize,
+    /// Ollama endpoint used to embed the selection
+    pub embedding_base_url: String,
+    /// Embedding model; defaults to the model recorded on the collection
+    pub embedding_model: Option<String>,
+}
+
+impl Default for SelectionContextConfig {
+    fn default() -> Self {
+        Self {
+            collection: None,
+            top_k: 3,
+            embedding_base_url: "http://localhost:11434".to_string(),
+            embedding_model: None,
+        }
+    }
+}
+
+/// Extract the text of a retrieved vector point from its payload
+pub fn context_snippet_text(payload: &serde_json::Value) -> Option<String> {
+    ["content", "text"]
+        .iter()
+        .find_map(|key| payload.get(key).and_then(|v| v.as_str()))
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Prepend retrieved snippets to an action prompt
+pub fn with_context_snippets(prompt: String, snippets: &[String]) -> String {
+    if snippets.is_empty() {
+        return prompt;
+    }
+
+    let context = snippets
+        .iter()
+        .enumerate()
+        .map(|(i, snippet)| format!("[{}] {}", i + 1, snippet))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!(
+        "Use the following context if it is relevant:\n\n{}\n\n---\n\n{}",
+        context, prompt
+    )
+}
+
+/// Whether an action's output depends on the target language
+pub fn action_uses_target_language(action_id: &str) -> bool {
+    action_id == "translate"
+}
+
+fn language_name(code: &str) -> &str {
+    match code {
+        "zh-CN" => "Simplified Chinese",
+        "zh-TW" => "Traditional Chinese",
+        "en" | "en-US" => "English",
+        "ja" | "ja-JP" => "Japanese",
+        "ko" | "ko-KR" => "Korean",
+        "fr" | "fr-FR" => "French",
+        "de" | "de-DE" => "German",
+        "es" | "es-ES" => "Spanish",
+        "ru" | "ru-RU" => "Russian",
+        other => other,
+    }
+}
+
+/// Build the user prompt for an AI action.
+///
+/// Returns `None` for unknown actions and for non-AI actions such as `copy` or `search`.
+pub fn build_action_prompt(action_id: &str, text: &str, target_language: &str) -> Option<String> {
+    let template = ACTION_PROMPTS.actions.get(action_id)?;
+    Some(
+        template
+            .replacen("{language}", language_name(target_language), 1)
+            .replacen("{text}", text, 1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_action_prompt_known_actions() {
+        let prompt = build_action_prompt("explain", "hello", "en").unwrap();
+        assert!(prompt.contains("\"hello\""));
+
+        let prompt = build_action_prompt("code-explain", "fn main() {}", "en").unwrap();
+        assert!(prompt.contains("```\nfn main() {}\n```"));
+    }
+
+    #[test]
+    fn test_build_action_prompt_uses_language_name() {
+        let prompt = build_action_prompt("translate", "hello", "zh-CN").unwrap();
+        assert!(prompt.contains("Simplified Chinese"));
+
+        let prompt = build_action_prompt("translate", "hello", "pt-BR").unwrap();
+        assert!(prompt.contains("pt-BR"));
+    }
+
+    #[test]
+    fn test_shared_prompts_are_complete() {
+        assert!(!selection_system_prompt().is_empty());
+        for (action_id, template) in &ACTION_PROMPTS.actions {
+            assert!(template.contains("{text}"), "{action_id}");
+            assert_eq!(
+                template.contains("{language}"),
+                action_uses_target_language(action_id),
+                "{action_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_action_prompt_rejects_non_ai_actions() {
+        assert!(build_action_prompt("copy", "hello", "en").is_none());
+        assert!(build_action_prompt("search", "hello", "en").is_none());
+        assert!(build_action_prompt("unknown", "hello", "en").is_none());
+    }
+
+    #[test]
+    fn test_action_uses_target_language() {
+        assert!(action_uses_target_language("translate"));
+        assert!(!action_uses_target_language("explain"));
+    }
+
+    #[test]
+    fn test_context_snippet_text() {
+        let payload = serde_json::json!({ "content": "  Rust ownership  ", "source": "docs" });
+        assert_eq!(
+            context_snippet_text(&payload).as_deref(),
+            Some("Rust ownership")
+        );
+
+        let payload = serde_json::json!({ "text": "Borrowing rules" });
+        assert_eq!(
+            context_snippet_text(&payload).as_deref(),
+            Some("Borrowing rules")
+        );
+
+        assert!(context_snippet_text(&serde_json::json!({ "content": "   " })).is_none());
+        assert!(context_snippet_text(&serde_json::json!({ "title": "x" })).is_none());
+    }
+
+    #[test]
+    fn test_with_context_snippets() {
+        let prompt = "Explain this".to_string();
+        assert_eq!(with_context_snippets(prompt.clone(), &[]), prompt);
+
+        let grounded = with_context_snippets(prompt, &["first".to_string(), "second".to_string()]);
+        assert!(grounded.starts_with("Use the following context"));
+        assert!(grounded.contains("[1] first\n\n[2] second"));
+        assert!(grounded.ends_with("---\n\nExplain this"));
+    }
+}
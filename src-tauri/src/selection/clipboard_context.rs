@@ -767,7 +767,8 @@ impl ClipboardContextAnalyzer {
             .map(|(lang, _)| lang)
     }
 
-    fn check_sensitive(&self, content: &str) -> bool {
+    /// Whether content looks like a secret or personal identifier
+    pub fn check_sensitive(&self, content: &str) -> bool {
         log::debug!(
             "[ClipboardContextAnalyzer] Checking for sensitive content in {} chars",
             content.len()
@@ -0,0 +1,170 @@
+//! Clipboard content conversion
+//!
+//! Converts clipboard history entries between HTML, Markdown and plain text.
+//! HTML is parsed with `html2md` and Markdown with `pulldown-cmark`, so nested
+//! markup, entities and code blocks survive the conversion.
+
+use super::clipboard_history::{ClipboardContentType, ClipboardEntry};
+use pulldown_cmark::{html, Event, Options, Parser, TagEnd};
+use serde::{Deserialize, Serialize};
+
+/// Format of clipboard content before or after conversion
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardFormat {
+    Html,
+    Markdown,
+    Text,
+}
+
+/// Converted content of a clipboard entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardConversion {
+    pub entry_id: String,
+    /// Format the entry content was read as
+    pub source_format: ClipboardFormat,
+    pub target_format: ClipboardFormat,
+    pub content: String,
+    /// Whether the converted content was written to the clipboard
+    pub written_to_clipboard: bool,
+}
+
+/// Convert a clipboard entry to `target`.
+///
+/// HTML entries are read from their HTML payload; text entries are treated as
+/// Markdown, so converting them to Markdown or text returns them unchanged.
+pub fn convert_entry(
+    entry: &ClipboardEntry,
+    target: ClipboardFormat,
+) -> Result<ClipboardConversion, String> {
+    let (source_format, source) = match (&entry.content_type, &entry.html, &entry.text) {
+        (ClipboardContentType::Html, Some(html), _) => (ClipboardFormat::Html, html.as_str()),
+        (_, _, Some(text)) => (ClipboardFormat::Markdown, text.as_str()),
+        _ => {
+            return Err(format!(
+                "Conversion not supported for {:?} clipboard entries",
+                entry.content_type
+            ))
+        }
+    };
+
+    let content = match (source_format, target) {
+        (ClipboardFormat::Html, ClipboardFormat::Markdown) => html_to_markdown(source),
+        (ClipboardFormat::Html, ClipboardFormat::Text) => html_to_text(source),
+        (ClipboardFormat::Markdown, ClipboardFormat::Html) => markdown_to_html(source),
+        _ => source.to_string(),
+    };
+
+    Ok(ClipboardConversion {
+        entry_id: entry.id.clone(),
+        source_format,
+        target_format: target,
+        content,
+        written_to_clipboard: false,
+    })
+}
+
+/// Convert HTML to Markdown
+pub fn html_to_markdown(html: &str) -> String {
+    html2md::parse_html(html).trim().to_string()
+}
+
+/// Convert HTML to plain text
+pub fn html_to_text(html: &str) -> String {
+    markdown_to_text(&html_to_markdown(html))
+}
+
+/// Render Markdown to HTML
+pub fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, markdown_options());
+    let mut output = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// Strip Markdown formatting, keeping text and block structure
+pub fn markdown_to_text(markdown: &str) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    for event in Parser::new_ext(markdown, markdown_options()) {
+        match event {
+            Event::Text(text) | Event::Code(text) => output.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => output.push('\n'),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableRow
+                | TagEnd::TableHead,
+            ) => output.push('\n'),
+            Event::End(TagEnd::TableCell) => output.push('\t'),
+            _ => {}
+        }
+    }
+
+    output
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = "<h1>Title</h1><p>Some <strong>bold</strong> &amp; \
+                        <a href=\"https://example.com\">linked</a> text.</p>\
+                        <ul><li>one</li><li>two</li></ul>";
+
+    #[test]
+    fn test_html_to_markdown() {
+        let markdown = html_to_markdown(HTML);
+        assert!(markdown.contains("Title"));
+        assert!(markdown.contains("**bold**"));
+        assert!(markdown.contains("[linked](https://example.com)"));
+        assert!(markdown.contains("& "));
+    }
+
+    #[test]
+    fn test_html_to_text_strips_markup() {
+        let entry = ClipboardEntry::new_html("Title".to_string(), HTML.to_string());
+        let conversion = convert_entry(&entry, ClipboardFormat::Text).unwrap();
+
+        assert_eq!(conversion.source_format, ClipboardFormat::Html);
+        assert!(conversion.content.starts_with("Title\n"));
+        assert!(conversion.content.contains("Some bold & linked text."));
+        assert!(!conversion.content.contains('<'));
+        assert!(!conversion.content.contains("**"));
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let entry = ClipboardEntry::new_text("# Title\n\n- `code`\n- *em*".to_string());
+        let conversion = convert_entry(&entry, ClipboardFormat::Html).unwrap();
+
+        assert_eq!(conversion.source_format, ClipboardFormat::Markdown);
+        assert!(conversion.content.contains("<h1>Title</h1>"));
+        assert!(conversion.content.contains("<code>code</code>"));
+        assert!(conversion.content.contains("<em>em</em>"));
+    }
+
+    #[test]
+    fn test_text_entry_to_markdown_is_unchanged() {
+        let entry = ClipboardEntry::new_text("plain *text*".to_string());
+        let conversion = convert_entry(&entry, ClipboardFormat::Markdown).unwrap();
+        assert_eq!(conversion.content, "plain *text*");
+    }
+
+    #[test]
+    fn test_image_entry_is_rejected() {
+        let entry = ClipboardEntry::new_image("aGVsbG8=".to_string(), 1, 1);
+        assert!(convert_entry(&entry, ClipboardFormat::Text).is_err());
+    }
+}
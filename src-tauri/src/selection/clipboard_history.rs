@@ -10,6 +10,109 @@ use std::sync::Arc;
 /// Maximum number of clipboard entries to keep
 const MAX_CLIPBOARD_HISTORY: usize = 50;
 
+/// Default cap on the combined payload size of all entries (64 MiB)
+const DEFAULT_MAX_TOTAL_BYTES: usize = 64 * 1024 * 1024;
+
+/// Retention policy applied to clipboard history on every insert
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ClipboardRetentionPolicy {
+    /// Maximum number of entries to keep (0 = unlimited)
+    pub max_entries: usize,
+    /// Maximum combined payload size in bytes (0 = unlimited)
+    pub max_total_bytes: usize,
+    /// Maximum entry age in seconds (None = keep forever)
+    pub max_age_secs: Option<u64>,
+}
+
+impl Default for ClipboardRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: MAX_CLIPBOARD_HISTORY,
+            max_total_bytes: DEFAULT_MAX_TOTAL_BYTES,
+            max_age_secs: None,
+        }
+    }
+}
+
+/// Number of entries evicted by a retention pass, grouped by the rule that evicted them
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ClipboardEvictionReport {
+    /// Entries removed because they exceeded `max_age_secs`
+    pub by_age: usize,
+    /// Entries removed because the history exceeded `max_entries`
+    pub by_count: usize,
+    /// Entries removed because the history exceeded `max_total_bytes`
+    pub by_size: usize,
+}
+
+impl ClipboardEvictionReport {
+    /// Total number of evicted entries
+    pub fn total(&self) -> usize {
+        self.by_age + self.by_count + self.by_size
+    }
+
+    fn merge(&mut self, other: &ClipboardEvictionReport) {
+        self.by_age += other.by_age;
+        self.by_count += other.by_count;
+        self.by_size += other.by_size;
+    }
+}
+
+/// Result of a batch operation for one entry ID
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClipboardBatchItemResult {
+    pub id: String,
+    pub success: bool,
+    /// Why the operation failed for this ID
+    pub error: Option<String>,
+}
+
+/// Result of a pin, unpin or delete batch
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ClipboardBatchResult {
+    /// Number of IDs in the batch
+    pub total: usize,
+    /// Number of successful operations
+    pub success_count: usize,
+    /// Number of failed operations
+    pub failure_count: usize,
+    /// Per-ID results in request order
+    pub results: Vec<ClipboardBatchItemResult>,
+}
+
+impl ClipboardBatchResult {
+    fn from_results(results: Vec<ClipboardBatchItemResult>) -> Self {
+        let total = results.len();
+        let success_count = results.iter().filter(|item| item.success).count();
+        Self {
+            total,
+            success_count,
+            failure_count: total - success_count,
+            results,
+        }
+    }
+}
+
+/// Retention statistics for the clipboard history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardRetentionStats {
+    /// Active retention policy
+    pub policy: ClipboardRetentionPolicy,
+    /// Current number of entries
+    pub entry_count: usize,
+    /// Current number of pinned entries
+    pub pinned_count: usize,
+    /// Current combined payload size in bytes
+    pub total_bytes: usize,
+    /// Cumulative evictions since startup
+    pub total_evicted: ClipboardEvictionReport,
+    /// Evictions performed by the most recent retention pass that removed anything
+    pub last_eviction: Option<ClipboardEvictionReport>,
+    /// Timestamp of the most recent eviction
+    pub last_eviction_at: Option<i64>,
+}
+
 /// Clipboard content type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ClipboardContentType {
@@ -47,6 +150,12 @@ pub struct ClipboardEntry {
     pub label: Option<String>,
     /// Preview text (truncated for display)
     pub preview: String,
+    /// Correlation ID shared with the selection this entry was copied from
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// ID of the selection history entry this entry was copied from
+    #[serde(default)]
+    pub selection_entry_id: Option<String>,
 }
 
 impl ClipboardEntry {
@@ -70,6 +179,8 @@ impl ClipboardEntry {
             is_pinned: false,
             label: None,
             preview,
+            correlation_id: None,
+            selection_entry_id: None,
         }
     }
 
@@ -94,6 +205,8 @@ impl ClipboardEntry {
             is_pinned: false,
             label: None,
             preview,
+            correlation_id: None,
+            selection_entry_id: None,
         }
     }
 
@@ -111,6 +224,8 @@ impl ClipboardEntry {
             is_pinned: false,
             label: None,
             preview: format!("Image ({}x{})", width, height),
+            correlation_id: None,
+            selection_entry_id: None,
         }
     }
 
@@ -135,6 +250,8 @@ impl ClipboardEntry {
             is_pinned: false,
             label: None,
             preview,
+            correlation_id: None,
+            selection_entry_id: None,
         }
     }
 
@@ -157,31 +274,190 @@ impl ClipboardEntry {
     pub(crate) fn set_label(&mut self, label: String) {
         self.label = Some(label);
     }
+
+    /// Approximate payload size in bytes, used for retention accounting
+    pub fn size_bytes(&self) -> usize {
+        self.text.as_ref().map(|t| t.len()).unwrap_or(0)
+            + self.html.as_ref().map(|h| h.len()).unwrap_or(0)
+            + self.image_base64.as_ref().map(|i| i.len()).unwrap_or(0)
+            + self
+                .files
+                .as_ref()
+                .map(|f| f.iter().map(|p| p.len()).sum())
+                .unwrap_or(0)
+    }
 }
 
 /// Clipboard history manager
 pub struct ClipboardHistory {
     entries: Arc<RwLock<VecDeque<ClipboardEntry>>>,
-    max_size: usize,
+    /// Retention policy enforced on insert
+    policy: Arc<RwLock<ClipboardRetentionPolicy>>,
+    /// Cumulative eviction counters
+    total_evicted: Arc<RwLock<ClipboardEvictionReport>>,
+    /// Most recent non-empty eviction and when it happened
+    last_eviction: Arc<RwLock<Option<(ClipboardEvictionReport, i64)>>>,
+    /// Eviction not yet reported to the frontend
+    pending_eviction: Arc<RwLock<Option<ClipboardEvictionReport>>>,
     /// Last known clipboard content hash for change detection
     last_content_hash: Arc<RwLock<Option<u64>>>,
 }
 
 impl ClipboardHistory {
     pub fn new() -> Self {
+        Self::with_policy(ClipboardRetentionPolicy::default())
+    }
+
+    /// Create a history with a specific retention policy
+    pub fn with_policy(policy: ClipboardRetentionPolicy) -> Self {
         log::debug!(
-            "[ClipboardHistory] Creating new instance with max_size={}",
-            MAX_CLIPBOARD_HISTORY
+            "[ClipboardHistory] Creating new instance with max_entries={}, max_total_bytes={}, max_age_secs={:?}",
+            policy.max_entries,
+            policy.max_total_bytes,
+            policy.max_age_secs
         );
         Self {
             entries: Arc::new(RwLock::new(VecDeque::with_capacity(MAX_CLIPBOARD_HISTORY))),
-            max_size: MAX_CLIPBOARD_HISTORY,
+            policy: Arc::new(RwLock::new(policy)),
+            total_evicted: Arc::new(RwLock::new(ClipboardEvictionReport::default())),
+            last_eviction: Arc::new(RwLock::new(None)),
+            pending_eviction: Arc::new(RwLock::new(None)),
             last_content_hash: Arc::new(RwLock::new(None)),
         }
     }
 
-    /// Add a new entry to history
-    pub fn add(&self, entry: ClipboardEntry) {
+    /// Get the active retention policy
+    pub fn get_retention_policy(&self) -> ClipboardRetentionPolicy {
+        self.policy.read().clone()
+    }
+
+    /// Replace the retention policy and enforce it immediately
+    pub fn set_retention_policy(
+        &self,
+        policy: ClipboardRetentionPolicy,
+    ) -> ClipboardEvictionReport {
+        log::info!(
+            "[ClipboardHistory] Retention policy updated: max_entries={}, max_total_bytes={}, max_age_secs={:?}",
+            policy.max_entries,
+            policy.max_total_bytes,
+            policy.max_age_secs
+        );
+        *self.policy.write() = policy;
+        self.enforce_retention()
+    }
+
+    /// Enforce the retention policy against the current history
+    pub fn enforce_retention(&self) -> ClipboardEvictionReport {
+        self.enforce_retention_at(chrono::Utc::now().timestamp_millis())
+    }
+
+    fn enforce_retention_at(&self, now_ms: i64) -> ClipboardEvictionReport {
+        let mut entries = self.entries.write();
+        let report = self.apply_retention(&mut entries, now_ms);
+        drop(entries);
+        self.record_eviction(&report);
+        report
+    }
+
+    /// Evict oldest non-pinned entries until the policy is satisfied.
+    ///
+    /// Pinned entries are never evicted, even if that leaves the history over a limit.
+    fn apply_retention(
+        &self,
+        entries: &mut VecDeque<ClipboardEntry>,
+        now_ms: i64,
+    ) -> ClipboardEvictionReport {
+        let policy = self.policy.read().clone();
+        let mut report = ClipboardEvictionReport::default();
+
+        if let Some(max_age_secs) = policy.max_age_secs {
+            let cutoff = now_ms.saturating_sub((max_age_secs as i64).saturating_mul(1000));
+            let before = entries.len();
+            entries.retain(|e| e.is_pinned || e.timestamp >= cutoff);
+            report.by_age = before - entries.len();
+        }
+
+        if policy.max_entries > 0 {
+            while entries.len() > policy.max_entries {
+                if Self::evict_oldest_unpinned(entries).is_none() {
+                    log::trace!(
+                        "[ClipboardHistory] Max entries exceeded but all entries are pinned; preserving {} entries",
+                        entries.len()
+                    );
+                    break;
+                }
+                report.by_count += 1;
+            }
+        }
+
+        if policy.max_total_bytes > 0 {
+            let mut total_bytes: usize = entries.iter().map(|e| e.size_bytes()).sum();
+            while total_bytes > policy.max_total_bytes {
+                match Self::evict_oldest_unpinned(entries) {
+                    Some(removed) => {
+                        total_bytes = total_bytes.saturating_sub(removed.size_bytes());
+                        report.by_size += 1;
+                    }
+                    None => {
+                        log::trace!(
+                            "[ClipboardHistory] Max total bytes exceeded but all entries are pinned; preserving {} bytes",
+                            total_bytes
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    fn evict_oldest_unpinned(entries: &mut VecDeque<ClipboardEntry>) -> Option<ClipboardEntry> {
+        let pos = entries.iter().rposition(|e| !e.is_pinned)?;
+        entries.remove(pos)
+    }
+
+    fn record_eviction(&self, report: &ClipboardEvictionReport) {
+        if report.total() == 0 {
+            return;
+        }
+        log::debug!(
+            "[ClipboardHistory] Evicted {} entries (age={}, count={}, size={})",
+            report.total(),
+            report.by_age,
+            report.by_count,
+            report.by_size
+        );
+        self.total_evicted.write().merge(report);
+        *self.last_eviction.write() = Some((report.clone(), chrono::Utc::now().timestamp_millis()));
+        self.pending_eviction
+            .write()
+            .get_or_insert_with(ClipboardEvictionReport::default)
+            .merge(report);
+    }
+
+    /// Take evictions that have not been reported to the frontend yet
+    pub fn take_pending_eviction(&self) -> Option<ClipboardEvictionReport> {
+        self.pending_eviction.write().take()
+    }
+
+    /// Get retention statistics
+    pub fn get_retention_stats(&self) -> ClipboardRetentionStats {
+        let entries = self.entries.read();
+        let last_eviction = self.last_eviction.read().clone();
+        ClipboardRetentionStats {
+            policy: self.policy.read().clone(),
+            entry_count: entries.len(),
+            pinned_count: entries.iter().filter(|e| e.is_pinned).count(),
+            total_bytes: entries.iter().map(|e| e.size_bytes()).sum(),
+            total_evicted: self.total_evicted.read().clone(),
+            last_eviction_at: last_eviction.as_ref().map(|(_, at)| *at),
+            last_eviction: last_eviction.map(|(report, _)| report),
+        }
+    }
+
+    /// Add a new entry to history, evicting old entries according to the retention policy
+    pub fn add(&self, entry: ClipboardEntry) -> ClipboardEvictionReport {
         log::trace!(
             "[ClipboardHistory] add: type={:?}, preview='{}'",
             entry.content_type,
@@ -208,37 +484,22 @@ impl ClipboardHistory {
 
         if is_duplicate {
             log::trace!("[ClipboardHistory] Skipping duplicate entry");
-            return;
+            return ClipboardEvictionReport::default();
         }
 
         entries.push_front(entry);
-        let current_len = entries.len();
 
-        // Remove old non-pinned entries if over limit
-        let mut removed = 0;
-        while entries.len() > self.max_size {
-            // Find last non-pinned entry to remove
-            if let Some(pos) = entries.iter().rposition(|e| !e.is_pinned) {
-                entries.remove(pos);
-                removed += 1;
-            } else {
-                // Preserve pinned entries even if this exceeds max_size.
-                // Pinning is an explicit user retention signal.
-                log::trace!(
-                    "[ClipboardHistory] Max size exceeded but all entries are pinned; preserving {} entries",
-                    entries.len()
-                );
-                break;
-            }
-        }
+        // Pinning is an explicit user retention signal, so only unpinned entries are evicted.
+        let report = self.apply_retention(&mut entries, chrono::Utc::now().timestamp_millis());
+        let current_len = entries.len();
+        drop(entries);
+        self.record_eviction(&report);
 
-        if removed > 0 {
-            log::trace!("[ClipboardHistory] Removed {} old entries", removed);
-        }
         log::debug!(
             "[ClipboardHistory] Entry added, history_size={}",
-            current_len.min(self.max_size)
+            current_len
         );
+        report
     }
 
     /// Get recent entries
@@ -291,6 +552,17 @@ impl ClipboardHistory {
         entries.iter().find(|e| e.id == id).cloned()
     }
 
+    /// Record the selection an entry was copied from
+    pub fn link_selection(&self, id: &str, selection_entry_id: &str, correlation_id: &str) -> bool {
+        let mut entries = self.entries.write();
+        let Some(entry) = entries.iter_mut().find(|e| e.id == id) else {
+            return false;
+        };
+        entry.selection_entry_id = Some(selection_entry_id.to_string());
+        entry.correlation_id = Some(correlation_id.to_string());
+        true
+    }
+
     /// Pin an entry by ID
     pub fn pin_entry(&self, id: &str) -> bool {
         log::debug!("[ClipboardHistory] pin_entry: id={}", id);
@@ -333,6 +605,84 @@ impl ClipboardHistory {
         }
     }
 
+    /// Pin several entries by ID; unknown IDs are reported per item
+    pub fn pin_entries(&self, ids: &[String]) -> ClipboardBatchResult {
+        self.update_entries(ids, "pinned", ClipboardEntry::pin)
+    }
+
+    /// Unpin several entries by ID; unknown IDs are reported per item
+    pub fn unpin_entries(&self, ids: &[String]) -> ClipboardBatchResult {
+        self.update_entries(ids, "unpinned", ClipboardEntry::unpin)
+    }
+
+    /// Delete several entries by ID; unknown IDs are reported per item
+    pub fn delete_entries(&self, ids: &[String]) -> ClipboardBatchResult {
+        let mut entries = self.entries.write();
+        let results = ids
+            .iter()
+            .map(|id| match entries.iter().position(|e| &e.id == id) {
+                Some(pos) => {
+                    entries.remove(pos);
+                    Self::batch_success(id)
+                }
+                None => Self::batch_not_found(id),
+            })
+            .collect();
+
+        let result = ClipboardBatchResult::from_results(results);
+        log::info!(
+            "[ClipboardHistory] Batch deleted {}/{} entries",
+            result.success_count,
+            result.total
+        );
+        result
+    }
+
+    fn update_entries(
+        &self,
+        ids: &[String],
+        action: &str,
+        update: impl Fn(&mut ClipboardEntry),
+    ) -> ClipboardBatchResult {
+        let mut entries = self.entries.write();
+        let results = ids
+            .iter()
+            .map(|id| match entries.iter_mut().find(|e| &e.id == id) {
+                Some(entry) => {
+                    update(entry);
+                    Self::batch_success(id)
+                }
+                None => Self::batch_not_found(id),
+            })
+            .collect();
+
+        let result = ClipboardBatchResult::from_results(results);
+        log::info!(
+            "[ClipboardHistory] Batch {} {}/{} entries",
+            action,
+            result.success_count,
+            result.total
+        );
+        result
+    }
+
+    fn batch_success(id: &str) -> ClipboardBatchItemResult {
+        ClipboardBatchItemResult {
+            id: id.to_string(),
+            success: true,
+            error: None,
+        }
+    }
+
+    fn batch_not_found(id: &str) -> ClipboardBatchItemResult {
+        log::warn!("[ClipboardHistory] Entry not found in batch: {}", id);
+        ClipboardBatchItemResult {
+            id: id.to_string(),
+            success: false,
+            error: Some("Entry not found".to_string()),
+        }
+    }
+
     /// Clear all non-pinned entries
     pub fn clear_unpinned(&self) {
         let mut entries = self.entries.write();
@@ -346,6 +696,20 @@ impl ClipboardHistory {
         );
     }
 
+    /// Remove entries older than `cutoff_ms` (all entries when `None`)
+    ///
+    /// Pinned entries are kept unless `force` is set. Returns the number removed.
+    pub fn purge(&self, cutoff_ms: Option<i64>, force: bool) -> usize {
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|e| {
+            (e.is_pinned && !force) || cutoff_ms.is_some_and(|cutoff| e.timestamp >= cutoff)
+        });
+        let removed = before - entries.len();
+        log::info!("[ClipboardHistory] Purged {} entries", removed);
+        removed
+    }
+
     /// Clear all entries
     pub fn clear_all(&self) {
         let count = self.entries.read().len();
@@ -641,6 +1005,49 @@ mod tests {
         assert!(!history.delete_entry("nonexistent"));
     }
 
+    #[test]
+    fn test_batch_pin_unpin_reports_unknown_ids() {
+        let history = ClipboardHistory::new();
+        let first = ClipboardEntry::new_text("First".to_string());
+        let second = ClipboardEntry::new_text("Second".to_string());
+        let ids = vec![first.id.clone(), "missing".to_string(), second.id.clone()];
+        history.add(first);
+        history.add(second);
+
+        let result = history.pin_entries(&ids);
+        assert_eq!(result.total, 3);
+        assert_eq!(result.success_count, 2);
+        assert_eq!(result.failure_count, 1);
+        assert_eq!(result.results[1].id, "missing");
+        assert!(!result.results[1].success);
+        assert!(result.results[1].error.is_some());
+        assert_eq!(history.get_pinned().len(), 2);
+
+        let result = history.unpin_entries(&ids);
+        assert_eq!(result.success_count, 2);
+        assert!(history.get_pinned().is_empty());
+    }
+
+    #[test]
+    fn test_batch_delete() {
+        let history = ClipboardHistory::new();
+        let first = ClipboardEntry::new_text("First".to_string());
+        let second = ClipboardEntry::new_text("Second".to_string());
+        let kept = ClipboardEntry::new_text("Kept".to_string());
+        let ids = vec![first.id.clone(), second.id.clone(), first.id.clone()];
+        history.add(first);
+        history.add(second);
+        history.add(kept);
+
+        let result = history.delete_entries(&ids);
+        assert_eq!(result.success_count, 2);
+        // The repeated ID was already deleted
+        assert!(!result.results[2].success);
+        assert_eq!(history.len(), 1);
+
+        assert_eq!(history.delete_entries(&[]), ClipboardBatchResult::default());
+    }
+
     #[test]
     fn test_clear_unpinned() {
         let history = ClipboardHistory::new();
@@ -728,6 +1135,112 @@ mod tests {
         assert!(history.len() >= 50);
     }
 
+    #[test]
+    fn test_retention_max_entries_reports_evictions() {
+        let history = ClipboardHistory::with_policy(ClipboardRetentionPolicy {
+            max_entries: 3,
+            max_total_bytes: 0,
+            max_age_secs: None,
+        });
+
+        for i in 0..3 {
+            assert_eq!(
+                history
+                    .add(ClipboardEntry::new_text(format!("Entry {}", i)))
+                    .total(),
+                0
+            );
+        }
+        let report = history.add(ClipboardEntry::new_text("Entry 3".to_string()));
+
+        assert_eq!(report.by_count, 1);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get_all()[2].text, Some("Entry 1".to_string()));
+        assert_eq!(history.take_pending_eviction().map(|r| r.total()), Some(1));
+        assert!(history.take_pending_eviction().is_none());
+    }
+
+    #[test]
+    fn test_retention_max_total_bytes() {
+        let history = ClipboardHistory::with_policy(ClipboardRetentionPolicy {
+            max_entries: 0,
+            max_total_bytes: 25,
+            max_age_secs: None,
+        });
+
+        history.add(ClipboardEntry::new_image("a".repeat(20), 1, 1));
+        let report = history.add(ClipboardEntry::new_text("b".repeat(10)));
+
+        assert_eq!(report.by_size, 1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.get_retention_stats().total_bytes, 10);
+    }
+
+    #[test]
+    fn test_retention_max_age_skips_pinned() {
+        let history = ClipboardHistory::with_policy(ClipboardRetentionPolicy {
+            max_entries: 0,
+            max_total_bytes: 0,
+            max_age_secs: Some(60),
+        });
+
+        let mut old_pinned = ClipboardEntry::new_text("old pinned".to_string());
+        old_pinned.timestamp -= 120_000;
+        old_pinned.pin();
+        let mut old = ClipboardEntry::new_text("old".to_string());
+        old.timestamp -= 120_000;
+        history.add(old_pinned);
+        history.add(old);
+        history.add(ClipboardEntry::new_text("fresh".to_string()));
+
+        let stats = history.get_retention_stats();
+        assert_eq!(stats.entry_count, 2);
+        assert_eq!(stats.pinned_count, 1);
+        assert_eq!(stats.total_evicted.by_age, 1);
+        assert!(stats.last_eviction_at.is_some());
+    }
+
+    #[test]
+    fn test_set_retention_policy_enforces_immediately() {
+        let history = ClipboardHistory::new();
+        for i in 0..10 {
+            history.add(ClipboardEntry::new_text(format!("Entry {}", i)));
+        }
+
+        let report = history.set_retention_policy(ClipboardRetentionPolicy {
+            max_entries: 4,
+            ..ClipboardRetentionPolicy::default()
+        });
+
+        assert_eq!(report.by_count, 6);
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.get_retention_policy().max_entries, 4);
+    }
+
+    #[test]
+    fn test_enforce_retention_at_future_time_evicts_expired() {
+        let history = ClipboardHistory::with_policy(ClipboardRetentionPolicy {
+            max_age_secs: Some(10),
+            ..ClipboardRetentionPolicy::default()
+        });
+        history.add(ClipboardEntry::new_text("soon stale".to_string()));
+
+        let later = chrono::Utc::now().timestamp_millis() + 60_000;
+        let report = history.enforce_retention_at(later);
+
+        assert_eq!(report.by_age, 1);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_entry_size_bytes() {
+        let entry = ClipboardEntry::new_html("abc".to_string(), "<b>abc</b>".to_string());
+        assert_eq!(entry.size_bytes(), 13);
+
+        let files = ClipboardEntry::new_files(vec!["/a".to_string(), "/bc".to_string()]);
+        assert_eq!(files.size_bytes(), 5);
+    }
+
     #[test]
     fn test_entry_with_source() {
         let entry = ClipboardEntry::new_text("Test".to_string())
@@ -2,6 +2,7 @@
 //!
 //! Tracks selection history across applications for easy recall and analysis.
 
+use super::ai_actions::SelectionAiResult;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -10,9 +11,53 @@ use std::sync::Arc;
 /// Maximum number of history entries to keep
 const MAX_HISTORY_SIZE: usize = 100;
 
+/// Retention policy applied to selection history on ingestion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SelectionHistoryRetentionPolicy {
+    /// Maximum number of entries to keep (0 = unlimited)
+    pub max_entries: usize,
+    /// Maximum entry age in seconds (None = keep forever)
+    pub max_age_secs: Option<u64>,
+}
+
+impl Default for SelectionHistoryRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: MAX_HISTORY_SIZE,
+            max_age_secs: None,
+        }
+    }
+}
+
+/// Counters for entries removed by the automatic trimmer
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SelectionTrimStats {
+    /// Entries removed because the history exceeded `max_entries`
+    pub by_count: usize,
+    /// Entries removed because they exceeded `max_age_secs`
+    pub by_age: usize,
+    /// Timestamp of the most recent trim
+    pub last_trimmed_at: Option<i64>,
+}
+
+impl SelectionTrimStats {
+    /// Total number of trimmed entries
+    pub fn total(&self) -> usize {
+        self.by_count + self.by_age
+    }
+}
+
+fn new_entry_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
 /// A single selection history entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectionHistoryEntry {
+    /// Unique ID for this entry
+    #[serde(default = "new_entry_id")]
+    pub id: String,
     /// Correlation ID shared with emitted selection events
     pub event_id: Option<String>,
     /// The selected text
@@ -39,11 +84,24 @@ pub struct SelectionHistoryEntry {
     pub text_type: Option<String>,
     /// Detected language (for code)
     pub language: Option<String>,
+    /// Whether this entry is pinned (pinned entries are exempt from auto-trim)
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// Cached AI action results for this selection
+    #[serde(default)]
+    pub ai_results: Vec<SelectionAiResult>,
+    /// Correlation ID shared with the clipboard entry this selection was copied to
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// ID of the clipboard history entry created by copying this selection
+    #[serde(default)]
+    pub clipboard_entry_id: Option<String>,
 }
 
 impl SelectionHistoryEntry {
     pub fn new(text: String, x: i32, y: i32) -> Self {
         Self {
+            id: new_entry_id(),
             event_id: None,
             text,
             timestamp: chrono::Utc::now().timestamp_millis(),
@@ -57,6 +115,10 @@ impl SelectionHistoryEntry {
             tags: Vec::new(),
             text_type: None,
             language: None,
+            is_pinned: false,
+            ai_results: Vec::new(),
+            correlation_id: None,
+            clipboard_entry_id: None,
         }
     }
 
@@ -124,40 +186,133 @@ pub struct SelectionHistoryStats {
     /// Time range
     pub earliest_timestamp: Option<i64>,
     pub latest_timestamp: Option<i64>,
+    /// Number of pinned entries
+    pub pinned_count: usize,
+    /// Entries removed by the automatic trimmer since startup
+    pub trimmed: SelectionTrimStats,
 }
 
 /// Selection history manager
 pub struct SelectionHistory {
     entries: Arc<RwLock<VecDeque<SelectionHistoryEntry>>>,
-    max_size: usize,
+    /// Retention policy enforced on ingestion
+    policy: Arc<RwLock<SelectionHistoryRetentionPolicy>>,
+    /// Cumulative trim counters
+    trim_stats: Arc<RwLock<SelectionTrimStats>>,
 }
 
 impl SelectionHistory {
     pub fn new() -> Self {
+        Self::with_policy(SelectionHistoryRetentionPolicy::default())
+    }
+
+    /// Create a history with a specific retention policy
+    pub fn with_policy(policy: SelectionHistoryRetentionPolicy) -> Self {
         log::debug!(
-            "[SelectionHistory] Creating new instance with max_size={}",
-            MAX_HISTORY_SIZE
+            "[SelectionHistory] Creating new instance with max_entries={}, max_age_secs={:?}",
+            policy.max_entries,
+            policy.max_age_secs
         );
         Self {
             entries: Arc::new(RwLock::new(VecDeque::with_capacity(MAX_HISTORY_SIZE))),
-            max_size: MAX_HISTORY_SIZE,
+            policy: Arc::new(RwLock::new(policy)),
+            trim_stats: Arc::new(RwLock::new(SelectionTrimStats::default())),
         }
     }
 
     #[cfg(test)]
     pub(crate) fn with_max_size(max_size: usize) -> Self {
-        log::debug!(
-            "[SelectionHistory] Creating new instance with custom max_size={}",
-            max_size
+        Self::with_policy(SelectionHistoryRetentionPolicy {
+            max_entries: max_size,
+            max_age_secs: None,
+        })
+    }
+
+    /// Get the active retention policy
+    pub fn get_retention_policy(&self) -> SelectionHistoryRetentionPolicy {
+        self.policy.read().clone()
+    }
+
+    /// Replace the retention policy and trim immediately
+    pub fn set_retention_policy(
+        &self,
+        policy: SelectionHistoryRetentionPolicy,
+    ) -> SelectionTrimStats {
+        log::info!(
+            "[SelectionHistory] Retention policy updated: max_entries={}, max_age_secs={:?}",
+            policy.max_entries,
+            policy.max_age_secs
         );
-        Self {
-            entries: Arc::new(RwLock::new(VecDeque::with_capacity(max_size))),
-            max_size,
+        *self.policy.write() = policy;
+        self.trim()
+    }
+
+    /// Apply the retention policy now, returning what was removed by this pass
+    pub fn trim(&self) -> SelectionTrimStats {
+        self.trim_at(chrono::Utc::now().timestamp_millis())
+    }
+
+    fn trim_at(&self, now_ms: i64) -> SelectionTrimStats {
+        let mut entries = self.entries.write();
+        self.apply_retention(&mut entries, now_ms)
+    }
+
+    /// Remove the oldest unpinned entries until the policy is satisfied.
+    ///
+    /// Pinned entries are never trimmed, even if that leaves the history over a limit.
+    fn apply_retention(
+        &self,
+        entries: &mut VecDeque<SelectionHistoryEntry>,
+        now_ms: i64,
+    ) -> SelectionTrimStats {
+        let policy = self.policy.read().clone();
+        let mut pass = SelectionTrimStats::default();
+
+        if let Some(max_age_secs) = policy.max_age_secs {
+            let cutoff = now_ms.saturating_sub((max_age_secs as i64).saturating_mul(1000));
+            let before = entries.len();
+            entries.retain(|e| e.is_pinned || e.timestamp >= cutoff);
+            pass.by_age = before - entries.len();
+        }
+
+        if policy.max_entries > 0 {
+            while entries.len() > policy.max_entries {
+                match entries.iter().rposition(|e| !e.is_pinned) {
+                    Some(pos) => {
+                        entries.remove(pos);
+                        pass.by_count += 1;
+                    }
+                    None => break,
+                }
+            }
         }
+
+        if pass.total() > 0 {
+            pass.last_trimmed_at = Some(now_ms);
+            let mut stats = self.trim_stats.write();
+            stats.by_count += pass.by_count;
+            stats.by_age += pass.by_age;
+            stats.last_trimmed_at = pass.last_trimmed_at;
+            log::trace!(
+                "[SelectionHistory] Trimmed {} entries (count={}, age={}), current size={}",
+                pass.total(),
+                pass.by_count,
+                pass.by_age,
+                entries.len()
+            );
+        }
+
+        pass
     }
 
-    /// Add a new entry to history
-    pub fn add(&self, entry: SelectionHistoryEntry) {
+    /// Get cumulative trim statistics
+    #[cfg(test)]
+    pub(crate) fn get_trim_stats(&self) -> SelectionTrimStats {
+        self.trim_stats.read().clone()
+    }
+
+    /// Add a new entry to history; returns `false` when it was skipped as a duplicate
+    pub fn add(&self, entry: SelectionHistoryEntry) -> bool {
         let text_len = entry.text.len();
         log::trace!(
             "[SelectionHistory] add: {} chars, app={:?}",
@@ -171,32 +326,19 @@ impl SelectionHistory {
         if let Some(last) = entries.front() {
             if last.text == entry.text && (entry.timestamp - last.timestamp).abs() < 5000 {
                 log::trace!("[SelectionHistory] Skipping duplicate entry (same text within 5s)");
-                return; // Skip duplicate
+                return false; // Skip duplicate
             }
         }
 
         entries.push_front(entry);
-        let new_len = entries.len();
-
-        // Trim to max size
-        let mut trimmed = 0;
-        while entries.len() > self.max_size {
-            entries.pop_back();
-            trimmed += 1;
-        }
+        self.apply_retention(&mut entries, chrono::Utc::now().timestamp_millis());
 
-        if trimmed > 0 {
-            log::trace!(
-                "[SelectionHistory] Trimmed {} old entries, current size={}",
-                trimmed,
-                entries.len()
-            );
-        }
         log::debug!(
             "[SelectionHistory] Entry added: {} chars, history_size={}",
             text_len,
-            new_len.min(self.max_size)
+            entries.len()
         );
+        true
     }
 
     /// Get recent entries
@@ -293,6 +435,114 @@ impl SelectionHistory {
         results
     }
 
+    /// Get entry by ID
+    pub fn get_by_id(&self, id: &str) -> Option<SelectionHistoryEntry> {
+        self.entries.read().iter().find(|e| e.id == id).cloned()
+    }
+
+    /// Pin or unpin an entry by ID
+    pub fn set_pinned(&self, id: &str, pinned: bool) -> bool {
+        log::debug!(
+            "[SelectionHistory] set_pinned: id={}, pinned={}",
+            id,
+            pinned
+        );
+        let mut entries = self.entries.write();
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.is_pinned = pinned;
+            true
+        } else {
+            log::warn!("[SelectionHistory] Entry not found: {}", id);
+            false
+        }
+    }
+
+    /// Link the most recent unlinked selection matching copied text to a clipboard entry
+    ///
+    /// Only selections made at most `window_ms` before `copied_at` are considered.
+    /// Returns the linked selection's ID and the shared correlation ID.
+    pub fn link_clipboard_entry(
+        &self,
+        copied_text: &str,
+        clipboard_entry_id: &str,
+        copied_at: i64,
+        window_ms: i64,
+    ) -> Option<(String, String)> {
+        let copied_text = copied_text.trim();
+        if copied_text.is_empty() {
+            return None;
+        }
+
+        let mut entries = self.entries.write();
+        let entry = entries.iter_mut().find(|e| {
+            let age = copied_at - e.timestamp;
+            (0..=window_ms).contains(&age)
+                && e.clipboard_entry_id.is_none()
+                && e.text.trim() == copied_text
+        })?;
+
+        let correlation_id = entry
+            .event_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        entry.correlation_id = Some(correlation_id.clone());
+        entry.clipboard_entry_id = Some(clipboard_entry_id.to_string());
+        log::debug!(
+            "[SelectionHistory] Linked selection {} to clipboard entry {}",
+            entry.id,
+            clipboard_entry_id
+        );
+        Some((entry.id.clone(), correlation_id))
+    }
+
+    /// Get a cached AI result for an entry
+    pub fn get_cached_ai_result(
+        &self,
+        id: &str,
+        action_id: &str,
+        target_language: Option<&str>,
+        context_collection: Option<&str>,
+    ) -> Option<SelectionAiResult> {
+        let entries = self.entries.read();
+        let entry = entries.iter().find(|e| e.id == id)?;
+        entry
+            .ai_results
+            .iter()
+            .find(|r| {
+                r.action_id == action_id
+                    && r.target_language.as_deref() == target_language
+                    && r.context_collection.as_deref() == context_collection
+            })
+            .cloned()
+    }
+
+    /// Store an AI result on an entry, replacing any previous result for the same
+    /// action, target language and context collection
+    pub fn store_ai_result(&self, id: &str, result: SelectionAiResult) -> bool {
+        let mut entries = self.entries.write();
+        let Some(entry) = entries.iter_mut().find(|e| e.id == id) else {
+            log::warn!("[SelectionHistory] Entry not found for AI result: {}", id);
+            return false;
+        };
+        entry.ai_results.retain(|r| {
+            r.action_id != result.action_id
+                || r.target_language != result.target_language
+                || r.context_collection != result.context_collection
+        });
+        entry.ai_results.push(result);
+        true
+    }
+
+    /// Get pinned entries
+    pub fn get_pinned(&self) -> Vec<SelectionHistoryEntry> {
+        self.entries
+            .read()
+            .iter()
+            .filter(|e| e.is_pinned)
+            .cloned()
+            .collect()
+    }
+
     /// Get entry by index
     #[cfg(test)]
     pub(crate) fn get(&self, index: usize) -> Option<SelectionHistoryEntry> {
@@ -305,6 +555,20 @@ impl SelectionHistory {
         self.entries.read().front().cloned()
     }
 
+    /// Remove entries older than `cutoff_ms` (all entries when `None`)
+    ///
+    /// Pinned entries are kept unless `force` is set. Returns the number removed.
+    pub fn purge(&self, cutoff_ms: Option<i64>, force: bool) -> usize {
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|e| {
+            (e.is_pinned && !force) || cutoff_ms.is_some_and(|cutoff| e.timestamp >= cutoff)
+        });
+        let removed = before - entries.len();
+        log::info!("[SelectionHistory] Purged {} entries", removed);
+        removed
+    }
+
     /// Clear all history
     pub fn clear(&self) {
         let prev_len = self.entries.read().len();
@@ -384,6 +648,8 @@ impl SelectionHistory {
             common_words,
             earliest_timestamp: earliest,
             latest_timestamp: latest,
+            pinned_count: entries.iter().filter(|e| e.is_pinned).count(),
+            trimmed: self.trim_stats.read().clone(),
         }
     }
 
@@ -418,17 +684,12 @@ impl SelectionHistory {
             entries.push_back(entry);
         }
 
-        // Trim to max size
-        let mut trimmed = 0;
-        while entries.len() > self.max_size {
-            entries.pop_back();
-            trimmed += 1;
-        }
+        let trimmed = self.apply_retention(&mut entries, chrono::Utc::now().timestamp_millis());
 
         log::info!(
             "[SelectionHistory] Imported {} entries (trimmed {})",
             count,
-            trimmed
+            trimmed.total()
         );
         Ok(count)
     }
@@ -483,6 +744,128 @@ mod tests {
         assert_eq!(history.len(), 5);
     }
 
+    #[test]
+    fn test_max_size_keeps_pinned_entries() {
+        let history = SelectionHistory::with_max_size(3);
+        let mut pinned = SelectionHistoryEntry::new("pinned".to_string(), 0, 0);
+        pinned.is_pinned = true;
+        let pinned_id = pinned.id.clone();
+        history.add(pinned);
+
+        for i in 0..5 {
+            let mut entry = SelectionHistoryEntry::new(format!("text {}", i), 0, 0);
+            entry.timestamp += (i as i64 + 1) * 10000;
+            history.add(entry);
+        }
+
+        assert_eq!(history.len(), 3);
+        assert!(history.get_by_id(&pinned_id).is_some());
+        let stats = history.get_stats();
+        assert_eq!(stats.pinned_count, 1);
+        assert_eq!(stats.trimmed.by_count, 3);
+    }
+
+    #[test]
+    fn test_max_age_trim() {
+        let history = SelectionHistory::with_policy(SelectionHistoryRetentionPolicy {
+            max_entries: 0,
+            max_age_secs: Some(60),
+        });
+        let mut old = SelectionHistoryEntry::new("old".to_string(), 0, 0);
+        old.timestamp -= 120_000;
+        history.add(old);
+        history.add(SelectionHistoryEntry::new("fresh".to_string(), 0, 0));
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.get_trim_stats().by_age, 1);
+
+        let later = chrono::Utc::now().timestamp_millis() + 120_000;
+        let pass = history.trim_at(later);
+        assert_eq!(pass.by_age, 1);
+        assert!(history.is_empty());
+        assert_eq!(history.get_trim_stats().total(), 2);
+    }
+
+    #[test]
+    fn test_set_pinned_and_policy_update() {
+        let history = SelectionHistory::new();
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let mut entry = SelectionHistoryEntry::new(format!("text {}", i), 0, 0);
+            entry.timestamp += i as i64 * 10000;
+            ids.push(entry.id.clone());
+            history.add(entry);
+        }
+
+        assert!(history.set_pinned(&ids[0], true));
+        assert!(!history.set_pinned("missing", true));
+
+        let pass = history.set_retention_policy(SelectionHistoryRetentionPolicy {
+            max_entries: 2,
+            max_age_secs: None,
+        });
+        assert_eq!(pass.by_count, 3);
+        assert_eq!(history.get_pinned().len(), 1);
+        assert!(history.get_by_id(&ids[0]).is_some());
+        assert!(history.get_by_id(&ids[4]).is_some());
+    }
+
+    #[test]
+    fn test_import_entry_without_id_or_pin() {
+        let json = r#"[{"event_id":null,"text":"legacy","timestamp":1,"app_name":null,
+            "window_title":null,"process_name":null,"position":[0,0],"context_before":null,
+            "context_after":null,"is_manual":false,"tags":[],"text_type":null,"language":null}]"#;
+        let history = SelectionHistory::with_policy(SelectionHistoryRetentionPolicy {
+            max_entries: 10,
+            max_age_secs: None,
+        });
+
+        assert_eq!(history.import_json(json).unwrap(), 1);
+        let entry = history.get_latest().unwrap();
+        assert!(!entry.id.is_empty());
+        assert!(!entry.is_pinned);
+    }
+
+    #[test]
+    fn test_store_and_get_cached_ai_result() {
+        let history = SelectionHistory::new();
+        let entry = SelectionHistoryEntry::new("bonjour".to_string(), 0, 0);
+        let id = entry.id.clone();
+        history.add(entry);
+
+        let make_result = |lang: &str, text: &str| SelectionAiResult {
+            action_id: "translate".to_string(),
+            target_language: Some(lang.to_string()),
+            result: text.to_string(),
+            timestamp: 0,
+            cached: false,
+            context_collection: None,
+        };
+
+        assert!(history.store_ai_result(&id, make_result("en", "hello")));
+        assert!(history.store_ai_result(&id, make_result("de", "hallo")));
+        assert!(history.store_ai_result(&id, make_result("en", "hi")));
+        assert!(!history.store_ai_result("missing", make_result("en", "hi")));
+        let grounded = SelectionAiResult {
+            context_collection: Some("notes".to_string()),
+            ..make_result("en", "hi, per your notes")
+        };
+        assert!(history.store_ai_result(&id, grounded));
+
+        let cached = history
+            .get_cached_ai_result(&id, "translate", Some("en"), None)
+            .unwrap();
+        assert_eq!(cached.result, "hi");
+        let cached = history
+            .get_cached_ai_result(&id, "translate", Some("en"), Some("notes"))
+            .unwrap();
+        assert_eq!(cached.result, "hi, per your notes");
+        assert!(history
+            .get_cached_ai_result(&id, "translate", Some("fr"), None)
+            .is_none());
+        assert_eq!(history.get_by_id(&id).unwrap().ai_results.len(), 3);
+    }
+
     #[test]
     fn test_duplicate_detection() {
         let history = SelectionHistory::new();
@@ -686,4 +1069,57 @@ mod tests {
         assert!(entry.tags.contains(&"tag1".to_string()));
         assert!(entry.tags.contains(&"tag2".to_string()));
     }
+
+    #[test]
+    fn test_purge_respects_cutoff_and_pins() {
+        let history = SelectionHistory::new();
+        for (text, timestamp) in [("old", 1_000), ("old pinned", 2_000), ("new", 50_000)] {
+            let mut entry = SelectionHistoryEntry::new(text.to_string(), 0, 0);
+            entry.timestamp = timestamp;
+            entry.is_pinned = text == "old pinned";
+            history.add(entry);
+        }
+
+        assert_eq!(history.purge(Some(10_000), false), 1);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.purge(None, false), 1);
+        assert_eq!(history.get_latest().unwrap().text, "old pinned");
+        assert_eq!(history.purge(None, true), 1);
+        assert_eq!(history.len(), 0);
+    }
+
+    #[test]
+    fn test_link_clipboard_entry_within_window() {
+        let history = SelectionHistory::new();
+        let mut older = SelectionHistoryEntry::new("copied text".to_string(), 0, 0);
+        older.timestamp = 1_000;
+        history.add(older);
+        let mut entry = SelectionHistoryEntry::new("copied text".to_string(), 0, 0)
+            .with_event_id(Some("event-1".to_string()));
+        entry.timestamp = 10_000;
+        history.add(entry);
+
+        // Stale or mismatched copies are not linked
+        assert!(history
+            .link_clipboard_entry("copied text", "clip-0", 30_000, 5_000)
+            .is_none());
+        assert!(history
+            .link_clipboard_entry("other text", "clip-0", 11_000, 5_000)
+            .is_none());
+
+        let (selection_id, correlation_id) = history
+            .link_clipboard_entry("  copied text\n", "clip-1", 11_000, 5_000)
+            .unwrap();
+        assert_eq!(correlation_id, "event-1");
+
+        let linked = history.get_by_id(&selection_id).unwrap();
+        assert_eq!(linked.timestamp, 10_000);
+        assert_eq!(linked.clipboard_entry_id.as_deref(), Some("clip-1"));
+        assert_eq!(linked.correlation_id.as_deref(), Some("event-1"));
+
+        // An already linked selection is not linked again
+        assert!(history
+            .link_clipboard_entry("copied text", "clip-2", 11_000, 5_000)
+            .is_none());
+    }
 }
@@ -0,0 +1,340 @@
+//! Selection macros
+//!
+//! Records the actions performed on selections (AI actions, copy, replace) into
+//! a named macro that is persisted to disk and can be replayed against new text.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// One recorded step of a selection macro
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SelectionMacroStep {
+    /// Run an AI action; its output becomes the input of the next step
+    Action {
+        action_id: String,
+        target_language: Option<String>,
+    },
+    /// Copy the current text to the clipboard
+    Copy,
+    /// Replace the selection in the source application with the current text
+    Replace,
+}
+
+/// A recorded, replayable sequence of selection steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionMacro {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<SelectionMacroStep>,
+    pub created_at: i64,
+    /// Timestamp of the last replay
+    pub last_run_at: Option<i64>,
+    #[serde(default)]
+    pub run_count: u32,
+}
+
+/// Output of one replayed step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionMacroStepResult {
+    pub step: SelectionMacroStep,
+    /// Text after the step ran
+    pub output: String,
+}
+
+/// Result of replaying a macro
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionMacroRunResult {
+    pub macro_id: String,
+    pub input: String,
+    /// Text after the last step
+    pub output: String,
+    pub steps: Vec<SelectionMacroStepResult>,
+}
+
+/// Persisted macros plus the recording in progress
+pub struct SelectionMacroStore {
+    path: PathBuf,
+    macros: RwLock<Vec<SelectionMacro>>,
+    recording: RwLock<Option<Vec<SelectionMacroStep>>>,
+}
+
+impl SelectionMacroStore {
+    /// Load macros from `path`; a missing or unreadable file yields an empty store
+    ///
+    /// A file that cannot be parsed is moved aside to `<path>.corrupt` so the next
+    /// save does not overwrite it.
+    pub fn load(path: PathBuf) -> Self {
+        let macros = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                let mut corrupt = path.clone().into_os_string();
+                corrupt.push(".corrupt");
+                let corrupt = PathBuf::from(corrupt);
+                log::warn!(
+                    "[SelectionMacros] Failed to parse {:?}, moving it to {:?}: {}",
+                    path,
+                    corrupt,
+                    e
+                );
+                if let Err(e) = std::fs::rename(&path, &corrupt) {
+                    log::warn!("[SelectionMacros] Failed to move {:?} aside: {}", path, e);
+                }
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        log::debug!("[SelectionMacros] Loaded {} macro(s)", macros.len());
+
+        Self {
+            path,
+            macros: RwLock::new(macros),
+            recording: RwLock::new(None),
+        }
+    }
+
+    /// Start recording; fails if a recording is already in progress
+    pub fn start_recording(&self) -> Result<(), String> {
+        let mut recording = self.recording.write();
+        if recording.is_some() {
+            return Err("Invalid state: a macro is already being recorded".to_string());
+        }
+        *recording = Some(Vec::new());
+        log::info!("[SelectionMacros] Recording started");
+        Ok(())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.read().is_some()
+    }
+
+    /// Append a step to the recording in progress; ignored when not recording
+    pub fn record_step(&self, step: SelectionMacroStep) {
+        if let Some(steps) = self.recording.write().as_mut() {
+            log::debug!("[SelectionMacros] Recorded step {:?}", step);
+            steps.push(step);
+        }
+    }
+
+    /// Stop recording and save the recorded steps as a new macro
+    ///
+    /// When saving fails the recording stays in progress, so stopping can be
+    /// retried without losing the steps.
+    pub fn stop_recording(&self, name: Option<String>) -> Result<SelectionMacro, String> {
+        let mut recording = self.recording.write();
+        let steps = recording
+            .clone()
+            .ok_or_else(|| "Invalid state: no macro is being recorded".to_string())?;
+        if steps.is_empty() {
+            *recording = None;
+            return Err("Invalid macro: no actions were recorded".to_string());
+        }
+
+        let created_at = chrono::Utc::now().timestamp_millis();
+        let name = name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| Self::default_name(&steps));
+        let selection_macro = SelectionMacro {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            steps,
+            created_at,
+            last_run_at: None,
+            run_count: 0,
+        };
+
+        {
+            // Only list the macro once it is on disk
+            let mut macros = self.macros.write();
+            let mut updated = macros.clone();
+            updated.push(selection_macro.clone());
+            self.write_macros(&updated)?;
+            *macros = updated;
+        }
+        *recording = None;
+        log::info!(
+            "[SelectionMacros] Saved macro '{}' with {} step(s)",
+            selection_macro.name,
+            selection_macro.steps.len()
+        );
+        Ok(selection_macro)
+    }
+
+    /// Discard the recording in progress
+    pub fn cancel_recording(&self) {
+        self.recording.write().take();
+    }
+
+    pub fn list(&self) -> Vec<SelectionMacro> {
+        self.macros.read().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<SelectionMacro> {
+        self.macros.read().iter().find(|m| m.id == id).cloned()
+    }
+
+    /// Delete a macro, returning whether it existed
+    pub fn delete(&self, id: &str) -> Result<bool, String> {
+        let mut macros = self.macros.write();
+        let mut updated = macros.clone();
+        updated.retain(|m| m.id != id);
+        if updated.len() == macros.len() {
+            return Ok(false);
+        }
+        self.write_macros(&updated)?;
+        *macros = updated;
+        Ok(true)
+    }
+
+    /// Update replay statistics after a successful run
+    pub fn mark_run(&self, id: &str) {
+        let mut macros = self.macros.write();
+        let mut updated = macros.clone();
+        let Some(selection_macro) = updated.iter_mut().find(|m| m.id == id) else {
+            return;
+        };
+        selection_macro.last_run_at = Some(chrono::Utc::now().timestamp_millis());
+        selection_macro.run_count += 1;
+        match self.write_macros(&updated) {
+            Ok(()) => *macros = updated,
+            Err(e) => log::warn!("[SelectionMacros] Failed to save run statistics: {}", e),
+        }
+    }
+
+    fn default_name(steps: &[SelectionMacroStep]) -> String {
+        steps
+            .iter()
+            .map(|step| match step {
+                SelectionMacroStep::Action { action_id, .. } => action_id.as_str(),
+                SelectionMacroStep::Copy => "copy",
+                SelectionMacroStep::Replace => "replace",
+            })
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
+    fn write_macros(&self, macros: &[SelectionMacro]) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(macros)
+            .map_err(|e| format!("Failed to serialize macros: {}", e))?;
+
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        std::fs::write(&self.path, content)
+            .map_err(|e| format!("Failed to write macros file: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate() -> SelectionMacroStep {
+        SelectionMacroStep::Action {
+            action_id: "translate".to_string(),
+            target_language: Some("en".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_record_and_persist_macro() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("selection_macros.json");
+        let store = SelectionMacroStore::load(path.clone());
+
+        // Steps outside a recording are ignored
+        store.record_step(SelectionMacroStep::Copy);
+        store.start_recording().unwrap();
+        assert!(store.start_recording().is_err());
+        store.record_step(translate());
+        store.record_step(SelectionMacroStep::Replace);
+
+        let saved = store.stop_recording(None).unwrap();
+        assert_eq!(saved.name, "translate → replace");
+        assert_eq!(saved.steps, vec![translate(), SelectionMacroStep::Replace]);
+        assert!(!store.is_recording());
+
+        let reloaded = SelectionMacroStore::load(path);
+        let macros = reloaded.list();
+        assert_eq!(macros.len(), 1);
+        assert_eq!(macros[0].id, saved.id);
+        assert_eq!(macros[0].steps, saved.steps);
+    }
+
+    #[test]
+    fn test_stop_without_steps_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SelectionMacroStore::load(dir.path().join("macros.json"));
+
+        assert!(store.stop_recording(None).is_err());
+        store.start_recording().unwrap();
+        assert!(store.stop_recording(Some("empty".to_string())).is_err());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_failed_save_does_not_list_macro() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file where the parent directory should be makes every write fail
+        let blocker = dir.path().join("blocker");
+        std::fs::write(&blocker, "").unwrap();
+        let store = SelectionMacroStore::load(blocker.join("macros.json"));
+
+        store.start_recording().unwrap();
+        store.record_step(SelectionMacroStep::Copy);
+        assert!(store.stop_recording(None).is_err());
+        assert!(store.list().is_empty());
+        // The recording is kept so stopping can be retried
+        assert!(store.is_recording());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("macros.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let store = SelectionMacroStore::load(path.clone());
+        assert!(store.list().is_empty());
+        let backup = dir.path().join("macros.json.corrupt");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "not json");
+
+        store.start_recording().unwrap();
+        store.record_step(SelectionMacroStep::Copy);
+        store.stop_recording(None).unwrap();
+        assert_eq!(SelectionMacroStore::load(path).list().len(), 1);
+    }
+
+    #[test]
+    fn test_delete_and_mark_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SelectionMacroStore::load(dir.path().join("macros.json"));
+        store.start_recording().unwrap();
+        store.record_step(SelectionMacroStep::Copy);
+        let saved = store
+            .stop_recording(Some("  Copy it ".to_string()))
+            .unwrap();
+        assert_eq!(saved.name, "Copy it");
+
+        store.mark_run(&saved.id);
+        let updated = store.get(&saved.id).unwrap();
+        assert_eq!(updated.run_count, 1);
+        assert!(updated.last_run_at.is_some());
+
+        assert!(store.delete(&saved.id).unwrap());
+        assert!(!store.delete(&saved.id).unwrap());
+        assert!(store.get(&saved.id).is_none());
+    }
+
+    #[test]
+    fn test_step_serialization() {
+        let json = serde_json::to_string(&translate()).unwrap();
+        assert!(json.contains("\"type\":\"action\""));
+        assert!(json.contains("\"action_id\":\"translate\""));
+
+        let step: SelectionMacroStep = serde_json::from_str("{\"type\":\"replace\"}").unwrap();
+        assert_eq!(step, SelectionMacroStep::Replace);
+    }
+}
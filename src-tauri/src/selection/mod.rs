@@ -9,16 +9,22 @@
 //! by `SelectionManager` and composed into higher-level abstractions, while others
 //! are exposed for direct use by consumers of this API.
 
+mod ai_actions;
 mod analyzer;
 mod clipboard_context;
+mod clipboard_convert;
 mod clipboard_history;
 mod detector;
 mod expander;
 mod extractor;
 mod history;
+mod macros;
 mod mouse_hook;
+mod semantic_history;
 mod smart_selection;
+mod toolbar_theme;
 mod toolbar_window;
+mod translation_cache;
 mod types;
 
 // Re-export internal components for potential external use
@@ -39,26 +45,52 @@ pub use clipboard_context::{
 pub use clipboard_context::FormattingHints;
 
 // Clipboard history types - actively used in Tauri commands
-pub use clipboard_history::{ClipboardEntry, ClipboardHistory};
+pub use clipboard_history::{
+    ClipboardBatchResult, ClipboardEntry, ClipboardEvictionReport, ClipboardHistory,
+    ClipboardRetentionPolicy, ClipboardRetentionStats,
+};
+// Clipboard content conversion
+pub use clipboard_convert::{convert_entry, html_to_text, ClipboardConversion, ClipboardFormat};
 // ClipboardContentType is part of ClipboardEntry but not directly referenced in commands
 #[allow(unused_imports)]
 pub use clipboard_history::ClipboardContentType;
 
+// AI actions re-run on history entries
+pub use ai_actions::{
+    action_uses_target_language, build_action_prompt, context_snippet_text, with_context_snippets,
+    selection_system_prompt, SelectionAiResult, SelectionContextConfig,
+};
+
 // Core types - actively used
 pub use detector::SelectionDetector;
-pub use history::{SelectionHistory, SelectionHistoryEntry, SelectionHistoryStats};
+pub use history::{
+    SelectionHistory, SelectionHistoryEntry, SelectionHistoryRetentionPolicy,
+    SelectionHistoryStats, SelectionTrimStats,
+};
+pub use macros::{
+    SelectionMacro, SelectionMacroRunResult, SelectionMacroStep, SelectionMacroStepResult,
+    SelectionMacroStore,
+};
 pub use mouse_hook::{MouseEvent, MouseHook};
+pub use semantic_history::{
+    source_filter, SemanticHistoryConfig, SemanticHistoryHit, SemanticHistoryIndexer,
+    SemanticHistorySource, SemanticHistoryStores,
+};
 pub use smart_selection::{SelectionContext, SelectionExpansion, SelectionMode, SmartSelection};
+pub use toolbar_theme::{SelectionToolbarTheme, ToolbarThemeName};
 pub use toolbar_window::ToolbarWindow;
-pub use types::{Selection, SourceAppInfo};
+pub use translation_cache::{model_key, TranslationCache};
+pub use types::{SelectedText, Selection, SelectionRegion, SelectionTextSource, SourceAppInfo};
 // TextType is used in Selection struct but not directly referenced in commands
 #[allow(unused_imports)]
 pub use types::TextType;
 
+use crate::accessibility::{self, DegradedStatus};
 use crate::context::WindowManager;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
 use tokio::sync::mpsc;
@@ -69,6 +101,12 @@ const ERROR_KIND_EXTRACT_FAILED: &str = "extract_failed";
 const ERROR_KIND_TOOLBAR_SHOW_FAILED: &str = "toolbar_show_failed";
 const ERROR_KIND_CONFIG_SYNC_FAILED: &str = "config_sync_failed";
 
+/// Maximum time between a selection and the clipboard copy linked to it
+const SELECTION_COPY_CORRELATION_WINDOW_MS: i64 = 15_000;
+
+/// Upper bound for `SelectionConfig::debounce_ms`
+const MAX_SELECTION_DEBOUNCE_MS: u64 = 1_000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TriggerMode {
     Auto,
@@ -132,16 +170,38 @@ pub struct SelectionConfig {
     pub enabled: bool,
     /// Trigger mode: "auto", "shortcut", or "both"
     pub trigger_mode: String,
-    /// Minimum text length to trigger toolbar
+    /// Minimum text length to trigger toolbar; shorter selections are ignored
     pub min_text_length: usize,
     /// Maximum text length to process
     pub max_text_length: usize,
     /// Delay in milliseconds before showing toolbar
     pub delay_ms: u64,
+    /// Quiet period in milliseconds before a burst of mouse events is handled;
+    /// only the last event of the burst triggers detection (0 disables, max 1000)
+    #[serde(default)]
+    pub debounce_ms: u64,
     /// Default target language for translation
     pub target_language: String,
     /// List of excluded application names
     pub excluded_apps: Vec<String>,
+    /// Retention policy for clipboard history
+    #[serde(default)]
+    pub clipboard_retention: ClipboardRetentionPolicy,
+    /// Retention policy for selection history
+    #[serde(default)]
+    pub history_retention: SelectionHistoryRetentionPolicy,
+    /// OCR the selected screen region when the app exposes no selectable text
+    #[serde(default)]
+    pub ocr_fallback: bool,
+    /// Vector collection retrieval for context-aware AI actions
+    #[serde(default)]
+    pub ai_context: SelectionContextConfig,
+    /// Toolbar theme and per-action colors and icons
+    #[serde(default)]
+    pub toolbar_theme: SelectionToolbarTheme,
+    /// Embedding of selection and clipboard entries for semantic search
+    #[serde(default)]
+    pub semantic_history: SemanticHistoryConfig,
 }
 
 impl Default for SelectionConfig {
@@ -152,8 +212,15 @@ impl Default for SelectionConfig {
             min_text_length: 1,
             max_text_length: 5000,
             delay_ms: 200,
+            debounce_ms: 0,
             target_language: "zh-CN".to_string(),
             excluded_apps: vec![],
+            clipboard_retention: ClipboardRetentionPolicy::default(),
+            history_retention: SelectionHistoryRetentionPolicy::default(),
+            ocr_fallback: false,
+            ai_context: SelectionContextConfig::default(),
+            toolbar_theme: SelectionToolbarTheme::default(),
+            semantic_history: SemanticHistoryConfig::default(),
         }
     }
 }
@@ -173,6 +240,46 @@ pub struct SelectionStatus {
     pub last_selection_timestamp: Option<i64>,
     /// Current configuration
     pub config: SelectionConfig,
+    /// Whether selection detection runs without the accessibility permission it needs
+    #[serde(default)]
+    pub degraded: bool,
+    /// Why selection detection is degraded
+    #[serde(default)]
+    pub degraded_reason: Option<String>,
+}
+
+/// Counters for auto-detection events filtered before reaching the toolbar
+#[derive(Debug, Default)]
+pub struct DetectionFilterCounters {
+    /// Mouse events superseded by a later event within the debounce window
+    debounced: AtomicU64,
+    /// Selections shorter than `min_text_length`
+    suppressed_short: AtomicU64,
+    /// Selections longer than `max_text_length`
+    suppressed_long: AtomicU64,
+}
+
+impl DetectionFilterCounters {
+    fn record_debounced(&self, count: u64) {
+        self.debounced.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn record_length_rejection(&self, text: &str, cfg: &SelectionConfig) {
+        if text.chars().count() < cfg.min_text_length {
+            self.suppressed_short.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.suppressed_long.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Snapshot as (debounced, suppressed_short, suppressed_long)
+    pub fn snapshot(&self) -> (u64, u64, u64) {
+        (
+            self.debounced.load(Ordering::Relaxed),
+            self.suppressed_short.load(Ordering::Relaxed),
+            self.suppressed_long.load(Ordering::Relaxed),
+        )
+    }
 }
 
 /// Selection manager state
@@ -194,6 +301,16 @@ pub struct SelectionManager {
     pub smart_selection: Arc<SmartSelection>,
     /// Clipboard context analyzer
     pub clipboard_analyzer: Arc<ClipboardContextAnalyzer>,
+    /// Recorded selection macros
+    pub macros: Arc<SelectionMacroStore>,
+    /// Background embedding of history entries
+    pub semantic_history: Arc<SemanticHistoryIndexer>,
+    /// Cached translations
+    pub translation_cache: Arc<TranslationCache>,
+    /// Permission state detected when the service last started
+    degraded: Arc<RwLock<DegradedStatus>>,
+    /// Events dropped by debouncing and length limits
+    pub filter_counters: Arc<DetectionFilterCounters>,
     app_handle: tauri::AppHandle,
     /// Config file path for persistence
     config_path: PathBuf,
@@ -209,6 +326,16 @@ impl SelectionManager {
             .app_data_dir()
             .map(|p| p.join("selection_config.json"))
             .unwrap_or_else(|_| PathBuf::from("selection_config.json"));
+        let macros_path = app_handle
+            .path()
+            .app_data_dir()
+            .map(|p| p.join("selection_macros.json"))
+            .unwrap_or_else(|_| PathBuf::from("selection_macros.json"));
+        let translation_cache_path = app_handle
+            .path()
+            .app_data_dir()
+            .map(|p| p.join("selection_translation_cache.json"))
+            .unwrap_or_else(|_| PathBuf::from("selection_translation_cache.json"));
 
         // Try to load existing config
         let config = Self::load_config_from_file(&config_path).unwrap_or_default();
@@ -222,10 +349,25 @@ impl SelectionManager {
         let detector = Arc::new(SelectionDetector::new());
         let mouse_hook = Arc::new(MouseHook::new());
         let toolbar_window = Arc::new(ToolbarWindow::new(app_handle.clone()));
-        let history = Arc::new(SelectionHistory::new());
-        let clipboard_history = Arc::new(ClipboardHistory::new());
+        let history = Arc::new(SelectionHistory::with_policy(
+            config.read().history_retention.clone(),
+        ));
+        let clipboard_history = Arc::new(ClipboardHistory::with_policy(
+            config.read().clipboard_retention.clone(),
+        ));
         let smart_selection = Arc::new(SmartSelection::new());
         let clipboard_analyzer = Arc::new(ClipboardContextAnalyzer::new());
+        let macros = Arc::new(SelectionMacroStore::load(macros_path));
+        let translation_cache = Arc::new(TranslationCache::load(translation_cache_path));
+        let semantic_history = Arc::new(SemanticHistoryIndexer::new(
+            app_handle.clone(),
+            config.clone(),
+            clipboard_analyzer.clone(),
+            SemanticHistoryStores {
+                selection: history.clone(),
+                clipboard: clipboard_history.clone(),
+            },
+        ));
 
         log::debug!("[SelectionManager] All components initialized");
         Self {
@@ -240,6 +382,11 @@ impl SelectionManager {
             clipboard_history,
             smart_selection,
             clipboard_analyzer,
+            macros,
+            semantic_history,
+            translation_cache,
+            degraded: Arc::new(RwLock::new(DegradedStatus::default())),
+            filter_counters: Arc::new(DetectionFilterCounters::default()),
             app_handle,
             config_path,
         }
@@ -352,6 +499,8 @@ impl SelectionManager {
             }
         }
 
+        *self.degraded.write() = accessibility::check_and_notify(&self.app_handle, "selection");
+
         // Create cancellation token for this session
         log::trace!("[SelectionManager] Creating cancellation token");
         let cancel_token = CancellationToken::new();
@@ -370,11 +519,14 @@ impl SelectionManager {
         // Clone necessary references for the event loop
         let config = self.config.clone();
         let detector = self.detector.clone();
+        let mouse_hook = self.mouse_hook.clone();
         let toolbar_window = self.toolbar_window.clone();
         let app_handle = self.app_handle.clone();
         let is_running = self.is_running.clone();
         let last_selection_timestamp = self.last_selection_timestamp.clone();
         let history = self.history.clone();
+        let semantic_history = self.semantic_history.clone();
+        let filter_counters = self.filter_counters.clone();
 
         // Spawn event processing task with cancellation support
         log::debug!("[SelectionManager] Spawning event processing task");
@@ -390,7 +542,7 @@ impl SelectionManager {
                     }
                     // Process mouse events
                     event = rx.recv() => {
-                        let Some(event) = event else {
+                        let Some(mut event) = event else {
                             log::info!("[SelectionManager] Event channel closed");
                             break;
                         };
@@ -416,6 +568,27 @@ impl SelectionManager {
                             continue;
                         }
 
+                        // Debounce bursts of events (e.g. repeated clicks): wait until no
+                        // new event arrives within the window and handle only the latest
+                        if cfg.debounce_ms > 0 {
+                            loop {
+                                tokio::time::sleep(tokio::time::Duration::from_millis(cfg.debounce_ms)).await;
+                                let mut superseded = 0;
+                                while let Ok(newer) = rx.try_recv() {
+                                    event = newer;
+                                    superseded += 1;
+                                }
+                                if superseded == 0 {
+                                    break;
+                                }
+                                filter_counters.record_debounced(superseded);
+                                log::trace!(
+                                    "[SelectionManager] Debounced {} mouse event(s)",
+                                    superseded
+                                );
+                            }
+                        }
+
                         // Extract position from event and determine if this is a selection action
                         // Simple LeftButtonUp (single click) should only be used to HIDE toolbar
                         // when selection is cleared, not to SHOW a new toolbar
@@ -444,8 +617,16 @@ impl SelectionManager {
                             continue;
                         }
 
-                        // Try to get selected text
-                        match detector.get_selected_text() {
+                        // Try to get selected text, using OCR only for actual selection gestures
+                        let selected = crate::commands::window::selection::get_selected_text_with_fallback(
+                            &detector,
+                            &mouse_hook,
+                            cfg.ocr_fallback && is_selection_action,
+                            &app_handle,
+                        )
+                        .await
+                        .map(|selected| selected.map(|selected| selected.text));
+                        match selected {
                             Ok(Some(text)) if !is_effectively_empty_selection(&text) => {
                                 // For simple clicks (not selection actions), don't show new toolbar
                                 // This prevents false triggers when clicking to position cursor
@@ -456,6 +637,7 @@ impl SelectionManager {
 
                                 // Check text length
                                 if !SelectionManager::is_text_within_limits(&text, &cfg) {
+                                    filter_counters.record_length_rejection(&text, &cfg);
                                     log::debug!(
                                         "[SelectionManager] Text length {} outside bounds [{}, {}]",
                                         text.chars().count(),
@@ -485,7 +667,10 @@ impl SelectionManager {
                                     analysis.language.clone(),
                                 );
                                 history_entry.is_manual = false;
-                                history.add(history_entry);
+                                let entry_id = history_entry.id.clone();
+                                if history.add(history_entry) {
+                                    semantic_history.enqueue(SemanticHistorySource::Selection, &entry_id, &text);
+                                }
 
                                 // Show toolbar
                                 if let Err(e) = toolbar_window.show(x as i32, y as i32, text.clone()) {
@@ -539,7 +724,7 @@ impl SelectionManager {
                                     &app_handle,
                                     ERROR_KIND_EXTRACT_FAILED,
                                     "extract_selection_auto",
-                                    &e,
+                                    &e.message,
                                     serde_json::json!({
                                         "triggerMode": cfg.trigger_mode,
                                         "selectionAction": is_selection_action,
@@ -559,6 +744,14 @@ impl SelectionManager {
         Ok(())
     }
 
+    /// Persist pending state before the app exits
+    ///
+    /// Selection and clipboard history are kept in memory only; the translation
+    /// cache holds recently-used order that is not yet on disk.
+    pub fn shutdown(&self) -> Result<(), String> {
+        self.translation_cache.flush()
+    }
+
     /// Stop the selection detection service
     pub fn stop(&self) -> Result<(), String> {
         log::debug!("[SelectionManager] stop() called");
@@ -611,6 +804,24 @@ impl SelectionManager {
             TriggerMode::Shortcut => "shortcut".to_string(),
             TriggerMode::Both => "both".to_string(),
         };
+        normalized_config.debounce_ms =
+            normalized_config.debounce_ms.min(MAX_SELECTION_DEBOUNCE_MS);
+        if !normalized_config.semantic_history.has_local_endpoint() {
+            let requested = std::mem::replace(
+                &mut normalized_config.semantic_history.embedding_base_url,
+                SemanticHistoryConfig::default().embedding_base_url,
+            );
+            SelectionManager::emit_selection_error(
+                &self.app_handle,
+                ERROR_KIND_CONFIG_SYNC_FAILED,
+                "normalize_embedding_base_url",
+                "Semantic history embeddings must use a local endpoint; falling back to the default",
+                serde_json::json!({
+                    "requested": requested,
+                    "normalized": normalized_config.semantic_history.embedding_base_url,
+                }),
+            );
+        }
         if requested_trigger_mode != normalized_config.trigger_mode {
             SelectionManager::emit_selection_error(
                 &self.app_handle,
@@ -630,10 +841,22 @@ impl SelectionManager {
             *current = normalized_config.clone();
         }
 
+        if old_config.clipboard_retention != normalized_config.clipboard_retention {
+            self.clipboard_history
+                .set_retention_policy(normalized_config.clipboard_retention.clone());
+        }
+        if old_config.history_retention != normalized_config.history_retention {
+            self.history
+                .set_retention_policy(normalized_config.history_retention.clone());
+        }
+
         // Emit config change event
         let _ = self
             .app_handle
             .emit("selection-config-changed", &normalized_config);
+        if old_config.toolbar_theme != normalized_config.toolbar_theme {
+            self.emit_toolbar_theme_changed(&normalized_config.toolbar_theme);
+        }
 
         // Handle enable/disable state change
         if old_config.enabled != normalized_config.enabled {
@@ -652,10 +875,12 @@ impl SelectionManager {
         }
 
         log::debug!(
-            "[SelectionManager] Config updated: enabled={}, trigger_mode={}, delay={}ms",
+            "[SelectionManager] Config updated: enabled={}, trigger_mode={}, delay={}ms, debounce={}ms, min_length={}",
             normalized_config.enabled,
             normalized_config.trigger_mode,
-            normalized_config.delay_ms
+            normalized_config.delay_ms,
+            normalized_config.debounce_ms,
+            normalized_config.min_text_length
         );
     }
 
@@ -694,6 +919,7 @@ impl SelectionManager {
     pub fn get_status(&self) -> SelectionStatus {
         let config = self.config.read().clone();
         let is_running = *self.is_running.read() && self.mouse_hook.is_running();
+        let degraded = self.degraded.read().clone();
         SelectionStatus {
             is_running,
             toolbar_visible: self.toolbar_window.is_visible(),
@@ -705,6 +931,8 @@ impl SelectionManager {
             selected_text: self.toolbar_window.get_selected_text(),
             last_selection_timestamp: *self.last_selection_timestamp.read(),
             config,
+            degraded: degraded.degraded,
+            degraded_reason: degraded.reason,
         }
     }
 
@@ -713,6 +941,90 @@ impl SelectionManager {
         self.config.read().clone()
     }
 
+    /// Update the clipboard retention policy and enforce it immediately
+    pub fn set_clipboard_retention(
+        &self,
+        policy: ClipboardRetentionPolicy,
+    ) -> ClipboardEvictionReport {
+        self.config.write().clipboard_retention = policy.clone();
+        let report = self.clipboard_history.set_retention_policy(policy);
+        self.semantic_history.forget_removed();
+        report
+    }
+
+    /// Update the selection history retention policy and trim immediately
+    pub fn set_history_retention(
+        &self,
+        policy: SelectionHistoryRetentionPolicy,
+    ) -> SelectionTrimStats {
+        self.config.write().history_retention = policy.clone();
+        let stats = self.history.set_retention_policy(policy);
+        self.semantic_history.forget_removed();
+        stats
+    }
+
+    /// Validate and apply a toolbar theme, notifying the overlay
+    pub fn set_toolbar_theme(&self, theme: SelectionToolbarTheme) -> Result<(), String> {
+        theme.validate()?;
+        self.config.write().toolbar_theme = theme.clone();
+        log::debug!("[SelectionManager] Toolbar theme set to {:?}", theme.theme);
+
+        self.emit_toolbar_theme_changed(&theme);
+        Ok(())
+    }
+
+    /// Notify the overlay of the active toolbar theme and its resolved colors
+    fn emit_toolbar_theme_changed(&self, theme: &SelectionToolbarTheme) {
+        let _ = self.app_handle.emit(
+            "selection-toolbar-theme-changed",
+            serde_json::json!({ "theme": theme, "colors": theme.resolved_colors() }),
+        );
+    }
+
+    /// Link the newest clipboard entry to the selection it was copied from, if any
+    ///
+    /// Returns the shared correlation ID and emits `selection-copy-correlated` when linked.
+    pub fn correlate_latest_clipboard_entry(&self) -> Option<String> {
+        let entry = self.clipboard_history.get_recent(1).into_iter().next()?;
+        if entry.selection_entry_id.is_some() {
+            return None;
+        }
+        let text = entry.text.as_deref()?;
+        let (selection_entry_id, correlation_id) = self.history.link_clipboard_entry(
+            text,
+            &entry.id,
+            entry.timestamp,
+            SELECTION_COPY_CORRELATION_WINDOW_MS,
+        )?;
+        self.clipboard_history
+            .link_selection(&entry.id, &selection_entry_id, &correlation_id);
+
+        let payload = serde_json::json!({
+            "correlationId": correlation_id,
+            "selectionEntryId": selection_entry_id,
+            "clipboardEntryId": entry.id,
+        });
+        if let Err(e) = self.app_handle.emit("selection-copy-correlated", payload) {
+            log::error!(
+                "[SelectionManager] Failed to emit selection-copy-correlated: {}",
+                e
+            );
+        }
+        Some(correlation_id)
+    }
+
+    /// Queue the newest clipboard text entry for semantic indexing
+    pub fn index_latest_clipboard_entry(&self) -> bool {
+        let Some(entry) = self.clipboard_history.get_recent(1).into_iter().next() else {
+            return false;
+        };
+        let Some(text) = entry.text.as_deref() else {
+            return false;
+        };
+        self.semantic_history
+            .enqueue(SemanticHistorySource::Clipboard, &entry.id, text)
+    }
+
     /// Manually trigger selection detection
     pub fn trigger(&self) -> Result<Option<SelectionPayload>, String> {
         log::debug!("[SelectionManager] trigger() called");
@@ -750,6 +1062,7 @@ impl SelectionManager {
 
         // Check text length
         if !Self::is_text_within_limits(&text, &cfg) {
+            self.filter_counters.record_length_rejection(&text, &cfg);
             log::debug!(
                 "[SelectionManager] Trigger: text length {} outside bounds",
                 text.chars().count()
@@ -779,7 +1092,11 @@ impl SelectionManager {
             analysis.language.clone(),
         );
         history_entry.is_manual = true;
-        self.history.add(history_entry);
+        let entry_id = history_entry.id.clone();
+        if self.history.add(history_entry) {
+            self.semantic_history
+                .enqueue(SemanticHistorySource::Selection, &entry_id, &text);
+        }
 
         // Create payload
         let payload = SelectionPayload {
@@ -924,6 +1241,7 @@ mod tests {
             delay_ms: 100,
             target_language: "en-US".to_string(),
             excluded_apps: vec!["notepad.exe".to_string(), "calc.exe".to_string()],
+            ..SelectionConfig::default()
         };
 
         assert!(!config.enabled);
@@ -983,6 +1301,41 @@ mod tests {
         assert_eq!(config.excluded_apps, vec!["app1", "app2"]);
     }
 
+    #[test]
+    fn test_selection_config_debounce_defaults_when_missing() {
+        let json = r#"{
+            "enabled": true,
+            "trigger_mode": "auto",
+            "min_text_length": 3,
+            "max_text_length": 2000,
+            "delay_ms": 300,
+            "target_language": "ja-JP",
+            "excluded_apps": []
+        }"#;
+
+        let config: SelectionConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.debounce_ms, 0);
+        assert_eq!(config.debounce_ms, SelectionConfig::default().debounce_ms);
+        assert_eq!(config.min_text_length, 3);
+    }
+
+    #[test]
+    fn test_filter_counters_split_short_and_long() {
+        let counters = DetectionFilterCounters::default();
+        let cfg = SelectionConfig {
+            min_text_length: 3,
+            max_text_length: 5,
+            ..SelectionConfig::default()
+        };
+
+        counters.record_length_rejection("ab", &cfg);
+        counters.record_length_rejection("abcdefg", &cfg);
+        counters.record_length_rejection("a", &cfg);
+        counters.record_debounced(4);
+
+        assert_eq!(counters.snapshot(), (4, 2, 1));
+    }
+
     // SelectionStatus tests
     #[test]
     fn test_selection_status_creation() {
@@ -993,6 +1346,8 @@ mod tests {
             selected_text: None,
             last_selection_timestamp: None,
             config: SelectionConfig::default(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         assert!(status.is_running);
@@ -1011,6 +1366,8 @@ mod tests {
             selected_text: Some("selected".to_string()),
             last_selection_timestamp: Some(1234567890),
             config: SelectionConfig::default(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         assert!(status.toolbar_visible);
@@ -1028,6 +1385,8 @@ mod tests {
             selected_text: Some("text".to_string()),
             last_selection_timestamp: Some(999),
             config: SelectionConfig::default(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         let cloned = status.clone();
@@ -1046,6 +1405,8 @@ mod tests {
             selected_text: None,
             last_selection_timestamp: None,
             config: SelectionConfig::default(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         let json = serde_json::to_string(&status);
@@ -1124,6 +1485,7 @@ mod tests {
             delay_ms: 0,
             target_language: "".to_string(),
             excluded_apps: vec![],
+            ..SelectionConfig::default()
         };
 
         assert_eq!(config.min_text_length, 0);
@@ -1141,6 +1503,7 @@ mod tests {
             delay_ms: u64::MAX,
             target_language: "test".to_string(),
             excluded_apps: vec![],
+            ..SelectionConfig::default()
         };
 
         assert_eq!(config.min_text_length, usize::MAX);
@@ -1160,6 +1523,7 @@ mod tests {
             delay_ms: 200,
             target_language: "en-US".to_string(),
             excluded_apps: excluded.clone(),
+            ..SelectionConfig::default()
         };
 
         assert_eq!(config.excluded_apps.len(), 100);
@@ -1176,6 +1540,8 @@ mod tests {
             selected_text: None,
             last_selection_timestamp: None,
             config: SelectionConfig::default(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         let debug_str = format!("{:?}", status);
@@ -1194,6 +1560,7 @@ mod tests {
             delay_ms: 500,
             target_language: "de-DE".to_string(),
             excluded_apps: vec!["test.exe".to_string()],
+            ..SelectionConfig::default()
         };
 
         let status = SelectionStatus {
@@ -1203,6 +1570,8 @@ mod tests {
             selected_text: None,
             last_selection_timestamp: None,
             config: config.clone(),
+            degraded: false,
+            degraded_reason: None,
         };
 
         assert_eq!(status.config.enabled, config.enabled);
@@ -1289,6 +1658,7 @@ mod tests {
             delay_ms: 200,
             target_language: "zh-CN".to_string(),
             excluded_apps: vec!["notepad.exe".to_string()],
+            ..SelectionConfig::default()
         };
 
         let source = SourceAppInfo {
@@ -1313,6 +1683,7 @@ mod tests {
             delay_ms: 150,
             target_language: "fr-FR".to_string(),
             excluded_apps: vec!["app1.exe".to_string(), "app2.exe".to_string()],
+            ..SelectionConfig::default()
         };
 
         let json = serde_json::to_string(&original_config).unwrap();
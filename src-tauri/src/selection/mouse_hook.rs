@@ -45,6 +45,8 @@ pub struct MouseHook {
     last_position: Arc<RwLock<(f64, f64)>>,
     /// Whether left button is currently pressed (for drag detection)
     left_button_down: Arc<AtomicBool>,
+    /// Start and end points of the most recent drag selection, until the next press
+    last_drag: Arc<RwLock<Option<((f64, f64), (f64, f64))>>>,
 }
 
 impl MouseHook {
@@ -59,6 +61,7 @@ impl MouseHook {
             click_count: Arc::new(RwLock::new(0)),
             last_position: Arc::new(RwLock::new((0.0, 0.0))),
             left_button_down: Arc::new(AtomicBool::new(false)),
+            last_drag: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -84,6 +87,7 @@ impl MouseHook {
         let event_tx = self.event_tx.clone();
         let last_position = self.last_position.clone();
         let left_button_down = self.left_button_down.clone();
+        let last_drag = self.last_drag.clone();
 
         let handle = thread::spawn(move || {
             is_running.store(true, Ordering::SeqCst);
@@ -98,6 +102,8 @@ impl MouseHook {
                 match event.event_type {
                     EventType::ButtonPress(rdev::Button::Left) => {
                         left_button_down.store(true, Ordering::SeqCst);
+                        // A new click ends the previous selection
+                        *last_drag.write() = None;
                         let (x, y) = get_mouse_position();
                         *last_position.write() = (x, y);
                     }
@@ -129,6 +135,7 @@ impl MouseHook {
                             // Drag selection completed
                             log::debug!("[MouseHook] Drag detected: ({:.0}, {:.0}) -> ({:.0}, {:.0}), distance={:.1}px", 
                                 last_x, last_y, x, y, distance);
+                            *last_drag.write() = Some(((last_x, last_y), (x, y)));
                             MouseEvent::DragEnd { x, y }
                         } else {
                             match *count {
@@ -216,6 +223,7 @@ impl MouseHook {
         *self.last_click_time.write() = Instant::now();
         self.left_button_down.store(false, Ordering::SeqCst);
         *self.last_position.write() = (0.0, 0.0);
+        *self.last_drag.write() = None;
         log::debug!("[MouseHook] State reset complete");
     }
 
@@ -225,6 +233,12 @@ impl MouseHook {
         *self.event_tx.write() = Some(tx);
     }
 
+    /// Take the start and end points of the most recent drag selection, so each
+    /// drag is used at most once
+    pub fn take_last_drag(&self) -> Option<((f64, f64), (f64, f64))> {
+        self.last_drag.write().take()
+    }
+
     /// Check if the hook is running
     pub(crate) fn is_running(&self) -> bool {
         self.is_running.load(Ordering::SeqCst)
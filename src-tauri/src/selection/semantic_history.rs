@@ -0,0 +1,644 @@
+//! Semantic history indexing
+//!
+//! When enabled, new selection and clipboard text entries are embedded with a
+//! local Ollama model and upserted into a dedicated vector collection, so history
+//! can be searched by meaning. Embedding runs on a background queue and never
+//! delays capture; text flagged by the sensitive-content filter is never embedded.
+//! History text is only sent to an embedding endpoint on a loopback host.
+//! Points of entries that left the history are deleted, so trimmed or cleared
+//! text does not stay searchable.
+
+use super::clipboard_context::ClipboardContextAnalyzer;
+use super::clipboard_history::ClipboardHistory;
+use super::history::SelectionHistory;
+use super::SelectionConfig;
+use crate::commands::error::CommandError;
+use crate::commands::providers::ollama::ollama_generate_embedding;
+use crate::commands::storage::vector::{
+    create_collection_impl, create_payload_index_impl, delete_collection_impl, get_collection_impl,
+    retain_points_impl, upsert_points_impl, CreateCollectionPayload, PayloadFilter, PointRecord,
+    SearchResult, UpsertPoint, VectorStoreState,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::Manager;
+use tokio::sync::mpsc;
+
+/// Entries waiting to be embedded; new entries are dropped while the queue is full
+const SEMANTIC_QUEUE_CAPACITY: usize = 256;
+
+/// Provider recorded on the history collection
+const EMBEDDING_PROVIDER: &str = "ollama";
+
+/// Payload field holding the history source, indexed for source-filtered searches
+const SOURCE_FIELD: &str = "source";
+
+/// Settings for embedding history entries into a vector collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticHistoryConfig {
+    /// Embed new selection and clipboard text entries
+    pub enabled: bool,
+    /// Vector collection receiving the embeddings
+    pub collection: String,
+    /// Ollama endpoint used for embeddings; must be on a loopback host
+    pub embedding_base_url: String,
+    /// Embedding model
+    pub embedding_model: String,
+}
+
+impl Default for SemanticHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            collection: "history_semantic".to_string(),
+            embedding_base_url: "http://localhost:11434".to_string(),
+            embedding_model: "nomic-embed-text".to_string(),
+        }
+    }
+}
+
+impl SemanticHistoryConfig {
+    /// Whether the embedding endpoint is on a loopback host, the only kind
+    /// history text is sent to
+    pub fn has_local_endpoint(&self) -> bool {
+        let Ok(url) = reqwest::Url::parse(self.embedding_base_url.trim()) else {
+            return false;
+        };
+        match url.host_str() {
+            Some(host) => {
+                let host = host.trim_start_matches('[').trim_end_matches(']');
+                host.eq_ignore_ascii_case("localhost")
+                    || host
+                        .parse::<std::net::IpAddr>()
+                        .map(|ip| ip.is_loopback())
+                        .unwrap_or(false)
+            }
+            None => false,
+        }
+    }
+}
+
+/// History store an indexed entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SemanticHistorySource {
+    Selection,
+    Clipboard,
+}
+
+impl SemanticHistorySource {
+    fn as_str(self) -> &'static str {
+        match self {
+            SemanticHistorySource::Selection => "selection",
+            SemanticHistorySource::Clipboard => "clipboard",
+        }
+    }
+}
+
+/// A history entry waiting to be embedded
+#[derive(Debug, Clone)]
+struct SemanticHistoryJob {
+    source: SemanticHistorySource,
+    entry_id: String,
+    text: String,
+    timestamp: i64,
+}
+
+impl SemanticHistoryJob {
+    fn point_id(&self) -> String {
+        point_id(self.source, &self.entry_id)
+    }
+
+    fn payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "source": self.source,
+            "entry_id": self.entry_id,
+            "text": self.text,
+            "timestamp": self.timestamp,
+        })
+    }
+}
+
+/// Work for the indexing queue
+#[derive(Debug, Clone)]
+enum SemanticHistoryTask {
+    Index(SemanticHistoryJob),
+    /// Delete points whose history entry no longer exists
+    Prune,
+}
+
+fn point_id(source: SemanticHistorySource, entry_id: &str) -> String {
+    format!("{}:{}", source.as_str(), entry_id)
+}
+
+/// The history stores whose entries are indexed
+#[derive(Clone)]
+pub struct SemanticHistoryStores {
+    pub selection: Arc<SelectionHistory>,
+    pub clipboard: Arc<ClipboardHistory>,
+}
+
+impl SemanticHistoryStores {
+    /// Point IDs of every entry currently in the history, and of the pinned ones
+    fn point_ids(&self) -> (HashSet<String>, HashSet<String>) {
+        let mut live = HashSet::new();
+        let mut pinned = HashSet::new();
+        for entry in self.selection.get_all() {
+            let id = point_id(SemanticHistorySource::Selection, &entry.id);
+            if entry.is_pinned {
+                pinned.insert(id.clone());
+            }
+            live.insert(id);
+        }
+        for entry in self.clipboard.get_recent(usize::MAX) {
+            let id = point_id(SemanticHistorySource::Clipboard, &entry.id);
+            if entry.is_pinned {
+                pinned.insert(id.clone());
+            }
+            live.insert(id);
+        }
+        (live, pinned)
+    }
+}
+
+/// A history entry matched by a semantic search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticHistoryHit {
+    pub source: SemanticHistorySource,
+    /// ID of the selection or clipboard history entry
+    pub entry_id: String,
+    pub text: String,
+    pub timestamp: i64,
+    /// Cosine similarity to the query
+    pub score: f64,
+}
+
+impl SemanticHistoryHit {
+    /// Read a hit back from a search result of the history collection
+    pub fn from_search_result(result: &SearchResult) -> Option<Self> {
+        let payload = result.payload.as_ref()?;
+        Some(Self {
+            source: serde_json::from_value(payload.get(SOURCE_FIELD)?.clone()).ok()?,
+            entry_id: payload.get("entry_id")?.as_str()?.to_string(),
+            text: payload.get("text")?.as_str()?.to_string(),
+            timestamp: payload.get("timestamp")?.as_i64()?,
+            score: result.score,
+        })
+    }
+}
+
+/// Filter restricting a history search to one source
+pub fn source_filter(source: SemanticHistorySource) -> PayloadFilter {
+    PayloadFilter {
+        key: SOURCE_FIELD.to_string(),
+        value: serde_json::json!(source),
+        operation: "equals".to_string(),
+    }
+}
+
+/// Background queue embedding history entries into the configured collection
+pub struct SemanticHistoryIndexer {
+    app_handle: tauri::AppHandle,
+    config: Arc<RwLock<SelectionConfig>>,
+    analyzer: Arc<ClipboardContextAnalyzer>,
+    stores: SemanticHistoryStores,
+    sender: mpsc::Sender<SemanticHistoryTask>,
+}
+
+impl SemanticHistoryIndexer {
+    pub fn new(
+        app_handle: tauri::AppHandle,
+        config: Arc<RwLock<SelectionConfig>>,
+        analyzer: Arc<ClipboardContextAnalyzer>,
+        stores: SemanticHistoryStores,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(SEMANTIC_QUEUE_CAPACITY);
+        tauri::async_runtime::spawn(run_worker(
+            app_handle.clone(),
+            config.clone(),
+            stores.clone(),
+            receiver,
+        ));
+        Self {
+            app_handle,
+            config,
+            analyzer,
+            stores,
+            sender,
+        }
+    }
+
+    /// Queue deletion of the points of entries removed from the history
+    ///
+    /// Call after clearing, trimming or deleting history entries.
+    pub fn forget_removed(&self) {
+        if let Err(e) = self.sender.try_send(SemanticHistoryTask::Prune) {
+            log::warn!("[SemanticHistory] Failed to queue pruning: {}", e);
+        }
+    }
+
+    /// Delete indexed points older than `cutoff_ms`, or all of them when `None`
+    ///
+    /// Points of pinned entries are kept unless `force` is set. Returns the
+    /// number of deleted points.
+    pub fn purge(&self, cutoff_ms: Option<i64>, force: bool) -> Result<usize, CommandError> {
+        let vector = vector_store(&self.app_handle)?;
+        let collection = self.config.read().semantic_history.collection.clone();
+        let (_, pinned) = self.stores.point_ids();
+        purge_points(&vector, &collection, &pinned, cutoff_ms, force)
+    }
+
+    /// Queue a new history entry for embedding
+    ///
+    /// Returns whether the entry was queued. Nothing is queued while indexing is
+    /// disabled, for blank text, or for text the sensitive-content filter flags.
+    pub fn enqueue(&self, source: SemanticHistorySource, entry_id: &str, text: &str) -> bool {
+        if !self.config.read().semantic_history.enabled || text.trim().is_empty() {
+            return false;
+        }
+        if self.analyzer.check_sensitive(text) {
+            log::debug!(
+                "[SemanticHistory] Skipping sensitive {} entry {}",
+                source.as_str(),
+                entry_id
+            );
+            return false;
+        }
+
+        let job = SemanticHistoryJob {
+            source,
+            entry_id: entry_id.to_string(),
+            text: text.to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        match self.sender.try_send(SemanticHistoryTask::Index(job)) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("[SemanticHistory] Dropping history entry: {}", e);
+                false
+            }
+        }
+    }
+}
+
+fn vector_store(app_handle: &tauri::AppHandle) -> Result<Arc<VectorStoreState>, CommandError> {
+    app_handle
+        .try_state::<Arc<VectorStoreState>>()
+        .map(|state| state.inner().clone())
+        .ok_or_else(|| CommandError::unavailable("Vector store is not available"))
+}
+
+async fn run_worker(
+    app_handle: tauri::AppHandle,
+    config: Arc<RwLock<SelectionConfig>>,
+    stores: SemanticHistoryStores,
+    mut receiver: mpsc::Receiver<SemanticHistoryTask>,
+) {
+    while let Some(task) = receiver.recv().await {
+        // Handle everything queued meanwhile as one batch, so the store file is
+        // rewritten once per batch instead of once per entry
+        let mut tasks = vec![task];
+        while let Ok(task) = receiver.try_recv() {
+            tasks.push(task);
+        }
+        let mut jobs = Vec::new();
+        let mut prune = false;
+        for task in tasks {
+            match task {
+                SemanticHistoryTask::Index(job) => jobs.push(job),
+                SemanticHistoryTask::Prune => prune = true,
+            }
+        }
+
+        // Indexing may have been turned off while the entries were queued
+        let cfg = config.read().semantic_history.clone();
+        if cfg.enabled && !jobs.is_empty() && !cfg.has_local_endpoint() {
+            log::warn!(
+                "[SemanticHistory] Not indexing {} entries: {} is not a local endpoint",
+                jobs.len(),
+                cfg.embedding_base_url
+            );
+        } else if cfg.enabled && !jobs.is_empty() {
+            index_jobs(&app_handle, &cfg, jobs).await;
+            // Adding entries may have trimmed older ones
+            prune = true;
+        }
+        if prune {
+            if let Err(e) = prune_removed(&app_handle, &stores, cfg.collection).await {
+                log::warn!("[SemanticHistory] Failed to prune removed entries: {}", e);
+            }
+        }
+    }
+    log::debug!("[SemanticHistory] Indexing queue closed");
+}
+
+async fn index_jobs(
+    app_handle: &tauri::AppHandle,
+    cfg: &SemanticHistoryConfig,
+    jobs: Vec<SemanticHistoryJob>,
+) {
+    let vector = match vector_store(app_handle) {
+        Ok(vector) => vector,
+        Err(e) => {
+            log::warn!("[SemanticHistory] Failed to index entries: {}", e);
+            return;
+        }
+    };
+    let mut embedded = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        match ollama_generate_embedding(
+            cfg.embedding_base_url.clone(),
+            cfg.embedding_model.clone(),
+            job.text.clone(),
+        )
+        .await
+        {
+            Ok(embedding) => embedded.push((job, embedding)),
+            Err(e) => log::warn!(
+                "[SemanticHistory] Failed to embed {}: {}",
+                job.point_id(),
+                e
+            ),
+        }
+    }
+    if embedded.is_empty() {
+        return;
+    }
+
+    // Upserts rewrite the store file, keep them off the async runtime
+    let cfg = cfg.clone();
+    let count = embedded.len();
+    let result =
+        tauri::async_runtime::spawn_blocking(move || store_embeddings(&vector, &cfg, embedded))
+            .await
+            .map_err(|e| CommandError::internal(format!("Semantic history task failed: {}", e)))
+            .and_then(|result| result);
+    if let Err(e) = result {
+        log::warn!("[SemanticHistory] Failed to index {} entries: {}", count, e);
+    }
+}
+
+async fn prune_removed(
+    app_handle: &tauri::AppHandle,
+    stores: &SemanticHistoryStores,
+    collection: String,
+) -> Result<usize, CommandError> {
+    let vector = vector_store(app_handle)?;
+    let (live, _) = stores.point_ids();
+    tauri::async_runtime::spawn_blocking(move || prune_points(&vector, &collection, &live))
+        .await
+        .map_err(|e| CommandError::internal(format!("Semantic history task failed: {}", e)))?
+}
+
+/// Upsert embedded entries, creating the collection on first use
+///
+/// The collection is recreated when it was built with another embedding model
+/// or dimension, since its vectors cannot be compared with new embeddings.
+fn store_embeddings(
+    vector: &VectorStoreState,
+    cfg: &SemanticHistoryConfig,
+    embedded: Vec<(SemanticHistoryJob, Vec<f64>)>,
+) -> Result<(), CommandError> {
+    let Some(dimension) = embedded.first().map(|(_, embedding)| embedding.len()) else {
+        return Ok(());
+    };
+    if let Ok(existing) = get_collection_impl(vector, cfg.collection.clone()) {
+        let model_changed = existing
+            .embedding_model
+            .as_deref()
+            .is_some_and(|model| model != cfg.embedding_model);
+        if existing.dimension != dimension || model_changed {
+            log::warn!(
+                "[SemanticHistory] Embedding model changed ({:?}, {} dimensions -> {}, {} dimensions), recreating collection '{}'",
+                existing.embedding_model,
+                existing.dimension,
+                cfg.embedding_model,
+                dimension,
+                cfg.collection
+            );
+            delete_collection_impl(vector, cfg.collection.clone())?;
+        }
+    }
+
+    create_collection_impl(
+        vector,
+        CreateCollectionPayload {
+            name: cfg.collection.clone(),
+            dimension,
+            metadata: None,
+            description: Some("Selection and clipboard history".to_string()),
+            embedding_model: Some(cfg.embedding_model.clone()),
+            embedding_provider: Some(EMBEDDING_PROVIDER.to_string()),
+        },
+    )?;
+    create_payload_index_impl(vector, cfg.collection.clone(), SOURCE_FIELD.to_string())?;
+    let points = embedded
+        .into_iter()
+        // An embedding of another length would fail the whole batch
+        .filter(|(_, embedding)| embedding.len() == dimension)
+        .map(|(job, embedding)| UpsertPoint {
+            id: job.point_id(),
+            vector: embedding,
+            payload: Some(job.payload()),
+        })
+        .collect();
+    upsert_points_impl(vector, cfg.collection.clone(), points)?;
+    Ok(())
+}
+
+/// Delete points whose entry is not in `live`
+fn prune_points(
+    vector: &VectorStoreState,
+    collection: &str,
+    live: &HashSet<String>,
+) -> Result<usize, CommandError> {
+    let deleted = retain_points_impl(vector, collection, |point| live.contains(&point.id))?;
+    if deleted > 0 {
+        log::debug!(
+            "[SemanticHistory] Deleted {} points of removed entries",
+            deleted
+        );
+    }
+    Ok(deleted)
+}
+
+/// Delete points indexed before `cutoff_ms` (all when `None`), keeping pinned ones unless `force`
+fn purge_points(
+    vector: &VectorStoreState,
+    collection: &str,
+    pinned: &HashSet<String>,
+    cutoff_ms: Option<i64>,
+    force: bool,
+) -> Result<usize, CommandError> {
+    retain_points_impl(vector, collection, |point| {
+        (!force && pinned.contains(&point.id))
+            || cutoff_ms.is_some_and(|cutoff| point_timestamp(point) >= Some(cutoff))
+    })
+}
+
+fn point_timestamp(point: &PointRecord) -> Option<i64> {
+    point.payload.as_ref()?.get("timestamp")?.as_i64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::storage::vector::{search_points_impl, SearchPayload};
+
+    fn job(source: SemanticHistorySource, entry_id: &str, text: &str) -> SemanticHistoryJob {
+        SemanticHistoryJob {
+            source,
+            entry_id: entry_id.to_string(),
+            text: text.to_string(),
+            timestamp: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_to_disabled() {
+        let config: SemanticHistoryConfig = serde_json::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config, SemanticHistoryConfig::default());
+    }
+
+    #[test]
+    fn test_only_loopback_endpoints_are_local() {
+        let with_url = |url: &str| SemanticHistoryConfig {
+            embedding_base_url: url.to_string(),
+            ..Default::default()
+        };
+        for url in [
+            "http://localhost:11434",
+            "http://LOCALHOST:11434/",
+            "http://127.0.0.1:11434",
+            "http://127.1.2.3",
+            "http://[::1]:11434",
+        ] {
+            assert!(with_url(url).has_local_endpoint(), "{url}");
+        }
+        for url in [
+            "https://ollama.example.com",
+            "http://192.168.1.10:11434",
+            "http://localhost.example.com",
+            "http://example.com/?next=http://localhost",
+            "not a url",
+        ] {
+            assert!(!with_url(url).has_local_endpoint(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_store_embedding_and_filter_by_source() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector = VectorStoreState::new(temp_dir.path().join("vector_store.json")).unwrap();
+        let cfg = SemanticHistoryConfig::default();
+
+        let selection = job(
+            SemanticHistorySource::Selection,
+            "s1",
+            "rust borrow checker",
+        );
+        let clipboard = job(SemanticHistorySource::Clipboard, "c1", "grocery list");
+        store_embeddings(
+            &vector,
+            &cfg,
+            vec![
+                (selection.clone(), vec![1.0, 0.0, 0.0]),
+                (clipboard, vec![0.9, 0.1, 0.0]),
+            ],
+        )
+        .unwrap();
+        // Re-indexing the same entry replaces its point
+        store_embeddings(&vector, &cfg, vec![(selection, vec![1.0, 0.0, 0.0])]).unwrap();
+
+        let response = search_points_impl(
+            &vector,
+            SearchPayload {
+                collection: cfg.collection.clone(),
+                vector: vec![1.0, 0.0, 0.0],
+                top_k: Some(10),
+                score_threshold: None,
+                offset: None,
+                limit: None,
+                filters: Some(vec![source_filter(SemanticHistorySource::Clipboard)]),
+                filter_mode: None,
+            },
+        )
+        .unwrap();
+        let hits: Vec<SemanticHistoryHit> = response
+            .results
+            .iter()
+            .filter_map(SemanticHistoryHit::from_search_result)
+            .collect();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source, SemanticHistorySource::Clipboard);
+        assert_eq!(hits[0].entry_id, "c1");
+        assert_eq!(hits[0].text, "grocery list");
+        assert_eq!(hits[0].timestamp, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_model_change_recreates_collection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector = VectorStoreState::new(temp_dir.path().join("vector_store.json")).unwrap();
+        let mut cfg = SemanticHistoryConfig::default();
+        let entry = job(SemanticHistorySource::Selection, "s1", "rust");
+
+        store_embeddings(&vector, &cfg, vec![(entry.clone(), vec![1.0, 0.0, 0.0])]).unwrap();
+        cfg.embedding_model = "mxbai-embed-large".to_string();
+        store_embeddings(&vector, &cfg, vec![(entry, vec![0.5, 0.5])]).unwrap();
+
+        let meta = get_collection_impl(&vector, cfg.collection.clone()).unwrap();
+        assert_eq!(meta.dimension, 2);
+        assert_eq!(meta.embedding_model.as_deref(), Some("mxbai-embed-large"));
+        assert_eq!(meta.document_count, 1);
+    }
+
+    #[test]
+    fn test_prune_and_purge_points() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector = VectorStoreState::new(temp_dir.path().join("vector_store.json")).unwrap();
+        let cfg = SemanticHistoryConfig::default();
+        let mut old = job(SemanticHistorySource::Selection, "old", "old");
+        old.timestamp = 1_000;
+        let mut pinned = job(SemanticHistorySource::Selection, "pinned", "pinned");
+        pinned.timestamp = 1_000;
+        let recent = job(SemanticHistorySource::Clipboard, "recent", "recent");
+        let removed = job(SemanticHistorySource::Clipboard, "removed", "removed");
+        store_embeddings(
+            &vector,
+            &cfg,
+            [&old, &pinned, &recent, &removed]
+                .into_iter()
+                .map(|job| (job.clone(), vec![1.0, 0.0]))
+                .collect(),
+        )
+        .unwrap();
+
+        let live: HashSet<String> = [&old, &pinned, &recent]
+            .into_iter()
+            .map(SemanticHistoryJob::point_id)
+            .collect();
+        assert_eq!(prune_points(&vector, &cfg.collection, &live).unwrap(), 1);
+        assert_eq!(prune_points(&vector, &cfg.collection, &live).unwrap(), 0);
+
+        let pinned_ids = HashSet::from([pinned.point_id()]);
+        assert_eq!(
+            purge_points(&vector, &cfg.collection, &pinned_ids, Some(2_000), false).unwrap(),
+            1
+        );
+        let meta = get_collection_impl(&vector, cfg.collection.clone()).unwrap();
+        assert_eq!(meta.document_count, 2);
+        assert_eq!(
+            purge_points(&vector, &cfg.collection, &pinned_ids, None, true).unwrap(),
+            2
+        );
+        assert_eq!(
+            purge_points(&vector, "missing", &pinned_ids, None, true).unwrap(),
+            0
+        );
+    }
+}
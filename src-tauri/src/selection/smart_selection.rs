@@ -2,6 +2,7 @@
 //!
 //! Provides intelligent text selection features including:
 //! - Smart expansion (word, sentence, paragraph, code block)
+//! - Type-aware expansion (URLs, dotted identifiers, numbers with units)
 //! - Context-aware selection
 //! - Application-specific optimizations
 
@@ -34,6 +35,10 @@ pub enum SelectionMode {
     Email,
     /// File path selection
     FilePath,
+    /// Dotted or namespaced identifier (`std::io::Result`, `window.location.href`)
+    Identifier,
+    /// Number including its sign, currency symbol and unit (`-12.5 km/h`, `$4.99`, `80%`)
+    NumberWithUnit,
 }
 
 /// Selection expansion result
@@ -110,6 +115,8 @@ impl SmartSelection {
             SelectionMode::Url => self.expand_url(&chars, cursor),
             SelectionMode::Email => self.expand_email(&chars, cursor),
             SelectionMode::FilePath => self.expand_file_path(&chars, cursor),
+            SelectionMode::Identifier => self.expand_identifier(&chars, cursor),
+            SelectionMode::NumberWithUnit => self.expand_number_with_unit(&chars, cursor),
             SelectionMode::Normal => (cursor, cursor),
         };
 
@@ -159,6 +166,16 @@ impl SmartSelection {
             return email_expansion;
         }
 
+        // Check if cursor is on a number, keeping its unit
+        if let Some(number_expansion) = self.try_expand_number_with_unit(&chars, cursor) {
+            return number_expansion;
+        }
+
+        // Check if cursor is on a dotted or namespaced identifier
+        if let Some(identifier_expansion) = self.try_expand_identifier(&chars, cursor) {
+            return identifier_expansion;
+        }
+
         // Check if cursor is inside quotes
         if let Some(quote_expansion) = self.try_expand_quote(&chars, cursor) {
             return quote_expansion;
@@ -527,9 +544,26 @@ impl SmartSelection {
             end += 1;
         }
 
+        // Drop surrounding punctuation, e.g. "(see https://example.com/a)."
+        while start < end && !chars[start].is_alphanumeric() {
+            start += 1;
+        }
+        while end > start {
+            let last = chars[end - 1];
+            let unbalanced = match last {
+                ')' => Self::count(chars, start, end, '(') < Self::count(chars, start, end, ')'),
+                ']' => Self::count(chars, start, end, '[') < Self::count(chars, start, end, ']'),
+                _ => ".,;:!?'".contains(last),
+            };
+            if !unbalanced {
+                break;
+            }
+            end -= 1;
+        }
+
         // Verify it looks like a URL
         let text: String = chars[start..end].iter().collect();
-        if text.contains("://") || text.starts_with("www.") {
+        if (text.contains("://") || text.starts_with("www.")) && cursor >= start && cursor < end {
             log::trace!(
                 "[SmartSelection] expand_url: found URL ({}, {})",
                 start,
@@ -617,8 +651,191 @@ impl SmartSelection {
         }
     }
 
+    /// Expand to a dotted or namespaced identifier
+    ///
+    /// Segments joined by `.`, `::` or `->` are included; a separator is only taken
+    /// when an identifier character follows it, so sentence punctuation is left out.
+    fn expand_identifier(&self, chars: &[char], cursor: usize) -> (usize, usize) {
+        if cursor >= chars.len() || !Self::is_identifier_char(chars[cursor]) {
+            return (cursor, cursor);
+        }
+
+        let mut start = cursor;
+        let mut end = cursor;
+
+        // Expand left
+        loop {
+            while start > 0 && Self::is_identifier_char(chars[start - 1]) {
+                start -= 1;
+            }
+            match Self::separator_before(chars, start) {
+                Some(len) if start > len && Self::is_identifier_char(chars[start - len - 1]) => {
+                    start -= len;
+                }
+                _ => break,
+            }
+        }
+
+        // Expand right
+        loop {
+            while end < chars.len() && Self::is_identifier_char(chars[end]) {
+                end += 1;
+            }
+            match Self::separator_at(chars, end) {
+                Some(len)
+                    if end + len < chars.len() && Self::is_identifier_char(chars[end + len]) =>
+                {
+                    end += len;
+                }
+                _ => break,
+            }
+        }
+
+        // A leading digit means a number such as `3.14`, not an identifier
+        if chars[start].is_ascii_digit() {
+            return (cursor, cursor);
+        }
+
+        log::trace!(
+            "[SmartSelection] expand_identifier: result=({}, {})",
+            start,
+            end
+        );
+        (start, end)
+    }
+
+    /// Expand to a number together with its sign, currency symbol and unit
+    fn expand_number_with_unit(&self, chars: &[char], cursor: usize) -> (usize, usize) {
+        if cursor >= chars.len() {
+            return (cursor, cursor);
+        }
+
+        let is_number_char = |c: char| c.is_ascii_digit() || c == '.' || c == ',';
+        let is_unit_char = |c: char| c.is_alphabetic() || "%°µ/²³".contains(c);
+
+        // Find a digit of the number under the cursor, walking back from a unit
+        let mut anchor = cursor;
+        if is_unit_char(chars[cursor]) {
+            while anchor > 0 && is_unit_char(chars[anchor - 1]) {
+                anchor -= 1;
+            }
+            if anchor > 0 && chars[anchor - 1] == ' ' {
+                anchor -= 1;
+            }
+            if anchor == 0 || !chars[anchor - 1].is_ascii_digit() {
+                return (cursor, cursor);
+            }
+            anchor -= 1;
+        } else if !is_number_char(chars[cursor]) {
+            return (cursor, cursor);
+        }
+
+        let mut start = anchor;
+        while start > 0 && is_number_char(chars[start - 1]) {
+            start -= 1;
+        }
+        let mut end = anchor;
+        while end < chars.len() && is_number_char(chars[end]) {
+            end += 1;
+        }
+
+        // Drop separators that belong to the surrounding sentence
+        while start < end && matches!(chars[start], '.' | ',') {
+            start += 1;
+        }
+        while end > start && matches!(chars[end - 1], '.' | ',') {
+            end -= 1;
+        }
+        // Digits inside an identifier (`x86`, `v2`) are not a number
+        if !chars[start..end].iter().any(|c| c.is_ascii_digit())
+            || (start > 0 && (chars[start - 1].is_alphanumeric() || chars[start - 1] == '_'))
+        {
+            return (cursor, cursor);
+        }
+        if start > 0 && "+-$€£¥".contains(chars[start - 1]) {
+            start -= 1;
+        }
+
+        // Include a known unit, attached or after a single space
+        let spaced = end < chars.len() && chars[end] == ' ';
+        let unit_start = if spaced { end + 1 } else { end };
+        let mut unit_end = unit_start;
+        while unit_end < chars.len() && is_unit_char(chars[unit_end]) {
+            unit_end += 1;
+        }
+        while unit_end > unit_start && chars[unit_end - 1] == '/' {
+            unit_end -= 1;
+        }
+        let unit: String = chars[unit_start..unit_end].iter().collect();
+        if Self::is_known_unit(&unit, spaced) {
+            end = unit_end;
+        }
+
+        if cursor < start || cursor >= end {
+            return (cursor, cursor);
+        }
+
+        log::trace!(
+            "[SmartSelection] expand_number_with_unit: result=({}, {})",
+            start,
+            end
+        );
+        (start, end)
+    }
+
     // Helper methods
 
+    fn count(chars: &[char], start: usize, end: usize, target: char) -> usize {
+        chars[start..end].iter().filter(|&&c| c == target).count()
+    }
+
+    fn is_identifier_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || c == '$'
+    }
+
+    /// Length of the identifier separator ending at `pos`, if any
+    fn separator_before(chars: &[char], pos: usize) -> Option<usize> {
+        if pos >= 2 && matches!(&chars[pos - 2..pos], [':', ':'] | ['-', '>']) {
+            Some(2)
+        } else if pos >= 1 && chars[pos - 1] == '.' {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Length of the identifier separator starting at `pos`, if any
+    fn separator_at(chars: &[char], pos: usize) -> Option<usize> {
+        if pos + 2 <= chars.len() && matches!(&chars[pos..pos + 2], [':', ':'] | ['-', '>']) {
+            Some(2)
+        } else if pos < chars.len() && chars[pos] == '.' {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `unit` is a recognized unit suffix
+    ///
+    /// Short units that double as words or letters (`in`, `s`, `x`) only count
+    /// when attached to the number, so "3 in stock" stays "3".
+    fn is_known_unit(unit: &str, spaced: bool) -> bool {
+        const UNITS: &[&str] = &[
+            "%", "°", "°c", "°f", "px", "em", "rem", "pt", "vh", "vw", "ns", "µs", "us", "ms", "s",
+            "sec", "min", "h", "hr", "hrs", "d", "b", "kb", "mb", "gb", "tb", "kib", "mib", "gib",
+            "tib", "bytes", "hz", "khz", "mhz", "ghz", "mm", "cm", "m", "km", "in", "ft", "mi",
+            "mg", "g", "kg", "lb", "lbs", "oz", "ml", "l", "w", "kw", "v", "mah", "fps", "dpi",
+            "km/h", "mph", "m/s", "x",
+        ];
+        const AMBIGUOUS_UNITS: &[&str] = &[
+            "s", "h", "d", "b", "m", "g", "l", "w", "v", "x", "in", "us", "em", "pt", "mi",
+        ];
+        let unit = unit.to_lowercase();
+        !unit.is_empty()
+            && UNITS.contains(&unit.as_str())
+            && !(spaced && AMBIGUOUS_UNITS.contains(&unit.as_str()))
+    }
+
     fn is_word_char(c: char) -> bool {
         c.is_alphanumeric() || c == '_' || c == '-'
     }
@@ -793,6 +1010,47 @@ impl SmartSelection {
         }
     }
 
+    fn try_expand_number_with_unit(
+        &self,
+        chars: &[char],
+        cursor: usize,
+    ) -> Option<SelectionExpansion> {
+        let (start, end) = self.expand_number_with_unit(chars, cursor);
+        if start != end {
+            let text: String = chars[start..end].iter().collect();
+            Some(SelectionExpansion {
+                original_start: cursor,
+                original_end: cursor,
+                expanded_start: start,
+                expanded_end: end,
+                expanded_text: text,
+                mode: SelectionMode::NumberWithUnit,
+                confidence: 0.9,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Only identifiers with a separator are worth more than plain word expansion
+    fn try_expand_identifier(&self, chars: &[char], cursor: usize) -> Option<SelectionExpansion> {
+        let (start, end) = self.expand_identifier(chars, cursor);
+        let text: String = chars[start..end].iter().collect();
+        if text.contains('.') || text.contains("::") || text.contains("->") {
+            Some(SelectionExpansion {
+                original_start: cursor,
+                original_end: cursor,
+                expanded_start: start,
+                expanded_end: end,
+                expanded_text: text,
+                mode: SelectionMode::Identifier,
+                confidence: 0.85,
+            })
+        } else {
+            None
+        }
+    }
+
     fn try_expand_quote(&self, chars: &[char], cursor: usize) -> Option<SelectionExpansion> {
         let (start, end) = self.expand_quote(chars, cursor);
         if start != end {
@@ -1016,6 +1274,8 @@ mod tests {
             SelectionMode::Url,
             SelectionMode::Email,
             SelectionMode::FilePath,
+            SelectionMode::Identifier,
+            SelectionMode::NumberWithUnit,
         ];
 
         for mode in modes {
@@ -1096,4 +1356,99 @@ mod tests {
         let (start, end) = smart.expand_word(&chars, 6);
         assert_eq!(&text[start..end], "word");
     }
+
+    #[test]
+    fn test_url_expansion_trims_surrounding_punctuation() {
+        let smart = SmartSelection::new();
+        let text = "(see https://example.com/wiki/Rust_(language)).";
+        let chars: Vec<char> = text.chars().collect();
+
+        let (start, end) = smart.expand_url(&chars, 15);
+        assert_eq!(
+            chars[start..end].iter().collect::<String>(),
+            "https://example.com/wiki/Rust_(language)"
+        );
+    }
+
+    #[test]
+    fn test_identifier_expansion() {
+        let smart = SmartSelection::new();
+        let text = "use std::io::Result; let x = window.location.href; a->b. Done.";
+        let chars: Vec<char> = text.chars().collect();
+
+        let (start, end) = smart.expand_identifier(&chars, 10);
+        assert_eq!(&text[start..end], "std::io::Result");
+
+        let (start, end) = smart.expand_identifier(&chars, 31);
+        assert_eq!(&text[start..end], "window.location.href");
+
+        // Sentence punctuation is not a separator
+        let (start, end) = smart.expand_identifier(&chars, 51);
+        assert_eq!(&text[start..end], "a->b");
+
+        // Plain numbers are not identifiers
+        let chars: Vec<char> = "pi is 3.14".chars().collect();
+        assert_eq!(smart.expand_identifier(&chars, 7), (7, 7));
+    }
+
+    #[test]
+    fn test_number_with_unit_expansion() {
+        let smart = SmartSelection::new();
+        let text = "Speed: -12.5 km/h, price $4,999.99, load 80%, 5 apples, x86 cpu.";
+        let chars: Vec<char> = text.chars().collect();
+        let expand = |cursor: usize| {
+            let (start, end) = smart.expand_number_with_unit(&chars, cursor);
+            chars[start..end].iter().collect::<String>()
+        };
+
+        assert_eq!(expand(9), "-12.5 km/h");
+        // Cursor on the unit expands back to the number
+        assert_eq!(expand(14), "-12.5 km/h");
+        assert_eq!(expand(28), "$4,999.99");
+        assert_eq!(expand(42), "80%");
+        // Unknown words are not units
+        assert_eq!(expand(46), "5");
+        assert_eq!(expand(49), "");
+        // Digits inside an identifier
+        assert_eq!(expand(57), "");
+
+        // Word-like units only count when attached
+        let text = "3 in stock, 3in wide, 2 x 4, 4x zoom, 5 km";
+        let chars: Vec<char> = text.chars().collect();
+        let expand = |cursor: usize| {
+            let (start, end) = smart.expand_number_with_unit(&chars, cursor);
+            chars[start..end].iter().collect::<String>()
+        };
+        assert_eq!(expand(0), "3");
+        assert_eq!(expand(12), "3in");
+        assert_eq!(expand(22), "2");
+        assert_eq!(expand(29), "4x");
+        assert_eq!(expand(38), "5 km");
+    }
+
+    #[test]
+    fn test_auto_expand_is_type_aware() {
+        let smart = SmartSelection::new();
+        let context = |text: &str, cursor_pos: usize| SelectionContext {
+            full_text: text.to_string(),
+            cursor_pos,
+            selection_start: None,
+            selection_end: None,
+            app_type: None,
+            is_code: false,
+            language: None,
+        };
+
+        let expansion = smart.auto_expand(&context("wait 250 ms please", 6));
+        assert_eq!(expansion.mode, SelectionMode::NumberWithUnit);
+        assert_eq!(expansion.expanded_text, "250 ms");
+
+        let expansion = smart.auto_expand(&context("call self.client.send() now", 12));
+        assert_eq!(expansion.mode, SelectionMode::Identifier);
+        assert_eq!(expansion.expanded_text, "self.client.send");
+
+        let expansion = smart.auto_expand(&context("plain words here", 7));
+        assert_eq!(expansion.mode, SelectionMode::Word);
+        assert_eq!(expansion.expanded_text, "words");
+    }
 }
@@ -0,0 +1,292 @@
+//! Selection toolbar theming
+//!
+//! Named color themes for the toolbar overlay plus per-action colors and icons,
+//! so custom AI actions can be told apart. Colors are validated when the theme
+//! is set so the overlay never receives CSS it cannot render.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Named toolbar theme
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolbarThemeName {
+    Light,
+    #[default]
+    Dark,
+    HighContrast,
+    /// Colors taken from `SelectionToolbarTheme::custom_colors`
+    Custom,
+}
+
+impl ToolbarThemeName {
+    /// Parse a theme name as sent by the frontend
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "high-contrast" => Some(Self::HighContrast),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+
+    /// Preset colors; `None` for the custom theme
+    pub fn preset_colors(self) -> Option<ToolbarThemeColors> {
+        let (background, text, accent, border) = match self {
+            Self::Light => ("#ffffff", "#1f2937", "#0891b2", "#e5e7eb"),
+            Self::Dark => ("#111827", "#f9fafb", "#22d3ee", "#374151"),
+            Self::HighContrast => ("#000000", "#ffffff", "#ffff00", "#ffffff"),
+            Self::Custom => return None,
+        };
+        Some(ToolbarThemeColors {
+            background: background.to_string(),
+            text: text.to_string(),
+            accent: accent.to_string(),
+            border: border.to_string(),
+        })
+    }
+}
+
+/// Colors used to render the toolbar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolbarThemeColors {
+    pub background: String,
+    pub text: String,
+    pub accent: String,
+    pub border: String,
+}
+
+/// Visual overrides for one toolbar action
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolbarActionStyle {
+    /// Button color; defaults to the theme accent
+    pub color: Option<String>,
+    /// Icon name understood by the overlay
+    pub icon: Option<String>,
+}
+
+/// Toolbar theme configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SelectionToolbarTheme {
+    pub theme: ToolbarThemeName,
+    /// Colors for the custom theme
+    pub custom_colors: Option<ToolbarThemeColors>,
+    /// Per-action colors and icons keyed by action ID
+    pub action_styles: HashMap<String, ToolbarActionStyle>,
+}
+
+impl SelectionToolbarTheme {
+    /// Check that the theme is complete and every color is a valid CSS color
+    pub fn validate(&self) -> Result<(), String> {
+        if self.theme == ToolbarThemeName::Custom && self.custom_colors.is_none() {
+            return Err("Invalid toolbar theme: custom theme requires custom_colors".to_string());
+        }
+
+        if let Some(colors) = &self.custom_colors {
+            for (field, value) in [
+                ("background", &colors.background),
+                ("text", &colors.text),
+                ("accent", &colors.accent),
+                ("border", &colors.border),
+            ] {
+                validate_color(field, value)?;
+            }
+        }
+
+        for (action_id, style) in &self.action_styles {
+            if let Some(color) = &style.color {
+                validate_color(&format!("action_styles.{}.color", action_id), color)?;
+            }
+            if style
+                .icon
+                .as_deref()
+                .is_some_and(|icon| icon.trim().is_empty())
+            {
+                return Err(format!(
+                    "Invalid toolbar theme: action_styles.{}.icon is empty",
+                    action_id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Colors the overlay should render with
+    pub fn resolved_colors(&self) -> ToolbarThemeColors {
+        match (self.theme, &self.custom_colors) {
+            (ToolbarThemeName::Custom, Some(colors)) => colors.clone(),
+            (theme, _) => theme
+                .preset_colors()
+                .unwrap_or_else(|| ToolbarThemeName::default().preset_colors().unwrap()),
+        }
+    }
+}
+
+fn validate_color(field: &str, value: &str) -> Result<(), String> {
+    if is_valid_color(value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid toolbar theme: {} has invalid color '{}'",
+            field, value
+        ))
+    }
+}
+
+/// Accept hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`), `rgb()`/`rgba()` and
+/// `hsl()`/`hsla()` colors
+pub fn is_valid_color(value: &str) -> bool {
+    let value = value.trim().to_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+
+    let Some((function, rest)) = value.split_once('(') else {
+        return false;
+    };
+    let Some(args) = rest.strip_suffix(')') else {
+        return false;
+    };
+    if !matches!(function, "rgb" | "rgba" | "hsl" | "hsla") {
+        return false;
+    }
+
+    let parts: Vec<&str> = args
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    matches!(parts.len(), 3 | 4)
+        && parts.iter().all(|part| {
+            let number = part
+                .strip_suffix('%')
+                .or_else(|| part.strip_suffix("deg"))
+                .unwrap_or(part);
+            number
+                .parse::<f64>()
+                .is_ok_and(|n| n.is_finite() && n >= 0.0)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_colors() -> ToolbarThemeColors {
+        ToolbarThemeColors {
+            background: "#1e1e2e".to_string(),
+            text: "rgb(205, 214, 244)".to_string(),
+            accent: "hsl(267 84% 81%)".to_string(),
+            border: "#45475a80".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_color() {
+        for color in [
+            "#fff",
+            "#FFFA",
+            "#112233",
+            "#11223344",
+            "rgb(0, 0, 0)",
+            "rgba(255,255,255,0.5)",
+            "rgb(0 0 0 / 50%)",
+            "hsl(120deg 50% 50%)",
+        ] {
+            assert!(is_valid_color(color), "{} should be valid", color);
+        }
+        for color in [
+            "",
+            "red",
+            "#12",
+            "#ggg",
+            "rgb(0, 0)",
+            "rgb(0, 0, 0",
+            "url(x)",
+            "rgb(a, b, c)",
+        ] {
+            assert!(!is_valid_color(color), "{} should be invalid", color);
+        }
+    }
+
+    #[test]
+    fn test_parse_theme_name() {
+        assert_eq!(
+            ToolbarThemeName::parse("High_Contrast"),
+            Some(ToolbarThemeName::HighContrast)
+        );
+        assert_eq!(
+            ToolbarThemeName::parse("light"),
+            Some(ToolbarThemeName::Light)
+        );
+        assert_eq!(ToolbarThemeName::parse("glass"), None);
+    }
+
+    #[test]
+    fn test_validate_custom_theme() {
+        let mut theme = SelectionToolbarTheme {
+            theme: ToolbarThemeName::Custom,
+            ..Default::default()
+        };
+        assert!(theme.validate().is_err());
+
+        theme.custom_colors = Some(custom_colors());
+        assert!(theme.validate().is_ok());
+        assert_eq!(theme.resolved_colors(), custom_colors());
+
+        theme.custom_colors.as_mut().unwrap().accent = "purple".to_string();
+        let err = theme.validate().unwrap_err();
+        assert!(err.contains("accent"));
+    }
+
+    #[test]
+    fn test_validate_action_styles() {
+        let mut theme = SelectionToolbarTheme::default();
+        theme.action_styles.insert(
+            "summarize".to_string(),
+            ToolbarActionStyle {
+                color: Some("#22c55e".to_string()),
+                icon: Some("sparkles".to_string()),
+            },
+        );
+        assert!(theme.validate().is_ok());
+
+        theme.action_styles.insert(
+            "translate".to_string(),
+            ToolbarActionStyle {
+                color: Some("not-a-color".to_string()),
+                icon: None,
+            },
+        );
+        assert!(theme.validate().unwrap_err().contains("translate"));
+    }
+
+    #[test]
+    fn test_resolved_colors_for_presets() {
+        let theme = SelectionToolbarTheme {
+            theme: ToolbarThemeName::HighContrast,
+            ..Default::default()
+        };
+        assert_eq!(theme.resolved_colors().background, "#000000");
+        assert_eq!(
+            SelectionToolbarTheme::default().resolved_colors(),
+            ToolbarThemeName::Dark.preset_colors().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_theme_serialization() {
+        let json = serde_json::to_string(&SelectionToolbarTheme {
+            theme: ToolbarThemeName::HighContrast,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(json.contains("\"high-contrast\""));
+
+        let theme: SelectionToolbarTheme = serde_json::from_str("{}").unwrap();
+        assert_eq!(theme, SelectionToolbarTheme::default());
+    }
+}
@@ -0,0 +1,292 @@
+//! Selection translation cache
+//!
+//! Translations are cached by normalized source text, target language and model
+//! in a small least-recently-used store persisted to disk, so re-translating a
+//! phrase returns instantly without another model call.
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::input_completion::CompletionModelConfig;
+
+/// Maximum number of cached translations
+pub const TRANSLATION_CACHE_CAPACITY: usize = 500;
+/// Minimum time between two writes of the cache file; later changes are batched
+const TRANSLATION_CACHE_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A cached translation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranslationCacheEntry {
+    /// Source text with whitespace normalized
+    pub source_text: String,
+    pub target_language: String,
+    /// Model that produced the translation (`provider/model_id`)
+    pub model: String,
+    pub translation: String,
+    pub created_at: i64,
+}
+
+/// Normalize source text so whitespace-only differences share a cache entry
+pub fn normalize_source_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cache key component identifying a completion model
+pub fn model_key(model: &CompletionModelConfig) -> String {
+    format!("{:?}/{}", model.provider, model.model_id).to_lowercase()
+}
+
+/// Persisted LRU of translations, least recently used first
+///
+/// Inserts are written at most once per save interval; `flush` persists whatever
+/// is still pending and runs on shutdown and drop.
+pub struct TranslationCache {
+    path: PathBuf,
+    capacity: usize,
+    entries: RwLock<Vec<TranslationCacheEntry>>,
+    /// Whether entries changed since the last save
+    dirty: AtomicBool,
+    last_saved: Mutex<Option<Instant>>,
+}
+
+impl TranslationCache {
+    pub fn load(path: PathBuf) -> Self {
+        Self::with_capacity(path, TRANSLATION_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(path: PathBuf, capacity: usize) -> Self {
+        let mut entries: Vec<TranslationCacheEntry> = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("[TranslationCache] Failed to parse {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        let excess = entries.len().saturating_sub(capacity);
+        entries.drain(..excess);
+        log::debug!("[TranslationCache] Loaded {} translation(s)", entries.len());
+
+        Self {
+            path,
+            capacity,
+            entries: RwLock::new(entries),
+            dirty: AtomicBool::new(false),
+            last_saved: Mutex::new(None),
+        }
+    }
+
+    /// Look up a translation and mark it as recently used
+    ///
+    /// The new order is only persisted with the next save, so hits never touch disk.
+    pub fn get(&self, text: &str, target_language: &str, model: &str) -> Option<String> {
+        let source_text = normalize_source_text(text);
+        let mut entries = self.entries.write();
+        let index = entries.iter().position(|entry| {
+            entry.source_text == source_text
+                && entry.target_language == target_language
+                && entry.model == model
+        })?;
+        let entry = entries.remove(index);
+        let translation = entry.translation.clone();
+        entries.push(entry);
+        self.dirty.store(true, Ordering::SeqCst);
+        Some(translation)
+    }
+
+    /// Cache a translation, evicting the least recently used entry when full
+    ///
+    /// The file is rewritten only if the last save is older than the save interval.
+    pub fn insert(&self, text: &str, target_language: &str, model: &str, translation: &str) {
+        let source_text = normalize_source_text(text);
+        if source_text.is_empty() || self.capacity == 0 {
+            return;
+        }
+        {
+            let mut entries = self.entries.write();
+            entries.retain(|entry| {
+                !(entry.source_text == source_text
+                    && entry.target_language == target_language
+                    && entry.model == model)
+            });
+            if entries.len() >= self.capacity {
+                let excess = entries.len() + 1 - self.capacity;
+                entries.drain(..excess);
+            }
+            entries.push(TranslationCacheEntry {
+                source_text,
+                target_language: target_language.to_string(),
+                model: model.to_string(),
+                translation: translation.to_string(),
+                created_at: chrono::Utc::now().timestamp_millis(),
+            });
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+
+        let save_due = self.last_saved.lock().map_or(true, |saved| {
+            saved.elapsed() >= TRANSLATION_CACHE_SAVE_INTERVAL
+        });
+        if save_due {
+            if let Err(e) = self.save() {
+                log::warn!("[TranslationCache] {}", e);
+            }
+        }
+    }
+
+    /// Remove all cached translations, returning how many were removed
+    pub fn clear(&self) -> Result<usize, String> {
+        let removed = self.purge(None)?;
+        log::info!("[TranslationCache] Cleared {} translation(s)", removed);
+        Ok(removed)
+    }
+
+    /// Remove translations cached before `cutoff` (ms), or all when `None`
+    ///
+    /// Removals are written immediately, returning how many entries were removed.
+    pub fn purge(&self, cutoff: Option<i64>) -> Result<usize, String> {
+        let removed = {
+            let mut entries = self.entries.write();
+            let before = entries.len();
+            entries.retain(|entry| cutoff.is_some_and(|cutoff| entry.created_at >= cutoff));
+            before - entries.len()
+        };
+        if removed > 0 || self.dirty.load(Ordering::SeqCst) {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    /// Persist changes that have not been written yet
+    pub fn flush(&self) -> Result<(), String> {
+        if self.dirty.load(Ordering::SeqCst) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let content = {
+            let entries = self.entries.read();
+            // Cleared under the read lock so a concurrent change marks it dirty again
+            self.dirty.store(false, Ordering::SeqCst);
+            serde_json::to_string(&*entries)
+                .map_err(|e| format!("Failed to serialize translation cache: {}", e))?
+        };
+        *self.last_saved.lock() = Some(Instant::now());
+
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        std::fs::write(&self.path, content).map_err(|e| {
+            self.dirty.store(true, Ordering::SeqCst);
+            format!("Failed to write translation cache file: {}", e)
+        })
+    }
+}
+
+impl Drop for TranslationCache {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!("[TranslationCache] {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_normalizes_source_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = TranslationCache::load(dir.path().join("cache.json"));
+
+        cache.insert("Hello   world\n", "de", "ollama/qwen", "Hallo Welt");
+        assert_eq!(
+            cache.get(" Hello world", "de", "ollama/qwen").as_deref(),
+            Some("Hallo Welt")
+        );
+        assert!(cache.get("Hello world", "fr", "ollama/qwen").is_none());
+        assert!(cache.get("Hello world", "de", "openai/gpt-4o").is_none());
+
+        // Re-translating replaces the entry
+        cache.insert("Hello world", "de", "ollama/qwen", "Hallo, Welt");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.get("Hello world", "de", "ollama/qwen").as_deref(),
+            Some("Hallo, Welt")
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache = TranslationCache::with_capacity(path.clone(), 2);
+
+        cache.insert("one", "de", "m", "eins");
+        cache.insert("two", "de", "m", "zwei");
+        // Using "one" makes "two" the eviction candidate
+        assert!(cache.get("one", "de", "m").is_some());
+        cache.insert("three", "de", "m", "drei");
+        cache.flush().unwrap();
+
+        let reloaded = TranslationCache::with_capacity(path, 2);
+        assert_eq!(reloaded.len(), 2);
+        assert!(reloaded.get("two", "de", "m").is_none());
+        assert_eq!(reloaded.get("one", "de", "m").as_deref(), Some("eins"));
+        assert_eq!(reloaded.get("three", "de", "m").as_deref(), Some("drei"));
+
+        assert_eq!(reloaded.clear().unwrap(), 2);
+        assert!(reloaded.is_empty());
+    }
+
+    #[test]
+    fn test_inserts_are_batched_until_flush() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache = TranslationCache::load(path.clone());
+
+        cache.insert("one", "de", "m", "eins");
+        cache.insert("two", "de", "m", "zwei");
+        // The first insert saves; the second waits for the save interval
+        assert_eq!(TranslationCache::load(path.clone()).len(), 1);
+
+        cache.flush().unwrap();
+        assert_eq!(TranslationCache::load(path.clone()).len(), 2);
+
+        cache.insert("three", "de", "m", "drei");
+        drop(cache);
+        assert_eq!(TranslationCache::load(path).len(), 3);
+    }
+
+    #[test]
+    fn test_purge_older_than_cutoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache = TranslationCache::load(path.clone());
+        cache.insert("one", "de", "m", "eins");
+        cache.insert("two", "de", "m", "zwei");
+        cache.entries.write()[0].created_at = 1_000;
+
+        assert_eq!(cache.purge(Some(2_000)).unwrap(), 1);
+        assert!(cache.get("one", "de", "m").is_none());
+        let reloaded = TranslationCache::load(path);
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded.get("two", "de", "m").as_deref(), Some("zwei"));
+
+        assert_eq!(reloaded.purge(None).unwrap(), 1);
+        assert!(reloaded.is_empty());
+    }
+}
@@ -79,6 +79,63 @@ pub struct SourceAppInfo {
     pub app_type: String,
 }
 
+/// Screen rectangle covering a selection, used for the OCR fallback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectionRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SelectionRegion {
+    /// Horizontal padding around the dragged span
+    const PADDING_X: f64 = 4.0;
+    /// Vertical padding; drag points sit mid-line, so this covers the line height
+    const PADDING_Y: f64 = 14.0;
+
+    /// Rectangle spanned by a drag from `start` to `end`, padded to cover the text lines
+    ///
+    /// A drag over several lines selects the lines in between in full, so it then
+    /// spans `full_width` (left edge and width, e.g. of the monitor) when given.
+    pub fn from_drag(start: (f64, f64), end: (f64, f64), full_width: Option<(i32, u32)>) -> Self {
+        let multi_line = (end.1 - start.1).abs() > Self::PADDING_Y;
+        let (left, right) = match full_width.filter(|_| multi_line) {
+            Some((x, width)) => (x as f64, x as f64 + width as f64),
+            None => (
+                start.0.min(end.0) - Self::PADDING_X,
+                start.0.max(end.0) + Self::PADDING_X,
+            ),
+        };
+        let top = start.1.min(end.1) - Self::PADDING_Y;
+        let bottom = start.1.max(end.1) + Self::PADDING_Y;
+
+        Self {
+            x: left.round() as i32,
+            y: top.round() as i32,
+            width: (right - left).round().max(1.0) as u32,
+            height: (bottom - top).round().max(1.0) as u32,
+        }
+    }
+}
+
+/// How selected text was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionTextSource {
+    /// Read from the focused application (accessibility / clipboard)
+    Accessibility,
+    /// Recognized from a screenshot of the selected region
+    Ocr,
+}
+
+/// Selected text together with its source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedText {
+    pub text: String,
+    pub source: SelectionTextSource,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +198,36 @@ mod tests {
         let json = serde_json::to_string(&selection);
         assert!(json.is_ok());
     }
+
+    #[test]
+    fn test_selection_region_from_drag() {
+        // Dragging right-to-left on a single line
+        let region = SelectionRegion::from_drag((300.0, 100.0), (100.0, 100.0), None);
+        assert_eq!(region.x, 96);
+        assert_eq!(region.y, 86);
+        assert_eq!(region.width, 208);
+        assert_eq!(region.height, 28);
+        // A single line keeps to the dragged span
+        let bounded = SelectionRegion::from_drag((300.0, 100.0), (100.0, 100.0), Some((0, 1920)));
+        assert_eq!(bounded, region);
+
+        let multiline = SelectionRegion::from_drag((100.0, 100.0), (50.0, 160.0), None);
+        assert_eq!(multiline.x, 46);
+        assert_eq!(multiline.y, 86);
+        assert_eq!(multiline.height, 88);
+        // Lines between the ends are selected in full
+        let full = SelectionRegion::from_drag((100.0, 100.0), (50.0, 160.0), Some((-1920, 1920)));
+        assert_eq!((full.x, full.width), (-1920, 1920));
+        assert_eq!((full.y, full.height), (86, 88));
+    }
+
+    #[test]
+    fn test_selected_text_source_serialization() {
+        let selected = SelectedText {
+            text: "hello".to_string(),
+            source: SelectionTextSource::Ocr,
+        };
+        let json = serde_json::to_value(&selected).unwrap();
+        assert_eq!(json["source"], "ocr");
+    }
 }
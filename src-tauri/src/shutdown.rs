@@ -0,0 +1,228 @@
+//! Graceful shutdown
+//!
+//! On exit every manager holding state that is not yet on disk gets a chance to
+//! persist it through its `shutdown()`. Steps run in registration order, each on
+//! its own thread with a timeout, so a stuck flush is abandoned instead of
+//! hanging quit; once the overall budget is spent the remaining steps are skipped.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Manager;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::awareness::AwarenessManager;
+use crate::commands::storage::vector::VectorStoreState;
+use crate::sandbox::SandboxState;
+use crate::screen_recording::ScreenRecordingManager;
+use crate::selection::SelectionManager;
+
+/// Maximum time one manager may take to flush
+pub const SHUTDOWN_STEP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Maximum time spent flushing all managers
+pub const SHUTDOWN_TOTAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a shutdown step ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    Completed,
+    Failed(String),
+    TimedOut,
+    /// Not started because the overall budget was spent
+    Skipped,
+}
+
+type ShutdownTask = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// Runs manager shutdown steps in order with bounded time
+pub struct ShutdownCoordinator {
+    steps: Vec<(&'static str, ShutdownTask)>,
+    step_timeout: Duration,
+    total_timeout: Duration,
+}
+
+impl ShutdownCoordinator {
+    pub fn new(step_timeout: Duration, total_timeout: Duration) -> Self {
+        Self {
+            steps: Vec::new(),
+            step_timeout,
+            total_timeout,
+        }
+    }
+
+    /// Add a step; steps run in the order they are added
+    pub fn add_step<F>(&mut self, name: &'static str, task: F) -> &mut Self
+    where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.steps.push((name, Box::pin(task)));
+        self
+    }
+
+    /// Add a step that blocks; it runs on its own thread so it can be abandoned
+    pub fn add_blocking_step<F>(&mut self, name: &'static str, task: F) -> &mut Self
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.add_step(name, async move {
+            let (sender, receiver) = oneshot::channel();
+            std::thread::Builder::new()
+                .name(format!("shutdown-{}", name))
+                .spawn(move || {
+                    let _ = sender.send(task());
+                })
+                .map_err(|e| format!("Failed to start: {}", e))?;
+            receiver
+                .await
+                .unwrap_or_else(|_| Err("Step panicked".to_string()))
+        })
+    }
+
+    /// Run every step, returning how each one ended
+    pub async fn run(self) -> Vec<(&'static str, ShutdownOutcome)> {
+        let deadline = Instant::now() + self.total_timeout;
+        let mut outcomes = Vec::with_capacity(self.steps.len());
+
+        for (name, task) in self.steps {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                log::warn!("[Shutdown] Skipping '{}': shutdown budget spent", name);
+                outcomes.push((name, ShutdownOutcome::Skipped));
+                continue;
+            }
+
+            let outcome = match tokio::time::timeout(self.step_timeout.min(remaining), task).await {
+                Ok(Ok(())) => ShutdownOutcome::Completed,
+                Ok(Err(e)) => ShutdownOutcome::Failed(e),
+                Err(_) => ShutdownOutcome::TimedOut,
+            };
+
+            match &outcome {
+                ShutdownOutcome::Completed => log::debug!("[Shutdown] '{}' flushed", name),
+                ShutdownOutcome::Failed(e) => log::warn!("[Shutdown] '{}' failed: {}", name, e),
+                ShutdownOutcome::TimedOut => {
+                    log::warn!("[Shutdown] '{}' timed out, continuing", name)
+                }
+                ShutdownOutcome::Skipped => {}
+            }
+            outcomes.push((name, outcome));
+        }
+
+        outcomes
+    }
+}
+
+/// Flush the state of every manager registered with the app
+pub fn flush_app_state(app: &tauri::AppHandle) {
+    let mut coordinator = ShutdownCoordinator::new(SHUTDOWN_STEP_TIMEOUT, SHUTDOWN_TOTAL_TIMEOUT);
+
+    let handle = app.clone();
+    coordinator.add_blocking_step("selection", move || {
+        handle
+            .try_state::<SelectionManager>()
+            .map_or(Ok(()), |manager| manager.shutdown())
+    });
+    let handle = app.clone();
+    coordinator.add_blocking_step("awareness", move || {
+        if let Some(manager) = handle.try_state::<AwarenessManager>() {
+            manager.shutdown();
+        }
+        Ok(())
+    });
+    let handle = app.clone();
+    coordinator.add_blocking_step("screen_recording", move || {
+        if let Some(manager) = handle.try_state::<ScreenRecordingManager>() {
+            manager.shutdown();
+        }
+        Ok(())
+    });
+    let handle = app.clone();
+    coordinator.add_blocking_step("vector_store", move || {
+        if let Some(state) = handle.try_state::<Arc<VectorStoreState>>() {
+            state.shutdown();
+        }
+        Ok(())
+    });
+    let handle = app.clone();
+    coordinator.add_blocking_step("sandbox", move || {
+        handle
+            .try_state::<SandboxState>()
+            .map_or(Ok(()), |state| state.shutdown().map_err(|e| e.to_string()))
+    });
+
+    let outcomes = tauri::async_runtime::block_on(coordinator.run());
+    let completed = outcomes
+        .iter()
+        .filter(|(_, outcome)| *outcome == ShutdownOutcome::Completed)
+        .count();
+    log::info!(
+        "[Shutdown] Flushed {}/{} manager(s)",
+        completed,
+        outcomes.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_steps_run_in_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut coordinator =
+            ShutdownCoordinator::new(Duration::from_secs(1), Duration::from_secs(5));
+        for name in ["first", "second", "third"] {
+            let order = order.clone();
+            coordinator.add_blocking_step(name, move || {
+                order.lock().unwrap().push(name);
+                if name == "second" {
+                    Err("disk full".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+        }
+
+        let outcomes = coordinator.run().await;
+        assert_eq!(*order.lock().unwrap(), vec!["first", "second", "third"]);
+        assert_eq!(
+            outcomes,
+            vec![
+                ("first", ShutdownOutcome::Completed),
+                ("second", ShutdownOutcome::Failed("disk full".to_string())),
+                ("third", ShutdownOutcome::Completed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stuck_step_times_out_and_budget_skips_rest() {
+        tokio::time::pause();
+        let mut coordinator =
+            ShutdownCoordinator::new(Duration::from_millis(50), Duration::from_millis(90));
+        coordinator
+            .add_step("stuck", async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .add_step("quick", async { Ok(()) })
+            .add_step("also_stuck", async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            })
+            .add_step("last", async { Ok(()) });
+
+        // The paused clock advances straight to each timeout: 50ms for "stuck",
+        // then the 40ms left of the budget for "also_stuck"
+        let started = Instant::now();
+        let outcomes = coordinator.run().await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert_eq!(outcomes[0], ("stuck", ShutdownOutcome::TimedOut));
+        assert_eq!(outcomes[1], ("quick", ShutdownOutcome::Completed));
+        assert_eq!(outcomes[2], ("also_stuck", ShutdownOutcome::TimedOut));
+        assert_eq!(outcomes[3], ("last", ShutdownOutcome::Skipped));
+    }
+}
@@ -138,6 +138,7 @@ impl SkillService {
 
     /// Discover skills from all enabled repositories
     pub async fn discover_skills(&self) -> Result<Vec<DiscoverableSkill>> {
+        crate::http::ensure_online().map_err(SkillError::Network)?;
         let repos = {
             let store = self.store.read().await;
             store
@@ -952,6 +953,7 @@ impl SkillService {
 
     /// Download repository to temp directory
     async fn download_repo(&self, repo: &SkillRepo) -> Result<(PathBuf, String)> {
+        crate::http::ensure_online().map_err(SkillError::Network)?;
         let temp_dir = tempfile::tempdir()?;
         let temp_path = temp_dir.path().to_path_buf();
         // Persist the temp directory by forgetting about it (keeps it around)
@@ -6,6 +6,7 @@
 mod storage;
 
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -18,6 +19,13 @@ use serde_json::Value as JsonValue;
 pub use storage::{SpeedPassRuntimeStorage, SpeedPassRuntimeStoredSnapshot};
 
 const DEFAULT_SPEEDPASS_USER_ID: &str = "local-user";
+/// Most knowledge-point titles offered to the model as match candidates
+const MAX_AI_CANDIDATE_POINTS: usize = 200;
+/// System prompt for model-assisted teacher-keypoint matching
+pub const KEYPOINT_MATCH_SYSTEM_PROMPT: &str =
+    "You match a teacher's study notes to textbook knowledge points. \
+Reply with a JSON array only, one object per note: \
+{\"note\": <note number>, \"point\": <knowledge point number or null>, \"confidence\": <0 to 1>}.";
 static TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"[\p{Han}A-Za-z0-9_]+").expect("speedpass token regex should compile")
 });
@@ -68,8 +76,13 @@ fn looks_like_pdf(extension: Option<&str>, mime_type: Option<&str>) -> bool {
 }
 
 async fn extract_pdf_content(pdf_path: &str) -> Result<String, String> {
-    let conversion =
-        crate::commands::academic::academic_extract_pdf_content(pdf_path.to_string(), None).await?;
+    let conversion = crate::commands::academic::convert_pdf(
+        pdf_path.to_string(),
+        Default::default(),
+        None,
+        |_, _| {},
+    )
+    .await?;
     if !conversion.success {
         return Err(conversion
             .error
@@ -238,6 +251,56 @@ fn round_to(value: f64, digits: i32) -> f64 {
     (value * factor).round() / factor
 }
 
+fn build_keypoint_match_prompt(notes: &[&str], knowledge_points: &[KnowledgePointView]) -> String {
+    let mut prompt = String::from("Teacher notes:\n");
+    for (index, note) in notes.iter().enumerate() {
+        prompt.push_str(&format!("{}. {}\n", index + 1, note));
+    }
+    prompt.push_str("\nKnowledge points:\n");
+    for (index, knowledge_point) in knowledge_points
+        .iter()
+        .take(MAX_AI_CANDIDATE_POINTS)
+        .enumerate()
+    {
+        prompt.push_str(&format!("{}. {}\n", index + 1, knowledge_point.title));
+    }
+    prompt
+}
+
+/// Parse the model's note-to-point mapping into `(note index, point index, confidence)`
+///
+/// Tolerates prose or code fences around the JSON array; entries with
+/// out-of-range numbers or no match are dropped.
+fn parse_keypoint_match_reply(
+    reply: &str,
+    note_count: usize,
+    point_count: usize,
+) -> Result<Vec<(usize, usize, f64)>, String> {
+    let start = reply.find('[');
+    let end = reply.rfind(']');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => return Err("Model reply contains no JSON array".to_string()),
+    };
+    let entries = serde_json::from_str::<Vec<JsonValue>>(json)
+        .map_err(|error| format!("Model reply is not a valid match list: {error}"))?;
+
+    let point_count = point_count.min(MAX_AI_CANDIDATE_POINTS);
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let object = entry.as_object()?;
+            let note = usize::try_from(json_i64(object, &["note"])?).ok()?;
+            let point = usize::try_from(json_i64(object, &["point"])?).ok()?;
+            let confidence = object.get("confidence")?.as_f64()?;
+            if note == 0 || note > note_count || point == 0 || point > point_count {
+                return None;
+            }
+            Some((note - 1, point - 1, confidence.clamp(0.0, 1.0)))
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use base64::Engine;
@@ -277,8 +340,7 @@ mod tests {
         assert!(by_bytes.content.contains("重点一"));
     }
 
-    #[test]
-    fn matches_teacher_keypoints_with_stable_rule_engine() {
+    fn keypoint_test_state() -> SpeedPassRuntimeState {
         let storage = SpeedPassRuntimeStorage::in_memory().expect("storage should init");
         let snapshot = serde_json::json!({
             "textbookChapters": {
@@ -344,23 +406,34 @@ mod tests {
         storage
             .save_snapshot(DEFAULT_SPEEDPASS_USER_ID, &snapshot, None)
             .expect("snapshot should save");
-        let state = SpeedPassRuntimeState::from_test_storage(storage);
+        SpeedPassRuntimeState::from_test_storage(storage)
+    }
+
+    fn keypoint_test_request(ai_enhance: bool) -> TeacherKeyPointMatchRequest {
+        TeacherKeyPointMatchRequest {
+            textbook_id: "tb-1".to_string(),
+            teacher_notes: vec![
+                "重点看函数极限定义与例题".to_string(),
+                "连续函数判定多刷题".to_string(),
+                "这个暂时不在教材里".to_string(),
+            ],
+            ai_enhance: Some(ai_enhance),
+            provider: Some("ollama".to_string()),
+            model: Some("qwen2.5:7b".to_string()),
+            confidence_override: Some(0.4),
+        }
+    }
+
+    #[tokio::test]
+    async fn matches_teacher_keypoints_with_stable_rule_engine() {
+        let state = keypoint_test_state();
         let result = state
             .match_teacher_keypoints(
                 Some(DEFAULT_SPEEDPASS_USER_ID.to_string()),
-                TeacherKeyPointMatchRequest {
-                    textbook_id: "tb-1".to_string(),
-                    teacher_notes: vec![
-                        "重点看函数极限定义与例题".to_string(),
-                        "连续函数判定多刷题".to_string(),
-                        "这个暂时不在教材里".to_string(),
-                    ],
-                    ai_enhance: Some(false),
-                    provider: None,
-                    model: None,
-                    confidence_override: Some(0.4),
-                },
+                keypoint_test_request(false),
+                |_, _| async { Err("provider should not be called".to_string()) },
             )
+            .await
             .expect("matching should succeed");
 
         assert_eq!(result.status, "partial");
@@ -368,6 +441,78 @@ mod tests {
         assert!(!result.unmatched_notes.is_empty());
         assert!(result.match_rate > 0.0 && result.match_rate < 1.0);
     }
+
+    #[tokio::test]
+    async fn ai_enhance_matches_notes_the_rule_engine_missed() {
+        let state = keypoint_test_state();
+        let result = state
+            .match_teacher_keypoints(
+                Some(DEFAULT_SPEEDPASS_USER_ID.to_string()),
+                keypoint_test_request(true),
+                |system_prompt, prompt| async move {
+                    assert_eq!(system_prompt, KEYPOINT_MATCH_SYSTEM_PROMPT);
+                    assert!(prompt.contains("1. 这个暂时不在教材里"));
+                    assert!(!prompt.contains("连续函数判定多刷题"));
+                    assert!(prompt.contains("2. 连续函数"));
+                    Ok(
+                        "```json\n[{\"note\": 1, \"point\": 2, \"confidence\": 0.82}]\n```"
+                            .to_string(),
+                    )
+                },
+            )
+            .await
+            .expect("matching should succeed");
+
+        assert_eq!(result.status, "success");
+        assert!(result.unmatched_notes.is_empty());
+        assert_eq!(result.match_rate, 1.0);
+        let enhanced = result
+            .matched_points
+            .iter()
+            .find(|point| point.teacher_note == "这个暂时不在教材里")
+            .expect("unmatched note should be matched by the model");
+        assert_eq!(enhanced.matched_knowledge_point["id"], "kp-2");
+        assert_eq!(enhanced.match_confidence, 0.82);
+
+        let offline = state
+            .match_teacher_keypoints(
+                Some(DEFAULT_SPEEDPASS_USER_ID.to_string()),
+                keypoint_test_request(true),
+                |_, _| async { Err("connection refused".to_string()) },
+            )
+            .await
+            .expect("matching should fall back to the rule engine");
+        assert_eq!(offline.status, "offline_fallback");
+        assert_eq!(
+            offline.unmatched_notes,
+            vec!["这个暂时不在教材里".to_string()]
+        );
+
+        let mut unplaceable = keypoint_test_request(true);
+        unplaceable.teacher_notes = vec!["这个暂时不在教材里".to_string()];
+        let nothing_matched = state
+            .match_teacher_keypoints(
+                Some(DEFAULT_SPEEDPASS_USER_ID.to_string()),
+                unplaceable,
+                |_, _| async { Err("connection refused".to_string()) },
+            )
+            .await
+            .expect("matching should fall back to the rule engine");
+        assert!(nothing_matched.matched_points.is_empty());
+        assert_eq!(nothing_matched.status, "offline_fallback");
+    }
+
+    #[test]
+    fn parses_lenient_keypoint_match_replies() {
+        let reply = "Here you go: [{\"note\": 1, \"point\": 3, \"confidence\": 1.4}, \
+            {\"note\": 2, \"point\": null, \"confidence\": 0.9}, \
+            {\"note\": 3, \"point\": 9, \"confidence\": 0.7}]";
+        assert_eq!(
+            parse_keypoint_match_reply(reply, 3, 4).unwrap(),
+            vec![(0, 2, 1.0)]
+        );
+        assert!(parse_keypoint_match_reply("no idea", 1, 1).is_err());
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -734,11 +879,22 @@ impl SpeedPassRuntimeState {
         Err("Either filePath or fileBytesBase64 is required".to_string())
     }
 
-    pub fn match_teacher_keypoints(
+    /// Match teacher notes to textbook knowledge points
+    ///
+    /// Notes are scored by the offline rule engine. With `aiEnhance`, notes it
+    /// cannot place are sent to `enhance` (system prompt, prompt), which returns
+    /// the model's reply; when that fails the rule-engine result is returned with
+    /// status `offline_fallback`.
+    pub async fn match_teacher_keypoints<F, Fut>(
         &self,
         user_id: Option<String>,
         request: TeacherKeyPointMatchRequest,
-    ) -> Result<TeacherKeyPointMatchResult, String> {
+        enhance: F,
+    ) -> Result<TeacherKeyPointMatchResult, String>
+    where
+        F: FnOnce(&'static str, String) -> Fut,
+        Fut: Future<Output = Result<String, String>>,
+    {
         let normalized_user_id = normalize_user_id(user_id.as_deref());
         let snapshot = self
             .storage
@@ -784,14 +940,6 @@ impl SpeedPassRuntimeState {
 
         let threshold = request.confidence_override.unwrap_or(0.45).clamp(0.1, 0.95);
 
-        if request.ai_enhance.unwrap_or(false) {
-            log::info!(
-                "[speedpass-runtime] aiEnhance requested but currently using offline rule engine (provider={:?}, model={:?})",
-                request.provider,
-                request.model
-            );
-        }
-
         let chapter_by_id = chapters
             .iter()
             .filter(|chapter| !chapter.id.is_empty())
@@ -811,34 +959,77 @@ impl SpeedPassRuntimeState {
             })
             .collect::<HashMap<_, _>>();
 
-        let mut matched_points = Vec::new();
-        let mut unmatched_notes = Vec::new();
-        let mut chapter_numbers = HashSet::<i64>::new();
-        let mut total_examples = HashSet::<String>::new();
-        let mut total_exercises = HashSet::<String>::new();
-
-        for note in request
+        let notes = request
             .teacher_notes
             .iter()
             .map(|note| note.trim())
             .filter(|note| !note.is_empty())
-        {
-            let note_tokens = tokenize(note);
-            let mut best: Option<(usize, f64)> = None;
-            for (index, knowledge_point) in knowledge_points.iter().enumerate() {
-                let chapter = chapter_by_id.get(&knowledge_point.chapter_id);
-                let order = *chapter_order.get(&knowledge_point.chapter_id).unwrap_or(&1);
-                let score =
-                    calculate_match_score(note, &note_tokens, knowledge_point, chapter, order);
-                if let Some((_, best_score)) = best {
-                    if score > best_score {
+            .collect::<Vec<_>>();
+        let mut best_matches = notes
+            .iter()
+            .map(|note| {
+                let note_tokens = tokenize(note);
+                let mut best: Option<(usize, f64)> = None;
+                for (index, knowledge_point) in knowledge_points.iter().enumerate() {
+                    let chapter = chapter_by_id.get(&knowledge_point.chapter_id);
+                    let order = *chapter_order.get(&knowledge_point.chapter_id).unwrap_or(&1);
+                    let score =
+                        calculate_match_score(note, &note_tokens, knowledge_point, chapter, order);
+                    if let Some((_, best_score)) = best {
+                        if score > best_score {
+                            best = Some((index, score));
+                        }
+                    } else {
                         best = Some((index, score));
                     }
-                } else {
-                    best = Some((index, score));
+                }
+                best
+            })
+            .collect::<Vec<_>>();
+
+        // Offer notes the rule engine could not place to the model; its matches
+        // replace rule matches only where they are more confident.
+        let mut offline_fallback = false;
+        let pending = (0..notes.len())
+            .filter(|index| !matches!(best_matches[*index], Some((_, score)) if score >= threshold))
+            .collect::<Vec<_>>();
+        if request.ai_enhance.unwrap_or(false) && !pending.is_empty() {
+            let pending_notes = pending
+                .iter()
+                .map(|index| notes[*index])
+                .collect::<Vec<_>>();
+            let prompt = build_keypoint_match_prompt(&pending_notes, &knowledge_points);
+            let reply = enhance(KEYPOINT_MATCH_SYSTEM_PROMPT, prompt).await;
+            match reply.and_then(|reply| {
+                parse_keypoint_match_reply(&reply, pending.len(), knowledge_points.len())
+            }) {
+                Ok(ai_matches) => {
+                    for (pending_index, point_index, confidence) in ai_matches {
+                        let best = &mut best_matches[pending[pending_index]];
+                        if best.map_or(true, |(_, score)| confidence > score) {
+                            *best = Some((point_index, confidence));
+                        }
+                    }
+                }
+                Err(error) => {
+                    log::warn!(
+                        "[speedpass-runtime] aiEnhance failed, using offline rule engine (provider={:?}, model={:?}): {}",
+                        request.provider,
+                        request.model,
+                        error
+                    );
+                    offline_fallback = true;
                 }
             }
+        }
+
+        let mut matched_points = Vec::new();
+        let mut unmatched_notes = Vec::new();
+        let mut chapter_numbers = HashSet::<i64>::new();
+        let mut total_examples = HashSet::<String>::new();
+        let mut total_exercises = HashSet::<String>::new();
 
+        for (note, best) in notes.iter().copied().zip(best_matches) {
             let (best_index, best_score) = match best {
                 Some(value) if value.1 >= threshold => value,
                 _ => {
@@ -948,7 +1139,10 @@ impl SpeedPassRuntimeState {
             });
         }
 
-        let status = if matched_points.is_empty() {
+        // A failed provider is reported even when the rule engine matched nothing
+        let status = if offline_fallback {
+            "offline_fallback"
+        } else if matched_points.is_empty() {
             "failed"
         } else if unmatched_notes.is_empty() {
             "success"
@@ -959,11 +1153,7 @@ impl SpeedPassRuntimeState {
             (matched_points.len() * 18 + total_examples.len() * 5 + total_exercises.len() * 7)
                 .max(20);
         let estimated_hours = round_to(estimated_minutes as f64 / 60.0, 1);
-        let total_notes = request
-            .teacher_notes
-            .iter()
-            .filter(|note| !note.trim().is_empty())
-            .count();
+        let total_notes = notes.len();
         let match_rate = if total_notes == 0 {
             0.0
         } else {
@@ -0,0 +1,453 @@
+//! Text diffing
+//!
+//! One LCS-based diff shared by every feature that compares text (completion
+//! rewrites, execution comparison, snippet versions). Text is split into lines,
+//! words or characters and the edit script is returned either as inline segments
+//! or, in unified mode, as line hunks with surrounding context.
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on the LCS table size; larger edits are reported as a single
+/// delete/insert pair instead of a token-level diff.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// Unchanged lines shown around each change in unified mode
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Granularity of a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffMode {
+    /// Line hunks with context, like `diff -u`
+    Unified,
+    Line,
+    Word,
+    Char,
+}
+
+/// Operation of a single diff segment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    /// Text kept from the original
+    Equal,
+    /// Text added by the new version
+    Insert,
+    /// Text removed from the original
+    Delete,
+}
+
+/// A contiguous run of text with the same diff operation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// A line of a unified hunk
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub op: DiffOp,
+    /// Line content without its line ending
+    pub text: String,
+    /// 1-based line number in the original, for equal and deleted lines
+    pub old_line: Option<usize>,
+    /// 1-based line number in the new text, for equal and inserted lines
+    pub new_line: Option<usize>,
+}
+
+/// A group of nearby changed lines with their context
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    /// `@@ -old_start,old_lines +new_start,new_lines @@`
+    pub header: String,
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Diff of two texts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDiff {
+    pub mode: DiffMode,
+    pub identical: bool,
+    /// Inserted lines, words or characters, depending on the mode
+    pub insertions: usize,
+    /// Deleted lines, words or characters, depending on the mode
+    pub deletions: usize,
+    /// Inline segments; empty in unified mode
+    pub segments: Vec<DiffSegment>,
+    /// Line hunks; only filled in unified mode
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// Diff `old` against `new`
+///
+/// `context` is the number of unchanged lines kept around each unified hunk and
+/// is ignored by the other modes.
+pub fn diff(old: &str, new: &str, mode: DiffMode, context: usize) -> TextDiff {
+    let (old_tokens, new_tokens) = match mode {
+        DiffMode::Unified | DiffMode::Line => (split_lines(old), split_lines(new)),
+        DiffMode::Word => (split_words(old), split_words(new)),
+        DiffMode::Char => (split_chars(old), split_chars(new)),
+    };
+    let edits = diff_tokens(&old_tokens, &new_tokens);
+    let count = |op: DiffOp| edits.iter().filter(|(edit_op, _)| *edit_op == op).count();
+
+    let (segments, hunks) = if mode == DiffMode::Unified {
+        (Vec::new(), build_hunks(&edits, context))
+    } else {
+        (merge_segments(&edits), Vec::new())
+    };
+    TextDiff {
+        mode,
+        identical: old == new,
+        insertions: count(DiffOp::Insert),
+        deletions: count(DiffOp::Delete),
+        segments,
+        hunks,
+    }
+}
+
+/// Word-level diff from `original` to `replacement`
+///
+/// Text is split into words, whitespace runs and single punctuation characters,
+/// and adjacent segments with the same operation are merged. Concatenating the
+/// `Equal` and `Delete` segments yields `original`; `Equal` and `Insert` yield
+/// `replacement`.
+pub fn diff_words(original: &str, replacement: &str) -> Vec<DiffSegment> {
+    merge_segments(&diff_tokens(
+        &split_words(original),
+        &split_words(replacement),
+    ))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TokenClass {
+    Word,
+    Whitespace,
+    Punctuation,
+}
+
+impl TokenClass {
+    fn of(ch: char) -> Self {
+        if ch.is_alphanumeric() || ch == '_' {
+            Self::Word
+        } else if ch.is_whitespace() {
+            Self::Whitespace
+        } else {
+            Self::Punctuation
+        }
+    }
+}
+
+/// Lines including their line ending, so the tokens concatenate back to the text
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+fn split_words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<TokenClass> = None;
+
+    for (idx, ch) in text.char_indices() {
+        let class = TokenClass::of(ch);
+        if let Some(previous) = previous {
+            if previous != class || class == TokenClass::Punctuation {
+                tokens.push(&text[start..idx]);
+                start = idx;
+            }
+        }
+        previous = Some(class);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn split_chars(text: &str) -> Vec<&str> {
+    text.char_indices()
+        .map(|(idx, ch)| &text[idx..idx + ch.len_utf8()])
+        .collect()
+}
+
+/// Token-level edit script turning `old` into `new`
+fn diff_tokens<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut edits: Vec<(DiffOp, &'a str)> = old[..prefix]
+        .iter()
+        .map(|token| (DiffOp::Equal, *token))
+        .collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        edits.extend(old_mid.iter().map(|token| (DiffOp::Delete, *token)));
+        edits.extend(new_mid.iter().map(|token| (DiffOp::Insert, *token)));
+    } else {
+        diff_lcs(old_mid, new_mid, &mut edits);
+    }
+    edits.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|token| (DiffOp::Equal, *token)),
+    );
+    edits
+}
+
+fn diff_lcs<'a>(old: &[&'a str], new: &[&'a str], edits: &mut Vec<(DiffOp, &'a str)>) {
+    let width = new.len() + 1;
+    // lcs[i * width + j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push((DiffOp::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            edits.push((DiffOp::Delete, old[i]));
+            i += 1;
+        } else {
+            edits.push((DiffOp::Insert, new[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[i..].iter().map(|token| (DiffOp::Delete, *token)));
+    edits.extend(new[j..].iter().map(|token| (DiffOp::Insert, *token)));
+}
+
+fn merge_segments(edits: &[(DiffOp, &str)]) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for (op, text) in edits {
+        match segments.last_mut() {
+            Some(last) if last.op == *op => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                op: *op,
+                text: text.to_string(),
+            }),
+        }
+    }
+    segments
+}
+
+/// Group line edits into hunks, keeping `context` unchanged lines around changes
+fn build_hunks(edits: &[(DiffOp, &str)], context: usize) -> Vec<DiffHunk> {
+    // Number every line on both sides
+    let mut lines = Vec::with_capacity(edits.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for (op, text) in edits {
+        let (old_no, new_no) = match op {
+            DiffOp::Equal => {
+                old_line += 1;
+                new_line += 1;
+                (Some(old_line), Some(new_line))
+            }
+            DiffOp::Delete => {
+                old_line += 1;
+                (Some(old_line), None)
+            }
+            DiffOp::Insert => {
+                new_line += 1;
+                (None, Some(new_line))
+            }
+        };
+        lines.push(DiffLine {
+            op: *op,
+            text: text.trim_end_matches(['\n', '\r']).to_string(),
+            old_line: old_no,
+            new_line: new_no,
+        });
+    }
+
+    // Index ranges of lines to show, merging ranges whose context overlaps
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if line.op == DiffOp::Equal {
+            continue;
+        }
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let hunk_lines = lines[start..end].to_vec();
+            let old_lines = hunk_lines.iter().filter(|l| l.old_line.is_some()).count();
+            let new_lines = hunk_lines.iter().filter(|l| l.new_line.is_some()).count();
+            // Empty sides start at the line before the hunk, as in `diff -u`
+            let old_start = first_line(&lines, start, |l| l.old_line);
+            let new_start = first_line(&lines, start, |l| l.new_line);
+            let old_start = if old_lines == 0 {
+                old_start - 1
+            } else {
+                old_start
+            };
+            let new_start = if new_lines == 0 {
+                new_start - 1
+            } else {
+                new_start
+            };
+            DiffHunk {
+                header: format!(
+                    "@@ -{},{} +{},{} @@",
+                    old_start, old_lines, new_start, new_lines
+                ),
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: hunk_lines,
+            }
+        })
+        .collect()
+}
+
+/// Number of the first line at or after `idx` on one side, or one past the last line
+fn first_line(lines: &[DiffLine], idx: usize, side: impl Fn(&DiffLine) -> Option<usize>) -> usize {
+    lines[idx..]
+        .iter()
+        .find_map(&side)
+        .or_else(|| lines[..idx].iter().rev().find_map(&side).map(|n| n + 1))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rebuild(segments: &[DiffSegment], skip: DiffOp) -> String {
+        segments
+            .iter()
+            .filter(|segment| segment.op != skip)
+            .map(|segment| segment.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_identical_text() {
+        let diff = diff_words("hello world", "hello world");
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].op, DiffOp::Equal);
+    }
+
+    #[test]
+    fn test_diff_replaced_word() {
+        let diff = diff_words("I has a cat.", "I have a cat.");
+        let ops: Vec<DiffOp> = diff.iter().map(|segment| segment.op).collect();
+        assert_eq!(
+            ops,
+            vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Insert, DiffOp::Equal]
+        );
+        assert_eq!(diff[1].text, "has");
+        assert_eq!(diff[2].text, "have");
+    }
+
+    #[test]
+    fn test_diff_round_trips_both_sides() {
+        let original = "let x = foo(a, b);";
+        let replacement = "let result = foo(a, b, c);";
+        for mode in [DiffMode::Line, DiffMode::Word, DiffMode::Char] {
+            let diff = diff(original, replacement, mode, DEFAULT_CONTEXT_LINES);
+            assert_eq!(rebuild(&diff.segments, DiffOp::Insert), original);
+            assert_eq!(rebuild(&diff.segments, DiffOp::Delete), replacement);
+        }
+    }
+
+    #[test]
+    fn test_diff_empty_sides() {
+        let inserted = diff_words("", "new text");
+        assert_eq!(inserted.len(), 1);
+        assert_eq!(inserted[0].op, DiffOp::Insert);
+
+        let deleted = diff_words("old text", "");
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].op, DiffOp::Delete);
+    }
+
+    #[test]
+    fn test_diff_handles_multibyte_text() {
+        let diff_w = diff_words("你好，世界", "你好，朋友");
+        assert_eq!(rebuild(&diff_w, DiffOp::Insert), "你好，世界");
+        assert_eq!(rebuild(&diff_w, DiffOp::Delete), "你好，朋友");
+
+        let diff_c = diff("naïve café", "naive cafe", DiffMode::Char, 0);
+        assert_eq!(diff_c.deletions, 2);
+        assert_eq!(diff_c.insertions, 2);
+        assert_eq!(rebuild(&diff_c.segments, DiffOp::Insert), "naïve café");
+    }
+
+    #[test]
+    fn test_line_mode_counts_lines() {
+        let diff = diff("a\nb\nc\n", "a\nB\nc\nd\n", DiffMode::Line, 0);
+        assert!(!diff.identical);
+        assert_eq!(diff.deletions, 1);
+        assert_eq!(diff.insertions, 2);
+        assert!(diff.hunks.is_empty());
+        assert_eq!(diff.segments[0].text, "a\n");
+    }
+
+    #[test]
+    fn test_unified_hunks_with_context() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old
+            .replace("line 3\n", "line three\n")
+            .replace("line 18\n", "");
+        let diff = diff(&old, &new, DiffMode::Unified, 2);
+
+        assert!(diff.segments.is_empty());
+        assert_eq!(diff.hunks.len(), 2);
+
+        let first = &diff.hunks[0];
+        assert_eq!(first.header, "@@ -1,5 +1,5 @@");
+        assert_eq!(first.lines[2].op, DiffOp::Delete);
+        assert_eq!(first.lines[2].text, "line 3");
+        assert_eq!(first.lines[2].old_line, Some(3));
+        assert_eq!(first.lines[3].op, DiffOp::Insert);
+        assert_eq!(first.lines[3].new_line, Some(3));
+
+        let second = &diff.hunks[1];
+        assert_eq!(second.header, "@@ -16,5 +16,4 @@");
+        assert_eq!(second.lines.len(), 5);
+    }
+
+    #[test]
+    fn test_unified_identical_and_pure_insert() {
+        let same = diff("a\nb\n", "a\nb\n", DiffMode::Unified, 3);
+        assert!(same.identical);
+        assert!(same.hunks.is_empty());
+
+        let added = diff("", "x\ny\n", DiffMode::Unified, 3);
+        assert_eq!(added.hunks.len(), 1);
+        assert_eq!(added.hunks[0].header, "@@ -0,0 +1,2 @@");
+    }
+}
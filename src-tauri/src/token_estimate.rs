@@ -0,0 +1,331 @@
+//! Token estimation
+//!
+//! Fast, local token counts for budgeting prompts before they are sent. No
+//! vocabulary is loaded: OpenAI models get a tiktoken-style estimate that walks
+//! the same pre-tokenization pieces (words, digit groups, punctuation runs,
+//! whitespace), other providers a character-class heuristic that counts CJK text
+//! per character rather than per word.
+//!
+//! Estimates are approximate. On English prose and source code the OpenAI
+//! estimate is usually within 10% of the real tokenizer and the character-class
+//! heuristic within 20%; `TokenEstimate::margin` carries the bound for the
+//! method used so callers can budget against `max_tokens`.
+
+use serde::{Deserialize, Serialize};
+
+/// Relative error bound of the tiktoken-style estimate
+const OPENAI_MARGIN: f64 = 0.10;
+/// Relative error bound of the character-class heuristic
+const HEURISTIC_MARGIN: f64 = 0.20;
+
+/// Characters of non-CJK, non-ASCII letters (Cyrillic, Greek, accents) per token
+const OTHER_CHARS_PER_TOKEN: f64 = 2.0;
+
+/// Tokenizer family a provider/model pair is estimated with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenizerFamily {
+    OpenAi,
+    Anthropic,
+    Gemini,
+    Generic,
+}
+
+impl TokenizerFamily {
+    /// Pick the family from the model name when it is recognizable, else from the provider
+    ///
+    /// Model names win so routed models (e.g. `openai/gpt-4o` via OpenRouter) use
+    /// their own tokenizer.
+    pub fn resolve(provider: &str, model: Option<&str>) -> Self {
+        if let Some(family) = model.and_then(Self::from_model) {
+            return family;
+        }
+        match provider.trim().to_lowercase().as_str() {
+            "openai" | "azure" | "azure-openai" => TokenizerFamily::OpenAi,
+            "anthropic" | "claude" => TokenizerFamily::Anthropic,
+            "google" | "gemini" | "vertex" => TokenizerFamily::Gemini,
+            _ => TokenizerFamily::Generic,
+        }
+    }
+
+    fn from_model(model: &str) -> Option<Self> {
+        let model = model_name(model);
+        if is_openai_model(&model) {
+            Some(TokenizerFamily::OpenAi)
+        } else if model.starts_with("claude") {
+            Some(TokenizerFamily::Anthropic)
+        } else if model.starts_with("gemini") {
+            Some(TokenizerFamily::Gemini)
+        } else {
+            None
+        }
+    }
+}
+
+/// Estimated token count of a text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenEstimate {
+    /// Estimated number of tokens
+    pub tokens: usize,
+    pub family: TokenizerFamily,
+    /// Relative error bound of the estimate (0.1 = ±10%)
+    pub margin: f64,
+    /// Lower bound of the likely token count
+    pub min_tokens: usize,
+    /// Upper bound of the likely token count
+    pub max_tokens: usize,
+    /// Number of characters in the text
+    pub characters: usize,
+}
+
+/// Estimate how many tokens `text` uses with the given provider and model
+pub fn estimate_tokens(text: &str, provider: &str, model: Option<&str>) -> TokenEstimate {
+    let family = TokenizerFamily::resolve(provider, model);
+    let (raw, margin) = match family {
+        TokenizerFamily::OpenAi => {
+            let cjk_weight = match model {
+                Some(model) if !uses_o200k(&model_name(model)) => 1.3,
+                _ => 1.0,
+            };
+            (estimate_openai(text, cjk_weight), OPENAI_MARGIN)
+        }
+        TokenizerFamily::Anthropic => (estimate_by_class(text, 3.5, 1.2), HEURISTIC_MARGIN),
+        TokenizerFamily::Gemini => (estimate_by_class(text, 4.0, 0.9), HEURISTIC_MARGIN),
+        TokenizerFamily::Generic => (estimate_by_class(text, 3.5, 1.0), HEURISTIC_MARGIN),
+    };
+
+    let tokens = raw.ceil() as usize;
+    TokenEstimate {
+        tokens,
+        family,
+        margin,
+        min_tokens: (tokens as f64 * (1.0 - margin)).floor() as usize,
+        max_tokens: (tokens as f64 * (1.0 + margin)).ceil() as usize,
+        characters: text.chars().count(),
+    }
+}
+
+/// Lowercased model name without a routing prefix such as `openai/`
+fn model_name(model: &str) -> String {
+    let model = model.trim().to_lowercase();
+    match model.rsplit_once('/') {
+        Some((_, name)) => name.to_string(),
+        None => model,
+    }
+}
+
+fn is_openai_model(model: &str) -> bool {
+    [
+        "gpt-",
+        "chatgpt",
+        "o1",
+        "o3",
+        "o4",
+        "text-embedding",
+        "davinci",
+        "babbage",
+    ]
+    .iter()
+    .any(|prefix| model.starts_with(prefix))
+}
+
+/// Whether the model uses the o200k encoding, which packs CJK text more tightly than cl100k
+fn uses_o200k(model: &str) -> bool {
+    [
+        "gpt-4o", "gpt-4.1", "gpt-4.5", "gpt-5", "chatgpt", "o1", "o3", "o4",
+    ]
+    .iter()
+    .any(|prefix| model.starts_with(prefix))
+}
+
+/// Han ideographs, kana, Hangul, CJK punctuation and full-width forms
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3000..=0x30FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0xFF00..=0xFFEF
+            | 0x20000..=0x2FFFF
+    )
+}
+
+/// Count a text by character class
+fn estimate_by_class(text: &str, chars_per_token: f64, cjk_weight: f64) -> f64 {
+    let (mut ascii, mut cjk, mut other) = (0usize, 0usize, 0usize);
+    for c in text.chars() {
+        if c.is_ascii() {
+            ascii += 1;
+        } else if is_cjk(c) {
+            cjk += 1;
+        } else {
+            other += 1;
+        }
+    }
+    ascii as f64 / chars_per_token + cjk as f64 * cjk_weight + other as f64 / OTHER_CHARS_PER_TOKEN
+}
+
+/// Walk the pieces tiktoken's pre-tokenizer produces and estimate each one
+fn estimate_openai(text: &str, cjk_weight: f64) -> f64 {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0.0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if is_cjk(c) {
+            tokens += cjk_weight;
+            i += 1;
+        } else if c.is_alphabetic() {
+            while i < chars.len() && chars[i].is_alphabetic() && !is_cjk(chars[i]) {
+                i += 1;
+            }
+            tokens += word_tokens(&chars[start..i]);
+        } else if c.is_numeric() {
+            while i < chars.len() && chars[i].is_numeric() {
+                i += 1;
+            }
+            // Numbers are split into groups of up to three digits
+            tokens += (i - start).div_ceil(3) as f64;
+        } else if c.is_whitespace() {
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            // A single space merges into the following word; other runs are one token
+            if i - start > 1 || c != ' ' {
+                tokens += 1.0;
+            }
+        } else {
+            while i < chars.len()
+                && !chars[i].is_alphanumeric()
+                && !chars[i].is_whitespace()
+                && !is_cjk(chars[i])
+            {
+                i += 1;
+            }
+            tokens += (i - start).div_ceil(2) as f64;
+        }
+    }
+
+    tokens
+}
+
+/// Tokens of one word, split at camelCase boundaries
+///
+/// Common words up to about nine letters are a single token; longer or rarer
+/// words break into pieces of roughly six letters.
+fn word_tokens(word: &[char]) -> f64 {
+    let mut tokens = 0.0;
+    let mut ascii_run = 0usize;
+    let mut other = 0usize;
+
+    let flush = |run: &mut usize, tokens: &mut f64| {
+        if *run > 0 {
+            *tokens += (1 + run.saturating_sub(4) / 6) as f64;
+            *run = 0;
+        }
+    };
+
+    for (index, &c) in word.iter().enumerate() {
+        if !c.is_ascii() {
+            other += 1;
+            continue;
+        }
+        let camel_boundary =
+            index > 0 && c.is_ascii_uppercase() && word[index - 1].is_ascii_lowercase();
+        if camel_boundary {
+            flush(&mut ascii_run, &mut tokens);
+        }
+        ascii_run += 1;
+    }
+    flush(&mut ascii_run, &mut tokens);
+
+    tokens + (other as f64 / OTHER_CHARS_PER_TOKEN).ceil()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_family() {
+        assert_eq!(
+            TokenizerFamily::resolve("openai", None),
+            TokenizerFamily::OpenAi
+        );
+        assert_eq!(
+            TokenizerFamily::resolve("openrouter", Some("openai/gpt-4o-mini")),
+            TokenizerFamily::OpenAi
+        );
+        assert_eq!(
+            TokenizerFamily::resolve("openrouter", Some("anthropic/claude-3.5-sonnet")),
+            TokenizerFamily::Anthropic
+        );
+        assert_eq!(
+            TokenizerFamily::resolve("Google", Some("custom-tuned")),
+            TokenizerFamily::Gemini
+        );
+        assert_eq!(
+            TokenizerFamily::resolve("ollama", Some("llama3.1")),
+            TokenizerFamily::Generic
+        );
+    }
+
+    #[test]
+    fn test_empty_text() {
+        let estimate = estimate_tokens("", "openai", None);
+        assert_eq!(estimate.tokens, 0);
+        assert_eq!(estimate.max_tokens, 0);
+        assert_eq!(estimate_tokens("", "ollama", None).tokens, 0);
+    }
+
+    #[test]
+    fn test_openai_english_within_margin() {
+        // cl100k_base: 10 tokens
+        let estimate = estimate_tokens(
+            "The quick brown fox jumps over the lazy dog.",
+            "openai",
+            Some("gpt-4"),
+        );
+        assert_eq!(estimate.family, TokenizerFamily::OpenAi);
+        assert!(estimate.min_tokens <= 10 && 10 <= estimate.max_tokens);
+    }
+
+    #[test]
+    fn test_openai_code_splits_identifiers() {
+        assert_eq!(
+            word_tokens(&"getElementById".chars().collect::<Vec<_>>()),
+            4.0
+        );
+        assert_eq!(
+            word_tokens(&"internationalization".chars().collect::<Vec<_>>()),
+            3.0
+        );
+        // 123456789 -> "123" "456" "789"
+        assert_eq!(estimate_openai("123456789", 1.0), 3.0);
+        // Indentation and newlines are single tokens
+        assert_eq!(estimate_openai("a\n    b", 1.0), 3.0);
+    }
+
+    #[test]
+    fn test_cjk_counts_per_character() {
+        let text = "你好世界，今天天气很好";
+        let chars = text.chars().count();
+
+        let generic = estimate_tokens(text, "ollama", None);
+        assert_eq!(generic.tokens, chars);
+        assert_eq!(generic.characters, chars);
+
+        let o200k = estimate_tokens(text, "openai", Some("gpt-4o"));
+        let cl100k = estimate_tokens(text, "openai", Some("gpt-3.5-turbo"));
+        assert_eq!(o200k.tokens, chars);
+        assert!(cl100k.tokens > o200k.tokens);
+
+        // A word-based estimate would count this as one or two tokens
+        assert!(estimate_tokens(text, "anthropic", None).tokens >= chars);
+    }
+}
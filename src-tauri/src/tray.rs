@@ -928,6 +928,7 @@ pub fn handle_tray_menu_event(app: &AppHandle, item_id: String) {
             log::info!("Clear clipboard history requested from tray");
             if let Some(manager) = app.try_state::<SelectionManager>() {
                 manager.clipboard_history.clear_all();
+                manager.semantic_history.forget_removed();
                 log::info!("Clipboard history cleared");
             }
             let _ = app.emit("clipboard-history-cleared", ());
@@ -84,6 +84,7 @@ pub(crate) async fn execute_webhook_step(
     let rendered_url = render_template(url_template, input);
     let allow_internal = step.allow_internal_network.unwrap_or(false);
     let parsed_url = validate_webhook_url(&rendered_url, allow_internal)?;
+    crate::http::ensure_url_allowed(parsed_url.as_str())?;
 
     let method_name = step
         .method
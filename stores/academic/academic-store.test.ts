@@ -221,6 +221,37 @@ describe('useAcademicStore', () => {
       expect(mockInvoke).toHaveBeenCalledWith('academic_update_paper', expect.any(Object));
     });
 
+    it('should update reading progress', async () => {
+      const libraryPaper = createMockLibraryPaper('1');
+      mockInvoke.mockResolvedValueOnce(libraryPaper);
+
+      const { result } = renderHook(() => useAcademicStore());
+
+      await act(async () => {
+        await result.current.addToLibrary(createMockPaper('1'));
+      });
+
+      const updatedPaper = {
+        ...libraryPaper,
+        readingStatus: 'reading' as const,
+        readingProgress: 40,
+        lastPageRead: 6,
+        readingTimeSecs: 300,
+      };
+      mockInvoke.mockResolvedValueOnce(updatedPaper);
+
+      const progress = { progressPercent: 40, lastPageRead: 6, addReadingTimeSecs: 300 };
+      await act(async () => {
+        await result.current.updateReadingProgress('1', progress);
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('academic_update_reading_progress', {
+        paperId: '1',
+        progress,
+      });
+      expect(result.current.library.papers['1'].readingTimeSecs).toBe(300);
+    });
+
     it('should refresh library', async () => {
       const papers = [createMockLibraryPaper('1'), createMockLibraryPaper('2')];
       mockInvoke.mockResolvedValueOnce(papers);
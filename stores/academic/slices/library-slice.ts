@@ -9,6 +9,7 @@ import type {
   LibraryPaper,
   PaperReadingStatus,
   PaperAnalysisResult,
+  ReadingProgressUpdate,
 } from '@/types/academic';
 import type { AcademicSliceCreator } from '../types';
 
@@ -48,6 +49,7 @@ export interface LibraryActions {
   addToLibrary: (paper: Paper, collectionId?: string) => Promise<LibraryPaper>;
   removeFromLibrary: (paperId: string) => Promise<void>;
   updatePaper: (paperId: string, updates: Partial<LibraryPaper>) => Promise<void>;
+  updateReadingProgress: (paperId: string, progress: ReadingProgressUpdate) => Promise<void>;
   getPaper: (paperId: string) => Promise<LibraryPaper | null>;
   refreshLibrary: () => Promise<void>;
   addTag: (paperId: string, tag: string) => Promise<void>;
@@ -195,6 +197,25 @@ export const createLibrarySlice: AcademicSliceCreator<LibraryActions> = (set, ge
       await get().updatePaper(paperId, { tags: newTags });
     },
 
+    updateReadingProgress: async (paperId, progress) => {
+      try {
+        const updated = await academicRuntimeInvoke<LibraryPaper>(
+          'academic_update_reading_progress',
+          { paperId, progress }
+        );
+
+        set((state) => ({
+          library: {
+            ...state.library,
+            papers: { ...state.library.papers, [paperId]: updated },
+          },
+        }));
+      } catch (error) {
+        set({ error: error instanceof Error ? error.message : String(error) });
+        throw error;
+      }
+    },
+
     batchUpdateStatus: async (paperIds, status) => {
       set({ isLoading: true, error: null });
       try {
@@ -236,6 +236,7 @@ describe('useProcessStore', () => {
         onlyTerminateOwn: true,
         maxTrackedProcesses: 100,
         defaultTimeoutSecs: 30,
+        minRefreshIntervalMs: 2000,
       });
     });
 
@@ -9,4 +9,5 @@ export const DEFAULT_PROCESS_CONFIG: ProcessManagerConfig = {
   onlyTerminateOwn: true,
   maxTrackedProcesses: 100,
   defaultTimeoutSecs: 30,
+  minRefreshIntervalMs: 2000,
 };
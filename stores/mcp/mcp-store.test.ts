@@ -193,6 +193,47 @@ describe('useMcpStore', () => {
     });
   });
 
+  describe('importConfig', () => {
+    it('imports a standard config and reloads servers', async () => {
+      const report = { imported: ['filesystem'], skipped: [], autoConnectDisabled: [] };
+      mockInvoke.mockResolvedValueOnce(report); // mcp_import_config
+      mockInvoke.mockResolvedValueOnce([]); // loadServers
+
+      const json = '{"mcpServers":{"filesystem":{"command":"npx"}}}';
+      let result;
+      await act(async () => {
+        result = await useMcpStore.getState().importConfig(json, true);
+      });
+
+      expect(mockInvoke).toHaveBeenCalledWith('mcp_import_config', { json, autoConnect: true });
+      expect(mockInvoke).toHaveBeenCalledWith('mcp_get_servers');
+      expect(result).toEqual(report);
+    });
+
+    it('skips reloading when nothing was imported', async () => {
+      mockInvoke.mockResolvedValueOnce({
+        imported: [],
+        skipped: [{ name: 'filesystem', reason: 'A server with this name already exists' }],
+        autoConnectDisabled: [],
+      });
+
+      await useMcpStore.getState().importConfig('{"mcpServers":{}}');
+
+      expect(mockInvoke).toHaveBeenCalledTimes(1);
+    });
+  });
+
+  describe('exportConfig', () => {
+    it('returns the standard config document', async () => {
+      mockInvoke.mockResolvedValueOnce('{"mcpServers":{}}');
+
+      const json = await useMcpStore.getState().exportConfig();
+
+      expect(mockInvoke).toHaveBeenCalledWith('mcp_export_config');
+      expect(json).toBe('{"mcpServers":{}}');
+    });
+  });
+
   describe('removeServer', () => {
     it('removes a server', async () => {
       mockInvoke.mockResolvedValueOnce(undefined); // mcp_remove_server
@@ -8,6 +8,7 @@ import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import type {
   McpServerState,
   McpServerConfig,
+  McpImportReport,
   McpTool,
   ToolCallResult,
   ResourceContent,
@@ -77,6 +78,8 @@ interface McpState {
   loadServers: () => Promise<void>;
   addServer: (id: string, config: McpServerConfig) => Promise<void>;
   removeServer: (id: string) => Promise<void>;
+  importConfig: (json: string, autoConnect?: boolean) => Promise<McpImportReport>;
+  exportConfig: () => Promise<string>;
   updateServer: (id: string, config: McpServerConfig) => Promise<void>;
   connectServer: (id: string) => Promise<void>;
   disconnectServer: (id: string) => Promise<void>;
@@ -330,6 +333,23 @@ export const useMcpStore = create<McpState>((set, get) => ({
     }
   },
 
+  importConfig: async (json, autoConnect = false) => {
+    try {
+      const report = await invoke<McpImportReport>('mcp_import_config', { json, autoConnect });
+      if (report.imported.length > 0) {
+        await get().loadServers();
+      }
+      return report;
+    } catch (error) {
+      set({ error: String(error) });
+      throw error;
+    }
+  },
+
+  exportConfig: async () => {
+    return invoke<string>('mcp_export_config');
+  },
+
   removeServer: async (id) => {
     try {
       await invoke('mcp_remove_server', { id });
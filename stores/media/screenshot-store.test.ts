@@ -39,6 +39,7 @@ describe('useScreenshotStore', () => {
           copyToClipboard: true,
           showNotification: true,
           ocrLanguage: 'eng',
+          ocrEngine: 'auto',
           autoSave: false,
           filenameTemplate: 'screenshot_{timestamp}',
           openEditorAfterCapture: false,
@@ -140,6 +141,7 @@ describe('useScreenshotStore', () => {
       expect(result.current.config.format).toBe('jpg');
       expect(result.current.config.delayMs).toBe(300);
       expect(result.current.config.ocrLanguage).toBe('chi_sim');
+      expect(result.current.config.ocrEngine).toBe('auto');
       expect(result.current.config.filenameTemplate).toBe('cap_{mode}_{timestamp}');
       expect(result.current.currentOcrLanguage).toBe('chi_sim');
       expect(result.current.config.openEditorAfterCapture).toBe(false);
@@ -514,7 +516,12 @@ describe('useScreenshotStore', () => {
 
   describe('OCR', () => {
     it('should extract text from image', async () => {
-      mockInvoke.mockResolvedValue('Extracted text content');
+      mockInvoke.mockResolvedValue({
+        text: 'Extracted text content',
+        engine: 'platform',
+        provider: 'Windows OCR',
+        confidence: 0.9,
+      });
 
       const { result } = renderHook(() => useScreenshotStore());
 
@@ -550,6 +557,7 @@ describe('useScreenshotStore', () => {
           format: 'jpg',
           delayMs: 250,
           ocrLanguage: 'jpn',
+          ocrEngine: 'tesseract',
           filenameTemplate: 'ss_{mode}_{timestamp}',
           openEditorAfterCapture: true,
         });
@@ -570,6 +578,7 @@ describe('useScreenshotStore', () => {
           format: 'jpg',
           include_cursor: false,
           ocr_language: 'jpn',
+          ocr_engine: 'tesseract',
           quality: 85,
           save_directory: undefined,
           show_notification: true,
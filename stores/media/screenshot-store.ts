@@ -35,6 +35,7 @@ export interface ScreenshotConfig {
   copyToClipboard: boolean;
   showNotification: boolean;
   ocrLanguage: string;
+  ocrEngine: screenshotApi.OcrEngineSelection;
   autoSave: boolean;
   filenameTemplate: string;
   saveDirectory?: string;
@@ -203,6 +204,7 @@ const defaultConfig: ScreenshotConfig = {
   copyToClipboard: true,
   showNotification: true,
   ocrLanguage: 'eng',
+  ocrEngine: 'auto',
   autoSave: false,
   filenameTemplate: 'screenshot_{timestamp}',
   openEditorAfterCapture: false,
@@ -293,6 +295,7 @@ function fromNativeConfig(
     copyToClipboard: nativeConfig.copy_to_clipboard,
     showNotification: nativeConfig.show_notification,
     ocrLanguage: nativeConfig.ocr_language ?? 'eng',
+    ocrEngine: nativeConfig.ocr_engine ?? 'auto',
     autoSave: nativeConfig.auto_save,
     filenameTemplate: nativeConfig.filename_template || defaultConfig.filenameTemplate,
     saveDirectory: nativeConfig.save_directory || undefined,
@@ -310,6 +313,7 @@ function toNativeConfig(config: ScreenshotConfig): screenshotApi.ScreenshotConfi
     copy_to_clipboard: config.copyToClipboard,
     show_notification: config.showNotification,
     ocr_language: config.ocrLanguage,
+    ocr_engine: config.ocrEngine,
     auto_save: config.autoSave,
     filename_template: config.filenameTemplate,
   };
@@ -24,6 +24,7 @@ export type {
   LibraryPaper,
   PaperReadingStatus,
   PaperPriority,
+  ReadingProgressUpdate,
   PaperAnnotation,
   PaperNote,
 } from './library';
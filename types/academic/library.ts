@@ -12,6 +12,15 @@ import type { Paper, PaperAuthor } from './paper';
 export type PaperReadingStatus = 'unread' | 'reading' | 'completed' | 'archived';
 export type PaperPriority = 'low' | 'medium' | 'high' | 'urgent';
 
+/** Reading progress reported by the reader; 'read' is stored as 'completed' */
+export interface ReadingProgressUpdate {
+  status?: PaperReadingStatus | 'read';
+  progressPercent?: number; // 0-100
+  lastPageRead?: number;
+  /** Reading time to add to the paper's total */
+  addReadingTimeSecs?: number;
+}
+
 // ============================================================================
 // Annotation & Note Types
 // ============================================================================
@@ -62,6 +71,8 @@ export interface LibraryPaper extends Paper {
   readingStatus: PaperReadingStatus;
   priority: PaperPriority;
   readingProgress?: number; // 0-100
+  lastPageRead?: number;
+  readingTimeSecs?: number;
 
   // User data
   userRating?: number; // 1-5
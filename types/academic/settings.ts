@@ -98,6 +98,7 @@ export interface AcademicStatistics {
   papersReadThisWeek: number;
   papersReadThisMonth: number;
   averageReadingTime?: number;
+  totalReadingTimeSecs?: number;
 
   topAuthors: { name: string; count: number }[];
   topVenues: { name: string; count: number }[];
@@ -55,16 +55,63 @@ export interface CompletionContext {
   mode?: CompletionMode;
   /** UI surface where completion is requested */
   surface?: CompletionSurface;
+  /** Focused application override; detected natively when omitted (v2 only) */
+  focused_app?: FocusedAppContext;
 }
 
 /** Surface of completion request */
-export type CompletionSurface = 'chat_input' | 'chat_widget' | 'latex_editor' | 'generic';
+export type CompletionSurface =
+  | 'chat_input'
+  | 'chat_widget'
+  | 'latex_editor'
+  | 'terminal'
+  | 'code_editor'
+  | 'document'
+  | 'generic';
+
+/** Kind of the focused application, as reported by context awareness */
+export type FocusedAppKind =
+  | 'terminal'
+  | 'code_editor'
+  | 'document_editor'
+  | 'note_taking'
+  | 'email'
+  | 'chat'
+  | 'browser'
+  | 'other';
+
+/** Focused application and document the completion is requested for */
+export interface FocusedAppContext {
+  /** Application name (e.g. "Visual Studio Code") */
+  app_name?: string;
+  /** Application kind */
+  app_kind?: FocusedAppKind;
+  /** Document type: the file's language when known, otherwise its extension */
+  document_type?: string;
+}
 
 /** High-level completion mode */
 export type CompletionMode = 'chat' | 'code' | 'markdown' | 'plain_text';
 
 /** Type of completion suggestion */
-export type CompletionType = 'Line' | 'Block' | 'Word' | 'Snippet';
+export type CompletionType = 'Line' | 'Block' | 'Word' | 'Snippet' | 'Rewrite';
+
+/** Range of the original text replaced by a rewrite suggestion */
+export interface ReplaceRange {
+  /** Start offset (inclusive) */
+  start: number;
+  /** End offset (exclusive) */
+  end: number;
+}
+
+/** Operation of a single diff segment */
+export type DiffOp = 'equal' | 'insert' | 'delete';
+
+/** A contiguous run of text with the same diff operation */
+export interface DiffSegment {
+  op: DiffOp;
+  text: string;
+}
 
 /** A single completion suggestion */
 export interface CompletionSuggestion {
@@ -78,6 +125,10 @@ export interface CompletionSuggestion {
   completion_type: CompletionType;
   /** Unique identifier for this suggestion */
   id: string;
+  /** Range of the original text this suggestion replaces (rewrite suggestions only) */
+  replace_range?: ReplaceRange;
+  /** Word-level diff from the replaced text to `text` (rewrite suggestions only) */
+  diff?: DiffSegment[];
 }
 
 /** Result of a completion request */
@@ -102,6 +153,10 @@ export interface CompletionStatus {
   has_suggestion: boolean;
   /** Current input buffer length */
   buffer_length: number;
+  /** Whether global capture lacks the OS accessibility permission */
+  degraded: boolean;
+  /** Why global capture is degraded */
+  degraded_reason?: string | null;
 }
 
 /** Supported completion providers */
@@ -243,6 +298,38 @@ export interface TriggerCompletionV3Result extends InputCompletionResult {
   cursor_offset?: number;
 }
 
+/** Result of accepting a suggestion and requesting the next continuation */
+export interface CompletionChainResult extends InputCompletionResult {
+  /** Suggestion that was accepted */
+  accepted: CompletionSuggestion;
+  /** Context text with the accepted suggestion appended */
+  text: string;
+}
+
+/** Rewrite request for the current line or selection */
+export interface CompletionRewriteRequest {
+  /** Optional client request id for stale-response protection */
+  request_id?: string;
+  /** Current line or selection to rewrite */
+  original: string;
+  /** Range of `original` in the full text, echoed back on the suggestion */
+  replace_range: ReplaceRange;
+  /** Text before the range, used as context only */
+  text_before?: string;
+  /** Text after the range, used as context only */
+  text_after?: string;
+  /** Optional rewrite instruction (e.g. "fix grammar") */
+  instruction?: string;
+  /** File path (if editing a file) */
+  file_path?: string;
+  /** Programming language (if known) */
+  language?: string;
+  /** Completion mode hint */
+  mode?: CompletionMode;
+  /** UI surface hint */
+  surface?: CompletionSurface;
+}
+
 /** Events emitted by the input completion system */
 export type InputCompletionEvent =
   | { type: 'Suggestion'; data: CompletionSuggestion }
@@ -237,6 +237,38 @@ export interface PDFConversionResult {
   mindMap?: MindMapData;
   metadata: PDFDocumentMetadata;
   errors?: string[];
+  /** 'cancelled' when the extraction was stopped partway */
+  status?: PDFExtractionStatus;
+}
+
+export type PDFExtractionStatus = 'completed' | 'cancelled';
+
+/** Payload of the `academic://pdf-progress` event */
+export interface PDFExtractionProgress {
+  operationId: string | null;
+  pdfPath: string;
+  page: number;
+  totalPages: number;
+}
+
+export type KnowledgeMapGenerationStatus = 'completed' | 'cancelled';
+
+/** Payload of the `knowledge-map://trace` event, emitted per parsed trace */
+export interface KnowledgeMapTraceEvent {
+  operationId: string;
+  knowledgeMapId: string;
+  index: number;
+  trace: KnowledgeMapTrace;
+}
+
+/** Payload of the `knowledge-map://complete` event and result of a streamed generation */
+export interface KnowledgeMapGenerationResult {
+  operationId: string;
+  knowledgeMapId: string;
+  status: KnowledgeMapGenerationStatus;
+  tracesEmitted: number;
+  /** Null when the generation was cancelled */
+  knowledgeMap: KnowledgeMap | null;
 }
 
 export interface PDFDocumentMetadata {
@@ -322,6 +354,8 @@ export interface KnowledgeMapGenerationRequest {
     linkToReferences: boolean;
     maxTraces?: number;
     focusTopics?: string[];
+    /** Derive ids from content so identical input yields an identical map */
+    deterministicIds?: boolean;
   };
 }
 
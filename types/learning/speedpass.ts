@@ -362,7 +362,7 @@ export interface MatchedKnowledgePoint {
 }
 
 export interface TeacherKeyPointResult {
-  status: 'success' | 'partial' | 'failed';
+  status: 'success' | 'partial' | 'failed' | 'offline_fallback';
   matchedPoints: MatchedKnowledgePoint[];
   unmatchedNotes: string[];
 
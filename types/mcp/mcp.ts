@@ -41,6 +41,21 @@ export interface McpServerConfig {
   autoStart: boolean;
 }
 
+/** Server skipped while importing a standard `mcpServers` config */
+export interface McpImportSkipped {
+  name: string;
+  reason: string;
+}
+
+/** Outcome of importing a standard `mcpServers` config */
+export interface McpImportReport {
+  /** Names of the servers that were added */
+  imported: string[];
+  skipped: McpImportSkipped[];
+  /** Imported servers left without auto-connect because their command was not found */
+  autoConnectDisabled: string[];
+}
+
 /** MCP Tool definition */
 export interface McpTool {
   /** Tool name (unique within server) */
@@ -484,12 +484,19 @@ export {
   type RunCommandAction,
   type LaunchAppAction,
   type SystemTaskAction,
+  type TaskNotifyOn,
+  type RetryPolicy,
   type TaskRunResult,
+  type TaskRunRecord,
   type SystemTask,
   type CreateSystemTaskInput,
+  type TaskBundle,
+  type SkippedImport,
+  type TaskImportResult,
   type TaskConfirmationDetails,
   type TaskConfirmationRequest,
   type SchedulerCapabilities,
+  type SchedulerUsage,
   type ValidationResult,
   type TaskOperationResponse,
   // Constants
@@ -110,6 +110,19 @@ export interface LaunchAppAction {
 /** Union of all action types */
 export type SystemTaskAction = ExecuteScriptAction | RunCommandAction | LaunchAppAction;
 
+/** When to notify the user after a task run */
+export type TaskNotifyOn = 'never' | 'failure' | 'always';
+
+/** How a failed run is retried before waiting for the next trigger */
+export interface RetryPolicy {
+  /** Total attempts per run, including the first (1-10) */
+  max_attempts: number;
+  /** Delay before the first retry in seconds */
+  backoff_secs: number;
+  /** Factor applied to the delay after each retry (1-10, default 1) */
+  backoff_multiplier?: number;
+}
+
 /** Result of a task execution */
 export interface TaskRunResult {
   success: boolean;
@@ -138,6 +151,28 @@ export interface SystemTask {
   next_run_at?: string;
   last_result?: TaskRunResult;
   metadata_state?: SystemTaskMetadataState;
+  /** Created outside Cognia; external tasks are read-only */
+  external?: boolean;
+  /** When to show a desktop notification after a run */
+  notify_on?: TaskNotifyOn;
+  /** Retries after a failed run */
+  retry_policy?: RetryPolicy;
+}
+
+/** Persisted record of one run of a task started through Cognia */
+export interface TaskRunRecord {
+  task_id: SystemTaskId;
+  started_at: string;
+  finished_at: string;
+  success: boolean;
+  exit_code?: number;
+  /** Output, truncated to its last 4000 characters */
+  stdout?: string;
+  stderr?: string;
+  error?: string;
+  duration_ms?: number;
+  /** Attempt number (1-based) when the task has a retry policy */
+  attempt?: number;
 }
 
 /** Input for creating a system task */
@@ -148,6 +183,34 @@ export interface CreateSystemTaskInput {
   action: SystemTaskAction;
   run_level?: RunLevel;
   tags?: string[];
+  /** When to show a desktop notification after a run (default 'never') */
+  notify_on?: TaskNotifyOn;
+  /** Retries after a failed run */
+  retry_policy?: RetryPolicy;
+}
+
+/** Portable set of task definitions; tasks carry no IDs and get new ones on import */
+export interface TaskBundle {
+  version: number;
+  /** Export timestamp (RFC 3339) */
+  exported_at: string;
+  tasks: CreateSystemTaskInput[];
+}
+
+/** Task from a bundle that was not imported */
+export interface SkippedImport {
+  name: string;
+  reason: string;
+}
+
+/** Outcome of importing a task bundle */
+export interface TaskImportResult {
+  /** Tasks created or overwritten */
+  imported: SystemTask[];
+  /** Tasks waiting for the user to confirm them */
+  confirmations: TaskConfirmationRequest[];
+  /** Tasks left out because of a name clash or an error */
+  skipped: SkippedImport[];
 }
 
 /** Details shown in confirmation dialog */
@@ -183,6 +246,15 @@ export interface SchedulerCapabilities {
   max_tasks: number;
 }
 
+/** Current task count against the platform task limit */
+export interface SchedulerUsage {
+  task_count: number;
+  /** Maximum tasks allowed (0 = unlimited) */
+  max_tasks: number;
+  /** Tasks that can still be created (null = unlimited) */
+  remaining: number | null;
+}
+
 /** Validation result */
 export interface ValidationResult {
   valid: boolean;
@@ -215,6 +215,8 @@ export interface BackendSandboxConfig {
   workspace_dir: string | null;
   /** Enabled languages */
   enabled_languages: string[];
+  /** Fraction of the time limit (0-1] at which streaming executions emit a timeout warning */
+  timeout_warning_fraction?: number;
   /** Policy profile selected in UI (optional for backward compatibility) */
   active_policy_profile?: string;
   /** Optional profile map for UI/runtime policy preview */
@@ -322,6 +324,35 @@ export interface OutputLine {
   timestamp_ms: number;
 }
 
+/** Payload of the "sandbox://timeout-warning" event */
+export interface TimeoutWarning {
+  /** Execution ID that is approaching its limit */
+  execution_id: string;
+  /** Seconds elapsed since execution started */
+  elapsed_secs: number;
+  /** Hard limit in seconds, including extensions */
+  timeout_secs: number;
+}
+
+/** Classification of a highlighted code token */
+export type HighlightTokenKind =
+  | 'plain'
+  | 'keyword'
+  | 'literal'
+  | 'string'
+  | 'number'
+  | 'comment'
+  | 'function'
+  | 'type'
+  | 'operator'
+  | 'punctuation';
+
+/** A run of source text returned by sandbox_highlight */
+export interface HighlightToken {
+  text: string;
+  kind: HighlightTokenKind;
+}
+
 /** Code execution result */
 export interface SandboxExecutionResult {
   /** Execution ID */
@@ -408,6 +439,7 @@ export const DEFAULT_SANDBOX_CONFIG: BackendSandboxConfig = {
     'csharp',
     'zig',
   ],
+  timeout_warning_fraction: 0.8,
   active_policy_profile: 'balanced',
   policy_profiles: {
     strict: {